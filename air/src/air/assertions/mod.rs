@@ -21,6 +21,28 @@ mod tests;
 const MIN_STRIDE_LENGTH: usize = 2;
 const NO_STRIDE: usize = 0;
 
+// STEP
+// ================================================================================================
+
+/// A step of an execution trace against which a single assertion is placed.
+///
+/// Steps other than [Step::At] are specified relative to the start or to the end of the trace,
+/// and are resolved against the length of the trace only when boundary constraints are built.
+/// This allows placing assertions against the last rows of a trace without knowing its length
+/// when an AIR is instantiated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    /// The step at the specified index.
+    At(usize),
+    /// The first step of the trace.
+    First,
+    /// The last step of the trace.
+    Last,
+    /// The step which is the specified number of steps before the last step of the trace; i.e.,
+    /// `FromEnd(0)` is the same as [Step::Last].
+    FromEnd(usize),
+}
+
 // ASSERTION
 // ================================================================================================
 
@@ -52,6 +74,7 @@ pub struct Assertion<E: FieldElement> {
     pub(super) first_step: usize,
     pub(super) stride: usize,
     pub(super) values: Vec<E>,
+    pub(super) from_end: bool,
 }
 
 impl<E: FieldElement> Assertion<E> {
@@ -67,6 +90,30 @@ impl<E: FieldElement> Assertion<E> {
             first_step: step,
             stride: NO_STRIDE,
             values: vec![value],
+            from_end: false,
+        }
+    }
+
+    /// Returns an assertion against a single cell of an execution trace at a step which may be
+    /// specified relative to the end of the trace.
+    ///
+    /// The returned assertion requires that the value in the specified `column` at the specified
+    /// `step` is equal to the provided `value`. Steps specified via [Step::Last] and
+    /// [Step::FromEnd] are resolved against the length of the execution trace when boundary
+    /// constraints are built (see [resolve()](Assertion::resolve)).
+    pub fn single_at(column: usize, step: Step, value: E) -> Self {
+        let (first_step, from_end) = match step {
+            Step::At(step) => (step, false),
+            Step::First => (0, false),
+            Step::Last => (0, true),
+            Step::FromEnd(offset) => (offset, true),
+        };
+        Assertion {
+            column,
+            first_step,
+            stride: NO_STRIDE,
+            values: vec![value],
+            from_end,
         }
     }
 
@@ -87,6 +134,7 @@ impl<E: FieldElement> Assertion<E> {
             first_step,
             stride,
             values: vec![value],
+            from_end: false,
         }
     }
 
@@ -118,6 +166,7 @@ impl<E: FieldElement> Assertion<E> {
             first_step,
             stride: if values.len() == 1 { NO_STRIDE } else { stride },
             values,
+            from_end: false,
         }
     }
 
@@ -131,11 +180,23 @@ impl<E: FieldElement> Assertion<E> {
 
     /// Returns the first step of the execution trace against which this assertion is placed.
     ///
-    /// For single value assertions this is equivalent to the assertion step.
+    /// For single value assertions this is equivalent to the assertion step. For assertions
+    /// placed relative to the end of the trace, this is the number of steps between the asserted
+    /// step and the last step of the trace.
     pub fn first_step(&self) -> usize {
         self.first_step
     }
 
+    /// Returns the step against which this assertion is placed if this is a single-value
+    /// assertion, or None otherwise.
+    pub fn step(&self) -> Option<Step> {
+        match (self.is_single(), self.from_end) {
+            (false, _) => None,
+            (true, false) => Some(Step::At(self.first_step)),
+            (true, true) => Some(Step::FromEnd(self.first_step)),
+        }
+    }
+
     /// Returns the interval at which the assertion repeats in the execution trace.
     ///
     /// For single value assertions, this will be 0.
@@ -165,16 +226,28 @@ impl<E: FieldElement> Assertion<E> {
         self.values.len() > 1
     }
 
+    /// Returns true if this assertion is placed against a step specified relative to the end of
+    /// the execution trace.
+    pub fn is_relative_to_end(&self) -> bool {
+        self.from_end
+    }
+
     // PUBLIC METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Checks if this assertion overlaps with the provided assertion.
     ///
-    /// Overlap is defined as asserting a value for the same step in the same column.
+    /// Overlap is defined as asserting a value for the same step in the same column. Overlaps
+    /// between an assertion placed relative to the end of the trace and an assertion placed
+    /// relative to its start can be detected only after both are resolved against the trace
+    /// length.
     pub fn overlaps_with(&self, other: &Assertion<E>) -> bool {
         if self.column != other.column {
             return false;
         }
+        if self.from_end || other.from_end {
+            return self.from_end == other.from_end && self.first_step == other.first_step;
+        }
         if self.first_step == other.first_step {
             return true;
         }
@@ -227,6 +300,8 @@ impl<E: FieldElement> Assertion<E> {
             return Err(AssertionError::TraceLengthNotPowerOfTwo(trace_length));
         }
         if self.is_single() {
+            // for assertions placed relative to the end of the trace, the first step is the
+            // offset from the last step, and thus the same bound applies
             if self.first_step >= trace_length {
                 return Err(AssertionError::TraceLengthTooShort(
                     (self.first_step + 1).next_power_of_two(),
@@ -252,6 +327,25 @@ impl<E: FieldElement> Assertion<E> {
         Ok(())
     }
 
+    /// Returns this assertion with its step resolved against an execution trace of the
+    /// specified length.
+    ///
+    /// Assertions which are not placed relative to the end of the trace are returned unchanged.
+    ///
+    /// # Panics
+    /// Panics if the specified trace length is not valid for this assertion.
+    pub fn resolve(mut self, trace_length: usize) -> Self {
+        self.validate_trace_length(trace_length)
+            .unwrap_or_else(|err| {
+                panic!("invalid trace length: {err}");
+            });
+        if self.from_end {
+            self.first_step = trace_length - 1 - self.first_step;
+            self.from_end = false;
+        }
+        self
+    }
+
     /// Executes the provided closure for all possible instantiations of this assertions against
     /// a execution trace of the specified length.
     ///
@@ -266,7 +360,12 @@ impl<E: FieldElement> Assertion<E> {
                 panic!("invalid trace length: {err}");
             });
         if self.is_single() {
-            f(self.first_step, self.values[0]);
+            let step = if self.from_end {
+                trace_length - 1 - self.first_step
+            } else {
+                self.first_step
+            };
+            f(step, self.values[0]);
         } else if self.is_periodic() {
             for i in 0..(trace_length / self.stride) {
                 f(self.first_step + self.stride * i, self.values[0]);
@@ -306,11 +405,14 @@ impl<E: FieldElement> Assertion<E> {
 // =================================================================================================
 
 /// We define ordering of assertions to be first by stride, then by first_step, and finally by
-/// column in ascending order.
+/// column in ascending order. Assertions placed relative to the end of the trace are ordered
+/// after all other assertions with the same stride.
 impl<E: FieldElement> Ord for Assertion<E> {
     fn cmp(&self, other: &Self) -> Ordering {
         if self.stride == other.stride {
-            if self.first_step == other.first_step {
+            if self.from_end != other.from_end {
+                self.from_end.cmp(&other.from_end)
+            } else if self.first_step == other.first_step {
                 self.column.partial_cmp(&other.column).unwrap()
             } else {
                 self.first_step.partial_cmp(&other.first_step).unwrap()
@@ -331,6 +433,10 @@ impl<E: FieldElement> Display for Assertion<E> {
     fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
         write!(f, "(column={}, ", self.column)?;
         match self.stride {
+            0 if self.from_end => match self.first_step {
+                0 => write!(f, "step=last, ")?,
+                offset => write!(f, "step=last-{offset}, ")?,
+            },
             0 => write!(f, "step={}, ", self.first_step)?,
             _ => {
                 let second_step = self.first_step + self.stride;
@@ -4,7 +4,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Assertion, AssertionError};
+use super::{Assertion, AssertionError, Step};
 use math::{fields::f128::BaseElement, FieldElement};
 use rand_utils::{rand_value, rand_vector};
 use utils::collections::Vec;
@@ -40,6 +40,54 @@ fn single_assertion() {
     );
 }
 
+#[test]
+fn single_assertion_from_end() {
+    let value = rand_value::<BaseElement>();
+    let a = Assertion::single_at(2, Step::FromEnd(3), value);
+    assert_eq!(2, a.column);
+    assert_eq!(3, a.first_step);
+    assert!(a.is_single());
+    assert!(a.is_relative_to_end());
+    assert_eq!(Some(Step::FromEnd(3)), a.step());
+    assert_eq!(1, a.get_num_steps(16));
+
+    // the step is resolved against the length of the trace
+    a.apply(16, |step, val| {
+        assert_eq!(12, step);
+        assert_eq!(value, val);
+    });
+    a.apply(32, |step, _| assert_eq!(28, step));
+    assert_eq!(Assertion::single(2, 12, value), a.clone().resolve(16));
+    assert_eq!(Assertion::single(2, 28, value), a.clone().resolve(32));
+
+    assert_eq!(Ok(()), a.validate_trace_length(4));
+    assert_eq!(
+        Err(AssertionError::TraceLengthTooShort(4, 2)),
+        a.validate_trace_length(2)
+    );
+
+    // first and last steps are shorthands for offsets from the start and from the end
+    let a = Assertion::single_at(1, Step::Last, value);
+    assert_eq!(Some(Step::FromEnd(0)), a.step());
+    assert_eq!(Assertion::single(1, 15, value), a.resolve(16));
+    let a = Assertion::single_at(1, Step::First, value);
+    assert!(!a.is_relative_to_end());
+    assert_eq!(Assertion::single(1, 0, value), a);
+    assert_eq!(
+        Assertion::single(1, 5, value),
+        Assertion::single_at(1, Step::At(5), value)
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "invalid trace length: expected trace length to be at least 16, but was 8"
+)]
+fn single_assertion_from_end_resolve_error() {
+    let a = Assertion::single_at(0, Step::FromEnd(8), BaseElement::ONE);
+    let _ = a.resolve(8);
+}
+
 // PERIODIC ASSERTIONS
 // ================================================================================================
 
@@ -205,6 +253,17 @@ fn sequence_assertion_num_values_not_power_of_two() {
 
 #[test]
 fn assertion_overlap() {
+    // assertions relative to the end of the trace overlap only with each other before they are
+    // resolved
+    let a = Assertion::single_at(3, Step::Last, BaseElement::ONE);
+    let b = Assertion::single_at(3, Step::FromEnd(0), BaseElement::ZERO);
+    assert!(a.overlaps_with(&b));
+    let b = Assertion::single_at(3, Step::FromEnd(1), BaseElement::ZERO);
+    assert!(!a.overlaps_with(&b));
+    let b = Assertion::single(3, 15, BaseElement::ZERO);
+    assert!(!a.overlaps_with(&b));
+    assert!(a.resolve(16).overlaps_with(&b));
+
     // ----- single-single overlap ----------------------------------------------------------------
 
     let a = Assertion::single(3, 2, BaseElement::ONE);
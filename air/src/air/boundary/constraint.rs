@@ -86,6 +86,25 @@ where
         }
     }
 
+    /// Returns this constraint moved onto the coset of the trace domain with the specified
+    /// `offset`.
+    ///
+    /// The value polynomial $b(x)$ of a multi-value assertion is interpolated over a subgroup of
+    /// the trace domain. If the execution trace is defined over a coset of the trace domain with
+    /// offset $h$, the polynomial is replaced with $b(x / h)$ by dividing its $i$th coefficient
+    /// by $h^i$. Single-value assertions are not affected.
+    pub(super) fn with_trace_domain_offset(mut self, offset: F::BaseField) -> Self {
+        if self.poly.len() > 1 && offset != F::BaseField::ONE {
+            let inv_offset = offset.inv();
+            let mut scale = F::BaseField::ONE;
+            for coeff in self.poly.iter_mut() {
+                *coeff = coeff.mul_base(scale);
+                scale *= inv_offset;
+            }
+        }
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -41,7 +41,7 @@ where
     E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
 {
     constraints: Vec<BoundaryConstraint<F, E>>,
-    divisor: ConstraintDivisor<F::BaseField>,
+    divisor_idx: usize,
     degree_adjustment: u64,
 }
 
@@ -52,9 +52,12 @@ where
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new  boundary constraint group to hold constraints with the specified divisor.
+    /// Returns a new  boundary constraint group to hold constraints with the specified divisor;
+    /// `divisor_idx` is the index of the divisor in the list of divisors shared by boundary
+    /// constraints.
     pub(super) fn new(
-        divisor: ConstraintDivisor<F::BaseField>,
+        divisor_idx: usize,
+        divisor: &ConstraintDivisor<F::BaseField>,
         trace_poly_degree: usize,
         composition_degree: usize,
     ) -> Self {
@@ -67,7 +70,7 @@ where
 
         BoundaryConstraintGroup {
             constraints: Vec::new(),
-            divisor,
+            divisor_idx,
             degree_adjustment,
         }
     }
@@ -80,9 +83,10 @@ where
         &self.constraints
     }
 
-    /// Returns a divisor applicable to all boundary constraints in this group.
-    pub fn divisor(&self) -> &ConstraintDivisor<F::BaseField> {
-        &self.divisor
+    /// Returns the index of the divisor applicable to all boundary constraints in this group in
+    /// the list returned by [BoundaryConstraints::divisors()](super::BoundaryConstraints::divisors).
+    pub fn divisor_idx(&self) -> usize {
+        self.divisor_idx
     }
 
     /// Returns a degree adjustment factor for all boundary constraints in this group.
@@ -94,25 +98,30 @@ where
     // --------------------------------------------------------------------------------------------
 
     /// Creates a new boundary constraint from the specified assertion and adds it to the group.
+    ///
+    /// The constraint is moved onto the coset of the trace domain with the specified
+    /// `trace_domain_offset`.
     pub(super) fn add(
         &mut self,
         assertion: Assertion<F>,
         inv_g: F::BaseField,
+        trace_domain_offset: F::BaseField,
         twiddle_map: &mut BTreeMap<usize, Vec<F::BaseField>>,
         composition_coefficients: (E, E),
     ) {
-        self.constraints.push(BoundaryConstraint::new(
-            assertion,
-            inv_g,
-            twiddle_map,
-            composition_coefficients,
-        ));
+        let constraint =
+            BoundaryConstraint::new(assertion, inv_g, twiddle_map, composition_coefficients)
+                .with_trace_domain_offset(trace_domain_offset);
+        self.constraints.push(constraint);
     }
 
     /// Evaluates all constraints in this group at the specified point `x`.
     ///
-    /// `xp` is a degree adjustment multiplier which must be computed as `x^degree_adjustment`.
-    /// This value is provided as an argument to this function for optimization purposes.
+    /// `xp` is a degree adjustment multiplier which must be computed as `x^degree_adjustment`,
+    /// and `z` is the evaluation of the divisor of this group at `x`. These values are provided
+    /// as arguments to this function for optimization purposes: the divisor is shared with other
+    /// groups, and thus, its evaluations can be computed once for all groups via
+    /// [BoundaryConstraints::evaluate_divisors_at()](super::BoundaryConstraints::evaluate_divisors_at).
     ///
     /// Constraint evaluations are merges into a single value by computing their random linear
     /// combination and dividing the result by the divisor of this constraint group as follows:
@@ -129,7 +138,7 @@ where
     ///
     /// Thus, the merged evaluations represent a polynomial of degree $D$, as the degree of the
     /// numerator is $D + deg(z(x))$, and the division by $z(x)$ reduces the degree by $deg(z(x))$.
-    pub fn evaluate_at(&self, state: &[E], x: E, xp: E) -> E {
+    pub fn evaluate_at(&self, state: &[E], x: E, xp: E, z: E) -> E {
         debug_assert_eq!(
             x.exp(self.degree_adjustment.into()),
             xp,
@@ -142,8 +151,6 @@ where
             numerator += evaluation * (constraint.cc().0 + constraint.cc().1 * xp);
         }
 
-        let denominator = self.divisor.evaluate_at(x);
-
-        numerator / denominator
+        numerator / z
     }
 }
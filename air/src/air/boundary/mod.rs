@@ -27,6 +27,10 @@ mod tests;
 /// category, the constraints are grouped by their divisor (see [BoundaryConstraintGroup] for
 /// more info on boundary constraint structure).
 ///
+/// Divisors are shared between the two categories: constraint groups derived from assertions
+/// with the same stride and first step refer to the same divisor, regardless of the trace segment
+/// the assertions are placed against (see [BoundaryConstraints::divisors()]).
+///
 /// When the protocol is run in a large field, types `B` and `E` are the same. However, when
 /// working with small fields, these types are used as follows:
 /// * Constraints against columns of the main segment of the execution trace are defined over the
@@ -38,6 +42,7 @@ mod tests;
 pub struct BoundaryConstraints<E: FieldElement> {
     main_constraints: Vec<BoundaryConstraintGroup<E::BaseField, E>>,
     aux_constraints: Vec<BoundaryConstraintGroup<E, E>>,
+    divisors: Vec<ConstraintDivisor<E::BaseField>>,
 }
 
 impl<E: FieldElement> BoundaryConstraints<E> {
@@ -101,6 +106,11 @@ impl<E: FieldElement> BoundaryConstraints<E> {
         // to re-build them for assertions with identical strides
         let mut twiddle_map = BTreeMap::new();
 
+        // build divisors only once for all assertions with the same stride and first step; the
+        // map holds indexes of already built divisors in the list of shared divisors
+        let mut divisors = Vec::new();
+        let mut divisor_map = BTreeMap::new();
+
         // split composition coefficients into main and auxiliary parts
         let (main_composition_coefficients, aux_composition_coefficients) =
             composition_coefficients.split_at(main_assertions.len());
@@ -112,6 +122,8 @@ impl<E: FieldElement> BoundaryConstraints<E> {
             main_composition_coefficients,
             inv_g,
             &mut twiddle_map,
+            &mut divisor_map,
+            &mut divisors,
         );
 
         // build constraints for the assertions against auxiliary trace segments
@@ -121,11 +133,14 @@ impl<E: FieldElement> BoundaryConstraints<E> {
             aux_composition_coefficients,
             inv_g,
             &mut twiddle_map,
+            &mut divisor_map,
+            &mut divisors,
         );
 
         Self {
             main_constraints,
             aux_constraints,
+            divisors,
         }
     }
 
@@ -143,6 +158,31 @@ impl<E: FieldElement> BoundaryConstraints<E> {
     pub fn aux_constraints(&self) -> &[BoundaryConstraintGroup<E, E>] {
         &self.aux_constraints
     }
+
+    /// Returns divisors shared by boundary constraint groups.
+    ///
+    /// A divisor is determined by the stride and the first step of the assertions in a group, and
+    /// thus, groups of constraints against the main and auxiliary trace segments may have the
+    /// same divisor. Each such divisor is included in the returned list only once, and constraint
+    /// groups refer to their divisors via [BoundaryConstraintGroup::divisor_idx()].
+    pub fn divisors(&self) -> &[ConstraintDivisor<E::BaseField>] {
+        &self.divisors
+    }
+
+    // EVALUATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns evaluations of all shared divisors at the specified point `x`.
+    ///
+    /// The evaluations are returned in the same order as the divisors returned by
+    /// [BoundaryConstraints::divisors()], and are meant to be passed to
+    /// [BoundaryConstraintGroup::evaluate_at()] so that every divisor is evaluated only once.
+    pub fn evaluate_divisors_at(&self, x: E) -> Vec<E> {
+        self.divisors
+            .iter()
+            .map(|divisor| divisor.evaluate_at(x))
+            .collect()
+    }
 }
 
 // HELPER FUNCTIONS
@@ -150,12 +190,18 @@ impl<E: FieldElement> BoundaryConstraints<E> {
 
 /// Translates the provided assertions into boundary constraints, groups the constraints by their
 /// divisor, and sorts the resulting groups by the degree adjustment factor.
+///
+/// Divisors which have not been built yet are appended to the `divisors` list, and their indexes
+/// in this list are recorded in `divisor_map` under the (stride, first step) key of the assertions
+/// they were built for.
 fn group_constraints<F, E>(
     assertions: Vec<Assertion<F>>,
     context: &AirContext<F::BaseField>,
     composition_coefficients: &[(E, E)],
     inv_g: F::BaseField,
     twiddle_map: &mut BTreeMap<usize, Vec<F::BaseField>>,
+    divisor_map: &mut BTreeMap<(usize, usize), usize>,
+    divisors: &mut Vec<ConstraintDivisor<F::BaseField>>,
 ) -> Vec<BoundaryConstraintGroup<F, E>>
 where
     F: FieldElement,
@@ -167,15 +213,29 @@ where
     for (assertion, &cc) in assertions.into_iter().zip(composition_coefficients) {
         let key = (assertion.stride(), assertion.first_step());
         let group = groups.entry(key).or_insert_with(|| {
+            let divisor_idx = *divisor_map.entry(key).or_insert_with(|| {
+                divisors.push(
+                    ConstraintDivisor::from_assertion(&assertion, context.trace_len())
+                        .with_trace_domain_offset(context.options.trace_domain_offset()),
+                );
+                divisors.len() - 1
+            });
             BoundaryConstraintGroup::new(
-                ConstraintDivisor::from_assertion(&assertion, context.trace_len()),
+                divisor_idx,
+                &divisors[divisor_idx],
                 context.trace_poly_degree(),
                 context.composition_degree(),
             )
         });
 
         // add a new assertion constraint to the current group (last group in the list)
-        group.add(assertion, inv_g, twiddle_map, cc);
+        group.add(
+            assertion,
+            inv_g,
+            context.options.trace_domain_offset(),
+            twiddle_map,
+            cc,
+        );
     }
 
     // make sure groups are sorted by adjustment degree
@@ -209,6 +269,10 @@ fn prepare_assertions<E: FieldElement>(
             .unwrap_or_else(|err| {
                 panic!("assertion {assertion} is invalid: {err}");
             });
+
+        // steps specified relative to the end of the trace are resolved against the trace length
+        // here, so that the prover and the verifier place such assertions at the same steps
+        let assertion = assertion.resolve(trace_length);
         for a in result.iter().filter(|a| a.column == assertion.column) {
             assert!(
                 !a.overlaps_with(&assertion),
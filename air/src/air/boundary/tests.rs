@@ -5,9 +5,14 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    super::tests::{build_prng, build_sequence_poly},
-    Assertion, BoundaryConstraint,
+    super::{
+        tests::{build_prng, build_sequence_poly},
+        AirContext, ConstraintDivisor, ProofOptions, Step, TraceInfo, TraceLayout,
+        TransitionConstraintDegree,
+    },
+    Assertion, BoundaryConstraint, BoundaryConstraints,
 };
+use crate::FieldExtension;
 use crypto::{hashers::Blake3_256, RandomCoin};
 use math::{fields::f128::BaseElement, log2, polynom, FieldElement, StarkField};
 use rand_utils::{rand_value, rand_vector, shuffle};
@@ -237,6 +242,44 @@ fn prepare_assertions() {
     assert_eq!(expected, result);
 }
 
+#[test]
+fn prepare_assertions_from_end() {
+    let assertions = vec![
+        Assertion::single_at(1, Step::Last, BaseElement::new(5)),
+        Assertion::single_at(0, Step::FromEnd(2), BaseElement::new(3)),
+        Assertion::single_at(0, Step::First, BaseElement::new(1)),
+    ];
+
+    // the same assertions are placed against different steps of traces of different lengths
+    let expected = vec![
+        Assertion::single(0, 0, BaseElement::new(1)),
+        Assertion::single(0, 13, BaseElement::new(3)),
+        Assertion::single(1, 15, BaseElement::new(5)),
+    ];
+    let result = super::prepare_assertions(assertions.clone(), 2, 16);
+    assert_eq!(expected, result);
+
+    let expected = vec![
+        Assertion::single(0, 0, BaseElement::new(1)),
+        Assertion::single(0, 61, BaseElement::new(3)),
+        Assertion::single(1, 63, BaseElement::new(5)),
+    ];
+    let result = super::prepare_assertions(assertions, 2, 64);
+    assert_eq!(expected, result);
+}
+
+#[test]
+#[should_panic(
+    expected = "assertion (column=0, step=15, value=7) overlaps with assertion (column=0, step=15, value=5)"
+)]
+fn prepare_assertions_from_end_with_overlap() {
+    let assertions = vec![
+        Assertion::single(0, 15, BaseElement::new(5)),
+        Assertion::single_at(0, Step::Last, BaseElement::new(7)),
+    ];
+    let _ = super::prepare_assertions(assertions, 2, 16);
+}
+
 #[test]
 #[should_panic(
     expected = "assertion (column=0, steps=[1, 9, ...], value=7) overlaps with assertion (column=0, step=9, value=5)"
@@ -267,6 +310,182 @@ fn prepare_assertions_with_invalid_trace_width() {
     let _ = super::prepare_assertions(assertions, 2, 16);
 }
 
+// BOUNDARY CONSTRAINTS TESTS
+// ================================================================================================
+
+#[test]
+fn boundary_constraints_share_divisors() {
+    let trace_length = 16;
+    let layout = TraceLayout::new(4, [2, 0], [1, 0]);
+    let trace_info = TraceInfo::new_multi_segment(layout, trace_length, vec![]);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+    let context = AirContext::<BaseElement>::new_multi_segment(
+        trace_info,
+        vec![TransitionConstraintDegree::new(2)],
+        vec![TransitionConstraintDegree::new(2)],
+        4,
+        2,
+        options,
+    );
+
+    // periodic assertions with stride 4 and first step 1 are placed against both trace segments
+    let main_assertions = vec![
+        Assertion::single(0, 0, BaseElement::new(1)),
+        Assertion::periodic(1, 1, 4, BaseElement::new(2)),
+        Assertion::periodic(2, 1, 4, BaseElement::new(3)),
+        Assertion::periodic(3, 2, 4, BaseElement::new(4)),
+    ];
+    let aux_assertions = vec![
+        Assertion::periodic(0, 1, 4, BaseElement::new(5)),
+        Assertion::single(1, 3, BaseElement::new(6)),
+    ];
+    let mut prng = build_prng();
+    let coefficients = (0..6)
+        .map(|_| prng.draw_pair().unwrap())
+        .collect::<Vec<(BaseElement, BaseElement)>>();
+    let constraints = BoundaryConstraints::<BaseElement>::new(
+        &context,
+        main_assertions.clone(),
+        aux_assertions.clone(),
+        &coefficients,
+    );
+
+    // there are 3 groups of main constraints and 2 groups of auxiliary constraints, but only
+    // 4 distinct divisors
+    assert_eq!(3, constraints.main_constraints().len());
+    assert_eq!(2, constraints.aux_constraints().len());
+    assert_eq!(4, constraints.divisors().len());
+
+    // each group refers to the divisor built from its assertions, and groups of assertions with
+    // the same stride and first step refer to the same divisor
+    let offset = context.options.trace_domain_offset();
+    let mut divisor_indexes = BTreeMap::new();
+    let main_groups = constraints.main_constraints().iter().map(|group| {
+        let assertion = main_assertions
+            .iter()
+            .find(|a| a.column() == group.constraints()[0].column())
+            .unwrap();
+        (assertion, group.divisor_idx())
+    });
+    let aux_groups = constraints.aux_constraints().iter().map(|group| {
+        let assertion = aux_assertions
+            .iter()
+            .find(|a| a.column() == group.constraints()[0].column())
+            .unwrap();
+        (assertion, group.divisor_idx())
+    });
+    for (assertion, divisor_idx) in main_groups.chain(aux_groups) {
+        let expected = ConstraintDivisor::from_assertion(assertion, trace_length)
+            .with_trace_domain_offset(offset);
+        assert_eq!(expected, constraints.divisors()[divisor_idx]);
+
+        let key = (assertion.stride(), assertion.first_step());
+        assert_eq!(
+            divisor_idx,
+            *divisor_indexes.entry(key).or_insert(divisor_idx)
+        );
+    }
+    assert_eq!(4, divisor_indexes.len());
+
+    // divisors are evaluated once for all groups
+    let x = rand_value::<BaseElement>();
+    let evaluations = constraints.evaluate_divisors_at(x);
+    for (divisor, &evaluation) in constraints.divisors().iter().zip(evaluations.iter()) {
+        assert_eq!(divisor.evaluate_at(x), evaluation);
+    }
+}
+
+#[test]
+fn boundary_constraints_evaluation_with_shared_divisors() {
+    let trace_length = 64;
+    let (main_width, aux_width, stride) = (32, 2, 16);
+    let layout = TraceLayout::new(main_width, [aux_width, 0], [1, 0]);
+    let trace_info = TraceInfo::new_multi_segment(layout, trace_length, vec![]);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+
+    // build 512 periodic assertions against the main trace segment and 32 periodic assertions
+    // against the auxiliary trace segment; the assertions are built in their natural order, and
+    // thus, the i-th assertion gets the i-th pair of composition coefficients
+    let build_assertions = |width: usize| {
+        let mut result = Vec::new();
+        for first_step in 0..stride {
+            for column in 0..width {
+                result.push(Assertion::periodic(
+                    column,
+                    first_step,
+                    stride,
+                    rand_value(),
+                ));
+            }
+        }
+        result
+    };
+    let main_assertions = build_assertions(main_width);
+    let aux_assertions = build_assertions(aux_width);
+    assert_eq!(512, main_assertions.len());
+
+    let context = AirContext::<BaseElement>::new_multi_segment(
+        trace_info,
+        vec![TransitionConstraintDegree::new(2)],
+        vec![TransitionConstraintDegree::new(2)],
+        main_assertions.len(),
+        aux_assertions.len(),
+        options,
+    );
+    let mut prng = build_prng();
+    let coefficients = (0..main_assertions.len() + aux_assertions.len())
+        .map(|_| prng.draw_pair().unwrap())
+        .collect::<Vec<(BaseElement, BaseElement)>>();
+    let constraints = BoundaryConstraints::<BaseElement>::new(
+        &context,
+        main_assertions.clone(),
+        aux_assertions.clone(),
+        &coefficients,
+    );
+
+    // there is a single divisor for each first step, shared by main and auxiliary groups
+    assert_eq!(stride, constraints.main_constraints().len());
+    assert_eq!(stride, constraints.aux_constraints().len());
+    assert_eq!(stride, constraints.divisors().len());
+
+    // evaluate constraint groups using shared divisor evaluations
+    let x = rand_value::<BaseElement>();
+    let main_state = rand_vector::<BaseElement>(main_width);
+    let aux_state = rand_vector::<BaseElement>(aux_width);
+    let divisors = constraints.evaluate_divisors_at(x);
+    let mut actual = BaseElement::ZERO;
+    for group in constraints.main_constraints() {
+        let xp = x.exp(group.degree_adjustment().into());
+        actual += group.evaluate_at(&main_state, x, xp, divisors[group.divisor_idx()]);
+    }
+    for group in constraints.aux_constraints() {
+        let xp = x.exp(group.degree_adjustment().into());
+        actual += group.evaluate_at(&aux_state, x, xp, divisors[group.divisor_idx()]);
+    }
+
+    // evaluate each assertion individually with its own divisor; the result must be the same
+    let offset = context.options.trace_domain_offset();
+    let mut expected = BaseElement::ZERO;
+    let assertions = main_assertions
+        .iter()
+        .map(|assertion| (assertion, &main_state))
+        .chain(
+            aux_assertions
+                .iter()
+                .map(|assertion| (assertion, &aux_state)),
+        );
+    for ((assertion, state), cc) in assertions.zip(coefficients) {
+        let divisor = ConstraintDivisor::from_assertion(assertion, trace_length)
+            .with_trace_domain_offset(offset);
+        let degree_adjustment =
+            context.composition_degree() + divisor.degree() - context.trace_poly_degree();
+        let xp = x.exp((degree_adjustment as u64).into());
+        let value = state[assertion.column()] - assertion.values()[0];
+        expected += value * (cc.0 + cc.1 * xp) / divisor.evaluate_at(x);
+    }
+    assert_eq!(expected, actual);
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
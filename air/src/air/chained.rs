@@ -0,0 +1,512 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+use core::marker::PhantomData;
+use math::{ExtensibleField, FieldElement, StarkField, ToElements};
+use utils::{collections::Vec, Serializable};
+
+// ROUND FUNCTION
+// ================================================================================================
+/// Describes the constraints of a single round of a chained computation (see [ChainedAir]).
+///
+/// Constraints are evaluated both over the base field (when the prover evaluates them over the
+/// constraint evaluation domain) and over an extension field (when they are evaluated at an
+/// out-of-domain point); thus, the round function is described by a type rather than by a
+/// closure.
+pub trait RoundFunction<B: StarkField>: Send + Sync {
+    /// Returns the degree of the round constraints with respect to the columns of the execution
+    /// trace.
+    fn degree(&self) -> usize;
+
+    /// Evaluates the round constraints over the specified evaluation frame.
+    ///
+    /// The `ark` slice contains the values of the round constants at the current step, and the
+    /// `result` slice contains one entry for every column of the state. The entries are
+    /// initialized to zeros, and must evaluate to zeros only when the next row of the `frame`
+    /// is the result of applying the round to the current row.
+    fn evaluate<E: FieldElement<BaseField = B>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        ark: &[E],
+        result: &mut [E],
+    );
+}
+
+// CHAINED COMPUTATION
+// ================================================================================================
+/// Describes a chained computation for which [ChainedAir] generates the AIR.
+///
+/// Since [Air::new()] instantiates an AIR from trace info, public inputs and proof options
+/// only, the computation is described by a type whose
+/// [build_air()](ChainedComputation::build_air) function configures a [ChainedAirBuilder] for
+/// the specified public inputs.
+pub trait ChainedComputation: Send + Sync {
+    /// Base field of the computation.
+    type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3> + ExtensibleField<6>;
+
+    /// Public inputs of the computation.
+    type PublicInputs: Serializable + ToElements<Self::BaseField>;
+
+    /// Round function of the computation.
+    type RoundFunction: RoundFunction<Self::BaseField>;
+
+    /// Returns a builder describing an instance of the computation with the specified public
+    /// inputs.
+    fn build_air(
+        pub_inputs: Self::PublicInputs,
+    ) -> ChainedAirBuilder<Self::BaseField, Self::RoundFunction>;
+}
+
+// CHAINED AIR BUILDER
+// ================================================================================================
+/// Declarative description of the AIR of a chained computation.
+///
+/// A chained computation (e.g., a hash chain) keeps its state in `state_width` columns of the
+/// main trace segment, and repeats a cycle of `cycle_length` steps over the entire trace:
+/// * In the first `num_rounds` steps of every cycle, the next state is obtained by applying the
+///   round function to the current state, using the round constants of the step.
+/// * In the remaining steps of every cycle, the state is carried over to the next step, except
+///   for reset columns which are set to zeros (e.g., the capacity of a sponge).
+///
+/// Input assertions are placed on the first step of the trace, and output assertions on its last
+/// step.
+///
+/// The generated AIR has the following limits:
+/// * The execution trace consists of a single segment; auxiliary segments are not supported.
+/// * All transition constraints use the default divisor, i.e., they are enforced on all steps
+///   but the last one, and the frame consists of the current and the next rows.
+/// * The round function schedule and the round constants repeat with the cycle length.
+pub struct ChainedAirBuilder<B: StarkField, R> {
+    state_width: usize,
+    round_function: R,
+    cycle_length: usize,
+    num_rounds: Option<usize>,
+    round_constants: Vec<Vec<B>>,
+    reset_columns: Vec<usize>,
+    input_assertions: Vec<(usize, B)>,
+    output_assertions: Vec<(usize, B)>,
+    commit_periodic_columns: bool,
+}
+
+impl<B: StarkField> ChainedAirBuilder<B, ()> {
+    /// Returns a new builder for a chained computation with a state of `state_width` columns.
+    ///
+    /// # Panics
+    /// Panics if `state_width` is zero.
+    pub fn new(state_width: usize) -> Self {
+        assert!(state_width > 0, "state width must be greater than zero");
+        Self {
+            state_width,
+            round_function: (),
+            cycle_length: 0,
+            num_rounds: None,
+            round_constants: Vec::new(),
+            reset_columns: Vec::new(),
+            input_assertions: Vec::new(),
+            output_assertions: Vec::new(),
+            commit_periodic_columns: false,
+        }
+    }
+}
+
+impl<B: StarkField, R> ChainedAirBuilder<B, R> {
+    /// Sets the round function of the computation.
+    pub fn with_round_function<F: RoundFunction<B>>(
+        self,
+        round_function: F,
+    ) -> ChainedAirBuilder<B, F> {
+        ChainedAirBuilder {
+            state_width: self.state_width,
+            round_function,
+            cycle_length: self.cycle_length,
+            num_rounds: self.num_rounds,
+            round_constants: self.round_constants,
+            reset_columns: self.reset_columns,
+            input_assertions: self.input_assertions,
+            output_assertions: self.output_assertions,
+            commit_periodic_columns: self.commit_periodic_columns,
+        }
+    }
+
+    /// Sets the number of steps in a cycle of the computation.
+    ///
+    /// # Panics
+    /// Panics if `cycle_length` is smaller than two or is not a power of two.
+    pub fn with_cycle_length(mut self, cycle_length: usize) -> Self {
+        assert!(
+            cycle_length >= 2 && cycle_length.is_power_of_two(),
+            "cycle length must be a power of two greater than one, but was {cycle_length}"
+        );
+        self.cycle_length = cycle_length;
+        self
+    }
+
+    /// Sets the number of steps at the start of every cycle in which the round function is
+    /// applied; by default, the round function is applied on all steps of a cycle but the last
+    /// one.
+    ///
+    /// # Panics
+    /// Panics if `num_rounds` is zero.
+    pub fn with_num_rounds(mut self, num_rounds: usize) -> Self {
+        assert!(num_rounds > 0, "number of rounds must be greater than zero");
+        self.num_rounds = Some(num_rounds);
+        self
+    }
+
+    /// Sets the round constants of the computation; every column must contain one value for
+    /// every step of a cycle.
+    pub fn with_round_constants(mut self, round_constants: Vec<Vec<B>>) -> Self {
+        self.round_constants = round_constants;
+        self
+    }
+
+    /// Sets the columns of the state which are set to zeros, rather than carried over, on steps
+    /// in which the round function is not applied.
+    pub fn with_reset_columns(mut self, columns: &[usize]) -> Self {
+        self.reset_columns = columns.to_vec();
+        self
+    }
+
+    /// Sets the values which the specified columns must have on the first step of the trace;
+    /// every assertion is a pair of a column index and a value.
+    pub fn with_input_assertions(mut self, assertions: Vec<(usize, B)>) -> Self {
+        self.input_assertions = assertions;
+        self
+    }
+
+    /// Sets the values which the specified columns must have on the last step of the trace;
+    /// every assertion is a pair of a column index and a value.
+    pub fn with_output_assertions(mut self, assertions: Vec<(usize, B)>) -> Self {
+        self.output_assertions = assertions;
+        self
+    }
+
+    /// Makes the prover commit to the periodic columns of the computation (the round flag and
+    /// the round constants) instead of leaving them to be evaluated by the verifier.
+    pub fn with_committed_periodic_columns(mut self) -> Self {
+        self.commit_periodic_columns = true;
+        self
+    }
+}
+
+impl<B: StarkField, R: RoundFunction<B>> ChainedAirBuilder<B, R> {
+    /// Returns the parts of an AIR described by this builder for the execution trace described
+    /// by `trace_info`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The cycle length has not been set, or is greater than the trace length.
+    /// * The number of rounds is greater than the cycle length.
+    /// * Width of the execution trace is different from the state width, or the trace consists
+    ///   of more than one segment.
+    /// * Any column of round constants does not contain exactly one value per step of a cycle.
+    /// * Any reset or assertion column is not smaller than the state width.
+    fn build(self, trace_info: TraceInfo, options: ProofOptions) -> ChainedAirParts<B, R> {
+        assert!(self.cycle_length > 0, "cycle length must be set");
+        assert!(
+            self.cycle_length <= trace_info.length(),
+            "cycle length cannot be greater than trace length of {}, but was {}",
+            trace_info.length(),
+            self.cycle_length
+        );
+        let num_rounds = self.num_rounds.unwrap_or(self.cycle_length - 1);
+        assert!(
+            num_rounds <= self.cycle_length,
+            "number of rounds cannot be greater than cycle length of {}, but was {}",
+            self.cycle_length,
+            num_rounds
+        );
+        assert!(
+            !trace_info.is_multi_segment(),
+            "chained computations cannot have auxiliary trace segments"
+        );
+        assert_eq!(
+            self.state_width,
+            trace_info.width(),
+            "expected execution trace of {} columns, but was {}",
+            self.state_width,
+            trace_info.width()
+        );
+        for (i, column) in self.round_constants.iter().enumerate() {
+            assert_eq!(
+                self.cycle_length,
+                column.len(),
+                "round constant column {} must contain {} values, but contained {}",
+                i,
+                self.cycle_length,
+                column.len()
+            );
+        }
+        let columns = self
+            .reset_columns
+            .iter()
+            .chain(self.input_assertions.iter().map(|(column, _)| column))
+            .chain(self.output_assertions.iter().map(|(column, _)| column));
+        for &column in columns {
+            assert!(
+                column < self.state_width,
+                "column index must be smaller than state width of {}, but was {}",
+                self.state_width,
+                column
+            );
+        }
+
+        // every state column is constrained by a single constraint combining the round
+        // constraint with the copy constraint via the periodic round flag
+        let degree = self.round_function.degree().max(1);
+        let degrees = (0..self.state_width)
+            .map(|_| TransitionConstraintDegree::with_cycles(degree, vec![self.cycle_length]))
+            .collect();
+
+        // input assertions are placed on the first step and output assertions on the last step
+        let last_step = trace_info.length() - 1;
+        let assertions = self
+            .input_assertions
+            .iter()
+            .map(|&(column, value)| Assertion::single(column, 0, value))
+            .chain(
+                self.output_assertions
+                    .iter()
+                    .map(|&(column, value)| Assertion::single(column, last_step, value)),
+            )
+            .collect::<Vec<_>>();
+
+        // the first periodic column holds the round flag, followed by the round constants
+        let mut periodic_columns = Vec::with_capacity(self.round_constants.len() + 1);
+        periodic_columns.push(
+            (0..self.cycle_length)
+                .map(|step| if step < num_rounds { B::ONE } else { B::ZERO })
+                .collect(),
+        );
+        periodic_columns.extend(self.round_constants);
+
+        let mut is_reset = vec![false; self.state_width];
+        for &column in self.reset_columns.iter() {
+            is_reset[column] = true;
+        }
+
+        ChainedAirParts {
+            context: AirContext::new(trace_info, degrees, assertions.len(), options),
+            round_function: self.round_function,
+            is_reset,
+            assertions,
+            periodic_columns,
+            commit_periodic_columns: self.commit_periodic_columns,
+        }
+    }
+}
+
+// CHAINED AIR
+// ================================================================================================
+/// AIR of a chained computation generated from a [ChainedAirBuilder].
+///
+/// The builder is obtained from [ChainedComputation::build_air()] for the public inputs of a
+/// proof; see [ChainedAirBuilder] for the structure of the generated constraints and for their
+/// limits. Every column of the state is constrained by a single transition constraint of the
+/// form `flag * round + (1 - flag) * copy`, where `flag` is a periodic column set to one on
+/// steps in which the round function is applied, `round` is the corresponding round constraint,
+/// and `copy` requires the column to be carried over to (or reset to zero on) the next step.
+pub struct ChainedAir<C: ChainedComputation> {
+    parts: ChainedAirParts<C::BaseField, C::RoundFunction>,
+    _computation: PhantomData<C>,
+}
+
+/// Parts of a [ChainedAir] which do not depend on the type describing the computation.
+struct ChainedAirParts<B: StarkField, R> {
+    context: AirContext<B>,
+    round_function: R,
+    is_reset: Vec<bool>,
+    assertions: Vec<Assertion<B>>,
+    periodic_columns: Vec<Vec<B>>,
+    commit_periodic_columns: bool,
+}
+
+impl<C: ChainedComputation> Air for ChainedAir<C> {
+    type BaseField = C::BaseField;
+    type PublicInputs = C::PublicInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: C::PublicInputs, options: ProofOptions) -> Self {
+        ChainedAir {
+            parts: C::build_air(pub_inputs).build(trace_info, options),
+            _computation: PhantomData,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.parts.context
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        // split periodic values into the round flag and the round constants
+        let round_flag = periodic_values[0];
+        let ark = &periodic_values[1..];
+
+        // the round function may accumulate its constraints, and thus, needs zeroed entries
+        result.fill(E::ZERO);
+        self.parts.round_function.evaluate(frame, ark, result);
+
+        // when the round flag is zero, the state is carried over to the next step, except for
+        // the reset columns which must be zeros
+        let copy_flag = E::ONE - round_flag;
+        for (i, value) in result.iter_mut().enumerate() {
+            let copy = if self.parts.is_reset[i] {
+                next[i]
+            } else {
+                current[i] - next[i]
+            };
+            *value = round_flag * *value + copy_flag * copy;
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        self.parts.assertions.clone()
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        self.parts.periodic_columns.clone()
+    }
+
+    fn has_committed_periodic_columns(&self) -> bool {
+        self.parts.commit_periodic_columns
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Air, Assertion, ChainedAir, ChainedAirBuilder, ChainedComputation, EvaluationFrame,
+        ProofOptions, RoundFunction, TraceInfo,
+    };
+    use crate::FieldExtension;
+    use math::{fields::f128::BaseElement, FieldElement};
+    use utils::collections::Vec;
+
+    #[test]
+    fn chained_air_assertions_and_periodic_columns() {
+        let seed = BaseElement::from(5u8);
+        let air = build_air(seed);
+        let expected = vec![
+            Assertion::single(0, 0, seed),
+            Assertion::single(0, 15, seed),
+        ];
+        assert_eq!(expected, air.get_assertions());
+
+        let flags = vec![
+            BaseElement::ONE,
+            BaseElement::ONE,
+            BaseElement::ONE,
+            BaseElement::ZERO,
+        ];
+        assert_eq!(
+            vec![flags, round_constants()],
+            air.get_periodic_column_values()
+        );
+        assert!(!air.has_committed_periodic_columns());
+    }
+
+    #[test]
+    fn chained_air_evaluate_transition() {
+        let air = build_air(BaseElement::from(5u8));
+        let a = BaseElement::from(3u8);
+        let b = BaseElement::from(4u8);
+        let k = BaseElement::from(7u8);
+        let mut result = vec![BaseElement::ONE; 2];
+
+        // on round steps, the round function must be applied
+        let frame = EvaluationFrame::from_rows(vec![a, b], vec![a.cube() + k, a + b]);
+        air.evaluate_transition(&frame, &[BaseElement::ONE, k], &mut result);
+        assert_eq!(vec![BaseElement::ZERO; 2], result);
+
+        let frame = EvaluationFrame::from_rows(vec![a, b], vec![a, BaseElement::ZERO]);
+        air.evaluate_transition(&frame, &[BaseElement::ONE, k], &mut result);
+        assert_ne!(vec![BaseElement::ZERO; 2], result);
+
+        // on the remaining steps, the state must be carried over and reset columns set to zeros
+        air.evaluate_transition(&frame, &[BaseElement::ZERO, k], &mut result);
+        assert_eq!(vec![BaseElement::ZERO; 2], result);
+
+        let frame = EvaluationFrame::from_rows(vec![a, b], vec![a, b]);
+        air.evaluate_transition(&frame, &[BaseElement::ZERO, k], &mut result);
+        assert_eq!(vec![BaseElement::ZERO, b], result);
+    }
+
+    #[test]
+    #[should_panic(expected = "round constant column 0 must contain 4 values, but contained 3")]
+    fn chained_air_invalid_round_constants() {
+        let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+        ChainedAirBuilder::new(2)
+            .with_round_function(CubeRound)
+            .with_cycle_length(4)
+            .with_round_constants(vec![round_constants()[..3].to_vec()])
+            .build(TraceInfo::new(2, 16), options);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies `x -> x^3 + k` to the first column, and adds the first column to the second one.
+    struct CubeRound;
+
+    impl RoundFunction<BaseElement> for CubeRound {
+        fn degree(&self) -> usize {
+            3
+        }
+
+        fn evaluate<E: FieldElement<BaseField = BaseElement>>(
+            &self,
+            frame: &EvaluationFrame<E>,
+            ark: &[E],
+            result: &mut [E],
+        ) {
+            let current = frame.current();
+            let next = frame.next();
+            result[0] = next[0] - (current[0].cube() + ark[0]);
+            result[1] = next[1] - (current[0] + current[1]);
+        }
+    }
+
+    struct CubeChain;
+
+    impl ChainedComputation for CubeChain {
+        type BaseField = BaseElement;
+        type PublicInputs = BaseElement;
+        type RoundFunction = CubeRound;
+
+        fn build_air(seed: BaseElement) -> ChainedAirBuilder<BaseElement, CubeRound> {
+            ChainedAirBuilder::new(2)
+                .with_round_function(CubeRound)
+                .with_cycle_length(4)
+                .with_round_constants(vec![round_constants()])
+                .with_reset_columns(&[1])
+                .with_input_assertions(vec![(0, seed)])
+                .with_output_assertions(vec![(0, seed)])
+        }
+    }
+
+    fn build_air(seed: BaseElement) -> ChainedAir<CubeChain> {
+        let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+        ChainedAir::new(TraceInfo::new(2, 16), seed, options)
+    }
+
+    fn round_constants() -> Vec<BaseElement> {
+        (1..5u8).map(BaseElement::from).collect()
+    }
+}
@@ -35,6 +35,18 @@ impl<E: FieldElement> AuxTraceRandElements<E> {
     pub fn add_segment_elements(&mut self, rand_elements: Vec<E>) {
         self.0.push(rand_elements);
     }
+
+    /// Appends random elements drawn in a subsequent round of the last auxiliary segment to the
+    /// elements of that segment.
+    ///
+    /// # Panics
+    /// Panics if no elements have been added for any auxiliary segment yet.
+    pub fn add_round_elements(&mut self, rand_elements: Vec<E>) {
+        self.0
+            .last_mut()
+            .expect("no auxiliary segment to add round elements to")
+            .extend(rand_elements);
+    }
 }
 
 impl<E: FieldElement> Default for AuxTraceRandElements<E> {
@@ -83,25 +95,33 @@ pub struct ConstraintCompositionCoefficients<E: FieldElement> {
 /// we compute a random linear combination of trace and constraint composition polynomials as:
 /// $$
 /// Y(x) = \sum_{i=0}^k{(
-///     \alpha_i \cdot \frac{T_i(x) - T_i(z)}{x - z} +
-///     \beta_i \cdot \frac{T_i(x) - T_i(z \cdot g)}{x - z \cdot g} +
+///     \sum_{l=0}^r{\alpha_{i,l} \cdot \frac{T_i(x) - T_i(z \cdot g^{o_l})}{x - z \cdot g^{o_l}}} +
 ///     \gamma_i \cdot \frac{T_i(x) - T_i(\overline{z})}{x - \overline{z}}
 /// )} + \sum_{j=0}^m{\delta \cdot \frac{H_j(x) - H_j(z^m)}{x - z^m}}
 /// $$
+/// If the AIR commits to its periodic columns (see
+/// [Air::has_committed_periodic_columns()](crate::Air::has_committed_periodic_columns)), the sum
+/// additionally includes terms $\epsilon_p \cdot \frac{P_p(x) - P_p(z)}{x - z}$ for every
+/// periodic column polynomial $P_p(x)$.
+///
 /// where:
 /// * $z$ is an out-of-domain point drawn randomly from the entire field. In the interactive
 ///   version of the protocol, $z$ is provided by the verifier. $\overline{z}$ is the conjugate
 ///   of $z$.
 /// * $g$ is the generator of the trace domain. This is the same as $n$th root of unity where
 ///   $n$ is the length of the execution trace.
+/// * $o_l$ is the $l$th offset of the evaluation frame, and $r$ is the number of offsets (see
+///   [AirContext::frame_offsets()](crate::AirContext::frame_offsets)). By default, the offsets
+///   are $0$ and $1$, and thus, the trace polynomials are evaluated at $z$ and $z \cdot g$.
 /// * $T_i(x)$ is an evaluation of the $i$th trace polynomial at $x$, and $k$ is the total
 ///   number of trace polynomials (which is equal to the width of the execution trace).
 /// * $H_i(x)$ is an evaluation of the $j$th constraint composition column polynomial at $x$,
 ///   and $m$ is the total number of column polynomials. The number of column polynomials is equal
 ///   to the highest constraint degree rounded to the next power of two. For example, if the
 ///   highest constraint degree is 6, $m$ will be equal to 8.
-/// * $\alpha_i, \beta_i, \gamma_i$ are composition coefficients for the $i$th trace polynomial.
+/// * $\alpha_{i,l}$ and $\gamma_i$ are composition coefficients for the $i$th trace polynomial.
 /// * $\delta_j$ is a composition coefficient for $j$th constraint column polynomial.
+/// * $\epsilon_p$ is a composition coefficient for $p$th committed periodic column polynomial.
 ///
 /// $T(x)$ and $H(x)$ are polynomials of degree $n - 1$, where $n$ is the length of the execution
 /// trace. Thus, the degree of $Y(x)$ polynomial is $n - 2$. To bring the degree back up to
@@ -112,10 +132,16 @@ pub struct ConstraintCompositionCoefficients<E: FieldElement> {
 /// where $\lambda$ and $\mu$ are the composition coefficients for degree adjustment.
 #[derive(Debug, Clone)]
 pub struct DeepCompositionCoefficients<E: FieldElement> {
-    /// Trace polynomial composition coefficients $\alpha_i$, $\beta_i$, and $\gamma_i$.
-    pub trace: Vec<(E, E, E)>,
+    /// Trace polynomial composition coefficients $\alpha_{i,l}$; the $i$th entry contains one
+    /// coefficient for each frame offset.
+    pub trace: Vec<Vec<E>>,
+    /// Trace polynomial conjugate composition coefficients $\gamma_i$.
+    pub trace_conjugate: Vec<E>,
     /// Constraint column polynomial composition coefficients $\delta_j$.
     pub constraints: Vec<E>,
+    /// Periodic column polynomial composition coefficients $\epsilon_p$; this is empty unless
+    /// the periodic columns of the computation are committed.
+    pub periodic: Vec<E>,
     /// Degree adjustment composition coefficients $\lambda$ and $\mu$.
     pub degree: (E, E),
 }
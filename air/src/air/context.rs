@@ -4,7 +4,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{air::TransitionConstraintDegree, ProofOptions, TraceInfo};
+use crate::{
+    air::{SelectorColumn, TransitionConstraintDegree, TransitionDivisor},
+    ProofOptions, TraceInfo,
+};
 use math::{log2, StarkField};
 use utils::collections::Vec;
 
@@ -23,6 +26,9 @@ pub struct AirContext<B: StarkField> {
     pub(super) trace_domain_generator: B,
     pub(super) lde_domain_generator: B,
     pub(super) num_transition_exemptions: usize,
+    pub(super) transition_divisors: Vec<TransitionDivisor>,
+    pub(super) frame_offsets: Vec<usize>,
+    pub(super) transition_selectors: Vec<Option<SelectorColumn>>,
 }
 
 impl<B: StarkField> AirContext<B> {
@@ -42,6 +48,8 @@ impl<B: StarkField> AirContext<B> {
     /// * `num_assertions` is zero.
     /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
     ///   of the specified transition constraints.
+    /// * Trace domain offset specified by the provided `options` places the trace domain inside
+    ///   the low-degree extension domain.
     /// * `trace_info` describes a multi-segment execution trace.
     pub fn new(
         trace_info: TraceInfo,
@@ -85,6 +93,8 @@ impl<B: StarkField> AirContext<B> {
     ///   - `num_aux_assertions` is greater than zero.
     /// * Blowup factor specified by the provided `options` is too small to accommodate degrees
     ///   of the specified transition constraints.
+    /// * Trace domain offset specified by the provided `options` places the trace domain inside
+    ///   the low-degree extension domain.
     pub fn new_multi_segment(
         trace_info: TraceInfo,
         main_transition_constraint_degrees: Vec<TransitionConstraintDegree>,
@@ -122,30 +132,48 @@ impl<B: StarkField> AirContext<B> {
             );
         }
 
+        let trace_length = trace_info.length();
+        let trace_poly_size = options.trace_poly_size(trace_length);
+
         // determine minimum blowup factor needed to evaluate transition constraints by taking
         // the blowup factor of the highest degree constraint
         let mut ce_blowup_factor = 0;
-        for degree in main_transition_constraint_degrees.iter() {
-            if degree.min_blowup_factor() > ce_blowup_factor {
-                ce_blowup_factor = degree.min_blowup_factor();
-            }
-        }
-
-        for degree in aux_transition_constraint_degrees.iter() {
-            if degree.min_blowup_factor() > ce_blowup_factor {
-                ce_blowup_factor = degree.min_blowup_factor();
-            }
+        for degree in main_transition_constraint_degrees
+            .iter()
+            .chain(aux_transition_constraint_degrees.iter())
+        {
+            let blowup_factor = if options.is_zero_knowledge() {
+                // randomized trace polynomials are larger than the trace, and thus, the blowup
+                // factor is determined by the exact degree of the constraint quotient
+                let eval_degree =
+                    degree.get_randomized_evaluation_degree(trace_length, trace_poly_size - 1);
+                let quotient_degree = eval_degree - (trace_length - 1);
+                get_ce_domain_size(quotient_degree, trace_poly_size) / trace_length
+            } else {
+                degree.min_blowup_factor()
+            };
+            ce_blowup_factor = core::cmp::max(ce_blowup_factor, blowup_factor);
         }
 
+        let min_blowup_factor = trace_length * ce_blowup_factor / trace_poly_size;
         assert!(
-            options.blowup_factor() >= ce_blowup_factor,
+            options.blowup_factor() >= min_blowup_factor,
             "blowup factor too small; expected at least {}, but was {}",
-            ce_blowup_factor,
+            min_blowup_factor,
             options.blowup_factor()
         );
 
-        let trace_length = trace_info.length();
-        let lde_domain_size = trace_length * options.blowup_factor();
+        let lde_domain_size = trace_poly_size * options.blowup_factor();
+
+        // the trace domain is a subgroup of the LDE domain subgroup; thus, the cosets of these
+        // subgroups overlap if and only if the ratio of their offsets is in the LDE domain
+        // subgroup
+        let offset_ratio = options.trace_domain_offset::<B>() / options.domain_offset::<B>();
+        assert!(
+            offset_ratio.exp((lde_domain_size as u64).into()) != B::ONE,
+            "trace domain offset {} places the trace domain inside the LDE domain",
+            options.trace_domain_offset::<B>()
+        );
 
         AirContext {
             options,
@@ -158,6 +186,9 @@ impl<B: StarkField> AirContext<B> {
             trace_domain_generator: B::get_root_of_unity(log2(trace_length)),
             lde_domain_generator: B::get_root_of_unity(log2(lde_domain_size)),
             num_transition_exemptions: 1,
+            transition_divisors: Vec::new(),
+            frame_offsets: vec![0, 1],
+            transition_selectors: Vec::new(),
         }
     }
 
@@ -173,9 +204,11 @@ impl<B: StarkField> AirContext<B> {
 
     /// Returns degree of trace polynomials for an instance of a computation.
     ///
-    /// The degree is always `trace_length` - 1.
+    /// The degree is `trace_length` - 1, unless the proof options specify zero-knowledge, in
+    /// which case trace polynomials are randomized, and the degree is one less than the trace
+    /// polynomial size (see [ProofOptions::trace_poly_size()]).
     pub fn trace_poly_degree(&self) -> usize {
-        self.trace_info.length() - 1
+        self.options.trace_poly_size(self.trace_len()) - 1
     }
 
     /// Returns size of the constraint evaluation domain.
@@ -188,16 +221,45 @@ impl<B: StarkField> AirContext<B> {
     /// Returns the degree to which all constraint polynomials are normalized before they are
     /// composed together.
     ///
-    /// This degree is always `ce_domain_size` - 1.
+    /// This degree is always `num_segments` * `trace_length` - 1, where `num_segments` is the
+    /// number of constraint composition columns excluding the random column committed to in
+    /// zero-knowledge mode, and thus, is smaller than the size of the constraint evaluation
+    /// domain.
     pub fn composition_degree(&self) -> usize {
-        self.ce_domain_size() - 1
+        self.num_composition_poly_segments() * self.trace_len() - 1
+    }
+
+    /// Returns the number of columns into which the constraint composition polynomial is split.
+    ///
+    /// Each column is a polynomial of degree `trace_length` - 1, and the number of columns is the
+    /// smallest one sufficient to accommodate the quotients of all transition constraints by
+    /// their divisors (quotients of boundary constraints always fit into a single column). This
+    /// is never greater than the constraint evaluation blowup factor, but could be smaller. For
+    /// example, when the highest transition constraint degree is 7, the constraint evaluation
+    /// blowup factor is 8, but the composition polynomial is split into 6 columns only.
+    ///
+    /// In zero-knowledge mode, the columns are randomized, and thus, have the degree of trace
+    /// polynomials (see [AirContext::trace_poly_degree()]); the quotients of boundary constraints
+    /// may require more than one column; and an additional random column is committed to
+    /// together with the composition columns (see
+    /// [ProofOptions::with_zero_knowledge()](crate::ProofOptions::with_zero_knowledge)).
+    pub fn num_constraint_composition_columns(&self) -> usize {
+        self.num_composition_poly_segments() + self.options.is_zero_knowledge() as usize
     }
 
     /// Returns the size of the low-degree extension domain.
     ///
-    /// This is guaranteed to be a power of two, and is equal to `trace_length * lde_blowup_factor`.
+    /// This is guaranteed to be a power of two, and is equal to `trace_poly_size * blowup_factor`,
+    /// where `trace_poly_size` is the number of coefficients of trace polynomials (i.e., the
+    /// trace length unless the proof options specify zero-knowledge).
     pub fn lde_domain_size(&self) -> usize {
-        self.trace_info.length() * self.options.blowup_factor()
+        self.options.trace_poly_size(self.trace_len()) * self.options.blowup_factor()
+    }
+
+    /// Returns the degree of the specified transition constraint when it is evaluated over trace
+    /// polynomials of this context.
+    pub(crate) fn get_evaluation_degree(&self, degree: &TransitionConstraintDegree) -> usize {
+        degree.get_randomized_evaluation_degree(self.trace_len(), self.trace_poly_degree())
     }
 
     /// Returns the number of transition constraints for a computation.
@@ -220,6 +282,18 @@ impl<B: StarkField> AirContext<B> {
         self.aux_transition_constraint_degrees.len()
     }
 
+    /// Returns degree descriptors of transition constraints placed against the main trace
+    /// segment.
+    pub fn main_transition_constraint_degrees(&self) -> &[TransitionConstraintDegree] {
+        &self.main_transition_constraint_degrees
+    }
+
+    /// Returns degree descriptors of transition constraints placed against all auxiliary trace
+    /// segments.
+    pub fn aux_transition_constraint_degrees(&self) -> &[TransitionConstraintDegree] {
+        &self.aux_transition_constraint_degrees
+    }
+
     /// Returns the total number of assertions defined for a computation.
     ///
     /// The number of assertions consists of the assertions placed against the main segment of an
@@ -234,15 +308,156 @@ impl<B: StarkField> AirContext<B> {
     /// This is guaranteed to be at least 1 (which is the default value), but could be greater.
     /// The maximum number of exemptions is determined by a combination of transition constraint
     /// degrees and blowup factor specified for the computation.
+    ///
+    /// If the number of exemptions was set individually for each transition constraint (see
+    /// [AirContext::set_transition_exemptions()]), this returns the smallest number of exemptions
+    /// across all transition constraints. If transition divisors were set explicitly (see
+    /// [AirContext::set_transition_divisors()]), this returns the smallest number of exempted
+    /// steps across all transition divisors, which could be zero.
     pub fn num_transition_exemptions(&self) -> usize {
         self.num_transition_exemptions
     }
 
+    /// Returns the number of steps to which the transition constraint at the specified index does
+    /// not apply.
+    ///
+    /// Constraints are indexed in the same way as for [AirContext::set_transition_exemptions()]:
+    /// constraints against the main trace segment come first, followed by constraints against
+    /// auxiliary trace segments.
+    ///
+    /// # Panics
+    /// Panics if `constraint_idx` is greater than or equal to the number of transition
+    /// constraints.
+    pub fn get_num_transition_exemptions(&self, constraint_idx: usize) -> usize {
+        self.get_transition_divisor(constraint_idx).num_exemptions()
+    }
+
+    /// Returns a descriptor of the steps on which the transition constraint at the specified index
+    /// must hold.
+    ///
+    /// Unless transition divisors were set explicitly (see
+    /// [AirContext::set_transition_divisors()]), the constraint must hold on all steps except
+    /// for the trailing exempted steps.
+    ///
+    /// # Panics
+    /// Panics if `constraint_idx` is greater than or equal to the number of transition
+    /// constraints.
+    pub fn get_transition_divisor(&self, constraint_idx: usize) -> TransitionDivisor {
+        assert!(
+            constraint_idx < self.num_transition_constraints(),
+            "constraint index must be smaller than {}, but was {}",
+            self.num_transition_constraints(),
+            constraint_idx
+        );
+        if self.transition_divisors.is_empty() {
+            TransitionDivisor::with_trailing_exemptions(
+                self.trace_len(),
+                self.num_transition_exemptions,
+            )
+        } else {
+            self.transition_divisors[constraint_idx].clone()
+        }
+    }
+
+    /// Returns offsets of the rows in an evaluation frame relative to the current step.
+    ///
+    /// Transition constraints are evaluated over frames in which the $j$th row contains the
+    /// values of the execution trace at step $i + o_j$, where $i$ is the current step and $o_j$
+    /// is the $j$th offset. Unless set explicitly via [AirContext::set_frame_offsets()], the
+    /// offsets are `[0, 1]` (i.e., the frame consists of the current and the next rows).
+    pub fn frame_offsets(&self) -> &[usize] {
+        &self.frame_offsets
+    }
+
+    /// Returns the number of rows in an evaluation frame.
+    pub fn num_frame_rows(&self) -> usize {
+        self.frame_offsets.len()
+    }
+
+    /// Returns the selector wrapping the transition constraint at the specified index, or None
+    /// if the constraint is not wrapped by a selector.
+    ///
+    /// Constraints are indexed in the same way as for [AirContext::set_transition_exemptions()].
+    /// Selectors are set via [AirContext::set_transition_selectors()].
+    ///
+    /// # Panics
+    /// Panics if `constraint_idx` is greater than or equal to the number of transition
+    /// constraints.
+    pub fn get_transition_selector(&self, constraint_idx: usize) -> Option<&SelectorColumn> {
+        assert!(
+            constraint_idx < self.num_transition_constraints(),
+            "constraint index must be smaller than {}, but was {}",
+            self.num_transition_constraints(),
+            constraint_idx
+        );
+        self.transition_selectors
+            .get(constraint_idx)
+            .and_then(|selector| selector.as_ref())
+    }
+
     // DATA MUTATORS
     // --------------------------------------------------------------------------------------------
 
+    /// Sets offsets of the rows in an evaluation frame relative to the current step.
+    ///
+    /// This allows transition constraints to relate more than two rows of the execution trace.
+    /// For example, offsets `[0, 1, 2]` define frames containing three consecutive rows. Rows
+    /// past the end of the trace wrap around to its beginning, and thus, a constraint which
+    /// refers to a row at offset $o$ should usually be exempt from at least the last $o$ steps.
+    ///
+    /// Each offset adds one out-of-domain evaluation per trace column to the proof. In
+    /// zero-knowledge mode, randomized trace polynomials are revealed at every queried position
+    /// and at the out-of-domain point for each offset, and thus, they must have enough random
+    /// coefficients to hide all revealed values.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `offsets` is empty or its first element is not zero.
+    /// * `offsets` is not strictly increasing.
+    /// * Any of the offsets is greater than or equal to the trace length.
+    /// * The proof options specify zero-knowledge, and the number of random coefficients of
+    ///   trace polynomials is smaller than the number of offsets multiplied by the number of
+    ///   revealed values per offset.
+    pub fn set_frame_offsets(mut self, offsets: Vec<usize>) -> Self {
+        assert!(
+            offsets.first() == Some(&0),
+            "frame offsets must start with zero"
+        );
+        assert!(
+            offsets.windows(2).all(|pair| pair[0] < pair[1]),
+            "frame offsets must be strictly increasing"
+        );
+        let last_offset = offsets[offsets.len() - 1];
+        assert!(
+            last_offset < self.trace_len(),
+            "frame offsets must be smaller than {}, but the largest offset was {}",
+            self.trace_len(),
+            last_offset
+        );
+        if self.options.is_zero_knowledge() {
+            let num_queries = core::cmp::max(
+                self.options.num_trace_queries(),
+                self.options.num_constraint_queries(),
+            );
+            let num_revealed_values =
+                offsets.len() * (num_queries + self.options.field_extension().degree() as usize);
+            let num_random_coefficients = self.trace_poly_degree() + 1 - self.trace_len();
+            assert!(
+                num_revealed_values <= num_random_coefficients,
+                "frame offsets reveal {num_revealed_values} values of randomized trace polynomials, \
+                but the polynomials have only {num_random_coefficients} random coefficients"
+            );
+        }
+
+        self.frame_offsets = offsets;
+        self
+    }
+
     /// Sets the number of transition exemptions for this context.
     ///
+    /// The specified number of exemptions applies to all transition constraints, and overrides
+    /// any previously set per-constraint exemptions or transition divisors.
+    ///
     /// # Panics
     /// Panics if:
     /// * The number of exemptions is zero.
@@ -269,8 +484,8 @@ impl<B: StarkField> AirContext<B> {
             .iter()
             .chain(self.aux_transition_constraint_degrees.iter())
         {
-            let eval_degree = degree.get_evaluation_degree(self.trace_len());
-            let max_exemptions = self.composition_degree() + self.trace_len() - eval_degree;
+            let eval_degree = self.get_evaluation_degree(degree);
+            let max_exemptions = self.ce_domain_size() - 1 + self.trace_len() - eval_degree;
             assert!(
                 n <= max_exemptions,
                 "number of transition exemptions cannot exceed: {max_exemptions}, but was {n}"
@@ -278,6 +493,231 @@ impl<B: StarkField> AirContext<B> {
         }
 
         self.num_transition_exemptions = n;
+        self.transition_divisors = Vec::new();
+        self
+    }
+
+    /// Sets the number of transition exemptions individually for each transition constraint.
+    ///
+    /// Entry `i` of `exemptions` specifies the number of steps at the end of the execution trace
+    /// on which transition constraint `i` does not need to hold. Constraints against the main
+    /// trace segment come first (in the order defined by `main_transition_constraint_degrees`),
+    /// followed by constraints against auxiliary trace segments (in the order defined by
+    /// `aux_transition_constraint_degrees`).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The length of `exemptions` is not equal to the number of transition constraints.
+    /// * Any of the exemption counts is zero or is greater than or equal to the trace length.
+    /// * Given the degree of a transition constraint and the blowup factor in this context, the
+    ///   number of exemptions for this constraint is too large for a valid computation of the
+    ///   constraint composition polynomial.
+    pub fn set_transition_exemptions(mut self, exemptions: Vec<usize>) -> Self {
+        assert_eq!(
+            exemptions.len(),
+            self.num_transition_constraints(),
+            "number of transition exemptions must be equal to the number of transition constraints"
+        );
+
+        for (i, (degree, &n)) in self
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+            .zip(exemptions.iter())
+            .enumerate()
+        {
+            assert!(
+                n > 0,
+                "number of transition exemptions for constraint {i} must be greater than zero"
+            );
+            assert!(
+                n < self.trace_len(),
+                "number of transition exemptions for constraint {} must be smaller than {}, but was {}",
+                i,
+                self.trace_len(),
+                n
+            );
+            let eval_degree = self.get_evaluation_degree(degree);
+            let max_exemptions = self.ce_domain_size() - 1 + self.trace_len() - eval_degree;
+            assert!(
+                n <= max_exemptions,
+                "number of transition exemptions for constraint {i} cannot exceed: {max_exemptions}, but was {n}"
+            );
+        }
+
+        self.num_transition_exemptions = exemptions
+            .iter()
+            .copied()
+            .min()
+            .expect("no transition constraints");
+        self.transition_divisors = exemptions
+            .into_iter()
+            .map(|n| TransitionDivisor::with_trailing_exemptions(self.trace_len(), n))
+            .collect();
+        self
+    }
+
+    /// Sets the divisors of transition constraints individually for each transition constraint.
+    ///
+    /// Entry `i` of `divisors` describes the steps of the execution trace on which transition
+    /// constraint `i` must hold. Constraints are indexed in the same way as for
+    /// [AirContext::set_transition_exemptions()].
+    ///
+    /// A divisor which enforces a constraint on fewer steps has a lower degree, and thus, the
+    /// constraint evaluation domain may need to be extended to accommodate the quotient of the
+    /// constraint. This method increases the constraint evaluation blowup factor as needed. For
+    /// example, the quotient of a degree 2 constraint by a divisor enforcing it on an interval of
+    /// $m$ steps (see [TransitionDivisor::with_interval()]) has degree $2 \cdot (n - 1) - m$,
+    /// where $n$ is the trace length, and thus requires a blowup factor of at least 2.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The length of `divisors` is not equal to the number of transition constraints.
+    /// * Any of the divisors is not valid for the trace length in this context (see
+    ///   [ConstraintDivisor::from_transition_coset()](crate::ConstraintDivisor::from_transition_coset)
+    ///   and
+    ///   [ConstraintDivisor::from_transition_interval()](crate::ConstraintDivisor::from_transition_interval)).
+    /// * The degree of a divisor is greater than the evaluation degree of its constraint.
+    /// * The blowup factor specified in this context is too small to accommodate the quotient
+    ///   of a constraint by its divisor.
+    pub fn set_transition_divisors(mut self, divisors: Vec<TransitionDivisor>) -> Self {
+        assert_eq!(
+            divisors.len(),
+            self.num_transition_constraints(),
+            "number of transition divisors must be equal to the number of transition constraints"
+        );
+
+        let trace_length = self.trace_len();
+        let trace_poly_size = self.options.trace_poly_size(trace_length);
+        let mut ce_blowup_factor = self.ce_blowup_factor;
+        for (i, (degree, divisor)) in self
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+            .zip(divisors.iter())
+            .enumerate()
+        {
+            // make sure the divisor is valid for the trace length in this context
+            let _ = divisor.to_constraint_divisor::<B>(trace_length);
+
+            let eval_degree = self.get_evaluation_degree(degree);
+            let div_degree = divisor.degree(trace_length);
+            assert!(
+                div_degree <= eval_degree,
+                "degree of the divisor for transition constraint {i} cannot exceed {eval_degree}, but was {div_degree}"
+            );
+
+            // the constraint evaluation domain must be large enough to contain the quotient of
+            // the constraint by its divisor
+            let quotient_degree = eval_degree - div_degree;
+            let ce_domain_size = get_ce_domain_size(quotient_degree, trace_poly_size);
+            ce_blowup_factor = core::cmp::max(ce_blowup_factor, ce_domain_size / trace_length);
+        }
+
+        let min_blowup_factor = trace_length * ce_blowup_factor / trace_poly_size;
+        assert!(
+            self.options.blowup_factor() >= min_blowup_factor,
+            "blowup factor too small; expected at least {}, but was {}",
+            min_blowup_factor,
+            self.options.blowup_factor()
+        );
+
+        self.ce_blowup_factor = ce_blowup_factor;
+        self.num_transition_exemptions = divisors
+            .iter()
+            .map(|divisor| divisor.num_exemptions())
+            .min()
+            .expect("no transition constraints");
+        self.transition_divisors = divisors;
         self
     }
+
+    /// Sets the selectors wrapping transition constraints of this context.
+    ///
+    /// Entry `i` of `selectors` specifies the selector column by which transition constraint `i`
+    /// is multiplied, or None if the constraint is not wrapped by a selector. Constraints are
+    /// indexed in the same way as for [AirContext::set_transition_exemptions()].
+    ///
+    /// This does not change how constraints are evaluated; instead, it makes sure the degree
+    /// declared for every wrapped constraint accounts for its selector (e.g., by building the
+    /// degree descriptor via [SelectorColumn::constraint_degree()]). Otherwise, the actual
+    /// degree of the constraint would not match its declared degree, which would be detected
+    /// only when (and if) the prover validates constraint degrees in debug mode.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The length of `selectors` is not equal to the number of transition constraints.
+    /// * The cycle length of any of the selectors is greater than the trace length.
+    /// * The degree declared for a wrapped constraint does not include a periodic column with
+    ///   the cycle length of its selector.
+    pub fn set_transition_selectors(mut self, selectors: Vec<Option<SelectorColumn>>) -> Self {
+        assert_eq!(
+            selectors.len(),
+            self.num_transition_constraints(),
+            "number of transition selectors must be equal to the number of transition constraints"
+        );
+
+        for (i, (degree, selector)) in self
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+            .zip(selectors.iter())
+            .enumerate()
+        {
+            if let Some(selector) = selector {
+                assert!(
+                    selector.cycle_length() <= self.trace_len(),
+                    "cycle length of the selector for transition constraint {} cannot exceed trace length {}, but was {}",
+                    i,
+                    self.trace_len(),
+                    selector.cycle_length()
+                );
+                assert!(
+                    degree.cycles().contains(&selector.cycle_length()),
+                    "declared degree of transition constraint {} does not account for its selector with cycle length {}",
+                    i,
+                    selector.cycle_length()
+                );
+            }
+        }
+
+        self.transition_selectors = selectors;
+        self
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns of length `trace_length` into which the constraint
+    /// composition polynomial is split.
+    fn num_composition_poly_segments(&self) -> usize {
+        let trace_length = self.trace_len();
+        let max_quotient_degree = self
+            .main_transition_constraint_degrees
+            .iter()
+            .chain(self.aux_transition_constraint_degrees.iter())
+            .enumerate()
+            .map(|(i, degree)| {
+                let eval_degree = self.get_evaluation_degree(degree);
+                let div_degree = self.get_transition_divisor(i).degree(trace_length);
+                eval_degree.saturating_sub(div_degree)
+            })
+            // quotients of boundary constraints have degree trace_poly_degree - 1
+            .chain(core::iter::once(self.trace_poly_degree() - 1))
+            .max()
+            .unwrap_or(0);
+
+        // the number of columns of length trace_length needed to hold max_quotient_degree + 1
+        // coefficients
+        (max_quotient_degree + trace_length) / trace_length
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the size of the constraint evaluation domain needed to accommodate a constraint
+/// quotient of the specified degree over trace polynomials with `trace_poly_size` coefficients.
+fn get_ce_domain_size(quotient_degree: usize, trace_poly_size: usize) -> usize {
+    core::cmp::max(quotient_degree + 1, trace_poly_size + 1).next_power_of_two()
 }
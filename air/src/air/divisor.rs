@@ -21,12 +21,18 @@ use utils::collections::Vec;
 /// For example divisor $(x^a - 1) \cdot (x^b - 2) / (x - 3)$ can be represented as:
 /// numerator: `[(a, 1), (b, 2)]`, exemptions: `[3]`.
 ///
+/// Divisors of transition constraints which must hold only on a contiguous interval of steps
+/// (see [ConstraintDivisor::from_transition_interval()]) cannot be described by a sparse
+/// polynomial, and instead have an interval portion encoding an expression
+/// $\prod_{j=0}^{m-1} (x - s \cdot g^j)$.
+///
 /// A divisor cannot be instantiated directly, and instead must be created either for an
 /// [Assertion] or for a transition constraint.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ConstraintDivisor<B: StarkField> {
     pub(super) numerator: Vec<(usize, B)>,
     pub(super) exemptions: Vec<B>,
+    pub(super) interval: Option<(usize, B, B)>,
 }
 
 impl<B: StarkField> ConstraintDivisor<B> {
@@ -38,6 +44,7 @@ impl<B: StarkField> ConstraintDivisor<B> {
         ConstraintDivisor {
             numerator,
             exemptions,
+            interval: None,
         }
     }
 
@@ -59,10 +66,124 @@ impl<B: StarkField> ConstraintDivisor<B> {
             num_exemptions > 0,
             "invalid number of transition exemptions: must be greater than zero"
         );
-        let exemptions = (trace_length - num_exemptions..trace_length)
-            .map(|step| get_trace_domain_value_at::<B>(trace_length, step))
+        let exemptions = (trace_length - num_exemptions..trace_length).collect::<Vec<_>>();
+        Self::from_transition_coset(trace_length, 1, 0, &exemptions)
+    }
+
+    /// Builds a divisor for transition constraints which must hold on every `period`-th step of
+    /// the execution trace starting at step `offset`, except for the steps listed in `exemptions`.
+    ///
+    /// The divisor polynomial $z(x)$ is defined as:
+    ///
+    /// $$
+    /// z(x) = \frac{x^{n / p} - g^{o \cdot n / p}}{ \prod_{e \in E} (x - g^e)}
+    /// $$
+    ///
+    /// where, $n$ is the length of the execution trace, $g$ is the generator of the trace
+    /// domain, $p$ is the period, $o$ is the offset, and $E$ is the set of exempted steps. The
+    /// numerator vanishes exactly on steps $o, o + p, o + 2 \cdot p, ...$, and thus, every
+    /// exempted step must be one of these steps.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `period` is not a power of two or is greater than `trace_length`.
+    /// * `offset` is greater than or equal to `period`.
+    /// * Any of the exempted steps is outside of the trace domain, is not on the coset defined by
+    ///   `period` and `offset`, or is listed more than once.
+    /// * All steps of the coset are exempted.
+    pub fn from_transition_coset(
+        trace_length: usize,
+        period: usize,
+        offset: usize,
+        exemptions: &[usize],
+    ) -> Self {
+        assert!(
+            period.is_power_of_two(),
+            "transition divisor period must be a power of two, but was {period}"
+        );
+        assert!(
+            period <= trace_length,
+            "transition divisor period cannot exceed trace length {trace_length}, but was {period}"
+        );
+        assert!(
+            offset < period,
+            "transition divisor offset must be smaller than {period}, but was {offset}"
+        );
+
+        let num_steps = trace_length / period;
+        assert!(
+            exemptions.len() < num_steps,
+            "number of exempted steps must be smaller than {}, but was {}",
+            num_steps,
+            exemptions.len()
+        );
+        for (i, &step) in exemptions.iter().enumerate() {
+            assert!(
+                step < trace_length && step % period == offset,
+                "exempted step {step} is not enforced by a divisor with period {period} and offset {offset}"
+            );
+            assert!(
+                !exemptions[..i].contains(&step),
+                "exempted step {step} is listed more than once"
+            );
+        }
+
+        let numerator_offset = get_trace_domain_value_at::<B>(trace_length, num_steps * offset);
+        let exemptions = exemptions
+            .iter()
+            .map(|&step| get_trace_domain_value_at::<B>(trace_length, step))
             .collect();
-        Self::new(vec![(trace_length, B::ONE)], exemptions)
+        Self::new(vec![(num_steps, numerator_offset)], exemptions)
+    }
+
+    /// Builds a divisor for transition constraints which must hold only on the steps in the
+    /// interval [`start`, `end`) of an execution trace of the specified length.
+    ///
+    /// The divisor polynomial vanishes exactly on the steps of the interval:
+    ///
+    /// $$
+    /// z(x) = \prod_{i=a}^{b-1} (x - g^i) = \prod_{j=0}^{m-1} (x - g^a \cdot g^j)
+    /// $$
+    ///
+    /// where $a$ and $b$ are the bounds of the interval, $m = b - a$ is the length of the
+    /// interval, and $g$ is the generator of the trace domain. Unlike other divisors, $z(x)$ is
+    /// not sparse: evaluating it at a single point requires $m$ multiplications, and evaluating
+    /// it over a domain requires computing its coefficients first.
+    ///
+    /// The degree of the divisor is $m$; thus, the degree of the quotient of a constraint by this
+    /// divisor is greater than for a divisor enforcing the constraint on all steps of the trace,
+    /// and may require a larger constraint evaluation domain.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `end - start` is not a power of two, or `start` is not a multiple of `end - start`.
+    /// * `end - start` is not smaller than `trace_length`, or `end` is greater than
+    ///   `trace_length`.
+    pub fn from_transition_interval(trace_length: usize, start: usize, end: usize) -> Self {
+        assert!(
+            start < end,
+            "transition divisor interval [{start}, {end}) must not be empty"
+        );
+        let length = end - start;
+        assert!(
+            length.is_power_of_two(),
+            "transition divisor interval length must be a power of two, but was {length}"
+        );
+        assert!(
+            start % length == 0,
+            "transition divisor interval start must be a multiple of {length}, but was {start}"
+        );
+        assert!(
+            length < trace_length && end <= trace_length,
+            "transition divisor interval [{start}, {end}) is not a proper subset of the trace domain [0, {trace_length})"
+        );
+
+        let g = B::get_root_of_unity(log2(trace_length));
+        ConstraintDivisor {
+            numerator: vec![],
+            exemptions: vec![],
+            interval: Some((length, g.exp((start as u64).into()), g)),
+        }
     }
 
     /// Builds a divisor for a boundary constraint described by the assertion.
@@ -102,6 +223,37 @@ impl<B: StarkField> ConstraintDivisor<B> {
         }
     }
 
+    /// Returns this divisor moved onto the coset of the trace domain with the specified
+    /// `offset`.
+    ///
+    /// Divisors built by the constructors above vanish on the steps of an execution trace defined
+    /// over the multiplicative subgroup of the trace domain. If the trace is defined over a coset
+    /// of this subgroup with offset $h$, the divisor $z(x)$ must be replaced with $z(x / h)$.
+    /// For example, $x^n - 1$ becomes $(x / h)^n - 1$. To keep the sparse representation of the
+    /// divisor, every numerator term $(x^a - b)$ is multiplied by $h^a$, and every exemption
+    /// point $e$ is multiplied by $h$; the result differs from $z(x / h)$ only by a constant
+    /// factor, and thus, vanishes on exactly the same points. Similarly, the first point $s$ of
+    /// the interval portion of the divisor (if any) is multiplied by $h$.
+    pub fn with_trace_domain_offset(self, offset: B) -> Self {
+        if offset == B::ONE {
+            return self;
+        }
+        let numerator = self
+            .numerator
+            .into_iter()
+            .map(|(degree, constant)| (degree, constant * offset.exp((degree as u64).into())))
+            .collect();
+        let exemptions = self.exemptions.into_iter().map(|e| e * offset).collect();
+        let interval = self
+            .interval
+            .map(|(length, shift, root)| (length, shift * offset, root));
+        ConstraintDivisor {
+            numerator,
+            exemptions,
+            interval,
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -115,12 +267,22 @@ impl<B: StarkField> ConstraintDivisor<B> {
         &self.exemptions
     }
 
+    /// Returns the interval portion of this constraint divisor, if any.
+    ///
+    /// The interval is described by a tuple $(m, s, g)$ which encodes the expression
+    /// $\prod_{j=0}^{m-1} (x - s \cdot g^j)$, where $m$ is a power of two and $g$ is the generator
+    /// of the trace domain; this expression is a part of the divisor's numerator.
+    pub fn interval(&self) -> Option<(usize, B, B)> {
+        self.interval
+    }
+
     /// Returns the degree of the divisor polynomial
     pub fn degree(&self) -> usize {
         let numerator_degree = self
             .numerator
             .iter()
-            .fold(0, |degree, term| degree + term.0);
+            .fold(0, |degree, term| degree + term.0)
+            + self.interval.map_or(0, |(length, ..)| length);
         let denominator_degree = self.exemptions.len();
         numerator_degree - denominator_degree
     }
@@ -130,12 +292,7 @@ impl<B: StarkField> ConstraintDivisor<B> {
     /// Evaluates the divisor polynomial at the provided `x` coordinate.
     pub fn evaluate_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
         // compute the numerator value
-        let mut numerator = E::ONE;
-        for (degree, constant) in self.numerator.iter() {
-            let v = x.exp((*degree as u32).into());
-            let v = v - E::from(*constant);
-            numerator *= v;
-        }
+        let numerator = self.evaluate_numerator_at(x);
 
         // compute the denominator value
         let denominator = self.evaluate_exemptions_at(x);
@@ -143,6 +300,34 @@ impl<B: StarkField> ConstraintDivisor<B> {
         numerator / denominator
     }
 
+    /// Evaluates the numerator of this divisor at the provided `x` coordinate.
+    ///
+    /// Evaluating the numerator and the exemption points separately can be used to evaluate the
+    /// divisor (or its inverse) over many points while performing a single batch inversion.
+    ///
+    /// If this divisor has an interval portion of length $m$, evaluating it requires $m$
+    /// additional multiplications.
+    #[inline(always)]
+    pub fn evaluate_numerator_at<E: FieldElement<BaseField = B>>(&self, x: E) -> E {
+        let result = self
+            .numerator
+            .iter()
+            .fold(E::ONE, |r, &(degree, constant)| {
+                r * (x.exp((degree as u32).into()) - E::from(constant))
+            });
+        match self.interval {
+            Some((length, shift, root)) => {
+                let mut point = shift;
+                (0..length).fold(result, |r, _| {
+                    let term = x - E::from(point);
+                    point *= root;
+                    r * term
+                })
+            }
+            None => result,
+        }
+    }
+
     /// Evaluates the denominator of this divisor (the exemption points) at the provided `x`
     /// coordinate.
     #[inline(always)]
@@ -158,6 +343,9 @@ impl<B: StarkField> Display for ConstraintDivisor<B> {
         for (degree, offset) in self.numerator.iter() {
             write!(f, "(x^{degree} - {offset})")?;
         }
+        if let Some((length, shift, root)) = self.interval {
+            write!(f, "prod_{{j < {length}}}(x - {shift} * {root}^j)")?;
+        }
         if !self.exemptions.is_empty() {
             write!(f, " / ")?;
             for x in self.exemptions.iter() {
@@ -251,6 +439,37 @@ mod tests {
         assert_eq!(expected, div.evaluate_at(BaseElement::new(4)));
     }
 
+    #[test]
+    fn constraint_divisor_with_trace_domain_offset() {
+        let n = 8_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+        let h = BaseElement::new(7);
+
+        // the transition divisor must vanish on h * g^i for all steps but the last one
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(n, 1);
+        let shifted = divisor.clone().with_trace_domain_offset(h);
+        assert_eq!(divisor.degree(), shifted.degree());
+        for i in 0..n - 1 {
+            let x = h * g.exp((i as u32).into());
+            assert_eq!(BaseElement::ZERO, shifted.evaluate_numerator_at(x));
+            assert_ne!(BaseElement::ZERO, shifted.evaluate_exemptions_at(x));
+        }
+        let x = h * g.exp(((n - 1) as u32).into());
+        assert_eq!(BaseElement::ZERO, shifted.evaluate_exemptions_at(x));
+
+        // the shifted divisor is a constant multiple of z(x / h)
+        let x = BaseElement::new(11);
+        let ratio = shifted.evaluate_at(x) / divisor.evaluate_at(x / h);
+        let y = BaseElement::new(13);
+        assert_eq!(ratio, shifted.evaluate_at(y) / divisor.evaluate_at(y / h));
+
+        // moving a divisor onto the subgroup itself does not change it
+        assert_eq!(
+            divisor,
+            divisor.clone().with_trace_domain_offset(BaseElement::ONE)
+        );
+    }
+
     #[test]
     fn constraint_divisor_equivalence() {
         let n = 8_usize;
@@ -343,4 +562,101 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn transition_divisor_coset() {
+        let n = 16_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // trailing exemptions are a special case of a coset divisor with period 1
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition(n, 2);
+        assert_eq!(
+            ConstraintDivisor::from_transition_coset(n, 1, 0, &[14, 15]),
+            divisor
+        );
+        assert_eq!(n - 2, divisor.degree());
+
+        // divisor for steps 3, 7, 11, 15 with step 11 exempted:
+        // z(x) = (x^4 - g^12) / (x - g^11) = (x - g^3) * (x - g^7) * (x - g^15)
+        let divisor = ConstraintDivisor::from_transition_coset(n, 4, 3, &[11]);
+        assert_eq!(vec![(4, g.exp(12))], divisor.numerator());
+        assert_eq!(vec![g.exp(11)], divisor.exemptions());
+        assert_eq!(3, divisor.degree());
+
+        let poly = polynom::mul(
+            &polynom::mul(
+                &[-g.exp(3), BaseElement::ONE],
+                &[-g.exp(7), BaseElement::ONE],
+            ),
+            &[-g.exp(15), BaseElement::ONE],
+        );
+
+        // the divisor must agree with the polynomial outside of the exempted point (where the
+        // rational function is undefined), and must vanish only on the enforced steps
+        for i in (0..n).filter(|&i| i != 11) {
+            let x = g.exp((i as u32).into());
+            let actual = divisor.evaluate_at(x);
+            assert_eq!(polynom::eval(&poly, x), actual);
+            assert_eq!(i % 4 == 3, actual == BaseElement::ZERO);
+        }
+        let x = BaseElement::new(42);
+        assert_eq!(polynom::eval(&poly, x), divisor.evaluate_at(x));
+    }
+
+    #[test]
+    fn transition_divisor_interval() {
+        let n = 32_usize;
+        let g = BaseElement::get_root_of_unity(n.trailing_zeros());
+
+        // divisor for steps 8, 9, ..., 15
+        let divisor = ConstraintDivisor::<BaseElement>::from_transition_interval(n, 8, 16);
+        assert!(divisor.numerator().is_empty());
+        assert!(divisor.exemptions().is_empty());
+        assert_eq!(Some((8, g.exp(8), g)), divisor.interval());
+        assert_eq!(8, divisor.degree());
+
+        // the divisor must agree with the polynomial built from its roots, and must vanish only
+        // on the steps of the interval
+        let poly = (8..16).fold(vec![BaseElement::ONE], |poly, i| {
+            polynom::mul(&poly, &[-g.exp(i), BaseElement::ONE])
+        });
+        for i in 0..n {
+            let x = g.exp((i as u32).into());
+            let actual = divisor.evaluate_at(x);
+            assert_eq!(polynom::eval(&poly, x), actual);
+            assert_eq!((8..16).contains(&i), actual == BaseElement::ZERO);
+        }
+        let x = BaseElement::new(42);
+        assert_eq!(polynom::eval(&poly, x), divisor.evaluate_at(x));
+
+        // moving the divisor onto a coset of the trace domain moves its roots
+        let h = BaseElement::new(7);
+        let shifted = divisor.with_trace_domain_offset(h);
+        assert_eq!(Some((8, h * g.exp(8), g)), shifted.interval());
+        for i in 0..n {
+            let x = h * g.exp((i as u32).into());
+            assert_eq!(
+                (8..16).contains(&i),
+                shifted.evaluate_at(x) == BaseElement::ZERO
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "interval start must be a multiple of 8, but was 4")]
+    fn transition_divisor_interval_unaligned() {
+        let _ = ConstraintDivisor::<BaseElement>::from_transition_interval(32, 4, 12);
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a proper subset of the trace domain")]
+    fn transition_divisor_interval_full_trace() {
+        let _ = ConstraintDivisor::<BaseElement>::from_transition_interval(32, 0, 32);
+    }
+
+    #[test]
+    #[should_panic(expected = "exempted step 5 is not enforced by a divisor")]
+    fn transition_divisor_coset_invalid_exemption() {
+        let _ = ConstraintDivisor::<BaseElement>::from_transition_coset(16, 4, 3, &[5]);
+    }
 }
@@ -4,9 +4,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::ProofOptions;
-use crypto::{Hasher, RandomCoin, RandomCoinError};
-use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField};
+use crate::{labels, ProofOptions};
+use crypto::{ElementHasher, Hasher, MerkleTree, RandomCoinError, Transcript};
+use math::{fft, ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
 use utils::{
     collections::{BTreeMap, Vec},
     Serializable,
@@ -19,14 +19,15 @@ mod context;
 pub use context::AirContext;
 
 mod assertions;
-pub use assertions::Assertion;
+pub use assertions::{Assertion, Step};
 
 mod boundary;
 pub use boundary::{BoundaryConstraint, BoundaryConstraintGroup, BoundaryConstraints};
 
 mod transition;
 pub use transition::{
-    EvaluationFrame, TransitionConstraintDegree, TransitionConstraintGroup, TransitionConstraints,
+    EvaluationFrame, SelectorColumn, TransitionConstraintDegree, TransitionConstraintGroup,
+    TransitionConstraints, TransitionDivisor, TransitionEvaluator,
 };
 
 mod coefficients;
@@ -37,6 +38,12 @@ pub use coefficients::{
 mod divisor;
 pub use divisor::ConstraintDivisor;
 
+mod periodic;
+pub use periodic::PeriodicColumnPolys;
+
+mod chained;
+pub use chained::{ChainedAir, ChainedAirBuilder, ChainedComputation, RoundFunction};
+
 #[cfg(test)]
 mod tests;
 
@@ -132,6 +139,9 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// * A single assertion - such assertion specifies that a single cell of an execution trace must
 ///   be equal to a specific value. For example: *value in column 0, at step 0, must be equal
 ///   to 1*.
+///   The step of a single assertion can also be specified relative to the end of the trace
+///   (see [Step]), in which case it is resolved against the trace length when boundary
+///   constraints are built.
 /// * A periodic assertion - such assertion specifies that values in a given column at specified
 ///   intervals should be equal to some value. For example: *values in column 0, at steps 0, 8,
 ///   16, 24 etc. must be equal to 2*.
@@ -151,6 +161,10 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// step of the computation will be supplied to the [Air::evaluate_transition()] method via the
 /// `periodic_values` parameter.
 ///
+/// Periodic columns with binary values are frequently used to switch constraints on and off at
+/// specific steps. Such columns can be described by a [SelectorColumn], which computes degrees of
+/// the constraints it wraps and applies itself to their evaluations.
+///
 /// ### Randomized AIR
 /// Randomized AIR is a powerful extension of AIR which enables, among other things, multiset and
 /// permutation checks similar to the ones available in PLONKish systems. These, in turn, allow
@@ -163,7 +177,16 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// building auxiliary trace segments, the prover has access to extra randomness sent by the
 /// verifier (in the non-interactive version of the protocol, this randomness is derived from the
 /// previous trace segment commitments). Currently, the number of auxiliary trace segments is
-/// limited to one.
+/// limited to [TraceLayout::MAX_AUX_SEGMENTS]. Auxiliary segments are built one at a time: the
+/// random elements for a segment are drawn after the prover commits to all preceding segments,
+/// and the segment may depend on the values of these segments. A segment may also be built in
+/// several rounds, each drawing its own random elements after the prover commits to the columns
+/// built in prior rounds (see [TraceLayout::with_aux_segment_rounds()]).
+///
+/// The auxiliary evaluation frame and the auxiliary assertions cover the columns of all
+/// auxiliary segments, with the columns of each segment following the columns of the preceding
+/// segment; random elements for each segment can be accessed via
+/// [AuxTraceRandElements::get_segment_elements()].
 ///
 /// To describe Randomized AIR, you will need to do the following when implementing the [Air]
 /// trait:
@@ -182,11 +205,17 @@ pub trait Air: Send + Sync {
     /// Base field for the computation described by this AIR. STARK protocol for this computation
     /// may be executed in the base field, or in an extension of the base fields as specified
     /// by [ProofOptions] struct.
-    type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3>;
+    type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3> + ExtensibleField<6>;
 
     /// A type defining shape of public inputs for the computation described by this protocol.
-    /// This could be any type as long as it can be serialized into a sequence of bytes.
-    type PublicInputs: Serializable;
+    /// This could be any type as long as it can be serialized into a sequence of bytes, and can
+    /// be converted into a canonical sequence of elements in the base field.
+    ///
+    /// The public coin of the protocol is seeded with the element encoding of public inputs (see
+    /// [ToElements]) rather than with their serialized bytes. Thus, public inputs which are
+    /// equal as sequences of elements result in the same proof transcript, even if they are
+    /// serialized differently.
+    type PublicInputs: Serializable + ToElements<Self::BaseField>;
 
     // REQUIRED METHODS
     // --------------------------------------------------------------------------------------------
@@ -214,6 +243,11 @@ pub trait Air: Send + Sync {
     /// We define type `E` separately from `Self::BaseField` to allow evaluation of constraints
     /// over the out-of-domain evaluation frame, which may be defined over an extension field
     /// (when extension fields are used).
+    ///
+    /// In debug builds, the prover and the verifier invoke this method via
+    /// [TransitionEvaluator], which panics with a descriptive message if the number of periodic
+    /// values or the length of the `result` slice is inconsistent with this AIR, or if any of the
+    /// transition constraints is not written into the `result` slice.
     fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
@@ -235,6 +269,11 @@ pub trait Air: Send + Sync {
     /// this AIR. Thus, the length of the `result` slice will equal to the number of auxiliary
     /// transition constraints defined for this computation.
     ///
+    /// The `periodic_values` slice contains values of the periodic columns returned from the
+    /// [get_periodic_column_values()](Air::get_periodic_column_values) method followed by values
+    /// of the periodic columns returned from the
+    /// [get_aux_periodic_column_values()](Air::get_aux_periodic_column_values) method.
+    ///
     /// The default implementation of this function panics. It must be overridden for AIRs
     /// describing computations which require multiple trace segments.
     ///
@@ -288,6 +327,22 @@ pub trait Air: Send + Sync {
         Vec::new()
     }
 
+    /// Returns values for all periodic columns used only by auxiliary transition constraints.
+    ///
+    /// Values of these columns are computed at specific states of the computation and passed in
+    /// to the [evaluate_aux_transition()](Air::evaluate_aux_transition) method as part of the
+    /// `periodic_values` parameter, right after the values of the periodic columns returned from
+    /// the [get_periodic_column_values()](Air::get_periodic_column_values) method. These values
+    /// are not passed in to the [evaluate_transition()](Air::evaluate_transition) method.
+    ///
+    /// The default implementation of this method returns an empty vector. It should be
+    /// overridden only if auxiliary transition constraints rely on periodic columns which are not
+    /// needed by the main transition constraints. Number of values for each periodic column must
+    /// be a power of two.
+    fn get_aux_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        Vec::new()
+    }
+
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -309,38 +364,105 @@ pub trait Air: Send + Sync {
     /// These polynomials are interpolated from the values returned from the
     /// [get_periodic_column_values()](Air::get_periodic_column_values) method.
     fn get_periodic_column_polys(&self) -> Vec<Vec<Self::BaseField>> {
-        // cache inverse twiddles for each cycle length so that we don't have to re-build them
-        // for columns with identical cycle lengths
-        let mut twiddle_map = BTreeMap::new();
-        // iterate over all periodic columns and convert column values into polynomials
-        self.get_periodic_column_values()
+        interpolate_periodic_columns(
+            self.get_periodic_column_values(),
+            self.trace_length(),
+            self.trace_domain_offset(),
+        )
+    }
+
+    /// Returns polynomials for all periodic columns used by auxiliary transition constraints.
+    ///
+    /// These polynomials are interpolated from the values returned from the
+    /// [get_aux_periodic_column_values()](Air::get_aux_periodic_column_values) method.
+    fn get_aux_periodic_column_polys(&self) -> Vec<Vec<Self::BaseField>> {
+        interpolate_periodic_columns(
+            self.get_aux_periodic_column_values(),
+            self.trace_length(),
+            self.trace_domain_offset(),
+        )
+    }
+
+    /// Returns true if the prover commits to evaluations of the periodic columns over the LDE
+    /// domain.
+    ///
+    /// When periodic columns are committed, the verifier does not evaluate periodic column
+    /// polynomials at the out-of-domain point. Instead, the prover sends these evaluations
+    /// together with the out-of-domain frame, includes periodic column polynomials in the DEEP
+    /// composition polynomial, and opens the committed evaluations at every queried position.
+    /// This reduces the work of the verifier for computations with many (or long) periodic
+    /// columns at the expense of a larger proof.
+    ///
+    /// Only periodic columns returned from the
+    /// [get_periodic_column_values()](Air::get_periodic_column_values) method are committed;
+    /// periodic columns used only by auxiliary transition constraints are always evaluated by
+    /// the verifier.
+    ///
+    /// The default implementation of this method returns false.
+    fn has_committed_periodic_columns(&self) -> bool {
+        false
+    }
+
+    /// Returns evaluations of all periodic column polynomials over the LDE domain.
+    ///
+    /// The $i$th evaluation of a column is the value of its polynomial at $x^k$, where $x$ is the
+    /// $i$th element of the LDE domain and $k$ is the number of cycles of the column in the
+    /// execution trace. These are the values committed to by the prover when
+    /// [has_committed_periodic_columns()](Air::has_committed_periodic_columns) returns true.
+    fn get_periodic_column_evaluations(&self) -> Vec<Vec<Self::BaseField>> {
+        let lde_domain_size = self.lde_domain_size();
+        self.get_periodic_column_polys()
             .into_iter()
-            .map(|mut column| {
-                let cycle_length = column.len();
-                assert!(
-                    cycle_length >= MIN_CYCLE_LENGTH,
-                    "number of values in a periodic column must be at least {MIN_CYCLE_LENGTH}, but was {cycle_length}"
+            .map(|poly| {
+                // as x ranges over the LDE domain, x^num_cycles ranges over a domain which is
+                // num_cycles times smaller; thus, we evaluate the polynomial over that domain
+                // and repeat the evaluations to fill the entire LDE domain
+                let num_cycles = (self.trace_length() / poly.len()) as u64;
+                let offset = self.domain_offset().exp(num_cycles.into());
+                let twiddles = fft::get_twiddles::<Self::BaseField>(poly.len());
+                let evaluations = fft::evaluate_poly_with_offset(
+                    &poly,
+                    &twiddles,
+                    offset,
+                    self.lde_blowup_factor(),
                 );
-                assert!(
-                    cycle_length.is_power_of_two(),
-                    "number of values in a periodic column must be a power of two, but was {cycle_length}"
-                );
-                assert!(cycle_length <= self.trace_length(),
-                    "number of values in a periodic column cannot exceed trace length {}, but was {}",
-                    self.trace_length(),
-                    cycle_length
-                );
-
-                // get twiddles for interpolation and interpolate values into a polynomial
-                let inv_twiddles = twiddle_map
-                    .entry(cycle_length)
-                    .or_insert_with(|| fft::get_inv_twiddles::<Self::BaseField>(cycle_length));
-                fft::interpolate_poly(&mut column, inv_twiddles);
-                column
+                (0..lde_domain_size)
+                    .map(|i| evaluations[i % evaluations.len()])
+                    .collect()
             })
             .collect()
     }
 
+    /// Returns a commitment to the evaluations of periodic columns over the LDE domain.
+    ///
+    /// The commitment is the root of a Merkle tree whose leaves are hashes of the rows of the
+    /// matrix returned from the
+    /// [get_periodic_column_evaluations()](Air::get_periodic_column_evaluations) method. Both the
+    /// prover and the verifier use this method; thus, the verifier never needs to trust a
+    /// commitment sent by the prover.
+    ///
+    /// The default implementation computes the commitment from scratch, which requires a low-degree
+    /// extension of all periodic columns. Since periodic columns do not depend on inputs of the
+    /// computation, specialized implementations may override this method to return precomputed
+    /// commitments for supported trace lengths and proof options.
+    fn get_periodic_column_commitment<H>(&self) -> H::Digest
+    where
+        H: ElementHasher<BaseField = Self::BaseField>,
+    {
+        let evaluations = self.get_periodic_column_evaluations();
+        let mut row = Vec::with_capacity(evaluations.len());
+        let leaves = (0..self.lde_domain_size())
+            .map(|i| {
+                row.clear();
+                row.extend(evaluations.iter().map(|column| column[i]));
+                H::hash_elements(&row)
+            })
+            .collect();
+        *MerkleTree::<H>::new(leaves)
+            .expect("failed to construct Merkle tree")
+            .root()
+    }
+
     /// Groups transition constraints together by their degree.
     ///
     /// This function also assigns composition coefficients to each constraint. These coefficients
@@ -405,7 +527,8 @@ pub trait Air: Send + Sync {
     /// Returns degree of trace polynomials for an instance of the computation described by
     /// this AIR.
     ///
-    /// The degree is always `trace_length` - 1.
+    /// The degree is `trace_length` - 1, unless the proof options specify zero-knowledge; see
+    /// [AirContext::trace_poly_degree()] for details.
     fn trace_poly_degree(&self) -> usize {
         self.context().trace_poly_degree()
     }
@@ -418,6 +541,14 @@ pub trait Air: Send + Sync {
         self.context().trace_domain_generator
     }
 
+    /// Returns offsets of the rows in an evaluation frame relative to the current step for the
+    /// computation described by this AIR.
+    ///
+    /// By default, these are `[0, 1]`; see [AirContext::set_frame_offsets()] for details.
+    fn frame_offsets(&self) -> &[usize] {
+        self.context().frame_offsets()
+    }
+
     /// Returns constraint evaluation domain blowup factor for the computation described by this
     /// AIR.
     ///
@@ -440,16 +571,32 @@ pub trait Air: Send + Sync {
     /// Returns the degree to which all constraint polynomials are normalized before they are
     /// composed together.
     ///
-    /// This degree is one less than the size of constraint evaluation domain.
+    /// This degree is one less than the number of constraint composition columns multiplied by
+    /// the trace length.
     fn composition_degree(&self) -> usize {
         self.context().composition_degree()
     }
 
+    /// Returns the number of columns into which the constraint composition polynomial is split.
+    ///
+    /// Each column is a polynomial of degree `trace_length` - 1. The number of columns is
+    /// determined by the highest degree of a transition constraint quotient, and is never
+    /// greater than `ce_blowup_factor`. In zero-knowledge mode, the columns are randomized, and
+    /// an additional random column is included; see
+    /// [AirContext::num_constraint_composition_columns()] for details.
+    fn num_constraint_composition_columns(&self) -> usize {
+        self.context().num_constraint_composition_columns()
+    }
+
     /// Returns low-degree extension domain blowup factor for the computation described by this
     /// AIR. This is guaranteed to be a power of two, and is always either equal to or greater
     /// than ce_blowup_factor.
+    ///
+    /// This is the ratio of the low-degree extension domain size to the trace length, which is
+    /// equal to the blowup factor specified by the proof options unless the options specify
+    /// zero-knowledge (in which case trace polynomials are larger than the trace).
     fn lde_blowup_factor(&self) -> usize {
-        self.context().options.blowup_factor()
+        self.lde_domain_size() / self.trace_length()
     }
 
     /// Returns the size of the low-degree extension domain.
@@ -474,29 +621,114 @@ pub trait Air: Send + Sync {
         self.context().options.domain_offset()
     }
 
+    /// Returns the offset of the coset over which the execution trace is defined.
+    ///
+    /// This is one unless a different offset was specified via
+    /// [ProofOptions::with_trace_domain_offset()].
+    fn trace_domain_offset(&self) -> Self::BaseField {
+        self.context().options.trace_domain_offset()
+    }
+
+    /// Returns true if `z` can be used as an out-of-domain point for an instance of the
+    /// computation described by this AIR.
+    ///
+    /// A point is out-of-domain if it is neither in the trace domain (i.e., the coset of the
+    /// trace domain defined by [trace_domain_offset()](Air::trace_domain_offset)) nor in the
+    /// low-degree extension domain. Both, the prover and the verifier, keep drawing points from
+    /// the public coin until they draw an out-of-domain point.
+    fn is_out_of_domain<E: FieldElement<BaseField = Self::BaseField>>(&self, z: E) -> bool {
+        let trace_length = self.trace_length() as u64;
+        let lde_domain_size = self.lde_domain_size() as u64;
+        let trace_offset = self.trace_domain_offset().exp(trace_length.into());
+        let lde_offset = self.domain_offset().exp(lde_domain_size.into());
+        z.exp(trace_length.into()) != E::from(trace_offset)
+            && z.exp(lde_domain_size.into()) != E::from(lde_offset)
+    }
+
     // TRACE SEGMENT RANDOMNESS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns data which is absorbed into the public coin right before the random elements for
+    /// the first auxiliary trace segment are drawn.
+    ///
+    /// The random elements used to build auxiliary trace segments are sound only with respect to
+    /// the data absorbed into the public coin before they are drawn: the prover learns the
+    /// elements before committing to the auxiliary segments, and thus, could adapt to them any
+    /// data which the elements do not depend on. Public inputs and the commitment to the main
+    /// trace segment are always absorbed; this method can be used to also bind the elements to
+    /// public data which the auxiliary segments refer to but which is not part of the serialized
+    /// public inputs (e.g., a public lookup table or program derived by the AIR). The data must be
+    /// computable by the verifier, as both the prover and the verifier absorb it.
+    ///
+    /// The data is hashed with the hash function of the public coin before being absorbed. When
+    /// the returned vector is empty (the default), nothing is absorbed.
+    fn get_aux_rand_absorb_data(&self) -> Vec<u8> {
+        Vec::new()
+    }
+
     /// Returns a vector of field elements required for construction of an auxiliary trace segment
     /// with the specified index.
     ///
-    /// The elements are drawn uniformly at random from the provided public coin.
-    fn get_aux_trace_segment_random_elements<E, H>(
+    /// The elements of all rounds of the segment are drawn one round after another via
+    /// [Air::get_aux_trace_round_random_elements()]; for single-round segments, this draws the
+    /// same elements as the prover and the verifier do.
+    fn get_aux_trace_segment_random_elements<E, R>(
+        &self,
+        aux_segment_idx: usize,
+        public_coin: &mut R,
+    ) -> Result<Vec<E>, RandomCoinError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        R: Transcript<Self::BaseField>,
+    {
+        let num_rounds = self
+            .trace_info()
+            .layout()
+            .num_aux_segment_rounds(aux_segment_idx);
+
+        let mut result = Vec::new();
+        for round_idx in 0..num_rounds {
+            result.extend(self.get_aux_trace_round_random_elements::<E, R>(
+                aux_segment_idx,
+                round_idx,
+                public_coin,
+            )?);
+        }
+        Ok(result)
+    }
+
+    /// Returns a vector of field elements drawn in the specified round of construction of an
+    /// auxiliary trace segment with the specified index.
+    ///
+    /// The elements are drawn uniformly at random from the provided public coin. Before the
+    /// elements for the first round of the first auxiliary segment are drawn, the data returned by
+    /// [Air::get_aux_rand_absorb_data()] (if any) is absorbed into the coin.
+    fn get_aux_trace_round_random_elements<E, R>(
         &self,
         aux_segment_idx: usize,
-        public_coin: &mut RandomCoin<Self::BaseField, H>,
+        round_idx: usize,
+        public_coin: &mut R,
     ) -> Result<Vec<E>, RandomCoinError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
-        H: Hasher,
+        R: Transcript<Self::BaseField>,
     {
         let num_elements = self
             .trace_info()
             .layout()
-            .get_aux_segment_rand_elements(aux_segment_idx);
+            .get_aux_segment_round_rand_elements(aux_segment_idx, round_idx);
+
+        if aux_segment_idx == 0 && round_idx == 0 {
+            let absorb_data = self.get_aux_rand_absorb_data();
+            if !absorb_data.is_empty() {
+                let digest = <R::Hasher as Hasher>::hash(&absorb_data);
+                public_coin.absorb(labels::AUX_RAND_ABSORB_DATA, digest);
+            }
+        }
+
         let mut result = Vec::with_capacity(num_elements);
         for _ in 0..num_elements {
-            result.push(public_coin.draw()?);
+            result.push(public_coin.draw(labels::AUX_TRACE_RAND_ELEMENTS)?);
         }
         Ok(result)
     }
@@ -506,22 +738,22 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combination during construction of constraint
     /// composition polynomial.
-    fn get_constraint_composition_coefficients<E, H>(
+    fn get_constraint_composition_coefficients<E, R>(
         &self,
-        public_coin: &mut RandomCoin<Self::BaseField, H>,
+        public_coin: &mut R,
     ) -> Result<ConstraintCompositionCoefficients<E>, RandomCoinError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
-        H: Hasher,
+        R: Transcript<Self::BaseField>,
     {
         let mut t_coefficients = Vec::new();
         for _ in 0..self.context().num_transition_constraints() {
-            t_coefficients.push(public_coin.draw_pair()?);
+            t_coefficients.push(public_coin.draw_pair(labels::CONSTRAINT_COEFFICIENTS)?);
         }
 
         let mut b_coefficients = Vec::new();
         for _ in 0..self.context().num_assertions() {
-            b_coefficients.push(public_coin.draw_pair()?);
+            b_coefficients.push(public_coin.draw_pair(labels::CONSTRAINT_COEFFICIENTS)?);
         }
 
         Ok(ConstraintCompositionCoefficients {
@@ -532,29 +764,104 @@ pub trait Air: Send + Sync {
 
     /// Returns coefficients needed for random linear combinations during construction of DEEP
     /// composition polynomial.
-    fn get_deep_composition_coefficients<E, H>(
+    fn get_deep_composition_coefficients<E, R>(
         &self,
-        public_coin: &mut RandomCoin<Self::BaseField, H>,
+        public_coin: &mut R,
     ) -> Result<DeepCompositionCoefficients<E>, RandomCoinError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
-        H: Hasher,
+        R: Transcript<Self::BaseField>,
     {
+        let label = labels::DEEP_COEFFICIENTS;
+
+        // for each trace column, draw one coefficient per frame offset followed by a coefficient
+        // for the conjugate term; for the default offsets, this is the same as drawing a triple
+        let num_offsets = self.frame_offsets().len();
         let mut t_coefficients = Vec::new();
+        let mut t_conjugate_coefficients = Vec::new();
         for _ in 0..self.trace_info().width() {
-            t_coefficients.push(public_coin.draw_triple()?);
+            let mut column_coefficients = Vec::with_capacity(num_offsets);
+            for _ in 0..num_offsets {
+                column_coefficients.push(public_coin.draw(label)?);
+            }
+            t_coefficients.push(column_coefficients);
+            t_conjugate_coefficients.push(public_coin.draw(label)?);
         }
 
-        // self.ce_blowup_factor() is the same as number of composition columns
         let mut c_coefficients = Vec::new();
-        for _ in 0..self.ce_blowup_factor() {
-            c_coefficients.push(public_coin.draw()?);
+        for _ in 0..self.num_constraint_composition_columns() {
+            c_coefficients.push(public_coin.draw(label)?);
+        }
+
+        // committed periodic columns are drawn after constraint columns; when periodic columns
+        // are not committed, no coefficients are drawn
+        let mut p_coefficients = Vec::new();
+        if self.has_committed_periodic_columns() {
+            for _ in 0..self.get_periodic_column_values().len() {
+                p_coefficients.push(public_coin.draw(label)?);
+            }
         }
 
         Ok(DeepCompositionCoefficients {
             trace: t_coefficients,
+            trace_conjugate: t_conjugate_coefficients,
             constraints: c_coefficients,
-            degree: public_coin.draw_pair()?,
+            periodic: p_coefficients,
+            degree: public_coin.draw_pair(label)?,
         })
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Interpolates values of the specified periodic columns into polynomials.
+///
+/// A polynomial of a column with $k$ cycles in the execution trace is evaluated at $x^k$. Thus,
+/// for a trace defined over a coset of the trace domain with offset $h$, the values of the column
+/// are interpolated over the coset with offset $h^k$.
+///
+/// # Panics
+/// Panics if the number of values in any of the columns is smaller than 2, is not a power of
+/// two, or is greater than the trace length.
+fn interpolate_periodic_columns<B: StarkField>(
+    columns: Vec<Vec<B>>,
+    trace_length: usize,
+    trace_domain_offset: B,
+) -> Vec<Vec<B>> {
+    // cache inverse twiddles for each cycle length so that we don't have to re-build them
+    // for columns with identical cycle lengths
+    let mut twiddle_map = BTreeMap::new();
+    // iterate over all periodic columns and convert column values into polynomials
+    columns
+        .into_iter()
+        .map(|mut column| {
+            let cycle_length = column.len();
+            assert!(
+                cycle_length >= MIN_CYCLE_LENGTH,
+                "number of values in a periodic column must be at least {MIN_CYCLE_LENGTH}, but was {cycle_length}"
+            );
+            assert!(
+                cycle_length.is_power_of_two(),
+                "number of values in a periodic column must be a power of two, but was {cycle_length}"
+            );
+            assert!(
+                cycle_length <= trace_length,
+                "number of values in a periodic column cannot exceed trace length {trace_length}, but was {cycle_length}"
+            );
+
+            // get twiddles for interpolation and interpolate values into a polynomial
+            let inv_twiddles = twiddle_map
+                .entry(cycle_length)
+                .or_insert_with(|| fft::get_inv_twiddles::<B>(cycle_length));
+            if trace_domain_offset == B::ONE {
+                fft::interpolate_poly(&mut column, inv_twiddles);
+            } else {
+                let num_cycles = (trace_length / cycle_length) as u64;
+                let offset = trace_domain_offset.exp(num_cycles.into());
+                fft::interpolate_poly_with_offset(&mut column, inv_twiddles, offset);
+            }
+            column
+        })
+        .collect()
+}
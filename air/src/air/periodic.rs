@@ -0,0 +1,194 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// PERIODIC COLUMN POLYNOMIALS
+// ================================================================================================
+/// Polynomials of periodic columns grouped by cycle length for evaluation at a single point.
+///
+/// A polynomial of a periodic column with k cycles in the execution trace is evaluated at $x^k$.
+/// Columns with the same cycle length are evaluated at the same point; thus, polynomials of such
+/// columns are stored jointly as a single polynomial with vector coefficients, where the $i$th
+/// coefficient holds the $i$th coefficients of all columns in the group. Evaluating such a
+/// polynomial requires computing $x^k$ only once per group, and powers of $x$ for groups with
+/// more cycles are derived from the previous group by repeated squaring.
+///
+/// Evaluations are returned in the order in which the polynomials were provided, and are
+/// identical to evaluating each polynomial individually.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeriodicColumnPolys<B: StarkField> {
+    num_columns: usize,
+    groups: Vec<PeriodicColumnGroup<B>>,
+}
+
+impl<B: StarkField> PeriodicColumnPolys<B> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns periodic column polynomials grouped by cycle length built from the specified
+    /// polynomials of periodic columns for an execution trace of the specified length.
+    ///
+    /// # Panics
+    /// Panics if the length of any of the polynomials is not a power of two, or is greater than
+    /// `trace_length`.
+    pub fn new(polys: Vec<Vec<B>>, trace_length: usize) -> Self {
+        let num_columns = polys.len();
+
+        // group columns by cycle length, such that groups with longer cycles (and, thus, fewer
+        // cycles in the execution trace) come first
+        let mut groups: Vec<PeriodicColumnGroup<B>> = Vec::new();
+        for (column_idx, poly) in polys.iter().enumerate() {
+            let cycle_length = poly.len();
+            assert!(
+                cycle_length.is_power_of_two(),
+                "number of coefficients in a periodic column polynomial must be a power of two, but was {cycle_length}"
+            );
+            assert!(
+                cycle_length <= trace_length,
+                "number of coefficients in a periodic column polynomial cannot exceed trace length {trace_length}, but was {cycle_length}"
+            );
+            match groups.iter_mut().find(|g| g.cycle_length == cycle_length) {
+                Some(group) => group.columns.push(column_idx),
+                None => groups.push(PeriodicColumnGroup {
+                    cycle_length,
+                    num_cycles: trace_length / cycle_length,
+                    columns: vec![column_idx],
+                    coefficients: Vec::new(),
+                }),
+            }
+        }
+        groups.sort_by_key(|group| group.num_cycles);
+
+        // interleave coefficients of all columns in each group
+        for group in groups.iter_mut() {
+            let width = group.columns.len();
+            group.coefficients = Vec::with_capacity(group.cycle_length * width);
+            #[allow(clippy::needless_range_loop)]
+            for i in 0..group.cycle_length {
+                for &column_idx in group.columns.iter() {
+                    group.coefficients.push(polys[column_idx][i]);
+                }
+            }
+        }
+
+        Self {
+            num_columns,
+            groups,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of periodic columns.
+    pub fn num_columns(&self) -> usize {
+        self.num_columns
+    }
+
+    /// Returns true if there are no periodic columns.
+    pub fn is_empty(&self) -> bool {
+        self.num_columns == 0
+    }
+
+    /// Returns the number of distinct cycle lengths of the periodic columns.
+    pub fn num_groups(&self) -> usize {
+        self.groups.len()
+    }
+
+    // EVALUATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns evaluations of all periodic column polynomials at `x`; the polynomial of a column
+    /// with k cycles in the execution trace is evaluated at $x^k$.
+    pub fn evaluate_at<E: FieldElement<BaseField = B>>(&self, x: E) -> Vec<E> {
+        let mut result = vec![E::ZERO; self.num_columns];
+        let mut num_cycles = 0;
+        let mut xp = x;
+        for group in self.groups.iter() {
+            // groups are sorted by the number of cycles, which are all powers of two; thus, x^k
+            // for the current group can be obtained from x^k of the previous group by squaring
+            if num_cycles == 0 {
+                xp = x.exp((group.num_cycles as u64).into());
+            } else {
+                while num_cycles < group.num_cycles {
+                    xp = xp.square();
+                    num_cycles <<= 1;
+                }
+            }
+            num_cycles = group.num_cycles;
+
+            // evaluate polynomials of all columns in the group using Horner's method
+            let width = group.columns.len();
+            let mut evaluations = vec![E::ZERO; width];
+            for coefficients in group.coefficients.chunks(width).rev() {
+                for (evaluation, &coefficient) in evaluations.iter_mut().zip(coefficients) {
+                    *evaluation = *evaluation * xp + E::from(coefficient);
+                }
+            }
+
+            for (&column_idx, evaluation) in group.columns.iter().zip(evaluations) {
+                result[column_idx] = evaluation;
+            }
+        }
+        result
+    }
+}
+
+// PERIODIC COLUMN GROUP
+// ================================================================================================
+/// Polynomials of periodic columns with the same cycle length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PeriodicColumnGroup<B: StarkField> {
+    /// Number of values in a single cycle of the columns.
+    cycle_length: usize,
+    /// Number of cycles of the columns in the execution trace.
+    num_cycles: usize,
+    /// Indexes of the columns in this group.
+    columns: Vec<usize>,
+    /// Coefficients of column polynomials; the $i$th coefficient of the $j$th column in the
+    /// group is at index $i \cdot w + j$, where $w$ is the number of columns in the group.
+    coefficients: Vec<B>,
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::PeriodicColumnPolys;
+    use math::{fields::f128::BaseElement, polynom, FieldElement};
+    use rand_utils::rand_vector;
+
+    #[test]
+    fn evaluate_periodic_column_polys() {
+        let trace_length = 64;
+        let cycle_lengths = [8, 2, 8, 16, 2, 64, 8];
+        let polys = cycle_lengths
+            .iter()
+            .map(|&cycle_length| rand_vector::<BaseElement>(cycle_length))
+            .collect::<Vec<_>>();
+
+        let grouped = PeriodicColumnPolys::new(polys.clone(), trace_length);
+        assert_eq!(cycle_lengths.len(), grouped.num_columns());
+        assert_eq!(4, grouped.num_groups());
+
+        let x = BaseElement::from(7u8);
+        let expected = polys
+            .iter()
+            .map(|poly| {
+                let num_cycles = (trace_length / poly.len()) as u64;
+                polynom::eval(poly, x.exp(num_cycles.into()))
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(expected, grouped.evaluate_at(x));
+
+        // no periodic columns
+        let grouped = PeriodicColumnPolys::<BaseElement>::new(Vec::new(), trace_length);
+        assert!(grouped.is_empty());
+        assert!(grouped.evaluate_at(x).is_empty());
+    }
+}
@@ -5,8 +5,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, BoundaryConstraintGroup, ConstraintDivisor, EvaluationFrame,
+    ProofOptions, SelectorColumn, TraceInfo, TransitionConstraintDegree, TransitionConstraints,
+    TransitionDivisor, TransitionEvaluator,
 };
 use crate::{AuxTraceRandElements, FieldExtension};
 use crypto::{hashers::Blake3_256, RandomCoin};
@@ -62,6 +63,71 @@ fn get_periodic_column_polys_num_values_not_power_of_two() {
 
 // TODO
 
+// TRANSITION EVALUATOR
+// ================================================================================================
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(
+    expected = "MockAir: expected 2 periodic values for main transition constraints, but received \
+    1; values for periodic columns 1..2 are missing"
+)]
+fn transition_evaluator_missing_periodic_values() {
+    let column = vec![BaseElement::ONE, BaseElement::ZERO];
+    let air = MockAir::with_periodic_columns(vec![column.clone(), column], 16);
+    let frame = EvaluationFrame::<BaseElement>::new(4);
+    let mut result = [BaseElement::ZERO];
+    TransitionEvaluator::new(&air).evaluate_main(&frame, &[BaseElement::ONE], &mut result);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(
+    expected = "MockAir: expected 0 periodic values for main transition constraints, but received \
+    1; values at positions 0..1 do not belong to any periodic column"
+)]
+fn transition_evaluator_extra_periodic_values() {
+    let air = MockAir::with_periodic_columns(vec![], 16);
+    let frame = EvaluationFrame::<BaseElement>::new(4);
+    let mut result = [BaseElement::ZERO];
+    TransitionEvaluator::new(&air).evaluate_main(&frame, &[BaseElement::ONE], &mut result);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(
+    expected = "MockAir: the AIR context defines 1 main transition constraints, but the evaluation \
+    buffer has 2 slots"
+)]
+fn transition_evaluator_wrong_result_length() {
+    let air = MockAir::with_periodic_columns(vec![], 16);
+    let frame = EvaluationFrame::<BaseElement>::new(4);
+    let mut result = [BaseElement::ZERO; 2];
+    TransitionEvaluator::new(&air).evaluate_main(&frame, &[], &mut result);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "MockAir: main transition constraint 0 was not written by the AIR")]
+fn transition_evaluator_constraint_not_written() {
+    // the transition constraint of the mock AIR is never written
+    let air = MockAir::with_periodic_columns(vec![], 16);
+    let frame = EvaluationFrame::<BaseElement>::new(4);
+    let mut result = [BaseElement::ZERO];
+    TransitionEvaluator::new(&air).evaluate_main(&frame, &[], &mut result);
+}
+
+#[test]
+fn transition_evaluator_without_write_checks() {
+    let air = MockAir::with_periodic_columns(vec![], 16);
+    let frame = EvaluationFrame::<BaseElement>::new(4);
+    let mut result = [BaseElement::ZERO];
+    TransitionEvaluator::new(&air)
+        .without_write_checks()
+        .evaluate_main(&frame, &[], &mut result);
+    assert_eq!([BaseElement::ZERO], result);
+}
+
 // BOUNDARY CONSTRAINTS
 // ================================================================================================
 
@@ -113,11 +179,15 @@ fn get_boundary_constraints() {
         .collect::<Vec<(BaseElement, BaseElement)>>();
     let constraints = air.get_boundary_constraints(&AuxTraceRandElements::new(), &coefficients);
     let mut groups = constraints.main_constraints().to_vec();
+    let divisor = |group: &BoundaryConstraintGroup<BaseElement, BaseElement>| {
+        &constraints.divisors()[group.divisor_idx()]
+    };
+    assert_eq!(5, constraints.divisors().len());
 
     groups.sort_by(|g1, g2| {
         if g1.degree_adjustment() == g2.degree_adjustment() {
-            let n1 = &g1.divisor().numerator()[0].1;
-            let n2 = &g2.divisor().numerator()[0].1;
+            let n1 = &divisor(g1).numerator()[0].1;
+            let n2 = &divisor(g2).numerator()[0].1;
             n1.to_repr().partial_cmp(&n2.to_repr()).unwrap()
         } else {
             g1.degree_adjustment()
@@ -129,8 +199,8 @@ fn get_boundary_constraints() {
 
     // group 0
     let group = &groups[0];
-    assert_eq!(1, group.divisor().degree());
-    assert_eq!(vec![(1, g.exp(0))], group.divisor().numerator());
+    assert_eq!(1, divisor(group).degree());
+    assert_eq!(vec![(1, g.exp(0))], divisor(group).numerator());
     assert_eq!(1, group.constraints().len());
 
     let constraint = &group.constraints()[0];
@@ -141,8 +211,8 @@ fn get_boundary_constraints() {
 
     // group 1
     let group = &groups[1];
-    assert_eq!(1, group.divisor().degree());
-    assert_eq!(vec![(1, g.exp(9))], group.divisor().numerator());
+    assert_eq!(1, divisor(group).degree());
+    assert_eq!(vec![(1, g.exp(9))], divisor(group).numerator());
     assert_eq!(2, group.constraints().len());
 
     let constraint = &group.constraints()[0];
@@ -159,8 +229,8 @@ fn get_boundary_constraints() {
 
     // group 2
     let group = &groups[2];
-    assert_eq!(2, group.divisor().degree());
-    assert_eq!(vec![(2, g.exp(0))], group.divisor().numerator());
+    assert_eq!(2, divisor(group).degree());
+    assert_eq!(vec![(2, g.exp(0))], divisor(group).numerator());
     assert_eq!(1, group.constraints().len());
 
     let constraint = &group.constraints()[0];
@@ -174,8 +244,8 @@ fn get_boundary_constraints() {
 
     // group 3
     let group = &groups[3];
-    assert_eq!(2, group.divisor().degree());
-    assert_eq!(vec![(2, g.exp(2 * 3))], group.divisor().numerator());
+    assert_eq!(2, divisor(group).degree());
+    assert_eq!(vec![(2, g.exp(2 * 3))], divisor(group).numerator());
     assert_eq!(2, group.constraints().len());
 
     let constraint = &group.constraints()[0];
@@ -195,8 +265,8 @@ fn get_boundary_constraints() {
 
     // group 4
     let group = &groups[4];
-    assert_eq!(4, group.divisor().degree());
-    assert_eq!(vec![(4, g.exp(4 * 2))], group.divisor().numerator());
+    assert_eq!(4, divisor(group).degree());
+    assert_eq!(vec![(4, g.exp(4 * 2))], divisor(group).numerator());
     assert_eq!(2, group.constraints().len());
 
     let constraint = &group.constraints()[0];
@@ -218,6 +288,383 @@ fn get_boundary_constraints() {
     assert_eq!(expected_cc[&7], constraint.cc().clone());
 }
 
+// TRANSITION EXEMPTIONS
+// ================================================================================================
+
+#[test]
+fn set_transition_exemptions() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+    let t_degrees = vec![
+        TransitionConstraintDegree::new(1),
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(2),
+    ];
+    let trace_info = TraceInfo::new(4, 16);
+    let context = AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options);
+    assert_eq!(1, context.num_transition_exemptions());
+    assert_eq!(1, context.get_num_transition_exemptions(2));
+
+    let context = context.set_transition_exemptions(vec![3, 2, 4]);
+    assert_eq!(2, context.num_transition_exemptions());
+    assert_eq!(3, context.get_num_transition_exemptions(0));
+    assert_eq!(2, context.get_num_transition_exemptions(1));
+    assert_eq!(4, context.get_num_transition_exemptions(2));
+
+    // setting a global number of exemptions overrides per-constraint exemptions
+    let context = context.set_num_transition_exemptions(2);
+    assert_eq!(2, context.get_num_transition_exemptions(0));
+    assert_eq!(2, context.get_num_transition_exemptions(2));
+}
+
+#[test]
+#[should_panic(
+    expected = "number of transition exemptions must be equal to the number of transition constraints"
+)]
+fn set_transition_exemptions_wrong_length() {
+    let context = build_context::<BaseElement>(16, 4, 1);
+    let _ = context.set_transition_exemptions(vec![1, 2]);
+}
+
+#[test]
+#[should_panic(
+    expected = "number of transition exemptions for constraint 0 must be greater than zero"
+)]
+fn set_transition_exemptions_zero() {
+    let context = build_context::<BaseElement>(16, 4, 1);
+    let _ = context.set_transition_exemptions(vec![0]);
+}
+
+// TRANSITION DIVISORS
+// ================================================================================================
+
+#[test]
+fn set_transition_divisors() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+    let t_degrees = vec![
+        TransitionConstraintDegree::new(3),
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(2),
+    ];
+    let trace_info = TraceInfo::new(4, 16);
+    let context = AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options);
+    assert_eq!(32, context.ce_domain_size());
+    assert_eq!(
+        TransitionDivisor::with_trailing_exemptions(16, 1),
+        context.get_transition_divisor(1)
+    );
+
+    // constraints enforced on even and odd steps respectively
+    let divisors = vec![
+        TransitionDivisor::new(2, 0, vec![14]),
+        TransitionDivisor::new(2, 1, vec![15]),
+        TransitionDivisor::new(2, 1, vec![11, 15]),
+    ];
+    let context = context.set_transition_divisors(divisors.clone());
+    assert_eq!(1, context.num_transition_exemptions());
+    assert_eq!(2, context.get_num_transition_exemptions(2));
+    assert_eq!(divisors[1], context.get_transition_divisor(1));
+
+    // the divisor of the first constraint has degree 7, and thus, the quotient of the degree 3
+    // constraint has degree 38; this requires constraint evaluation domain of size 64
+    assert_eq!(64, context.ce_domain_size());
+
+    // constraints with the same period and offset share a divisor which is exempt only on the
+    // steps exempted for all of these constraints
+    let coefficients = vec![(BaseElement::ONE, BaseElement::ONE); 3];
+    let constraints = TransitionConstraints::new(&context, &coefficients);
+    assert_eq!(
+        vec![
+            ConstraintDivisor::from_transition_coset(16, 2, 0, &[14]),
+            ConstraintDivisor::from_transition_coset(16, 2, 1, &[15]),
+        ],
+        constraints.divisors()
+    );
+
+    let g = BaseElement::get_root_of_unity(4);
+    let groups = constraints.main_constraints();
+    assert_eq!(3, groups.len());
+    for group in groups {
+        match group.indexes() {
+            [0] => assert_eq!(0, group.divisor_idx()),
+            [1] => assert_eq!((1, 0), (group.divisor_idx(), group.num_extra_exemptions())),
+            [2] => {
+                assert_eq!(1, group.divisor_idx());
+                assert_eq!(&[g.exp(11)], group.extra_exemptions());
+            }
+            _ => panic!("unexpected constraint group"),
+        }
+    }
+}
+
+#[test]
+fn set_transition_divisors_interval() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+    let t_degrees = vec![
+        TransitionConstraintDegree::new(2),
+        TransitionConstraintDegree::new(1),
+        TransitionConstraintDegree::new(1),
+    ];
+    let trace_info = TraceInfo::new(4, 16);
+
+    // the first two constraints hold on the first 4 steps only, and the last constraint holds
+    // on the second half of the trace
+    let divisors = vec![
+        TransitionDivisor::with_interval(0, 4),
+        TransitionDivisor::with_interval(0, 4),
+        TransitionDivisor::with_interval(8, 16),
+    ];
+    assert!(divisors[0].is_enforced_at(3));
+    assert!(!divisors[0].is_enforced_at(4));
+    assert!(!divisors[2].is_enforced_at(7));
+    assert!(divisors[2].is_enforced_at(15));
+
+    let context = AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options)
+        .set_transition_divisors(divisors);
+    assert_eq!(0, context.num_transition_exemptions());
+
+    // the divisor of the first constraint has degree 4, and thus, the quotient of the degree 2
+    // constraint has degree 26; this requires constraint evaluation domain of size 32, and the
+    // composition polynomial is split into 2 columns
+    assert_eq!(32, context.ce_domain_size());
+    assert_eq!(2, context.num_constraint_composition_columns());
+
+    // constraints with the same interval share a divisor
+    let coefficients = vec![(BaseElement::ONE, BaseElement::ONE); 3];
+    let constraints = TransitionConstraints::new(&context, &coefficients);
+    assert_eq!(
+        vec![
+            ConstraintDivisor::from_transition_interval(16, 0, 4),
+            ConstraintDivisor::from_transition_interval(16, 8, 16),
+        ],
+        constraints.divisors()
+    );
+    for group in constraints.main_constraints() {
+        match group.indexes() {
+            [0] | [1] => assert_eq!(0, group.divisor_idx()),
+            [2] => assert_eq!(1, group.divisor_idx()),
+            _ => panic!("unexpected constraint group"),
+        }
+        assert_eq!(0, group.num_extra_exemptions());
+    }
+}
+
+#[test]
+#[should_panic(expected = "interval start must be a multiple of 4, but was 2")]
+fn set_transition_divisors_interval_unaligned() {
+    let _ = TransitionDivisor::with_interval(2, 6);
+}
+
+#[test]
+#[should_panic(expected = "blowup factor too small; expected at least 4, but was 2")]
+fn set_transition_divisors_blowup_too_small() {
+    let options = ProofOptions::new(32, 2, 0, FieldExtension::None, 4, 256);
+    let t_degrees = vec![TransitionConstraintDegree::new(3)];
+    let trace_info = TraceInfo::new(4, 16);
+    let context = AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options);
+    let _ = context.set_transition_divisors(vec![TransitionDivisor::new(4, 1, vec![])]);
+}
+
+// CONSTRAINT COMPOSITION COLUMNS
+// ================================================================================================
+
+#[test]
+fn num_constraint_composition_columns() {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+    let trace_info = TraceInfo::new(4, 16);
+
+    // a degree 7 constraint requires constraint evaluation domain 8x the trace length, but its
+    // quotient by the divisor has degree 6 * 15, and thus, fits into 6 columns
+    let t_degrees = vec![
+        TransitionConstraintDegree::new(7),
+        TransitionConstraintDegree::new(2),
+    ];
+    let context = AirContext::<BaseElement>::new(trace_info.clone(), t_degrees, 1, options.clone());
+    assert_eq!(8, context.ce_blowup_factor);
+    assert_eq!(6, context.num_constraint_composition_columns());
+    assert_eq!(6 * 16 - 1, context.composition_degree());
+
+    // quotients of degree 2 constraints fit into a single column
+    let context = build_context::<BaseElement>(16, 4, 1);
+    assert_eq!(2, context.ce_blowup_factor);
+    assert_eq!(1, context.num_constraint_composition_columns());
+    assert_eq!(15, context.composition_degree());
+
+    // additional exemptions increase the degree of the quotient
+    let context = context.set_num_transition_exemptions(2);
+    assert_eq!(2, context.num_constraint_composition_columns());
+
+    // divisors enforced on a subset of steps increase the degree of the quotient as well; the
+    // divisor below has degree 7, and thus, the quotient of the degree 3 constraint has degree
+    // 38 and requires 3 columns
+    let t_degrees = vec![TransitionConstraintDegree::new(3)];
+    let context = AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options)
+        .set_transition_divisors(vec![TransitionDivisor::new(2, 0, vec![14])]);
+    assert_eq!(4, context.ce_blowup_factor);
+    assert_eq!(3, context.num_constraint_composition_columns());
+}
+
+#[test]
+fn zero_knowledge_context() {
+    // trace polynomials of a 16-step trace are extended by 2 * (32 + 1) random coefficients, and
+    // thus, have 128 coefficients
+    let options = ProofOptions::new(32, 4, 0, FieldExtension::None, 4, 256).with_zero_knowledge();
+    let trace_info = TraceInfo::new(4, 16);
+    let t_degrees = vec![TransitionConstraintDegree::new(1)];
+    let context = AirContext::<BaseElement>::new(trace_info.clone(), t_degrees, 1, options.clone());
+    assert_eq!(127, context.trace_poly_degree());
+    assert_eq!(128 * 4, context.lde_domain_size());
+
+    // the quotient of the degree 1 constraint has degree 127 - 15, and the boundary quotient has
+    // degree 126; thus, the composition polynomial has 8 columns of 16 coefficients, and a random
+    // column is committed to together with these columns
+    assert_eq!(8 * 16 - 1, context.composition_degree());
+    assert_eq!(9, context.num_constraint_composition_columns());
+    assert_eq!(16, context.ce_blowup_factor);
+
+    // a degree 2 constraint has degree 2 * 127 over the randomized trace polynomials
+    let t_degrees = vec![TransitionConstraintDegree::new(2)];
+    let context = AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options);
+    assert_eq!(15 * 16 - 1, context.composition_degree());
+    assert_eq!(16, context.num_constraint_composition_columns());
+    assert_eq!(16, context.ce_blowup_factor);
+
+    // frames of up to three rows reveal at most 3 * 33 values of the 112 random coefficients
+    let context = context.set_frame_offsets(vec![0, 1, 3]);
+    assert_eq!(3, context.num_frame_rows());
+}
+
+#[test]
+#[should_panic(
+    expected = "frame offsets reveal 132 values of randomized trace polynomials, but the polynomials have only 112 random coefficients"
+)]
+fn zero_knowledge_frame_offsets_too_many() {
+    let options = ProofOptions::new(32, 4, 0, FieldExtension::None, 4, 256).with_zero_knowledge();
+    let trace_info = TraceInfo::new(4, 16);
+    let t_degrees = vec![TransitionConstraintDegree::new(1)];
+    let context = AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options);
+    let _ = context.set_frame_offsets(vec![0, 1, 2, 3]);
+}
+
+// TRANSITION SELECTORS
+// ================================================================================================
+
+#[test]
+fn set_transition_selectors() {
+    let values = vec![
+        BaseElement::ONE,
+        BaseElement::ONE,
+        BaseElement::ONE,
+        BaseElement::ZERO,
+    ];
+    let selector = SelectorColumn::new(0, &values);
+    assert_eq!(0, selector.column_idx());
+    assert_eq!(4, selector.cycle_length());
+
+    // the degree of a wrapped constraint accounts for the cycle of the selector
+    let degree = selector.constraint_degree(TransitionConstraintDegree::new(2));
+    assert_eq!(TransitionConstraintDegree::with_cycles(2, vec![4]), degree);
+    assert_eq!(2 * 15 + 12, degree.get_evaluation_degree(16));
+
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+    let t_degrees = vec![degree, TransitionConstraintDegree::new(2)];
+    let trace_info = TraceInfo::new(4, 16);
+    let context = AirContext::<BaseElement>::new(trace_info, t_degrees, 1, options)
+        .set_transition_selectors(vec![Some(selector.clone()), None]);
+    assert_eq!(Some(&selector), context.get_transition_selector(0));
+    assert_eq!(None, context.get_transition_selector(1));
+
+    // evaluations are multiplied by the value of the selector column
+    let periodic_values = [BaseElement::ZERO, BaseElement::ONE];
+    let evaluation = BaseElement::new(5);
+    assert_eq!(
+        BaseElement::ZERO,
+        selector.apply(&periodic_values, evaluation)
+    );
+    let selector = SelectorColumn::new(1, &values);
+    assert_eq!(evaluation, selector.apply(&periodic_values, evaluation));
+}
+
+#[test]
+#[should_panic(
+    expected = "declared degree of transition constraint 0 does not account for its selector with cycle length 4"
+)]
+fn set_transition_selectors_degree_mismatch() {
+    let values = vec![
+        BaseElement::ONE,
+        BaseElement::ONE,
+        BaseElement::ONE,
+        BaseElement::ZERO,
+    ];
+    let selector = SelectorColumn::new(0, &values);
+    let context = build_context::<BaseElement>(16, 4, 1);
+    let _ = context.set_transition_selectors(vec![Some(selector)]);
+}
+
+#[test]
+#[should_panic(
+    expected = "values in a selector column must be binary, but value at position 1 was 2"
+)]
+fn selector_column_not_binary() {
+    let _ = SelectorColumn::new(0, &[BaseElement::ONE, BaseElement::new(2)]);
+}
+
+// FRAME OFFSETS
+// ================================================================================================
+
+#[test]
+fn set_frame_offsets() {
+    let context = build_context::<BaseElement>(16, 4, 1);
+    assert_eq!(&[0, 1], context.frame_offsets());
+    assert_eq!(2, context.num_frame_rows());
+
+    let context = context.set_frame_offsets(vec![0, 1, 3]);
+    assert_eq!(&[0, 1, 3], context.frame_offsets());
+    assert_eq!(3, context.num_frame_rows());
+}
+
+#[test]
+#[should_panic(expected = "frame offsets must start with zero")]
+fn set_frame_offsets_no_zero() {
+    let context = build_context::<BaseElement>(16, 4, 1);
+    let _ = context.set_frame_offsets(vec![1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "frame offsets must be strictly increasing")]
+fn set_frame_offsets_not_increasing() {
+    let context = build_context::<BaseElement>(16, 4, 1);
+    let _ = context.set_frame_offsets(vec![0, 2, 2]);
+}
+
+#[test]
+#[should_panic(expected = "frame offsets must be smaller than 16, but the largest offset was 16")]
+fn set_frame_offsets_too_large() {
+    let context = build_context::<BaseElement>(16, 4, 1);
+    let _ = context.set_frame_offsets(vec![0, 16]);
+}
+
+#[test]
+fn get_deep_composition_coefficients() {
+    // for the default frame offsets, trace coefficients are drawn as triples
+    let assertions = vec![Assertion::single(0, 0, BaseElement::ONE)];
+    let air = MockAir::with_assertions(assertions, 16);
+    let coefficients = air
+        .get_deep_composition_coefficients::<BaseElement, _>(&mut build_prng())
+        .unwrap();
+
+    let mut prng = build_prng();
+    for (trace, &conjugate) in coefficients
+        .trace
+        .iter()
+        .zip(coefficients.trace_conjugate.iter())
+    {
+        let (a, b, c) = prng.draw_triple::<BaseElement>().unwrap();
+        assert_eq!(vec![a, b], *trace);
+        assert_eq!(c, conjugate);
+    }
+    assert_eq!(4, coefficients.trace.len());
+}
+
 // MOCK AIR
 // ================================================================================================
 
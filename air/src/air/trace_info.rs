@@ -13,7 +13,19 @@ use utils::{
 // ================================================================================================
 
 /// Number of allowed auxiliary trace segments.
-const NUM_AUX_SEGMENTS: usize = 1;
+const NUM_AUX_SEGMENTS: usize = TraceLayout::MAX_AUX_SEGMENTS;
+
+/// Marker byte indicating that a segment width is encoded as a u16 value following the marker.
+/// Widths smaller than this value are encoded as a single byte. Since trace widths of 255 and
+/// above were not supported before, this keeps the encoding compatible with layouts serialized
+/// by earlier versions.
+const WIDE_SEGMENT_MARKER: u8 = u8::MAX;
+
+/// Marker byte written in place of the number of random elements of an auxiliary segment which is
+/// built in more than one round; the marker is followed by the number of rounds, and by the width
+/// and the number of random elements of each round. Since non-empty segments cannot require zero
+/// random elements, layouts of single-round segments are encoded as before.
+const MULTI_ROUND_MARKER: u8 = 0;
 
 // TRACE INFO
 // ================================================================================================
@@ -21,8 +33,8 @@ const NUM_AUX_SEGMENTS: usize = 1;
 ///
 /// Trace info consists of trace layout info, length, and optional custom metadata. Trace layout
 /// specifies the number of columns for all trace segments. Currently, a trace can consist of at
-/// most two segments. Metadata is just a vector of bytes and can store any values up to 64KB in
-/// size.
+/// most three segments: the main segment and up to two auxiliary segments. Metadata is just a
+/// vector of bytes and can store any values up to 64KB in size.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceInfo {
     layout: TraceLayout,
@@ -33,8 +45,9 @@ pub struct TraceInfo {
 impl TraceInfo {
     /// Smallest allowed execution trace length; currently set at 8.
     pub const MIN_TRACE_LENGTH: usize = 8;
-    /// Maximum number of columns in an execution trace (across all segments); currently set at 255.
-    pub const MAX_TRACE_WIDTH: usize = 255;
+    /// Maximum number of columns in an execution trace (across all segments); currently set at
+    /// 1024.
+    pub const MAX_TRACE_WIDTH: usize = 1024;
     /// Maximum number of bytes in trace metadata; currently set at 65535.
     pub const MAX_META_LENGTH: usize = 65535;
     /// Maximum number of random elements per auxiliary trace segment; currently set to 255.
@@ -49,7 +62,7 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 1024.
     /// * Trace length is smaller than 8 or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
         Self::with_meta(width, length, vec![])
@@ -61,12 +74,12 @@ impl TraceInfo {
     ///
     /// # Panics
     /// Panics if:
-    /// * Trace width is zero or greater than 255.
+    /// * Trace width is zero or greater than 1024.
     /// * Trace length is smaller than 8 or is not a power of two.
     /// * Length of `meta` is greater than 65535;
     pub fn with_meta(width: usize, length: usize, meta: Vec<u8>) -> Self {
         assert!(width > 0, "trace width must be greater than 0");
-        let layout = TraceLayout::new(width, [0], [0]);
+        let layout = TraceLayout::new(width, [0; NUM_AUX_SEGMENTS], [0; NUM_AUX_SEGMENTS]);
         Self::new_multi_segment(layout, length, meta)
     }
 
@@ -75,7 +88,7 @@ impl TraceInfo {
     /// # Panics
     /// Panics if:
     /// * The width of the first trace segment is zero.
-    /// * Total width of all trace segments is greater than 255.
+    /// * Total width of all trace segments is greater than 1024.
     /// * Trace length is smaller than 8 or is not a power of two.
     pub fn new_multi_segment(layout: TraceLayout, length: usize, meta: Vec<u8>) -> Self {
         assert!(
@@ -106,14 +119,14 @@ impl TraceInfo {
 
     /// Returns a description of how execution trace columns are arranged into segments.
     ///
-    /// Currently, an execution trace can consist of at most two segments.
+    /// Currently, an execution trace can consist of at most three segments.
     pub fn layout(&self) -> &TraceLayout {
         &self.layout
     }
 
     /// Returns the total number of columns in an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 1024.
     pub fn width(&self) -> usize {
         self.layout.main_trace_width() + self.layout().aux_trace_width()
     }
@@ -143,7 +156,13 @@ impl TraceInfo {
 ///
 /// A layout describes how columns of a trace are arranged into segments. All execution traces must
 /// have a non-zero main segment, and may have additional auxiliary trace segments. Currently, the
-/// number of auxiliary trace segments is limited to one.
+/// number of auxiliary trace segments is limited to [TraceLayout::MAX_AUX_SEGMENTS].
+///
+/// Auxiliary segments are built and committed to in sequence: random elements for a given
+/// auxiliary segment are drawn after the prover commits to all preceding segments, and thus, a
+/// segment may depend on the values of all preceding segments. For example, the first auxiliary
+/// segment may hold a permutation argument and the second one a lookup argument, each using its
+/// own random elements.
 ///
 /// Additionally, a layout contains information on how many random elements are required to build a
 /// given auxiliary trace segment. This information is used to construct
@@ -154,23 +173,61 @@ impl TraceInfo {
 /// The number of random elements may be different from the number of columns in a given auxiliary
 /// segment. For example, an auxiliary segment may contain just one column, but may require many
 /// random elements.
+///
+/// By default, all random elements of an auxiliary segment are drawn at once, and the segment is
+/// committed to as a whole. An auxiliary segment may instead be built in several rounds (see
+/// [TraceLayout::with_aux_segment_rounds()]): in each round, a subset of the segment's random
+/// elements is drawn, and the prover builds and commits to a subset of the segment's columns
+/// before the elements of the next round are drawn. Thus, columns of a round may depend on all
+/// random elements drawn in this and prior rounds, and on all columns built in prior rounds. For
+/// example, a lookup argument may draw β, build a column using β, and then draw γ to build
+/// another column of the same segment.
+///
+/// Every round is committed to separately, and the commitments are absorbed into the public coin
+/// in the same order in which rounds are built. This is what makes multi-round segments sound:
+/// the elements of a round are drawn only after the columns of all prior rounds are fixed by a
+/// commitment which the verifier checks query openings against, and thus, the prover cannot adapt
+/// these columns to the elements. A scheme in which the prover commits to the segment once, and
+/// only absorbs hashes of partially built columns between rounds, would not be sound: the
+/// verifier never checks the opened values against such hashes, and thus, the prover could
+/// choose columns of earlier rounds after learning the elements of later rounds. As a result, each
+/// additional round adds one commitment and one set of authentication paths to a proof, but a
+/// multi-round segment is otherwise treated as a single segment: its random elements are
+/// accessed via [AuxTraceRandElements::get_segment_elements()](crate::AuxTraceRandElements),
+/// and its columns form a contiguous range of the auxiliary trace.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct TraceLayout {
     main_segment_width: usize,
     aux_segment_widths: [usize; NUM_AUX_SEGMENTS],
     aux_segment_rands: [usize; NUM_AUX_SEGMENTS],
+    aux_segment_rounds: [Vec<(usize, usize)>; NUM_AUX_SEGMENTS],
     num_aux_segments: usize,
 }
 
 impl TraceLayout {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Maximum number of auxiliary trace segments; currently set to 2.
+    pub const MAX_AUX_SEGMENTS: usize = 2;
+
+    /// Maximum number of rounds in which a single auxiliary trace segment can be built; currently
+    /// set to 16.
+    pub const MAX_AUX_SEGMENT_ROUNDS: usize = 16;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new [TraceLayout] instantiated with the provided info.
     ///
+    /// The `aux_widths` and `aux_rands` arrays specify the number of columns and the number of
+    /// random elements for each auxiliary segment; unused auxiliary segments must be set to zero
+    /// in both arrays. Each auxiliary segment is built in a single round; multi-round segments
+    /// can be defined via [TraceLayout::with_aux_segment_rounds()].
+    ///
     /// # Panics
     /// Panics if:
     /// * Width of the main trace segment is set to zero.
-    /// * Sum of all segment widths exceeds 255.
+    /// * Sum of all segment widths exceeds 1024.
     /// * A zero entry in auxiliary segment width array is followed by a non-zero entry.
     /// * Number of random elements for an auxiliary trace segment of non-zero width is set to zero.
     /// * Number of random elements for an auxiliary trace segment of zero width is set to non-zero.
@@ -222,20 +279,102 @@ impl TraceLayout {
             );
         }
 
+        // by default, every non-empty auxiliary segment is built in a single round
+        let mut aux_segment_rounds: [Vec<(usize, usize)>; NUM_AUX_SEGMENTS] = Default::default();
+        for (i, rounds) in aux_segment_rounds.iter_mut().enumerate() {
+            if aux_widths[i] != 0 {
+                rounds.push((aux_widths[i], aux_rands[i]));
+            }
+        }
+
         Self {
             main_segment_width: main_width,
             aux_segment_widths: aux_widths,
             aux_segment_rands: aux_rands,
+            aux_segment_rounds,
             num_aux_segments,
         }
     }
 
+    /// Returns this layout with the auxiliary segment at the specified index built in several
+    /// rounds.
+    ///
+    /// The `round_widths` and `round_rands` slices specify the number of columns built, and the
+    /// number of random elements drawn, in each round. Columns of a round follow the columns of
+    /// prior rounds within the segment, and so do its random elements in
+    /// [AuxTraceRandElements::get_segment_elements()](crate::AuxTraceRandElements).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `segment_idx` is not smaller than the number of auxiliary segments.
+    /// * The lengths of `round_widths` and `round_rands` are different, or are zero or greater
+    ///   than 16.
+    /// * The number of columns or the number of random elements of any round is zero.
+    /// * The widths of the rounds do not add up to the width of the segment.
+    /// * The numbers of random elements of the rounds do not add up to the number of random
+    ///   elements required by the segment.
+    pub fn with_aux_segment_rounds(
+        mut self,
+        segment_idx: usize,
+        round_widths: &[usize],
+        round_rands: &[usize],
+    ) -> Self {
+        assert!(
+            segment_idx < self.num_aux_segments,
+            "auxiliary segment index must be smaller than {}, but was {}",
+            self.num_aux_segments,
+            segment_idx
+        );
+        assert_eq!(
+            round_widths.len(),
+            round_rands.len(),
+            "number of round widths must be equal to the number of round random element counts"
+        );
+        assert!(
+            !round_widths.is_empty() && round_widths.len() <= Self::MAX_AUX_SEGMENT_ROUNDS,
+            "number of rounds must be between 1 and {}, but was {}",
+            Self::MAX_AUX_SEGMENT_ROUNDS,
+            round_widths.len()
+        );
+        assert!(
+            round_widths.iter().all(|&width| width > 0),
+            "every round must build at least one column"
+        );
+        assert!(
+            round_rands
+                .iter()
+                .all(|&num_rand_elements| num_rand_elements > 0),
+            "every round must draw at least one random element"
+        );
+        let segment_width = self.aux_segment_widths[segment_idx];
+        let round_widths_sum = round_widths.iter().sum::<usize>();
+        assert_eq!(
+            round_widths_sum, segment_width,
+            "round widths must add up to the segment width of {segment_width}, but added up to \
+            {round_widths_sum}"
+        );
+        let segment_rands = self.aux_segment_rands[segment_idx];
+        let round_rands_sum = round_rands.iter().sum::<usize>();
+        assert_eq!(
+            round_rands_sum, segment_rands,
+            "round random elements must add up to the {segment_rands} random elements of the \
+            segment, but added up to {round_rands_sum}"
+        );
+
+        self.aux_segment_rounds[segment_idx] = round_widths
+            .iter()
+            .copied()
+            .zip(round_rands.iter().copied())
+            .collect();
+        self
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns the number of columns in the main segment of an execution trace.
     ///
-    /// This is guaranteed to be between 1 and 255.
+    /// This is guaranteed to be between 1 and 1024.
     pub fn main_trace_width(&self) -> usize {
         self.main_segment_width
     }
@@ -256,36 +395,136 @@ impl TraceLayout {
     }
 
     /// Returns the number of columns in the auxiliary trace segment at the specified index.
+    ///
+    /// # Panics
+    /// Panics if `segment_idx` is not smaller than the number of auxiliary segments.
     pub fn get_aux_segment_width(&self, segment_idx: usize) -> usize {
-        // TODO: panic if segment_idx is not within num_aux_segments
+        assert!(
+            segment_idx < self.num_aux_segments,
+            "auxiliary segment index must be smaller than {}, but was {}",
+            self.num_aux_segments,
+            segment_idx
+        );
         self.aux_segment_widths[segment_idx]
     }
 
     /// Returns the number of random elements required by the auxiliary trace segment at the
     /// specified index.
+    ///
+    /// # Panics
+    /// Panics if `segment_idx` is not smaller than the number of auxiliary segments.
     pub fn get_aux_segment_rand_elements(&self, segment_idx: usize) -> usize {
-        // TODO: panic if segment_idx is not within num_aux_segments
+        assert!(
+            segment_idx < self.num_aux_segments,
+            "auxiliary segment index must be smaller than {}, but was {}",
+            self.num_aux_segments,
+            segment_idx
+        );
         self.aux_segment_rands[segment_idx]
     }
+
+    /// Returns the number of rounds in which the auxiliary trace segment at the specified index
+    /// is built.
+    ///
+    /// # Panics
+    /// Panics if `segment_idx` is not smaller than the number of auxiliary segments.
+    pub fn num_aux_segment_rounds(&self, segment_idx: usize) -> usize {
+        assert!(
+            segment_idx < self.num_aux_segments,
+            "auxiliary segment index must be smaller than {}, but was {}",
+            self.num_aux_segments,
+            segment_idx
+        );
+        self.aux_segment_rounds[segment_idx].len()
+    }
+
+    /// Returns the number of columns built in the specified round of the auxiliary trace segment
+    /// at the specified index.
+    ///
+    /// # Panics
+    /// Panics if `segment_idx` is not smaller than the number of auxiliary segments, or if
+    /// `round_idx` is not smaller than the number of rounds of the segment.
+    pub fn get_aux_segment_round_width(&self, segment_idx: usize, round_idx: usize) -> usize {
+        self.get_aux_segment_round(segment_idx, round_idx).0
+    }
+
+    /// Returns the number of random elements drawn in the specified round of the auxiliary trace
+    /// segment at the specified index.
+    ///
+    /// # Panics
+    /// Panics if `segment_idx` is not smaller than the number of auxiliary segments, or if
+    /// `round_idx` is not smaller than the number of rounds of the segment.
+    pub fn get_aux_segment_round_rand_elements(
+        &self,
+        segment_idx: usize,
+        round_idx: usize,
+    ) -> usize {
+        self.get_aux_segment_round(segment_idx, round_idx).1
+    }
+
+    /// Returns the number of commitments to an execution trace with this layout.
+    ///
+    /// The main segment is committed to once, and every auxiliary segment is committed to once
+    /// per round; thus, for layouts without multi-round segments, this is equal to the number of
+    /// segments.
+    pub fn num_trace_commitments(&self) -> usize {
+        1 + self
+            .aux_segment_rounds
+            .iter()
+            .map(|rounds| rounds.len())
+            .sum::<usize>()
+    }
+
+    /// Returns the number of columns covered by each commitment to the auxiliary segments of an
+    /// execution trace with this layout, in the order in which the commitments are made.
+    pub fn aux_commitment_widths(&self) -> Vec<usize> {
+        self.aux_segment_rounds
+            .iter()
+            .flat_map(|rounds| rounds.iter().map(|&(width, _)| width))
+            .collect()
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the width and the number of random elements of the specified round of the
+    /// auxiliary segment at the specified index.
+    fn get_aux_segment_round(&self, segment_idx: usize, round_idx: usize) -> (usize, usize) {
+        let num_rounds = self.num_aux_segment_rounds(segment_idx);
+        assert!(
+            round_idx < num_rounds,
+            "round index must be smaller than {num_rounds}, but was {round_idx}"
+        );
+        self.aux_segment_rounds[segment_idx][round_idx]
+    }
 }
 
 impl Serializable for TraceLayout {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_u8(self.main_segment_width as u8);
+        write_segment_width(target, self.main_segment_width);
         for &w in self.aux_segment_widths.iter() {
-            debug_assert!(
-                w <= u8::MAX as usize,
-                "aux segment width does not fit into u8 value"
-            );
-            target.write_u8(w as u8);
+            write_segment_width(target, w);
         }
-        for &rc in self.aux_segment_rands.iter() {
+        for (&rc, rounds) in self
+            .aux_segment_rands
+            .iter()
+            .zip(self.aux_segment_rounds.iter())
+        {
             debug_assert!(
                 rc <= u8::MAX as usize,
                 "aux segment random element count does not fit into u8 value"
             );
-            target.write_u8(rc as u8);
+            if rounds.len() > 1 {
+                target.write_u8(MULTI_ROUND_MARKER);
+                target.write_u8(rounds.len() as u8);
+                for &(width, num_rand_elements) in rounds.iter() {
+                    write_segment_width(target, width);
+                    target.write_u8(num_rand_elements as u8);
+                }
+            } else {
+                target.write_u8(rc as u8);
+            }
         }
     }
 }
@@ -297,7 +536,7 @@ impl Deserializable for TraceLayout {
     /// Returns an error of a valid [TraceLayout] struct could not be read from the specified
     /// `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let main_width = source.read_u8()? as usize;
+        let main_width = read_segment_width(source)?;
         if main_width == 0 {
             return Err(DeserializationError::InvalidValue(
                 "main trace segment width must be greater than zero".to_string(),
@@ -308,7 +547,7 @@ impl Deserializable for TraceLayout {
         let mut was_zero_width = false;
         let mut aux_widths = [0; NUM_AUX_SEGMENTS];
         for width in aux_widths.iter_mut() {
-            *width = source.read_u8()? as usize;
+            *width = read_segment_width(source)?;
             if *width != 0 {
                 if was_zero_width {
                     return Err(DeserializationError::InvalidValue(
@@ -321,7 +560,7 @@ impl Deserializable for TraceLayout {
         }
 
         let full_trace_width = main_width + aux_widths.iter().sum::<usize>();
-        if full_trace_width >= TraceInfo::MAX_TRACE_WIDTH {
+        if full_trace_width > TraceInfo::MAX_TRACE_WIDTH {
             return Err(DeserializationError::InvalidValue(format!(
                 "full trace width cannot be greater than {}, but was {}",
                 TraceInfo::MAX_TRACE_WIDTH,
@@ -329,10 +568,21 @@ impl Deserializable for TraceLayout {
             )));
         }
 
-        // read and validate number of random elements for each auxiliary trace segment
+        // read and validate number of random elements for each auxiliary trace segment; for
+        // multi-round segments, also read and validate the rounds
         let mut aux_rands = [0; NUM_AUX_SEGMENTS];
-        for (num_rand_elements, &width) in aux_rands.iter_mut().zip(aux_widths.iter()) {
+        let mut aux_rounds: [Vec<(usize, usize)>; NUM_AUX_SEGMENTS] = Default::default();
+        let segments = aux_rands
+            .iter_mut()
+            .zip(aux_rounds.iter_mut())
+            .zip(aux_widths.iter());
+        for ((num_rand_elements, rounds), &width) in segments {
             *num_rand_elements = source.read_u8()? as usize;
+            if width != 0 && *num_rand_elements == MULTI_ROUND_MARKER as usize {
+                *rounds = read_segment_rounds(source, width)?;
+                *num_rand_elements = rounds.iter().map(|&(_, num_rands)| num_rands).sum();
+            }
+
             if width == 0 && *num_rand_elements != 0 {
                 return Err(DeserializationError::InvalidValue(
                     "an empty trace segment cannot require random elements".to_string(),
@@ -351,6 +601,261 @@ impl Deserializable for TraceLayout {
             }
         }
 
-        Ok(TraceLayout::new(main_width, aux_widths, aux_rands))
+        let mut layout = TraceLayout::new(main_width, aux_widths, aux_rands);
+        for (i, rounds) in aux_rounds.iter().enumerate() {
+            if !rounds.is_empty() {
+                let (round_widths, round_rands): (Vec<_>, Vec<_>) = rounds.iter().copied().unzip();
+                layout = layout.with_aux_segment_rounds(i, &round_widths, &round_rands);
+            }
+        }
+        Ok(layout)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the width of a trace segment into the `target`.
+///
+/// Widths smaller than 255 are written as a single byte; larger widths are written as a marker
+/// byte followed by the width encoded as a u16 value.
+fn write_segment_width<W: ByteWriter>(target: &mut W, width: usize) {
+    debug_assert!(
+        width <= u16::MAX as usize,
+        "segment width does not fit into u16 value"
+    );
+    if width < WIDE_SEGMENT_MARKER as usize {
+        target.write_u8(width as u8);
+    } else {
+        target.write_u8(WIDE_SEGMENT_MARKER);
+        target.write_u16(width as u16);
+    }
+}
+
+/// Reads the width of a trace segment from the `source`.
+///
+/// # Errors
+/// Returns an error if a width could not be read from the `source`, or if the width exceeds the
+/// maximum allowed trace width.
+fn read_segment_width<R: ByteReader>(source: &mut R) -> Result<usize, DeserializationError> {
+    let width = match source.read_u8()? {
+        WIDE_SEGMENT_MARKER => source.read_u16()? as usize,
+        width => width as usize,
+    };
+    if width > TraceInfo::MAX_TRACE_WIDTH {
+        return Err(DeserializationError::InvalidValue(format!(
+            "trace segment width cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            width
+        )));
+    }
+    Ok(width)
+}
+
+/// Reads the rounds of a multi-round auxiliary segment of the specified width from the `source`.
+///
+/// # Errors
+/// Returns an error if the rounds could not be read from the `source`, if the number of rounds is
+/// smaller than 2 or greater than the maximum, if any round is empty or does not require random
+/// elements, or if the widths of the rounds do not add up to the width of the segment.
+fn read_segment_rounds<R: ByteReader>(
+    source: &mut R,
+    segment_width: usize,
+) -> Result<Vec<(usize, usize)>, DeserializationError> {
+    let num_rounds = source.read_u8()? as usize;
+    if !(2..=TraceLayout::MAX_AUX_SEGMENT_ROUNDS).contains(&num_rounds) {
+        return Err(DeserializationError::InvalidValue(format!(
+            "number of rounds of a multi-round segment must be between 2 and {}, but was {}",
+            TraceLayout::MAX_AUX_SEGMENT_ROUNDS,
+            num_rounds
+        )));
+    }
+
+    let mut rounds = Vec::with_capacity(num_rounds);
+    for _ in 0..num_rounds {
+        let width = read_segment_width(source)?;
+        let num_rand_elements = source.read_u8()? as usize;
+        if width == 0 || num_rand_elements == 0 {
+            return Err(DeserializationError::InvalidValue(
+                "every round must build at least one column and draw at least one random element"
+                    .to_string(),
+            ));
+        }
+        rounds.push((width, num_rand_elements));
+    }
+
+    let rounds_width = rounds.iter().map(|&(width, _)| width).sum::<usize>();
+    if rounds_width != segment_width {
+        return Err(DeserializationError::InvalidValue(format!(
+            "round widths must add up to the segment width of {segment_width}, but added up to \
+            {rounds_width}"
+        )));
+    }
+    Ok(rounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TraceInfo, TraceLayout};
+    use utils::{Deserializable, DeserializationError, Serializable, SliceReader};
+
+    #[test]
+    fn trace_layout_serialization() {
+        // narrow layouts use a single byte per segment width
+        let layout = TraceLayout::new(12, [3, 0], [2, 0]);
+        let bytes = layout.to_bytes();
+        assert_eq!(vec![12, 3, 0, 2, 0], bytes);
+        assert_eq!(
+            layout,
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)).unwrap()
+        );
+
+        // two auxiliary segments
+        let layout = TraceLayout::new(12, [3, 1], [2, 5]);
+        let bytes = layout.to_bytes();
+        assert_eq!(vec![12, 3, 1, 2, 5], bytes);
+        assert_eq!(2, layout.num_aux_segments());
+        assert_eq!(4, layout.aux_trace_width());
+        assert_eq!(1, layout.get_aux_segment_width(1));
+        assert_eq!(5, layout.get_aux_segment_rand_elements(1));
+        assert_eq!(
+            layout,
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)).unwrap()
+        );
+
+        // wide layouts
+        let layout = TraceLayout::new(300, [0, 0], [0, 0]);
+        let bytes = layout.to_bytes();
+        assert_eq!(
+            layout,
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)).unwrap()
+        );
+
+        let layout = TraceLayout::new(218, [300, 200], [33, 1]);
+        let bytes = layout.to_bytes();
+        assert_eq!(
+            layout,
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)).unwrap()
+        );
+
+        let layout = TraceLayout::new(255, [TraceInfo::MAX_TRACE_WIDTH - 255, 0], [1, 0]);
+        let bytes = layout.to_bytes();
+        assert_eq!(
+            layout,
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)).unwrap()
+        );
+    }
+
+    #[test]
+    fn trace_layout_rounds_serialization() {
+        // single-round segments are encoded the same way regardless of how they were defined
+        let layout = TraceLayout::new(12, [3, 1], [2, 5]).with_aux_segment_rounds(0, &[3], &[2]);
+        assert_eq!(vec![12, 3, 1, 2, 5], layout.to_bytes());
+        assert_eq!(1, layout.num_aux_segment_rounds(0));
+        assert_eq!(3, layout.num_trace_commitments());
+
+        // a multi-round segment is encoded as a marker followed by the rounds
+        let layout =
+            TraceLayout::new(12, [3, 1], [2, 5]).with_aux_segment_rounds(0, &[1, 2], &[1, 1]);
+        let bytes = layout.to_bytes();
+        assert_eq!(vec![12, 3, 1, 0, 2, 1, 1, 2, 1, 5], bytes);
+        assert_eq!(2, layout.num_aux_segment_rounds(0));
+        assert_eq!(2, layout.get_aux_segment_round_width(0, 1));
+        assert_eq!(1, layout.get_aux_segment_round_rand_elements(0, 1));
+        assert_eq!(2, layout.get_aux_segment_rand_elements(0));
+        assert_eq!(4, layout.num_trace_commitments());
+        assert_eq!(vec![1, 2, 1], layout.aux_commitment_widths());
+        assert_eq!(
+            layout,
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)).unwrap()
+        );
+
+        // wide rounds
+        let layout = TraceLayout::new(4, [600, 0], [3, 0]).with_aux_segment_rounds(
+            0,
+            &[300, 1, 299],
+            &[1, 1, 1],
+        );
+        let bytes = layout.to_bytes();
+        assert_eq!(
+            layout,
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)).unwrap()
+        );
+    }
+
+    #[test]
+    fn trace_layout_invalid_rounds() {
+        // rounds must add up to the segment width
+        let bytes = vec![4, 3, 0, 0, 2, 1, 1, 1, 1, 0];
+        assert!(matches!(
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+
+        // a multi-round segment must have at least two rounds
+        let bytes = vec![4, 3, 0, 0, 1, 3, 1, 0];
+        assert!(matches!(
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+
+        // every round must draw random elements
+        let bytes = vec![4, 3, 0, 0, 2, 1, 1, 2, 0, 0];
+        assert!(matches!(
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+
+        // the total number of random elements of a segment is limited
+        let bytes = vec![4, 3, 0, 0, 2, 1, 200, 2, 100, 0];
+        assert!(matches!(
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "round widths must add up to the segment width of 3")]
+    fn trace_layout_rounds_width_mismatch() {
+        TraceLayout::new(4, [3, 0], [2, 0]).with_aux_segment_rounds(0, &[1, 1], &[1, 1]);
+    }
+
+    #[test]
+    fn trace_layout_too_wide() {
+        // main segment exceeds max trace width
+        let mut bytes = vec![u8::MAX];
+        bytes.extend_from_slice(&(TraceInfo::MAX_TRACE_WIDTH as u16 + 1).to_le_bytes());
+        bytes.extend_from_slice(&[0, 0, 0, 0]);
+        assert!(matches!(
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+
+        // full trace width exceeds max trace width
+        let mut bytes = vec![u8::MAX];
+        bytes.extend_from_slice(&1000u16.to_le_bytes());
+        bytes.push(u8::MAX);
+        bytes.extend_from_slice(&1000u16.to_le_bytes());
+        bytes.extend_from_slice(&[0, 1, 0]);
+        assert!(matches!(
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn trace_layout_aux_segment_order() {
+        // the second auxiliary segment cannot be non-empty if the first one is empty
+        let bytes = vec![4, 0, 1, 0, 1];
+        assert!(matches!(
+            TraceLayout::read_from(&mut SliceReader::new(&bytes)),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "a non-empty trace segment cannot follow an empty segment")]
+    fn trace_layout_empty_first_aux_segment() {
+        TraceLayout::new(4, [0, 1], [0, 1]);
     }
 }
@@ -75,6 +75,29 @@ impl TransitionConstraintDegree {
         }
     }
 
+    /// Returns a copy of this degree descriptor with a periodic column of the specified cycle
+    /// length added to it.
+    ///
+    /// # Panics
+    /// Panics if `cycle_length` is smaller than two or is not a power of two.
+    pub(crate) fn with_cycle(self, cycle_length: usize) -> Self {
+        let mut cycles = self.cycles;
+        cycles.push(cycle_length);
+        Self::with_cycles(self.base, cycles)
+    }
+
+    /// Returns the number of trace columns multiplied together in the constraint described by
+    /// this degree descriptor.
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    /// Returns cycle lengths of periodic columns involved in the constraint described by this
+    /// degree descriptor.
+    pub fn cycles(&self) -> &[usize] {
+        &self.cycles
+    }
+
     /// Computes a degree to which this degree description expands in the context of execution
     /// trace of the specified length.
     ///
@@ -96,7 +119,24 @@ impl TransitionConstraintDegree {
     /// 2 \cdot (64 - 1) + \frac{64 \cdot (32 - 1)}{32} = 126 + 62 = 188
     /// $$
     pub fn get_evaluation_degree(&self, trace_length: usize) -> usize {
-        let mut result = self.base * (trace_length - 1);
+        self.get_randomized_evaluation_degree(trace_length, trace_length - 1)
+    }
+
+    /// Computes a degree to which this degree description expands in the context of execution
+    /// trace of the specified length when trace polynomials have degree `trace_poly_degree`.
+    ///
+    /// This is the same as [TransitionConstraintDegree::get_evaluation_degree()], except that the
+    /// base degree is multiplied by `trace_poly_degree` rather than by `trace_length - 1`. Trace
+    /// polynomials have a higher degree than `trace_length - 1` when they are randomized in
+    /// zero-knowledge mode (see
+    /// [ProofOptions::with_zero_knowledge()](crate::ProofOptions::with_zero_knowledge)); degrees
+    /// of periodic columns are not affected by this.
+    pub fn get_randomized_evaluation_degree(
+        &self,
+        trace_length: usize,
+        trace_poly_degree: usize,
+    ) -> usize {
+        let mut result = self.base * trace_poly_degree;
         for cycle_length in self.cycles.iter() {
             result += (trace_length / cycle_length) * (cycle_length - 1);
         }
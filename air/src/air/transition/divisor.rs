@@ -0,0 +1,205 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ConstraintDivisor, Vec};
+use math::StarkField;
+
+// TRANSITION DIVISOR
+// ================================================================================================
+/// Describes the steps of an execution trace on which a transition constraint must hold.
+///
+/// A transition constraint described by this struct must hold on every `period`-th step of the
+/// execution trace starting at step `offset`, except for the explicitly listed exempted steps.
+/// For example, a descriptor with `period = 4`, `offset = 1`, and `exemptions = [13]` enforces
+/// a constraint on steps 1, 5, and 9 of an execution trace of length 16.
+///
+/// By default, transition constraints must hold on all steps of the execution trace except for
+/// the last step. This corresponds to a descriptor with `period = 1`, `offset = 0`, and
+/// `exemptions = [n - 1]`, where $n$ is the length of the execution trace.
+///
+/// Alternatively, a descriptor can enforce a constraint only on a contiguous interval of steps
+/// (see [TransitionDivisor::with_interval()]). For example, a descriptor with `interval = [0, 64)`
+/// enforces a constraint on the first 64 steps of an execution trace only; this is useful for
+/// computations which execute an initialization phase with different rules than the rest of the
+/// computation.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TransitionDivisor {
+    period: usize,
+    offset: usize,
+    exemptions: Vec<usize>,
+    interval: Option<(usize, usize)>,
+}
+
+impl TransitionDivisor {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new transition divisor descriptor for constraints which must hold on every
+    /// `period`-th step of the execution trace starting at step `offset`, except for the steps
+    /// listed in `exemptions`.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `period` is not a power of two.
+    /// * `offset` is greater than or equal to `period`.
+    /// * Any of the exempted steps is not on the coset defined by `period` and `offset`, or is
+    ///   listed more than once.
+    pub fn new(period: usize, offset: usize, mut exemptions: Vec<usize>) -> Self {
+        assert!(
+            period.is_power_of_two(),
+            "transition divisor period must be a power of two, but was {period}"
+        );
+        assert!(
+            offset < period,
+            "transition divisor offset must be smaller than {period}, but was {offset}"
+        );
+        exemptions.sort_unstable();
+        for (i, &step) in exemptions.iter().enumerate() {
+            assert!(
+                step % period == offset,
+                "exempted step {step} is not enforced by a divisor with period {period} and offset {offset}"
+            );
+            assert!(
+                i == 0 || exemptions[i - 1] != step,
+                "exempted step {step} is listed more than once"
+            );
+        }
+
+        TransitionDivisor {
+            period,
+            offset,
+            exemptions,
+            interval: None,
+        }
+    }
+
+    /// Returns a transition divisor descriptor for constraints which must hold on all steps of
+    /// an execution trace of the specified length except for the last `num_exemptions` steps.
+    ///
+    /// # Panics
+    /// Panics if `num_exemptions` is zero or is greater than or equal to `trace_length`.
+    pub fn with_trailing_exemptions(trace_length: usize, num_exemptions: usize) -> Self {
+        assert!(
+            num_exemptions > 0 && num_exemptions < trace_length,
+            "number of trailing exemptions must be between 1 and {}, but was {}",
+            trace_length - 1,
+            num_exemptions
+        );
+        let exemptions = (trace_length - num_exemptions..trace_length).collect();
+        Self::new(1, 0, exemptions)
+    }
+
+    /// Returns a transition divisor descriptor for constraints which must hold only on the steps
+    /// in the interval [`start`, `end`) of the execution trace.
+    ///
+    /// The degree of the resulting divisor is `end - start`, which is usually much smaller than
+    /// the degree of a divisor enforcing a constraint on all steps of the trace. Thus, the degree
+    /// of the quotient of a constraint by this divisor is greater, and may require a larger
+    /// constraint evaluation domain (this is accounted for by
+    /// [AirContext::set_transition_divisors()](crate::AirContext::set_transition_divisors)).
+    ///
+    /// # Panics
+    /// Panics if `end - start` is not a power of two, or if `start` is not a multiple of
+    /// `end - start`.
+    pub fn with_interval(start: usize, end: usize) -> Self {
+        assert!(
+            start < end,
+            "transition divisor interval [{start}, {end}) must not be empty"
+        );
+        let length = end - start;
+        assert!(
+            length.is_power_of_two(),
+            "transition divisor interval length must be a power of two, but was {length}"
+        );
+        assert!(
+            start % length == 0,
+            "transition divisor interval start must be a multiple of {length}, but was {start}"
+        );
+        TransitionDivisor {
+            period: 1,
+            offset: 0,
+            exemptions: Vec::new(),
+            interval: Some((start, end)),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the distance between consecutive steps on which constraints must hold.
+    ///
+    /// For descriptors with an interval, this is always 1.
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the first step on which constraints must hold (unless this step is exempted).
+    ///
+    /// For descriptors with an interval, this is always 0; the first step on which constraints
+    /// must hold is the start of the interval.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the steps on which constraints do not need to hold; the steps are sorted in
+    /// ascending order.
+    pub fn exemptions(&self) -> &[usize] {
+        &self.exemptions
+    }
+
+    /// Returns the number of exempted steps.
+    pub fn num_exemptions(&self) -> usize {
+        self.exemptions.len()
+    }
+
+    /// Returns the interval [`start`, `end`) of steps on which constraints must hold, or None if
+    /// this descriptor does not restrict constraints to an interval.
+    pub fn interval(&self) -> Option<(usize, usize)> {
+        self.interval
+    }
+
+    /// Returns true if constraints described by this descriptor must hold at the specified step.
+    pub fn is_enforced_at(&self, step: usize) -> bool {
+        if let Some((start, end)) = self.interval {
+            return (start..end).contains(&step);
+        }
+        step % self.period == self.offset && self.exemptions.binary_search(&step).is_err()
+    }
+
+    /// Returns the degree of the divisor polynomial for an execution trace of the specified
+    /// length.
+    pub fn degree(&self, trace_length: usize) -> usize {
+        if let Some((start, end)) = self.interval {
+            return end - start;
+        }
+        trace_length / self.period - self.exemptions.len()
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds a [ConstraintDivisor] described by this descriptor for an execution trace of the
+    /// specified length.
+    ///
+    /// # Panics
+    /// Panics if this descriptor is not valid for an execution trace of the specified length
+    /// (see [ConstraintDivisor::from_transition_coset()] and
+    /// [ConstraintDivisor::from_transition_interval()]).
+    pub fn to_constraint_divisor<B: StarkField>(
+        &self,
+        trace_length: usize,
+    ) -> ConstraintDivisor<B> {
+        if let Some((start, end)) = self.interval {
+            return ConstraintDivisor::from_transition_interval(trace_length, start, end);
+        }
+        ConstraintDivisor::from_transition_coset(
+            trace_length,
+            self.period,
+            self.offset,
+            &self.exemptions,
+        )
+    }
+}
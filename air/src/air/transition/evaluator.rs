@@ -0,0 +1,255 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{super::AuxTraceRandElements, EvaluationFrame, ExtensionOf, FieldElement, Vec};
+use crate::Air;
+use core::any::type_name;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Value written into evaluation buffers before transition constraints are evaluated in debug
+/// builds; constraint slots which still contain this value after the evaluation were not written
+/// by the AIR.
+const SENTINEL: u64 = 0x5e47_1e11_0bad_cafe;
+
+// TRANSITION EVALUATOR
+// ================================================================================================
+/// Evaluates transition constraints of an AIR, validating inputs and outputs of the evaluation in
+/// debug builds.
+///
+/// Mistakes in an AIR, such as an incorrect number of periodic columns or a constraint which is
+/// never written, usually manifest themselves as out-of-bounds panics inside
+/// [Air::evaluate_transition()] or as proofs which fail to verify. In debug builds, this evaluator
+/// catches these mistakes right before and right after the AIR is invoked, and panics with a
+/// message naming the AIR and the offending periodic column or constraint. Specifically:
+/// * The number of periodic values must be equal to the number of periodic columns defined by
+///   the AIR.
+/// * The length of the result slice must be equal to the number of transition constraints
+///   defined in the context of the AIR.
+/// * When write checks are enabled, every constraint must be written by the AIR. This is detected
+///   by evaluating the constraints once more into a buffer filled with a sentinel value.
+///
+/// In release builds, the evaluator simply forwards all calls to the AIR.
+pub struct TransitionEvaluator<'a, A: Air> {
+    air: &'a A,
+    num_main_periodic_columns: usize,
+    num_aux_periodic_columns: usize,
+    check_writes: bool,
+}
+
+impl<'a, A: Air> TransitionEvaluator<'a, A> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new evaluator of transition constraints for the specified AIR.
+    ///
+    /// Write checks are enabled for the returned evaluator; the check is reliable only when
+    /// constraints are evaluated at points outside of the trace domain.
+    pub fn new(air: &'a A) -> Self {
+        // periodic columns are counted only when the counts are used for validation
+        let (num_main_periodic_columns, num_aux_periodic_columns) = if cfg!(debug_assertions) {
+            (
+                air.get_periodic_column_values().len(),
+                air.get_aux_periodic_column_values().len(),
+            )
+        } else {
+            (0, 0)
+        };
+
+        Self {
+            air,
+            num_main_periodic_columns,
+            num_aux_periodic_columns,
+            check_writes: true,
+        }
+    }
+
+    /// Returns this evaluator with write checks disabled.
+    ///
+    /// Write checks should be disabled when constraints are evaluated over the trace domain:
+    /// constraints accumulated into their slots (e.g., using selector flags) legitimately leave
+    /// these slots unchanged on steps at which all flags are zero.
+    pub fn without_write_checks(mut self) -> Self {
+        self.check_writes = false;
+        self
+    }
+
+    // EVALUATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates transition constraints of the main trace segment over the specified frame using
+    /// [Air::evaluate_transition()].
+    ///
+    /// # Panics
+    /// In debug builds, panics if:
+    /// * The number of `periodic_values` is not equal to the number of periodic columns of the
+    ///   AIR.
+    /// * The length of `result` is not equal to the number of main transition constraints.
+    /// * Write checks are enabled, and any of the constraints was not written by the AIR.
+    pub fn evaluate_main<E>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) where
+        E: FieldElement<BaseField = A::BaseField>,
+    {
+        if cfg!(debug_assertions) {
+            self.validate_inputs(
+                "main",
+                periodic_values.len(),
+                self.num_main_periodic_columns,
+                result.len(),
+                self.air.context().num_main_transition_constraints(),
+            );
+        }
+
+        self.air.evaluate_transition(frame, periodic_values, result);
+
+        if cfg!(debug_assertions) && self.check_writes {
+            self.validate_writes("main", result.len(), |probe| {
+                self.air.evaluate_transition(frame, periodic_values, probe)
+            });
+        }
+    }
+
+    /// Evaluates transition constraints of auxiliary trace segments over the specified frames
+    /// using [Air::evaluate_aux_transition()].
+    ///
+    /// # Panics
+    /// In debug builds, panics if:
+    /// * The number of `periodic_values` is not equal to the total number of periodic columns of
+    ///   the AIR (i.e., for the main and auxiliary transition constraints).
+    /// * The length of `result` is not equal to the number of auxiliary transition constraints.
+    /// * Write checks are enabled, and any of the constraints was not written by the AIR.
+    pub fn evaluate_aux<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = A::BaseField>,
+        E: FieldElement<BaseField = A::BaseField> + ExtensionOf<F>,
+    {
+        if cfg!(debug_assertions) {
+            self.validate_inputs(
+                "auxiliary",
+                periodic_values.len(),
+                self.num_main_periodic_columns + self.num_aux_periodic_columns,
+                result.len(),
+                self.air.context().num_aux_transition_constraints(),
+            );
+        }
+
+        self.air.evaluate_aux_transition(
+            main_frame,
+            aux_frame,
+            periodic_values,
+            aux_rand_elements,
+            result,
+        );
+
+        if cfg!(debug_assertions) && self.check_writes {
+            self.validate_writes("auxiliary", result.len(), |probe| {
+                self.air.evaluate_aux_transition(
+                    main_frame,
+                    aux_frame,
+                    periodic_values,
+                    aux_rand_elements,
+                    probe,
+                )
+            });
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Makes sure the number of periodic values and the length of the result buffer match the
+    /// numbers of periodic columns and transition constraints defined by the AIR.
+    fn validate_inputs(
+        &self,
+        segment: &str,
+        num_periodic_values: usize,
+        num_periodic_columns: usize,
+        result_len: usize,
+        num_constraints: usize,
+    ) {
+        if num_periodic_values < num_periodic_columns {
+            panic!(
+                "{}: expected {} periodic values for {} transition constraints, but received {}; \
+                values for periodic columns {}..{} are missing",
+                type_name::<A>(),
+                num_periodic_columns,
+                segment,
+                num_periodic_values,
+                num_periodic_values,
+                num_periodic_columns,
+            );
+        }
+        if num_periodic_values > num_periodic_columns {
+            panic!(
+                "{}: expected {} periodic values for {} transition constraints, but received {}; \
+                values at positions {}..{} do not belong to any periodic column",
+                type_name::<A>(),
+                num_periodic_columns,
+                segment,
+                num_periodic_values,
+                num_periodic_columns,
+                num_periodic_values,
+            );
+        }
+        if result_len != num_constraints {
+            panic!(
+                "{}: the AIR context defines {} {} transition constraints, but the evaluation \
+                buffer has {} slots",
+                type_name::<A>(),
+                num_constraints,
+                segment,
+                result_len,
+            );
+        }
+    }
+
+    /// Makes sure that all constraints were written by the AIR.
+    ///
+    /// The constraints are evaluated into a buffer filled with a sentinel value; a constraint
+    /// is considered not written only if its slot still contains the sentinel value, and if it
+    /// still contains a different sentinel value after another evaluation. This way, constraints
+    /// which happen to evaluate to the sentinel value are not reported.
+    fn validate_writes<E, P>(&self, segment: &str, num_constraints: usize, evaluate: P)
+    where
+        E: FieldElement,
+        P: Fn(&mut [E]),
+    {
+        let sentinel = E::from(SENTINEL);
+        let mut probe = vec![sentinel; num_constraints];
+        evaluate(&mut probe);
+        let candidates = probe
+            .iter()
+            .enumerate()
+            .filter(|(_, &value)| value == sentinel)
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return;
+        }
+
+        let sentinel = sentinel + E::ONE;
+        let mut probe = vec![sentinel; num_constraints];
+        evaluate(&mut probe);
+        if let Some(&idx) = candidates.iter().find(|&&i| probe[i] == sentinel) {
+            panic!(
+                "{}: {} transition constraint {} was not written by the AIR",
+                type_name::<A>(),
+                segment,
+                idx,
+            );
+        }
+    }
+}
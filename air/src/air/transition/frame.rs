@@ -10,48 +10,86 @@ use super::{FieldElement, Vec};
 // ================================================================================================
 /// A set of execution trace rows required for evaluation of transition constraints.
 ///
-/// In the current implementation, an evaluation frame always contains two consecutive rows of the
-/// execution trace. It is passed in as one of the parameters into
+/// By default, an evaluation frame contains two consecutive rows of the execution trace: the
+/// current row and the next row. An AIR may request additional rows by specifying a list of row
+/// offsets via [AirContext::set_frame_offsets()](crate::AirContext::set_frame_offsets); in this
+/// case, the $j$th row of the frame contains the values of the trace at step $i + o_j$, where $i$
+/// is the current step and $o_j$ is the $j$th offset.
+///
+/// An evaluation frame is passed in as one of the parameters into
 /// [Air::evaluate_transition()](crate::Air::evaluate_transition) function.
 #[derive(Debug, Clone)]
 pub struct EvaluationFrame<E: FieldElement> {
-    current: Vec<E>,
-    next: Vec<E>,
+    rows: Vec<Vec<E>>,
 }
 
 impl<E: FieldElement> EvaluationFrame<E> {
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a new evaluation frame instantiated with the specified number of columns.
+    /// Returns a new two-row evaluation frame instantiated with the specified number of columns.
     ///
     /// # Panics
     /// Panics if `num_columns` is zero.
     pub fn new(num_columns: usize) -> Self {
+        Self::new_multi_row(2, num_columns)
+    }
+
+    /// Returns a new evaluation frame instantiated with the specified number of rows and columns.
+    ///
+    /// # Panics
+    /// Panics if `num_rows` or `num_columns` is zero.
+    pub fn new_multi_row(num_rows: usize, num_columns: usize) -> Self {
+        assert!(num_rows > 0, "number of rows must be greater than zero");
         assert!(
             num_columns > 0,
             "number of columns must be greater than zero"
         );
         EvaluationFrame {
-            current: E::zeroed_vector(num_columns),
-            next: E::zeroed_vector(num_columns),
+            rows: (0..num_rows)
+                .map(|_| E::zeroed_vector(num_columns))
+                .collect(),
         }
     }
 
-    /// Returns a new evaluation frame instantiated from the provided rows.
+    /// Returns a new two-row evaluation frame instantiated from the provided rows.
     ///
     /// # Panics
     /// Panics if:
     /// * Lengths of the provided rows are zero.
     /// * Lengths of the provided rows are not the same.
     pub fn from_rows(current: Vec<E>, next: Vec<E>) -> Self {
-        assert!(!current.is_empty(), "a row must contain at least one value");
-        assert_eq!(
-            current.len(),
-            next.len(),
+        Self::from_multi_rows(vec![current, next])
+    }
+
+    /// Returns a new evaluation frame instantiated from the provided list of rows.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The list of rows is empty.
+    /// * Lengths of the provided rows are zero.
+    /// * Lengths of the provided rows are not the same.
+    pub fn from_multi_rows(rows: Vec<Vec<E>>) -> Self {
+        assert!(!rows.is_empty(), "a frame must contain at least one row");
+        assert!(!rows[0].is_empty(), "a row must contain at least one value");
+        assert!(
+            rows.iter().all(|row| row.len() == rows[0].len()),
             "number of values in the rows must be the same"
         );
-        Self { current, next }
+        Self { rows }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of rows in this frame.
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of columns in each row of this frame.
+    pub fn num_columns(&self) -> usize {
+        self.rows[0].len()
     }
 
     // ROW ACCESSORS
@@ -60,24 +98,56 @@ impl<E: FieldElement> EvaluationFrame<E> {
     /// Returns a reference to the current row.
     #[inline(always)]
     pub fn current(&self) -> &[E] {
-        &self.current
+        &self.rows[0]
     }
 
     /// Returns a mutable reference to the current row.
     #[inline(always)]
     pub fn current_mut(&mut self) -> &mut [E] {
-        &mut self.current
+        &mut self.rows[0]
     }
 
     /// Returns a reference to the next row.
+    ///
+    /// This is the row at the second offset of the frame; for the default offsets, this is the
+    /// row immediately following the current row.
+    ///
+    /// # Panics
+    /// Panics if this frame contains a single row.
     #[inline(always)]
     pub fn next(&self) -> &[E] {
-        &self.next
+        &self.rows[1]
     }
 
     /// Returns a mutable reference to the next row.
+    ///
+    /// # Panics
+    /// Panics if this frame contains a single row.
     #[inline(always)]
     pub fn next_mut(&mut self) -> &mut [E] {
-        &mut self.next
+        &mut self.rows[1]
+    }
+
+    /// Returns a reference to the row at the specified index in this frame.
+    ///
+    /// # Panics
+    /// Panics if `idx` is greater than or equal to the number of rows in this frame.
+    #[inline(always)]
+    pub fn row(&self, idx: usize) -> &[E] {
+        &self.rows[idx]
+    }
+
+    /// Returns a mutable reference to the row at the specified index in this frame.
+    ///
+    /// # Panics
+    /// Panics if `idx` is greater than or equal to the number of rows in this frame.
+    #[inline(always)]
+    pub fn row_mut(&mut self, idx: usize) -> &mut [E] {
+        &mut self.rows[idx]
+    }
+
+    /// Returns an iterator over the rows of this frame.
+    pub fn rows(&self) -> impl Iterator<Item = &[E]> {
+        self.rows.iter().map(|row| row.as_slice())
     }
 }
@@ -4,7 +4,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{AirContext, BTreeMap, ConstraintDivisor, ExtensionOf, FieldElement, Vec};
+use super::{AirContext, BTreeMap, ConstraintDivisor, ExtensionOf, FieldElement, StarkField, Vec};
 
 mod frame;
 pub use frame::EvaluationFrame;
@@ -12,6 +12,15 @@ pub use frame::EvaluationFrame;
 mod degree;
 pub use degree::TransitionConstraintDegree;
 
+mod divisor;
+pub use divisor::TransitionDivisor;
+
+mod selector;
+pub use selector::SelectorColumn;
+
+mod evaluator;
+pub use evaluator::TransitionEvaluator;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -24,15 +33,15 @@ const MIN_CYCLE_LENGTH: usize = 2;
 /// This metadata includes:
 /// - List of transition constraint degrees for the main trace segment, as well as for auxiliary
 ///   trace segments (if any).
-/// - Groupings of constraints by their degree, separately for the main trace segment and for
-///   auxiliary tace segment.
-/// - Divisor of transition constraints for a computation.
+/// - Groupings of constraints by their degree and divisor, separately for the main trace segment
+///   and for auxiliary tace segment.
+/// - Divisors of transition constraints for a computation.
 pub struct TransitionConstraints<E: FieldElement> {
     main_constraints: Vec<TransitionConstraintGroup<E>>,
     main_constraint_degrees: Vec<TransitionConstraintDegree>,
     aux_constraints: Vec<TransitionConstraintGroup<E>>,
     aux_constraint_degrees: Vec<TransitionConstraintDegree>,
-    divisor: ConstraintDivisor<E::BaseField>,
+    divisors: Vec<ConstraintDivisor<E::BaseField>>,
 }
 
 impl<E: FieldElement> TransitionConstraints<E> {
@@ -51,31 +60,46 @@ impl<E: FieldElement> TransitionConstraints<E> {
             "number of transition constraints must match the number of composition coefficient tuples"
         );
 
-        // build constraint divisor; the same divisor applies to all transition constraints
-        let divisor = ConstraintDivisor::from_transition(
+        // collect divisor descriptors of all transition constraints; constraints against the main
+        // trace segment come first, followed by constraints against auxiliary trace segments
+        let descriptors = (0..context.num_transition_constraints())
+            .map(|i| context.get_transition_divisor(i))
+            .collect::<Vec<_>>();
+
+        // build shared constraint divisors; constraints which must hold on the same coset of the
+        // trace domain (i.e., have the same period and offset) share a divisor. the divisor is
+        // exempt only on the steps exempted for all of these constraints, and constraints with
+        // additional exemptions account for them via exemption multipliers. constraints which
+        // must hold on the same interval of steps share a divisor as well.
+        let divisors = build_shared_divisors(
+            &descriptors,
             context.trace_len(),
-            context.num_transition_exemptions(),
+            context.options.trace_domain_offset(),
         );
 
-        // group constraints by their degree, separately for constraints against main and auxiliary
-        // trace segments
+        // group constraints by their degree and divisor, separately for constraints against main
+        // and auxiliary trace segments
 
+        let num_main_constraints = context.main_transition_constraint_degrees.len();
         let (main_constraint_coefficients, aux_constraint_coefficients) =
-            composition_coefficients.split_at(context.main_transition_constraint_degrees.len());
+            composition_coefficients.split_at(num_main_constraints);
+        let (main_descriptors, aux_descriptors) = descriptors.split_at(num_main_constraints);
 
         let main_constraint_degrees = context.main_transition_constraint_degrees.clone();
         let main_constraints = group_constraints(
             &main_constraint_degrees,
+            main_descriptors,
             context,
             main_constraint_coefficients,
-            divisor.degree(),
+            &divisors,
         );
         let aux_constraint_degrees = context.aux_transition_constraint_degrees.clone();
         let aux_constraints = group_constraints(
             &aux_constraint_degrees,
+            aux_descriptors,
             context,
             aux_constraint_coefficients,
-            divisor.degree(),
+            &divisors,
         );
 
         Self {
@@ -83,7 +107,7 @@ impl<E: FieldElement> TransitionConstraints<E> {
             main_constraint_degrees,
             aux_constraints,
             aux_constraint_degrees,
-            divisor,
+            divisors: divisors.into_iter().map(|(_, divisor)| divisor).collect(),
         }
     }
 
@@ -132,26 +156,31 @@ impl<E: FieldElement> TransitionConstraints<E> {
         self.aux_constraint_degrees.len()
     }
 
-    /// Returns a divisor for transition constraints.
+    /// Returns divisors shared by transition constraints.
     ///
-    /// All transition constraints have the same divisor which has the form:
+    /// Transition constraints which must hold on the same coset of the trace domain share a
+    /// divisor of the form:
     /// $$
-    /// z(x) = \frac{x^n - 1}{x - g^{n - 1}}
+    /// z(x) = \frac{x^{n / p} - g^{o \cdot n / p}}{\prod_{e \in E} (x - g^e)}
     /// $$
-    /// where: $n$ is the length of the execution trace and $g$ is the generator of the trace
-    /// domain.
+    /// where: $n$ is the length of the execution trace, $g$ is the generator of the trace domain,
+    /// $p$ and $o$ are the period and the offset of the coset, and $E$ is the set of steps which
+    /// are exempted for all constraints sharing the divisor.
     ///
-    /// This divisor specifies that transition constraints must hold on all steps of the
-    /// execution trace except for the last one.
-    pub fn divisor(&self) -> &ConstraintDivisor<E::BaseField> {
-        &self.divisor
+    /// By default, all transition constraints share a single divisor specifying that the
+    /// constraints must hold on all steps of the execution trace except for the last $k$ steps.
+    /// Constraint groups with additional exemption points account for them via
+    /// [TransitionConstraintGroup::evaluate_exemption_multiplier_at()], and refer to their shared
+    /// divisor via [TransitionConstraintGroup::divisor_idx()].
+    pub fn divisors(&self) -> &[ConstraintDivisor<E::BaseField>] {
+        &self.divisors
     }
 
     // CONSTRAINT COMPOSITION
     // --------------------------------------------------------------------------------------------
 
     /// Computes a linear combination of all transition constraint evaluations and divides the
-    /// result by transition constraint divisor.
+    /// result by transition constraint divisors.
     ///
     /// A transition constraint is described by a rational function of the form $\frac{C(x)}{z(x)}$,
     /// where:
@@ -161,38 +190,48 @@ impl<E: FieldElement> TransitionConstraints<E> {
     /// Thus, this function computes a linear combination of $C(x)$ evaluations. For more detail on
     /// how this linear combination is computed refer to [TransitionConstraintGroup::merge_evaluations].
     ///
-    /// Since, the divisor polynomial is the same for all transition constraints (see
-    /// [ConstraintDivisor::from_transition]), we can divide the linear combination by the
-    /// divisor rather than dividing each individual $C(x)$ evaluation. This requires executing only
-    /// one division at the end.
+    /// Since divisor polynomials are shared by groups of transition constraints (see
+    /// [TransitionConstraints::divisors()]), we can divide the linear combination of each group
+    /// of constraints by their shared divisor rather than dividing each individual $C(x)$
+    /// evaluation. This requires executing only one division per shared divisor. Evaluations of
+    /// constraint groups which are exempt on more steps than their shared divisor are multiplied
+    /// by the exemption multiplier of the group before they are added to the linear combination.
     pub fn combine_evaluations<F>(&self, main_evaluations: &[F], aux_evaluations: &[E], x: F) -> E
     where
         F: FieldElement<BaseField = E::BaseField>,
         E: ExtensionOf<F>,
     {
+        let mut results = vec![E::ZERO; self.divisors.len()];
+
         // merge constraint evaluations for the main trace segment
-        let mut result = self.main_constraints().iter().fold(E::ZERO, |acc, group| {
+        for group in self.main_constraints().iter() {
             let xp = x.exp(group.degree_adjustment.into());
-            acc + group.merge_evaluations::<F, F>(main_evaluations, xp)
-        });
+            let evaluation = group.merge_evaluations::<F, F>(main_evaluations, xp);
+            results[group.divisor_idx] +=
+                evaluation.mul_base(group.evaluate_exemption_multiplier_at(x));
+        }
 
         // merge constraint evaluations for auxiliary trace segments (if any)
-        if self.num_aux_constraints() > 0 {
-            result += self.aux_constraints().iter().fold(E::ZERO, |acc, group| {
-                let xp = x.exp(group.degree_adjustment.into());
-                acc + group.merge_evaluations::<F, E>(aux_evaluations, xp)
-            });
+        for group in self.aux_constraints().iter() {
+            let xp = x.exp(group.degree_adjustment.into());
+            let evaluation = group.merge_evaluations::<F, E>(aux_evaluations, xp);
+            results[group.divisor_idx] +=
+                evaluation.mul_base(group.evaluate_exemption_multiplier_at(x));
         }
 
-        // divide out the evaluation of divisor at x and return the result
-        let z = E::from(self.divisor.evaluate_at(x));
-        result / z
+        // divide out the evaluations of divisors at x and return the sum of the results
+        results
+            .into_iter()
+            .zip(self.divisors.iter())
+            .fold(E::ZERO, |acc, (result, divisor)| {
+                acc + result / E::from(divisor.evaluate_at(x))
+            })
     }
 }
 
 // TRANSITION CONSTRAINT GROUP
 // ================================================================================================
-/// A group of transition constraints all having the same degree.
+/// A group of transition constraints all having the same degree and the same divisor.
 ///
 /// A transition constraint group does not actually store transition constraints - it stores only
 /// their indexes and the info needed to compute their random linear combination. The indexes are
@@ -204,6 +243,9 @@ pub struct TransitionConstraintGroup<E: FieldElement> {
     degree: TransitionConstraintDegree,
     degree_adjustment: u64,
     domain_offset_exp: E::BaseField,
+    divisor: ConstraintDivisor<E::BaseField>,
+    divisor_idx: usize,
+    extra_exemptions: Vec<E::BaseField>,
     indexes: Vec<usize>,
     coefficients: Vec<(E, E)>,
 }
@@ -211,28 +253,46 @@ pub struct TransitionConstraintGroup<E: FieldElement> {
 impl<E: FieldElement> TransitionConstraintGroup<E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Returns a new transition constraint group to hold constraints of the specified degree.
+    /// Returns a new transition constraint group to hold constraints of the specified degree and
+    /// divisor; `evaluation_degree` is the degree of these constraints when they are evaluated
+    /// over trace polynomials.
+    ///
+    /// The `divisor` of the group must vanish on a subset of the points on which the
+    /// `shared_divisor` vanishes; `divisor_idx` is the index of the `shared_divisor` in the list
+    /// of divisors shared by transition constraints.
     pub(super) fn new(
         degree: TransitionConstraintDegree,
-        trace_length: usize,
+        evaluation_degree: usize,
         composition_degree: usize,
-        divisor_degree: usize,
+        divisor: ConstraintDivisor<E::BaseField>,
+        divisor_idx: usize,
+        shared_divisor: &ConstraintDivisor<E::BaseField>,
         domain_offset: E::BaseField,
     ) -> Self {
         // We want to make sure that once we divide a constraint polynomial by its divisor, the
         // degree of the resulting polynomial will be exactly equal to the composition_degree.
-        let target_degree = composition_degree + divisor_degree;
-        let evaluation_degree = degree.get_evaluation_degree(trace_length);
+        let target_degree = composition_degree + divisor.degree();
         let degree_adjustment = (target_degree - evaluation_degree) as u64;
 
         // pre-compute domain offset exponent; this is used only by the prover and is not relevant
         // for the verifier
         let domain_offset_exp = domain_offset.exp(degree_adjustment.into());
 
+        // exemption points of this group which are not exemption points of the shared divisor
+        let extra_exemptions = divisor
+            .exemptions()
+            .iter()
+            .filter(|point| !shared_divisor.exemptions().contains(point))
+            .copied()
+            .collect();
+
         TransitionConstraintGroup {
             degree,
             degree_adjustment,
             domain_offset_exp,
+            divisor,
+            divisor_idx,
+            extra_exemptions,
             indexes: vec![],
             coefficients: vec![],
         }
@@ -261,6 +321,29 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
         self.domain_offset_exp
     }
 
+    /// Returns the divisor of constraints in this group.
+    pub fn divisor(&self) -> &ConstraintDivisor<E::BaseField> {
+        &self.divisor
+    }
+
+    /// Returns the index of the shared divisor of this group in the list returned by
+    /// [TransitionConstraints::divisors()].
+    pub fn divisor_idx(&self) -> usize {
+        self.divisor_idx
+    }
+
+    /// Returns exemption points of this group which are not exemption points of the shared
+    /// divisor of this group.
+    pub fn extra_exemptions(&self) -> &[E::BaseField] {
+        &self.extra_exemptions
+    }
+
+    /// Returns the number of steps on which constraints in this group are exempt in addition to
+    /// the steps exempted by the shared divisor of this group.
+    pub fn num_extra_exemptions(&self) -> usize {
+        self.extra_exemptions.len()
+    }
+
     /// Adds a new constraint to the group. The constraint is identified by an index in the
     /// evaluation table.
     pub fn add(&mut self, constraint_idx: usize, coefficients: (E, E)) {
@@ -310,32 +393,120 @@ impl<E: FieldElement> TransitionConstraintGroup<E> {
         }
         result
     }
+
+    /// Evaluates the exemption multiplier of this group at the specified `x`.
+    ///
+    /// The exemption multiplier is a polynomial $\prod_{i}(x - e_i)$, where $e_i$ are exemption
+    /// points of this group's divisor which are not exemption points of the shared divisor of this
+    /// group. Multiplying constraint evaluations by this polynomial and then
+    /// dividing them by the shared divisor is equivalent to dividing them by the divisor of this
+    /// group.
+    ///
+    /// If constraints in this group have no extra exemptions, this returns ONE.
+    pub fn evaluate_exemption_multiplier_at<B>(&self, x: B) -> B
+    where
+        B: FieldElement<BaseField = E::BaseField>,
+    {
+        self.extra_exemptions
+            .iter()
+            .fold(B::ONE, |acc, &point| acc * (x - B::from(point)))
+    }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
-/// Groups transition constraints by their degree.
+/// Builds divisors shared by transition constraints with the specified divisor descriptors.
+///
+/// A divisor is built for each distinct combination of period, offset, and interval; it is
+/// exempt only on the steps which are exempted by all descriptors with this combination. The
+/// returned divisors are paired with their keys (see [get_shared_divisor_key()]), and are moved
+/// onto the coset of the trace domain defined by `trace_domain_offset`.
+fn build_shared_divisors<B: StarkField>(
+    descriptors: &[TransitionDivisor],
+    trace_length: usize,
+    trace_domain_offset: B,
+) -> Vec<(SharedDivisorKey, ConstraintDivisor<B>)> {
+    let mut cosets: BTreeMap<SharedDivisorKey, Vec<usize>> = BTreeMap::new();
+    for descriptor in descriptors {
+        cosets
+            .entry(get_shared_divisor_key(descriptor))
+            .and_modify(|exemptions| {
+                exemptions.retain(|step| descriptor.exemptions().contains(step))
+            })
+            .or_insert_with(|| descriptor.exemptions().to_vec());
+    }
+
+    cosets
+        .into_iter()
+        .map(|((period, offset, interval), exemptions)| {
+            let divisor = match interval {
+                Some((start, end)) => {
+                    ConstraintDivisor::from_transition_interval(trace_length, start, end)
+                }
+                None => ConstraintDivisor::from_transition_coset(
+                    trace_length,
+                    period,
+                    offset,
+                    &exemptions,
+                ),
+            };
+            let key = (period, offset, interval);
+            (key, divisor.with_trace_domain_offset(trace_domain_offset))
+        })
+        .collect()
+}
+
+/// Key identifying a divisor shared by transition constraints: a tuple of period, offset, and
+/// interval of the divisor descriptors.
+type SharedDivisorKey = (usize, usize, Option<(usize, usize)>);
+
+/// Returns the key of the shared divisor for constraints with the specified divisor descriptor.
+fn get_shared_divisor_key(descriptor: &TransitionDivisor) -> SharedDivisorKey {
+    (
+        descriptor.period(),
+        descriptor.offset(),
+        descriptor.interval(),
+    )
+}
+
+/// Groups transition constraints by their degree and divisor.
+///
+/// `descriptors` must contain divisor descriptors for the constraints described by `degrees`, and
+/// `shared_divisors` must contain divisors built by [build_shared_divisors()] for all transition
+/// constraints of the computation.
 fn group_constraints<E: FieldElement>(
     degrees: &[TransitionConstraintDegree],
+    descriptors: &[TransitionDivisor],
     context: &AirContext<E::BaseField>,
     coefficients: &[(E, E)],
-    divisor_degree: usize,
+    shared_divisors: &[(SharedDivisorKey, ConstraintDivisor<E::BaseField>)],
 ) -> Vec<TransitionConstraintGroup<E>> {
     // iterate over transition constraint degrees, and assign each constraint to the appropriate
-    // group based on its degree
+    // group based on its degree and divisor
     let mut groups = BTreeMap::new();
-    for (i, degree) in degrees.iter().enumerate() {
-        let evaluation_degree = degree.get_evaluation_degree(context.trace_len());
-        let group = groups.entry(evaluation_degree).or_insert_with(|| {
-            TransitionConstraintGroup::new(
-                degree.clone(),
-                context.trace_len(),
-                context.composition_degree(),
-                divisor_degree,
-                context.options.domain_offset(),
-            )
-        });
+    for (i, (degree, descriptor)) in degrees.iter().zip(descriptors).enumerate() {
+        let evaluation_degree = context.get_evaluation_degree(degree);
+        let group = groups
+            .entry((evaluation_degree, descriptor.clone()))
+            .or_insert_with(|| {
+                let shared_key = get_shared_divisor_key(descriptor);
+                let divisor_idx = shared_divisors
+                    .iter()
+                    .position(|(key, _)| *key == shared_key)
+                    .expect("shared divisor not found");
+                TransitionConstraintGroup::new(
+                    degree.clone(),
+                    evaluation_degree,
+                    context.composition_degree(),
+                    descriptor
+                        .to_constraint_divisor(context.trace_len())
+                        .with_trace_domain_offset(context.options.trace_domain_offset()),
+                    divisor_idx,
+                    &shared_divisors[divisor_idx].1,
+                    context.options.domain_offset(),
+                )
+            });
         group.add(i, coefficients[i]);
     }
 
@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{FieldElement, StarkField, TransitionConstraintDegree, MIN_CYCLE_LENGTH};
+
+// SELECTOR COLUMN
+// ================================================================================================
+/// A periodic column with binary values used to switch transition constraints on and off.
+///
+/// Constraints of the form $s(x) \cdot C(x)$, where $s(x)$ is a selector column, are enforced
+/// only on the steps at which the selector is one. Multiplying a constraint by a periodic column
+/// increases its degree, and this needs to be reflected in the degree descriptor of the
+/// constraint. A selector takes care of this: it is declared once from the values of a periodic
+/// column returned by [Air::get_periodic_column_values()](crate::Air::get_periodic_column_values),
+/// and is then used both to build degree descriptors of the constraints it wraps (via
+/// [constraint_degree()](SelectorColumn::constraint_degree)), and to apply the selector to
+/// constraint evaluations (via [apply()](SelectorColumn::apply)).
+///
+/// Constraints wrapped by selectors should also be registered with the context of the
+/// computation via [set_transition_selectors()](crate::AirContext::set_transition_selectors) to
+/// make sure their declared degrees account for the selectors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelectorColumn {
+    column_idx: usize,
+    cycle_length: usize,
+}
+
+impl SelectorColumn {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new selector for the periodic column at the specified index with the specified
+    /// values.
+    ///
+    /// The `column_idx` is the index of the column in the vector returned by
+    /// [Air::get_periodic_column_values()](crate::Air::get_periodic_column_values), and thus,
+    /// also the index of the column value in the `periodic_values` slice passed to
+    /// [Air::evaluate_transition()](crate::Air::evaluate_transition).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The number of values is smaller than two or is not a power of two.
+    /// * Any of the values is not zero or one.
+    pub fn new<B: StarkField>(column_idx: usize, values: &[B]) -> Self {
+        let cycle_length = values.len();
+        assert!(
+            cycle_length >= MIN_CYCLE_LENGTH,
+            "number of values in a selector column must be at least {MIN_CYCLE_LENGTH}, but was {cycle_length}"
+        );
+        assert!(
+            cycle_length.is_power_of_two(),
+            "number of values in a selector column must be a power of two, but was {cycle_length}"
+        );
+        for (i, &value) in values.iter().enumerate() {
+            assert!(
+                value == B::ZERO || value == B::ONE,
+                "values in a selector column must be binary, but value at position {i} was {value}"
+            );
+        }
+        Self {
+            column_idx,
+            cycle_length,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the periodic column of this selector.
+    pub fn column_idx(&self) -> usize {
+        self.column_idx
+    }
+
+    /// Returns the number of values in the periodic column of this selector.
+    pub fn cycle_length(&self) -> usize {
+        self.cycle_length
+    }
+
+    // CONSTRAINT HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the degree descriptor of a constraint with the specified `degree` after it is
+    /// multiplied by this selector.
+    ///
+    /// For example, for a constraint which multiplies three trace columns and is wrapped by a
+    /// selector with a period of 32 steps, this returns `base: 3, cycles: [32]`.
+    pub fn constraint_degree(
+        &self,
+        degree: TransitionConstraintDegree,
+    ) -> TransitionConstraintDegree {
+        degree.with_cycle(self.cycle_length)
+    }
+
+    /// Returns the value of this selector at the current step.
+    ///
+    /// # Panics
+    /// Panics if `periodic_values` does not contain a value for the column of this selector.
+    pub fn value<E: FieldElement>(&self, periodic_values: &[E]) -> E {
+        periodic_values[self.column_idx]
+    }
+
+    /// Returns the specified constraint `evaluation` multiplied by the value of this selector
+    /// at the current step.
+    ///
+    /// # Panics
+    /// Panics if `periodic_values` does not contain a value for the column of this selector.
+    pub fn apply<E: FieldElement>(&self, periodic_values: &[E], evaluation: E) -> E {
+        evaluation * self.value(periodic_values)
+    }
+}
@@ -5,6 +5,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use core::fmt;
+use utils::string::String;
 
 // ASSERTION ERROR
 // ================================================================================================
@@ -44,3 +45,97 @@ impl fmt::Display for AssertionError {
         }
     }
 }
+
+// PROOF OPTIONS ERROR
+// ================================================================================================
+/// Represents an error returned when constructing [ProofOptions](crate::ProofOptions) from an
+/// invalid combination of parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofOptionsError {
+    /// This error occurs when the number of queries is zero.
+    NumQueriesTooSmall(usize),
+    /// This error occurs when the number of queries is greater than 128.
+    NumQueriesTooLarge(usize),
+    /// This error occurs when the blowup factor is not a power of two.
+    BlowupFactorNotPowerOfTwo(usize),
+    /// This error occurs when the blowup factor is smaller than 2.
+    BlowupFactorTooSmall(usize),
+    /// This error occurs when the blowup factor is greater than 128.
+    BlowupFactorTooLarge(usize),
+    /// This error occurs when the grinding factor is greater than 32.
+    GrindingFactorTooLarge(u32),
+    /// This error occurs when the FRI folding factor is not 2, 4, 8, or 16.
+    UnsupportedFriFoldingFactor(usize),
+    /// This error occurs when the FRI max remainder size is not a power of two.
+    FriMaxRemainderSizeNotPowerOfTwo(usize),
+    /// This error occurs when the FRI max remainder size is smaller than 32.
+    FriMaxRemainderSizeTooSmall(usize),
+    /// This error occurs when the FRI max remainder size is greater than 1024.
+    FriMaxRemainderSizeTooLarge(usize),
+    /// This error occurs when the conjectured security level implied by the number of queries,
+    /// the blowup factor, and the grinding factor is below the minimum accepted security level,
+    /// and insecure options were not explicitly allowed.
+    InsufficientSecurity(u32, u32),
+    /// This error occurs when the offset of the trace domain is zero.
+    ZeroTraceDomainOffset,
+    /// This error occurs when the offset of the trace domain places the trace domain inside the
+    /// low-degree extension domain.
+    TraceDomainOffsetInLdeDomain,
+    /// This error occurs when the minimum field security is greater than 255 bits.
+    MinFieldSecurityTooLarge(u32),
+    /// This error occurs when a string does not name any of the supported field extensions.
+    UnknownFieldExtension(String),
+}
+
+impl fmt::Display for ProofOptionsError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NumQueriesTooSmall(actual) => {
+                write!(f, "number of queries must be greater than 0, but was {actual}")
+            }
+            Self::NumQueriesTooLarge(actual) => {
+                write!(f, "number of queries cannot be greater than 128, but was {actual}")
+            }
+            Self::BlowupFactorNotPowerOfTwo(actual) => {
+                write!(f, "blowup factor must be a power of 2, but was {actual}")
+            }
+            Self::BlowupFactorTooSmall(actual) => {
+                write!(f, "blowup factor cannot be smaller than 2, but was {actual}")
+            }
+            Self::BlowupFactorTooLarge(actual) => {
+                write!(f, "blowup factor cannot be greater than 128, but was {actual}")
+            }
+            Self::GrindingFactorTooLarge(actual) => {
+                write!(f, "grinding factor cannot be greater than 32, but was {actual}")
+            }
+            Self::UnsupportedFriFoldingFactor(actual) => {
+                write!(f, "FRI folding factor must be 2, 4, 8, or 16, but was {actual}")
+            }
+            Self::FriMaxRemainderSizeNotPowerOfTwo(actual) => {
+                write!(f, "FRI max remainder size must be a power of 2, but was {actual}")
+            }
+            Self::FriMaxRemainderSizeTooSmall(actual) => {
+                write!(f, "FRI max remainder size cannot be smaller than 32, but was {actual}")
+            }
+            Self::FriMaxRemainderSizeTooLarge(actual) => {
+                write!(f, "FRI max remainder size cannot be greater than 1024, but was {actual}")
+            }
+            Self::InsufficientSecurity(actual, min) => {
+                write!(f, "proof options provide only {actual} bits of conjectured security, but at least {min} bits are required")
+            }
+            Self::ZeroTraceDomainOffset => {
+                write!(f, "trace domain offset cannot be zero")
+            }
+            Self::TraceDomainOffsetInLdeDomain => {
+                write!(f, "trace domain offset places the trace domain inside the LDE domain")
+            }
+            Self::MinFieldSecurityTooLarge(actual) => {
+                write!(f, "minimum field security cannot be greater than 255 bits, but was {actual}")
+            }
+            Self::UnknownFieldExtension(value) => {
+                write!(f, "'{value}' is not a valid field extension; accepted values are none, quadratic, cubic, and sextic (or 1, 2, 3, and 6)")
+            }
+        }
+    }
+}
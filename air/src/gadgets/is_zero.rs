@@ -0,0 +1,82 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    not, EvaluationFrame, FieldElement, Gadget, StarkField, TransitionConstraintDegree, Vec,
+};
+
+// IS ZERO GADGET
+// ================================================================================================
+/// Computes a binary flag indicating whether a value in the execution trace is zero.
+///
+/// The gadget uses a single auxiliary column holding the inverse of the input value (or zero
+/// when the input value is zero). The flag is then computed as $1 - a \cdot a^{-1}$, and a single
+/// constraint $a \cdot (1 - a \cdot a^{-1}) = 0$ guarantees that the flag is one if and only if
+/// $a$ is zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsZeroGadget {
+    input: usize,
+    inverse: usize,
+}
+
+impl IsZeroGadget {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new gadget checking whether the value in the `input` column is zero, and using
+    /// the `inverse` column to hold the inverse of the input value.
+    ///
+    /// # Panics
+    /// Panics if `input` and `inverse` refer to the same column.
+    pub fn new(input: usize, inverse: usize) -> Self {
+        assert_ne!(
+            input, inverse,
+            "input and inverse columns must be distinct, but both were {input}"
+        );
+        IsZeroGadget { input, inverse }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the column containing the value being checked.
+    pub fn input(&self) -> usize {
+        self.input
+    }
+
+    /// Returns the index of the auxiliary column containing the inverse of the input value.
+    pub fn inverse(&self) -> usize {
+        self.inverse
+    }
+
+    /// Returns an expression which evaluates to one when the input value in the provided row is
+    /// zero, and to zero otherwise; the degree of this expression is 2.
+    ///
+    /// The expression is guaranteed to be binary only when constraints of this gadget hold.
+    pub fn output<E: FieldElement>(&self, row: &[E]) -> E {
+        not(row[self.input] * row[self.inverse])
+    }
+}
+
+impl Gadget for IsZeroGadget {
+    fn num_aux_columns(&self) -> usize {
+        1
+    }
+
+    fn constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        vec![TransitionConstraintDegree::new(3)]
+    }
+
+    fn fill<B: StarkField>(&self, row: &mut [B]) {
+        // inverse of zero is defined to be zero
+        row[self.inverse] = row[self.input].inv();
+    }
+
+    fn evaluate<E: FieldElement>(&self, frame: &EvaluationFrame<E>, result: &mut [E]) {
+        let current = frame.current();
+        result[0] = current[self.input] * self.output(current);
+    }
+}
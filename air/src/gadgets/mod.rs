@@ -0,0 +1,141 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Reusable building blocks for writing AIR constraints.
+//!
+//! This module contains the following kinds of helpers:
+//! * Plain constraint evaluation helpers (e.g., [are_equal()], [is_binary()], [select()]), which
+//!   build expressions that evaluate to zero when some relation between values holds.
+//! * Gadgets implementing the [Gadget] trait. A gadget occupies a set of auxiliary columns in the
+//!   execution trace, knows how to fill these columns during trace construction, and how to
+//!   evaluate the constraints which tie these columns to the gadget's input columns.
+//! * A [PermutationArgument], which uses a column in the auxiliary trace segment and random
+//!   elements drawn for this segment to enforce that two groups of main trace columns are
+//!   permutations of each other.
+//! * A [RangeLookupArgument], which batches range checks of main trace columns into a single
+//!   lookup against a periodic table of all $k$-bit values, splitting wider values into limbs.
+//!
+//! All gadgets enforce their constraints against the current row of an [EvaluationFrame]. Thus,
+//! when gadget constraints are included into a set of transition constraints, they are subject to
+//! the same exemptions as all other transition constraints (by default, the last row of the
+//! execution trace is not constrained).
+
+use crate::{EvaluationFrame, TransitionConstraintDegree};
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+mod is_zero;
+pub use is_zero::IsZeroGadget;
+
+mod select;
+pub use select::SelectGadget;
+
+mod range_check;
+pub use range_check::RangeCheckGadget;
+
+mod permutation;
+pub use permutation::PermutationArgument;
+
+mod range_lookup;
+pub use range_lookup::{RangeLookupArgument, MAX_LIMB_BITS};
+
+#[cfg(test)]
+mod tests;
+
+// GADGET TRAIT
+// ================================================================================================
+/// Describes a reusable set of constraints together with the auxiliary trace columns needed to
+/// enforce them.
+///
+/// Columns of a gadget are specified as indexes into a row of the execution trace. Input columns
+/// are expected to be filled by the user, while auxiliary columns are filled by
+/// [Gadget::fill()] once the input columns of a row have been set.
+pub trait Gadget {
+    /// Returns the number of auxiliary columns this gadget requires in the execution trace.
+    fn num_aux_columns(&self) -> usize;
+
+    /// Returns the degrees of constraints enforced by this gadget in the order in which they are
+    /// written by [Gadget::evaluate()].
+    fn constraint_degrees(&self) -> Vec<TransitionConstraintDegree>;
+
+    /// Returns the number of constraints enforced by this gadget.
+    fn num_constraints(&self) -> usize {
+        self.constraint_degrees().len()
+    }
+
+    /// Fills auxiliary columns of this gadget in the provided row of the execution trace based on
+    /// values already present in the input columns of this gadget.
+    fn fill<B: StarkField>(&self, row: &mut [B]);
+
+    /// Evaluates constraints of this gadget against the current row of the provided evaluation
+    /// frame and writes the results into the first [Gadget::num_constraints()] slots of `result`.
+    fn evaluate<E: FieldElement>(&self, frame: &EvaluationFrame<E>, result: &mut [E]);
+}
+
+// CONSTRAINT EVALUATION HELPERS
+// ================================================================================================
+
+/// Returns zero only when a == b.
+pub fn are_equal<E: FieldElement>(a: E, b: E) -> E {
+    a - b
+}
+
+/// Returns zero only when a == zero.
+pub fn is_zero<E: FieldElement>(a: E) -> E {
+    a
+}
+
+/// Returns zero only when a = zero || a == one.
+pub fn is_binary<E: FieldElement>(a: E) -> E {
+    a * a - a
+}
+
+/// Return zero when a == one, and one when a == zero;
+/// assumes that a is a binary value.
+pub fn not<E: FieldElement>(a: E) -> E {
+    E::ONE - a
+}
+
+/// Returns a when flag == one, and b when flag == zero;
+/// assumes that flag is a binary value.
+pub fn select<E: FieldElement>(flag: E, a: E, b: E) -> E {
+    flag * a + not(flag) * b
+}
+
+// TRAIT TO SIMPLIFY CONSTRAINT AGGREGATION
+// ================================================================================================
+
+/// Simplifies accumulation of constraint evaluations which are enabled by a selector flag.
+pub trait EvaluationResult<E> {
+    /// Adds `value` multiplied by `flag` to the constraint evaluation at the specified index.
+    fn agg_constraint(&mut self, index: usize, flag: E, value: E);
+
+    /// Adds each of the `values` multiplied by `flag` to the constraint evaluations starting at
+    /// the specified index.
+    fn agg_constraints(&mut self, index: usize, flag: E, values: &[E]);
+}
+
+impl<E: FieldElement> EvaluationResult<E> for [E] {
+    fn agg_constraint(&mut self, index: usize, flag: E, value: E) {
+        self[index] += flag * value;
+    }
+
+    fn agg_constraints(&mut self, index: usize, flag: E, values: &[E]) {
+        for (result, &value) in self[index..index + values.len()].iter_mut().zip(values) {
+            *result += flag * value;
+        }
+    }
+}
+
+impl<E: FieldElement> EvaluationResult<E> for Vec<E> {
+    fn agg_constraint(&mut self, index: usize, flag: E, value: E) {
+        self[index] += flag * value;
+    }
+
+    fn agg_constraints(&mut self, index: usize, flag: E, values: &[E]) {
+        self.as_mut_slice().agg_constraints(index, flag, values);
+    }
+}
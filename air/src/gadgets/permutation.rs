@@ -0,0 +1,170 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{are_equal, EvaluationFrame, FieldElement, TransitionConstraintDegree, Vec};
+use crate::{Assertion, AuxTraceRandElements};
+use math::ExtensionOf;
+
+// PERMUTATION ARGUMENT
+// ================================================================================================
+/// Enforces that two groups of columns in the main segment of an execution trace contain the
+/// same multiset of rows.
+///
+/// Given two groups of $k$ main trace columns $a$ and $b$, the argument reduces each row of a
+/// group to a single value using $k + 1$ random elements $\alpha_0, ..., \alpha_{k-1}, \gamma$
+/// drawn for the first auxiliary trace segment:
+///
+/// $$
+/// f_a(i) = \gamma + \sum_{j=0}^{k-1} \alpha_j \cdot a_{i, j}
+/// $$
+///
+/// A single column $z$ in the auxiliary trace segment then holds the running product of
+/// $f_a(i) / f_b(i)$, such that $z_0 = 1$ and $z_{i + 1} \cdot f_b(i) = z_i \cdot f_a(i)$. The
+/// argument is enforced via one transition constraint of degree 2 and two assertions:
+/// $z_0 = 1$ and $z_{n - 1} = 1$.
+///
+/// Since transition constraints are not enforced on the last row of the execution trace, only
+/// rows $0, ..., n - 2$ of the two groups are compared. Rows which should not take part in the
+/// argument can be excluded by setting them to the same values (e.g., zeros) in both groups.
+///
+/// All permutation arguments of a computation may share the same random elements, in which
+/// case the auxiliary segment needs as many random elements as the widest of the arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermutationArgument {
+    lhs: Vec<usize>,
+    rhs: Vec<usize>,
+    column: usize,
+}
+
+impl PermutationArgument {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new argument enforcing that rows of the `lhs` columns are a permutation of rows
+    /// of the `rhs` columns, and using the `column` of the auxiliary trace segment to hold the
+    /// running product.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `lhs` is empty.
+    /// * `lhs` and `rhs` contain a different number of columns.
+    pub fn new(lhs: Vec<usize>, rhs: Vec<usize>, column: usize) -> Self {
+        assert!(
+            !lhs.is_empty(),
+            "permutation argument must cover at least one column"
+        );
+        assert_eq!(
+            lhs.len(),
+            rhs.len(),
+            "column groups must have the same number of columns, but were {} and {}",
+            lhs.len(),
+            rhs.len()
+        );
+        PermutationArgument { lhs, rhs, column }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns indexes of main trace columns on the left-hand side of this argument.
+    pub fn lhs(&self) -> &[usize] {
+        &self.lhs
+    }
+
+    /// Returns indexes of main trace columns on the right-hand side of this argument.
+    pub fn rhs(&self) -> &[usize] {
+        &self.rhs
+    }
+
+    /// Returns the index of the auxiliary trace column holding the running product.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the number of random elements consumed by this argument.
+    pub fn num_rand_elements(&self) -> usize {
+        self.lhs.len() + 1
+    }
+
+    /// Returns the degree of the transition constraint enforced by this argument.
+    pub fn constraint_degree(&self) -> TransitionConstraintDegree {
+        TransitionConstraintDegree::new(2)
+    }
+
+    // REDUCTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Reduces the left-hand side columns of the provided main trace row to a single value
+    /// using the provided random elements.
+    pub fn reduce_lhs<F, E>(&self, row: &[F], rand_elements: &[E]) -> E
+    where
+        F: FieldElement,
+        E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    {
+        self.reduce(&self.lhs, row, rand_elements)
+    }
+
+    /// Reduces the right-hand side columns of the provided main trace row to a single value
+    /// using the provided random elements.
+    pub fn reduce_rhs<F, E>(&self, row: &[F], rand_elements: &[E]) -> E
+    where
+        F: FieldElement,
+        E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    {
+        self.reduce(&self.rhs, row, rand_elements)
+    }
+
+    // CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the transition constraint of this argument against the provided frames of the
+    /// main and the auxiliary trace segments.
+    pub fn evaluate_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> E
+    where
+        F: FieldElement,
+        E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    {
+        let rand_elements = aux_rand_elements.get_segment_elements(0);
+        let lhs = self.reduce_lhs(main_frame.current(), rand_elements);
+        let rhs = self.reduce_rhs(main_frame.current(), rand_elements);
+
+        are_equal(
+            aux_frame.next()[self.column] * rhs,
+            aux_frame.current()[self.column] * lhs,
+        )
+    }
+
+    /// Returns assertions that the running product column of this argument starts and ends
+    /// with one in an execution trace of the specified length.
+    pub fn get_assertions<E: FieldElement>(&self, trace_length: usize) -> Vec<Assertion<E>> {
+        vec![
+            Assertion::single(self.column, 0, E::ONE),
+            Assertion::single(self.column, trace_length - 1, E::ONE),
+        ]
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn reduce<F, E>(&self, columns: &[usize], row: &[F], rand_elements: &[E]) -> E
+    where
+        F: FieldElement,
+        E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    {
+        let num_columns = columns.len();
+        columns
+            .iter()
+            .zip(&rand_elements[..num_columns])
+            .fold(rand_elements[num_columns], |acc, (&column, &alpha)| {
+                acc + alpha.mul_base(row[column])
+            })
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    are_equal, is_binary, EvaluationFrame, FieldElement, Gadget, StarkField,
+    TransitionConstraintDegree, Vec,
+};
+use core::ops::Range;
+
+// RANGE CHECK GADGET
+// ================================================================================================
+/// Checks that a value in the execution trace is smaller than $2^n$ by decomposing it into $n$
+/// bits.
+///
+/// The gadget uses $n$ auxiliary columns holding the bits of the value in little-endian order,
+/// and enforces $n + 1$ constraints:
+/// * the value in each of the bit columns is binary;
+/// * the value in the `value` column is equal to the value recomposed from the bit columns.
+///
+/// The decomposition is sound only when $2^n$ is smaller than the field modulus.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeCheckGadget {
+    value: usize,
+    bits: Range<usize>,
+}
+
+impl RangeCheckGadget {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new gadget checking that the value in the `value` column fits into `num_bits`
+    /// bits, and using `num_bits` consecutive columns starting at `first_bit` to hold the bits of
+    /// the value.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_bits` is zero.
+    /// * The `value` column is one of the bit columns.
+    pub fn new(value: usize, first_bit: usize, num_bits: usize) -> Self {
+        assert!(num_bits > 0, "number of bits must be greater than zero");
+        let bits = first_bit..first_bit + num_bits;
+        assert!(
+            !bits.contains(&value),
+            "value column {value} must not be one of the bit columns {bits:?}"
+        );
+        RangeCheckGadget { value, bits }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the column containing the value being range-checked.
+    pub fn value(&self) -> usize {
+        self.value
+    }
+
+    /// Returns the range of auxiliary columns containing bits of the value in little-endian
+    /// order.
+    pub fn bits(&self) -> Range<usize> {
+        self.bits.clone()
+    }
+
+    /// Returns the number of bits into which the value is decomposed.
+    pub fn num_bits(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Returns the value recomposed from the bit columns of the provided row; the degree of this
+    /// expression is 1.
+    pub fn recompose<E: FieldElement>(&self, row: &[E]) -> E {
+        row[self.bits.clone()]
+            .iter()
+            .rev()
+            .fold(E::ZERO, |acc, &bit| acc.double() + bit)
+    }
+}
+
+impl Gadget for RangeCheckGadget {
+    fn num_aux_columns(&self) -> usize {
+        self.num_bits()
+    }
+
+    fn constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        let mut result = vec![TransitionConstraintDegree::new(2); self.num_bits()];
+        result.push(TransitionConstraintDegree::new(1));
+        result
+    }
+
+    /// Fills the bit columns with the `num_bits` least significant bits of the value.
+    ///
+    /// If the value does not fit into `num_bits` bits, the constraints of this gadget will not
+    /// hold for the resulting row.
+    ///
+    /// # Panics
+    /// Panics if $2^n$ is not smaller than the modulus of the field `B`.
+    fn fill<B: StarkField>(&self, row: &mut [B]) {
+        assert!(
+            (self.num_bits() as u32) < B::MODULUS_BITS,
+            "cannot range-check {} bits in a field with a {}-bit modulus",
+            self.num_bits(),
+            B::MODULUS_BITS
+        );
+        let one = B::Representation::from(1u32);
+        let value = row[self.value].to_repr();
+        for (i, column) in self.bits.clone().enumerate() {
+            row[column] = if (value >> i as u32) & one == one {
+                B::ONE
+            } else {
+                B::ZERO
+            };
+        }
+    }
+
+    fn evaluate<E: FieldElement>(&self, frame: &EvaluationFrame<E>, result: &mut [E]) {
+        let current = frame.current();
+        let num_bits = self.num_bits();
+        for (result, &bit) in result.iter_mut().zip(&current[self.bits.clone()]) {
+            *result = is_binary(bit);
+        }
+        result[num_bits] = are_equal(current[self.value], self.recompose(current));
+    }
+}
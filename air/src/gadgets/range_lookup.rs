@@ -0,0 +1,300 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    are_equal, EvaluationFrame, FieldElement, Gadget, StarkField, TransitionConstraintDegree, Vec,
+};
+use crate::{Assertion, AuxTraceRandElements};
+use core::ops::Range;
+use math::ExtensionOf;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Maximum number of bits in a single limb of a range lookup argument.
+pub const MAX_LIMB_BITS: u32 = 20;
+
+// RANGE LOOKUP ARGUMENT
+// ================================================================================================
+/// Enforces that values in main trace columns fit into a given number of bits by looking them
+/// up in a table of all $k$-bit values.
+///
+/// The table $t$ is a periodic column of length $2^k$ holding values $0, ..., 2^k - 1$ (see
+/// [RangeLookupArgument::table_values()]), and a multiplicity column $m$ in the main trace
+/// segment records how many times each table value is looked up. All range checks requested via
+/// [RangeLookupArgument::range_check()] are batched into a single column $z$ in the auxiliary
+/// trace segment, which accumulates the following running sum using a random element $\alpha$
+/// drawn for the first auxiliary trace segment:
+///
+/// $$
+/// z_{i + 1} - z_i = \sum_{j} \frac{1}{\alpha + v_{i, j}} - \frac{m_i}{\alpha + t_i}
+/// $$
+///
+/// where $v_{i, j}$ are the values looked up in row $i$. The argument is enforced via one
+/// transition constraint (with denominators cleared) and two assertions: $z_0 = 0$ and
+/// $z_{n - 1} = 0$. A running sum is used rather than a running product because a single table
+/// value may be looked up any number of times, and the multiplicities are folded into the sum.
+///
+/// A range check of $b \le k$ bits looks up the value directly; when $b < k$, the value shifted
+/// left by $k - b$ bits is looked up as well. Wider range checks split the value into
+/// $\lceil b / k \rceil$ limbs of $k$ bits each, which are placed into consecutive main trace
+/// columns, range-checked in the same way, and tied to the value via a degree 1 recomposition
+/// constraint enforced through the [Gadget] implementation of this argument.
+///
+/// Since transition constraints are not enforced on the last row of the execution trace, only
+/// rows $0, ..., n - 2$ are range-checked, and the trace must be longer than the table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeLookupArgument {
+    limb_bits: u32,
+    table_column: usize,
+    multiplicity_column: usize,
+    first_limb_column: usize,
+    column: usize,
+    lookups: Vec<(usize, u32)>,
+    decompositions: Vec<(usize, Range<usize>)>,
+}
+
+impl RangeLookupArgument {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new argument looking values up in a table of all `limb_bits`-bit values.
+    ///
+    /// The table is expected in the periodic column at index `table_column`, and multiplicities
+    /// of table values in the `multiplicity_column` of the main trace segment. Limbs of values
+    /// wider than `limb_bits` are placed into consecutive main trace columns starting at
+    /// `first_limb_column`, and the running sum is held in the `column` of the auxiliary trace
+    /// segment.
+    ///
+    /// # Panics
+    /// Panics if `limb_bits` is zero or greater than [MAX_LIMB_BITS].
+    pub fn new(
+        limb_bits: u32,
+        table_column: usize,
+        multiplicity_column: usize,
+        first_limb_column: usize,
+        column: usize,
+    ) -> Self {
+        assert!(
+            limb_bits > 0 && limb_bits <= MAX_LIMB_BITS,
+            "number of limb bits must be between 1 and {MAX_LIMB_BITS}, but was {limb_bits}"
+        );
+        RangeLookupArgument {
+            limb_bits,
+            table_column,
+            multiplicity_column,
+            first_limb_column,
+            column,
+            lookups: Vec::new(),
+            decompositions: Vec::new(),
+        }
+    }
+
+    /// Adds a check that the value in the specified main trace column fits into `num_bits` bits.
+    ///
+    /// If `num_bits` is greater than the number of limb bits of this argument, the next
+    /// $\lceil b / k \rceil$ limb columns are allocated for the value.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `num_bits` is zero.
+    /// * The `column` is one of the limb columns of this argument.
+    pub fn range_check(mut self, column: usize, num_bits: u32) -> Self {
+        assert!(num_bits > 0, "number of bits must be greater than zero");
+        let k = self.limb_bits;
+        if num_bits <= k {
+            self.add_lookups(column, num_bits);
+            return self;
+        }
+
+        let num_limbs = ((num_bits + k - 1) / k) as usize;
+        let first_limb = self.limb_columns().end;
+        let limbs = first_limb..first_limb + num_limbs;
+        assert!(
+            !limbs.contains(&column),
+            "value column {column} must not be one of the limb columns {limbs:?}"
+        );
+        for limb in limbs.clone().take(num_limbs - 1) {
+            self.add_lookups(limb, k);
+        }
+        self.add_lookups(limbs.end - 1, num_bits - k * (num_limbs as u32 - 1));
+        self.decompositions.push((column, limbs));
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of bits in a single limb, i.e., $k$.
+    pub fn limb_bits(&self) -> u32 {
+        self.limb_bits
+    }
+
+    /// Returns the number of rows in the lookup table, i.e., $2^k$.
+    pub fn table_length(&self) -> usize {
+        1 << self.limb_bits
+    }
+
+    /// Returns the index of the periodic column holding the lookup table.
+    pub fn table_column(&self) -> usize {
+        self.table_column
+    }
+
+    /// Returns the index of the main trace column holding multiplicities of table values.
+    pub fn multiplicity_column(&self) -> usize {
+        self.multiplicity_column
+    }
+
+    /// Returns the range of main trace columns holding limbs of range-checked values.
+    pub fn limb_columns(&self) -> Range<usize> {
+        let num_limbs = self
+            .decompositions
+            .iter()
+            .map(|(_, limbs)| limbs.len())
+            .sum::<usize>();
+        self.first_limb_column..self.first_limb_column + num_limbs
+    }
+
+    /// Returns the index of the auxiliary trace column holding the running sum.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns the number of values looked up in the table in every row of the trace.
+    pub fn num_lookups(&self) -> usize {
+        self.lookups.len()
+    }
+
+    /// Returns the number of random elements consumed by this argument.
+    pub fn num_rand_elements(&self) -> usize {
+        1
+    }
+
+    /// Returns the degree of the transition constraint enforced by this argument.
+    pub fn constraint_degree(&self) -> TransitionConstraintDegree {
+        TransitionConstraintDegree::with_cycles(self.num_lookups() + 1, vec![self.table_length()])
+    }
+
+    /// Returns values of the periodic column holding the lookup table.
+    pub fn table_values<B: StarkField>(&self) -> Vec<B> {
+        (0..self.table_length() as u64).map(B::from).collect()
+    }
+
+    /// Returns the values looked up in the table for the provided main trace row.
+    pub fn lookup_values<F: FieldElement>(&self, row: &[F]) -> Vec<F> {
+        self.lookups
+            .iter()
+            .map(|&(column, shift)| row[column] * F::from(1u64 << shift))
+            .collect()
+    }
+
+    // CONSTRAINTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates the transition constraint of this argument against the provided frames of the
+    /// main and the auxiliary trace segments.
+    pub fn evaluate_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> E
+    where
+        F: FieldElement,
+        E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
+    {
+        let alpha = aux_rand_elements.get_segment_elements(0)[0];
+        let row = main_frame.current();
+
+        // sum up the inverses of the looked up values as a single fraction
+        let (numerator, denominator) = self.lookup_values(row).into_iter().fold(
+            (E::ZERO, E::ONE),
+            |(numerator, denominator), value| {
+                let value = alpha + E::from(value);
+                (numerator * value + denominator, denominator * value)
+            },
+        );
+        let table_value = alpha + E::from(periodic_values[self.table_column]);
+        let multiplicity = E::from(row[self.multiplicity_column]);
+
+        let delta = aux_frame.next()[self.column] - aux_frame.current()[self.column];
+        are_equal(
+            delta * denominator * table_value,
+            numerator * table_value - multiplicity * denominator,
+        )
+    }
+
+    /// Returns assertions that the running sum column of this argument starts and ends with
+    /// zero in an execution trace of the specified length.
+    pub fn get_assertions<E: FieldElement>(&self, trace_length: usize) -> Vec<Assertion<E>> {
+        vec![
+            Assertion::single(self.column, 0, E::ZERO),
+            Assertion::single(self.column, trace_length - 1, E::ZERO),
+        ]
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Looks up the value in the specified column, and its copy shifted to the top of the limb
+    /// when the value is narrower than a limb.
+    fn add_lookups(&mut self, column: usize, num_bits: u32) {
+        self.lookups.push((column, 0));
+        if num_bits < self.limb_bits {
+            self.lookups.push((column, self.limb_bits - num_bits));
+        }
+    }
+}
+
+impl Gadget for RangeLookupArgument {
+    fn num_aux_columns(&self) -> usize {
+        self.limb_columns().len()
+    }
+
+    fn constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        vec![TransitionConstraintDegree::new(1); self.decompositions.len()]
+    }
+
+    /// Fills the limb columns with the little-endian limbs of the values being range-checked.
+    ///
+    /// If a value does not fit into its limbs, the constraints of this argument will not hold
+    /// for the resulting row.
+    ///
+    /// # Panics
+    /// Panics if the limbs of a value span more bits than the modulus of the field `B`.
+    fn fill<B: StarkField>(&self, row: &mut [B]) {
+        let one = B::Representation::from(1u32);
+        for (value, limbs) in self.decompositions.iter() {
+            let num_bits = limbs.len() as u32 * self.limb_bits;
+            assert!(
+                num_bits < B::MODULUS_BITS,
+                "cannot decompose {} bits in a field with a {}-bit modulus",
+                num_bits,
+                B::MODULUS_BITS
+            );
+            let value = row[*value].to_repr();
+            for (i, column) in limbs.clone().enumerate() {
+                let offset = i as u32 * self.limb_bits;
+                row[column] = (0..self.limb_bits)
+                    .filter(|&bit| (value >> (offset + bit)) & one == one)
+                    .fold(B::ZERO, |acc, bit| acc + B::from(1u64 << bit));
+            }
+        }
+    }
+
+    fn evaluate<E: FieldElement>(&self, frame: &EvaluationFrame<E>, result: &mut [E]) {
+        let current = frame.current();
+        let base = E::from(1u64 << self.limb_bits);
+        for (result, (value, limbs)) in result.iter_mut().zip(&self.decompositions) {
+            let recomposed = current[limbs.clone()]
+                .iter()
+                .rev()
+                .fold(E::ZERO, |acc, &limb| acc * base + limb);
+            *result = are_equal(current[*value], recomposed);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    are_equal, is_binary, select, EvaluationFrame, FieldElement, Gadget, StarkField,
+    TransitionConstraintDegree, Vec,
+};
+
+// SELECT GADGET
+// ================================================================================================
+/// Selects one of two values in the execution trace based on a binary flag.
+///
+/// The gadget uses a single auxiliary column holding the selected value, and enforces two
+/// constraints:
+/// * the value in the `flag` column is binary;
+/// * the value in the `output` column is equal to the value in the `lhs` column when the flag is
+///   one, and to the value in the `rhs` column when the flag is zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SelectGadget {
+    flag: usize,
+    lhs: usize,
+    rhs: usize,
+    output: usize,
+}
+
+impl SelectGadget {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new gadget writing the value of either the `lhs` or the `rhs` column into the
+    /// `output` column, depending on the value in the `flag` column.
+    ///
+    /// # Panics
+    /// Panics if the `output` column is the same as any of the input columns.
+    pub fn new(flag: usize, lhs: usize, rhs: usize, output: usize) -> Self {
+        assert!(
+            output != flag && output != lhs && output != rhs,
+            "output column {output} must be distinct from the input columns"
+        );
+        SelectGadget {
+            flag,
+            lhs,
+            rhs,
+            output,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the column containing the selector flag.
+    pub fn flag(&self) -> usize {
+        self.flag
+    }
+
+    /// Returns the index of the column selected when the flag is one.
+    pub fn lhs(&self) -> usize {
+        self.lhs
+    }
+
+    /// Returns the index of the column selected when the flag is zero.
+    pub fn rhs(&self) -> usize {
+        self.rhs
+    }
+
+    /// Returns the index of the auxiliary column containing the selected value.
+    pub fn output(&self) -> usize {
+        self.output
+    }
+}
+
+impl Gadget for SelectGadget {
+    fn num_aux_columns(&self) -> usize {
+        1
+    }
+
+    fn constraint_degrees(&self) -> Vec<TransitionConstraintDegree> {
+        vec![
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(2),
+        ]
+    }
+
+    fn fill<B: StarkField>(&self, row: &mut [B]) {
+        row[self.output] = select(row[self.flag], row[self.lhs], row[self.rhs]);
+    }
+
+    fn evaluate<E: FieldElement>(&self, frame: &EvaluationFrame<E>, result: &mut [E]) {
+        let current = frame.current();
+        result[0] = is_binary(current[self.flag]);
+        result[1] = are_equal(
+            current[self.output],
+            select(current[self.flag], current[self.lhs], current[self.rhs]),
+        );
+    }
+}
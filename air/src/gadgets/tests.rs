@@ -0,0 +1,406 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    EvaluationFrame, EvaluationResult, Gadget, IsZeroGadget, PermutationArgument, RangeCheckGadget,
+    RangeLookupArgument, SelectGadget,
+};
+use crate::AuxTraceRandElements;
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::collections::Vec;
+
+// IS ZERO GADGET
+// ================================================================================================
+
+#[test]
+fn is_zero_gadget() {
+    let gadget = IsZeroGadget::new(0, 1);
+    assert_eq!(1, gadget.num_aux_columns());
+    assert_eq!(1, gadget.num_constraints());
+
+    // non-zero input
+    let row = fill_row(&gadget, &[BaseElement::new(42), BaseElement::ZERO]);
+    assert_eq!(BaseElement::ZERO, gadget.output(&row));
+    assert_eq!(vec![BaseElement::ZERO], evaluate(&gadget, &row));
+
+    // zero input
+    let row = fill_row(&gadget, &[BaseElement::ZERO, BaseElement::ZERO]);
+    assert_eq!(BaseElement::ONE, gadget.output(&row));
+    assert_eq!(vec![BaseElement::ZERO], evaluate(&gadget, &row));
+
+    // wrong inverse for a non-zero input
+    let row = vec![BaseElement::new(42), BaseElement::new(3)];
+    assert_ne!(vec![BaseElement::ZERO], evaluate(&gadget, &row));
+}
+
+#[test]
+#[should_panic(expected = "input and inverse columns must be distinct, but both were 1")]
+fn is_zero_gadget_same_columns() {
+    let _ = IsZeroGadget::new(1, 1);
+}
+
+// SELECT GADGET
+// ================================================================================================
+
+#[test]
+fn select_gadget() {
+    let gadget = SelectGadget::new(0, 1, 2, 3);
+    assert_eq!(1, gadget.num_aux_columns());
+    assert_eq!(2, gadget.num_constraints());
+
+    let lhs = BaseElement::new(5);
+    let rhs = BaseElement::new(7);
+
+    let row = fill_row(&gadget, &[BaseElement::ONE, lhs, rhs, BaseElement::ZERO]);
+    assert_eq!(lhs, row[3]);
+    assert_eq!(vec![BaseElement::ZERO; 2], evaluate(&gadget, &row));
+
+    let row = fill_row(&gadget, &[BaseElement::ZERO, lhs, rhs, BaseElement::ZERO]);
+    assert_eq!(rhs, row[3]);
+    assert_eq!(vec![BaseElement::ZERO; 2], evaluate(&gadget, &row));
+
+    // non-binary flag
+    let row = fill_row(&gadget, &[BaseElement::new(2), lhs, rhs, BaseElement::ZERO]);
+    let result = evaluate(&gadget, &row);
+    assert_ne!(BaseElement::ZERO, result[0]);
+    assert_eq!(BaseElement::ZERO, result[1]);
+
+    // wrong output
+    let row = vec![BaseElement::ONE, lhs, rhs, rhs];
+    let result = evaluate(&gadget, &row);
+    assert_eq!(BaseElement::ZERO, result[0]);
+    assert_ne!(BaseElement::ZERO, result[1]);
+}
+
+#[test]
+#[should_panic(expected = "output column 1 must be distinct from the input columns")]
+fn select_gadget_output_is_input() {
+    let _ = SelectGadget::new(0, 1, 2, 1);
+}
+
+// RANGE CHECK GADGET
+// ================================================================================================
+
+#[test]
+fn range_check_gadget() {
+    let gadget = RangeCheckGadget::new(0, 1, 8);
+    assert_eq!(8, gadget.num_aux_columns());
+    assert_eq!(9, gadget.num_constraints());
+    assert_eq!(1..9, gadget.bits());
+
+    let mut row = vec![BaseElement::ZERO; 9];
+    row[0] = BaseElement::new(0b1010_0110);
+    let row = fill_row(&gadget, &row);
+    let expected_bits = [0, 1, 1, 0, 0, 1, 0, 1].map(BaseElement::new);
+    assert_eq!(expected_bits.to_vec(), row[1..].to_vec());
+    assert_eq!(row[0], gadget.recompose(&row));
+    assert_eq!(vec![BaseElement::ZERO; 9], evaluate(&gadget, &row));
+
+    // value which does not fit into 8 bits
+    let mut row = vec![BaseElement::ZERO; 9];
+    row[0] = BaseElement::new(256);
+    let row = fill_row(&gadget, &row);
+    let result = evaluate(&gadget, &row);
+    assert_eq!(vec![BaseElement::ZERO; 8], result[..8].to_vec());
+    assert_ne!(BaseElement::ZERO, result[8]);
+
+    // non-binary bit which still recomposes into the value
+    let mut row = vec![BaseElement::ZERO; 9];
+    row[0] = BaseElement::new(2);
+    row[1] = BaseElement::new(2);
+    let result = evaluate(&gadget, &row);
+    assert_ne!(BaseElement::ZERO, result[0]);
+    assert_eq!(BaseElement::ZERO, result[8]);
+}
+
+#[test]
+#[should_panic(expected = "value column 3 must not be one of the bit columns 1..5")]
+fn range_check_gadget_value_is_bit() {
+    let _ = RangeCheckGadget::new(3, 1, 4);
+}
+
+// PERMUTATION ARGUMENT
+// ================================================================================================
+
+#[test]
+fn permutation_argument() {
+    let argument = PermutationArgument::new(vec![0, 1], vec![2, 3], 0);
+    assert_eq!(3, argument.num_rand_elements());
+
+    // rows of the right-hand side are a permutation of the rows of the left-hand side; the last
+    // row is not covered by the argument
+    let lhs = [
+        [1, 2],
+        [3, 4],
+        [5, 6],
+        [7, 8],
+        [0, 0],
+        [0, 0],
+        [1, 2],
+        [9, 9],
+    ];
+    let rhs = [
+        [5, 6],
+        [0, 0],
+        [1, 2],
+        [3, 4],
+        [1, 2],
+        [0, 0],
+        [7, 8],
+        [4, 4],
+    ];
+    let (z, constraints) = run_permutation_argument(&argument, &lhs, &rhs);
+    assert_eq!(BaseElement::ONE, z[0]);
+    assert_eq!(BaseElement::ONE, z[7]);
+    assert_eq!(vec![BaseElement::ZERO; 7], constraints);
+}
+
+#[test]
+fn permutation_argument_not_a_permutation() {
+    let argument = PermutationArgument::new(vec![0, 1], vec![2, 3], 0);
+
+    // the values in the columns are the same, but the pairs are different
+    let lhs = [
+        [1, 2],
+        [3, 4],
+        [5, 6],
+        [7, 8],
+        [0, 0],
+        [0, 0],
+        [1, 2],
+        [9, 9],
+    ];
+    let rhs = [
+        [5, 6],
+        [0, 0],
+        [2, 1],
+        [3, 4],
+        [1, 2],
+        [0, 0],
+        [7, 8],
+        [9, 9],
+    ];
+    let (z, constraints) = run_permutation_argument(&argument, &lhs, &rhs);
+    assert_ne!(BaseElement::ONE, z[7]);
+    assert_eq!(vec![BaseElement::ZERO; 7], constraints);
+}
+
+#[test]
+#[should_panic(expected = "column groups must have the same number of columns, but were 2 and 1")]
+fn permutation_argument_different_widths() {
+    let _ = PermutationArgument::new(vec![0, 1], vec![2], 0);
+}
+
+// RANGE LOOKUP ARGUMENT
+// ================================================================================================
+
+#[test]
+fn range_lookup_argument() {
+    // a 2-bit value in column 0, and a 5-bit value in column 2 split into limbs in columns 3..6
+    let argument = RangeLookupArgument::new(2, 0, 1, 3, 0)
+        .range_check(0, 2)
+        .range_check(2, 5);
+    assert_eq!(4, argument.table_length());
+    assert_eq!(3..6, argument.limb_columns());
+    assert_eq!(3, argument.num_aux_columns());
+    assert_eq!(1, argument.num_constraints());
+    assert_eq!(5, argument.num_lookups());
+    assert_eq!(
+        [0, 1, 2, 3].map(BaseElement::new).to_vec(),
+        argument.table_values::<BaseElement>()
+    );
+
+    let mut row = vec![BaseElement::ZERO; 6];
+    row[2] = BaseElement::new(0b1_11_01);
+    let row = fill_row(&argument, &row);
+    assert_eq!([1, 3, 1].map(BaseElement::new).to_vec(), row[3..].to_vec());
+    assert_eq!(vec![BaseElement::ZERO], evaluate(&argument, &row));
+
+    // the last row is not covered by the argument
+    let values = [
+        [3, 31],
+        [0, 0],
+        [1, 17],
+        [2, 8],
+        [3, 30],
+        [1, 1],
+        [0, 12],
+        [9, 99],
+    ];
+    let (z, constraints) = run_range_lookup_argument(&argument, &values);
+    assert_eq!(BaseElement::ZERO, z[0]);
+    assert_eq!(BaseElement::ZERO, z[7]);
+    assert_eq!(vec![BaseElement::ZERO; 7], constraints);
+}
+
+#[test]
+fn range_lookup_argument_out_of_range() {
+    let argument = RangeLookupArgument::new(2, 0, 1, 3, 0)
+        .range_check(0, 2)
+        .range_check(2, 5);
+
+    // 32 does not fit into 5 bits even though all of its limbs fit into 2 bits
+    let values = [
+        [3, 31],
+        [0, 0],
+        [1, 32],
+        [2, 8],
+        [3, 30],
+        [1, 1],
+        [0, 12],
+        [0, 0],
+    ];
+    let (z, constraints) = run_range_lookup_argument(&argument, &values);
+    assert_ne!(BaseElement::ZERO, z[7]);
+    assert_eq!(vec![BaseElement::ZERO; 7], constraints);
+
+    // 4 does not fit into 2 bits
+    let values = [
+        [3, 31],
+        [0, 0],
+        [4, 17],
+        [2, 8],
+        [3, 30],
+        [1, 1],
+        [0, 12],
+        [0, 0],
+    ];
+    let (z, constraints) = run_range_lookup_argument(&argument, &values);
+    assert_ne!(BaseElement::ZERO, z[7]);
+    assert_eq!(vec![BaseElement::ZERO; 7], constraints);
+}
+
+#[test]
+#[should_panic(expected = "value column 4 must not be one of the limb columns 3..6")]
+fn range_lookup_argument_value_is_limb() {
+    let _ = RangeLookupArgument::new(2, 0, 1, 3, 0).range_check(4, 5);
+}
+
+#[test]
+#[should_panic(expected = "number of limb bits must be between 1 and 20, but was 21")]
+fn range_lookup_argument_limb_too_wide() {
+    let _ = RangeLookupArgument::new(21, 0, 1, 3, 0);
+}
+
+// CONSTRAINT AGGREGATION
+// ================================================================================================
+
+#[test]
+fn agg_constraints() {
+    let flag = BaseElement::new(3);
+    let values = [BaseElement::new(1), BaseElement::new(2)];
+
+    let mut result = vec![BaseElement::ONE; 4];
+    result.agg_constraints(1, flag, &values);
+    let expected = [1, 4, 7, 1].map(BaseElement::new);
+    assert_eq!(expected.to_vec(), result);
+
+    let mut result = [BaseElement::ONE; 4];
+    result[..].agg_constraints(2, flag, &values);
+    assert_eq!([1, 1, 4, 7].map(BaseElement::new), result);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn fill_row<G: Gadget>(gadget: &G, row: &[BaseElement]) -> Vec<BaseElement> {
+    let mut row = row.to_vec();
+    gadget.fill(&mut row);
+    row
+}
+
+fn evaluate<G: Gadget>(gadget: &G, row: &[BaseElement]) -> Vec<BaseElement> {
+    let frame = EvaluationFrame::from_rows(row.to_vec(), row.to_vec());
+    let mut result = vec![BaseElement::ZERO; gadget.num_constraints()];
+    gadget.evaluate(&frame, &mut result);
+    result
+}
+
+/// Builds the running product column of the argument for the provided column groups, and
+/// evaluates the transition constraint of the argument against all consecutive pairs of rows.
+fn run_permutation_argument(
+    argument: &PermutationArgument,
+    lhs: &[[u128; 2]],
+    rhs: &[[u128; 2]],
+) -> (Vec<BaseElement>, Vec<BaseElement>) {
+    let rows = lhs
+        .iter()
+        .zip(rhs)
+        .map(|(a, b)| [a[0], a[1], b[0], b[1]].map(BaseElement::new).to_vec())
+        .collect::<Vec<_>>();
+
+    let mut rand_elements = AuxTraceRandElements::new();
+    rand_elements.add_segment_elements([11, 13, 17].map(BaseElement::new).to_vec());
+    let alphas = rand_elements.get_segment_elements(0);
+
+    let mut z = vec![BaseElement::ONE];
+    for row in rows.iter().take(rows.len() - 1) {
+        let next = *z.last().unwrap() * argument.reduce_lhs(row, alphas)
+            / argument.reduce_rhs(row, alphas);
+        z.push(next);
+    }
+
+    let constraints = (0..rows.len() - 1)
+        .map(|i| {
+            let main_frame = EvaluationFrame::from_rows(rows[i].clone(), rows[i + 1].clone());
+            let aux_frame = EvaluationFrame::from_rows(vec![z[i]], vec![z[i + 1]]);
+            argument.evaluate_transition(&main_frame, &aux_frame, &rand_elements)
+        })
+        .collect();
+
+    (z, constraints)
+}
+
+/// Builds a trace with the provided values in columns 0 and 2, fills the limbs and the
+/// multiplicities of the argument, builds its running sum column, and evaluates the transition
+/// constraint of the argument against all consecutive pairs of rows.
+fn run_range_lookup_argument(
+    argument: &RangeLookupArgument,
+    values: &[[u128; 2]],
+) -> (Vec<BaseElement>, Vec<BaseElement>) {
+    let table = argument.table_values::<BaseElement>();
+    let mut rows = values
+        .iter()
+        .map(|&[a, b]| {
+            let mut row = vec![BaseElement::ZERO; 6];
+            row[0] = BaseElement::new(a);
+            row[2] = BaseElement::new(b);
+            fill_row(argument, &row)
+        })
+        .collect::<Vec<_>>();
+    for i in 0..rows.len() - 1 {
+        for value in argument.lookup_values(&rows[i]) {
+            if let Some(index) = table.iter().position(|&t| t == value) {
+                rows[index][1] += BaseElement::ONE;
+            }
+        }
+    }
+
+    let mut rand_elements = AuxTraceRandElements::new();
+    rand_elements.add_segment_elements(vec![BaseElement::new(11)]);
+    let alpha = rand_elements.get_segment_elements(0)[0];
+
+    let mut z = vec![BaseElement::ZERO];
+    for (i, row) in rows.iter().take(rows.len() - 1).enumerate() {
+        let lookups = argument
+            .lookup_values(row)
+            .into_iter()
+            .fold(BaseElement::ZERO, |acc, value| acc + (alpha + value).inv());
+        let table_term = row[1] / (alpha + table[i % table.len()]);
+        z.push(*z.last().unwrap() + lookups - table_term);
+    }
+
+    let constraints = (0..rows.len() - 1)
+        .map(|i| {
+            let main_frame = EvaluationFrame::from_rows(rows[i].clone(), rows[i + 1].clone());
+            let aux_frame = EvaluationFrame::from_rows(vec![z[i]], vec![z[i + 1]]);
+            let periodic_values = [table[i % table.len()]];
+            argument.evaluate_transition(&main_frame, &aux_frame, &periodic_values, &rand_elements)
+        })
+        .collect();
+
+    (z, constraints)
+}
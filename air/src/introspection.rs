@@ -0,0 +1,721 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Introspection of AIRs for external tooling.
+//!
+//! An [AirInfo] collects the structure of an AIR instance into plain, serializable structs: the
+//! trace layout, the sizes of the evaluation domains, the degrees, divisors and columns of all
+//! transition constraints, summaries of all assertions, and an estimate of the size of a proof
+//! generated for the AIR. Building an [AirInfo] does not require a prover or an execution trace;
+//! an AIR instance is sufficient.
+//!
+//! The trace columns touched by each transition constraint are detected by evaluating the
+//! constraints over a pseudo-random evaluation frame and changing the values of the frame one at
+//! a time. This detects all columns on which a constraint depends, except for pathological cases
+//! in which a change of a single value does not change the value of the constraint.
+
+use crate::{
+    proof::estimate_proof_size, Air, Assertion, AuxTraceRandElements, ConstraintDivisor,
+    EvaluationFrame, ProofOptions, TraceLayout, TransitionDivisor, TransitionEvaluator,
+};
+use core::fmt;
+use math::{FieldElement, StarkField};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Size of a hash digest (in bytes) assumed when estimating proof sizes.
+const DIGEST_SIZE: usize = 32;
+
+/// Seed of the pseudo-random sequence used to populate evaluation frames.
+const FRAME_SEED: u64 = 0x5eed_a1f0_1234_5678;
+
+// AIR INFO
+// ================================================================================================
+/// Description of the structure of an AIR instance.
+///
+/// Columns of the auxiliary trace segments are indexed after the columns of the main trace
+/// segment: column $i$ of the auxiliary segments has index $w + i$, where $w$ is the width of the
+/// main trace segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirInfo {
+    /// Layout of the execution trace.
+    pub trace_layout: TraceLayout,
+    /// Length of the execution trace.
+    pub trace_length: usize,
+    /// STARK protocol parameters of the AIR instance.
+    pub options: ProofOptions,
+    /// Sizes of the domains over which the AIR instance is evaluated.
+    pub domains: DomainInfo,
+    /// Offsets of the rows in an evaluation frame relative to the current step.
+    pub frame_offsets: Vec<usize>,
+    /// Number of periodic columns, including the columns used only by auxiliary constraints.
+    pub num_periodic_columns: usize,
+    /// Descriptions of all transition constraints; main constraints come first, followed by
+    /// auxiliary constraints.
+    pub transition_constraints: Vec<TransitionConstraintInfo>,
+    /// Summaries of all assertions; main assertions come first, followed by auxiliary
+    /// assertions.
+    pub assertions: Vec<AssertionInfo>,
+    /// Number of columns into which the constraint composition polynomial is split.
+    pub num_composition_columns: usize,
+    /// Degree to which all constraint polynomials are normalized before they are composed.
+    pub composition_degree: usize,
+    /// Estimated size (in bytes) of a serialized proof for the AIR instance, assuming hash
+    /// digests of 32 bytes (see [StarkProof::estimated_size()](crate::proof::StarkProof)).
+    pub estimated_proof_size: usize,
+}
+
+impl AirInfo {
+    /// Returns a description of the structure of the specified `air`.
+    ///
+    /// This evaluates the transition constraints of the `air` over a number of pseudo-random
+    /// evaluation frames, and thus, takes time proportional to the number of values in an
+    /// evaluation frame.
+    pub fn from_air<A: Air>(air: &A) -> Self {
+        let context = air.context();
+        let trace_layout = air.trace_layout().clone();
+        let trace_length = air.trace_length();
+        let main_width = trace_layout.main_trace_width();
+
+        // random elements for auxiliary segments are drawn from the same sequence as the frame
+        // values; they do not affect the structure of the AIR
+        let mut sequence = ElementSequence::new(FRAME_SEED);
+        let mut aux_rand_elements = AuxTraceRandElements::<A::BaseField>::new();
+        for i in 0..trace_layout.num_aux_segments() {
+            let num_elements = trace_layout.get_aux_segment_rand_elements(i);
+            aux_rand_elements.add_segment_elements(sequence.draw_many(num_elements));
+        }
+
+        // describe transition constraints
+        let columns = find_touched_columns(air, &aux_rand_elements, &mut sequence);
+        let degrees = context
+            .main_transition_constraint_degrees()
+            .iter()
+            .chain(context.aux_transition_constraint_degrees());
+        let transition_constraints = degrees
+            .zip(columns)
+            .enumerate()
+            .map(|(i, (degree, columns))| {
+                let divisor = context.get_transition_divisor(i);
+                TransitionConstraintInfo {
+                    is_aux: i >= context.num_main_transition_constraints(),
+                    base_degree: degree.base(),
+                    cycles: degree.cycles().to_vec(),
+                    evaluation_degree: context.get_evaluation_degree(degree),
+                    divisor: DivisorInfo::new(&divisor, trace_length),
+                    selector: context
+                        .get_transition_selector(i)
+                        .map(|selector| selector.column_idx()),
+                    columns,
+                }
+            })
+            .collect();
+
+        // summarize assertions
+        let mut assertions = air
+            .get_assertions()
+            .iter()
+            .map(|assertion| AssertionInfo::new(assertion, trace_length, 0, false))
+            .collect::<Vec<_>>();
+        if trace_layout.num_aux_segments() > 0 {
+            let aux_assertions = air.get_aux_assertions::<A::BaseField>(&aux_rand_elements);
+            assertions.extend(
+                aux_assertions
+                    .iter()
+                    .map(|assertion| AssertionInfo::new(assertion, trace_length, main_width, true)),
+            );
+        }
+
+        let num_periodic_columns =
+            air.get_periodic_column_values().len() + air.get_aux_periodic_column_values().len();
+
+        AirInfo {
+            domains: DomainInfo {
+                trace_domain_size: trace_length,
+                ce_domain_size: air.ce_domain_size(),
+                ce_blowup_factor: air.ce_blowup_factor(),
+                lde_domain_size: air.lde_domain_size(),
+                lde_blowup_factor: air.lde_blowup_factor(),
+            },
+            trace_layout,
+            trace_length,
+            options: air.options().clone(),
+            frame_offsets: air.frame_offsets().to_vec(),
+            num_periodic_columns,
+            transition_constraints,
+            assertions,
+            num_composition_columns: air.num_constraint_composition_columns(),
+            composition_degree: air.composition_degree(),
+            estimated_proof_size: estimate_proof_size(air, air.options(), DIGEST_SIZE),
+        }
+    }
+}
+
+impl fmt::Display for AirInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let layout = &self.trace_layout;
+        writeln!(
+            f,
+            "trace: {} main columns, {} auxiliary columns in {} segments, {} rows",
+            layout.main_trace_width(),
+            layout.aux_trace_width(),
+            layout.num_aux_segments(),
+            self.trace_length
+        )?;
+        writeln!(f, "frame offsets: {:?}", self.frame_offsets)?;
+        writeln!(f, "periodic columns: {}", self.num_periodic_columns)?;
+        writeln!(f, "{}", self.domains)?;
+        writeln!(
+            f,
+            "composition: {} columns, degree {}",
+            self.num_composition_columns, self.composition_degree
+        )?;
+        writeln!(
+            f,
+            "transition constraints ({}):",
+            self.transition_constraints.len()
+        )?;
+        for (i, constraint) in self.transition_constraints.iter().enumerate() {
+            writeln!(f, "  [{i}] {constraint}")?;
+        }
+        writeln!(f, "assertions ({}):", self.assertions.len())?;
+        for (i, assertion) in self.assertions.iter().enumerate() {
+            writeln!(f, "  [{i}] {assertion}")?;
+        }
+        write!(
+            f,
+            "estimated proof size: {} bytes",
+            self.estimated_proof_size
+        )
+    }
+}
+
+impl Serializable for AirInfo {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.trace_layout.write_into(target);
+        target.write_u32(self.trace_length as u32);
+        self.options.write_into(target);
+        self.domains.write_into(target);
+        write_usize_list(target, &self.frame_offsets);
+        target.write_u32(self.num_periodic_columns as u32);
+        target.write_u32(self.transition_constraints.len() as u32);
+        for constraint in self.transition_constraints.iter() {
+            constraint.write_into(target);
+        }
+        target.write_u32(self.assertions.len() as u32);
+        for assertion in self.assertions.iter() {
+            assertion.write_into(target);
+        }
+        target.write_u32(self.num_composition_columns as u32);
+        target.write_u32(self.composition_degree as u32);
+        target.write_u32(self.estimated_proof_size as u32);
+    }
+}
+
+impl Deserializable for AirInfo {
+    /// Reads an AIR description from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid AIR description could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let trace_layout = TraceLayout::read_from(source)?;
+        let trace_length = source.read_u32()? as usize;
+        let options = ProofOptions::read_from(source)?;
+        let domains = DomainInfo::read_from(source)?;
+        let frame_offsets = read_usize_list(source)?;
+        let num_periodic_columns = source.read_u32()? as usize;
+        let num_constraints = source.read_u32()? as usize;
+        let transition_constraints = (0..num_constraints)
+            .map(|_| TransitionConstraintInfo::read_from(source))
+            .collect::<Result<_, _>>()?;
+        let num_assertions = source.read_u32()? as usize;
+        let assertions = (0..num_assertions)
+            .map(|_| AssertionInfo::read_from(source))
+            .collect::<Result<_, _>>()?;
+
+        Ok(AirInfo {
+            trace_layout,
+            trace_length,
+            options,
+            domains,
+            frame_offsets,
+            num_periodic_columns,
+            transition_constraints,
+            assertions,
+            num_composition_columns: source.read_u32()? as usize,
+            composition_degree: source.read_u32()? as usize,
+            estimated_proof_size: source.read_u32()? as usize,
+        })
+    }
+}
+
+// DOMAIN INFO
+// ================================================================================================
+/// Sizes of the domains over which an AIR instance is evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DomainInfo {
+    /// Size of the trace domain; this is equal to the length of the execution trace.
+    pub trace_domain_size: usize,
+    /// Size of the domain over which constraints are evaluated by the prover.
+    pub ce_domain_size: usize,
+    /// Ratio of the constraint evaluation domain size to the trace domain size.
+    pub ce_blowup_factor: usize,
+    /// Size of the low-degree extension domain.
+    pub lde_domain_size: usize,
+    /// Ratio of the low-degree extension domain size to the trace domain size.
+    pub lde_blowup_factor: usize,
+}
+
+impl fmt::Display for DomainInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "domains: trace {}, constraint evaluation {} (blowup {}), LDE {} (blowup {})",
+            self.trace_domain_size,
+            self.ce_domain_size,
+            self.ce_blowup_factor,
+            self.lde_domain_size,
+            self.lde_blowup_factor
+        )
+    }
+}
+
+impl Serializable for DomainInfo {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.trace_domain_size as u32);
+        target.write_u32(self.ce_domain_size as u32);
+        target.write_u32(self.ce_blowup_factor as u32);
+        target.write_u32(self.lde_domain_size as u32);
+        target.write_u32(self.lde_blowup_factor as u32);
+    }
+}
+
+impl Deserializable for DomainInfo {
+    /// Reads domain sizes from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if the `source` does not contain enough bytes.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(DomainInfo {
+            trace_domain_size: source.read_u32()? as usize,
+            ce_domain_size: source.read_u32()? as usize,
+            ce_blowup_factor: source.read_u32()? as usize,
+            lde_domain_size: source.read_u32()? as usize,
+            lde_blowup_factor: source.read_u32()? as usize,
+        })
+    }
+}
+
+// TRANSITION CONSTRAINT INFO
+// ================================================================================================
+/// Description of a single transition constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitionConstraintInfo {
+    /// True if the constraint is placed against auxiliary trace segments.
+    pub is_aux: bool,
+    /// Number of trace columns multiplied together in the constraint.
+    pub base_degree: usize,
+    /// Cycle lengths of periodic columns multiplied together in the constraint.
+    pub cycles: Vec<usize>,
+    /// Degree of the constraint polynomial for the execution trace of the AIR instance.
+    pub evaluation_degree: usize,
+    /// Description of the divisor of the constraint.
+    pub divisor: DivisorInfo,
+    /// Index of the periodic column selecting the steps on which the constraint is enforced, if
+    /// the constraint is wrapped by a selector.
+    pub selector: Option<usize>,
+    /// Indexes of the trace columns on which the constraint depends, in ascending order.
+    pub columns: Vec<usize>,
+}
+
+impl fmt::Display for TransitionConstraintInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segment = if self.is_aux { "aux " } else { "main" };
+        write!(f, "{segment} degree {}", self.base_degree)?;
+        if !self.cycles.is_empty() {
+            write!(f, " with cycles {:?}", self.cycles)?;
+        }
+        write!(
+            f,
+            " (evaluation degree {}), {}",
+            self.evaluation_degree, self.divisor
+        )?;
+        if let Some(selector) = self.selector {
+            write!(f, ", selector {selector}")?;
+        }
+        write!(f, ", columns {:?}", self.columns)
+    }
+}
+
+impl Serializable for TransitionConstraintInfo {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.is_aux as u8);
+        target.write_u32(self.base_degree as u32);
+        write_usize_list(target, &self.cycles);
+        target.write_u32(self.evaluation_degree as u32);
+        self.divisor.write_into(target);
+        write_optional_usize(target, self.selector);
+        write_usize_list(target, &self.columns);
+    }
+}
+
+impl Deserializable for TransitionConstraintInfo {
+    /// Reads a transition constraint description from the specified `source` and returns the
+    /// result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid description could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(TransitionConstraintInfo {
+            is_aux: source.read_u8()? != 0,
+            base_degree: source.read_u32()? as usize,
+            cycles: read_usize_list(source)?,
+            evaluation_degree: source.read_u32()? as usize,
+            divisor: DivisorInfo::read_from(source)?,
+            selector: read_optional_usize(source)?,
+            columns: read_usize_list(source)?,
+        })
+    }
+}
+
+// DIVISOR INFO
+// ================================================================================================
+/// Description of the divisor of a transition constraint.
+///
+/// The fields of this struct mirror the fields of a [TransitionDivisor]; see
+/// [TransitionDivisor::to_constraint_divisor()] for how a [ConstraintDivisor] is built from them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DivisorInfo {
+    /// Distance between consecutive steps on which the constraint must hold.
+    pub period: usize,
+    /// First step on which the constraint must hold (unless this step is exempted).
+    pub offset: usize,
+    /// Steps on which the constraint does not need to hold, in ascending order.
+    pub exemptions: Vec<usize>,
+    /// Interval [`start`, `end`) of steps on which the constraint must hold, if the constraint
+    /// is restricted to an interval.
+    pub interval: Option<(usize, usize)>,
+    /// Degree of the divisor polynomial.
+    pub degree: usize,
+}
+
+impl DivisorInfo {
+    /// Returns a description of the specified transition divisor for an execution trace of the
+    /// specified length.
+    fn new(divisor: &TransitionDivisor, trace_length: usize) -> Self {
+        DivisorInfo {
+            period: divisor.period(),
+            offset: divisor.offset(),
+            exemptions: divisor.exemptions().to_vec(),
+            interval: divisor.interval(),
+            degree: divisor.degree(trace_length),
+        }
+    }
+}
+
+impl fmt::Display for DivisorInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "divisor degree {} ", self.degree)?;
+        match self.interval {
+            Some((start, end)) => write!(f, "(steps {start}..{end})"),
+            None => write!(
+                f,
+                "(period {}, offset {}, exemptions {:?})",
+                self.period, self.offset, self.exemptions
+            ),
+        }
+    }
+}
+
+impl Serializable for DivisorInfo {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.period as u32);
+        target.write_u32(self.offset as u32);
+        write_usize_list(target, &self.exemptions);
+        match self.interval {
+            Some((start, end)) => {
+                target.write_u8(1);
+                target.write_u32(start as u32);
+                target.write_u32(end as u32);
+            }
+            None => target.write_u8(0),
+        }
+        target.write_u32(self.degree as u32);
+    }
+}
+
+impl Deserializable for DivisorInfo {
+    /// Reads a divisor description from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid description could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let period = source.read_u32()? as usize;
+        let offset = source.read_u32()? as usize;
+        let exemptions = read_usize_list(source)?;
+        let interval = match source.read_u8()? {
+            0 => None,
+            1 => Some((source.read_u32()? as usize, source.read_u32()? as usize)),
+            value => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "invalid interval flag: {value}"
+                )))
+            }
+        };
+
+        Ok(DivisorInfo {
+            period,
+            offset,
+            exemptions,
+            interval,
+            degree: source.read_u32()? as usize,
+        })
+    }
+}
+
+// ASSERTION INFO
+// ================================================================================================
+/// Summary of a single assertion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssertionInfo {
+    /// True if the assertion is placed against auxiliary trace segments.
+    pub is_aux: bool,
+    /// Index of the column against which the assertion is placed.
+    pub column: usize,
+    /// First step against which the assertion is placed.
+    pub first_step: usize,
+    /// Interval at which the assertion repeats; this is 0 for single value assertions.
+    pub stride: usize,
+    /// Number of asserted values.
+    pub num_values: usize,
+    /// Number of steps against which the assertion is placed.
+    pub num_steps: usize,
+    /// Degree of the divisor of the boundary constraint built from the assertion.
+    pub divisor_degree: usize,
+}
+
+impl AssertionInfo {
+    /// Returns a summary of the specified assertion for an execution trace of the specified
+    /// length; the index of the asserted column is shifted by `column_offset`.
+    fn new<E: FieldElement>(
+        assertion: &Assertion<E>,
+        trace_length: usize,
+        column_offset: usize,
+        is_aux: bool,
+    ) -> Self {
+        let assertion = assertion.clone().resolve(trace_length);
+        AssertionInfo {
+            is_aux,
+            column: column_offset + assertion.column(),
+            first_step: assertion.first_step(),
+            stride: assertion.stride(),
+            num_values: assertion.values().len(),
+            num_steps: assertion.get_num_steps(trace_length),
+            divisor_degree: ConstraintDivisor::<E::BaseField>::from_assertion(
+                &assertion,
+                trace_length,
+            )
+            .degree(),
+        }
+    }
+}
+
+impl fmt::Display for AssertionInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segment = if self.is_aux { "aux " } else { "main" };
+        write!(
+            f,
+            "{segment} column {}, step {}",
+            self.column, self.first_step
+        )?;
+        if self.stride != 0 {
+            write!(f, " every {} steps", self.stride)?;
+        }
+        write!(
+            f,
+            ", {} values over {} steps, divisor degree {}",
+            self.num_values, self.num_steps, self.divisor_degree
+        )
+    }
+}
+
+impl Serializable for AssertionInfo {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.is_aux as u8);
+        target.write_u32(self.column as u32);
+        target.write_u32(self.first_step as u32);
+        target.write_u32(self.stride as u32);
+        target.write_u32(self.num_values as u32);
+        target.write_u32(self.num_steps as u32);
+        target.write_u32(self.divisor_degree as u32);
+    }
+}
+
+impl Deserializable for AssertionInfo {
+    /// Reads an assertion summary from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if the `source` does not contain enough bytes.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(AssertionInfo {
+            is_aux: source.read_u8()? != 0,
+            column: source.read_u32()? as usize,
+            first_step: source.read_u32()? as usize,
+            stride: source.read_u32()? as usize,
+            num_values: source.read_u32()? as usize,
+            num_steps: source.read_u32()? as usize,
+            divisor_degree: source.read_u32()? as usize,
+        })
+    }
+}
+
+// COLUMN DETECTION
+// ================================================================================================
+
+/// Returns indexes of the trace columns on which each transition constraint of the `air`
+/// depends; main constraints come first, followed by auxiliary constraints.
+///
+/// The constraints are evaluated over a pseudo-random frame, and then over copies of the frame
+/// in which a single value is changed; a constraint depends on a column if changing any value of
+/// the column changes the evaluation of the constraint.
+fn find_touched_columns<A: Air>(
+    air: &A,
+    aux_rand_elements: &AuxTraceRandElements<A::BaseField>,
+    sequence: &mut ElementSequence,
+) -> Vec<Vec<usize>> {
+    let context = air.context();
+    let layout = air.trace_layout();
+    let num_rows = context.num_frame_rows();
+    let main_width = layout.main_trace_width();
+    let aux_width = layout.aux_trace_width();
+
+    let main_rows: Vec<Vec<A::BaseField>> = (0..num_rows)
+        .map(|_| sequence.draw_many(main_width))
+        .collect();
+    let aux_rows: Vec<Vec<A::BaseField>> = (0..num_rows)
+        .map(|_| sequence.draw_many(aux_width))
+        .collect();
+    let main_periodic_values: Vec<A::BaseField> =
+        sequence.draw_many(air.get_periodic_column_values().len());
+    let mut aux_periodic_values = main_periodic_values.clone();
+    aux_periodic_values
+        .extend(sequence.draw_many::<A::BaseField>(air.get_aux_periodic_column_values().len()));
+
+    // evaluates all constraints over frames built from the specified rows
+    let transition_evaluator = TransitionEvaluator::new(air);
+    let evaluate = |main_rows: &[Vec<A::BaseField>], aux_rows: &[Vec<A::BaseField>]| {
+        let main_frame = EvaluationFrame::from_multi_rows(main_rows.to_vec());
+        let mut result = vec![A::BaseField::ZERO; context.num_main_transition_constraints()];
+        transition_evaluator.evaluate_main(&main_frame, &main_periodic_values, &mut result);
+        if aux_width > 0 {
+            let aux_frame = EvaluationFrame::from_multi_rows(aux_rows.to_vec());
+            let mut aux_result = vec![A::BaseField::ZERO; context.num_aux_transition_constraints()];
+            transition_evaluator.evaluate_aux(
+                &main_frame,
+                &aux_frame,
+                &aux_periodic_values,
+                aux_rand_elements,
+                &mut aux_result,
+            );
+            result.extend(aux_result);
+        }
+        result
+    };
+
+    let expected = evaluate(&main_rows, &aux_rows);
+    let mut result = vec![Vec::new(); expected.len()];
+    for column in 0..main_width + aux_width {
+        let mut touched = vec![false; expected.len()];
+        for row in 0..num_rows {
+            let mut main_rows = main_rows.clone();
+            let mut aux_rows = aux_rows.clone();
+            if column < main_width {
+                main_rows[row][column] += A::BaseField::ONE;
+            } else {
+                aux_rows[row][column - main_width] += A::BaseField::ONE;
+            }
+            let actual = evaluate(&main_rows, &aux_rows);
+            for (touched, (a, b)) in touched.iter_mut().zip(expected.iter().zip(actual)) {
+                *touched |= *a != b;
+            }
+        }
+        for (columns, touched) in result.iter_mut().zip(touched) {
+            if touched {
+                columns.push(column);
+            }
+        }
+    }
+    result
+}
+
+/// Deterministic pseudo-random sequence of field elements (based on SplitMix64).
+struct ElementSequence(u64);
+
+impl ElementSequence {
+    fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    fn draw<B: StarkField>(&mut self) -> B {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        B::from_u64_reduced(z ^ (z >> 31))
+    }
+
+    fn draw_many<B: StarkField>(&mut self, n: usize) -> Vec<B> {
+        (0..n).map(|_| self.draw()).collect()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn write_usize_list<W: ByteWriter>(target: &mut W, values: &[usize]) {
+    target.write_u32(values.len() as u32);
+    for &value in values {
+        target.write_u32(value as u32);
+    }
+}
+
+fn read_usize_list<R: ByteReader>(source: &mut R) -> Result<Vec<usize>, DeserializationError> {
+    let num_values = source.read_u32()? as usize;
+    (0..num_values)
+        .map(|_| source.read_u32().map(|value| value as usize))
+        .collect()
+}
+
+fn write_optional_usize<W: ByteWriter>(target: &mut W, value: Option<usize>) {
+    match value {
+        Some(value) => {
+            target.write_u8(1);
+            target.write_u32(value as u32);
+        }
+        None => target.write_u8(0),
+    }
+}
+
+fn read_optional_usize<R: ByteReader>(
+    source: &mut R,
+) -> Result<Option<usize>, DeserializationError> {
+    match source.read_u8()? {
+        0 => Ok(None),
+        1 => Ok(Some(source.read_u32()? as usize)),
+        value => Err(DeserializationError::InvalidValue(format!(
+            "invalid option flag: {value}"
+        ))),
+    }
+}
@@ -26,7 +26,16 @@
 //! details, please refer to the documentation of the [Air] trait itself.
 //!
 //! This crate also contains components describing STARK protocol parameters ([ProofOptions]) and
-//! proof structure ([StarkProof](proof::StarkProof)).
+//! proof structure ([StarkProof](proof::StarkProof)), as well as reusable building blocks for
+//! writing AIR constraints ([gadgets]). The structure of an AIR (e.g., degrees, divisors and
+//! columns of its constraints) can be inspected by external tooling via the [introspection]
+//! module.
+//!
+//! The execution trace and the constraint evaluations of a proof can be opened at different
+//! numbers of positions ([ProofOptions::num_trace_queries()] and
+//! [ProofOptions::num_constraint_queries()]). Only positions at which both are opened contribute
+//! to proof soundness, and thus, the security level of a proof is determined by the smaller of
+//! the two numbers; see [ProofOptions] for details.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -34,18 +43,28 @@
 #[macro_use]
 extern crate alloc;
 
+pub mod gadgets;
+pub mod introspection;
 pub mod proof;
 
 mod errors;
-pub use errors::AssertionError;
+pub use errors::{AssertionError, ProofOptionsError};
 
 mod options;
-pub use options::{FieldExtension, ProofOptions};
+pub use options::{FieldExtension, ProofOptions, ProofOptionsBuilder, TranscriptType};
+
+mod transcript;
+pub use transcript::{
+    labels, PublicCoin, QueryPositions, TranscriptDivergence, TranscriptEntry, TranscriptEntryKind,
+    TranscriptLog,
+};
 
 mod air;
 pub use air::{
     Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
-    BoundaryConstraints, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, TraceInfo, TraceLayout,
-    TransitionConstraintDegree, TransitionConstraintGroup, TransitionConstraints,
+    BoundaryConstraints, ChainedAir, ChainedAirBuilder, ChainedComputation,
+    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
+    EvaluationFrame, PeriodicColumnPolys, RoundFunction, SelectorColumn, Step, TraceInfo,
+    TraceLayout, TransitionConstraintDegree, TransitionConstraintGroup, TransitionConstraints,
+    TransitionDivisor, TransitionEvaluator,
 };
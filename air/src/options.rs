@@ -4,9 +4,23 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use crate::ProofOptionsError;
+use core::{cmp, fmt, str::FromStr};
 use fri::FriOptions;
-use math::StarkField;
-use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+use math::{log2, ExtensibleField, StarkField};
+use utils::{
+    string::ToString, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Bit of the serialized option flags which is set when proofs are zero-knowledge.
+const ZERO_KNOWLEDGE_FLAG: u8 = 1;
+
+/// Bit of the serialized option flags which is set when the flags are followed by a minimum field
+/// security other than [ProofOptions::DEFAULT_MIN_FIELD_SECURITY].
+const MIN_FIELD_SECURITY_FLAG: u8 = 2;
 
 // TYPES AND INTERFACES
 // ================================================================================================
@@ -20,7 +34,8 @@ use utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serial
 ///
 /// For example, if the size of base field is ~64-bits, a quadratic extension must be use to
 /// achieve ~100 bits of soundness, and a cubic extension must be used to achieve 128+ bits
-/// of soundness.
+/// of soundness. A sextic extension can be used when the soundness of the cubic extension is
+/// still inadequate (e.g., for very high security levels over ~64-bit fields).
 ///
 /// However, increasing extension degree will increase proof generation time and proof size by
 /// as much as 50%.
@@ -33,6 +48,30 @@ pub enum FieldExtension {
     Quadratic = 2,
     /// Composition polynomial is constructed in the cubic extension of the base field.
     Cubic = 3,
+    /// Composition polynomial is constructed in the sextic extension of the base field.
+    Sextic = 6,
+}
+
+/// Defines how the public coin of the STARK protocol is instantiated.
+///
+/// The prover and the verifier derive all their random challenges (random elements for auxiliary
+/// trace segments, composition coefficients, the out-of-domain point, FRI layer alphas, and query
+/// positions) from a public coin. The transcript type specifies whether values drawn at these
+/// different places of the protocol are domain-separated:
+///
+/// * [TranscriptType::Legacy] - the coin ignores the place of the protocol at which it is used;
+///   proofs generated with this transcript are compatible with proofs generated before
+///   transcripts types were introduced.
+/// * [TranscriptType::Labeled] - every message absorbed into the coin and every value drawn from
+///   it is prefixed with a label identifying the place of the protocol where the operation
+///   happens (see [crate::labels]).
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TranscriptType {
+    /// Public coin is a [LegacyTranscript](crypto::LegacyTranscript).
+    Legacy = 0,
+    /// Public coin is a [LabeledTranscript](crypto::LabeledTranscript).
+    Labeled = 1,
 }
 
 /// STARK protocol parameters.
@@ -45,6 +84,14 @@ pub enum FieldExtension {
 ///    used to achieve adequate security. And even for ~128 bit fields, to achieve security over
 ///    100 bits, a field extension may be required.
 /// 2. Number of queries - higher values increase proof soundness, but also increase proof size.
+///    The number of positions at which the execution trace is opened and the number of positions
+///    at which the constraint evaluations are opened can be set separately (see
+///    [ProofOptionsBuilder::num_trace_queries()] and
+///    [ProofOptionsBuilder::num_constraint_queries()]). The DEEP composition polynomial can be
+///    evaluated only at positions where both are opened, and thus, proof soundness is determined
+///    by the smaller of the two numbers (see [ProofOptions::num_queries()]); openings beyond it
+///    are checked against their commitments, but only increase proof size. Thus, lowering one of
+///    the numbers reduces proof size, but also proof soundness.
 /// 3. Blowup factor - higher values increase proof soundness, but also increase proof generation
 ///    time and proof size. However, higher blowup factors require fewer queries for the same
 ///    security level. Thus, it is frequently possible to increase blowup factor and at the same
@@ -52,6 +99,23 @@ pub enum FieldExtension {
 /// 4. Grinding factor - higher values increase proof soundness, but also may increase proof
 ///    generation time. More precisely, proof soundness is bounded by
 ///    `num_queries * log2(blowup_factor) + grinding_factor`.
+/// 5. Zero-knowledge - when enabled, trace and constraint composition polynomials are randomized
+///    and commitments to them are salted with fresh randomness (see
+///    [ProofOptions::with_zero_knowledge()]). This roughly doubles proof generation time and
+///    increases proof size, but does not affect proof soundness.
+/// 6. Trace domain offset - the offset of the coset over which the execution trace is defined
+///    (see [ProofOptions::with_trace_domain_offset()]). This does not affect proof soundness,
+///    proof generation time, or proof size.
+/// 7. Transcript type - defines whether values drawn from the public coin are domain-separated
+///    by the place of the protocol at which they are drawn (see [TranscriptType]). This does not
+///    affect proof generation time or proof size.
+/// 8. Minimum field security - the number of bits of security which the field (including its
+///    extension) must provide for a given low-degree extension domain (see
+///    [ProofOptions::field_security()]). The prover refuses to generate proofs for which the
+///    field provides less security than this; for ~64-bit fields, this means that a field
+///    extension must be used. The check can be disabled via
+///    [ProofOptions::allow_insecure_field()]. This minimum is not used by the verifier, which
+///    enforces a minimum of its own.
 ///
 /// Another important parameter in defining STARK security level, which is not a part of [ProofOptions]
 /// is the hash function used in the protocol. The soundness of a STARK proof is limited by the
@@ -59,12 +123,17 @@ pub enum FieldExtension {
 /// with 128-bit collision resistance is used, soundness of a STARK proof cannot exceed 128 bits.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct ProofOptions {
-    num_queries: u8,
+    num_trace_queries: u8,
+    num_constraint_queries: u8,
     blowup_factor: u8,
     grinding_factor: u8,
     field_extension: FieldExtension,
     fri_folding_factor: u8,
     fri_max_remainder_size: u8, // stored as power of 2
+    zero_knowledge: bool,
+    trace_domain_offset: u64,
+    transcript_type: TranscriptType,
+    min_field_security: u8,
 }
 
 // PROOF OPTIONS IMPLEMENTATION
@@ -80,18 +149,42 @@ impl ProofOptions {
     /// have a blowup factor smaller than 2.
     pub const MIN_BLOWUP_FACTOR: usize = 2;
 
+    /// Largest allowed blowup factor which is currently set to 128.
+    pub const MAX_BLOWUP_FACTOR: usize = 128;
+
+    /// Largest allowed number of queries which is currently set to 128.
+    pub const MAX_NUM_QUERIES: usize = 128;
+
+    /// Largest allowed grinding factor which is currently set to 32.
+    pub const MAX_GRINDING_FACTOR: u32 = 32;
+
+    /// Minimum conjectured security level (in bits) which [ProofOptions::try_new()] accepts
+    /// without an explicit [ProofOptionsBuilder::allow_insecure()] call.
+    pub const MIN_CONJECTURED_SECURITY: u32 = 32;
+
+    /// Minimum number of bits of security which the field must provide for proofs generated with
+    /// options which do not specify a different minimum; this is currently set to 80.
+    ///
+    /// See [ProofOptions::with_min_field_security()] for details.
+    pub const DEFAULT_MIN_FIELD_SECURITY: u32 = 80;
+
+    /// Largest allowed minimum field security which is currently set to 255.
+    pub const MAX_MIN_FIELD_SECURITY: u32 = 255;
+
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of [ProofOptions] struct constructed from the specified parameters.
     ///
+    /// Unlike [ProofOptions::try_new()], this constructor does not enforce the minimum conjectured
+    /// security level defined by [ProofOptions::MIN_CONJECTURED_SECURITY].
+    ///
     /// # Panics
     /// Panics if:
     /// * `num_queries` is zero or greater than 128.
-    /// * `blowup_factor` is smaller than 4, greater than 256, or is not a power of two.
+    /// * `blowup_factor` is smaller than 2, greater than 128, or is not a power of two.
     /// * `grinding_factor` is greater than 32.
-    /// * `fri_folding_factor` is not 4, 8, or 16.
+    /// * `fri_folding_factor` is not 2, 4, 8, or 16.
     /// * `fri_max_remainder_size` is smaller than 32, greater than 1024, or is not a power of two.
-    #[rustfmt::skip]
     pub fn new(
         num_queries: usize,
         blowup_factor: usize,
@@ -100,33 +193,178 @@ impl ProofOptions {
         fri_folding_factor: usize,
         fri_max_remainder_size: usize,
     ) -> ProofOptions {
-        // TODO: return errors instead of panicking
-        assert!(num_queries > 0, "number of queries must be greater than 0");
-        assert!(num_queries <= 128, "number of queries cannot be greater than 128");
+        ProofOptionsBuilder::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor,
+            fri_max_remainder_size,
+        )
+        .allow_insecure()
+        .build()
+        .unwrap_or_else(|err| panic!("invalid proof options: {err}"))
+    }
 
-        assert!(blowup_factor.is_power_of_two(), "blowup factor must be a power of 2");
-        assert!(blowup_factor >= Self::MIN_BLOWUP_FACTOR,
-            "blowup factor cannot be smaller than {}", Self::MIN_BLOWUP_FACTOR);
-        assert!(blowup_factor <= 128, "blowup factor cannot be greater than 128");
+    /// Returns these options with the zero-knowledge mode enabled.
+    ///
+    /// In zero-knowledge mode, the prover randomizes all polynomials whose evaluations are
+    /// revealed in a proof, and salts all commitments to these evaluations:
+    /// * A trace polynomial $T(x)$ of degree $n - 1$, where $n$ is the trace length, is replaced
+    ///   by $T(x) + Z(x) \cdot r(x)$, where $Z(x)$ is the vanishing polynomial of the trace
+    ///   domain and $r(x)$ is a random polynomial of degree $m - n - 1$. The randomized
+    ///   polynomial has the same values over the trace domain, but its degree is $m - 1$, where
+    ///   $m$ is the trace polynomial size returned by [ProofOptions::trace_poly_size()].
+    /// * The constraint composition polynomial $H(x)$ is split into columns $H_i(x)$ of degree
+    ///   $n - 1$ such that $H(x) = \sum_i x^{i \cdot n} \cdot H_i(x)$, and every pair of
+    ///   adjacent columns is randomized by a random polynomial $r_i(x)$ of degree $m - n - 1$:
+    ///   $x^n \cdot r_i(x)$ is added to column $i$ and $r_i(x)$ is subtracted from column $i + 1$.
+    ///   This keeps the sum unchanged while making the values of every column random. The
+    ///   columns are evaluated at the out-of-domain point $z$ (rather than at $z^k$).
+    /// * An additional random polynomial of degree $m - 1$ is committed to together with the
+    ///   composition columns, and is included into the DEEP composition polynomial; this masks
+    ///   the values of the DEEP composition polynomial revealed by FRI.
+    /// * Every leaf of the Merkle trees committing to the execution trace segments and to the
+    ///   constraint evaluations is set to `merge(hash(row), salt)`, where the salt is a digest
+    ///   of fresh randomness. Salts of the queried leaves are included in the proof, and the
+    ///   verifier incorporates them into leaf hashing.
+    ///
+    /// The randomized polynomials are revealed at the queried positions and at the out-of-domain
+    /// points only. Thus, they hide the execution trace as long as the number of random
+    /// coefficients $m - n$ is at least the number of trace rows in an evaluation frame
+    /// multiplied by the number of revealed values, i.e., the larger of the numbers of trace and
+    /// constraint queries plus the extension degree of the field. The trace polynomial size is
+    /// chosen such that this holds for frames of up to two rows;
+    /// [AirContext::set_frame_offsets()](crate::AirContext::set_frame_offsets) makes sure it
+    /// holds for larger frames as well.
+    ///
+    /// Enabling this mode has the following overhead:
+    /// * Trace polynomials have degree $m - 1 \geq 2n - 1$, and thus, the low-degree extension
+    ///   domain, the committed trace and constraint evaluations, and the FFTs computed by the
+    ///   prover are at least twice as large as without zero-knowledge. For the same reason, the
+    ///   constraint evaluation domain may need to be larger, and thus, computations with
+    ///   constraints of degree 3 or higher may require a higher blowup factor.
+    /// * The degree of the constraint composition polynomial grows with the degree of trace
+    ///   polynomials, and thus, the polynomial is split into at least twice as many columns;
+    ///   the random polynomial is committed to as an additional column. Each column adds a value
+    ///   per query and an out-of-domain evaluation to the proof.
+    /// * The proof grows by one digest per query for each trace segment and for the constraint
+    ///   evaluations; for example, a single-segment trace with 28 queries and a 32-byte digest
+    ///   adds 1792 bytes. FRI proofs grow as well because the low-degree extension domain is
+    ///   larger.
+    /// * Proof generation is no longer deterministic.
+    ///
+    /// Randomness is drawn from a random number generator seeded by the operating system; thus,
+    /// proofs in zero-knowledge mode can be generated only when the prover is compiled with the
+    /// `std` feature enabled.
+    pub fn with_zero_knowledge(mut self) -> Self {
+        self.zero_knowledge = true;
+        self
+    }
 
-        assert!(grinding_factor <= 32, "grinding factor cannot be greater than 32");
+    /// Returns these options with the execution trace defined over a coset of the trace domain
+    /// with the specified `offset`.
+    ///
+    /// By default, the $i$th row of an execution trace of length $n$ is the evaluation of trace
+    /// polynomials at $g^i$, where $g$ is the generator of the multiplicative subgroup of size
+    /// $n$. With an offset $h$, the rows are the evaluations at $h \cdot g^i$ instead, and
+    /// thus, the vanishing polynomial of the trace domain becomes $(x / h)^n - 1$. This can be
+    /// used to make sure that the points at which the trace polynomials of this proof are
+    /// defined do not collide with the domain of another proof system.
+    ///
+    /// The offset is converted into an element of the base field of the computation via
+    /// `From<u64>` conversion. It must not place the trace domain inside the low-degree extension
+    /// domain; this is checked when an [AirContext](crate::AirContext) is instantiated with these
+    /// options.
+    ///
+    /// # Panics
+    /// Panics if `offset` is zero.
+    pub fn with_trace_domain_offset(mut self, offset: u64) -> Self {
+        assert_ne!(offset, 0, "trace domain offset cannot be zero");
+        self.trace_domain_offset = offset;
+        self
+    }
 
-        assert!(fri_folding_factor.is_power_of_two(), "FRI folding factor must be a power of 2");
-        assert!(fri_folding_factor >= 4, "FRI folding factor cannot be smaller than 4");
-        assert!(fri_folding_factor <= 16, "FRI folding factor cannot be greater than 16");
+    /// Returns these options with the public coin instantiated as the specified transcript type.
+    ///
+    /// Both the prover and the verifier instantiate the public coin based on the transcript type
+    /// specified in the proof context; thus, a proof generated with one transcript type does not
+    /// verify against options with a different transcript type. See [TranscriptType] for
+    /// details.
+    pub fn with_transcript_type(mut self, transcript_type: TranscriptType) -> Self {
+        self.transcript_type = transcript_type;
+        self
+    }
 
-        assert!(fri_max_remainder_size.is_power_of_two(), "FRI max remainder size must be a power of 2");
-        assert!(fri_max_remainder_size >= 32, "FRI max remainder size cannot be smaller than 32");
-        assert!(fri_max_remainder_size <= 1024, "FRI max remainder size cannot be greater than 1024");
+    /// Returns these options with the minimum field security set to the specified number of
+    /// `bits`.
+    ///
+    /// The security offered by the field is bounded by `log2(|F|) - log2(lde_domain_size)`,
+    /// where `F` is the field in which the composition polynomial is constructed (see
+    /// [ProofOptions::field_security()]). Unlike the query parameters, this bound does not depend
+    /// on the options alone, and thus, it is checked by the prover before a proof is generated;
+    /// the prover refuses to generate proofs for which the field provides fewer than the
+    /// specified number of bits.
+    ///
+    /// The minimum is serialized together with the options, but it is not trusted by the
+    /// verifier: the verifier enforces its own minimum, which is set to
+    /// [ProofOptions::DEFAULT_MIN_FIELD_SECURITY] bits unless the verifier is invoked with a
+    /// different one explicitly.
+    ///
+    /// By default, the minimum is set to [ProofOptions::DEFAULT_MIN_FIELD_SECURITY] bits. Thus,
+    /// for ~64-bit fields, proofs can be generated only with a field extension.
+    ///
+    /// # Panics
+    /// Panics if `bits` is greater than [ProofOptions::MAX_MIN_FIELD_SECURITY].
+    pub fn with_min_field_security(mut self, bits: u32) -> Self {
+        assert!(
+            bits <= Self::MAX_MIN_FIELD_SECURITY,
+            "minimum field security cannot be greater than {} bits, but was {bits}",
+            Self::MAX_MIN_FIELD_SECURITY
+        );
+        self.min_field_security = bits as u8;
+        self
+    }
 
-        ProofOptions {
-            num_queries: num_queries as u8,
-            blowup_factor: blowup_factor as u8,
-            grinding_factor: grinding_factor as u8,
+    /// Returns these options with the minimum field security check disabled.
+    ///
+    /// This allows generating proofs in small fields without a field extension (e.g., for
+    /// testing purposes); the security of such proofs is limited by the size of the field (see
+    /// [ProofOptions::field_security()]), and they should not be relied upon in production. Such
+    /// proofs are rejected by the verifier unless its own minimum is lowered as well.
+    pub fn allow_insecure_field(self) -> Self {
+        self.with_min_field_security(0)
+    }
+
+    /// Returns a new instance of [ProofOptions] struct constructed from the specified parameters.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * `num_queries` is zero or greater than 128.
+    /// * `blowup_factor` is smaller than 2, greater than 128, or is not a power of two.
+    /// * `grinding_factor` is greater than 32.
+    /// * `fri_folding_factor` is not 2, 4, 8, or 16.
+    /// * `fri_max_remainder_size` is smaller than 32, greater than 1024, or is not a power of two.
+    /// * The conjectured security level implied by `num_queries`, `blowup_factor`, and
+    ///   `grinding_factor` is smaller than [ProofOptions::MIN_CONJECTURED_SECURITY] bits. To
+    ///   construct such options anyway, use [ProofOptionsBuilder::allow_insecure()].
+    pub fn try_new(
+        num_queries: usize,
+        blowup_factor: usize,
+        grinding_factor: u32,
+        field_extension: FieldExtension,
+        fri_folding_factor: usize,
+        fri_max_remainder_size: usize,
+    ) -> Result<ProofOptions, ProofOptionsError> {
+        ProofOptionsBuilder::new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
             field_extension,
-            fri_folding_factor: fri_folding_factor as u8,
-            fri_max_remainder_size: fri_max_remainder_size.trailing_zeros() as u8,
-        }
+            fri_folding_factor,
+            fri_max_remainder_size,
+        )
+        .build()
     }
 
     // PUBLIC ACCESSORS
@@ -134,11 +372,35 @@ impl ProofOptions {
 
     /// Returns number of queries for a STARK proof.
     ///
+    /// This is the number of positions at which both the execution trace and the constraint
+    /// evaluations are opened, i.e., the smaller of [ProofOptions::num_trace_queries()] and
+    /// [ProofOptions::num_constraint_queries()]. The DEEP composition polynomial is evaluated
+    /// and FRI layers are queried at these positions. When the two numbers are equal (which is
+    /// the case for options built with [ProofOptions::new()]), this is simply the number of
+    /// queries.
+    ///
     /// This directly impacts proof soundness as each additional query adds roughly
     /// `log2(blowup_factor)` bits of security to a proof. However, each additional query also
     /// increases proof size.
     pub fn num_queries(&self) -> usize {
-        self.num_queries as usize
+        cmp::min(self.num_trace_queries, self.num_constraint_queries) as usize
+    }
+
+    /// Returns the number of positions at which the execution trace is opened in a STARK proof.
+    ///
+    /// Each opened position adds a row and a Merkle authentication path for every trace segment
+    /// to a proof; for wide traces, these openings dominate proof size. Positions beyond
+    /// [ProofOptions::num_queries()] do not contribute to proof soundness.
+    pub fn num_trace_queries(&self) -> usize {
+        self.num_trace_queries as usize
+    }
+
+    /// Returns the number of positions at which the constraint evaluations are opened in a STARK
+    /// proof.
+    ///
+    /// Positions beyond [ProofOptions::num_queries()] do not contribute to proof soundness.
+    pub fn num_constraint_queries(&self) -> usize {
+        self.num_constraint_queries as usize
     }
 
     /// Returns trace blowup factor for a STARK proof.
@@ -151,6 +413,45 @@ impl ProofOptions {
         self.blowup_factor as usize
     }
 
+    /// Returns the maximum length of an execution trace which can be proven with these options
+    /// in the field `B`.
+    ///
+    /// The low-degree extension domain must be a multiplicative subgroup of `B`, and thus, the
+    /// trace polynomial size (see [ProofOptions::trace_poly_size()]) multiplied by the blowup
+    /// factor cannot exceed [MAX_DOMAIN_SIZE](StarkField::MAX_DOMAIN_SIZE) of `B`.
+    pub fn max_trace_length<B: StarkField>(&self) -> usize {
+        let max_poly_size = B::MAX_DOMAIN_SIZE / self.blowup_factor();
+        if self.zero_knowledge {
+            // the largest power of two n such that n + num_randomizer_coefficients does not
+            // exceed max_poly_size
+            (max_poly_size.saturating_sub(self.num_randomizer_coefficients()) + 1)
+                .next_power_of_two()
+                / 2
+        } else {
+            max_poly_size
+        }
+    }
+
+    /// Returns the number of coefficients of trace polynomials for an execution trace of the
+    /// specified length.
+    ///
+    /// Without zero-knowledge, this is equal to `trace_length`. In zero-knowledge mode, trace
+    /// polynomials are randomized (see [ProofOptions::with_zero_knowledge()]), and this is the
+    /// smallest power of two which is greater than or equal to `trace_length` plus
+    /// `2 * (q + e)`, where `q` is the larger of the numbers of trace and constraint queries and
+    /// `e` is the extension degree of the field; thus, it is always at least twice the trace
+    /// length. The low-degree extension domain is `blowup_factor` times larger than this.
+    pub fn trace_poly_size(&self, trace_length: usize) -> usize {
+        if self.zero_knowledge {
+            trace_length
+                .saturating_add(self.num_randomizer_coefficients())
+                .checked_next_power_of_two()
+                .unwrap_or(usize::MAX)
+        } else {
+            trace_length
+        }
+    }
+
     /// Returns query seed grinding factor for a STARK proof.
     ///
     /// Grinding applies Proof-of-Work/ to the query position seed. An honest prover needs to
@@ -171,31 +472,140 @@ impl ProofOptions {
         self.field_extension
     }
 
+    /// Returns the factor by which the degree of a polynomial is reduced with each FRI layer.
+    pub fn fri_folding_factor(&self) -> usize {
+        self.fri_folding_factor as usize
+    }
+
+    /// Returns the maximum size of the remainder (the last FRI layer).
+    ///
+    /// FRI folding stops as soon as the size of a layer does not exceed this value, and the
+    /// remainder is sent to the verifier in full; the verifier interpolates the remainder and
+    /// checks its degree directly. Thus, larger values result in fewer FRI layers (and fewer
+    /// authentication paths in the proof) at the expense of a larger remainder.
+    pub fn fri_max_remainder_size(&self) -> usize {
+        2usize.pow(self.fri_max_remainder_size as u32)
+    }
+
+    /// Returns `true` if proofs generated with these options are zero-knowledge.
+    ///
+    /// See [ProofOptions::with_zero_knowledge()] for details.
+    pub fn is_zero_knowledge(&self) -> bool {
+        self.zero_knowledge
+    }
+
     /// Returns the offset by which the low-degree extension domain is shifted in relation to the
-    /// trace domain.
+    /// multiplicative subgroup of the same size.
     ///
     /// Currently, this is hard-coded to the primitive element of the underlying base field.
     pub fn domain_offset<B: StarkField>(&self) -> B {
         B::GENERATOR
     }
 
+    /// Returns the offset of the coset over which the execution trace is defined.
+    ///
+    /// This is one by default, in which case the trace is defined over the multiplicative
+    /// subgroup of the trace length size. See [ProofOptions::with_trace_domain_offset()] for
+    /// details.
+    pub fn trace_domain_offset<B: StarkField>(&self) -> B {
+        B::from(self.trace_domain_offset)
+    }
+
+    /// Returns the type of the transcript used to instantiate the public coin of the protocol.
+    ///
+    /// This is [TranscriptType::Legacy] by default.
+    pub fn transcript_type(&self) -> TranscriptType {
+        self.transcript_type
+    }
+
+    /// Returns the minimum number of bits of security which the field must provide for a proof
+    /// generated with these options; zero means that the check is disabled.
+    ///
+    /// See [ProofOptions::with_min_field_security()] for details.
+    pub fn min_field_security(&self) -> u32 {
+        self.min_field_security as u32
+    }
+
+    /// Returns the number of bits of security offered by the field for a proof with a low-degree
+    /// extension domain of the specified size, when the base field has `base_field_bits` bits.
+    ///
+    /// This is computed as `base_field_bits * extension_degree - log2(lde_domain_size)`; the
+    /// conjectured security level of a proof cannot exceed this value (see
+    /// [StarkProof::security_level()](crate::proof::StarkProof::security_level)).
+    pub fn field_security(&self, base_field_bits: u32, lde_domain_size: usize) -> u32 {
+        let field_size = base_field_bits * self.field_extension().degree();
+        field_size.saturating_sub(lde_domain_size.trailing_zeros())
+    }
+
+    /// Returns the conjectured security level (in bits) implied by these options.
+    ///
+    /// This is computed as `num_queries * log2(blowup_factor) + grinding_factor`, and does not
+    /// take into account the size of the field or the collision resistance of the hash function
+    /// used by the protocol; both of these can only lower the actual security level of a proof.
+    pub fn conjectured_security(&self) -> u32 {
+        get_conjectured_security(
+            self.num_queries(),
+            self.blowup_factor(),
+            self.grinding_factor(),
+        )
+    }
+
     /// Returns options for FRI protocol instantiated with parameters from this proof options.
     pub fn to_fri_options(&self) -> FriOptions {
-        let folding_factor = self.fri_folding_factor as usize;
-        let max_remainder_size = 2usize.pow(self.fri_max_remainder_size as u32);
-        FriOptions::new(self.blowup_factor(), folding_factor, max_remainder_size)
+        FriOptions::new(
+            self.blowup_factor(),
+            self.fri_folding_factor(),
+            self.fri_max_remainder_size(),
+        )
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the minimum number of random coefficients by which trace polynomials are extended
+    /// in zero-knowledge mode.
+    ///
+    /// A trace polynomial is revealed at every queried position and at the out-of-domain point
+    /// (which contributes one value per coefficient of an extension field element) for each row
+    /// of an evaluation frame; this allows for frames of two rows.
+    fn num_randomizer_coefficients(&self) -> usize {
+        let num_queries = cmp::max(self.num_trace_queries(), self.num_constraint_queries());
+        2 * (num_queries + self.field_extension().degree() as usize)
     }
 }
 
 impl Serializable for ProofOptions {
     /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// When the number of trace queries is equal to the number of constraint queries, a single
+    /// byte is written for both. Otherwise, a zero byte (which is not a valid number of queries)
+    /// is followed by the number of trace queries and the number of constraint queries.
+    ///
+    /// The zero-knowledge flag is written as a byte of flags. When the minimum field security
+    /// differs from [ProofOptions::DEFAULT_MIN_FIELD_SECURITY], the second bit of this byte is
+    /// set, and the flags are followed by the minimum field security; thus, options with the
+    /// default minimum are serialized in the same way as before the minimum was introduced.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_u8(self.num_queries);
+        if self.num_trace_queries == self.num_constraint_queries {
+            target.write_u8(self.num_trace_queries);
+        } else {
+            target.write_u8(0);
+            target.write_u8(self.num_trace_queries);
+            target.write_u8(self.num_constraint_queries);
+        }
         target.write_u8(self.blowup_factor);
         target.write_u8(self.grinding_factor);
         target.write(self.field_extension);
         target.write_u8(self.fri_folding_factor);
         target.write_u8(self.fri_max_remainder_size);
+        if self.min_field_security() == Self::DEFAULT_MIN_FIELD_SECURITY {
+            target.write_u8(self.zero_knowledge as u8);
+        } else {
+            target.write_u8(self.zero_knowledge as u8 | MIN_FIELD_SECURITY_FLAG);
+            target.write_u8(self.min_field_security);
+        }
+        target.write_u64(self.trace_domain_offset);
+        target.write(self.transcript_type);
     }
 }
 
@@ -205,14 +615,307 @@ impl Deserializable for ProofOptions {
     /// # Errors
     /// Returns an error of a valid proof options could not be read from the specified `source`.
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        Ok(ProofOptions::new(
-            source.read_u8()? as usize,
-            source.read_u8()? as usize,
-            source.read_u8()? as u32,
-            FieldExtension::read_from(source)?,
-            source.read_u8()? as usize,
-            2usize.pow(source.read_u8()? as u32),
-        ))
+        // different numbers of trace and constraint queries are written after a zero byte; equal
+        // numbers must be written as a single byte
+        let (num_trace_queries, num_constraint_queries) = match source.read_u8()? {
+            0 => {
+                let num_trace_queries = source.read_u8()? as usize;
+                let num_constraint_queries = source.read_u8()? as usize;
+                if num_trace_queries == num_constraint_queries {
+                    return Err(DeserializationError::InvalidValue(
+                        "equal numbers of trace and constraint queries must not be serialized \
+                        separately"
+                            .to_string(),
+                    ));
+                }
+                (num_trace_queries, num_constraint_queries)
+            }
+            num_queries => (num_queries as usize, num_queries as usize),
+        };
+        let blowup_factor = source.read_u8()? as usize;
+        let grinding_factor = source.read_u8()? as u32;
+        let field_extension = FieldExtension::read_from(source)?;
+        let fri_folding_factor = source.read_u8()? as usize;
+        let fri_max_remainder_size = source.read_u8()?;
+        if fri_max_remainder_size >= usize::BITS as u8 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "FRI max remainder size of 2^{fri_max_remainder_size} is not supported"
+            )));
+        }
+        let flags = source.read_u8()?;
+        if flags & !(ZERO_KNOWLEDGE_FLAG | MIN_FIELD_SECURITY_FLAG) != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "value {flags} cannot be deserialized as proof option flags"
+            )));
+        }
+        let zero_knowledge = flags & ZERO_KNOWLEDGE_FLAG != 0;
+        let min_field_security = if flags & MIN_FIELD_SECURITY_FLAG != 0 {
+            let bits = source.read_u8()? as u32;
+            if bits == ProofOptions::DEFAULT_MIN_FIELD_SECURITY {
+                return Err(DeserializationError::InvalidValue(
+                    "default minimum field security must not be serialized explicitly".to_string(),
+                ));
+            }
+            bits
+        } else {
+            ProofOptions::DEFAULT_MIN_FIELD_SECURITY
+        };
+        let trace_domain_offset = source.read_u64()?;
+        let transcript_type = TranscriptType::read_from(source)?;
+
+        // security level of the options is not enforced here; it is up to the verifier to decide
+        // whether a proof generated with the deserialized options is acceptable
+        let mut builder = ProofOptionsBuilder::new(
+            num_trace_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor,
+            2usize.pow(fri_max_remainder_size as u32),
+        )
+        .num_constraint_queries(num_constraint_queries)
+        .allow_insecure();
+        if zero_knowledge {
+            builder = builder.zero_knowledge();
+        }
+        builder
+            .trace_domain_offset(trace_domain_offset)
+            .transcript_type(transcript_type)
+            .min_field_security(min_field_security)
+            .build()
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// PROOF OPTIONS BUILDER
+// ================================================================================================
+
+/// A builder for [ProofOptions] which validates the specified parameters.
+///
+/// By default, the builder rejects parameters which imply a conjectured security level below
+/// [ProofOptions::MIN_CONJECTURED_SECURITY] bits. This check can be disabled by calling
+/// [ProofOptionsBuilder::allow_insecure()], which may be useful for testing purposes.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProofOptionsBuilder {
+    num_trace_queries: usize,
+    num_constraint_queries: usize,
+    blowup_factor: usize,
+    grinding_factor: u32,
+    field_extension: FieldExtension,
+    fri_folding_factor: usize,
+    fri_max_remainder_size: usize,
+    allow_insecure: bool,
+    zero_knowledge: bool,
+    trace_domain_offset: u64,
+    transcript_type: TranscriptType,
+    min_field_security: u32,
+}
+
+impl ProofOptionsBuilder {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new builder initialized with the specified parameters. The parameters are not
+    /// validated until [ProofOptionsBuilder::build()] is called.
+    ///
+    /// `num_queries` is used as both the number of trace queries and the number of constraint
+    /// queries.
+    pub fn new(
+        num_queries: usize,
+        blowup_factor: usize,
+        grinding_factor: u32,
+        field_extension: FieldExtension,
+        fri_folding_factor: usize,
+        fri_max_remainder_size: usize,
+    ) -> Self {
+        ProofOptionsBuilder {
+            num_trace_queries: num_queries,
+            num_constraint_queries: num_queries,
+            blowup_factor,
+            grinding_factor,
+            field_extension,
+            fri_folding_factor,
+            fri_max_remainder_size,
+            allow_insecure: false,
+            zero_knowledge: false,
+            trace_domain_offset: 1,
+            transcript_type: TranscriptType::Legacy,
+            min_field_security: ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+        }
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+    /// Sets both the number of trace queries and the number of constraint queries for the options
+    /// being built.
+    pub fn num_queries(mut self, num_queries: usize) -> Self {
+        self.num_trace_queries = num_queries;
+        self.num_constraint_queries = num_queries;
+        self
+    }
+
+    /// Sets the number of positions at which the execution trace is opened for the options being
+    /// built.
+    ///
+    /// See [ProofOptions::num_trace_queries()] for details.
+    pub fn num_trace_queries(mut self, num_queries: usize) -> Self {
+        self.num_trace_queries = num_queries;
+        self
+    }
+
+    /// Sets the number of positions at which the constraint evaluations are opened for the
+    /// options being built.
+    ///
+    /// See [ProofOptions::num_constraint_queries()] for details.
+    pub fn num_constraint_queries(mut self, num_queries: usize) -> Self {
+        self.num_constraint_queries = num_queries;
+        self
+    }
+
+    /// Allows building options which provide less than [ProofOptions::MIN_CONJECTURED_SECURITY]
+    /// bits of conjectured security.
+    ///
+    /// Proofs generated with such options should not be relied upon in production.
+    pub fn allow_insecure(mut self) -> Self {
+        self.allow_insecure = true;
+        self
+    }
+
+    /// Enables the zero-knowledge mode for the options being built.
+    ///
+    /// See [ProofOptions::with_zero_knowledge()] for details.
+    pub fn zero_knowledge(mut self) -> Self {
+        self.zero_knowledge = true;
+        self
+    }
+
+    /// Sets the offset of the coset over which the execution trace is defined for the options
+    /// being built.
+    ///
+    /// See [ProofOptions::with_trace_domain_offset()] for details.
+    pub fn trace_domain_offset(mut self, offset: u64) -> Self {
+        self.trace_domain_offset = offset;
+        self
+    }
+
+    /// Sets the type of the transcript used to instantiate the public coin for the options being
+    /// built.
+    ///
+    /// See [ProofOptions::with_transcript_type()] for details.
+    pub fn transcript_type(mut self, transcript_type: TranscriptType) -> Self {
+        self.transcript_type = transcript_type;
+        self
+    }
+
+    /// Sets the minimum number of bits of security which the field must provide for proofs
+    /// generated with the options being built.
+    ///
+    /// See [ProofOptions::with_min_field_security()] for details.
+    pub fn min_field_security(mut self, bits: u32) -> Self {
+        self.min_field_security = bits;
+        self
+    }
+
+    /// Disables the minimum field security check for the options being built.
+    ///
+    /// See [ProofOptions::allow_insecure_field()] for details.
+    pub fn allow_insecure_field(self) -> Self {
+        self.min_field_security(0)
+    }
+
+    // BUILDER
+    // --------------------------------------------------------------------------------------------
+    /// Validates the parameters of this builder and returns the resulting [ProofOptions].
+    ///
+    /// # Errors
+    /// Returns an error describing the first parameter which is out of range, or
+    /// [ProofOptionsError::InsufficientSecurity] if the parameters provide less than
+    /// [ProofOptions::MIN_CONJECTURED_SECURITY] bits of conjectured security and insecure
+    /// options were not allowed.
+    pub fn build(self) -> Result<ProofOptions, ProofOptionsError> {
+        for num_queries in [self.num_trace_queries, self.num_constraint_queries] {
+            if num_queries == 0 {
+                return Err(ProofOptionsError::NumQueriesTooSmall(num_queries));
+            }
+            if num_queries > ProofOptions::MAX_NUM_QUERIES {
+                return Err(ProofOptionsError::NumQueriesTooLarge(num_queries));
+            }
+        }
+
+        if !self.blowup_factor.is_power_of_two() {
+            return Err(ProofOptionsError::BlowupFactorNotPowerOfTwo(
+                self.blowup_factor,
+            ));
+        }
+        if self.blowup_factor < ProofOptions::MIN_BLOWUP_FACTOR {
+            return Err(ProofOptionsError::BlowupFactorTooSmall(self.blowup_factor));
+        }
+        if self.blowup_factor > ProofOptions::MAX_BLOWUP_FACTOR {
+            return Err(ProofOptionsError::BlowupFactorTooLarge(self.blowup_factor));
+        }
+
+        if self.grinding_factor > ProofOptions::MAX_GRINDING_FACTOR {
+            return Err(ProofOptionsError::GrindingFactorTooLarge(
+                self.grinding_factor,
+            ));
+        }
+
+        if !matches!(self.fri_folding_factor, 2 | 4 | 8 | 16) {
+            return Err(ProofOptionsError::UnsupportedFriFoldingFactor(
+                self.fri_folding_factor,
+            ));
+        }
+
+        if !self.fri_max_remainder_size.is_power_of_two() {
+            return Err(ProofOptionsError::FriMaxRemainderSizeNotPowerOfTwo(
+                self.fri_max_remainder_size,
+            ));
+        }
+        if self.fri_max_remainder_size < 32 {
+            return Err(ProofOptionsError::FriMaxRemainderSizeTooSmall(
+                self.fri_max_remainder_size,
+            ));
+        }
+        if self.fri_max_remainder_size > 1024 {
+            return Err(ProofOptionsError::FriMaxRemainderSizeTooLarge(
+                self.fri_max_remainder_size,
+            ));
+        }
+
+        if self.trace_domain_offset == 0 {
+            return Err(ProofOptionsError::ZeroTraceDomainOffset);
+        }
+
+        if self.min_field_security > ProofOptions::MAX_MIN_FIELD_SECURITY {
+            return Err(ProofOptionsError::MinFieldSecurityTooLarge(
+                self.min_field_security,
+            ));
+        }
+
+        // only the positions at which both the trace and the constraint evaluations are opened
+        // contribute to soundness
+        let num_queries = cmp::min(self.num_trace_queries, self.num_constraint_queries);
+        let security =
+            get_conjectured_security(num_queries, self.blowup_factor, self.grinding_factor);
+        if !self.allow_insecure && security < ProofOptions::MIN_CONJECTURED_SECURITY {
+            return Err(ProofOptionsError::InsufficientSecurity(
+                security,
+                ProofOptions::MIN_CONJECTURED_SECURITY,
+            ));
+        }
+
+        Ok(ProofOptions {
+            num_trace_queries: self.num_trace_queries as u8,
+            num_constraint_queries: self.num_constraint_queries as u8,
+            blowup_factor: self.blowup_factor as u8,
+            grinding_factor: self.grinding_factor as u8,
+            field_extension: self.field_extension,
+            fri_folding_factor: self.fri_folding_factor as u8,
+            fri_max_remainder_size: self.fri_max_remainder_size.trailing_zeros() as u8,
+            zero_knowledge: self.zero_knowledge,
+            trace_domain_offset: self.trace_domain_offset,
+            transcript_type: self.transcript_type,
+            min_field_security: self.min_field_security as u8,
+        })
     }
 }
 
@@ -231,6 +934,56 @@ impl FieldExtension {
             Self::None => 1,
             Self::Quadratic => 2,
             Self::Cubic => 3,
+            Self::Sextic => 6,
+        }
+    }
+
+    /// Returns `true` if this field extension is implemented for the base field `B`.
+    ///
+    /// For example, cubic and sextic extensions are not implemented for the 128-bit field, as the
+    /// quadratic extension already provides sufficient security level.
+    pub fn is_supported_by<B>(&self) -> bool
+    where
+        B: StarkField + ExtensibleField<2> + ExtensibleField<3> + ExtensibleField<6>,
+    {
+        match self {
+            Self::None => true,
+            Self::Quadratic => <B as ExtensibleField<2>>::is_supported(),
+            Self::Cubic => <B as ExtensibleField<3>>::is_supported(),
+            Self::Sextic => <B as ExtensibleField<6>>::is_supported(),
+        }
+    }
+}
+
+impl fmt::Display for FieldExtension {
+    /// Writes the name of this field extension; the name can be parsed back using [FromStr].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "none"),
+            Self::Quadratic => write!(f, "quadratic"),
+            Self::Cubic => write!(f, "cubic"),
+            Self::Sextic => write!(f, "sextic"),
+        }
+    }
+}
+
+impl FromStr for FieldExtension {
+    type Err = ProofOptionsError;
+
+    /// Parses a field extension from its name (e.g., "quadratic") or from its degree (e.g., "2");
+    /// names are case-insensitive.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let is = |name: &str, degree: &str| value.eq_ignore_ascii_case(name) || value == degree;
+        if is("none", "1") {
+            Ok(Self::None)
+        } else if is("quadratic", "2") {
+            Ok(Self::Quadratic)
+        } else if is("cubic", "3") {
+            Ok(Self::Cubic)
+        } else if is("sextic", "6") {
+            Ok(Self::Sextic)
+        } else {
+            Err(ProofOptionsError::UnknownFieldExtension(value.to_string()))
         }
     }
 }
@@ -249,9 +1002,580 @@ impl Deserializable for FieldExtension {
             1 => Ok(FieldExtension::None),
             2 => Ok(FieldExtension::Quadratic),
             3 => Ok(FieldExtension::Cubic),
+            6 => Ok(FieldExtension::Sextic),
             value => Err(DeserializationError::InvalidValue(format!(
                 "value {value} cannot be deserialized as FieldExtension enum"
             ))),
         }
     }
 }
+
+// TRANSCRIPT TYPE IMPLEMENTATION
+// ================================================================================================
+
+impl Serializable for TranscriptType {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl Deserializable for TranscriptType {
+    /// Reads a transcript type enum from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(TranscriptType::Legacy),
+            1 => Ok(TranscriptType::Labeled),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "value {value} cannot be deserialized as TranscriptType enum"
+            ))),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of bits of conjectured security implied by the specified query parameters.
+fn get_conjectured_security(num_queries: usize, blowup_factor: usize, grinding_factor: u32) -> u32 {
+    num_queries as u32 * log2(blowup_factor) + grinding_factor
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        FieldExtension, ProofOptions, ProofOptionsBuilder, ProofOptionsError, TranscriptType,
+    };
+    use math::{
+        fields::{f128::BaseElement, f62, f64},
+        FieldElement, StarkField,
+    };
+    use utils::{
+        string::ToString, Deserializable, DeserializationError, Serializable, SliceReader,
+    };
+
+    fn try_build(
+        num_queries: usize,
+        blowup_factor: usize,
+        grinding_factor: u32,
+        fri_folding_factor: usize,
+        fri_max_remainder_size: usize,
+    ) -> Result<ProofOptions, ProofOptionsError> {
+        ProofOptions::try_new(
+            num_queries,
+            blowup_factor,
+            grinding_factor,
+            FieldExtension::None,
+            fri_folding_factor,
+            fri_max_remainder_size,
+        )
+    }
+
+    #[test]
+    fn try_new_valid_options() {
+        let options = try_build(32, 8, 0, 4, 256).unwrap();
+        assert_eq!(32, options.num_queries());
+        assert_eq!(8, options.blowup_factor());
+        assert_eq!(0, options.grinding_factor());
+        assert_eq!(96, options.conjectured_security());
+        assert_eq!(
+            ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256),
+            options
+        );
+
+        for folding_factor in [2, 4, 8, 16] {
+            let options = try_build(32, 8, 0, folding_factor, 256).unwrap();
+            assert_eq!(folding_factor, options.fri_folding_factor());
+            assert_eq!(folding_factor, options.to_fri_options().folding_factor());
+        }
+
+        for max_remainder_size in [32, 64, 256, 1024] {
+            let options = try_build(32, 8, 0, 4, max_remainder_size).unwrap();
+            assert_eq!(max_remainder_size, options.fri_max_remainder_size());
+            let fri_options = options.to_fri_options();
+            assert_eq!(max_remainder_size, fri_options.max_remainder_size());
+        }
+    }
+
+    #[test]
+    fn try_new_invalid_num_queries() {
+        assert_eq!(
+            Err(ProofOptionsError::NumQueriesTooSmall(0)),
+            try_build(0, 8, 0, 4, 256)
+        );
+        assert_eq!(
+            Err(ProofOptionsError::NumQueriesTooLarge(129)),
+            try_build(129, 8, 0, 4, 256)
+        );
+    }
+
+    #[test]
+    fn separate_query_counts() {
+        let builder = ProofOptionsBuilder::new(32, 8, 0, FieldExtension::None, 4, 256);
+
+        // (trace queries, constraint queries, queries, conjectured security)
+        let cases = [
+            (32, 32, 32, 96),
+            (16, 32, 16, 48),
+            (32, 16, 16, 48),
+            (1, 128, 1, 3),
+            (128, 1, 1, 3),
+        ];
+        for (num_trace_queries, num_constraint_queries, num_queries, security) in cases {
+            let options = builder
+                .clone()
+                .num_trace_queries(num_trace_queries)
+                .num_constraint_queries(num_constraint_queries)
+                .allow_insecure()
+                .build()
+                .unwrap();
+            assert_eq!(num_trace_queries, options.num_trace_queries());
+            assert_eq!(num_constraint_queries, options.num_constraint_queries());
+            assert_eq!(num_queries, options.num_queries());
+            assert_eq!(security, options.conjectured_security());
+        }
+
+        // setting the number of queries sets both numbers
+        let options = builder
+            .clone()
+            .num_trace_queries(16)
+            .num_queries(20)
+            .build()
+            .unwrap();
+        assert_eq!(20, options.num_trace_queries());
+        assert_eq!(20, options.num_constraint_queries());
+        assert_eq!(
+            ProofOptions::new(20, 8, 0, FieldExtension::None, 4, 256),
+            options
+        );
+
+        // both numbers are validated, and the security level is checked against the smaller one
+        assert_eq!(
+            Err(ProofOptionsError::NumQueriesTooSmall(0)),
+            builder.clone().num_trace_queries(0).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::NumQueriesTooLarge(129)),
+            builder.clone().num_constraint_queries(129).build()
+        );
+        assert_eq!(
+            Err(ProofOptionsError::InsufficientSecurity(30, 32)),
+            builder.num_trace_queries(10).build()
+        );
+    }
+
+    #[test]
+    fn serialize_separate_query_counts() {
+        // equal numbers are serialized into a single byte, as before the numbers could differ
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        let bytes = options.to_bytes();
+        assert_eq!([28, 8], bytes[..2]);
+
+        // different numbers are serialized after a zero byte
+        for (num_trace_queries, num_constraint_queries) in [(28, 20), (20, 28), (1, 128)] {
+            let options = ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 256)
+                .num_trace_queries(num_trace_queries)
+                .num_constraint_queries(num_constraint_queries)
+                .allow_insecure()
+                .build()
+                .unwrap();
+            let split_bytes = options.to_bytes();
+            assert_eq!(bytes.len() + 2, split_bytes.len());
+            assert_eq!(
+                [0, num_trace_queries as u8, num_constraint_queries as u8, 8],
+                split_bytes[..4]
+            );
+            let result = ProofOptions::read_from(&mut SliceReader::new(&split_bytes)).unwrap();
+            assert_eq!(options, result);
+        }
+
+        // a zero number of queries cannot be deserialized, and equal numbers must not be
+        // serialized separately
+        let mut bytes = [&[0, 28, 28], &bytes[1..]].concat();
+        assert!(matches!(
+            ProofOptions::read_from(&mut SliceReader::new(&bytes)),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+        bytes[2] = 0;
+        assert!(matches!(
+            ProofOptions::read_from(&mut SliceReader::new(&bytes)),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
+
+    #[test]
+    fn try_new_invalid_blowup_factor() {
+        assert_eq!(
+            Err(ProofOptionsError::BlowupFactorNotPowerOfTwo(0)),
+            try_build(32, 0, 0, 4, 256)
+        );
+        assert_eq!(
+            Err(ProofOptionsError::BlowupFactorNotPowerOfTwo(6)),
+            try_build(32, 6, 0, 4, 256)
+        );
+        assert_eq!(
+            Err(ProofOptionsError::BlowupFactorTooSmall(1)),
+            try_build(32, 1, 0, 4, 256)
+        );
+        assert_eq!(
+            Err(ProofOptionsError::BlowupFactorTooLarge(256)),
+            try_build(32, 256, 0, 4, 256)
+        );
+    }
+
+    #[test]
+    fn try_new_invalid_grinding_factor() {
+        assert_eq!(
+            Err(ProofOptionsError::GrindingFactorTooLarge(33)),
+            try_build(32, 8, 33, 4, 256)
+        );
+    }
+
+    #[test]
+    fn try_new_invalid_fri_folding_factor() {
+        for folding_factor in [0, 1, 3, 5, 32] {
+            assert_eq!(
+                Err(ProofOptionsError::UnsupportedFriFoldingFactor(
+                    folding_factor
+                )),
+                try_build(32, 8, 0, folding_factor, 256)
+            );
+        }
+    }
+
+    #[test]
+    fn try_new_invalid_fri_max_remainder_size() {
+        assert_eq!(
+            Err(ProofOptionsError::FriMaxRemainderSizeNotPowerOfTwo(100)),
+            try_build(32, 8, 0, 4, 100)
+        );
+        assert_eq!(
+            Err(ProofOptionsError::FriMaxRemainderSizeTooSmall(16)),
+            try_build(32, 8, 0, 4, 16)
+        );
+        assert_eq!(
+            Err(ProofOptionsError::FriMaxRemainderSizeTooLarge(2048)),
+            try_build(32, 8, 0, 4, 2048)
+        );
+    }
+
+    #[test]
+    fn try_new_insufficient_security() {
+        // 10 queries with blowup factor 2 and no grinding give 10 bits of security
+        assert_eq!(
+            Err(ProofOptionsError::InsufficientSecurity(10, 32)),
+            try_build(10, 2, 0, 4, 256)
+        );
+
+        // 31 bits of security is still not enough
+        assert_eq!(
+            Err(ProofOptionsError::InsufficientSecurity(31, 32)),
+            try_build(10, 8, 1, 4, 256)
+        );
+
+        // 32 bits is the minimum accepted level
+        assert!(try_build(10, 8, 2, 4, 256).is_ok());
+
+        // insecure options can be built explicitly
+        let options = ProofOptionsBuilder::new(10, 2, 0, FieldExtension::None, 4, 256)
+            .allow_insecure()
+            .build()
+            .unwrap();
+        assert_eq!(10, options.conjectured_security());
+
+        // allowing insecure options does not disable range checks
+        let result = ProofOptionsBuilder::new(0, 2, 0, FieldExtension::None, 4, 256)
+            .allow_insecure()
+            .build();
+        assert_eq!(Err(ProofOptionsError::NumQueriesTooSmall(0)), result);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_invalid_options() {
+        ProofOptions::new(32, 3, 0, FieldExtension::None, 4, 256);
+    }
+
+    #[test]
+    fn deserialize_invalid_options() {
+        let options = ProofOptions::new(4, 2, 0, FieldExtension::Quadratic, 8, 128);
+        let mut bytes = options.to_bytes();
+        let result = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(options, result);
+
+        // set blowup factor to 3
+        bytes[1] = 3;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+
+        // set FRI max remainder size to 2^64
+        bytes[1] = 2;
+        bytes[5] = 64;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+
+        // set option flags to an invalid value
+        bytes[5] = 7;
+        bytes[6] = 4;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+    }
+
+    #[test]
+    fn zero_knowledge_options() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        assert!(!options.is_zero_knowledge());
+
+        let zk_options = options.clone().with_zero_knowledge();
+        assert!(zk_options.is_zero_knowledge());
+        assert_ne!(options, zk_options);
+        assert_eq!(
+            options.conjectured_security(),
+            zk_options.conjectured_security()
+        );
+
+        // trace polynomials are extended by at least 2 * (28 + 1) random coefficients
+        assert_eq!(64, options.trace_poly_size(64));
+        assert_eq!(128, zk_options.trace_poly_size(64));
+        assert_eq!(128, zk_options.trace_poly_size(8));
+        assert_eq!(2048, zk_options.trace_poly_size(1024));
+        let options = ProofOptions::new(64, 8, 0, FieldExtension::Quadratic, 4, 256);
+        assert_eq!(256, options.with_zero_knowledge().trace_poly_size(64));
+
+        // the flag is preserved by serialization, and is built by the builder as well
+        let bytes = zk_options.to_bytes();
+        let result = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(zk_options, result);
+        let built = ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 256)
+            .zero_knowledge()
+            .build()
+            .unwrap();
+        assert_eq!(zk_options, built);
+    }
+
+    #[test]
+    fn trace_domain_offset_options() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        assert_eq!(
+            BaseElement::ONE,
+            options.trace_domain_offset::<BaseElement>()
+        );
+
+        let shifted_options = options.clone().with_trace_domain_offset(7);
+        assert_eq!(
+            BaseElement::new(7),
+            shifted_options.trace_domain_offset::<BaseElement>()
+        );
+        assert_ne!(options, shifted_options);
+
+        // the offset is preserved by serialization, and is built by the builder as well
+        let mut bytes = shifted_options.to_bytes();
+        let result = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(shifted_options, result);
+        let built = ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 256)
+            .trace_domain_offset(7)
+            .build()
+            .unwrap();
+        assert_eq!(shifted_options, built);
+
+        // zero offset is rejected
+        let result = ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 256)
+            .trace_domain_offset(0)
+            .build();
+        assert_eq!(Err(ProofOptionsError::ZeroTraceDomainOffset), result);
+        bytes[7..].fill(0);
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+    }
+
+    #[test]
+    fn transcript_type_options() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        assert_eq!(TranscriptType::Legacy, options.transcript_type());
+
+        let labeled_options = options
+            .clone()
+            .with_transcript_type(TranscriptType::Labeled);
+        assert_eq!(TranscriptType::Labeled, labeled_options.transcript_type());
+        assert_ne!(options, labeled_options);
+
+        // the transcript type is preserved by serialization, and is built by the builder as well
+        let mut bytes = labeled_options.to_bytes();
+        let result = ProofOptions::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(labeled_options, result);
+        let built = ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 256)
+            .transcript_type(TranscriptType::Labeled)
+            .build()
+            .unwrap();
+        assert_eq!(labeled_options, built);
+
+        // unknown transcript types are rejected
+        let last = bytes.len() - 1;
+        bytes[last] = 2;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&bytes)).is_err());
+    }
+
+    #[test]
+    fn min_field_security_options() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        assert_eq!(
+            ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+            options.min_field_security()
+        );
+
+        let insecure_options = options.clone().allow_insecure_field();
+        assert_eq!(0, insecure_options.min_field_security());
+        assert_ne!(options, insecure_options);
+
+        // options with the default minimum are serialized as before the minimum was introduced;
+        // other minimums add a single byte, and are preserved by serialization
+        let bytes = options.to_bytes();
+        assert_eq!(0, bytes[6]);
+        for bits in [0, 100, ProofOptions::MAX_MIN_FIELD_SECURITY] {
+            let custom_options = options.clone().with_min_field_security(bits);
+            let custom_bytes = custom_options.to_bytes();
+            assert_eq!(bytes.len() + 1, custom_bytes.len());
+            let result = ProofOptions::read_from(&mut SliceReader::new(&custom_bytes)).unwrap();
+            assert_eq!(custom_options, result);
+        }
+        let zk_options = insecure_options.clone().with_zero_knowledge();
+        let zk_bytes = zk_options.to_bytes();
+        assert_eq!([3, 0], zk_bytes[6..8]);
+        let result = ProofOptions::read_from(&mut SliceReader::new(&zk_bytes)).unwrap();
+        assert_eq!(zk_options, result);
+
+        // the minimum is built by the builder as well
+        let built = ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 256)
+            .allow_insecure_field()
+            .build()
+            .unwrap();
+        assert_eq!(insecure_options, built);
+        let result = ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 256)
+            .min_field_security(256)
+            .build();
+        assert_eq!(
+            Err(ProofOptionsError::MinFieldSecurityTooLarge(256)),
+            result
+        );
+
+        // the default minimum must not be serialized explicitly
+        let mut custom_bytes = options.clone().with_min_field_security(100).to_bytes();
+        custom_bytes[7] = ProofOptions::DEFAULT_MIN_FIELD_SECURITY as u8;
+        assert!(ProofOptions::read_from(&mut SliceReader::new(&custom_bytes)).is_err());
+    }
+
+    #[test]
+    fn field_security_thresholds() {
+        let f62_bits = f62::BaseElement::MODULUS_BITS;
+        let f64_bits = f64::BaseElement::MODULUS_BITS;
+        let f128_bits = BaseElement::MODULUS_BITS;
+
+        // with the default minimum of 80 bits, ~64-bit fields require an extension for traces of
+        // any length; a quadratic extension is sufficient for all domains supported by the fields
+        assert_eq!(
+            None,
+            max_lde_domain_size(f62_bits, FieldExtension::None, 80)
+        );
+        assert_eq!(
+            None,
+            max_lde_domain_size(f64_bits, FieldExtension::None, 80)
+        );
+        let max_domain_size = max_lde_domain_size(f62_bits, FieldExtension::Quadratic, 80);
+        assert_eq!(Some(1 << 44), max_domain_size);
+        assert!(f62::BaseElement::MAX_DOMAIN_SIZE < max_domain_size.unwrap());
+        let max_domain_size = max_lde_domain_size(f64_bits, FieldExtension::Quadratic, 80);
+        assert_eq!(Some(1 << 48), max_domain_size);
+        assert!(f64::BaseElement::MAX_DOMAIN_SIZE < max_domain_size.unwrap());
+
+        // the 128-bit field never requires an extension with the default minimum
+        let max_domain_size = max_lde_domain_size(f128_bits, FieldExtension::None, 80);
+        assert_eq!(Some(1 << 48), max_domain_size);
+        assert!(BaseElement::MAX_DOMAIN_SIZE < max_domain_size.unwrap());
+
+        // with a minimum of 100 bits, a quadratic extension of the 62-bit field is sufficient for
+        // domains of up to 2^24 elements (i.e., traces of up to 2^21 rows with blowup factor 8),
+        // and a cubic extension is required for larger domains
+        let max_domain_size = max_lde_domain_size(f62_bits, FieldExtension::Quadratic, 100);
+        assert_eq!(Some(1 << 24), max_domain_size);
+        let max_domain_size = max_lde_domain_size(f62_bits, FieldExtension::Cubic, 100);
+        assert!(f62::BaseElement::MAX_DOMAIN_SIZE <= max_domain_size.unwrap());
+
+        // in the 64-bit field, the threshold is at 2^28 elements
+        let max_domain_size = max_lde_domain_size(f64_bits, FieldExtension::Quadratic, 100);
+        assert_eq!(Some(1 << 28), max_domain_size);
+        let max_domain_size = max_lde_domain_size(f64_bits, FieldExtension::Cubic, 100);
+        assert!(f64::BaseElement::MAX_DOMAIN_SIZE <= max_domain_size.unwrap());
+
+        // in the 128-bit field, an extension is required for domains larger than 2^28 elements
+        let max_domain_size = max_lde_domain_size(f128_bits, FieldExtension::None, 100);
+        assert_eq!(Some(1 << 28), max_domain_size);
+        let max_domain_size = max_lde_domain_size(f128_bits, FieldExtension::Quadratic, 100);
+        assert!(BaseElement::MAX_DOMAIN_SIZE <= max_domain_size.unwrap());
+    }
+
+    /// Returns the largest low-degree extension domain for which a field with the specified
+    /// number of bits and extension provides at least `min_field_security` bits of security.
+    fn max_lde_domain_size(
+        base_field_bits: u32,
+        field_extension: FieldExtension,
+        min_field_security: u32,
+    ) -> Option<usize> {
+        let options = ProofOptions::new(28, 8, 0, field_extension, 4, 256)
+            .with_min_field_security(min_field_security);
+        (1..usize::BITS)
+            .map(|log_size| 1 << log_size)
+            .take_while(|&size| {
+                options.field_security(base_field_bits, size) >= options.min_field_security()
+            })
+            .last()
+    }
+
+    #[test]
+    fn field_extension_names() {
+        for extension in [
+            FieldExtension::None,
+            FieldExtension::Quadratic,
+            FieldExtension::Cubic,
+            FieldExtension::Sextic,
+        ] {
+            assert_eq!(Ok(extension), extension.to_string().parse());
+            assert_eq!(Ok(extension), extension.degree().to_string().parse());
+        }
+        assert_eq!(Ok(FieldExtension::Quadratic), "Quadratic".parse());
+
+        let err = "quartic".parse::<FieldExtension>().unwrap_err();
+        assert_eq!(
+            ProofOptionsError::UnknownFieldExtension("quartic".to_string()),
+            err
+        );
+        assert!(err
+            .to_string()
+            .contains("none, quadratic, cubic, and sextic"));
+
+        // only the quadratic extension is implemented for the 128-bit field
+        assert!(FieldExtension::Quadratic.is_supported_by::<BaseElement>());
+        assert!(!FieldExtension::Cubic.is_supported_by::<BaseElement>());
+        assert!(!FieldExtension::Sextic.is_supported_by::<BaseElement>());
+        assert!(FieldExtension::Cubic.is_supported_by::<f64::BaseElement>());
+    }
+
+    #[test]
+    fn max_trace_length() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        assert_eq!(1 << 37, options.max_trace_length::<BaseElement>());
+        assert_eq!(1 << 36, options.max_trace_length::<f62::BaseElement>());
+        assert_eq!(1 << 29, options.max_trace_length::<f64::BaseElement>());
+
+        let options = ProofOptions::new(28, 2, 0, FieldExtension::None, 4, 256);
+        assert_eq!(1 << 39, options.max_trace_length::<BaseElement>());
+        assert_eq!(1 << 38, options.max_trace_length::<f62::BaseElement>());
+        assert_eq!(1 << 31, options.max_trace_length::<f64::BaseElement>());
+
+        // in zero-knowledge mode, trace polynomials are at least twice as large as the trace
+        let options = options.with_zero_knowledge();
+        assert_eq!(1 << 38, options.max_trace_length::<BaseElement>());
+        assert_eq!(1 << 30, options.max_trace_length::<f64::BaseElement>());
+        let max_trace_length = options.max_trace_length::<f64::BaseElement>();
+        assert_eq!(1 << 31, options.trace_poly_size(max_trace_length));
+        assert_eq!(1 << 32, options.trace_poly_size(2 * max_trace_length));
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    read_optional_queries, read_pow_nonce, write_optional_queries, Commitments, Context, OodFrame,
+    Queries,
+};
+use crate::{ProofOptions, TraceInfo, TraceLayout};
+use fri::FriProof;
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
+
+// BATCH PROOF
+// ================================================================================================
+/// A proof of several instances of the same computation which share a single FRI proof.
+///
+/// All instances of the computation are executed against the same trace info and proof
+/// options, which are described by a single proof [Context]. Each instance is proven by its own
+/// [InstanceProof] containing the commitments to the execution trace and to the constraint
+/// evaluations of the instance, the decommitments of these at the queried positions, and the
+/// out-of-domain evaluation frame. DEEP composition polynomials of all instances are combined
+/// into a single polynomial using powers of a random coefficient, and the low-degree proof for
+/// this polynomial is shared by all instances; all instances are also queried at the same
+/// positions.
+///
+/// Thus, a batch proof of $n$ instances is smaller than $n$ separate proofs by the size of
+/// $n - 1$ FRI proofs, and verifying it requires only a single execution of the FRI verifier.
+///
+/// # Security
+/// A batch proof is valid only if every one of its instances is valid; there is no way to
+/// verify some of the instances independently of the others.
+///
+/// The public inputs of all instances are absorbed into the public coin before any commitments
+/// are made, and the commitments of each instance are absorbed before any randomness is drawn for
+/// the following instance. The random coefficient $\gamma$ used to combine DEEP composition
+/// polynomials is drawn only after the out-of-domain frames of all instances have been absorbed;
+/// the combined polynomial is then $\sum_{i=0}^{n-1} \gamma^i \cdot D_i(x)$. If any of the
+/// polynomials $D_i$ is far from a polynomial of the expected degree, the combined polynomial is
+/// also far from such polynomial except with probability at most $(n - 1) / |\mathbb{F}|$, where
+/// $\mathbb{F}$ is the field from which $\gamma$ is drawn. Thus, batching reduces the soundness
+/// of the field-size component of a proof by up to $\log_2 n$ bits, while the query component of
+/// the soundness is the same as for a proof of a single instance with the same proof options.
+/// For small fields, this makes use of a field extension even more important.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BatchProof {
+    /// Basic metadata about the execution of the computation shared by all instances.
+    pub context: Context,
+    /// Parts of the proof specific to each instance of the computation.
+    pub instances: Vec<InstanceProof>,
+    /// Commitments to the FRI layers of the combined DEEP composition polynomial.
+    pub fri_commitments: Commitments,
+    /// Low-degree proof for the combined DEEP composition polynomial.
+    pub fri_proof: FriProof,
+    /// Proof-of-work nonce for query seed grinding.
+    pub pow_nonce: u64,
+}
+
+impl BatchProof {
+    /// Largest number of instances which can be described by a batch proof; the number of
+    /// instances is serialized as a 16-bit integer.
+    pub const MAX_NUM_INSTANCES: usize = u16::MAX as usize;
+
+    /// Returns STARK protocol parameters used to generate this proof.
+    pub fn options(&self) -> &ProofOptions {
+        self.context.options()
+    }
+
+    /// Returns a layout describing how columns of the execution traces of all instances are
+    /// arranged into segments.
+    pub fn trace_layout(&self) -> &TraceLayout {
+        self.context.trace_layout()
+    }
+
+    /// Returns trace length of all instances described by this proof.
+    pub fn trace_length(&self) -> usize {
+        self.context.trace_length()
+    }
+
+    /// Returns trace info of all instances described by this proof.
+    pub fn get_trace_info(&self) -> TraceInfo {
+        self.context.get_trace_info()
+    }
+
+    /// Returns the size of the LDE domain for the computation described by this proof.
+    pub fn lde_domain_size(&self) -> usize {
+        self.context.lde_domain_size()
+    }
+
+    /// Returns the number of instances of the computation described by this proof.
+    pub fn num_instances(&self) -> usize {
+        self.instances.len()
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this proof into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::new();
+        self.write_into(&mut result);
+        result
+    }
+
+    /// Returns a batch proof read from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error of a valid batch proof could not be read from the specified `source`, or
+    /// if the `source` contains bytes beyond the end of the proof.
+    pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let proof = Self::read_from(&mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(proof)
+    }
+}
+
+impl Serializable for BatchProof {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.context.write_into(target);
+        assert!(self.instances.len() <= Self::MAX_NUM_INSTANCES);
+        target.write_u16(self.instances.len() as u16);
+        for instance in self.instances.iter() {
+            instance.write_into(target);
+        }
+        self.fri_commitments.write_into(target);
+        self.fri_proof.write_into(target);
+        target.write_u64(self.pow_nonce);
+    }
+}
+
+impl Deserializable for BatchProof {
+    /// Reads a batch proof from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error of a valid batch proof could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let version = Context::read_version(source)?;
+        let context = Context::read_unversioned_from(source)?;
+
+        let num_trace_commitments = context.trace_layout().num_trace_commitments();
+        let num_instances = source.read_u16()? as usize;
+        let mut instances = Vec::with_capacity(num_instances);
+        for _ in 0..num_instances {
+            instances.push(InstanceProof::read_from(
+                source,
+                num_trace_commitments,
+                version,
+            )?);
+        }
+
+        Ok(BatchProof {
+            context,
+            instances,
+            fri_commitments: Commitments::read_from(source)?,
+            fri_proof: FriProof::read_from(source)?,
+            pow_nonce: read_pow_nonce(source, version)?,
+        })
+    }
+}
+
+// INSTANCE PROOF
+// ================================================================================================
+/// Part of a [BatchProof] specific to a single instance of the computation.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InstanceProof {
+    /// Commitments to the execution trace segments and to the constraint composition polynomial
+    /// evaluations of this instance; unlike commitments of a [StarkProof](super::StarkProof),
+    /// these do not include commitments to FRI layers.
+    pub commitments: Commitments,
+    /// Decommitments of extended execution trace values (for all trace segments) at positions
+    /// queried by the verifier.
+    pub trace_queries: Vec<Queries>,
+    /// Decommitments of constraint composition polynomial evaluations at positions queried by
+    /// the verifier.
+    pub constraint_queries: Queries,
+    /// Decommitments of periodic column evaluations at positions queried by the verifier; this
+    /// is set only for computations which commit to their periodic columns.
+    pub periodic_queries: Option<Queries>,
+    /// Trace and constraint polynomial evaluations at the out-of-domain point of this instance.
+    pub ood_frame: OodFrame,
+}
+
+impl InstanceProof {
+    /// Reads an instance proof with the specified number of trace commitments serialized using
+    /// the specified `version` of the proof serialization format from the `source`.
+    fn read_from<R: ByteReader>(
+        source: &mut R,
+        num_trace_commitments: usize,
+        version: u8,
+    ) -> Result<Self, DeserializationError> {
+        let commitments = Commitments::read_from(source)?;
+        let mut trace_queries = Vec::with_capacity(num_trace_commitments);
+        for _ in 0..num_trace_commitments {
+            trace_queries.push(Queries::read_from(source)?);
+        }
+
+        Ok(InstanceProof {
+            commitments,
+            trace_queries,
+            constraint_queries: Queries::read_from(source)?,
+            periodic_queries: read_optional_queries(source)?,
+            ood_frame: OodFrame::read_versioned_from(source, version)?,
+        })
+    }
+}
+
+impl Serializable for InstanceProof {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.commitments.write_into(target);
+        self.trace_queries.write_into(target);
+        self.constraint_queries.write_into(target);
+        write_optional_queries(&self.periodic_queries, target);
+        self.ood_frame.write_into(target);
+    }
+}
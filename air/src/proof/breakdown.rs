@@ -0,0 +1,106 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+use utils::{collections::Vec, ByteWriter, Serializable};
+
+// PROOF SIZE BREAKDOWN
+// ================================================================================================
+/// Sizes (in bytes) of the serialized components of a [StarkProof](super::StarkProof).
+///
+/// A breakdown is returned by [size_breakdown()](super::StarkProof::size_breakdown) function.
+/// The sizes of all components add up to the size of the serialized proof, which is returned by
+/// [total()](ProofSizeBreakdown::total).
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ProofSizeBreakdown {
+    /// Size of the proof context.
+    pub context: usize,
+    /// Size of the commitments made during the commit phase of the protocol.
+    pub commitments: usize,
+    /// Size of the trace queries, for each trace commitment (i.e., for each trace segment, or for
+    /// each round of an auxiliary segment built in several rounds).
+    pub trace_queries: Vec<usize>,
+    /// Size of the constraint composition polynomial queries.
+    pub constraint_queries: usize,
+    /// Size of the periodic column queries; this includes a single byte flag indicating whether
+    /// the proof contains such queries.
+    pub periodic_queries: usize,
+    /// Size of the out-of-domain frame.
+    pub ood_frame: usize,
+    /// Size of each layer of the FRI proof.
+    pub fri_layers: Vec<usize>,
+    /// Size of the FRI remainder.
+    pub fri_remainder: usize,
+    /// Size of the entire FRI proof; this includes the layers, the remainder, and some metadata.
+    pub fri_proof: usize,
+    /// Size of the proof-of-work nonce.
+    pub pow_nonce: usize,
+}
+
+impl ProofSizeBreakdown {
+    /// Returns the total size of the proof in bytes.
+    pub fn total(&self) -> usize {
+        self.context
+            + self.commitments
+            + self.trace_queries.iter().sum::<usize>()
+            + self.constraint_queries
+            + self.periodic_queries
+            + self.ood_frame
+            + self.fri_proof
+            + self.pow_nonce
+    }
+}
+
+impl fmt::Display for ProofSizeBreakdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_row(f, "context", self.context)?;
+        write_row(f, "commitments", self.commitments)?;
+        for (i, &size) in self.trace_queries.iter().enumerate() {
+            write_row(f, &format!("trace queries [{i}]"), size)?;
+        }
+        write_row(f, "constraint queries", self.constraint_queries)?;
+        write_row(f, "periodic queries", self.periodic_queries)?;
+        write_row(f, "OOD frame", self.ood_frame)?;
+        write_row(f, "FRI proof", self.fri_proof)?;
+        for (i, &size) in self.fri_layers.iter().enumerate() {
+            write_row(f, &format!("  layer [{i}]"), size)?;
+        }
+        write_row(f, "  remainder", self.fri_remainder)?;
+        write_row(f, "PoW nonce", self.pow_nonce)?;
+        write!(f, "{:<24}{:>10}", "total", self.total())
+    }
+}
+
+impl Serializable for ProofSizeBreakdown {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.context as u32);
+        target.write_u32(self.commitments as u32);
+        target.write_u8(self.trace_queries.len() as u8);
+        for &size in self.trace_queries.iter() {
+            target.write_u32(size as u32);
+        }
+        target.write_u32(self.constraint_queries as u32);
+        target.write_u32(self.periodic_queries as u32);
+        target.write_u32(self.ood_frame as u32);
+        target.write_u8(self.fri_layers.len() as u8);
+        for &size in self.fri_layers.iter() {
+            target.write_u32(size as u32);
+        }
+        target.write_u32(self.fri_remainder as u32);
+        target.write_u32(self.fri_proof as u32);
+        target.write_u32(self.pow_nonce as u32);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes a single line of the breakdown table with the label left-aligned and the size
+/// right-aligned.
+fn write_row(f: &mut fmt::Formatter<'_>, label: &str, size: usize) -> fmt::Result {
+    writeln!(f, "{label:<24}{size:>10}")
+}
@@ -16,7 +16,7 @@ use utils::{
 ///
 /// These commitments include:
 /// * Commitment to the extended execution trace, which may include commitments to one or more
-///   execution trace segments.
+///   execution trace segments, and to each round of auxiliary segments built in several rounds.
 /// * Commitment to the evaluations of constraint composition polynomial over LDE domain.
 /// * Commitments to the evaluations of polynomials at all FRI layers.
 ///
@@ -65,13 +65,13 @@ impl Commitments {
     #[allow(clippy::type_complexity)]
     pub fn parse<H: Hasher>(
         self,
-        num_trace_segments: usize,
+        num_trace_commitments: usize,
         num_fri_layers: usize,
     ) -> Result<(Vec<H::Digest>, H::Digest, Vec<H::Digest>), DeserializationError> {
         let mut reader = SliceReader::new(&self.0);
 
         // parse trace commitments
-        let trace_commitments = H::Digest::read_batch_from(&mut reader, num_trace_segments)?;
+        let trace_commitments = H::Digest::read_batch_from(&mut reader, num_trace_commitments)?;
 
         // parse constraint evaluation commitment:
         let constraint_commitment = H::Digest::read_from(&mut reader)?;
@@ -85,6 +85,46 @@ impl Commitments {
         }
         Ok((trace_commitments, constraint_commitment, fri_commitments))
     }
+
+    /// Parses the serialized commitments of a single instance of a
+    /// [BatchProof](super::BatchProof) into distinct parts.
+    ///
+    /// The parts are the same as the first two parts returned by [parse()](Commitments::parse);
+    /// commitments of an instance do not include commitments to FRI layers.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes stored in self could not be parsed into the requested number
+    /// of commitments, or if there are any unconsumed bytes remaining after the parsing completes.
+    pub fn parse_instance<H: Hasher>(
+        self,
+        num_trace_commitments: usize,
+    ) -> Result<(Vec<H::Digest>, H::Digest), DeserializationError> {
+        let mut reader = SliceReader::new(&self.0);
+        let trace_commitments = H::Digest::read_batch_from(&mut reader, num_trace_commitments)?;
+        let constraint_commitment = H::Digest::read_from(&mut reader)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok((trace_commitments, constraint_commitment))
+    }
+
+    /// Parses the serialized FRI layer commitments of a [BatchProof](super::BatchProof).
+    ///
+    /// # Errors
+    /// Returns an error if the bytes stored in self could not be parsed into commitments to the
+    /// specified number of FRI layers and the FRI remainder, or if there are any unconsumed bytes
+    /// remaining after the parsing completes.
+    pub fn parse_fri_layers<H: Hasher>(
+        self,
+        num_fri_layers: usize,
+    ) -> Result<Vec<H::Digest>, DeserializationError> {
+        let mut reader = SliceReader::new(&self.0);
+        let fri_commitments = H::Digest::read_batch_from(&mut reader, num_fri_layers + 1)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(fri_commitments)
+    }
 }
 
 impl Serializable for Commitments {
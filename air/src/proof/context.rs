@@ -5,6 +5,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{ProofOptions, TraceInfo, TraceLayout};
+use crypto::{Digest, Hasher};
 use math::StarkField;
 use utils::{
     collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
@@ -14,6 +15,11 @@ use utils::{
 // PROOF CONTEXT
 // ================================================================================================
 /// Basic metadata about a specific execution of a computation.
+///
+/// A serialized context starts with the version of the serialization format
+/// ([Context::VERSION]); the remaining fields are encoded the same way in all format versions,
+/// and the same way as in the unversioned format used before versioning was introduced.
+/// Contexts in the unversioned format can be read via [Context::read_unversioned_from()].
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Context {
     trace_layout: TraceLayout,
@@ -21,23 +27,60 @@ pub struct Context {
     trace_meta: Vec<u8>,
     field_modulus_bytes: Vec<u8>,
     options: ProofOptions,
+    commitment_hash_id: [u8; 4],
+    coin_hash_id: [u8; 4],
 }
 
 impl Context {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Version of the serialization format of proof contexts (and thus, of STARK proofs) written
+    /// by this crate.
+    pub const VERSION: u8 = 2;
+
+    /// Oldest version of the serialization format which can still be read by this crate.
+    pub const MIN_SUPPORTED_VERSION: u8 = 1;
+
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new context for a computation described by the specified field, trace info, and
     /// proof options.
-    pub fn new<B: StarkField>(trace_info: &TraceInfo, options: ProofOptions) -> Self {
+    ///
+    /// The context records fingerprints of hash function `H` used to commit to the execution trace,
+    /// constraint evaluations, and FRI layers, and of hash function `C` used by the public coin
+    /// (see [Context::hash_fingerprint()]).
+    pub fn new<B: StarkField, H: Hasher, C: Hasher>(
+        trace_info: &TraceInfo,
+        options: ProofOptions,
+    ) -> Self {
         Context {
             trace_layout: trace_info.layout().clone(),
             trace_length: trace_info.length(),
             trace_meta: trace_info.meta().to_vec(),
             field_modulus_bytes: B::get_modulus_le_bytes(),
             options,
+            commitment_hash_id: Self::hash_fingerprint::<H>(),
+            coin_hash_id: Self::hash_fingerprint::<C>(),
         }
     }
 
+    /// Returns a 4-byte fingerprint of hash function `H`.
+    ///
+    /// The fingerprint is computed by XOR-folding the digest of an empty input into 4 bytes. Hash
+    /// functions do not carry explicit identifiers; the fingerprint identifies a hash function
+    /// well enough for detecting proofs generated with a different hash function than the one
+    /// used for verification. Since digests are padded to 32 bytes, hash functions which differ
+    /// only in their output length (e.g., BLAKE3 with 192-bit and 256-bit outputs) have
+    /// different fingerprints.
+    pub fn hash_fingerprint<H: Hasher>() -> [u8; 4] {
+        let mut result = [0; 4];
+        for (i, byte) in H::hash(&[]).as_bytes().iter().enumerate() {
+            result[i % 4] ^= byte;
+        }
+        result
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -62,8 +105,11 @@ impl Context {
     }
 
     /// Returns the size of the LDE domain for the computation described by this context.
+    ///
+    /// This is the trace polynomial size (see [ProofOptions::trace_poly_size()]) multiplied by
+    /// the blowup factor.
     pub fn lde_domain_size(&self) -> usize {
-        self.trace_length() * self.options.blowup_factor()
+        self.options.trace_poly_size(self.trace_length()) * self.options.blowup_factor()
     }
 
     /// Returns modulus of the field for the computation described by this context.
@@ -92,11 +138,40 @@ impl Context {
     pub fn options(&self) -> &ProofOptions {
         &self.options
     }
-}
 
-impl Serializable for Context {
-    /// Serializes `self` and writes the resulting bytes into the `target`.
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+    /// Returns the fingerprint of the hash function used for commitments in this context.
+    pub fn commitment_hash_id(&self) -> [u8; 4] {
+        self.commitment_hash_id
+    }
+
+    /// Returns the fingerprint of the hash function used by the public coin in this context.
+    pub fn coin_hash_id(&self) -> [u8; 4] {
+        self.coin_hash_id
+    }
+
+    // UNVERSIONED SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads the version of the serialization format from the specified `source`.
+    ///
+    /// # Errors
+    /// Returns an error if the version is older than [Context::MIN_SUPPORTED_VERSION] or newer
+    /// than [Context::VERSION].
+    pub(crate) fn read_version<R: ByteReader>(source: &mut R) -> Result<u8, DeserializationError> {
+        let version = source.read_u8()?;
+        if !(Self::MIN_SUPPORTED_VERSION..=Self::VERSION).contains(&version) {
+            return Err(DeserializationError::UnsupportedVersion(version));
+        }
+        Ok(version)
+    }
+
+    /// Serializes `self` without the format version and writes the resulting bytes into the
+    /// `target`.
+    ///
+    /// This is the encoding used before versioning was introduced. It is also the encoding of the
+    /// context absorbed into the seed of the public coin; this way, the transcript of a proof does
+    /// not depend on the version of the format in which the proof is serialized.
+    pub fn write_unversioned_into<W: ByteWriter>(&self, target: &mut W) {
         self.trace_layout.write_into(target);
         target.write_u8(math::log2(self.trace_length) as u8); // store as power of two
         target.write_u16(self.trace_meta.len() as u16);
@@ -105,15 +180,18 @@ impl Serializable for Context {
         target.write_u8(self.field_modulus_bytes.len() as u8);
         target.write_u8_slice(&self.field_modulus_bytes);
         self.options.write_into(target);
+        target.write_u8_slice(&self.commitment_hash_id);
+        target.write_u8_slice(&self.coin_hash_id);
     }
-}
 
-impl Deserializable for Context {
-    /// Reads proof context from the specified `source` and returns the result.
+    /// Reads proof context serialized without the format version (e.g., by a crate version
+    /// released before versioning was introduced) from the specified `source`.
     ///
     /// # Errors
-    /// Returns an error of a valid Context struct could not be read from the specified `source`.
-    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+    /// Returns an error if a valid Context struct could not be read from the specified `source`.
+    pub fn read_unversioned_from<R: ByteReader>(
+        source: &mut R,
+    ) -> Result<Self, DeserializationError> {
         // read and validate trace layout info
         let trace_layout = TraceLayout::read_from(source)?;
 
@@ -126,6 +204,13 @@ impl Deserializable for Context {
                 trace_length
             )));
         }
+        if trace_length as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "trace length cannot be greater than 2^{}, but was 2^{}",
+                usize::BITS - 1,
+                trace_length
+            )));
+        }
         let trace_length = 2_usize.pow(trace_length as u32);
 
         // read trace metadata
@@ -148,12 +233,179 @@ impl Deserializable for Context {
         // read options
         let options = ProofOptions::read_from(source)?;
 
+        // read hash function fingerprints
+        let commitment_hash_id = source.read_u8_array()?;
+        let coin_hash_id = source.read_u8_array()?;
+
         Ok(Context {
             trace_layout,
             trace_length,
             trace_meta,
             field_modulus_bytes,
             options,
+            commitment_hash_id,
+            coin_hash_id,
         })
     }
 }
+
+impl Serializable for Context {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    ///
+    /// The bytes start with the version of the serialization format, followed by the bytes
+    /// written by [Context::write_unversioned_into()].
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(Self::VERSION);
+        self.write_unversioned_into(target);
+    }
+}
+
+impl Deserializable for Context {
+    /// Reads proof context from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if the context was serialized using a format version which is not
+    /// supported (see [Context::MIN_SUPPORTED_VERSION]), or if a valid Context struct could not be
+    /// read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_version(source)?;
+        Self::read_unversioned_from(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Context, ProofOptions, TraceInfo, TraceLayout};
+    use crate::FieldExtension;
+    use crypto::hashers::{Blake3_192, Blake3_256, Sha3_256};
+    use math::fields::f64::BaseElement;
+    use utils::{
+        collections::Vec, Deserializable, DeserializationError, Serializable, SliceReader,
+    };
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    #[test]
+    fn context_serialization_wide_trace() {
+        let layout = TraceLayout::new(300, [40, 0], [4, 0]);
+        let trace_info = TraceInfo::new_multi_segment(layout, 1024, vec![1, 2, 3]);
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        let context = Context::new::<BaseElement, Blake3, Blake3>(&trace_info, options);
+
+        let bytes = context.to_bytes();
+        let result = Context::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(context, result);
+        assert_eq!(340, result.get_trace_info().width());
+    }
+
+    #[test]
+    fn context_serialization_with_meta() {
+        let layout = TraceLayout::new(4, [0, 0], [0, 0]);
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+
+        let trace_info = TraceInfo::new_multi_segment(layout.clone(), 1024, vec![7; 32]);
+        let context = Context::new::<BaseElement, Blake3, Blake3>(&trace_info, options.clone());
+        let bytes = context.to_bytes();
+        let result = Context::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(context, result);
+        assert_eq!(&[7; 32], result.get_trace_info().meta());
+
+        // contexts which differ only in metadata must serialize differently, so that the
+        // metadata is bound to the public coin seed
+        let trace_info = TraceInfo::new_multi_segment(layout, 1024, vec![8; 32]);
+        let other = Context::new::<BaseElement, Blake3, Blake3>(&trace_info, options);
+        assert_ne!(bytes, other.to_bytes());
+    }
+
+    #[test]
+    fn context_deserialization_oversized_trace() {
+        let layout = TraceLayout::new(4, [0, 0], [0, 0]);
+        let trace_info = TraceInfo::new_multi_segment(layout.clone(), 1024, vec![]);
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        let mut bytes =
+            Context::new::<BaseElement, Blake3, Blake3>(&trace_info, options).to_bytes();
+
+        // trace length is stored as a power of two right after the format version and the trace
+        // layout
+        let offset = 1 + layout.to_bytes().len();
+        bytes[offset] = (usize::BITS - 1) as u8;
+        assert!(Context::read_from(&mut SliceReader::new(&bytes)).is_ok());
+        bytes[offset] = usize::BITS as u8;
+        assert!(Context::read_from(&mut SliceReader::new(&bytes)).is_err());
+    }
+
+    #[test]
+    fn context_serialization_version() {
+        let layout = TraceLayout::new(4, [2, 0], [1, 0]);
+        let trace_info = TraceInfo::new_multi_segment(layout, 1024, vec![1, 2, 3]);
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        let context = Context::new::<BaseElement, Blake3, Blake3>(&trace_info, options);
+
+        // the serialized context starts with the format version, followed by the unversioned
+        // encoding of the context
+        let mut unversioned_bytes = Vec::new();
+        context.write_unversioned_into(&mut unversioned_bytes);
+        let mut bytes = context.to_bytes();
+        assert_eq!(Context::VERSION, bytes[0]);
+        assert_eq!(unversioned_bytes, bytes[1..]);
+
+        // contexts in the unversioned format can be read only via read_unversioned_from()
+        let result = Context::read_unversioned_from(&mut SliceReader::new(&unversioned_bytes));
+        assert_eq!(Ok(context.clone()), result);
+        assert!(Context::read_from(&mut SliceReader::new(&unversioned_bytes)).is_err());
+
+        // contexts written using older supported versions of the format can still be read
+        bytes[0] = Context::MIN_SUPPORTED_VERSION;
+        assert_eq!(
+            Ok(context),
+            Context::read_from(&mut SliceReader::new(&bytes))
+        );
+
+        // unknown versions are rejected
+        bytes[0] = Context::VERSION + 1;
+        assert_eq!(
+            Err(DeserializationError::UnsupportedVersion(
+                Context::VERSION + 1
+            )),
+            Context::read_from(&mut SliceReader::new(&bytes))
+        );
+        bytes[0] = 0;
+        assert_eq!(
+            Err(DeserializationError::UnsupportedVersion(0)),
+            Context::read_from(&mut SliceReader::new(&bytes))
+        );
+    }
+
+    #[test]
+    fn context_hash_fingerprints() {
+        type B3_192 = Blake3_192<BaseElement>;
+        type S3_256 = Sha3_256<BaseElement>;
+
+        let layout = TraceLayout::new(4, [0, 0], [0, 0]);
+        let trace_info = TraceInfo::new_multi_segment(layout, 1024, vec![]);
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        let context = Context::new::<BaseElement, Blake3, S3_256>(&trace_info, options);
+
+        assert_eq!(
+            Context::hash_fingerprint::<Blake3>(),
+            context.commitment_hash_id()
+        );
+        assert_eq!(
+            Context::hash_fingerprint::<S3_256>(),
+            context.coin_hash_id()
+        );
+        let bytes = context.to_bytes();
+        let result = Context::read_from(&mut SliceReader::new(&bytes)).unwrap();
+        assert_eq!(context, result);
+
+        // hash functions which differ only in digest size must have different fingerprints
+        assert_ne!(
+            Context::hash_fingerprint::<B3_192>(),
+            Context::hash_fingerprint::<Blake3>()
+        );
+        assert_ne!(
+            Context::hash_fingerprint::<Blake3>(),
+            Context::hash_fingerprint::<S3_256>()
+        );
+    }
+}
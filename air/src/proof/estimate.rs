@@ -0,0 +1,164 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{Air, ProofOptions};
+use math::{log2, FieldElement, StarkField};
+use utils::Serializable;
+
+// PROOF SIZE ESTIMATION
+// ================================================================================================
+
+/// Returns the expected size (in bytes) of a serialized proof generated for the specified `air`
+/// with the specified `options`, assuming hash digests of `digest_size` bytes.
+///
+/// The size of every component of a proof except for the Merkle authentication paths is fully
+/// determined by the AIR and the options. Query positions are drawn at random; thus, for batch
+/// Merkle proofs (in which the nodes shared by several paths are included only once), and for
+/// FRI layers (in which positions folded onto the same coset are opened only once), the expected
+/// number of nodes and openings is used.
+pub(crate) fn estimate_proof_size<A: Air>(
+    air: &A,
+    options: &ProofOptions,
+    digest_size: usize,
+) -> usize {
+    let layout = air.trace_layout();
+    let base_bytes = A::BaseField::ELEMENT_BYTES;
+    let ext_bytes = base_bytes * options.field_extension().degree() as usize;
+    let lde_domain_size = options.trace_poly_size(air.trace_length()) * options.blowup_factor();
+    let fri_options = options.to_fri_options();
+    let num_fri_layers = fri_options.num_fri_layers(lde_domain_size);
+    let num_composition_columns = air.num_constraint_composition_columns();
+    let num_committed_periodic_columns = if air.has_committed_periodic_columns() {
+        air.get_periodic_column_values().len() + air.get_aux_periodic_column_values().len()
+    } else {
+        0
+    };
+
+    // version, trace layout, trace length, trace meta, field modulus, options and hash ids
+    let context = 1
+        + layout.to_bytes().len()
+        + 1
+        + 2
+        + air.trace_info().meta().len()
+        + 1
+        + A::BaseField::get_modulus_le_bytes().len()
+        + options.to_bytes().len()
+        + 8;
+
+    // one commitment for each trace segment (or round of a multi-round segment), the constraint
+    // evaluations, each FRI layer and the FRI remainder
+    let commitments = 2 + (layout.num_trace_commitments() + num_fri_layers + 2) * digest_size;
+
+    // trace, constraint and periodic queries; query positions are distinct, and thus, each of
+    // them opens a separate row
+    let query_size = |num_queries: usize, row_bytes: usize| {
+        let salts = if options.is_zero_knowledge() {
+            num_queries * digest_size
+        } else {
+            0
+        };
+        let paths = batch_paths_size(num_queries, lde_domain_size, lde_domain_size, digest_size);
+        12 + num_queries * row_bytes + paths + salts
+    };
+    let num_trace_queries = options.num_trace_queries();
+    let mut queries = query_size(num_trace_queries, layout.main_trace_width() * base_bytes);
+    for width in layout.aux_commitment_widths() {
+        queries += query_size(num_trace_queries, width * ext_bytes);
+    }
+    queries += query_size(
+        options.num_constraint_queries(),
+        num_composition_columns * ext_bytes,
+    );
+    queries += 1;
+    if num_committed_periodic_columns > 0 {
+        queries += query_size(
+            num_trace_queries,
+            num_committed_periodic_columns * base_bytes,
+        );
+    }
+
+    // trace states, constraint evaluations and periodic values at the out-of-domain point
+    let trace_width = layout.main_trace_width() + layout.aux_trace_width();
+    let ood_frame = 12
+        + (air.context().num_frame_rows() * trace_width
+            + num_composition_columns
+            + num_committed_periodic_columns)
+            * ext_bytes;
+
+    // FRI layers and remainder; FRI layers are queried only at the positions at which both the
+    // trace and the constraint evaluations are opened. each position opened in a layer opens a
+    // coset of `folding_factor` values, and positions folded onto the same coset are opened only
+    // once
+    let num_queries = options.num_queries();
+    let folding_factor = fri_options.folding_factor();
+    let mut fri_proof = 1;
+    let mut domain_size = lde_domain_size;
+    for _ in 0..num_fri_layers {
+        domain_size /= folding_factor;
+        let num_openings = expected_distinct(num_queries, lde_domain_size, domain_size);
+        let values = num_openings * (folding_factor * ext_bytes) as f64;
+        let paths = batch_paths_size(num_queries, lde_domain_size, domain_size, digest_size);
+        fri_proof += 8 + (values + 0.5) as usize + paths;
+    }
+    fri_proof += 3 + domain_size * ext_bytes;
+
+    // proof-of-work nonce
+    context + commitments + queries + ood_frame + fri_proof + 8
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the expected size (in bytes) of a batch Merkle proof against a tree with `num_leaves`
+/// leaves, when `num_queries` distinct positions are drawn uniformly from a domain of
+/// `domain_size` elements, and position `i` is mapped onto leaf `i mod num_leaves`.
+///
+/// For every level of the tree, the proof contains the siblings of the nodes on the paths from
+/// the opened leaves which cannot be computed from the nodes below; i.e., if `d_k` nodes are
+/// reached at level `k`, the proof contains `2 * d_{k + 1} - d_k` nodes of that level.
+fn batch_paths_size(
+    num_queries: usize,
+    domain_size: usize,
+    num_leaves: usize,
+    digest_size: usize,
+) -> usize {
+    if num_queries == 0 {
+        return 0;
+    }
+    let depth = log2(num_leaves) as usize;
+    let num_opened_leaves = expected_distinct(num_queries, domain_size, num_leaves);
+
+    let mut num_nodes = 0.0;
+    let mut num_reached = num_opened_leaves;
+    for level in 1..=depth {
+        let num_parents = expected_distinct(num_queries, domain_size, num_leaves >> level);
+        num_nodes += 2.0 * num_parents - num_reached;
+        num_reached = num_parents;
+    }
+
+    // the number of paths, the number of nodes in each path, and the nodes
+    let size = 1.0 + num_opened_leaves + num_nodes * digest_size as f64;
+    (size + 0.5) as usize
+}
+
+/// Returns the expected number of classes hit by `num_queries` distinct positions drawn uniformly
+/// from a domain of `domain_size` elements, when the domain is partitioned into `num_classes`
+/// classes of equal size (e.g., by position modulo `num_classes`).
+///
+/// A class of `domain_size / num_classes` positions is not hit by any of the queries with
+/// probability `C(domain_size - class_size, num_queries) / C(domain_size, num_queries)`.
+fn expected_distinct(num_queries: usize, domain_size: usize, num_classes: usize) -> f64 {
+    let class_size = domain_size / num_classes;
+    let mut p_miss = 1.0;
+    for i in 0..num_queries {
+        if domain_size < class_size + i + 1 {
+            p_miss = 0.0;
+            break;
+        }
+        p_miss *= (domain_size - class_size - i) as f64 / (domain_size - i) as f64;
+    }
+    num_classes as f64 * (1.0 - p_miss)
+}
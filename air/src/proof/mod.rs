@@ -5,19 +5,48 @@
 // LICENSE file in the root directory of this source tree.
 
 //! Contains STARK proof struct and associated components.
+//!
+//! # Serialization format stability
+//! Serialized proofs start with a [Context], and the first byte of a serialized context is the
+//! version of the serialization format ([Context::VERSION]). Proofs serialized using a format
+//! version which is not known to this crate are rejected with
+//! [DeserializationError::UnsupportedVersion] rather than misinterpreted.
+//!
+//! The version is incremented whenever the byte encoding of a proof (or of any of its
+//! components) changes. Proofs are always written using the latest version of the format, but
+//! proofs serialized using any version from [Context::MIN_SUPPORTED_VERSION] up to
+//! [Context::VERSION] can be read. Proofs which were serialized before the version byte was
+//! introduced can still be read via [StarkProof::from_bytes_unversioned()].
+//!
+//! The following versions of the format have been defined so far:
+//! * Version 1: identical to the unversioned format apart from the leading version byte.
+//! * Version 2: lengths of out-of-domain frame components are encoded as u32 rather than u16
+//!   values; this allows proofs for traces with more than 65535 bytes of out-of-domain trace
+//!   states (e.g., traces with many columns) to be serialized. The number of unique query
+//!   positions is no longer recorded after the proof-of-work nonce.
+//!
+//! The version byte is not a part of the data absorbed into the public coin; thus, proofs read
+//! via either of the readers verify in the same way. A set of serialized proofs is checked into
+//! this crate as test fixtures; these fixtures must keep parsing (and re-serializing into the
+//! same bytes) for as long as the corresponding format version is supported.
 
-use crate::{ProofOptions, TraceInfo, TraceLayout};
-use core::cmp;
+use crate::{Air, ProofOptions, TraceInfo, TraceLayout};
+use core::{cmp, mem};
 use crypto::Hasher;
 use fri::FriProof;
 use math::log2;
 use utils::{
-    collections::Vec, ByteReader, Deserializable, DeserializationError, Serializable, SliceReader,
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
 };
 
 mod context;
 pub use context::Context;
 
+/// Version of the serialization format in which components of proofs serialized without a
+/// version byte are encoded; apart from the version byte, the two formats are identical.
+const UNVERSIONED_BODY_VERSION: u8 = 1;
+
 mod commitments;
 pub use commitments::Commitments;
 
@@ -30,6 +59,18 @@ pub use ood_frame::OodFrame;
 mod table;
 pub use table::Table;
 
+mod breakdown;
+pub use breakdown::ProofSizeBreakdown;
+
+mod batch;
+pub use batch::{BatchProof, InstanceProof};
+
+mod estimate;
+pub(crate) use estimate::estimate_proof_size;
+
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
@@ -45,10 +86,13 @@ const GRINDING_CONTRIBUTION_FLOOR: u32 = 80;
 ///
 /// A proof can be serialized into a sequence of bytes using [to_bytes()](StarkProof::to_bytes)
 /// function, and deserialized from a sequence of bytes using [from_bytes()](StarkProof::from_bytes)
-/// function.
+/// function. Since [StarkProof] implements [Serializable] and [Deserializable] traits, a proof can
+/// also be written into (or read from) any [ByteWriter] (or [ByteReader]) directly; this makes it
+/// possible to stream a proof into a file without building its byte representation in memory first.
 ///
 /// To estimate soundness of a proof (in bits), [security_level()](StarkProof::security_level)
-/// function can be used.
+/// function can be used. To find out how the bytes of a serialized proof are distributed among
+/// its components, [size_breakdown()](StarkProof::size_breakdown) function can be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct StarkProof {
     /// Basic metadata about the execution of the computation described by this proof.
@@ -61,6 +105,9 @@ pub struct StarkProof {
     /// Decommitments of constraint composition polynomial evaluations at positions queried by
     /// the verifier.
     pub constraint_queries: Queries,
+    /// Decommitments of periodic column evaluations at positions queried by the verifier; this
+    /// is set only for computations which commit to their periodic columns.
+    pub periodic_queries: Option<Queries>,
     /// Trace and constraint polynomial evaluations at an out-of-domain point.
     pub ood_frame: OodFrame,
     /// Low-degree proof for a DEEP composition polynomial.
@@ -104,12 +151,24 @@ impl StarkProof {
     /// security level is returned. Usually, the number of queries needed for provable security is
     /// 2x - 3x higher than the number of queries needed for conjectured security at the same
     /// security level.
+    ///
+    /// Query positions are always unique, and thus, the security level is estimated from the
+    /// number of queries specified by the proof options. When the trace and the constraint
+    /// evaluations are opened at different numbers of positions, only the positions at which
+    /// both are opened are counted (see [ProofOptions::num_queries()]).
+    ///
+    /// The number of unique positions is not recorded in the proof: the prover and the verifier
+    /// draw positions using [RandomCoin::draw_integers()](crypto::RandomCoin::draw_integers),
+    /// which returns exactly the requested number of unique positions, or fails with
+    /// `DomainTooSmall` when there are not fewer queries than elements in the LDE domain, or with
+    /// `FailedToDrawIntegers` after max(1000, 32 · num_queries) draws from the PRNG.
     pub fn security_level<H: Hasher>(&self, conjectured: bool) -> u32 {
         if conjectured {
             get_conjectured_security(
                 self.context.options(),
                 self.context.num_modulus_bits(),
                 self.lde_domain_size() as u64,
+                self.options().num_queries(),
                 H::COLLISION_RESISTANCE,
             )
         } else {
@@ -118,76 +177,214 @@ impl StarkProof {
         }
     }
 
+    // SIZE BREAKDOWN
+    // --------------------------------------------------------------------------------------------
+    /// Returns the sizes (in bytes) of the serialized components of this proof.
+    pub fn size_breakdown(&self) -> ProofSizeBreakdown {
+        ProofSizeBreakdown {
+            context: self.context.to_bytes().len(),
+            commitments: self.commitments.to_bytes().len(),
+            trace_queries: self
+                .trace_queries
+                .iter()
+                .map(|queries| queries.to_bytes().len())
+                .collect(),
+            constraint_queries: self.constraint_queries.to_bytes().len(),
+            periodic_queries: self
+                .periodic_queries
+                .as_ref()
+                .map_or(1, |queries| 1 + queries.to_bytes().len()),
+            ood_frame: self.ood_frame.to_bytes().len(),
+            fri_layers: self.fri_proof.layer_sizes(),
+            fri_remainder: self.fri_proof.remainder_size(),
+            fri_proof: self.fri_proof.size(),
+            pow_nonce: mem::size_of::<u64>(),
+        }
+    }
+
+    /// Returns an estimate of the size (in bytes) of a serialized proof generated for the
+    /// specified `air` with the specified `options`, without generating the proof.
+    ///
+    /// The options may differ from the options of the `air` (e.g., to compare several sets of
+    /// parameters for the same computation); the trace layout, trace length, and constraints are
+    /// taken from the `air`. Hash digests are assumed to be of the size of `H::Digest`.
+    ///
+    /// Query positions are drawn at random, and thus, the number of Merkle authentication path
+    /// nodes shared by several queries varies from proof to proof; the expected number of nodes
+    /// is used for the estimate. For the parameters used in practice, the estimate is usually
+    /// within a few percent of the actual size of a proof.
+    pub fn estimated_size<A: Air, H: Hasher>(air: &A, options: &ProofOptions) -> usize {
+        let digest_size = H::Digest::default().to_bytes().len();
+        estimate_proof_size(air, options, digest_size)
+    }
+
     // SERIALIZATION / DESERIALIZATION
     // --------------------------------------------------------------------------------------------
 
     /// Serializes this proof into a vector of bytes.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut result = Vec::new();
-        self.context.write_into(&mut result);
-        self.commitments.write_into(&mut result);
-        self.trace_queries.write_into(&mut result);
-        self.constraint_queries.write_into(&mut result);
-        self.ood_frame.write_into(&mut result);
-        self.fri_proof.write_into(&mut result);
-        result.extend_from_slice(&self.pow_nonce.to_le_bytes());
+        self.write_into(&mut result);
         result
     }
 
     /// Returns a STARK proof read from the specified `source`.
     ///
     /// # Errors
-    /// Returns an error of a valid STARK proof could not be read from the specified `source`.
+    /// Returns an error of a valid STARK proof could not be read from the specified `source`, or
+    /// if the `source` contains bytes beyond the end of the proof.
     pub fn from_bytes(source: &[u8]) -> Result<Self, DeserializationError> {
         let mut source = SliceReader::new(source);
+        let proof = Self::read_from(&mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(proof)
+    }
 
-        // parse the context
-        let context = Context::read_from(&mut source)?;
+    /// Returns a STARK proof read from the specified `source` serialized using the format which
+    /// preceded versioning of proof serialization (i.e., without a leading version byte).
+    ///
+    /// # Errors
+    /// Returns an error of a valid STARK proof could not be read from the specified `source`, or
+    /// if the `source` contains bytes beyond the end of the proof.
+    pub fn from_bytes_unversioned(source: &[u8]) -> Result<Self, DeserializationError> {
+        let mut source = SliceReader::new(source);
+        let context = Context::read_unversioned_from(&mut source)?;
+        let proof = Self::read_body_from(context, UNVERSIONED_BODY_VERSION, &mut source)?;
+        if source.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        Ok(proof)
+    }
 
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads all components of a STARK proof which follow the proof context from the specified
+    /// `source`, and returns the resulting proof; the components are expected to be serialized
+    /// using the specified `version` of the serialization format.
+    fn read_body_from<R: ByteReader>(
+        context: Context,
+        version: u8,
+        source: &mut R,
+    ) -> Result<Self, DeserializationError> {
         // parse the commitments
-        let commitments = Commitments::read_from(&mut source)?;
+        let commitments = Commitments::read_from(source)?;
 
         // parse trace queries
-        let num_trace_segments = context.trace_layout().num_segments();
-        let mut trace_queries = Vec::with_capacity(num_trace_segments);
-        for _ in 0..num_trace_segments {
-            trace_queries.push(Queries::read_from(&mut source)?);
+        let num_trace_commitments = context.trace_layout().num_trace_commitments();
+        let mut trace_queries = Vec::with_capacity(num_trace_commitments);
+        for _ in 0..num_trace_commitments {
+            trace_queries.push(Queries::read_from(source)?);
         }
 
+        // parse constraint and periodic column queries
+        let constraint_queries = Queries::read_from(source)?;
+        let periodic_queries = read_optional_queries(source)?;
+
         // parse the rest of the proof
-        let proof = StarkProof {
+        Ok(StarkProof {
             context,
             commitments,
             trace_queries,
-            constraint_queries: Queries::read_from(&mut source)?,
-            ood_frame: OodFrame::read_from(&mut source)?,
-            fri_proof: FriProof::read_from(&mut source)?,
-            pow_nonce: source.read_u64()?,
-        };
-        if source.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
-        }
-        Ok(proof)
+            constraint_queries,
+            periodic_queries,
+            ood_frame: OodFrame::read_versioned_from(source, version)?,
+            fri_proof: FriProof::read_from(source)?,
+            pow_nonce: read_pow_nonce(source, version)?,
+        })
+    }
+}
+
+impl Serializable for StarkProof {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.context.write_into(target);
+        self.commitments.write_into(target);
+        self.trace_queries.write_into(target);
+        self.constraint_queries.write_into(target);
+        write_optional_queries(&self.periodic_queries, target);
+        self.ood_frame.write_into(target);
+        self.fri_proof.write_into(target);
+        target.write_u64(self.pow_nonce);
+    }
+}
+
+impl Deserializable for StarkProof {
+    /// Reads a STARK proof from the specified `source` and returns the result.
+    ///
+    /// Unlike [from_bytes()](StarkProof::from_bytes), this does not check whether the `source`
+    /// contains any bytes beyond the end of the proof.
+    ///
+    /// # Errors
+    /// Returns an error of a valid STARK proof could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // parse the context
+        let version = Context::read_version(source)?;
+        let context = Context::read_unversioned_from(source)?;
+
+        // parse the rest of the proof
+        Self::read_body_from(context, version, source)
     }
 }
 
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Writes the specified periodic column queries into the `target`, preceded by a flag indicating
+/// whether the queries are present.
+fn write_optional_queries<W: ByteWriter>(queries: &Option<Queries>, target: &mut W) {
+    match queries {
+        Some(queries) => {
+            target.write_u8(1);
+            queries.write_into(target);
+        }
+        None => target.write_u8(0),
+    }
+}
+
+/// Reads periodic column queries preceded by a flag indicating whether the queries are present
+/// from the specified `source`.
+fn read_optional_queries<R: ByteReader>(
+    source: &mut R,
+) -> Result<Option<Queries>, DeserializationError> {
+    match source.read_u8()? {
+        0 => Ok(None),
+        1 => Ok(Some(Queries::read_from(source)?)),
+        value => Err(DeserializationError::InvalidValue(format!(
+            "value {value} cannot be deserialized as a periodic queries flag"
+        ))),
+    }
+}
+
+/// Reads the proof-of-work nonce serialized using the specified `version` of the serialization
+/// format from the specified `source`.
+///
+/// Version 1 of the format also recorded the number of unique query positions right after the
+/// nonce; since query positions are always unique, this number is skipped.
+fn read_pow_nonce<R: ByteReader>(source: &mut R, version: u8) -> Result<u64, DeserializationError> {
+    let pow_nonce = source.read_u64()?;
+    if version == 1 {
+        source.read_u8()?;
+    }
+    Ok(pow_nonce)
+}
+
 /// Computes conjectured security level for the specified proof parameters.
 fn get_conjectured_security(
     options: &ProofOptions,
     base_field_bits: u32,
     lde_domain_size: u64,
+    num_queries: usize,
     collision_resistance: u32,
 ) -> u32 {
     // compute max security we can get for a given field size
-    let field_size = base_field_bits * options.field_extension().degree();
-    let field_security = field_size - lde_domain_size.trailing_zeros();
+    let field_security = options.field_security(base_field_bits, lde_domain_size as usize);
 
     // compute security we get by executing multiple query rounds
     let security_per_query = log2(options.blowup_factor());
-    let mut query_security = security_per_query * options.num_queries() as u32;
+    let mut query_security = security_per_query * num_queries as u32;
 
     // include grinding factor contributions only for proofs adequate security
     if query_security >= GRINDING_CONTRIBUTION_FLOOR {
@@ -195,7 +392,7 @@ fn get_conjectured_security(
     }
 
     cmp::min(
-        cmp::min(field_security, query_security) - 1,
+        cmp::min(field_security, query_security).saturating_sub(1),
         collision_resistance,
     )
 }
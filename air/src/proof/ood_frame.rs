@@ -4,6 +4,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::Context;
 use crate::EvaluationFrame;
 use math::FieldElement;
 use utils::{
@@ -14,16 +15,23 @@ use utils::{
 // TYPE ALIASES
 // ================================================================================================
 
-type ParsedOodFrame<E> = (EvaluationFrame<E>, Option<EvaluationFrame<E>>, Vec<E>);
+type ParsedOodFrame<E> = (
+    EvaluationFrame<E>,
+    Option<EvaluationFrame<E>>,
+    Vec<E>,
+    Vec<E>,
+);
 
 // OUT-OF-DOMAIN FRAME
 // ================================================================================================
 /// Trace and constraint polynomial evaluations at an out-of-domain point.
 ///
 /// This struct contains the following evaluations:
-/// * Evaluations of all trace polynomials at *z*.
-/// * Evaluations of all trace polynomials at *z * g*.
+/// * Evaluations of all trace polynomials at *z * g^o* for each frame offset *o*; for the default
+///   offsets, these are evaluations at *z* and *z * g*.
 /// * Evaluations of constraint composition column polynomials at *z*.
+/// * Evaluations of committed periodic column polynomials at *z*; this is empty unless the
+///   periodic columns of the computation are committed.
 ///
 /// where *z* is an out-of-domain point and *g* is the generator of the trace domain.
 ///
@@ -33,6 +41,7 @@ type ParsedOodFrame<E> = (EvaluationFrame<E>, Option<EvaluationFrame<E>>, Vec<E>
 pub struct OodFrame {
     trace_states: Vec<u8>,
     evaluations: Vec<u8>,
+    periodic_values: Vec<u8>,
 }
 
 impl OodFrame {
@@ -71,44 +80,76 @@ impl OodFrame {
         evaluations.write_into(&mut self.evaluations)
     }
 
+    /// Updates committed periodic column portion of this out-of-domain frame.
+    ///
+    /// # Panics
+    /// Panics if periodic values have already been set.
+    pub fn set_periodic_values<E: FieldElement>(&mut self, values: &[E]) {
+        assert!(
+            self.periodic_values.is_empty(),
+            "periodic values have already been set"
+        );
+        values.write_into(&mut self.periodic_values)
+    }
+
     // PARSER
     // --------------------------------------------------------------------------------------------
-    /// Returns main and auxiliary (if any) trace evaluation frames and a vector of out-of-domain
-    /// constraint evaluations contained in `self`.
+    /// Returns main and auxiliary (if any) trace evaluation frames, a vector of out-of-domain
+    /// constraint evaluations, and a vector of out-of-domain evaluations of committed periodic
+    /// columns contained in `self`.
+    ///
+    /// Each of the returned frames contains `num_rows` rows, one for each frame offset.
     ///
     /// # Panics
-    /// Panics if either `main_trace_width` or `num_evaluations` are equal to zero.
+    /// Panics if either `main_trace_width`, `num_rows`, or `num_evaluations` are equal to zero.
     ///
     /// # Errors
     /// Returns an error if:
-    /// * Valid [EvaluationFrame]s for the specified `main_trace_width` and `aux_trace_width`
-    ///   could not be parsed from the internal bytes.
-    /// * A vector of evaluations specified by `num_evaluations` could not be parsed from the
-    ///   internal bytes.
-    /// * Any unconsumed bytes remained after the parsing was complete.
+    /// * The number of trace state bytes is not the number of bytes needed to encode `num_rows`
+    ///   rows of `main_trace_width` + `aux_trace_width` elements.
+    /// * The number of constraint evaluation bytes is not the number of bytes needed to encode
+    ///   `num_evaluations` elements.
+    /// * The number of periodic value bytes is not the number of bytes needed to encode
+    ///   `num_periodic_values` elements.
+    /// * Any of the elements could not be parsed from the internal bytes.
     pub fn parse<E: FieldElement>(
         self,
         main_trace_width: usize,
         aux_trace_width: usize,
+        num_rows: usize,
         num_evaluations: usize,
+        num_periodic_values: usize,
     ) -> Result<ParsedOodFrame<E>, DeserializationError> {
         assert!(main_trace_width > 0, "trace width cannot be zero");
+        assert!(num_rows > 0, "number of frame rows cannot be zero");
         assert!(num_evaluations > 0, "number of evaluations cannot be zero");
 
-        // parse current and next trace states for main and auxiliary trace evaluation frames
+        // make sure the frame contains exactly as many values as expected by the verifier before
+        // any values are parsed; the widths are defined by the computation, and thus, a frame of
+        // any other shape was not generated for this computation
+        let trace_width = main_trace_width + aux_trace_width;
+        check_num_bytes::<E>("trace states", &self.trace_states, num_rows * trace_width)?;
+        check_num_bytes::<E>("constraint evaluations", &self.evaluations, num_evaluations)?;
+        check_num_bytes::<E>(
+            "periodic values",
+            &self.periodic_values,
+            num_periodic_values,
+        )?;
+
+        // parse trace states for all frame offsets; each trace state contains main trace values
+        // followed by auxiliary trace values
         let mut reader = SliceReader::new(&self.trace_states);
-        let current = E::read_batch_from(&mut reader, main_trace_width)?;
-        let current_aux = E::read_batch_from(&mut reader, aux_trace_width)?;
-        let next = E::read_batch_from(&mut reader, main_trace_width)?;
-        let next_aux = E::read_batch_from(&mut reader, aux_trace_width)?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
+        let mut main_rows = Vec::with_capacity(num_rows);
+        let mut aux_rows = Vec::with_capacity(num_rows);
+        for _ in 0..num_rows {
+            main_rows.push(E::read_batch_from(&mut reader, main_trace_width)?);
+            aux_rows.push(E::read_batch_from(&mut reader, aux_trace_width)?);
         }
 
         // instantiate the frames from the parsed rows
-        let main_frame = EvaluationFrame::from_rows(current, next);
+        let main_frame = EvaluationFrame::from_multi_rows(main_rows);
         let aux_frame = if aux_trace_width > 0 {
-            Some(EvaluationFrame::from_rows(current_aux, next_aux))
+            Some(EvaluationFrame::from_multi_rows(aux_rows))
         } else {
             None
         };
@@ -116,11 +157,12 @@ impl OodFrame {
         // parse the constraint evaluations
         let mut reader = SliceReader::new(&self.evaluations);
         let evaluations = E::read_batch_from(&mut reader, num_evaluations)?;
-        if reader.has_more_bytes() {
-            return Err(DeserializationError::UnconsumedBytes);
-        }
 
-        Ok((main_frame, aux_frame, evaluations))
+        // parse the periodic values
+        let mut reader = SliceReader::new(&self.periodic_values);
+        let periodic_values = E::read_batch_from(&mut reader, num_periodic_values)?;
+
+        Ok((main_frame, aux_frame, evaluations, periodic_values))
     }
 }
 
@@ -128,32 +170,200 @@ impl Serializable for OodFrame {
     /// Serializes `self` and writes the resulting bytes into the `target`.
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // write trace rows
-        target.write_u16(self.trace_states.len() as u16);
+        target.write_u32(self.trace_states.len() as u32);
         target.write_u8_slice(&self.trace_states);
 
         // write constraint evaluations row
-        target.write_u16(self.evaluations.len() as u16);
-        target.write_u8_slice(&self.evaluations)
+        target.write_u32(self.evaluations.len() as u32);
+        target.write_u8_slice(&self.evaluations);
+
+        // write committed periodic values
+        target.write_u32(self.periodic_values.len() as u32);
+        target.write_u8_slice(&self.periodic_values)
     }
 }
 
-impl Deserializable for OodFrame {
-    /// Reads a OOD frame from the specified `source` and returns the result
+impl OodFrame {
+    /// Reads a OOD frame serialized using the specified `version` of the proof serialization
+    /// format from the specified `source` and returns the result.
+    ///
+    /// Version 1 of the format (as well as the unversioned format which preceded it) encodes
+    /// lengths of the frame components as u16 values; later versions encode them as u32 values.
     ///
     /// # Errors
     /// Returns an error of a valid OOD frame could not be read from the specified `source`.
-    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+    pub(crate) fn read_versioned_from<R: ByteReader>(
+        source: &mut R,
+        version: u8,
+    ) -> Result<Self, DeserializationError> {
+        let read_len = |source: &mut R| -> Result<usize, DeserializationError> {
+            if version == 1 {
+                Ok(source.read_u16()? as usize)
+            } else {
+                Ok(source.read_u32()? as usize)
+            }
+        };
+
         // read trace rows
-        let num_trace_state_bytes = source.read_u16()? as usize;
+        let num_trace_state_bytes = read_len(source)?;
         let trace_states = source.read_u8_vec(num_trace_state_bytes)?;
 
         // read constraint evaluations row
-        let num_constraint_evaluation_bytes = source.read_u16()? as usize;
+        let num_constraint_evaluation_bytes = read_len(source)?;
         let evaluations = source.read_u8_vec(num_constraint_evaluation_bytes)?;
 
+        // read committed periodic values
+        let num_periodic_value_bytes = read_len(source)?;
+        let periodic_values = source.read_u8_vec(num_periodic_value_bytes)?;
+
         Ok(OodFrame {
             trace_states,
             evaluations,
+            periodic_values,
         })
     }
 }
+
+impl Deserializable for OodFrame {
+    /// Reads a OOD frame serialized using the current version of the proof serialization format
+    /// from the specified `source` and returns the result
+    ///
+    /// # Errors
+    /// Returns an error of a valid OOD frame could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_versioned_from(source, Context::VERSION)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns an error if `bytes` do not encode exactly `num_elements` elements of type `E`; `name`
+/// describes the elements in the error message.
+fn check_num_bytes<E: FieldElement>(
+    name: &str,
+    bytes: &[u8],
+    num_elements: usize,
+) -> Result<(), DeserializationError> {
+    let expected = num_elements * E::ELEMENT_BYTES;
+    if bytes.len() != expected {
+        return Err(DeserializationError::InvalidValue(format!(
+            "out-of-domain frame must contain {expected} bytes of {name} ({num_elements} \
+            elements), but was {}",
+            bytes.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OodFrame;
+    use math::{fields::f64::BaseElement, FieldElement};
+    use utils::{
+        collections::Vec, string::String, ByteReader, ByteWriter, Deserializable,
+        DeserializationError, Serializable, SliceReader,
+    };
+
+    fn build_frame(num_rows: usize, trace_width: usize, num_evaluations: usize) -> OodFrame {
+        let trace_states = (0..num_rows)
+            .map(|i| {
+                (0..trace_width)
+                    .map(|j| BaseElement::from((i * 10 + j) as u64))
+                    .collect()
+            })
+            .collect::<Vec<Vec<_>>>();
+        let mut frame = OodFrame::default();
+        frame.set_trace_states(&trace_states);
+        frame.set_constraint_evaluations(&vec![BaseElement::ONE; num_evaluations]);
+        frame
+    }
+
+    #[test]
+    fn parse_ood_frame() {
+        let frame = build_frame(2, 5, 3);
+        let (main_frame, aux_frame, evaluations, periodic_values) =
+            frame.parse::<BaseElement>(3, 2, 2, 3, 0).unwrap();
+        assert_eq!(
+            &[
+                BaseElement::new(10),
+                BaseElement::new(11),
+                BaseElement::new(12)
+            ],
+            main_frame.next()
+        );
+        assert_eq!(
+            &[BaseElement::new(3), BaseElement::new(4)],
+            aux_frame.unwrap().current()
+        );
+        assert_eq!(vec![BaseElement::ONE; 3], evaluations);
+        assert!(periodic_values.is_empty());
+    }
+
+    #[test]
+    fn parse_ood_frame_with_wrong_widths() {
+        let frame = build_frame(2, 5, 3);
+
+        // the trace states are parsed for a trace which is one column wider or narrower
+        assert!(parse_error(&frame, 4, 2, 2, 3, 0).contains("trace states"));
+        assert!(parse_error(&frame, 3, 1, 2, 3, 0).contains("trace states"));
+
+        // the number of frame rows, constraint evaluations, and periodic values must match
+        assert!(parse_error(&frame, 3, 2, 3, 3, 0).contains("trace states"));
+        assert!(parse_error(&frame, 3, 2, 2, 2, 0).contains("constraint evaluations"));
+        assert!(parse_error(&frame, 3, 2, 2, 3, 1).contains("periodic values"));
+    }
+
+    #[test]
+    fn serialize_large_ood_frame() {
+        // 1024 columns * 8 rows * 8 bytes = 65536 bytes of trace states
+        let frame = build_frame(8, 1024, 3);
+        let bytes = frame.to_bytes();
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(frame, OodFrame::read_from(&mut reader).unwrap());
+        assert!(!reader.has_more_bytes());
+    }
+
+    #[test]
+    fn read_ood_frame_v1() {
+        // version 1 of the format encodes lengths of the frame components as u16 values
+        let frame = build_frame(2, 5, 3);
+        let mut bytes = Vec::new();
+        for component in [
+            &frame.trace_states,
+            &frame.evaluations,
+            &frame.periodic_values,
+        ] {
+            bytes.write_u16(component.len() as u16);
+            bytes.write_u8_slice(component);
+        }
+
+        let mut reader = SliceReader::new(&bytes);
+        assert_eq!(
+            frame,
+            OodFrame::read_versioned_from(&mut reader, 1).unwrap()
+        );
+        assert!(!reader.has_more_bytes());
+    }
+
+    fn parse_error(
+        frame: &OodFrame,
+        main_trace_width: usize,
+        aux_trace_width: usize,
+        num_rows: usize,
+        num_evaluations: usize,
+        num_periodic_values: usize,
+    ) -> String {
+        let result = frame.clone().parse::<BaseElement>(
+            main_trace_width,
+            aux_trace_width,
+            num_rows,
+            num_evaluations,
+            num_periodic_values,
+        );
+        match result.err() {
+            Some(DeserializationError::InvalidValue(msg)) => msg,
+            err => panic!("expected an invalid value error, but was {err:?}"),
+        }
+    }
+}
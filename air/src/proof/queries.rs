@@ -8,8 +8,8 @@ use super::Table;
 use crypto::{BatchMerkleProof, ElementHasher, Hasher};
 use math::{log2, FieldElement};
 use utils::{
-    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
-    SliceReader,
+    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable, SliceReader,
 };
 
 // QUERIES
@@ -25,13 +25,19 @@ use utils::{
 /// This struct can contain one or more queries. In cases when more than one query is stored,
 /// Merkle authentication paths are compressed to remove redundant nodes.
 ///
-/// Internally, all Merkle paths and query values are stored as a sequence of bytes. Thus, to
-/// retrieve query values and the corresponding Merkle authentication paths,
+/// For proofs generated with salted commitments, leaves of the Merkle tree are salted: a leaf at
+/// position *i* is set to `merge(hash(values_i), salt_i)`. In this case, the salts of all
+/// queried leaves are stored together with the queries, and [parse_salted()](Queries::parse_salted)
+/// function should be used to parse the queries.
+///
+/// Internally, all Merkle paths, query values, and salts are stored as a sequence of bytes. Thus,
+/// to retrieve query values and the corresponding Merkle authentication paths,
 /// [parse()](Queries::parse) function should be used.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Queries {
     paths: Vec<u8>,
     values: Vec<u8>,
+    salts: Vec<u8>,
 }
 
 impl Queries {
@@ -77,7 +83,41 @@ impl Queries {
         // because leaf nodes can be reconstructed from hashes of query values
         let paths = merkle_proof.serialize_nodes();
 
-        Queries { paths, values }
+        Queries {
+            paths,
+            values,
+            salts: Vec::new(),
+        }
+    }
+
+    /// Returns queries constructed from evaluations of a set of functions at some number of points
+    /// in a domain, their corresponding Merkle authentication paths, and the salts of the leaves
+    /// at these points.
+    ///
+    /// For each evaluation point, `merge(hash(values), salt)` must be equal to a leaf node in the
+    /// corresponding Merkle authentication path.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * No queries were provided (`query_values` is an empty vector).
+    /// * Any of the queries does not contain any evaluations.
+    /// * Not all queries contain the same number of evaluations.
+    /// * The number of salts is not equal to the number of queries.
+    pub fn new_salted<H: Hasher, E: FieldElement>(
+        merkle_proof: BatchMerkleProof<H>,
+        query_values: Vec<Vec<E>>,
+        salts: Vec<H::Digest>,
+    ) -> Self {
+        assert_eq!(
+            query_values.len(),
+            salts.len(),
+            "expected {} salts, but was {}",
+            query_values.len(),
+            salts.len()
+        );
+        let mut queries = Self::new(merkle_proof, query_values);
+        salts.write_into(&mut queries.salts);
+        queries
     }
 
     // PARSER
@@ -85,6 +125,9 @@ impl Queries {
     /// Convert internally stored bytes into a set of query values and the corresponding Merkle
     /// authentication paths.
     ///
+    /// # Errors
+    /// Returns an error if the queries could not be parsed, or if the queries contain salts.
+    ///
     /// # Panics
     /// Panics if:
     /// * `domain_size` is not a power of two.
@@ -96,6 +139,58 @@ impl Queries {
         num_queries: usize,
         values_per_query: usize,
     ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        if !self.salts.is_empty() {
+            return Err(DeserializationError::InvalidValue(
+                "queries contain salts, but unsalted queries were expected".to_string(),
+            ));
+        }
+        self.parse_internal(domain_size, num_queries, values_per_query, None)
+    }
+
+    /// Convert internally stored bytes into a set of query values and the corresponding Merkle
+    /// authentication paths, incorporating the salts of the queried leaves into leaf hashing.
+    ///
+    /// # Errors
+    /// Returns an error if the queries could not be parsed, or if the queries do not contain
+    /// exactly one salt per query.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * `domain_size` is not a power of two.
+    /// * `num_queries` is zero.
+    /// * `values_per_query` is zero.
+    pub fn parse_salted<H, E>(
+        self,
+        domain_size: usize,
+        num_queries: usize,
+        values_per_query: usize,
+    ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
+    where
+        E: FieldElement,
+        H: ElementHasher<BaseField = E::BaseField>,
+    {
+        let mut reader = SliceReader::new(&self.salts);
+        let salts = H::Digest::read_batch_from(&mut reader, num_queries)?;
+        if reader.has_more_bytes() {
+            return Err(DeserializationError::UnconsumedBytes);
+        }
+        self.parse_internal(domain_size, num_queries, values_per_query, Some(salts))
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn parse_internal<H, E>(
+        self,
+        domain_size: usize,
+        num_queries: usize,
+        values_per_query: usize,
+        salts: Option<Vec<H::Digest>>,
+    ) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
     where
         E: FieldElement,
         H: ElementHasher<BaseField = E::BaseField>,
@@ -122,12 +217,20 @@ impl Queries {
         }
 
         // read bytes corresponding to each query, convert them into field elements,
-        // and also hash them to build leaf nodes of the batch Merkle proof
+        // and also hash them (together with salts, if any) to build leaf nodes of the batch
+        // Merkle proof
         let query_values = Table::<E>::from_bytes(&self.values, num_queries, values_per_query)?;
-        let hashed_queries = query_values
-            .rows()
-            .map(|row| H::hash_elements(row))
-            .collect();
+        let hashed_queries = match salts {
+            Some(salts) => query_values
+                .rows()
+                .zip(salts)
+                .map(|(row, salt)| H::merge(&[H::hash_elements(row), salt]))
+                .collect(),
+            None => query_values
+                .rows()
+                .map(|row| H::hash_elements(row))
+                .collect(),
+        };
 
         // build batch Merkle proof
         let mut reader = SliceReader::new(&self.paths);
@@ -151,6 +254,10 @@ impl Serializable for Queries {
         // write path bytes
         target.write_u32(self.paths.len() as u32);
         target.write_u8_slice(&self.paths);
+
+        // write salt bytes
+        target.write_u32(self.salts.len() as u32);
+        target.write_u8_slice(&self.salts);
     }
 }
 
@@ -168,6 +275,14 @@ impl Deserializable for Queries {
         let num_paths_bytes = source.read_u32()?;
         let paths = source.read_u8_vec(num_paths_bytes as usize)?;
 
-        Ok(Queries { paths, values })
+        // read salts
+        let num_salt_bytes = source.read_u32()?;
+        let salts = source.read_u8_vec(num_salt_bytes as usize)?;
+
+        Ok(Queries {
+            paths,
+            values,
+            salts,
+        })
     }
 }
@@ -1,4 +1,5 @@
 use super::{DeserializationError, SliceReader, Vec};
+use crate::TraceInfo;
 use core::iter::FusedIterator;
 use math::FieldElement;
 
@@ -6,7 +7,7 @@ use math::FieldElement;
 // ================================================================================================
 
 const MAX_ROWS: usize = 255;
-const MAX_COLS: usize = 255;
+const MAX_COLS: usize = TraceInfo::MAX_TRACE_WIDTH;
 
 // TABLE
 // ================================================================================================
@@ -30,7 +31,7 @@ impl<E: FieldElement> Table<E> {
     /// # Panics
     /// Panics if:
     /// * Specified number of rows is 0 or greater than 255.
-    /// * Specified number of columns is 0 or greater than 255.
+    /// * Specified number of columns is 0 or greater than [TraceInfo::MAX_TRACE_WIDTH].
     /// * Provided bytes do not encode valid field elements required to fill the table.
     pub fn from_bytes(
         bytes: &[u8],
@@ -44,7 +45,7 @@ impl<E: FieldElement> Table<E> {
         );
         assert!(num_cols > 0, "number of columns must be greater than 0");
         assert!(
-            num_cols < MAX_ROWS,
+            num_cols <= MAX_COLS,
             "number of columns cannot exceed {MAX_COLS}, but was {num_cols}"
         );
 
@@ -83,20 +84,40 @@ impl<E: FieldElement> Table<E> {
     // TABLE PROCESSING
     // --------------------------------------------------------------------------------------------
 
+    /// Shortens this table, keeping the first `num_rows` rows and dropping the rest.
+    ///
+    /// If `num_rows` is greater than or equal to the number of rows in this table, this has no
+    /// effect.
+    pub fn truncate(&mut self, num_rows: usize) {
+        self.data.truncate(num_rows * self.row_width);
+    }
+
     /// Combines multiple tables together into a single table by stacking tables column-wise (e.g.
     /// the number of rows remains the same but the number of columns changes).
     ///
-    /// Currently, this method does not support inputs containing more than one table.
-    ///
     /// # Panics
-    /// Panics if the list of tables is empty.
+    /// Panics if the list of tables is empty, or if not all tables have the same number of rows.
     pub fn merge(mut tables: Vec<Table<E>>) -> Table<E> {
         assert!(!tables.is_empty(), "cannot merge an empty set of tables");
         if tables.len() == 1 {
-            tables.remove(0)
-        } else {
-            unimplemented!("merging of multiple tables is not yet implemented")
+            return tables.remove(0);
         }
+
+        let num_rows = tables[0].num_rows();
+        assert!(
+            tables.iter().all(|table| table.num_rows() == num_rows),
+            "cannot merge tables with different numbers of rows"
+        );
+
+        let row_width = tables.iter().map(|table| table.row_width).sum();
+        let mut data = Vec::with_capacity(num_rows * row_width);
+        for row_idx in 0..num_rows {
+            for table in tables.iter() {
+                data.extend_from_slice(table.get_row(row_idx));
+            }
+        }
+
+        Self { data, row_width }
     }
 }
 
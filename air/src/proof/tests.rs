@@ -0,0 +1,190 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Context, StarkProof};
+use crate::{FieldExtension, TranscriptType};
+use math::{fields, StarkField};
+use utils::DeserializationError;
+
+// FIXTURES
+// ================================================================================================
+//
+// The fixtures below are serialized proofs which must keep parsing for as long as their format
+// version is supported. The fixtures exercise the serialization format only: contents of
+// commitments, queries, and other opaque byte sequences are arbitrary, and thus, the proofs are
+// not expected to verify.
+
+/// A proof for a computation with a single trace segment of width 2 and length 64, serialized
+/// using format version 1.
+const FIB_V1: &[u8] = include_bytes!("fixtures/fib_v1.bin");
+
+/// The same proof as [FIB_V1] serialized using the format which preceded versioning.
+const FIB_UNVERSIONED: &[u8] = include_bytes!("fixtures/fib_unversioned.bin");
+
+/// A proof for a computation with a wide main trace segment and an auxiliary trace segment, with
+/// committed periodic columns and trace metadata, serialized using format version 1.
+const MULTI_SEGMENT_V1: &[u8] = include_bytes!("fixtures/multi_segment_v1.bin");
+
+/// The same proof as [FIB_V1] serialized using format version 2.
+const FIB_V2: &[u8] = include_bytes!("fixtures/fib_v2.bin");
+
+/// The same proof as [MULTI_SEGMENT_V1] serialized using format version 2.
+const MULTI_SEGMENT_V2: &[u8] = include_bytes!("fixtures/multi_segment_v2.bin");
+
+// TESTS
+// ================================================================================================
+
+#[test]
+fn fixture_fib_v1() {
+    assert_eq!(1, FIB_V1[0]);
+    let proof = StarkProof::from_bytes(FIB_V1).unwrap();
+    check_fib_proof(&proof);
+
+    // re-serialization must produce the same proof in the latest format
+    assert_eq!(FIB_V2, proof.to_bytes());
+}
+
+#[test]
+fn fixture_fib_v2() {
+    assert_eq!(2, FIB_V2[0]);
+    let proof = StarkProof::from_bytes(FIB_V2).unwrap();
+    check_fib_proof(&proof);
+    assert_eq!(StarkProof::from_bytes(FIB_V1).unwrap(), proof);
+
+    // re-serialization must produce the same bytes
+    assert_eq!(FIB_V2, proof.to_bytes());
+}
+
+#[test]
+fn fixture_multi_segment_v1() {
+    assert_eq!(1, MULTI_SEGMENT_V1[0]);
+    let proof = StarkProof::from_bytes(MULTI_SEGMENT_V1).unwrap();
+    check_multi_segment_proof(&proof);
+
+    // re-serialization must produce the same proof in the latest format
+    assert_eq!(MULTI_SEGMENT_V2, proof.to_bytes());
+}
+
+#[test]
+fn fixture_multi_segment_v2() {
+    assert_eq!(2, MULTI_SEGMENT_V2[0]);
+    let proof = StarkProof::from_bytes(MULTI_SEGMENT_V2).unwrap();
+    check_multi_segment_proof(&proof);
+    assert_eq!(StarkProof::from_bytes(MULTI_SEGMENT_V1).unwrap(), proof);
+
+    // re-serialization must produce the same bytes
+    assert_eq!(MULTI_SEGMENT_V2, proof.to_bytes());
+}
+
+#[test]
+fn fixture_fib_unversioned() {
+    // the unversioned encoding is the version 1 encoding without the leading version byte
+    assert_eq!(&FIB_V1[1..], FIB_UNVERSIONED);
+
+    // an unversioned proof can be read via the compatibility reader, and is the same proof as
+    // the versioned one
+    let proof = StarkProof::from_bytes_unversioned(FIB_UNVERSIONED).unwrap();
+    assert_eq!(StarkProof::from_bytes(FIB_V1).unwrap(), proof);
+
+    // the first byte of an unversioned proof is interpreted as the format version, and thus, the
+    // rest of the proof cannot be read
+    assert!(StarkProof::from_bytes(FIB_UNVERSIONED).is_err());
+
+    // a versioned proof cannot be read via the compatibility reader
+    assert!(StarkProof::from_bytes_unversioned(FIB_V1).is_err());
+    assert!(StarkProof::from_bytes_unversioned(FIB_V2).is_err());
+}
+
+#[test]
+fn unsupported_version() {
+    let mut bytes = FIB_V2.to_vec();
+    bytes[0] = Context::VERSION + 1;
+    assert!(matches!(
+        StarkProof::from_bytes(&bytes),
+        Err(DeserializationError::UnsupportedVersion(version)) if version == Context::VERSION + 1
+    ));
+    bytes[0] = 0;
+    assert!(matches!(
+        StarkProof::from_bytes(&bytes),
+        Err(DeserializationError::UnsupportedVersion(0))
+    ));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that the specified proof is the proof serialized in [FIB_V1] and [FIB_V2].
+fn check_fib_proof(proof: &StarkProof) {
+    // check the context
+    let layout = proof.trace_layout();
+    assert_eq!(2, layout.main_trace_width());
+    assert_eq!(0, layout.num_aux_segments());
+    assert_eq!(64, proof.trace_length());
+    assert!(proof.get_trace_info().meta().is_empty());
+    assert_eq!(
+        fields::f64::BaseElement::get_modulus_le_bytes(),
+        proof.context.field_modulus_bytes()
+    );
+    assert_eq!([1, 2, 3, 4], proof.context.commitment_hash_id());
+    assert_eq!([1, 2, 3, 4], proof.context.coin_hash_id());
+
+    // check the options
+    let options = proof.options();
+    assert_eq!(28, options.num_queries());
+    assert_eq!(8, options.blowup_factor());
+    assert_eq!(0, options.grinding_factor());
+    assert_eq!(FieldExtension::None, options.field_extension());
+    assert_eq!(4, options.fri_folding_factor());
+    assert_eq!(32, options.fri_max_remainder_size());
+    assert!(!options.is_zero_knowledge());
+    assert_eq!(TranscriptType::Legacy, options.transcript_type());
+
+    // check the rest of the proof
+    assert_eq!(1, proof.trace_queries.len());
+    assert!(proof.periodic_queries.is_none());
+    assert_eq!(1, proof.fri_proof.num_layers());
+    assert_eq!(2, proof.fri_proof.num_partitions());
+    assert_eq!(0, proof.pow_nonce);
+}
+
+/// Checks that the specified proof is the proof serialized in [MULTI_SEGMENT_V1] and
+/// [MULTI_SEGMENT_V2].
+fn check_multi_segment_proof(proof: &StarkProof) {
+    // check the context
+    let layout = proof.trace_layout();
+    assert_eq!(300, layout.main_trace_width());
+    assert_eq!(1, layout.num_aux_segments());
+    assert_eq!(40, layout.get_aux_segment_width(0));
+    assert_eq!(4, layout.get_aux_segment_rand_elements(0));
+    assert_eq!(1024, proof.trace_length());
+    assert_eq!(&[1, 2, 3], proof.get_trace_info().meta());
+    assert_eq!(
+        fields::f128::BaseElement::get_modulus_le_bytes(),
+        proof.context.field_modulus_bytes()
+    );
+    assert_eq!([0xa1, 0xa2, 0xa3, 0xa4], proof.context.commitment_hash_id());
+    assert_eq!([0xb1, 0xb2, 0xb3, 0xb4], proof.context.coin_hash_id());
+
+    // check the options
+    let options = proof.options();
+    assert_eq!(30, options.num_trace_queries());
+    assert_eq!(20, options.num_constraint_queries());
+    assert_eq!(20, options.num_queries());
+    assert_eq!(16, options.blowup_factor());
+    assert_eq!(20, options.grinding_factor());
+    assert_eq!(FieldExtension::Quadratic, options.field_extension());
+    assert_eq!(8, options.fri_folding_factor());
+    assert_eq!(64, options.fri_max_remainder_size());
+    assert!(options.is_zero_knowledge());
+    assert_eq!(TranscriptType::Labeled, options.transcript_type());
+
+    // check the rest of the proof
+    assert_eq!(2, proof.trace_queries.len());
+    assert!(proof.periodic_queries.is_some());
+    assert_eq!(2, proof.fri_proof.num_layers());
+    assert_eq!(16, proof.fri_proof.num_partitions());
+    assert_eq!(0x0123_4567_89ab_cdef, proof.pow_nonce);
+}
@@ -0,0 +1,274 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use core::fmt;
+use utils::{
+    collections::Vec,
+    string::{String, ToString},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// TRANSCRIPT LOG
+// ================================================================================================
+
+/// A record of the values which went through the public coin during a single run of the STARK
+/// protocol, together with intermediate values of the out-of-domain consistency check.
+///
+/// The prover records a log when a proof is generated via `Prover::prove_with_transcript_log()`,
+/// and the verifier records a log when a proof is verified via
+/// `winter_verifier::verify_with_transcript_log()`. For a valid proof, both logs contain exactly
+/// the same sequence of entries; thus, comparing the logs via [diff()](TranscriptLog::diff)
+/// pinpoints the first value on which the prover and the verifier disagree. This is useful for
+/// debugging an AIR when verification fails with nothing more than a mismatch of out-of-domain
+/// constraint evaluations.
+///
+/// Values are recorded in their display form; digests are recorded as hex strings.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct TranscriptLog {
+    entries: Vec<TranscriptEntry>,
+}
+
+impl TranscriptLog {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new empty transcript log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the entries of this log in the order in which they were recorded.
+    pub fn entries(&self) -> &[TranscriptEntry] {
+        &self.entries
+    }
+
+    /// Returns the number of entries in this log.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no entries have been recorded in this log.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // RECORDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends an entry of the specified `kind` tagged with `label` to this log.
+    pub fn record<T: fmt::Display>(
+        &mut self,
+        kind: TranscriptEntryKind,
+        label: &str,
+        values: &[T],
+    ) {
+        self.entries.push(TranscriptEntry {
+            kind,
+            label: label.to_string(),
+            values: values.iter().map(|value| value.to_string()).collect(),
+        });
+    }
+
+    // COMPARISON
+    // --------------------------------------------------------------------------------------------
+
+    /// Compares this log with the `other` log, and returns the first entry at which the logs
+    /// differ, or None if the logs are identical.
+    ///
+    /// If one log is a prefix of the other, the logs are assumed to diverge at the first entry
+    /// missing from the shorter log.
+    pub fn diff(&self, other: &Self) -> Option<TranscriptDivergence> {
+        let num_entries = core::cmp::max(self.len(), other.len());
+        (0..num_entries)
+            .find(|&i| self.entries.get(i) != other.entries.get(i))
+            .map(|index| TranscriptDivergence {
+                index,
+                left: self.entries.get(index).cloned(),
+                right: other.entries.get(index).cloned(),
+            })
+    }
+}
+
+impl Serializable for TranscriptLog {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u32(self.entries.len() as u32);
+        for entry in self.entries.iter() {
+            entry.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for TranscriptLog {
+    /// Reads a transcript log from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid transcript log could not be read from the specified `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_entries = source.read_u32()? as usize;
+        let mut entries = Vec::with_capacity(num_entries);
+        for _ in 0..num_entries {
+            entries.push(TranscriptEntry::read_from(source)?);
+        }
+        Ok(Self { entries })
+    }
+}
+
+// TRANSCRIPT ENTRY
+// ================================================================================================
+
+/// Type of an operation recorded in a [TranscriptLog].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[repr(u8)]
+pub enum TranscriptEntryKind {
+    /// Data absorbed into the public coin.
+    Absorb = 0,
+    /// Values drawn from the public coin.
+    Draw = 1,
+    /// Intermediate values computed during the out-of-domain consistency check; these values do
+    /// not affect the public coin.
+    Evaluation = 2,
+}
+
+impl fmt::Display for TranscriptEntryKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Absorb => write!(f, "absorb"),
+            Self::Draw => write!(f, "draw"),
+            Self::Evaluation => write!(f, "evaluation"),
+        }
+    }
+}
+
+/// A single entry of a [TranscriptLog].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TranscriptEntry {
+    kind: TranscriptEntryKind,
+    label: String,
+    values: Vec<String>,
+}
+
+impl TranscriptEntry {
+    /// Returns the type of the operation recorded in this entry.
+    pub fn kind(&self) -> TranscriptEntryKind {
+        self.kind
+    }
+
+    /// Returns the label with which the operation was tagged.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the recorded values in their display form.
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+}
+
+impl fmt::Display for TranscriptEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: [{}]",
+            self.kind,
+            self.label,
+            self.values.join(", ")
+        )
+    }
+}
+
+impl Serializable for TranscriptEntry {
+    /// Serializes `self` and writes the resulting bytes into the `target`.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.kind as u8);
+        write_string(target, &self.label);
+        target.write_u32(self.values.len() as u32);
+        for value in self.values.iter() {
+            write_string(target, value);
+        }
+    }
+}
+
+impl Deserializable for TranscriptEntry {
+    /// Reads a transcript entry from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid transcript entry could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let kind = match source.read_u8()? {
+            0 => TranscriptEntryKind::Absorb,
+            1 => TranscriptEntryKind::Draw,
+            2 => TranscriptEntryKind::Evaluation,
+            value => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "value {value} cannot be deserialized as TranscriptEntryKind enum"
+                )))
+            }
+        };
+        let label = read_string(source)?;
+        let num_values = source.read_u32()? as usize;
+        let mut values = Vec::with_capacity(num_values);
+        for _ in 0..num_values {
+            values.push(read_string(source)?);
+        }
+        Ok(Self {
+            kind,
+            label,
+            values,
+        })
+    }
+}
+
+// TRANSCRIPT DIVERGENCE
+// ================================================================================================
+
+/// The first point at which two transcript logs differ, as returned by
+/// [TranscriptLog::diff()].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TranscriptDivergence {
+    /// Index of the first entry which differs between the logs.
+    pub index: usize,
+    /// Entry at this index in the log on which `diff()` was invoked, or None if the log is
+    /// shorter than that.
+    pub left: Option<TranscriptEntry>,
+    /// Entry at this index in the log passed to `diff()`, or None if the log is shorter than
+    /// that.
+    pub right: Option<TranscriptEntry>,
+}
+
+impl fmt::Display for TranscriptDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "transcripts diverge at entry {}:", self.index)?;
+        match &self.left {
+            Some(entry) => writeln!(f, "  left:  {entry}")?,
+            None => writeln!(f, "  left:  <end of log>")?,
+        }
+        match &self.right {
+            Some(entry) => write!(f, "  right: {entry}"),
+            None => write!(f, "  right: <end of log>"),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the length of the specified string followed by its UTF-8 bytes into the `target`.
+fn write_string<W: ByteWriter>(target: &mut W, value: &str) {
+    target.write_u32(value.len() as u32);
+    target.write_u8_slice(value.as_bytes());
+}
+
+/// Reads a string written by [write_string()] from the specified `source`.
+fn read_string<R: ByteReader>(source: &mut R) -> Result<String, DeserializationError> {
+    let len = source.read_u32()? as usize;
+    let bytes = source.read_u8_vec(len)?;
+    String::from_utf8(bytes)
+        .map_err(|_| DeserializationError::InvalidValue("string is not valid UTF-8".to_string()))
+}
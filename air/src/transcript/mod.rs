@@ -0,0 +1,406 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::TranscriptType;
+use crypto::{Digest, Hasher, LabeledTranscript, LegacyTranscript, RandomCoinError, Transcript};
+use math::{FieldElement, StarkField};
+use utils::{collections::Vec, string::String};
+
+mod log;
+pub use log::{TranscriptDivergence, TranscriptEntry, TranscriptEntryKind, TranscriptLog};
+
+mod positions;
+pub use positions::QueryPositions;
+
+// LABELS
+// ================================================================================================
+
+pub mod labels {
+    //! Contains labels with which STARK protocol operations are tagged in the public coin.
+    //!
+    //! The labels affect the values drawn from the coin only when the coin is instantiated as a
+    //! [LabeledTranscript](crypto::LabeledTranscript) (see
+    //! [TranscriptType](crate::TranscriptType)).
+    //!
+    //! Labels prefixed with `ood-eval` tag intermediate values of the out-of-domain consistency
+    //! check recorded in a [TranscriptLog](crate::TranscriptLog); these values are never absorbed
+    //! into the public coin.
+
+    pub use fri::labels::{FRI_ALPHA, FRI_LAYER_COMMITMENT};
+
+    /// Label for absorbing a commitment to a trace segment.
+    pub const TRACE_COMMITMENT: &str = "trace-commitment";
+    /// Label for absorbing AIR-specific data before drawing auxiliary trace random elements.
+    pub const AUX_RAND_ABSORB_DATA: &str = "aux-rand-absorb-data";
+    /// Label for drawing random elements for building an auxiliary trace segment.
+    pub const AUX_TRACE_RAND_ELEMENTS: &str = "aux-trace-rand-elements";
+    /// Label for drawing constraint composition coefficients.
+    pub const CONSTRAINT_COEFFICIENTS: &str = "constraint-coefficients";
+    /// Label for absorbing a commitment to the constraint composition polynomial evaluations.
+    pub const CONSTRAINT_COMMITMENT: &str = "constraint-commitment";
+    /// Label for drawing the out-of-domain point.
+    pub const OOD_POINT: &str = "ood-point";
+    /// Label for absorbing out-of-domain trace states.
+    pub const OOD_TRACE_STATES: &str = "ood-trace-states";
+    /// Label for absorbing out-of-domain constraint evaluations.
+    pub const OOD_CONSTRAINT_EVALUATIONS: &str = "ood-constraint-evaluations";
+    /// Label for absorbing out-of-domain values of committed periodic columns.
+    pub const OOD_PERIODIC_VALUES: &str = "ood-periodic-values";
+    /// Label for drawing DEEP composition coefficients.
+    pub const DEEP_COEFFICIENTS: &str = "deep-coefficients";
+    /// Label for drawing the coefficient which combines DEEP composition polynomials of all
+    /// instances of a batch proof.
+    pub const BATCH_COEFFICIENT: &str = "batch-coefficient";
+    /// Label for absorbing the proof-of-work nonce of the query seed.
+    pub const POW_NONCE: &str = "pow-nonce";
+    /// Label for drawing query positions at which both the trace and the constraint evaluations
+    /// are opened.
+    pub const QUERY_POSITIONS: &str = "query-positions";
+    /// Label for drawing positions at which only the trace is opened.
+    pub const TRACE_QUERY_POSITIONS: &str = "trace-query-positions";
+    /// Label for drawing positions at which only the constraint evaluations are opened.
+    pub const CONSTRAINT_QUERY_POSITIONS: &str = "constraint-query-positions";
+
+    /// Label for recording values of periodic columns at the out-of-domain point.
+    pub const OOD_EVAL_PERIODIC_VALUES: &str = "ood-eval-periodic-values";
+    /// Label for recording evaluations of main transition constraints at the out-of-domain point.
+    pub const OOD_EVAL_MAIN_TRANSITIONS: &str = "ood-eval-main-transitions";
+    /// Label for recording evaluations of auxiliary transition constraints at the out-of-domain
+    /// point.
+    pub const OOD_EVAL_AUX_TRANSITIONS: &str = "ood-eval-aux-transitions";
+    /// Label for recording values of transition constraint divisors at the out-of-domain point.
+    pub const OOD_EVAL_TRANSITION_DIVISORS: &str = "ood-eval-transition-divisors";
+    /// Label for recording the random linear combination of transition constraint evaluations
+    /// divided by their divisors.
+    pub const OOD_EVAL_TRANSITION_COMBINATION: &str = "ood-eval-transition-combination";
+    /// Label for recording values of boundary constraint divisors at the out-of-domain point.
+    pub const OOD_EVAL_BOUNDARY_DIVISORS: &str = "ood-eval-boundary-divisors";
+    /// Label for recording evaluations of boundary constraint groups at the out-of-domain point.
+    pub const OOD_EVAL_BOUNDARY_GROUPS: &str = "ood-eval-boundary-groups";
+    /// Label for recording the value of the constraint composition polynomial at the
+    /// out-of-domain point. The prover records the value of the polynomial it committed to, while
+    /// the verifier records the value implied by the AIR; a divergence at this entry which is not
+    /// preceded by any other divergence indicates that the trace does not satisfy the constraints.
+    pub const OOD_EVAL_CONSTRAINT_COMPOSITION: &str = "ood-eval-constraint-composition";
+}
+
+// PUBLIC COIN
+// ================================================================================================
+
+/// Public coin of the STARK protocol instantiated as one of the supported transcripts.
+///
+/// The transcript is selected at runtime based on the [TranscriptType] specified in the proof
+/// options. This way, the prover and the verifier use the same transcript implementation for a
+/// given proof without the need for an additional generic parameter.
+///
+/// Optionally, the coin can record all values absorbed into and drawn from it into a
+/// [TranscriptLog] (see [with_log()](PublicCoin::with_log)).
+pub struct PublicCoin<B: StarkField, H: Hasher> {
+    transcript: CoinTranscript<B, H>,
+    log: Option<TranscriptLog>,
+}
+
+/// Transcript implementations backing a [PublicCoin].
+enum CoinTranscript<B: StarkField, H: Hasher> {
+    /// Transcript which ignores operation labels.
+    Legacy(LegacyTranscript<B, H>),
+    /// Transcript which includes operation labels into the hash input.
+    Labeled(LabeledTranscript<B, H>),
+}
+
+impl<B: StarkField, H: Hasher> PublicCoin<B, H> {
+    /// Returns a new public coin of the specified type instantiated with the provided `seed`.
+    pub fn new(transcript_type: TranscriptType, seed: &[u8]) -> Self {
+        let transcript = match transcript_type {
+            TranscriptType::Legacy => CoinTranscript::Legacy(LegacyTranscript::new(seed)),
+            TranscriptType::Labeled => CoinTranscript::Labeled(LabeledTranscript::new(seed)),
+        };
+        Self {
+            transcript,
+            log: None,
+        }
+    }
+
+    /// Returns this public coin with recording of all subsequent operations enabled.
+    ///
+    /// Recording does not affect the values drawn from the coin.
+    pub fn with_log(mut self) -> Self {
+        self.log = Some(TranscriptLog::new());
+        self
+    }
+
+    /// Returns a mutable reference to the log of this coin, or None if recording is not enabled;
+    /// this can be used to record values which do not go through the coin.
+    pub fn log_mut(&mut self) -> Option<&mut TranscriptLog> {
+        self.log.as_mut()
+    }
+
+    /// Returns the log recorded by this coin so far, and stops recording; returns None if
+    /// recording was not enabled.
+    pub fn take_log(&mut self) -> Option<TranscriptLog> {
+        self.log.take()
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the specified values in the log of this coin, if recording is enabled.
+    fn record<T: core::fmt::Display>(
+        &mut self,
+        kind: TranscriptEntryKind,
+        label: &str,
+        values: &[T],
+    ) {
+        if let Some(log) = self.log.as_mut() {
+            log.record(kind, label, values);
+        }
+    }
+}
+
+impl<B: StarkField, H: Hasher> Transcript<B> for PublicCoin<B, H> {
+    type Hasher = H;
+
+    fn absorb(&mut self, label: &str, data: H::Digest) {
+        match &mut self.transcript {
+            CoinTranscript::Legacy(coin) => coin.absorb(label, data),
+            CoinTranscript::Labeled(coin) => coin.absorb(label, data),
+        }
+        self.record(
+            TranscriptEntryKind::Absorb,
+            label,
+            &[to_hex(&data.as_bytes())],
+        );
+    }
+
+    fn absorb_int(&mut self, label: &str, value: u64) {
+        match &mut self.transcript {
+            CoinTranscript::Legacy(coin) => coin.absorb_int(label, value),
+            CoinTranscript::Labeled(coin) => coin.absorb_int(label, value),
+        }
+        self.record(TranscriptEntryKind::Absorb, label, &[value]);
+    }
+
+    fn draw<E>(&mut self, label: &str) -> Result<E, RandomCoinError>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        let value = match &mut self.transcript {
+            CoinTranscript::Legacy(coin) => Transcript::draw(coin, label),
+            CoinTranscript::Labeled(coin) => coin.draw(label),
+        }?;
+        self.record(TranscriptEntryKind::Draw, label, &[value]);
+        Ok(value)
+    }
+
+    fn draw_integers(
+        &mut self,
+        label: &str,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        let values = match &mut self.transcript {
+            CoinTranscript::Legacy(coin) => {
+                Transcript::draw_integers(coin, label, num_values, domain_size)
+            }
+            CoinTranscript::Labeled(coin) => coin.draw_integers(label, num_values, domain_size),
+        }?;
+        self.record(TranscriptEntryKind::Draw, label, &values);
+        Ok(values)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        match &self.transcript {
+            CoinTranscript::Legacy(coin) => coin.leading_zeros(),
+            CoinTranscript::Labeled(coin) => coin.leading_zeros(),
+        }
+    }
+
+    fn find_pow_nonce(&self, label: &str, grinding_factor: u32, offset: u64) -> u64 {
+        match &self.transcript {
+            CoinTranscript::Legacy(coin) => {
+                Transcript::find_pow_nonce(coin, label, grinding_factor, offset)
+            }
+            CoinTranscript::Labeled(coin) => coin.find_pow_nonce(label, grinding_factor, offset),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a hex representation of the specified bytes.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{labels, PublicCoin, TranscriptEntryKind, TranscriptLog};
+    use crate::{FieldExtension, ProofOptions, ProofOptionsBuilder, TranscriptType};
+    use crypto::{hashers::Blake3_256, Hasher, Transcript};
+    use math::fields::f64::BaseElement;
+    use utils::{Deserializable, Serializable, SliceReader};
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    /// Runs a short sequence of public coin operations and returns the drawn values.
+    fn run_protocol(coin: &mut PublicCoin<BaseElement, Blake3>) -> (BaseElement, Vec<usize>) {
+        coin.absorb(labels::TRACE_COMMITMENT, Blake3::hash(&[1, 2, 3]));
+        let z = coin.draw::<BaseElement>(labels::OOD_POINT).unwrap();
+        coin.absorb_int(labels::POW_NONCE, 42);
+        let positions = coin.draw_integers(labels::QUERY_POSITIONS, 4, 64).unwrap();
+        (z, positions)
+    }
+
+    #[test]
+    fn public_coin_log() {
+        for transcript_type in [TranscriptType::Legacy, TranscriptType::Labeled] {
+            let mut coin = PublicCoin::<BaseElement, Blake3>::new(transcript_type, &[1, 2, 3]);
+            let expected = run_protocol(&mut coin);
+            assert_eq!(None, coin.take_log());
+
+            // recording does not affect the values drawn from the coin
+            let mut coin = PublicCoin::new(transcript_type, &[1, 2, 3]).with_log();
+            assert_eq!(expected, run_protocol(&mut coin));
+
+            let log = coin.take_log().unwrap();
+            let kinds = log
+                .entries()
+                .iter()
+                .map(|entry| entry.kind())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vec![
+                    TranscriptEntryKind::Absorb,
+                    TranscriptEntryKind::Draw,
+                    TranscriptEntryKind::Absorb,
+                    TranscriptEntryKind::Draw
+                ],
+                kinds
+            );
+            assert_eq!(labels::OOD_POINT, log.entries()[1].label());
+            assert_eq!([expected.0.to_string()], log.entries()[1].values());
+            assert_eq!(["42"], log.entries()[2].values());
+            assert_eq!(4, log.entries()[3].values().len());
+
+            // the log can be serialized and deserialized
+            let bytes = log.to_bytes();
+            let result = TranscriptLog::read_from(&mut SliceReader::new(&bytes)).unwrap();
+            assert_eq!(log, result);
+        }
+    }
+
+    #[test]
+    fn draw_query_positions() {
+        let build_options = |num_trace_queries, num_constraint_queries| {
+            ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 256)
+                .num_trace_queries(num_trace_queries)
+                .num_constraint_queries(num_constraint_queries)
+                .allow_insecure()
+                .build()
+                .unwrap()
+        };
+
+        for transcript_type in [TranscriptType::Legacy, TranscriptType::Labeled] {
+            let new_coin = || PublicCoin::<BaseElement, Blake3>::new(transcript_type, &[1, 2, 3]);
+
+            // with equal numbers of queries, the positions are drawn as a single set
+            let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+            let positions = new_coin().draw_query_positions(&options, 64).unwrap();
+            let expected = new_coin()
+                .draw_integers(labels::QUERY_POSITIONS, 28, 64)
+                .unwrap();
+            assert_eq!(expected, positions.shared());
+            assert_eq!(expected, positions.trace());
+            assert_eq!(expected, positions.constraint());
+
+            for (num_trace_queries, num_constraint_queries) in [(20, 28), (28, 20), (1, 63)] {
+                let options = build_options(num_trace_queries, num_constraint_queries);
+                let positions = new_coin().draw_query_positions(&options, 64).unwrap();
+                assert_eq!(num_trace_queries, positions.trace().len());
+                assert_eq!(num_constraint_queries, positions.constraint().len());
+
+                // both sets start with the shared positions, which are drawn as before the
+                // numbers could differ
+                let num_queries = options.num_queries();
+                let expected = new_coin()
+                    .draw_integers(labels::QUERY_POSITIONS, num_queries, 64)
+                    .unwrap();
+                assert_eq!(expected, positions.shared());
+                assert_eq!(expected, positions.trace()[..num_queries]);
+                assert_eq!(expected, positions.constraint()[..num_queries]);
+
+                // the remaining positions are unique
+                let mut trace = positions.trace().to_vec();
+                trace.sort_unstable();
+                trace.dedup();
+                assert_eq!(num_trace_queries, trace.len());
+                let mut constraint = positions.constraint().to_vec();
+                constraint.sort_unstable();
+                constraint.dedup();
+                assert_eq!(num_constraint_queries, constraint.len());
+            }
+
+            // the remaining positions are drawn with a label identifying the set they belong to
+            let mut coin = new_coin().with_log();
+            coin.draw_query_positions(&build_options(28, 20), 64)
+                .unwrap();
+            coin.draw_query_positions(&build_options(20, 28), 64)
+                .unwrap();
+            let labels = coin
+                .take_log()
+                .unwrap()
+                .entries()
+                .iter()
+                .map(|entry| entry.label().to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vec![
+                    labels::QUERY_POSITIONS,
+                    labels::TRACE_QUERY_POSITIONS,
+                    labels::QUERY_POSITIONS,
+                    labels::CONSTRAINT_QUERY_POSITIONS
+                ],
+                labels
+            );
+        }
+
+        // the positions cannot be drawn when there are not enough elements in the domain
+        let mut coin = PublicCoin::<BaseElement, Blake3>::new(TranscriptType::Labeled, &[1, 2, 3]);
+        assert!(coin
+            .draw_query_positions(&build_options(20, 64), 64)
+            .is_err());
+    }
+
+    #[test]
+    fn transcript_log_diff() {
+        let mut log1 = TranscriptLog::new();
+        log1.record(TranscriptEntryKind::Draw, labels::OOD_POINT, &[1u64]);
+        log1.record(TranscriptEntryKind::Evaluation, "a", &[2u64, 3]);
+        assert_eq!(None, log1.diff(&log1.clone()));
+
+        // logs diverge at the first differing value
+        let mut log2 = TranscriptLog::new();
+        log2.record(TranscriptEntryKind::Draw, labels::OOD_POINT, &[1u64]);
+        log2.record(TranscriptEntryKind::Evaluation, "a", &[2u64, 4]);
+        let divergence = log1.diff(&log2).unwrap();
+        assert_eq!(1, divergence.index);
+        assert_eq!(Some(&log1.entries()[1]), divergence.left.as_ref());
+        assert_eq!(Some(&log2.entries()[1]), divergence.right.as_ref());
+        let expected = "transcripts diverge at entry 1:\n\
+            \x20 left:  evaluation a: [2, 3]\n\
+            \x20 right: evaluation a: [2, 4]";
+        assert_eq!(expected, divergence.to_string());
+
+        // a log which is a prefix of another log diverges at the first missing entry
+        let mut log3 = log1.clone();
+        log3.record(TranscriptEntryKind::Draw, labels::QUERY_POSITIONS, &[5u64]);
+        let divergence = log1.diff(&log3).unwrap();
+        assert_eq!(2, divergence.index);
+        assert_eq!(None, divergence.left);
+        assert_eq!(Some(&log3.entries()[2]), divergence.right.as_ref());
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{labels, PublicCoin};
+use crate::ProofOptions;
+use crypto::{Hasher, RandomCoinError, Transcript};
+use math::StarkField;
+use utils::collections::Vec;
+
+// QUERY POSITIONS
+// ================================================================================================
+
+/// Positions in the LDE domain at which the execution trace and the constraint evaluations of a
+/// STARK proof are opened.
+///
+/// Both sets of positions start with the same [ProofOptions::num_queries()] positions, at which
+/// the DEEP composition polynomial is evaluated and FRI layers are queried. When the numbers of
+/// trace and constraint queries differ, the larger set is followed by the remaining positions,
+/// which are opened only against the respective commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryPositions {
+    trace: Vec<usize>,
+    constraint: Vec<usize>,
+    num_queries: usize,
+}
+
+impl QueryPositions {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns positions at which both the trace and the constraint evaluations are opened; the
+    /// DEEP composition polynomial is evaluated and FRI layers are queried at these positions.
+    pub fn shared(&self) -> &[usize] {
+        &self.trace[..self.num_queries]
+    }
+
+    /// Returns positions at which the execution trace (and committed periodic columns, if any)
+    /// are opened.
+    pub fn trace(&self) -> &[usize] {
+        &self.trace
+    }
+
+    /// Returns positions at which the constraint evaluations are opened.
+    pub fn constraint(&self) -> &[usize] {
+        &self.constraint
+    }
+}
+
+// PUBLIC COIN
+// ================================================================================================
+
+impl<B: StarkField, H: Hasher> PublicCoin<B, H> {
+    /// Draws positions at which the trace and the constraint evaluations of a proof generated
+    /// with the specified `options` are opened.
+    ///
+    /// The shared positions are drawn with the [labels::QUERY_POSITIONS] label; thus, when the
+    /// numbers of trace and constraint queries are equal, the positions are the same as the ones
+    /// drawn before the numbers could differ. Otherwise, the remaining positions of the larger
+    /// set are drawn with the [labels::TRACE_QUERY_POSITIONS] or
+    /// [labels::CONSTRAINT_QUERY_POSITIONS] label, and are distinct from the shared positions.
+    /// The labels separate the draws only for [TranscriptType::Labeled](crate::TranscriptType)
+    /// coins; for legacy coins, the remaining positions are the next values drawn from the coin.
+    ///
+    /// # Errors
+    /// Returns an error if the positions could not be drawn; in particular, the number of trace
+    /// and constraint queries must be smaller than `lde_domain_size`.
+    pub fn draw_query_positions(
+        &mut self,
+        options: &ProofOptions,
+        lde_domain_size: usize,
+    ) -> Result<QueryPositions, RandomCoinError> {
+        let num_queries = options.num_queries();
+        let shared = self.draw_integers(labels::QUERY_POSITIONS, num_queries, lde_domain_size)?;
+
+        let num_trace_queries = options.num_trace_queries();
+        let num_constraint_queries = options.num_constraint_queries();
+        let (trace, constraint) = if num_trace_queries > num_constraint_queries {
+            let trace = self.extend_positions(
+                labels::TRACE_QUERY_POSITIONS,
+                &shared,
+                num_trace_queries,
+                lde_domain_size,
+            )?;
+            (trace, shared)
+        } else if num_constraint_queries > num_trace_queries {
+            let constraint = self.extend_positions(
+                labels::CONSTRAINT_QUERY_POSITIONS,
+                &shared,
+                num_constraint_queries,
+                lde_domain_size,
+            )?;
+            (shared, constraint)
+        } else {
+            (shared.clone(), shared)
+        };
+
+        Ok(QueryPositions {
+            trace,
+            constraint,
+            num_queries,
+        })
+    }
+
+    /// Returns the `shared` positions followed by unique positions drawn with the specified
+    /// `label` which are not among the `shared` positions, such that the result contains
+    /// `num_values` positions.
+    fn extend_positions(
+        &mut self,
+        label: &str,
+        shared: &[usize],
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        // at most shared.len() of the drawn positions can be among the shared positions, and
+        // thus, there are always enough remaining positions
+        let drawn = self.draw_integers(label, num_values, domain_size)?;
+        let remaining = drawn
+            .into_iter()
+            .filter(|position| !shared.contains(position));
+
+        let mut result = shared.to_vec();
+        result.extend(remaining.take(num_values - shared.len()));
+        Ok(result)
+    }
+}
@@ -8,7 +8,7 @@ use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion
 use math::fields::f128;
 use rand_utils::rand_value;
 use winter_crypto::{
-    hashers::{Blake3_256, Rp62_248, Rp64_256, Sha3_256},
+    hashers::{Blake3_256, Poseidon64_256, Rp62_248, Rp64_256, Sha3_256},
     Hasher,
 };
 
@@ -20,6 +20,7 @@ type Sha3Digest = <Sha3 as Hasher>::Digest;
 
 type Rp62_248Digest = <Rp62_248 as Hasher>::Digest;
 type Rp64_256Digest = <Rp64_256 as Hasher>::Digest;
+type Poseidon64_256Digest = <Poseidon64_256 as Hasher>::Digest;
 
 fn blake3(c: &mut Criterion) {
     let v: [Blake3Digest; 2] = [Blake3::hash(&[1u8]), Blake3::hash(&[2u8])];
@@ -101,5 +102,25 @@ fn rescue256(c: &mut Criterion) {
     });
 }
 
-criterion_group!(hash_group, blake3, sha3, rescue248, rescue256);
+fn poseidon256(c: &mut Criterion) {
+    let v: [Poseidon64_256Digest; 2] = [Poseidon64_256::hash(&[1u8]), Poseidon64_256::hash(&[2u8])];
+    c.bench_function("hash_poseidon64_256 (cached)", |bench| {
+        bench.iter(|| Poseidon64_256::merge(black_box(&v)))
+    });
+
+    c.bench_function("hash_poseidon64_256 (random)", |b| {
+        b.iter_batched(
+            || {
+                [
+                    Poseidon64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                    Poseidon64_256::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Poseidon64_256::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(hash_group, blake3, sha3, rescue248, rescue256, poseidon256);
 criterion_main!(hash_group);
@@ -7,7 +7,6 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 use math::fields::f128::BaseElement;
 use rand_utils::rand_value;
-use utils::uninit_vector;
 use winter_crypto::{build_merkle_nodes, concurrent, hashers::Blake3_256, Hasher, MerkleTree};
 
 type Blake3 = Blake3_256<BaseElement>;
@@ -19,13 +18,9 @@ pub fn merkle_tree_construction(c: &mut Criterion) {
     static BATCH_SIZES: [usize; 3] = [65536, 131072, 262144];
 
     for size in &BATCH_SIZES {
-        let data: Vec<Blake3Digest> = {
-            let mut res = unsafe { uninit_vector(*size) };
-            for i in 0..*size {
-                res[i] = Blake3::hash(&rand_value::<u128>().to_le_bytes());
-            }
-            res
-        };
+        let data: Vec<Blake3Digest> = (0..*size)
+            .map(|_| Blake3::hash(&rand_value::<u128>().to_le_bytes()))
+            .collect();
         merkle_group.bench_with_input(BenchmarkId::new("sequential", size), &data, |b, i| {
             b.iter(|| build_merkle_nodes::<Blake3>(&i))
         });
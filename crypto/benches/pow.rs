@@ -0,0 +1,35 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use math::fields::f128::BaseElement;
+use rand_utils::rand_value;
+use std::time::Duration;
+use winter_crypto::{hashers::Blake3_256, RandomCoin};
+
+type Blake3 = Blake3_256<BaseElement>;
+
+const GRINDING_FACTORS: [u32; 3] = [8, 12, 16];
+
+fn find_pow_nonce(c: &mut Criterion) {
+    let mut group = c.benchmark_group("find_pow_nonce");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &grinding_factor in GRINDING_FACTORS.iter() {
+        group.bench_function(BenchmarkId::new("blake3", grinding_factor), |bench| {
+            bench.iter_batched(
+                || RandomCoin::<BaseElement, Blake3>::new(&rand_value::<u64>().to_le_bytes()),
+                |coin| coin.find_pow_nonce(grinding_factor, 0),
+                BatchSize::SmallInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(pow_group, find_pow_nonce);
+criterion_main!(pow_group);
@@ -76,6 +76,9 @@ pub enum RandomCoinError {
     /// The required number of integer values could not be drawn from the specified domain after
     /// the specified number of tries.
     FailedToDrawIntegers(usize, usize, usize),
+    /// The specified number of unique integer values could not be drawn because it is not smaller
+    /// than the size of the specified domain.
+    DomainTooSmall(usize, usize),
 }
 
 impl fmt::Display for RandomCoinError {
@@ -87,6 +90,12 @@ impl fmt::Display for RandomCoinError {
                     "failed to generate a valid field element after {num_tries} tries"
                 )
             }
+            Self::DomainTooSmall(num_values, domain_size) => {
+                write!(
+                    f,
+                    "cannot draw {num_values} unique integers from a domain of size {domain_size}"
+                )
+            }
             Self::FailedToDrawIntegers(num_expected, num_actual, num_tries) => {
                 write!(
                     f,
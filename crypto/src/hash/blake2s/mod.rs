@@ -0,0 +1,89 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ByteDigest, ElementHasher, Hasher};
+use blake2::{Blake2s256, Digest};
+use core::{fmt::Debug, marker::PhantomData};
+use math::{FieldElement, StarkField};
+use utils::ByteWriter;
+
+#[cfg(test)]
+mod tests;
+
+// BLAKE2s WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for BLAKE2s hash function with 256-bit
+/// output.
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct Blake2s_256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Blake2s_256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(Blake2s256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(Blake2s256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_le_bytes());
+        ByteDigest(Blake2s256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Blake2s_256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can hash
+            // element bytes directly
+            let bytes = E::elements_as_bytes(elements);
+            ByteDigest(Blake2s256::digest(bytes).into())
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them before hashing
+            let mut hasher = Blake2sHasher::new();
+            hasher.write(elements);
+            ByteDigest(hasher.finalize())
+        }
+    }
+}
+
+// BLAKE2s HASHER
+// ================================================================================================
+
+/// Wrapper around BLAKE2s hasher to implement [ByteWriter] trait for it.
+struct Blake2sHasher(Blake2s256);
+
+impl Blake2sHasher {
+    pub fn new() -> Self {
+        Self(Blake2s256::new())
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+}
+
+impl ByteWriter for Blake2sHasher {
+    fn write_u8(&mut self, value: u8) {
+        self.0.update([value]);
+    }
+
+    fn write_u8_slice(&mut self, values: &[u8]) {
+        self.0.update(values);
+    }
+}
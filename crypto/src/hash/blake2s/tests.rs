@@ -0,0 +1,80 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Blake2s_256, ElementHasher, Hasher};
+use math::fields::{f128, f62};
+
+type Blake2s = Blake2s_256<f128::BaseElement>;
+
+#[test]
+fn hash() {
+    // test vector from RFC 7693
+    let result = Blake2s::hash(b"abc");
+    assert_eq!(
+        hex_to_bytes("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982"),
+        result.0
+    );
+}
+
+#[test]
+fn merge() {
+    let d1 = Blake2s::hash(&[1u8]);
+    let d2 = Blake2s::hash(&[2u8]);
+    assert_eq!(
+        hex_to_bytes("fc87f4f1d721942d282aa61989c069b290d1447d20d4b811430f47ea0edf9991"),
+        d1.0
+    );
+    assert_eq!(
+        hex_to_bytes("2ca4b2f5cb29b023b371d3d6ef0161262b6dd2f20b57b09b44938bc654c789fc"),
+        d2.0
+    );
+
+    let result = Blake2s::merge(&[d1, d2]);
+    assert_eq!(
+        hex_to_bytes("b38e97a6a1d2ef6f664b3a4ff93649757079163d1944a1aea68d521ebd6b8273"),
+        result.0
+    );
+}
+
+#[test]
+fn merge_with_int() {
+    let seed = Blake2s::hash(&[1u8]);
+    let result = Blake2s::merge_with_int(seed, 42);
+    assert_eq!(
+        hex_to_bytes("8ca36db9e98570783a7ca5beb69d69a32bc72568ff10e7f7c034833450848114"),
+        result.0
+    );
+}
+
+#[test]
+fn hash_elements() {
+    // elements with canonical internal representation are hashed directly
+    let elements = [1u128, 2, 3].map(f128::BaseElement::new);
+    let result = Blake2s_256::hash_elements(&elements);
+    assert_eq!(
+        hex_to_bytes("4a086f3cc928da4e4b6352f0767fd97836d704b0d6f4c597b58f7f7d07287454"),
+        result.0
+    );
+
+    // elements with non-canonical internal representation are serialized before hashing
+    let elements = [1u64, 2, 3].map(f62::BaseElement::new);
+    let result = Blake2s_256::hash_elements(&elements);
+    assert_eq!(
+        hex_to_bytes("30a4e9f23cecf6bdf4947c89a439c31e5919dc2e1451f7fcb2492b8e50e3fe5a"),
+        result.0
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn hex_to_bytes(value: &str) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    for (i, byte) in result.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).unwrap();
+    }
+    result
+}
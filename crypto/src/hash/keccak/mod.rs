@@ -0,0 +1,131 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ByteDigest, ElementHasher, Hasher};
+use core::marker::PhantomData;
+use math::{FieldElement, StarkField};
+use sha3::{Digest, Keccak256};
+use utils::ByteWriter;
+
+#[cfg(test)]
+mod tests;
+
+// KECCAK WITH 256-BIT OUTPUT
+// ================================================================================================
+
+/// Implementation of the [Hasher](super::Hasher) trait for Keccak hash function with 256-bit
+/// output.
+///
+/// This is the original Keccak padding (as used by Ethereum's `keccak256`), and not the padding
+/// standardized in FIPS 202 which is used by [Sha3_256](super::Sha3_256). Inputs are encoded so
+/// that every hash can be recomputed on the EVM with a single `keccak256` call:
+/// * `hash(bytes)` is `keccak256(bytes)`.
+/// * `merge([a, b])` is `keccak256(a ‖ b)`, i.e., `keccak256(abi.encodePacked(a, b))` for
+///   `bytes32` values `a` and `b`. This is used for internal nodes of Merkle trees.
+/// * `merge_with_int(seed, value)` is `keccak256(seed ‖ value)` where `value` is encoded as 8
+///   big-endian bytes, i.e., `keccak256(abi.encodePacked(seed, uint64(value)))`.
+/// * `hash_elements(elements)` encodes every base field element (elements in extension fields
+///   are split into their base field coordinates) as its canonical integer value in a 32-byte
+///   big-endian word, i.e., `keccak256(abi.encodePacked(values))` for a `uint256[]` array of
+///   values. This is used for leaves of Merkle trees, and the resulting digest does not depend
+///   on the size of the base field.
+#[allow(non_camel_case_types)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct Keccak_256<B: StarkField>(PhantomData<B>);
+
+impl<B: StarkField> Hasher for Keccak_256<B> {
+    type Digest = ByteDigest<32>;
+
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        ByteDigest(Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        ByteDigest(Keccak256::digest(ByteDigest::digests_as_bytes(values)).into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut data = [0; 40];
+        data[..32].copy_from_slice(&seed.0);
+        data[32..].copy_from_slice(&value.to_be_bytes());
+        ByteDigest(Keccak256::digest(data).into())
+    }
+}
+
+impl<B: StarkField> ElementHasher for Keccak_256<B> {
+    type BaseField = B;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        let mut hasher = KeccakHasher::new(B::ELEMENT_BYTES);
+        if B::IS_CANONICAL {
+            // when element's internal and canonical representations are the same, we can read
+            // the canonical little-endian bytes of base field elements directly
+            hasher.write_u8_slice(E::elements_as_bytes(elements));
+        } else {
+            // when elements' internal and canonical representations differ, we need to serialize
+            // them first
+            hasher.write(elements);
+        }
+        ByteDigest(hasher.finalize())
+    }
+}
+
+// KECCAK HASHER
+// ================================================================================================
+
+/// Wrapper around Keccak hasher to implement [ByteWriter] trait for it.
+///
+/// Bytes are expected to be little-endian encodings of base field elements, each `element_bytes`
+/// long; every such encoding is absorbed into the hasher as a 32-byte big-endian word.
+struct KeccakHasher {
+    hasher: Keccak256,
+    word: [u8; 32],
+    element_bytes: usize,
+    num_bytes: usize,
+}
+
+impl KeccakHasher {
+    pub fn new(element_bytes: usize) -> Self {
+        debug_assert!(
+            element_bytes <= 32,
+            "element does not fit into a 32-byte word"
+        );
+        Self {
+            hasher: Keccak256::new(),
+            word: [0; 32],
+            element_bytes,
+            num_bytes: 0,
+        }
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        debug_assert_eq!(
+            self.num_bytes, 0,
+            "incomplete element was written into the hasher"
+        );
+        self.hasher.finalize().into()
+    }
+}
+
+impl ByteWriter for KeccakHasher {
+    fn write_u8(&mut self, value: u8) {
+        self.word[31 - self.num_bytes] = value;
+        self.num_bytes += 1;
+        if self.num_bytes == self.element_bytes {
+            self.hasher.update(self.word);
+            self.word = [0; 32];
+            self.num_bytes = 0;
+        }
+    }
+
+    fn write_u8_slice(&mut self, values: &[u8]) {
+        for &value in values {
+            self.write_u8(value);
+        }
+    }
+}
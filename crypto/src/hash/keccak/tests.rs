@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ElementHasher, Hasher, Keccak_256};
+use math::{
+    fields::{f128, f62, QuadExtension},
+    FieldElement,
+};
+
+type Keccak = Keccak_256<f128::BaseElement>;
+
+#[test]
+fn hash() {
+    // Ethereum's keccak256 of the empty string and of "abc"
+    let result = Keccak::hash(b"");
+    assert_eq!(
+        hex_to_bytes("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"),
+        result.0
+    );
+
+    let result = Keccak::hash(b"abc");
+    assert_eq!(
+        hex_to_bytes("4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"),
+        result.0
+    );
+}
+
+#[test]
+fn merge() {
+    let d1 = Keccak::hash(&[1u8]);
+    let d2 = Keccak::hash(&[2u8]);
+    assert_eq!(
+        hex_to_bytes("5fe7f977e71dba2ea1a68e21057beebb9be2ac30c6410aa38d4f3fbe41dcffd2"),
+        d1.0
+    );
+    assert_eq!(
+        hex_to_bytes("f2ee15ea639b73fa3db9b34a245bdfa015c260c598b211bf05a1ecc4b3e3b4f2"),
+        d2.0
+    );
+
+    // keccak256(abi.encodePacked(d1, d2))
+    let result = Keccak::merge(&[d1, d2]);
+    assert_eq!(
+        hex_to_bytes("71d8979cbfae9b197a4fbcc7d387b1fae9560e2f284d30b4e90c80f6bc074f57"),
+        result.0
+    );
+}
+
+#[test]
+fn merge_with_int() {
+    // keccak256(abi.encodePacked(seed, uint64(42)))
+    let seed = Keccak::hash(&[1u8]);
+    let result = Keccak::merge_with_int(seed, 42);
+    assert_eq!(
+        hex_to_bytes("5474f6a560ce40f247b5ba515ce4489a026080917ee134de9aa23ea5d1504217"),
+        result.0
+    );
+}
+
+#[test]
+fn hash_elements() {
+    // keccak256(abi.encodePacked([uint256(1), uint256(2), uint256(3)])); the digest is the same
+    // for fields with canonical and non-canonical internal representations
+    let expected = hex_to_bytes("6e0c627900b24bd432fe7b1f713f1b0744091a646a9fe4a65a18dfed21f2949c");
+
+    let elements = [1u128, 2, 3].map(f128::BaseElement::new);
+    assert_eq!(expected, Keccak_256::hash_elements(&elements).0);
+
+    let elements = [1u64, 2, 3].map(f62::BaseElement::new);
+    assert_eq!(expected, Keccak_256::hash_elements(&elements).0);
+
+    // elements are encoded as big-endian words
+    let element = f128::BaseElement::new(0x0102030405060708090a0b0c0d0e0f10);
+    assert_eq!(
+        hex_to_bytes("45b65ac8f478b9af122425346fd3d2a28038274f61ae8dafbce5812f29c598f0"),
+        Keccak_256::hash_elements(&[element]).0
+    );
+
+    let element = f62::BaseElement::new(0x0102030405060708);
+    assert_eq!(
+        hex_to_bytes("514148c05833ddeea0364a81300262a25ad938a9a4d00acb82fbc1f0a17b1bcf"),
+        Keccak_256::hash_elements(&[element]).0
+    );
+}
+
+#[test]
+fn hash_extension_elements() {
+    // extension field elements are hashed as sequences of their base field coordinates
+    let elements = [
+        QuadExtension::new(f62::BaseElement::new(1), f62::BaseElement::new(2)),
+        QuadExtension::new(f62::BaseElement::new(3), f62::BaseElement::ZERO),
+    ];
+    let base_elements = [1u64, 2, 3, 0].map(f62::BaseElement::new);
+    assert_eq!(
+        Keccak_256::hash_elements(&base_elements),
+        Keccak_256::hash_elements(&elements)
+    );
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn hex_to_bytes(value: &str) -> [u8; 32] {
+    let mut result = [0u8; 32];
+    for (i, byte) in result.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&value[2 * i..2 * i + 2], 16).unwrap();
+    }
+    result
+}
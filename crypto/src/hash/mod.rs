@@ -11,12 +11,21 @@ use utils::{ByteReader, Deserializable, DeserializationError, Serializable};
 mod blake;
 pub use blake::{Blake3_192, Blake3_256};
 
+mod blake2s;
+pub use blake2s::Blake2s_256;
+
 mod sha;
 pub use sha::Sha3_256;
 
+mod keccak;
+pub use keccak::Keccak_256;
+
 mod rescue;
 pub use rescue::{Rp62_248, Rp64_256};
 
+mod poseidon;
+pub use poseidon::Poseidon64_256;
+
 // HASHER TRAITS
 // ================================================================================================
 
@@ -0,0 +1,10 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Digest, ElementHasher, Hasher};
+
+mod p64_256;
+pub use p64_256::Poseidon64_256;
@@ -5,7 +5,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{exp_acc, Digest, ElementHasher, Hasher};
-use core::convert::TryInto;
+use core::{convert::TryInto, ops::Range};
 use math::{fields::f62::BaseElement, FieldElement, StarkField};
 
 mod digest;
@@ -176,7 +176,7 @@ impl Hasher for Rp62_248 {
         //   to 6.
         let mut state = [BaseElement::ZERO; STATE_WIDTH];
         state[..DIGEST_SIZE].copy_from_slice(seed.as_elements());
-        state[DIGEST_SIZE] = BaseElement::new(value);
+        state[DIGEST_SIZE] = BaseElement::from_u64_reduced(value);
         if value < BaseElement::MODULUS {
             state[STATE_WIDTH - 1] = BaseElement::new(DIGEST_SIZE as u64 + 1);
         } else {
@@ -229,6 +229,44 @@ impl ElementHasher for Rp62_248 {
     }
 }
 
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Rp62_248 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 7 to target 124-bit security level with 40% security margin.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 12 field elements or 744 bytes; 8 elements are reserved for rate and
+    /// the remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 7 (inclusive).
+    pub const RATE_RANGE: Range<usize> = 0..RATE_WIDTH;
+
+    /// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+    pub const CAPACITY_RANGE: Range<usize> = RATE_WIDTH..STATE_WIDTH;
+
+    /// The output of the hash function can be read from state elements 0, 1, 2, and 3.
+    pub const DIGEST_RANGE: Range<usize> = 0..DIGEST_SIZE;
+
+    // RESCUE PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies Rescue-XLIX permutation to the provided state.
+    pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+        apply_permutation(state)
+    }
+
+    /// Rescue-XLIX round function.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+        apply_round(state, round)
+    }
+}
+
 // RESCUE PERMUTATION
 // ================================================================================================
 
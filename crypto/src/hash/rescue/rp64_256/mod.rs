@@ -204,7 +204,7 @@ impl Hasher for Rp64_256 {
         //   to 6.
         let mut state = [BaseElement::ZERO; STATE_WIDTH];
         state[INPUT1_RANGE].copy_from_slice(seed.as_elements());
-        state[INPUT2_RANGE.start] = BaseElement::new(value);
+        state[INPUT2_RANGE.start] = BaseElement::from_u64_reduced(value);
         if value < BaseElement::MODULUS {
             state[CAPACITY_RANGE.start] = BaseElement::new(DIGEST_SIZE as u64 + 1);
         } else {
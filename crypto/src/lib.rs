@@ -7,15 +7,18 @@
 //! This crate contains cryptographic primitives used in STARK proof generation and verification.
 //! These include:
 //!
-//! * **Hash functions** - which are defined using the [Hasher] trait. The crate also contains two
-//!   implementations of the trait for BLAKE3 and SHA3 hash functions.
+//! * **Hash functions** - which are defined using the [Hasher] trait. The crate also contains
+//!   implementations of the trait for BLAKE3, BLAKE2s, SHA3, Rescue Prime, and Poseidon hash
+//!   functions.
 //! * **Merkle trees** - which are used as a commitment scheme in the STARK protocol. The
 //!   [MerkleTree] implementation supports concurrent tree construction as well as compact
 //!   aggregation of Merkle paths implemented using a variation of the
 //!   [Octopus](https://eprint.iacr.org/2017/933) algorithm.
 //! * **PRNG** - which is used to generate pseudo-random elements in a finite field. The
 //!   [RandomCoin] implementation uses a cryptographic hash function to generate pseudo-random
-//!   elements form a seed.
+//!   elements form a seed. The [Transcript] trait abstracts over the public coin; besides the
+//!   [LegacyTranscript] (which is just a [RandomCoin]), the crate provides a [LabeledTranscript]
+//!   which domain-separates values drawn at different places of a protocol.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -28,8 +31,11 @@ pub use hash::{Digest, ElementHasher, Hasher};
 pub mod hashers {
     //! Contains implementations of currently supported hash functions.
 
+    pub use super::hash::Blake2s_256;
     pub use super::hash::Blake3_192;
     pub use super::hash::Blake3_256;
+    pub use super::hash::Keccak_256;
+    pub use super::hash::Poseidon64_256;
     pub use super::hash::Rp62_248;
     pub use super::hash::Rp64_256;
     pub use super::hash::Sha3_256;
@@ -42,7 +48,7 @@ pub use merkle::{build_merkle_nodes, BatchMerkleProof, MerkleTree};
 pub use merkle::concurrent;
 
 mod random;
-pub use random::RandomCoin;
+pub use random::{LabeledTranscript, LegacyTranscript, RandomCoin, Transcript};
 
 mod errors;
 pub use errors::{MerkleTreeError, RandomCoinError};
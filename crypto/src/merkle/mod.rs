@@ -127,20 +127,67 @@ impl<H: Hasher> MerkleTree<H> {
         Ok(MerkleTree { nodes, leaves })
     }
 
+    /// Returns new Merkle tree built from the provided leaves, where every layer of internal nodes
+    /// is computed by the provided `build_layer` closure.
+    ///
+    /// The closure receives the nodes of a single layer of the tree (starting with the leaves),
+    /// and must return the layer above it; i.e., the i-th node of the returned layer must be the
+    /// result of merging nodes 2i and 2i + 1 of the received layer using hash function specified
+    /// by the `H` generic parameter. This allows building Merkle trees on external hardware.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * Fewer than two leaves were provided.
+    /// * Number of leaves is not a power of two.
+    ///
+    /// # Panics
+    /// Panics if the number of nodes in a layer returned by `build_layer` is not half the number
+    /// of nodes in the layer passed to it.
+    pub fn with_layer_builder<F>(
+        leaves: Vec<H::Digest>,
+        mut build_layer: F,
+    ) -> Result<Self, MerkleTreeError>
+    where
+        F: FnMut(&[H::Digest]) -> Vec<H::Digest>,
+    {
+        if leaves.len() < 2 {
+            return Err(MerkleTreeError::TooFewLeaves(2, leaves.len()));
+        }
+        if !leaves.len().is_power_of_two() {
+            return Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(leaves.len()));
+        }
+
+        // internal nodes are stored such that the root of the tree is at position 1, and the
+        // layer immediately above the leaves occupies the second half of the vector
+        let mut nodes = vec![H::Digest::default(); leaves.len()];
+        let mut layer = build_layer(&leaves);
+        let mut layer_start = leaves.len() / 2;
+        loop {
+            assert_eq!(
+                layer.len(),
+                layer_start,
+                "expected a layer of {} nodes, but was {}",
+                layer_start,
+                layer.len()
+            );
+            nodes[layer_start..2 * layer_start].copy_from_slice(&layer);
+            if layer_start == 1 {
+                break;
+            }
+            layer = build_layer(&nodes[layer_start..2 * layer_start]);
+            layer_start /= 2;
+        }
+
+        Ok(MerkleTree { nodes, leaves })
+    }
+
     /// Returns new Merkle tree built from default, empty leaves using hash function
     /// specified by the `H` generic parameter.
     pub fn build_empty(depth: usize) -> Self {
         // Calculate number of leaves
         let n = 2usize.pow(depth as u32);
-        // Build the leaves
-        let result = vec![0u8; n * core::mem::size_of::<H::Digest>()];
-
-        // translate a zero-filled vector of u8s into a vector of digests
-        let mut v = core::mem::ManuallyDrop::new(result);
-        let p = v.as_mut_ptr();
-        let len = n;
-        let cap = n;
-        let leaves = unsafe { Vec::from_raw_parts(p as *mut H::Digest, len, cap) };
+        // Build the leaves; all leaves are set to the default digest
+        let leaves = vec![H::Digest::default(); n];
         // Determine hash values for nodes depending upon level
         let mut cur_empty = leaves[0];
         let mut empty_nodes = Vec::with_capacity(depth);
@@ -368,9 +415,8 @@ impl<H: Hasher> MerkleTree<H> {
 pub fn build_merkle_nodes<H: Hasher>(leaves: &[H::Digest]) -> Vec<H::Digest> {
     let n = leaves.len() / 2;
 
-    // create un-initialized array to hold all intermediate nodes
-    let mut nodes = unsafe { utils::uninit_vector::<H::Digest>(2 * n) };
-    nodes[0] = H::Digest::default();
+    // allocate memory to hold all intermediate nodes; the node at position 0 is not used
+    let mut nodes = vec![H::Digest::default(); 2 * n];
 
     // re-interpret leaves as an array of two leaves fused together
     let two_leaves = unsafe { slice::from_raw_parts(leaves.as_ptr() as *const [H::Digest; 2], n) };
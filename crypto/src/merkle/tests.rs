@@ -7,6 +7,7 @@
 use super::*;
 use math::fields::f128::BaseElement;
 use proptest::prelude::*;
+use utils::{ByteReader, SliceReader};
 
 type Digest256 = crate::hash::ByteDigest<32>;
 type Blake3_256 = crate::hash::Blake3_256<BaseElement>;
@@ -92,6 +93,40 @@ fn new_tree() {
     assert_eq!(&root, tree.root());
 }
 
+#[test]
+fn new_tree_with_layer_builder() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let expected = MerkleTree::<Blake3_256>::new(leaves.clone()).unwrap();
+
+    let mut layer_sizes = Vec::new();
+    let tree = MerkleTree::<Blake3_256>::with_layer_builder(leaves, |nodes| {
+        layer_sizes.push(nodes.len());
+        nodes
+            .chunks(2)
+            .map(|pair| hash_2x1(pair[0], pair[1]))
+            .collect()
+    })
+    .unwrap();
+    assert_eq!(vec![8, 4, 2], layer_sizes);
+    assert_eq!(expected.root(), tree.root());
+    for i in 0..8 {
+        assert_eq!(expected.prove(i).unwrap(), tree.prove(i).unwrap());
+    }
+
+    let leaves = Digest256::bytes_as_digests(&LEAVES8[..3]).to_vec();
+    assert_eq!(
+        Err(MerkleTreeError::NumberOfLeavesNotPowerOfTwo(3)),
+        MerkleTree::<Blake3_256>::with_layer_builder(leaves, |_| unreachable!()).map(|_| ())
+    );
+}
+
+#[test]
+#[should_panic(expected = "expected a layer of 4 nodes, but was 8")]
+fn new_tree_with_invalid_layer_builder() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let _ = MerkleTree::<Blake3_256>::with_layer_builder(leaves, |nodes| nodes.to_vec());
+}
+
 #[test]
 fn prove() {
     // depth 4
@@ -233,6 +268,41 @@ fn verify_batch() {
     assert!(MerkleTree::verify_batch(tree.root(), &[0, 1, 2, 3, 4, 5, 6, 7], &proof).is_ok());
 }
 
+#[test]
+fn serialize_batch_proof() {
+    let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
+    let tree = MerkleTree::<Blake3_256>::new(leaves).unwrap();
+    let indexes = [1, 2, 3, 6];
+
+    let proof = tree.prove_batch(&indexes).unwrap();
+    let node_bytes = proof.serialize_nodes();
+
+    // internal nodes shared by the paths are serialized only once: the 4 individual paths
+    // contain 12 nodes, but only 3 of these are needed (as well as 1 byte for the number of
+    // node vectors and 1 byte for the number of nodes in each vector)
+    let num_nodes = proof.nodes.iter().map(|nodes| nodes.len()).sum::<usize>();
+    assert_eq!(3, num_nodes);
+    assert_eq!(1 + proof.nodes.len() + num_nodes * 32, node_bytes.len());
+
+    // deserializing the nodes results in the same proof, which can be verified
+    let mut reader = SliceReader::new(&node_bytes);
+    let parsed =
+        BatchMerkleProof::<Blake3_256>::deserialize(&mut reader, proof.leaves.clone(), proof.depth)
+            .unwrap();
+    assert!(!reader.has_more_bytes());
+    assert_eq!(proof, parsed);
+    assert!(MerkleTree::verify_batch(tree.root(), &indexes, &parsed).is_ok());
+
+    // truncated node bytes cannot be deserialized
+    let mut reader = SliceReader::new(&node_bytes[..node_bytes.len() - 1]);
+    assert!(BatchMerkleProof::<Blake3_256>::deserialize(
+        &mut reader,
+        proof.leaves.clone(),
+        proof.depth
+    )
+    .is_err());
+}
+
 #[test]
 fn verify_into_paths() {
     let leaves = Digest256::bytes_as_digests(&LEAVES8).to_vec();
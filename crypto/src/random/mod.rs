@@ -9,6 +9,12 @@ use core::{convert::TryInto, marker::PhantomData};
 use math::{FieldElement, StarkField};
 use utils::collections::Vec;
 
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+mod transcript;
+pub use transcript::{LabeledTranscript, LegacyTranscript, Transcript};
+
 // RANDOM COIN
 // ================================================================================================
 
@@ -169,6 +175,46 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         seed_head.trailing_zeros()
     }
 
+    // PROOF-OF-WORK
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the first nonce for which hash(`seed` || `nonce`) has at least `grinding_factor`
+    /// leading zeros (as computed by [check_leading_zeros()](RandomCoin::check_leading_zeros)).
+    ///
+    /// Nonces are tried in order starting with `offset + 1` (with wrap-around on overflow).
+    ///
+    /// When the crate is compiled with `concurrent` feature enabled, the nonce space is split
+    /// into chunks which are searched by multiple threads; threads stop as soon as a suitable
+    /// nonce is found in a preceding chunk. The search still returns the first suitable nonce,
+    /// and thus, the result does not depend on the number of threads.
+    ///
+    /// # Examples
+    /// ```
+    /// # use winter_crypto::{RandomCoin, hashers::Blake3_256};
+    /// # use math::fields::f128::BaseElement;
+    /// let mut coin = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+    ///
+    /// let nonce = coin.find_pow_nonce(8, 0);
+    /// assert!((1..nonce).all(|value| coin.check_leading_zeros(value) < 8));
+    ///
+    /// coin.reseed_with_int(nonce);
+    /// assert!(coin.leading_zeros() >= 8);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if a suitable nonce does not exist.
+    pub fn find_pow_nonce(&self, grinding_factor: u32, offset: u64) -> u64 {
+        #[cfg(not(feature = "concurrent"))]
+        let nonce = (1..u64::MAX)
+            .map(|i| offset.wrapping_add(i))
+            .find(|&nonce| self.check_leading_zeros(nonce) >= grinding_factor);
+
+        #[cfg(feature = "concurrent")]
+        let nonce = self.find_pow_nonce_concurrent(grinding_factor, offset);
+
+        nonce.expect("nonce not found")
+    }
+
     // DRAW METHODS
     // --------------------------------------------------------------------------------------------
 
@@ -182,13 +228,23 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
         E: FieldElement<BaseField = B>,
     {
         for _ in 0..1000 {
-            // get the next pseudo-random value and take the first ELEMENT_BYTES from it
-            let value = self.next();
-            let bytes = &value.as_bytes()[..E::ELEMENT_BYTES];
+            // get the next pseudo-random value and take the first ELEMENT_BYTES from it; elements
+            // wider than a digest (e.g., in a sextic extension) are built from the values of
+            // several consecutive calls to the PRNG
+            let value = self.next().as_bytes();
+            let element = if E::ELEMENT_BYTES <= value.len() {
+                E::from_random_bytes(&value[..E::ELEMENT_BYTES])
+            } else {
+                let mut bytes = value.to_vec();
+                while bytes.len() < E::ELEMENT_BYTES {
+                    bytes.extend_from_slice(&self.next().as_bytes());
+                }
+                E::from_random_bytes(&bytes[..E::ELEMENT_BYTES])
+            };
 
             // check if the bytes can be converted into a valid field element; if they can,
             // return; otherwise try again
-            if let Some(element) = E::from_random_bytes(bytes) {
+            if let Some(element) = element {
                 if element != E::ZERO {
                     return Ok(element);
                 }
@@ -224,14 +280,19 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
 
     /// Returns a vector of unique integers selected from the range [0, domain_size).
     ///
+    /// Values are drawn from the PRNG one at a time, and values which have already been drawn are
+    /// skipped. Thus, the result depends only on the state of the coin, and the same sequence of
+    /// unique values is returned by any coin with the same state.
+    ///
     /// # Errors
-    /// Returns an error if the specified number of unique integers could not be generated
-    /// after 1000 calls to the PRNG.
+    /// Returns an error if:
+    /// - `num_values` is greater than or equal to `domain_size`.
+    /// - The specified number of unique integers could not be generated after the maximum
+    ///   number of calls to the PRNG; this number is the greater of 1000 and 32 times
+    ///   `num_values`.
     ///
     /// # Panics
-    /// Panics if:
-    /// - `domain_size` is not a power of two.
-    /// - `num_values` is greater than or equal to `domain_size`.
+    /// Panics if `domain_size` is not a power of two.
     ///
     /// # Examples
     /// ```
@@ -261,17 +322,20 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
             domain_size.is_power_of_two(),
             "domain size must be a power of two"
         );
-        assert!(
-            num_values < domain_size,
-            "number of values must be smaller than domain size"
-        );
+        if num_values >= domain_size {
+            return Err(RandomCoinError::DomainTooSmall(num_values, domain_size));
+        }
 
         // determine how many bits are needed to represent valid values in the domain
         let v_mask = (domain_size - 1) as u64;
 
+        // the number of tries grows with the number of values so that drawing many values from
+        // a small domain does not fail only because of collisions
+        let num_tries = core::cmp::max(1000, num_values * 32);
+
         // draw values from PRNG until we get as many unique values as specified by num_queries
         let mut values = Vec::new();
-        for _ in 0..1000 {
+        for _ in 0..num_tries {
             // get the next pseudo-random value and read the first 8 bytes from it
             let bytes: [u8; 8] = self.next().as_bytes()[..8].try_into().unwrap();
 
@@ -292,7 +356,7 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
             return Err(RandomCoinError::FailedToDrawIntegers(
                 num_values,
                 values.len(),
-                1000,
+                num_tries,
             ));
         }
 
@@ -302,9 +366,198 @@ impl<B: StarkField, H: Hasher> RandomCoin<B, H> {
     // HELPER METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Searches for the first suitable proof-of-work nonce using multiple threads.
+    ///
+    /// The nonce space is processed in batches of `num_threads * 4` chunks. Within a batch, chunks
+    /// are searched concurrently, and a search of a chunk stops once it reaches a nonce which
+    /// follows the first suitable nonce found so far. If a batch contains a suitable nonce, the
+    /// first such nonce is returned; otherwise, the search proceeds to the next batch.
+    #[cfg(feature = "concurrent")]
+    fn find_pow_nonce_concurrent(&self, grinding_factor: u32, offset: u64) -> Option<u64> {
+        use core::sync::atomic::{AtomicU64, Ordering};
+
+        const CHUNK_SIZE: u64 = 1 << 10;
+        let num_chunks = (rayon_num_threads() * 4) as u64;
+
+        let mut batch_start = 1u64;
+        while batch_start < u64::MAX {
+            let first_hit = AtomicU64::new(u64::MAX);
+            (0..num_chunks).into_par_iter().for_each(|chunk| {
+                let chunk_start = batch_start.saturating_add(chunk * CHUNK_SIZE);
+                let chunk_end = chunk_start.saturating_add(CHUNK_SIZE);
+                for i in chunk_start..chunk_end {
+                    if i >= first_hit.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    if self.check_leading_zeros(offset.wrapping_add(i)) >= grinding_factor {
+                        first_hit.fetch_min(i, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            });
+
+            let first_hit = first_hit.into_inner();
+            if first_hit != u64::MAX {
+                return Some(offset.wrapping_add(first_hit));
+            }
+            batch_start = batch_start.saturating_add(num_chunks * CHUNK_SIZE);
+        }
+        None
+    }
+
     /// Updates the state by incrementing the counter and returns hash(seed || counter)
     fn next(&mut self) -> H::Digest {
         self.counter += 1;
         H::merge_with_int(self.seed, self.counter)
     }
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{LabeledTranscript, LegacyTranscript, RandomCoin, Transcript};
+    use crate::{
+        hashers::{Blake3_256, Sha3_256},
+        Digest, Hasher, RandomCoinError,
+    };
+    use math::{
+        fields::{f128::BaseElement, f64::BaseElement as F64Element, SexticExtension},
+        FieldElement,
+    };
+
+    #[test]
+    fn draw_sextic_extension_element() {
+        // elements of the sextic extension of the f64 field are wider than a digest
+        type E = SexticExtension<F64Element>;
+        type H = Blake3_256<F64Element>;
+        assert!(E::ELEMENT_BYTES > H::hash(&[]).as_bytes().len());
+
+        let mut coin = RandomCoin::<F64Element, H>::new(&[1, 2, 3, 4]);
+        let e1 = coin.draw::<E>().unwrap();
+        let e2 = coin.draw::<E>().unwrap();
+        assert_ne!(e1, e2);
+
+        // every coefficient of the element is drawn from the PRNG
+        for e in [e1, e2] {
+            let elements = [e];
+            let coefficients = E::as_base_elements(&elements);
+            assert_eq!(6, coefficients.len());
+            assert!(coefficients.iter().all(|&c| c != F64Element::ZERO));
+        }
+    }
+
+    #[test]
+    fn find_pow_nonce() {
+        let coin = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+        for grinding_factor in [0, 1, 4, 8, 12] {
+            for offset in [0, 42, u64::MAX - 3] {
+                let nonce = coin.find_pow_nonce(grinding_factor, offset);
+                assert!(coin.check_leading_zeros(nonce) >= grinding_factor);
+
+                // the nonce must be the first suitable one after the offset
+                let num_tries = nonce.wrapping_sub(offset);
+                assert!(num_tries >= 1);
+                assert!((1..num_tries)
+                    .all(|i| coin.check_leading_zeros(offset.wrapping_add(i)) < grinding_factor));
+
+                // reseeding the coin with the nonce must result in the required number of zeros
+                let mut coin =
+                    RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+                coin.reseed_with_int(nonce);
+                assert!(coin.leading_zeros() >= grinding_factor);
+            }
+        }
+    }
+
+    #[test]
+    fn find_pow_nonce_depends_on_seed() {
+        let coin1 = RandomCoin::<BaseElement, Sha3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+        let coin2 = RandomCoin::<BaseElement, Sha3_256<BaseElement>>::new(&[5, 6, 7, 8]);
+        let nonce1 = coin1.find_pow_nonce(16, 0);
+        let nonce2 = coin2.find_pow_nonce(16, 0);
+        assert_ne!(nonce1, nonce2);
+        assert_eq!(nonce1, coin1.find_pow_nonce(16, 0));
+    }
+
+    #[test]
+    fn labeled_transcript_pow_nonce() {
+        let mut transcript =
+            LabeledTranscript::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+        let nonce = transcript.find_pow_nonce("pow", 8, 0);
+
+        // absorbing the nonce with the same label must result in the required number of zeros
+        transcript.absorb_int("pow", nonce);
+        assert!(transcript.leading_zeros() >= 8);
+    }
+
+    #[test]
+    fn transcripts_are_label_sensitive() {
+        type Blake3 = Blake3_256<BaseElement>;
+
+        // legacy transcript ignores labels and matches a plain random coin
+        let mut coin = RandomCoin::<BaseElement, Blake3>::new(&[1, 2, 3, 4]);
+        let mut legacy = LegacyTranscript::<BaseElement, Blake3>::new(&[1, 2, 3, 4]);
+        let e1: BaseElement = coin.draw().unwrap();
+        let e2: BaseElement = Transcript::draw(&mut legacy, "alpha").unwrap();
+        assert_eq!(e1, e2);
+
+        // labeled transcripts draw different values for different labels
+        let mut t1 = LabeledTranscript::<BaseElement, Blake3>::new(&[1, 2, 3, 4]);
+        let mut t2 = LabeledTranscript::<BaseElement, Blake3>::new(&[1, 2, 3, 4]);
+        t1.absorb("commitment", Blake3::hash(&[5]));
+        t2.absorb("commitment", Blake3::hash(&[5]));
+        assert_eq!(
+            t1.draw::<BaseElement>("alpha").unwrap(),
+            t2.draw::<BaseElement>("alpha").unwrap()
+        );
+        assert_ne!(
+            t1.draw_integers("queries", 4, 64).unwrap(),
+            t2.draw_integers("positions", 4, 64).unwrap()
+        );
+    }
+
+    #[test]
+    fn draw_integers_from_small_domain() {
+        let mut coin = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+
+        // all but one value of the domain can be drawn, and all drawn values are unique
+        let mut values = coin.draw_integers(15, 16).unwrap();
+        values.sort_unstable();
+        values.dedup();
+        assert_eq!(15, values.len());
+        assert!(values.iter().all(|&value| value < 16));
+
+        // coins with the same state draw the same values
+        let mut coin1 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[5, 6]);
+        let mut coin2 = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[5, 6]);
+        assert_eq!(
+            coin1.draw_integers(15, 16).unwrap(),
+            coin2.draw_integers(15, 16).unwrap()
+        );
+
+        // drawing as many values as there are in the domain is an error
+        assert_eq!(
+            Err(RandomCoinError::DomainTooSmall(16, 16)),
+            coin.draw_integers(16, 16)
+        );
+        assert_eq!(
+            Err(RandomCoinError::DomainTooSmall(32, 16)),
+            coin.draw_integers(32, 16)
+        );
+    }
+
+    #[test]
+    fn draw_integers_from_almost_exhausted_domain() {
+        // the number of tries grows with the number of values, and thus, all but one value of a
+        // large domain can be drawn without exhausting the tries
+        for domain_size in [64, 1024] {
+            let mut coin = RandomCoin::<BaseElement, Blake3_256<BaseElement>>::new(&[1, 2, 3, 4]);
+            let mut values = coin.draw_integers(domain_size - 1, domain_size).unwrap();
+            values.sort_unstable();
+            values.dedup();
+            assert_eq!(domain_size - 1, values.len());
+        }
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::RandomCoin;
+use crate::{errors::RandomCoinError, Hasher};
+use core::marker::PhantomData;
+use math::{FieldElement, StarkField};
+use utils::collections::Vec;
+
+// TRANSCRIPT TRAIT
+// ================================================================================================
+
+/// Defines an interface for a public coin which absorbs messages sent by the prover and from
+/// which the verifier's challenges are drawn.
+///
+/// Every operation is tagged with a label identifying the place in the protocol where the
+/// operation happens. It is up to the implementation to decide whether the labels affect the
+/// drawn values: [LegacyTranscript] ignores the labels, while [LabeledTranscript] includes them
+/// into the hash input, and thus, provides domain separation between different draw sites.
+pub trait Transcript<B: StarkField> {
+    /// Hash function used by this transcript.
+    type Hasher: Hasher;
+
+    /// Absorbs the specified `data` into this transcript.
+    fn absorb(&mut self, label: &str, data: <Self::Hasher as Hasher>::Digest);
+
+    /// Absorbs the specified integer `value` into this transcript.
+    fn absorb_int(&mut self, label: &str, value: u64);
+
+    /// Returns the next pseudo-random field element.
+    ///
+    /// # Errors
+    /// Returns an error if a valid field element could not be generated.
+    fn draw<E>(&mut self, label: &str) -> Result<E, RandomCoinError>
+    where
+        E: FieldElement<BaseField = B>;
+
+    /// Returns the next pair of pseudo-random field elements; both elements are drawn with the
+    /// same label.
+    ///
+    /// # Errors
+    /// Returns an error if any of the field elements could not be generated.
+    fn draw_pair<E>(&mut self, label: &str) -> Result<(E, E), RandomCoinError>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        Ok((self.draw(label)?, self.draw(label)?))
+    }
+
+    /// Returns a vector of `num_values` unique integers selected from the range
+    /// [0, domain_size).
+    ///
+    /// # Errors
+    /// Returns an error if the specified number of unique integers could not be generated.
+    fn draw_integers(
+        &mut self,
+        label: &str,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError>;
+
+    /// Returns the number of leading zeros in the current state of this transcript.
+    fn leading_zeros(&self) -> u32;
+
+    /// Returns the first nonce (starting with `offset + 1`) which, once absorbed into this
+    /// transcript via [absorb_int()](Transcript::absorb_int) with the same `label`, results in
+    /// the state with at least `grinding_factor` leading zeros.
+    ///
+    /// # Panics
+    /// Panics if a suitable nonce does not exist.
+    fn find_pow_nonce(&self, label: &str, grinding_factor: u32, offset: u64) -> u64;
+}
+
+// LEGACY TRANSCRIPT
+// ================================================================================================
+
+/// A transcript which ignores operation labels.
+///
+/// This is the same as using a [RandomCoin] directly, and thus, proofs generated with this
+/// transcript are compatible with proofs generated before transcripts were introduced.
+pub type LegacyTranscript<B, H> = RandomCoin<B, H>;
+
+impl<B: StarkField, H: Hasher> Transcript<B> for RandomCoin<B, H> {
+    type Hasher = H;
+
+    fn absorb(&mut self, _label: &str, data: H::Digest) {
+        self.reseed(data);
+    }
+
+    fn absorb_int(&mut self, _label: &str, value: u64) {
+        self.reseed_with_int(value);
+    }
+
+    fn draw<E>(&mut self, _label: &str) -> Result<E, RandomCoinError>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        RandomCoin::draw(self)
+    }
+
+    fn draw_integers(
+        &mut self,
+        _label: &str,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        RandomCoin::draw_integers(self, num_values, domain_size)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        RandomCoin::leading_zeros(self)
+    }
+
+    fn find_pow_nonce(&self, _label: &str, grinding_factor: u32, offset: u64) -> u64 {
+        RandomCoin::find_pow_nonce(self, grinding_factor, offset)
+    }
+}
+
+// LABELED TRANSCRIPT
+// ================================================================================================
+
+/// A transcript which includes operation labels into the hash input.
+///
+/// Internally, the transcript is backed by a [RandomCoin]. Before each operation, the coin is
+/// reseeded with hash(`label`), and then the operation is performed against the coin as usual.
+/// Thus, values drawn at different places of the protocol are domain-separated even when the
+/// preceding messages are the same.
+///
+/// # Examples
+/// ```
+/// # use winter_crypto::{LabeledTranscript, LegacyTranscript, Transcript, hashers::Blake3_256};
+/// # use math::fields::f128::BaseElement;
+/// type Blake3 = Blake3_256<BaseElement>;
+/// let mut t1 = LabeledTranscript::<BaseElement, Blake3>::new(&[1, 2, 3, 4]);
+/// let mut t2 = LabeledTranscript::<BaseElement, Blake3>::new(&[1, 2, 3, 4]);
+///
+/// // the same label results in the same element, a different label - in a different one
+/// let e1: BaseElement = t1.draw("alpha").unwrap();
+/// let e2: BaseElement = t2.draw("alpha").unwrap();
+/// assert_eq!(e1, e2);
+/// let e1: BaseElement = t1.draw("alpha").unwrap();
+/// let e2: BaseElement = t2.draw("beta").unwrap();
+/// assert_ne!(e1, e2);
+///
+/// // a labeled transcript draws different elements than a legacy one
+/// let mut t1 = LabeledTranscript::<BaseElement, Blake3>::new(&[1, 2, 3, 4]);
+/// let mut t2 = LegacyTranscript::<BaseElement, Blake3>::new(&[1, 2, 3, 4]);
+/// let e1: BaseElement = t1.draw("alpha").unwrap();
+/// let e2: BaseElement = Transcript::draw(&mut t2, "alpha").unwrap();
+/// assert_ne!(e1, e2);
+/// ```
+pub struct LabeledTranscript<B, H>
+where
+    B: StarkField,
+    H: Hasher,
+{
+    coin: RandomCoin<B, H>,
+}
+
+impl<B: StarkField, H: Hasher> LabeledTranscript<B, H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new labeled transcript instantiated with the provided `seed`.
+    pub fn new(seed: &[u8]) -> Self {
+        LabeledTranscript {
+            coin: RandomCoin::new(seed),
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Reseeds the underlying coin with hash(`label`).
+    fn absorb_label(&mut self, label: &str) {
+        self.coin.reseed(H::hash(label.as_bytes()));
+    }
+}
+
+impl<B: StarkField, H: Hasher> Transcript<B> for LabeledTranscript<B, H> {
+    type Hasher = H;
+
+    fn absorb(&mut self, label: &str, data: H::Digest) {
+        self.absorb_label(label);
+        self.coin.reseed(data);
+    }
+
+    fn absorb_int(&mut self, label: &str, value: u64) {
+        self.absorb_label(label);
+        self.coin.reseed_with_int(value);
+    }
+
+    fn draw<E>(&mut self, label: &str) -> Result<E, RandomCoinError>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        self.absorb_label(label);
+        self.coin.draw()
+    }
+
+    fn draw_integers(
+        &mut self,
+        label: &str,
+        num_values: usize,
+        domain_size: usize,
+    ) -> Result<Vec<usize>, RandomCoinError> {
+        self.absorb_label(label);
+        self.coin.draw_integers(num_values, domain_size)
+    }
+
+    fn leading_zeros(&self) -> u32 {
+        self.coin.leading_zeros()
+    }
+
+    fn find_pow_nonce(&self, label: &str, grinding_factor: u32, offset: u64) -> u64 {
+        // search for the nonce against the state the coin would have after absorbing the label
+        let coin = RandomCoin::<B, H> {
+            seed: H::merge(&[self.coin.seed, H::hash(label.as_bytes())]),
+            counter: 0,
+            _base_field: PhantomData,
+        };
+        coin.find_pow_nonce(grinding_factor, offset)
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use examples::{benchmark::PeakAllocator, get_example, run_benchmark, ExampleOptions};
+use std::{env, fs, path::PathBuf, time::Duration};
+use structopt::StructOpt;
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// Command-line arguments of the benchmarked examples; proof-of-work is disabled so that the
+/// measurements are not dominated by grinding.
+const EXAMPLES: [&[&str]; 3] = [
+    &["fib8", "-n", "65536"],
+    &["rescue-f128", "-n", "256"],
+    &["lamport-a", "-n", "4"],
+];
+
+/// Environment variable which overrides the path of the JSON report.
+const REPORT_PATH_VAR: &str = "EXAMPLES_BENCH_REPORT";
+
+fn examples(c: &mut Criterion) {
+    let mut group = c.benchmark_group("examples");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    let mut reports = Vec::new();
+    for args in EXAMPLES.iter() {
+        let options =
+            ExampleOptions::from_iter(["winterfell", "--grinding", "0"].iter().chain(*args));
+        let example = get_example(&options.example, &options).unwrap();
+        let name = example.descriptor().name;
+
+        group.bench_function(BenchmarkId::new("build_trace", name), |bench| {
+            bench.iter(|| example.build_trace());
+        });
+        group.bench_function(BenchmarkId::new("prove", name), |bench| {
+            bench.iter_batched(
+                || example.build_trace(),
+                |trace| example.prove_trace(trace),
+                BatchSize::PerIteration,
+            );
+        });
+        let proof = example.prove();
+        group.bench_function(BenchmarkId::new("verify", name), |bench| {
+            bench.iter_batched(
+                || proof.clone(),
+                |proof| example.verify(proof).unwrap(),
+                BatchSize::SmallInput,
+            );
+        });
+
+        reports.push(run_benchmark(&options.example, &options).unwrap());
+    }
+    group.finish();
+
+    // write the metrics of a single run of each example into the JSON report
+    let path = env::var(REPORT_PATH_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../target/examples-bench-report.json")
+        });
+    let reports = reports
+        .iter()
+        .map(|report| report.to_json())
+        .collect::<Vec<_>>();
+    fs::write(&path, format!("[{}]\n", reports.join(",")))
+        .unwrap_or_else(|err| panic!("failed to write report to {}: {err}", path.display()));
+}
+
+criterion_group!(examples_group, examples);
+criterion_main!(examples_group);
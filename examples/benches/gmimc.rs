@@ -0,0 +1,34 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use examples::{gmimc, Example};
+
+use std::time::Duration;
+use winterfell::{
+    crypto::hashers::Blake3_256, math::fields::f64::BaseElement, FieldExtension, ProofOptions,
+};
+
+const SIZES: [usize; 2] = [256, 512];
+
+fn gmimc(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gmimc");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(25));
+
+    let options = ProofOptions::new(42, 8, 0, FieldExtension::Quadratic, 4, 256);
+
+    for &size in SIZES.iter() {
+        let gmimc = gmimc::GmimcExample::<Blake3_256<BaseElement>>::new(size, options.clone());
+        group.bench_function(BenchmarkId::from_parameter(size), |bench| {
+            bench.iter(|| gmimc.prove());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(gmimc_group, gmimc);
+criterion_main!(gmimc_group);
@@ -9,10 +9,13 @@ use examples::{rescue, Example};
 
 use std::time::Duration;
 use winterfell::{
-    crypto::hashers::Blake3_256, math::fields::f128::BaseElement, FieldExtension, ProofOptions,
+    crypto::hashers::Blake3_256,
+    math::{fft, fields::f128::BaseElement, polynom, FieldElement},
+    FieldExtension, PeriodicColumnPolys, ProofOptions,
 };
 
 const SIZES: [usize; 2] = [256, 512];
+const BATCH_SIZE: usize = 16;
 
 fn rescue(c: &mut Criterion) {
     let mut group = c.benchmark_group("rescue");
@@ -33,5 +36,105 @@ fn rescue(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(rescue_group, rescue);
+fn rescue_verify(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rescue_verify");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    let options = ProofOptions::new(32, 32, 0, FieldExtension::None, 4, 256);
+
+    // compare verification of proofs with evaluated and committed periodic columns
+    for &size in SIZES.iter() {
+        let resc = rescue::rescue_128::RescueExample::<Blake3_256<BaseElement>>::new(
+            size,
+            options.clone(),
+        );
+        let proof = resc.prove();
+        group.bench_function(BenchmarkId::new("evaluated", size), |bench| {
+            bench.iter(|| resc.verify(proof.clone()).unwrap());
+        });
+
+        let resc = resc.with_committed_periodic_columns();
+        let proof = resc.prove();
+        group.bench_function(BenchmarkId::new("committed", size), |bench| {
+            bench.iter(|| resc.verify(proof.clone()).unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn rescue_verify_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rescue_verify_batch");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    let options = ProofOptions::new(32, 32, 0, FieldExtension::None, 4, 256);
+
+    // compare verification of a batch of proofs one by one and with a prepared AIR
+    for &size in SIZES.iter() {
+        let resc = rescue::rescue_128::RescueExample::<Blake3_256<BaseElement>>::new(
+            size,
+            options.clone(),
+        )
+        .with_committed_periodic_columns();
+        let proofs = vec![resc.prove(); BATCH_SIZE];
+        group.bench_function(BenchmarkId::new("individual", size), |bench| {
+            bench.iter(|| {
+                for proof in proofs.iter() {
+                    resc.verify(proof.clone()).unwrap();
+                }
+            });
+        });
+        group.bench_function(BenchmarkId::new("prepared", size), |bench| {
+            bench.iter(|| resc.verify_batch(proofs.clone()).unwrap());
+        });
+    }
+    group.finish();
+}
+
+fn rescue_periodic_columns(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rescue_periodic_columns");
+
+    // interpolate Rescue round constants into periodic column polynomials
+    let polys = rescue::rescue_128::rescue::get_round_constants()
+        .into_iter()
+        .map(|mut column| {
+            let inv_twiddles = fft::get_inv_twiddles::<BaseElement>(column.len());
+            fft::interpolate_poly(&mut column, &inv_twiddles);
+            column
+        })
+        .collect::<Vec<_>>();
+    let z = BaseElement::from(0x0123_4567_89ab_cdef_u64);
+
+    // compare evaluating each periodic column polynomial at the out-of-domain point individually
+    // and evaluating polynomials grouped by cycle length
+    for &size in SIZES.iter() {
+        let trace_length = size * 16;
+        group.bench_function(BenchmarkId::new("individual", trace_length), |bench| {
+            bench.iter(|| {
+                polys
+                    .iter()
+                    .map(|poly| {
+                        let num_cycles = (trace_length / poly.len()) as u32;
+                        polynom::eval(poly, z.exp(num_cycles.into()))
+                    })
+                    .collect::<Vec<BaseElement>>()
+            });
+        });
+
+        let grouped = PeriodicColumnPolys::new(polys.clone(), trace_length);
+        group.bench_function(BenchmarkId::new("grouped", trace_length), |bench| {
+            bench.iter(|| grouped.evaluate_at(z));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    rescue_group,
+    rescue,
+    rescue_verify,
+    rescue_verify_batch,
+    rescue_periodic_columns
+);
 criterion_main!(rescue_group);
@@ -0,0 +1,262 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{ExampleOptions, ExampleRegistry, ExampleType, HashFunction};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+use winterfell::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// BENCHMARK RUNNER
+// ================================================================================================
+
+/// Instantiates the specified example, and measures the time it takes to build the execution
+/// trace, to generate the proof, and to verify it.
+///
+/// Peak memory usage is reported only when [PeakAllocator] is installed as the global allocator
+/// of the running binary.
+///
+/// # Errors
+/// Returns an error if the example could not be instantiated with the specified options, or if
+/// the generated proof failed to verify.
+pub fn run_benchmark(
+    example: &ExampleType,
+    options: &ExampleOptions,
+) -> Result<BenchReport, String> {
+    run_benchmark_with_registry(&ExampleRegistry::default(), example, options)
+}
+
+/// Same as [run_benchmark()], but instantiates the example from the specified `registry`, which
+/// may contain examples defined outside of this crate.
+///
+/// # Errors
+/// Returns an error if the example has not been registered, or in any of the cases listed for
+/// [run_benchmark()].
+pub fn run_benchmark_with_registry(
+    registry: &ExampleRegistry,
+    example: &ExampleType,
+    options: &ExampleOptions,
+) -> Result<BenchReport, String> {
+    let example = registry.get_example(example, options)?;
+    let descriptor = example.descriptor();
+    reset_peak_memory();
+
+    let now = Instant::now();
+    let trace = example.build_trace();
+    let trace_time = now.elapsed();
+
+    let now = Instant::now();
+    let proof = example.prove_trace(trace);
+    let prove_time = now.elapsed();
+
+    let proof_size = proof.to_bytes().len();
+    let now = Instant::now();
+    example
+        .verify(proof)
+        .map_err(|err| format!("failed to verify proof: {err}"))?;
+    let verify_time = now.elapsed();
+
+    Ok(BenchReport {
+        name: descriptor.name.to_string(),
+        field_id: descriptor.field_id,
+        hash_fn: descriptor.hash_fn,
+        trace_time,
+        prove_time,
+        verify_time,
+        proof_size,
+        peak_memory: peak_memory(),
+    })
+}
+
+// BENCHMARK REPORT
+// ================================================================================================
+
+/// Performance metrics of a single run of an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BenchReport {
+    /// Name of the example as used on the command line.
+    pub name: String,
+    /// Base field id, defined as the number of bits in the modulus of the base field.
+    pub field_id: u8,
+    /// Hash function used by the protocol.
+    pub hash_fn: HashFunction,
+    /// Time spent on building the execution trace.
+    pub trace_time: Duration,
+    /// Time spent on generating the proof from the execution trace.
+    pub prove_time: Duration,
+    /// Time spent on verifying the proof.
+    pub verify_time: Duration,
+    /// Size of the serialized proof in bytes.
+    pub proof_size: usize,
+    /// Maximum number of bytes allocated on the heap at any point while the trace was built and
+    /// the proof was generated and verified, or None if memory usage was not tracked.
+    pub peak_memory: Option<usize>,
+}
+
+impl BenchReport {
+    /// Returns this report as a JSON object; durations are reported in microseconds.
+    pub fn to_json(&self) -> String {
+        let peak_memory = match self.peak_memory {
+            Some(peak_memory) => peak_memory.to_string(),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\"name\":\"{}\",\"field_id\":{},\"hash_fn\":\"{:?}\",\"trace_time_us\":{},\
+            \"prove_time_us\":{},\"verify_time_us\":{},\"proof_size\":{},\"peak_memory\":{}}}",
+            self.name,
+            self.field_id,
+            self.hash_fn,
+            self.trace_time.as_micros(),
+            self.prove_time.as_micros(),
+            self.verify_time.as_micros(),
+            self.proof_size,
+            peak_memory
+        )
+    }
+}
+
+impl Serializable for BenchReport {
+    /// Serializes `self` and writes the resulting bytes into the `target`; durations are
+    /// serialized as numbers of microseconds.
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.name.len() as u8);
+        target.write_u8_slice(self.name.as_bytes());
+        target.write_u8(self.field_id);
+        target.write_u8(self.hash_fn as u8);
+        target.write_u64(self.trace_time.as_micros() as u64);
+        target.write_u64(self.prove_time.as_micros() as u64);
+        target.write_u64(self.verify_time.as_micros() as u64);
+        target.write_u64(self.proof_size as u64);
+        match self.peak_memory {
+            Some(peak_memory) => {
+                target.write_u8(1);
+                target.write_u64(peak_memory as u64);
+            }
+            None => target.write_u8(0),
+        }
+    }
+}
+
+impl Deserializable for BenchReport {
+    /// Reads a benchmark report from the specified `source` and returns the result.
+    ///
+    /// # Errors
+    /// Returns an error if a valid benchmark report could not be read from the specified
+    /// `source`.
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let name_len = source.read_u8()? as usize;
+        let name = String::from_utf8(source.read_u8_vec(name_len)?).map_err(|_| {
+            DeserializationError::InvalidValue("example name is not valid UTF-8".to_string())
+        })?;
+        let field_id = source.read_u8()?;
+        let hash_fn_id = source.read_u8()?;
+        let hash_fn = HashFunction::from_id(hash_fn_id).ok_or_else(|| {
+            DeserializationError::InvalidValue(format!("unknown hash function id {hash_fn_id}"))
+        })?;
+        let trace_time = Duration::from_micros(source.read_u64()?);
+        let prove_time = Duration::from_micros(source.read_u64()?);
+        let verify_time = Duration::from_micros(source.read_u64()?);
+        let proof_size = source.read_u64()? as usize;
+        let peak_memory = match source.read_u8()? {
+            0 => None,
+            1 => Some(source.read_u64()? as usize),
+            value => {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "invalid peak memory flag {value}"
+                )))
+            }
+        };
+
+        Ok(Self {
+            name,
+            field_id,
+            hash_fn,
+            trace_time,
+            prove_time,
+            verify_time,
+            proof_size,
+            peak_memory,
+        })
+    }
+}
+
+// PEAK MEMORY TRACKING
+// ================================================================================================
+
+/// Number of bytes currently allocated via [PeakAllocator].
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Maximum value of [ALLOCATED] since the last reset.
+static PEAK_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+/// Set once any memory is allocated via [PeakAllocator].
+static TRACKING: AtomicBool = AtomicBool::new(false);
+
+/// A wrapper around the system allocator which keeps track of the peak heap usage.
+///
+/// To report peak memory usage in benchmark reports, this allocator must be installed as the
+/// global allocator of the binary running the benchmarks:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: examples::benchmark::PeakAllocator = examples::benchmark::PeakAllocator;
+/// ```
+pub struct PeakAllocator;
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_allocation(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+            record_allocation(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Returns the maximum number of bytes allocated via [PeakAllocator] since the last call to
+/// [reset_peak_memory()], or None if [PeakAllocator] is not the global allocator.
+pub fn peak_memory() -> Option<usize> {
+    if TRACKING.load(Ordering::Relaxed) {
+        Some(PEAK_ALLOCATED.load(Ordering::Relaxed))
+    } else {
+        None
+    }
+}
+
+/// Resets the peak memory usage to the number of bytes currently allocated.
+pub fn reset_peak_memory() {
+    PEAK_ALLOCATED.store(ALLOCATED.load(Ordering::Relaxed), Ordering::Relaxed);
+}
+
+fn record_allocation(size: usize) {
+    let allocated = ALLOCATED.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK_ALLOCATED.fetch_max(allocated, Ordering::Relaxed);
+    TRACKING.store(true, Ordering::Relaxed);
+}
@@ -4,7 +4,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::utils::build_proof_options, Blake3_256};
+use super::{super::utils::build_proof_options, Blake2s_256, Blake3_256, Keccak_256};
+use crate::{Example, ExampleOptions};
+use structopt::StructOpt;
+use winterfell::{introspection::AirInfo, Deserializable, Serializable, SliceReader};
 
 #[test]
 fn fib2_test_basic_proof_verification() {
@@ -24,6 +27,30 @@ fn fib2_test_basic_proof_verification_extension() {
     crate::tests::test_basic_proof_verification(fib);
 }
 
+#[test]
+fn fib2_test_basic_proof_verification_blake2s() {
+    let fib = Box::new(super::FibExample::<Blake2s_256>::new(
+        16,
+        build_proof_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib2_test_basic_proof_verification_keccak() {
+    let fib = Box::new(super::FibExample::<Keccak_256>::new(
+        16,
+        build_proof_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+
+    let fib = Box::new(super::FibExample::<Keccak_256>::new(
+        16,
+        build_proof_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
 #[test]
 fn fib2_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Blake3_256>::new(
@@ -32,3 +59,123 @@ fn fib2_test_basic_proof_verification_fail() {
     ));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib2_test_proof_serialization_roundtrip() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+        16,
+        build_proof_options(false),
+    ));
+    crate::tests::test_proof_serialization_roundtrip(fib);
+}
+
+#[test]
+fn fib2_test_proof_file_roundtrip() {
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+        16,
+        build_proof_options(false),
+    ));
+    crate::tests::test_proof_file_roundtrip(fib);
+}
+
+#[test]
+fn fib2_test_proof_size_breakdown() {
+    // the sequence needs to be long enough for the proof to contain at least one FRI layer
+    let fib = Box::new(super::FibExample::<Blake3_256>::new(
+        1024,
+        build_proof_options(false),
+    ));
+    crate::tests::test_proof_size_breakdown(fib);
+}
+
+#[test]
+fn fib2_test_proof_size_estimate() {
+    for use_extension_field in [false, true] {
+        let fib = Box::new(super::FibExample::<Blake3_256>::new(
+            1024,
+            build_proof_options(use_extension_field),
+        ));
+        crate::tests::test_proof_size_estimate(fib);
+    }
+}
+
+#[test]
+fn fib2_test_fri_options_from_args() {
+    // FRI parameters specified via command-line arguments must be used by the prover, and picked
+    // up by the verifier from the proof context
+    for folding_factor in [2, 16] {
+        let folding = folding_factor.to_string();
+        let options = ExampleOptions::from_iter_safe([
+            "winterfell",
+            "--grinding",
+            "0",
+            "--folding",
+            folding.as_str(),
+            "--max-remainder",
+            "32",
+            "fib",
+        ])
+        .unwrap();
+        let fib = super::get_example(&options, 1024).unwrap();
+        let proof = fib.prove();
+        let fri_options = proof.options().to_fri_options();
+        assert_eq!(folding_factor, fri_options.folding_factor());
+        assert_eq!(32, fri_options.max_remainder_size());
+        assert!(fib.verify(proof).is_ok());
+    }
+
+    // unsupported values are rejected when parsing the arguments
+    let args = ["winterfell", "--folding", "32", "fib"];
+    assert!(ExampleOptions::from_iter_safe(args).is_err());
+    let args = ["winterfell", "--max-remainder", "100", "fib"];
+    assert!(ExampleOptions::from_iter_safe(args).is_err());
+}
+
+#[test]
+fn fib2_test_air_description() {
+    let fib = super::FibExample::<Blake3_256>::new(1024, build_proof_options(false));
+    let info = fib.describe();
+    assert_eq!(512, info.trace_length);
+    assert_eq!(2, info.trace_layout.main_trace_width());
+    assert_eq!(4096, info.domains.lde_domain_size);
+    assert_eq!(1, info.num_composition_columns);
+    assert_eq!(511, info.composition_degree);
+
+    // both constraints are of degree 1, and depend on both columns of the trace
+    assert_eq!(2, info.transition_constraints.len());
+    for constraint in info.transition_constraints.iter() {
+        assert!(!constraint.is_aux);
+        assert_eq!(1, constraint.base_degree);
+        assert_eq!(511, constraint.evaluation_degree);
+        assert_eq!(511, constraint.divisor.degree);
+        assert_eq!(vec![511], constraint.divisor.exemptions);
+        assert_eq!(vec![0, 1], constraint.columns);
+    }
+
+    // the result is asserted in the last row of the second column
+    assert_eq!(3, info.assertions.len());
+    assert_eq!(
+        (1, 511),
+        (info.assertions[2].column, info.assertions[2].first_step)
+    );
+    assert!(info.assertions.iter().all(|a| a.divisor_degree == 1));
+
+    // the description survives serialization, and can be printed as a report
+    let info_bytes = info.to_bytes();
+    assert_eq!(
+        info,
+        AirInfo::read_from(&mut SliceReader::new(&info_bytes)).unwrap()
+    );
+    assert!(info.to_string().contains("transition constraints (2)"));
+
+    // the estimated proof size is in the ballpark of the actual proof size
+    let proof_size = fib.prove().to_bytes().len();
+    assert!(info.estimated_proof_size > proof_size / 2);
+    assert!(info.estimated_proof_size < proof_size * 2);
+
+    // the description can be requested via command-line arguments
+    let options = ExampleOptions::from_iter_safe(["winterfell", "--describe", "fib"]).unwrap();
+    assert!(options.describe);
+    let args = ["winterfell", "--describe", "--verify", "proof.bin", "fib"];
+    assert!(ExampleOptions::from_iter_safe(args).is_err());
+}
@@ -5,12 +5,16 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, Example, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
     crypto::ElementHasher,
+    introspection::AirInfo,
     math::{fields::f128::BaseElement, log2, FieldElement},
     ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
 };
@@ -36,7 +40,7 @@ pub fn get_example(
     options: &ExampleOptions,
     sequence_length: usize,
 ) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(28, 8);
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8)?;
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(Fib8Example::<Blake3_192>::new(
@@ -51,6 +55,14 @@ pub fn get_example(
             sequence_length,
             options,
         ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(Fib8Example::<Blake2s_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(Fib8Example::<Keccak_256>::new(
+            sequence_length,
+            options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -94,7 +106,11 @@ impl<H: ElementHasher> Example for Fib8Example<H>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
-    fn prove(&self) -> StarkProof {
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("fib8")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
         debug!(
             "Generating proof for computing Fibonacci sequence (8 terms per step) up to {}th term\n\
             ---------------------",
@@ -116,8 +132,20 @@ where
             now.elapsed().as_millis()
         );
 
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = Fib8Prover::<H>::new(self.options.clone());
+
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = Fib8Prover::<H>::new(self.options.clone());
+        describe_air(&prover, &trace.into_inner())
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
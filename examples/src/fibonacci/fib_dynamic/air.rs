@@ -0,0 +1,107 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    compute_fib_term, is_valid_terms_per_step, BaseElement, FieldElement, ProofOptions,
+    MAX_TERMS_PER_STEP,
+};
+use crate::utils::are_equal;
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, Serializable, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+#[derive(Clone, Serializable, ToElements)]
+pub struct PublicInputs {
+    /// Number of Fibonacci terms computed in a single step; this is also the width of the trace.
+    pub terms_per_step: u32,
+    /// The last term of the sequence.
+    pub result: BaseElement,
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+pub struct FibDynAir {
+    context: AirContext<BaseElement>,
+    terms_per_step: usize,
+    result: BaseElement,
+}
+
+impl Air for FibDynAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let terms_per_step = pub_inputs.terms_per_step as usize;
+        assert!(
+            is_valid_terms_per_step(terms_per_step),
+            "number of terms per step must be a power of 2 between 2 and {MAX_TERMS_PER_STEP}, \
+            but was {terms_per_step}"
+        );
+        assert_eq!(
+            terms_per_step,
+            trace_info.width(),
+            "number of terms per step does not match the width of the execution trace"
+        );
+
+        // each column of the trace is constrained by a single linear constraint; there is one
+        // assertion against each column of the first row, and one against the last term
+        let degrees = vec![TransitionConstraintDegree::new(1); terms_per_step];
+        FibDynAir {
+            context: AirContext::new(trace_info, degrees, terms_per_step + 1, options),
+            terms_per_step,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let k = self.terms_per_step;
+        let current = frame.current();
+        let next = frame.next();
+        // expected state width is k field elements
+        debug_assert_eq!(k, current.len());
+        debug_assert_eq!(k, next.len());
+
+        // constraints of Fibonacci sequence (k registers, skipping over k terms): the first two
+        // terms of the next row depend on the last two terms of the current row, and every
+        // subsequent term is the sum of the two terms preceding it in the next row
+        result[0] = are_equal(next[0], current[k - 2] + current[k - 1]);
+        result[1] = are_equal(next[1], current[k - 1] + next[0]);
+        for i in 2..k {
+            result[i] = are_equal(next[i], next[i - 2] + next[i - 1]);
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // assert that the trace starts with the first k terms of Fibonacci sequence, and ends
+        // with the expected result
+        let last_step = self.trace_length() - 1;
+        let mut assertions = (0..self.terms_per_step)
+            .map(|i| Assertion::single(i, 0, compute_fib_term::<BaseElement>(i + 1)))
+            .collect::<Vec<_>>();
+        assertions.push(Assertion::single(
+            self.terms_per_step - 1,
+            last_step,
+            self.result,
+        ));
+        assertions
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::utils::compute_fib_term;
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, Example, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::ElementHasher,
+    introspection::AirInfo,
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, TraceInfo, TraceTable, VerifierError,
+};
+
+mod air;
+pub use air::{FibDynAir, PublicInputs};
+
+mod prover;
+pub use prover::FibDynProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Largest supported number of Fibonacci terms computed in a single step (i.e., the largest
+/// supported trace width).
+pub const MAX_TERMS_PER_STEP: usize = 64;
+
+// FIBONACCI EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    sequence_length: usize,
+    terms_per_step: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8)?;
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(FibDynExample::<Blake3_192>::new(
+            sequence_length,
+            terms_per_step,
+            options,
+        ))),
+        HashFunction::Blake3_256 => Ok(Box::new(FibDynExample::<Blake3_256>::new(
+            sequence_length,
+            terms_per_step,
+            options,
+        ))),
+        HashFunction::Sha3_256 => Ok(Box::new(FibDynExample::<Sha3_256>::new(
+            sequence_length,
+            terms_per_step,
+            options,
+        ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(FibDynExample::<Blake2s_256>::new(
+            sequence_length,
+            terms_per_step,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(FibDynExample::<Keccak_256>::new(
+            sequence_length,
+            terms_per_step,
+            options,
+        ))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct FibDynExample<H: ElementHasher> {
+    options: ProofOptions,
+    sequence_length: usize,
+    terms_per_step: usize,
+    result: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FibDynExample<H> {
+    pub fn new(sequence_length: usize, terms_per_step: usize, options: ProofOptions) -> Self {
+        assert!(
+            sequence_length.is_power_of_two(),
+            "sequence length must be a power of 2"
+        );
+        assert!(
+            is_valid_terms_per_step(terms_per_step),
+            "number of terms per step must be a power of 2 between 2 and {MAX_TERMS_PER_STEP}"
+        );
+        assert!(
+            sequence_length / terms_per_step >= TraceInfo::MIN_TRACE_LENGTH,
+            "sequence length must be at least {} times greater than the number of terms per step",
+            TraceInfo::MIN_TRACE_LENGTH
+        );
+
+        // compute Fibonacci sequence
+        let now = Instant::now();
+        let result = compute_fib_term(sequence_length);
+        debug!(
+            "Computed Fibonacci sequence up to {}th term in {} ms",
+            sequence_length,
+            now.elapsed().as_millis()
+        );
+
+        FibDynExample {
+            options,
+            sequence_length,
+            terms_per_step,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+
+    fn pub_inputs(&self) -> PublicInputs {
+        PublicInputs {
+            terms_per_step: self.terms_per_step as u32,
+            result: self.result,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for FibDynExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("fib-dyn")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
+        debug!(
+            "Generating proof for computing Fibonacci sequence ({} terms per step) up to \
+            {}th term\n---------------------",
+            self.terms_per_step, self.sequence_length
+        );
+
+        // create a prover
+        let prover = FibDynProver::<H>::new(self.terms_per_step, self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.sequence_length);
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = FibDynProver::<H>::new(self.terms_per_step, self.options.clone());
+
+        // generate the proof
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = FibDynProver::<H>::new(self.terms_per_step, self.options.clone());
+        describe_air(&prover, &trace.into_inner())
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<FibDynAir, H>(proof, self.pub_inputs())
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            result: self.result + BaseElement::ONE,
+            ..self.pub_inputs()
+        };
+        winterfell::verify::<FibDynAir, H>(proof, pub_inputs)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns `true` if a Fibonacci sequence can be computed with the specified number of terms per
+/// step; that is, if the number is a power of two between 2 and [MAX_TERMS_PER_STEP].
+fn is_valid_terms_per_step(terms_per_step: usize) -> bool {
+    terms_per_step.is_power_of_two() && (2..=MAX_TERMS_PER_STEP).contains(&terms_per_step)
+}
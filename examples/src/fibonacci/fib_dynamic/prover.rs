@@ -0,0 +1,85 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    is_valid_terms_per_step, BaseElement, ElementHasher, FibDynAir, FieldElement, PhantomData,
+    ProofOptions, Prover, PublicInputs, Trace, TraceTable,
+};
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+pub struct FibDynProver<H: ElementHasher> {
+    options: ProofOptions,
+    terms_per_step: usize,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FibDynProver<H> {
+    pub fn new(terms_per_step: usize, options: ProofOptions) -> Self {
+        assert!(
+            is_valid_terms_per_step(terms_per_step),
+            "invalid number of terms per step: {terms_per_step}"
+        );
+        Self {
+            options,
+            terms_per_step,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for computing a Fibonacci sequence of the specified length such
+    /// that each row advances the sequence by the number of terms specified for this prover.
+    pub fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
+        assert!(
+            sequence_length.is_power_of_two(),
+            "sequence length must be a power of 2"
+        );
+
+        let k = self.terms_per_step;
+        let mut trace = TraceTable::new(k, sequence_length / k);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ONE;
+                state[1] = BaseElement::ONE;
+                for i in 2..k {
+                    state[i] = state[i - 2] + state[i - 1];
+                }
+            },
+            |_, state| {
+                state[0] = state[k - 2] + state[k - 1];
+                state[1] = state[k - 1] + state[0];
+                for i in 2..k {
+                    state[i] = state[i - 2] + state[i - 1];
+                }
+            },
+        );
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for FibDynProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = FibDynAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            terms_per_step: trace.width() as u32,
+            result: trace.get(trace.width() - 1, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
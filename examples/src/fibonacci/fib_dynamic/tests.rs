@@ -0,0 +1,66 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{super::utils::build_proof_options, Blake3_256, FibDynAir, FibDynProver, PublicInputs};
+use winterfell::{Prover, Trace};
+
+#[test]
+fn fib_dynamic_test_basic_proof_verification() {
+    for terms_per_step in [2, 16, 64] {
+        let fib = Box::new(super::FibDynExample::<Blake3_256>::new(
+            1024,
+            terms_per_step,
+            build_proof_options(false),
+        ));
+        crate::tests::test_basic_proof_verification(fib);
+    }
+}
+
+#[test]
+fn fib_dynamic_test_basic_proof_verification_extension() {
+    for terms_per_step in [2, 16, 64] {
+        let fib = Box::new(super::FibDynExample::<Blake3_256>::new(
+            1024,
+            terms_per_step,
+            build_proof_options(true),
+        ));
+        crate::tests::test_basic_proof_verification(fib);
+    }
+}
+
+#[test]
+fn fib_dynamic_test_basic_proof_verification_fail() {
+    for terms_per_step in [2, 16, 64] {
+        let fib = Box::new(super::FibDynExample::<Blake3_256>::new(
+            1024,
+            terms_per_step,
+            build_proof_options(false),
+        ));
+        crate::tests::test_basic_proof_verification_fail(fib);
+    }
+}
+
+#[test]
+#[should_panic(expected = "number of terms per step does not match the width")]
+fn fib_dynamic_test_width_mismatch() {
+    let prover = FibDynProver::<Blake3_256>::new(16, build_proof_options(false));
+    let trace = prover.build_trace(1024);
+    let result = trace.get(15, trace.length() - 1);
+    let proof = prover.prove(trace).unwrap();
+
+    // the verifier reads a different width from public inputs than the one of the proof's trace
+    let pub_inputs = PublicInputs {
+        terms_per_step: 8,
+        result,
+    };
+    let _ = winterfell::verify::<FibDynAir, Blake3_256>(proof, pub_inputs);
+}
+
+#[test]
+#[should_panic(expected = "number of terms per step must be a power of 2")]
+fn fib_dynamic_test_invalid_terms_per_step() {
+    super::FibDynExample::<Blake3_256>::new(1024, 128, build_proof_options(false));
+}
@@ -0,0 +1,72 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, ProofOptions, FRAME_OFFSETS, TRACE_WIDTH};
+use crate::utils::are_equal;
+use winterfell::{
+    Air, AirContext, Assertion, EvaluationFrame, TraceInfo, TransitionConstraintDegree,
+};
+
+// FIBONACCI AIR
+// ================================================================================================
+
+pub struct FibMultiRowAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibMultiRowAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: Self::BaseField, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1)];
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+
+        // the constraint refers to the two rows following the current row, and thus, it does
+        // not apply to the last two steps of the trace
+        let context = AirContext::new(trace_info, degrees, 3, options)
+            .set_frame_offsets(FRAME_OFFSETS.to_vec())
+            .set_num_transition_exemptions(2);
+
+        FibMultiRowAir {
+            context,
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        // expected frame contains 3 rows of 1 field element each
+        debug_assert_eq!(FRAME_OFFSETS.len(), frame.num_rows());
+        debug_assert_eq!(TRACE_WIDTH, frame.num_columns());
+
+        // constraint of Fibonacci sequence (1 term per step):
+        // s_{i+2} = s_{i+1} + s_i
+        result[0] = are_equal(frame.row(2)[0], frame.row(1)[0] + frame.row(0)[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        // a valid Fibonacci sequence should start with two ones and terminate with
+        // the expected result
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, Self::BaseField::ONE),
+            Assertion::single(0, 1, Self::BaseField::ONE),
+            Assertion::single(0, last_step, self.result),
+        ]
+    }
+}
@@ -0,0 +1,163 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::utils::compute_fib_term;
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, Example, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::ElementHasher,
+    introspection::AirInfo,
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+use air::FibMultiRowAir;
+
+mod prover;
+use prover::FibMultiRowProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const TRACE_WIDTH: usize = 1;
+
+/// Offsets of the rows in the evaluation frame; each transition constraint relates three
+/// consecutive rows of the execution trace.
+const FRAME_OFFSETS: [usize; 3] = [0, 1, 2];
+
+// FIBONACCI EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    sequence_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8)?;
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(FibExample::<Blake3_192>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Blake3_256 => Ok(Box::new(FibExample::<Blake3_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Sha3_256 => Ok(Box::new(FibExample::<Sha3_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(FibExample::<Blake2s_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(FibExample::<Keccak_256>::new(
+            sequence_length,
+            options,
+        ))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct FibExample<H: ElementHasher> {
+    options: ProofOptions,
+    sequence_length: usize,
+    result: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FibExample<H> {
+    pub fn new(sequence_length: usize, options: ProofOptions) -> Self {
+        assert!(
+            sequence_length.is_power_of_two(),
+            "sequence length must be a power of 2"
+        );
+
+        // compute Fibonacci sequence
+        let now = Instant::now();
+        let result = compute_fib_term(sequence_length);
+        debug!(
+            "Computed Fibonacci sequence up to {}th term in {} ms",
+            sequence_length,
+            now.elapsed().as_millis()
+        );
+
+        FibExample {
+            options,
+            sequence_length,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for FibExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("fib-multi-row")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
+        debug!(
+            "Generating proof for computing Fibonacci sequence (1 term per step) up to {}th term\n\
+            ---------------------",
+            self.sequence_length
+        );
+
+        // create a prover
+        let prover = FibMultiRowProver::<H>::new(self.options.clone());
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.sequence_length);
+
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = FibMultiRowProver::<H>::new(self.options.clone());
+
+        // generate the proof
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = FibMultiRowProver::<H>::new(self.options.clone());
+        describe_air(&prover, &trace.into_inner())
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<FibMultiRowAir, H>(proof, self.result)
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<FibMultiRowAir, H>(proof, self.result + BaseElement::ONE)
+    }
+}
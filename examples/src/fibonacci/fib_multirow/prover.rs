@@ -0,0 +1,64 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementHasher, FibMultiRowAir, FieldElement, PhantomData, ProofOptions, Prover,
+    Trace, TraceTable,
+};
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+pub struct FibMultiRowProver<H: ElementHasher> {
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> FibMultiRowProver<H> {
+    pub fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Builds an execution trace for computing a Fibonacci sequence of the specified length such
+    /// that each row contains a single term of the sequence.
+    pub fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
+        assert!(
+            sequence_length.is_power_of_two(),
+            "sequence length must be a power of 2"
+        );
+
+        let mut column = Vec::with_capacity(sequence_length);
+        column.push(BaseElement::ONE);
+        column.push(BaseElement::ONE);
+        for i in 2..sequence_length {
+            column.push(column[i - 1] + column[i - 2]);
+        }
+
+        TraceTable::init(vec![column])
+    }
+}
+
+impl<H: ElementHasher> Prover for FibMultiRowProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = FibMultiRowAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        let last_step = trace.length() - 1;
+        trace.get(0, last_step)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
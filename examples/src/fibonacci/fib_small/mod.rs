@@ -5,12 +5,13 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::utils::compute_fib_term;
-use crate::{Example, ExampleOptions, HashFunction};
+use crate::{describe_air, Example, ExampleDescriptor, ExampleOptions, ExampleTrace, HashFunction};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
     crypto::ElementHasher,
+    introspection::AirInfo,
     math::{fields::f64::BaseElement, log2, FieldElement},
     ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
 };
@@ -32,7 +33,12 @@ const TRACE_WIDTH: usize = 2;
 type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
 type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
 type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+#[allow(non_camel_case_types)]
+type Blake2s_256 = winterfell::crypto::hashers::Blake2s_256<BaseElement>;
+#[allow(non_camel_case_types)]
+type Keccak_256 = winterfell::crypto::hashers::Keccak_256<BaseElement>;
 type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
+type Poseidon64_256 = winterfell::crypto::hashers::Poseidon64_256;
 
 // FIBONACCI EXAMPLE
 // ================================================================================================
@@ -41,7 +47,7 @@ pub fn get_example(
     options: &ExampleOptions,
     sequence_length: usize,
 ) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(28, 8);
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8)?;
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(FibExample::<Blake3_192>::new(
@@ -56,10 +62,22 @@ pub fn get_example(
             sequence_length,
             options,
         ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(FibExample::<Blake2s_256>::new(
+            sequence_length,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(FibExample::<Keccak_256>::new(
+            sequence_length,
+            options,
+        ))),
         HashFunction::Rp64_256 => Ok(Box::new(FibExample::<Rp64_256>::new(
             sequence_length,
             options,
         ))),
+        HashFunction::Poseidon64_256 => Ok(Box::new(FibExample::<Poseidon64_256>::new(
+            sequence_length,
+            options,
+        ))),
     }
 }
 
@@ -102,7 +120,11 @@ impl<H: ElementHasher> Example for FibExample<H>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
-    fn prove(&self) -> StarkProof {
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("fib-small")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
         debug!(
             "Generating proof for computing Fibonacci sequence (2 terms per step) up to {}th term\n\
             ---------------------",
@@ -125,15 +147,37 @@ where
             now.elapsed().as_millis()
         );
 
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = FibSmallProver::<H>::new(self.options.clone());
+
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = FibSmallProver::<H>::new(self.options.clone());
+        describe_air(&prover, &trace.into_inner())
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<FibSmall, H>(proof, self.result)
+        // the verifier accepts proofs in a field without an extension only if insecure fields were
+        // allowed for this example explicitly
+        winterfell::verify_with_min_field_security::<FibSmall, H>(
+            proof,
+            self.result,
+            self.options.min_field_security(),
+        )
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        winterfell::verify::<FibSmall, H>(proof, self.result + BaseElement::ONE)
+        winterfell::verify_with_min_field_security::<FibSmall, H>(
+            proof,
+            self.result + BaseElement::ONE,
+            self.options.min_field_security(),
+        )
     }
 }
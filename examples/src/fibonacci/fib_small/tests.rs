@@ -4,13 +4,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{super::utils::build_proof_options, Rp64_256};
+use super::{
+    super::utils::build_proof_options, FibSmall, FibSmallProver, Poseidon64_256, Rp64_256,
+};
+use winterfell::{FieldExtension, ProofOptions, Prover, ProverError, Trace, VerifierError};
 
 #[test]
 fn fib_small_test_basic_proof_verification() {
     let fib = Box::new(super::FibExample::<Rp64_256>::new(
         16,
-        build_proof_options(false),
+        build_proof_options(false).allow_insecure_field(),
     ));
     crate::tests::test_basic_proof_verification(fib);
 }
@@ -24,11 +27,96 @@ fn fib_small_test_basic_proof_verification_extension() {
     crate::tests::test_basic_proof_verification(fib);
 }
 
+#[test]
+fn fib_small_test_basic_proof_verification_sextic_extension() {
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::Sextic, 4, 256);
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(16, options));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
 #[test]
 fn fib_small_test_basic_proof_verification_fail() {
     let fib = Box::new(super::FibExample::<Rp64_256>::new(
         16,
-        build_proof_options(false),
+        build_proof_options(false).allow_insecure_field(),
+    ));
+    crate::tests::test_basic_proof_verification_fail(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_poseidon() {
+    let fib = Box::new(super::FibExample::<Poseidon64_256>::new(
+        16,
+        build_proof_options(false).allow_insecure_field(),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_poseidon_extension() {
+    let fib = Box::new(super::FibExample::<Poseidon64_256>::new(
+        16,
+        build_proof_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(fib);
+}
+
+#[test]
+fn fib_small_test_basic_proof_verification_poseidon_fail() {
+    let fib = Box::new(super::FibExample::<Poseidon64_256>::new(
+        16,
+        build_proof_options(false).allow_insecure_field(),
     ));
     crate::tests::test_basic_proof_verification_fail(fib);
 }
+
+#[test]
+fn fib_small_test_proof_file_roundtrip() {
+    let fib = Box::new(super::FibExample::<Rp64_256>::new(
+        16,
+        build_proof_options(false).allow_insecure_field(),
+    ));
+    crate::tests::test_proof_file_roundtrip(fib);
+}
+
+#[test]
+fn fib_small_test_insufficient_field_security() {
+    // without an extension, the 64-bit field provides fewer than 80 bits of security for a
+    // low-degree extension domain of any size
+    let prover = FibSmallProver::<Rp64_256>::new(build_proof_options(false));
+    let trace = prover.build_trace(16);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let lde_domain_size = trace.length() * 8;
+    let expected = ProverError::InsufficientFieldSecurity {
+        achieved: 64 - lde_domain_size.trailing_zeros(),
+        required: ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+    };
+    assert_eq!(Err(expected), prover.prove(trace));
+
+    // once the check is disabled explicitly, a proof can be generated
+    let insecure_options = build_proof_options(false).allow_insecure_field();
+    assert_eq!(FieldExtension::None, insecure_options.field_extension());
+    let prover = FibSmallProver::<Rp64_256>::new(insecure_options);
+    let proof = prover.prove(prover.build_trace(16)).unwrap();
+
+    // but the proof is rejected by a default verifier even though the proof options disable
+    // the check, as the minimum field security of the verifier does not come from the proof
+    assert_eq!(
+        Err(VerifierError::InsufficientFieldSecurity {
+            achieved: 64 - lde_domain_size.trailing_zeros(),
+            required: ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+        }),
+        winterfell::verify::<FibSmall, Rp64_256>(proof.clone(), pub_inputs)
+    );
+
+    // the proof is accepted only if the verifier disables the check as well
+    assert!(
+        winterfell::verify_with_min_field_security::<FibSmall, Rp64_256>(proof, pub_inputs, 0)
+            .is_ok()
+    );
+
+    // a quadratic extension provides adequate security
+    let prover = FibSmallProver::<Rp64_256>::new(build_proof_options(true));
+    let proof = prover.prove(prover.build_trace(16)).unwrap();
+    assert!(winterfell::verify::<FibSmall, Rp64_256>(proof, pub_inputs).is_ok());
+}
@@ -6,6 +6,8 @@
 
 pub mod fib2;
 pub mod fib8;
+pub mod fib_dynamic;
+pub mod fib_multirow;
 pub mod fib_small;
 pub mod mulfib2;
 pub mod mulfib8;
@@ -0,0 +1,204 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::gmimc;
+use crate::{describe_air, Example, ExampleDescriptor, ExampleOptions, ExampleTrace, HashFunction};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::ElementHasher,
+    introspection::AirInfo,
+    math::{fields::f64::BaseElement, log2, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+use air::{GmimcAir, PublicInputs};
+
+mod prover;
+use prover::GmimcProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS AND TYPES
+// ================================================================================================
+
+const TRACE_WIDTH: usize = gmimc::STATE_WIDTH;
+
+type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
+type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+#[allow(non_camel_case_types)]
+type Blake2s_256 = winterfell::crypto::hashers::Blake2s_256<BaseElement>;
+#[allow(non_camel_case_types)]
+type Keccak_256 = winterfell::crypto::hashers::Keccak_256<BaseElement>;
+type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
+type Poseidon64_256 = winterfell::crypto::hashers::Poseidon64_256;
+
+// GMIMC HASH CHAIN EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    chain_length: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8)?;
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(GmimcExample::<Blake3_192>::new(
+            chain_length,
+            options,
+        ))),
+        HashFunction::Blake3_256 => Ok(Box::new(GmimcExample::<Blake3_256>::new(
+            chain_length,
+            options,
+        ))),
+        HashFunction::Sha3_256 => Ok(Box::new(GmimcExample::<Sha3_256>::new(
+            chain_length,
+            options,
+        ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(GmimcExample::<Blake2s_256>::new(
+            chain_length,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(GmimcExample::<Keccak_256>::new(
+            chain_length,
+            options,
+        ))),
+        HashFunction::Rp64_256 => Ok(Box::new(GmimcExample::<Rp64_256>::new(
+            chain_length,
+            options,
+        ))),
+        HashFunction::Poseidon64_256 => Ok(Box::new(GmimcExample::<Poseidon64_256>::new(
+            chain_length,
+            options,
+        ))),
+    }
+}
+
+pub struct GmimcExample<H: ElementHasher> {
+    options: ProofOptions,
+    chain_length: usize,
+    seed: [BaseElement; 2],
+    result: [BaseElement; 2],
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> GmimcExample<H> {
+    pub fn new(chain_length: usize, options: ProofOptions) -> Self {
+        assert!(
+            chain_length.is_power_of_two(),
+            "chain length must a power of 2"
+        );
+        let seed = [BaseElement::from(42u8), BaseElement::from(43u8)];
+
+        // compute the sequence of hashes using external implementation of GMiMC hash
+        let now = Instant::now();
+        let result = compute_hash_chain(seed, chain_length);
+        debug!(
+            "Computed a chain of {} GMiMC hashes in {} ms",
+            chain_length,
+            now.elapsed().as_millis(),
+        );
+
+        GmimcExample {
+            options,
+            chain_length,
+            seed,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for GmimcExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("gmimc-chain")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
+        // generate the execution trace
+        debug!(
+            "Generating proof for computing a chain of {} GMiMC hashes\n\
+            ---------------------",
+            self.chain_length
+        );
+
+        let prover = GmimcProver::<H>::new(self.options.clone());
+
+        // generate the execution trace
+        let now = Instant::now();
+        let trace = prover.build_trace(self.seed, self.chain_length);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = GmimcProver::<H>::new(self.options.clone());
+
+        // generate the proof
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = GmimcProver::<H>::new(self.options.clone());
+        describe_air(&prover, &trace.into_inner())
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            seed: self.seed,
+            result: self.result,
+        };
+        // the verifier accepts proofs in a field without an extension only if insecure fields were
+        // allowed for this example explicitly
+        winterfell::verify_with_min_field_security::<GmimcAir, H>(
+            proof,
+            pub_inputs,
+            self.options.min_field_security(),
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            seed: self.seed,
+            result: [self.result[0], self.result[1] + BaseElement::ONE],
+        };
+        winterfell::verify_with_min_field_security::<GmimcAir, H>(
+            proof,
+            pub_inputs,
+            self.options.min_field_security(),
+        )
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+fn compute_hash_chain(seed: [BaseElement; 2], length: usize) -> [BaseElement; 2] {
+    let mut values = seed;
+    let mut result = [BaseElement::ZERO; 2];
+    for _ in 0..length {
+        gmimc::hash(values, &mut result);
+        values.copy_from_slice(&result);
+    }
+    result
+}
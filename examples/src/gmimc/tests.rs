@@ -0,0 +1,58 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{compute_hash_chain, BaseElement, Blake3_256};
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn gmimc_hash_chain_kat() {
+    let seed = [BaseElement::new(42), BaseElement::new(43)];
+
+    // expected values are obtained by executing python reference implementation code
+    let expected = [
+        BaseElement::new(14160419571807266318),
+        BaseElement::new(14911165304411593633),
+    ];
+
+    assert_eq!(expected, compute_hash_chain(seed, 16));
+}
+
+#[test]
+fn gmimc_test_basic_proof_verification() {
+    let gmimc_eg = Box::new(super::GmimcExample::<Blake3_256>::new(
+        16,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(gmimc_eg);
+}
+
+#[test]
+fn gmimc_test_basic_proof_verification_extension() {
+    let gmimc_eg = Box::new(super::GmimcExample::<Blake3_256>::new(
+        16,
+        build_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(gmimc_eg);
+}
+
+#[test]
+fn gmimc_test_basic_proof_verification_fail() {
+    let gmimc_eg = Box::new(super::GmimcExample::<Blake3_256>::new(
+        16,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(gmimc_eg);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    if use_extension_field {
+        ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 256)
+    } else {
+        // the 64-bit field does not provide adequate security without an extension, and thus,
+        // the field security check must be disabled explicitly
+        ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256).allow_insecure_field()
+    }
+}
@@ -5,39 +5,44 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    rescue, CYCLE_LENGTH as HASH_CYCLE_LEN, SIG_CYCLE_LENGTH as SIG_CYCLE_LEN, TRACE_WIDTH,
+    rescue, sign_dummy_message, CYCLE_LENGTH as HASH_CYCLE_LEN, SIG_CYCLE_LENGTH as SIG_CYCLE_LEN,
+    TRACE_WIDTH,
 };
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
-    math::{fields::f128::BaseElement, FieldElement},
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
-    TransitionConstraintDegree,
+    math::{fields::f128::BaseElement, FieldElement, ToElements},
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, SelectorColumn, Serializable,
+    TraceInfo, TransitionConstraintDegree,
 };
 
 // CONSTANTS
 // ================================================================================================
 const TWO: BaseElement = BaseElement::new(2);
 
+/// Index of the periodic column of the padding selector; the selector follows the signature
+/// cycle mask, the powers of two, the hash cycle mask, and the Rescue round constants.
+const SELECTOR_COLUMN_IDX: usize = 3 + 2 * rescue::STATE_WIDTH;
+
 // AGGREGATE LAMPORT PLUS SIGNATURE AIR
 // ================================================================================================
 
-#[derive(Clone)]
+#[derive(Clone, Serializable, ToElements)]
 pub struct PublicInputs {
     pub pub_keys: Vec<[BaseElement; 2]>,
     pub messages: Vec<[BaseElement; 2]>,
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.pub_keys);
-        target.write(&self.messages);
-    }
-}
-
+/// AIR for verifying an aggregate of Lamport+ signatures.
+///
+/// Each signature is verified over a cycle of 1024 steps. When the number of signatures is not a
+/// power of two, the trace is padded with cycles verifying a dummy signature. Transition
+/// constraints are switched off on these cycles by a selector column, while the public keys and
+/// messages against which they are asserted are padded with the dummy public key and message.
 pub struct LamportAggregateAir {
     context: AirContext<BaseElement>,
     pub_keys: Vec<[BaseElement; 2]>,
     messages: Vec<[BaseElement; 2]>,
+    selector: Option<(SelectorColumn, Vec<BaseElement>)>,
 }
 
 impl Air for LamportAggregateAir {
@@ -82,10 +87,51 @@ impl Air for LamportAggregateAir {
             TransitionConstraintDegree::with_cycles(5, vec![HASH_CYCLE_LEN, SIG_CYCLE_LEN]),
         ];
         assert_eq!(TRACE_WIDTH, trace_info.width());
+
+        let num_signatures = pub_inputs.pub_keys.len();
+        let num_cycles = num_signatures.next_power_of_two();
+        assert_eq!(
+            num_cycles * SIG_CYCLE_LEN,
+            trace_info.length(),
+            "expected trace length to be {}, but was {}",
+            num_cycles * SIG_CYCLE_LEN,
+            trace_info.length()
+        );
+
+        let mut pub_keys = pub_inputs.pub_keys;
+        let mut messages = pub_inputs.messages;
+        if num_signatures == num_cycles {
+            return LamportAggregateAir {
+                context: AirContext::new(trace_info, degrees, 22, options),
+                pub_keys,
+                messages,
+                selector: None,
+            };
+        }
+
+        // pad public keys and messages with the ones of the dummy signature
+        let dummy = sign_dummy_message();
+        pub_keys.resize(num_cycles, dummy.pub_key.to_elements());
+        messages.resize(num_cycles, dummy.message_elements());
+
+        // the selector is one on the cycles verifying actual signatures, and zero on the padding
+        // cycles, which switches off all transition constraints on the padding cycles
+        let mut selector_values = vec![BaseElement::ZERO; trace_info.length()];
+        selector_values[..num_signatures * SIG_CYCLE_LEN].fill(BaseElement::ONE);
+        let selector = SelectorColumn::new(SELECTOR_COLUMN_IDX, &selector_values);
+
+        let degrees = degrees
+            .into_iter()
+            .map(|degree| selector.constraint_degree(degree))
+            .collect::<Vec<_>>();
+        let selectors = vec![Some(selector.clone()); degrees.len()];
+        let context =
+            AirContext::new(trace_info, degrees, 22, options).set_transition_selectors(selectors);
         LamportAggregateAir {
-            context: AirContext::new(trace_info, degrees, 22, options),
-            pub_keys: pub_inputs.pub_keys,
-            messages: pub_inputs.messages,
+            context,
+            pub_keys,
+            messages,
+            selector: Some((selector, selector_values)),
         }
     }
 
@@ -109,7 +155,7 @@ impl Air for LamportAggregateAir {
         let sig_cycle_end_flag = periodic_values[0];
         let power_of_two = periodic_values[1];
         let hash_flag = periodic_values[2];
-        let ark = &periodic_values[3..];
+        let ark = &periodic_values[3..SELECTOR_COLUMN_IDX];
 
         // evaluate the constraints
         evaluate_constraints(
@@ -121,6 +167,13 @@ impl Air for LamportAggregateAir {
             sig_cycle_end_flag,
             power_of_two,
         );
+
+        // switch off the constraints on the cycles verifying the dummy signature
+        if let Some((selector, _)) = &self.selector {
+            for evaluation in result.iter_mut() {
+                *evaluation = selector.apply(periodic_values, *evaluation);
+            }
+        }
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
@@ -188,6 +241,12 @@ impl Air for LamportAggregateAir {
         result.push(HASH_CYCLE_MASK.to_vec());
         result.append(&mut rescue::get_round_constants());
 
+        // add the padding selector (if any)
+        if let Some((_, selector_values)) = &self.selector {
+            debug_assert_eq!(SELECTOR_COLUMN_IDX, result.len());
+            result.push(selector_values.clone());
+        }
+
         result
     }
 }
@@ -5,14 +5,19 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH, NUM_HASH_ROUNDS,
+    message_to_elements, rescue, Example, PrivateKey, PublicKey, Signature, CYCLE_LENGTH,
+    NUM_HASH_ROUNDS,
+};
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
 };
-use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
     crypto::ElementHasher,
+    introspection::AirInfo,
     math::{fields::f128::BaseElement, get_power_series, log2, FieldElement, StarkField},
     ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
 };
@@ -23,19 +28,27 @@ use air::{LamportAggregateAir, PublicInputs};
 mod prover;
 use prover::LamportAggregateProver;
 
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
 const TRACE_WIDTH: usize = 22;
 const SIG_CYCLE_LENGTH: usize = 128 * CYCLE_LENGTH; // 1024 steps
 
+/// Seed of the key which signs the dummy message; verifications of the dummy signature pad the
+/// execution trace when the number of signatures is not a power of two.
+const DUMMY_KEY_SEED: [u8; 32] = *b"winterfell lamport dummy signer!";
+const DUMMY_MESSAGE: &str = "dummy message";
+
 // LAMPORT MULTI-MESSAGE, MULTI-KEY, SIGNATURE EXAMPLE
 // ================================================================================================
 pub fn get_example(
     options: &ExampleOptions,
     num_signatures: usize,
 ) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(28, 8);
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8)?;
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(LamportAggregateExample::<Blake3_192>::new(
@@ -50,6 +63,14 @@ pub fn get_example(
             num_signatures,
             options,
         ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(LamportAggregateExample::<Blake2s_256>::new(
+            num_signatures,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(LamportAggregateExample::<Keccak_256>::new(
+            num_signatures,
+            options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -58,56 +79,30 @@ pub struct LamportAggregateExample<H: ElementHasher> {
     options: ProofOptions,
     pub_keys: Vec<[BaseElement; 2]>,
     messages: Vec<[BaseElement; 2]>,
-    signatures: Vec<Signature>,
     _hasher: PhantomData<H>,
 }
 
 impl<H: ElementHasher> LamportAggregateExample<H> {
     pub fn new(num_signatures: usize, options: ProofOptions) -> Self {
         assert!(
-            num_signatures.is_power_of_two(),
-            "number of signatures must be a power of 2"
-        );
-        // generate private/public key pairs for the specified number of signatures
-        let mut private_keys = Vec::with_capacity(num_signatures);
-        let mut public_keys = Vec::with_capacity(num_signatures);
-        let now = Instant::now();
-        for i in 0..num_signatures {
-            private_keys.push(PrivateKey::from_seed([i as u8; 32]));
-            public_keys.push(private_keys[i].pub_key().to_elements());
-        }
-        debug!(
-            "Generated {} private-public key pairs in {} ms",
-            num_signatures,
-            now.elapsed().as_millis()
+            num_signatures > 0,
+            "number of signatures must be greater than zero"
         );
 
-        // sign messages
+        // generate keys, sign messages, and verify signatures one signature at a time; only
+        // public keys and messages are retained, while private keys and signatures are dropped
+        // right away and are re-generated later when the execution trace is built
         let now = Instant::now();
-        let mut signatures = Vec::new();
-        let mut messages = Vec::new();
-        for (i, private_key) in private_keys.iter().enumerate() {
-            let msg = format!("test message {i}");
-            signatures.push(private_key.sign(msg.as_bytes()));
-            messages.push(message_to_elements(msg.as_bytes()));
-        }
-        debug!(
-            "Signed {} messages in {} ms",
-            num_signatures,
-            now.elapsed().as_millis()
-        );
-
-        // verify signature
-        let now = Instant::now();
-        let mut pub_keys = Vec::new();
-        for (i, signature) in signatures.iter().enumerate() {
-            let pk = private_keys[i].pub_key();
-            pub_keys.push(pk.to_elements());
-            let msg = format!("test message {i}");
-            assert!(pk.verify(msg.as_bytes(), signature));
+        let mut pub_keys = Vec::with_capacity(num_signatures);
+        let mut messages = Vec::with_capacity(num_signatures);
+        for i in 0..num_signatures {
+            let signed = sign_message(i);
+            assert!(signed.verify());
+            pub_keys.push(signed.pub_key.to_elements());
+            messages.push(signed.message_elements());
         }
         debug!(
-            "Verified {} signature in {} ms",
+            "Generated keys, signed and verified {} messages in {} ms",
             num_signatures,
             now.elapsed().as_millis()
         );
@@ -116,7 +111,6 @@ impl<H: ElementHasher> LamportAggregateExample<H> {
             options,
             pub_keys,
             messages,
-            signatures,
             _hasher: PhantomData,
         }
     }
@@ -129,12 +123,16 @@ impl<H: ElementHasher> Example for LamportAggregateExample<H>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
-    fn prove(&self) -> StarkProof {
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("lamport-a")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
         // generate the execution trace
         debug!(
             "Generating proof for verifying {} Lamport+ signatures \n\
             ---------------------",
-            self.signatures.len(),
+            self.messages.len(),
         );
 
         // create a prover
@@ -142,7 +140,7 @@ where
             LamportAggregateProver::<H>::new(&self.pub_keys, &self.messages, self.options.clone());
 
         let now = Instant::now();
-        let trace = prover.build_trace(&self.messages, &self.signatures);
+        let trace = prover.build_trace();
         let trace_length = trace.length();
         debug!(
             "Generated execution trace of {} registers and 2^{} steps in {} ms",
@@ -151,8 +149,22 @@ where
             now.elapsed().as_millis()
         );
 
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover =
+            LamportAggregateProver::<H>::new(&self.pub_keys, &self.messages, self.options.clone());
+
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover =
+            LamportAggregateProver::<H>::new(&self.pub_keys, &self.messages, self.options.clone());
+        describe_air(&prover, &trace.into_inner())
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -173,3 +185,50 @@ where
         winterfell::verify::<LamportAggregateAir, H>(proof, pub_inputs)
     }
 }
+
+// SIGNED MESSAGES
+// ================================================================================================
+
+/// A message signed by a Lamport+ key together with the public key of the signer.
+struct SignedMessage {
+    pub_key: PublicKey,
+    message: String,
+    signature: Signature,
+}
+
+impl SignedMessage {
+    /// Signs the specified message with the key generated from the specified seed.
+    fn new(seed: [u8; 32], message: String) -> Self {
+        let private_key = PrivateKey::from_seed(seed);
+        let signature = private_key.sign(message.as_bytes());
+        SignedMessage {
+            pub_key: private_key.pub_key(),
+            message,
+            signature,
+        }
+    }
+
+    /// Returns the message of this signature encoded into field elements.
+    fn message_elements(&self) -> [BaseElement; 2] {
+        message_to_elements(self.message.as_bytes())
+    }
+
+    /// Returns true if the signature is valid for the message and the public key.
+    fn verify(&self) -> bool {
+        self.pub_key
+            .verify(self.message.as_bytes(), &self.signature)
+    }
+}
+
+/// Returns the message signed by the signer at the specified index.
+///
+/// Keys and messages are derived from the index of the signer, and thus, signatures can be
+/// re-generated whenever they are needed instead of being kept in memory.
+fn sign_message(index: usize) -> SignedMessage {
+    SignedMessage::new([index as u8; 32], format!("test message {index}"))
+}
+
+/// Returns the dummy message signed by the dummy signer.
+fn sign_dummy_message() -> SignedMessage {
+    SignedMessage::new(DUMMY_KEY_SEED, DUMMY_MESSAGE.to_string())
+}
@@ -5,9 +5,9 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    get_power_series, rescue, BaseElement, ElementHasher, FieldElement, LamportAggregateAir,
-    PhantomData, ProofOptions, Prover, PublicInputs, Signature, StarkField, TraceTable,
-    CYCLE_LENGTH, NUM_HASH_ROUNDS, SIG_CYCLE_LENGTH, TRACE_WIDTH,
+    get_power_series, rescue, sign_dummy_message, sign_message, BaseElement, ElementHasher,
+    FieldElement, LamportAggregateAir, PhantomData, ProofOptions, Prover, PublicInputs, Signature,
+    StarkField, TraceTable, CYCLE_LENGTH, NUM_HASH_ROUNDS, SIG_CYCLE_LENGTH, TRACE_WIDTH,
 };
 
 #[cfg(feature = "concurrent")]
@@ -61,20 +61,31 @@ impl<H: ElementHasher> LamportAggregateProver<H> {
         }
     }
 
-    pub fn build_trace(
-        &self,
-        messages: &[[BaseElement; 2]],
-        signatures: &[Signature],
-    ) -> TraceTable<BaseElement> {
+    /// Builds an execution trace verifying the signatures of all signers.
+    ///
+    /// Every signature is verified in its own fragment of the trace. The signatures are
+    /// re-generated one at a time while their fragments are filled, and are dropped as soon as
+    /// the key schedule for the fragment is built; thus, the memory used to build the trace
+    /// is dominated by the trace itself. If the number of signatures is not a power of two, the
+    /// remaining fragments verify the dummy signature.
+    pub fn build_trace(&self) -> TraceTable<BaseElement> {
         // allocate memory to hold the trace table
-        let trace_length = SIG_CYCLE_LENGTH * messages.len();
+        let num_signatures = self.pub_inputs.messages.len();
+        let trace_length = SIG_CYCLE_LENGTH * num_signatures.next_power_of_two();
         let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
 
         let powers_of_two = get_power_series(TWO, 128);
 
         trace.fragments(SIG_CYCLE_LENGTH).for_each(|mut sig_trace| {
             let i = sig_trace.index();
-            let sig_info = build_sig_info(&messages[i], &signatures[i]);
+            let signed = if i < num_signatures {
+                sign_message(i)
+            } else {
+                sign_dummy_message()
+            };
+            let sig_info = build_sig_info(&signed.message_elements(), &signed.signature);
+            drop(signed);
+
             sig_trace.fill(
                 |state| {
                     init_sig_verification_state(&sig_info, state);
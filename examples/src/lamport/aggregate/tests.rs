@@ -0,0 +1,71 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{sign_dummy_message, sign_message, Blake3_256, SIG_CYCLE_LENGTH};
+use winterfell::{FieldExtension, ProofOptions, Trace};
+
+#[test]
+fn lamport_aggregate_test_3() {
+    let lamport = Box::new(super::LamportAggregateExample::<Blake3_256>::new(
+        3,
+        build_options(),
+    ));
+    crate::tests::test_basic_proof_verification(lamport);
+}
+
+#[test]
+fn lamport_aggregate_test_3_fail() {
+    let lamport = Box::new(super::LamportAggregateExample::<Blake3_256>::new(
+        3,
+        build_options(),
+    ));
+    crate::tests::test_basic_proof_verification_fail(lamport);
+}
+
+#[test]
+fn lamport_aggregate_test_17() {
+    let lamport = Box::new(super::LamportAggregateExample::<Blake3_256>::new(
+        17,
+        build_options(),
+    ));
+    crate::tests::test_basic_proof_verification(lamport);
+}
+
+#[test]
+fn lamport_aggregate_trace_padding() {
+    let lamport = super::LamportAggregateExample::<Blake3_256>::new(3, build_options());
+    let prover = super::LamportAggregateProver::<Blake3_256>::new(
+        &lamport.pub_keys,
+        &lamport.messages,
+        build_options(),
+    );
+    let trace = prover.build_trace();
+    assert_eq!(4 * SIG_CYCLE_LENGTH, trace.length());
+
+    // the last cycle verifies the dummy signature: the message accumulators and the public key
+    // hasher end up with the dummy message and the dummy public key
+    let dummy = sign_dummy_message();
+    let last_step = trace.length() - 1;
+    let message = dummy.message_elements();
+    let pub_key = dummy.pub_key.to_elements();
+    assert_eq!(message[0], trace.get(2, last_step));
+    assert_eq!(message[1], trace.get(3, last_step));
+    assert_eq!(pub_key[0], trace.get(16, last_step));
+    assert_eq!(pub_key[1], trace.get(17, last_step));
+}
+
+#[test]
+fn lamport_aggregate_signatures_are_deterministic() {
+    let signed = sign_message(5);
+    assert!(signed.verify());
+    assert_eq!(signed.pub_key, sign_message(5).pub_key);
+    assert_ne!(signed.pub_key, sign_message(6).pub_key);
+    assert!(sign_dummy_message().verify());
+}
+
+fn build_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256)
+}
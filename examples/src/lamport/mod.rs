@@ -10,6 +10,6 @@ pub mod aggregate;
 pub mod threshold;
 
 mod signature;
-use signature::{message_to_elements, PrivateKey, Signature};
+use signature::{message_to_elements, PrivateKey, PublicKey, Signature};
 
 use crate::utils::rescue::{self, CYCLE_LENGTH, NUM_ROUNDS as NUM_HASH_ROUNDS};
@@ -188,7 +188,8 @@ pub fn message_to_elements(message: &[u8]) -> [BaseElement; 2] {
     let checksum = m0.count_zeros() + m1.count_zeros();
     let m1 = m1 | ((checksum as u128) << 119);
 
-    [BaseElement::from(m0), BaseElement::from(m1)]
+    // both values fit into 127 bits, and thus are smaller than the field modulus
+    [m0, m1].map(|m| BaseElement::try_from_u128(m).expect("value exceeds field modulus"))
 }
 
 /// Reduces a list of public key elements to a single 32-byte value. The reduction is done
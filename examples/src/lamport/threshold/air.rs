@@ -5,12 +5,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    super::rescue, HASH_CYCLE_LENGTH as HASH_CYCLE_LEN, SIG_CYCLE_LENGTH as SIG_CYCLE_LEN,
-    TRACE_WIDTH,
+    super::rescue, get_num_cycles, HASH_CYCLE_LENGTH as HASH_CYCLE_LEN,
+    SIG_CYCLE_LENGTH as SIG_CYCLE_LEN, TRACE_WIDTH,
 };
 use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
 use winterfell::{
-    math::{fields::f128::BaseElement, log2, FieldElement, StarkField},
+    math::{fields::f128::BaseElement, log2, FieldElement, StarkField, ToElements},
     Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
     TransitionConstraintDegree,
 };
@@ -22,19 +22,37 @@ const TWO: BaseElement = BaseElement::new(2);
 // THRESHOLD LAMPORT PLUS SIGNATURE AIR
 // ================================================================================================
 
-#[derive(Clone)]
+/// Public inputs of the threshold signature verification.
+///
+/// The `signers` bitmap contains one entry per key in the aggregated public key, set to `true`
+/// for every key which signed the message. The AIR binds the bitmap to the signatures verified in
+/// the execution trace, but it does not enforce a threshold: the verifier is expected to check
+/// that the number of signers in the bitmap is sufficient before accepting a proof.
+#[derive(Clone, ToElements)]
 pub struct PublicInputs {
     pub pub_key_root: [BaseElement; 2],
-    pub num_pub_keys: usize,
-    pub num_signatures: usize,
+    pub signers: Vec<bool>,
     pub message: [BaseElement; 2],
 }
 
+impl PublicInputs {
+    /// Returns the number of keys which signed the message.
+    pub fn num_signers(&self) -> usize {
+        self.signers.iter().filter(|&&signed| signed).count()
+    }
+}
+
 impl Serializable for PublicInputs {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write(&self.pub_key_root[..]);
-        target.write_u32(self.num_pub_keys as u32);
-        target.write_u32(self.num_signatures as u32);
+        target.write_u32(self.signers.len() as u32);
+        for chunk in self.signers.chunks(8) {
+            let byte = chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |acc, (i, &signed)| acc | ((signed as u8) << i));
+            target.write_u8(byte);
+        }
         target.write(&self.message[..]);
     }
 }
@@ -42,8 +60,8 @@ impl Serializable for PublicInputs {
 pub struct LamportThresholdAir {
     context: AirContext<BaseElement>,
     pub_key_root: [BaseElement; 2],
-    num_pub_keys: usize,
-    num_signatures: usize,
+    signers: Vec<bool>,
+    num_signers: usize,
     message: [BaseElement; 2],
 }
 
@@ -96,10 +114,10 @@ impl Air for LamportThresholdAir {
         ];
         assert_eq!(TRACE_WIDTH, trace_info.width());
         LamportThresholdAir {
-            context: AirContext::new(trace_info, degrees, 26, options),
+            context: AirContext::new(trace_info, degrees, 27, options),
             pub_key_root: pub_inputs.pub_key_root,
-            num_pub_keys: pub_inputs.num_pub_keys,
-            num_signatures: pub_inputs.num_signatures,
+            num_signers: pub_inputs.num_signers(),
+            signers: pub_inputs.signers,
             message: pub_inputs.message,
         }
     }
@@ -170,7 +188,7 @@ impl Air for LamportThresholdAir {
         // ----- assertions against the step in every cycle when the Merkle path computation ends -
         // these steps depend on the depth of the public key Merkle tree; for example, if the Merkle
         // tree has 4 elements, then the steps are: 24, 1048, 2072, 3096
-        let num_cycles = self.num_pub_keys.next_power_of_two();
+        let num_cycles = get_num_cycles(self.signers.len());
         let merkle_root_offset = (log2(num_cycles) + 1) as usize * HASH_CYCLE_LEN;
 
         // distinct key indexes should be used; the sequence starts at the last index of the tree
@@ -186,11 +204,21 @@ impl Air for LamportThresholdAir {
 
         // ----- assertions for the entire execution trace -----------------------------------------
 
+        // signature flags should match the signer bitmap; cycles of virtual signers, which pad
+        // the keys to the number of cycles, should not verify a signature
+        let mut sig_flags = self
+            .signers
+            .iter()
+            .map(|&signed| BaseElement::from(signed as u64))
+            .collect::<Vec<_>>();
+        sig_flags.resize(num_cycles, BaseElement::ZERO);
+
         let last_step = self.trace_length() - 1;
         assertions.extend_from_slice(&[
-            // signature counter starts at zero and terminates with the expected count of signatures
+            Assertion::sequence(26, 0, SIG_CYCLE_LEN, sig_flags),
+            // signature counter starts at zero and terminates with the number of signers
             Assertion::single(27, 0, BaseElement::ZERO),
-            Assertion::single(27, last_step, BaseElement::from(self.num_signatures as u64)),
+            Assertion::single(27, last_step, BaseElement::from(self.num_signers as u64)),
             // the first public key for merkle path verification should be a zero key (it is only
             // used for padding)
             Assertion::single(18, 0, BaseElement::ZERO),
@@ -8,12 +8,17 @@ use super::{
     message_to_elements, rescue, Example, PrivateKey, Signature, CYCLE_LENGTH as HASH_CYCLE_LENGTH,
     NUM_HASH_ROUNDS,
 };
-use crate::{Blake3_192, Blake3_256, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
+use rand_utils::prng_vector;
 use std::time::Instant;
 use winterfell::{
     crypto::ElementHasher,
+    introspection::AirInfo,
     math::{fields::f128::BaseElement, get_power_series, log2, FieldElement, StarkField},
     ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
 };
@@ -27,32 +32,52 @@ use air::{LamportThresholdAir, PublicInputs};
 mod prover;
 use prover::LamportThresholdProver;
 
+#[cfg(test)]
+mod tests;
+
 // CONSTANTS
 // ================================================================================================
 
 const TRACE_WIDTH: usize = 28;
 const SIG_CYCLE_LENGTH: usize = 128 * HASH_CYCLE_LENGTH; // 1024 steps
 
+/// Seed used to select the keys which sign the message.
+const SIGNERS_SEED: [u8; 32] = [0x11; 32];
+
 // LAMPORT THRESHOLD SIGNATURE EXAMPLE
 // ================================================================================================
 
 pub fn get_example(
     options: &ExampleOptions,
     num_signers: usize,
+    threshold: usize,
 ) -> Result<Box<dyn Example>, String> {
-    let (_, hash_fn) = options.to_proof_options(28, 8);
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8)?;
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(LamportThresholdExample::<Blake3_192>::new(
             num_signers,
+            threshold,
             options,
         ))),
         HashFunction::Blake3_256 => Ok(Box::new(LamportThresholdExample::<Blake3_256>::new(
             num_signers,
+            threshold,
             options,
         ))),
         HashFunction::Sha3_256 => Ok(Box::new(LamportThresholdExample::<Sha3_256>::new(
             num_signers,
+            threshold,
+            options,
+        ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(LamportThresholdExample::<Blake2s_256>::new(
+            num_signers,
+            threshold,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(LamportThresholdExample::<Keccak_256>::new(
+            num_signers,
+            threshold,
             options,
         ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
@@ -63,15 +88,20 @@ pub struct LamportThresholdExample<H: ElementHasher> {
     options: ProofOptions,
     pub_key: AggPublicKey,
     signatures: Vec<(usize, Signature)>,
+    signers: Vec<bool>,
     message: [BaseElement; 2],
     _hasher: PhantomData<H>,
 }
 
 impl<H: ElementHasher> LamportThresholdExample<H> {
-    pub fn new(num_signers: usize, options: &ExampleOptions) -> Self {
+    pub fn new(num_signers: usize, threshold: usize, options: ProofOptions) -> Self {
         assert!(
-            (num_signers + 1).is_power_of_two(),
-            "number of signers must be one less than a power of 2"
+            num_signers > 0,
+            "number of signers must be greater than zero"
+        );
+        assert!(
+            threshold > 0 && threshold <= num_signers,
+            "threshold must be between 1 and {num_signers}, but was {threshold}"
         );
         // generate private/public key pairs for the specified number of signatures
         let now = Instant::now();
@@ -83,13 +113,16 @@ impl<H: ElementHasher> LamportThresholdExample<H> {
         );
         let public_keys = private_keys.iter().map(|k| k.pub_key()).collect();
 
-        // sign the message with the subset of previously generated keys
+        // sign the message with the subset of previously generated keys, and mark the keys which
+        // signed the message in the signer bitmap
         let message = "test message";
-        let selected_indexes = pick_random_indexes(num_signers);
+        let selected_indexes = pick_signer_indexes(num_signers, threshold);
         let mut signatures = Vec::new();
+        let mut signers = vec![false; num_signers];
         for &key_index in selected_indexes.iter() {
             let signature = private_keys[key_index].sign(message.as_bytes());
             signatures.push((key_index, signature));
+            signers[key_index] = true;
         }
 
         // build the aggregated public key
@@ -100,12 +133,11 @@ impl<H: ElementHasher> LamportThresholdExample<H> {
             now.elapsed().as_millis()
         );
 
-        let (options, _) = options.to_proof_options(28, 8);
-
         LamportThresholdExample {
             options,
             pub_key,
             signatures,
+            signers,
             message: message_to_elements(message.as_bytes()),
             _hasher: PhantomData,
         }
@@ -119,7 +151,11 @@ impl<H: ElementHasher> Example for LamportThresholdExample<H>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
-    fn prove(&self) -> StarkProof {
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("lamport-t")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
         // generate the execution trace
         debug!(
             "Generating proof for verifying {}-of-{} signature \n\
@@ -147,25 +183,55 @@ where
             now.elapsed().as_millis()
         );
 
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = LamportThresholdProver::<H>::new(
+            &self.pub_key,
+            self.message,
+            &self.signatures,
+            self.options.clone(),
+        );
+
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = LamportThresholdProver::<H>::new(
+            &self.pub_key,
+            self.message,
+            &self.signatures,
+            self.options.clone(),
+        );
+        describe_air(&prover, &trace.into_inner())
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
         let pub_inputs = PublicInputs {
             pub_key_root: self.pub_key.root().to_elements(),
-            num_pub_keys: self.pub_key.num_keys(),
-            num_signatures: self.signatures.len(),
+            signers: self.signers.clone(),
             message: self.message,
         };
         winterfell::verify::<LamportThresholdAir, H>(proof, pub_inputs)
     }
 
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        // claim that the first key which signed the message did not sign it; if possible, claim
+        // that the first key which did not sign the message did, so that the number of signers
+        // does not change
+        let mut signers = self.signers.clone();
+        if let Some(non_signer) = signers.iter().position(|&signed| !signed) {
+            signers[non_signer] = true;
+        }
+        let signer = self.signers.iter().position(|&signed| signed).unwrap();
+        signers[signer] = false;
+
         let pub_inputs = PublicInputs {
             pub_key_root: self.pub_key.root().to_elements(),
-            num_pub_keys: self.pub_key.num_keys(),
-            num_signatures: self.signatures.len() + 1,
+            signers,
             message: self.message,
         };
         winterfell::verify::<LamportThresholdAir, H>(proof, pub_inputs)
@@ -183,12 +249,25 @@ fn build_keys(num_keys: usize) -> Vec<PrivateKey> {
     result
 }
 
-fn pick_random_indexes(num_keys: usize) -> Vec<usize> {
-    let num_selected_keys = num_keys * 2 / 3;
-    let mut result = Vec::with_capacity(num_selected_keys);
-    // TODO: change to actual random selection
-    for i in 0..num_selected_keys {
-        result.push(i);
-    }
-    result
+/// Returns the number of signature verification cycles needed for the specified number of keys.
+///
+/// Each cycle verifies a signature against one key and a Merkle path for the key of the previous
+/// cycle, with the first cycle verifying a Merkle path for the last leaf. Thus, the number of
+/// cycles must be equal to the number of leaves in the aggregated public key, and there must be
+/// at least one more leaf than there are keys. The keys are padded with virtual signers which
+/// never sign the message.
+fn get_num_cycles(num_keys: usize) -> usize {
+    (num_keys + 1).next_power_of_two()
+}
+
+/// Returns a run of `num_selected_keys` consecutive key indexes out of `num_keys` key indexes,
+/// starting at a pseudo-random offset.
+///
+/// The signers are consecutive so that the signature flags of the cycles never repeat with a
+/// period shorter than the number of cycles: a periodic signature flag column (e.g. flags
+/// [0, 1, 0, 1] for keys 0 and 2 out of 3) has lower degree than the one declared by the AIR.
+fn pick_signer_indexes(num_keys: usize, num_selected_keys: usize) -> Vec<usize> {
+    let num_offsets = (num_keys - num_selected_keys + 1) as u64;
+    let offset = (prng_vector::<u64>(SIGNERS_SEED, 1)[0] % num_offsets) as usize;
+    (offset..offset + num_selected_keys).collect()
 }
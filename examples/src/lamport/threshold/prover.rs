@@ -57,10 +57,13 @@ impl<H: ElementHasher> LamportThresholdProver<H> {
         signatures: &[(usize, Signature)],
         options: ProofOptions,
     ) -> Self {
+        let mut signers = vec![false; pub_key.num_keys()];
+        for &(key_index, _) in signatures {
+            signers[key_index] = true;
+        }
         let pub_inputs = PublicInputs {
             pub_key_root: pub_key.root().to_elements(),
-            num_pub_keys: pub_key.num_keys(),
-            num_signatures: signatures.len(),
+            signers,
             message,
         };
         Self {
@@ -77,7 +80,7 @@ impl<H: ElementHasher> LamportThresholdProver<H> {
         signatures: &[(usize, Signature)],
     ) -> TraceTable<BaseElement> {
         // allocate memory to hold the trace table
-        let num_cycles = pub_key.num_keys().next_power_of_two();
+        let num_cycles = pub_key.num_leaves();
         let trace_length = SIG_CYCLE_LENGTH * num_cycles;
         let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
 
@@ -4,6 +4,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::get_num_cycles;
 use crate::{
     lamport::signature::PublicKey,
     utils::rescue::{Hash, Rescue128},
@@ -35,11 +36,7 @@ impl AggPublicKey {
 
         // pad the list of keys with zero keys to make sure the number of leaves is greater than
         // the number of keys and is a power of two
-        let num_leaves = if leaves.len().is_power_of_two() {
-            (leaves.len() + 1).next_power_of_two()
-        } else {
-            leaves.len().next_power_of_two()
-        };
+        let num_leaves = get_num_cycles(leaves.len());
         let zero_hash = Rescue128::digest(&[BaseElement::ZERO, BaseElement::ZERO]);
         for _ in leaves.len()..num_leaves {
             leaves.push(zero_hash);
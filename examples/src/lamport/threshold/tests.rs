@@ -0,0 +1,70 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{get_num_cycles, pick_signer_indexes, Blake3_256};
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn lamport_threshold_test_5_of_9() {
+    let lamport = Box::new(super::LamportThresholdExample::<Blake3_256>::new(
+        9,
+        5,
+        build_options(),
+    ));
+    crate::tests::test_basic_proof_verification(lamport);
+}
+
+#[test]
+fn lamport_threshold_test_2_of_3() {
+    let lamport = Box::new(super::LamportThresholdExample::<Blake3_256>::new(
+        3,
+        2,
+        build_options(),
+    ));
+    crate::tests::test_basic_proof_verification(lamport);
+}
+
+#[test]
+fn lamport_threshold_test_signer_bitmap_mismatch() {
+    let lamport = Box::new(super::LamportThresholdExample::<Blake3_256>::new(
+        9,
+        5,
+        build_options(),
+    ));
+    crate::tests::test_basic_proof_verification_fail(lamport);
+}
+
+#[test]
+fn lamport_threshold_test_all_signers_bitmap_mismatch() {
+    let lamport = Box::new(super::LamportThresholdExample::<Blake3_256>::new(
+        3,
+        3,
+        build_options(),
+    ));
+    crate::tests::test_basic_proof_verification_fail(lamport);
+}
+
+#[test]
+fn lamport_threshold_num_cycles() {
+    // there is always at least one virtual signer padding the keys
+    assert_eq!(2, get_num_cycles(1));
+    assert_eq!(4, get_num_cycles(3));
+    assert_eq!(8, get_num_cycles(4));
+    assert_eq!(16, get_num_cycles(9));
+}
+
+#[test]
+fn lamport_threshold_pick_signer_indexes() {
+    let indexes = pick_signer_indexes(9, 5);
+    assert_eq!(5, indexes.len());
+    assert!(indexes.windows(2).all(|w| w[0] + 1 == w[1]));
+    assert!(indexes.iter().all(|&i| i < 9));
+    assert_eq!(indexes, pick_signer_indexes(9, 5));
+}
+
+fn build_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256)
+}
@@ -4,20 +4,42 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use core::{
+    any::{type_name, Any},
+    fmt,
+    str::FromStr,
+};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 use structopt::StructOpt;
 use winterfell::{
-    crypto::hashers::Rp64_256, math::fields::f128::BaseElement, FieldExtension, ProofOptions,
-    StarkProof, VerifierError,
+    crypto::{
+        hashers::{Poseidon64_256, Rp64_256},
+        Hasher,
+    },
+    introspection::AirInfo,
+    math::{fields::f128::BaseElement, ExtensibleField, StarkField},
+    Air, FieldExtension, ProofOptions, Prover, StarkProof, Trace, VerifierError,
 };
 
+pub mod benchmark;
 pub mod fibonacci;
+pub mod gmimc;
 #[cfg(feature = "std")]
 pub mod lamport;
 #[cfg(feature = "std")]
 pub mod merkle;
+pub mod proof_file;
+pub mod registry;
 pub mod rescue;
 #[cfg(feature = "std")]
 pub mod rescue_raps;
+pub mod runner;
+#[cfg(feature = "std")]
+pub mod schnorr;
+pub mod sweep;
 pub mod utils;
 pub mod vdf;
 
@@ -27,28 +49,244 @@ mod tests;
 // TYPES AND INTERFACES
 // ================================================================================================
 
+#[allow(non_camel_case_types)]
+pub type Blake2s_256 = winterfell::crypto::hashers::Blake2s_256<BaseElement>;
 pub type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
 pub type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+#[allow(non_camel_case_types)]
+pub type Keccak_256 = winterfell::crypto::hashers::Keccak_256<BaseElement>;
 pub type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
 
+pub use benchmark::{run_benchmark, run_benchmark_with_registry, BenchReport};
+pub use proof_file::{ExampleDescriptor, ProofFileError};
+pub use registry::{ExampleArgs, ExampleBuilder, ExampleRegistry};
+pub use runner::run;
+pub use sweep::{run_sweep, run_sweep_with_registry, SweepPoint, SweepPointReport, SweepReport};
+
 pub trait Example {
-    fn prove(&self) -> StarkProof;
+    /// Builds the execution trace of the computation described by this example.
+    fn build_trace(&self) -> ExampleTrace;
+
+    /// Generates a proof of the computation from an execution trace built by
+    /// [Example::build_trace()].
+    ///
+    /// # Panics
+    /// Panics if the trace was built by an example of a different type.
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof;
+
+    /// Generates a proof of the computation from a copy of an execution trace built by
+    /// [Example::build_trace()], leaving the trace available for further proofs.
+    ///
+    /// This is useful for generating proofs of the same computation with different proof
+    /// options: the trace depends only on the inputs of the computation, and thus, can be built
+    /// once by one instance of the example and proven by instances configured with other
+    /// options.
+    ///
+    /// # Panics
+    /// Panics if the trace was built by an example of a different type.
+    fn prove_with_trace(&self, trace: &ExampleTrace) -> StarkProof {
+        self.prove_trace(trace.clone())
+    }
+
+    /// Builds the execution trace of this example and generates a proof of the computation.
+    fn prove(&self) -> StarkProof {
+        self.prove_trace(self.build_trace())
+    }
+
+    /// Returns a description of the AIR of the computation for an execution trace built by
+    /// [Example::build_trace()].
+    ///
+    /// # Panics
+    /// Panics if the trace was built by an example of a different type.
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo;
+
+    /// Builds the execution trace of this example and describes the AIR of the computation
+    /// without generating a proof.
+    fn describe(&self) -> AirInfo {
+        self.describe_trace(self.build_trace())
+    }
+
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError>;
     fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError>;
+
+    /// Returns a descriptor identifying this example in the header of proof files.
+    fn descriptor(&self) -> ExampleDescriptor;
+
+    /// Generates a proof and writes it into the file at the specified path, preceded by a header
+    /// identifying this example.
+    fn prove_to_file(&self, path: &Path) -> io::Result<()> {
+        let proof = self.prove();
+        proof_file::write_proof(path, &self.descriptor(), &proof)
+    }
+
+    /// Reads a proof from the file at the specified path and verifies it.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read, if its header does not match this example,
+    /// or if the proof cannot be deserialized or fails to verify.
+    fn verify_from_file(&self, path: &Path) -> Result<(), ProofFileError> {
+        let proof = proof_file::read_proof(path, &self.descriptor())?;
+        self.verify(proof).map_err(ProofFileError::Verification)
+    }
+}
+
+/// Execution trace built by an [Example].
+///
+/// The concrete type of the trace is erased so that building the trace and generating the proof
+/// can be invoked (and timed) separately through an [Example] trait object.
+///
+/// Cloning the trace clones the wrapped execution trace. The prover consumes the traces it
+/// proves (e.g., auxiliary trace segments are built by mutating the trace), and thus, proving
+/// the same trace several times requires a copy of the trace for each proof; copying a trace
+/// is much cheaper than building it from scratch.
+pub struct ExampleTrace(Box<dyn CloneableTrace>);
+
+impl ExampleTrace {
+    /// Wraps the specified execution trace.
+    pub fn new<T: Any + Clone>(trace: T) -> Self {
+        Self(Box::new(trace))
+    }
+
+    /// Returns the wrapped execution trace.
+    ///
+    /// # Panics
+    /// Panics if the wrapped trace is not of type `T`.
+    pub fn into_inner<T: Any>(self) -> T {
+        *self
+            .0
+            .into_any()
+            .downcast::<T>()
+            .unwrap_or_else(|_| panic!("execution trace is not of type {}", type_name::<T>()))
+    }
+}
+
+impl Clone for ExampleTrace {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_box())
+    }
+}
+
+/// Type-erased execution trace which can be cloned.
+trait CloneableTrace: Any {
+    fn clone_box(&self) -> Box<dyn CloneableTrace>;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<T: Any + Clone> CloneableTrace for T {
+    fn clone_box(&self) -> Box<dyn CloneableTrace> {
+        Box::new(self.clone())
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Returns a description of the AIR which the specified `prover` instantiates to prove the
+/// specified execution `trace`.
+pub fn describe_air<P: Prover>(prover: &P, trace: &P::Trace) -> AirInfo {
+    let air = P::Air::new(
+        trace.get_info(),
+        prover.get_pub_inputs(trace),
+        prover.options().clone(),
+    );
+    AirInfo::from_air(&air)
+}
+
+/// Instantiates the specified built-in example with the protocol parameters defined by `options`.
+///
+/// Examples registered outside of this crate can be instantiated via
+/// [ExampleRegistry::get_example()].
+///
+/// # Errors
+/// Returns an error if the example is not a built-in example, or if the example does not support
+/// the options (e.g., the hash function).
+pub fn get_example(
+    example: &ExampleType,
+    options: &ExampleOptions,
+) -> Result<Box<dyn Example>, String> {
+    ExampleRegistry::default().get_example(example, options)
+}
+
+/// Instantiates the specified built-in example; this is the builder of all built-in examples in
+/// the default [ExampleRegistry].
+pub(crate) fn get_builtin_example(
+    example: &ExampleType,
+    options: &ExampleOptions,
+) -> Result<Box<dyn Example>, String> {
+    match *example {
+        ExampleType::Fib { sequence_length } => {
+            fibonacci::fib2::get_example(options, sequence_length)
+        }
+        ExampleType::Fib8 { sequence_length } => {
+            fibonacci::fib8::get_example(options, sequence_length)
+        }
+        ExampleType::Mulfib { sequence_length } => {
+            fibonacci::mulfib2::get_example(options, sequence_length)
+        }
+        ExampleType::Mulfib8 { sequence_length } => {
+            fibonacci::mulfib8::get_example(options, sequence_length)
+        }
+        ExampleType::FibSmall { sequence_length } => {
+            fibonacci::fib_small::get_example(options, sequence_length)
+        }
+        ExampleType::FibDyn {
+            sequence_length,
+            terms_per_step,
+        } => fibonacci::fib_dynamic::get_example(options, sequence_length, terms_per_step),
+        ExampleType::FibMultiRow { sequence_length } => {
+            fibonacci::fib_multirow::get_example(options, sequence_length)
+        }
+        ExampleType::Vdf { num_steps } => vdf::regular::get_example(options, num_steps),
+        ExampleType::VdfExempt { num_steps } => vdf::exempt::get_example(options, num_steps),
+        ExampleType::VdfCheckpointed {
+            num_steps,
+            checkpoint_every,
+        } => vdf::checkpointed::get_example(options, num_steps, checkpoint_every),
+        ExampleType::RescueF128 { chain_length } => {
+            rescue::rescue_128::get_example(options, chain_length)
+        }
+        ExampleType::GmimcChain { chain_length } => gmimc::get_example(options, chain_length),
+        #[cfg(feature = "std")]
+        ExampleType::RescueRaps { chain_length } => rescue_raps::get_example(options, chain_length),
+        #[cfg(feature = "std")]
+        ExampleType::Merkle { tree_depth, index } => {
+            merkle::get_example(options, tree_depth, index)
+        }
+        #[cfg(feature = "std")]
+        ExampleType::LamportA { num_signatures } => {
+            lamport::aggregate::get_example(options, num_signatures)
+        }
+        #[cfg(feature = "std")]
+        ExampleType::LamportT {
+            num_signers,
+            threshold,
+        } => lamport::threshold::get_example(options, num_signers, threshold),
+        #[cfg(feature = "std")]
+        ExampleType::Schnorr { num_signatures } => schnorr::get_example(options, num_signatures),
+        ExampleType::List | ExampleType::External(_) => {
+            Err(format!("'{}' is not a built-in example", example.name()))
+        }
+    }
 }
 
 // EXAMPLE OPTIONS
 // ================================================================================================
 
-#[derive(StructOpt, Debug)]
+#[derive(StructOpt, Debug, Clone)]
 #[structopt(name = "winterfell", about = "Winterfell examples")]
 pub struct ExampleOptions {
     #[structopt(subcommand)]
     pub example: ExampleType,
 
     /// Hash function used in the protocol
-    #[structopt(short = "h", long = "hash_fn", default_value = "blake3_256")]
-    hash_fn: String,
+    #[structopt(
+        short = "h",
+        long = "hash",
+        alias = "hash_fn",
+        default_value = "blake3_256"
+    )]
+    hash_fn: HashFunction,
 
     /// Number of queries to include in a proof
     #[structopt(short = "q", long = "queries")]
@@ -62,62 +300,131 @@ pub struct ExampleOptions {
     #[structopt(short = "g", long = "grinding", default_value = "16")]
     grinding_factor: u32,
 
-    /// Field extension degree for composition polynomial
-    #[structopt(short = "e", long = "field_extension", default_value = "1")]
-    field_extension: u32,
+    /// Field extension for composition polynomial: none, quadratic, cubic, or sextic; defaults
+    /// to none for the 128-bit field and to quadratic for smaller fields
+    #[structopt(short = "e", long = "extension", alias = "field_extension")]
+    field_extension: Option<FieldExtension>,
+
+    /// Generate proofs even if the field provides less than 80 bits of security for the
+    /// low-degree extension domain (e.g., a 64-bit field without an extension)
+    #[structopt(long = "allow-insecure-field")]
+    allow_insecure_field: bool,
 
     /// Folding factor for FRI protocol
-    #[structopt(short = "f", long = "folding", default_value = "8")]
+    #[structopt(
+        short = "f",
+        long = "folding",
+        default_value = "8",
+        possible_values = &["2", "4", "8", "16"]
+    )]
     folding_factor: usize,
+
+    /// Maximum size of the remainder in FRI protocol
+    #[structopt(
+        short = "r",
+        long = "max-remainder",
+        default_value = "256",
+        possible_values = &["32", "64", "128", "256", "512", "1024"]
+    )]
+    fri_max_remainder_size: usize,
+
+    /// File into which the generated proof is written
+    #[structopt(short = "o", long = "output", parse(from_os_str))]
+    pub output: Option<PathBuf>,
+
+    /// Proof file to verify instead of generating a new proof
+    #[structopt(long = "verify", parse(from_os_str), conflicts_with = "output")]
+    pub verify: Option<PathBuf>,
+
+    /// Print a description of the AIR of the example instead of generating a proof
+    #[structopt(long = "describe", conflicts_with_all = &["output", "verify"])]
+    pub describe: bool,
+
+    /// Comma-separated list of queries:blowup pairs; a proof is generated for each pair from a
+    /// single execution trace, instead of generating a single proof
+    #[structopt(
+        long = "sweep",
+        use_delimiter = true,
+        number_of_values = 1,
+        conflicts_with_all = &["output", "verify", "describe"]
+    )]
+    pub sweep: Vec<SweepPoint>,
 }
 
 impl ExampleOptions {
-    pub fn to_proof_options(&self, q: usize, b: usize) -> (ProofOptions, HashFunction) {
+    /// Returns proof options and the hash function for an example defined over base field `B`.
+    ///
+    /// The number of queries and the blowup factor default to `q` and `b` respectively, unless
+    /// they were specified explicitly. Unless specified explicitly, no field extension is used
+    /// for fields of at least 128 bits, and a quadratic extension is used for smaller fields.
+    ///
+    /// # Errors
+    /// Returns an error if the selected field extension is not supported for field `B`.
+    pub fn to_proof_options<B>(
+        &self,
+        q: usize,
+        b: usize,
+    ) -> Result<(ProofOptions, HashFunction), String>
+    where
+        B: StarkField + ExtensibleField<2> + ExtensibleField<3> + ExtensibleField<6>,
+    {
+        let field_extension = self.field_extension.unwrap_or(if B::MODULUS_BITS < 128 {
+            FieldExtension::Quadratic
+        } else {
+            FieldExtension::None
+        });
+        if !field_extension.is_supported_by::<B>() {
+            return Err(format!(
+                "{} field extension is not supported for the {}-bit base field of this example",
+                field_extension,
+                B::MODULUS_BITS
+            ));
+        }
+
         let num_queries = self.num_queries.unwrap_or(q);
         let blowup_factor = self.blowup_factor.unwrap_or(b);
-        let field_extension = match self.field_extension {
-            1 => FieldExtension::None,
-            2 => FieldExtension::Quadratic,
-            3 => FieldExtension::Cubic,
-            val => panic!("'{val}' is not a valid field extension option"),
-        };
-
-        let hash_fn = match self.hash_fn.as_str() {
-            "blake3_192" => HashFunction::Blake3_192,
-            "blake3_256" => HashFunction::Blake3_256,
-            "sha3_256" => HashFunction::Sha3_256,
-            "rp64_256" => HashFunction::Rp64_256,
-            val => panic!("'{val}' is not a valid hash function option"),
-        };
+        let mut options = ProofOptions::new(
+            num_queries,
+            blowup_factor,
+            self.grinding_factor,
+            field_extension,
+            self.folding_factor,
+            self.fri_max_remainder_size,
+        );
+        if self.allow_insecure_field {
+            options = options.allow_insecure_field();
+        }
+        Ok((options, self.hash_fn))
+    }
 
-        (
-            ProofOptions::new(
-                num_queries,
-                blowup_factor,
-                self.grinding_factor,
-                field_extension,
-                self.folding_factor,
-                256,
-            ),
-            hash_fn,
-        )
+    /// Returns a copy of these options in which the number of queries and the blowup factor are
+    /// replaced with the values of the specified sweep point.
+    pub fn with_sweep_point(&self, point: SweepPoint) -> Self {
+        Self {
+            num_queries: Some(point.num_queries),
+            blowup_factor: Some(point.blowup_factor),
+            sweep: Vec::new(),
+            ..self.clone()
+        }
     }
 
     /// Returns security level of the input proof in bits.
     pub fn get_proof_security_level(&self, proof: &StarkProof) -> usize {
-        let security_level = match self.hash_fn.as_str() {
-            "blake3_192" => proof.security_level::<Blake3_192>(true),
-            "blake3_256" => proof.security_level::<Blake3_256>(true),
-            "sha3_256" => proof.security_level::<Sha3_256>(true),
-            "rp64_256" => proof.security_level::<Rp64_256>(true),
-            val => panic!("'{val}' is not a valid hash function option"),
+        let security_level = match self.hash_fn {
+            HashFunction::Blake3_192 => proof.security_level::<Blake3_192>(true),
+            HashFunction::Blake3_256 => proof.security_level::<Blake3_256>(true),
+            HashFunction::Sha3_256 => proof.security_level::<Sha3_256>(true),
+            HashFunction::Blake2s_256 => proof.security_level::<Blake2s_256>(true),
+            HashFunction::Rp64_256 => proof.security_level::<Rp64_256>(true),
+            HashFunction::Poseidon64_256 => proof.security_level::<Poseidon64_256>(true),
+            HashFunction::Keccak_256 => proof.security_level::<Keccak_256>(true),
         };
 
         security_level as usize
     }
 }
 
-#[derive(StructOpt, Debug)]
+#[derive(StructOpt, Debug, Clone)]
 //#[structopt(about = "available examples")]
 pub enum ExampleType {
     /// Compute a Fibonacci sequence using trace table with 2 registers
@@ -150,24 +457,56 @@ pub enum ExampleType {
         #[structopt(short = "n", default_value = "65536")]
         sequence_length: usize,
     },
+    /// Compute a Fibonacci sequence using trace table with a number of registers specified at
+    /// runtime
+    FibDyn {
+        /// Length of Fibonacci sequence; must be a power of two
+        #[structopt(short = "n", default_value = "1048576")]
+        sequence_length: usize,
+        /// Number of terms computed in a single step; must be a power of two between 2 and 64
+        #[structopt(short = "k", long = "terms-per-step", default_value = "2")]
+        terms_per_step: usize,
+    },
+    /// Compute a Fibonacci sequence using trace table with 1 register and 3-row constraints
+    FibMultiRow {
+        /// Length of Fibonacci sequence; must be a power of two
+        #[structopt(short = "n", default_value = "1048576")]
+        sequence_length: usize,
+    },
     /// Execute a simple VDF function
     Vdf {
         /// Number of steps in the VDF function; must be a power of two
         #[structopt(short = "n", default_value = "1048576")]
         num_steps: usize,
     },
-    /// Similar to the VDF example, but exempts an extra row from transition constraints.
+    /// Similar to the VDF example, but switches off the transition constraint for an extra row
+    /// using a selector column.
     VdfExempt {
-        /// Number of steps in the VDF function; must be one less than a power of two
+        /// Number of steps in the VDF function; must be greater than one
         #[structopt(short = "n", default_value = "1048575")]
         num_steps: usize,
     },
+    /// Similar to the VDF example, but exposes intermediate states of the VDF as public inputs
+    VdfCheckpointed {
+        /// Number of steps in the VDF function; must be a power of two
+        #[structopt(short = "n", default_value = "1048576")]
+        num_steps: usize,
+        /// Number of steps between checkpoints; must be a power of two greater than one
+        #[structopt(short = "c", long = "checkpoint-every", default_value = "1024")]
+        checkpoint_every: usize,
+    },
     /// Compute a hash chain using Rescue hash function
     RescueF128 {
         /// Length of the hash chain; must be a power of two
         #[structopt(short = "n", default_value = "1024")]
         chain_length: usize,
     },
+    /// Compute a hash chain using GMiMC hash function in `f64` field
+    GmimcChain {
+        /// Length of the hash chain; must be a power of two
+        #[structopt(short = "n", default_value = "1024")]
+        chain_length: usize,
+    },
     /// Compute two hash chains absorbing sequences that are a permutation of each other
     #[cfg(feature = "std")]
     RescueRaps {
@@ -181,21 +520,74 @@ pub enum ExampleType {
         /// Depth of the Merkle tree; must be one less than a power of two
         #[structopt(short = "n", default_value = "7")]
         tree_depth: usize,
+        /// Index of the leaf for which the path is verified; must be smaller than 2^depth. If not
+        /// specified, a random leaf is selected
+        #[structopt(short = "i", long = "index")]
+        index: Option<usize>,
     },
     /// Compute an aggregate Lamport+ signature
     #[cfg(feature = "std")]
     LamportA {
-        /// Number of signatures to aggregate; must be a power of two
-        #[structopt(short = "n", default_value = "4")]
+        /// Number of signatures to aggregate; if not a power of two, the execution trace is
+        /// padded with verifications of a dummy signature
+        #[structopt(short = "n", long = "num-signatures", default_value = "4")]
         num_signatures: usize,
     },
     /// Compute a threshold Lamport+ signature
     #[cfg(feature = "std")]
     LamportT {
-        /// Number of signers; must be one less than a power of two
-        #[structopt(short = "n", default_value = "3")]
+        /// Number of keys in the aggregated public key
+        #[structopt(short = "n", long = "num-signers", default_value = "3")]
         num_signers: usize,
+        /// Number of keys which sign the message; must not exceed the number of keys
+        #[structopt(short = "t", long = "threshold", default_value = "2")]
+        threshold: usize,
+    },
+    /// Verify an aggregate of Schnorr signatures over the Cheetah curve
+    #[cfg(feature = "std")]
+    Schnorr {
+        /// Number of signatures to aggregate; must be a power of two
+        #[structopt(short = "n", default_value = "4")]
+        num_signatures: usize,
     },
+    /// Print the names of all available examples
+    List,
+    /// Example registered in an [ExampleRegistry] outside of this crate; contains the name of the
+    /// example followed by its arguments
+    #[structopt(external_subcommand)]
+    External(Vec<String>),
+}
+
+impl ExampleType {
+    /// Returns the name of the subcommand which selects this example.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Fib { .. } => "fib",
+            Self::Fib8 { .. } => "fib8",
+            Self::Mulfib { .. } => "mulfib",
+            Self::Mulfib8 { .. } => "mulfib8",
+            Self::FibSmall { .. } => "fib-small",
+            Self::FibDyn { .. } => "fib-dyn",
+            Self::FibMultiRow { .. } => "fib-multi-row",
+            Self::Vdf { .. } => "vdf",
+            Self::VdfExempt { .. } => "vdf-exempt",
+            Self::VdfCheckpointed { .. } => "vdf-checkpointed",
+            Self::RescueF128 { .. } => "rescue-f128",
+            Self::GmimcChain { .. } => "gmimc-chain",
+            #[cfg(feature = "std")]
+            Self::RescueRaps { .. } => "rescue-raps",
+            #[cfg(feature = "std")]
+            Self::Merkle { .. } => "merkle",
+            #[cfg(feature = "std")]
+            Self::LamportA { .. } => "lamport-a",
+            #[cfg(feature = "std")]
+            Self::LamportT { .. } => "lamport-t",
+            #[cfg(feature = "std")]
+            Self::Schnorr { .. } => "schnorr",
+            Self::List => "list",
+            Self::External(args) => args.first().map(String::as_str).unwrap_or_default(),
+        }
+    }
 }
 
 /// Defines a set of hash functions available for the provided examples. Some examples may not
@@ -222,8 +614,97 @@ pub enum HashFunction {
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     Sha3_256,
 
+    /// BLAKE2s hash function with 256 bit output.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    #[allow(non_camel_case_types)]
+    Blake2s_256,
+
     /// Rescue Prime hash function with 256 bit output. It only works in `f64` field.
     ///
     /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
     Rp64_256,
+
+    /// Poseidon hash function with 256 bit output. It only works in `f64` field.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    Poseidon64_256,
+
+    /// Keccak hash function with 256 bit output, i.e., Ethereum's `keccak256`. Inputs are encoded
+    /// so that hashes can be recomputed cheaply by verifiers running on the EVM.
+    ///
+    /// When this function is used in the STARK protocol, proof security cannot exceed 128 bits.
+    #[allow(non_camel_case_types)]
+    Keccak_256,
+}
+
+impl HashFunction {
+    /// Returns the hash function with the specified id, or None if the id is not valid.
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Blake3_192),
+            1 => Some(Self::Blake3_256),
+            2 => Some(Self::Sha3_256),
+            3 => Some(Self::Blake2s_256),
+            4 => Some(Self::Rp64_256),
+            5 => Some(Self::Poseidon64_256),
+            6 => Some(Self::Keccak_256),
+            _ => None,
+        }
+    }
+
+    /// Returns the hash function implemented by hasher `H`, or None if `H` does not implement any
+    /// of the listed hash functions.
+    pub fn of<H: Hasher>() -> Option<Self> {
+        let name = type_name::<H>();
+        let name = name.split('<').next().unwrap_or(name);
+        match name.rsplit("::").next() {
+            Some("Blake3_192") => Some(Self::Blake3_192),
+            Some("Blake3_256") => Some(Self::Blake3_256),
+            Some("Sha3_256") => Some(Self::Sha3_256),
+            Some("Blake2s_256") => Some(Self::Blake2s_256),
+            Some("Rp64_256") => Some(Self::Rp64_256),
+            Some("Poseidon64_256") => Some(Self::Poseidon64_256),
+            Some("Keccak_256") => Some(Self::Keccak_256),
+            _ => None,
+        }
+    }
+
+    /// Returns the name under which this hash function is accepted on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Blake3_192 => "blake3_192",
+            Self::Blake3_256 => "blake3_256",
+            Self::Sha3_256 => "sha3_256",
+            Self::Blake2s_256 => "blake2s_256",
+            Self::Rp64_256 => "rp64_256",
+            Self::Poseidon64_256 => "poseidon64_256",
+            Self::Keccak_256 => "keccak_256",
+        }
+    }
+}
+
+impl fmt::Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for HashFunction {
+    type Err = String;
+
+    /// Parses a hash function from its name (e.g., "blake3_256"); names are case-insensitive.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let hash_fns = (0..).map_while(Self::from_id);
+        for hash_fn in hash_fns.clone() {
+            if value.eq_ignore_ascii_case(hash_fn.name()) {
+                return Ok(hash_fn);
+            }
+        }
+        let names = hash_fns.map(|hash_fn| hash_fn.name()).collect::<Vec<_>>();
+        Err(format!(
+            "'{value}' is not a valid hash function; accepted values are {}",
+            names.join(", ")
+        ))
+    }
 }
@@ -5,25 +5,20 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{rescue, BaseElement, FieldElement, HASH_CYCLE_LEN, HASH_STATE_WIDTH, TRACE_WIDTH};
-use crate::utils::{are_equal, is_binary, is_zero, not, EvaluationResult};
+use crate::utils::{are_equal, is_binary, is_zero, not, select, EvaluationResult};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, ProofOptions, Serializable,
+    TraceInfo, TransitionConstraintDegree,
 };
 
 // MERKLE PATH VERIFICATION AIR
 // ================================================================================================
 
+#[derive(Serializable, ToElements)]
 pub struct PublicInputs {
     pub tree_root: [BaseElement; 2],
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.tree_root[..]);
-    }
-}
-
 pub struct MerkleAir {
     context: AirContext<BaseElement>,
     tree_root: [BaseElement; 2],
@@ -82,21 +77,20 @@ impl Air for MerkleAir {
         );
 
         // when hash_flag = 0, make sure accumulated hash is placed in the right place in the hash
-        // state for the next round of hashing. Specifically: when index bit = 0 accumulated hash
-        // must go into registers [0, 1], and when index bit = 0, it must go into registers [2, 3]
+        // state for the next round of hashing. Specifically: when the direction bit = 0 the
+        // accumulated hash must go into registers [0, 1], and when the direction bit = 1, it must
+        // go into registers [2, 3]; the sibling node goes into the other pair of registers.
         let hash_init_flag = not(hash_flag);
         let bit = next[6];
-        let not_bit = not(bit);
-        result.agg_constraint(0, hash_init_flag, not_bit * are_equal(current[0], next[0]));
-        result.agg_constraint(1, hash_init_flag, not_bit * are_equal(current[1], next[1]));
-        result.agg_constraint(2, hash_init_flag, bit * are_equal(current[0], next[2]));
-        result.agg_constraint(3, hash_init_flag, bit * are_equal(current[1], next[3]));
+        let node = [select(bit, next[2], next[0]), select(bit, next[3], next[1])];
+        result.agg_constraint(0, hash_init_flag, are_equal(current[0], node[0]));
+        result.agg_constraint(1, hash_init_flag, are_equal(current[1], node[1]));
 
         // make sure capacity registers of the hash state are reset to zeros
         result.agg_constraint(4, hash_init_flag, is_zero(next[4]));
         result.agg_constraint(5, hash_init_flag, is_zero(next[5]));
 
-        // finally, we always enforce that values in the bit register must be binary
+        // finally, we always enforce that values in the direction bit register must be binary
         result[6] = is_binary(current[6]);
     }
 
@@ -9,8 +9,13 @@ use crate::utils::rescue::{
     STATE_WIDTH as HASH_STATE_WIDTH,
 };
 use crate::{
-    utils::rescue::{Hash, Rescue128},
-    Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256,
+    describe_air,
+    utils::{
+        rescue::{Hash, Rescue128},
+        sponge::RescueSponge,
+    },
+    Blake2s_256, Blake3_192, Blake3_256, Example, ExampleDescriptor, ExampleOptions, ExampleTrace,
+    HashFunction, Keccak_256, Sha3_256,
 };
 use core::marker::PhantomData;
 use log::debug;
@@ -18,6 +23,7 @@ use rand_utils::{rand_value, rand_vector};
 use std::time::Instant;
 use winterfell::{
     crypto::{Digest, ElementHasher, MerkleTree},
+    introspection::AirInfo,
     math::{fields::f128::BaseElement, log2, FieldElement, StarkField},
     ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
 };
@@ -41,18 +47,25 @@ const TRACE_WIDTH: usize = 7;
 pub fn get_example(
     options: &ExampleOptions,
     tree_depth: usize,
+    index: Option<usize>,
 ) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(28, 8);
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8)?;
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(MerkleExample::<Blake3_192>::new(
-            tree_depth, options,
+            tree_depth, index, options,
         ))),
         HashFunction::Blake3_256 => Ok(Box::new(MerkleExample::<Blake3_256>::new(
-            tree_depth, options,
+            tree_depth, index, options,
         ))),
         HashFunction::Sha3_256 => Ok(Box::new(MerkleExample::<Sha3_256>::new(
-            tree_depth, options,
+            tree_depth, index, options,
+        ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(MerkleExample::<Blake2s_256>::new(
+            tree_depth, index, options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(MerkleExample::<Keccak_256>::new(
+            tree_depth, index, options,
         ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
@@ -68,14 +81,29 @@ pub struct MerkleExample<H: ElementHasher> {
 }
 
 impl<H: ElementHasher> MerkleExample<H> {
-    pub fn new(tree_depth: usize, options: ProofOptions) -> Self {
+    /// Builds a Merkle tree of the specified depth, and an authentication path from the leaf at
+    /// the specified index to the root of the tree; if the index is not specified, a random leaf
+    /// is selected.
+    ///
+    /// Bits of the leaf index define the directions of the path: the i-th bit (starting with the
+    /// least significant one) is ONE if the node at the i-th level of the path is a right child.
+    pub fn new(tree_depth: usize, index: Option<usize>, options: ProofOptions) -> Self {
         assert!(
             (tree_depth + 1).is_power_of_two(),
             "tree depth must be one less than a power of 2"
         );
+        let num_leaves = usize::pow(2, tree_depth as u32);
+        let index = match index {
+            Some(index) => {
+                assert!(
+                    index < num_leaves,
+                    "leaf index must be smaller than {num_leaves}, but was {index}"
+                );
+                index
+            }
+            None => (rand_value::<BaseElement>().to_repr() % num_leaves as u128) as usize,
+        };
         let value = [BaseElement::new(42), BaseElement::new(43)];
-        let index =
-            (rand_value::<BaseElement>().to_repr() % u128::pow(2, tree_depth as u32)) as usize;
 
         // build Merkle tree of the specified depth
         let now = Instant::now();
@@ -114,7 +142,11 @@ impl<H: ElementHasher> Example for MerkleExample<H>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
-    fn prove(&self) -> StarkProof {
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("merkle")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
         // generate the execution trace
         debug!(
             "Generating proof for proving membership in a Merkle tree of depth {}\n\
@@ -135,8 +167,20 @@ where
             now.elapsed().as_millis()
         );
 
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = MerkleProver::<H>::new(self.options.clone());
+
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = MerkleProver::<H>::new(self.options.clone());
+        describe_air(&prover, &trace.into_inner())
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -165,6 +209,13 @@ fn build_merkle_tree(depth: usize, value: [BaseElement; 2], index: usize) -> Mer
         leaves.push(Hash::new(leaf_elements[i], leaf_elements[i + 1]));
     }
 
-    leaves[index] = Rescue128::digest(&value);
+    leaves[index] = hash_leaf(value);
     MerkleTree::new(leaves).unwrap()
 }
+
+/// Hashes the leaf value with the Rescue sponge. Absorbing two elements takes a single
+/// permutation, which is computed in the first cycle of the execution trace.
+fn hash_leaf(value: [BaseElement; 2]) -> Hash {
+    let digest = RescueSponge::<Rescue128>::hash(&value, 2);
+    Hash::new(digest[0], digest[1])
+}
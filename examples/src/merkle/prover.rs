@@ -6,8 +6,8 @@
 
 use super::{
     rescue, BaseElement, ElementHasher, FieldElement, MerkleAir, PhantomData, ProofOptions, Prover,
-    PublicInputs, Trace, TraceTable, HASH_CYCLE_LEN, HASH_STATE_WIDTH, NUM_HASH_ROUNDS,
-    TRACE_WIDTH,
+    PublicInputs, Rescue128, RescueSponge, Trace, TraceTable, HASH_CYCLE_LEN, HASH_STATE_WIDTH,
+    NUM_HASH_ROUNDS, TRACE_WIDTH,
 };
 
 // MERKLE PROVER
@@ -39,12 +39,15 @@ impl<H: ElementHasher> MerkleProver<H> {
         // skip the first node of the branch because it will be computed in the trace as hash(value)
         let branch = &branch[1..];
 
+        // the leaf value is hashed with the Rescue sponge, and thus the rate portion of the first
+        // state holds the padded value
+        let leaf_block = RescueSponge::<Rescue128>::pad(&value).remove(0);
+
         trace.fill(
             |state| {
                 // initialize first state of the computation
-                state[0] = value[0];
-                state[1] = value[1];
-                state[2..].fill(BaseElement::ZERO);
+                state[..leaf_block.len()].copy_from_slice(&leaf_block);
+                state[leaf_block.len()..].fill(BaseElement::ZERO);
             },
             |step, state| {
                 // execute the transition function for all steps
@@ -4,13 +4,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use super::{
+    BaseElement, Blake3_256, Example, Hash, MerkleAir, MerkleProver, PublicInputs, TraceTable,
+    HASH_CYCLE_LEN,
+};
+use winterfell::{Deserializable, FieldExtension, ProofOptions, Prover, SliceReader};
 
 #[test]
 fn merkle_test_basic_proof_verification() {
     let merkle = Box::new(super::MerkleExample::<Blake3_256>::new(
         7,
+        None,
         build_options(false),
     ));
     crate::tests::test_basic_proof_verification(merkle);
@@ -20,6 +24,7 @@ fn merkle_test_basic_proof_verification() {
 fn merkle_test_basic_proof_verification_extension() {
     let merkle = Box::new(super::MerkleExample::<Blake3_256>::new(
         7,
+        None,
         build_options(true),
     ));
     crate::tests::test_basic_proof_verification(merkle);
@@ -29,11 +34,95 @@ fn merkle_test_basic_proof_verification_extension() {
 fn merkle_test_basic_proof_verification_fail() {
     let merkle = Box::new(super::MerkleExample::<Blake3_256>::new(
         7,
+        None,
         build_options(false),
     ));
     crate::tests::test_basic_proof_verification_fail(merkle);
 }
 
+#[test]
+fn merkle_leaf_hash() {
+    // the leaf is hashed with the Rescue sponge, i.e., the value is padded with a single ONE
+    let value = [BaseElement::new(42), BaseElement::new(43)];
+    let expected = Hash::new(
+        BaseElement::new(37847549180304292727183030688583823457),
+        BaseElement::new(168373540777015277773237745075608660448),
+    );
+    assert_eq!(expected, super::hash_leaf(value));
+}
+
+#[test]
+fn merkle_test_path_directions() {
+    // paths from the leftmost and the rightmost leaves go in a single direction, while other
+    // paths alternate between left and right children in different orders
+    for index in [0, 127, 0b1010101, 0b0101010, 0b0001111] {
+        let merkle = Box::new(super::MerkleExample::<Blake3_256>::new(
+            7,
+            Some(index),
+            build_options(false),
+        ));
+        assert_eq!(index, merkle.index);
+        crate::tests::test_basic_proof_verification(merkle);
+    }
+}
+
+#[test]
+#[should_panic(expected = "leaf index must be smaller than 128, but was 128")]
+fn merkle_test_index_out_of_bounds() {
+    super::MerkleExample::<Blake3_256>::new(7, Some(128), build_options(false));
+}
+
+#[test]
+fn merkle_test_flipped_direction_bit() {
+    let merkle = super::MerkleExample::<Blake3_256>::new(7, Some(0b0110010), build_options(false));
+    let prover = MerkleProver::<Blake3_256>::new(build_options(false));
+
+    // flipping a direction bit places the accumulated hash on the wrong side of its sibling at
+    // one level of the path; the resulting trace is valid, but it resolves to a different root,
+    // and thus, the proof must not verify against the root of the tree
+    for level in 0..7 {
+        let trace = prover.build_trace(merkle.value, &merkle.path, merkle.index ^ (1 << level));
+        let proof = prover.prove(trace).unwrap();
+        assert!(merkle.verify(proof).is_err());
+    }
+}
+
+#[test]
+fn merkle_test_flipped_root_byte() {
+    let merkle = super::MerkleExample::<Blake3_256>::new(7, Some(0b0110010), build_options(false));
+    let proof = merkle.prove();
+
+    // the root is bound to the last row of the trace by assertions, and thus, a proof must not
+    // verify against a root which differs from the actual root in a single byte
+    let mut root_bytes = merkle.tree_root.to_bytes();
+    root_bytes[0] ^= 1;
+    let tree_root = Hash::read_from(&mut SliceReader::new(&root_bytes)).unwrap();
+    let pub_inputs = PublicInputs {
+        tree_root: tree_root.to_elements(),
+    };
+    assert!(winterfell::verify::<MerkleAir, Blake3_256>(proof.clone(), pub_inputs).is_err());
+    assert!(merkle.verify(proof).is_ok());
+}
+
+#[test]
+fn merkle_test_index_option() {
+    use crate::ExampleOptions;
+    use structopt::StructOpt;
+
+    let args = ["winterfell", "merkle", "-n", "7", "--index", "5"];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let merkle = crate::get_example(&options.example, &options).unwrap();
+    let trace: TraceTable<BaseElement> = merkle.build_trace().into_inner();
+
+    // the direction bit for the i-th level of the path is inserted into the trace at the first
+    // step of the (i + 1)-th hash cycle
+    let bits = (0..7)
+        .map(|level| trace.get(6, (level + 1) * HASH_CYCLE_LEN))
+        .collect::<Vec<_>>();
+    let expected = [1, 0, 1, 0, 0, 0, 0].map(BaseElement::new);
+    assert_eq!(expected.to_vec(), bits);
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -0,0 +1,264 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::HashFunction;
+use core::{any::type_name, fmt};
+use std::{fs, io, path::Path};
+use winterfell::{
+    crypto::ElementHasher, math::StarkField, DeserializationError, StarkProof, VerifierError,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Magic bytes at the start of every proof file.
+pub const MAGIC: [u8; 4] = *b"WFPF";
+
+/// Current version of the proof file format.
+pub const VERSION: u8 = 1;
+
+/// Offset of the format version byte in a proof file.
+pub const VERSION_OFFSET: usize = 4;
+
+/// Offset of the base field id byte in a proof file.
+pub const FIELD_ID_OFFSET: usize = 5;
+
+/// Offset of the hash function id byte in a proof file.
+pub const HASH_FN_OFFSET: usize = 6;
+
+/// Offset of the example name (prefixed with its length) in a proof file.
+const NAME_OFFSET: usize = 7;
+
+// EXAMPLE DESCRIPTOR
+// ================================================================================================
+
+/// Identifies an example and the parameters of the protocol used to prove it.
+///
+/// A descriptor is written into the header of every proof file so that a proof file is
+/// self-describing, and is checked against the example attempting to verify the proof before
+/// the proof is deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExampleDescriptor {
+    /// Name of the example as used on the command line.
+    pub name: &'static str,
+    /// Base field id, defined as the number of bits in the modulus of the base field.
+    pub field_id: u8,
+    /// Hash function used by the protocol.
+    pub hash_fn: HashFunction,
+}
+
+impl ExampleDescriptor {
+    /// Returns a descriptor for the example with the specified name proven using hash function
+    /// `H` in the base field of this hash function.
+    ///
+    /// # Panics
+    /// Panics if `H` is not one of the hash functions listed in [HashFunction].
+    pub fn new<H: ElementHasher>(name: &'static str) -> Self {
+        let hash_fn = HashFunction::of::<H>()
+            .unwrap_or_else(|| panic!("hash function {} is not supported", type_name::<H>()));
+        Self {
+            name,
+            field_id: H::BaseField::MODULUS_BITS as u8,
+            hash_fn,
+        }
+    }
+}
+
+// PROOF FILE HEADER
+// ================================================================================================
+
+/// Header preceding a serialized proof in a proof file.
+///
+/// The header is serialized as follows:
+/// * 4 magic bytes ([MAGIC]).
+/// * 1 byte for the format version ([VERSION]).
+/// * 1 byte for the base field id.
+/// * 1 byte for the hash function id.
+/// * 1 byte for the length of the example name, followed by the UTF-8 bytes of the name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofFileHeader {
+    pub version: u8,
+    pub name: String,
+    pub field_id: u8,
+    pub hash_fn: HashFunction,
+}
+
+impl ProofFileHeader {
+    /// Returns a header of the current format version for the specified example.
+    pub fn new(descriptor: &ExampleDescriptor) -> Self {
+        Self {
+            version: VERSION,
+            name: descriptor.name.to_string(),
+            field_id: descriptor.field_id,
+            hash_fn: descriptor.hash_fn,
+        }
+    }
+
+    /// Serializes this header into a vector of bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(NAME_OFFSET + 1 + self.name.len());
+        result.extend_from_slice(&MAGIC);
+        result.push(self.version);
+        result.push(self.field_id);
+        result.push(self.hash_fn as u8);
+        result.push(self.name.len() as u8);
+        result.extend_from_slice(self.name.as_bytes());
+        result
+    }
+
+    /// Parses a header from the start of the provided bytes, and returns the header together with
+    /// the number of bytes it occupies.
+    ///
+    /// # Errors
+    /// Returns an error if the bytes do not start with [MAGIC], if the format version is not
+    /// supported, or if the header is malformed.
+    pub fn read_from(bytes: &[u8]) -> Result<(Self, usize), ProofFileError> {
+        if bytes.len() < NAME_OFFSET + 1 || bytes[..VERSION_OFFSET] != MAGIC {
+            return Err(ProofFileError::InvalidMagic);
+        }
+
+        let version = bytes[VERSION_OFFSET];
+        if version != VERSION {
+            return Err(ProofFileError::UnsupportedVersion(version));
+        }
+
+        let field_id = bytes[FIELD_ID_OFFSET];
+        let hash_fn = HashFunction::from_id(bytes[HASH_FN_OFFSET]).ok_or_else(|| {
+            ProofFileError::MalformedHeader(format!(
+                "unknown hash function id {}",
+                bytes[HASH_FN_OFFSET]
+            ))
+        })?;
+
+        let name_len = bytes[NAME_OFFSET] as usize;
+        let header_len = NAME_OFFSET + 1 + name_len;
+        let name = bytes
+            .get(NAME_OFFSET + 1..header_len)
+            .ok_or_else(|| ProofFileError::MalformedHeader("example name is truncated".into()))?;
+        let name = String::from_utf8(name.to_vec()).map_err(|_| {
+            ProofFileError::MalformedHeader("example name is not valid UTF-8".into())
+        })?;
+
+        let header = Self {
+            version,
+            name,
+            field_id,
+            hash_fn,
+        };
+        Ok((header, header_len))
+    }
+
+    /// Checks that this header describes a proof of the example with the specified descriptor.
+    pub fn check(&self, descriptor: &ExampleDescriptor) -> Result<(), ProofFileError> {
+        if self.name != descriptor.name {
+            return Err(ProofFileError::ExampleMismatch {
+                expected: descriptor.name,
+                found: self.name.clone(),
+            });
+        }
+        if self.field_id != descriptor.field_id {
+            return Err(ProofFileError::FieldMismatch {
+                expected: descriptor.field_id,
+                found: self.field_id,
+            });
+        }
+        if self.hash_fn != descriptor.hash_fn {
+            return Err(ProofFileError::HashFunctionMismatch {
+                expected: descriptor.hash_fn,
+                found: self.hash_fn,
+            });
+        }
+        Ok(())
+    }
+}
+
+// PROOF FILE I/O
+// ================================================================================================
+
+/// Writes the specified proof into the file at the specified path, preceded by a header for the
+/// example with the specified descriptor.
+pub fn write_proof(
+    path: &Path,
+    descriptor: &ExampleDescriptor,
+    proof: &StarkProof,
+) -> io::Result<()> {
+    let mut bytes = ProofFileHeader::new(descriptor).to_bytes();
+    bytes.extend_from_slice(&proof.to_bytes());
+    fs::write(path, bytes)
+}
+
+/// Reads a proof from the file at the specified path.
+///
+/// The header of the file is checked against the specified descriptor before the proof is
+/// deserialized.
+pub fn read_proof(
+    path: &Path,
+    descriptor: &ExampleDescriptor,
+) -> Result<StarkProof, ProofFileError> {
+    let bytes = fs::read(path).map_err(ProofFileError::Io)?;
+    let (header, header_len) = ProofFileHeader::read_from(&bytes)?;
+    header.check(descriptor)?;
+    StarkProof::from_bytes(&bytes[header_len..]).map_err(ProofFileError::Deserialization)
+}
+
+// PROOF FILE ERROR
+// ================================================================================================
+
+/// Represents an error returned when reading or verifying a proof from a proof file.
+#[derive(Debug)]
+pub enum ProofFileError {
+    /// The proof file could not be read.
+    Io(io::Error),
+    /// The file does not start with the magic bytes of a proof file.
+    InvalidMagic,
+    /// The format version of the proof file is not supported.
+    UnsupportedVersion(u8),
+    /// The header of the proof file is malformed.
+    MalformedHeader(String),
+    /// The proof file contains a proof for a different example.
+    ExampleMismatch {
+        expected: &'static str,
+        found: String,
+    },
+    /// The proof file contains a proof generated in a different base field.
+    FieldMismatch { expected: u8, found: u8 },
+    /// The proof file contains a proof generated with a different hash function.
+    HashFunctionMismatch {
+        expected: HashFunction,
+        found: HashFunction,
+    },
+    /// The proof following the header could not be deserialized.
+    Deserialization(DeserializationError),
+    /// The proof was read successfully but failed to verify.
+    Verification(VerifierError),
+}
+
+impl fmt::Display for ProofFileError {
+    #[rustfmt::skip]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read proof file: {err}"),
+            Self::InvalidMagic => write!(f, "file is not a proof file"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "proof file format version {version} is not supported; expected version {VERSION}")
+            }
+            Self::MalformedHeader(msg) => write!(f, "proof file header is malformed: {msg}"),
+            Self::ExampleMismatch { expected, found } => {
+                write!(f, "proof file contains a proof for example '{found}', but example '{expected}' was expected")
+            }
+            Self::FieldMismatch { expected, found } => {
+                write!(f, "proof file contains a proof in a {found}-bit base field, but the example uses a {expected}-bit base field")
+            }
+            Self::HashFunctionMismatch { expected, found } => {
+                write!(f, "proof file contains a proof generated with hash function {found:?}, but the example uses {expected:?}")
+            }
+            Self::Deserialization(err) => write!(f, "failed to deserialize proof: {err}"),
+            Self::Verification(err) => write!(f, "failed to verify proof: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ProofFileError {}
@@ -0,0 +1,227 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Registry of examples which can be run by the examples runner.
+//!
+//! Built-in examples are selected via the subcommands of [ExampleType]. Examples defined outside
+//! of this crate can be plugged into the same runner (including proof file handling, sweeps, and
+//! benchmarks) by registering them in an [ExampleRegistry] under a name which is then used as a
+//! subcommand. For example, a crate defining its own AIR can run it like so:
+//!
+//! ```ignore
+//! use examples::{run, Example, ExampleOptions, ExampleRegistry};
+//! use structopt::{
+//!     clap::{App, Arg, ArgMatches},
+//!     StructOpt,
+//! };
+//!
+//! fn my_air_args<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+//!     app.arg(Arg::with_name("num_steps").short("n").takes_value(true).default_value("1024"))
+//! }
+//!
+//! fn build_my_air(
+//!     options: &ExampleOptions,
+//!     args: &ArgMatches,
+//! ) -> Result<Box<dyn Example>, String> {
+//!     let num_steps = args.value_of("num_steps").unwrap().parse().map_err(|_| "bad -n")?;
+//!     Ok(Box::new(MyAirExample::new(num_steps, options)?))
+//! }
+//!
+//! fn main() {
+//!     let mut registry = ExampleRegistry::default();
+//!     registry.register_example("my-air", "Prove my AIR", my_air_args, build_my_air);
+//!     run(&registry, &ExampleOptions::from_args());
+//! }
+//! ```
+//!
+//! With this, `my-runner [OPTIONS] my-air -n 4096` generates and verifies a proof for the
+//! example, and `my-runner list` lists it together with the built-in examples.
+
+use crate::{get_builtin_example, Example, ExampleOptions, ExampleType};
+use structopt::clap::{App, ArgMatches};
+
+// TYPES
+// ================================================================================================
+
+/// Function which declares the command-line arguments of a registered example on the subcommand
+/// selecting this example.
+pub type ExampleArgs = for<'a> fn(App<'a, 'a>) -> App<'a, 'a>;
+
+/// Function which instantiates a registered example from the protocol parameters specified by
+/// [ExampleOptions] and the arguments of the subcommand selecting the example.
+pub type ExampleBuilder = fn(&ExampleOptions, &ArgMatches) -> Result<Box<dyn Example>, String>;
+
+/// An example registered in an [ExampleRegistry].
+#[derive(Clone)]
+struct RegisteredExample {
+    name: String,
+    about: String,
+    args: ExampleArgs,
+    builder: ExampleBuilder,
+}
+
+// EXAMPLE REGISTRY
+// ================================================================================================
+
+/// A set of examples which can be instantiated by name.
+///
+/// The default registry contains all built-in examples. Arguments of built-in examples are
+/// parsed into the variants of [ExampleType], and thus, their builders receive empty argument
+/// matches and instantiate the example described by [ExampleOptions::example]. Arguments of
+/// other examples are parsed when the example is instantiated, according to the arguments
+/// declared for the example on registration.
+#[derive(Clone)]
+pub struct ExampleRegistry {
+    examples: Vec<RegisteredExample>,
+}
+
+impl ExampleRegistry {
+    /// Returns a new registry which does not contain any examples.
+    pub fn new() -> Self {
+        Self {
+            examples: Vec::new(),
+        }
+    }
+
+    /// Returns a new registry containing all built-in examples.
+    pub fn with_builtin_examples() -> Self {
+        let mut registry = Self::new();
+        registry.examples = BUILTIN_EXAMPLES
+            .iter()
+            .map(|&(name, about)| RegisteredExample {
+                name: name.to_string(),
+                about: about.to_string(),
+                args: no_args,
+                builder: build_builtin_example,
+            })
+            .collect();
+        registry
+    }
+
+    /// Registers an example under the specified `name`.
+    ///
+    /// The example is selected by a subcommand with the specified `name`; `args` declares the
+    /// arguments of this subcommand, and `builder` instantiates the example.
+    ///
+    /// # Panics
+    /// Panics if an example with the same name has already been registered, or if the name is
+    /// reserved for the `list` subcommand or for one of the built-in examples.
+    pub fn register_example(
+        &mut self,
+        name: &str,
+        about: &str,
+        args: ExampleArgs,
+        builder: ExampleBuilder,
+    ) {
+        assert!(
+            name != "list" && BUILTIN_EXAMPLES.iter().all(|&(builtin, _)| builtin != name),
+            "example name '{name}' is reserved"
+        );
+        assert!(
+            self.find(name).is_none(),
+            "example '{name}' has already been registered"
+        );
+        self.examples.push(RegisteredExample {
+            name: name.to_string(),
+            about: about.to_string(),
+            args,
+            builder,
+        });
+    }
+
+    /// Returns an iterator over the names and descriptions of all registered examples, in the
+    /// order in which they were registered.
+    pub fn examples(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.examples
+            .iter()
+            .map(|example| (example.name.as_str(), example.about.as_str()))
+    }
+
+    /// Instantiates the specified example with the protocol parameters defined by `options`.
+    ///
+    /// # Errors
+    /// Returns an error if the example has not been registered, if the arguments of the example
+    /// could not be parsed, or if the example does not support the options (e.g., the hash
+    /// function).
+    pub fn get_example(
+        &self,
+        example: &ExampleType,
+        options: &ExampleOptions,
+    ) -> Result<Box<dyn Example>, String> {
+        let name = example.name();
+        let registered = self
+            .find(name)
+            .ok_or_else(|| format!("example '{name}' has not been registered"))?;
+
+        let options = ExampleOptions {
+            example: example.clone(),
+            ..options.clone()
+        };
+        match example {
+            ExampleType::External(args) => {
+                let app = (registered.args)(App::new(name).about(registered.about.as_str()));
+                let matches = app.get_matches_from_safe(args).map_err(|err| err.message)?;
+                (registered.builder)(&options, &matches)
+            }
+            _ => (registered.builder)(&options, &ArgMatches::default()),
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    fn find(&self, name: &str) -> Option<&RegisteredExample> {
+        self.examples.iter().find(|example| example.name == name)
+    }
+}
+
+impl Default for ExampleRegistry {
+    fn default() -> Self {
+        Self::with_builtin_examples()
+    }
+}
+
+// BUILT-IN EXAMPLES
+// ================================================================================================
+
+/// Names and descriptions of the built-in examples.
+#[rustfmt::skip]
+const BUILTIN_EXAMPLES: &[(&str, &str)] = &[
+    ("fib", "Compute a Fibonacci sequence using trace table with 2 registers"),
+    ("fib8", "Compute a Fibonacci sequence using trace table with 8 registers"),
+    ("mulfib", "Compute a multiplicative Fibonacci sequence using trace table with 2 registers"),
+    ("mulfib8", "Compute a multiplicative Fibonacci sequence using trace table with 8 registers"),
+    ("fib-small", "Compute a Fibonacci sequence using trace table with 2 registers in `f64` field"),
+    ("fib-dyn", "Compute a Fibonacci sequence using trace table with a number of registers specified at runtime"),
+    ("fib-multi-row", "Compute a Fibonacci sequence using trace table with 1 register and 3-row constraints"),
+    ("vdf", "Execute a simple VDF function"),
+    ("vdf-exempt", "Similar to the VDF example, but switches off the transition constraint for an extra row"),
+    ("vdf-checkpointed", "Similar to the VDF example, but exposes intermediate states of the VDF as public inputs"),
+    ("rescue-f128", "Compute a hash chain using Rescue hash function"),
+    ("gmimc-chain", "Compute a hash chain using GMiMC hash function in `f64` field"),
+    #[cfg(feature = "std")]
+    ("rescue-raps", "Compute two hash chains absorbing sequences that are a permutation of each other"),
+    #[cfg(feature = "std")]
+    ("merkle", "Compute a root of a Merkle path using Rescue hash function"),
+    #[cfg(feature = "std")]
+    ("lamport-a", "Compute an aggregate Lamport+ signature"),
+    #[cfg(feature = "std")]
+    ("lamport-t", "Compute a threshold Lamport+ signature"),
+    #[cfg(feature = "std")]
+    ("schnorr", "Verify an aggregate of Schnorr signatures over the Cheetah curve"),
+];
+
+/// Arguments of built-in examples are declared by [ExampleType].
+fn no_args<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+    app
+}
+
+fn build_builtin_example(
+    options: &ExampleOptions,
+    _args: &ArgMatches,
+) -> Result<Box<dyn Example>, String> {
+    get_builtin_example(&options.example, options)
+}
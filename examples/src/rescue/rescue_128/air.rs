@@ -4,137 +4,80 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{rescue, BaseElement, FieldElement, ProofOptions};
-use crate::utils::{are_equal, is_zero, not, EvaluationResult};
+use super::{rescue, BaseElement, FieldElement, CYCLE_LENGTH, NUM_HASH_ROUNDS};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, ChainedAir, ChainedAirBuilder, ChainedComputation, Deserializable,
+    EvaluationFrame, RoundFunction, Serializable,
 };
 
 // CONSTANTS
 // ================================================================================================
 
-const CYCLE_LENGTH: usize = 16;
 const TRACE_WIDTH: usize = 4;
 
-/// Specifies steps on which Rescue transition function is applied.
-const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ONE,
-    BaseElement::ZERO,
-    BaseElement::ZERO,
-];
-
 // RESCUE AIR
 // ================================================================================================
 
+#[derive(Serializable, Deserializable, ToElements)]
 pub struct PublicInputs {
     pub seed: [BaseElement; 2],
     pub result: [BaseElement; 2],
 }
 
-impl Serializable for PublicInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(&self.seed[..]);
-        target.write(&self.result[..]);
-    }
-}
+/// AIR of the Rescue hash chain computation; when `COMMIT_PERIODIC` is true, the prover commits
+/// to the hash flag and round constant columns instead of leaving them to be evaluated by the
+/// verifier.
+pub type RescueAir<const COMMIT_PERIODIC: bool = false> = ChainedAir<RescueChain<COMMIT_PERIODIC>>;
 
-pub struct RescueAir {
-    context: AirContext<BaseElement>,
-    seed: [BaseElement; 2],
-    result: [BaseElement; 2],
-}
+/// Describes a chain of Rescue hashes: every hash is computed in the first 14 steps of a 16-step
+/// cycle; in the remaining steps, the first two registers are carried over to the next step and
+/// the other two registers are reset to zeros.
+pub struct RescueChain<const COMMIT_PERIODIC: bool>;
 
-impl Air for RescueAir {
+impl<const COMMIT_PERIODIC: bool> ChainedComputation for RescueChain<COMMIT_PERIODIC> {
     type BaseField = BaseElement;
     type PublicInputs = PublicInputs;
-
-    // CONSTRUCTOR
-    // --------------------------------------------------------------------------------------------
-    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
-        let degrees = vec![
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
-        ];
-        assert_eq!(TRACE_WIDTH, trace_info.width());
-        RescueAir {
-            context: AirContext::new(trace_info, degrees, 4, options),
-            seed: pub_inputs.seed,
-            result: pub_inputs.result,
+    type RoundFunction = RescueRound;
+
+    fn build_air(pub_inputs: PublicInputs) -> ChainedAirBuilder<BaseElement, RescueRound> {
+        let builder = ChainedAirBuilder::new(TRACE_WIDTH)
+            .with_round_function(RescueRound)
+            .with_cycle_length(CYCLE_LENGTH)
+            .with_num_rounds(NUM_HASH_ROUNDS)
+            .with_round_constants(rescue::get_round_constants())
+            .with_reset_columns(&[2, 3])
+            .with_input_assertions(vec![(0, pub_inputs.seed[0]), (1, pub_inputs.seed[1])])
+            .with_output_assertions(vec![(0, pub_inputs.result[0]), (1, pub_inputs.result[1])]);
+
+        if COMMIT_PERIODIC {
+            builder.with_committed_periodic_columns()
+        } else {
+            builder
         }
     }
+}
+
+// RESCUE ROUND
+// ================================================================================================
+
+/// Constraints of a single round of Rescue hash function.
+pub struct RescueRound;
 
-    fn context(&self) -> &AirContext<Self::BaseField> {
-        &self.context
+impl RoundFunction<BaseElement> for RescueRound {
+    fn degree(&self) -> usize {
+        3
     }
 
-    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+    fn evaluate<E: FieldElement<BaseField = BaseElement>>(
         &self,
         frame: &EvaluationFrame<E>,
-        periodic_values: &[E],
+        ark: &[E],
         result: &mut [E],
     ) {
-        let current = frame.current();
-        let next = frame.next();
         // expected state width is 4 field elements
-        debug_assert_eq!(TRACE_WIDTH, current.len());
-        debug_assert_eq!(TRACE_WIDTH, next.len());
+        debug_assert_eq!(TRACE_WIDTH, frame.current().len());
+        debug_assert_eq!(TRACE_WIDTH, frame.next().len());
 
-        // split periodic values into hash_flag and Rescue round constants
-        let hash_flag = periodic_values[0];
-        let ark = &periodic_values[1..];
-
-        // when hash_flag = 1, constraints for Rescue round are enforced
-        rescue::enforce_round(result, current, next, ark, hash_flag);
-
-        // when hash_flag = 0, constraints for copying hash values to the next
-        // step are enforced.
-        let copy_flag = not(hash_flag);
-        enforce_hash_copy(result, current, next, copy_flag);
+        rescue::enforce_round(result, frame.current(), frame.next(), ark, E::ONE);
     }
-
-    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
-        // Assert starting and ending values of the hash chain
-        let last_step = self.trace_length() - 1;
-        vec![
-            Assertion::single(0, 0, self.seed[0]),
-            Assertion::single(1, 0, self.seed[1]),
-            Assertion::single(0, last_step, self.result[0]),
-            Assertion::single(1, last_step, self.result[1]),
-        ]
-    }
-
-    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
-        let mut result = vec![CYCLE_MASK.to_vec()];
-        result.append(&mut rescue::get_round_constants());
-        result
-    }
-}
-
-// HELPER EVALUATORS
-// ------------------------------------------------------------------------------------------------
-
-/// when flag = 1, enforces that the next state of the computation is defined like so:
-/// - the first two registers are equal to the values from the previous step
-/// - the other two registers are equal to 0
-fn enforce_hash_copy<E: FieldElement>(result: &mut [E], current: &[E], next: &[E], flag: E) {
-    result.agg_constraint(0, flag, are_equal(current[0], next[0]));
-    result.agg_constraint(1, flag, are_equal(current[1], next[1]));
-    result.agg_constraint(2, flag, is_zero(next[2]));
-    result.agg_constraint(3, flag, is_zero(next[3]));
 }
@@ -4,14 +4,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, Example, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
     crypto::ElementHasher,
+    introspection::AirInfo,
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    Air, PreparedAir, ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
 };
 
 #[allow(clippy::module_inception)]
@@ -39,7 +43,7 @@ pub fn get_example(
     options: &ExampleOptions,
     chain_length: usize,
 ) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(42, 4);
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(42, 4)?;
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(RescueExample::<Blake3_192>::new(
@@ -54,6 +58,14 @@ pub fn get_example(
             chain_length,
             options,
         ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(RescueExample::<Blake2s_256>::new(
+            chain_length,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(RescueExample::<Keccak_256>::new(
+            chain_length,
+            options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -63,6 +75,7 @@ pub struct RescueExample<H: ElementHasher> {
     chain_length: usize,
     seed: [BaseElement; 2],
     result: [BaseElement; 2],
+    commit_periodic_columns: bool,
     _hasher: PhantomData<H>,
 }
 
@@ -88,9 +101,32 @@ impl<H: ElementHasher> RescueExample<H> {
             chain_length,
             seed,
             result,
+            commit_periodic_columns: false,
             _hasher: PhantomData,
         }
     }
+
+    /// Makes the prover commit to the periodic columns of the computation (the hash flag and
+    /// the Rescue round constants) so that the verifier does not need to evaluate them.
+    pub fn with_committed_periodic_columns(mut self) -> Self {
+        self.commit_periodic_columns = true;
+        self
+    }
+}
+
+impl<H: ElementHasher> RescueExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    /// Verifies a batch of proofs generated by this example; data which does not depend on
+    /// public inputs is prepared once for the entire batch.
+    pub fn verify_batch(&self, proofs: Vec<StarkProof>) -> Result<(), VerifierError> {
+        if self.commit_periodic_columns {
+            self.verify_batch_with::<true>(proofs)
+        } else {
+            self.verify_batch_with::<false>(proofs)
+        }
+    }
 }
 
 // EXAMPLE IMPLEMENTATION
@@ -100,7 +136,11 @@ impl<H: ElementHasher> Example for RescueExample<H>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
-    fn prove(&self) -> StarkProof {
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("rescue-f128")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
         // generate the execution trace
         debug!(
             "Generating proof for computing a chain of {} Rescue hashes\n\
@@ -108,7 +148,49 @@ where
             self.chain_length
         );
 
-        let prover = RescueProver::<H>::new(self.options.clone());
+        if self.commit_periodic_columns {
+            self.build_trace_with::<true>()
+        } else {
+            self.build_trace_with::<false>()
+        }
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        if self.commit_periodic_columns {
+            self.prove_trace_with::<true>(trace)
+        } else {
+            self.prove_trace_with::<false>(trace)
+        }
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        if self.commit_periodic_columns {
+            self.describe_trace_with::<true>(trace)
+        } else {
+            self.describe_trace_with::<false>(trace)
+        }
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        self.verify_with(proof, self.get_pub_inputs())
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            seed: self.seed,
+            result: [self.result[0], self.result[1] + BaseElement::ONE],
+        };
+        self.verify_with(proof, pub_inputs)
+    }
+}
+
+impl<H: ElementHasher> RescueExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    /// Generates a proof using a prover which either commits to periodic columns or not.
+    fn build_trace_with<const COMMIT_PERIODIC: bool>(&self) -> ExampleTrace {
+        let prover = RescueProver::<H, COMMIT_PERIODIC>::new(self.options.clone());
 
         // generate the execution trace
         let now = Instant::now();
@@ -121,24 +203,63 @@ where
             now.elapsed().as_millis()
         );
 
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace_with<const COMMIT_PERIODIC: bool>(&self, trace: ExampleTrace) -> StarkProof {
+        let prover = RescueProver::<H, COMMIT_PERIODIC>::new(self.options.clone());
+
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove(trace.into_inner()).unwrap()
     }
 
-    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        let pub_inputs = PublicInputs {
-            seed: self.seed,
-            result: self.result,
+    fn describe_trace_with<const COMMIT_PERIODIC: bool>(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = RescueProver::<H, COMMIT_PERIODIC>::new(self.options.clone());
+        describe_air(&prover, &trace.into_inner())
+    }
+
+    /// Verifies a batch of proofs against the AIR matching the periodic column mode of this
+    /// example; the AIR is prepared from the first proof in the batch.
+    fn verify_batch_with<const COMMIT_PERIODIC: bool>(
+        &self,
+        proofs: Vec<StarkProof>,
+    ) -> Result<(), VerifierError> {
+        let prepared = match proofs.first() {
+            Some(proof) => {
+                let air = RescueAir::<COMMIT_PERIODIC>::new(
+                    proof.get_trace_info(),
+                    self.get_pub_inputs(),
+                    proof.options().clone(),
+                );
+                PreparedAir::<_, H>::new(&air)
+            }
+            None => return Ok(()),
         };
-        winterfell::verify::<RescueAir, H>(proof, pub_inputs)
+        for proof in proofs {
+            winterfell::verify_prepared(&prepared, proof, self.get_pub_inputs())?;
+        }
+        Ok(())
     }
 
-    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
-        let pub_inputs = PublicInputs {
+    /// Returns public inputs of the computation described by this example.
+    fn get_pub_inputs(&self) -> PublicInputs {
+        PublicInputs {
             seed: self.seed,
-            result: [self.result[0], self.result[1] + BaseElement::ONE],
-        };
-        winterfell::verify::<RescueAir, H>(proof, pub_inputs)
+            result: self.result,
+        }
+    }
+
+    /// Verifies the proof against the AIR matching the periodic column mode of this example.
+    fn verify_with(
+        &self,
+        proof: StarkProof,
+        pub_inputs: PublicInputs,
+    ) -> Result<(), VerifierError> {
+        if self.commit_periodic_columns {
+            winterfell::verify::<RescueAir<true>, H>(proof, pub_inputs)
+        } else {
+            winterfell::verify::<RescueAir, H>(proof, pub_inputs)
+        }
     }
 }
 
@@ -12,12 +12,12 @@ use super::{
 // RESCUE PROVER
 // ================================================================================================
 
-pub struct RescueProver<H: ElementHasher> {
+pub struct RescueProver<H: ElementHasher, const COMMIT_PERIODIC: bool = false> {
     options: ProofOptions,
     _hasher: PhantomData<H>,
 }
 
-impl<H: ElementHasher> RescueProver<H> {
+impl<H: ElementHasher, const COMMIT_PERIODIC: bool> RescueProver<H, COMMIT_PERIODIC> {
     pub fn new(options: ProofOptions) -> Self {
         Self {
             options,
@@ -61,12 +61,12 @@ impl<H: ElementHasher> RescueProver<H> {
     }
 }
 
-impl<H: ElementHasher> Prover for RescueProver<H>
+impl<H: ElementHasher, const COMMIT_PERIODIC: bool> Prover for RescueProver<H, COMMIT_PERIODIC>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
     type BaseField = BaseElement;
-    type Air = RescueAir;
+    type Air = RescueAir<COMMIT_PERIODIC>;
     type Trace = TraceTable<BaseElement>;
     type HashFn = H;
 
@@ -4,8 +4,17 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use super::{rescue, BaseElement, Blake3_256, FieldElement, PublicInputs, CYCLE_LENGTH};
+use crate::{
+    utils::{are_equal, is_zero, not, EvaluationResult},
+    Example,
+};
+use winterfell::{
+    crypto::{Digest, ElementHasher, Hasher, MerkleTree},
+    Air, AirContext, Assertion, ByteReader, ByteWriter, Deserializable, EvaluationFrame,
+    FieldExtension, ProofOptions, Prover, Serializable, SliceReader, Trace, TraceInfo, TraceTable,
+    TransitionConstraintDegree, VerifierError,
+};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
@@ -34,6 +43,151 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_basic_proof_verification_committed_periodic_columns() {
+    let rescue_eg = Box::new(
+        super::RescueExample::<Blake3_256>::new(128, build_options(false))
+            .with_committed_periodic_columns(),
+    );
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_committed_periodic_columns_extension() {
+    let rescue_eg = Box::new(
+        super::RescueExample::<Blake3_256>::new(128, build_options(true))
+            .with_committed_periodic_columns(),
+    );
+    crate::tests::test_basic_proof_verification(rescue_eg);
+}
+
+#[test]
+fn rescue_test_basic_proof_verification_committed_periodic_columns_fail() {
+    let rescue_eg = Box::new(
+        super::RescueExample::<Blake3_256>::new(128, build_options(false))
+            .with_committed_periodic_columns(),
+    );
+    crate::tests::test_basic_proof_verification_fail(rescue_eg);
+}
+
+#[test]
+fn rescue_test_proof_size_estimate() {
+    for use_extension_field in [false, true] {
+        let rescue_eg = Box::new(super::RescueExample::<Blake3_256>::new(
+            128,
+            build_options(use_extension_field),
+        ));
+        crate::tests::test_proof_size_estimate(rescue_eg);
+    }
+
+    // committed periodic columns add queries and out-of-domain values to the proof
+    let rescue_eg = Box::new(
+        super::RescueExample::<Blake3_256>::new(128, build_options(false))
+            .with_committed_periodic_columns(),
+    );
+    crate::tests::test_proof_size_estimate(rescue_eg);
+}
+
+#[test]
+fn rescue_test_prepared_proof_verification() {
+    let rescue_eg = super::RescueExample::<Blake3_256>::new(128, build_options(false));
+    let proof = rescue_eg.prove();
+    assert!(rescue_eg.verify_batch(vec![proof.clone(), proof]).is_ok());
+}
+
+#[test]
+fn rescue_test_prepared_proof_verification_committed_periodic_columns() {
+    let rescue_eg = super::RescueExample::<Blake3_256>::new(128, build_options(true))
+        .with_committed_periodic_columns();
+    let proof = rescue_eg.prove();
+    assert!(rescue_eg.verify_batch(vec![proof.clone(), proof]).is_ok());
+}
+
+#[test]
+fn rescue_test_prepared_proof_verification_fail() {
+    // proofs generated with different options cannot be verified with the same prepared AIR
+    let rescue_eg = super::RescueExample::<Blake3_256>::new(128, build_options(false));
+    let proof1 = rescue_eg.prove();
+    let rescue_eg2 = super::RescueExample::<Blake3_256>::new(128, build_options(true));
+    let proof2 = rescue_eg2.prove();
+    assert_eq!(
+        Err(VerifierError::InconsistentPreparedAir),
+        rescue_eg.verify_batch(vec![proof1, proof2])
+    );
+}
+
+#[test]
+fn rescue_test_public_inputs_transcript_stability() {
+    let rescue_eg = super::RescueExample::<Blake3_256>::new(128, build_options(false));
+    let pub_inputs = rescue_eg.get_pub_inputs();
+    let bytes = pub_inputs.to_bytes();
+
+    // derived serialization of arrays matches the encoding of the elements written one by one,
+    // and thus, transcripts of proofs generated before switching to the derive are unchanged
+    let mut expected = Vec::new();
+    expected.write(&pub_inputs.seed[..]);
+    expected.write(&pub_inputs.result[..]);
+    assert_eq!(expected, bytes);
+    assert_eq!(bytes, rescue_eg.get_pub_inputs().to_bytes());
+
+    let mut reader = SliceReader::new(&bytes);
+    let parsed = super::PublicInputs::read_from(&mut reader).unwrap();
+    assert_eq!(pub_inputs.seed, parsed.seed);
+    assert_eq!(pub_inputs.result, parsed.result);
+    assert!(!reader.has_more_bytes());
+}
+
+#[test]
+fn rescue_test_chained_air_parity() {
+    for use_extension_field in [false, true] {
+        let rescue_eg =
+            super::RescueExample::<Blake3_256>::new(128, build_options(use_extension_field));
+        check_chained_air_parity::<false>(&rescue_eg);
+
+        let rescue_eg = rescue_eg.with_committed_periodic_columns();
+        check_chained_air_parity::<true>(&rescue_eg);
+    }
+}
+
+/// Checks that proofs generated against the AIR built by the chained AIR builder verify
+/// against the hand-written reference AIR, and vice versa.
+fn check_chained_air_parity<const COMMIT_PERIODIC: bool>(
+    rescue_eg: &super::RescueExample<Blake3_256>,
+) {
+    let proof = rescue_eg.prove();
+    assert!(
+        winterfell::verify::<ReferenceRescueAir<COMMIT_PERIODIC>, Blake3_256>(
+            proof,
+            rescue_eg.get_pub_inputs()
+        )
+        .is_ok()
+    );
+
+    let prover = ReferenceRescueProver::<COMMIT_PERIODIC>(rescue_eg.options.clone());
+    let proof = prover.prove(rescue_eg.build_trace().into_inner()).unwrap();
+    assert!(rescue_eg.verify(proof).is_ok());
+}
+
+#[test]
+fn rescue_test_precomputed_periodic_column_commitment() {
+    let rescue_eg = super::RescueExample::<Blake3_256>::new(128, build_options(false))
+        .with_committed_periodic_columns();
+    let trace: TraceTable<BaseElement> = rescue_eg.build_trace().into_inner();
+    assert_eq!(PERIODIC_COLUMN_COMMITMENT_TRACE_LENGTH, trace.length());
+    let air = ReferenceRescueAir::<true>::new(
+        trace.get_info(),
+        rescue_eg.get_pub_inputs(),
+        rescue_eg.options.clone(),
+    );
+    assert_eq!(PERIODIC_COLUMN_COMMITMENT_BLOWUP, air.lde_blowup_factor());
+
+    // the precomputed commitment must match the commitment built from scratch
+    assert_eq!(
+        build_periodic_column_commitment::<Blake3_256, _>(&air),
+        air.get_periodic_column_commitment::<Blake3_256>()
+    );
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -42,3 +196,176 @@ fn build_options(use_extension_field: bool) -> ProofOptions {
     };
     ProofOptions::new(28, 8, 0, extension, 4, 256)
 }
+
+// REFERENCE RESCUE AIR
+// ================================================================================================
+
+/// Specifies steps on which Rescue transition function is applied.
+const CYCLE_MASK: [BaseElement; CYCLE_LENGTH] = [
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ONE,
+    BaseElement::ZERO,
+    BaseElement::ZERO,
+];
+
+/// Length of the execution trace for which [PERIODIC_COLUMN_COMMITMENT] was computed.
+const PERIODIC_COLUMN_COMMITMENT_TRACE_LENGTH: usize = 128 * CYCLE_LENGTH;
+
+/// Blowup factor for which [PERIODIC_COLUMN_COMMITMENT] was computed.
+const PERIODIC_COLUMN_COMMITMENT_BLOWUP: usize = 8;
+
+/// Precomputed commitment to the periodic columns of [ReferenceRescueAir] using BLAKE3 with
+/// 256-bit output.
+const PERIODIC_COLUMN_COMMITMENT: [u8; 32] = [
+    0x34, 0x4b, 0x10, 0xa6, 0xe4, 0x6d, 0x42, 0xd2, 0x73, 0x87, 0x88, 0xb6, 0x89, 0xf8, 0x44, 0xac,
+    0x87, 0x89, 0x8c, 0x26, 0xfb, 0x4a, 0x4d, 0x69, 0x78, 0x2e, 0x15, 0x5d, 0x4e, 0xd9, 0xe1, 0xec,
+];
+
+/// Hand-written AIR of the Rescue hash chain computation which [super::RescueAir] replaced; it
+/// is kept to check that the AIR generated by the chained AIR builder is equivalent.
+struct ReferenceRescueAir<const COMMIT_PERIODIC: bool> {
+    context: AirContext<BaseElement>,
+    seed: [BaseElement; 2],
+    result: [BaseElement; 2],
+}
+
+impl<const COMMIT_PERIODIC: bool> Air for ReferenceRescueAir<COMMIT_PERIODIC> {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
+            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
+            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
+            TransitionConstraintDegree::with_cycles(3, vec![CYCLE_LENGTH]),
+        ];
+        assert_eq!(4, trace_info.width());
+        ReferenceRescueAir {
+            context: AirContext::new(trace_info, degrees, 4, options),
+            seed: pub_inputs.seed,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        // split periodic values into hash_flag and Rescue round constants
+        let hash_flag = periodic_values[0];
+        let ark = &periodic_values[1..];
+
+        // when hash_flag = 1, constraints for Rescue round are enforced
+        rescue::enforce_round(result, current, next, ark, hash_flag);
+
+        // when hash_flag = 0, the first two registers are carried over to the next step, and
+        // the other two registers are reset to zeros
+        let copy_flag = not(hash_flag);
+        result.agg_constraint(0, copy_flag, are_equal(current[0], next[0]));
+        result.agg_constraint(1, copy_flag, are_equal(current[1], next[1]));
+        result.agg_constraint(2, copy_flag, is_zero(next[2]));
+        result.agg_constraint(3, copy_flag, is_zero(next[3]));
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, self.seed[0]),
+            Assertion::single(1, 0, self.seed[1]),
+            Assertion::single(0, last_step, self.result[0]),
+            Assertion::single(1, last_step, self.result[1]),
+        ]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut result = vec![CYCLE_MASK.to_vec()];
+        result.append(&mut rescue::get_round_constants());
+        result
+    }
+
+    fn has_committed_periodic_columns(&self) -> bool {
+        COMMIT_PERIODIC
+    }
+
+    fn get_periodic_column_commitment<H>(&self) -> H::Digest
+    where
+        H: ElementHasher<BaseField = Self::BaseField>,
+    {
+        // periodic columns do not depend on public inputs, and thus, the commitment for the
+        // configuration used in the tests does not need to be recomputed on every verification
+        if self.trace_length() == PERIODIC_COLUMN_COMMITMENT_TRACE_LENGTH
+            && self.lde_blowup_factor() == PERIODIC_COLUMN_COMMITMENT_BLOWUP
+            && H::hash(&[]).as_bytes() == Blake3_256::hash(&[]).as_bytes()
+        {
+            let mut source = SliceReader::new(&PERIODIC_COLUMN_COMMITMENT);
+            return H::Digest::read_from(&mut source).expect("invalid precomputed commitment");
+        }
+        build_periodic_column_commitment::<H, _>(self)
+    }
+}
+
+/// Commits to the evaluations of periodic columns of the specified AIR over the LDE domain in
+/// the same way as the default implementation of [Air::get_periodic_column_commitment()].
+fn build_periodic_column_commitment<H, A>(air: &A) -> H::Digest
+where
+    H: ElementHasher<BaseField = BaseElement>,
+    A: Air<BaseField = BaseElement>,
+{
+    let evaluations = air.get_periodic_column_evaluations();
+    let leaves = (0..air.lde_domain_size())
+        .map(|i| {
+            let row = evaluations
+                .iter()
+                .map(|column| column[i])
+                .collect::<Vec<_>>();
+            H::hash_elements(&row)
+        })
+        .collect();
+    *MerkleTree::<H>::new(leaves)
+        .expect("failed to construct Merkle tree")
+        .root()
+}
+
+/// Prover generating proofs against [ReferenceRescueAir].
+struct ReferenceRescueProver<const COMMIT_PERIODIC: bool>(ProofOptions);
+
+impl<const COMMIT_PERIODIC: bool> Prover for ReferenceRescueProver<COMMIT_PERIODIC> {
+    type BaseField = BaseElement;
+    type Air = ReferenceRescueAir<COMMIT_PERIODIC>;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
+        let last_step = trace.length() - 1;
+        PublicInputs {
+            seed: [trace.get(0, 0), trace.get(1, 0)],
+            result: [trace.get(0, last_step), trace.get(1, last_step)],
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.0
+    }
+}
@@ -4,19 +4,24 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, Example, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
-use rand_utils::rand_array;
+use rand_utils::prng_vector;
 use std::time::Instant;
 use winterfell::{
     crypto::ElementHasher,
+    gadgets::PermutationArgument,
+    introspection::AirInfo,
     math::{fields::f128::BaseElement, log2, ExtensionOf, FieldElement},
     ProofOptions, Prover, StarkProof, Trace, VerifierError,
 };
 
 mod custom_trace_table;
-pub use custom_trace_table::RapTraceTable;
+pub use custom_trace_table::{build_rap_trace, RapTraceBuilder, RapTraceTable};
 
 use crate::rescue::rescue_128::rescue::{self, STATE_WIDTH};
 
@@ -34,7 +39,15 @@ mod tests;
 
 const CYCLE_LENGTH: usize = 16;
 const NUM_HASH_ROUNDS: usize = 14;
-const TRACE_WIDTH: usize = 4 * 2;
+
+// each row of the trace contains the states of the two hash chains, followed by the seeds
+// absorbed into the first chain and the seeds absorbed into the second chain
+const SEED_COLUMNS: usize = 2 * STATE_WIDTH;
+const TRACE_WIDTH: usize = SEED_COLUMNS + 2 * 2;
+
+/// Seed used to derive the values absorbed into the hash chains, so that consecutive runs of
+/// the example prove the same computation.
+const CHAIN_SEED: [u8; 32] = [0x2a; 32];
 
 // RESCUE SPLIT HASH CHAIN EXAMPLE
 // ================================================================================================
@@ -43,7 +56,7 @@ pub fn get_example(
     options: &ExampleOptions,
     chain_length: usize,
 ) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(42, 4);
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(42, 4)?;
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(RescueRapsExample::<Blake3_192>::new(
@@ -58,6 +71,14 @@ pub fn get_example(
             chain_length,
             options,
         ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(RescueRapsExample::<Blake2s_256>::new(
+            chain_length,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(RescueRapsExample::<Keccak_256>::new(
+            chain_length,
+            options,
+        ))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -79,10 +100,10 @@ impl<H: ElementHasher> RescueRapsExample<H> {
         );
         assert!(chain_length > 2, "chain length must be at least 4");
 
-        let mut seeds = vec![[BaseElement::ZERO; 2]; chain_length];
-        for internal_seed in seeds.iter_mut() {
-            *internal_seed = rand_array();
-        }
+        let seeds: Vec<[BaseElement; 2]> = prng_vector::<BaseElement>(CHAIN_SEED, 2 * chain_length)
+            .chunks(2)
+            .map(|chunk| [chunk[0], chunk[1]])
+            .collect();
         let mut permuted_seeds = seeds[2..].to_vec();
         permuted_seeds.push(seeds[0]);
         permuted_seeds.push(seeds[1]);
@@ -114,7 +135,11 @@ impl<H: ElementHasher> Example for RescueRapsExample<H>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
-    fn prove(&self) -> StarkProof {
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("rescue-raps")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
         // generate the execution trace
         debug!(
             "Generating proof for computing a chain of {} Rescue hashes\n\
@@ -131,13 +156,25 @@ where
         let trace_length = trace.length();
         debug!(
             "Generated execution trace of {} registers and 2^{} steps in {} ms",
-            trace.width(),
+            trace.main_trace().width(),
             log2(trace_length),
             now.elapsed().as_millis()
         );
 
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = RescueRapsProver::<H>::new(self.options.clone());
+
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = RescueRapsProver::<H>::new(self.options.clone());
+        describe_air(&prover, &trace.into_inner())
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
@@ -158,6 +195,16 @@ where
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Returns the permutation argument enforcing that the seeds absorbed into the first hash chain
+/// are a permutation of the seeds absorbed into the second hash chain.
+fn build_permutation_argument() -> PermutationArgument {
+    PermutationArgument::new(
+        vec![SEED_COLUMNS, SEED_COLUMNS + 1],
+        vec![SEED_COLUMNS + 2, SEED_COLUMNS + 3],
+        0,
+    )
+}
+
 fn absorb(state: &mut [BaseElement; STATE_WIDTH], values: &[BaseElement; 2]) {
     state[0] += values[0];
     state[1] += values[1];
@@ -5,10 +5,11 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    apply_rescue_round_parallel, rescue::STATE_WIDTH, BaseElement, ElementHasher, FieldElement,
+    apply_rescue_round_parallel, build_rap_trace, BaseElement, ElementHasher, FieldElement,
     PhantomData, ProofOptions, Prover, PublicInputs, RapTraceTable, RescueRapsAir, Trace,
-    CYCLE_LENGTH, NUM_HASH_ROUNDS,
+    CYCLE_LENGTH, NUM_HASH_ROUNDS, SEED_COLUMNS, TRACE_WIDTH,
 };
+use winterfell::TraceTable;
 
 // RESCUE PROVER
 // ================================================================================================
@@ -34,48 +35,48 @@ impl<H: ElementHasher> RescueRapsProver<H> {
         seeds: &[[BaseElement; 2]],
         permuted_seeds: &[[BaseElement; 2]],
         result: [[BaseElement; 2]; 2],
-    ) -> RapTraceTable<BaseElement> {
+    ) -> RapTraceTable {
         debug_assert_eq!(seeds.len(), permuted_seeds.len());
         // allocate memory to hold the trace table
         let trace_length = seeds.len() * CYCLE_LENGTH;
-        let mut trace = RapTraceTable::new(2 * STATE_WIDTH, trace_length);
-        const END_INCLUSIVE_RANGE: usize = NUM_HASH_ROUNDS - 1;
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
 
         trace.fill(
             |state| {
-                // initialize original chain
-                state[0] = seeds[0][0];
-                state[1] = seeds[0][1];
-                state[2] = BaseElement::ZERO;
-                state[3] = BaseElement::ZERO;
-
-                // initialize permuted chain
-                state[4] = permuted_seeds[0][0];
-                state[5] = permuted_seeds[0][1];
-                state[6] = BaseElement::ZERO;
-                state[7] = BaseElement::ZERO;
+                // both chains start with all-zero states; the first seeds of the chains are
+                // absorbed on the first step
+                state[SEED_COLUMNS..SEED_COLUMNS + 2].copy_from_slice(&seeds[0]);
+                state[SEED_COLUMNS + 2..TRACE_WIDTH].copy_from_slice(&permuted_seeds[0]);
             },
             |step, state| {
                 // execute the transition function for all steps
                 //
-                // for the first 14 steps in every cycle, compute a single round of
-                // Rescue hash; for the remaining 2 rounds, carry over the values
-                // in the first two registers of the two chains to the next step
-                // and insert the additional seeds into the capacity registers
+                // for the first step in every cycle, add the seeds to the first two registers
+                // of the two chains; for the next 14 steps, compute a single round of Rescue
+                // hash; for the last step, carry over the values of the two chains to the next
+                // step and insert the next seeds into the seed columns
                 match step % CYCLE_LENGTH {
-                    0..=END_INCLUSIVE_RANGE => apply_rescue_round_parallel(state, step),
-                    NUM_HASH_ROUNDS => {
+                    0 => {
+                        state[0] += state[SEED_COLUMNS];
+                        state[1] += state[SEED_COLUMNS + 1];
+
+                        state[4] += state[SEED_COLUMNS + 2];
+                        state[5] += state[SEED_COLUMNS + 3];
+
+                        state[SEED_COLUMNS..].fill(BaseElement::ZERO);
+                    }
+                    1..=NUM_HASH_ROUNDS => {
+                        apply_rescue_round_parallel(&mut state[..SEED_COLUMNS], step - 1)
+                    }
+                    _ => {
                         let idx = step / CYCLE_LENGTH + 1;
                         // We don't have seeds for the final step once last hashing is done.
                         if idx < seeds.len() {
-                            state[0] += seeds[idx][0];
-                            state[1] += seeds[idx][1];
-
-                            state[4] += permuted_seeds[idx][0];
-                            state[5] += permuted_seeds[idx][1];
+                            state[SEED_COLUMNS..SEED_COLUMNS + 2].copy_from_slice(&seeds[idx]);
+                            state[SEED_COLUMNS + 2..TRACE_WIDTH]
+                                .copy_from_slice(&permuted_seeds[idx]);
                         }
                     }
-                    _ => {}
                 };
             },
         );
@@ -86,7 +87,9 @@ impl<H: ElementHasher> RescueRapsProver<H> {
         debug_assert_eq!(trace.get(4, trace_length - 1), result[1][0]);
         debug_assert_eq!(trace.get(5, trace_length - 1), result[1][1]);
 
-        trace
+        // the auxiliary trace segment is built by the prover once the random elements for it
+        // are drawn
+        build_rap_trace(trace)
     }
 }
 
@@ -96,7 +99,7 @@ where
 {
     type BaseField = BaseElement;
     type Air = RescueRapsAir;
-    type Trace = RapTraceTable<BaseElement>;
+    type Trace = RapTraceTable;
     type HashFn = H;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
@@ -4,8 +4,15 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use super::{
+    compute_permuted_hash_chains, Blake3_256, PublicInputs, RescueRapsAir, RescueRapsProver,
+};
+use rand_utils::rand_array;
+use winterfell::{
+    math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
+    Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, FieldExtension,
+    ProofOptions, Prover, TraceInfo,
+};
 
 #[test]
 fn rescue_test_basic_proof_verification() {
@@ -34,6 +41,65 @@ fn rescue_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(rescue_eg);
 }
 
+#[test]
+fn rescue_test_proof_size_breakdown() {
+    // the proof contains queries for both the main and the auxiliary trace segments
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(
+        128,
+        build_options(false),
+    ));
+    crate::tests::test_proof_size_breakdown(rescue_eg);
+}
+
+#[test]
+fn rescue_test_proof_size_estimate() {
+    let rescue_eg = Box::new(super::RescueRapsExample::<Blake3_256>::new(
+        128,
+        build_options(false),
+    ));
+    crate::tests::test_proof_size_estimate(rescue_eg);
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn rescue_test_not_a_permutation() {
+    // the second chain absorbs the same seeds as the first one, except for a single seed which
+    // is replaced with a different value; in debug mode the prover catches this when validating
+    // the trace
+    let seeds: Vec<[BaseElement; 2]> = (0..8).map(|_| rand_array()).collect();
+    let mut permuted_seeds = seeds.clone();
+    permuted_seeds.swap(0, 5);
+    permuted_seeds[3] = rand_array();
+    let result = compute_permuted_hash_chains(&seeds, &permuted_seeds);
+
+    let prover = RescueRapsProver::<Blake3_256>::new(build_options(false));
+    let trace = prover.build_trace(&seeds, &permuted_seeds, result);
+    if let Ok(proof) = prover.prove(trace) {
+        let pub_inputs = PublicInputs { result };
+        assert!(winterfell::verify::<RescueRapsAir, Blake3_256>(proof, pub_inputs).is_err());
+    }
+}
+
+#[test]
+fn rescue_test_altered_aux_rand_absorb_data() {
+    let seeds: Vec<[BaseElement; 2]> = (0..8).map(|_| rand_array()).collect();
+    let mut permuted_seeds = seeds.clone();
+    permuted_seeds.swap(0, 5);
+    let result = compute_permuted_hash_chains(&seeds, &permuted_seeds);
+
+    let prover = RescueRapsProver::<Blake3_256>::new(build_options(false));
+    let trace = prover.build_trace(&seeds, &permuted_seeds, result);
+    let proof = prover.prove(trace).unwrap();
+
+    let pub_inputs = PublicInputs { result };
+    assert!(winterfell::verify::<RescueRapsAir, Blake3_256>(proof.clone(), pub_inputs).is_ok());
+
+    // the same proof must be rejected when the data absorbed into the public coin before
+    // drawing the randomness for the permutation argument is different
+    let pub_inputs = PublicInputs { result };
+    assert!(winterfell::verify::<AlteredDataAir, Blake3_256>(proof, pub_inputs).is_err());
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
@@ -42,3 +108,70 @@ fn build_options(use_extension_field: bool) -> ProofOptions {
     };
     ProofOptions::new(28, 8, 0, extension, 4, 256)
 }
+
+/// Rescue RAPs AIR which absorbs altered data into the public coin before the randomness for the
+/// permutation argument is drawn.
+struct AlteredDataAir(RescueRapsAir);
+
+impl Air for AlteredDataAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        Self(RescueRapsAir::new(trace_info, pub_inputs, options))
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        self.0.context()
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result)
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        self.0.evaluate_aux_transition(
+            main_frame,
+            aux_frame,
+            periodic_values,
+            aux_rand_elements,
+            result,
+        )
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        self.0.get_assertions()
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        self.0.get_aux_assertions(aux_rand_elements)
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        self.0.get_periodic_column_values()
+    }
+
+    fn get_aux_rand_absorb_data(&self) -> Vec<u8> {
+        let mut data = self.0.get_aux_rand_absorb_data();
+        data[0] ^= 1;
+        data
+    }
+}
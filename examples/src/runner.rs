@@ -0,0 +1,126 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{proof_file, run_sweep_with_registry, ExampleOptions, ExampleRegistry, ExampleType};
+use log::debug;
+use std::time::Instant;
+use winterfell::StarkProof;
+
+// EXAMPLE RUNNER
+// ================================================================================================
+
+/// Runs the example selected by `options` using the examples available in the specified
+/// `registry`, and logs the results.
+///
+/// Depending on `options`, this generates and verifies a proof of the computation described by
+/// the example, runs a sweep over proof parameters, describes the AIR of the example, or verifies
+/// a proof read from a file. If the `list` subcommand was selected, the names of all examples in
+/// the registry are printed instead.
+///
+/// # Panics
+/// Panics if the example could not be instantiated, or if the proof could not be written to the
+/// output file.
+pub fn run(registry: &ExampleRegistry, options: &ExampleOptions) {
+    if let ExampleType::List = options.example {
+        for (name, about) in registry.examples() {
+            println!("{name:<20}{about}");
+        }
+        return;
+    }
+
+    debug!("============================================================");
+
+    // generate proofs for each of the sweep points from a single execution trace if requested
+    if !options.sweep.is_empty() {
+        let report = run_sweep_with_registry(registry, &options.example, options)
+            .expect("The sweep failed.");
+        debug!(
+            "Trace shared by {} sweep points built in {} ms",
+            report.points.len(),
+            report.trace_time.as_millis()
+        );
+        for point in report.points.iter() {
+            debug!("{}", point);
+        }
+        debug!("============================================================");
+        return;
+    }
+
+    // instantiate and prepare the example
+    let example = registry
+        .get_example(&options.example, options)
+        .expect("The example failed to initialize.");
+
+    let example = example.as_ref();
+
+    // describe the AIR of the example without generating a proof if requested
+    if options.describe {
+        debug!("{}", example.describe());
+        debug!("============================================================");
+        return;
+    }
+
+    // verify a previously generated proof if a proof file was provided
+    if let Some(path) = options.verify.as_ref() {
+        let now = Instant::now();
+        match example.verify_from_file(path) {
+            Ok(_) => debug!(
+                "Proof from {} verified in {:.1} ms",
+                path.display(),
+                now.elapsed().as_micros() as f64 / 1000f64
+            ),
+            Err(err) => debug!("Failed to verify proof from {}: {}", path.display(), err),
+        }
+        debug!("============================================================");
+        return;
+    }
+
+    // generate proof
+    let now = Instant::now();
+    let proof = example.prove();
+    debug!(
+        "---------------------\nProof generated in {} ms",
+        now.elapsed().as_millis()
+    );
+
+    let proof_bytes = proof.to_bytes();
+    debug!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
+    let fri_options = proof.options().to_fri_options();
+    debug!(
+        "FRI folding factor: {}, max remainder size: {}",
+        fri_options.folding_factor(),
+        fri_options.max_remainder_size()
+    );
+    debug!("Proof size breakdown (bytes):\n{}", proof.size_breakdown());
+    let security_level = options.get_proof_security_level(&proof);
+    debug!("Proof security: {} bits", security_level);
+    #[cfg(feature = "std")]
+    debug!(
+        "Proof hash: {}",
+        hex::encode(blake3::hash(&proof_bytes).as_bytes())
+    );
+
+    // write the proof into the output file, if one was provided
+    if let Some(path) = options.output.as_ref() {
+        proof_file::write_proof(path, &example.descriptor(), &proof)
+            .expect("failed to write proof file");
+        debug!("Proof written to {}", path.display());
+    }
+
+    // verify the proof
+    debug!("---------------------");
+    let parsed_proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert_eq!(proof, parsed_proof);
+    let now = Instant::now();
+    match example.verify(proof) {
+        Ok(_) => debug!(
+            "Proof verified in {:.1} ms",
+            now.elapsed().as_micros() as f64 / 1000f64
+        ),
+        Err(msg) => debug!("Failed to verify proof: {}", msg),
+    }
+    debug!("============================================================");
+}
@@ -0,0 +1,328 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    signature::offset_point, Message, ACC, ADD_G, DBL, DBL_SLOPE, DIGEST_SIZE, E_ACC, E_BIT,
+    E_LIMBS, G_SLOPE, HASH, LIMB_BITS, OUT, PK, PK_SLOPE, SIG_CYCLE_LENGTH as SIG_CYCLE_LEN, S_BIT,
+    TRACE_WIDTH, T_X,
+};
+use crate::utils::{
+    are_equal, ecc,
+    ecc::{EXT_DEGREE, POINT_WIDTH},
+    is_binary, not,
+    rp64_256::{
+        self as rescue, CAPACITY_RANGE, CYCLE_LENGTH as HASH_CYCLE_LEN, DIGEST_RANGE, RATE_RANGE,
+        STATE_WIDTH as HASH_STATE_WIDTH,
+    },
+    EvaluationResult,
+};
+use winterfell::{
+    math::{curves::cheetah::AffinePoint, fields::f64::BaseElement, FieldElement, ToElements},
+    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, Serializable, TraceInfo,
+    TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of elements absorbed by the hasher computing the challenge: the message digest
+/// followed by the x coordinate of the final accumulator value.
+const NUM_HASHED_ELEMENTS: usize = DIGEST_SIZE + EXT_DEGREE;
+
+/// Number of elements of x(T) absorbed into the hasher state together with the message; the
+/// remaining elements are absorbed after the first permutation.
+const NUM_INIT_ELEMENTS: usize = RATE_RANGE.end - RATE_RANGE.start - DIGEST_SIZE;
+
+// SCHNORR SIGNATURE AIR
+// ================================================================================================
+
+#[derive(Clone, Serializable, ToElements)]
+pub struct PublicInputs {
+    pub pub_keys: Vec<[BaseElement; POINT_WIDTH]>,
+    pub message: Message,
+}
+
+pub struct SchnorrAir {
+    context: AirContext<BaseElement>,
+    pub_keys: Vec<[BaseElement; POINT_WIDTH]>,
+    message: Message,
+    generator: [BaseElement; POINT_WIDTH],
+    offset: [BaseElement; POINT_WIDTH],
+}
+
+impl Air for SchnorrAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PublicInputs;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
+        let cycle = || vec![SIG_CYCLE_LEN];
+
+        // point doubling and conditional point additions
+        let mut degrees = vec![TransitionConstraintDegree::new(2); 3 * EXT_DEGREE];
+        degrees.resize(9 * EXT_DEGREE, TransitionConstraintDegree::new(3));
+        // s and e bits are binary
+        degrees.push(TransitionConstraintDegree::new(2));
+        degrees.push(TransitionConstraintDegree::new(2));
+        // accumulator, public key, x(T) and challenge limbs are copied over within a cycle
+        let num_copied_columns = 2 * POINT_WIDTH + EXT_DEGREE + DIGEST_SIZE;
+        for _ in 0..num_copied_columns {
+            degrees.push(TransitionConstraintDegree::with_cycles(1, cycle()));
+        }
+        // challenge limb recomposition and checks against the limbs
+        degrees.push(TransitionConstraintDegree::with_cycles(1, cycle()));
+        degrees.push(TransitionConstraintDegree::with_cycles(1, cycle()));
+        degrees.push(TransitionConstraintDegree::with_cycles(1, cycle()));
+        // final accumulator value is equal to T
+        for _ in 0..EXT_DEGREE {
+            degrees.push(TransitionConstraintDegree::with_cycles(1, cycle()));
+        }
+        // hasher state is initialized with x(T)
+        for _ in 0..NUM_INIT_ELEMENTS {
+            degrees.push(TransitionConstraintDegree::with_cycles(1, cycle()));
+        }
+        // hash rounds and absorption of the last elements
+        for _ in 0..HASH_STATE_WIDTH {
+            degrees.push(TransitionConstraintDegree::with_cycles(7, cycle()));
+        }
+        // hash result is equal to the challenge limbs
+        for _ in 0..DIGEST_SIZE {
+            degrees.push(TransitionConstraintDegree::with_cycles(1, cycle()));
+        }
+
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let num_assertions = 2 * POINT_WIDTH + CAPACITY_RANGE.len() + DIGEST_SIZE;
+        SchnorrAir {
+            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            pub_keys: pub_inputs.pub_keys,
+            message: pub_inputs.message,
+            generator: ecc::point_to_elements(&AffinePoint::generator()),
+            offset: ecc::point_to_elements(&offset_point()),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        debug_assert_eq!(TRACE_WIDTH, current.len());
+        debug_assert_eq!(TRACE_WIDTH, next.len());
+
+        // split periodic values into flags and Rescue round constants
+        let first_step_flag = periodic_values[0];
+        let last_step_flag = periodic_values[1];
+        let limb_end_flags = [
+            last_step_flag,
+            periodic_values[4],
+            periodic_values[3],
+            periodic_values[2],
+        ];
+        let hash_round_flag = periodic_values[5];
+        let hash_absorb_flag = periodic_values[6];
+        let hash_digest_flag = periodic_values[7];
+        let ark = &periodic_values[8..];
+
+        // --- double-and-add step ----------------------------------------------------------------
+        // DBL = 2 * ACC
+        ecc::enforce_point_doubling(
+            &mut result[..3 * EXT_DEGREE],
+            &current[ACC..ACC + POINT_WIDTH],
+            &current[DBL_SLOPE..DBL_SLOPE + EXT_DEGREE],
+            &current[DBL..DBL + POINT_WIDTH],
+        );
+
+        // ADD_G = DBL + s_bit * G
+        let generator = self.generator.map(E::from);
+        ecc::enforce_point_addition(
+            &mut result[3 * EXT_DEGREE..6 * EXT_DEGREE],
+            &current[DBL..DBL + POINT_WIDTH],
+            &generator,
+            &current[G_SLOPE..G_SLOPE + EXT_DEGREE],
+            &current[ADD_G..ADD_G + POINT_WIDTH],
+            current[S_BIT],
+        );
+
+        // OUT = ADD_G + e_bit * PK
+        ecc::enforce_point_addition(
+            &mut result[6 * EXT_DEGREE..9 * EXT_DEGREE],
+            &current[ADD_G..ADD_G + POINT_WIDTH],
+            &current[PK..PK + POINT_WIDTH],
+            &current[PK_SLOPE..PK_SLOPE + EXT_DEGREE],
+            &current[OUT..OUT + POINT_WIDTH],
+            current[E_BIT],
+        );
+
+        let mut idx = 9 * EXT_DEGREE;
+        result[idx] = is_binary(current[S_BIT]);
+        result[idx + 1] = is_binary(current[E_BIT]);
+        idx += 2;
+
+        // --- values copied over within a cycle --------------------------------------------------
+        // the output of this step becomes the accumulator of the next step; the public key,
+        // x(T) and the challenge limbs remain the same for the entire cycle
+        let copy_flag = not(last_step_flag);
+        for i in 0..POINT_WIDTH {
+            result.agg_constraint(idx, copy_flag, are_equal(next[ACC + i], current[OUT + i]));
+            idx += 1;
+        }
+        let copied_columns = (PK..PK + POINT_WIDTH)
+            .chain(T_X..T_X + EXT_DEGREE)
+            .chain(E_LIMBS..E_LIMBS + DIGEST_SIZE);
+        for i in copied_columns {
+            result.agg_constraint(idx, copy_flag, are_equal(next[i], current[i]));
+            idx += 1;
+        }
+
+        // --- challenge decomposition ------------------------------------------------------------
+        // e bits are processed starting with the most significant one, and are accumulated into
+        // the current limb; the accumulator is reset after the last bit of each limb
+        let limb_end_flag = limb_end_flags.iter().fold(E::ZERO, |acc, &f| acc + f);
+        let two = E::from(2u32);
+        let expected = not(limb_end_flag) * current[E_ACC] * two + next[E_BIT];
+        result[idx] = are_equal(next[E_ACC], expected);
+        // the accumulator on the first step of a cycle contains just the first e bit
+        result[idx + 1] = first_step_flag * are_equal(current[E_ACC], current[E_BIT]);
+        // at the last bit of each limb, the accumulator must be equal to the limb
+        for (i, &flag) in limb_end_flags.iter().enumerate() {
+            result.agg_constraint(
+                idx + 2,
+                flag,
+                are_equal(current[E_ACC], current[E_LIMBS + i]),
+            );
+        }
+        idx += 3;
+
+        // --- final accumulator value ------------------------------------------------------------
+        for i in 0..EXT_DEGREE {
+            result[idx] = last_step_flag * are_equal(current[OUT + i], current[T_X + i]);
+            idx += 1;
+        }
+
+        // --- challenge computation --------------------------------------------------------------
+        // on the first step of a cycle, the rate portion of the hasher state is initialized with
+        // the message (enforced via assertions) followed by the first 4 elements of x(T)
+        for i in 0..NUM_INIT_ELEMENTS {
+            let state_idx = HASH + RATE_RANGE.start + DIGEST_SIZE + i;
+            result[idx] = first_step_flag * are_equal(current[state_idx], current[T_X + i]);
+            idx += 1;
+        }
+
+        // the permutation is applied on steps 0..7 and 8..15; in between, the remaining elements
+        // of x(T) are absorbed into the rate portion of the state
+        let hash_current = &current[HASH..HASH + HASH_STATE_WIDTH];
+        let hash_next = &next[HASH..HASH + HASH_STATE_WIDTH];
+        let hash_result = &mut result[idx..idx + HASH_STATE_WIDTH];
+        rescue::enforce_round(hash_result, hash_current, hash_next, ark, hash_round_flag);
+        let num_absorbed_elements = EXT_DEGREE - NUM_INIT_ELEMENTS;
+        for i in 0..HASH_STATE_WIDTH {
+            let absorbed =
+                if (RATE_RANGE.start..RATE_RANGE.start + num_absorbed_elements).contains(&i) {
+                    current[T_X + NUM_INIT_ELEMENTS + i - RATE_RANGE.start]
+                } else {
+                    E::ZERO
+                };
+            let expected = hash_current[i] + absorbed;
+            hash_result.agg_constraint(i, hash_absorb_flag, are_equal(hash_next[i], expected));
+        }
+        idx += HASH_STATE_WIDTH;
+
+        // after the second permutation, the digest must be equal to the challenge limbs
+        for i in 0..DIGEST_SIZE {
+            let digest = current[HASH + DIGEST_RANGE.start + i];
+            result[idx] = hash_digest_flag * are_equal(digest, current[E_LIMBS + i]);
+            idx += 1;
+        }
+
+        debug_assert_eq!(idx, result.len());
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let mut assertions = Vec::new();
+
+        // the accumulator starts at the offset point on the first step of every cycle
+        for (i, &value) in self.offset.iter().enumerate() {
+            assertions.push(Assertion::periodic(ACC + i, 0, SIG_CYCLE_LEN, value));
+        }
+
+        // public keys are set on the first step of every cycle
+        for i in 0..POINT_WIDTH {
+            let values = self.pub_keys.iter().map(|pk| pk[i]).collect();
+            assertions.push(Assertion::sequence(PK + i, 0, SIG_CYCLE_LEN, values));
+        }
+
+        // the capacity portion of the hasher state contains the number of hashed elements, and
+        // the first part of the rate portion contains the message
+        let num_elements = BaseElement::new(NUM_HASHED_ELEMENTS as u64);
+        for i in CAPACITY_RANGE {
+            let value = if i == CAPACITY_RANGE.start {
+                num_elements
+            } else {
+                BaseElement::ZERO
+            };
+            assertions.push(Assertion::periodic(HASH + i, 0, SIG_CYCLE_LEN, value));
+        }
+        for (i, &value) in self.message.iter().enumerate() {
+            let column = HASH + RATE_RANGE.start + i;
+            assertions.push(Assertion::periodic(column, 0, SIG_CYCLE_LEN, value));
+        }
+
+        assertions
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut result = vec![];
+
+        // flags marking the first and the last step of a cycle
+        result.push(build_mask(&[0]));
+        result.push(build_mask(&[SIG_CYCLE_LEN - 1]));
+
+        // flags marking the last bits of limbs 3, 2 and 1 of the challenge; the last bit of
+        // limb 0 is processed on the last step of a cycle
+        for i in 1..DIGEST_SIZE {
+            result.push(build_mask(&[i * LIMB_BITS - 1]));
+        }
+
+        // flags marking the steps on which hash rounds are applied, the step on which the
+        // remaining elements are absorbed, and the step on which the digest is available
+        let rounds: Vec<usize> = (0..rescue::NUM_ROUNDS)
+            .chain(HASH_CYCLE_LEN..HASH_CYCLE_LEN + rescue::NUM_ROUNDS)
+            .collect();
+        result.push(build_mask(&rounds));
+        result.push(build_mask(&[HASH_CYCLE_LEN - 1]));
+        result.push(build_mask(&[2 * HASH_CYCLE_LEN - 1]));
+
+        // round constants for two consecutive permutations at the start of every cycle
+        for column in rescue::get_round_constants() {
+            let mut values = column.repeat(2);
+            values.resize(SIG_CYCLE_LEN, BaseElement::ZERO);
+            result.push(values);
+        }
+
+        result
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a periodic column of cycle length equal to the signature cycle which is set to ONE
+/// on the specified steps and to ZERO on all other steps.
+fn build_mask(steps: &[usize]) -> Vec<BaseElement> {
+    let mut mask = vec![BaseElement::ZERO; SIG_CYCLE_LEN];
+    for &step in steps {
+        mask[step] = BaseElement::ONE;
+    }
+    mask
+}
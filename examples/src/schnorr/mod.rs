@@ -0,0 +1,254 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::{
+    ecc::{EXT_DEGREE, POINT_WIDTH},
+    rp64_256::STATE_WIDTH as HASH_STATE_WIDTH,
+};
+use crate::{describe_air, Example, ExampleDescriptor, ExampleOptions, ExampleTrace, HashFunction};
+use core::marker::PhantomData;
+use log::debug;
+use std::{convert::TryInto, time::Instant};
+use winterfell::{
+    crypto::{ElementHasher, Hasher},
+    introspection::AirInfo,
+    math::{fields::f64::BaseElement, log2},
+    ProofOptions, Prover, StarkProof, Trace, VerifierError,
+};
+
+mod signature;
+use signature::{Message, PrivateKey, Signature, DIGEST_SIZE, SCALAR_BITS};
+
+mod air;
+use air::{PublicInputs, SchnorrAir};
+
+mod prover;
+use prover::SchnorrProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS AND TYPES
+// ================================================================================================
+
+/// Number of steps needed to verify a single signature: one step per bit of the scalars.
+const SIG_CYCLE_LENGTH: usize = SCALAR_BITS;
+
+/// Number of bits in each limb of the challenge.
+const LIMB_BITS: usize = 64;
+
+// Each row of the trace performs a single step of the double-and-add loop computing
+// OUT = 2 * ACC + s_bit * G + e_bit * PK, and is laid out as follows:
+const ACC: usize = 0; // accumulator point
+const DBL_SLOPE: usize = ACC + POINT_WIDTH; // slope of the tangent at ACC
+const DBL: usize = DBL_SLOPE + EXT_DEGREE; // 2 * ACC
+const S_BIT: usize = DBL + POINT_WIDTH; // current bit of s
+const G_SLOPE: usize = S_BIT + 1; // slope of the line through DBL and G
+const ADD_G: usize = G_SLOPE + EXT_DEGREE; // DBL + s_bit * G
+const E_BIT: usize = ADD_G + POINT_WIDTH; // current bit of e
+const PK_SLOPE: usize = E_BIT + 1; // slope of the line through ADD_G and PK
+const OUT: usize = PK_SLOPE + EXT_DEGREE; // ADD_G + e_bit * PK
+
+// the remaining columns are used to compute and decompose the challenge e:
+const PK: usize = OUT + POINT_WIDTH; // public key, copied over for the entire cycle
+const T_X: usize = PK + POINT_WIDTH; // x coordinate of the final accumulator value
+const E_LIMBS: usize = T_X + EXT_DEGREE; // challenge limbs, copied over for the entire cycle
+const E_ACC: usize = E_LIMBS + DIGEST_SIZE; // running recomposition of the current limb of e
+const HASH: usize = E_ACC + 1; // hasher state computing e = hash(m || T_X)
+
+const TRACE_WIDTH: usize = HASH + HASH_STATE_WIDTH;
+
+type Blake3_192 = winterfell::crypto::hashers::Blake3_192<BaseElement>;
+type Blake3_256 = winterfell::crypto::hashers::Blake3_256<BaseElement>;
+type Sha3_256 = winterfell::crypto::hashers::Sha3_256<BaseElement>;
+#[allow(non_camel_case_types)]
+type Blake2s_256 = winterfell::crypto::hashers::Blake2s_256<BaseElement>;
+#[allow(non_camel_case_types)]
+type Keccak_256 = winterfell::crypto::hashers::Keccak_256<BaseElement>;
+type Rp64_256 = winterfell::crypto::hashers::Rp64_256;
+type Poseidon64_256 = winterfell::crypto::hashers::Poseidon64_256;
+
+// SCHNORR MULTI-KEY SIGNATURE EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_signatures: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8)?;
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(SchnorrExample::<Blake3_192>::new(
+            num_signatures,
+            options,
+        ))),
+        HashFunction::Blake3_256 => Ok(Box::new(SchnorrExample::<Blake3_256>::new(
+            num_signatures,
+            options,
+        ))),
+        HashFunction::Sha3_256 => Ok(Box::new(SchnorrExample::<Sha3_256>::new(
+            num_signatures,
+            options,
+        ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(SchnorrExample::<Blake2s_256>::new(
+            num_signatures,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(SchnorrExample::<Keccak_256>::new(
+            num_signatures,
+            options,
+        ))),
+        HashFunction::Rp64_256 => Ok(Box::new(SchnorrExample::<Rp64_256>::new(
+            num_signatures,
+            options,
+        ))),
+        HashFunction::Poseidon64_256 => Ok(Box::new(SchnorrExample::<Poseidon64_256>::new(
+            num_signatures,
+            options,
+        ))),
+    }
+}
+
+pub struct SchnorrExample<H: ElementHasher> {
+    options: ProofOptions,
+    pub_keys: Vec<[BaseElement; POINT_WIDTH]>,
+    message: Message,
+    signatures: Vec<Signature>,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SchnorrExample<H> {
+    pub fn new(num_signatures: usize, options: ProofOptions) -> Self {
+        assert!(
+            num_signatures.is_power_of_two(),
+            "number of signatures must be a power of 2"
+        );
+        // generate private/public key pairs for the specified number of signatures
+        let mut private_keys = Vec::with_capacity(num_signatures);
+        let mut pub_keys = Vec::with_capacity(num_signatures);
+        let now = Instant::now();
+        for i in 0..num_signatures {
+            let private_key = PrivateKey::from_seed([i as u8; 32]);
+            pub_keys.push(private_key.pub_key().to_elements());
+            private_keys.push(private_key);
+        }
+        debug!(
+            "Generated {} private-public key pairs in {} ms",
+            num_signatures,
+            now.elapsed().as_millis()
+        );
+
+        // sign the message with all private keys
+        let now = Instant::now();
+        let message: Message = Rp64_256::hash("test message".as_bytes())
+            .as_elements()
+            .try_into()
+            .unwrap();
+        let signatures: Vec<Signature> = private_keys.iter().map(|sk| sk.sign(message)).collect();
+        debug!(
+            "Signed the message {} times in {} ms",
+            num_signatures,
+            now.elapsed().as_millis()
+        );
+
+        // verify signatures
+        let now = Instant::now();
+        for (private_key, signature) in private_keys.iter().zip(&signatures) {
+            let pk = private_key.pub_key();
+            assert!(pk.verify(message, signature));
+        }
+        debug!(
+            "Verified {} signatures in {} ms",
+            num_signatures,
+            now.elapsed().as_millis()
+        );
+
+        SchnorrExample {
+            options,
+            pub_keys,
+            message,
+            signatures,
+            _hasher: PhantomData,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for SchnorrExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("schnorr")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
+        // generate the execution trace
+        debug!(
+            "Generating proof for verifying {} Schnorr signatures \n\
+            ---------------------",
+            self.signatures.len(),
+        );
+
+        // create a prover
+        let prover = SchnorrProver::<H>::new(&self.pub_keys, self.message, self.options.clone());
+
+        let now = Instant::now();
+        let trace = prover.build_trace(&self.signatures);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace.width(),
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = SchnorrProver::<H>::new(&self.pub_keys, self.message, self.options.clone());
+
+        // generate the proof
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = SchnorrProver::<H>::new(&self.pub_keys, self.message, self.options.clone());
+        describe_air(&prover, &trace.into_inner())
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let pub_inputs = PublicInputs {
+            pub_keys: self.pub_keys.clone(),
+            message: self.message,
+        };
+        // the verifier accepts proofs in a field without an extension only if insecure fields were
+        // allowed for this example explicitly
+        winterfell::verify_with_min_field_security::<SchnorrAir, H>(
+            proof,
+            pub_inputs,
+            self.options.min_field_security(),
+        )
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        let mut message = self.message;
+        message[0] += BaseElement::new(1);
+        let pub_inputs = PublicInputs {
+            pub_keys: self.pub_keys.clone(),
+            message,
+        };
+        winterfell::verify_with_min_field_security::<SchnorrAir, H>(
+            proof,
+            pub_inputs,
+            self.options.min_field_security(),
+        )
+    }
+}
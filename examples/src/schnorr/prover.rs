@@ -0,0 +1,249 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    signature::{compute_challenge, offset_point, shifted_commitment},
+    BaseElement, ElementHasher, Message, PhantomData, ProofOptions, Prover, PublicInputs,
+    SchnorrAir, Signature, ACC, ADD_G, DBL, DBL_SLOPE, DIGEST_SIZE, EXT_DEGREE, E_ACC, E_BIT,
+    E_LIMBS, G_SLOPE, HASH, LIMB_BITS, OUT, PK, PK_SLOPE, POINT_WIDTH, SCALAR_BITS,
+    SIG_CYCLE_LENGTH, S_BIT, TRACE_WIDTH, T_X,
+};
+use crate::utils::{
+    ecc,
+    rp64_256::{self as rescue, CAPACITY_RANGE, RATE_RANGE, STATE_WIDTH as HASH_STATE_WIDTH},
+};
+use std::convert::TryInto;
+use winterfell::{
+    math::{curves::cheetah::AffinePoint, FieldElement, StarkField},
+    TraceTable,
+};
+
+#[cfg(feature = "concurrent")]
+use winterfell::iterators::*;
+
+// TYPES AND INTERFACES
+// ================================================================================================
+
+struct SignatureInfo {
+    pub_key: [BaseElement; POINT_WIDTH],
+    message: Message,
+    s: [u8; 32],
+    t_x: [BaseElement; EXT_DEGREE],
+    e: [BaseElement; DIGEST_SIZE],
+}
+
+impl SignatureInfo {
+    /// Returns the bit of s processed on the specified step; bits are processed starting with
+    /// the most significant one.
+    fn s_bit(&self, step: usize) -> BaseElement {
+        let bit_idx = SCALAR_BITS - 1 - step;
+        BaseElement::new(((self.s[bit_idx / 8] >> (bit_idx % 8)) & 1) as u64)
+    }
+
+    /// Returns the bit of e processed on the specified step; bits are processed starting with
+    /// the most significant one.
+    fn e_bit(&self, step: usize) -> BaseElement {
+        let bit_idx = SCALAR_BITS - 1 - step;
+        let limb = self.e[bit_idx / LIMB_BITS].as_int();
+        BaseElement::new((limb >> (bit_idx % LIMB_BITS)) & 1)
+    }
+}
+
+// SCHNORR PROVER
+// ================================================================================================
+
+pub struct SchnorrProver<H: ElementHasher> {
+    pub_inputs: PublicInputs,
+    options: ProofOptions,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> SchnorrProver<H> {
+    pub fn new(
+        pub_keys: &[[BaseElement; POINT_WIDTH]],
+        message: Message,
+        options: ProofOptions,
+    ) -> Self {
+        let pub_inputs = PublicInputs {
+            pub_keys: pub_keys.to_vec(),
+            message,
+        };
+        Self {
+            pub_inputs,
+            options,
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn build_trace(&self, signatures: &[Signature]) -> TraceTable<BaseElement> {
+        // allocate memory to hold the trace table
+        let trace_length = SIG_CYCLE_LENGTH * signatures.len();
+        let mut trace = TraceTable::new(TRACE_WIDTH, trace_length);
+
+        let generator = ecc::point_to_elements(&AffinePoint::generator());
+        let offset = ecc::point_to_elements(&offset_point());
+
+        trace.fragments(SIG_CYCLE_LENGTH).for_each(|mut sig_trace| {
+            let i = sig_trace.index();
+            let sig_info = build_sig_info(
+                &self.pub_inputs.pub_keys[i],
+                &self.pub_inputs.message,
+                &signatures[i],
+            );
+            sig_trace.fill(
+                |state| {
+                    init_sig_verification_state(&sig_info, &offset, &generator, state);
+                },
+                |step, state| {
+                    update_sig_verification_state(step, &sig_info, &generator, state);
+                },
+            );
+        });
+
+        trace
+    }
+}
+
+impl<H: ElementHasher> Prover for SchnorrProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = SchnorrAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> PublicInputs {
+        self.pub_inputs.clone()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// TRACE INITIALIZATION
+// ================================================================================================
+
+fn init_sig_verification_state(
+    sig_info: &SignatureInfo,
+    offset: &[BaseElement; POINT_WIDTH],
+    generator: &[BaseElement; POINT_WIDTH],
+    state: &mut [BaseElement],
+) {
+    // the accumulator starts at the offset point
+    state[ACC..ACC + POINT_WIDTH].copy_from_slice(offset);
+
+    // values which remain the same for the entire cycle
+    state[PK..PK + POINT_WIDTH].copy_from_slice(&sig_info.pub_key);
+    state[T_X..T_X + EXT_DEGREE].copy_from_slice(&sig_info.t_x);
+    state[E_LIMBS..E_LIMBS + DIGEST_SIZE].copy_from_slice(&sig_info.e);
+
+    // the hasher state is initialized with the number of hashed elements, the message and the
+    // first elements of x(T)
+    let hash_state = &mut state[HASH..HASH + HASH_STATE_WIDTH];
+    hash_state[CAPACITY_RANGE.start] = BaseElement::new((DIGEST_SIZE + EXT_DEGREE) as u64);
+    let (message_part, t_x_part) = hash_state[RATE_RANGE].split_at_mut(DIGEST_SIZE);
+    message_part.copy_from_slice(&sig_info.message);
+    t_x_part.copy_from_slice(&sig_info.t_x[..t_x_part.len()]);
+
+    apply_double_and_add_step(0, sig_info, generator, state);
+}
+
+// TRANSITION FUNCTION
+// ================================================================================================
+
+fn update_sig_verification_state(
+    step: usize,
+    sig_info: &SignatureInfo,
+    generator: &[BaseElement; POINT_WIDTH],
+    state: &mut [BaseElement],
+) {
+    // the output of the last step becomes the new accumulator
+    state.copy_within(OUT..OUT + POINT_WIDTH, ACC);
+    apply_double_and_add_step(step + 1, sig_info, generator, state);
+
+    // the challenge is computed over the first 16 steps of the cycle: two permutations with the
+    // remaining elements of x(T) absorbed in between
+    let hash_state = &mut state[HASH..HASH + HASH_STATE_WIDTH];
+    let hash_state: &mut [BaseElement; HASH_STATE_WIDTH] = hash_state.try_into().unwrap();
+    let num_init_elements = RATE_RANGE.len() - DIGEST_SIZE;
+    if step < rescue::NUM_ROUNDS {
+        rescue::apply_round(hash_state, step);
+    } else if step == rescue::CYCLE_LENGTH - 1 {
+        for (i, &element) in sig_info.t_x[num_init_elements..].iter().enumerate() {
+            hash_state[RATE_RANGE.start + i] += element;
+        }
+    } else if step < rescue::CYCLE_LENGTH + rescue::NUM_ROUNDS {
+        rescue::apply_round(hash_state, step - rescue::CYCLE_LENGTH);
+    }
+}
+
+/// Populates the double-and-add and challenge decomposition columns for the specified step
+/// from the accumulator in the provided state.
+fn apply_double_and_add_step(
+    step: usize,
+    sig_info: &SignatureInfo,
+    generator: &[BaseElement; POINT_WIDTH],
+    state: &mut [BaseElement],
+) {
+    // DBL = 2 * ACC
+    let (dbl, dbl_slope) = ecc::double(&state[ACC..ACC + POINT_WIDTH]);
+    state[DBL_SLOPE..DBL_SLOPE + EXT_DEGREE].copy_from_slice(&dbl_slope);
+    state[DBL..DBL + POINT_WIDTH].copy_from_slice(&dbl);
+
+    // ADD_G = DBL + s_bit * G
+    let s_bit = sig_info.s_bit(step);
+    let (add_g, g_slope) = conditional_add(&dbl, generator, s_bit);
+    state[S_BIT] = s_bit;
+    state[G_SLOPE..G_SLOPE + EXT_DEGREE].copy_from_slice(&g_slope);
+    state[ADD_G..ADD_G + POINT_WIDTH].copy_from_slice(&add_g);
+
+    // OUT = ADD_G + e_bit * PK
+    let e_bit = sig_info.e_bit(step);
+    let (out, pk_slope) = conditional_add(&add_g, &sig_info.pub_key, e_bit);
+    state[E_BIT] = e_bit;
+    state[PK_SLOPE..PK_SLOPE + EXT_DEGREE].copy_from_slice(&pk_slope);
+    state[OUT..OUT + POINT_WIDTH].copy_from_slice(&out);
+
+    // accumulate e bits into the current limb, starting a new limb every 64 steps
+    state[E_ACC] = if step % LIMB_BITS == 0 {
+        e_bit
+    } else {
+        state[E_ACC].double() + e_bit
+    };
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_sig_info(
+    pub_key: &[BaseElement; POINT_WIDTH],
+    message: &Message,
+    signature: &Signature,
+) -> SignatureInfo {
+    SignatureInfo {
+        pub_key: *pub_key,
+        message: *message,
+        s: signature.s.to_bytes(),
+        t_x: shifted_commitment(&signature.r).get_x(),
+        e: compute_challenge(message, &signature.r),
+    }
+}
+
+/// Returns p + flag * q together with the slope of the line through p and q; when flag is
+/// zero, the slope is set to zero.
+fn conditional_add(
+    p: &[BaseElement; POINT_WIDTH],
+    q: &[BaseElement; POINT_WIDTH],
+    flag: BaseElement,
+) -> ([BaseElement; POINT_WIDTH], [BaseElement; EXT_DEGREE]) {
+    if flag == BaseElement::ONE {
+        ecc::add_points(p, q)
+    } else {
+        (*p, [BaseElement::ZERO; EXT_DEGREE])
+    }
+}
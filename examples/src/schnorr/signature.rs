@@ -0,0 +1,151 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::utils::ecc::{point_to_elements, POINT_WIDTH};
+use rand_utils::prng_vector;
+use std::convert::TryInto;
+use winterfell::{
+    crypto::{hashers::Rp64_256, ElementHasher},
+    math::{
+        curves::cheetah::{AffinePoint, ProjectivePoint, Scalar},
+        fields::f64::BaseElement,
+        StarkField,
+    },
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of bits in the scalars processed by the signature verification circuit.
+pub const SCALAR_BITS: usize = 256;
+
+/// Number of field elements in a message digest and in a challenge.
+pub const DIGEST_SIZE: usize = 4;
+
+/// Seed used to derive the offset point of the verification circuit.
+const OFFSET_SEED: [u8; 32] = [0x5c; 32];
+
+// TYPES AND INTERFACES
+// ================================================================================================
+
+pub type Message = [BaseElement; DIGEST_SIZE];
+
+pub struct PrivateKey {
+    sec_key: Scalar,
+    pub_key: PublicKey,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(AffinePoint);
+
+/// Schnorr signature defined as a pair (R, s), where R = k * G for a secret nonce k, and
+/// s = k - e * sk for a challenge e = hash(m || x(R + C)).
+///
+/// The point C = 2^256 * H is derived from the offset point H (see [offset_point()]). Mixing C
+/// into the challenge ensures that the commitment to R can be recovered from the final value
+/// of an accumulator which starts at H, and thus never passes through the identity.
+#[derive(Debug, Clone, Copy)]
+pub struct Signature {
+    pub r: AffinePoint,
+    pub s: Scalar,
+}
+
+// PRIVATE KEY IMPLEMENTATION
+// ================================================================================================
+
+impl PrivateKey {
+    /// Returns a private key generated from the specified `seed`.
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        let sec_key = prng_vector::<Scalar>(seed, 1)[0];
+        // the secret key is multiplied in constant time by the `*` operator
+        let pub_key = PublicKey(AffinePoint::generator() * sec_key);
+        PrivateKey { sec_key, pub_key }
+    }
+
+    /// Returns a public key corresponding to this private key.
+    pub fn pub_key(&self) -> PublicKey {
+        self.pub_key
+    }
+
+    /// Signs the specified `message` with this private key.
+    ///
+    /// The nonce is derived deterministically from the private key and the message.
+    pub fn sign(&self, message: Message) -> Signature {
+        let mut nonce_seed = self.sec_key.to_bytes().to_vec();
+        for element in message.iter() {
+            nonce_seed.extend_from_slice(&element.as_int().to_le_bytes());
+        }
+        let k = prng_vector::<Scalar>(*blake3::hash(&nonce_seed).as_bytes(), 1)[0];
+
+        // the nonce is secret, and thus, is multiplied in constant time
+        let r = AffinePoint::generator() * k;
+        let e = challenge_to_scalar(&compute_challenge(&message, &r));
+        let s = k - e * self.sec_key;
+
+        Signature { r, s }
+    }
+}
+
+// PUBLIC KEY IMPLEMENTATION
+// ================================================================================================
+
+impl PublicKey {
+    /// Returns true if the specified signature was generated by signing the specified message
+    /// with a private key corresponding to this public key.
+    pub fn verify(&self, message: Message, sig: &Signature) -> bool {
+        let e = challenge_to_scalar(&compute_challenge(&message, &sig.r));
+        // all scalars used in verification are public, and thus, can be multiplied in variable
+        // time
+        let r = AffinePoint::generator().multiply_double(&self.0, &sig.s.to_bytes(), &e.to_bytes());
+        r == sig.r
+    }
+
+    /// Returns the affine coordinates of this public key.
+    pub fn to_elements(&self) -> [BaseElement; POINT_WIDTH] {
+        point_to_elements(&self.0)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the offset point H used as the initial value of the accumulator in the signature
+/// verification circuit.
+///
+/// Note that the discrete logarithm of H with respect to the generator is publicly known.
+pub fn offset_point() -> AffinePoint {
+    AffinePoint::generator().multiply_vartime(&prng_vector::<Scalar>(OFFSET_SEED, 1)[0].to_bytes())
+}
+
+/// Returns the point R + 2^256 * H, which is equal to the final value of the accumulator in the
+/// signature verification circuit.
+pub fn shifted_commitment(r: &AffinePoint) -> AffinePoint {
+    let mut result = ProjectivePoint::from(offset_point());
+    for _ in 0..SCALAR_BITS {
+        result = result.double();
+    }
+    AffinePoint::from(result + *r)
+}
+
+/// Returns the challenge e = hash(m || x(R + C)) as a list of field elements, where R is the
+/// commitment of a signature and C = 2^256 * H.
+pub fn compute_challenge(message: &Message, r: &AffinePoint) -> [BaseElement; DIGEST_SIZE] {
+    let mut elements = message.to_vec();
+    elements.extend_from_slice(&shifted_commitment(r).get_x());
+    Rp64_256::hash_elements(&elements)
+        .as_elements()
+        .try_into()
+        .unwrap()
+}
+
+/// Interprets a challenge as a 256-bit integer in little-endian limb order and reduces it
+/// modulo the order of the curve.
+pub fn challenge_to_scalar(challenge: &[BaseElement; DIGEST_SIZE]) -> Scalar {
+    let two_pow_64 = Scalar::from(1u128 << 64);
+    challenge.iter().rev().fold(Scalar::zero(), |acc, e| {
+        acc * two_pow_64 + Scalar::from(e.as_int())
+    })
+}
@@ -0,0 +1,45 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Rp64_256;
+use winterfell::{FieldExtension, ProofOptions};
+
+#[test]
+fn schnorr_test_basic_proof_verification() {
+    let schnorr = Box::new(super::SchnorrExample::<Rp64_256>::new(
+        2,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(schnorr);
+}
+
+#[test]
+fn schnorr_test_basic_proof_verification_extension() {
+    let schnorr = Box::new(super::SchnorrExample::<Rp64_256>::new(
+        1,
+        build_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(schnorr);
+}
+
+#[test]
+fn schnorr_test_basic_proof_verification_fail() {
+    let schnorr = Box::new(super::SchnorrExample::<Rp64_256>::new(
+        2,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(schnorr);
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    if use_extension_field {
+        ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 256)
+    } else {
+        // the 64-bit field does not provide adequate security without an extension, and thus,
+        // the field security check must be disabled explicitly
+        ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256).allow_insecure_field()
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{ExampleOptions, ExampleRegistry, ExampleType};
+use core::{fmt, str::FromStr};
+use std::time::{Duration, Instant};
+
+// SWEEP RUNNER
+// ================================================================================================
+
+/// Instantiates the specified example, and generates proofs of the computation for each of the
+/// sweep points specified in `options`.
+///
+/// The execution trace is built only once, by the example instantiated with `options`; it is
+/// then proven by instances of the example in which the number of queries and the blowup factor
+/// are replaced with the values of each sweep point. All proofs are verified by the instance
+/// which built the trace, since the inputs of some examples are generated randomly whenever the
+/// example is instantiated.
+///
+/// # Errors
+/// Returns an error if no sweep points were specified, if the example could not be instantiated
+/// with the options of any of the sweep points, or if any of the generated proofs failed to
+/// verify.
+pub fn run_sweep(example: &ExampleType, options: &ExampleOptions) -> Result<SweepReport, String> {
+    run_sweep_with_registry(&ExampleRegistry::default(), example, options)
+}
+
+/// Same as [run_sweep()], but instantiates the example from the specified `registry`, which may
+/// contain examples defined outside of this crate.
+///
+/// # Errors
+/// Returns an error if the example has not been registered, or in any of the cases listed for
+/// [run_sweep()].
+pub fn run_sweep_with_registry(
+    registry: &ExampleRegistry,
+    example: &ExampleType,
+    options: &ExampleOptions,
+) -> Result<SweepReport, String> {
+    if options.sweep.is_empty() {
+        return Err("no sweep points were specified".to_string());
+    }
+
+    let base = registry.get_example(example, options)?;
+    let now = Instant::now();
+    let trace = base.build_trace();
+    let trace_time = now.elapsed();
+
+    let mut points = Vec::with_capacity(options.sweep.len());
+    for &point in options.sweep.iter() {
+        let point_options = options.with_sweep_point(point);
+        let example = registry.get_example(example, &point_options)?;
+
+        let now = Instant::now();
+        let proof = example.prove_with_trace(&trace);
+        let prove_time = now.elapsed();
+
+        let proof_size = proof.to_bytes().len();
+        let security_level = point_options.get_proof_security_level(&proof);
+        let now = Instant::now();
+        base.verify(proof)
+            .map_err(|err| format!("failed to verify proof for sweep point {point}: {err}"))?;
+        let verify_time = now.elapsed();
+
+        points.push(SweepPointReport {
+            point,
+            proof_size,
+            security_level,
+            prove_time,
+            verify_time,
+        });
+    }
+
+    Ok(SweepReport { trace_time, points })
+}
+
+// SWEEP POINT
+// ================================================================================================
+
+/// A combination of proof parameters for which a proof is generated during a sweep.
+///
+/// On the command line, sweep points are written as `queries:blowup` (e.g., `28:8`).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct SweepPoint {
+    /// Number of queries to include in the proof.
+    pub num_queries: usize,
+    /// Blowup factor for low degree extension.
+    pub blowup_factor: usize,
+}
+
+impl fmt::Display for SweepPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.num_queries, self.blowup_factor)
+    }
+}
+
+impl FromStr for SweepPoint {
+    type Err = String;
+
+    /// Parses a sweep point from a string of the form `queries:blowup`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let err = || format!("'{value}' is not a valid sweep point; expected queries:blowup");
+        let (num_queries, blowup_factor) = value.split_once(':').ok_or_else(err)?;
+        Ok(Self {
+            num_queries: num_queries.trim().parse().map_err(|_| err())?,
+            blowup_factor: blowup_factor.trim().parse().map_err(|_| err())?,
+        })
+    }
+}
+
+// SWEEP REPORTS
+// ================================================================================================
+
+/// Results of a sweep over proof parameters for a single execution trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepReport {
+    /// Time spent on building the execution trace shared by all sweep points.
+    pub trace_time: Duration,
+    /// Results for each of the sweep points, in the order in which they were specified.
+    pub points: Vec<SweepPointReport>,
+}
+
+/// Results of generating a proof for a single sweep point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepPointReport {
+    /// Parameters for which the proof was generated.
+    pub point: SweepPoint,
+    /// Size of the serialized proof in bytes.
+    pub proof_size: usize,
+    /// Conjectured security level of the proof in bits.
+    pub security_level: usize,
+    /// Time spent on generating the proof from the shared execution trace.
+    pub prove_time: Duration,
+    /// Time spent on verifying the proof.
+    pub verify_time: Duration,
+}
+
+impl fmt::Display for SweepPointReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "queries: {:>3}, blowup: {:>3} | proof size: {:>7.1} KB, security: {:>3} bits, \
+            proving: {:>6} ms, verification: {:>6.1} ms",
+            self.point.num_queries,
+            self.point.blowup_factor,
+            self.proof_size as f64 / 1024f64,
+            self.security_level,
+            self.prove_time.as_millis(),
+            self.verify_time.as_micros() as f64 / 1000f64
+        )
+    }
+}
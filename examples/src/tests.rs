@@ -4,7 +4,22 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::Example;
+use crate::{
+    proof_file::{FIELD_ID_OFFSET, HASH_FN_OFFSET, VERSION, VERSION_OFFSET},
+    Example, HashFunction, ProofFileError,
+};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter, Cursor},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use winterfell::{
+    ByteReader, Deserializable, ReadAdapter, Serializable, SliceReader, StarkProof, WriteAdapter,
+};
+
+/// Used to give each proof file written by the tests a unique name.
+static NEXT_PROOF_FILE_ID: AtomicUsize = AtomicUsize::new(0);
 
 pub fn test_basic_proof_verification(e: Box<dyn Example>) {
     let proof = e.prove();
@@ -16,3 +31,669 @@ pub fn test_basic_proof_verification_fail(e: Box<dyn Example>) {
     let verified = e.verify_with_wrong_inputs(proof);
     assert!(verified.is_err());
 }
+
+pub fn test_proof_serialization_roundtrip(e: Box<dyn Example>) {
+    let proof = e.prove();
+    let proof_bytes = proof.to_bytes();
+
+    // streaming the proof into a writer should produce the same bytes as to_bytes()
+    let mut target = WriteAdapter::new(Cursor::new(Vec::new()));
+    proof.write_into(&mut target);
+    let streamed_bytes = target.finish().unwrap().into_inner();
+    assert_eq!(proof_bytes, streamed_bytes);
+
+    // the proof should be readable from both a slice and a reader
+    let mut source = SliceReader::new(&proof_bytes);
+    assert_eq!(proof, StarkProof::read_from(&mut source).unwrap());
+    let mut source = ReadAdapter::new(Cursor::new(&streamed_bytes));
+    assert_eq!(proof, StarkProof::read_from(&mut source).unwrap());
+
+    // write the proof into a file and read it back
+    let path = next_proof_file_path();
+    let mut target = WriteAdapter::new(BufWriter::new(File::create(&path).unwrap()));
+    proof.write_into(&mut target);
+    target.finish().unwrap();
+
+    let mut source = ReadAdapter::new(BufReader::new(File::open(&path).unwrap()));
+    let read_proof = StarkProof::read_from(&mut source);
+    assert!(!source.has_more_bytes());
+    std::fs::remove_file(&path).unwrap();
+
+    let read_proof = read_proof.unwrap();
+    assert_eq!(proof, read_proof);
+    assert!(e.verify(read_proof).is_ok());
+}
+
+pub fn test_proof_size_breakdown(e: Box<dyn Example>) {
+    let proof = e.prove();
+    let breakdown = proof.size_breakdown();
+
+    // the sizes of all components must add up to the size of the serialized proof
+    assert_eq!(proof.to_bytes().len(), breakdown.total());
+    let num_commitments = proof.trace_layout().num_trace_commitments();
+    assert_eq!(num_commitments, breakdown.trace_queries.len());
+    assert_eq!(proof.fri_proof.num_layers(), breakdown.fri_layers.len());
+    let fri_layers_size: usize = breakdown.fri_layers.iter().sum();
+    assert!(fri_layers_size + breakdown.fri_remainder <= breakdown.fri_proof);
+
+    // the breakdown is listed line by line, followed by the total
+    let table = breakdown.to_string();
+    let last_line = table.lines().last().unwrap();
+    assert!(last_line.ends_with(&breakdown.total().to_string()));
+}
+
+pub fn test_proof_size_estimate(e: Box<dyn Example>) {
+    // the estimate is computed from the AIR alone, and must be within 10% of the actual size
+    let estimate = e.describe().estimated_proof_size;
+    let proof_size = e.prove().to_bytes().len();
+    assert!(
+        estimate.abs_diff(proof_size) * 10 <= proof_size,
+        "estimated {estimate} bytes, but the proof has {proof_size} bytes"
+    );
+}
+
+pub fn test_proof_file_roundtrip(e: Box<dyn Example>) {
+    let path = next_proof_file_path();
+    e.prove_to_file(&path).unwrap();
+    let file_bytes = std::fs::read(&path).unwrap();
+
+    // the proof written into the file should be verifiable
+    assert!(e.verify_from_file(&path).is_ok());
+
+    // the header should describe the example
+    let descriptor = e.descriptor();
+    assert_eq!(VERSION, file_bytes[VERSION_OFFSET]);
+    assert_eq!(descriptor.field_id, file_bytes[FIELD_ID_OFFSET]);
+    assert_eq!(descriptor.hash_fn as u8, file_bytes[HASH_FN_OFFSET]);
+
+    // a file with a wrong format version should be rejected
+    let mut tampered = file_bytes.clone();
+    tampered[VERSION_OFFSET] = VERSION + 1;
+    let err = verify_tampered_file(e.as_ref(), &path, &tampered);
+    assert!(matches!(err, ProofFileError::UnsupportedVersion(v) if v == VERSION + 1));
+    assert!(err.to_string().contains("version"));
+
+    // a file with a wrong base field id should be rejected
+    let mut tampered = file_bytes.clone();
+    tampered[FIELD_ID_OFFSET] ^= 1;
+    let err = verify_tampered_file(e.as_ref(), &path, &tampered);
+    assert!(matches!(
+        err,
+        ProofFileError::FieldMismatch { expected, found }
+            if expected == descriptor.field_id && found == descriptor.field_id ^ 1
+    ));
+    assert!(err.to_string().contains("base field"));
+
+    // a file with a wrong hash function id should be rejected
+    let other_hash_fn = if descriptor.hash_fn == HashFunction::Sha3_256 {
+        HashFunction::Blake3_256
+    } else {
+        HashFunction::Sha3_256
+    };
+    let mut tampered = file_bytes.clone();
+    tampered[HASH_FN_OFFSET] = other_hash_fn as u8;
+    let err = verify_tampered_file(e.as_ref(), &path, &tampered);
+    assert!(matches!(err, ProofFileError::HashFunctionMismatch { .. }));
+
+    // a file for a different example should be rejected
+    let mut tampered = file_bytes.clone();
+    let name_pos = HASH_FN_OFFSET + 2;
+    tampered[name_pos] = tampered[name_pos].to_ascii_uppercase();
+    let err = verify_tampered_file(e.as_ref(), &path, &tampered);
+    assert!(matches!(err, ProofFileError::ExampleMismatch { .. }));
+    assert!(err.to_string().contains(descriptor.name));
+
+    // a file without the magic bytes should be rejected
+    let err = verify_tampered_file(e.as_ref(), &path, &file_bytes[1..]);
+    assert!(matches!(err, ProofFileError::InvalidMagic));
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// Returns a unique path in the temporary directory for a proof file written by the tests.
+fn next_proof_file_path() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "winterfell_proof_{}_{}.bin",
+        std::process::id(),
+        NEXT_PROOF_FILE_ID.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+/// Overwrites the proof file at the specified path with the specified bytes, and returns the error
+/// resulting from verifying the proof in the file.
+fn verify_tampered_file(e: &dyn Example, path: &Path, bytes: &[u8]) -> ProofFileError {
+    std::fs::write(path, bytes).unwrap();
+    e.verify_from_file(path).unwrap_err()
+}
+
+// BENCHMARK REPORTS
+// ================================================================================================
+
+#[test]
+fn benchmark_report() {
+    use crate::{run_benchmark, BenchReport, ExampleOptions};
+    use structopt::StructOpt;
+
+    let args = ["winterfell", "--grinding", "0", "fib", "-n", "64"];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let report = run_benchmark(&options.example, &options).unwrap();
+    assert_eq!("fib", report.name);
+    assert_eq!(128, report.field_id);
+    assert_eq!(HashFunction::Blake3_256, report.hash_fn);
+    assert!(report.proof_size > 0);
+
+    // the proof size matches the size of a proof generated directly
+    let example = crate::get_example(&options.example, &options).unwrap();
+    assert_eq!(example.prove().to_bytes().len(), report.proof_size);
+
+    // peak memory is not tracked unless the tracking allocator is installed
+    assert_eq!(None, report.peak_memory);
+    let json = report.to_json();
+    assert!(json.starts_with("{\"name\":\"fib\",\"field_id\":128,\"hash_fn\":\"Blake3_256\""));
+    assert!(json.ends_with(&format!(
+        "\"proof_size\":{},\"peak_memory\":null}}",
+        report.proof_size
+    )));
+
+    // durations are serialized with microsecond precision
+    let report = BenchReport {
+        peak_memory: Some(1 << 20),
+        ..report
+    };
+    let bytes = report.to_bytes();
+    let parsed = BenchReport::read_from(&mut SliceReader::new(&bytes)).unwrap();
+    assert_eq!(report.trace_time.as_micros(), parsed.trace_time.as_micros());
+    assert_eq!(report.prove_time.as_micros(), parsed.prove_time.as_micros());
+    assert_eq!(
+        report.verify_time.as_micros(),
+        parsed.verify_time.as_micros()
+    );
+    let parsed = BenchReport {
+        trace_time: report.trace_time,
+        prove_time: report.prove_time,
+        verify_time: report.verify_time,
+        ..parsed
+    };
+    assert_eq!(report, parsed);
+}
+
+#[test]
+#[should_panic(expected = "execution trace is not of type")]
+fn prove_trace_of_other_example() {
+    use crate::ExampleOptions;
+    use structopt::StructOpt;
+
+    let options = ExampleOptions::from_iter_safe(["winterfell", "fib", "-n", "64"]).unwrap();
+    let fib = crate::get_example(&options.example, &options).unwrap();
+    let options = ExampleOptions::from_iter_safe(["winterfell", "fib-small", "-n", "64"]).unwrap();
+    let fib_small = crate::get_example(&options.example, &options).unwrap();
+
+    // the trace of the Fibonacci example in f64 field cannot be proven by the f128 example
+    fib.prove_trace(fib_small.build_trace());
+}
+
+// EXAMPLE OPTIONS
+// ================================================================================================
+
+#[test]
+fn parse_hash_function_and_field_extension() {
+    use crate::ExampleOptions;
+    use structopt::StructOpt;
+    use winterfell::{math::fields::f128::BaseElement, FieldExtension};
+
+    let args = [
+        "winterfell",
+        "--hash",
+        "keccak_256",
+        "--extension",
+        "quadratic",
+        "fib",
+    ];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let (proof_options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8).unwrap();
+    assert_eq!(HashFunction::Keccak_256, hash_fn);
+    assert_eq!(FieldExtension::Quadratic, proof_options.field_extension());
+
+    // previous names of the options, and extension degrees, are still accepted
+    let args = [
+        "winterfell",
+        "--hash_fn",
+        "sha3_256",
+        "--field_extension",
+        "2",
+        "fib",
+    ];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let (proof_options, hash_fn) = options.to_proof_options::<BaseElement>(28, 8).unwrap();
+    assert_eq!(HashFunction::Sha3_256, hash_fn);
+    assert_eq!(FieldExtension::Quadratic, proof_options.field_extension());
+
+    // names of all hash functions can be parsed back
+    for hash_fn in (0..).map_while(HashFunction::from_id) {
+        assert_eq!(Ok(hash_fn), hash_fn.to_string().parse());
+    }
+
+    // errors for unknown values list the accepted values
+    let err = ExampleOptions::from_iter_safe(["winterfell", "--hash", "md5", "fib"]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("accepted values are blake3_192, blake3_256, sha3_256"));
+    let args = ["winterfell", "--extension", "quartic", "fib"];
+    let err = ExampleOptions::from_iter_safe(args).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("accepted values are none, quadratic, cubic, and sextic"));
+}
+
+#[test]
+fn default_field_extension() {
+    use crate::ExampleOptions;
+    use structopt::StructOpt;
+    use winterfell::{
+        math::fields::{f128, f64},
+        FieldExtension, ProofOptions,
+    };
+
+    // unless specified explicitly, an extension is used only for fields smaller than 128 bits
+    let options = ExampleOptions::from_iter_safe(["winterfell", "fib-small"]).unwrap();
+    let (proof_options, _) = options.to_proof_options::<f64::BaseElement>(28, 8).unwrap();
+    assert_eq!(FieldExtension::Quadratic, proof_options.field_extension());
+    let (proof_options, _) = options
+        .to_proof_options::<f128::BaseElement>(28, 8)
+        .unwrap();
+    assert_eq!(FieldExtension::None, proof_options.field_extension());
+    assert_eq!(
+        ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+        proof_options.min_field_security()
+    );
+
+    // small fields without an extension must be allowed explicitly
+    let args = ["winterfell", "-e", "none", "fib-small", "-n", "64"];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let (proof_options, _) = options.to_proof_options::<f64::BaseElement>(28, 8).unwrap();
+    assert_eq!(FieldExtension::None, proof_options.field_extension());
+    assert_eq!(
+        ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+        proof_options.min_field_security()
+    );
+
+    let args = [
+        "winterfell",
+        "-e",
+        "none",
+        "--allow-insecure-field",
+        "--grinding",
+        "0",
+        "fib-small",
+        "-n",
+        "64",
+    ];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let (proof_options, _) = options.to_proof_options::<f64::BaseElement>(28, 8).unwrap();
+    assert_eq!(0, proof_options.min_field_security());
+    let example = crate::get_example(&options.example, &options).unwrap();
+    assert!(example.verify(example.prove()).is_ok());
+}
+
+#[test]
+fn prove_with_each_field_extension() {
+    use crate::ExampleOptions;
+    use structopt::StructOpt;
+
+    // cubic and sextic extensions are supported only for the 64-bit field
+    let runs = [
+        ("fib", "none"),
+        ("fib", "quadratic"),
+        ("fib-small", "cubic"),
+        ("fib-small", "sextic"),
+    ];
+    for (name, extension) in runs {
+        let args = [
+            "winterfell",
+            "--grinding",
+            "0",
+            "--extension",
+            extension,
+            name,
+            "-n",
+            "64",
+        ];
+        let options = ExampleOptions::from_iter_safe(args).unwrap();
+        let example = crate::get_example(&options.example, &options).unwrap();
+        let proof = example.prove();
+        assert_eq!(extension, proof.options().field_extension().to_string());
+        assert!(example.verify(proof).is_ok());
+    }
+
+    // the combination of an extension and a base field is validated when the example is built
+    let args = ["winterfell", "--extension", "cubic", "fib", "-n", "64"];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let err = crate::get_example(&options.example, &options)
+        .err()
+        .unwrap();
+    assert_eq!(
+        "cubic field extension is not supported for the 128-bit base field of this example",
+        err
+    );
+}
+
+// PROOF OPTION SWEEPS
+// ================================================================================================
+
+#[test]
+fn prove_with_shared_trace() {
+    use crate::ExampleOptions;
+    use structopt::StructOpt;
+
+    let args = ["winterfell", "--grinding", "0", "fib", "-n", "64"];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let example = crate::get_example(&options.example, &options).unwrap();
+
+    // the same trace can be proven any number of times, and the proofs are the same as the
+    // proof generated from a freshly built trace
+    let trace = example.build_trace();
+    let proof = example.prove_with_trace(&trace);
+    assert_eq!(proof, example.prove_with_trace(&trace));
+    assert_eq!(proof, example.prove_trace(trace));
+    assert_eq!(proof, example.prove());
+}
+
+#[test]
+fn sweep_fib() {
+    use crate::{run_sweep, ExampleOptions, SweepPoint};
+    use structopt::StructOpt;
+
+    let args = [
+        "winterfell",
+        "--grinding",
+        "0",
+        "--sweep",
+        "28:8,42:4",
+        "--sweep",
+        "16:16",
+        "fib",
+        "-n",
+        "64",
+    ];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let points = [
+        SweepPoint {
+            num_queries: 28,
+            blowup_factor: 8,
+        },
+        SweepPoint {
+            num_queries: 42,
+            blowup_factor: 4,
+        },
+        SweepPoint {
+            num_queries: 16,
+            blowup_factor: 16,
+        },
+    ];
+    assert_eq!(points.to_vec(), options.sweep);
+
+    // each sweep point is reported in order, and matches a proof generated directly with the
+    // parameters of the point
+    let report = run_sweep(&options.example, &options).unwrap();
+    assert_eq!(points.len(), report.points.len());
+    for (&point, point_report) in points.iter().zip(report.points.iter()) {
+        let point_options = options.with_sweep_point(point);
+        let example = crate::get_example(&options.example, &point_options).unwrap();
+        let proof = example.prove();
+        assert_eq!(point.num_queries, proof.options().num_queries());
+        assert_eq!(point.blowup_factor, proof.options().blowup_factor());
+
+        assert_eq!(point, point_report.point);
+        assert_eq!(proof.to_bytes().len(), point_report.proof_size);
+        let security_level = point_options.get_proof_security_level(&proof);
+        assert_eq!(security_level, point_report.security_level);
+    }
+}
+
+#[test]
+fn parse_sweep_points() {
+    use crate::{run_sweep, ExampleOptions, SweepPoint};
+    use structopt::StructOpt;
+
+    assert_eq!(
+        Ok(SweepPoint {
+            num_queries: 28,
+            blowup_factor: 8
+        }),
+        "28:8".parse()
+    );
+    assert_eq!("28:8", "28:8".parse::<SweepPoint>().unwrap().to_string());
+
+    let err = ExampleOptions::from_iter_safe(["winterfell", "--sweep", "28", "fib"]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("'28' is not a valid sweep point; expected queries:blowup"));
+    let args = ["winterfell", "--sweep", "28:x", "fib"];
+    assert!(ExampleOptions::from_iter_safe(args).is_err());
+
+    // a sweep does not generate a single proof which could be written into a file
+    let args = [
+        "winterfell",
+        "--sweep",
+        "28:8",
+        "--output",
+        "proof.bin",
+        "fib",
+    ];
+    assert!(ExampleOptions::from_iter_safe(args).is_err());
+
+    // a sweep requires at least one sweep point
+    let options = ExampleOptions::from_iter_safe(["winterfell", "fib", "-n", "64"]).unwrap();
+    assert_eq!(
+        Err("no sweep points were specified".to_string()),
+        run_sweep(&options.example, &options)
+    );
+}
+
+// EXAMPLE REGISTRY
+// ================================================================================================
+
+#[test]
+fn run_registered_example() {
+    use crate::{ExampleOptions, ExampleRegistry, ExampleType};
+    use structopt::{
+        clap::{App, Arg, ArgMatches},
+        StructOpt,
+    };
+
+    fn dummy_args<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+        app.arg(
+            Arg::with_name("sequence_length")
+                .short("n")
+                .takes_value(true)
+                .default_value("1024"),
+        )
+    }
+
+    fn build_dummy(
+        options: &ExampleOptions,
+        args: &ArgMatches,
+    ) -> Result<Box<dyn Example>, String> {
+        let sequence_length = args
+            .value_of("sequence_length")
+            .unwrap()
+            .parse()
+            .map_err(|_| "invalid sequence length".to_string())?;
+        crate::fibonacci::fib2::get_example(options, sequence_length)
+    }
+
+    let mut registry = ExampleRegistry::default();
+    registry.register_example("dummy", "Dummy example", dummy_args, build_dummy);
+    assert_eq!(Some(("dummy", "Dummy example")), registry.examples().last());
+
+    // the example is selected by a subcommand with its name and instantiated with its arguments
+    let args = ["winterfell", "--grinding", "0", "dummy", "-n", "64"];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    assert!(matches!(options.example, ExampleType::External(_)));
+    assert_eq!("dummy", options.example.name());
+
+    let example = registry.get_example(&options.example, &options).unwrap();
+    let proof = example.prove();
+    assert_eq!(64 / 2, proof.trace_length());
+    assert_eq!(0, proof.options().grinding_factor());
+    assert!(example.verify(proof).is_ok());
+
+    // arguments of the example are validated when the example is instantiated
+    let args = ["winterfell", "dummy", "-n", "x"];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    let err = registry
+        .get_example(&options.example, &options)
+        .err()
+        .unwrap();
+    assert_eq!("invalid sequence length", err);
+    let args = ["winterfell", "dummy", "-k", "2"];
+    let options = ExampleOptions::from_iter_safe(args).unwrap();
+    assert!(registry.get_example(&options.example, &options).is_err());
+
+    // examples which have not been registered cannot be instantiated
+    let options = ExampleOptions::from_iter_safe(["winterfell", "dummy"]).unwrap();
+    let err = crate::get_example(&options.example, &options)
+        .err()
+        .unwrap();
+    assert_eq!("example 'dummy' has not been registered", err);
+}
+
+#[test]
+fn builtin_example_names() {
+    use crate::{ExampleOptions, ExampleRegistry, ExampleType};
+    use structopt::StructOpt;
+
+    // every built-in example is selected by the subcommand under which it is registered
+    let registry = ExampleRegistry::default();
+    for (name, _) in registry.examples() {
+        let options = ExampleOptions::from_iter_safe(["winterfell", name]).unwrap();
+        assert!(!matches!(options.example, ExampleType::External(_)));
+        assert_eq!(name, options.example.name());
+    }
+
+    let options = ExampleOptions::from_iter_safe(["winterfell", "list"]).unwrap();
+    assert!(matches!(options.example, ExampleType::List));
+    assert!(crate::get_example(&options.example, &options).is_err());
+}
+
+#[test]
+#[should_panic(expected = "example name 'fib' is reserved")]
+fn register_builtin_example_name() {
+    use crate::{ExampleOptions, ExampleRegistry};
+    use structopt::clap::{App, ArgMatches};
+
+    fn fib_args<'a>(app: App<'a, 'a>) -> App<'a, 'a> {
+        app
+    }
+
+    fn build_fib(options: &ExampleOptions, _args: &ArgMatches) -> Result<Box<dyn Example>, String> {
+        crate::fibonacci::fib2::get_example(options, 64)
+    }
+
+    // built-in examples are parsed by the options, and thus, their names cannot be reused even
+    // in a registry which does not contain the built-in examples
+    let mut registry = ExampleRegistry::new();
+    registry.register_example("fib", "Fibonacci", fib_args, build_fib);
+}
+
+// TRANSITION CONSTRAINT DEGREES
+// ================================================================================================
+
+/// In debug mode, the prover measures the actual degrees of transition constraints and reports
+/// constraints with degrees which differ from the declared ones.
+#[cfg(debug_assertions)]
+mod transition_degrees {
+    use crate::Blake3_256;
+    use winterfell::{
+        math::{fields::f128::BaseElement, FieldElement},
+        Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, Prover,
+        ProverError, TraceInfo, TraceTable, TransitionConstraintDegree,
+    };
+
+    #[test]
+    fn under_declared_transition_constraint_degree() {
+        let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256);
+        let prover = SquaresProver { options };
+
+        let mut trace = TraceTable::new(2, 64);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::new(3);
+                state[1] = BaseElement::new(5);
+            },
+            |_, state| {
+                state[0] = state[0].square();
+                state[1] = state[1].square();
+            },
+        );
+
+        let err = prover.prove(trace).unwrap_err();
+        assert!(matches!(
+            err,
+            ProverError::MismatchedTransitionConstraintDegree(1, _, _)
+        ));
+        assert!(err.to_string().contains("transition constraint 1 "));
+    }
+
+    /// AIR with two columns squared on each step; the degree of the second constraint is
+    /// deliberately declared as 1 instead of 2.
+    struct SquaresAir {
+        context: AirContext<BaseElement>,
+    }
+
+    impl Air for SquaresAir {
+        type BaseField = BaseElement;
+        type PublicInputs = ();
+
+        fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+            let degrees = vec![
+                TransitionConstraintDegree::new(2),
+                TransitionConstraintDegree::new(1),
+            ];
+            Self {
+                context: AirContext::new(trace_info, degrees, 2, options),
+            }
+        }
+
+        fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+            &self,
+            frame: &EvaluationFrame<E>,
+            _periodic_values: &[E],
+            result: &mut [E],
+        ) {
+            let current = frame.current();
+            let next = frame.next();
+
+            result[0] = next[0] - current[0].square();
+            result[1] = next[1] - current[1].square();
+        }
+
+        fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+            vec![
+                Assertion::single(0, 0, BaseElement::new(3)),
+                Assertion::single(1, 0, BaseElement::new(5)),
+            ]
+        }
+
+        fn context(&self) -> &AirContext<Self::BaseField> {
+            &self.context
+        }
+    }
+
+    struct SquaresProver {
+        options: ProofOptions,
+    }
+
+    impl Prover for SquaresProver {
+        type BaseField = BaseElement;
+        type Air = SquaresAir;
+        type Trace = TraceTable<BaseElement>;
+        type HashFn = Blake3_256;
+
+        fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+        fn options(&self) -> &ProofOptions {
+            &self.options
+        }
+    }
+}
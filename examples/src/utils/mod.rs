@@ -4,84 +4,78 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use core::ops::Range;
-use winterfell::{
-    math::{FieldElement, StarkField},
-    Trace, TraceTable,
-};
+use winterfell::{math::StarkField, Trace};
 
-pub mod rescue;
+pub use gadgets::{ecc, gmimc, rescue, rp64_256, sponge};
+pub use winterfell::gadgets::{are_equal, is_binary, is_zero, not, select, EvaluationResult};
 
-// CONSTRAINT EVALUATION HELPERS
-// ================================================================================================
-
-/// Returns zero only when a == b.
-pub fn are_equal<E: FieldElement>(a: E, b: E) -> E {
-    a - b
-}
-
-/// Returns zero only when a == zero.
-pub fn is_zero<E: FieldElement>(a: E) -> E {
-    a
-}
-
-/// Returns zero only when a = zero || a == one.
-pub fn is_binary<E: FieldElement>(a: E) -> E {
-    a * a - a
-}
-
-/// Return zero when a == one, and one when a == zero;
-/// assumes that a is a binary value.
-pub fn not<E: FieldElement>(a: E) -> E {
-    E::ONE - a
-}
-
-// TRAIT TO SIMPLIFY CONSTRAINT AGGREGATION
-// ================================================================================================
-
-pub trait EvaluationResult<E> {
-    fn agg_constraint(&mut self, index: usize, flag: E, value: E);
-}
-
-impl<E: FieldElement> EvaluationResult<E> for [E] {
-    fn agg_constraint(&mut self, index: usize, flag: E, value: E) {
-        self[index] += flag * value;
-    }
-}
-
-impl<E: FieldElement> EvaluationResult<E> for Vec<E> {
-    fn agg_constraint(&mut self, index: usize, flag: E, value: E) {
-        self[index] += flag * value;
-    }
-}
+#[cfg(test)]
+mod tests;
 
 // OTHER FUNCTIONS
 // ================================================================================================
 
-/// Prints out an execution trace.
-pub fn print_trace<E: StarkField>(
-    trace: &TraceTable<E>,
+/// Prints out rows of an execution trace.
+///
+/// A row is printed only if its index minus `offset` is a multiple of `multiples_of`, and only
+/// values in the specified `columns` of the main trace segment are printed. Values are printed
+/// as canonical integers in the specified `radix` (see [format_element()]).
+///
+/// # Panics
+/// Panics if `radix` is not between 2 and 36, or if any of the `columns` is out of bounds.
+pub fn print_trace<T: Trace>(
+    trace: &T,
     multiples_of: usize,
     offset: usize,
-    range: Range<usize>,
+    columns: &[usize],
+    radix: u32,
 ) {
-    let trace_width = trace.width();
-
-    let mut state = vec![E::ZERO; trace_width];
     for i in 0..trace.length() {
         if (i.wrapping_sub(offset)) % multiples_of != 0 {
             continue;
         }
-        trace.read_row_into(i, &mut state);
-        println!(
-            "{}\t{:?}",
-            i,
-            state[range.clone()]
-                .iter()
-                .map(|v| v.to_repr())
-                .collect::<Vec<E::Representation>>()
-        );
+        let row = columns
+            .iter()
+            .map(|&column| format_element(trace.get_column(column)[i], radix))
+            .collect::<Vec<_>>();
+        println!("{}\t[{}]", i, row.join(", "));
+    }
+}
+
+/// Returns the canonical integer representation of the specified field element formatted in the
+/// specified `radix`.
+///
+/// # Panics
+/// Panics if `radix` is not between 2 and 36, or if elements of the field `E` are wider than
+/// 128 bits.
+pub fn format_element<E: StarkField>(value: E, radix: u32) -> String {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be between 2 and 36, but was {radix}"
+    );
+
+    // canonical serialization of a base field element is the little-endian encoding of its
+    // integer representation
+    let bytes = value.to_bytes();
+    assert!(
+        bytes.len() <= 16,
+        "field elements wider than 128 bits are not supported"
+    );
+    let mut value = bytes
+        .iter()
+        .rev()
+        .fold(0u128, |acc, &byte| (acc << 8) | byte as u128);
+
+    let mut digits = Vec::new();
+    loop {
+        let digit = (value % radix as u128) as u32;
+        digits.push(char::from_digit(digit, radix).expect("invalid digit"));
+        value /= radix as u128;
+        if value == 0 {
+            break;
+        }
     }
+    digits.iter().rev().collect()
 }
 
 pub fn print_trace_step<E: StarkField>(trace: &[Vec<E>], step: usize) {
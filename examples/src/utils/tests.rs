@@ -0,0 +1,872 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::Blake3_256;
+use core::marker::PhantomData;
+use winterfell::{
+    build_permutation_column, build_range_lookup_column, build_range_lookup_multiplicities,
+    gadgets::{
+        Gadget, IsZeroGadget, PermutationArgument, RangeCheckGadget, RangeLookupArgument,
+        SelectGadget,
+    },
+    math::{fields::f128::BaseElement, ExtensionOf, FieldElement},
+    Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, FieldExtension, Matrix,
+    MultiSegmentTraceBuilder, MultiSegmentTraceTable, ProofOptions, Prover, ProverError,
+    StarkProof, Trace, TraceInfo, TraceLayout, TraceTable, TransitionConstraintDegree,
+    VerifierError,
+};
+
+const TRACE_LENGTH: usize = 32;
+
+// IS ZERO GADGET
+// ================================================================================================
+
+#[test]
+fn is_zero_gadget_proof_verification() {
+    let trace = build_trace::<IsZeroGadget>();
+    assert!(prove_and_verify::<IsZeroGadget>(trace).is_ok());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn is_zero_gadget_tamper_inverse() {
+    // the input at step 1 is non-zero, and thus its inverse is fully determined
+    let mut trace = build_trace::<IsZeroGadget>();
+    trace.set(2, 1, BaseElement::new(123));
+    assert!(prove_and_verify::<IsZeroGadget>(trace).is_err());
+}
+
+impl TestGadget for IsZeroGadget {
+    const TRACE_WIDTH: usize = 3;
+
+    fn build() -> Self {
+        IsZeroGadget::new(1, 2)
+    }
+
+    fn fill_inputs(step: usize, row: &mut [BaseElement]) {
+        row[1] = BaseElement::new((step % 3) as u128);
+    }
+}
+
+// SELECT GADGET
+// ================================================================================================
+
+#[test]
+fn select_gadget_proof_verification() {
+    let trace = build_trace::<SelectGadget>();
+    assert!(prove_and_verify::<SelectGadget>(trace).is_ok());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn select_gadget_tamper_output() {
+    let mut trace = build_trace::<SelectGadget>();
+    trace.set(4, 3, BaseElement::new(123));
+    assert!(prove_and_verify::<SelectGadget>(trace).is_err());
+}
+
+impl TestGadget for SelectGadget {
+    const TRACE_WIDTH: usize = 5;
+
+    fn build() -> Self {
+        SelectGadget::new(1, 2, 3, 4)
+    }
+
+    fn fill_inputs(step: usize, row: &mut [BaseElement]) {
+        let step = step as u128;
+        row[1] = BaseElement::new(pseudo_random_value(step) % 2);
+        row[2] = BaseElement::new(step);
+        row[3] = BaseElement::new(step * step);
+    }
+}
+
+// RANGE CHECK GADGET
+// ================================================================================================
+
+#[test]
+fn range_check_gadget_proof_verification() {
+    let trace = build_trace::<RangeCheckGadget>();
+    assert!(prove_and_verify::<RangeCheckGadget>(trace).is_ok());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn range_check_gadget_tamper_value() {
+    // 256 does not fit into 8 bits, and thus cannot be recomposed from any binary decomposition
+    let mut trace = build_trace::<RangeCheckGadget>();
+    trace.set(1, 5, BaseElement::new(256));
+    assert!(prove_and_verify::<RangeCheckGadget>(trace).is_err());
+}
+
+impl TestGadget for RangeCheckGadget {
+    const TRACE_WIDTH: usize = 10;
+
+    fn build() -> Self {
+        RangeCheckGadget::new(1, 2, 8)
+    }
+
+    fn fill_inputs(step: usize, row: &mut [BaseElement]) {
+        row[1] = BaseElement::new(pseudo_random_value(step as u128) % 256);
+    }
+}
+
+// GADGET AIR
+// ================================================================================================
+
+/// Describes how a gadget is laid out in the execution trace of [GadgetAir].
+trait TestGadget: Gadget + Send + Sync {
+    const TRACE_WIDTH: usize;
+
+    /// Returns a gadget using columns starting with column 1.
+    fn build() -> Self;
+
+    /// Sets the values in the input columns of the gadget for the specified step.
+    fn fill_inputs(step: usize, row: &mut [BaseElement]);
+}
+
+/// AIR which enforces constraints of a single gadget on all but the last step. The first column
+/// of the trace is a counter incremented by one on each step, and the remaining columns are
+/// occupied by the gadget.
+struct GadgetAir<G: TestGadget> {
+    context: AirContext<BaseElement>,
+    gadget: G,
+}
+
+impl<G: TestGadget> Air for GadgetAir<G> {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let gadget = G::build();
+        let mut degrees = vec![TransitionConstraintDegree::new(1)];
+        degrees.append(&mut gadget.constraint_degrees());
+        let context = AirContext::new(trace_info, degrees, 1, options);
+        Self { context, gadget }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        result[0] = next[0] - (current[0] + E::ONE);
+        self.gadget.evaluate(frame, &mut result[1..]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+struct GadgetProver<G: TestGadget> {
+    options: ProofOptions,
+    _gadget: PhantomData<G>,
+}
+
+impl<G: TestGadget> Prover for GadgetProver<G> {
+    type BaseField = BaseElement;
+    type Air = GadgetAir<G>;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+fn build_trace<G: TestGadget>() -> TraceTable<BaseElement> {
+    let gadget = G::build();
+    let mut trace = TraceTable::new(G::TRACE_WIDTH, TRACE_LENGTH);
+    trace.fill(
+        |state| {
+            G::fill_inputs(0, state);
+            gadget.fill(state);
+        },
+        |step, state| {
+            state[0] += BaseElement::ONE;
+            G::fill_inputs(step + 1, state);
+            gadget.fill(state);
+        },
+    );
+    trace
+}
+
+/// Returns a pseudo-random value for the specified step.
+///
+/// The values are reduced modulo a prime; low bits of values computed modulo a power of two
+/// would repeat with a short period, and the degrees of constraints on these bits would be lower
+/// than expected.
+fn pseudo_random_value(step: u128) -> u128 {
+    ((step + 1) * 40503) % 65521
+}
+
+fn prove_and_verify<G: TestGadget>(trace: TraceTable<BaseElement>) -> Result<(), VerifierError> {
+    let prover = GadgetProver::<G> {
+        options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256),
+        _gadget: PhantomData,
+    };
+    let proof = prover.prove(trace).expect("failed to generate proof");
+    winterfell::verify::<GadgetAir<G>, Blake3_256>(proof, ())
+}
+
+// TWO AUXILIARY SEGMENTS
+// ================================================================================================
+
+#[test]
+fn two_aux_segments_proof_verification() {
+    for extension in [FieldExtension::None, FieldExtension::Quadratic] {
+        let prover = TwoSegmentProver {
+            options: ProofOptions::new(28, 8, 0, extension, 4, 256),
+        };
+        let proof = prover
+            .prove(build_two_segment_trace(false))
+            .expect("failed to generate proof");
+        assert_eq!(2, proof.trace_layout().num_aux_segments());
+        assert_eq!(3, proof.trace_queries.len());
+        assert!(winterfell::verify::<TwoSegmentAir, Blake3_256>(proof, ()).is_ok());
+    }
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn two_aux_segments_not_a_permutation() {
+    let prover = TwoSegmentProver {
+        options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256),
+    };
+    if let Ok(proof) = prover.prove(build_two_segment_trace(true)) {
+        assert!(winterfell::verify::<TwoSegmentAir, Blake3_256>(proof, ()).is_err());
+    }
+}
+
+/// Builds the auxiliary segments of [TwoSegmentAir]: the first segment contains the running
+/// product column of a permutation argument, and the second segment contains a column which
+/// folds the running product using a random element drawn after the first segment has been
+/// committed to.
+struct TwoSegmentTraceBuilder {
+    permutation: PermutationArgument,
+}
+
+impl MultiSegmentTraceBuilder<BaseElement> for TwoSegmentTraceBuilder {
+    fn build_aux_segment<E>(
+        &self,
+        main: &TraceTable<BaseElement>,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = BaseElement>,
+    {
+        match aux_segments.len() {
+            0 => {
+                let column = build_permutation_column(
+                    &self.permutation,
+                    main.main_segment(),
+                    rand_elements.get_segment_elements(0),
+                );
+                Some(Matrix::new(vec![column]))
+            }
+            1 => {
+                let beta = rand_elements.get_segment_elements(1)[0];
+                let products = aux_segments[0].get_column(0);
+                let mut column = Vec::with_capacity(products.len());
+                column.push(E::ZERO);
+                for &product in &products[..products.len() - 1] {
+                    let last = column[column.len() - 1];
+                    column.push(last * beta + product);
+                }
+                Some(Matrix::new(vec![column]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// AIR with one main and two auxiliary trace segments. The first main column is a counter, and
+/// the remaining two main columns are tied together by a permutation argument in the first
+/// auxiliary segment; the second auxiliary segment depends on both the first auxiliary segment
+/// and on its own random element.
+struct TwoSegmentAir {
+    context: AirContext<BaseElement>,
+    permutation: PermutationArgument,
+}
+
+impl Air for TwoSegmentAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let permutation = build_permutation();
+        let main_degrees = vec![TransitionConstraintDegree::new(1)];
+        let aux_degrees = vec![
+            permutation.constraint_degree(),
+            TransitionConstraintDegree::new(1),
+        ];
+        let context =
+            AirContext::new_multi_segment(trace_info, main_degrees, aux_degrees, 1, 3, options);
+        Self {
+            context,
+            permutation,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - (frame.current()[0] + E::ONE);
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let current = aux_frame.current();
+        let next = aux_frame.next();
+        let beta = aux_rand_elements.get_segment_elements(1)[0];
+
+        result[0] = self
+            .permutation
+            .evaluate_transition(main_frame, aux_frame, aux_rand_elements);
+        result[1] = next[1] - (current[1] * beta + current[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        let mut assertions = self.permutation.get_assertions(self.trace_length());
+        assertions.push(Assertion::single(1, 0, E::ZERO));
+        assertions
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+struct TwoSegmentProver {
+    options: ProofOptions,
+}
+
+impl Prover for TwoSegmentProver {
+    type BaseField = BaseElement;
+    type Air = TwoSegmentAir;
+    type Trace = MultiSegmentTraceTable<BaseElement, TwoSegmentTraceBuilder>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+fn build_permutation() -> PermutationArgument {
+    PermutationArgument::new(vec![1], vec![2], 0)
+}
+
+/// Builds a trace in which all but the last rows of the third column are the corresponding rows
+/// of the second column in reverse order; if `tamper` is set, one of the permuted values is
+/// modified so that the columns are no longer permutations of each other.
+fn build_two_segment_trace(
+    tamper: bool,
+) -> MultiSegmentTraceTable<BaseElement, TwoSegmentTraceBuilder> {
+    let counter = (0..TRACE_LENGTH as u128)
+        .map(BaseElement::new)
+        .collect::<Vec<_>>();
+    let values = (0..TRACE_LENGTH as u128)
+        .map(|i| BaseElement::new(i * i + 1))
+        .collect::<Vec<_>>();
+    let mut permuted = values[..TRACE_LENGTH - 1].to_vec();
+    permuted.reverse();
+    permuted.push(values[TRACE_LENGTH - 1]);
+    if tamper {
+        permuted[3] += BaseElement::ONE;
+    }
+
+    let permutation = build_permutation();
+    let layout = TraceLayout::new(3, [1, 1], [permutation.num_rand_elements(), 1]);
+    let main = TraceTable::init(vec![counter, values, permuted]);
+    MultiSegmentTraceTable::new(main, layout, TwoSegmentTraceBuilder { permutation })
+}
+
+// TWO-ROUND AUXILIARY SEGMENT
+// ================================================================================================
+
+#[test]
+fn two_round_aux_segment_proof_verification() {
+    for extension in [FieldExtension::None, FieldExtension::Quadratic] {
+        let prover = TwoRoundProver {
+            options: ProofOptions::new(28, 8, 0, extension, 4, 256),
+        };
+        let proof = prover
+            .prove(build_two_round_trace(false))
+            .expect("failed to generate proof");
+
+        // the segment is committed to once per round
+        assert_eq!(1, proof.trace_layout().num_aux_segments());
+        assert_eq!(2, proof.trace_layout().num_aux_segment_rounds(0));
+        assert_eq!(3, proof.trace_queries.len());
+
+        let read_proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+        assert_eq!(proof, read_proof);
+        assert!(winterfell::verify::<TwoRoundAir, Blake3_256>(read_proof, ()).is_ok());
+    }
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn two_round_aux_segment_not_a_permutation() {
+    let prover = TwoRoundProver {
+        options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256),
+    };
+    if let Ok(proof) = prover.prove(build_two_round_trace(true)) {
+        assert!(winterfell::verify::<TwoRoundAir, Blake3_256>(proof, ()).is_err());
+    }
+}
+
+#[test]
+fn two_round_aux_segment_missing_round() {
+    // the builder stops after the first round of the segment
+    let permutation = build_permutation();
+    let main = build_two_segment_trace(false).main_trace().clone();
+    let layout = build_two_round_layout(&permutation);
+    let builder = TwoRoundTraceBuilder {
+        permutation,
+        num_rounds: 1,
+    };
+    let prover = TwoRoundProver {
+        options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256),
+    };
+    assert_eq!(
+        Err(ProverError::MissingAuxSegmentRound {
+            segment: 0,
+            round: 1
+        }),
+        prover
+            .prove(MultiSegmentTraceTable::new(main, layout, builder))
+            .map(|_| ())
+    );
+}
+
+/// Builds the auxiliary segment of [TwoRoundAir] in two rounds: the first round builds the
+/// running product column of a permutation argument, and the second round builds a column which
+/// folds the running product using a random element drawn after the first round has been
+/// committed to. Rounds past `num_rounds` are not built.
+struct TwoRoundTraceBuilder {
+    permutation: PermutationArgument,
+    num_rounds: usize,
+}
+
+impl MultiSegmentTraceBuilder<BaseElement> for TwoRoundTraceBuilder {
+    fn build_aux_segment<E>(
+        &self,
+        _main: &TraceTable<BaseElement>,
+        _aux_segments: &[Matrix<E>],
+        _rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = BaseElement>,
+    {
+        None
+    }
+
+    fn build_aux_segment_round<E>(
+        &self,
+        main: &TraceTable<BaseElement>,
+        _aux_segments: &[Matrix<E>],
+        prior_rounds: &[Matrix<E>],
+        rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = BaseElement>,
+    {
+        let rand_elements = rand_elements.get_segment_elements(0);
+        match prior_rounds.len() {
+            round_idx if round_idx >= self.num_rounds => None,
+            0 => {
+                // only the elements of the first round have been drawn at this point
+                assert_eq!(self.permutation.num_rand_elements(), rand_elements.len());
+                let column =
+                    build_permutation_column(&self.permutation, main.main_segment(), rand_elements);
+                Some(Matrix::new(vec![column]))
+            }
+            1 => {
+                let beta = rand_elements[self.permutation.num_rand_elements()];
+                let products = prior_rounds[0].get_column(0);
+                let mut column = Vec::with_capacity(products.len());
+                column.push(E::ZERO);
+                for &product in &products[..products.len() - 1] {
+                    let last = column[column.len() - 1];
+                    column.push(last * beta + product);
+                }
+                Some(Matrix::new(vec![column]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// AIR with one main and one auxiliary trace segment built in two rounds. The constraints are
+/// the same as the constraints of [TwoSegmentAir], but both auxiliary columns belong to the same
+/// segment, and the random element of the second round follows the elements of the first round.
+struct TwoRoundAir {
+    context: AirContext<BaseElement>,
+    permutation: PermutationArgument,
+}
+
+impl Air for TwoRoundAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let permutation = build_permutation();
+        let main_degrees = vec![TransitionConstraintDegree::new(1)];
+        let aux_degrees = vec![
+            permutation.constraint_degree(),
+            TransitionConstraintDegree::new(1),
+        ];
+        let context =
+            AirContext::new_multi_segment(trace_info, main_degrees, aux_degrees, 1, 3, options);
+        Self {
+            context,
+            permutation,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - (frame.current()[0] + E::ONE);
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let current = aux_frame.current();
+        let next = aux_frame.next();
+        let beta = aux_rand_elements.get_segment_elements(0)[self.permutation.num_rand_elements()];
+
+        result[0] = self
+            .permutation
+            .evaluate_transition(main_frame, aux_frame, aux_rand_elements);
+        result[1] = next[1] - (current[1] * beta + current[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        let mut assertions = self.permutation.get_assertions(self.trace_length());
+        assertions.push(Assertion::single(1, 0, E::ZERO));
+        assertions
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+struct TwoRoundProver {
+    options: ProofOptions,
+}
+
+impl Prover for TwoRoundProver {
+    type BaseField = BaseElement;
+    type Air = TwoRoundAir;
+    type Trace = MultiSegmentTraceTable<BaseElement, TwoRoundTraceBuilder>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+/// Returns the layout of a trace of [TwoRoundAir]: the permutation argument draws its elements in
+/// the first round, and the fold column draws its element in the second round.
+fn build_two_round_layout(permutation: &PermutationArgument) -> TraceLayout {
+    let num_rand_elements = permutation.num_rand_elements();
+    TraceLayout::new(3, [2, 0], [num_rand_elements + 1, 0]).with_aux_segment_rounds(
+        0,
+        &[1, 1],
+        &[num_rand_elements, 1],
+    )
+}
+
+/// Builds a trace with the same main segment as the trace built by [build_two_segment_trace()].
+fn build_two_round_trace(
+    tamper: bool,
+) -> MultiSegmentTraceTable<BaseElement, TwoRoundTraceBuilder> {
+    let main = build_two_segment_trace(tamper).main_trace().clone();
+    let permutation = build_permutation();
+    let layout = build_two_round_layout(&permutation);
+    let builder = TwoRoundTraceBuilder {
+        permutation,
+        num_rounds: 2,
+    };
+    MultiSegmentTraceTable::new(main, layout, builder)
+}
+
+// RANGE LOOKUP ARGUMENT
+// ================================================================================================
+
+#[test]
+fn range_lookup_proof_verification() {
+    for extension in [FieldExtension::None, FieldExtension::Quadratic] {
+        let prover = RangeLookupProver {
+            options: ProofOptions::new(28, 8, 0, extension, 4, 256),
+        };
+        let proof = prover
+            .prove(build_range_lookup_trace(false))
+            .expect("failed to generate proof");
+        assert!(winterfell::verify::<RangeLookupAir, Blake3_256>(proof, ()).is_ok());
+    }
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn range_lookup_value_out_of_range() {
+    let prover = RangeLookupProver {
+        options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256),
+    };
+    if let Ok(proof) = prover.prove(build_range_lookup_trace(true)) {
+        assert!(winterfell::verify::<RangeLookupAir, Blake3_256>(proof, ()).is_err());
+    }
+}
+
+#[test]
+fn range_lookup_trace_width() {
+    // decomposing a 32-bit value into bits takes 32 main trace columns in addition to the value
+    let bits = RangeCheckGadget::new(0, 1, 32);
+    assert_eq!(33, 1 + bits.num_aux_columns());
+
+    // with 16-bit limbs, the same check takes two limb columns and one multiplicity column in
+    // the main trace segment, and a single column in the auxiliary trace segment
+    let limbs = RangeLookupArgument::new(16, 0, 1, 2, 0).range_check(0, 32);
+    assert_eq!(4, 1 + 1 + limbs.num_aux_columns());
+
+    // the lookup table is shared by all range checks, and so each additional 32-bit value takes
+    // three more main trace columns rather than 33
+    let limbs = limbs.range_check(8, 32).range_check(9, 32);
+    assert_eq!(2..8, limbs.limb_columns());
+    assert_eq!(10, 3 + 1 + limbs.num_aux_columns());
+}
+
+/// Builds the auxiliary segment of [RangeLookupAir], which contains the running sum column of
+/// the range lookup argument.
+struct RangeLookupTraceBuilder {
+    argument: RangeLookupArgument,
+}
+
+impl MultiSegmentTraceBuilder<BaseElement> for RangeLookupTraceBuilder {
+    fn build_aux_segment<E>(
+        &self,
+        main: &TraceTable<BaseElement>,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = BaseElement>,
+    {
+        if !aux_segments.is_empty() {
+            return None;
+        }
+        let column = build_range_lookup_column(
+            &self.argument,
+            main.main_segment(),
+            rand_elements.get_segment_elements(0),
+        );
+        Some(Matrix::new(vec![column]))
+    }
+}
+
+/// AIR which checks that all but the last values in the first main column fit into 16 bits by
+/// splitting them into 4-bit limbs and looking the limbs up in a table of all 4-bit values. The
+/// second main column holds multiplicities of table values, and the remaining columns hold the
+/// limbs.
+struct RangeLookupAir {
+    context: AirContext<BaseElement>,
+    argument: RangeLookupArgument,
+}
+
+impl Air for RangeLookupAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let argument = build_range_lookup();
+        let main_degrees = argument.constraint_degrees();
+        let aux_degrees = vec![argument.constraint_degree()];
+        let context =
+            AirContext::new_multi_segment(trace_info, main_degrees, aux_degrees, 1, 2, options);
+        Self { context, argument }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.argument.evaluate(frame, result);
+    }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        result[0] = self.argument.evaluate_transition(
+            main_frame,
+            aux_frame,
+            periodic_values,
+            aux_rand_elements,
+        );
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        self.argument.get_assertions(self.trace_length())
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![self.argument.table_values()]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+struct RangeLookupProver {
+    options: ProofOptions,
+}
+
+impl Prover for RangeLookupProver {
+    type BaseField = BaseElement;
+    type Air = RangeLookupAir;
+    type Trace = MultiSegmentTraceTable<BaseElement, RangeLookupTraceBuilder>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+fn build_range_lookup() -> RangeLookupArgument {
+    RangeLookupArgument::new(4, 0, 1, 2, 0).range_check(0, 16)
+}
+
+/// Builds a trace with pseudo-random 16-bit values in the first column; if `tamper` is set, one
+/// of the values is replaced with a value which does not fit into 16 bits.
+fn build_range_lookup_trace(
+    tamper: bool,
+) -> MultiSegmentTraceTable<BaseElement, RangeLookupTraceBuilder> {
+    let argument = build_range_lookup();
+    let mut trace = TraceTable::new(6, TRACE_LENGTH);
+    trace.fill(
+        |state| argument.fill(state),
+        |step, state| {
+            state[0] = BaseElement::new(pseudo_random_value(step as u128));
+            if tamper && step == 10 {
+                state[0] += BaseElement::new(1 << 16);
+            }
+            argument.fill(state);
+        },
+    );
+
+    let multiplicities = build_range_lookup_multiplicities(&argument, trace.main_segment());
+    for (step, value) in multiplicities.into_iter().enumerate() {
+        trace.set(argument.multiplicity_column(), step, value);
+    }
+
+    let layout = TraceLayout::new(6, [1, 0], [argument.num_rand_elements(), 0]);
+    MultiSegmentTraceTable::new(trace, layout, RangeLookupTraceBuilder { argument })
+}
+
+// TRACE PRINTING
+// ================================================================================================
+
+#[test]
+fn format_element_radix() {
+    use winterfell::math::fields::f64::BaseElement as F64Element;
+
+    let value = BaseElement::new(255);
+    assert_eq!("255", super::format_element(value, 10));
+    assert_eq!("ff", super::format_element(value, 16));
+    assert_eq!("11111111", super::format_element(value, 2));
+    assert_eq!("0", super::format_element(BaseElement::ZERO, 10));
+
+    // elements are formatted using their canonical representation regardless of the field
+    assert_eq!(
+        "ffffffffffffffffffffd2ffffffffff",
+        super::format_element(-BaseElement::from(2u32), 16)
+    );
+    assert_eq!(
+        "ffffffff00000000",
+        super::format_element(-F64Element::ONE, 16)
+    );
+}
+
+#[test]
+#[should_panic(expected = "radix must be between 2 and 36, but was 37")]
+fn format_element_invalid_radix() {
+    let _ = super::format_element(BaseElement::ONE, 37);
+}
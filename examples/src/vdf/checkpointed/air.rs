@@ -0,0 +1,112 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, FieldElement, ProofOptions, ALPHA, FORTY_TWO};
+use winterfell::{
+    math::ToElements, Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable, Step,
+    TraceInfo, TransitionConstraintDegree,
+};
+
+// PUBLIC INPUTS
+// ================================================================================================
+
+/// Public inputs of the checkpointed VDF computation.
+///
+/// `checkpoints[i]` is the state of the VDF at step `i * checkpoint_every`, where the distance
+/// between checkpoints is implied by the number of checkpoints and the length of the execution
+/// trace; thus, the first checkpoint is the seed of the VDF.
+#[derive(Clone)]
+pub struct VdfInputs {
+    pub checkpoints: Vec<BaseElement>,
+    pub result: BaseElement,
+}
+
+impl Serializable for VdfInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.checkpoints.len() as u64);
+        target.write(&self.checkpoints);
+        target.write(self.result);
+    }
+}
+
+impl ToElements<BaseElement> for VdfInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        let mut result = Vec::with_capacity(self.checkpoints.len() + 2);
+        result.push(BaseElement::from(self.checkpoints.len() as u64));
+        result.extend_from_slice(&self.checkpoints);
+        result.push(self.result);
+        result
+    }
+}
+
+// VDF AIR
+// ================================================================================================
+
+/// AIR of the VDF computation exposing its intermediate states as checkpoints.
+///
+/// The transition constraint is the same as in the regular VDF example, and uses the default
+/// divisor: the constraint must hold on all steps but the last one, since every step of the
+/// trace is a step of the computation.
+///
+/// All checkpoints are bound by a single sequence assertion with a stride equal to the distance
+/// between checkpoints. The boundary constraint of such an assertion has a succinct divisor
+/// `x^k - 1` (for `k` checkpoints), and the verifier only needs to interpolate a polynomial of
+/// degree `k - 1` over the checkpoint values. Placing a separate single assertion against every
+/// checkpoint would instead require the verifier to evaluate `k` divisors and would create `k`
+/// boundary constraint groups. The result is bound by a single assertion against the last step.
+pub struct VdfAir {
+    context: AirContext<BaseElement>,
+    checkpoints: Vec<BaseElement>,
+    checkpoint_every: usize,
+    result: BaseElement,
+}
+
+impl Air for VdfAir {
+    type BaseField = BaseElement;
+    type PublicInputs = VdfInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: VdfInputs, options: ProofOptions) -> Self {
+        let num_checkpoints = pub_inputs.checkpoints.len();
+        assert!(
+            num_checkpoints.is_power_of_two() && num_checkpoints < trace_info.length(),
+            "number of checkpoints must be a power of two smaller than {}, but was {}",
+            trace_info.length(),
+            num_checkpoints
+        );
+
+        let checkpoint_every = trace_info.length() / num_checkpoints;
+        let degrees = vec![TransitionConstraintDegree::new(3)];
+        Self {
+            context: AirContext::new(trace_info, degrees, 2, options),
+            checkpoint_every,
+            checkpoints: pub_inputs.checkpoints,
+            result: pub_inputs.result,
+        }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current_state = frame.current()[0];
+        let next_state = frame.next()[0];
+
+        result[0] = current_state - (next_state.exp(ALPHA.into()) + FORTY_TWO.into());
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::sequence(0, 0, self.checkpoint_every, self.checkpoints.clone()),
+            Assertion::single_at(0, Step::Last, self.result),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
@@ -0,0 +1,211 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, Example, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
+};
+use core::marker::PhantomData;
+use log::debug;
+use std::time::Instant;
+use winterfell::{
+    crypto::ElementHasher,
+    introspection::AirInfo,
+    math::{fields::f128::BaseElement, log2, FieldElement},
+    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+};
+
+mod air;
+use air::{VdfAir, VdfInputs};
+
+mod prover;
+use prover::VdfProver;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const ALPHA: u64 = 3;
+const INV_ALPHA: u128 = 226854911280625642308916371969163307691;
+const FORTY_TWO: BaseElement = BaseElement::new(42);
+
+// VDF EXAMPLE
+// ================================================================================================
+
+pub fn get_example(
+    options: &ExampleOptions,
+    num_steps: usize,
+    checkpoint_every: usize,
+) -> Result<Box<dyn Example>, String> {
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(85, 2)?;
+
+    match hash_fn {
+        HashFunction::Blake3_192 => Ok(Box::new(VdfExample::<Blake3_192>::new(
+            num_steps,
+            checkpoint_every,
+            options,
+        ))),
+        HashFunction::Blake3_256 => Ok(Box::new(VdfExample::<Blake3_256>::new(
+            num_steps,
+            checkpoint_every,
+            options,
+        ))),
+        HashFunction::Sha3_256 => Ok(Box::new(VdfExample::<Sha3_256>::new(
+            num_steps,
+            checkpoint_every,
+            options,
+        ))),
+        HashFunction::Blake2s_256 => Ok(Box::new(VdfExample::<Blake2s_256>::new(
+            num_steps,
+            checkpoint_every,
+            options,
+        ))),
+        HashFunction::Keccak_256 => Ok(Box::new(VdfExample::<Keccak_256>::new(
+            num_steps,
+            checkpoint_every,
+            options,
+        ))),
+        _ => Err("The specified hash function cannot be used with this example.".to_string()),
+    }
+}
+
+pub struct VdfExample<H: ElementHasher> {
+    options: ProofOptions,
+    num_steps: usize,
+    checkpoint_every: usize,
+    checkpoints: Vec<BaseElement>,
+    result: BaseElement,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> VdfExample<H> {
+    pub fn new(num_steps: usize, checkpoint_every: usize, options: ProofOptions) -> Self {
+        assert!(
+            num_steps.is_power_of_two(),
+            "number of steps must be a power of 2"
+        );
+        assert!(
+            checkpoint_every.is_power_of_two() && checkpoint_every > 1,
+            "distance between checkpoints must be a power of 2 greater than 1"
+        );
+        assert!(
+            checkpoint_every <= num_steps,
+            "distance between checkpoints cannot be greater than the number of steps"
+        );
+
+        // run the VDF function
+        let now = Instant::now();
+        let seed = BaseElement::new(123);
+        let (checkpoints, result) = execute_vdf(seed, num_steps, checkpoint_every);
+        debug!(
+            "Executed the VDF function for {} steps with {} checkpoints in {} ms",
+            num_steps,
+            checkpoints.len(),
+            now.elapsed().as_millis()
+        );
+
+        Self {
+            options,
+            num_steps,
+            checkpoint_every,
+            checkpoints,
+            result,
+            _hasher: PhantomData,
+        }
+    }
+
+    /// Returns public inputs of the computation described by this example.
+    fn get_pub_inputs(&self) -> VdfInputs {
+        VdfInputs {
+            checkpoints: self.checkpoints.clone(),
+            result: self.result,
+        }
+    }
+}
+
+// EXAMPLE IMPLEMENTATION
+// ================================================================================================
+
+impl<H: ElementHasher> Example for VdfExample<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("vdf-checkpointed")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
+        debug!(
+            "Generating proof for executing a VDF function for {} steps with a checkpoint every \
+            {} steps\n\
+            ---------------------",
+            self.num_steps, self.checkpoint_every
+        );
+
+        // generate execution trace
+        let now = Instant::now();
+        let trace = VdfProver::<H>::build_trace(self.checkpoints[0], self.num_steps);
+
+        let trace_width = trace.width();
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace of {} registers and 2^{} steps in {} ms",
+            trace_width,
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = VdfProver::<H>::new(self.options.clone(), self.checkpoint_every);
+
+        // generate the proof
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = VdfProver::<H>::new(self.options.clone(), self.checkpoint_every);
+        describe_air(&prover, &trace.into_inner())
+    }
+
+    fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        winterfell::verify::<VdfAir, H>(proof, self.get_pub_inputs())
+    }
+
+    fn verify_with_wrong_inputs(&self, proof: StarkProof) -> Result<(), VerifierError> {
+        // corrupt the last checkpoint, which is the one closest to the result
+        let mut pub_inputs = self.get_pub_inputs();
+        let last = pub_inputs.checkpoints.len() - 1;
+        pub_inputs.checkpoints[last] += BaseElement::ONE;
+        winterfell::verify::<VdfAir, H>(proof, pub_inputs)
+    }
+}
+
+// VDF FUNCTION
+// ================================================================================================
+
+/// Executes the VDF function for `n` steps starting with `seed`, and returns the states at every
+/// `checkpoint_every` steps (starting with the seed) together with the final state.
+fn execute_vdf(
+    seed: BaseElement,
+    n: usize,
+    checkpoint_every: usize,
+) -> (Vec<BaseElement>, BaseElement) {
+    let mut checkpoints = Vec::with_capacity(n / checkpoint_every);
+    let mut state = seed;
+    for step in 0..(n - 1) {
+        if step % checkpoint_every == 0 {
+            checkpoints.push(state);
+        }
+        state = (state - FORTY_TWO).exp(INV_ALPHA);
+    }
+    (checkpoints, state)
+}
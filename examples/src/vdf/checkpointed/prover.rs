@@ -0,0 +1,67 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    BaseElement, ElementHasher, FieldElement, PhantomData, ProofOptions, Prover, Trace, TraceTable,
+    VdfAir, VdfInputs, FORTY_TWO, INV_ALPHA,
+};
+
+// VDF PROVER
+// ================================================================================================
+
+pub struct VdfProver<H: ElementHasher> {
+    options: ProofOptions,
+    checkpoint_every: usize,
+    _hasher: PhantomData<H>,
+}
+
+impl<H: ElementHasher> VdfProver<H> {
+    pub fn new(options: ProofOptions, checkpoint_every: usize) -> Self {
+        Self {
+            options,
+            checkpoint_every,
+            _hasher: PhantomData,
+        }
+    }
+
+    pub fn build_trace(seed: BaseElement, n: usize) -> TraceTable<BaseElement> {
+        let mut trace = Vec::with_capacity(n);
+        let mut state = seed;
+
+        trace.push(state);
+        for _ in 0..(n - 1) {
+            state = (state - FORTY_TWO).exp(INV_ALPHA);
+            trace.push(state);
+        }
+
+        TraceTable::init(vec![trace])
+    }
+}
+
+impl<H: ElementHasher> Prover for VdfProver<H>
+where
+    H: ElementHasher<BaseField = BaseElement>,
+{
+    type BaseField = BaseElement;
+    type Air = VdfAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = H;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> VdfInputs {
+        let last_step = trace.length() - 1;
+        VdfInputs {
+            checkpoints: (0..trace.length())
+                .step_by(self.checkpoint_every)
+                .map(|step| trace.get(0, step))
+                .collect(),
+            result: trace.get(0, last_step),
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
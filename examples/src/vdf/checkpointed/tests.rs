@@ -0,0 +1,97 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{BaseElement, Blake3_256, Example, FieldElement, VdfAir, VdfInputs};
+use winterfell::{FieldExtension, ProofOptions, VerifierError};
+
+#[test]
+fn vdf_test_basic_proof_verification() {
+    let vdf = Box::new(super::VdfExample::<Blake3_256>::new(
+        128,
+        16,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification(vdf);
+}
+
+#[test]
+fn vdf_test_basic_proof_verification_extension() {
+    let vdf = Box::new(super::VdfExample::<Blake3_256>::new(
+        128,
+        16,
+        build_options(true),
+    ));
+    crate::tests::test_basic_proof_verification(vdf);
+}
+
+#[test]
+fn vdf_test_basic_proof_verification_fail() {
+    let vdf = Box::new(super::VdfExample::<Blake3_256>::new(
+        128,
+        16,
+        build_options(false),
+    ));
+    crate::tests::test_basic_proof_verification_fail(vdf);
+}
+
+#[test]
+fn vdf_test_checkpoint_distances() {
+    // a single checkpoint is the seed of the VDF, and checkpoints can be as close as two steps
+    for checkpoint_every in [2, 8, 128] {
+        let vdf = super::VdfExample::<Blake3_256>::new(128, checkpoint_every, build_options(false));
+        assert_eq!(128 / checkpoint_every, vdf.checkpoints.len());
+        let proof = vdf.prove();
+        assert!(vdf.verify(proof).is_ok());
+    }
+}
+
+#[test]
+fn vdf_test_corrupted_checkpoint() {
+    let vdf = super::VdfExample::<Blake3_256>::new(128, 16, build_options(false));
+    let proof = vdf.prove();
+
+    // corrupting any single checkpoint, including the seed, must fail verification
+    for i in 0..vdf.checkpoints.len() {
+        let mut pub_inputs = vdf.get_pub_inputs();
+        pub_inputs.checkpoints[i] += BaseElement::ONE;
+        assert!(verify(proof.clone(), pub_inputs).is_err());
+    }
+
+    // swapping two checkpoints must fail verification as well
+    let mut pub_inputs = vdf.get_pub_inputs();
+    pub_inputs.checkpoints.swap(1, 2);
+    assert!(verify(proof.clone(), pub_inputs).is_err());
+
+    // checkpoints taken at a different distance must fail verification
+    let mut pub_inputs = vdf.get_pub_inputs();
+    pub_inputs.checkpoints.truncate(4);
+    assert!(verify(proof.clone(), pub_inputs).is_err());
+
+    assert!(verify(proof, vdf.get_pub_inputs()).is_ok());
+}
+
+#[test]
+fn vdf_test_checkpoints_match_trace() {
+    let vdf = super::VdfExample::<Blake3_256>::new(64, 8, build_options(false));
+    let trace = super::VdfProver::<Blake3_256>::build_trace(vdf.checkpoints[0], 64);
+    for (i, checkpoint) in vdf.checkpoints.iter().enumerate() {
+        assert_eq!(*checkpoint, trace.get(0, i * 8));
+    }
+    assert_eq!(vdf.result, trace.get(0, 63));
+}
+
+fn verify(proof: winterfell::StarkProof, pub_inputs: VdfInputs) -> Result<(), VerifierError> {
+    winterfell::verify::<VdfAir, Blake3_256>(proof, pub_inputs)
+}
+
+fn build_options(use_extension_field: bool) -> ProofOptions {
+    let extension = if use_extension_field {
+        FieldExtension::Quadratic
+    } else {
+        FieldExtension::None
+    };
+    ProofOptions::new(85, 2, 0, extension, 4, 256)
+}
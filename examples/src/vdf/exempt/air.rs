@@ -6,8 +6,8 @@
 
 use super::{BaseElement, FieldElement, ProofOptions, ALPHA, FORTY_TWO};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable, TraceInfo,
-    TransitionConstraintDegree,
+    math::ToElements, Air, AirContext, Assertion, ByteWriter, EvaluationFrame, SelectorColumn,
+    Serializable, TraceInfo, TransitionConstraintDegree,
 };
 
 // PUBLIC INPUTS
@@ -17,12 +17,24 @@ use winterfell::{
 pub struct VdfInputs {
     pub seed: BaseElement,
     pub result: BaseElement,
+    pub num_steps: usize,
 }
 
 impl Serializable for VdfInputs {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write(self.seed);
         target.write(self.result);
+        target.write_u64(self.num_steps as u64);
+    }
+}
+
+impl ToElements<BaseElement> for VdfInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![
+            self.seed,
+            self.result,
+            BaseElement::from(self.num_steps as u64),
+        ]
     }
 }
 
@@ -33,6 +45,9 @@ pub struct VdfAir {
     context: AirContext<BaseElement>,
     seed: BaseElement,
     result: BaseElement,
+    num_steps: usize,
+    selector: SelectorColumn,
+    selector_values: Vec<BaseElement>,
 }
 
 impl Air for VdfAir {
@@ -40,40 +55,64 @@ impl Air for VdfAir {
     type PublicInputs = VdfInputs;
 
     fn new(trace_info: TraceInfo, pub_inputs: VdfInputs, options: ProofOptions) -> Self {
-        let degrees = vec![TransitionConstraintDegree::new(3)];
-        // make sure the last two rows are excluded from transition constraints as we populate
-        // values in the last row with garbage
-        let context =
-            AirContext::new(trace_info, degrees, 2, options).set_num_transition_exemptions(2);
+        // the trace must contain all steps of the computation followed by at least one padding
+        // row, which is the one populated with garbage
+        assert!(
+            pub_inputs.num_steps < trace_info.length(),
+            "number of steps must be smaller than {}, but was {}",
+            trace_info.length(),
+            pub_inputs.num_steps
+        );
+
+        // we populate values in the last row with garbage, and thus, the transition into this
+        // row is switched off by a selector which is zero only at the second to last step; the
+        // transition from the last row is excluded by the default transition exemption
+        let trace_length = trace_info.length();
+        let mut selector_values = vec![BaseElement::ONE; trace_length];
+        selector_values[trace_length - 2] = BaseElement::ZERO;
+        let selector = SelectorColumn::new(0, &selector_values);
+
+        // the degree of the transition constraint accounts for the selector wrapping it
+        let degrees = vec![selector.constraint_degree(TransitionConstraintDegree::new(3))];
+        let context = AirContext::new(trace_info, degrees, 2, options)
+            .set_transition_selectors(vec![Some(selector.clone())]);
         Self {
             context,
             seed: pub_inputs.seed,
             result: pub_inputs.result,
+            num_steps: pub_inputs.num_steps,
+            selector,
+            selector_values,
         }
     }
 
     fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
-        _periodic_values: &[E],
+        periodic_values: &[E],
         result: &mut [E],
     ) {
         let current_state = frame.current()[0];
         let next_state = frame.next()[0];
 
-        result[0] = current_state - (next_state.exp(ALPHA.into()) + FORTY_TWO.into());
+        let evaluation = current_state - (next_state.exp(ALPHA.into()) + FORTY_TWO.into());
+        result[0] = self.selector.apply(periodic_values, evaluation);
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
-        // the second boundary constraint is applied to the second to last step
-        // as the last step will contain garbage
-        let second_to_last_step = self.trace_length() - 2;
+        // the second boundary constraint is applied to the last step of the computation, which
+        // is followed by padding rows (the last of which contains garbage)
+        let last_step = self.num_steps - 1;
         vec![
             Assertion::single(0, 0, self.seed),
-            Assertion::single(0, second_to_last_step, self.result),
+            Assertion::single(0, last_step, self.result),
         ]
     }
 
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![self.selector_values.clone()]
+    }
+
     fn context(&self) -> &AirContext<Self::BaseField> {
         &self.context
     }
@@ -4,14 +4,18 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, Example, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
     crypto::ElementHasher,
+    introspection::AirInfo,
     math::{fields::f128::BaseElement, log2, FieldElement},
-    ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
+    PaddingPolicy, ProofOptions, Prover, StarkProof, Trace, TracePadder, TraceTable, VerifierError,
 };
 
 mod air;
@@ -34,12 +38,16 @@ const FORTY_TWO: BaseElement = BaseElement::new(42);
 // ================================================================================================
 
 pub fn get_example(options: &ExampleOptions, num_steps: usize) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(85, 2);
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(42, 4)?;
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(VdfExample::<Blake3_192>::new(num_steps, options))),
         HashFunction::Blake3_256 => Ok(Box::new(VdfExample::<Blake3_256>::new(num_steps, options))),
         HashFunction::Sha3_256 => Ok(Box::new(VdfExample::<Sha3_256>::new(num_steps, options))),
+        HashFunction::Blake2s_256 => {
+            Ok(Box::new(VdfExample::<Blake2s_256>::new(num_steps, options)))
+        }
+        HashFunction::Keccak_256 => Ok(Box::new(VdfExample::<Keccak_256>::new(num_steps, options))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -54,10 +62,8 @@ pub struct VdfExample<H: ElementHasher> {
 
 impl<H: ElementHasher> VdfExample<H> {
     pub fn new(num_steps: usize, options: ProofOptions) -> Self {
-        assert!(
-            (num_steps + 1).is_power_of_two(),
-            "number of steps must be one less than a power of 2"
-        );
+        // with a single step, the seed and the result would be asserted against the same cell
+        assert!(num_steps > 1, "number of steps must be greater than one");
 
         // run the VDF function
         let now = Instant::now();
@@ -86,7 +92,11 @@ impl<H: ElementHasher> Example for VdfExample<H>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
-    fn prove(&self) -> StarkProof {
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("vdf-exempt")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
         debug!(
             "Generating proof for executing a VDF function for {} steps\n\
             ---------------------",
@@ -94,11 +104,11 @@ where
         );
 
         // create a prover
-        let prover = VdfProver::<H>::new(self.options.clone());
+        let prover = VdfProver::<H>::new(self.options.clone(), self.num_steps);
 
         // generate execution trace
         let now = Instant::now();
-        let trace = VdfProver::<H>::build_trace(self.seed, self.num_steps + 1);
+        let trace = prover.build_trace(self.seed);
 
         let trace_width = trace.width();
         let trace_length = trace.length();
@@ -109,14 +119,27 @@ where
             now.elapsed().as_millis()
         );
 
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = VdfProver::<H>::new(self.options.clone(), self.num_steps);
+
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = VdfProver::<H>::new(self.options.clone(), self.num_steps);
+        describe_air(&prover, &trace.into_inner())
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
         let pub_inputs = VdfInputs {
             seed: self.seed,
             result: self.result,
+            num_steps: self.num_steps,
         };
         winterfell::verify::<VdfAir, H>(proof, pub_inputs)
     }
@@ -125,6 +148,7 @@ where
         let pub_inputs = VdfInputs {
             seed: self.seed,
             result: self.result + BaseElement::ONE,
+            num_steps: self.num_steps,
         };
         winterfell::verify::<VdfAir, H>(proof, pub_inputs)
     }
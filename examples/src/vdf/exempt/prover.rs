@@ -5,8 +5,8 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    BaseElement, ElementHasher, FieldElement, PhantomData, ProofOptions, Prover, Trace, TraceTable,
-    VdfAir, VdfInputs, FORTY_TWO, INV_ALPHA,
+    BaseElement, ElementHasher, FieldElement, PaddingPolicy, PhantomData, ProofOptions, Prover,
+    Trace, TracePadder, TraceTable, VdfAir, VdfInputs, FORTY_TWO, INV_ALPHA,
 };
 
 // VDF PROVER
@@ -14,31 +14,45 @@ use super::{
 
 pub struct VdfProver<H: ElementHasher> {
     options: ProofOptions,
+    num_steps: usize,
     _hasher: PhantomData<H>,
 }
 
 impl<H: ElementHasher> VdfProver<H> {
-    pub fn new(options: ProofOptions) -> Self {
+    pub fn new(options: ProofOptions, num_steps: usize) -> Self {
         Self {
             options,
+            num_steps,
             _hasher: PhantomData,
         }
     }
 
-    pub fn build_trace(seed: BaseElement, n: usize) -> TraceTable<BaseElement> {
-        let mut trace = Vec::with_capacity(n);
+    pub fn build_trace(&self, seed: BaseElement) -> TraceTable<BaseElement> {
+        let mut trace = Vec::with_capacity(self.num_steps);
         let mut state = seed;
 
         trace.push(state);
-        for _ in 0..(n - 2) {
+        for _ in 0..(self.num_steps - 1) {
             state = (state - FORTY_TWO).exp(INV_ALPHA);
             trace.push(state);
         }
 
+        // pad the trace to the next power of two by continuing to execute the VDF function, so
+        // that transitions into padding rows are valid; the transition into the last padding
+        // row is switched off by the selector of the transition constraint, and thus we need to
+        // make sure there is at least one padding row
+        let padder = TracePadder::new(PaddingPolicy::Compute(Box::new(
+            |_, prev: &[BaseElement], next: &mut [BaseElement]| {
+                next[0] = (prev[0] - FORTY_TWO).exp(INV_ALPHA);
+            },
+        )))
+        .with_min_padding(1);
+        let (mut trace, _) = padder.pad(vec![trace]);
+
         // put garbage value into the last step
-        trace.push(BaseElement::new(123));
+        trace.set(0, trace.length() - 1, BaseElement::new(123));
 
-        TraceTable::init(vec![trace])
+        trace
     }
 }
 
@@ -52,12 +66,13 @@ where
     type HashFn = H;
 
     fn get_pub_inputs(&self, trace: &Self::Trace) -> VdfInputs {
-        // the result is read from the second to last step because the last last step contains
-        // garbage
-        let second_to_last_step = trace.length() - 2;
+        // the result is read from the last step of the computation because the steps after it
+        // are padding
+        let last_step = self.num_steps - 1;
         VdfInputs {
             seed: trace.get(0, 0),
-            result: trace.get(0, second_to_last_step),
+            result: trace.get(0, last_step),
+            num_steps: self.num_steps,
         }
     }
 
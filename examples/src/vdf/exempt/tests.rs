@@ -4,8 +4,14 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::Blake3_256;
-use winterfell::{FieldExtension, ProofOptions};
+use super::{
+    BaseElement, Blake3_256, Example, FieldElement, PaddingPolicy, Prover, Trace, TracePadder,
+    TraceTable,
+};
+use winterfell::{
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, Step, TraceInfo,
+    TransitionConstraintDegree, TransitionDivisor,
+};
 
 #[test]
 fn vdf_test_basic_proof_verification() {
@@ -34,11 +40,634 @@ fn vdf_test_basic_proof_verification_fail() {
     crate::tests::test_basic_proof_verification_fail(fib);
 }
 
+#[test]
+fn vdf_test_padded_trace() {
+    // 1000 steps are padded to 1024 rows
+    let vdf = super::VdfExample::<Blake3_256>::new(1000, build_options(false));
+    let proof = vdf.prove();
+    assert_eq!(1024, proof.trace_length());
+    assert!(vdf.verify(proof).is_ok());
+
+    // 1024 steps need at least one padding row for the garbage value, and are thus padded to
+    // 2048 rows
+    let vdf = super::VdfExample::<Blake3_256>::new(1024, build_options(false));
+    let proof = vdf.prove();
+    assert_eq!(2048, proof.trace_length());
+    assert!(vdf.verify(proof).is_ok());
+}
+
+#[test]
+fn vdf_test_short_padded_trace() {
+    // 3 steps are padded to the minimum trace length
+    let vdf = super::VdfExample::<Blake3_256>::new(3, build_options(false));
+    let proof = vdf.prove();
+    assert_eq!(8, proof.trace_length());
+    assert!(vdf.verify(proof).is_ok());
+
+    let proof = vdf.prove();
+    assert!(vdf.verify_with_wrong_inputs(proof).is_err());
+}
+
+// PER-CONSTRAINT EXEMPTIONS
+// ================================================================================================
+
+const EXEMPTIONS_TRACE_LENGTH: usize = 64;
+
+#[test]
+fn per_constraint_exemptions_proof_verification() {
+    let trace = build_exemptions_trace(EXEMPTIONS_TRACE_LENGTH);
+    assert!(prove_and_verify_exemptions(trace).is_ok());
+}
+
+#[test]
+fn per_constraint_exemptions_tamper_exempted_rows() {
+    // the second constraint is exempt from the last 3 steps, and thus the values in the last two
+    // rows of the second column are not constrained by it
+    let mut trace = build_exemptions_trace(EXEMPTIONS_TRACE_LENGTH);
+    trace.set(1, EXEMPTIONS_TRACE_LENGTH - 2, BaseElement::new(123));
+    trace.set(1, EXEMPTIONS_TRACE_LENGTH - 1, BaseElement::new(456));
+    assert!(prove_and_verify_exemptions(trace).is_ok());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn per_constraint_exemptions_tamper_constrained_row() {
+    // the first constraint is exempt only from the last step, and thus tampering with the
+    // second to last row of the first column must be detected; in debug mode the prover
+    // catches this when validating the trace
+    let mut trace = build_exemptions_trace(EXEMPTIONS_TRACE_LENGTH);
+    trace.set(0, EXEMPTIONS_TRACE_LENGTH - 2, BaseElement::new(123));
+    assert!(prove_and_verify_exemptions(trace).is_err());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn per_constraint_exemptions_tamper_early_row() {
+    let mut trace = build_exemptions_trace(EXEMPTIONS_TRACE_LENGTH);
+    trace.set(1, EXEMPTIONS_TRACE_LENGTH / 2, BaseElement::new(123));
+    assert!(prove_and_verify_exemptions(trace).is_err());
+}
+
+/// AIR with two transition constraints which are exempt from a different number of steps:
+/// * the first column is a counter incremented by one on each step (1 exemption);
+/// * the second column is squared on each step (3 exemptions).
+struct ExemptionsAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for ExemptionsAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+        ];
+        let context =
+            AirContext::new(trace_info, degrees, 2, options).set_transition_exemptions(vec![1, 3]);
+        Self { context }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        result[0] = next[0] - (current[0] + E::ONE);
+        result[1] = next[1] - current[1].square();
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(1, 0, BaseElement::new(3)),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+struct ExemptionsProver {
+    options: ProofOptions,
+}
+
+impl Prover for ExemptionsProver {
+    type BaseField = BaseElement;
+    type Air = ExemptionsAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+fn build_exemptions_trace(length: usize) -> TraceTable<BaseElement> {
+    let mut trace = TraceTable::new(2, length);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ZERO;
+            state[1] = BaseElement::new(3);
+        },
+        |_, state| {
+            state[0] += BaseElement::ONE;
+            state[1] = state[1].square();
+        },
+    );
+    trace
+}
+
+#[test]
+fn per_constraint_exemptions_single_row_trace() {
+    // a trace consisting of the initial row only is padded by executing the computation, and
+    // thus satisfies all constraints
+    let padder = TracePadder::new(PaddingPolicy::Compute(Box::new(
+        |_, prev: &[BaseElement], next: &mut [BaseElement]| {
+            next[0] = prev[0] + BaseElement::ONE;
+            next[1] = prev[1].square();
+        },
+    )));
+    let (trace, length) = padder.pad(vec![vec![BaseElement::ZERO], vec![BaseElement::new(3)]]);
+    assert_eq!(1, length);
+    assert_eq!(8, trace.length());
+    assert!(prove_and_verify_exemptions(trace).is_ok());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn per_constraint_exemptions_repeated_padding_rows() {
+    // repeating the last row breaks the transition into the first padding row
+    let trace = build_exemptions_trace(EXEMPTIONS_TRACE_LENGTH);
+    let columns = (0..2)
+        .map(|i| trace.get_column(i)[..EXEMPTIONS_TRACE_LENGTH - 4].to_vec())
+        .collect();
+    let (trace, _) = TracePadder::new(PaddingPolicy::RepeatLast).pad(columns);
+    assert!(prove_and_verify_exemptions(trace).is_err());
+}
+
+fn prove_and_verify_exemptions(
+    trace: TraceTable<BaseElement>,
+) -> Result<(), winterfell::VerifierError> {
+    let prover = ExemptionsProver {
+        options: build_options(false),
+    };
+    let proof = prover.prove(trace).expect("failed to generate proof");
+    winterfell::verify::<ExemptionsAir, Blake3_256>(proof, ())
+}
+
+// COSET DIVISORS
+// ================================================================================================
+
+const COSETS_TRACE_LENGTH: usize = 64;
+
+#[test]
+fn coset_divisors_proof_verification() {
+    let trace = build_cosets_trace(COSETS_TRACE_LENGTH);
+    assert!(prove_and_verify_cosets(trace).is_ok());
+}
+
+#[test]
+fn coset_divisors_tamper_unconstrained_rows() {
+    // the first constraint holds only on even steps except for the exempted step in the middle
+    // of the trace, and thus rows of the first column around the exempted step are not
+    // constrained by it
+    let mut trace = build_cosets_trace(COSETS_TRACE_LENGTH);
+    trace.set(0, COSETS_TRACE_LENGTH / 2, BaseElement::new(123));
+    trace.set(0, COSETS_TRACE_LENGTH / 2 + 1, BaseElement::new(456));
+    assert!(prove_and_verify_cosets(trace).is_ok());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn coset_divisors_tamper_even_step() {
+    let mut trace = build_cosets_trace(COSETS_TRACE_LENGTH);
+    trace.set(0, COSETS_TRACE_LENGTH / 2 - 1, BaseElement::new(123));
+    assert!(prove_and_verify_cosets(trace).is_err());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn coset_divisors_tamper_odd_step() {
+    let mut trace = build_cosets_trace(COSETS_TRACE_LENGTH);
+    trace.set(1, 2, BaseElement::new(123));
+    assert!(prove_and_verify_cosets(trace).is_err());
+}
+
+/// AIR with two transition constraints which hold on different cosets of the trace domain:
+/// * the first column is incremented by one on even steps, except for the step in the middle of
+///   the trace;
+/// * the second column is squared on odd steps, except for the last step.
+struct CosetsAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for CosetsAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let trace_length = trace_info.length();
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+        ];
+        let divisors = vec![
+            TransitionDivisor::new(2, 0, vec![trace_length / 2]),
+            TransitionDivisor::new(2, 1, vec![trace_length - 1]),
+        ];
+        let context =
+            AirContext::new(trace_info, degrees, 2, options).set_transition_divisors(divisors);
+        Self { context }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        result[0] = next[0] - (current[0] + E::ONE);
+        result[1] = next[1] - current[1].square();
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(1, 0, BaseElement::new(3)),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+struct CosetsProver {
+    options: ProofOptions,
+}
+
+impl Prover for CosetsProver {
+    type BaseField = BaseElement;
+    type Air = CosetsAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+fn build_cosets_trace(length: usize) -> TraceTable<BaseElement> {
+    let mut trace = TraceTable::new(2, length);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ZERO;
+            state[1] = BaseElement::new(3);
+        },
+        |step, state| {
+            if step % 2 == 0 {
+                state[0] += BaseElement::ONE;
+            } else {
+                state[1] = state[1].square();
+            }
+        },
+    );
+    trace
+}
+
+fn prove_and_verify_cosets(
+    trace: TraceTable<BaseElement>,
+) -> Result<(), winterfell::VerifierError> {
+    let prover = CosetsProver {
+        options: build_options(false),
+    };
+    let proof = prover.prove(trace).expect("failed to generate proof");
+    winterfell::verify::<CosetsAir, Blake3_256>(proof, ())
+}
+
+// INTERVAL DIVISORS
+// ================================================================================================
+
+const PHASES_TRACE_LENGTH: usize = 64;
+
+/// Number of steps in the initialization phase of [PhasesAir].
+const INIT_PHASE_LENGTH: usize = 16;
+
+/// Interval of steps on which the second column of [PhasesAir] accumulates the first column.
+const ACCUMULATION_INTERVAL: (usize, usize) = (32, 48);
+
+#[test]
+fn interval_divisors_proof_verification() {
+    let trace = build_phases_trace(PHASES_TRACE_LENGTH);
+    assert!(prove_and_verify_phases(trace).is_ok());
+}
+
+#[test]
+fn interval_divisors_tamper_unconstrained_rows() {
+    // the second column is constrained only by the accumulation interval and by the assertion
+    // at the start of the interval
+    let mut trace = build_phases_trace(PHASES_TRACE_LENGTH);
+    trace.set(1, 10, BaseElement::new(123));
+    trace.set(1, ACCUMULATION_INTERVAL.1 + 2, BaseElement::new(456));
+    assert!(prove_and_verify_phases(trace).is_ok());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn interval_divisors_tamper_init_phase() {
+    let mut trace = build_phases_trace(PHASES_TRACE_LENGTH);
+    trace.set(0, INIT_PHASE_LENGTH / 2, BaseElement::new(123));
+    assert!(prove_and_verify_phases(trace).is_err());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn interval_divisors_tamper_phase_switch() {
+    // the transition into the first row of the steady state is still a part of the
+    // initialization phase
+    let mut trace = build_phases_trace(PHASES_TRACE_LENGTH);
+    trace.set(0, INIT_PHASE_LENGTH, BaseElement::new(17 * 17));
+    assert!(prove_and_verify_phases(trace).is_err());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn interval_divisors_tamper_accumulation() {
+    let mut trace = build_phases_trace(PHASES_TRACE_LENGTH);
+    trace.set(1, ACCUMULATION_INTERVAL.0 + 8, BaseElement::new(123));
+    assert!(prove_and_verify_phases(trace).is_err());
+}
+
+/// AIR for a computation which switches between phases with different rules:
+/// * during the initialization phase (the first [INIT_PHASE_LENGTH] steps), the first column is
+///   incremented by one;
+/// * after the initialization phase, the first column is squared on all steps except for the
+///   last one;
+/// * the second column accumulates values of the first column only on the steps in
+///   [ACCUMULATION_INTERVAL], and is unconstrained otherwise.
+///
+/// Constraints enforced on intervals use interval divisors; the steady state constraint covers
+/// most of the trace, and thus, uses a divisor with explicitly exempted steps instead.
+struct PhasesAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for PhasesAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let trace_length = trace_info.length();
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+            TransitionConstraintDegree::new(1),
+        ];
+        let steady_state_exemptions = (0..INIT_PHASE_LENGTH)
+            .chain(core::iter::once(trace_length - 1))
+            .collect();
+        let divisors = vec![
+            TransitionDivisor::with_interval(0, INIT_PHASE_LENGTH),
+            TransitionDivisor::new(1, 0, steady_state_exemptions),
+            TransitionDivisor::with_interval(ACCUMULATION_INTERVAL.0, ACCUMULATION_INTERVAL.1),
+        ];
+        let context =
+            AirContext::new(trace_info, degrees, 2, options).set_transition_divisors(divisors);
+        Self { context }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        result[0] = next[0] - (current[0] + E::ONE);
+        result[1] = next[0] - current[0].square();
+        result[2] = next[1] - (current[1] + current[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(1, ACCUMULATION_INTERVAL.0, BaseElement::ZERO),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+struct PhasesProver {
+    options: ProofOptions,
+}
+
+impl Prover for PhasesProver {
+    type BaseField = BaseElement;
+    type Air = PhasesAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+fn build_phases_trace(length: usize) -> TraceTable<BaseElement> {
+    let (acc_start, acc_end) = ACCUMULATION_INTERVAL;
+    let mut trace = TraceTable::new(2, length);
+    trace.fill(
+        |state| {
+            state[0] = BaseElement::ZERO;
+            state[1] = BaseElement::new(5);
+        },
+        |step, state| {
+            // outside of the accumulation interval, the second column holds arbitrary values
+            if (acc_start..acc_end).contains(&step) {
+                state[1] += state[0];
+            } else if step + 1 == acc_start {
+                state[1] = BaseElement::ZERO;
+            } else {
+                state[1] *= BaseElement::new(3);
+            }
+
+            if step < INIT_PHASE_LENGTH {
+                state[0] += BaseElement::ONE;
+            } else {
+                state[0] = state[0].square();
+            }
+        },
+    );
+    trace
+}
+
+fn prove_and_verify_phases(
+    trace: TraceTable<BaseElement>,
+) -> Result<(), winterfell::VerifierError> {
+    let prover = PhasesProver {
+        options: build_options(false),
+    };
+    let proof = prover.prove(trace).expect("failed to generate proof");
+    winterfell::verify::<PhasesAir, Blake3_256>(proof, ())
+}
+
+// ASSERTIONS FROM THE END OF THE TRACE
+// ================================================================================================
+
+/// Value written into the last row of a padded trace; the transition into this row is exempt
+/// from the transition constraint.
+const TERMINATOR: u128 = 7;
+
+#[test]
+fn from_end_assertions_padded_traces() {
+    // the same AIR accepts traces of different lengths which are padded to different lengths
+    for num_steps in [3, 60, 100, 1000] {
+        let trace = build_from_end_trace(num_steps);
+        assert_eq!((num_steps + 2).next_power_of_two().max(8), trace.length());
+        assert!(prove_and_verify_from_end(trace).is_ok());
+    }
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn from_end_assertions_tamper_second_to_last_row() {
+    // the flag in the second to last row must be set
+    let mut trace = build_from_end_trace(60);
+    trace.set(1, trace.length() - 2, BaseElement::new(123));
+    assert!(prove_and_verify_from_end(trace).is_err());
+}
+
+#[test]
+#[cfg_attr(debug_assertions, should_panic)]
+fn from_end_assertions_tamper_last_row() {
+    let mut trace = build_from_end_trace(60);
+    trace.set(0, trace.length() - 1, BaseElement::new(123));
+    assert!(prove_and_verify_from_end(trace).is_err());
+}
+
+/// AIR with a counter in the first column, and a binary flag in the second column which is set
+/// on padding rows. Assertions against the padding rows
+/// are placed relative to the end of the trace, and thus do not depend on the trace length:
+/// * the last row of the first column holds [TERMINATOR];
+/// * the second to last row of the second column holds one.
+struct FromEndAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for FromEndAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(2),
+        ];
+        let context =
+            AirContext::new(trace_info, degrees, 4, options).set_num_transition_exemptions(2);
+        Self { context }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+
+        // the counter is incremented on each step, and the flag in the second column is binary
+        result[0] = next[0] - (current[0] + E::ONE);
+        result[1] = next[1].square() - next[1];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single_at(0, Step::First, BaseElement::ZERO),
+            Assertion::single_at(1, Step::First, BaseElement::ZERO),
+            Assertion::single_at(1, Step::FromEnd(1), BaseElement::ONE),
+            Assertion::single_at(0, Step::Last, BaseElement::new(TERMINATOR)),
+        ]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+struct FromEndProver {
+    options: ProofOptions,
+}
+
+impl Prover for FromEndProver {
+    type BaseField = BaseElement;
+    type Air = FromEndAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+fn build_from_end_trace(num_steps: usize) -> TraceTable<BaseElement> {
+    let counter = (0..num_steps as u128).map(BaseElement::new).collect();
+    let flags = vec![BaseElement::ZERO; num_steps];
+
+    // padding rows keep incrementing the counter and set the flag; the last padding row is
+    // exempt from transition constraints and holds the terminator
+    let padder = TracePadder::new(PaddingPolicy::Compute(Box::new(
+        |_, prev: &[BaseElement], next: &mut [BaseElement]| {
+            next[0] = prev[0] + BaseElement::ONE;
+            next[1] = BaseElement::ONE;
+        },
+    )))
+    .with_min_padding(2);
+    let (mut trace, _) = padder.pad(vec![counter, flags]);
+    trace.set(0, trace.length() - 1, BaseElement::new(TERMINATOR));
+    trace
+}
+
+fn prove_and_verify_from_end(
+    trace: TraceTable<BaseElement>,
+) -> Result<(), winterfell::VerifierError> {
+    let prover = FromEndProver {
+        options: build_options(false),
+    };
+    let proof = prover.prove(trace).expect("failed to generate proof");
+    winterfell::verify::<FromEndAir, Blake3_256>(proof, ())
+}
+
 fn build_options(use_extension_field: bool) -> ProofOptions {
     let extension = if use_extension_field {
         FieldExtension::Quadratic
     } else {
         FieldExtension::None
     };
-    ProofOptions::new(85, 2, 0, extension, 4, 256)
+    // query positions are unique, and thus, the number of queries must be smaller than the LDE
+    // domain of the shortest traces in these tests (8 rows with blowup factor 4)
+    ProofOptions::new(28, 4, 0, extension, 4, 256)
 }
@@ -1,2 +1,3 @@
+pub mod checkpointed;
 pub mod exempt;
 pub mod regular;
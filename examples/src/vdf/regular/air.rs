@@ -6,26 +6,19 @@
 
 use super::{BaseElement, FieldElement, ProofOptions, ALPHA, FORTY_TWO};
 use winterfell::{
-    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable, TraceInfo,
+    math::ToElements, Air, AirContext, Assertion, EvaluationFrame, Serializable, Step, TraceInfo,
     TransitionConstraintDegree,
 };
 
 // PUBLIC INPUTS
 // ================================================================================================
 
-#[derive(Clone)]
+#[derive(Clone, Serializable, ToElements)]
 pub struct VdfInputs {
     pub seed: BaseElement,
     pub result: BaseElement,
 }
 
-impl Serializable for VdfInputs {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write(self.seed);
-        target.write(self.result);
-    }
-}
-
 // VDF AIR
 // ================================================================================================
 
@@ -61,10 +54,9 @@ impl Air for VdfAir {
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
-        let last_step = self.trace_length() - 1;
         vec![
-            Assertion::single(0, 0, self.seed),
-            Assertion::single(0, last_step, self.result),
+            Assertion::single_at(0, Step::First, self.seed),
+            Assertion::single_at(0, Step::Last, self.result),
         ]
     }
 
@@ -4,12 +4,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::{Blake3_192, Blake3_256, Example, ExampleOptions, HashFunction, Sha3_256};
+use crate::{
+    describe_air, Blake2s_256, Blake3_192, Blake3_256, Example, ExampleDescriptor, ExampleOptions,
+    ExampleTrace, HashFunction, Keccak_256, Sha3_256,
+};
 use core::marker::PhantomData;
 use log::debug;
 use std::time::Instant;
 use winterfell::{
     crypto::ElementHasher,
+    introspection::AirInfo,
     math::{fields::f128::BaseElement, log2, FieldElement},
     ProofOptions, Prover, StarkProof, Trace, TraceTable, VerifierError,
 };
@@ -34,12 +38,16 @@ const FORTY_TWO: BaseElement = BaseElement::new(42);
 // ================================================================================================
 
 pub fn get_example(options: &ExampleOptions, num_steps: usize) -> Result<Box<dyn Example>, String> {
-    let (options, hash_fn) = options.to_proof_options(85, 2);
+    let (options, hash_fn) = options.to_proof_options::<BaseElement>(85, 2)?;
 
     match hash_fn {
         HashFunction::Blake3_192 => Ok(Box::new(VdfExample::<Blake3_192>::new(num_steps, options))),
         HashFunction::Blake3_256 => Ok(Box::new(VdfExample::<Blake3_256>::new(num_steps, options))),
         HashFunction::Sha3_256 => Ok(Box::new(VdfExample::<Sha3_256>::new(num_steps, options))),
+        HashFunction::Blake2s_256 => {
+            Ok(Box::new(VdfExample::<Blake2s_256>::new(num_steps, options)))
+        }
+        HashFunction::Keccak_256 => Ok(Box::new(VdfExample::<Keccak_256>::new(num_steps, options))),
         _ => Err("The specified hash function cannot be used with this example.".to_string()),
     }
 }
@@ -86,16 +94,17 @@ impl<H: ElementHasher> Example for VdfExample<H>
 where
     H: ElementHasher<BaseField = BaseElement>,
 {
-    fn prove(&self) -> StarkProof {
+    fn descriptor(&self) -> ExampleDescriptor {
+        ExampleDescriptor::new::<H>("vdf")
+    }
+
+    fn build_trace(&self) -> ExampleTrace {
         debug!(
             "Generating proof for executing a VDF function for {} steps\n\
             ---------------------",
             self.num_steps
         );
 
-        // create a prover
-        let prover = VdfProver::<H>::new(self.options.clone());
-
         // generate execution trace
         let now = Instant::now();
         let trace = VdfProver::<H>::build_trace(self.seed, self.num_steps);
@@ -109,8 +118,20 @@ where
             now.elapsed().as_millis()
         );
 
+        ExampleTrace::new(trace)
+    }
+
+    fn prove_trace(&self, trace: ExampleTrace) -> StarkProof {
+        // create a prover
+        let prover = VdfProver::<H>::new(self.options.clone());
+
         // generate the proof
-        prover.prove(trace).unwrap()
+        prover.prove(trace.into_inner()).unwrap()
+    }
+
+    fn describe_trace(&self, trace: ExampleTrace) -> AirInfo {
+        let prover = VdfProver::<H>::new(self.options.clone());
+        describe_air(&prover, &trace.into_inner())
     }
 
     fn verify(&self, proof: StarkProof) -> Result<(), VerifierError> {
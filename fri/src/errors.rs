@@ -17,18 +17,21 @@ pub enum VerifierError {
     /// Attempt to draw a random value from a public coin failed.
     PublicCoinError(RandomCoinError),
     /// Folding factor specified for the protocol is not supported. Currently, supported folding
-    /// factors are: 4, 8, and 16.
+    /// factors are: 2, 4, 8, and 16.
     UnsupportedFoldingFactor(usize),
     /// Number of query positions does not match the number of provided evaluations.
     NumPositionEvaluationMismatch(usize, usize),
-    /// Evaluations at queried positions did not match layer commitment made by the prover.
-    LayerCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at one of the layers.
-    InvalidLayerFolding(usize),
+    /// Evaluations at queried positions did not match commitment made by the prover for the
+    /// specified layer.
+    LayerCommitmentMismatch(usize),
+    /// Degree-respecting projection was not performed correctly at the specified layer for the
+    /// specified query position.
+    InvalidLayerFolding(usize, usize),
     /// FRI remainder did not match the commitment.
     RemainderCommitmentMismatch,
-    /// Degree-respecting projection was not performed correctly at the last layer.
-    InvalidRemainderFolding,
+    /// Degree-respecting projection was not performed correctly at the last layer for the
+    /// specified query position.
+    InvalidRemainderFolding(usize),
     /// FRI remainder expected degree is greater than number of remainder values.
     RemainderDegreeNotValid,
     /// FRI remainder degree is greater than the polynomial degree expected for the last layer.
@@ -50,17 +53,17 @@ impl fmt::Display for VerifierError {
             Self::NumPositionEvaluationMismatch(num_positions, num_evaluations) => write!(f,
                 "the number of query positions must be the same as the number of polynomial evaluations, but {num_positions} and {num_evaluations} were provided"
             ),
-            Self::LayerCommitmentMismatch => {
-                write!(f, "FRI queries did not match layer commitment made by the prover")
+            Self::LayerCommitmentMismatch(layer) => {
+                write!(f, "FRI queries did not match commitment made by the prover for layer {layer}")
             }
-            Self::InvalidLayerFolding(layer) => {
-                write!(f, "degree-respecting projection is not consistent at layer {layer}")
+            Self::InvalidLayerFolding(layer, position) => {
+                write!(f, "degree-respecting projection is not consistent at layer {layer} for query position {position}")
             }
             Self::RemainderCommitmentMismatch => {
                 write!(f, "FRI remainder did not match the commitment")
             }
-            Self::InvalidRemainderFolding => {
-                write!(f, "degree-respecting projection is inconsistent at the last FRI layer")
+            Self::InvalidRemainderFolding(position) => {
+                write!(f, "degree-respecting projection is inconsistent at the last FRI layer for query position {position}")
             }
             Self::RemainderDegreeNotValid => {
                 write!(f, "FRI remainder expected degree is greater than number of remainder values")
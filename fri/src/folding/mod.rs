@@ -17,7 +17,7 @@ use math::{
     fft::{get_inv_twiddles, serial_fft},
     get_power_series_with_offset, polynom, FieldElement, StarkField,
 };
-use utils::{collections::Vec, iter_mut, uninit_vector};
+use utils::{collections::Vec, iter_mut};
 
 // DEGREE-RESPECTING PROJECTION
 // ================================================================================================
@@ -94,7 +94,7 @@ where
     let inv_twiddles = get_inv_twiddles::<B>(N);
     let len_offset = E::inv((N as u64).into());
 
-    let mut result = unsafe { uninit_vector(values.len()) };
+    let mut result = E::zeroed_vector(values.len());
     iter_mut!(result)
         .zip(values)
         .zip(inv_offsets)
@@ -86,3 +86,13 @@ mod errors;
 pub use errors::VerifierError;
 
 pub mod utils;
+
+pub mod labels {
+    //! Contains labels with which FRI protocol operations are tagged in the public coin (see
+    //! [Transcript](crypto::Transcript)).
+
+    /// Label for absorbing a commitment to a FRI layer.
+    pub const FRI_LAYER_COMMITMENT: &str = "fri-layer-commitment";
+    /// Label for drawing a random α value for folding a FRI layer.
+    pub const FRI_ALPHA: &str = "fri-alpha";
+}
@@ -112,4 +112,17 @@ impl FriOptions {
         }
         domain_size
     }
+
+    /// Computes and returns the maximum degree of the remainder polynomial (the polynomial
+    /// implied by the last FRI layer) for a polynomial of degree at most `max_poly_degree`
+    /// evaluated over a domain of the specified size.
+    ///
+    /// The degree bound plus one is divided by the `folding_factor` at each FRI layer; that is,
+    /// for $k$ FRI layers, the remainder degree is bounded by $(d + 1) / f^k - 1$, where $d$ is
+    /// `max_poly_degree` and $f$ is the folding factor. The verifier interpolates the remainder
+    /// over its domain and checks its degree against this bound.
+    pub fn remainder_max_degree(&self, domain_size: usize, max_poly_degree: usize) -> usize {
+        let num_layers = self.num_fri_layers(domain_size) as u32;
+        ((max_poly_degree + 1) / self.folding_factor.pow(num_layers)).saturating_sub(1)
+    }
 }
@@ -97,10 +97,21 @@ impl FriProof {
 
     /// Returns the size of this proof in bytes.
     pub fn size(&self) -> usize {
-        // +1 for number of layers, +1 for remainder length, +1 for number of partitions
+        // +1 for number of layers, +1 for number of partitions
         self.layers
             .iter()
-            .fold(self.remainder.len() + 3, |acc, layer| acc + layer.size())
+            .fold(self.remainder_size() + 2, |acc, layer| acc + layer.size())
+    }
+
+    /// Returns the size of each layer of this proof in bytes.
+    pub fn layer_sizes(&self) -> Vec<usize> {
+        self.layers.iter().map(|layer| layer.size()).collect()
+    }
+
+    /// Returns the size of the remainder of this proof in bytes.
+    pub fn remainder_size(&self) -> usize {
+        // +2 for remainder length
+        self.remainder.len() + 2
     }
 
     // PARSING
@@ -142,8 +153,14 @@ impl FriProof {
         let mut layer_queries = Vec::new();
         let num_remainder_elements = self.num_remainder_elements::<E>();
 
-        // parse all layers
+        // parse all layers; each layer must reduce the domain by the folding factor
         for (i, layer) in self.layers.into_iter().enumerate() {
+            if domain_size < folding_factor {
+                return Err(DeserializationError::InvalidValue(format!(
+                    "FRI layer {i} cannot fold a domain of size {domain_size} by factor \
+                    {folding_factor}"
+                )));
+            }
             domain_size /= folding_factor;
             let (qv, mp) = layer.parse(domain_size, folding_factor).map_err(|err| {
                 DeserializationError::InvalidValue(format!("failed to parse FRI layer {i}: {err}"))
@@ -223,8 +240,14 @@ impl Deserializable for FriProof {
         let num_remainder_bytes = source.read_u16()? as usize;
         let remainder = source.read_u8_vec(num_remainder_bytes)?;
 
-        // read number of partitions
+        // read number of partitions; the number is stored as a power of two
         let num_partitions = source.read_u8()?;
+        if num_partitions as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of partitions cannot be greater than 2^{}, but was 2^{num_partitions}",
+                usize::BITS - 1
+            )));
+        }
 
         Ok(FriProof {
             layers,
@@ -7,7 +7,7 @@
 use crate::{
     folding::{apply_drp, fold_positions},
     proof::{FriProof, FriProofLayer},
-    utils::hash_values,
+    utils::hash_columns,
     FriOptions,
 };
 use core::marker::PhantomData;
@@ -169,7 +169,32 @@ where
     ///
     /// # Panics
     /// Panics if the prover state is dirty (the vector of layers is not empty).
-    pub fn build_layers(&mut self, channel: &mut C, mut evaluations: Vec<E>) {
+    pub fn build_layers(&mut self, channel: &mut C, evaluations: Vec<E>) {
+        self.build_layers_with(channel, evaluations, |columns| {
+            MerkleTree::new(hash_columns::<H, E>(columns))
+                .expect("failed to construct FRI layer tree")
+        });
+    }
+
+    /// Executes the commit phase of the FRI protocol using the provided closure to commit to the
+    /// evaluations at each layer.
+    ///
+    /// This is equivalent to [build_layers()](FriProver::build_layers()), but the Merkle tree of
+    /// each layer is built by `commit_layer`. The closure receives the evaluations of the layer
+    /// split into `folding_factor` columns of equal length, and must return a Merkle tree whose
+    /// i-th leaf is the hash of the i-th elements of all columns (in column order). This allows
+    /// offloading commitments to external hardware.
+    ///
+    /// # Panics
+    /// Panics if the prover state is dirty (the vector of layers is not empty).
+    pub fn build_layers_with<F>(
+        &mut self,
+        channel: &mut C,
+        mut evaluations: Vec<E>,
+        mut commit_layer: F,
+    ) where
+        F: FnMut(&[&[E]]) -> MerkleTree<H>,
+    {
         assert!(
             self.layers.is_empty(),
             "a prior proof generation request has not been completed yet"
@@ -179,10 +204,10 @@ where
         // is small enough
         for _ in 0..self.options.num_fri_layers(evaluations.len()) {
             match self.folding_factor() {
-                2 => self.build_layer::<2>(channel, &mut evaluations),
-                4 => self.build_layer::<4>(channel, &mut evaluations),
-                8 => self.build_layer::<8>(channel, &mut evaluations),
-                16 => self.build_layer::<16>(channel, &mut evaluations),
+                2 => self.build_layer::<2, F>(channel, &mut evaluations, &mut commit_layer),
+                4 => self.build_layer::<4, F>(channel, &mut evaluations, &mut commit_layer),
+                8 => self.build_layer::<8, F>(channel, &mut evaluations, &mut commit_layer),
+                16 => self.build_layer::<16, F>(channel, &mut evaluations, &mut commit_layer),
                 _ => unimplemented!("folding factor {} is not supported", self.folding_factor()),
             }
         }
@@ -201,17 +226,28 @@ where
 
     /// Builds a single FRI layer by first committing to the `evaluations`, then drawing a random
     /// alpha from the channel and use it to perform degree-respecting projection.
-    fn build_layer<const N: usize>(&mut self, channel: &mut C, evaluations: &mut Vec<E>) {
-        // commit to the evaluations at the current layer; we do this by first transposing the
-        // evaluations into a matrix of N columns, and then building a Merkle tree from the
-        // rows of this matrix; we do this so that we could de-commit to N values with a single
+    fn build_layer<const N: usize, F>(
+        &mut self,
+        channel: &mut C,
+        evaluations: &mut Vec<E>,
+        commit_layer: &mut F,
+    ) where
+        F: FnMut(&[&[E]]) -> MerkleTree<H>,
+    {
+        // commit to the evaluations at the current layer; we do this by splitting the
+        // evaluations into N columns, and then building a Merkle tree from the rows of the
+        // resulting matrix; we do this so that we could de-commit to N values with a single
         // Merkle authentication path.
-        let transposed_evaluations = transpose_slice(evaluations);
-        let hashed_evaluations = hash_values::<H, E, N>(&transposed_evaluations);
-        let evaluation_tree =
-            MerkleTree::<H>::new(hashed_evaluations).expect("failed to construct FRI layer tree");
+        let columns = evaluations
+            .chunks(evaluations.len() / N)
+            .collect::<Vec<_>>();
+        let evaluation_tree = commit_layer(&columns);
         channel.commit_fri_layer(*evaluation_tree.root());
 
+        // the rows of the matrix are also needed for degree-respecting projection and for
+        // building query proofs
+        let transposed_evaluations: Vec<[E; N]> = transpose_slice(evaluations);
+
         // draw a pseudo-random coefficient from the channel, and use it in degree-respecting
         // projection to reduce the degree of evaluations by N
         let alpha = channel.draw_fri_alpha();
@@ -46,6 +46,68 @@ fn fri_folding_4() {
     )
 }
 
+#[test]
+fn fri_remainder_sizes() {
+    // with the largest remainder size, the entire LDE domain of 2^11 elements is sent as the
+    // remainder; its length in bytes must fit into the 16-bit length prefix of the remainder
+    let trace_length_e = 8;
+    let lde_blowup_e = 3;
+    for folding_factor_e in [1, 2, 3] {
+        // the domain is folded until it fits into the remainder, and thus, the remainder may be
+        // smaller than its max size by almost a folding factor; it must still be at least as
+        // large as the blowup factor, otherwise, the degree of the remainder would be truncated
+        let min_remainder_size_e = lde_blowup_e + folding_factor_e - 1;
+        for max_remainder_size_e in [min_remainder_size_e, 5, 7, 9, 13] {
+            fri_prove_verify(
+                trace_length_e,
+                lde_blowup_e,
+                folding_factor_e,
+                max_remainder_size_e,
+            )
+        }
+    }
+}
+
+#[test]
+fn fri_remainder_degree_violation() {
+    let trace_length = 1 << 10;
+    let lde_blowup = 8;
+    let domain_size = trace_length * lde_blowup;
+    let max_degree = trace_length - 1;
+
+    for max_remainder_size in [16, 64, 256, 1024] {
+        let options = FriOptions::new(lde_blowup, 4, max_remainder_size);
+
+        // a malicious prover folds evaluations of a polynomial of twice the claimed degree
+        // honestly; thus, all layers are consistent, but the remainder has too high a degree
+        let mut channel = build_prover_channel(trace_length, &options);
+        let evaluations = build_evaluations(trace_length * 2, lde_blowup / 2);
+        let mut prover = FriProver::new(options.clone());
+        prover.build_layers(&mut channel, evaluations.clone());
+        let positions = channel.draw_query_positions();
+        let proof = prover.build_proof(&positions);
+        assert_eq!(
+            options.fri_remainder_size(domain_size),
+            proof.num_remainder_elements::<BaseElement>()
+        );
+
+        let remainder_max_degree = options.remainder_max_degree(domain_size, max_degree);
+        let result = verify_proof(
+            proof,
+            channel.layer_commitments().to_vec(),
+            &evaluations,
+            max_degree,
+            domain_size,
+            &positions,
+            &options,
+        );
+        assert_eq!(
+            Err(VerifierError::RemainderDegreeMismatch(remainder_max_degree)),
+            result
+        );
+    }
+}
+
 // TEST UTILS
 // ================================================================================================
 
@@ -81,6 +143,7 @@ pub fn verify_proof(
     // test proof serialization / deserialization
     let mut proof_bytes = Vec::new();
     proof.write_into(&mut proof_bytes);
+    assert_eq!(proof.size(), proof_bytes.len());
 
     let mut reader = SliceReader::new(&proof_bytes);
     let proof = FriProof::read_from(&mut reader).unwrap();
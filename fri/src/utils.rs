@@ -6,7 +6,7 @@
 
 use crypto::ElementHasher;
 use math::FieldElement;
-use utils::{collections::Vec, iter_mut, uninit_vector};
+use utils::{batch_iter_mut, collections::Vec};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
@@ -38,15 +38,27 @@ pub fn map_positions_to_indexes(
     result
 }
 
-/// Hashes each of the arrays in the provided slice and returns a vector of resulting hashes.
-pub fn hash_values<H, E, const N: usize>(values: &[[E; N]]) -> Vec<H::Digest>
+/// Hashes the rows of a matrix defined by the provided columns and returns a vector of resulting
+/// hashes; the i-th row consists of the i-th elements of all columns.
+pub fn hash_columns<H, E>(columns: &[&[E]]) -> Vec<H::Digest>
 where
     E: FieldElement,
     H: ElementHasher<BaseField = E::BaseField>,
 {
-    let mut result: Vec<H::Digest> = unsafe { uninit_vector(values.len()) };
-    iter_mut!(result, 1024).zip(values).for_each(|(r, v)| {
-        *r = H::hash_elements(v);
-    });
+    let num_rows = columns[0].len();
+    let mut result = vec![H::Digest::default(); num_rows];
+    batch_iter_mut!(
+        &mut result,
+        1024, // min batch size
+        |batch: &mut [H::Digest], batch_offset: usize| {
+            let mut row = vec![E::ZERO; columns.len()];
+            for (i, r) in batch.iter_mut().enumerate() {
+                for (value, column) in row.iter_mut().zip(columns) {
+                    *value = column[i + batch_offset];
+                }
+                *r = H::hash_elements(&row);
+            }
+        }
+    );
     result
 }
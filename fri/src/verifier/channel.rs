@@ -72,18 +72,20 @@ pub trait VerifierChannel<E: FieldElement> {
     /// Returns FRI query values at the specified positions from the current FRI layer and advances
     /// layer pointer by one.
     ///
-    /// This also checks if the values are valid against the provided FRI layer commitment.
+    /// This also checks if the values are valid against the provided FRI layer commitment; `depth`
+    /// is the index of the current FRI layer and is used only for error reporting.
     ///
     /// # Errors
     /// Returns an error if query values did not match layer commitment.
     fn read_layer_queries<const N: usize>(
         &mut self,
+        depth: usize,
         positions: &[usize],
         commitment: &<<Self as VerifierChannel<E>>::Hasher as Hasher>::Digest,
     ) -> Result<Vec<[E; N]>, VerifierError> {
         let layer_proof = self.take_next_fri_layer_proof();
         MerkleTree::<Self::Hasher>::verify_batch(commitment, positions, &layer_proof)
-            .map_err(|_| VerifierError::LayerCommitmentMismatch)?;
+            .map_err(|_| VerifierError::LayerCommitmentMismatch(depth))?;
 
         // TODO: make sure layer queries hash into leaves of layer proof
 
@@ -142,7 +144,9 @@ where
     /// Builds a new verifier channel from the specified [FriProof].
     ///
     /// # Errors
-    /// Returns an error if the specified `proof` could not be parsed correctly.
+    /// Returns an error if the specified `proof` could not be parsed correctly, or if the number
+    /// of layers in the `proof` is not one less than the number of `layer_commitments` (the last
+    /// commitment is the commitment to the remainder).
     pub fn new(
         proof: FriProof,
         layer_commitments: Vec<H::Digest>,
@@ -154,6 +158,13 @@ where
         let remainder = proof.parse_remainder()?;
         let (layer_queries, layer_proofs) =
             proof.parse_layers::<H, E>(domain_size, folding_factor)?;
+        if layer_queries.len() + 1 != layer_commitments.len() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "expected {} FRI layers, but was {}",
+                layer_commitments.len().saturating_sub(1),
+                layer_queries.len()
+            )));
+        }
 
         Ok(DefaultVerifierChannel {
             layer_commitments,
@@ -6,9 +6,14 @@
 
 //! Contains an implementation of FRI verifier and associated components.
 
-use crate::{folding::fold_positions, utils::map_positions_to_indexes, FriOptions, VerifierError};
+use crate::{
+    folding::fold_positions,
+    labels::{FRI_ALPHA, FRI_LAYER_COMMITMENT},
+    utils::map_positions_to_indexes,
+    FriOptions, VerifierError,
+};
 use core::{convert::TryInto, marker::PhantomData, mem};
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, Hasher, Transcript};
 use math::{fft, log2, polynom, FieldElement, StarkField};
 use utils::collections::Vec;
 
@@ -88,8 +93,13 @@ where
     ///
     /// Creating a FRI verifier executes the commit phase of the FRI protocol from the verifier's
     /// perspective. Specifically, the verifier reads FRI layer commitments from the `channel`,
-    /// and for each commitment, updates the `public_coin` with this commitment and then draws
-    /// a random value α from the coin.
+    /// and for each commitment, updates the `public_coin` with this commitment and then, unless
+    /// the commitment is to the remainder, draws a random value α from the coin. Both operations
+    /// are tagged with the labels defined in [labels](crate::labels).
+    ///
+    /// The `public_coin` may use a hash function different from the one used to build layer
+    /// commitments, as long as layer commitments can be converted into digests of the coin's
+    /// hash function.
     ///
     /// The verifier stores layer commitments and corresponding α values in its internal state,
     /// and, thus, an instance of FRI verifier can be used to verify only a single proof.
@@ -99,12 +109,16 @@ where
     /// * `max_poly_degree` is inconsistent with the number of FRI layers read from the channel
     ///   and `folding_factor` specified in the `options` parameter.
     /// * An error was encountered while drawing a random α value from the coin.
-    pub fn new(
+    pub fn new<R>(
         channel: &mut C,
-        public_coin: &mut RandomCoin<B, H>,
+        public_coin: &mut R,
         options: FriOptions,
         max_poly_degree: usize,
-    ) -> Result<Self, VerifierError> {
+    ) -> Result<Self, VerifierError>
+    where
+        R: Transcript<B>,
+        <R::Hasher as Hasher>::Digest: From<H::Digest>,
+    {
         // infer evaluation domain info
         let domain_size = max_poly_degree.next_power_of_two() * options.blowup_factor();
         let domain_generator = B::get_root_of_unity(log2(domain_size));
@@ -116,15 +130,21 @@ where
         let mut layer_alphas = Vec::with_capacity(layer_commitments.len());
         let mut max_degree_plus_1 = max_poly_degree + 1;
         for (depth, commitment) in layer_commitments.iter().enumerate() {
-            public_coin.reseed(*commitment);
-            let alpha = public_coin.draw().map_err(VerifierError::PublicCoinError)?;
+            public_coin.absorb(FRI_LAYER_COMMITMENT, (*commitment).into());
+
+            // the last commitment is a commitment to the remainder; the remainder is not folded,
+            // and thus, the prover does not draw α after committing to it
+            if depth == layer_commitments.len() - 1 {
+                break;
+            }
+            let alpha = public_coin
+                .draw(FRI_ALPHA)
+                .map_err(VerifierError::PublicCoinError)?;
             layer_alphas.push(alpha);
 
             // make sure the degree can be reduced by the folding factor at all layers
             // but the remainder layer
-            if depth != layer_commitments.len() - 1
-                && max_degree_plus_1 % options.folding_factor() != 0
-            {
+            if max_degree_plus_1 % options.folding_factor() != 0 {
                 return Err(VerifierError::DegreeTruncation(
                     max_degree_plus_1 - 1,
                     options.folding_factor(),
@@ -163,6 +183,11 @@ where
         self.domain_size
     }
 
+    /// Returns commitments to FRI layers read from the channel during the commit phase.
+    pub fn layer_commitments(&self) -> &[H::Digest] {
+        &self.layer_commitments
+    }
+
     /// Returns number of partitions used during FRI proof generation.
     ///
     /// For non-distributed proof generation, number of partitions is usually set to 1.
@@ -260,12 +285,12 @@ where
             );
             // read query values from the specified indexes in the Merkle tree
             let layer_commitment = self.layer_commitments[depth];
-            // TODO: add layer depth to the potential error message
-            let layer_values = channel.read_layer_queries(&position_indexes, &layer_commitment)?;
+            let layer_values =
+                channel.read_layer_queries(depth, &position_indexes, &layer_commitment)?;
             let query_values =
                 get_query_values::<E, N>(&layer_values, &positions, &folded_positions, domain_size);
-            if evaluations != query_values {
-                return Err(VerifierError::InvalidLayerFolding(depth));
+            if let Some(i) = (0..positions.len()).find(|&i| evaluations[i] != query_values[i]) {
+                return Err(VerifierError::InvalidLayerFolding(depth, positions[i]));
             }
 
             // build a set of x coordinates for each row polynomial
@@ -312,12 +337,16 @@ where
         let remainder = channel.read_remainder::<N>(remainder_commitment)?;
         for (&position, evaluation) in positions.iter().zip(evaluations) {
             if remainder[position] != evaluation {
-                return Err(VerifierError::InvalidRemainderFolding);
+                return Err(VerifierError::InvalidRemainderFolding(position));
             }
         }
 
-        // make sure the remainder values satisfy the degree
-        verify_remainder(remainder, max_degree_plus_1 - 1)
+        // make sure the remainder values satisfy the degree bound; this interpolates the
+        // remainder over its domain rather than requiring it to be a constant
+        let remainder_max_degree = self
+            .options
+            .remainder_max_degree(self.domain_size, self.max_poly_degree);
+        verify_remainder(remainder, remainder_max_degree)
     }
 }
 
@@ -0,0 +1,18 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use std::{fs, path::Path};
+
+/// Writes a valid proof into the corpus of the `verify_proof` fuzz target.
+fn main() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("corpus/verify_proof");
+    fs::create_dir_all(&dir).expect("failed to create corpus directory");
+
+    let proof = winter_fuzz::build_proof();
+    let path = dir.join("seed");
+    fs::write(&path, proof.to_bytes()).expect("failed to write seed proof");
+    println!("Seed proof written to {}", path.display());
+}
@@ -0,0 +1,165 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Computation shared by the fuzz targets and the corpus generator.
+//!
+//! The fuzz targets verify mutated proofs of a Fibonacci sequence computation; a valid proof of
+//! this computation generated by [build_proof()] is used as the seed of the corpus.
+
+use winterfell::{
+    crypto::hashers::Blake3_256,
+    math::{fields::f128::BaseElement, FieldElement},
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, Prover, StarkProof,
+    Trace, TraceInfo, TraceTable, TransitionConstraintDegree,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of terms in the Fibonacci sequence proven by the seed proof.
+pub const SEQUENCE_LENGTH: usize = 64;
+
+/// Width of the execution trace of the computation.
+const TRACE_WIDTH: usize = 2;
+
+type HashFn = Blake3_256<BaseElement>;
+
+// FUZZING INTERFACE
+// ================================================================================================
+
+/// Generates a proof of the computation for a sequence of [SEQUENCE_LENGTH] terms.
+pub fn build_proof() -> StarkProof {
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 32);
+    let prover = FibProver { options };
+    prover
+        .prove(prover.build_trace(SEQUENCE_LENGTH))
+        .expect("failed to generate the seed proof")
+}
+
+/// Parses the specified bytes into a proof, and verifies the proof against the result of the
+/// computation for a sequence of [SEQUENCE_LENGTH] terms.
+///
+/// Neither parsing nor verification are expected to panic, regardless of the bytes.
+pub fn verify_proof_bytes(bytes: &[u8]) {
+    let proof = match StarkProof::from_bytes(bytes) {
+        Ok(proof) => proof,
+        Err(_) => return,
+    };
+
+    // the verifier instantiates the AIR for the trace layout read from the proof; as most AIRs,
+    // this AIR is defined only for traces of a given shape and panics otherwise, and thus, it is
+    // up to the caller of the verifier to reject proofs of traces of any other shape
+    let layout = proof.trace_layout();
+    if layout.main_trace_width() != TRACE_WIDTH || layout.aux_trace_width() != 0 {
+        return;
+    }
+
+    let _ = winterfell::verify::<FibAir, HashFn>(proof, compute_result(SEQUENCE_LENGTH));
+}
+
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR of a Fibonacci sequence computation in which each row advances the sequence by 2 terms.
+pub struct FibAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, pub_inputs: BaseElement, options: ProofOptions) -> Self {
+        assert_eq!(TRACE_WIDTH, trace_info.width());
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result: pub_inputs,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+            Assertion::single(1, last_step, self.result),
+        ]
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+struct FibProver {
+    options: ProofOptions,
+}
+
+impl FibProver {
+    fn build_trace(&self, sequence_length: usize) -> TraceTable<BaseElement> {
+        let mut trace = TraceTable::new(TRACE_WIDTH, sequence_length / 2);
+        trace.fill(
+            |state| {
+                state[0] = BaseElement::ONE;
+                state[1] = BaseElement::ONE;
+            },
+            |_, state| {
+                state[0] += state[1];
+                state[1] += state[0];
+            },
+        );
+        trace
+    }
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = HashFn;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(1, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the last term of a Fibonacci sequence of the specified length.
+fn compute_result(sequence_length: usize) -> BaseElement {
+    let mut t0 = BaseElement::ONE;
+    let mut t1 = BaseElement::ONE;
+    for _ in 0..(sequence_length - 1) {
+        t1 = t0 + t1;
+        core::mem::swap(&mut t0, &mut t1);
+    }
+    t1
+}
@@ -0,0 +1,508 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Arithmetic and constraint helpers for the Cheetah curve.
+//!
+//! The curve is defined by the equation y^2 = x^3 + x + B over the sextic extension
+//! Fp6 = Fp[u]/(u^6 - 7), where p = 2^64 - 2^32 + 1. Extension field elements are represented
+//! as 6 base field elements in ascending order of powers of u, and affine points are
+//! represented as 12 base field elements: the 6 coordinates of x followed by the 6 coordinates
+//! of y.
+//!
+//! # Fixed-base scalar multiplication
+//! Multiplication of a fixed base point by a scalar can be computed with 4-bit windows using a
+//! table of precomputed multiples of the base point (see [compute_fixed_base_table()]). Each
+//! row of the trace then adds one table entry to an accumulator, and thus, a k-bit scalar is
+//! processed in k / 4 rows instead of k rows of the double-and-add method. A row requires 4
+//! selector columns holding the bits of the window, 6 columns for the slope, and 12 columns for
+//! the resulting point, while the table is provided via 16 * 12 = 192 periodic columns (see
+//! [FixedBaseTable::get_periodic_column_values()]).
+
+use math::{
+    curves::cheetah::{AffinePoint, ProjectivePoint},
+    fields::f64::BaseElement,
+    FieldElement,
+};
+use utils::{collections::Vec, Serializable};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of base field elements needed to represent an element of Fp6.
+pub const EXT_DEGREE: usize = 6;
+
+/// Number of base field elements needed to represent a curve point in affine coordinates.
+pub const POINT_WIDTH: usize = 2 * EXT_DEGREE;
+
+/// Number of scalar bits processed per window in fixed-base scalar multiplication.
+pub const WINDOW_BITS: usize = 4;
+
+/// Number of entries in each window of a fixed-base table.
+pub const WINDOW_SIZE: usize = 1 << WINDOW_BITS;
+
+/// Domain used to derive random points of the curve.
+const RANDOM_POINTS_DOMAIN: &[u8] = b"winterfell-gadgets-ecc";
+
+/// Non-residue defining the sextic extension: u^6 = 7.
+const NON_RESIDUE: BaseElement = BaseElement::new(7);
+
+/// p^6 - 2 in little-endian limbs; used to compute inverses in Fp6.
+const INV_EXPONENT: [u64; 6] = [
+    0xfffffff9ffffffff,
+    0xffffffce00000014,
+    0xffffff8200000059,
+    0xffffff820000008c,
+    0xffffffce00000059,
+    0xfffffffa00000014,
+];
+
+// EXTENSION FIELD ARITHMETIC
+// ================================================================================================
+
+/// Returns a + b in Fp6.
+pub fn add<E: FieldElement>(a: &[E], b: &[E]) -> [E; EXT_DEGREE] {
+    let mut result = [E::ZERO; EXT_DEGREE];
+    for i in 0..EXT_DEGREE {
+        result[i] = a[i] + b[i];
+    }
+    result
+}
+
+/// Returns a - b in Fp6.
+pub fn sub<E: FieldElement>(a: &[E], b: &[E]) -> [E; EXT_DEGREE] {
+    let mut result = [E::ZERO; EXT_DEGREE];
+    for i in 0..EXT_DEGREE {
+        result[i] = a[i] - b[i];
+    }
+    result
+}
+
+/// Returns a * b in Fp6.
+pub fn mul<E: FieldElement + From<BaseElement>>(a: &[E], b: &[E]) -> [E; EXT_DEGREE] {
+    // compute the product as a polynomial of degree at most 10, and then reduce the upper
+    // coefficients using u^6 = 7
+    let mut product = [E::ZERO; 2 * EXT_DEGREE - 1];
+    for i in 0..EXT_DEGREE {
+        for j in 0..EXT_DEGREE {
+            product[i + j] += a[i] * b[j];
+        }
+    }
+
+    let non_residue = E::from(NON_RESIDUE);
+    let mut result = [E::ZERO; EXT_DEGREE];
+    result.copy_from_slice(&product[..EXT_DEGREE]);
+    for i in EXT_DEGREE..product.len() {
+        result[i - EXT_DEGREE] += product[i] * non_residue;
+    }
+    result
+}
+
+/// Returns a^2 in Fp6.
+pub fn square<E: FieldElement + From<BaseElement>>(a: &[E]) -> [E; EXT_DEGREE] {
+    mul(a, a)
+}
+
+/// Returns a * k in Fp6 for a base field element k.
+pub fn mul_by_base<E: FieldElement>(a: &[E], k: E) -> [E; EXT_DEGREE] {
+    let mut result = [E::ZERO; EXT_DEGREE];
+    for i in 0..EXT_DEGREE {
+        result[i] = a[i] * k;
+    }
+    result
+}
+
+/// Returns a^(-1) in Fp6, or zero if a is zero.
+///
+/// The inverse is computed as a^(p^6 - 2), and thus this function is relatively slow; it is
+/// meant to be used only when building execution traces.
+pub fn inv(a: &[BaseElement]) -> [BaseElement; EXT_DEGREE] {
+    let mut result = [BaseElement::ZERO; EXT_DEGREE];
+    result[0] = BaseElement::ONE;
+    for &limb in INV_EXPONENT.iter().rev() {
+        for i in (0..64).rev() {
+            result = square(&result);
+            if (limb >> i) & 1 == 1 {
+                result = mul(&result, a);
+            }
+        }
+    }
+    result
+}
+
+// CURVE ARITHMETIC
+// ================================================================================================
+
+/// Returns the affine coordinates of the specified point.
+///
+/// # Panics
+/// Panics if the point is the identity.
+pub fn point_to_elements(point: &AffinePoint) -> [BaseElement; POINT_WIDTH] {
+    assert!(
+        !point.is_identity(),
+        "the identity has no affine coordinates"
+    );
+    let mut result = [BaseElement::ZERO; POINT_WIDTH];
+    result[..EXT_DEGREE].copy_from_slice(&point.get_x());
+    result[EXT_DEGREE..].copy_from_slice(&point.get_y());
+    result
+}
+
+/// Returns 2P together with the slope of the tangent line at P.
+///
+/// The point P must not be the identity and must not have order 2.
+pub fn double(p: &[BaseElement]) -> ([BaseElement; POINT_WIDTH], [BaseElement; EXT_DEGREE]) {
+    let (x, y) = p.split_at(EXT_DEGREE);
+
+    // slope = (3x^2 + 1) / 2y
+    let mut numerator = mul_by_base(&square(x), BaseElement::new(3));
+    numerator[0] += BaseElement::ONE;
+    let slope = mul(&numerator, &inv(&add(y, y)));
+
+    (line_intersection(x, y, x, &slope), slope)
+}
+
+/// Returns P + Q together with the slope of the line through P and Q.
+///
+/// The points must not be the identity and must have distinct x coordinates.
+pub fn add_points(
+    p: &[BaseElement],
+    q: &[BaseElement],
+) -> ([BaseElement; POINT_WIDTH], [BaseElement; EXT_DEGREE]) {
+    let (x1, y1) = p.split_at(EXT_DEGREE);
+    let (x2, y2) = q.split_at(EXT_DEGREE);
+
+    // slope = (y2 - y1) / (x2 - x1)
+    let slope = mul(&sub(y2, y1), &inv(&sub(x2, x1)));
+
+    (line_intersection(x1, y1, x2, &slope), slope)
+}
+
+/// Returns `count` points of the curve derived deterministically from the specified `seed`.
+///
+/// The discrete logarithms of these points with respect to the generator, and with respect to
+/// each other, are unknown.
+pub fn get_random_points(seed: &[u8], count: usize) -> Vec<AffinePoint> {
+    (0..count as u32)
+        .map(|i| {
+            let mut msg = seed.to_vec();
+            msg.extend_from_slice(&i.to_le_bytes());
+            AffinePoint::hash_to_curve(RANDOM_POINTS_DOMAIN, &msg)
+        })
+        .collect()
+}
+
+/// Returns the third intersection point of the curve with the line through (x1, y1) with the
+/// specified slope, reflected over the x axis; x2 is the x coordinate of the second point.
+fn line_intersection(
+    x1: &[BaseElement],
+    y1: &[BaseElement],
+    x2: &[BaseElement],
+    slope: &[BaseElement],
+) -> [BaseElement; POINT_WIDTH] {
+    // x3 = slope^2 - x1 - x2; y3 = slope * (x1 - x3) - y1
+    let x3 = sub(&sub(&square(slope), x1), x2);
+    let y3 = sub(&mul(slope, &sub(x1, &x3)), y1);
+
+    let mut result = [BaseElement::ZERO; POINT_WIDTH];
+    result[..EXT_DEGREE].copy_from_slice(&x3);
+    result[EXT_DEGREE..].copy_from_slice(&y3);
+    result
+}
+
+// FIXED-BASE TABLE
+// ================================================================================================
+
+/// Precomputed multiples of a base point B used for fixed-base scalar multiplication.
+///
+/// For a scalar k with 4-bit windows k_0, ..., k_{n-1} (starting with the least significant
+/// one), the entry for window w and digit d is (d + 1) * 16^w * B, so that no entry is the
+/// identity. The accumulator starts at a random point H (see [get_random_points()]), and after
+/// the entries for all windows are added, it is equal to k * B + H + (16^n - 1) / 15 * B; this
+/// offset is returned by [FixedBaseTable::result_offset()].
+///
+/// Since the discrete logarithm of H is unknown, the incomplete addition formulas used by
+/// [enforce_fixed_base_window_add()] do not run into exceptional cases except with negligible
+/// probability.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixedBaseTable {
+    entries: Vec<[[BaseElement; POINT_WIDTH]; WINDOW_SIZE]>,
+    initial_point: [BaseElement; POINT_WIDTH],
+    result_offset: AffinePoint,
+}
+
+/// Returns a table for multiplying `base` by scalars of up to 4 * `num_windows` bits.
+///
+/// The initial value of the accumulator is derived from `base`, and thus, tables built for the
+/// same base point are identical.
+///
+/// # Panics
+/// Panics if `base` is the identity, or if `num_windows` is zero.
+pub fn compute_fixed_base_table(base: AffinePoint, num_windows: usize) -> FixedBaseTable {
+    assert!(!base.is_identity(), "base point cannot be the identity");
+    assert!(
+        num_windows > 0,
+        "number of windows must be greater than zero"
+    );
+
+    let initial_point = get_random_points(&base.to_bytes(), 1)[0];
+
+    // compute 16^w * B for every window w
+    let mut window_base = ProjectivePoint::from(base);
+    let mut result_offset = ProjectivePoint::from(initial_point);
+    let mut window_bases = Vec::with_capacity(num_windows);
+    for _ in 0..num_windows {
+        window_bases.push(window_base);
+        result_offset += window_base;
+        for _ in 0..WINDOW_BITS {
+            window_base = window_base.double();
+        }
+    }
+    let mut affine_window_bases = vec![AffinePoint::identity(); num_windows];
+    ProjectivePoint::batch_normalize(&window_bases, &mut affine_window_bases);
+
+    // the entries for window w are the prefix sums of a chain of 16 copies of 16^w * B
+    let chains = affine_window_bases
+        .into_iter()
+        .map(|window_base| vec![window_base; WINDOW_SIZE])
+        .collect::<Vec<_>>();
+    let entries = AffinePoint::batch_add_chains(&chains)
+        .iter()
+        .map(|points| {
+            let mut entry = [[BaseElement::ZERO; POINT_WIDTH]; WINDOW_SIZE];
+            for (elements, point) in entry.iter_mut().zip(points.iter()) {
+                *elements = point_to_elements(point);
+            }
+            entry
+        })
+        .collect();
+
+    FixedBaseTable {
+        entries,
+        initial_point: point_to_elements(&initial_point),
+        result_offset: AffinePoint::from(result_offset),
+    }
+}
+
+impl FixedBaseTable {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of windows in this table.
+    pub fn num_windows(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the entry of this table for the specified window and digit.
+    ///
+    /// # Panics
+    /// Panics if `window` is not smaller than the number of windows, or if `digit` is not
+    /// smaller than 16.
+    pub fn entry(&self, window: usize, digit: usize) -> &[BaseElement; POINT_WIDTH] {
+        &self.entries[window][digit]
+    }
+
+    /// Returns the point at which the accumulator starts.
+    pub fn initial_point(&self) -> &[BaseElement; POINT_WIDTH] {
+        &self.initial_point
+    }
+
+    /// Returns the point which is added to k * B by the computation: the accumulator ends at
+    /// k * B + `result_offset()`.
+    pub fn result_offset(&self) -> AffinePoint {
+        self.result_offset
+    }
+
+    // TRACE HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the table as 16 * 12 periodic columns.
+    ///
+    /// The value of column d * 12 + i on step w of a cycle is the ith coordinate of the entry
+    /// for window w and digit d. The cycle length is the number of windows rounded up to the
+    /// next power of two (and to at least 2); values on the steps past the last window are
+    /// set to ZERO, and constraints evaluated with them must be disabled by the AIR.
+    pub fn get_periodic_column_values(&self) -> Vec<Vec<BaseElement>> {
+        let cycle_length = self.num_windows().next_power_of_two().max(2);
+        let mut result = Vec::with_capacity(WINDOW_SIZE * POINT_WIDTH);
+        for d in 0..WINDOW_SIZE {
+            for i in 0..POINT_WIDTH {
+                let mut column = vec![BaseElement::ZERO; cycle_length];
+                for (value, entries) in column.iter_mut().zip(self.entries.iter()) {
+                    *value = entries[d][i];
+                }
+                result.push(column);
+            }
+        }
+        result
+    }
+
+    /// Returns `p` + the entry for the specified window and digit, together with the slope of
+    /// the line through the two points.
+    pub fn add_window(
+        &self,
+        p: &[BaseElement],
+        window: usize,
+        digit: usize,
+    ) -> ([BaseElement; POINT_WIDTH], [BaseElement; EXT_DEGREE]) {
+        add_points(p, self.entry(window, digit))
+    }
+}
+
+/// Returns the 4-bit windows of the specified little-endian scalar, starting with the least
+/// significant one.
+///
+/// # Panics
+/// Panics if the scalar does not fit into the specified number of windows.
+pub fn scalar_to_windows(scalar: &[u8], num_windows: usize) -> Vec<usize> {
+    let digits = scalar
+        .iter()
+        .flat_map(|&byte| [(byte & 0xf) as usize, (byte >> 4) as usize])
+        .collect::<Vec<_>>();
+    assert!(
+        digits.iter().skip(num_windows).all(|&digit| digit == 0),
+        "scalar does not fit into {num_windows} windows"
+    );
+    let mut result = digits;
+    result.resize(num_windows, 0);
+    result
+}
+
+/// Returns the binary selectors for the specified digit, starting with the least significant
+/// bit.
+pub fn digit_to_selectors(digit: usize) -> [BaseElement; WINDOW_BITS] {
+    let mut result = [BaseElement::ZERO; WINDOW_BITS];
+    for (i, selector) in result.iter_mut().enumerate() {
+        *selector = BaseElement::new(((digit >> i) & 1) as u64);
+    }
+    result
+}
+
+// CONSTRAINTS
+// ================================================================================================
+
+/// Enforces that `r` = 2 * `p` using the provided tangent `slope`; this requires 3 * 6 = 18
+/// constraints of degree 2.
+///
+/// The formulas are incomplete: `p` must not be the identity and must not have order 2.
+pub fn enforce_point_doubling<E: FieldElement + From<BaseElement>>(
+    result: &mut [E],
+    p: &[E],
+    slope: &[E],
+    r: &[E],
+) {
+    let (x1, y1) = p.split_at(EXT_DEGREE);
+    let (x3, y3) = r.split_at(EXT_DEGREE);
+
+    // slope * 2y1 = 3x1^2 + 1
+    let lhs = mul(slope, &add(y1, y1));
+    let mut rhs = mul_by_base(&square(x1), E::from(BaseElement::new(3)));
+    rhs[0] += E::ONE;
+    enforce_equal(&mut result[..EXT_DEGREE], &lhs, &rhs);
+
+    // x3 = slope^2 - 2x1
+    let rhs = sub(&square(slope), &add(x1, x1));
+    enforce_equal(&mut result[EXT_DEGREE..2 * EXT_DEGREE], x3, &rhs);
+
+    // y3 = slope * (x1 - x3) - y1
+    let rhs = sub(&mul(slope, &sub(x1, x3)), y1);
+    enforce_equal(&mut result[2 * EXT_DEGREE..3 * EXT_DEGREE], y3, &rhs);
+}
+
+/// Enforces that `r` = `p` + `flag` * `q` using the provided chord `slope`; this requires
+/// 3 * 6 = 18 constraints of degree 3 (when `q` is a point in the trace).
+///
+/// `flag` is expected to be binary. When `flag` is zero, `r` must be equal to `p` and `slope`
+/// is unconstrained. The formulas are incomplete: when `flag` is one, `p` and `q` must not be
+/// the identity and must have distinct x coordinates.
+pub fn enforce_point_addition<E: FieldElement + From<BaseElement>>(
+    result: &mut [E],
+    p: &[E],
+    q: &[E],
+    slope: &[E],
+    r: &[E],
+    flag: E,
+) {
+    let (x1, y1) = p.split_at(EXT_DEGREE);
+    let (x2, y2) = q.split_at(EXT_DEGREE);
+    let (x3, y3) = r.split_at(EXT_DEGREE);
+    let not_flag = E::ONE - flag;
+
+    // flag * (slope * (x2 - x1) - (y2 - y1)) = 0
+    let lhs = mul_by_base(&mul(slope, &sub(x2, x1)), flag);
+    let rhs = mul_by_base(&sub(y2, y1), flag);
+    enforce_equal(&mut result[..EXT_DEGREE], &lhs, &rhs);
+
+    // x3 = flag * (slope^2 - x1 - x2) + (1 - flag) * x1
+    let rhs = add(
+        &mul_by_base(&sub(&sub(&square(slope), x1), x2), flag),
+        &mul_by_base(x1, not_flag),
+    );
+    enforce_equal(&mut result[EXT_DEGREE..2 * EXT_DEGREE], x3, &rhs);
+
+    // y3 = flag * (slope * (x1 - x3) - y1) + (1 - flag) * y1
+    let rhs = add(
+        &mul_by_base(&sub(&mul(slope, &sub(x1, x3)), y1), flag),
+        &mul_by_base(y1, not_flag),
+    );
+    enforce_equal(&mut result[2 * EXT_DEGREE..3 * EXT_DEGREE], y3, &rhs);
+}
+
+/// Enforces that `r` = `p` + T\[d\] using the provided chord `slope`, where T is the row of a
+/// fixed-base table for the current window and d is the digit encoded by `selectors`; this
+/// requires 3 * 6 = 18 constraints of degree at most 5.
+///
+/// `table_row` must contain the values of the 16 * 12 periodic columns returned from
+/// [FixedBaseTable::get_periodic_column_values()] on the current step. `selectors` must contain
+/// 4 values encoding the digit starting with the least significant bit; these values are
+/// expected to be binary, and this must be enforced separately. The formulas are incomplete:
+/// `p` must not be the identity and must have an x coordinate distinct from the one of the
+/// selected entry.
+pub fn enforce_fixed_base_window_add<E: FieldElement + From<BaseElement>>(
+    result: &mut [E],
+    p: &[E],
+    selectors: &[E],
+    table_row: &[E],
+    slope: &[E],
+    r: &[E],
+) {
+    let q = select_table_entry(selectors, table_row);
+    enforce_point_addition(result, p, &q, slope, r, E::ONE);
+}
+
+/// Returns the entry of `table_row` selected by the binary `selectors`; the result is a
+/// polynomial of degree 4 in the selectors.
+fn select_table_entry<E: FieldElement>(selectors: &[E], table_row: &[E]) -> [E; POINT_WIDTH] {
+    debug_assert_eq!(WINDOW_BITS, selectors.len());
+    debug_assert_eq!(WINDOW_SIZE * POINT_WIDTH, table_row.len());
+
+    // compute the Lagrange basis over {0, 1}^4 at the selectors; the weight of digit d is 1 for
+    // the digit encoded by binary selectors and 0 for all other digits
+    let mut weights = [E::ZERO; WINDOW_SIZE];
+    weights[0] = E::ONE;
+    for (i, &bit) in selectors.iter().enumerate() {
+        let half = 1 << i;
+        for d in 0..half {
+            weights[d + half] = weights[d] * bit;
+            weights[d] *= E::ONE - bit;
+        }
+    }
+
+    let mut result = [E::ZERO; POINT_WIDTH];
+    for (weight, entry) in weights.iter().zip(table_row.chunks(POINT_WIDTH)) {
+        for (value, &coordinate) in result.iter_mut().zip(entry) {
+            *value += *weight * coordinate;
+        }
+    }
+    result
+}
+
+/// Sets each of the 6 values in `result` to the difference between the corresponding
+/// coordinates of `a` and `b`.
+fn enforce_equal<E: FieldElement>(result: &mut [E], a: &[E], b: &[E]) {
+    for i in 0..EXT_DEGREE {
+        result[i] = a[i] - b[i];
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Hash function and constraint helpers for the GMiMC_erf permutation over the 64-bit field.
+
+use air::gadgets::{are_equal, EvaluationResult};
+use math::{fields::f64::BaseElement, FieldElement};
+use utils::collections::Vec;
+
+/// Function state is set to 4 field elements; 2 elements are reserved for rate and 2 elements are
+/// reserved for capacity.
+pub const STATE_WIDTH: usize = 4;
+
+/// Number of state elements into which inputs are absorbed.
+pub const RATE_WIDTH: usize = 2;
+
+/// The number of rounds is set to 62 to provide 128-bit security level. Resisting interpolation
+/// attacks with S-Box degree 7 requires ceil(128 / log2(7)) = 46 rounds; we add STATE_WIDTH^2 = 16
+/// rounds on top of that to account for the slow diffusion of the unbalanced Feistel network.
+/// The resulting number of rounds also leaves room for two extra steps in a 64-step cycle.
+pub const NUM_ROUNDS: usize = 62;
+
+/// Minimum cycle length required to describe GMiMC permutation.
+pub const CYCLE_LENGTH: usize = 64;
+
+// HASH FUNCTION
+// ================================================================================================
+
+/// Implementation of GMiMC_erf hash function with a 4 element state and 62 rounds. Accepts a
+/// 2-element input, and returns a 2-element digest.
+pub fn hash(value: [BaseElement; 2], result: &mut [BaseElement]) {
+    let mut state = [BaseElement::ZERO; STATE_WIDTH];
+    state[..RATE_WIDTH].copy_from_slice(&value);
+    apply_permutation(&mut state);
+    result.copy_from_slice(&state[..RATE_WIDTH]);
+}
+
+// GMIMC PERMUTATION
+// ================================================================================================
+
+/// Applies GMiMC_erf permutation to the provided state.
+pub fn apply_permutation(state: &mut [BaseElement; STATE_WIDTH]) {
+    for i in 0..NUM_ROUNDS {
+        apply_round(state, i);
+    }
+}
+
+/// GMiMC_erf round function; implementation based on the expanding round function (erf)
+/// construction from <https://eprint.iacr.org/2019/397.pdf>.
+///
+/// The round function is applied as follows:
+/// * the round constant is added to the first state element and the result is raised to the
+///   power of ALPHA;
+/// * the resulting value is added to all other state elements;
+/// * the state is rotated by one element to the left.
+///
+/// Round constants are selected based on `step % CYCLE_LENGTH`; the step must correspond to one
+/// of the first NUM_ROUNDS steps of a cycle.
+pub fn apply_round(state: &mut [BaseElement], step: usize) {
+    let f = (state[0] + ARK[step % CYCLE_LENGTH]).exp(ALPHA.into());
+    let first = state[0];
+    for i in 0..(STATE_WIDTH - 1) {
+        state[i] = state[i + 1] + f;
+    }
+    state[STATE_WIDTH - 1] = first;
+}
+
+// CONSTRAINTS
+// ================================================================================================
+
+/// when flag = 1, enforces constraints for a single round of GMiMC_erf hash function
+pub fn enforce_round<E: FieldElement + From<BaseElement>>(
+    result: &mut [E],
+    current: &[E],
+    next: &[E],
+    ark: E,
+    flag: E,
+) {
+    // compute the value of the round function applied to the first state element
+    let f = (current[0] + ark).exp(ALPHA.into());
+
+    // make sure that all other elements were shifted and incremented by the round function
+    // value, and that the first element was moved to the end of the state
+    for i in 0..(STATE_WIDTH - 1) {
+        result.agg_constraint(i, flag, are_equal(next[i], current[i + 1] + f));
+    }
+    result.agg_constraint(
+        STATE_WIDTH - 1,
+        flag,
+        are_equal(next[STATE_WIDTH - 1], current[0]),
+    );
+}
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Returns GMiMC round constants arranged in column-major form.
+///
+/// A single column is returned; the column contains NUM_ROUNDS round constants followed by
+/// zeros for the remaining steps of the cycle.
+pub fn get_round_constants() -> Vec<Vec<BaseElement>> {
+    let mut column = ARK.to_vec();
+    column.resize(CYCLE_LENGTH, BaseElement::ZERO);
+    vec![column]
+}
+
+// GMIMC CONSTANTS
+// ================================================================================================
+
+/// S-Box power; 7 is the smallest power for which x^ALPHA is a permutation in the f64 field.
+const ALPHA: u32 = 7;
+
+/// GMiMC round constants; generated by reading 8-byte little-endian chunks from the output of
+/// SHAKE128("GMiMC_erf_f64_t4") and discarding values which are not valid field elements.
+const ARK: [BaseElement; NUM_ROUNDS] = [
+    BaseElement::new(17600201848501995754),
+    BaseElement::new(12499408268447184541),
+    BaseElement::new(12198848087210516916),
+    BaseElement::new(7311318267681294149),
+    BaseElement::new(1844855238980256605),
+    BaseElement::new(15287623408000042272),
+    BaseElement::new(10145477287126173265),
+    BaseElement::new(7200176638192416021),
+    BaseElement::new(8505513997076874811),
+    BaseElement::new(8102804642028077085),
+    BaseElement::new(5595018820885905127),
+    BaseElement::new(561333594400512862),
+    BaseElement::new(10813243576769136417),
+    BaseElement::new(10121126054028363366),
+    BaseElement::new(8623807211522369252),
+    BaseElement::new(15848074384688725948),
+    BaseElement::new(4569423232862046152),
+    BaseElement::new(1116132824176663767),
+    BaseElement::new(18133622045081179814),
+    BaseElement::new(5967003180204464123),
+    BaseElement::new(14207258661253386249),
+    BaseElement::new(444328606898610358),
+    BaseElement::new(17919301113499317512),
+    BaseElement::new(9559947388730525242),
+    BaseElement::new(2112400633923123162),
+    BaseElement::new(15311172821179897204),
+    BaseElement::new(13939808458031664906),
+    BaseElement::new(3284523864414533848),
+    BaseElement::new(11544632618030648993),
+    BaseElement::new(11634567810531181747),
+    BaseElement::new(9464039476172591876),
+    BaseElement::new(11326211278890685172),
+    BaseElement::new(15774076098330596026),
+    BaseElement::new(17640391306536980468),
+    BaseElement::new(16458756221814489930),
+    BaseElement::new(18244758840347482783),
+    BaseElement::new(9341628686089573805),
+    BaseElement::new(7898243541135665087),
+    BaseElement::new(3678401571026594142),
+    BaseElement::new(7567644434692567577),
+    BaseElement::new(54966268336656312),
+    BaseElement::new(7111461561473306192),
+    BaseElement::new(16383017038018646678),
+    BaseElement::new(11407270662949823002),
+    BaseElement::new(8064123789079841493),
+    BaseElement::new(2388665500837699499),
+    BaseElement::new(15028387638904606244),
+    BaseElement::new(17447413661401942814),
+    BaseElement::new(17729068612794093676),
+    BaseElement::new(1832191286074754831),
+    BaseElement::new(17142092046357325595),
+    BaseElement::new(7737634952191975410),
+    BaseElement::new(13439500357573592637),
+    BaseElement::new(2666096040951237638),
+    BaseElement::new(7057835176345023160),
+    BaseElement::new(3095763868892782769),
+    BaseElement::new(11788892384031929201),
+    BaseElement::new(3552256850119969150),
+    BaseElement::new(12438444045977068401),
+    BaseElement::new(2826701452910676721),
+    BaseElement::new(15201115078118156925),
+    BaseElement::new(16103838612015845918),
+];
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_permutation, hash, BaseElement, FieldElement, STATE_WIDTH};
+
+    #[test]
+    fn apply_permutation_kat() {
+        let mut state: [BaseElement; STATE_WIDTH] = [
+            BaseElement::new(0),
+            BaseElement::new(1),
+            BaseElement::new(2),
+            BaseElement::new(3),
+        ];
+
+        apply_permutation(&mut state);
+
+        // expected values are obtained by executing python reference implementation code
+        let expected = [
+            BaseElement::new(8480872022354986195),
+            BaseElement::new(5548893906526564263),
+            BaseElement::new(5562175359104941684),
+            BaseElement::new(4992798745840252544),
+        ];
+
+        assert_eq!(expected, state);
+    }
+
+    #[test]
+    fn hash_kat() {
+        let mut result = [BaseElement::ZERO; 2];
+        hash([BaseElement::new(1), BaseElement::new(2)], &mut result);
+
+        // expected values are obtained by executing python reference implementation code
+        let expected = [
+            BaseElement::new(16652851669436053019),
+            BaseElement::new(8519218480056578498),
+        ];
+
+        assert_eq!(expected, result);
+    }
+}
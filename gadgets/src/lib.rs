@@ -0,0 +1,45 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains hash functions and elliptic curve helpers together with the constraints
+//! needed to verify their computations in an execution trace.
+//!
+//! Every module describes a single primitive: it exposes a native implementation of the
+//! primitive (used to fill execution traces), and functions evaluating transition constraints
+//! of the primitive (used in [Air](air::Air) implementations). The following primitives are
+//! currently available:
+//!
+//! * [rescue] - the Rescue hash function over the 128-bit field, with 2-element digests.
+//! * [rp64_256] - the round function of the [Rp64_256](crypto::hashers::Rp64_256) hash
+//!   function over the 64-bit field.
+//! * [gmimc] - the GMiMC_erf hash function over the 64-bit field.
+//! * [sponge] - a sponge construction over the Rescue permutations of the 128-bit, 64-bit, and
+//!   62-bit fields, with helpers describing how the sponge is laid out in an execution trace.
+//! * [ecc] - point doubling, addition, and fixed-base scalar multiplication on the Cheetah
+//!   curve defined over a sextic extension of the 64-bit field.
+//!
+//! Constraint evaluation helpers of this crate build on the helpers from
+//! [air::gadgets](air::gadgets) and aggregate constraints via [EvaluationResult] trait.
+//!
+//! This crate can be compiled in `no_std` environments (with `alloc`) by disabling the default
+//! `std` feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+#[macro_use]
+extern crate alloc;
+
+pub use air::gadgets::EvaluationResult;
+
+pub mod ecc;
+pub mod gmimc;
+pub mod rescue;
+pub mod rp64_256;
+pub mod sponge;
+
+#[cfg(test)]
+mod tests;
@@ -4,17 +4,21 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::utils::{are_equal, EvaluationResult};
+//! Hash function and constraint helpers for the Rescue permutation over the 128-bit field.
+
+use air::gadgets::{are_equal, EvaluationResult};
 use core::slice;
-use winterfell::{
-    crypto::{Digest, Hasher},
-    math::{fields::f128::BaseElement, FieldElement},
-    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+use crypto::{Digest, Hasher};
+use math::{fields::f128::BaseElement, FieldElement};
+use utils::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
 };
 
 /// Function state is set to 6 field elements or 96 bytes; 4 elements are reserved for rate
 /// and 2 elements are reserved for capacity.
 pub const STATE_WIDTH: usize = 6;
+
+/// Number of state elements into which inputs are absorbed.
 pub const RATE_WIDTH: usize = 4;
 
 /// Two elements (32-bytes) are returned as digest.
@@ -32,11 +36,13 @@ pub const CYCLE_LENGTH: usize = 8;
 // TYPES AND INTERFACES
 // ================================================================================================
 
+/// Rescue hash function with a 6 element state and 7 rounds; produces 2-element digests.
 pub struct Rescue128 {
     state: [BaseElement; STATE_WIDTH],
     idx: usize,
 }
 
+/// Digest of the [Rescue128] hash function.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
 pub struct Hash([BaseElement; DIGEST_SIZE]);
 
@@ -123,11 +129,12 @@ impl Hasher for Rescue128 {
 // ================================================================================================
 
 impl Hash {
+    /// Returns a new digest consisting of the provided elements.
     pub fn new(v1: BaseElement, v2: BaseElement) -> Self {
         Hash([v1, v2])
     }
 
-    #[allow(dead_code)]
+    /// Returns this digest serialized into 32 bytes.
     #[allow(clippy::wrong_self_convention)]
     pub fn to_bytes(&self) -> [u8; 32] {
         let mut bytes = [0; 32];
@@ -136,11 +143,13 @@ impl Hash {
         bytes
     }
 
+    /// Returns the elements of this digest.
     #[allow(clippy::wrong_self_convention)]
     pub fn to_elements(&self) -> [BaseElement; DIGEST_SIZE] {
         self.0
     }
 
+    /// Returns the provided digests as a slice of their elements (without copying).
     pub fn hashes_as_elements(hashes: &[Hash]) -> &[BaseElement] {
         let p = hashes.as_ptr();
         let len = hashes.len() * DIGEST_SIZE;
@@ -0,0 +1,132 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Constraint helpers for the Rescue Prime (Rp64_256) permutation over the 64-bit field.
+//!
+//! Unlike [Rescue128](super::rescue::Rescue128), this module does not define its own hash
+//! function: it describes the permutation used by
+//! [Rp64_256](crypto::hashers::Rp64_256) so that digests computed in an execution
+//! trace match the ones computed with the hasher.
+
+use air::gadgets::{are_equal, EvaluationResult};
+use core::ops::Range;
+use crypto::hashers::Rp64_256;
+use math::{fields::f64::BaseElement, FieldElement};
+use utils::collections::Vec;
+
+/// Sponge state is set to 12 field elements; 8 elements are reserved for rate and the
+/// remaining 4 elements are reserved for capacity.
+pub const STATE_WIDTH: usize = Rp64_256::STATE_WIDTH;
+
+/// The capacity portion of the state is located in elements 0, 1, 2, and 3.
+pub const CAPACITY_RANGE: Range<usize> = Rp64_256::CAPACITY_RANGE;
+
+/// The rate portion of the state is located in elements 4 through 11 (inclusive).
+pub const RATE_RANGE: Range<usize> = Rp64_256::RATE_RANGE;
+
+/// The digest can be read from state elements 4, 5, 6, and 7.
+pub const DIGEST_RANGE: Range<usize> = Rp64_256::DIGEST_RANGE;
+
+/// The number of rounds of the permutation.
+pub const NUM_ROUNDS: usize = Rp64_256::NUM_ROUNDS;
+
+/// Minimum cycle length required to describe the permutation.
+pub const CYCLE_LENGTH: usize = 8;
+
+// PERMUTATION
+// ================================================================================================
+
+/// Applies a single round of the permutation to the provided state.
+pub fn apply_round(state: &mut [BaseElement; STATE_WIDTH], round: usize) {
+    Rp64_256::apply_round(state, round);
+}
+
+// CONSTRAINTS
+// ================================================================================================
+
+/// When flag = 1, enforces constraints for a single round of the permutation; `ark` must
+/// contain the 2 * 12 round constants of the round.
+pub fn enforce_round<E: FieldElement + From<BaseElement>>(
+    result: &mut [E],
+    current: &[E],
+    next: &[E],
+    ark: &[E],
+    flag: E,
+) {
+    // compute the state that should result from applying the first half of the round to the
+    // current state of the computation
+    let mut step1 = [E::ZERO; STATE_WIDTH];
+    step1.copy_from_slice(current);
+    apply_sbox(&mut step1);
+    let mut step1 = apply_mds(&step1, &Rp64_256::MDS);
+    for i in 0..STATE_WIDTH {
+        step1[i] += ark[i];
+    }
+
+    // compute the state that should result from applying the inverse of the second half of
+    // the round to the next step of the computation
+    let mut step2 = [E::ZERO; STATE_WIDTH];
+    step2.copy_from_slice(next);
+    for i in 0..STATE_WIDTH {
+        step2[i] -= ark[STATE_WIDTH + i];
+    }
+    let mut step2 = apply_mds(&step2, &Rp64_256::INV_MDS);
+    apply_sbox(&mut step2);
+
+    // make sure that the results are equal
+    for i in 0..STATE_WIDTH {
+        result.agg_constraint(i, flag, are_equal(step2[i], step1[i]));
+    }
+}
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Returns round constants arranged in column-major form; the first 12 columns contain the
+/// constants added in the first half of each round, and the remaining 12 columns contain the
+/// constants added in the second half.
+pub fn get_round_constants() -> Vec<Vec<BaseElement>> {
+    let mut constants = Vec::new();
+    for _ in 0..(STATE_WIDTH * 2) {
+        constants.push(vec![BaseElement::ZERO; CYCLE_LENGTH]);
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    for i in 0..NUM_ROUNDS {
+        for j in 0..STATE_WIDTH {
+            constants[j][i] = Rp64_256::ARK1[i][j];
+            constants[j + STATE_WIDTH][i] = Rp64_256::ARK2[i][j];
+        }
+    }
+
+    constants
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+fn apply_sbox<E: FieldElement>(state: &mut [E; STATE_WIDTH]) {
+    for v in state.iter_mut() {
+        let v2 = v.square();
+        let v4 = v2.square();
+        *v *= v4 * v2;
+    }
+}
+
+#[inline(always)]
+fn apply_mds<E: FieldElement + From<BaseElement>>(
+    state: &[E; STATE_WIDTH],
+    matrix: &[[BaseElement; STATE_WIDTH]; STATE_WIDTH],
+) -> [E; STATE_WIDTH] {
+    let mut result = [E::ZERO; STATE_WIDTH];
+    for (i, row) in matrix.iter().enumerate() {
+        for (&s, &m) in state.iter().zip(row.iter()) {
+            result[i] += E::from(m) * s;
+        }
+    }
+    result
+}
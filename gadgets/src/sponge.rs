@@ -0,0 +1,257 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Sponge construction over Rescue permutations, usable both on the host and in an execution
+//! trace.
+//!
+//! The sponge absorbs field elements into the rate portion of its state, applying the permutation
+//! every time the rate is filled up. Before the first squeeze, the input is padded with a single
+//! ONE element followed by as many ZERO elements as needed to fill up the last rate block, and
+//! the permutation is applied once more. Thus, absorbing $n$ elements into a sponge with rate $r$
+//! always takes $\lfloor n / r \rfloor + 1$ permutations, and the input is never ambiguous: two
+//! inputs which differ only in trailing ZERO elements are padded into different blocks.
+//!
+//! Since the number of absorbed elements does not need to be known upfront, outputs of the sponge
+//! differ from the outputs of [ElementHasher::hash_elements()](crypto::ElementHasher) of the
+//! [Rp64_256] and [Rp62_248] hash functions, which encode the number of elements in the capacity
+//! portion of the state instead of padding the input.
+//!
+//! Squeezed elements are read from the rate portion of the state; once all rate elements have
+//! been read, the permutation is applied again without absorbing anything.
+//!
+//! In an execution trace, each permutation occupies one cycle of
+//! [SpongePermutation::CYCLE_LENGTH] rows: the first row of a cycle holds the state with the next
+//! padded block already added to its rate portion, rounds of the permutation are applied between
+//! the following rows, and the transition from the last row of a cycle to the first row of the
+//! next cycle absorbs the next block (see [RescueSponge::enforce_absorption()]).
+
+use crate::rescue::{self, Rescue128};
+use air::gadgets::{are_equal, EvaluationResult};
+use core::{convert::TryInto, marker::PhantomData, ops::Range};
+use crypto::hashers::{Rp62_248, Rp64_256};
+use math::{fields, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// SPONGE PERMUTATION
+// ================================================================================================
+
+/// Describes a permutation over which a [RescueSponge] can be instantiated.
+pub trait SpongePermutation {
+    /// Field over which the permutation is defined.
+    type BaseField: StarkField;
+
+    /// Number of field elements in the state of the permutation.
+    const STATE_WIDTH: usize;
+
+    /// Range of state elements into which inputs are absorbed and from which outputs are
+    /// squeezed.
+    const RATE_RANGE: Range<usize>;
+
+    /// Number of execution trace rows needed to describe a single application of the
+    /// permutation.
+    const CYCLE_LENGTH: usize;
+
+    /// Applies the permutation to the provided state.
+    fn apply_permutation(state: &mut [Self::BaseField]);
+}
+
+impl SpongePermutation for Rescue128 {
+    type BaseField = fields::f128::BaseElement;
+
+    const STATE_WIDTH: usize = rescue::STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = 0..rescue::RATE_WIDTH;
+    const CYCLE_LENGTH: usize = rescue::CYCLE_LENGTH;
+
+    fn apply_permutation(state: &mut [Self::BaseField]) {
+        rescue::apply_permutation(state.try_into().expect("invalid state width"));
+    }
+}
+
+impl SpongePermutation for Rp64_256 {
+    type BaseField = fields::f64::BaseElement;
+
+    const STATE_WIDTH: usize = Rp64_256::STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = Rp64_256::RATE_RANGE;
+    const CYCLE_LENGTH: usize = 8;
+
+    fn apply_permutation(state: &mut [Self::BaseField]) {
+        Rp64_256::apply_permutation(state.try_into().expect("invalid state width"));
+    }
+}
+
+impl SpongePermutation for Rp62_248 {
+    type BaseField = fields::f62::BaseElement;
+
+    const STATE_WIDTH: usize = Rp62_248::STATE_WIDTH;
+    const RATE_RANGE: Range<usize> = Rp62_248::RATE_RANGE;
+    const CYCLE_LENGTH: usize = 8;
+
+    fn apply_permutation(state: &mut [Self::BaseField]) {
+        Rp62_248::apply_permutation(state.try_into().expect("invalid state width"));
+    }
+}
+
+// RESCUE SPONGE
+// ================================================================================================
+
+/// Sponge construction over the permutation `P`.
+///
+/// The state of a new sponge is set to all zeros. Elements can be absorbed via any number of
+/// calls to [RescueSponge::absorb()], after which any number of elements can be squeezed via
+/// [RescueSponge::squeeze()]; absorbing elements after squeezing has started is not supported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RescueSponge<P: SpongePermutation> {
+    state: Vec<P::BaseField>,
+    idx: usize,
+    squeezing: bool,
+    _permutation: PhantomData<P>,
+}
+
+impl<P: SpongePermutation> RescueSponge<P> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new sponge with the state initialized to all zeros.
+    pub fn new() -> Self {
+        RescueSponge {
+            state: vec![P::BaseField::ZERO; P::STATE_WIDTH],
+            idx: 0,
+            squeezing: false,
+            _permutation: PhantomData,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of elements absorbed or squeezed per application of the permutation.
+    pub fn rate_width() -> usize {
+        P::RATE_RANGE.len()
+    }
+
+    /// Returns the current state of this sponge.
+    pub fn state(&self) -> &[P::BaseField] {
+        &self.state
+    }
+
+    // SPONGE OPERATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs the provided elements into the state of this sponge.
+    ///
+    /// # Panics
+    /// Panics if elements have already been squeezed from this sponge.
+    pub fn absorb(&mut self, elements: &[P::BaseField]) {
+        assert!(
+            !self.squeezing,
+            "cannot absorb elements after squeezing has started"
+        );
+        for &element in elements {
+            self.state[P::RATE_RANGE.start + self.idx] += element;
+            self.idx += 1;
+            if self.idx == Self::rate_width() {
+                P::apply_permutation(&mut self.state);
+                self.idx = 0;
+            }
+        }
+    }
+
+    /// Squeezes the specified number of elements from this sponge.
+    ///
+    /// On the first call, the absorbed input is padded and the permutation is applied to the
+    /// last padded block.
+    pub fn squeeze(&mut self, num_elements: usize) -> Vec<P::BaseField> {
+        if !self.squeezing {
+            self.state[P::RATE_RANGE.start + self.idx] += P::BaseField::ONE;
+            P::apply_permutation(&mut self.state);
+            self.idx = 0;
+            self.squeezing = true;
+        }
+
+        let mut result = Vec::with_capacity(num_elements);
+        for _ in 0..num_elements {
+            if self.idx == Self::rate_width() {
+                P::apply_permutation(&mut self.state);
+                self.idx = 0;
+            }
+            result.push(self.state[P::RATE_RANGE.start + self.idx]);
+            self.idx += 1;
+        }
+        result
+    }
+
+    /// Absorbs the provided elements into a new sponge and squeezes the specified number of
+    /// elements from it.
+    pub fn hash(elements: &[P::BaseField], num_outputs: usize) -> Vec<P::BaseField> {
+        let mut sponge = Self::new();
+        sponge.absorb(elements);
+        sponge.squeeze(num_outputs)
+    }
+
+    // CIRCUIT HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of permutations needed to absorb the specified number of elements and
+    /// to squeeze up to [RescueSponge::rate_width()] elements.
+    ///
+    /// Each additional group of up to [RescueSponge::rate_width()] squeezed elements requires one
+    /// more permutation, which can be described in the trace as absorbing a block of all ZEROs.
+    pub fn num_permutations(num_elements: usize) -> usize {
+        num_elements / Self::rate_width() + 1
+    }
+
+    /// Returns the number of execution trace rows needed to absorb the specified number of
+    /// elements and to squeeze up to [RescueSponge::rate_width()] elements.
+    pub fn num_rows(num_elements: usize) -> usize {
+        Self::num_permutations(num_elements) * P::CYCLE_LENGTH
+    }
+
+    /// Pads the provided elements and splits them into blocks of [RescueSponge::rate_width()]
+    /// elements each.
+    ///
+    /// The first block is the rate portion of the first row of the sponge computation in the
+    /// trace (the capacity portion of this row is all ZEROs), and each of the following blocks
+    /// is absorbed at the start of the subsequent permutation cycles.
+    pub fn pad(elements: &[P::BaseField]) -> Vec<Vec<P::BaseField>> {
+        let num_blocks = Self::num_permutations(elements.len());
+        let mut padded = elements.to_vec();
+        padded.push(P::BaseField::ONE);
+        padded.resize(num_blocks * Self::rate_width(), P::BaseField::ZERO);
+        padded
+            .chunks(Self::rate_width())
+            .map(|block| block.to_vec())
+            .collect()
+    }
+
+    /// When flag = 1, enforces that the `next` state is obtained from the `current` state by
+    /// adding the `block` to the rate portion of the state and leaving the capacity portion
+    /// unchanged; writes [SpongePermutation::STATE_WIDTH] constraints of degree 1 into `result`.
+    ///
+    /// This constraint should be enforced on the transition from the last row of one permutation
+    /// cycle to the first row of the next one.
+    pub fn enforce_absorption<E: FieldElement + From<P::BaseField>>(
+        result: &mut [E],
+        current: &[E],
+        next: &[E],
+        block: &[E],
+        flag: E,
+    ) {
+        for i in 0..P::STATE_WIDTH {
+            let expected = if P::RATE_RANGE.contains(&i) {
+                current[i] + block[i - P::RATE_RANGE.start]
+            } else {
+                current[i]
+            };
+            result.agg_constraint(i, flag, are_equal(next[i], expected));
+        }
+    }
+}
+
+impl<P: SpongePermutation> Default for RescueSponge<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,305 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{
+    ecc,
+    rescue::{self, Rescue128},
+    sponge::RescueSponge,
+};
+use crypto::hashers::{Rp62_248, Rp64_256};
+use math::{
+    curves::cheetah::{AffinePoint, ProjectivePoint, Scalar},
+    fields::{f128, f62, f64::BaseElement},
+    FieldElement,
+};
+
+// CHEETAH CURVE ARITHMETIC
+// ================================================================================================
+
+#[test]
+fn ecc_ext_field_inv() {
+    let a = [1u64, 2, 3, 4, 5, 6].map(BaseElement::new);
+    let mut one = [BaseElement::ZERO; ecc::EXT_DEGREE];
+    one[0] = BaseElement::ONE;
+    assert_eq!(one, ecc::mul(&a, &ecc::inv(&a)));
+}
+
+#[test]
+fn ecc_double_and_add() {
+    // this also checks that the curve and extension field parameters used by the helpers match
+    // the ones used by the curve implementation
+    let g = AffinePoint::generator();
+    let g2 = AffinePoint::from(ProjectivePoint::from(g).double());
+    let g3 = g * Scalar::from(3u8);
+
+    let (result, _) = ecc::double(&ecc::point_to_elements(&g));
+    assert_eq!(ecc::point_to_elements(&g2), result);
+
+    let (result, _) = ecc::add_points(&ecc::point_to_elements(&g2), &ecc::point_to_elements(&g));
+    assert_eq!(ecc::point_to_elements(&g3), result);
+}
+
+#[test]
+fn ecc_point_constraints() {
+    let g = ecc::point_to_elements(&AffinePoint::generator());
+    let (g2, dbl_slope) = ecc::double(&g);
+    let (g3, add_slope) = ecc::add_points(&g2, &g);
+    let zero = [BaseElement::ZERO; 3 * ecc::EXT_DEGREE];
+
+    let mut result = [BaseElement::ZERO; 3 * ecc::EXT_DEGREE];
+    ecc::enforce_point_doubling(&mut result, &g, &dbl_slope, &g2);
+    assert_eq!(zero, result);
+
+    ecc::enforce_point_addition(&mut result, &g2, &g, &add_slope, &g3, BaseElement::ONE);
+    assert_eq!(zero, result);
+
+    // when the flag is not set, the point must be copied over
+    ecc::enforce_point_addition(&mut result, &g2, &g, &add_slope, &g2, BaseElement::ZERO);
+    assert_eq!(zero, result);
+
+    ecc::enforce_point_addition(&mut result, &g2, &g, &add_slope, &g2, BaseElement::ONE);
+    assert_ne!(zero, result);
+}
+
+#[test]
+fn ecc_fixed_base_table() {
+    let g = AffinePoint::generator();
+    let table = ecc::compute_fixed_base_table(g, 3);
+    assert_eq!(3, table.num_windows());
+
+    // the entry for window w and digit d is (d + 1) * 16^w * G
+    for w in 0..3 {
+        for d in 0..ecc::WINDOW_SIZE {
+            let expected = g * Scalar::from(((d + 1) << (4 * w)) as u32);
+            assert_eq!(ecc::point_to_elements(&expected), *table.entry(w, d));
+        }
+    }
+
+    // periodic columns hold the entries of consecutive windows, padded to a power of two
+    let columns = table.get_periodic_column_values();
+    assert_eq!(ecc::WINDOW_SIZE * ecc::POINT_WIDTH, columns.len());
+    for (j, column) in columns.iter().enumerate() {
+        assert_eq!(4, column.len());
+        let (d, i) = (j / ecc::POINT_WIDTH, j % ecc::POINT_WIDTH);
+        for w in 0..3 {
+            assert_eq!(table.entry(w, d)[i], column[w]);
+        }
+        assert_eq!(BaseElement::ZERO, column[3]);
+    }
+}
+
+#[test]
+fn ecc_fixed_base_multiplication() {
+    let g = AffinePoint::generator();
+    let num_windows = 64;
+    let table = ecc::compute_fixed_base_table(g, num_windows);
+    let columns = table.get_periodic_column_values();
+
+    let scalars = [
+        Scalar::from(1u8),
+        Scalar::from(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef_u128),
+        -Scalar::from(5u8),
+    ];
+    for scalar in scalars {
+        let scalar = scalar.to_bytes();
+
+        // compute k * G in the circuit, checking constraints for every window
+        let mut acc = *table.initial_point();
+        let zero = [BaseElement::ZERO; 3 * ecc::EXT_DEGREE];
+        let mut result = [BaseElement::ZERO; 3 * ecc::EXT_DEGREE];
+        for (w, digit) in ecc::scalar_to_windows(&scalar, num_windows)
+            .into_iter()
+            .enumerate()
+        {
+            let (out, slope) = table.add_window(&acc, w, digit);
+            let selectors = ecc::digit_to_selectors(digit);
+            let table_row = columns.iter().map(|column| column[w]).collect::<Vec<_>>();
+            ecc::enforce_fixed_base_window_add(
+                &mut result,
+                &acc,
+                &selectors,
+                &table_row,
+                &slope,
+                &out,
+            );
+            assert_eq!(zero, result);
+
+            // selecting a different entry must not satisfy the constraints
+            let selectors = ecc::digit_to_selectors(digit ^ 1);
+            ecc::enforce_fixed_base_window_add(
+                &mut result,
+                &acc,
+                &selectors,
+                &table_row,
+                &slope,
+                &out,
+            );
+            assert_ne!(zero, result);
+
+            acc = out;
+        }
+
+        // the result must be equal to k * G computed on the host, shifted by the table offset
+        let expected = ProjectivePoint::from(g.multiply_vartime(&scalar)) + table.result_offset();
+        assert_eq!(ecc::point_to_elements(&AffinePoint::from(expected)), acc);
+    }
+}
+
+// RESCUE SPONGE
+// ================================================================================================
+
+#[test]
+fn rescue_sponge_known_answers() {
+    let elements = [1u128, 2, 3, 4, 5].map(f128::BaseElement::new);
+    let expected = [
+        f128::BaseElement::new(86548634401272891775040167456457214715),
+        f128::BaseElement::new(288559817322327186655042363211658963341),
+    ];
+    assert_eq!(
+        expected.to_vec(),
+        RescueSponge::<Rescue128>::hash(&elements, 2)
+    );
+
+    let elements = (1..=10).map(BaseElement::new).collect::<Vec<_>>();
+    let expected = [
+        1160536989352590960,
+        11708768826724727833,
+        5436218775690003257,
+        5251559005798314691,
+    ];
+    assert_eq!(
+        expected.map(BaseElement::new).to_vec(),
+        RescueSponge::<Rp64_256>::hash(&elements, 4)
+    );
+
+    let elements = (1..=8).map(f62::BaseElement::new).collect::<Vec<_>>();
+    let expected = [
+        3932096286698306950,
+        2165588141698901517,
+        1683905690732964595,
+        1204502967373795255,
+    ];
+    assert_eq!(
+        expected.map(f62::BaseElement::new).to_vec(),
+        RescueSponge::<Rp62_248>::hash(&elements, 4)
+    );
+}
+
+#[test]
+fn rescue_sponge_squeeze_beyond_rate() {
+    // squeezing 10 elements from a sponge with rate 8 requires one extra permutation
+    let expected = [
+        6230195213417350559,
+        9921761400744683214,
+        9329776444429813326,
+        11788342577191735653,
+        8840459225191508533,
+        4397657000127238486,
+        285309136410659151,
+        10683671188038625676,
+        13824661836540165391,
+        13853234872850625444,
+    ];
+    assert_eq!(
+        expected.map(BaseElement::new).to_vec(),
+        RescueSponge::<Rp64_256>::hash(&[], 10)
+    );
+
+    // squeezing in several calls produces the same elements
+    let mut sponge = RescueSponge::<Rp64_256>::new();
+    let mut result = sponge.squeeze(3);
+    result.append(&mut sponge.squeeze(7));
+    assert_eq!(expected.map(BaseElement::new).to_vec(), result);
+}
+
+#[test]
+fn rescue_sponge_padding() {
+    // absorbing in several calls is the same as absorbing all elements at once
+    let elements = (1..=10).map(BaseElement::new).collect::<Vec<_>>();
+    let mut sponge = RescueSponge::<Rp64_256>::new();
+    sponge.absorb(&elements[..3]);
+    sponge.absorb(&elements[3..]);
+    assert_eq!(
+        RescueSponge::<Rp64_256>::hash(&elements, 4),
+        sponge.squeeze(4)
+    );
+
+    // trailing zeros change the result, including when they fill up the rate
+    let mut padded = elements.clone();
+    padded.resize(16, BaseElement::ZERO);
+    assert_ne!(
+        RescueSponge::<Rp64_256>::hash(&elements, 4),
+        RescueSponge::<Rp64_256>::hash(&padded, 4)
+    );
+
+    assert_eq!(1, RescueSponge::<Rp64_256>::num_permutations(7));
+    assert_eq!(2, RescueSponge::<Rp64_256>::num_permutations(8));
+    assert_eq!(16, RescueSponge::<Rp64_256>::num_rows(10));
+
+    let blocks = RescueSponge::<Rp64_256>::pad(&elements);
+    assert_eq!(2, blocks.len());
+    assert_eq!(elements[..8].to_vec(), blocks[0]);
+    let mut expected = vec![BaseElement::ZERO; 8];
+    expected[..3].copy_from_slice(&[9, 10, 1].map(BaseElement::new));
+    assert_eq!(expected, blocks[1]);
+}
+
+#[test]
+#[should_panic(expected = "cannot absorb elements after squeezing has started")]
+fn rescue_sponge_absorb_after_squeeze() {
+    let mut sponge = RescueSponge::<Rescue128>::new();
+    sponge.squeeze(1);
+    sponge.absorb(&[f128::BaseElement::ONE]);
+}
+
+#[test]
+fn rescue_sponge_trace_layout() {
+    type Sponge = RescueSponge<Rescue128>;
+    let elements = [1u128, 2, 3, 4, 5, 6].map(f128::BaseElement::new);
+    let blocks = Sponge::pad(&elements);
+    let ark = rescue::get_round_constants();
+    let zero = [f128::BaseElement::ZERO; rescue::STATE_WIDTH];
+
+    // lay the sponge out in an execution trace, one permutation cycle per padded block
+    let mut rows = Vec::with_capacity(Sponge::num_rows(elements.len()));
+    let mut state = [f128::BaseElement::ZERO; rescue::STATE_WIDTH];
+    for block in blocks.iter() {
+        for (s, &b) in state.iter_mut().zip(block) {
+            *s += b;
+        }
+        rows.push(state);
+        for round in 0..rescue::NUM_ROUNDS {
+            rescue::apply_round(&mut state, round);
+            rows.push(state);
+        }
+    }
+    assert_eq!(Sponge::num_rows(elements.len()), rows.len());
+    assert_eq!(
+        Sponge::hash(&elements, 2),
+        rows[rows.len() - 1][..2].to_vec()
+    );
+
+    // round constraints hold within each cycle, and absorption constraints hold between cycles
+    let one = f128::BaseElement::ONE;
+    for step in 0..rows.len() - 1 {
+        let (current, next) = (&rows[step], &rows[step + 1]);
+        let mut result = [f128::BaseElement::ZERO; rescue::STATE_WIDTH];
+        if step % rescue::CYCLE_LENGTH < rescue::NUM_ROUNDS {
+            let ark_row: Vec<_> = ark.iter().map(|c| c[step % rescue::CYCLE_LENGTH]).collect();
+            rescue::enforce_round(&mut result, current, next, &ark_row, one);
+        } else {
+            let block = &blocks[step / rescue::CYCLE_LENGTH + 1];
+            Sponge::enforce_absorption(&mut result, current, next, block, one);
+            assert_eq!(zero, result);
+
+            // absorbing a different block must not satisfy the constraints
+            Sponge::enforce_absorption(&mut result, current, next, &blocks[0], one);
+            assert_ne!(zero, result);
+            result = zero;
+        }
+        assert_eq!(zero, result);
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_utils::rand_value;
+use std::time::Duration;
+use winter_math::curves::cheetah::{AffinePoint, ProjectivePoint, Scalar};
+
+const SIZES: [usize; 3] = [16, 128, 1024];
+const CHAIN_LENGTH: usize = 1 << 20;
+
+fn multi_scalar_mul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multi_scalar_mul");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    let g = ProjectivePoint::generator();
+    for &size in SIZES.iter() {
+        let points = (0..size)
+            .map(|_| AffinePoint::from(g * rand_value::<Scalar>()))
+            .collect::<Vec<_>>();
+        let scalars = (0..size)
+            .map(|_| rand_value::<Scalar>())
+            .collect::<Vec<_>>();
+
+        group.bench_function(BenchmarkId::new("naive", size), |bench| {
+            bench.iter(|| {
+                points
+                    .iter()
+                    .zip(scalars.iter())
+                    .map(|(point, scalar)| point * scalar)
+                    .sum::<ProjectivePoint>()
+            });
+        });
+
+        group.bench_function(BenchmarkId::new("pippenger", size), |bench| {
+            bench.iter(|| ProjectivePoint::multi_scalar_mul(&points, &scalars));
+        });
+    }
+
+    group.finish();
+}
+
+fn batch_add_chain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_add_chain");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    // build a chain of 2^20 distinct points; the points are obtained by repeatedly adding a
+    // random point to the generator, which is much faster than random scalar multiplications
+    let step = ProjectivePoint::generator() * rand_value::<Scalar>();
+    let mut acc = ProjectivePoint::generator();
+    let projective_points = (0..CHAIN_LENGTH)
+        .map(|_| {
+            acc += step;
+            acc
+        })
+        .collect::<Vec<_>>();
+    let mut points = vec![AffinePoint::identity(); CHAIN_LENGTH];
+    ProjectivePoint::batch_normalize(&projective_points, &mut points);
+
+    group.bench_function(BenchmarkId::new("naive", CHAIN_LENGTH), |bench| {
+        bench.iter(|| {
+            let mut acc = ProjectivePoint::identity();
+            let sums = points
+                .iter()
+                .map(|point| {
+                    acc += point;
+                    acc
+                })
+                .collect::<Vec<_>>();
+            let mut result = vec![AffinePoint::identity(); CHAIN_LENGTH];
+            ProjectivePoint::batch_normalize(&sums, &mut result);
+            result
+        });
+    });
+
+    group.bench_function(BenchmarkId::new("batched", CHAIN_LENGTH), |bench| {
+        bench.iter(|| AffinePoint::batch_add_chain(&points));
+    });
+
+    group.finish();
+}
+
+criterion_group!(curve_group, multi_scalar_mul, batch_add_chain);
+criterion_main!(curve_group);
@@ -9,7 +9,7 @@ use rand_utils::rand_vector;
 use std::time::Duration;
 use winter_math::{
     fft,
-    fields::{f128, f62, f64, CubeExtension, QuadExtension},
+    fields::{f128, f62, f64, CubeExtension, QuadExtension, SexticExtension},
     FieldElement, StarkField,
 };
 
@@ -109,6 +109,7 @@ fn bench_fft(c: &mut Criterion) {
     fft_evaluate_poly::<f128::BaseElement, QuadExtension<f128::BaseElement>>(c, "f128_quad");
 
     fft_evaluate_poly::<f64::BaseElement, CubeExtension<f64::BaseElement>>(c, "f64_cube");
+    fft_evaluate_poly::<f64::BaseElement, SexticExtension<f64::BaseElement>>(c, "f64_sextic");
 
     fft_interpolate_poly::<f62::BaseElement, f62::BaseElement>(c, "f62");
     fft_interpolate_poly::<f64::BaseElement, f64::BaseElement>(c, "f64");
@@ -12,10 +12,10 @@ use criterion::{
 use rand_utils::{rand_array, rand_value, rand_vector};
 use std::time::Duration;
 use winter_math::{
-    batch_inversion,
+    add_in_place, batch_inversion, batch_inversion_in_place,
     fields::{f128, f62, f64},
-    fields::{CubeExtension, QuadExtension},
-    ExtensibleField, FieldElement, StarkField,
+    fields::{CubeExtension, QuadExtension, SexticExtension},
+    mul_in_place, scalar_mul_acc, ExtensibleField, FieldElement, StarkField,
 };
 
 const SIZES: [usize; 3] = [262_144, 524_288, 1_048_576];
@@ -34,16 +34,83 @@ pub fn batch_inv(c: &mut Criterion) {
         group.bench_function(BenchmarkId::new("no_coeff", size), |bench| {
             bench.iter_with_large_drop(|| batch_inversion(&values));
         });
+
+        group.bench_function(BenchmarkId::new("in_place", size), |bench| {
+            bench.iter_batched_ref(
+                || values.clone(),
+                |values| batch_inversion_in_place(values),
+                BatchSize::LargeInput,
+            );
+        });
+
+        group.bench_function(BenchmarkId::new("per_element", size), |bench| {
+            bench.iter_with_large_drop(|| values.iter().map(|v| v.inv()).collect::<Vec<_>>());
+        });
     }
 
     group.finish();
 }
 
+// SLICE OPS
+// ================================================================================================
+
+pub fn slice_ops<E: FieldElement>(c: &mut Criterion, field_name: &str) {
+    let mut group = c.benchmark_group(format!("slice_ops/{field_name}"));
+    group.sample_size(10);
+
+    const SIZE: usize = 1_048_576;
+    let a = rand_vector::<E>(SIZE);
+    let b = rand_vector::<E>(SIZE);
+    let k = rand_value::<E>();
+
+    group.bench_function("add_in_place", |bench| {
+        bench.iter_batched_ref(|| a.clone(), |a| add_in_place(a, &b), BatchSize::LargeInput);
+    });
+
+    group.bench_function("add_per_element", |bench| {
+        bench.iter_batched_ref(
+            || a.clone(),
+            |a| a.iter_mut().zip(b.iter()).for_each(|(a, &b)| *a += b),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("mul_in_place", |bench| {
+        bench.iter_batched_ref(|| a.clone(), |a| mul_in_place(a, &b), BatchSize::LargeInput);
+    });
+
+    group.bench_function("mul_per_element", |bench| {
+        bench.iter_batched_ref(
+            || a.clone(),
+            |a| a.iter_mut().zip(b.iter()).for_each(|(a, &b)| *a *= b),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("scalar_mul_acc", |bench| {
+        bench.iter_batched_ref(
+            || a.clone(),
+            |a| scalar_mul_acc(a, k, &b),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("scalar_mul_acc_per_element", |bench| {
+        bench.iter_batched_ref(
+            || a.clone(),
+            |a| a.iter_mut().zip(b.iter()).for_each(|(a, &b)| *a += b * k),
+            BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
 // SEQUENTIAL OPS
 // ================================================================================================
 pub fn field_ops<B>(c: &mut Criterion, field_name: &str)
 where
-    B: StarkField + ExtensibleField<2> + ExtensibleField<3>,
+    B: StarkField + ExtensibleField<2> + ExtensibleField<3> + ExtensibleField<6>,
 {
     let mut group = c.benchmark_group(format!("field/{field_name}"));
 
@@ -142,6 +209,41 @@ where
             bench.iter(|| black_box(x) * black_box(y))
         });
     }
+
+    // --- sextic extension -----------------------------------------------------------------------
+
+    if SexticExtension::<B>::is_supported() {
+        group.bench_function("sextic/add", |bench| {
+            let x = rand_value::<SexticExtension<B>>();
+            let y = rand_value::<SexticExtension<B>>();
+            bench.iter(|| black_box(x) + black_box(y))
+        });
+
+        group.bench_function("sextic/double", |bench| {
+            let x = rand_value::<SexticExtension<B>>();
+            bench.iter(|| black_box(x).double())
+        });
+
+        group.bench_function("sextic/sub", |bench| {
+            let x = rand_value::<SexticExtension<B>>();
+            let y = rand_value::<SexticExtension<B>>();
+            bench.iter(|| black_box(x) - black_box(y))
+        });
+
+        group.bench_function("sextic/mul", |bench| {
+            let x = rand_value::<SexticExtension<B>>();
+            let y = rand_value::<SexticExtension<B>>();
+            bench.iter(|| black_box(x) * black_box(y))
+        });
+
+        group.bench_function("sextic/inv", |bench| {
+            let x = rand_value::<SexticExtension<B>>();
+            bench.iter(|| black_box(x).inv())
+        });
+
+        batch_ops::<SexticExtension<B>, WallTime>(&mut group, "sextic");
+        array_ops::<SexticExtension<B>, WallTime>(&mut group, "sextic");
+    }
 }
 
 // ARRAY OPS
@@ -284,8 +386,13 @@ fn bench_field_ops(c: &mut Criterion) {
     field_ops::<f128::BaseElement>(c, "f128");
 }
 
+fn bench_slice_ops(c: &mut Criterion) {
+    slice_ops::<f62::BaseElement>(c, "f62");
+    slice_ops::<f64::BaseElement>(c, "f64");
+}
+
 // CRITERION BOILERPLATE
 // ================================================================================================
 
-criterion_group!(field_group, batch_inv, bench_field_ops);
+criterion_group!(field_group, batch_inv, bench_field_ops, bench_slice_ops);
 criterion_main!(field_group);
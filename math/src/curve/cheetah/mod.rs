@@ -7,8 +7,8 @@
 use cheetah::Fp6;
 use cheetah::{AffinePoint as AffinePointInner, ProjectivePoint as ProjectivePointInner};
 use utils::{
-    collections::Vec, string::ToString, ByteReader, ByteWriter, Deserializable,
-    DeserializationError, Serializable,
+    collections::Vec, string::ToString, ByteReader, ByteWriter, CanonicalDeserializable,
+    CanonicalSerializable, Deserializable, DeserializationError, Serializable,
 };
 
 use core::borrow::Borrow;
@@ -19,8 +19,15 @@ use core::ops::{Add, AddAssign, Deref, DerefMut, Mul, MulAssign, Neg, Sub, SubAs
 use crate::fields::f64::BaseElement;
 use crate::StarkField;
 
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
 pub use cheetah::B;
 
+/// Number of bytes in the hash output used to derive a candidate x coordinate in
+/// [AffinePoint::hash_to_curve()]: 8 bytes for each of the 6 base field coefficients.
+const HASH_TO_CURVE_OUTPUT_BYTES: usize = 48;
+
 mod scalar;
 pub use scalar::Scalar;
 
@@ -249,11 +256,22 @@ impl<'b> AddAssign<&'b AffinePoint> for ProjectivePoint {
     }
 }
 
+// Scalar multiplication operators always use the constant-time path, since the scalar may be
+// secret; use AffinePoint::multiply_vartime() explicitly when the scalar is public.
+
+impl<'a, 'b> Mul<&'b Scalar> for &'a AffinePoint {
+    type Output = AffinePoint;
+
+    fn mul(self, rhs: &'b Scalar) -> AffinePoint {
+        self.multiply_ct(&rhs.to_bytes())
+    }
+}
+
 impl<'b> Mul<&'b Scalar> for AffinePoint {
     type Output = AffinePoint;
 
     fn mul(self, rhs: &'b Scalar) -> AffinePoint {
-        AffinePoint(self.0.mul(rhs.0).into())
+        self.multiply_ct(&rhs.to_bytes())
     }
 }
 
@@ -261,7 +279,7 @@ impl<'a> Mul<Scalar> for &'a AffinePoint {
     type Output = AffinePoint;
 
     fn mul(self, rhs: Scalar) -> AffinePoint {
-        AffinePoint(self.0.mul(rhs.0).into())
+        self.multiply_ct(&rhs.to_bytes())
     }
 }
 
@@ -269,7 +287,7 @@ impl Mul<Scalar> for AffinePoint {
     type Output = AffinePoint;
 
     fn mul(self, rhs: Scalar) -> AffinePoint {
-        AffinePoint(self.0.mul(rhs.0).into())
+        self.multiply_ct(&rhs.to_bytes())
     }
 }
 
@@ -304,7 +322,26 @@ impl AffinePoint {
         AffinePoint(AffinePointInner::generator())
     }
 
+    /// Constructs an `AffinePoint` element from the specified coordinates, returning None if
+    /// the coordinates do not describe a point on the curve, or if the point is not in the
+    /// prime-order subgroup.
+    ///
+    /// This should be used for all coordinates which do not come from a trusted source (e.g.,
+    /// witness data).
+    pub fn from_raw_coordinates_checked(elems: [BaseElement; 12]) -> Option<Self> {
+        let point = Self::from_raw_coordinates(elems);
+        if point.is_on_curve() && point.is_torsion_free() {
+            Some(point)
+        } else {
+            None
+        }
+    }
+
     /// Constructs an `AffinePoint` element without checking that it is a valid point.
+    ///
+    /// The resulting point may be off the curve or have a small-order component; use
+    /// [AffinePoint::from_raw_coordinates_checked()] unless the coordinates are trusted.
+    #[doc(hidden)]
     pub fn from_raw_coordinates(elems: [BaseElement; 12]) -> Self {
         let x = Fp6::new([
             elems[0].to_repr(),
@@ -336,11 +373,30 @@ impl AffinePoint {
         bool::from(self.0.is_on_curve())
     }
 
+    /// Multiplies this point by the scalar encoded in `by` in constant time.
+    ///
+    /// The running time of this method does not depend on the value of the scalar, and thus,
+    /// this method must be used whenever the scalar is secret (e.g., a secret key or a nonce).
+    /// This is also the method used by the `*` operator.
     #[must_use]
-    pub fn multiply(&self, by: &[u8; 32]) -> AffinePoint {
+    pub fn multiply_ct(&self, by: &[u8; 32]) -> AffinePoint {
+        AffinePoint(self.0.multiply(by))
+    }
+
+    /// Multiplies this point by the scalar encoded in `by` in variable time.
+    ///
+    /// This method is faster than [AffinePoint::multiply_ct()], but its running time
+    /// depends on the value of the scalar. It must be used only with public scalars (e.g., when
+    /// verifying a signature).
+    #[must_use]
+    pub fn multiply_vartime(&self, by: &[u8; 32]) -> AffinePoint {
         AffinePoint(self.0.multiply_vartime(by))
     }
 
+    /// Computes `self * by_lhs + rhs * by_rhs` in variable time.
+    ///
+    /// The running time of this method depends on the values of the scalars, and thus, it must
+    /// be used only with public scalars.
     #[must_use]
     pub fn multiply_double(
         &self,
@@ -362,6 +418,120 @@ impl AffinePoint {
     pub fn is_torsion_free(&self) -> bool {
         self.0.is_torsion_free().into()
     }
+
+    /// Deterministically maps the specified `msg` to a torsion-free point of the curve, using
+    /// `domain` for domain separation.
+    ///
+    /// The point is derived with a try-and-increment method: for a counter starting at zero,
+    /// the domain, the message and the counter are hashed with BLAKE3 to obtain six base field
+    /// elements, which are used as the x coordinate of a candidate point. The first candidate
+    /// lying on the curve (taken with its lexicographically smallest y coordinate) is multiplied
+    /// by the cofactor, and the result is returned unless it is the identity.
+    ///
+    /// The discrete logarithm of the resulting point with respect to the generator, or to any
+    /// other point derived this way, is unknown. This makes this method suitable for deriving
+    /// independent "nothing-up-my-sleeve" generators from labels.
+    ///
+    /// This method is not constant time, and must not be used with secret messages.
+    pub fn hash_to_curve(domain: &[u8], msg: &[u8]) -> AffinePoint {
+        use cheetah::CompressedPoint;
+
+        for counter in 0u32.. {
+            let mut hasher = blake3::Hasher::new();
+            hasher.update(&(domain.len() as u64).to_le_bytes());
+            hasher.update(domain);
+            hasher.update(msg);
+            hasher.update(&counter.to_le_bytes());
+            let mut output = [0u8; HASH_TO_CURVE_OUTPUT_BYTES];
+            hasher.finalize_xof().fill(&mut output);
+
+            // reduce each 8-byte chunk of the output into a base field element
+            let mut x = [0u64; 6];
+            for (x_i, chunk) in x.iter_mut().zip(output.chunks(8)) {
+                let value = u64::from_le_bytes(chunk.try_into().expect("invalid chunk length"));
+                *x_i = BaseElement::new(value).to_repr();
+            }
+
+            // a compressed point with all flags unset encodes the point with the specified x
+            // coordinate and the lexicographically smallest y coordinate, if such a point exists
+            let x_bytes = Fp6::new(x).to_bytes();
+            let mut bytes = AffinePointInner::identity().to_compressed().to_bytes();
+            bytes.fill(0);
+            bytes[..x_bytes.len()].copy_from_slice(&x_bytes);
+
+            let candidate = AffinePointInner::from_compressed(&CompressedPoint(bytes));
+            if bool::from(candidate.is_some()) {
+                let point = AffinePoint(candidate.unwrap()).clear_cofactor();
+                if !point.is_identity() {
+                    return point;
+                }
+            }
+        }
+
+        unreachable!("failed to map the message to a point of the curve")
+    }
+
+    /// Returns all prefix sums of the specified chain of points, i.e., the ith point of the
+    /// result is `points[0] + ... + points[i]`.
+    ///
+    /// The sums are computed with affine addition formulas in 2 * log2(n) passes. Additions
+    /// within a single pass are independent of each other, and thus, the slope denominators of
+    /// all additions in a pass are inverted with a single field inversion (Montgomery's trick).
+    /// This is much faster than accumulating the points in projective coordinates and
+    /// normalizing the results.
+    ///
+    /// Identity points are allowed, as are chains in which a point is added to itself or to its
+    /// negation.
+    pub fn batch_add_chain(points: &[AffinePoint]) -> Vec<AffinePoint> {
+        if points.len() <= 1 {
+            return points.to_vec();
+        }
+
+        // add up adjacent pairs of points, and compute the prefix sums of the results; the jth
+        // of these sums is equal to points[0] + ... + points[2j + 1]
+        let pairs = points
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<Vec<_>>();
+        let pair_sums = Self::batch_add_chain(&batch_add_pairs(&pairs));
+
+        // all other prefix sums are obtained by adding a single point to one of these sums
+        let pairs = pair_sums
+            .iter()
+            .zip(points.iter().skip(2).step_by(2))
+            .map(|(&sum, &point)| (sum, point))
+            .collect::<Vec<_>>();
+        let other_sums = batch_add_pairs(&pairs);
+
+        let mut result = vec![points[0]];
+        for (i, &sum) in pair_sums.iter().enumerate() {
+            result.push(sum);
+            if let Some(&sum) = other_sums.get(i) {
+                result.push(sum);
+            }
+        }
+        result
+    }
+
+    /// Returns prefix sums of each of the specified chains of points; the chains are processed
+    /// independently of each other as described in [AffinePoint::batch_add_chain()].
+    ///
+    /// When `concurrent` feature is enabled, chains are processed in multiple threads.
+    pub fn batch_add_chains(chains: &[Vec<AffinePoint>]) -> Vec<Vec<AffinePoint>> {
+        #[cfg(not(feature = "concurrent"))]
+        let result = chains
+            .iter()
+            .map(|chain| Self::batch_add_chain(chain))
+            .collect();
+
+        #[cfg(feature = "concurrent")]
+        let result = chains
+            .par_iter()
+            .map(|chain| Self::batch_add_chain(chain))
+            .collect();
+
+        result
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -503,19 +673,14 @@ impl<'b> AddAssign<&'b ProjectivePoint> for ProjectivePoint {
     }
 }
 
-impl<'a, 'b> Mul<&'b Scalar> for &'a ProjectivePoint {
-    type Output = ProjectivePoint;
-
-    fn mul(self, other: &'b Scalar) -> Self::Output {
-        self.multiply(&other.to_bytes())
-    }
-}
+// Scalar multiplication operators always use the constant-time path, since the scalar may be
+// secret; use ProjectivePoint::multiply_vartime() explicitly when the scalar is public.
 
-impl<'a, 'b> Mul<&'b Scalar> for &'a AffinePoint {
+impl<'a, 'b> Mul<&'b Scalar> for &'a ProjectivePoint {
     type Output = ProjectivePoint;
 
-    fn mul(self, other: &'b Scalar) -> Self::Output {
-        ProjectivePoint::from(self).multiply(&other.to_bytes())
+    fn mul(self, other: &'b Scalar) -> ProjectivePoint {
+        self.multiply_ct(&other.to_bytes())
     }
 }
 
@@ -523,7 +688,7 @@ impl<'b> Mul<&'b Scalar> for ProjectivePoint {
     type Output = ProjectivePoint;
 
     fn mul(self, rhs: &'b Scalar) -> ProjectivePoint {
-        ProjectivePoint(self.0.mul(rhs.0))
+        self.multiply_ct(&rhs.to_bytes())
     }
 }
 
@@ -531,7 +696,7 @@ impl<'a> Mul<Scalar> for &'a ProjectivePoint {
     type Output = ProjectivePoint;
 
     fn mul(self, rhs: Scalar) -> ProjectivePoint {
-        ProjectivePoint(self.0.mul(rhs.0))
+        self.multiply_ct(&rhs.to_bytes())
     }
 }
 
@@ -539,7 +704,7 @@ impl Mul<Scalar> for ProjectivePoint {
     type Output = ProjectivePoint;
 
     fn mul(self, rhs: Scalar) -> ProjectivePoint {
-        ProjectivePoint(self.0.mul(rhs.0))
+        self.multiply_ct(&rhs.to_bytes())
     }
 }
 
@@ -595,7 +760,26 @@ impl ProjectivePoint {
         ProjectivePoint(ProjectivePointInner::generator())
     }
 
+    /// Constructs a `ProjectivePoint` element from the specified coordinates, returning None
+    /// if the coordinates do not describe a point on the curve, or if the point is not in the
+    /// prime-order subgroup.
+    ///
+    /// This should be used for all coordinates which do not come from a trusted source (e.g.,
+    /// witness data).
+    pub fn from_raw_coordinates_checked(elems: [BaseElement; 18]) -> Option<Self> {
+        let point = Self::from_raw_coordinates(elems);
+        if point.is_on_curve() && AffinePoint::from(&point).is_torsion_free() {
+            Some(point)
+        } else {
+            None
+        }
+    }
+
     /// Constructs a `ProjectivePoint` element without checking that it is a valid point.
+    ///
+    /// The resulting point may be off the curve or have a small-order component; use
+    /// [ProjectivePoint::from_raw_coordinates_checked()] unless the coordinates are trusted.
+    #[doc(hidden)]
     pub fn from_raw_coordinates(elems: [BaseElement; 18]) -> Self {
         let x = Fp6::new([
             elems[0].to_repr(),
@@ -642,11 +826,30 @@ impl ProjectivePoint {
         ProjectivePoint(self.0.add_mixed(&rhs.0))
     }
 
+    /// Multiplies this point by the scalar encoded in `by` in constant time.
+    ///
+    /// The running time of this method does not depend on the value of the scalar, and thus,
+    /// this method must be used whenever the scalar is secret (e.g., a secret key or a nonce).
+    /// This is also the method used by the `*` operator.
     #[must_use]
-    pub fn multiply(&self, by: &[u8; 32]) -> ProjectivePoint {
+    pub fn multiply_ct(&self, by: &[u8; 32]) -> ProjectivePoint {
+        ProjectivePoint(self.0.multiply(by))
+    }
+
+    /// Multiplies this point by the scalar encoded in `by` in variable time.
+    ///
+    /// This method is faster than [ProjectivePoint::multiply_ct()], but its running time
+    /// depends on the value of the scalar. It must be used only with public scalars (e.g., when
+    /// verifying a signature).
+    #[must_use]
+    pub fn multiply_vartime(&self, by: &[u8; 32]) -> ProjectivePoint {
         ProjectivePoint(self.0.multiply_vartime(by))
     }
 
+    /// Computes `self * by_lhs + rhs * by_rhs` in variable time.
+    ///
+    /// The running time of this method depends on the values of the scalars, and thus, it must
+    /// be used only with public scalars.
     #[must_use]
     pub fn multiply_double(
         &self,
@@ -685,6 +888,203 @@ impl ProjectivePoint {
     pub fn is_on_curve(&self) -> bool {
         bool::from(self.0.is_on_curve())
     }
+
+    /// Computes the sum of `points[i] * scalars[i]` over all `i`.
+    ///
+    /// This uses Pippenger's bucket method, with a window size derived from the number of
+    /// provided points, and is significantly faster than computing each scalar multiplication
+    /// independently when many points are involved. Identity points and zero scalars are
+    /// allowed, and an empty input yields the identity.
+    ///
+    /// When `concurrent` feature is enabled, windows are processed in multiple threads.
+    ///
+    /// This method is not constant-time and must not be used with secret scalars.
+    ///
+    /// # Panics
+    /// Panics if `points` and `scalars` slices have different lengths.
+    pub fn multi_scalar_mul(points: &[AffinePoint], scalars: &[Scalar]) -> ProjectivePoint {
+        assert_eq!(
+            points.len(),
+            scalars.len(),
+            "number of points must be the same as the number of scalars"
+        );
+
+        // identity points and zero scalars do not contribute to the sum
+        let (points, scalars): (Vec<AffinePoint>, Vec<[u8; 32]>) = points
+            .iter()
+            .zip(scalars)
+            .filter(|(point, scalar)| !point.is_identity() && **scalar != Scalar::zero())
+            .map(|(point, scalar)| (*point, scalar.to_bytes()))
+            .unzip();
+        if points.is_empty() {
+            return ProjectivePoint::identity();
+        }
+
+        let window_size = get_msm_window_size(points.len());
+        let num_windows = (SCALAR_BITS + window_size - 1) / window_size;
+
+        // compute the sum of each window independently
+        #[cfg(not(feature = "concurrent"))]
+        let window_sums = (0..num_windows)
+            .map(|window| compute_msm_window_sum(&points, &scalars, window, window_size))
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "concurrent")]
+        let window_sums = (0..num_windows)
+            .into_par_iter()
+            .map(|window| compute_msm_window_sum(&points, &scalars, window, window_size))
+            .collect::<Vec<_>>();
+
+        // combine window sums, starting from the most significant window
+        window_sums
+            .iter()
+            .rev()
+            .fold(ProjectivePoint::identity(), |acc, window_sum| {
+                let mut acc = acc;
+                for _ in 0..window_size {
+                    acc = acc.double();
+                }
+                acc + window_sum
+            })
+    }
+}
+
+// MULTI-SCALAR MULTIPLICATION HELPERS
+// ------------------------------------------------------------------------------------------------
+
+/// Number of bits in the byte representation of a scalar.
+const SCALAR_BITS: usize = 256;
+
+/// Returns the window size (in bits) used by Pippenger's method for the specified number of
+/// points; the window grows roughly as ln(num_points).
+fn get_msm_window_size(num_points: usize) -> usize {
+    if num_points < 32 {
+        3
+    } else {
+        let log2_num_points = (usize::BITS - num_points.leading_zeros()) as usize;
+        log2_num_points * 69 / 100 + 2
+    }
+}
+
+/// Returns `window_size` bits of the little-endian `scalar` starting at bit `window * window_size`.
+fn get_msm_window_digit(scalar: &[u8; 32], window: usize, window_size: usize) -> usize {
+    let mut digit = 0;
+    let start = window * window_size;
+    for i in 0..window_size.min(SCALAR_BITS - start) {
+        let bit = start + i;
+        digit |= (((scalar[bit / 8] >> (bit % 8)) & 1) as usize) << i;
+    }
+    digit
+}
+
+/// Computes sum of `points[i] * digit_i` over all `i`, where `digit_i` is the value of the
+/// specified window of `scalars[i]`.
+fn compute_msm_window_sum(
+    points: &[AffinePoint],
+    scalars: &[[u8; 32]],
+    window: usize,
+    window_size: usize,
+) -> ProjectivePoint {
+    // accumulate points into buckets according to their digit; points with a zero digit are
+    // skipped
+    let mut buckets = vec![ProjectivePoint::identity(); (1 << window_size) - 1];
+    for (point, scalar) in points.iter().zip(scalars) {
+        let digit = get_msm_window_digit(scalar, window, window_size);
+        if digit != 0 {
+            buckets[digit - 1] = buckets[digit - 1].add_mixed(point);
+        }
+    }
+
+    // compute sum of bucket[j] * (j + 1) using running sums
+    let mut running_sum = ProjectivePoint::identity();
+    let mut result = ProjectivePoint::identity();
+    for bucket in buckets.iter().rev() {
+        running_sum += bucket;
+        result += running_sum;
+    }
+    result
+}
+
+// BATCH ADDITION HELPERS
+// ------------------------------------------------------------------------------------------------
+
+/// Returns the sum of each of the specified pairs of points, using a single field inversion for
+/// all pairs.
+fn batch_add_pairs(pairs: &[(AffinePoint, AffinePoint)]) -> Vec<AffinePoint> {
+    // compute the numerator and the denominator of the slope of the line through each pair of
+    // points; when the sum of a pair can be determined without the slope (i.e., when one of the
+    // points is the identity, or when the points are negations of each other), the denominator
+    // is set to zero
+    let mut numerators = Vec::with_capacity(pairs.len());
+    let mut denominators = Vec::with_capacity(pairs.len());
+    for (p, q) in pairs.iter() {
+        let (numerator, denominator) = if p.is_identity() || q.is_identity() {
+            (Fp6::zero(), Fp6::zero())
+        } else {
+            let (x1, y1) = (p.0.get_x(), p.0.get_y());
+            let (x2, y2) = (q.0.get_x(), q.0.get_y());
+            if x1 != x2 {
+                // slope = (y2 - y1) / (x2 - x1)
+                (y2 - y1, x2 - x1)
+            } else if y1 == y2 {
+                // slope = (3x^2 + 1) / 2y; this is zero over zero for points of order 2, which
+                // are their own negations
+                let x_squared = x1.square();
+                (x_squared + x_squared + x_squared + Fp6::one(), y1 + y1)
+            } else {
+                (Fp6::zero(), Fp6::zero())
+            }
+        };
+        numerators.push(numerator);
+        denominators.push(denominator);
+    }
+
+    batch_invert(&mut denominators);
+
+    pairs
+        .iter()
+        .zip(numerators.iter().zip(denominators.iter()))
+        .map(|((p, q), (&numerator, &inv_denominator))| {
+            if p.is_identity() {
+                *q
+            } else if q.is_identity() {
+                *p
+            } else if inv_denominator == Fp6::zero() {
+                AffinePoint::identity()
+            } else {
+                // x3 = slope^2 - x1 - x2; y3 = slope * (x1 - x3) - y1
+                let slope = numerator * inv_denominator;
+                let (x1, y1) = (p.0.get_x(), p.0.get_y());
+                let x3 = slope.square() - x1 - q.0.get_x();
+                let y3 = slope * (x1 - x3) - y1;
+                AffinePoint(AffinePointInner::from_raw_coordinates([x3, y3]))
+            }
+        })
+        .collect()
+}
+
+/// Inverts all provided field elements in place using a single field inversion; zero elements
+/// are left unchanged.
+fn batch_invert(values: &mut [Fp6]) {
+    // compute products of all non-zero values preceding each value
+    let mut prefixes = Vec::with_capacity(values.len());
+    let mut acc = Fp6::one();
+    for value in values.iter() {
+        prefixes.push(acc);
+        if *value != Fp6::zero() {
+            acc *= value;
+        }
+    }
+
+    acc = acc.invert().unwrap_or_else(Fp6::zero);
+
+    for (value, prefix) in values.iter_mut().zip(prefixes.iter()).rev() {
+        if *value != Fp6::zero() {
+            let inverse = acc * prefix;
+            acc *= *value;
+            *value = inverse;
+        }
+    }
 }
 
 // SERIALIZATION / DESERIALIZATION
@@ -703,13 +1103,25 @@ impl Deserializable for AffinePoint {
         let pt = AffinePointInner::from_compressed(&CompressedPoint(bytes));
         if bool::from(pt.is_none()) {
             return Err(DeserializationError::InvalidValue(
-                "Invalid point".to_string(),
+                "bytes are not a valid compressed encoding of a curve point".to_string(),
             ));
         };
         Ok(AffinePoint(pt.unwrap()))
     }
 }
 
+impl CanonicalSerializable for AffinePoint {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        self.write_into(target)
+    }
+}
+
+impl CanonicalDeserializable for AffinePoint {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_from(source)
+    }
+}
+
 impl Serializable for ProjectivePoint {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write_u8_slice(&self.0.to_compressed().to_bytes());
@@ -723,13 +1135,25 @@ impl Deserializable for ProjectivePoint {
         let pt = ProjectivePointInner::from_compressed(&CompressedPoint(bytes));
         if bool::from(pt.is_none()) {
             return Err(DeserializationError::InvalidValue(
-                "Invalid point".to_string(),
+                "bytes are not a valid compressed encoding of a curve point".to_string(),
             ));
         };
         Ok(ProjectivePoint(pt.unwrap()))
     }
 }
 
+impl CanonicalSerializable for ProjectivePoint {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        self.write_into(target)
+    }
+}
+
+impl CanonicalDeserializable for ProjectivePoint {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_from(source)
+    }
+}
+
 // This module exports the unit tests from the underlying cheetah crate.
 // The heavy coordinates handling is necessary for testing all the wrapped
 // methods provided here, even though most of them won't be needed in this
@@ -737,6 +1161,7 @@ impl Deserializable for ProjectivePoint {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use rand_utils::rand_value;
 
     #[test]
@@ -1087,6 +1512,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_affine_scalar_multiplication_output() {
+        // all combinations of owned and borrowed operands must produce the same affine point
+        let g = AffinePoint::generator();
+        for _ in 0..10 {
+            let a: Scalar = rand_value();
+            let expected: AffinePoint = g * a;
+
+            assert_eq!(g * &a, expected);
+            assert_eq!(&g * a, expected);
+            assert_eq!(&g * &a, expected);
+            assert_eq!(AffinePoint::from(ProjectivePoint::from(g) * a), expected);
+        }
+    }
+
+    #[test]
+    fn test_constant_time_and_vartime_multiplication() {
+        let g = AffinePoint::generator();
+        let mut scalars = vec![Scalar::zero(), Scalar::one(), -Scalar::one()];
+        scalars.extend((0..20).map(|_| rand_value::<Scalar>()));
+
+        for a in scalars {
+            let p = AffinePoint::from(g * rand_value::<Scalar>());
+            let by = a.to_bytes();
+
+            let expected = p.multiply_vartime(&by);
+            assert_eq!(expected, p.multiply_ct(&by));
+            assert_eq!(expected, p * a);
+
+            let p = ProjectivePoint::from(p);
+            let expected = p.multiply_vartime(&by);
+            assert_eq!(expected, p.multiply_ct(&by));
+            assert_eq!(expected, &p * &a);
+            assert_eq!(AffinePoint::from(expected), AffinePoint::from(p) * a);
+        }
+
+        // arbitrary byte strings are multiplied in the same way as well
+        for _ in 0..20 {
+            let by = rand_utils::rand_array::<u8, 32>();
+            assert_eq!(g.multiply_vartime(&by), g.multiply_ct(&by));
+        }
+    }
+
+    #[test]
+    fn test_multiply_double() {
+        let g = AffinePoint::generator();
+        for _ in 0..10 {
+            let p = AffinePoint::from(g * rand_value::<Scalar>());
+            let a: Scalar = rand_value();
+            let b: Scalar = rand_value();
+
+            let expected = AffinePoint::from(ProjectivePoint::from(g * a) + p * b);
+            assert_eq!(
+                expected,
+                g.multiply_double(&p, &a.to_bytes(), &b.to_bytes())
+            );
+        }
+    }
+
+    #[test]
+    fn test_recoded_scalar_multiplication() {
+        let g = AffinePoint::generator();
+
+        for _ in 0..10 {
+            let p = AffinePoint::from(g * rand_value::<Scalar>());
+            let a: Scalar = rand_value();
+            let expected = p.multiply_vartime(&a.to_bytes());
+
+            // multiplication using width-4 non-adjacent form
+            let wnaf = a.to_wnaf(4);
+            assert_eq!(
+                expected,
+                AffinePoint::from(multiply_by_digits(&p, &wnaf, 1))
+            );
+
+            // multiplication using signed radix-16 digits
+            let digits = a.to_radix16();
+            assert_eq!(
+                expected,
+                AffinePoint::from(multiply_by_digits(&p, &digits, 4))
+            );
+        }
+    }
+
     #[test]
     fn test_clear_cofactor() {
         // the generator (and the identity) are always on the curve
@@ -1140,6 +1649,62 @@ mod tests {
         assert!(bool::from(AffinePoint::generator().is_torsion_free()));
     }
 
+    #[test]
+    fn test_from_raw_coordinates_checked() {
+        // the generator is accepted in both coordinate systems
+        let gen = AffinePoint::generator();
+        let point = AffinePoint::from_raw_coordinates_checked(affine_coordinates(&gen));
+        assert_eq!(Some(gen), point);
+
+        let gen = ProjectivePoint::generator();
+        let point = ProjectivePoint::from_raw_coordinates_checked(projective_coordinates(&gen));
+        assert_eq!(Some(gen), point);
+
+        // a point which is not on the curve is rejected
+        let mut coordinates = projective_coordinates(&gen);
+        coordinates[0..6].copy_from_slice(&gen.get_z());
+        assert!(!ProjectivePoint::from_raw_coordinates(coordinates).is_on_curve());
+        assert!(ProjectivePoint::from_raw_coordinates_checked(coordinates).is_none());
+
+        // a point which is on the curve but is not torsion-free is rejected
+        let coordinates = [
+            BaseElement::new(0x9bfcd3244afcb637),
+            BaseElement::new(0x39005e478830b187),
+            BaseElement::new(0x7046f1c03b42c6cc),
+            BaseElement::new(0xb5eeac99193711e5),
+            BaseElement::new(0x7fd272e724307b98),
+            BaseElement::new(0xcc371dd6dd5d8625),
+            BaseElement::new(0x9d03fdc216dfaae8),
+            BaseElement::new(0xbf4ade2a7665d9b8),
+            BaseElement::new(0xf08b022d5b3262b7),
+            BaseElement::new(0x2eaf583a3cf15c6f),
+            BaseElement::new(0xa92531e4b1338285),
+            BaseElement::new(0x5b8157814141a7a7),
+        ];
+        let point = AffinePoint::from_raw_coordinates(coordinates);
+        assert!(point.is_on_curve());
+        assert!(AffinePoint::from_raw_coordinates_checked(coordinates).is_none());
+
+        let coordinates = projective_coordinates(&ProjectivePoint::from(&point));
+        assert!(ProjectivePoint::from_raw_coordinates(coordinates).is_on_curve());
+        assert!(ProjectivePoint::from_raw_coordinates_checked(coordinates).is_none());
+    }
+
+    fn affine_coordinates(point: &AffinePoint) -> [BaseElement; 12] {
+        let mut result = [BaseElement::new(0); 12];
+        result[0..6].copy_from_slice(&point.get_x());
+        result[6..12].copy_from_slice(&point.get_y());
+        result
+    }
+
+    fn projective_coordinates(point: &ProjectivePoint) -> [BaseElement; 18] {
+        let mut result = [BaseElement::new(0); 18];
+        result[0..6].copy_from_slice(&point.get_x());
+        result[6..12].copy_from_slice(&point.get_y());
+        result[12..18].copy_from_slice(&point.get_z());
+        result
+    }
+
     #[test]
     fn test_batch_normalize() {
         let a = ProjectivePoint::generator().double();
@@ -1178,4 +1743,257 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_multi_scalar_mul() {
+        let g = ProjectivePoint::generator();
+        for &n in [0usize, 1, 2, 7, 31, 32, 100].iter() {
+            let mut points = (0..n)
+                .map(|_| AffinePoint::from(g * rand_value::<Scalar>()))
+                .collect::<Vec<_>>();
+            let mut scalars = (0..n).map(|_| rand_value::<Scalar>()).collect::<Vec<_>>();
+
+            // make sure identity points, zero and small scalars are handled correctly
+            if n > 2 {
+                points[0] = AffinePoint::identity();
+                scalars[1] = Scalar::zero();
+                scalars[2] = Scalar::one();
+            }
+
+            let expected: ProjectivePoint = points
+                .iter()
+                .zip(scalars.iter())
+                .map(|(point, scalar)| point * scalar)
+                .sum();
+            assert_eq!(
+                expected,
+                ProjectivePoint::multi_scalar_mul(&points, &scalars)
+            );
+        }
+    }
+
+    #[test]
+    fn test_multi_scalar_mul_negated_scalars() {
+        let g = ProjectivePoint::generator();
+        let points = (0..40)
+            .map(|_| AffinePoint::from(g * rand_value::<Scalar>()))
+            .collect::<Vec<_>>();
+        let scalars = (0..40).map(|_| rand_value::<Scalar>()).collect::<Vec<_>>();
+        let neg_scalars = scalars.iter().map(|s| -s).collect::<Vec<_>>();
+
+        let result = ProjectivePoint::multi_scalar_mul(&points, &scalars);
+        let neg_result = ProjectivePoint::multi_scalar_mul(&points, &neg_scalars);
+        assert!((result + neg_result).is_identity());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_multi_scalar_mul_length_mismatch() {
+        let points = [AffinePoint::generator(); 3];
+        let scalars = [Scalar::one(); 2];
+        ProjectivePoint::multi_scalar_mul(&points, &scalars);
+    }
+
+    #[test]
+    fn test_batch_add_chain() {
+        let g = ProjectivePoint::generator();
+        for &n in [0usize, 1, 2, 3, 4, 5, 16, 33, 100].iter() {
+            let points = (0..n)
+                .map(|_| AffinePoint::from(g * rand_value::<Scalar>()))
+                .collect::<Vec<_>>();
+            assert_eq!(
+                naive_add_chain(&points),
+                AffinePoint::batch_add_chain(&points)
+            );
+        }
+
+        // identity points, doublings and additions of negations are handled correctly
+        let p = AffinePoint::from(g * rand_value::<Scalar>());
+        let q = AffinePoint::from(g * rand_value::<Scalar>());
+        let two = Scalar::from(2u64);
+        let points = [
+            AffinePoint::identity(),
+            p,
+            p,
+            AffinePoint::identity(),
+            -(p * two),
+            q,
+            -q,
+            q,
+            q,
+            -(q * two),
+        ];
+        let expected = naive_add_chain(&points);
+        assert!(expected[4].is_identity());
+        assert!(expected[9].is_identity());
+        assert_eq!(expected, AffinePoint::batch_add_chain(&points));
+
+        // a chain of equal points yields their multiples
+        let points = vec![p; 17];
+        let result = AffinePoint::batch_add_chain(&points);
+        for (i, point) in result.iter().enumerate() {
+            assert_eq!(AffinePoint::from(p * Scalar::from((i + 1) as u64)), *point);
+        }
+    }
+
+    #[test]
+    fn test_batch_add_chains() {
+        let g = ProjectivePoint::generator();
+        let chains = [0usize, 1, 7, 64]
+            .iter()
+            .map(|&n| {
+                (0..n)
+                    .map(|_| AffinePoint::from(g * rand_value::<Scalar>()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let result = AffinePoint::batch_add_chains(&chains);
+        assert_eq!(chains.len(), result.len());
+        for (chain, sums) in chains.iter().zip(result.iter()) {
+            assert_eq!(&naive_add_chain(chain), sums);
+        }
+    }
+
+    fn naive_add_chain(points: &[AffinePoint]) -> Vec<AffinePoint> {
+        let mut acc = ProjectivePoint::identity();
+        points
+            .iter()
+            .map(|point| {
+                acc += point;
+                AffinePoint::from(acc)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hash_to_curve() {
+        let domain = b"winterfell-test";
+        let p = AffinePoint::hash_to_curve(domain, b"message");
+        assert!(p.is_on_curve());
+        assert!(p.is_torsion_free());
+        assert!(!p.is_identity());
+        assert_ne!(p, AffinePoint::generator());
+
+        // the mapping is deterministic
+        assert_eq!(p, AffinePoint::hash_to_curve(domain, b"message"));
+
+        // different messages and domains map to different points
+        assert_ne!(p, AffinePoint::hash_to_curve(domain, b"other message"));
+        assert_ne!(p, AffinePoint::hash_to_curve(b"other-domain", b"message"));
+
+        // the domain length is hashed, so moving bytes between domain and message matters
+        assert_ne!(
+            AffinePoint::hash_to_curve(b"ab", b"c"),
+            AffinePoint::hash_to_curve(b"a", b"bc")
+        );
+
+        // the resulting points have a serialized form which can be read back
+        let bytes = p.to_bytes();
+        let mut reader = utils::SliceReader::new(&bytes);
+        assert_eq!(p, AffinePoint::read_from(&mut reader).unwrap());
+    }
+
+    #[test]
+    fn test_serialization_identity() {
+        let bytes = AffinePoint::identity().to_bytes();
+        let mut reader = utils::SliceReader::new(&bytes);
+        let p = AffinePoint::read_from(&mut reader).unwrap();
+        assert!(p.is_identity());
+        assert!(!reader.has_more_bytes());
+
+        let bytes = ProjectivePoint::identity().to_bytes();
+        let mut reader = utils::SliceReader::new(&bytes);
+        let p = ProjectivePoint::read_from(&mut reader).unwrap();
+        assert_eq!(ProjectivePoint::identity(), p);
+        assert!(!reader.has_more_bytes());
+    }
+
+    #[test]
+    fn test_deserialization_invalid_bytes() {
+        let num_bytes = AffinePoint::generator().to_bytes().len();
+
+        // truncated encodings are rejected
+        let bytes = AffinePoint::generator().to_bytes();
+        let mut reader = utils::SliceReader::new(&bytes[..num_bytes - 1]);
+        assert!(AffinePoint::read_from(&mut reader).is_err());
+        let mut reader = utils::SliceReader::new(&bytes[..num_bytes - 1]);
+        assert!(ProjectivePoint::read_from(&mut reader).is_err());
+
+        // coordinates which are not canonical field elements are rejected
+        let bytes = vec![0xff; num_bytes];
+        let mut reader = utils::SliceReader::new(&bytes);
+        assert!(matches!(
+            AffinePoint::read_from(&mut reader),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+        let mut reader = utils::SliceReader::new(&bytes);
+        assert!(matches!(
+            ProjectivePoint::read_from(&mut reader),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn serialization_roundtrip_proptest(scalar in any::<[u8; 32]>()) {
+            let p = AffinePoint::generator().multiply_vartime(&scalar);
+            let bytes = p.to_bytes();
+            let mut reader = utils::SliceReader::new(&bytes);
+            prop_assert_eq!(p, AffinePoint::read_from(&mut reader).unwrap());
+            prop_assert!(!reader.has_more_bytes());
+
+            let p = ProjectivePoint::from(p);
+            let bytes = p.to_bytes();
+            let mut reader = utils::SliceReader::new(&bytes);
+            prop_assert_eq!(p, ProjectivePoint::read_from(&mut reader).unwrap());
+            prop_assert!(!reader.has_more_bytes());
+        }
+
+        #[test]
+        fn deserialization_corrupted_bytes_proptest(
+            scalar in any::<[u8; 32]>(),
+            index in any::<usize>(),
+            mask in 1..=u8::MAX,
+        ) {
+            // flip some bits of a valid encoding; the result must either be rejected, or be the
+            // canonical encoding of a different point on the curve
+            let mut bytes = AffinePoint::generator().multiply_vartime(&scalar).to_bytes();
+            let index = index % bytes.len();
+            bytes[index] ^= mask;
+
+            let mut reader = utils::SliceReader::new(&bytes);
+            if let Ok(p) = AffinePoint::read_from(&mut reader) {
+                prop_assert!(p.is_on_curve());
+                prop_assert_eq!(bytes, p.to_bytes());
+            }
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Computes `sum(digits[i] * 2^(i * shift)) * p` using double-and-add over signed digits.
+    fn multiply_by_digits(p: &AffinePoint, digits: &[i8], shift: usize) -> ProjectivePoint {
+        // precompute multiples of p up to the largest digit magnitude
+        let max_digit = digits.iter().map(|d| d.unsigned_abs()).max().unwrap_or(0) as usize;
+        let mut multiples = vec![ProjectivePoint::identity()];
+        for i in 0..max_digit {
+            multiples.push(multiples[i] + p);
+        }
+
+        let mut result = ProjectivePoint::identity();
+        for &digit in digits.iter().rev() {
+            for _ in 0..shift {
+                result = result.double();
+            }
+            let multiple = multiples[digit.unsigned_abs() as usize];
+            if digit < 0 {
+                result -= multiple;
+            } else {
+                result += multiple;
+            }
+        }
+        result
+    }
 }
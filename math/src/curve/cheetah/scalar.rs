@@ -14,8 +14,8 @@ use bitvec::{order::Lsb0, slice::BitSlice};
 use cheetah::Scalar as ScalarInner;
 use rand_core::RngCore;
 use utils::{
-    string::ToString, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
-    Serializable,
+    collections::Vec, string::ToString, ByteReader, ByteWriter, CanonicalDeserializable,
+    CanonicalSerializable, Deserializable, DeserializationError, Randomizable, Serializable,
 };
 
 // CONSTANTS
@@ -167,6 +167,130 @@ impl Scalar {
     pub fn random(mut rng: impl RngCore) -> Self {
         Scalar(ScalarInner::random(&mut rng))
     }
+
+    /// Inverts all provided scalars in place using batch inversion, performing a single
+    /// field inversion for the entire slice.
+    ///
+    /// Any zero scalars are left unchanged, and do not affect the inverses of other scalars.
+    pub fn batch_invert(values: &mut [Scalar]) {
+        // compute products of all non-zero values preceding each value
+        let mut prefixes = Vec::with_capacity(values.len());
+        let mut acc = Scalar::one();
+        for value in values.iter() {
+            prefixes.push(acc);
+            if *value != Scalar::zero() {
+                acc *= value;
+            }
+        }
+
+        acc = acc.invert();
+
+        for (value, prefix) in values.iter_mut().zip(prefixes.iter()).rev() {
+            if *value != Scalar::zero() {
+                let inverse = acc * *prefix;
+                acc *= *value;
+                *value = inverse;
+            }
+        }
+    }
+
+    // RECODINGS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the width-`w` non-adjacent form of this scalar.
+    ///
+    /// The result contains 256 signed digits $d_i$ in little-endian order such that:
+    /// * $\sum_i d_i \cdot 2^i$ is equal to this scalar;
+    /// * every non-zero digit is odd and lies in the range $(-2^{w-1}, 2^{w-1})$;
+    /// * any $w$ consecutive digits contain at most one non-zero digit.
+    ///
+    /// # Panics
+    /// Panics if `width` is not in the range [2, 8].
+    pub fn to_wnaf(&self, width: usize) -> Vec<i8> {
+        assert!(
+            (2..=8).contains(&width),
+            "wNAF width must be between 2 and 8, but was {width}"
+        );
+
+        let limbs = self.to_limbs();
+        let window_size = 1u64 << width;
+        let window_mask = window_size - 1;
+
+        let mut result = vec![0i8; 256];
+        let mut position = 0;
+        let mut carry = 0;
+        while position < 256 {
+            // read the next `width` bits of the scalar starting at the current position
+            let limb_idx = position / 64;
+            let bit_idx = position % 64;
+            let bits = if bit_idx + width <= 64 {
+                limbs[limb_idx] >> bit_idx
+            } else {
+                (limbs[limb_idx] >> bit_idx) | (limbs[limb_idx + 1] << (64 - bit_idx))
+            };
+
+            let window = carry + (bits & window_mask);
+            if window & 1 == 0 {
+                // the window starts with a zero bit; move on to the next bit
+                position += 1;
+                continue;
+            }
+
+            // the window is odd; use a signed digit and carry the excess to the next window
+            if window < window_size / 2 {
+                carry = 0;
+                result[position] = window as i8;
+            } else {
+                carry = 1;
+                result[position] = (window as i64 - window_size as i64) as i8;
+            }
+
+            position += width;
+        }
+
+        debug_assert_eq!(carry, 0, "scalar is too large for its wNAF representation");
+        result
+    }
+
+    /// Returns the signed radix-16 representation of this scalar.
+    ///
+    /// The result contains 64 signed digits $d_i$ in little-endian order such that
+    /// $\sum_i d_i \cdot 16^i$ is equal to this scalar. All digits except for the last one lie
+    /// in the range $[-8, 8)$; since scalars are smaller than $2^{253}$, the last digit lies in
+    /// the range $[0, 2]$.
+    pub fn to_radix16(&self) -> [i8; 64] {
+        let bytes = self.to_bytes();
+
+        // split the scalar into unsigned 4-bit digits
+        let mut result = [0i8; 64];
+        for (i, &byte) in bytes.iter().enumerate() {
+            result[2 * i] = (byte & 0xf) as i8;
+            result[2 * i + 1] = (byte >> 4) as i8;
+        }
+
+        // re-center the digits into the range [-8, 8) by carrying to the next digit
+        for i in 0..63 {
+            let carry = (result[i] + 8) >> 4;
+            result[i] -= carry << 4;
+            result[i + 1] += carry;
+        }
+
+        result
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the canonical value of this scalar as little-endian 64-bit limbs, followed by a
+    /// zero limb.
+    fn to_limbs(self) -> [u64; 5] {
+        let bytes = self.to_bytes();
+        let mut result = [0u64; 5];
+        for (limb, chunk) in result.iter_mut().zip(bytes.chunks(8)) {
+            *limb = u64::from_le_bytes(chunk.try_into().expect("invalid chunk length"));
+        }
+        result
+    }
 }
 
 // OVERLOADED OPERATORS
@@ -390,23 +514,24 @@ impl Serializable for Scalar {
 
 impl Deserializable for Scalar {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let bytes = source.read_u8_array()?;
-        if bytes.len() < ELEMENT_BYTES {
-            return Err(DeserializationError::InvalidValue(format!(
-                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
-                ELEMENT_BYTES,
-                bytes.len(),
-            )));
-        }
-        if bytes.len() > ELEMENT_BYTES {
-            return Err(DeserializationError::InvalidValue(format!(
-                "too many bytes for a field element; expected {} bytes, but was {} bytes",
-                ELEMENT_BYTES,
-                bytes.len(),
-            )));
-        }
+        let bytes: [u8; ELEMENT_BYTES] = source.read_u8_array()?;
+        Scalar::from_bytes(&bytes).ok_or_else(|| {
+            DeserializationError::InvalidValue(
+                "bytes are not a canonical encoding of a scalar field element".to_string(),
+            )
+        })
+    }
+}
 
-        Ok(Scalar::from_bytes(&bytes).unwrap_or_else(Self::zero))
+impl CanonicalSerializable for Scalar {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        self.write_into(target)
+    }
+}
+
+impl CanonicalDeserializable for Scalar {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_from(source)
     }
 }
 
@@ -704,6 +829,29 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_serialization() {
+        for value in [Scalar::zero(), Scalar::one(), -Scalar::one(), rand_value()] {
+            let bytes = value.to_bytes();
+            let mut reader = utils::SliceReader::new(&bytes);
+            assert_eq!(value, Scalar::read_from(&mut reader).unwrap());
+            assert!(!reader.has_more_bytes());
+        }
+
+        // truncated encodings are rejected
+        let bytes = Scalar::one().to_bytes();
+        let mut reader = utils::SliceReader::new(&bytes[..ELEMENT_BYTES - 1]);
+        assert!(Scalar::read_from(&mut reader).is_err());
+
+        // non-canonical encodings are rejected rather than read as zero
+        let bytes = [0xff; ELEMENT_BYTES];
+        let mut reader = utils::SliceReader::new(&bytes);
+        assert!(matches!(
+            Scalar::read_from(&mut reader),
+            Err(DeserializationError::InvalidValue(_))
+        ));
+    }
+
     #[test]
     fn test_from_bits() {
         let bytes = Scalar::zero().to_bytes();
@@ -760,4 +908,79 @@ mod tests {
         assert_eq!(element, Scalar::from(n as u64));
         assert_eq!(element, Scalar::from(n as u128));
     }
+
+    #[test]
+    fn test_batch_invert() {
+        let mut values = (0..32).map(|_| rand_value::<Scalar>()).collect::<Vec<_>>();
+        values[3] = Scalar::zero();
+        values[31] = Scalar::zero();
+
+        let mut inverses = values.clone();
+        Scalar::batch_invert(&mut inverses);
+        for (value, inverse) in values.iter().zip(inverses.iter()) {
+            assert_eq!(value.invert(), *inverse);
+        }
+    }
+
+    #[test]
+    fn test_to_wnaf() {
+        let mut scalars = (0..32).map(|_| rand_value::<Scalar>()).collect::<Vec<_>>();
+        scalars.push(Scalar::zero());
+        scalars.push(Scalar::one());
+        scalars.push(-Scalar::one());
+
+        for width in 2..=8 {
+            for scalar in scalars.iter() {
+                let wnaf = scalar.to_wnaf(width);
+                assert_eq!(256, wnaf.len());
+
+                let bound = 1i16 << (width - 1);
+                let mut last_nonzero: Option<usize> = None;
+                for (i, &digit) in wnaf.iter().enumerate() {
+                    if digit != 0 {
+                        assert_eq!(1, digit & 1);
+                        assert!((digit as i16) > -bound && (digit as i16) < bound);
+                        if let Some(j) = last_nonzero {
+                            assert!(i - j >= width);
+                        }
+                        last_nonzero = Some(i);
+                    }
+                }
+
+                assert_eq!(*scalar, reconstruct(&wnaf, 2));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "wNAF width must be between 2 and 8, but was 9")]
+    fn test_to_wnaf_invalid_width() {
+        let _ = Scalar::one().to_wnaf(9);
+    }
+
+    #[test]
+    fn test_to_radix16() {
+        let mut scalars = (0..32).map(|_| rand_value::<Scalar>()).collect::<Vec<_>>();
+        scalars.push(Scalar::zero());
+        scalars.push(-Scalar::one());
+
+        for scalar in scalars.iter() {
+            let digits = scalar.to_radix16();
+            for &digit in digits[..63].iter() {
+                assert!((-8..8).contains(&digit));
+            }
+            assert!((0..=2).contains(&digits[63]));
+
+            assert_eq!(*scalar, reconstruct(&digits, 16));
+        }
+    }
+
+    /// Computes the sum of `digits[i] * radix^i` in the scalar field.
+    fn reconstruct(digits: &[i8], radix: u8) -> Scalar {
+        digits.iter().rev().fold(Scalar::zero(), |acc, &digit| {
+            let digit_value = Scalar::from(digit.unsigned_abs());
+            let digit_value = if digit < 0 { -digit_value } else { digit_value };
+            acc * Scalar::from(radix) + digit_value
+        })
+    }
 }
@@ -8,7 +8,7 @@ use crate::{
     field::{FieldElement, StarkField},
     utils::log2,
 };
-use utils::{collections::Vec, iterators::*, rayon, uninit_vector};
+use utils::{collections::Vec, iterators::*, rayon};
 
 // POLYNOMIAL EVALUATION
 // ================================================================================================
@@ -31,7 +31,7 @@ pub fn evaluate_poly_with_offset<B: StarkField, E: FieldElement<BaseField = B>>(
 ) -> Vec<E> {
     let domain_size = p.len() * blowup_factor;
     let g = B::get_root_of_unity(log2(domain_size));
-    let mut result = unsafe { uninit_vector(domain_size) };
+    let mut result = E::zeroed_vector(domain_size);
 
     result
         .as_mut_slice()
@@ -48,6 +48,40 @@ pub fn evaluate_poly_with_offset<B: StarkField, E: FieldElement<BaseField = B>>(
     result
 }
 
+/// Evaluates polynomial `p` using FFT algorithm over the domain specified by `twiddles`, expanded
+/// by the `blowup_factor`, and shifted by the `domain_offset`; `p` is resized to the size of the
+/// domain and is updated with results of the evaluation.
+pub fn evaluate_poly_with_offset_in_place<B: StarkField, E: FieldElement<BaseField = B>>(
+    p: &mut Vec<E>,
+    twiddles: &[B],
+    domain_offset: B,
+    blowup_factor: usize,
+) {
+    let poly_size = p.len();
+    let domain_size = poly_size * blowup_factor;
+    let g = B::get_root_of_unity(log2(domain_size));
+    p.resize(domain_size, E::ZERO);
+
+    // the first chunk holds the coefficients of the polynomial; thus, all other chunks are
+    // evaluated first (reading the coefficients from the first chunk), and the first chunk is
+    // evaluated last
+    let (coefficients, chunks) = p.split_at_mut(poly_size);
+    chunks
+        .par_chunks_mut(poly_size)
+        .enumerate()
+        .for_each(|(i, chunk)| {
+            let idx = super::permute_index(blowup_factor, i + 1) as u64;
+            let offset = g.exp(idx.into()) * domain_offset;
+            clone_and_shift(coefficients, chunk, offset);
+            split_radix_fft(chunk, twiddles);
+        });
+
+    shift_in_place(coefficients, domain_offset);
+    split_radix_fft(coefficients, twiddles);
+
+    permute(p);
+}
+
 // POLYNOMIAL INTERPOLATION
 // ================================================================================================
 
@@ -229,3 +263,17 @@ fn clone_and_shift<E: FieldElement>(source: &[E], destination: &mut [E], offset:
             }
         });
 }
+
+fn shift_in_place<E: FieldElement>(values: &mut [E], offset: E::BaseField) {
+    let batch_size = values.len() / rayon::current_num_threads().next_power_of_two();
+    values
+        .par_chunks_mut(batch_size)
+        .enumerate()
+        .for_each(|(i, batch)| {
+            let mut factor = offset.exp(((i * batch_size) as u64).into());
+            for value in batch.iter_mut() {
+                *value = (*value).mul_base(factor);
+                factor = factor * offset;
+            }
+        });
+}
@@ -220,6 +220,90 @@ where
     result
 }
 
+/// Evaluates a polynomial on all points of the specified (shifted) domain using the FFT algorithm
+/// and writes the evaluations into the vector holding the polynomial.
+///
+/// This function computes the same evaluations as [evaluate_poly_with_offset()], but instead of
+/// allocating a separate vector for the result, `p` is resized to `p.len()` * `blowup_factor`
+/// elements and the evaluations are computed in place. Thus, the coefficients and the
+/// evaluations of the polynomial are never held in memory at the same time, and when `p` was
+/// allocated with sufficient capacity, no additional memory is allocated at all.
+///
+/// # Panics
+/// Panics if:
+/// * Length of `p` is not a power of two.
+/// * `blowup_factor` is not a power of two.
+/// * Length of `twiddles` is not `p.len()` / 2.
+/// * Field specified by `B` does not contain a multiplicative subgroup of size `p.len()`.
+/// * `domain_offset` is ZERO.
+///
+/// # Examples
+/// ```
+/// # use winter_math::{fft::*, fields::{f128::BaseElement}, FieldElement, StarkField};
+/// # use rand_utils::rand_vector;
+/// let n = 2048;
+/// let offset = BaseElement::GENERATOR;
+/// let blowup_factor = 4;
+///
+/// // build a random polynomial
+/// let p: Vec<BaseElement> = rand_vector(n / blowup_factor);
+/// let twiddles = get_twiddles::<BaseElement>(p.len());
+/// let expected = evaluate_poly_with_offset(&p, &twiddles, offset, blowup_factor);
+///
+/// // evaluate the polynomial in place
+/// let mut actual = p;
+/// evaluate_poly_with_offset_in_place(&mut actual, &twiddles, offset, blowup_factor);
+///
+/// assert_eq!(expected, actual);
+/// ```
+pub fn evaluate_poly_with_offset_in_place<B, E>(
+    p: &mut Vec<E>,
+    twiddles: &[B],
+    domain_offset: B,
+    blowup_factor: usize,
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert!(
+        p.len().is_power_of_two(),
+        "number of coefficients must be a power of 2"
+    );
+    assert!(
+        blowup_factor.is_power_of_two(),
+        "blowup factor must be a power of 2"
+    );
+    assert_eq!(
+        p.len(),
+        twiddles.len() * 2,
+        "invalid number of twiddles: expected {} but received {}",
+        p.len() / 2,
+        twiddles.len()
+    );
+    assert!(
+        log2(p.len() * blowup_factor) <= B::TWO_ADICITY,
+        "multiplicative subgroup of size {} does not exist in the specified base field",
+        p.len() * blowup_factor
+    );
+    assert_ne!(domain_offset, B::ZERO, "domain offset cannot be zero");
+
+    // when `concurrent` feature is enabled, run the concurrent version of the function; unless
+    // the polynomial is small, then don't bother with the concurrent version
+    if cfg!(feature = "concurrent") && p.len() >= MIN_CONCURRENT_SIZE {
+        #[cfg(feature = "concurrent")]
+        {
+            concurrent::evaluate_poly_with_offset_in_place(
+                p,
+                twiddles,
+                domain_offset,
+                blowup_factor,
+            );
+        }
+    } else {
+        serial::evaluate_poly_with_offset_in_place(p, twiddles, domain_offset, blowup_factor);
+    }
+}
+
 // POLYNOMIAL INTERPOLATION
 // ================================================================================================
 
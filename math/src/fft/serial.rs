@@ -6,7 +6,7 @@
 
 use super::fft_inputs::FftInputs;
 use crate::{field::StarkField, utils::log2, FieldElement};
-use utils::{collections::Vec, uninit_vector};
+use utils::collections::Vec;
 
 // POLYNOMIAL EVALUATION
 // ================================================================================================
@@ -36,7 +36,7 @@ where
 {
     let domain_size = p.len() * blowup_factor;
     let g = B::get_root_of_unity(log2(domain_size));
-    let mut result = unsafe { uninit_vector(domain_size) };
+    let mut result = E::zeroed_vector(domain_size);
 
     result
         .as_mut_slice()
@@ -57,6 +57,52 @@ where
     result
 }
 
+/// Evaluates polynomial `p` over the domain of length `p.len()` * `blowup_factor` shifted by
+/// `domain_offset` in the field specified `B` using the FFT algorithm; `p` is resized to the
+/// size of the domain and is updated with results of the evaluation.
+pub fn evaluate_poly_with_offset_in_place<B, E>(
+    p: &mut Vec<E>,
+    twiddles: &[B],
+    domain_offset: B,
+    blowup_factor: usize,
+) where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    let poly_size = p.len();
+    let domain_size = poly_size * blowup_factor;
+    let g = B::get_root_of_unity(log2(domain_size));
+    p.resize(domain_size, E::ZERO);
+
+    // the first chunk holds the coefficients of the polynomial; thus, all other chunks are
+    // evaluated first (reading the coefficients from the first chunk), and the first chunk is
+    // evaluated last; permute_index(blowup_factor, 0) is always 0, so the offset of the first
+    // chunk is just the domain offset
+    let (coefficients, chunks) = p.split_at_mut(poly_size);
+    chunks
+        .chunks_mut(poly_size)
+        .enumerate()
+        .for_each(|(i, chunk)| {
+            let idx = super::permute_index(blowup_factor, i + 1) as u64;
+            let offset = g.exp(idx.into()) * domain_offset;
+            let mut factor = E::BaseField::ONE;
+            for (d, c) in chunk.iter_mut().zip(coefficients.iter()) {
+                *d = (*c).mul_base(factor);
+                factor *= offset;
+            }
+            chunk.fft_in_place(twiddles);
+        });
+
+    let mut factor = E::BaseField::ONE;
+    for c in coefficients.iter_mut() {
+        *c = (*c).mul_base(factor);
+        factor *= domain_offset;
+    }
+    coefficients.fft_in_place(twiddles);
+
+    p.permute();
+}
+
 // POLYNOMIAL INTERPOLATION
 // ================================================================================================
 
@@ -71,6 +71,20 @@ fn fft_get_twiddles() {
     assert_eq!(expected, twiddles);
 }
 
+#[test]
+fn fft_evaluate_poly_with_offset_in_place() {
+    let offset = BaseElement::GENERATOR;
+    for (n, blowup_factor) in [(8, 2), (16, 8), (super::MIN_CONCURRENT_SIZE * 2, 4)] {
+        let p: Vec<BaseElement> = rand_vector(n);
+        let twiddles = super::get_twiddles::<BaseElement>(n);
+        let expected = super::evaluate_poly_with_offset(&p, &twiddles, offset, blowup_factor);
+
+        let mut actual = p;
+        super::evaluate_poly_with_offset_in_place(&mut actual, &twiddles, offset, blowup_factor);
+        assert_eq!(expected, actual);
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -8,12 +8,15 @@ use super::{ExtensibleField, ExtensionOf, FieldElement};
 use core::{
     convert::TryFrom,
     fmt,
+    iter::{Product, Sum},
+    mem,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     slice,
 };
 use utils::{
-    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
-    DeserializationError, Randomizable, Serializable, SliceReader,
+    collections::Vec, group_vector_elements, string::ToString, AsBytes, ByteReader, ByteWriter,
+    CanonicalDeserializable, CanonicalSerializable, Deserializable, DeserializationError,
+    Randomizable, Serializable, SliceReader,
 };
 
 // QUADRATIC EXTENSION FIELD
@@ -43,15 +46,18 @@ impl<B: ExtensibleField<3>> CubeExtension<B> {
     /// by fusing three adjacent base elements together. The output vector is half the length of
     /// the source vector.
     fn base_to_cubic_vector(source: Vec<B>) -> Vec<Self> {
-        debug_assert!(
-            source.len() % 3 == 0,
-            "source vector length must be divisible by three, but was {}",
-            source.len()
-        );
-        let mut v = core::mem::ManuallyDrop::new(source);
+        // the extension element is a #[repr(C)] struct of 3 base elements, and thus, has the
+        // same layout as an array of 3 base elements; both sides of these checks are
+        // constants, and thus, the checks are optimized away
+        assert_eq!(mem::size_of::<Self>(), mem::size_of::<[B; 3]>());
+        assert_eq!(mem::align_of::<Self>(), mem::align_of::<[B; 3]>());
+
+        // grouping the elements makes sure that the capacity of the vector is divisible by three,
+        // and thus, that the memory will be de-allocated with the layout it was allocated with
+        let mut v = mem::ManuallyDrop::new(group_vector_elements::<B, 3>(source));
         let p = v.as_mut_ptr();
-        let len = v.len() / 3;
-        let cap = v.capacity() / 3;
+        let len = v.len();
+        let cap = v.capacity();
         unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
     }
 }
@@ -244,6 +250,30 @@ impl<B: ExtensibleField<3>> Neg for CubeExtension<B> {
     }
 }
 
+impl<B: ExtensibleField<3>> Sum for CubeExtension<B> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<'a, B: ExtensibleField<3>> Sum<&'a Self> for CubeExtension<B> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, &x| acc + x)
+    }
+}
+
+impl<B: ExtensibleField<3>> Product for CubeExtension<B> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<'a, B: ExtensibleField<3>> Product<&'a Self> for CubeExtension<B> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, &x| acc * x)
+    }
+}
+
 // TYPE CONVERSIONS
 // ------------------------------------------------------------------------------------------------
 
@@ -336,6 +366,23 @@ impl<B: ExtensibleField<3>> Deserializable for CubeExtension<B> {
     }
 }
 
+impl<B: ExtensibleField<3> + CanonicalSerializable> CanonicalSerializable for CubeExtension<B> {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        self.0.write_canonical(target);
+        self.1.write_canonical(target);
+        self.2.write_canonical(target);
+    }
+}
+
+impl<B: ExtensibleField<3> + CanonicalDeserializable> CanonicalDeserializable for CubeExtension<B> {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value0 = B::read_canonical(source)?;
+        let value1 = B::read_canonical(source)?;
+        let value2 = B::read_canonical(source)?;
+        Ok(Self(value0, value1, value2))
+    }
+}
+
 // TESTS
 // ================================================================================================
 
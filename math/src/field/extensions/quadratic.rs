@@ -8,12 +8,15 @@ use super::{ExtensibleField, ExtensionOf, FieldElement};
 use core::{
     convert::TryFrom,
     fmt,
+    iter::{Product, Sum},
+    mem,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     slice,
 };
 use utils::{
-    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
-    DeserializationError, Randomizable, Serializable, SliceReader,
+    collections::Vec, group_vector_elements, string::ToString, AsBytes, ByteReader, ByteWriter,
+    CanonicalDeserializable, CanonicalSerializable, Deserializable, DeserializationError,
+    Randomizable, Serializable, SliceReader,
 };
 
 // QUADRATIC EXTENSION FIELD
@@ -43,15 +46,18 @@ impl<B: ExtensibleField<2>> QuadExtension<B> {
     /// field by fusing two adjacent base elements together. The output vector is half the length
     /// of the source vector.
     fn base_to_quad_vector(source: Vec<B>) -> Vec<Self> {
-        debug_assert!(
-            source.len() % 2 == 0,
-            "source vector length must be divisible by two, but was {}",
-            source.len()
-        );
-        let mut v = core::mem::ManuallyDrop::new(source);
+        // the extension element is a #[repr(C)] struct of 2 base elements, and thus, has the
+        // same layout as an array of 2 base elements; both sides of these checks are
+        // constants, and thus, the checks are optimized away
+        assert_eq!(mem::size_of::<Self>(), mem::size_of::<[B; 2]>());
+        assert_eq!(mem::align_of::<Self>(), mem::align_of::<[B; 2]>());
+
+        // grouping the elements makes sure that the capacity of the vector is divisible by two,
+        // and thus, that the memory will be de-allocated with the layout it was allocated with
+        let mut v = mem::ManuallyDrop::new(group_vector_elements::<B, 2>(source));
         let p = v.as_mut_ptr();
-        let len = v.len() / 2;
-        let cap = v.capacity() / 2;
+        let len = v.len();
+        let cap = v.capacity();
         unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
     }
 }
@@ -235,6 +241,30 @@ impl<B: ExtensibleField<2>> Neg for QuadExtension<B> {
     }
 }
 
+impl<B: ExtensibleField<2>> Sum for QuadExtension<B> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<'a, B: ExtensibleField<2>> Sum<&'a Self> for QuadExtension<B> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, &x| acc + x)
+    }
+}
+
+impl<B: ExtensibleField<2>> Product for QuadExtension<B> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<'a, B: ExtensibleField<2>> Product<&'a Self> for QuadExtension<B> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, &x| acc * x)
+    }
+}
+
 // TYPE CONVERSIONS
 // ------------------------------------------------------------------------------------------------
 
@@ -325,6 +355,21 @@ impl<B: ExtensibleField<2>> Deserializable for QuadExtension<B> {
     }
 }
 
+impl<B: ExtensibleField<2> + CanonicalSerializable> CanonicalSerializable for QuadExtension<B> {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        self.0.write_canonical(target);
+        self.1.write_canonical(target);
+    }
+}
+
+impl<B: ExtensibleField<2> + CanonicalDeserializable> CanonicalDeserializable for QuadExtension<B> {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let value0 = B::read_canonical(source)?;
+        let value1 = B::read_canonical(source)?;
+        Ok(Self(value0, value1))
+    }
+}
+
 // TESTS
 // ================================================================================================
 
@@ -0,0 +1,521 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ExtensibleField, ExtensionOf, FieldElement};
+use core::{
+    convert::TryFrom,
+    fmt,
+    iter::{Product, Sum},
+    mem,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    slice,
+};
+use utils::{
+    collections::Vec, group_vector_elements, string::ToString, AsBytes, ByteReader, ByteWriter,
+    CanonicalDeserializable, CanonicalSerializable, Deserializable, DeserializationError,
+    Randomizable, Serializable, SliceReader,
+};
+
+// SEXTIC EXTENSION FIELD
+// ================================================================================================
+
+/// Represents an element in a degree 6 extension of a [StarkField](crate::StarkField).
+///
+/// The extension element is represented by 6 base field elements; how these elements are
+/// interpreted (e.g., as coefficients of a polynomial in a root of an irreducible polynomial of
+/// degree 6, or as an element of a tower of extensions) is defined by the implementation of the
+/// [ExtensibleField] trait.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct SexticExtension<B: ExtensibleField<6>>([B; 6]);
+
+impl<B: ExtensibleField<6>> SexticExtension<B> {
+    /// Returns a new extension element instantiated from the provided base elements.
+    pub fn new(coefficients: [B; 6]) -> Self {
+        Self(coefficients)
+    }
+
+    /// Returns true if the base field specified by B type parameter supports sextic extensions.
+    pub fn is_supported() -> bool {
+        <B as ExtensibleField<6>>::is_supported()
+    }
+
+    /// Converts a vector of base elements into a vector of elements in a sextic extension field
+    /// by fusing six adjacent base elements together. The output vector is six times shorter
+    /// than the source vector.
+    fn base_to_sextic_vector(source: Vec<B>) -> Vec<Self> {
+        // the extension element is a #[repr(C)] struct of 6 base elements, and thus, has the
+        // same layout as an array of 6 base elements; both sides of these checks are
+        // constants, and thus, the checks are optimized away
+        assert_eq!(mem::size_of::<Self>(), mem::size_of::<[B; 6]>());
+        assert_eq!(mem::align_of::<Self>(), mem::align_of::<[B; 6]>());
+
+        // grouping the elements makes sure that the capacity of the vector is divisible by six,
+        // and thus, that the memory will be de-allocated with the layout it was allocated with
+        let mut v = mem::ManuallyDrop::new(group_vector_elements::<B, 6>(source));
+        let p = v.as_mut_ptr();
+        let len = v.len();
+        let cap = v.capacity();
+        unsafe { Vec::from_raw_parts(p as *mut Self, len, cap) }
+    }
+}
+
+impl<B: ExtensibleField<6>> FieldElement for SexticExtension<B> {
+    type Representation = B::Representation;
+    type BaseField = B;
+
+    const ELEMENT_BYTES: usize = B::ELEMENT_BYTES * 6;
+    const IS_CANONICAL: bool = B::IS_CANONICAL;
+    const ZERO: Self = Self([B::ZERO; 6]);
+    const ONE: Self = Self([B::ONE, B::ZERO, B::ZERO, B::ZERO, B::ZERO, B::ZERO]);
+
+    #[inline]
+    fn double(self) -> Self {
+        Self(self.0.map(|x| x.double()))
+    }
+
+    #[inline]
+    fn inv(self) -> Self {
+        if self == Self::ZERO {
+            return self;
+        }
+
+        // the product of all conjugates of x (including x itself) is the norm of x, which is an
+        // element of the base field; thus, 1/x is the product of all other conjugates divided
+        // by the norm
+        let mut conjugate = <B as ExtensibleField<6>>::frobenius(self.0);
+        let mut numerator = conjugate;
+        for _ in 0..4 {
+            conjugate = <B as ExtensibleField<6>>::frobenius(conjugate);
+            numerator = <B as ExtensibleField<6>>::mul(numerator, conjugate);
+        }
+
+        let norm = <B as ExtensibleField<6>>::mul(self.0, numerator);
+        debug_assert!(
+            norm[1..].iter().all(|&c| c == B::ZERO),
+            "norm must be in the base field"
+        );
+        let denom_inv = norm[0].inv();
+
+        Self(numerator.map(|c| c * denom_inv))
+    }
+
+    #[inline]
+    fn conjugate(&self) -> Self {
+        Self(<B as ExtensibleField<6>>::frobenius(self.0))
+    }
+
+    fn elements_as_bytes(elements: &[Self]) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(
+                elements.as_ptr() as *const u8,
+                elements.len() * Self::ELEMENT_BYTES,
+            )
+        }
+    }
+
+    unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError> {
+        if bytes.len() % Self::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "number of bytes ({}) does not divide into whole number of field elements",
+                bytes.len(),
+            )));
+        }
+
+        let p = bytes.as_ptr();
+        let len = bytes.len() / Self::ELEMENT_BYTES;
+
+        // make sure the bytes are aligned on the boundary consistent with base element alignment
+        if (p as usize) % Self::BaseField::ELEMENT_BYTES != 0 {
+            return Err(DeserializationError::InvalidValue(
+                "slice memory alignment is not valid for this field element type".to_string(),
+            ));
+        }
+
+        Ok(slice::from_raw_parts(p as *const Self, len))
+    }
+
+    fn zeroed_vector(n: usize) -> Vec<Self> {
+        // get six times the number of base elements and re-interpret them as sextic field
+        // elements
+        let result = B::zeroed_vector(n * 6);
+        Self::base_to_sextic_vector(result)
+    }
+
+    fn as_base_elements(elements: &[Self]) -> &[Self::BaseField] {
+        let ptr = elements.as_ptr();
+        let len = elements.len() * 6;
+        unsafe { slice::from_raw_parts(ptr as *const Self::BaseField, len) }
+    }
+}
+
+impl<B: ExtensibleField<6>> ExtensionOf<B> for SexticExtension<B> {
+    #[inline(always)]
+    fn mul_base(self, other: B) -> Self {
+        Self(<B as ExtensibleField<6>>::mul_base(self.0, other))
+    }
+}
+
+impl<B: ExtensibleField<6>> Randomizable for SexticExtension<B> {
+    const VALUE_SIZE: usize = B::ELEMENT_BYTES * 6;
+
+    fn from_random_bytes(bytes: &[u8]) -> Option<Self> {
+        Self::try_from(bytes).ok()
+    }
+}
+
+impl<B: ExtensibleField<6>> fmt::Display for SexticExtension<B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = &self.0;
+        write!(
+            f,
+            "({}, {}, {}, {}, {}, {})",
+            c[0], c[1], c[2], c[3], c[4], c[5]
+        )
+    }
+}
+
+// OVERLOADED OPERATORS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: ExtensibleField<6>> Add for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        let mut result = self.0;
+        for (a, b) in result.iter_mut().zip(rhs.0) {
+            *a += b;
+        }
+        Self(result)
+    }
+}
+
+impl<B: ExtensibleField<6>> AddAssign for SexticExtension<B> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs
+    }
+}
+
+impl<B: ExtensibleField<6>> Sub for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        let mut result = self.0;
+        for (a, b) in result.iter_mut().zip(rhs.0) {
+            *a -= b;
+        }
+        Self(result)
+    }
+}
+
+impl<B: ExtensibleField<6>> SubAssign for SexticExtension<B> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<B: ExtensibleField<6>> Mul for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self {
+        Self(<B as ExtensibleField<6>>::mul(self.0, rhs.0))
+    }
+}
+
+impl<B: ExtensibleField<6>> MulAssign for SexticExtension<B> {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs
+    }
+}
+
+impl<B: ExtensibleField<6>> Div for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self {
+        self * rhs.inv()
+    }
+}
+
+impl<B: ExtensibleField<6>> DivAssign for SexticExtension<B> {
+    #[inline]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs
+    }
+}
+
+impl<B: ExtensibleField<6>> Neg for SexticExtension<B> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.map(|x| -x))
+    }
+}
+
+impl<B: ExtensibleField<6>> Sum for SexticExtension<B> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<'a, B: ExtensibleField<6>> Sum<&'a Self> for SexticExtension<B> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, &x| acc + x)
+    }
+}
+
+impl<B: ExtensibleField<6>> Product for SexticExtension<B> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<'a, B: ExtensibleField<6>> Product<&'a Self> for SexticExtension<B> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, &x| acc * x)
+    }
+}
+
+// TYPE CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+impl<B: ExtensibleField<6>> From<B> for SexticExtension<B> {
+    fn from(value: B) -> Self {
+        Self([value, B::ZERO, B::ZERO, B::ZERO, B::ZERO, B::ZERO])
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u128> for SexticExtension<B> {
+    fn from(value: u128) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u64> for SexticExtension<B> {
+    fn from(value: u64) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u32> for SexticExtension<B> {
+    fn from(value: u32) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u16> for SexticExtension<B> {
+    fn from(value: u16) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<B: ExtensibleField<6>> From<u8> for SexticExtension<B> {
+    fn from(value: u8) -> Self {
+        Self::from(B::from(value))
+    }
+}
+
+impl<'a, B: ExtensibleField<6>> TryFrom<&'a [u8]> for SexticExtension<B> {
+    type Error = DeserializationError;
+
+    /// Converts a slice of bytes into a field element; returns error if the value encoded in bytes
+    /// is not a valid field element. The bytes are assumed to be in little-endian byte order.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < B::ELEMENT_BYTES * 6 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "not enough bytes for a full field element; expected {} bytes, but was {} bytes",
+                B::ELEMENT_BYTES * 6,
+                bytes.len(),
+            )));
+        }
+        if bytes.len() > B::ELEMENT_BYTES * 6 {
+            return Err(DeserializationError::InvalidValue(format!(
+                "too many bytes for a field element; expected {} bytes, but was {} bytes",
+                B::ELEMENT_BYTES * 6,
+                bytes.len(),
+            )));
+        }
+        let mut reader = SliceReader::new(bytes);
+        Self::read_from(&mut reader)
+    }
+}
+
+impl<B: ExtensibleField<6>> AsBytes for SexticExtension<B> {
+    fn as_bytes(&self) -> &[u8] {
+        // TODO: take endianness into account
+        let self_ptr: *const Self = self;
+        unsafe { slice::from_raw_parts(self_ptr as *const u8, B::ELEMENT_BYTES * 6) }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ------------------------------------------------------------------------------------------------
+
+impl<B: ExtensibleField<6>> Serializable for SexticExtension<B> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        for value in self.0.iter() {
+            value.write_into(target);
+        }
+    }
+}
+
+impl<B: ExtensibleField<6>> Deserializable for SexticExtension<B> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let mut result = [B::ZERO; 6];
+        for value in result.iter_mut() {
+            *value = B::read_from(source)?;
+        }
+        Ok(Self(result))
+    }
+}
+
+impl<B: ExtensibleField<6> + CanonicalSerializable> CanonicalSerializable for SexticExtension<B> {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        self.0.write_canonical(target);
+    }
+}
+
+impl<B: ExtensibleField<6> + CanonicalDeserializable> CanonicalDeserializable
+    for SexticExtension<B>
+{
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(Self(<[B; 6]>::read_canonical(source)?))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{DeserializationError, FieldElement, SexticExtension};
+    use crate::field::f64::BaseElement;
+    use rand_utils::rand_value;
+
+    // BASIC ALGEBRA
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn add() {
+        // identity
+        let r: SexticExtension<BaseElement> = rand_value();
+        assert_eq!(r, r + SexticExtension::<BaseElement>::ZERO);
+
+        // test random values
+        let r1: SexticExtension<BaseElement> = rand_value();
+        let r2: SexticExtension<BaseElement> = rand_value();
+
+        let mut expected = r1.0;
+        for (a, b) in expected.iter_mut().zip(r2.0) {
+            *a += b;
+        }
+        assert_eq!(SexticExtension(expected), r1 + r2);
+    }
+
+    #[test]
+    fn sub() {
+        // identity
+        let r: SexticExtension<BaseElement> = rand_value();
+        assert_eq!(r, r - SexticExtension::<BaseElement>::ZERO);
+
+        // test random values
+        let r1: SexticExtension<BaseElement> = rand_value();
+        let r2: SexticExtension<BaseElement> = rand_value();
+
+        let mut expected = r1.0;
+        for (a, b) in expected.iter_mut().zip(r2.0) {
+            *a -= b;
+        }
+        assert_eq!(SexticExtension(expected), r1 - r2);
+    }
+
+    // INITIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn zeroed_vector() {
+        let result = SexticExtension::<BaseElement>::zeroed_vector(4);
+        assert_eq!(4, result.len());
+        for element in result.into_iter() {
+            assert_eq!(SexticExtension::<BaseElement>::ZERO, element);
+        }
+    }
+
+    // SERIALIZATION / DESERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn elements_as_bytes() {
+        let source = build_test_elements();
+
+        let mut expected = vec![];
+        for element in source.iter() {
+            for value in element.0.iter() {
+                expected.extend_from_slice(&value.inner().to_le_bytes());
+            }
+        }
+
+        assert_eq!(
+            expected,
+            SexticExtension::<BaseElement>::elements_as_bytes(&source)
+        );
+    }
+
+    #[test]
+    fn bytes_as_elements() {
+        let elements = build_test_elements();
+
+        let mut bytes = vec![];
+        for element in elements.iter() {
+            for value in element.0.iter() {
+                bytes.extend_from_slice(&value.inner().to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&BaseElement::new(5).inner().to_le_bytes());
+
+        let result = unsafe { SexticExtension::<BaseElement>::bytes_as_elements(&bytes[..96]) };
+        assert!(result.is_ok());
+        assert_eq!(elements, result.unwrap());
+
+        let result = unsafe { SexticExtension::<BaseElement>::bytes_as_elements(&bytes) };
+        assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+
+        let result = unsafe { SexticExtension::<BaseElement>::bytes_as_elements(&bytes[1..]) };
+        assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+    }
+
+    // UTILITIES
+    // --------------------------------------------------------------------------------------------
+
+    #[test]
+    fn as_base_elements() {
+        let elements = build_test_elements();
+        let expected = (1..=12).map(BaseElement::new).collect::<Vec<_>>();
+
+        assert_eq!(
+            expected,
+            SexticExtension::<BaseElement>::as_base_elements(&elements)
+        );
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn build_test_elements() -> Vec<SexticExtension<BaseElement>> {
+        vec![
+            SexticExtension([1, 2, 3, 4, 5, 6].map(BaseElement::new)),
+            SexticExtension([7, 8, 9, 10, 11, 12].map(BaseElement::new)),
+        ]
+    }
+}
@@ -15,6 +15,7 @@ use super::{ExtensibleField, FieldElement, StarkField};
 use core::{
     convert::{TryFrom, TryInto},
     fmt::{Debug, Display, Formatter},
+    iter::{Product, Sum},
     mem,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     slice,
@@ -22,8 +23,8 @@ use core::{
 use utils::{
     collections::Vec,
     string::{String, ToString},
-    AsBytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Randomizable,
-    Serializable,
+    AsBytes, ByteReader, ByteWriter, CanonicalDeserializable, CanonicalSerializable,
+    Deserializable, DeserializationError, Randomizable, Serializable,
 };
 
 #[cfg(test)]
@@ -48,9 +49,15 @@ const ELEMENT_BYTES: usize = core::mem::size_of::<u128>();
 ///
 /// Internal values are stored in their canonical form in the range [0, M). The backing type is
 /// `u128`.
+#[repr(transparent)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 pub struct BaseElement(u128);
 
+// base field elements are re-interpreted as u128 values (e.g., to request zero-filled memory from
+// the allocator in zeroed_vector()); this relies on both types having the same layout
+const _: () = assert!(mem::size_of::<BaseElement>() == mem::size_of::<u128>());
+const _: () = assert!(mem::align_of::<BaseElement>() == mem::align_of::<u128>());
+
 impl BaseElement {
     /// Creates a new field element from a u128 value. If the value is greater than or equal to
     /// the field modulus, modular reduction is silently performed. This function can also be used
@@ -110,8 +117,9 @@ impl FieldElement for BaseElement {
         // this uses a specialized vector initialization code which requests zero-filled memory
         // from the OS; unfortunately, this works only for built-in types and we can't use
         // Self::ZERO here as much less efficient initialization procedure will be invoked.
-        // We also use u128 to make sure the memory is aligned correctly for our element size.
-        debug_assert_eq!(Self::ELEMENT_BYTES, mem::size_of::<u128>());
+        // We also use u128 to make sure the memory is aligned correctly for our element size;
+        // re-interpreting the vector is sound because BaseElement is a transparent wrapper
+        // around u128 (this is also checked by static assertions above).
         let result = vec![0u128; n];
 
         // translate a zero-filled vector of u128s into a vector of base field elements
@@ -167,6 +175,14 @@ impl StarkField for BaseElement {
     fn to_repr(&self) -> Self::Representation {
         self.0
     }
+
+    fn try_from_u128(value: u128) -> Option<Self> {
+        if value < M {
+            Some(BaseElement(value))
+        } else {
+            None
+        }
+    }
 }
 
 impl Randomizable for BaseElement {
@@ -250,6 +266,30 @@ impl Neg for BaseElement {
     }
 }
 
+impl Sum for BaseElement {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Self> for BaseElement {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, &x| acc + x)
+    }
+}
+
+impl Product for BaseElement {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Self> for BaseElement {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, &x| acc * x)
+    }
+}
+
 // QUADRATIC EXTENSION
 // ================================================================================================
 
@@ -299,12 +339,38 @@ impl ExtensibleField<3> for BaseElement {
     }
 }
 
+// SEXTIC EXTENSION
+// ================================================================================================
+
+/// Sextic extension for this field is not implemented as quadratic extension already provides
+/// sufficient security level.
+impl ExtensibleField<6> for BaseElement {
+    fn mul(_a: [Self; 6], _b: [Self; 6]) -> [Self; 6] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn mul_base(_a: [Self; 6], _b: Self) -> [Self; 6] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn frobenius(_x: [Self; 6]) -> [Self; 6] {
+        unimplemented!()
+    }
+
+    fn is_supported() -> bool {
+        false
+    }
+}
+
 // TYPE CONVERSIONS
 // ================================================================================================
 
 impl From<u128> for BaseElement {
     /// Converts a 128-bit value into a field element. If the value is greater than or equal to
-    /// the field modulus, modular reduction is silently performed.
+    /// the field modulus, modular reduction is silently performed; use
+    /// [StarkField::try_from_u128()] to reject such values instead.
     fn from(value: u128) -> Self {
         BaseElement::new(value)
     }
@@ -397,6 +463,18 @@ impl Deserializable for BaseElement {
     }
 }
 
+impl CanonicalSerializable for BaseElement {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        self.write_into(target)
+    }
+}
+
+impl CanonicalDeserializable for BaseElement {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_from(source)
+    }
+}
+
 // FINITE FIELD ARITHMETIC
 // ================================================================================================
 
@@ -244,6 +244,24 @@ fn read_elements_from() {
     }
 }
 
+// CONVERSIONS
+// ================================================================================================
+
+#[test]
+fn conversions_at_modulus_boundary() {
+    assert_eq!(
+        Some(BaseElement::new(M - 1)),
+        BaseElement::try_from_u128(M - 1)
+    );
+    assert_eq!(None, BaseElement::try_from_u128(M));
+    assert_eq!(None, BaseElement::try_from_u128(u128::MAX));
+
+    // every 64-bit value is smaller than the modulus
+    let e = BaseElement::try_from_u64(u64::MAX).unwrap();
+    assert_eq!(u64::MAX as u128, e.to_repr());
+    assert_eq!(e, BaseElement::from_u64_reduced(u64::MAX));
+}
+
 // INITIALIZATION
 // ================================================================================================
 
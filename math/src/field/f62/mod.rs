@@ -14,13 +14,14 @@ use super::{ExtensibleField, FieldElement, StarkField};
 use core::{
     convert::{TryFrom, TryInto},
     fmt::{Debug, Display, Formatter},
+    iter::{Product, Sum},
     mem,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     slice,
 };
 use utils::{
-    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
-    DeserializationError, Randomizable, Serializable,
+    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, CanonicalDeserializable,
+    CanonicalSerializable, Deserializable, DeserializationError, Randomizable, Serializable,
 };
 
 #[cfg(test)]
@@ -54,9 +55,15 @@ const G: u64 = 4421547261963328785;
 ///
 /// Internal values are stored in Montgomery representation and can be in the range [0; 2M). The
 /// backing type is `u64`.
+#[repr(transparent)]
 #[derive(Copy, Clone, Debug, Default)]
 pub struct BaseElement(u64);
 
+// base field elements are re-interpreted as u64 values (e.g., to request zero-filled memory from
+// the allocator in zeroed_vector()); this relies on both types having the same layout
+const _: () = assert!(mem::size_of::<BaseElement>() == mem::size_of::<u64>());
+const _: () = assert!(mem::align_of::<BaseElement>() == mem::align_of::<u64>());
+
 impl BaseElement {
     /// Creates a new field element from the provided `value`; the value is converted into
     /// Montgomery representation.
@@ -86,6 +93,9 @@ impl FieldElement for BaseElement {
         Self(z - q)
     }
 
+    /// Exponentiates this field element by `power` parameter.
+    ///
+    /// The running time of this method depends on the value of `power`.
     fn exp(self, power: Self::Representation) -> Self {
         let mut b = self;
 
@@ -106,6 +116,12 @@ impl FieldElement for BaseElement {
         r
     }
 
+    #[inline]
+    fn exp_vartime(self, power: Self::Representation) -> Self {
+        // exp() is already a variable-time implementation for this field
+        self.exp(power)
+    }
+
     fn inv(self) -> Self {
         BaseElement(inv(self.0))
     }
@@ -145,7 +161,9 @@ impl FieldElement for BaseElement {
         // this uses a specialized vector initialization code which requests zero-filled memory
         // from the OS; unfortunately, this works only for built-in types and we can't use
         // Self::ZERO here as much less efficient initialization procedure will be invoked.
-        // We also use u64 to make sure the memory is aligned correctly for our element size.
+        // We also use u64 to make sure the memory is aligned correctly for our element size;
+        // re-interpreting the vector is sound because BaseElement is a transparent wrapper
+        // around u64 (this is also checked by static assertions above).
         let result = vec![0u64; n];
 
         // translate a zero-filled vector of u64s into a vector of base field elements
@@ -204,6 +222,10 @@ impl StarkField for BaseElement {
         // since the result of multiplication can be in [0, 2M), we need to normalize it
         normalize(result)
     }
+
+    fn try_from_u128(value: u128) -> Option<Self> {
+        u64::try_from(value).ok().and_then(Self::try_from_u64)
+    }
 }
 
 impl Randomizable for BaseElement {
@@ -301,6 +323,30 @@ impl Neg for BaseElement {
     }
 }
 
+impl Sum for BaseElement {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Self> for BaseElement {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, &x| acc + x)
+    }
+}
+
+impl Product for BaseElement {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Self> for BaseElement {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, &x| acc * x)
+    }
+}
+
 // QUADRATIC EXTENSION
 // ================================================================================================
 
@@ -378,12 +424,38 @@ impl ExtensibleField<3> for BaseElement {
     }
 }
 
+// SEXTIC EXTENSION
+// ================================================================================================
+
+/// Sextic extension for this field is not implemented as cubic extension already provides
+/// sufficient security level.
+impl ExtensibleField<6> for BaseElement {
+    fn mul(_a: [Self; 6], _b: [Self; 6]) -> [Self; 6] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn mul_base(_a: [Self; 6], _b: Self) -> [Self; 6] {
+        unimplemented!()
+    }
+
+    #[inline(always)]
+    fn frobenius(_x: [Self; 6]) -> [Self; 6] {
+        unimplemented!()
+    }
+
+    fn is_supported() -> bool {
+        false
+    }
+}
+
 // TYPE CONVERSIONS
 // ================================================================================================
 
 impl From<u128> for BaseElement {
     /// Converts a 128-bit value into a field element. If the value is greater than or equal to
-    /// the field modulus, modular reduction is silently performed.
+    /// the field modulus, modular reduction is silently performed; use
+    /// [StarkField::try_from_u128()] to reject such values instead.
     fn from(value: u128) -> Self {
         // make sure the value is < 4M^2 - 4M + 1; this is overly conservative and a single
         // subtraction of (M * 2^65) should be enough, but this needs to be proven
@@ -405,7 +477,9 @@ impl From<u128> for BaseElement {
 
 impl From<u64> for BaseElement {
     /// Converts a 64-bit value into a field element. If the value is greater than or equal to
-    /// the field modulus, modular reduction is silently performed.
+    /// the field modulus, modular reduction is silently performed; use
+    /// [StarkField::try_from_u64()] to reject such values, or
+    /// [StarkField::from_u64_reduced()] to make the reduction explicit.
     fn from(value: u64) -> Self {
         BaseElement::new(value)
     }
@@ -507,6 +581,18 @@ impl Deserializable for BaseElement {
     }
 }
 
+impl CanonicalSerializable for BaseElement {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        self.write_into(target)
+    }
+}
+
+impl CanonicalDeserializable for BaseElement {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_from(source)
+    }
+}
+
 // FINITE FIELD ARITHMETIC
 // ================================================================================================
 
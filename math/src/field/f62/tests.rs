@@ -300,6 +300,32 @@ fn bytes_as_elements() {
     assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
 }
 
+// CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn conversions_at_modulus_boundary() {
+    let m = super::M;
+    assert_eq!(m - 1, BaseElement::try_from_u64(m - 1).unwrap().to_repr());
+    assert_eq!(None, BaseElement::try_from_u64(m));
+    assert_eq!(None, BaseElement::try_from_u64(u64::MAX));
+
+    assert_eq!(
+        m - 1,
+        BaseElement::try_from_u128(m as u128 - 1).unwrap().to_repr()
+    );
+    assert_eq!(None, BaseElement::try_from_u128(m as u128));
+    assert_eq!(None, BaseElement::try_from_u128(1 << 64));
+    assert_eq!(None, BaseElement::try_from_u128(u128::MAX));
+
+    assert_eq!(BaseElement::ZERO, BaseElement::from_u64_reduced(m));
+    assert_eq!(1, BaseElement::from_u64_reduced(m + 1).to_repr());
+    assert_eq!(
+        u64::MAX % m,
+        BaseElement::from_u64_reduced(u64::MAX).to_repr()
+    );
+}
+
 // INITIALIZATION
 // ------------------------------------------------------------------------------------------------
 
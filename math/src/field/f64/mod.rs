@@ -19,15 +19,19 @@ use super::{ExtensibleField, FieldElement, StarkField};
 use core::{
     convert::{TryFrom, TryInto},
     fmt::{Debug, Display, Formatter},
+    iter::{Product, Sum},
     mem,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
     slice,
 };
 use utils::{
-    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, Deserializable,
-    DeserializationError, Randomizable, Serializable,
+    collections::Vec, string::ToString, AsBytes, ByteReader, ByteWriter, CanonicalDeserializable,
+    CanonicalSerializable, Deserializable, DeserializationError, Randomizable, Serializable,
 };
 
+#[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "avx2"))]
+mod simd;
+
 #[cfg(test)]
 mod tests;
 
@@ -53,8 +57,14 @@ const ELEMENT_BYTES: usize = core::mem::size_of::<u64>();
 ///
 /// Internal values represent x * R mod M where R = 2^64 mod M and x in [0, M).
 /// The backing type is `u64` but the internal values are always in the range [0, M).
+#[repr(transparent)]
 #[derive(Copy, Clone, Debug, Default)]
 pub struct BaseElement(u64);
+
+// base field elements are re-interpreted as u64 values (e.g., to request zero-filled memory from
+// the allocator in zeroed_vector()); this relies on both types having the same layout
+const _: () = assert!(mem::size_of::<BaseElement>() == mem::size_of::<u64>());
+const _: () = assert!(mem::align_of::<BaseElement>() == mem::align_of::<u64>());
 impl BaseElement {
     /// Creates a new field element from the provided `value`; the value is converted into
     /// Montgomery representation.
@@ -101,6 +111,9 @@ impl FieldElement for BaseElement {
         Self(result.wrapping_sub(M * over))
     }
 
+    /// Exponentiates this field element by `power` parameter.
+    ///
+    /// The running time of this method is independent of the value of `power`.
     #[inline]
     fn exp(self, power: Self::Representation) -> Self {
         let mut b: Self;
@@ -117,6 +130,27 @@ impl FieldElement for BaseElement {
         r
     }
 
+    #[inline]
+    fn exp_vartime(self, power: Self::Representation) -> Self {
+        let mut b = self;
+
+        if power == 0 {
+            return Self::ONE;
+        } else if b == Self::ZERO {
+            return Self::ZERO;
+        }
+
+        let mut r = if power & 1 == 1 { b } else { Self::ONE };
+        for i in 1..64 - power.leading_zeros() {
+            b = b.square();
+            if (power >> i) & 1 == 1 {
+                r *= b;
+            }
+        }
+
+        r
+    }
+
     #[inline]
     #[allow(clippy::many_single_char_names)]
     fn inv(self) -> Self {
@@ -180,11 +214,28 @@ impl FieldElement for BaseElement {
         Ok(slice::from_raw_parts(p as *const Self, len))
     }
 
+    #[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "avx2"))]
+    fn add_assign_slice(a: &mut [Self], b: &[Self]) {
+        simd::add_assign_slice(a, b)
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "avx2"))]
+    fn mul_assign_slice(a: &mut [Self], b: &[Self]) {
+        simd::mul_assign_slice(a, b)
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64", target_feature = "avx2"))]
+    fn scalar_mul_acc_slice(a: &mut [Self], c: Self, b: &[Self]) {
+        simd::scalar_mul_acc_slice(a, c, b)
+    }
+
     fn zeroed_vector(n: usize) -> Vec<Self> {
         // this uses a specialized vector initialization code which requests zero-filled memory
         // from the OS; unfortunately, this works only for built-in types and we can't use
         // Self::ZERO here as much less efficient initialization procedure will be invoked.
-        // We also use u64 to make sure the memory is aligned correctly for our element size.
+        // We also use u64 to make sure the memory is aligned correctly for our element size;
+        // re-interpreting the vector is sound because BaseElement is a transparent wrapper
+        // around u64 (this is also checked by static assertions above).
         let result = vec![0u64; n];
 
         // translate a zero-filled vector of u64s into a vector of base field elements
@@ -246,7 +297,18 @@ impl StarkField for BaseElement {
         let x = self.0;
         let (r, c) = x.overflowing_add(x << 32);
         let res = r.wrapping_sub(r >> 32).wrapping_sub(c as u64);
-        M - res
+        // the difference is in the range [1, M]; M is the representation of zero, and thus, it
+        // must be reduced to 0
+        let res = M - res;
+        if res == M {
+            0
+        } else {
+            res
+        }
+    }
+
+    fn try_from_u128(value: u128) -> Option<Self> {
+        u64::try_from(value).ok().and_then(Self::try_from_u64)
     }
 }
 
@@ -360,6 +422,30 @@ impl Neg for BaseElement {
     }
 }
 
+impl Sum for BaseElement {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a Self> for BaseElement {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, |acc, &x| acc + x)
+    }
+}
+
+impl Product for BaseElement {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a Self> for BaseElement {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, |acc, &x| acc * x)
+    }
+}
+
 // QUADRATIC EXTENSION
 // ================================================================================================
 
@@ -444,11 +530,72 @@ impl ExtensibleField<3> for BaseElement {
     }
 }
 
+// SEXTIC EXTENSION
+// ================================================================================================
+
+/// Defines a sextic extension of the base field as a quadratic extension of the cubic extension
+/// defined above, over an irreducible polynomial y<sup>2</sup> - y + 2. Thus, an extension element
+/// is defined as a + b * ψ, where ψ is a root of this polynomial, and a and b are elements of the
+/// cubic extension field. An element is represented by 6 base field elements such that the first
+/// 3 are the coefficients of a, and the last 3 are the coefficients of b.
+impl ExtensibleField<6> for BaseElement {
+    #[inline(always)]
+    fn mul(a: [Self; 6], b: [Self; 6]) -> [Self; 6] {
+        // performs multiplication in the extension field using 3 multiplications in the cubic
+        // extension field, similarly to how multiplication is done in the quadratic extension.
+        let (a0, a1) = split_sextic(a);
+        let (b0, b1) = split_sextic(b);
+
+        let a0b0 = <Self as ExtensibleField<3>>::mul(a0, b0);
+        let a1b1 = <Self as ExtensibleField<3>>::mul(a1, b1);
+        let a0a1_b0b1 = <Self as ExtensibleField<3>>::mul(
+            [a0[0] + a1[0], a0[1] + a1[1], a0[2] + a1[2]],
+            [b0[0] + b1[0], b0[1] + b1[1], b0[2] + b1[2]],
+        );
+
+        [
+            a0b0[0] - a1b1[0].double(),
+            a0b0[1] - a1b1[1].double(),
+            a0b0[2] - a1b1[2].double(),
+            a0a1_b0b1[0] - a0b0[0],
+            a0a1_b0b1[1] - a0b0[1],
+            a0a1_b0b1[2] - a0b0[2],
+        ]
+    }
+
+    #[inline(always)]
+    fn mul_base(a: [Self; 6], b: Self) -> [Self; 6] {
+        // multiplying an extension field element by a base field element requires just 6
+        // multiplications in the base field.
+        [a[0] * b, a[1] * b, a[2] * b, a[3] * b, a[4] * b, a[5] * b]
+    }
+
+    #[inline(always)]
+    fn frobenius(x: [Self; 6]) -> [Self; 6] {
+        // the Frobenius automorphism maps ψ to the other root of y^2 - y + 2, which is 1 - ψ;
+        // thus, (a + b * ψ)^p = (a^p + b^p) - b^p * ψ
+        let (x0, x1) = split_sextic(x);
+        let x0 = <Self as ExtensibleField<3>>::frobenius(x0);
+        let x1 = <Self as ExtensibleField<3>>::frobenius(x1);
+
+        [
+            x0[0] + x1[0],
+            x0[1] + x1[1],
+            x0[2] + x1[2],
+            -x1[0],
+            -x1[1],
+            -x1[2],
+        ]
+    }
+}
+
 // TYPE CONVERSIONS
 // ================================================================================================
 
 impl From<u128> for BaseElement {
-    /// Converts a 128-bit value into a field element.
+    /// Converts a 128-bit value into a field element. If the value is greater than or equal to
+    /// the field modulus, modular reduction is silently performed; use
+    /// [StarkField::try_from_u128()] to reject such values instead.
     fn from(x: u128) -> Self {
         //const R3: u128 = 1 (= 2^192 mod M );// thus we get that mont_red_var((mont_red_var(x) as u128) * R3) becomes
         //Self(mont_red_var(mont_red_var(x) as u128))  // Variable time implementation
@@ -458,7 +605,9 @@ impl From<u128> for BaseElement {
 
 impl From<u64> for BaseElement {
     /// Converts a 64-bit value into a field element. If the value is greater than or equal to
-    /// the field modulus, modular reduction is silently performed.
+    /// the field modulus, modular reduction is silently performed; use
+    /// [StarkField::try_from_u64()] to reject such values, or
+    /// [StarkField::from_u64_reduced()] to make the reduction explicit.
     fn from(value: u64) -> Self {
         Self::new(value)
     }
@@ -560,6 +709,18 @@ impl Deserializable for BaseElement {
     }
 }
 
+impl CanonicalSerializable for BaseElement {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        self.write_into(target)
+    }
+}
+
+impl CanonicalDeserializable for BaseElement {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        Self::read_from(source)
+    }
+}
+
 /// Squares the base N number of times and multiplies the result by the tail value.
 #[inline(always)]
 fn exp_acc<const N: usize>(base: BaseElement, tail: BaseElement) -> BaseElement {
@@ -570,6 +731,13 @@ fn exp_acc<const N: usize>(base: BaseElement, tail: BaseElement) -> BaseElement
     result * tail
 }
 
+/// Splits an element of the sextic extension field into the two cubic extension field elements
+/// it is composed of.
+#[inline(always)]
+fn split_sextic(x: [BaseElement; 6]) -> ([BaseElement; 3], [BaseElement; 3]) {
+    ([x[0], x[1], x[2]], [x[3], x[4], x[5]])
+}
+
 /// Montgomery reduction (variable time)
 #[allow(dead_code)]
 #[inline(always)]
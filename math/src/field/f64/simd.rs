@@ -0,0 +1,144 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! AVX2 implementations of slice operations in the f64 field.
+//!
+//! Each 256-bit vector holds 4 field elements. The operations mirror the scalar implementations
+//! of addition and Montgomery multiplication step by step, and thus, produce exactly the same
+//! internal representations of the results.
+
+use super::{BaseElement, M};
+use core::arch::x86_64::*;
+
+/// Number of field elements in a single AVX2 vector.
+const LANES: usize = 4;
+
+// SLICE OPERATIONS
+// ================================================================================================
+
+/// Adds elements of `b` to the corresponding elements of `a`.
+pub fn add_assign_slice(a: &mut [BaseElement], b: &[BaseElement]) {
+    assert_eq!(a.len(), b.len(), "slices must have the same length");
+    let n = a.len() - a.len() % LANES;
+    // SAFETY: both slices contain at least `n` elements, and unaligned loads and stores are used
+    unsafe {
+        for i in (0..n).step_by(LANES) {
+            let pa = a.as_mut_ptr().add(i);
+            store(pa, add(load(pa), load(b.as_ptr().add(i))));
+        }
+    }
+    for (a, &b) in a[n..].iter_mut().zip(&b[n..]) {
+        *a += b;
+    }
+}
+
+/// Multiplies elements of `a` by the corresponding elements of `b`.
+pub fn mul_assign_slice(a: &mut [BaseElement], b: &[BaseElement]) {
+    assert_eq!(a.len(), b.len(), "slices must have the same length");
+    let n = a.len() - a.len() % LANES;
+    // SAFETY: both slices contain at least `n` elements, and unaligned loads and stores are used
+    unsafe {
+        for i in (0..n).step_by(LANES) {
+            let pa = a.as_mut_ptr().add(i);
+            store(pa, mul(load(pa), load(b.as_ptr().add(i))));
+        }
+    }
+    for (a, &b) in a[n..].iter_mut().zip(&b[n..]) {
+        *a *= b;
+    }
+}
+
+/// Multiplies elements of `b` by `c` and adds the results to the corresponding elements of `a`.
+pub fn scalar_mul_acc_slice(a: &mut [BaseElement], c: BaseElement, b: &[BaseElement]) {
+    assert_eq!(a.len(), b.len(), "slices must have the same length");
+    let n = a.len() - a.len() % LANES;
+    // SAFETY: both slices contain at least `n` elements, and unaligned loads and stores are used
+    unsafe {
+        let c_vec = _mm256_set1_epi64x(c.0 as i64);
+        for i in (0..n).step_by(LANES) {
+            let pa = a.as_mut_ptr().add(i);
+            store(pa, add(load(pa), mul(load(b.as_ptr().add(i)), c_vec)));
+        }
+    }
+    for (a, &b) in a[n..].iter_mut().zip(&b[n..]) {
+        *a += b * c;
+    }
+}
+
+// VECTOR ARITHMETIC
+// ================================================================================================
+
+/// Computes a + b for each lane as a - (p - b), mirroring the scalar implementation.
+#[inline(always)]
+unsafe fn add(a: __m256i, b: __m256i) -> __m256i {
+    let t = _mm256_sub_epi64(_mm256_set1_epi64x(M as i64), b);
+    let x = _mm256_sub_epi64(a, t);
+    let borrow = lt(a, t);
+    _mm256_sub_epi64(x, _mm256_srli_epi64(borrow, 32))
+}
+
+/// Computes a * b for each lane by computing the full 128-bit product from 32-bit limbs and then
+/// applying Montgomery reduction.
+#[inline(always)]
+unsafe fn mul(a: __m256i, b: __m256i) -> __m256i {
+    let a_hi = _mm256_srli_epi64(a, 32);
+    let b_hi = _mm256_srli_epi64(b, 32);
+    let ll = _mm256_mul_epu32(a, b);
+    let lh = _mm256_mul_epu32(a, b_hi);
+    let hl = _mm256_mul_epu32(a_hi, b);
+    let hh = _mm256_mul_epu32(a_hi, b_hi);
+
+    // the sum of the middle limbs may overflow; the carry is worth 2^96, i.e. 2^32 in the high
+    // half of the product
+    let mid = _mm256_add_epi64(lh, hl);
+    let mid_carry = _mm256_and_si256(lt(mid, lh), _mm256_set1_epi64x(1 << 32));
+
+    let xl = _mm256_add_epi64(ll, _mm256_slli_epi64(mid, 32));
+    let xl_carry = lt(xl, ll);
+
+    let xh = _mm256_add_epi64(hh, _mm256_srli_epi64(mid, 32));
+    let xh = _mm256_add_epi64(xh, mid_carry);
+    // the carry mask is -1 when set, and thus, subtracting it adds the carry
+    let xh = _mm256_sub_epi64(xh, xl_carry);
+
+    mont_red(xl, xh)
+}
+
+/// Montgomery reduction of a 128-bit value with the specified low and high halves; this mirrors
+/// the constant-time scalar reduction.
+#[inline(always)]
+unsafe fn mont_red(xl: __m256i, xh: __m256i) -> __m256i {
+    let a = _mm256_add_epi64(xl, _mm256_slli_epi64(xl, 32));
+    let e = lt(a, xl);
+
+    // the overflow mask is -1 when set, and thus, adding it subtracts the overflow bit
+    let b = _mm256_sub_epi64(a, _mm256_srli_epi64(a, 32));
+    let b = _mm256_add_epi64(b, e);
+
+    let r = _mm256_sub_epi64(xh, b);
+    let c = lt(xh, b);
+    _mm256_sub_epi64(r, _mm256_srli_epi64(c, 32))
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a mask with all bits set in the lanes where `x` < `y` as unsigned integers.
+#[inline(always)]
+unsafe fn lt(x: __m256i, y: __m256i) -> __m256i {
+    let sign = _mm256_set1_epi64x(i64::MIN);
+    _mm256_cmpgt_epi64(_mm256_xor_si256(y, sign), _mm256_xor_si256(x, sign))
+}
+
+#[inline(always)]
+unsafe fn load(p: *const BaseElement) -> __m256i {
+    _mm256_loadu_si256(p as *const __m256i)
+}
+
+#[inline(always)]
+unsafe fn store(p: *mut BaseElement, value: __m256i) {
+    _mm256_storeu_si256(p as *mut __m256i, value)
+}
@@ -5,11 +5,12 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{BaseElement, DeserializationError, FieldElement, Serializable, StarkField, M};
-use crate::field::{CubeExtension, ExtensionOf, QuadExtension};
+use crate::field::{CubeExtension, ExtensionOf, QuadExtension, SexticExtension};
 use core::convert::TryFrom;
 use num_bigint::BigUint;
 use proptest::prelude::*;
-use rand_utils::rand_value;
+use rand_utils::{rand_value, rand_vector};
+use utils::{Deserializable, SliceReader};
 
 // MANUAL TESTS
 // ================================================================================================
@@ -115,6 +116,17 @@ fn element_to_repr() {
     let v = u64::MAX;
     let e = BaseElement::new(v);
     assert_eq!(v % super::M, e.to_repr());
+
+    // zero has a canonical representation, and thus, it can be serialized and read back
+    assert_eq!(0, BaseElement::ZERO.to_repr());
+    assert_eq!(0, BaseElement::new(super::M).to_repr());
+    let bytes = BaseElement::ZERO.to_bytes();
+    assert_eq!(vec![0; 8], bytes);
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(
+        BaseElement::ZERO,
+        BaseElement::read_from(&mut reader).unwrap()
+    );
 }
 
 #[test]
@@ -218,6 +230,32 @@ fn bytes_as_elements() {
     assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
 }
 
+// CONVERSIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn conversions_at_modulus_boundary() {
+    let m = M;
+    assert_eq!(m - 1, BaseElement::try_from_u64(m - 1).unwrap().to_repr());
+    assert_eq!(None, BaseElement::try_from_u64(m));
+    assert_eq!(None, BaseElement::try_from_u64(u64::MAX));
+
+    assert_eq!(
+        m - 1,
+        BaseElement::try_from_u128(m as u128 - 1).unwrap().to_repr()
+    );
+    assert_eq!(None, BaseElement::try_from_u128(m as u128));
+    assert_eq!(None, BaseElement::try_from_u128(1 << 64));
+    assert_eq!(None, BaseElement::try_from_u128(u128::MAX));
+
+    assert_eq!(BaseElement::ZERO, BaseElement::from_u64_reduced(m));
+    assert_eq!(1, BaseElement::from_u64_reduced(m + 1).to_repr());
+    assert_eq!(
+        u64::MAX % m,
+        BaseElement::from_u64_reduced(u64::MAX).to_repr()
+    );
+}
+
 // INITIALIZATION
 // ------------------------------------------------------------------------------------------------
 
@@ -230,6 +268,43 @@ fn zeroed_vector() {
     }
 }
 
+// SLICE OPERATIONS
+// ------------------------------------------------------------------------------------------------
+
+#[test]
+fn slice_ops() {
+    // lengths which are not multiples of the SIMD vector width exercise the scalar tail
+    for n in [0, 1, 3, 4, 7, 1023] {
+        let mut a: Vec<BaseElement> = rand_vector(n);
+        let b: Vec<BaseElement> = rand_vector(n);
+        let c: BaseElement = rand_value();
+
+        // include values close to the modulus
+        for (value, edge) in a.iter_mut().zip([M - 1, M - 2, u32::MAX as u64, 1 << 32]) {
+            *value = BaseElement::from_mont(edge);
+        }
+
+        let mut result = a.clone();
+        BaseElement::add_assign_slice(&mut result, &b);
+        let expected: Vec<_> = a.iter().zip(b.iter()).map(|(&a, &b)| a + b).collect();
+        assert_eq!(expected, result);
+
+        let mut result = a.clone();
+        BaseElement::mul_assign_slice(&mut result, &b);
+        let expected: Vec<_> = a.iter().zip(b.iter()).map(|(&a, &b)| a * b).collect();
+        assert_eq!(expected, result);
+
+        let mut result = a.clone();
+        BaseElement::scalar_mul_acc_slice(&mut result, c, &b);
+        let expected: Vec<_> = a.iter().zip(b.iter()).map(|(&a, &b)| a + b * c).collect();
+        assert_eq!(expected, result);
+
+        // the internal representations must be the same as well
+        let expected_bytes = BaseElement::elements_as_bytes(&expected);
+        assert_eq!(expected_bytes, BaseElement::elements_as_bytes(&result));
+    }
+}
+
 // QUADRATIC EXTENSION
 // ------------------------------------------------------------------------------------------------
 #[test]
@@ -376,6 +451,105 @@ fn cube_mul_base() {
     assert_eq!(expected, a.mul_base(b0));
 }
 
+// SEXTIC EXTENSION
+// ------------------------------------------------------------------------------------------------
+#[test]
+fn sextic_mul() {
+    // identity
+    let r: SexticExtension<BaseElement> = rand_value();
+    assert_eq!(
+        <SexticExtension<BaseElement>>::ZERO,
+        r * <SexticExtension<BaseElement>>::ZERO
+    );
+    assert_eq!(r, r * <SexticExtension<BaseElement>>::ONE);
+
+    // test random values; expected values were computed independently by reducing the product
+    // of the two bivariate polynomials modulo x^3 - x - 1 and y^2 - y + 2
+    let a = build_sextic([
+        17485029721327973432,
+        7283207964119141687,
+        890727360438182992,
+        15149836622520594227,
+        1736392818365009963,
+        10750541312280087032,
+    ]);
+    let b = build_sextic([
+        16781078052021535861,
+        3960482443532127989,
+        1585446675937841368,
+        7713914763314685786,
+        4439448776366754703,
+        10165027665383847897,
+    ]);
+    let expected = build_sextic([
+        13116230292814241962,
+        16982791662368270341,
+        17405008573958623833,
+        9141379307689969977,
+        12495657172800638790,
+        17290289678894501231,
+    ]);
+    assert_eq!(expected, a * b);
+
+    let a = build_sextic([
+        1090396360377453094,
+        10430779633273967791,
+        17477362246067780643,
+        11632994891556335705,
+        10754394637803157173,
+        1141153371300629929,
+    ]);
+    let b = build_sextic([
+        10801332806156616911,
+        914761360679426580,
+        4078239883182463692,
+        10268654918125279152,
+        2456641775679608523,
+        7731750658069747094,
+    ]);
+    let expected = build_sextic([
+        7837547760142497457,
+        16958525748626699823,
+        659093861979159789,
+        12245111532055283073,
+        17362824811529663876,
+        10022047745619119388,
+    ]);
+    assert_eq!(expected, a * b);
+}
+
+#[test]
+fn sextic_mul_base() {
+    let a: SexticExtension<BaseElement> = rand_value();
+    let b0: BaseElement = rand_value();
+    let b = <SexticExtension<BaseElement>>::from(b0);
+
+    let expected = a * b;
+    assert_eq!(expected, a.mul_base(b0));
+}
+
+#[test]
+fn sextic_conjugate() {
+    // the conjugate is the image of the Frobenius automorphism, i.e., a^p
+    let a: SexticExtension<BaseElement> = rand_value();
+    assert_eq!(a.exp(M), a.conjugate());
+
+    // applying the Frobenius automorphism 6 times yields the original element
+    let mut b = a;
+    for _ in 0..6 {
+        b = b.conjugate();
+    }
+    assert_eq!(a, b);
+
+    // elements of the base field are fixed by the Frobenius automorphism
+    let c = <SexticExtension<BaseElement>>::from(rand_value::<BaseElement>());
+    assert_eq!(c, c.conjugate());
+}
+
+fn build_sextic(values: [u64; 6]) -> SexticExtension<BaseElement> {
+    SexticExtension::new(values.map(BaseElement::new))
+}
+
 // RANDOMIZED TESTS
 // ================================================================================================
 
@@ -491,4 +665,19 @@ proptest! {
         };
         prop_assert_eq!(expected, a * b);
     }
+
+    // SEXTIC EXTENSION
+    // --------------------------------------------------------------------------------------------
+    #[test]
+    fn sextic_mul_inv_proptest(a in prop::array::uniform6(any::<u64>())) {
+        let a = SexticExtension::<BaseElement>::new(a.map(BaseElement::from));
+        let b = a.inv();
+
+        let expected = if a == SexticExtension::<BaseElement>::ZERO {
+            SexticExtension::<BaseElement>::ZERO
+        } else {
+            SexticExtension::<BaseElement>::ONE
+        };
+        prop_assert_eq!(expected, a * b);
+    }
 }
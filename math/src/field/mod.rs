@@ -5,11 +5,14 @@
 // LICENSE file in the root directory of this source tree.
 
 mod traits;
-pub use traits::{ExtensibleField, ExtensionOf, FieldElement, StarkField};
+pub use traits::{ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
 
 pub mod f128;
 pub mod f62;
 pub mod f64;
 
 mod extensions;
-pub use extensions::{CubeExtension, QuadExtension};
+pub use extensions::{CubeExtension, QuadExtension, SexticExtension};
+
+#[cfg(test)]
+mod tests;
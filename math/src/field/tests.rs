@@ -0,0 +1,255 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! Tests of the behavior required from every type implementing the [FieldElement] trait; each
+//! generic check is instantiated for every concrete field (and supported field extension).
+
+use super::{
+    f128, f62, f64, CubeExtension, FieldElement, QuadExtension, SexticExtension, StarkField,
+    ToElements,
+};
+use rand_utils::{rand_value, rand_vector};
+use utils::collections::Vec;
+use winter_derive::ToElements;
+
+// SUM AND PRODUCT
+// ================================================================================================
+
+#[test]
+fn sum_and_product_f62() {
+    check_sum_and_product::<f62::BaseElement>();
+    check_sum_and_product::<QuadExtension<f62::BaseElement>>();
+    check_sum_and_product::<CubeExtension<f62::BaseElement>>();
+}
+
+#[test]
+fn sum_and_product_f64() {
+    check_sum_and_product::<f64::BaseElement>();
+    check_sum_and_product::<QuadExtension<f64::BaseElement>>();
+    check_sum_and_product::<CubeExtension<f64::BaseElement>>();
+    check_sum_and_product::<SexticExtension<f64::BaseElement>>();
+}
+
+#[test]
+fn sum_and_product_f128() {
+    check_sum_and_product::<f128::BaseElement>();
+    check_sum_and_product::<QuadExtension<f128::BaseElement>>();
+}
+
+// VARIABLE-TIME EXPONENTIATION
+// ================================================================================================
+
+#[test]
+fn exp_vartime_f62() {
+    check_exp_vartime::<f62::BaseElement>();
+    check_exp_vartime::<QuadExtension<f62::BaseElement>>();
+    check_exp_vartime::<CubeExtension<f62::BaseElement>>();
+}
+
+#[test]
+fn exp_vartime_f64() {
+    check_exp_vartime::<f64::BaseElement>();
+    check_exp_vartime::<QuadExtension<f64::BaseElement>>();
+    check_exp_vartime::<CubeExtension<f64::BaseElement>>();
+    check_exp_vartime::<SexticExtension<f64::BaseElement>>();
+}
+
+#[test]
+fn exp_vartime_f128() {
+    check_exp_vartime::<f128::BaseElement>();
+    check_exp_vartime::<QuadExtension<f128::BaseElement>>();
+}
+
+// MAXIMUM DOMAIN SIZE
+// ================================================================================================
+
+#[test]
+fn max_domain_size_f62() {
+    check_max_domain_size::<f62::BaseElement>();
+}
+
+#[test]
+fn max_domain_size_f64() {
+    check_max_domain_size::<f64::BaseElement>();
+}
+
+#[test]
+fn max_domain_size_f128() {
+    check_max_domain_size::<f128::BaseElement>();
+}
+
+#[test]
+#[should_panic]
+fn root_of_unity_above_max_domain_size_f62() {
+    f62::BaseElement::get_root_of_unity(f62::BaseElement::TWO_ADICITY + 1);
+}
+
+#[test]
+#[should_panic]
+fn root_of_unity_above_max_domain_size_f64() {
+    f64::BaseElement::get_root_of_unity(f64::BaseElement::TWO_ADICITY + 1);
+}
+
+#[test]
+#[should_panic]
+fn root_of_unity_above_max_domain_size_f128() {
+    f128::BaseElement::get_root_of_unity(f128::BaseElement::TWO_ADICITY + 1);
+}
+
+// TO ELEMENTS
+// ================================================================================================
+
+type BaseElement = f62::BaseElement;
+
+#[test]
+fn to_elements_primitives() {
+    let value = BaseElement::new(42);
+    assert_eq!(vec![value], encode(&value));
+    assert_eq!(Vec::<BaseElement>::new(), encode(&()));
+    assert_eq!(vec![BaseElement::ONE], encode(&true));
+    assert_eq!(vec![BaseElement::ZERO], encode(&false));
+    assert_eq!(vec![BaseElement::new(7)], encode(&7u8));
+    assert_eq!(vec![BaseElement::new(u16::MAX as u64)], encode(&u16::MAX));
+    assert_eq!(vec![BaseElement::new(u32::MAX as u64)], encode(&u32::MAX));
+
+    // u64 values are split into 32-bit limbs, and thus, values above the modulus of the field
+    // are not reduced
+    let expected = vec![BaseElement::new(3), BaseElement::ONE];
+    assert_eq!(expected, encode(&((1u64 << 32) + 3)));
+    assert_ne!(encode(&1u64), encode(&(BaseElement::MODULUS + 1)));
+}
+
+#[test]
+fn to_elements_extensions() {
+    let a = QuadExtension::new(BaseElement::new(1), BaseElement::new(2));
+    assert_eq!(vec![BaseElement::new(1), BaseElement::new(2)], encode(&a));
+
+    let b = CubeExtension::new(
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+    );
+    let expected = vec![
+        BaseElement::new(1),
+        BaseElement::new(2),
+        BaseElement::new(3),
+    ];
+    assert_eq!(expected, encode(&b));
+
+    let mut expected = vec![BaseElement::ZERO; 6];
+    expected[0] = BaseElement::ONE;
+    assert_eq!(expected, encode(&SexticExtension::<BaseElement>::ONE));
+}
+
+#[test]
+fn to_elements_collections() {
+    let items = [1u64, 2, 3].map(BaseElement::new);
+
+    // arrays and tuples are concatenations of their items
+    assert_eq!(items.to_vec(), encode(&items));
+    let mut expected = items.to_vec();
+    expected.push(BaseElement::ONE);
+    assert_eq!(expected, encode(&(items[0], [2u8, 3], true)));
+
+    // vectors and slices are prefixed with their lengths, so that the way a sequence is split
+    // into vectors is reflected in the encoding
+    let mut expected = vec![BaseElement::new(3)];
+    expected.extend_from_slice(&items);
+    assert_eq!(expected, encode(&items.to_vec()));
+    assert_eq!(expected, encode(&items[..]));
+    assert_ne!(
+        encode(&(vec![items[0]], vec![items[1]])),
+        encode(&(vec![items[0], items[1]], Vec::<BaseElement>::new()))
+    );
+}
+
+#[test]
+fn to_elements_derive() {
+    #[derive(ToElements)]
+    struct Inputs {
+        seed: BaseElement,
+        num_steps: u32,
+        outputs: Vec<BaseElement>,
+    }
+
+    #[derive(ToElements)]
+    struct Pair<T>(T, T);
+
+    let inputs = Inputs {
+        seed: BaseElement::new(5),
+        num_steps: 64,
+        outputs: vec![BaseElement::new(7), BaseElement::new(9)],
+    };
+    let expected = [5u64, 64, 2, 7, 9].map(BaseElement::new);
+    assert_eq!(expected.to_vec(), encode(&inputs));
+
+    // fields are encoded in the order of their declaration
+    let pair = Pair(
+        QuadExtension::new(BaseElement::new(1), BaseElement::new(2)),
+        QuadExtension::ONE,
+    );
+    let expected = [1u64, 2, 1, 0].map(BaseElement::new);
+    assert_eq!(expected.to_vec(), encode(&pair));
+}
+
+// GENERIC CHECKS
+// ================================================================================================
+
+fn check_sum_and_product<E: FieldElement>() {
+    let values: Vec<E> = rand_vector(32);
+
+    // sum and product over values and over references must match folding with + and *
+    let expected_sum = values.iter().fold(E::ZERO, |acc, &x| acc + x);
+    assert_eq!(expected_sum, values.iter().sum::<E>());
+    assert_eq!(expected_sum, values.iter().copied().sum::<E>());
+
+    let expected_product = values.iter().fold(E::ONE, |acc, &x| acc * x);
+    assert_eq!(expected_product, values.iter().product::<E>());
+    assert_eq!(expected_product, values.iter().copied().product::<E>());
+
+    // empty iterators resolve to identities
+    let empty: Vec<E> = Vec::new();
+    assert_eq!(E::ZERO, empty.iter().sum::<E>());
+    assert_eq!(E::ONE, empty.iter().product::<E>());
+}
+
+fn check_exp_vartime<E: FieldElement>() {
+    let b: E = rand_value();
+
+    // edge cases
+    assert_eq!(E::ONE, b.exp_vartime(E::Representation::from(0u32)));
+    assert_eq!(b, b.exp_vartime(E::Representation::from(1u32)));
+    assert_eq!(E::ONE, E::ZERO.exp_vartime(E::Representation::from(0u32)));
+    assert_eq!(E::ZERO, E::ZERO.exp_vartime(E::Representation::from(5u32)));
+
+    // small powers must match repeated multiplication
+    let mut expected = E::ONE;
+    for power in 0..32u32 {
+        assert_eq!(expected, b.exp_vartime(power.into()));
+        expected *= b;
+    }
+
+    // all powers must match the result of exp()
+    for power in [7u64, 1 << 31, u32::MAX as u64, (1 << 63) + 5, u64::MAX] {
+        let power = E::Representation::from(power);
+        assert_eq!(b.exp(power), b.exp_vartime(power));
+    }
+}
+
+fn check_max_domain_size<B: StarkField>() {
+    assert_eq!(1 << B::TWO_ADICITY, B::MAX_DOMAIN_SIZE);
+
+    // the root of unity generating the largest domain has exactly the order of the domain
+    let root = B::get_root_of_unity(B::TWO_ADICITY);
+    let domain_size = B::MAX_DOMAIN_SIZE as u64;
+    assert_eq!(B::ONE, root.exp(B::Representation::from(domain_size)));
+    assert_ne!(B::ONE, root.exp(B::Representation::from(domain_size / 2)));
+}
+
+/// Encodes the `value` as a sequence of elements in the 62-bit field.
+fn encode<T: ToElements<BaseElement> + ?Sized>(value: &T) -> Vec<BaseElement> {
+    value.to_elements()
+}
@@ -4,9 +4,11 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::{CubeExtension, QuadExtension, SexticExtension};
 use core::{
     convert::TryFrom,
     fmt::{Debug, Display},
+    iter::{Product, Sum},
     ops::{
         Add, AddAssign, BitAnd, Div, DivAssign, Mul, MulAssign, Neg, Shl, Shr, ShrAssign, Sub,
         SubAssign,
@@ -25,6 +27,9 @@ use utils::{
 /// multiplication, division) as well as several convenience functions (e.g. double, square cube).
 /// Moreover, it defines interfaces for serializing and deserializing field elements.
 ///
+/// Every field element type can also be summed up or multiplied together from an iterator over
+/// elements (or over references to elements) via the [Sum] and [Product] traits.
+///
 /// The elements could be in a prime field or an extension of a prime field. Currently, only
 /// quadratic and cubic field extensions are supported.
 pub trait FieldElement:
@@ -47,6 +52,10 @@ pub trait FieldElement:
     + MulAssign<Self>
     + DivAssign<Self>
     + Neg<Output = Self>
+    + Sum<Self>
+    + for<'a> Sum<&'a Self>
+    + Product<Self>
+    + for<'a> Product<&'a Self>
     + From<u128>
     + From<u64>
     + From<u32>
@@ -113,8 +122,27 @@ pub trait FieldElement:
     }
 
     /// Exponentiates this field element by `power` parameter.
+    ///
+    /// The default implementation delegates to [exp_vartime()](FieldElement::exp_vartime), and
+    /// thus, its running time depends on the value of `power`. Implementations may override this
+    /// method with one whose running time is independent of `power` (this is the case for the
+    /// [f64](crate::fields::f64) field); otherwise, this method should not be used with secret
+    /// exponents.
     #[must_use]
     fn exp(self, power: Self::Representation) -> Self {
+        self.exp_vartime(power)
+    }
+
+    /// Exponentiates this field element by `power` parameter in variable time.
+    ///
+    /// The running time of this method depends on the value of `power`: the default
+    /// implementation performs a squaring for every bit of `power` up to its most significant
+    /// set bit, and a multiplication for every set bit. Thus, this method should be used only
+    /// with public exponents; in exchange, it is never slower than [exp()](FieldElement::exp),
+    /// and is usually much faster for small exponents. The result is always the same as the
+    /// result of [exp()](FieldElement::exp).
+    #[must_use]
+    fn exp_vartime(self, power: Self::Representation) -> Self {
         let mut r = Self::ONE;
         let mut b = self;
         let mut p = power;
@@ -174,6 +202,46 @@ pub trait FieldElement:
     /// field elements according to their internal representation.
     unsafe fn bytes_as_elements(bytes: &[u8]) -> Result<&[Self], DeserializationError>;
 
+    // SLICE OPERATIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds elements of `b` to the corresponding elements of `a`.
+    ///
+    /// Specialized implementations of this function may be faster than the generic
+    /// implementation (e.g., by using SIMD instructions), but must produce the same result.
+    /// Lengths of `a` and `b` are expected to be the same.
+    fn add_assign_slice(a: &mut [Self], b: &[Self]) {
+        debug_assert_eq!(a.len(), b.len());
+        for (a, &b) in a.iter_mut().zip(b) {
+            *a += b;
+        }
+    }
+
+    /// Multiplies elements of `a` by the corresponding elements of `b`.
+    ///
+    /// Specialized implementations of this function may be faster than the generic
+    /// implementation (e.g., by using SIMD instructions), but must produce the same result.
+    /// Lengths of `a` and `b` are expected to be the same.
+    fn mul_assign_slice(a: &mut [Self], b: &[Self]) {
+        debug_assert_eq!(a.len(), b.len());
+        for (a, &b) in a.iter_mut().zip(b) {
+            *a *= b;
+        }
+    }
+
+    /// Multiplies elements of `b` by scalar `c` and adds the results to the corresponding
+    /// elements of `a`.
+    ///
+    /// Specialized implementations of this function may be faster than the generic
+    /// implementation (e.g., by using SIMD instructions), but must produce the same result.
+    /// Lengths of `a` and `b` are expected to be the same.
+    fn scalar_mul_acc_slice(a: &mut [Self], c: Self, b: &[Self]) {
+        debug_assert_eq!(a.len(), b.len());
+        for (a, &b) in a.iter_mut().zip(b) {
+            *a += b * c;
+        }
+    }
+
     // UTILITIES
     // --------------------------------------------------------------------------------------------
 
@@ -218,6 +286,15 @@ pub trait StarkField: FieldElement<BaseField = Self> {
     /// computed as Self::GENERATOR^`k`.
     const TWO_ADIC_ROOT_OF_UNITY: Self;
 
+    /// Size of the largest multiplicative subgroup of this field which can be used as an
+    /// evaluation domain; this is 2^TWO_ADICITY, unless 2^TWO_ADICITY does not fit into a `usize`
+    /// on the target platform, in which case this is the largest power of two which does.
+    const MAX_DOMAIN_SIZE: usize = if Self::TWO_ADICITY < usize::BITS {
+        1 << Self::TWO_ADICITY
+    } else {
+        1 << (usize::BITS - 1)
+    };
+
     /// Returns the root of unity of order 2^`n`.
     ///
     /// # Panics
@@ -229,6 +306,28 @@ pub trait StarkField: FieldElement<BaseField = Self> {
 
     /// Returns a canonical integer representation of the field element.
     fn to_repr(&self) -> Self::Representation;
+
+    /// Returns a field element equal to the provided `value`, or None if the value is greater
+    /// than or equal to the field modulus.
+    fn try_from_u64(value: u64) -> Option<Self> {
+        if Self::Representation::from(value) < Self::MODULUS {
+            Some(Self::from(value))
+        } else {
+            None
+        }
+    }
+
+    /// Returns a field element equal to the provided `value`, or None if the value is greater
+    /// than or equal to the field modulus.
+    fn try_from_u128(value: u128) -> Option<Self>;
+
+    /// Returns a field element equal to the provided `value` reduced modulo the field modulus.
+    ///
+    /// This should be used instead of `From<u64>` when the reduction is intentional, to make the
+    /// intent explicit at the call site.
+    fn from_u64_reduced(value: u64) -> Self {
+        Self::from(value)
+    }
 }
 
 // EXTENSIBLE FIELD
@@ -280,3 +379,129 @@ impl<E: FieldElement> ExtensionOf<E> for E {
         self * other
     }
 }
+
+// TO ELEMENTS
+// ================================================================================================
+
+/// Defines a canonical encoding of `Self` as a sequence of elements in the field `B`.
+///
+/// The encoding must be injective: distinct values must be mapped to distinct sequences of
+/// elements, and semantically equal values must be mapped to the same sequence. This encoding is
+/// used to bind public inputs of a computation to the transcript of a proof, and thus, the prover
+/// and the verifier get the same transcript whenever they agree on the meaning of public inputs,
+/// regardless of how the inputs are serialized into bytes.
+///
+/// This trait is implemented for the following types:
+/// * Base field elements, which are mapped to themselves, and extension field elements, which
+///   are mapped to their base field coordinates.
+/// * Booleans and unsigned integers up to 32 bits, which are mapped to a single element. A `u64`
+///   value is mapped to two elements holding its lower and upper 32 bits, so that the encoding
+///   is injective in fields with moduli smaller than 2^64.
+/// * Arrays and tuples, which are mapped to a concatenation of the encodings of their items.
+/// * Slices and vectors, which are mapped to the number of items followed by a concatenation of
+///   the encodings of the items.
+///
+/// For structs, the trait can be derived via the `derive` feature of this crate, in which case
+/// the struct is encoded as a concatenation of the encodings of its fields in the order of their
+/// declaration.
+pub trait ToElements<B: StarkField> {
+    /// Returns the canonical encoding of `self` as a sequence of field elements.
+    fn to_elements(&self) -> Vec<B>;
+}
+
+impl<B: StarkField> ToElements<B> for B {
+    fn to_elements(&self) -> Vec<B> {
+        vec![*self]
+    }
+}
+
+impl<B: ExtensibleField<2>> ToElements<B> for QuadExtension<B> {
+    fn to_elements(&self) -> Vec<B> {
+        Self::as_base_elements(core::slice::from_ref(self)).to_vec()
+    }
+}
+
+impl<B: ExtensibleField<3>> ToElements<B> for CubeExtension<B> {
+    fn to_elements(&self) -> Vec<B> {
+        Self::as_base_elements(core::slice::from_ref(self)).to_vec()
+    }
+}
+
+impl<B: ExtensibleField<6>> ToElements<B> for SexticExtension<B> {
+    fn to_elements(&self) -> Vec<B> {
+        Self::as_base_elements(core::slice::from_ref(self)).to_vec()
+    }
+}
+
+impl<B: StarkField> ToElements<B> for () {
+    fn to_elements(&self) -> Vec<B> {
+        Vec::new()
+    }
+}
+
+impl<B: StarkField> ToElements<B> for bool {
+    fn to_elements(&self) -> Vec<B> {
+        vec![B::from(*self as u8)]
+    }
+}
+
+macro_rules! impl_small_uint_to_elements {
+    ($($t:ty),*) => {
+        $(
+            impl<B: StarkField> ToElements<B> for $t {
+                fn to_elements(&self) -> Vec<B> {
+                    vec![B::from(*self)]
+                }
+            }
+        )*
+    };
+}
+
+impl_small_uint_to_elements!(u8, u16, u32);
+
+impl<B: StarkField> ToElements<B> for u64 {
+    fn to_elements(&self) -> Vec<B> {
+        vec![B::from(*self as u32), B::from((*self >> 32) as u32)]
+    }
+}
+
+impl<B: StarkField, T: ToElements<B>, const N: usize> ToElements<B> for [T; N] {
+    fn to_elements(&self) -> Vec<B> {
+        self.iter().flat_map(|item| item.to_elements()).collect()
+    }
+}
+
+impl<B: StarkField, T: ToElements<B>> ToElements<B> for [T] {
+    fn to_elements(&self) -> Vec<B> {
+        let mut result = vec![B::from(self.len() as u64)];
+        for item in self.iter() {
+            result.append(&mut item.to_elements());
+        }
+        result
+    }
+}
+
+impl<B: StarkField, T: ToElements<B>> ToElements<B> for Vec<T> {
+    fn to_elements(&self) -> Vec<B> {
+        self.as_slice().to_elements()
+    }
+}
+
+macro_rules! impl_tuple_to_elements {
+    ($($name:ident),+) => {
+        impl<B: StarkField, $($name: ToElements<B>),+> ToElements<B> for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn to_elements(&self) -> Vec<B> {
+                let ($($name,)+) = self;
+                let mut result = Vec::new();
+                $(result.append(&mut $name.to_elements());)+
+                result
+            }
+        }
+    };
+}
+
+impl_tuple_to_elements!(T0);
+impl_tuple_to_elements!(T0, T1);
+impl_tuple_to_elements!(T0, T1, T2);
+impl_tuple_to_elements!(T0, T1, T2, T3);
@@ -36,9 +36,9 @@
 //!
 //! ## Extension fields
 //!
-//! Currently, the library provides a generic way to create quadratic and cubic extensions of
-//! supported STARK fields. This can be done by implementing [ExtensibleField] trait for
-//! degrees 2 and 3.
+//! Currently, the library provides a generic way to create quadratic, cubic, and sextic
+//! extensions of supported STARK fields. This can be done by implementing [ExtensibleField]
+//! trait for degrees 2, 3, and 6.
 //!
 //! Quadratic extension fields are defined using the following irreducible polynomials:
 //! * For [f62](crate::fields::f62) field, the polynomial is x<sup>2</sup> - x - 1.
@@ -50,6 +50,12 @@
 //! * For [f64](crate::fields::f64) field, the polynomial is x<sup>3</sup> - x - 1.
 //! * For [f128](crate::fields::f128) field, cubic extensions are not supported.
 //!
+//! Sextic extension fields are defined as quadratic extensions of cubic extension fields:
+//! * For [f64](crate::fields::f64) field, the cubic extension is extended using the polynomial
+//!   y<sup>2</sup> - y + 2.
+//! * For [f62](crate::fields::f62) and [f128](crate::fields::f128) fields, sextic extensions are
+//!   not supported.
+//!
 //! # Polynomials
 //! [Polynomials](polynom) module implements basic polynomial operations such as:
 //!
@@ -78,9 +84,11 @@
 //!   - [add_in_place()]
 //!   - [mul_acc()]
 //!   - [batch_inversion()]
+//!   - [batch_inversion_in_place()]
 //! * `fft` module:
 //!   - [evaluate_poly()](fft::evaluate_poly())
 //!   - [evaluate_poly_with_offset()](fft::evaluate_poly_with_offset())
+//!   - [evaluate_poly_with_offset_in_place()](fft::evaluate_poly_with_offset_in_place())
 //!   - [interpolate_poly()](fft::interpolate_poly())
 //!   - [interpolate_poly_with_offset()][fft::interpolate_poly_with_offset()]
 //!   - [get_twiddles()](fft::get_twiddles())
@@ -104,7 +112,10 @@ pub mod fft;
 pub mod polynom;
 
 mod field;
-pub use field::{ExtensibleField, ExtensionOf, FieldElement, StarkField};
+pub use field::{ExtensibleField, ExtensionOf, FieldElement, StarkField, ToElements};
+
+#[cfg(feature = "derive")]
+pub use winter_derive::ToElements;
 pub mod fields {
     //! Finite field implementations.
     //!
@@ -116,9 +127,17 @@ pub mod fields {
     pub use super::field::f64;
     pub use super::field::CubeExtension;
     pub use super::field::QuadExtension;
+    pub use super::field::SexticExtension;
 }
 
 mod utils;
 pub use crate::utils::{
-    add_in_place, batch_inversion, get_power_series, get_power_series_with_offset, log2, mul_acc,
+    add_in_place, batch_inversion, batch_inversion_in_place, get_power_series,
+    get_power_series_with_offset, log2, mul_acc, mul_in_place, scalar_mul_acc,
 };
+
+#[doc(hidden)]
+pub mod __private {
+    //! Items referenced by the code generated via macros of the `winter-derive` crate.
+    pub use utils::collections::Vec;
+}
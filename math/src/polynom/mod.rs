@@ -25,7 +25,10 @@
 //! let p = [BaseElement::new(3), BaseElement::ZERO, BaseElement::new(4)];
 //! ```
 
-use crate::{field::FieldElement, utils::batch_inversion};
+use crate::{
+    field::FieldElement,
+    utils::{add_in_place, batch_inversion, scalar_mul_acc},
+};
 use core::mem;
 use utils::{collections::Vec, group_vector_elements};
 
@@ -252,13 +255,9 @@ pub fn add<E>(a: &[E], b: &[E]) -> Vec<E>
 where
     E: FieldElement,
 {
-    let result_len = core::cmp::max(a.len(), b.len());
-    let mut result = Vec::with_capacity(result_len);
-    for i in 0..result_len {
-        let c1 = if i < a.len() { a[i] } else { E::ZERO };
-        let c2 = if i < b.len() { b[i] } else { E::ZERO };
-        result.push(c1 + c2);
-    }
+    let (longer, shorter) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = longer.to_vec();
+    add_in_place(&mut result[..shorter.len()], shorter);
     result
 }
 
@@ -330,11 +329,8 @@ where
 {
     let result_len = a.len() + b.len() - 1;
     let mut result = E::zeroed_vector(result_len);
-    for i in 0..a.len() {
-        for j in 0..b.len() {
-            let s = a[i] * b[j];
-            result[i + j] += s;
-        }
+    for (i, &coeff) in a.iter().enumerate() {
+        scalar_mul_acc(&mut result[i..i + b.len()], coeff, b);
     }
     result
 }
@@ -637,7 +633,7 @@ where
 // HELPER FUNCTIONS
 // ================================================================================================
 fn get_zero_roots<E: FieldElement>(xs: &[E]) -> Vec<E> {
-    let mut result = unsafe { utils::uninit_vector(xs.len() + 1) };
+    let mut result = E::zeroed_vector(xs.len() + 1);
     fill_zero_roots(xs, &mut result);
     result
 }
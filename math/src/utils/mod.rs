@@ -5,7 +5,7 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{field::FieldElement, ExtensionOf};
-use utils::{batch_iter_mut, collections::Vec, iter_mut, uninit_vector};
+use utils::{batch_iter_mut, collections::Vec, iter_mut};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
@@ -38,7 +38,7 @@ pub fn get_power_series<E>(b: E, n: usize) -> Vec<E>
 where
     E: FieldElement,
 {
-    let mut result = unsafe { uninit_vector(n) };
+    let mut result = E::zeroed_vector(n);
     batch_iter_mut!(&mut result, 1024, |batch: &mut [E], batch_offset: usize| {
         let start = b.exp((batch_offset as u64).into());
         fill_power_series(batch, b, start);
@@ -73,7 +73,7 @@ pub fn get_power_series_with_offset<E>(b: E, s: E, n: usize) -> Vec<E>
 where
     E: FieldElement,
 {
-    let mut result = unsafe { uninit_vector(n) };
+    let mut result = E::zeroed_vector(n);
     batch_iter_mut!(&mut result, 1024, |batch: &mut [E], batch_offset: usize| {
         let start = s * b.exp((batch_offset as u64).into());
         fill_power_series(batch, b, start);
@@ -83,8 +83,9 @@ where
 
 /// Computes element-wise sum of the provided vectors, and stores the result in the first vector.
 ///
-/// When `concurrent` feature is enabled, the summation is performed concurrently in multiple
-/// threads.
+/// The summation is performed using [FieldElement::add_assign_slice()], which may use SIMD
+/// instructions for some fields (see `simd` feature). When `concurrent` feature is enabled, the
+/// summation is performed concurrently in multiple threads.
 ///
 /// # Panics
 /// Panics if lengths of `a` and `b` vectors are not the same.
@@ -112,7 +113,87 @@ where
         a.len() == b.len(),
         "number of values must be the same for both operands"
     );
-    iter_mut!(a).zip(b).for_each(|(a, &b)| *a += b);
+    batch_iter_mut!(a, 1024, |batch: &mut [E], batch_offset: usize| {
+        E::add_assign_slice(batch, &b[batch_offset..batch_offset + batch.len()]);
+    });
+}
+
+/// Computes element-wise product of the provided vectors, and stores the result in the first
+/// vector.
+///
+/// The multiplication is performed using [FieldElement::mul_assign_slice()], which may use SIMD
+/// instructions for some fields (see `simd` feature). When `concurrent` feature is enabled, the
+/// multiplication is performed concurrently in multiple threads.
+///
+/// # Panics
+/// Panics if lengths of `a` and `b` vectors are not the same.
+///
+/// # Examples
+/// ```
+/// # use winter_math::mul_in_place;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let a: Vec<BaseElement> = rand_vector(2048);
+/// let b: Vec<BaseElement> = rand_vector(2048);
+///
+/// let mut c = a.clone();
+/// mul_in_place(&mut c, &b);
+///
+/// for ((a, b), c) in a.into_iter().zip(b).zip(c) {
+///     assert_eq!(a * b, c);
+/// }
+/// ```
+pub fn mul_in_place<E>(a: &mut [E], b: &[E])
+where
+    E: FieldElement,
+{
+    assert!(
+        a.len() == b.len(),
+        "number of values must be the same for both operands"
+    );
+    batch_iter_mut!(a, 1024, |batch: &mut [E], batch_offset: usize| {
+        E::mul_assign_slice(batch, &b[batch_offset..batch_offset + batch.len()]);
+    });
+}
+
+/// Multiplies a sequence of values by a scalar from the same field and accumulates the results.
+///
+/// More precisely, computes `a[i]` + `b[i]` * `c` for all `i` and saves result into `a[i]`.
+/// Unlike [mul_acc()], both the values and the scalar must be in the same field; in exchange,
+/// the computation is performed using [FieldElement::scalar_mul_acc_slice()], which may use SIMD
+/// instructions for some fields (see `simd` feature). When `concurrent` feature is enabled, the
+/// computation is performed concurrently in multiple threads.
+///
+/// # Panics
+/// Panics if lengths of `a` and `b` slices are not the same.
+///
+/// # Examples
+/// ```
+/// # use winter_math::scalar_mul_acc;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let a: Vec<BaseElement> = rand_vector(2048);
+/// let b: Vec<BaseElement> = rand_vector(2048);
+/// let c = BaseElement::new(12345);
+///
+/// let mut d = a.clone();
+/// scalar_mul_acc(&mut d, c, &b);
+///
+/// for ((a, b), d) in a.into_iter().zip(b).zip(d) {
+///     assert_eq!(a + b * c, d);
+/// }
+/// ```
+pub fn scalar_mul_acc<E>(a: &mut [E], c: E, b: &[E])
+where
+    E: FieldElement,
+{
+    assert!(
+        a.len() == b.len(),
+        "number of values must be the same for both slices"
+    );
+    batch_iter_mut!(a, 1024, |batch: &mut [E], batch_offset: usize| {
+        E::scalar_mul_acc_slice(batch, c, &b[batch_offset..batch_offset + batch.len()]);
+    });
 }
 
 /// Multiplies a sequence of values by a scalar and accumulates the results.
@@ -179,7 +260,7 @@ pub fn batch_inversion<E>(values: &[E]) -> Vec<E>
 where
     E: FieldElement,
 {
-    let mut result: Vec<E> = unsafe { uninit_vector(values.len()) };
+    let mut result = E::zeroed_vector(values.len());
     batch_iter_mut!(&mut result, 1024, |batch: &mut [E], batch_offset: usize| {
         let start = batch_offset;
         let end = start + batch.len();
@@ -188,6 +269,41 @@ where
     result
 }
 
+/// Replaces each element of the provided sequence with its multiplicative inverse using batch
+/// inversion method.
+///
+/// Any ZEROs in the provided sequence are ignored (i.e., they remain ZEROs after the inversion),
+/// and do not affect the inverses of other elements.
+///
+/// When `concurrent` feature is enabled, the inversion is performed concurrently in multiple
+/// threads.
+///
+/// This function performs the same computation as [batch_inversion()] but does not allocate a
+/// new vector for the result. Similarly to [batch_inversion()], it transforms `n` inversions into
+/// `3 * n` multiplications + 1 inversion per batch.
+///
+/// # Examples
+/// ```
+/// # use winter_math::batch_inversion_in_place;
+/// # use winter_math::{fields::{f128::BaseElement}, FieldElement};
+/// # use rand_utils::rand_vector;
+/// let a: Vec<BaseElement> = rand_vector(2048);
+/// let mut b = a.clone();
+/// batch_inversion_in_place(&mut b);
+///
+/// for (&a, &b) in a.iter().zip(b.iter()) {
+///     assert_eq!(a.inv(), b);
+/// }
+/// ```
+pub fn batch_inversion_in_place<E>(values: &mut [E])
+where
+    E: FieldElement,
+{
+    batch_iter_mut!(values, 1024, |batch: &mut [E], _batch_offset: usize| {
+        serial_batch_inversion_in_place(batch);
+    });
+}
+
 /// Returns base 2 logarithm of `n`, where `n` is a power of two.
 ///
 /// # Panics
@@ -237,3 +353,31 @@ fn serial_batch_inversion<E: FieldElement>(values: &[E], result: &mut [E]) {
         }
     }
 }
+
+fn serial_batch_inversion_in_place<E: FieldElement>(values: &mut [E]) {
+    // compute products of all non-zero values preceding each value
+    let mut prefixes = E::zeroed_vector(values.len());
+    let mut last = E::ONE;
+    for (prefix, &value) in prefixes.iter_mut().zip(values.iter()) {
+        *prefix = last;
+        if value != E::ZERO {
+            last *= value;
+        }
+    }
+
+    last = last.inv();
+
+    for (value, &prefix) in values.iter_mut().zip(prefixes.iter()).rev() {
+        if *value != E::ZERO {
+            let inverse = last * prefix;
+            last *= *value;
+            *value = inverse;
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,104 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    add_in_place, batch_inversion, batch_inversion_in_place, mul_in_place, scalar_mul_acc,
+};
+use crate::{
+    fields::{f128, f62, f64, QuadExtension},
+    FieldElement,
+};
+use rand_utils::{rand_value, rand_vector};
+
+// BATCH INVERSION
+// ================================================================================================
+
+#[test]
+fn batch_inversion_in_place_f128() {
+    let values: Vec<f128::BaseElement> = rand_vector(4096);
+    check_batch_inversion_in_place(values);
+}
+
+#[test]
+fn batch_inversion_in_place_f62_quad_extension() {
+    let values: Vec<QuadExtension<f62::BaseElement>> = rand_vector(4096);
+    check_batch_inversion_in_place(values);
+}
+
+#[test]
+fn batch_inversion_in_place_with_zeros() {
+    let mut values: Vec<f128::BaseElement> = rand_vector(4096);
+    values[0] = f128::BaseElement::ZERO;
+    values[1000] = f128::BaseElement::ZERO;
+    values[4095] = f128::BaseElement::ZERO;
+    check_batch_inversion_in_place(values);
+
+    let mut values = vec![f128::BaseElement::ZERO; 16];
+    batch_inversion_in_place(&mut values);
+    assert!(values.iter().all(|&value| value == f128::BaseElement::ZERO));
+}
+
+#[test]
+fn batch_inversion_in_place_empty() {
+    let mut values: Vec<f128::BaseElement> = Vec::new();
+    batch_inversion_in_place(&mut values);
+    assert!(values.is_empty());
+}
+
+// SLICE OPERATIONS
+// ================================================================================================
+
+#[test]
+fn slice_ops_f64() {
+    check_slice_ops::<f64::BaseElement>(5003);
+}
+
+#[test]
+fn slice_ops_f62() {
+    check_slice_ops::<f62::BaseElement>(5003);
+}
+
+#[test]
+fn slice_ops_f128_quad_extension() {
+    check_slice_ops::<QuadExtension<f128::BaseElement>>(1027);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn check_batch_inversion_in_place<E: FieldElement>(values: Vec<E>) {
+    let mut result = values.clone();
+    batch_inversion_in_place(&mut result);
+
+    for (&value, &inverse) in values.iter().zip(result.iter()) {
+        assert_eq!(value.inv(), inverse);
+    }
+    assert_eq!(batch_inversion(&values), result);
+}
+
+fn check_slice_ops<E: FieldElement>(n: usize) {
+    let a: Vec<E> = rand_vector(n);
+    let b: Vec<E> = rand_vector(n);
+    let c: E = rand_value();
+
+    let mut result = a.clone();
+    add_in_place(&mut result, &b);
+    for ((&a, &b), &result) in a.iter().zip(b.iter()).zip(result.iter()) {
+        assert_eq!(a + b, result);
+    }
+
+    let mut result = a.clone();
+    mul_in_place(&mut result, &b);
+    for ((&a, &b), &result) in a.iter().zip(b.iter()).zip(result.iter()) {
+        assert_eq!(a * b, result);
+    }
+
+    let mut result = a.clone();
+    scalar_mul_acc(&mut result, c, &b);
+    for ((&a, &b), &result) in a.iter().zip(b.iter()).zip(result.iter()) {
+        assert_eq!(a + b * c, result);
+    }
+}
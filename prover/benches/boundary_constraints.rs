@@ -0,0 +1,131 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{
+    AirContext, Assertion, BoundaryConstraints, FieldExtension, ProofOptions, TraceInfo,
+    TraceLayout, TransitionConstraintDegree,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use math::{fields::f128::BaseElement, FieldElement};
+use rand_utils::{rand_value, rand_vector};
+use std::time::Duration;
+
+const TRACE_LENGTHS: [usize; 2] = [1 << 10, 1 << 14];
+const SEGMENT_WIDTH: usize = 16;
+const STRIDE: usize = 16;
+
+fn boundary_constraints(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boundary_constraints");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &trace_length in TRACE_LENGTHS.iter() {
+        // 256 periodic assertions against each trace segment; assertions against the main and
+        // the auxiliary segments share their strides and first steps, and thus, their divisors
+        let context = build_context(trace_length);
+        let main_assertions = build_periodic_assertions();
+        let aux_assertions = build_periodic_assertions();
+        let coefficients = (0..main_assertions.len() + aux_assertions.len())
+            .map(|_| (rand_value(), rand_value()))
+            .collect::<Vec<(BaseElement, BaseElement)>>();
+
+        group.bench_function(BenchmarkId::new("build", trace_length), |bench| {
+            bench.iter(|| {
+                BoundaryConstraints::<BaseElement>::new(
+                    &context,
+                    main_assertions.clone(),
+                    aux_assertions.clone(),
+                    &coefficients,
+                )
+            });
+        });
+
+        let constraints = BoundaryConstraints::<BaseElement>::new(
+            &context,
+            main_assertions,
+            aux_assertions,
+            &coefficients,
+        );
+        let x = rand_value::<BaseElement>();
+        let main_state = rand_vector::<BaseElement>(SEGMENT_WIDTH);
+        let aux_state = rand_vector::<BaseElement>(SEGMENT_WIDTH);
+
+        // compare evaluating the divisor of every constraint group at the out-of-domain point
+        // with evaluating each shared divisor only once
+        group.bench_function(BenchmarkId::new("ood_per_group", trace_length), |bench| {
+            bench.iter(|| {
+                let mut result = BaseElement::ZERO;
+                for group in constraints.main_constraints() {
+                    let xp = x.exp(group.degree_adjustment().into());
+                    let z = constraints.divisors()[group.divisor_idx()].evaluate_at(x);
+                    result += group.evaluate_at(&main_state, x, xp, z);
+                }
+                for group in constraints.aux_constraints() {
+                    let xp = x.exp(group.degree_adjustment().into());
+                    let z = constraints.divisors()[group.divisor_idx()].evaluate_at(x);
+                    result += group.evaluate_at(&aux_state, x, xp, z);
+                }
+                result
+            });
+        });
+
+        group.bench_function(BenchmarkId::new("ood_shared", trace_length), |bench| {
+            bench.iter(|| {
+                let divisors = constraints.evaluate_divisors_at(x);
+                let mut result = BaseElement::ZERO;
+                for group in constraints.main_constraints() {
+                    let xp = x.exp(group.degree_adjustment().into());
+                    let z = divisors[group.divisor_idx()];
+                    result += group.evaluate_at(&main_state, x, xp, z);
+                }
+                for group in constraints.aux_constraints() {
+                    let xp = x.exp(group.degree_adjustment().into());
+                    let z = divisors[group.divisor_idx()];
+                    result += group.evaluate_at(&aux_state, x, xp, z);
+                }
+                result
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(boundary_group, boundary_constraints);
+criterion_main!(boundary_group);
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn build_context(trace_length: usize) -> AirContext<BaseElement> {
+    let layout = TraceLayout::new(SEGMENT_WIDTH, [SEGMENT_WIDTH, 0], [1, 0]);
+    let trace_info = TraceInfo::new_multi_segment(layout, trace_length, vec![]);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+    AirContext::new_multi_segment(
+        trace_info,
+        vec![TransitionConstraintDegree::new(2)],
+        vec![TransitionConstraintDegree::new(2)],
+        SEGMENT_WIDTH * STRIDE,
+        SEGMENT_WIDTH * STRIDE,
+        options,
+    )
+}
+
+/// Builds a periodic assertion against every column of a trace segment for every first step
+/// within the stride.
+fn build_periodic_assertions() -> Vec<Assertion<BaseElement>> {
+    let mut result = Vec::new();
+    for first_step in 0..STRIDE {
+        for column in 0..SEGMENT_WIDTH {
+            result.push(Assertion::periodic(
+                column,
+                first_step,
+                STRIDE,
+                rand_value(),
+            ));
+        }
+    }
+    result
+}
@@ -0,0 +1,46 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_utils::rand_vector;
+use std::time::Duration;
+use winter_prover::{
+    build_commitment, crypto::hashers::Blake3_256, math::fields::f128::BaseElement, Matrix,
+};
+
+// MEMORY NOTE
+// ------------------------------------------------------------------------------------------------
+// Criterion measures time only. When changing how trace LDEs are built or committed to, peak
+// memory of the prover should be checked separately (e.g., via `/usr/bin/time -v` on a proof
+// for a 2^21-step, 100-column trace). The prover extends every trace column in place in a single
+// buffer of LDE size, does not retain trace polynomials during constraint evaluation, and
+// releases the execution trace before constraint evaluation; a change which reintroduces any of
+// these copies is a memory regression even if it does not show up in the timings below.
+
+const MATRIX_WIDTH: usize = 8;
+const NUM_ROWS: [usize; 2] = [1 << 16, 1 << 20];
+
+fn commitment(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_commitment");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(20));
+
+    for &num_rows in NUM_ROWS.iter() {
+        let columns = (0..MATRIX_WIDTH)
+            .map(|_| rand_vector::<BaseElement>(num_rows))
+            .collect::<Vec<_>>();
+        let matrix = Matrix::new(columns);
+
+        group.bench_function(BenchmarkId::new("blake3", num_rows), |bench| {
+            bench.iter(|| build_commitment::<BaseElement, Blake3_256<BaseElement>>(&matrix));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(commitment_group, commitment);
+criterion_main!(commitment_group);
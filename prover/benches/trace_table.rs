@@ -0,0 +1,52 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_utils::rand_vector;
+use std::time::Duration;
+use winter_prover::{math::fields::f128::BaseElement, RowMajorTraceBuilder, TraceTable};
+
+const TRACE_WIDTH: usize = 64;
+const TRACE_LENGTHS: [usize; 3] = [1 << 12, 1 << 14, 1 << 16];
+
+fn build_from_rows(c: &mut Criterion) {
+    let mut group = c.benchmark_group("trace_from_rows");
+    group.sample_size(10);
+    group.measurement_time(Duration::from_secs(10));
+
+    for &length in TRACE_LENGTHS.iter() {
+        let rows = (0..length)
+            .map(|_| rand_vector::<BaseElement>(TRACE_WIDTH))
+            .collect::<Vec<_>>();
+
+        group.bench_function(BenchmarkId::new("set", length), |bench| {
+            bench.iter(|| {
+                let mut trace = TraceTable::new(TRACE_WIDTH, length);
+                for (step, row) in rows.iter().enumerate() {
+                    for (column, &value) in row.iter().enumerate() {
+                        trace.set(column, step, value);
+                    }
+                }
+                trace
+            });
+        });
+
+        group.bench_function(BenchmarkId::new("transpose", length), |bench| {
+            bench.iter(|| {
+                let mut builder = RowMajorTraceBuilder::with_capacity(TRACE_WIDTH, length);
+                for row in rows.iter() {
+                    builder.push_row(row);
+                }
+                builder.build(|_, _| {})
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(trace_table_group, build_from_rows);
+criterion_main!(trace_table_group);
@@ -0,0 +1,249 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crypto::{ElementHasher, MerkleTree};
+use math::{fft, get_power_series, FieldElement, StarkField};
+use utils::{batch_iter_mut, collections::Vec, iter_mut};
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// FFT BACKEND
+// ================================================================================================
+
+/// Defines how polynomials are evaluated and interpolated during proof generation.
+///
+/// All FFT-based computations performed by the prover (trace low-degree extension, evaluation of
+/// constraint polynomials, and evaluation of the composition polynomials over the LDE domain)
+/// are routed through this trait. Thus, implementing it allows offloading these computations to
+/// external hardware (e.g., a GPU). [CpuBackend] implements this trait using the FFT functions
+/// of the [math](math::fft) crate.
+///
+/// Values may be elements of the base field or of its extensions; twiddles and domain offsets
+/// are always elements of the base field.
+pub trait FftBackend<B: StarkField>: Send + Sync {
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates polynomial `p` over the domain of length `p.len()` * `blowup_factor` shifted by
+    /// `domain_offset` and returns the result.
+    ///
+    /// The semantics of this method are the same as of [fft::evaluate_poly_with_offset()].
+    fn evaluate_poly_with_offset<E: FieldElement<BaseField = B>>(
+        &self,
+        p: &[E],
+        twiddles: &[B],
+        domain_offset: B,
+        blowup_factor: usize,
+    ) -> Vec<E>;
+
+    /// Interpolates `evaluations` over a domain of length `evaluations.len()` in the field
+    /// specified `B` into a polynomial in coefficient form; the interpolation is done in place.
+    ///
+    /// The semantics of this method are the same as of [fft::interpolate_poly()].
+    fn interpolate_poly<E: FieldElement<BaseField = B>>(
+        &self,
+        evaluations: &mut [E],
+        inv_twiddles: &[B],
+    );
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Evaluates polynomial `p` over the domain of length `p.len()` * `blowup_factor` shifted by
+    /// `domain_offset`, and writes the result back into `p`.
+    ///
+    /// By default, this calls [evaluate_poly_with_offset()](FftBackend::evaluate_poly_with_offset)
+    /// and replaces the contents of `p` with the result.
+    fn evaluate_poly_with_offset_in_place<E: FieldElement<BaseField = B>>(
+        &self,
+        p: &mut Vec<E>,
+        twiddles: &[B],
+        domain_offset: B,
+        blowup_factor: usize,
+    ) {
+        *p = self.evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor);
+    }
+
+    /// Interpolates `evaluations` over a domain of length `evaluations.len()` shifted by
+    /// `domain_offset` into a polynomial in coefficient form; the interpolation is done in place.
+    ///
+    /// By default, this calls [interpolate_poly()](FftBackend::interpolate_poly) and then
+    /// multiplies the i-th coefficient of the result by `domain_offset`^(-i).
+    fn interpolate_poly_with_offset<E: FieldElement<BaseField = B>>(
+        &self,
+        evaluations: &mut [E],
+        inv_twiddles: &[B],
+        domain_offset: B,
+    ) {
+        self.interpolate_poly(evaluations, inv_twiddles);
+        let offsets = get_power_series(domain_offset.inv(), evaluations.len());
+        iter_mut!(evaluations)
+            .zip(offsets)
+            .for_each(|(coefficient, offset)| *coefficient = coefficient.mul_base(offset));
+    }
+}
+
+// HASH BACKEND
+// ================================================================================================
+
+/// Defines how commitments to evaluation matrices are built during proof generation.
+///
+/// All Merkle tree commitments built by the prover (commitments to trace segments, to
+/// evaluations of the constraint composition polynomial, to periodic columns, and to FRI layers)
+/// are routed through this trait. Thus, implementing it allows offloading hashing to external
+/// hardware (e.g., a GPU). [CpuBackend] implements this trait using the hash function specified
+/// by `H`.
+///
+/// Leaves of commitment trees are hashes of matrix rows computed via
+/// [hash_elements()](ElementHasher::hash_elements), and thus, the hash function must implement
+/// [ElementHasher].
+pub trait HashBackend<H: ElementHasher>: Send + Sync {
+    // REQUIRED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Hashes the rows of the matrix defined by the provided `columns` and returns the resulting
+    /// digests; the i-th row consists of the i-th elements of all columns (in column order).
+    ///
+    /// All columns are expected to have the same length.
+    fn hash_leaves<E: FieldElement<BaseField = H::BaseField>>(
+        &self,
+        columns: &[&[E]],
+    ) -> Vec<H::Digest>;
+
+    /// Computes a layer of a Merkle tree from the layer below it; the i-th returned node must be
+    /// the result of merging `nodes` at positions 2i and 2i + 1.
+    fn build_tree_layer(&self, nodes: &[H::Digest]) -> Vec<H::Digest>;
+
+    // PROVIDED METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Builds a Merkle tree from the specified leaves.
+    ///
+    /// By default, the tree is built layer by layer using
+    /// [build_tree_layer()](HashBackend::build_tree_layer).
+    ///
+    /// # Panics
+    /// Panics if the number of leaves is smaller than two or is not a power of two.
+    fn build_tree(&self, leaves: Vec<H::Digest>) -> MerkleTree<H> {
+        MerkleTree::with_layer_builder(leaves, |nodes| self.build_tree_layer(nodes))
+            .expect("failed to construct Merkle tree")
+    }
+}
+
+// CPU BACKEND
+// ================================================================================================
+
+/// Default backend which performs all computations on the CPU.
+///
+/// When the crate is compiled with `concurrent` feature enabled, computations are performed in
+/// multiple threads.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CpuBackend;
+
+impl<B: StarkField> FftBackend<B> for CpuBackend {
+    fn evaluate_poly_with_offset<E: FieldElement<BaseField = B>>(
+        &self,
+        p: &[E],
+        twiddles: &[B],
+        domain_offset: B,
+        blowup_factor: usize,
+    ) -> Vec<E> {
+        fft::evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor)
+    }
+
+    fn interpolate_poly<E: FieldElement<BaseField = B>>(
+        &self,
+        evaluations: &mut [E],
+        inv_twiddles: &[B],
+    ) {
+        fft::interpolate_poly(evaluations, inv_twiddles)
+    }
+
+    fn evaluate_poly_with_offset_in_place<E: FieldElement<BaseField = B>>(
+        &self,
+        p: &mut Vec<E>,
+        twiddles: &[B],
+        domain_offset: B,
+        blowup_factor: usize,
+    ) {
+        fft::evaluate_poly_with_offset_in_place(p, twiddles, domain_offset, blowup_factor)
+    }
+
+    fn interpolate_poly_with_offset<E: FieldElement<BaseField = B>>(
+        &self,
+        evaluations: &mut [E],
+        inv_twiddles: &[B],
+        domain_offset: B,
+    ) {
+        fft::interpolate_poly_with_offset(evaluations, inv_twiddles, domain_offset)
+    }
+}
+
+impl<H: ElementHasher> HashBackend<H> for CpuBackend {
+    fn hash_leaves<E: FieldElement<BaseField = H::BaseField>>(
+        &self,
+        columns: &[&[E]],
+    ) -> Vec<H::Digest> {
+        let num_rows = columns.first().map_or(0, |column| column.len());
+        let mut row_hashes = vec![H::Digest::default(); num_rows];
+
+        // iterate though matrix rows, hashing each row; the hashing is done by first copying a
+        // row into row_buf to avoid heap allocations, and then by applying the hash function to
+        // the buffer.
+        batch_iter_mut!(
+            &mut row_hashes,
+            128, // min batch size
+            |batch: &mut [H::Digest], batch_offset: usize| {
+                let mut row_buf = vec![E::ZERO; columns.len()];
+                for (i, row_hash) in batch.iter_mut().enumerate() {
+                    for (value, column) in row_buf.iter_mut().zip(columns) {
+                        *value = column[i + batch_offset];
+                    }
+                    *row_hash = H::hash_elements(&row_buf);
+                }
+            }
+        );
+
+        row_hashes
+    }
+
+    fn build_tree_layer(&self, nodes: &[H::Digest]) -> Vec<H::Digest> {
+        let mut parents = vec![H::Digest::default(); nodes.len() / 2];
+        iter_mut!(parents, 1024)
+            .zip(nodes.chunks(2))
+            .for_each(|(parent, children)| *parent = H::merge(&[children[0], children[1]]));
+        parents
+    }
+
+    /// Builds a Merkle tree from the specified leaves via [MerkleTree::new()]; when the crate is
+    /// compiled with `concurrent` feature enabled, subtrees are built in parallel.
+    fn build_tree(&self, leaves: Vec<H::Digest>) -> MerkleTree<H> {
+        MerkleTree::new(leaves).expect("failed to construct Merkle tree")
+    }
+}
+
+// PROVER BACKENDS
+// ================================================================================================
+
+/// Backends used by a prover to evaluate polynomials and to build commitments.
+///
+/// By default, both backends are set to [CpuBackend]. Custom backends can be passed to
+/// [Prover::prove_with_backends()](crate::Prover::prove_with_backends).
+#[derive(Debug, Default, Clone)]
+pub struct ProverBackends<F = CpuBackend, H = CpuBackend> {
+    /// Backend used for polynomial evaluation and interpolation.
+    pub fft: F,
+    /// Backend used for building Merkle tree commitments.
+    pub hash: H,
+}
+
+impl<F, H> ProverBackends<F, H> {
+    /// Returns a new set of backends consisting of the specified FFT and hash backends.
+    pub fn new(fft: F, hash: H) -> Self {
+        Self { fft, hash }
+    }
+}
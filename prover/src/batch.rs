@@ -0,0 +1,212 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    constraints::ConstraintCommitment, trace::TraceCommitment, validate_proof_parameters,
+    validate_trace_shape, Air, BatchProof, CpuBackend, FieldExtension, InstanceProof, Prover,
+    ProverBackends, ProverChannel, ProverError, StarkDomain, Trace,
+};
+use crypto::ElementHasher;
+use fri::FriProver;
+use math::{
+    fields::{CubeExtension, QuadExtension, SexticExtension},
+    mul_acc, FieldElement, ToElements,
+};
+use utils::{collections::Vec, ByteWriter, Serializable};
+
+#[cfg(feature = "std")]
+use log::debug;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+// BATCH PROVER
+// ================================================================================================
+
+/// Defines generation of a single [BatchProof] for several instances of a computation.
+///
+/// This trait is implemented for every [Prover]. A batch proof attests to the correct execution
+/// of all provided execution traces, and shares a single FRI proof among all of them. See
+/// [BatchProof] for the description of the protocol and its security.
+pub trait BatchProver: Prover {
+    /// Returns a batch proof attesting to a correct execution of a computation for each of the
+    /// provided traces.
+    ///
+    /// All traces must have the same trace info; instances are described in the proof in the
+    /// order in which their traces are provided, and the public inputs of each instance must
+    /// match the value returned from [Prover::get_pub_inputs()] for the trace of the instance.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * No traces were provided, more than [BatchProof::MAX_NUM_INSTANCES] traces were
+    ///   provided, or the traces do not all have the same trace info.
+    /// * A proof could not be generated for any of the traces for the reasons described in
+    ///   [Prover::prove_with_seed()].
+    fn prove_batch(&self, traces: Vec<Self::Trace>) -> Result<BatchProof, ProverError> {
+        // trace info cannot be built for a trace of invalid length, and thus, the length of the
+        // first trace is validated before trace info of any trace is built; traces of other
+        // lengths are rejected before their trace info is built
+        let first_trace = traces.first().ok_or(ProverError::EmptyBatch)?;
+        if traces.len() > BatchProof::MAX_NUM_INSTANCES {
+            return Err(ProverError::BatchTooLarge(traces.len()));
+        }
+        validate_proof_parameters::<Self::BaseField>(first_trace.length(), self.options())?;
+        let trace_info = first_trace.get_info();
+        for (index, trace) in traces.iter().enumerate().skip(1) {
+            if trace.length() != trace_info.length() || trace.get_info() != trace_info {
+                return Err(ProverError::InconsistentBatchTraceInfo(index));
+            }
+        }
+
+        // figure out which version of the generic proof generation procedure to run; all
+        // instances are proven over the same extension field
+        match self.options().field_extension() {
+            FieldExtension::None => self.generate_batch_proof::<Self::BaseField>(traces),
+            FieldExtension::Quadratic => {
+                if !<QuadExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(2));
+                }
+                self.generate_batch_proof::<QuadExtension<Self::BaseField>>(traces)
+            }
+            FieldExtension::Cubic => {
+                if !<CubeExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(3));
+                }
+                self.generate_batch_proof::<CubeExtension<Self::BaseField>>(traces)
+            }
+            FieldExtension::Sextic => {
+                if !<SexticExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(6));
+                }
+                self.generate_batch_proof::<SexticExtension<Self::BaseField>>(traces)
+            }
+        }
+    }
+
+    /// Performs the actual batch proof generation procedure for the provided execution `traces`,
+    /// all of which have already been validated against the options of this prover.
+    #[doc(hidden)]
+    fn generate_batch_proof<E>(&self, traces: Vec<Self::Trace>) -> Result<BatchProof, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // 0 ----- instantiate AIRs and prover channel --------------------------------------------
+
+        // the seed for the public coin includes the canonical element encodings of the public
+        // inputs of all instances, each preceded by its length; thus, all public inputs are bound
+        // to the proof before any commitment is made
+        let mut pub_inputs_bytes = Vec::new();
+        pub_inputs_bytes.write_u16(traces.len() as u16);
+        let mut airs = Vec::with_capacity(traces.len());
+        for trace in traces.iter() {
+            let pub_inputs = self.get_pub_inputs(trace);
+            let elements = pub_inputs.to_elements();
+            pub_inputs_bytes.write_u32(elements.len() as u32);
+            elements.write_into(&mut pub_inputs_bytes);
+
+            let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
+            validate_trace_shape(trace, air.trace_info())?;
+            airs.push(air);
+        }
+
+        // all instances share the proof context, and thus, a single channel is used for all of
+        // them; the AIR of the channel is switched to the AIR of each instance in turn
+        let mut channel =
+            ProverChannel::<Self::Air, E, Self::HashFn>::new(&airs[0], pub_inputs_bytes);
+        let domain = StarkDomain::new(&airs[0]);
+        let backends = ProverBackends::new(CpuBackend, CpuBackend);
+
+        // 1 - 5 ----- commit to each instance and build its DEEP composition polynomial ----------
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let mut instances = Vec::with_capacity(traces.len());
+        for (air, trace) in airs.iter().zip(traces) {
+            channel.set_air(air);
+            let instance = self.commit_to_instance::<E, Self::HashFn, _, _>(
+                air,
+                trace,
+                &domain,
+                &mut channel,
+                false,
+                &backends,
+            )?;
+            let (commitments, ood_frame) = channel.take_instance_data();
+            instances.push((instance, commitments, ood_frame));
+        }
+        #[cfg(feature = "std")]
+        debug!(
+            "Committed to {} instances in {} ms",
+            instances.len(),
+            now.elapsed().as_millis()
+        );
+
+        // 6 ----- combine DEEP composition polynomials and compute FRI layers --------------------
+
+        // the coefficient is drawn only after all instances have been committed to; evaluations
+        // of the DEEP composition polynomial of instance i are multiplied by its i-th power
+        let batch_coefficient = channel.get_batch_coefficient();
+        let mut deep_evaluations = vec![E::ZERO; domain.lde_domain_size()];
+        let mut coefficient = E::ONE;
+        for (instance, _, _) in instances.iter_mut() {
+            let evaluations = core::mem::take(&mut instance.deep_evaluations);
+            mul_acc::<E, E>(&mut deep_evaluations, &evaluations, coefficient);
+            coefficient *= batch_coefficient;
+        }
+
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let mut fri_prover = FriProver::new(self.options().to_fri_options());
+        fri_prover.build_layers(&mut channel, deep_evaluations);
+        #[cfg(feature = "std")]
+        debug!(
+            "Computed {} FRI layers from combined composition polynomial evaluations in {} ms",
+            fri_prover.num_layers(),
+            now.elapsed().as_millis()
+        );
+
+        // 7 ----- determine query positions ------------------------------------------------------
+        channel.grind_query_seed([0; 32]);
+        let query_positions = channel.get_query_positions();
+
+        // 8 ----- build proof object -------------------------------------------------------------
+
+        // all instances are opened at the same positions as a single proof would be
+        let fri_proof = fri_prover.build_proof(query_positions.shared());
+        let instances = instances
+            .into_iter()
+            .map(|(instance, commitments, ood_frame)| InstanceProof {
+                commitments,
+                trace_queries: instance.trace_commitment.query(query_positions.trace()),
+                constraint_queries: instance
+                    .constraint_commitment
+                    .query(query_positions.constraint()),
+                periodic_queries: instance
+                    .periodic_commitment
+                    .map(|c| c.query(query_positions.trace())),
+                ood_frame,
+            })
+            .collect();
+
+        Ok(channel.build_batch_proof(instances, fri_proof))
+    }
+}
+
+impl<P: Prover> BatchProver for P {}
+
+// COMMITTED INSTANCE
+// ================================================================================================
+
+/// Data committed to by the prover for a single instance of a computation, together with the
+/// evaluations of the DEEP composition polynomial of the instance over the LDE domain.
+pub struct CommittedInstance<E, H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    pub trace_commitment: TraceCommitment<E, H>,
+    pub constraint_commitment: ConstraintCommitment<E, H>,
+    pub periodic_commitment: Option<ConstraintCommitment<E::BaseField, H>>,
+    pub deep_evaluations: Vec<E>,
+}
@@ -5,81 +5,123 @@
 // LICENSE file in the root directory of this source tree.
 
 use air::{
-    proof::{Commitments, Context, OodFrame, Queries, StarkProof},
-    Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients,
+    labels,
+    proof::{BatchProof, Commitments, Context, InstanceProof, OodFrame, Queries, StarkProof},
+    Air, ConstraintCompositionCoefficients, DeepCompositionCoefficients, PublicCoin,
+    QueryPositions, TranscriptLog,
 };
-use core::marker::PhantomData;
-use crypto::{ElementHasher, RandomCoin};
+use core::{marker::PhantomData, mem};
+use crypto::{ElementHasher, Transcript};
 use fri::{self, FriProof};
 use math::FieldElement;
-use utils::{collections::Vec, Serializable};
-
-#[cfg(feature = "concurrent")]
-use utils::iterators::*;
+use utils::collections::Vec;
 
 // TYPES AND INTERFACES
 // ================================================================================================
 
-pub struct ProverChannel<'a, A, E, H>
+/// Simulates the interaction between the prover and the verifier.
+///
+/// Commitments are built using hash function `H`, while the public coin uses hash function `C`;
+/// commitments are absorbed into the public coin by converting them into digests of `C`.
+pub struct ProverChannel<'a, A, E, H, C = H>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
+    C: ElementHasher<BaseField = A::BaseField>,
+    C::Digest: From<H::Digest>,
 {
     air: &'a A,
-    public_coin: RandomCoin<A::BaseField, H>,
+    public_coin: PublicCoin<A::BaseField, C>,
     context: Context,
     commitments: Commitments,
     ood_frame: OodFrame,
     pow_nonce: u64,
     _field_element: PhantomData<E>,
+    _commitment_hasher: PhantomData<H>,
 }
 
 // PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H> ProverChannel<'a, A, E, H>
+impl<'a, A, E, H, C> ProverChannel<'a, A, E, H, C>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
+    C: ElementHasher<BaseField = A::BaseField>,
+    C::Digest: From<H::Digest>,
 {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates a new prover channel for the specified `air` and public inputs.
     pub fn new(air: &'a A, pub_inputs_bytes: Vec<u8>) -> Self {
-        let context = Context::new::<A::BaseField>(air.trace_info(), air.options().clone());
+        let context = Context::new::<A::BaseField, H, C>(air.trace_info(), air.options().clone());
 
-        // build a seed for the public coin; the initial seed is the hash of public inputs and proof
-        // context, but as the protocol progresses, the coin will be reseeded with the info sent to
-        // the verifier
+        // build a seed for the public coin; the initial seed is the hash of the element encoding
+        // of public inputs and proof context, but as the protocol progresses, the coin will be
+        // reseeded with the info sent to the verifier; the type of the coin is defined by the
+        // transcript type in proof options
         let mut coin_seed = pub_inputs_bytes;
-        context.write_into(&mut coin_seed);
+        context.write_unversioned_into(&mut coin_seed);
+        let public_coin = PublicCoin::new(context.options().transcript_type(), &coin_seed);
 
         ProverChannel {
             air,
-            public_coin: RandomCoin::new(&coin_seed),
+            public_coin,
             context,
             commitments: Commitments::default(),
             ood_frame: OodFrame::default(),
             pow_nonce: 0,
             _field_element: PhantomData,
+            _commitment_hasher: PhantomData,
         }
     }
 
+    /// Returns this channel with recording of all subsequent public coin operations enabled.
+    pub fn with_transcript_log(mut self) -> Self {
+        self.public_coin = self.public_coin.with_log();
+        self
+    }
+
+    /// Replaces the AIR of this channel with the specified `air`.
+    ///
+    /// This is used to commit to several instances of a computation over the same channel; the
+    /// AIR of an instance defines how randomness is drawn for the instance.
+    pub fn set_air(&mut self, air: &'a A) {
+        self.air = air;
+    }
+
+    // TRANSCRIPT LOG
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a mutable reference to the transcript log of this channel, or None if recording
+    /// is not enabled.
+    pub fn transcript_log_mut(&mut self) -> Option<&mut TranscriptLog> {
+        self.public_coin.log_mut()
+    }
+
+    /// Returns the transcript log recorded by this channel so far, or None if recording is not
+    /// enabled.
+    pub fn take_transcript_log(&mut self) -> Option<TranscriptLog> {
+        self.public_coin.take_log()
+    }
+
     // COMMITMENT METHODS
     // --------------------------------------------------------------------------------------------
 
     /// Commits the prover the extended execution trace.
     pub fn commit_trace(&mut self, trace_root: H::Digest) {
         self.commitments.add::<H>(&trace_root);
-        self.public_coin.reseed(trace_root);
+        self.public_coin
+            .absorb(labels::TRACE_COMMITMENT, trace_root.into());
     }
 
     /// Commits the prover to the evaluations of the constraint composition polynomial.
     pub fn commit_constraints(&mut self, constraint_root: H::Digest) {
         self.commitments.add::<H>(&constraint_root);
-        self.public_coin.reseed(constraint_root);
+        self.public_coin
+            .absorb(labels::CONSTRAINT_COMMITMENT, constraint_root.into());
     }
 
     /// Saves the evaluations of trace polynomials over the out-of-domain evaluation frame. This
@@ -87,7 +129,8 @@ where
     pub fn send_ood_trace_states(&mut self, trace_states: &[Vec<E>]) {
         self.ood_frame.set_trace_states(trace_states);
         for trace_state in trace_states {
-            self.public_coin.reseed(H::hash_elements(trace_state));
+            self.public_coin
+                .absorb(labels::OOD_TRACE_STATES, C::hash_elements(trace_state));
         }
     }
 
@@ -95,19 +138,37 @@ where
     /// point. This also reseeds the public coin wit the hash of the evaluations.
     pub fn send_ood_constraint_evaluations(&mut self, evaluations: &[E]) {
         self.ood_frame.set_constraint_evaluations(evaluations);
-        self.public_coin.reseed(H::hash_elements(evaluations));
+        self.public_coin.absorb(
+            labels::OOD_CONSTRAINT_EVALUATIONS,
+            C::hash_elements(evaluations),
+        );
+    }
+
+    /// Saves the evaluations of committed periodic column polynomials at the out-of-domain
+    /// point. This also reseeds the public coin with the hash of the values; if periodic columns
+    /// are not committed (i.e., `values` is empty), this method does nothing.
+    pub fn send_ood_periodic_values(&mut self, values: &[E]) {
+        if !values.is_empty() {
+            self.ood_frame.set_periodic_values(values);
+            self.public_coin
+                .absorb(labels::OOD_PERIODIC_VALUES, C::hash_elements(values));
+        }
     }
 
     // PUBLIC COIN METHODS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a set of random elements required for constructing an auxiliary trace segment with
-    /// the specified index.
+    /// Returns a set of random elements required for constructing the specified round of an
+    /// auxiliary trace segment with the specified index.
     ///
     /// The elements are drawn from the public coin uniformly at random.
-    pub fn get_aux_trace_segment_rand_elements(&mut self, aux_segment_idx: usize) -> Vec<E> {
+    pub fn get_aux_trace_round_rand_elements(
+        &mut self,
+        aux_segment_idx: usize,
+        round_idx: usize,
+    ) -> Vec<E> {
         self.air
-            .get_aux_trace_segment_random_elements(aux_segment_idx, &mut self.public_coin)
+            .get_aux_trace_round_random_elements(aux_segment_idx, round_idx, &mut self.public_coin)
             .expect("failed to draw random elements for an auxiliary trace segment")
     }
 
@@ -121,8 +182,19 @@ where
     }
 
     /// Returns an out-of-domain point drawn uniformly at random from the public coin.
+    ///
+    /// Points which fall into the trace domain or into the LDE domain are discarded (see
+    /// [Air::is_out_of_domain()]), and a new point is drawn instead.
     pub fn get_ood_point(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw OOD point")
+        loop {
+            let z = self
+                .public_coin
+                .draw(labels::OOD_POINT)
+                .expect("failed to draw OOD point");
+            if self.air.is_out_of_domain(z) {
+                return z;
+            }
+        }
     }
 
     /// Returns a set of coefficients for constructing a DEEP composition polynomial.
@@ -134,37 +206,49 @@ where
             .expect("failed to draw DEEP composition coefficients")
     }
 
-    /// Returns a set of positions in the LDE domain against which the evaluations of trace and
+    /// Returns a coefficient for combining DEEP composition polynomials of all instances of a
+    /// batch proof.
+    ///
+    /// The coefficient is drawn from the public coin uniformly at random.
+    pub fn get_batch_coefficient(&mut self) -> E {
+        self.public_coin
+            .draw(labels::BATCH_COEFFICIENT)
+            .expect("failed to draw batch coefficient")
+    }
+
+    /// Returns sets of positions in the LDE domain against which the evaluations of trace and
     /// constraint composition polynomials should be queried.
     ///
-    /// The positions are drawn from the public coin uniformly at random.
-    pub fn get_query_positions(&mut self) -> Vec<usize> {
-        let num_queries = self.context.options().num_queries();
+    /// The positions are drawn from the public coin uniformly at random, and the positions within
+    /// each set are unique. See [PublicCoin::draw_query_positions()] for details.
+    pub fn get_query_positions(&mut self) -> QueryPositions {
         let lde_domain_size = self.context.lde_domain_size();
         self.public_coin
-            .draw_integers(num_queries, lde_domain_size)
+            .draw_query_positions(self.context.options(), lde_domain_size)
             .expect("failed to draw query position")
     }
 
     /// Determines a nonce, which when hashed with the current seed of the public coin results
     /// in a new seed with the number of leading zeros equal to the grinding_factor specified
     /// in the proof options.
-    pub fn grind_query_seed(&mut self) {
+    ///
+    /// Nonces are tried in order starting with the value right after the one derived from the
+    /// specified `seed`, and the first suitable nonce is selected even when the search is
+    /// performed concurrently (see
+    /// [RandomCoin::find_pow_nonce()](crypto::RandomCoin::find_pow_nonce)). Thus, the resulting
+    /// nonce depends only on the seed and the state of the public coin.
+    pub fn grind_query_seed(&mut self, seed: [u8; 32]) {
         let grinding_factor = self.context.options().grinding_factor();
+        let offset = seed.chunks(8).fold(0u64, |acc, chunk| {
+            let chunk = chunk.try_into().expect("invalid chunk length");
+            acc.wrapping_add(u64::from_le_bytes(chunk))
+        });
 
-        #[cfg(not(feature = "concurrent"))]
-        let nonce = (1..u64::MAX)
-            .find(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
-
-        #[cfg(feature = "concurrent")]
-        let nonce = (1..u64::MAX)
-            .into_par_iter()
-            .find_any(|&nonce| self.public_coin.check_leading_zeros(nonce) >= grinding_factor)
-            .expect("nonce not found");
-
+        let nonce = self
+            .public_coin
+            .find_pow_nonce(labels::POW_NONCE, grinding_factor, offset);
         self.pow_nonce = nonce;
-        self.public_coin.reseed_with_int(nonce);
+        self.public_coin.absorb_int(labels::POW_NONCE, nonce);
     }
 
     // PROOF BUILDER
@@ -175,6 +259,7 @@ where
         self,
         trace_queries: Vec<Queries>,
         constraint_queries: Queries,
+        periodic_queries: Option<Queries>,
         fri_proof: FriProof,
     ) -> StarkProof {
         StarkProof {
@@ -183,6 +268,35 @@ where
             ood_frame: self.ood_frame,
             trace_queries,
             constraint_queries,
+            periodic_queries,
+            fri_proof,
+            pow_nonce: self.pow_nonce,
+        }
+    }
+
+    /// Returns the commitments and the out-of-domain frame sent to the verifier since the last
+    /// call to this method, and resets them.
+    ///
+    /// This is used to separate the values sent for each instance of a batch proof from the
+    /// values sent for other instances and for the shared FRI proof.
+    pub fn take_instance_data(&mut self) -> (Commitments, OodFrame) {
+        (
+            mem::take(&mut self.commitments),
+            mem::take(&mut self.ood_frame),
+        )
+    }
+
+    /// Builds a batch proof from the provided instance proofs and the values committed to since
+    /// the last call to [take_instance_data()](Self::take_instance_data).
+    pub fn build_batch_proof(
+        self,
+        instances: Vec<InstanceProof>,
+        fri_proof: FriProof,
+    ) -> BatchProof {
+        BatchProof {
+            context: self.context,
+            instances,
+            fri_commitments: self.commitments,
             fri_proof,
             pow_nonce: self.pow_nonce,
         }
@@ -192,22 +306,27 @@ where
 // FRI PROVER CHANNEL IMPLEMENTATION
 // ================================================================================================
 
-impl<'a, A, E, H> fri::ProverChannel<E> for ProverChannel<'a, A, E, H>
+impl<'a, A, E, H, C> fri::ProverChannel<E> for ProverChannel<'a, A, E, H, C>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
+    C: ElementHasher<BaseField = A::BaseField>,
+    C::Digest: From<H::Digest>,
 {
     type Hasher = H;
 
     /// Commits the prover to a FRI layer.
     fn commit_fri_layer(&mut self, layer_root: H::Digest) {
         self.commitments.add::<H>(&layer_root);
-        self.public_coin.reseed(layer_root);
+        self.public_coin
+            .absorb(labels::FRI_LAYER_COMMITMENT, layer_root.into());
     }
 
     /// Returns a new alpha drawn from the public coin.
     fn draw_fri_alpha(&mut self) -> E {
-        self.public_coin.draw().expect("failed to draw FRI alpha")
+        self.public_coin
+            .draw(labels::FRI_ALPHA)
+            .expect("failed to draw FRI alpha")
     }
 }
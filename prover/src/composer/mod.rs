@@ -4,9 +4,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{constraints::CompositionPoly, StarkDomain, TracePolyTable};
+use super::{constraints::CompositionPoly, FftBackend, StarkDomain, TracePolyTable};
 use air::{Air, DeepCompositionCoefficients};
-use math::{add_in_place, fft, log2, mul_acc, polynom, ExtensionOf, FieldElement, StarkField};
+use math::{add_in_place, mul_acc, polynom, scalar_mul_acc, ExtensionOf, FieldElement};
 use utils::{collections::Vec, iter_mut};
 
 #[cfg(feature = "concurrent")]
@@ -18,7 +18,10 @@ pub struct DeepCompositionPoly<E: FieldElement> {
     coefficients: Vec<E>,
     cc: DeepCompositionCoefficients<E>,
     z: E,
+    frame_offsets: Vec<usize>,
     field_extension: bool,
+    trace_length: usize,
+    trace_generator: E::BaseField,
 }
 
 impl<E: FieldElement> DeepCompositionPoly<E> {
@@ -35,7 +38,10 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
             coefficients: vec![],
             cc,
             z,
+            frame_offsets: air.frame_offsets().to_vec(),
             field_extension: !air.options().field_extension().is_none(),
+            trace_length: air.trace_length(),
+            trace_generator: air.trace_domain_generator(),
         }
     }
 
@@ -57,37 +63,45 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     /// Combines all trace polynomials into a single polynomial and saves the result into
     /// the DEEP composition polynomial. The combination is done as follows:
     ///
-    /// - Compute polynomials T'_i(x) = (T_i(x) - T_i(z)) / (x - z) and
-    ///   T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g) for all i, where T_i(x) is a trace
-    ///   polynomial for column i.
-    /// - Then, combine together all T'_i(x) polynomials using random liner combination as
-    ///   T(x) = sum(T'_i(x) * cc'_i + T''_i(x) * cc''_i) for all i, where cc'_i and cc''_i are
-    ///   the coefficients for the random linear combination drawn from the public coin.
+    /// - For each frame offset o_j, compute polynomials
+    ///   T_i,j(x) = (T_i(x) - T_i(z * g^o_j)) / (x - z * g^o_j) for all i, where T_i(x) is a
+    ///   trace polynomial for column i. For the default offsets, these are
+    ///   T'_i(x) = (T_i(x) - T_i(z)) / (x - z) and T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g).
+    /// - Then, combine together all T_i,j(x) polynomials using random liner combination as
+    ///   T(x) = sum(T_i,j(x) * cc_i,j) for all i and j, where cc_i,j are the coefficients for
+    ///   the random linear combination drawn from the public coin.
     /// - In cases when we generate the proof using an extension field, we also compute
     ///   T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate), and add it to T(x) similarly
     ///   to the way described above. This is needed in order to verify that the trace is defined
     ///   over the base field, rather than the extension field.
     ///
-    /// Note that evaluations of T_i(z) and T_i(z * g) are passed in via the `ood_frame` parameter.
+    /// Note that evaluations of T_i(z * g^o_j) are passed in via the `ood_trace_states`
+    /// parameter, and must be listed in the same order as the frame offsets.
     pub fn add_trace_polys(
         &mut self,
         trace_polys: TracePolyTable<E>,
         ood_trace_states: Vec<Vec<E>>,
     ) {
         assert!(self.coefficients.is_empty());
-
-        // compute a second out-of-domain point offset from z by exactly trace generator; this
-        // point defines the "next" computation state in relation to point z
-        let trace_length = trace_polys.poly_size();
-        let g = E::from(E::BaseField::get_root_of_unity(log2(trace_length)));
-        let next_z = self.z * g;
-
-        // combine trace polynomials into 2 composition polynomials T'(x) and T''(x), and if
+        assert_eq!(self.frame_offsets.len(), ood_trace_states.len());
+
+        // compute out-of-domain points offset from z by powers of the trace generator; these
+        // points define the rows of the evaluation frame in relation to point z
+        let poly_size = trace_polys.poly_size();
+        let g = self.trace_generator;
+        let mut ood_points = self
+            .frame_offsets
+            .iter()
+            .map(|&offset| self.z * E::from(g.exp((offset as u64).into())))
+            .collect::<Vec<_>>();
+
+        // combine trace polynomials into one composition polynomial per frame offset, and if
         // we are using a field extension, also T'''(x)
-        let mut t1_composition = E::zeroed_vector(trace_length);
-        let mut t2_composition = E::zeroed_vector(trace_length);
-        let mut t3_composition = if self.field_extension {
-            E::zeroed_vector(trace_length)
+        let mut compositions = (0..ood_points.len())
+            .map(|_| E::zeroed_vector(poly_size))
+            .collect::<Vec<_>>();
+        let mut conjugate_composition = if self.field_extension {
+            E::zeroed_vector(poly_size)
         } else {
             Vec::new()
         };
@@ -98,32 +112,25 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
         // --- merge polynomials of the main trace segment ----------------------------------------
         for poly in trace_polys.main_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states[0][i],
-                self.cc.trace[i].0,
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E::BaseField, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states[1][i],
-                self.cc.trace[i].1,
-            );
+            // for each frame offset, compute T_i,j(x) = T(x) - T(z * g^o_j), multiply it by a
+            // pseudo-random coefficient, and add the result into composition polynomial
+            for (j, composition) in compositions.iter_mut().enumerate() {
+                acc_trace_poly::<E::BaseField, E>(
+                    composition,
+                    poly,
+                    ood_trace_states[j][i],
+                    self.cc.trace[i][j],
+                );
+            }
 
             // when extension field is enabled, compute T'''(x) = T(x) - T(z_conjugate), multiply
             // it by a pseudo-random coefficient, and add the result into composition polynomial
             if self.field_extension {
                 acc_trace_poly::<E::BaseField, E>(
-                    &mut t3_composition,
+                    &mut conjugate_composition,
                     poly,
                     ood_trace_states[0][i].conjugate(),
-                    self.cc.trace[i].2,
+                    self.cc.trace_conjugate[i],
                 );
             }
 
@@ -135,34 +142,26 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
         // since trace polynomials are already in an extension field (when extension fields are
         // used), we don't apply conjugate composition to them
         for poly in trace_polys.aux_trace_polys() {
-            // compute T'(x) = T(x) - T(z), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t1_composition,
-                poly,
-                ood_trace_states[0][i],
-                self.cc.trace[i].0,
-            );
-
-            // compute T''(x) = T(x) - T(z * g), multiply it by a pseudo-random coefficient,
-            // and add the result into composition polynomial
-            acc_trace_poly::<E, E>(
-                &mut t2_composition,
-                poly,
-                ood_trace_states[1][i],
-                self.cc.trace[i].1,
-            );
+            // for each frame offset, compute T_i,j(x) = T(x) - T(z * g^o_j), multiply it by a
+            // pseudo-random coefficient, and add the result into composition polynomial
+            for (j, composition) in compositions.iter_mut().enumerate() {
+                acc_trace_poly::<E, E>(
+                    composition,
+                    poly,
+                    ood_trace_states[j][i],
+                    self.cc.trace[i][j],
+                );
+            }
 
             i += 1;
         }
 
-        // divide the composition polynomials by (x - z), (x - z * g), and (x - z_conjugate)
+        // divide the composition polynomials by (x - z * g^o_j) and (x - z_conjugate)
         // respectively, and add the resulting polynomials together; the output of this step
-        // is a single trace polynomial T(x) and deg(T(x)) = trace_length - 2.
-        let trace_poly = merge_trace_compositions(
-            vec![t1_composition, t2_composition, t3_composition],
-            vec![self.z, next_z, self.z.conjugate()],
-        );
+        // is a single trace polynomial T(x) and deg(T(x)) = poly_size - 2.
+        compositions.push(conjugate_composition);
+        ood_points.push(self.z.conjugate());
+        let trace_poly = merge_trace_compositions(compositions, ood_points);
 
         // set the coefficients of the DEEP composition polynomial
         self.coefficients = trace_poly;
@@ -176,12 +175,14 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     /// add_trace_polys() method has been executed. The composition is done as follows:
     ///
     /// - For each H_i(x), compute H'_i(x) = (H_i(x) - H(z^m)) / (x - z^m), where H_i(x) is the
-    ///   ith composition polynomial column and m is the total number of columns.
+    ///   ith composition polynomial column and m is the total number of columns. For a
+    ///   composition polynomial randomized in zero-knowledge mode, z is used instead of z^m.
     /// - Then, combine all H_i(x) polynomials together by computing H(x) = sum(H_i(x) * cc_i) for
     ///   all i, where cc_i is the coefficient for the random linear combination drawn from the
     ///   public coin.
     ///
-    /// Note that evaluations of H_i(x) at z^m are passed in via the `ood_evaluations` parameter.
+    /// Note that evaluations of H_i(x) at z^m (or at z) are passed in via the `ood_evaluations`
+    /// parameter.
     pub fn add_composition_poly(
         &mut self,
         composition_poly: CompositionPoly<E>,
@@ -189,9 +190,8 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     ) {
         assert!(!self.coefficients.is_empty());
 
-        // compute z^m
-        let num_columns = composition_poly.num_columns() as u32;
-        let z_m = self.z.exp(num_columns.into());
+        // compute z^m, or take z for a randomized composition polynomial
+        let z_m = composition_poly.get_ood_point(self.z);
 
         let mut column_polys = composition_poly.into_columns();
 
@@ -206,8 +206,53 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
         // add H'_i(x) * cc_i for all i into the DEEP composition polynomial
         for (i, poly) in column_polys.into_iter().enumerate() {
-            mul_acc::<E, E>(&mut self.coefficients, &poly, self.cc.constraints[i]);
+            scalar_mul_acc(&mut self.coefficients, self.cc.constraints[i], &poly);
+        }
+        assert_eq!(self.poly_size() - 2, self.degree());
+    }
+
+    // PERIODIC POLYNOMIAL COMPOSITION
+    // --------------------------------------------------------------------------------------------
+    /// Divides out OOD point z from committed periodic column polynomials and saves the result
+    /// into the DEEP composition polynomial. This method is intended to be called only after the
+    /// add_trace_polys() method has been executed. The composition is done as follows:
+    ///
+    /// - For each P_i(x), compute P'_i(x) = (P_i(x^k) - P_i(z^k)) / (x - z), where P_i(x) is the
+    ///   ith periodic column polynomial and k is the number of cycles of the column in the
+    ///   execution trace.
+    /// - Then, combine all P'_i(x) polynomials together by computing P(x) = sum(P'_i(x) * cc_i)
+    ///   for all i, where cc_i is the coefficient for the random linear combination drawn from
+    ///   the public coin.
+    ///
+    /// Note that evaluations of P_i(x^k) at z are passed in via the `ood_values` parameter. If
+    /// periodic columns are not committed, both `periodic_polys` and `ood_values` are empty,
+    /// and this method does nothing.
+    pub fn add_periodic_polys(
+        &mut self,
+        periodic_polys: Vec<Vec<E::BaseField>>,
+        ood_values: Vec<E>,
+    ) {
+        assert!(!self.coefficients.is_empty());
+        assert_eq!(periodic_polys.len(), ood_values.len());
+        if periodic_polys.is_empty() {
+            return;
         }
+
+        // compute P(x) = sum((P_i(x^k) - P_i(z^k)) * cc_i); P_i(x^k) is obtained from P_i(x) by
+        // spacing out its coefficients k positions apart
+        let mut composition = E::zeroed_vector(self.poly_size());
+        for (i, (poly, value)) in periodic_polys.iter().zip(ood_values).enumerate() {
+            let num_cycles = self.trace_length / poly.len();
+            let k = self.cc.periodic[i];
+            for (j, &coefficient) in poly.iter().enumerate() {
+                composition[j * num_cycles] += k.mul_base(coefficient);
+            }
+            composition[0] -= value * k;
+        }
+
+        // divide the result by (x - z) and add it into the DEEP composition polynomial
+        polynom::syn_div_in_place(&mut composition, 1, self.z);
+        add_in_place(&mut self.coefficients, &composition);
         assert_eq!(self.poly_size() - 2, self.degree());
     }
 
@@ -215,9 +260,9 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
     // --------------------------------------------------------------------------------------------
     /// Increase the degree of the DEEP composition polynomial by one. After add_trace_polys() and
     /// add_composition_poly() are executed, the degree of the DEEP composition polynomial is
-    /// trace_length - 2 because in these functions we divide the polynomials of degree
-    /// trace_length - 1 by (x - z), (x - z * g) etc. which decreases the degree by one. We want to
-    /// ensure that degree of the DEEP composition polynomial is trace_length - 1, so we make the
+    /// poly_size - 2 because in these functions we divide the polynomials of degree
+    /// poly_size - 1 by (x - z), (x - z * g) etc. which decreases the degree by one. We want to
+    /// ensure that degree of the DEEP composition polynomial is poly_size - 1, so we make the
     /// adjustment here by computing C'(x) = C(x) * (cc_0 + x * cc_1), where cc_0 and cc_1 are the
     /// coefficients for the random linear combination drawn from the public coin.
     pub fn adjust_degree(&mut self) {
@@ -226,12 +271,12 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
         let mut result = E::zeroed_vector(self.coefficients.len());
 
         // this is equivalent to C(x) * cc_0
-        mul_acc::<E, E>(&mut result, &self.coefficients, self.cc.degree.0);
+        scalar_mul_acc(&mut result, self.cc.degree.0, &self.coefficients);
         // this is equivalent to C(x) * x * cc_1
-        mul_acc::<E, E>(
+        scalar_mul_acc(
             &mut result[1..],
-            &self.coefficients[..(self.coefficients.len() - 1)],
             self.cc.degree.1,
+            &self.coefficients[..(self.coefficients.len() - 1)],
         );
 
         self.coefficients = result;
@@ -240,13 +285,17 @@ impl<E: FieldElement> DeepCompositionPoly<E> {
 
     // LOW-DEGREE EXTENSION
     // --------------------------------------------------------------------------------------------
-    /// Evaluates DEEP composition polynomial over the specified LDE domain and returns the result.
-    pub fn evaluate(self, domain: &StarkDomain<E::BaseField>) -> Vec<E> {
-        fft::evaluate_poly_with_offset(
+    /// Evaluates DEEP composition polynomial over the specified LDE domain using the specified
+    /// FFT backend and returns the result.
+    pub fn evaluate<D>(self, domain: &StarkDomain<E::BaseField>, backend: &D) -> Vec<E>
+    where
+        D: FftBackend<E::BaseField>,
+    {
+        backend.evaluate_poly_with_offset(
             &self.coefficients,
-            domain.trace_twiddles(),
+            domain.trace_poly_twiddles(),
             domain.offset(),
-            domain.trace_to_lde_blowup(),
+            domain.trace_poly_to_lde_blowup(),
         )
     }
 }
@@ -4,7 +4,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::StarkDomain;
+use super::{FftBackend, StarkDomain};
 use air::{Air, AuxTraceRandElements, ConstraintDivisor};
 use math::{fft, ExtensionOf, FieldElement};
 use utils::collections::{BTreeMap, Vec};
@@ -25,17 +25,26 @@ const SMALL_POLY_DEGREE: usize = 63;
 ///
 /// We transform the constraints defined in the [air] crate into specialized constraints here
 /// to make evaluation of these constraints more efficient in the prover context.
-pub struct BoundaryConstraints<E: FieldElement>(Vec<BoundaryConstraintGroup<E>>);
+///
+/// Constraint divisors are shared between constraint groups in the same way as in the [air]
+/// crate, and each group refers to its divisor by index.
+pub struct BoundaryConstraints<E: FieldElement> {
+    groups: Vec<BoundaryConstraintGroup<E>>,
+    divisors: Vec<ConstraintDivisor<E::BaseField>>,
+}
 
 impl<E: FieldElement> BoundaryConstraints<E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new instance of [BoundaryConstraints] constructed from the constraints defined
     /// by an instance of AIR for a specific computation.
-    pub fn new<A: Air<BaseField = E::BaseField>>(
+    ///
+    /// Large polynomial constraints (if any) are evaluated using the specified FFT backend.
+    pub fn new<A: Air<BaseField = E::BaseField>, D: FftBackend<E::BaseField>>(
         air: &A,
         aux_rand_elements: &AuxTraceRandElements<E>,
         composition_coefficients: &[(E, E)],
+        backend: &D,
     ) -> Self {
         // get constraints from the AIR instance
         let source = air.get_boundary_constraints(aux_rand_elements, composition_coefficients);
@@ -52,35 +61,59 @@ impl<E: FieldElement> BoundaryConstraints<E> {
             .main_constraints()
             .iter()
             .map(|group| {
-                BoundaryConstraintGroup::from_main_constraints(group, air, &mut twiddle_map)
+                BoundaryConstraintGroup::from_main_constraints(
+                    group,
+                    air,
+                    &mut twiddle_map,
+                    backend,
+                )
             })
             .collect::<Vec<BoundaryConstraintGroup<E>>>();
 
+        // keep track of the positions of the groups in the result by the index of their shared
+        // divisor; since divisors are shared, there can be at most one main constraint group
+        // per divisor.
+        let mut group_positions = vec![None; source.divisors().len()];
+        for (i, group) in result.iter().enumerate() {
+            group_positions[group.divisor_idx] = Some(i);
+        }
+
         // transform constraints against auxiliary trace segments (if any) into specialized
         // constraints. this also checks if a group with the same divisor has already been
         // transformed (when processing constraints against the main trace above), and if so,
         // appends constraints to that group rather than creating a new group. this ensures
         // that we always end up with a single constraint group for the same divisor.
         for group in source.aux_constraints() {
-            match result.iter_mut().find(|g| &g.divisor == group.divisor()) {
-                Some(x) => x.add_aux_constraints(group, air, &mut twiddle_map),
+            match group_positions[group.divisor_idx()] {
+                Some(i) => result[i].add_aux_constraints(group, air, &mut twiddle_map, backend),
                 None => {
-                    let group =
-                        BoundaryConstraintGroup::from_aux_constraints(group, air, &mut twiddle_map);
+                    let group = BoundaryConstraintGroup::from_aux_constraints(
+                        group,
+                        air,
+                        &mut twiddle_map,
+                        backend,
+                    );
                     result.push(group);
                 }
             };
         }
 
-        Self(result)
+        Self {
+            groups: result,
+            divisors: source.divisors().to_vec(),
+        }
     }
 
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns a vector of all boundary constraint divisors.
+    /// Returns a vector of boundary constraint divisors; the vector contains the divisor of each
+    /// constraint group in the order in which the groups are evaluated.
     pub fn get_divisors(&self) -> Vec<ConstraintDivisor<E::BaseField>> {
-        self.0.iter().map(|g| g.divisor.clone()).collect()
+        self.groups
+            .iter()
+            .map(|g| self.divisors[g.divisor_idx].clone())
+            .collect()
     }
 
     // EVALUATORS
@@ -96,7 +129,7 @@ impl<E: FieldElement> BoundaryConstraints<E> {
         result: &mut [E],
     ) {
         let x = domain.get_ce_x_at(step);
-        for (group, result) in self.0.iter().zip(result.iter_mut()) {
+        for (group, result) in self.groups.iter().zip(result.iter_mut()) {
             // evaluate the group and save the result
             let (power, offset_exp) = (group.degree_adjustment, group.domain_offset_exp);
             let xp = domain.get_ce_x_power_at(step, power, offset_exp);
@@ -115,7 +148,7 @@ impl<E: FieldElement> BoundaryConstraints<E> {
         result: &mut [E],
     ) {
         let x = domain.get_ce_x_at(step);
-        for (group, result) in self.0.iter().zip(result.iter_mut()) {
+        for (group, result) in self.groups.iter().zip(result.iter_mut()) {
             // evaluate the group and save the result
             let (power, offset_exp) = (group.degree_adjustment, group.domain_offset_exp);
             let xp = domain.get_ce_x_power_at(step, power, offset_exp);
@@ -136,7 +169,7 @@ impl<E: FieldElement> BoundaryConstraints<E> {
 /// Domain offset exponent is pre-computed to be used later during constraint evaluation process,
 /// and thus, to help avoid exponentiations.
 pub struct BoundaryConstraintGroup<E: FieldElement> {
-    divisor: ConstraintDivisor<E::BaseField>,
+    divisor_idx: usize,
     degree_adjustment: u64,
     domain_offset_exp: E::BaseField,
     // main trace constraints
@@ -153,15 +186,11 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns an empty [BoundaryConstraintGroup] instantiated with the specified divisor and
-    /// degree adjustment factor.
-    fn new(
-        divisor: ConstraintDivisor<E::BaseField>,
-        degree_adjustment: u64,
-        domain_offset: E::BaseField,
-    ) -> Self {
+    /// Returns an empty [BoundaryConstraintGroup] instantiated with the specified divisor index
+    /// and degree adjustment factor.
+    fn new(divisor_idx: usize, degree_adjustment: u64, domain_offset: E::BaseField) -> Self {
         Self {
-            divisor,
+            divisor_idx,
             degree_adjustment,
             domain_offset_exp: domain_offset.exp(degree_adjustment.into()),
             main_single_value: Vec::new(),
@@ -177,15 +206,16 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     /// the main segment of an execution trace. Constraints against auxiliary trace segment in this
     /// group will be empty.
     ///
-    /// Twiddles and [Air] instance are passed in for evaluating large polynomial constraints
-    /// (if any).
-    pub fn from_main_constraints<A: Air<BaseField = E::BaseField>>(
+    /// Twiddles, [Air] instance, and FFT backend are passed in for evaluating large polynomial
+    /// constraints (if any).
+    pub fn from_main_constraints<A: Air<BaseField = E::BaseField>, D: FftBackend<E::BaseField>>(
         source: &air::BoundaryConstraintGroup<E::BaseField, E>,
         air: &A,
         twiddle_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
+        backend: &D,
     ) -> Self {
         let mut result = Self::new(
-            source.divisor().clone(),
+            source.divisor_idx(),
             source.degree_adjustment(),
             air.domain_offset(),
         );
@@ -198,7 +228,7 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
                 let constraint = SmallPolyConstraint::new(constraint);
                 result.main_small_poly.push(constraint);
             } else {
-                let constraint = LargePolyConstraint::new(constraint, air, twiddle_map);
+                let constraint = LargePolyConstraint::new(constraint, air, twiddle_map, backend);
                 result.main_large_poly.push(constraint);
             }
         }
@@ -210,19 +240,20 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     /// auxiliary segments of an execution trace. Constraints against the main trace segment in this
     /// group will be empty.
     ///
-    /// Twiddles and [Air] instance are passed in for evaluating large polynomial constraints
-    /// (if any).
-    pub fn from_aux_constraints<A: Air<BaseField = E::BaseField>>(
+    /// Twiddles, [Air] instance, and FFT backend are passed in for evaluating large polynomial
+    /// constraints (if any).
+    pub fn from_aux_constraints<A: Air<BaseField = E::BaseField>, D: FftBackend<E::BaseField>>(
         group: &air::BoundaryConstraintGroup<E, E>,
         air: &A,
         twiddle_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
+        backend: &D,
     ) -> Self {
         let mut result = Self::new(
-            group.divisor().clone(),
+            group.divisor_idx(),
             group.degree_adjustment(),
             air.domain_offset(),
         );
-        result.add_aux_constraints(group, air, twiddle_map);
+        result.add_aux_constraints(group, air, twiddle_map, backend);
         result
     }
 
@@ -232,20 +263,21 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
     /// Adds the provided constraints against auxiliary segments of an execution trace to this
     /// group.
     ///
-    /// Twiddles and [Air] instance are passed in for evaluating large polynomial constraints
-    /// (if any).
+    /// Twiddles, [Air] instance, and FFT backend are passed in for evaluating large polynomial
+    /// constraints (if any).
     ///
     /// # Panics
     /// Panics if the divisor of the provided constraints doesn't match the divisor of this group.
-    pub fn add_aux_constraints<A: Air<BaseField = E::BaseField>>(
+    pub fn add_aux_constraints<A: Air<BaseField = E::BaseField>, D: FftBackend<E::BaseField>>(
         &mut self,
         group: &air::BoundaryConstraintGroup<E, E>,
         air: &A,
         twiddle_map: &mut BTreeMap<usize, Vec<E::BaseField>>,
+        backend: &D,
     ) {
         assert_eq!(
-            group.divisor(),
-            &self.divisor,
+            group.divisor_idx(),
+            self.divisor_idx,
             "inconsistent constraint divisor"
         );
 
@@ -257,7 +289,7 @@ impl<E: FieldElement> BoundaryConstraintGroup<E> {
                 let constraint = SmallPolyConstraint::new(constraint);
                 self.aux_small_poly.push(constraint);
             } else {
-                let constraint = LargePolyConstraint::new(constraint, air, twiddle_map);
+                let constraint = LargePolyConstraint::new(constraint, air, twiddle_map, backend);
                 self.aux_large_poly.push(constraint);
             }
         }
@@ -445,11 +477,13 @@ where
     E: FieldElement<BaseField = F::BaseField> + ExtensionOf<F>,
 {
     /// Returns a new instance of [LargePolyConstraint] created from the specified source
-    /// boundary constraint.
-    pub fn new<A: Air<BaseField = F::BaseField>>(
+    /// boundary constraint; the polynomial of the constraint is evaluated using the specified
+    /// FFT backend.
+    pub fn new<A: Air<BaseField = F::BaseField>, D: FftBackend<F::BaseField>>(
         source: &air::BoundaryConstraint<F, E>,
         air: &A,
         twiddle_map: &mut BTreeMap<usize, Vec<F::BaseField>>,
+        backend: &D,
     ) -> Self {
         debug_assert!(
             source.poly().len() >= SMALL_POLY_DEGREE,
@@ -462,7 +496,7 @@ where
             .entry(poly_length)
             .or_insert_with(|| fft::get_twiddles(poly_length));
 
-        let values = fft::evaluate_poly_with_offset(
+        let values = backend.evaluate_poly_with_offset(
             source.poly(),
             twiddles,
             air.domain_offset(),
@@ -15,19 +15,27 @@ use utils::collections::Vec;
 
 /// Constraint evaluation commitment.
 ///
-/// The commitment consists of two components:
+/// The commitment consists of the following components:
 /// * Evaluations of composition polynomial columns over the LDE domain.
 /// * Merkle tree where each leaf in the tree corresponds to a row in the composition polynomial
 ///   evaluation matrix.
+/// * Salts of the Merkle tree leaves, if the evaluations were committed to with salts
+///   mode.
 pub struct ConstraintCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     evaluations: Matrix<E>,
     commitment: MerkleTree<H>,
+    salts: Option<Vec<H::Digest>>,
 }
 
 impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintCommitment<E, H> {
     /// Creates a new constraint evaluation commitment from the provided composition polynomial
-    /// evaluations and the corresponding Merkle tree commitment.
-    pub fn new(evaluations: Matrix<E>, commitment: MerkleTree<H>) -> ConstraintCommitment<E, H> {
+    /// evaluations, the corresponding Merkle tree commitment, and the salts of the tree leaves
+    /// (if any).
+    pub fn new(
+        evaluations: Matrix<E>,
+        commitment: MerkleTree<H>,
+        salts: Option<Vec<H::Digest>>,
+    ) -> ConstraintCommitment<E, H> {
         assert_eq!(
             evaluations.num_rows(),
             commitment.leaves().len(),
@@ -36,6 +44,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
         ConstraintCommitment {
             evaluations,
             commitment,
+            salts,
         }
     }
 
@@ -67,6 +76,13 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintComm
             evaluations.push(row);
         }
 
-        Queries::new(merkle_proof, evaluations)
+        // include salts of the queried leaves for salted commitments
+        match self.salts {
+            Some(salts) => {
+                let query_salts = positions.iter().map(|&i| salts[i]).collect();
+                Queries::new_salted(merkle_proof, evaluations, query_salts)
+            }
+            None => Queries::new(merkle_proof, evaluations),
+        }
     }
 }
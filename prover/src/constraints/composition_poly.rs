@@ -4,47 +4,87 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{Matrix, StarkDomain};
+use super::{zk, FftBackend, Matrix, StarkDomain};
 use math::{polynom, FieldElement, StarkField};
-use utils::{collections::Vec, uninit_vector};
+use utils::collections::Vec;
 
 // COMPOSITION POLYNOMIAL
 // ================================================================================================
 /// Represents a composition polynomial split into columns with each column being of length equal
 /// to trace_length. Thus, for example, if the composition polynomial has degree 2N - 1, where N
 /// is the trace length, it will be stored as two columns of size N (each of degree N - 1).
+///
+/// The number of columns does not need to be a power of two; e.g., a polynomial of degree
+/// 6N - 1 is stored as six columns.
+///
+/// In zero-knowledge mode, the columns are randomized, and an additional random column is
+/// appended to them (see [CompositionPoly::new_randomized()]).
 pub struct CompositionPoly<E: FieldElement> {
     data: Matrix<E>,
+    randomized: bool,
 }
 
 impl<E: FieldElement> CompositionPoly<E> {
     /// Returns a new composition polynomial.
     pub fn new(coefficients: Vec<E>, trace_length: usize) -> Self {
-        assert!(
-            coefficients.len().is_power_of_two(),
-            "size of composition polynomial must be a power of 2, but was {}",
-            coefficients.len(),
-        );
-        assert!(
-            trace_length.is_power_of_two(),
-            "trace length must be a power of 2, but was {trace_length}"
-        );
-        assert!(
-            trace_length < coefficients.len(),
-            "trace length must be smaller than size of composition polynomial"
-        );
-        assert!(
-            coefficients[coefficients.len() - 1] != E::ZERO,
-            "expected composition polynomial of degree {}, but was {}",
-            coefficients.len() - 1,
-            polynom::degree_of(&coefficients)
-        );
+        validate_coefficients(&coefficients, trace_length);
 
         let num_columns = coefficients.len() / trace_length;
         let polys = transpose(coefficients, num_columns);
 
         CompositionPoly {
             data: Matrix::new(polys),
+            randomized: false,
+        }
+    }
+
+    /// Returns a new composition polynomial randomized for zero-knowledge proofs, such that each
+    /// column has `poly_size` coefficients.
+    ///
+    /// The polynomial $H(x)$ is split into $k$ consecutive chunks of `trace_length` $n$
+    /// coefficients, such that $H(x) = \sum_{i=0}^{k-1} x^{i \cdot n} \cdot H_i(x)$. Then, for
+    /// every pair of adjacent chunks, a random polynomial $r_i(x)$ of degree `poly_size` - $n - 1$
+    /// is drawn; $x^n \cdot r_i(x)$ is added to column $i$, and $r_i(x)$ is subtracted from
+    /// column $i + 1$. Thus, $H(x)$ is still equal to the sum of the columns multiplied by
+    /// $x^{i \cdot n}$. Finally, a random column of `poly_size` coefficients is appended to the
+    /// $k$ columns; this column is not a part of $H(x)$.
+    ///
+    /// All columns of a randomized polynomial are evaluated at the out-of-domain point $z$ (see
+    /// [CompositionPoly::get_ood_point()]).
+    pub fn new_randomized(coefficients: Vec<E>, trace_length: usize, poly_size: usize) -> Self {
+        assert!(
+            poly_size > trace_length,
+            "randomized column size must be greater than trace length {trace_length}, but was {poly_size}"
+        );
+        validate_coefficients(&coefficients, trace_length);
+
+        // split the coefficients into consecutive chunks
+        let mut columns = coefficients
+            .chunks(trace_length)
+            .map(|chunk| {
+                let mut column = Vec::with_capacity(poly_size);
+                column.extend_from_slice(chunk);
+                column.resize(poly_size, E::ZERO);
+                column
+            })
+            .collect::<Vec<_>>();
+        let num_chunks = columns.len();
+
+        // randomize the chunks such that the randomizers cancel out in the sum
+        for i in 0..num_chunks - 1 {
+            let randomizer = zk::draw_random_elements::<E>(poly_size - trace_length);
+            for (j, &r) in randomizer.iter().enumerate() {
+                columns[i][j + trace_length] += r;
+                columns[i + 1][j] -= r;
+            }
+        }
+
+        // append a random column
+        columns.push(zk::draw_random_elements(poly_size));
+
+        CompositionPoly {
+            data: Matrix::new(columns),
+            randomized: true,
         }
     }
 
@@ -68,31 +108,44 @@ impl<E: FieldElement> CompositionPoly<E> {
         self.column_len() - 1
     }
 
+    /// Returns the point at which the column polynomials are evaluated to check their
+    /// consistency with the out-of-domain point `z`.
+    ///
+    /// For a composition polynomial split into $k$ columns, this is $z^k$. For a randomized
+    /// composition polynomial, this is $z$.
+    pub fn get_ood_point(&self, z: E) -> E {
+        if self.randomized {
+            z
+        } else {
+            z.exp((self.num_columns() as u32).into())
+        }
+    }
+
     // LOW-DEGREE EXTENSION
     // --------------------------------------------------------------------------------------------
     /// Evaluates the columns of the composition polynomial over the specified LDE domain and
-    /// returns the result.
-    pub fn evaluate<B>(&self, domain: &StarkDomain<B>) -> Matrix<E>
+    /// returns the result; the evaluations are computed using the specified FFT backend.
+    pub fn evaluate<B, D>(&self, domain: &StarkDomain<B>, backend: &D) -> Matrix<E>
     where
         B: StarkField,
         E: FieldElement<BaseField = B>,
+        D: FftBackend<B>,
     {
         assert_eq!(
             self.column_len(),
-            domain.trace_length(),
-            "inconsistent trace domain size; expected {}, but received {}",
+            domain.trace_poly_size(),
+            "inconsistent trace polynomial size; expected {}, but received {}",
             self.column_len(),
-            domain.trace_length()
+            domain.trace_poly_size()
         );
 
-        self.data.evaluate_columns_over(domain)
+        self.data.evaluate_columns_over_with(domain, backend)
     }
 
-    /// Returns evaluations of all composition polynomial columns at point z^m, where m is
-    /// the number of column polynomials.
+    /// Returns evaluations of all composition polynomial columns at the point returned by
+    /// [CompositionPoly::get_ood_point()] for `z`.
     pub fn evaluate_at(&self, z: E) -> Vec<E> {
-        let z_m = z.exp((self.num_columns() as u32).into());
-        self.data.evaluate_columns_at(z_m)
+        self.data.evaluate_columns_at(self.get_ood_point(z))
     }
 
     /// Transforms this composition polynomial into a vector of individual column polynomials.
@@ -104,6 +157,27 @@ impl<E: FieldElement> CompositionPoly<E> {
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Makes sure the specified coefficients describe a composition polynomial which can be split
+/// into columns of `trace_length` coefficients.
+fn validate_coefficients<E: FieldElement>(coefficients: &[E], trace_length: usize) {
+    assert!(
+        trace_length.is_power_of_two(),
+        "trace length must be a power of 2, but was {trace_length}"
+    );
+    assert!(
+        !coefficients.is_empty() && coefficients.len() % trace_length == 0,
+        "size of composition polynomial must be a non-zero multiple of trace length {}, but was {}",
+        trace_length,
+        coefficients.len(),
+    );
+    assert!(
+        coefficients[coefficients.len() - 1] != E::ZERO,
+        "expected composition polynomial of degree {}, but was {}",
+        coefficients.len() - 1,
+        polynom::degree_of(coefficients)
+    );
+}
+
 /// Splits polynomial coefficients into the specified number of columns. The coefficients are split
 /// in such a way that each resulting column has the same degree. For example, a polynomial
 /// a * x^3 + b * x^2 + c * x + d, can be rewritten as: (b * x^2 + d) + x * (a * x^2 + c), and then
@@ -111,11 +185,9 @@ impl<E: FieldElement> CompositionPoly<E> {
 fn transpose<E: FieldElement>(coefficients: Vec<E>, num_columns: usize) -> Vec<Vec<E>> {
     let column_len = coefficients.len() / num_columns;
 
-    let mut result = unsafe {
-        (0..num_columns)
-            .map(|_| uninit_vector(column_len))
-            .collect::<Vec<_>>()
-    };
+    let mut result = (0..num_columns)
+        .map(|_| E::zeroed_vector(column_len))
+        .collect::<Vec<_>>();
 
     // TODO: implement multi-threaded version
     for (i, coeff) in coefficients.into_iter().enumerate() {
@@ -132,8 +204,9 @@ fn transpose<E: FieldElement>(coefficients: Vec<E>, num_columns: usize) -> Vec<V
 
 #[cfg(test)]
 mod tests {
-
-    use math::fields::f128::BaseElement;
+    use super::CompositionPoly;
+    use math::{fields::f128::BaseElement, polynom, FieldElement};
+    use rand_utils::{rand_value, rand_vector};
     use utils::collections::Vec;
 
     #[test]
@@ -151,4 +224,40 @@ mod tests {
 
         assert_eq!(expected, actual)
     }
+
+    #[test]
+    fn new_randomized() {
+        let trace_length = 8;
+        let poly_size = 32;
+        let coefficients = rand_vector::<BaseElement>(3 * trace_length);
+        let poly = CompositionPoly::new_randomized(coefficients.clone(), trace_length, poly_size);
+
+        // the polynomial is split into three randomized columns and a random column
+        assert_eq!(4, poly.num_columns());
+        assert_eq!(poly_size, poly.column_len());
+
+        // columns are evaluated at z, and the composition polynomial is recovered from all but
+        // the last column
+        let z = rand_value::<BaseElement>();
+        assert_eq!(z, poly.get_ood_point(z));
+        let column_values = poly.evaluate_at(z);
+        let z_n = z.exp((trace_length as u32).into());
+        let expected = column_values[..3]
+            .iter()
+            .rev()
+            .fold(BaseElement::ZERO, |result, &value| result * z_n + value);
+        assert_eq!(expected, polynom::eval(&coefficients, z));
+
+        // the first chunk is extended by random coefficients, and the randomizers are
+        // subtracted from the next chunk
+        let columns = poly.into_columns();
+        assert_eq!(coefficients[..trace_length], columns[0][..trace_length]);
+        assert!(columns[0][trace_length..]
+            .iter()
+            .all(|&c| c != BaseElement::ZERO));
+        assert_ne!(
+            coefficients[trace_length..2 * trace_length],
+            columns[1][..trace_length]
+        );
+    }
 }
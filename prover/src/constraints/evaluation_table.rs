@@ -4,82 +4,131 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{CompositionPoly, ConstraintDivisor, ProverError, StarkDomain};
-use math::{batch_inversion, fft, FieldElement, StarkField};
-use utils::{batch_iter_mut, collections::Vec, iter_mut, uninit_vector};
+use super::{CompositionPoly, ConstraintDivisor, FftBackend, ProverError, StarkDomain};
+use core::ops::Range;
+use math::{batch_inversion_in_place, fft, get_power_series, polynom, FieldElement, StarkField};
+use utils::{batch_iter_mut, collections::Vec, iter_mut};
 
 #[cfg(debug_assertions)]
 use air::TransitionConstraints;
 
+#[cfg(debug_assertions)]
+use log::debug;
+
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
 
 // CONSTANTS
 // ================================================================================================
 
-const MIN_FRAGMENT_SIZE: usize = 16;
+pub(super) const MIN_FRAGMENT_SIZE: usize = 16;
 
 // CONSTRAINT EVALUATION TABLE
 // ================================================================================================
 
+/// Constraint evaluations over the constraint evaluation domain.
+///
+/// Constraints are evaluated over the domain tile by tile, where a tile is a contiguous range of
+/// rows of the domain. Evaluations of constraints over a tile are written into a buffer with one
+/// column per constraint divisor; once the tile is fully evaluated, the columns are divided by
+/// their divisors and added together into a single column of combined evaluations. Thus, only
+/// the combined column spans the entire domain, and the memory needed for the evaluations of
+/// individual columns is proportional to the size of a tile.
 pub struct ConstraintEvaluationTable<'a, E: FieldElement> {
-    evaluations: Vec<Vec<E>>,
-    divisors: Vec<ConstraintDivisor<E::BaseField>>,
+    combined_evaluations: Vec<E>,
+    tile: Vec<Vec<E>>,
+    divisor_values: Vec<DivisorValues<E::BaseField>>,
     domain: &'a StarkDomain<E::BaseField>,
 
+    #[cfg(debug_assertions)]
+    divisors: Vec<ConstraintDivisor<E::BaseField>>,
+    #[cfg(debug_assertions)]
+    evaluations: Vec<Vec<E>>,
     #[cfg(debug_assertions)]
     main_transition_evaluations: Vec<Vec<E::BaseField>>,
     #[cfg(debug_assertions)]
     aux_transition_evaluations: Vec<Vec<E>>,
     #[cfg(debug_assertions)]
     expected_transition_degrees: Vec<usize>,
+    #[cfg(debug_assertions)]
+    transition_divisors: Vec<ConstraintDivisor<E::BaseField>>,
 }
 
 impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Returns a new constraint evaluation table with number of columns equal to the number of
-    /// specified divisors, and number of rows equal to the size of constraint evaluation domain.
+    /// specified divisors, which is evaluated over the constraint evaluation domain in tiles of
+    /// `tile_size` rows.
+    ///
+    /// Values needed to divide evaluations of constraints by the divisors are computed over the
+    /// constraint evaluation domain using the specified FFT backend.
+    ///
+    /// `tile_size` must be a power of two which is not greater than the size of the constraint
+    /// evaluation domain.
     #[cfg(not(debug_assertions))]
-    pub fn new(
+    pub fn new<D: FftBackend<E::BaseField>>(
         domain: &'a StarkDomain<E::BaseField>,
         divisors: Vec<ConstraintDivisor<E::BaseField>>,
+        tile_size: usize,
+        backend: &D,
     ) -> Self {
         let num_columns = divisors.len();
         let num_rows = domain.ce_domain_size();
+        let divisor_values = divisors
+            .iter()
+            .map(|divisor| DivisorValues::new(divisor, domain, backend))
+            .collect();
         ConstraintEvaluationTable {
-            evaluations: uninit_matrix(num_columns, num_rows),
-            divisors,
+            combined_evaluations: E::zeroed_vector(num_rows),
+            tile: zeroed_matrix(num_columns, tile_size),
+            divisor_values,
             domain,
         }
     }
 
     /// Similar to the as above constructor but used in debug mode. In debug mode we also want
-    /// to keep track of all evaluated transition constraints so that we can verify that their
-    /// expected degrees match their actual degrees.
+    /// to keep track of all evaluated columns and transition constraints so that we can verify
+    /// that their expected degrees match their actual degrees.
     #[cfg(debug_assertions)]
-    pub fn new(
+    pub fn new<D: FftBackend<E::BaseField>>(
         domain: &'a StarkDomain<E::BaseField>,
         divisors: Vec<ConstraintDivisor<E::BaseField>>,
+        tile_size: usize,
+        backend: &D,
         transition_constraints: &TransitionConstraints<E>,
     ) -> Self {
         let num_columns = divisors.len();
         let num_rows = domain.ce_domain_size();
         let num_tm_columns = transition_constraints.num_main_constraints();
         let num_ta_columns = transition_constraints.num_aux_constraints();
+        let divisor_values = divisors
+            .iter()
+            .map(|divisor| DivisorValues::new(divisor, domain, backend))
+            .collect();
 
-        // collect expected degrees for all transition constraints to compare them against actual
-        // degrees; we do this in debug mode only because this comparison is expensive
-        let expected_transition_degrees =
-            build_transition_constraint_degrees(transition_constraints, domain.trace_length());
+        // collect divisors and expected degrees for all transition constraints to compare them
+        // against actual degrees; we do this in debug mode only because this comparison is
+        // expensive
+        let transition_divisors = build_transition_constraint_divisors(transition_constraints);
+        let expected_transition_degrees = build_transition_constraint_degrees(
+            transition_constraints,
+            &transition_divisors,
+            domain.trace_length(),
+            domain.trace_poly_size() - 1,
+        );
 
         ConstraintEvaluationTable {
-            evaluations: uninit_matrix(num_columns, num_rows),
-            divisors,
+            combined_evaluations: E::zeroed_vector(num_rows),
+            tile: zeroed_matrix(num_columns, tile_size),
+            divisor_values,
             domain,
-            main_transition_evaluations: uninit_matrix(num_tm_columns, num_rows),
-            aux_transition_evaluations: uninit_matrix(num_ta_columns, num_rows),
+            divisors,
+            evaluations: zeroed_matrix(num_columns, num_rows),
+            main_transition_evaluations: zeroed_matrix(num_tm_columns, num_rows),
+            aux_transition_evaluations: zeroed_matrix(num_ta_columns, num_rows),
             expected_transition_degrees,
+            transition_divisors,
         }
     }
 
@@ -89,39 +138,61 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
     /// Returns the number of rows in this table. This is the same as the size of the constraint
     /// evaluation domain.
     pub fn num_rows(&self) -> usize {
-        self.evaluations[0].len()
+        self.combined_evaluations.len()
     }
 
-    /// Returns number of columns in this table. The first column always contains the value of
-    /// combined transition constraint evaluations; the remaining columns contain values of
-    /// assertion constraint evaluations combined based on common divisors.
+    /// Returns number of columns in this table. The first columns contain values of transition
+    /// constraint evaluations combined based on common divisors; the remaining columns contain
+    /// values of assertion constraint evaluations combined based on common divisors.
     #[allow(dead_code)]
     pub fn num_columns(&self) -> usize {
-        self.evaluations.len()
+        self.tile.len()
+    }
+
+    /// Returns the number of rows in a single tile of this table.
+    pub fn tile_size(&self) -> usize {
+        self.tile[0].len()
     }
 
     // TABLE FRAGMENTS
     // --------------------------------------------------------------------------------------------
 
-    /// Break the table into the number of specified fragments. All fragments can be updated
-    /// independently - e.g. in different threads.
-    pub fn fragments(&mut self, num_fragments: usize) -> Vec<EvaluationTableFragment<E>> {
-        let fragment_size = self.num_rows() / num_fragments;
+    /// Break the tile starting at the specified row of the table into the number of specified
+    /// fragments. All fragments can be updated independently - e.g. in different threads.
+    ///
+    /// Once all fragments of the tile are updated, the tile must be added to the table via
+    /// [Self::accumulate_tile()].
+    pub fn tile_fragments(
+        &mut self,
+        tile_offset: usize,
+        num_fragments: usize,
+    ) -> Vec<EvaluationTableFragment<E>> {
+        let tile_size = self.tile_size();
+        let fragment_size = tile_size / num_fragments;
         assert!(
             fragment_size >= MIN_FRAGMENT_SIZE,
             "fragment size must be at least {MIN_FRAGMENT_SIZE}, but was {fragment_size}"
         );
 
         // break evaluations into fragments
-        let evaluation_data = make_fragments(&mut self.evaluations, num_fragments);
+        let tile_rows = 0..tile_size;
+        let evaluation_data = make_fragments(&mut self.tile, tile_rows, num_fragments);
 
         #[cfg(debug_assertions)]
         let result = {
-            // in debug mode, also break individual transition evaluations into fragments
-            let tm_evaluation_data =
-                make_fragments(&mut self.main_transition_evaluations, num_fragments);
-            let ta_evaluation_data =
-                make_fragments(&mut self.aux_transition_evaluations, num_fragments);
+            // in debug mode, also break individual transition evaluations into fragments; these
+            // are kept for the entire domain, and thus, rows of the tile are selected from them
+            let table_rows = tile_offset..tile_offset + tile_size;
+            let tm_evaluation_data = make_fragments(
+                &mut self.main_transition_evaluations,
+                table_rows.clone(),
+                num_fragments,
+            );
+            let ta_evaluation_data = make_fragments(
+                &mut self.aux_transition_evaluations,
+                table_rows,
+                num_fragments,
+            );
 
             evaluation_data
                 .into_iter()
@@ -130,7 +201,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
                 .enumerate()
                 .map(|(i, ((evaluations, tm_evaluations), ta_evaluations))| {
                     EvaluationTableFragment {
-                        offset: i * fragment_size,
+                        offset: tile_offset + i * fragment_size,
                         evaluations,
                         tm_evaluations,
                         ta_evaluations,
@@ -144,7 +215,7 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
             .into_iter()
             .enumerate()
             .map(|(i, evaluations)| EvaluationTableFragment {
-                offset: i * fragment_size,
+                offset: tile_offset + i * fragment_size,
                 evaluations,
             })
             .collect();
@@ -152,50 +223,134 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
         result
     }
 
+    /// Divides the columns of the tile starting at the specified row of the table by their
+    /// respective divisors, and writes the sum of the results into the combined evaluations.
+    pub fn accumulate_tile(&mut self, tile_offset: usize) {
+        let tile = &self.tile;
+        let divisor_values = &self.divisor_values;
+        let combined = &mut self.combined_evaluations[tile_offset..tile_offset + tile[0].len()];
+        batch_iter_mut!(
+            combined,
+            128, // min batch size
+            |batch: &mut [E], batch_offset: usize| {
+                for (i, combined_value) in batch.iter_mut().enumerate() {
+                    let row = batch_offset + i;
+                    let step = tile_offset + row;
+                    let mut result = E::ZERO;
+                    for (column, values) in tile.iter().zip(divisor_values.iter()) {
+                        result += column[row].mul_base(values.get_inv_value(step));
+                    }
+                    *combined_value = result;
+                }
+            }
+        );
+
+        // in debug mode, keep evaluations of all columns to validate their degrees later
+        #[cfg(debug_assertions)]
+        for (column, tile_column) in self.evaluations.iter_mut().zip(self.tile.iter()) {
+            column[tile_offset..tile_offset + tile_column.len()].copy_from_slice(tile_column);
+        }
+    }
+
     // CONSTRAINT COMPOSITION
     // --------------------------------------------------------------------------------------------
-    /// Divides constraint evaluation columns by their respective divisor (in evaluation form),
-    /// combines the results into a single column, and interpolates this column into a composition
-    /// polynomial in coefficient form.
-    pub fn into_poly(self) -> Result<CompositionPoly<E>, ProverError> {
-        // allocate memory for the combined polynomial
-        let mut combined_poly = E::zeroed_vector(self.num_rows());
-
-        // iterate over all columns of the constraint evaluation table, divide each column
-        // by the evaluations of its corresponding divisor, and add all resulting evaluations
-        // together into a single vector
-        for (column, divisor) in self.evaluations.into_iter().zip(self.divisors.iter()) {
-            // in debug mode, make sure post-division degree of each column matches the expected
-            // degree
-            #[cfg(debug_assertions)]
-            validate_column_degree(&column, divisor, self.domain, column.len() - 1)?;
-
-            // divide the column by the divisor and accumulate the result into combined_poly
-            acc_column(column, divisor, self.domain, &mut combined_poly);
+    /// Interpolates the combined evaluations of all constraint columns (each divided by its
+    /// divisor) into a composition polynomial in coefficient form.
+    ///
+    /// The composition polynomial is expected to have degree `num_segments` * `trace_length` - 1,
+    /// and is split into `num_segments` column polynomials. When trace polynomials are larger
+    /// than the trace (i.e., in zero-knowledge mode), the columns are randomized to the size of
+    /// trace polynomials, and a random column is appended to them (see
+    /// [CompositionPoly::new_randomized()]).
+    ///
+    /// The combined polynomial is interpolated using the specified FFT backend.
+    pub fn into_poly<D: FftBackend<E::BaseField>>(
+        self,
+        num_segments: usize,
+        backend: &D,
+    ) -> Result<CompositionPoly<E>, ProverError> {
+        let trace_length = self.domain.trace_length();
+        let composition_degree = num_segments * trace_length - 1;
+
+        // in debug mode, make sure post-division degree of each column matches the expected
+        // degree
+        #[cfg(debug_assertions)]
+        for (column, divisor) in self.evaluations.iter().zip(self.divisors.iter()) {
+            validate_column_degree(column, divisor, self.domain, composition_degree)?;
         }
 
-        // at this point, combined_poly contains evaluations of the combined constraint polynomial;
-        // we interpolate this polynomial to transform it into coefficient form.
+        // combined_evaluations contains evaluations of the combined constraint polynomial; we
+        // interpolate this polynomial to transform it into coefficient form.
+        let mut combined_poly = self.combined_evaluations;
         let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(combined_poly.len());
-        fft::interpolate_poly_with_offset(&mut combined_poly, &inv_twiddles, self.domain.offset());
+        backend.interpolate_poly_with_offset(
+            &mut combined_poly,
+            &inv_twiddles,
+            self.domain.offset(),
+        );
 
-        let trace_length = self.domain.trace_length();
-        Ok(CompositionPoly::new(combined_poly, trace_length))
+        // coefficients beyond the composition degree are all zeros; thus, they can be dropped
+        // before the polynomial is split into columns
+        combined_poly.truncate(composition_degree + 1);
+        let trace_poly_size = self.domain.trace_poly_size();
+        if trace_poly_size == trace_length {
+            Ok(CompositionPoly::new(combined_poly, trace_length))
+        } else {
+            Ok(CompositionPoly::new_randomized(
+                combined_poly,
+                trace_length,
+                trace_poly_size,
+            ))
+        }
     }
 
     // DEBUG HELPERS
     // --------------------------------------------------------------------------------------------
 
+    /// Makes sure that the actual degrees of all transition constraints match the degrees
+    /// declared for them by the AIR.
+    ///
+    /// The actual degree of each constraint is measured by interpolating the evaluations of the
+    /// constraint (divided by the constraint's divisor). Declared and actual degrees of all
+    /// constraints are logged as a table at the `debug` level.
+    ///
+    /// # Errors
+    /// Returns an error naming the first constraint for which the actual degree differs from the
+    /// declared degree.
+    ///
+    /// # Panics
+    /// Panics if the size of the constraint evaluation domain is not the one required by the
+    /// largest transition constraint degree.
     #[cfg(debug_assertions)]
-    pub fn validate_transition_degrees(&mut self) {
-        // evaluate transition constraint divisor (which is assumed to be the first one in the
-        // divisor list) over the constraint evaluation domain. this is used later to compute
-        // actual degrees of transition constraint evaluations.
-        let div_values = evaluate_divisor::<E::BaseField>(
-            &self.divisors[0],
-            self.num_rows(),
-            self.domain.offset(),
-        );
+    pub fn validate_transition_degrees(&mut self) -> Result<(), ProverError> {
+        // evaluate all distinct transition constraint divisors over the constraint evaluation
+        // domain. these are used later to compute actual degrees of transition constraint
+        // evaluations; constraints with different number of exemptions have different divisors.
+        let mut divisors: Vec<&ConstraintDivisor<E::BaseField>> = Vec::new();
+        for divisor in self.transition_divisors.iter() {
+            if !divisors.contains(&divisor) {
+                divisors.push(divisor);
+            }
+        }
+        let inv_div_values = divisors
+            .into_iter()
+            .map(|divisor| {
+                let values = evaluate_inv_divisor::<E::BaseField>(
+                    divisor,
+                    self.num_rows(),
+                    self.domain.offset(),
+                );
+                (divisor, values)
+            })
+            .collect::<Vec<_>>();
+        let get_inv_div_values = |constraint_idx: usize| {
+            let divisor = &self.transition_divisors[constraint_idx];
+            inv_div_values
+                .iter()
+                .find(|(d, _)| *d == divisor)
+                .map(|(_, values)| values.as_slice())
+                .expect("divisor values not found")
+        };
 
         // collect actual degrees for all transition constraints by interpolating saved
         // constraint evaluations into polynomials and checking their degree; also
@@ -205,29 +360,52 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
         let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(self.num_rows());
 
         // first process transition constraint evaluations for the main trace segment
-        for evaluations in self.main_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
+        for (i, evaluations) in self.main_transition_evaluations.iter().enumerate() {
+            let inv_div_values = get_inv_div_values(i);
+            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, inv_div_values);
             actual_degrees.push(degree);
             max_degree = core::cmp::max(max_degree, degree);
         }
 
         // then process transition constraint evaluations for auxiliary trace segments
-        for evaluations in self.aux_transition_evaluations.iter() {
-            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, &div_values);
+        let num_main_constraints = self.main_transition_evaluations.len();
+        for (i, evaluations) in self.aux_transition_evaluations.iter().enumerate() {
+            let inv_div_values = get_inv_div_values(num_main_constraints + i);
+            let degree = get_transition_poly_degree(evaluations, &inv_twiddles, inv_div_values);
             actual_degrees.push(degree);
             max_degree = core::cmp::max(max_degree, degree);
         }
 
-        // make sure expected and actual degrees are equal
-        assert_eq!(
-            self.expected_transition_degrees, actual_degrees,
-            "transition constraint degrees didn't match\nexpected: {:>3?}\nactual:   {:>3?}",
-            self.expected_transition_degrees, actual_degrees
-        );
+        // log declared and actual degrees of all constraints, and make sure they are equal
+        debug!("transition constraint | declared degree | actual degree");
+        for (i, (&declared, &actual)) in self
+            .expected_transition_degrees
+            .iter()
+            .zip(actual_degrees.iter())
+            .enumerate()
+        {
+            let marker = if declared != actual {
+                " <- mismatch"
+            } else {
+                ""
+            };
+            debug!("{i:>21} | {declared:>15} | {actual:>13}{marker}");
+        }
+        if let Some((i, (&declared, &actual))) = self
+            .expected_transition_degrees
+            .iter()
+            .zip(actual_degrees.iter())
+            .enumerate()
+            .find(|(_, (declared, actual))| declared != actual)
+        {
+            return Err(ProverError::MismatchedTransitionConstraintDegree(
+                i, declared, actual,
+            ));
+        }
 
         // make sure evaluation domain size does not exceed the size required by max degree
         let expected_domain_size =
-            core::cmp::max(max_degree, self.domain.trace_length() + 1).next_power_of_two();
+            core::cmp::max(max_degree + 1, self.domain.trace_poly_size() + 1).next_power_of_two();
         assert_eq!(
             expected_domain_size,
             self.num_rows(),
@@ -235,6 +413,8 @@ impl<'a, E: FieldElement> ConstraintEvaluationTable<'a, E> {
             expected_domain_size,
             self.num_rows()
         );
+
+        Ok(())
     }
 }
 
@@ -294,26 +474,27 @@ impl<'a, E: FieldElement> EvaluationTableFragment<'a, E> {
 // HELPER FUNCTIONS
 // ================================================================================================
 
-/// Allocates memory for a two-dimensional data structure without initializing it.
-fn uninit_matrix<E: FieldElement>(num_cols: usize, num_rows: usize) -> Vec<Vec<E>> {
-    unsafe { (0..num_cols).map(|_| uninit_vector(num_rows)).collect() }
+/// Allocates memory for a two-dimensional data structure filled with ZERO elements.
+fn zeroed_matrix<E: FieldElement>(num_cols: usize, num_rows: usize) -> Vec<Vec<E>> {
+    (0..num_cols).map(|_| E::zeroed_vector(num_rows)).collect()
 }
 
-/// Breaks the source data into a mutable set of fragments such that each fragment has the same
-/// number of columns as the source data, and the number of rows equal to `num_fragments`
-/// parameter.
+/// Breaks the specified rows of the source data into a mutable set of fragments such that each
+/// fragment has the same number of columns as the source data, and the number of fragments is
+/// equal to `num_fragments` parameter.
 ///
 /// If the source data is empty, the returned vector will contain number of empty vectors equal
 /// to `num_fragments` parameter.
 fn make_fragments<E: FieldElement>(
     source: &mut [Vec<E>],
+    rows: Range<usize>,
     num_fragments: usize,
 ) -> Vec<Vec<&mut [E]>> {
     let mut result = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
     if !source.is_empty() {
-        let fragment_size = source[0].len() / num_fragments;
+        let fragment_size = rows.len() / num_fragments;
         source.iter_mut().for_each(|column| {
-            for (i, fragment) in column.chunks_mut(fragment_size).enumerate() {
+            for (i, fragment) in column[rows.clone()].chunks_mut(fragment_size).enumerate() {
                 result[i].push(fragment);
             }
         });
@@ -321,57 +502,167 @@ fn make_fragments<E: FieldElement>(
     result
 }
 
-#[allow(clippy::many_single_char_names)]
-fn acc_column<E: FieldElement>(
-    column: Vec<E>,
-    divisor: &ConstraintDivisor<E::BaseField>,
-    domain: &StarkDomain<E::BaseField>,
-    result: &mut [E],
-) {
-    let numerator = divisor.numerator();
-    assert_eq!(numerator.len(), 1, "complex divisors are not yet supported");
-
-    // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b)
-    let z = get_inv_evaluation(divisor, domain);
-
-    // divide column values by the divisor; for boundary constraints this computed simply as
-    // multiplication of column value by the inverse of divisor numerator; for transition
-    // constraints, it is computed similarly, but the result is also multiplied by the divisor's
-    // denominator (exclusion point).
-    if divisor.exemptions().is_empty() {
-        // the column represents merged evaluations of boundary constraints, and divisor has the
-        // form of (x^a - b); thus to divide the column by the divisor, we compute: value * z,
-        // where z = 1 / (x^a - 1) and has already been computed above.
-        iter_mut!(result, 1024)
-            .zip(column)
-            .enumerate()
-            .for_each(|(i, (acc_value, value))| {
-                // determine which value of z corresponds to the current domain point
-                let z = z[i % z.len()];
-                // compute value * z and add it to the result
-                *acc_value += value.mul_base(z);
-            });
-    } else {
-        // the column represents merged evaluations of transition constraints, and divisor has the
-        // form of (x^a - 1) / e(x), where e(x) describes the exemption points; thus, to divide
-        // the column by the divisor, we compute: value * e(x) * z, where z = 1 / (x^a - 1) and has
-        // already been computed above.
-        batch_iter_mut!(
-            result,
-            128, // min batch size
-            |batch: &mut [E], batch_offset: usize| {
-                for (i, acc_value) in batch.iter_mut().enumerate() {
-                    // compute value of e(x) and compute next value of x
-                    let x = domain.get_ce_x_at(batch_offset + i);
-                    let e = divisor.evaluate_exemptions_at(x);
-                    // determine which value of z corresponds to the current domain point
-                    let z = z[i % z.len()];
-                    // compute value * e(x) * z and add it to the result
-                    *acc_value += column[batch_offset + i].mul_base(z * e);
-                }
-            }
-        );
+/// Values needed to divide a column of constraint evaluations by the column's divisor.
+///
+/// The values are computed once for the entire constraint evaluation domain, and are then used
+/// to divide evaluations of the column tile by tile.
+enum DivisorValues<B: StarkField> {
+    /// The column represents merged evaluations of boundary constraints, and the divisor has the
+    /// form of (x^a - b); the values are evaluations of 1 / (x^a - b), which repeat every
+    /// `values.len()` steps of the domain.
+    Numerator(Vec<B>),
+    /// The column represents merged evaluations of transition constraints, and the divisor has
+    /// the form of (x^a - b) / e(x), where e(x) describes the exemption points; the values are
+    /// evaluations of 1 / (x^a - b) (repeating as above), and evaluations of e(x) over the entire
+    /// domain. evaluations of e(x) are pre-computed so that the cost of dividing the column does
+    /// not depend on the number of exemption points.
+    Exempted(Vec<B>, Vec<B>),
+    /// The column represents merged evaluations of transition constraints which must hold on an
+    /// interval of steps; the values are evaluations of 1 / z(x) over the entire domain, where
+    /// z(x) is the divisor.
+    Interval(Vec<B>),
+}
+
+impl<B: StarkField> DivisorValues<B> {
+    /// Computes values needed to divide evaluations of constraints by the specified divisor over
+    /// the constraint evaluation domain.
+    fn new<D: FftBackend<B>>(
+        divisor: &ConstraintDivisor<B>,
+        domain: &StarkDomain<B>,
+        backend: &D,
+    ) -> Self {
+        if let Some(interval) = divisor.interval() {
+            assert!(
+                divisor.numerator().is_empty() && divisor.exemptions().is_empty(),
+                "complex divisors are not yet supported"
+            );
+            let mut z = evaluate_interval(interval, domain, backend);
+            batch_inversion_in_place(&mut z);
+            return Self::Interval(z);
+        }
+
+        let numerator = divisor.numerator();
+        assert_eq!(numerator.len(), 1, "complex divisors are not yet supported");
+
+        // compute inverse evaluations of the divisor's numerator, which has the form (x^a - b)
+        let z = get_inv_evaluation(divisor, domain);
+        if divisor.exemptions().is_empty() {
+            Self::Numerator(z)
+        } else {
+            let e = evaluate_exemptions(divisor.exemptions(), domain, backend);
+            Self::Exempted(z, e)
+        }
     }
+
+    /// Returns the value by which an evaluation of a constraint at the specified step of the
+    /// constraint evaluation domain needs to be multiplied to divide it by the divisor.
+    #[inline(always)]
+    fn get_inv_value(&self, step: usize) -> B {
+        match self {
+            Self::Numerator(z) => z[step % z.len()],
+            Self::Exempted(z, e) => z[step % z.len()] * e[step],
+            Self::Interval(z) => z[step],
+        }
+    }
+}
+
+/// Evaluates the polynomial $\prod_{i}(x - e_i)$, where $e_i$ are the specified exemption
+/// points, over the constraint evaluation domain.
+///
+/// The polynomial is built from its roots and is then evaluated over the domain using the
+/// specified FFT backend; this requires that the number of exemption points is smaller than the
+/// length of the execution trace.
+pub(super) fn evaluate_exemptions<B: StarkField, D: FftBackend<B>>(
+    points: &[B],
+    domain: &StarkDomain<B>,
+    backend: &D,
+) -> Vec<B> {
+    let trace_length = domain.trace_length();
+    debug_assert!(
+        points.len() < trace_length,
+        "number of exemption points must be smaller than {trace_length}"
+    );
+
+    // build the polynomial from its roots and pad it with zeros to the length of the trace
+    let mut poly = vec![B::ONE];
+    for &point in points {
+        poly = polynom::mul(&poly, &[-point, B::ONE]);
+    }
+    poly.resize(trace_length, B::ZERO);
+
+    // evaluate the polynomial over the constraint evaluation domain
+    backend.evaluate_poly_with_offset(
+        &poly,
+        domain.trace_twiddles(),
+        domain.offset(),
+        domain.trace_to_ce_blowup(),
+    )
+}
+
+/// Evaluates the polynomial $\prod_{j=0}^{m-1}(x - s \cdot g^j)$ described by the specified
+/// divisor interval $(m, s, g)$ over the constraint evaluation domain.
+///
+/// The polynomial is computed as $s^m \cdot p(x / s)$, where $p(y) = \prod_{j=0}^{m-1}(y - g^j)$.
+/// Coefficients of $p(y)$ are built by repeated doubling of the interval as
+/// $p_{2k}(y) = g^{k^2} \cdot p_k(y) \cdot p_k(y / g^k)$, where polynomials are multiplied using
+/// FFT; thus, building the polynomial requires $O(m \log m)$ operations. The polynomial is then
+/// evaluated over the domain using the specified FFT backend; this requires that $m$ is a power
+/// of two smaller than the length of the execution trace.
+pub(super) fn evaluate_interval<B: StarkField, D: FftBackend<B>>(
+    interval: (usize, B, B),
+    domain: &StarkDomain<B>,
+    backend: &D,
+) -> Vec<B> {
+    let (length, shift, root) = interval;
+    let trace_length = domain.trace_length();
+    debug_assert!(
+        length.is_power_of_two() && length < trace_length,
+        "interval length must be a power of two smaller than {trace_length}"
+    );
+
+    // build p(y) = (y - 1) * (y - g) * ... * (y - g^(m - 1)) by doubling the number of roots
+    let mut poly = vec![-B::ONE, B::ONE];
+    let mut k = 1;
+    while k < length {
+        // p_k(y / g^k) has coefficients c_i / g^(k * i)
+        let root_k = root.exp((k as u64).into());
+        let shifted = poly
+            .iter()
+            .zip(get_power_series(root_k.inv(), k + 1))
+            .map(|(&c, s)| c * s)
+            .collect::<Vec<_>>();
+
+        // multiply the polynomials by evaluating them over a domain of size 4k
+        let twiddles = fft::get_twiddles::<B>(2 * k);
+        let mut lhs = poly;
+        lhs.resize(2 * k, B::ZERO);
+        let mut rhs = shifted;
+        rhs.resize(2 * k, B::ZERO);
+        let mut product = backend.evaluate_poly_with_offset(&lhs, &twiddles, B::ONE, 2);
+        let rhs = backend.evaluate_poly_with_offset(&rhs, &twiddles, B::ONE, 2);
+        product.iter_mut().zip(rhs).for_each(|(a, b)| *a *= b);
+        backend.interpolate_poly(&mut product, &fft::get_inv_twiddles::<B>(4 * k));
+
+        // the product has degree 2k; scale it by g^(k^2)
+        product.truncate(2 * k + 1);
+        let scale = root_k.exp((k as u64).into());
+        product.iter_mut().for_each(|c| *c *= scale);
+
+        poly = product;
+        k *= 2;
+    }
+
+    // evaluate s^m * p(x / s) over the constraint evaluation domain
+    poly.resize(trace_length, B::ZERO);
+    let mut evaluations = backend.evaluate_poly_with_offset(
+        &poly,
+        domain.trace_twiddles(),
+        domain.offset() / shift,
+        domain.trace_to_ce_blowup(),
+    );
+    let scale = shift.exp((length as u64).into());
+    iter_mut!(evaluations, 1024).for_each(|value| *value *= scale);
+    evaluations
 }
 
 /// Computes evaluations of the divisor's numerator over the domain of the specified size and offset.
@@ -397,7 +688,7 @@ fn get_inv_evaluation<B: StarkField>(
     let domain_offset_exp = domain.offset().exp(a.into());
 
     // compute x^a - b for all x
-    let mut evaluations = unsafe { uninit_vector(n) };
+    let mut evaluations = B::zeroed_vector(n);
     batch_iter_mut!(
         &mut evaluations,
         128, // min batch size
@@ -410,7 +701,8 @@ fn get_inv_evaluation<B: StarkField>(
     );
 
     // compute 1 / (x^a - b)
-    batch_inversion(&evaluations)
+    batch_inversion_in_place(&mut evaluations);
+    evaluations
 }
 
 // DEBUG HELPERS
@@ -420,34 +712,68 @@ fn get_inv_evaluation<B: StarkField>(
 ///
 /// An evaluation degree is defined as degree of transition constraints in the context of a given
 /// execution trace accounting for constraint divisor degree. For most constraints, this degree is
-/// computed as `([trace_length - 1] * [constraint degree]) - [divisor degree]`. However, for
-/// constraints which rely on periodic columns this computation is slightly more complex.
+/// computed as `([trace_poly_degree] * [constraint degree]) - [divisor degree]`, where
+/// `trace_poly_degree` is `trace_length - 1` unless trace polynomials are randomized in
+/// zero-knowledge mode. However, for constraints which rely on periodic columns this
+/// computation is slightly more complex.
 ///
 /// The general idea is that evaluation degree is the degree of rational function `C(x) / z(x)`,
 /// where `C(x)` is the constraint polynomial and `z(x)` is the divisor polynomial.
 #[cfg(debug_assertions)]
 fn build_transition_constraint_degrees<E: FieldElement>(
     constraints: &TransitionConstraints<E>,
+    divisors: &[ConstraintDivisor<E::BaseField>],
     trace_length: usize,
+    trace_poly_degree: usize,
 ) -> Vec<usize> {
-    let mut result = Vec::new();
+    constraints
+        .main_constraint_degrees()
+        .iter()
+        .chain(constraints.aux_constraint_degrees().iter())
+        .zip(divisors)
+        .map(|(degree, divisor)| {
+            degree.get_randomized_evaluation_degree(trace_length, trace_poly_degree)
+                - divisor.degree()
+        })
+        .collect()
+}
 
-    for degree in constraints.main_constraint_degrees() {
-        result.push(degree.get_evaluation_degree(trace_length) - constraints.divisor().degree())
+/// Returns divisors of all transition constraints.
+///
+/// Divisors for constraints against the main trace segment come first, followed by divisors for
+/// constraints against auxiliary trace segments. Every transition constraint belongs to exactly
+/// one constraint group, and thus, gets the divisor of this group.
+#[cfg(debug_assertions)]
+fn build_transition_constraint_divisors<E: FieldElement>(
+    constraints: &TransitionConstraints<E>,
+) -> Vec<ConstraintDivisor<E::BaseField>> {
+    let num_main_constraints = constraints.num_main_constraints();
+    let num_constraints = num_main_constraints + constraints.num_aux_constraints();
+    let mut result = vec![None; num_constraints];
+
+    for group in constraints.main_constraints() {
+        for &i in group.indexes() {
+            result[i] = Some(group.divisor().clone());
+        }
     }
 
-    for degree in constraints.aux_constraint_degrees() {
-        result.push(degree.get_evaluation_degree(trace_length) - constraints.divisor().degree())
+    for group in constraints.aux_constraints() {
+        for &i in group.indexes() {
+            result[num_main_constraints + i] = Some(group.divisor().clone());
+        }
     }
 
     result
+        .into_iter()
+        .map(|divisor| divisor.expect("transition constraint does not belong to any group"))
+        .collect()
 }
 
 /// Computes the actual degree of a transition polynomial described by the provided evaluations.
 ///
 /// The degree is computed as follows:
 /// - First, we divide the polynomial evaluations by the evaluations of transition constraint
-///   divisor (`div_values`). This is needed because it is possible for the numerator portions of
+///   divisor (by multiplying them by `inv_div_values`). This is needed because it is possible for the numerator portions of
 ///   transition constraints to have a degree which is larger than the size of the evaluation
 ///   domain (and thus, interpolating the numerator would yield an incorrect result). However,
 ///   once the divisor values are divided out, the degree of the resulting polynomial should be
@@ -458,12 +784,12 @@ fn build_transition_constraint_degrees<E: FieldElement>(
 fn get_transition_poly_degree<E: FieldElement>(
     evaluations: &[E],
     inv_twiddles: &[E::BaseField],
-    div_values: &[E::BaseField],
+    inv_div_values: &[E::BaseField],
 ) -> usize {
     let mut evaluations = evaluations
         .iter()
-        .zip(div_values)
-        .map(|(&c, &d)| c / E::from(d))
+        .zip(inv_div_values)
+        .map(|(&c, &d)| c.mul_base(d))
         .collect::<Vec<_>>();
     fft::interpolate_poly(&mut evaluations, inv_twiddles);
     math::polynom::degree_of(&evaluations)
@@ -477,14 +803,14 @@ fn validate_column_degree<B: StarkField, E: FieldElement<BaseField = B>>(
     domain: &StarkDomain<B>,
     expected_degree: usize,
 ) -> Result<(), ProverError> {
-    // build domain for divisor evaluation, and evaluate it over this domain
-    let div_values = evaluate_divisor(divisor, column.len(), domain.offset());
+    // build domain for divisor evaluation, and evaluate inverse of the divisor over this domain
+    let inv_div_values = evaluate_inv_divisor::<B>(divisor, column.len(), domain.offset());
 
     // divide column values by the divisor
     let mut evaluations = column
         .iter()
-        .zip(div_values)
-        .map(|(&c, d)| c / d)
+        .zip(inv_div_values)
+        .map(|(&c, d)| c.mul_base(d))
         .collect::<Vec<_>>();
 
     // interpolate evaluations into a polynomial in coefficient form
@@ -501,19 +827,29 @@ fn validate_column_degree<B: StarkField, E: FieldElement<BaseField = B>>(
     Ok(())
 }
 
-/// Evaluates constraint divisor over the specified domain. This is similar to [get_inv_evaluation]
-/// function above but uses a more straight-forward but less efficient evaluation methodology and
-/// also does not invert the results.
+/// Evaluates inverse of constraint divisor over the specified domain. This is similar to
+/// [get_inv_evaluation] function above but uses a more straight-forward but less efficient
+/// evaluation methodology.
+///
+/// Numerators of the divisor are inverted using batch inversion, and thus, only a single
+/// inversion is performed for the entire domain.
 #[cfg(debug_assertions)]
-fn evaluate_divisor<E: FieldElement>(
-    divisor: &ConstraintDivisor<E::BaseField>,
+fn evaluate_inv_divisor<B: StarkField>(
+    divisor: &ConstraintDivisor<B>,
     domain_size: usize,
-    domain_offset: E::BaseField,
-) -> Vec<E> {
-    let g = E::BaseField::get_root_of_unity(domain_size.trailing_zeros());
+    domain_offset: B,
+) -> Vec<B> {
+    let g = B::get_root_of_unity(domain_size.trailing_zeros());
     let domain = math::get_power_series_with_offset(g, domain_offset, domain_size);
-    domain
-        .into_iter()
-        .map(|x| E::from(divisor.evaluate_at(x)))
-        .collect()
+
+    let mut result = domain
+        .iter()
+        .map(|&x| divisor.evaluate_numerator_at(x))
+        .collect::<Vec<_>>();
+    batch_inversion_in_place(&mut result);
+
+    for (value, &x) in result.iter_mut().zip(domain.iter()) {
+        *value *= divisor.evaluate_exemptions_at(x);
+    }
+    result
 }
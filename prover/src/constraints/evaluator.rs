@@ -5,15 +5,17 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{
-    super::TraceLde, evaluation_table::EvaluationTableFragment, BoundaryConstraints,
-    ConstraintEvaluationTable, PeriodicValueTable, StarkDomain,
+    super::TraceLde,
+    evaluation_table::{evaluate_exemptions, EvaluationTableFragment, MIN_FRAGMENT_SIZE},
+    BoundaryConstraints, ConstraintEvaluationTable, FftBackend, PeriodicValueTable, ProverError,
+    StarkDomain,
 };
 use air::{
     Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame,
-    TransitionConstraints,
+    TransitionConstraintGroup, TransitionConstraints, TransitionEvaluator,
 };
 use math::FieldElement;
-use utils::iter_mut;
+use utils::{collections::Vec, iter_mut};
 
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
@@ -29,10 +31,13 @@ const MIN_CONCURRENT_DOMAIN_SIZE: usize = 8192;
 
 pub struct ConstraintEvaluator<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> {
     air: &'a A,
+    transition_evaluator: TransitionEvaluator<'a, A>,
     boundary_constraints: BoundaryConstraints<E>,
     transition_constraints: TransitionConstraints<E>,
     aux_rand_elements: AuxTraceRandElements<E>,
     periodic_values: PeriodicValueTable<E::BaseField>,
+    main_exemption_multipliers: Vec<Vec<E::BaseField>>,
+    aux_exemption_multipliers: Vec<Vec<E::BaseField>>,
 }
 
 impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<'a, A, E> {
@@ -40,10 +45,14 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
     // --------------------------------------------------------------------------------------------
     /// Returns a new evaluator which can be used to evaluate transition and boundary constraints
     /// over extended execution trace.
-    pub fn new(
+    ///
+    /// Periodic columns and large boundary constraint polynomials are evaluated over the
+    /// constraint evaluation domain using the specified FFT backend.
+    pub fn new<D: FftBackend<E::BaseField>>(
         air: &'a A,
         aux_rand_elements: AuxTraceRandElements<E>,
         composition_coefficients: ConstraintCompositionCoefficients<E>,
+        backend: &D,
     ) -> Self {
         // build transition constraint groups; these will be used to compose transition constraint
         // evaluations
@@ -51,19 +60,26 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
             air.get_transition_constraints(&composition_coefficients.transition);
 
         // build periodic value table
-        let periodic_values = PeriodicValueTable::new(air);
+        let periodic_values = PeriodicValueTable::new(air, backend);
 
         // build boundary constraint groups; these will be used to evaluate and compose boundary
         // constraint evaluations.
-        let boundary_constraints =
-            BoundaryConstraints::new(air, &aux_rand_elements, &composition_coefficients.boundary);
+        let boundary_constraints = BoundaryConstraints::new(
+            air,
+            &aux_rand_elements,
+            &composition_coefficients.boundary,
+            backend,
+        );
 
         ConstraintEvaluator {
             air,
+            transition_evaluator: TransitionEvaluator::new(air),
             boundary_constraints,
             transition_constraints,
             aux_rand_elements,
             periodic_values,
+            main_exemption_multipliers: Vec::new(),
+            aux_exemption_multipliers: Vec::new(),
         }
     }
 
@@ -72,64 +88,114 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
     /// Evaluates constraints against the provided extended execution trace. Constraints are
     /// evaluated over a constraint evaluation domain. This is an optimization because constraint
     /// evaluation domain can be many times smaller than the full LDE domain.
-    pub fn evaluate(
-        self,
+    ///
+    /// The constraint evaluation domain is evaluated in tiles of `tile_size` rows, or as a single
+    /// tile if `tile_size` is not specified (or is greater than the size of the domain). The
+    /// memory needed for constraint evaluations is proportional to the size of a tile, except for
+    /// a single column of combined evaluations which spans the entire domain.
+    ///
+    /// Exemption multipliers of transition constraint groups and values of constraint divisors
+    /// are evaluated using the specified FFT backend.
+    ///
+    /// # Errors
+    /// Returns an error if `tile_size` is not a power of two or is smaller than 16. In debug
+    /// mode, also returns an error if the actual degree of any of the transition constraints
+    /// differs from the degree declared for this constraint by the AIR.
+    pub fn evaluate<D: FftBackend<E::BaseField>>(
+        mut self,
         trace: &TraceLde<E>,
         domain: &'a StarkDomain<E::BaseField>,
-    ) -> ConstraintEvaluationTable<'a, E> {
+        tile_size: Option<usize>,
+        backend: &D,
+    ) -> Result<ConstraintEvaluationTable<'a, E>, ProverError> {
         assert_eq!(
             trace.trace_len(),
             domain.lde_domain_size(),
             "extended trace length is not consistent with evaluation domain"
         );
 
-        // build a list of constraint divisors; currently, all transition constraints have the same
-        // divisor which we put at the front of the list; boundary constraint divisors are appended
-        // after that
-        let mut divisors = vec![self.transition_constraints.divisor().clone()];
+        // build a list of constraint divisors; divisors shared by transition constraints are put
+        // at the front of the list; boundary constraint divisors are appended after that
+        let mut divisors = self.transition_constraints.divisors().to_vec();
         divisors.append(&mut self.boundary_constraints.get_divisors());
 
-        // allocate space for constraint evaluations; when we are in debug mode, we also allocate
-        // memory to hold all transition constraint evaluations (before they are merged into a
-        // single value) so that we can check their degrees later
+        // evaluate exemption multipliers of transition constraint groups over the constraint
+        // evaluation domain; this way, applying the multipliers during constraint evaluation
+        // does not depend on the number of extra exemption points of a group
+        self.main_exemption_multipliers = build_exemption_multipliers(
+            self.transition_constraints.main_constraints(),
+            domain,
+            backend,
+        );
+        self.aux_exemption_multipliers = build_exemption_multipliers(
+            self.transition_constraints.aux_constraints(),
+            domain,
+            backend,
+        );
+
+        // the domain is evaluated in tiles of the specified size; by default, the entire domain
+        // is evaluated as a single tile
+        let ce_domain_size = domain.ce_domain_size();
+        let tile_size = match tile_size {
+            Some(tile_size) if !tile_size.is_power_of_two() || tile_size < MIN_FRAGMENT_SIZE => {
+                return Err(ProverError::InvalidConstraintEvaluationTileSize(tile_size));
+            }
+            Some(tile_size) => tile_size.min(ce_domain_size),
+            None => ce_domain_size,
+        };
+
+        // allocate space for constraint evaluations over a single tile; when we are in debug
+        // mode, we also allocate memory to hold all transition constraint evaluations (before
+        // they are merged into a single value) so that we can check their degrees later
         #[cfg(not(debug_assertions))]
-        let mut evaluation_table = ConstraintEvaluationTable::<E>::new(domain, divisors);
-        #[cfg(debug_assertions)]
         let mut evaluation_table =
-            ConstraintEvaluationTable::<E>::new(domain, divisors, &self.transition_constraints);
+            ConstraintEvaluationTable::<E>::new(domain, divisors, tile_size, backend);
+        #[cfg(debug_assertions)]
+        let mut evaluation_table = ConstraintEvaluationTable::<E>::new(
+            domain,
+            divisors,
+            tile_size,
+            backend,
+            &self.transition_constraints,
+        );
 
-        // when `concurrent` feature is enabled, break the evaluation table into multiple fragments
-        // to evaluate them into multiple threads; unless the constraint evaluation domain is small,
-        // then don't bother with concurrent evaluation
+        // when `concurrent` feature is enabled, break each tile into multiple fragments to
+        // evaluate them into multiple threads; unless the tile is small, then don't bother with
+        // concurrent evaluation
 
         #[cfg(not(feature = "concurrent"))]
         let num_fragments = 1;
 
         #[cfg(feature = "concurrent")]
-        let num_fragments = if domain.ce_domain_size() >= MIN_CONCURRENT_DOMAIN_SIZE {
+        let num_fragments = if tile_size >= MIN_CONCURRENT_DOMAIN_SIZE {
             rayon::current_num_threads().next_power_of_two()
         } else {
             1
         };
 
-        // evaluate constraints for each fragment; if the trace consist of multiple segments
-        // we evaluate constraints for all segments. otherwise, we evaluate constraints only
-        // for the main segment.
-        let mut fragments = evaluation_table.fragments(num_fragments);
-        iter_mut!(fragments).for_each(|fragment| {
-            if self.air.trace_info().is_multi_segment() {
-                self.evaluate_fragment_full(trace, domain, fragment);
-            } else {
-                self.evaluate_fragment_main(trace, domain, fragment);
-            }
-        });
+        // evaluate constraints for each fragment of each tile; if the trace consist of multiple
+        // segments we evaluate constraints for all segments. otherwise, we evaluate constraints
+        // only for the main segment. once a tile is evaluated, its columns are divided by their
+        // divisors and added to the combined evaluations, and the tile is reused for the next
+        // range of rows.
+        for tile_offset in (0..ce_domain_size).step_by(tile_size) {
+            let mut fragments = evaluation_table.tile_fragments(tile_offset, num_fragments);
+            iter_mut!(fragments).for_each(|fragment| {
+                if self.air.trace_info().is_multi_segment() {
+                    self.evaluate_fragment_full(trace, domain, fragment);
+                } else {
+                    self.evaluate_fragment_main(trace, domain, fragment);
+                }
+            });
+            evaluation_table.accumulate_tile(tile_offset);
+        }
 
         // when in debug mode, make sure expected transition constraint degrees align with
         // actual degrees we got during constraint evaluation
         #[cfg(debug_assertions)]
-        evaluation_table.validate_transition_degrees();
+        evaluation_table.validate_transition_degrees()?;
 
-        evaluation_table
+        Ok(evaluation_table)
     }
 
     // EVALUATION HELPERS
@@ -145,10 +211,16 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         fragment: &mut EvaluationTableFragment<E>,
     ) {
         // initialize buffers to hold trace values and evaluation results at each step;
-        let mut main_frame = EvaluationFrame::new(trace.main_trace_width());
+        let frame_offsets = self.air.frame_offsets();
+        let mut main_frame =
+            EvaluationFrame::new_multi_row(frame_offsets.len(), trace.main_trace_width());
         let mut evaluations = vec![E::ZERO; fragment.num_columns()];
         let mut t_evaluations = vec![E::BaseField::ZERO; self.num_main_transition_constraints()];
 
+        // transition constraint evaluations merged by their divisors go into the first slots
+        // of the evaluations buffer
+        let num_t_columns = self.transition_constraints.divisors().len();
+
         // this will be used to convert steps in constraint evaluation domain to steps in
         // LDE domain
         let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
@@ -157,15 +229,20 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
             let step = i + fragment.offset();
 
             // update evaluation frame buffer with data from the execution trace; this will
-            // read current and next rows from the trace into the buffer; data in the trace
+            // read rows at all frame offsets from the trace into the buffer; data in the trace
             // table is extended over the LDE domain, so, we need to convert step in constraint
             // evaluation domain, into a step in LDE domain, in case these domains are different
-            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
+            trace.read_main_trace_frame_into(step << lde_shift, frame_offsets, &mut main_frame);
 
-            // evaluate transition constraints and save the merged result the first slot of the
-            // evaluations buffer
-            evaluations[0] =
-                self.evaluate_main_transition(&main_frame, domain, step, &mut t_evaluations);
+            // evaluate transition constraints and save the merged results into the first slots
+            // of the evaluations buffer
+            self.evaluate_main_transition(
+                &main_frame,
+                domain,
+                step,
+                &mut t_evaluations,
+                &mut evaluations[..num_t_columns],
+            );
 
             // when in debug mode, save transition constraint evaluations
             #[cfg(debug_assertions)]
@@ -178,7 +255,7 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
                 main_state,
                 domain,
                 step,
-                &mut evaluations[1..],
+                &mut evaluations[num_t_columns..],
             );
 
             // record the result in the evaluation table
@@ -197,12 +274,19 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         fragment: &mut EvaluationTableFragment<E>,
     ) {
         // initialize buffers to hold trace values and evaluation results at each step
-        let mut main_frame = EvaluationFrame::new(trace.main_trace_width());
-        let mut aux_frame = EvaluationFrame::new(trace.aux_trace_width());
+        let frame_offsets = self.air.frame_offsets();
+        let mut main_frame =
+            EvaluationFrame::new_multi_row(frame_offsets.len(), trace.main_trace_width());
+        let mut aux_frame =
+            EvaluationFrame::new_multi_row(frame_offsets.len(), trace.aux_trace_width());
         let mut tm_evaluations = vec![E::BaseField::ZERO; self.num_main_transition_constraints()];
         let mut ta_evaluations = vec![E::ZERO; self.num_aux_transition_constraints()];
         let mut evaluations = vec![E::ZERO; fragment.num_columns()];
 
+        // transition constraint evaluations merged by their divisors go into the first slots
+        // of the evaluations buffer
+        let num_t_columns = self.transition_constraints.divisors().len();
+
         // this will be used to convert steps in constraint evaluation domain to steps in
         // LDE domain
         let lde_shift = domain.ce_to_lde_blowup().trailing_zeros();
@@ -211,20 +295,26 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
             let step = i + fragment.offset();
 
             // read both the main and the auxiliary evaluation frames from the trace
-            trace.read_main_trace_frame_into(step << lde_shift, &mut main_frame);
-            trace.read_aux_trace_frame_into(step << lde_shift, &mut aux_frame);
-
-            // evaluate transition constraints and save the merged result the first slot of the
-            // evaluations buffer; we evaluate and compose constraints in the same function, we
-            // can just add up the results of evaluating main and auxiliary constraints.
-            evaluations[0] =
-                self.evaluate_main_transition(&main_frame, domain, step, &mut tm_evaluations);
-            evaluations[0] += self.evaluate_aux_transition(
+            trace.read_main_trace_frame_into(step << lde_shift, frame_offsets, &mut main_frame);
+            trace.read_aux_trace_frame_into(step << lde_shift, frame_offsets, &mut aux_frame);
+
+            // evaluate transition constraints and save the merged results into the first slots
+            // of the evaluations buffer; we evaluate and compose constraints in the same function,
+            // so we can just add up the results of evaluating main and auxiliary constraints.
+            self.evaluate_main_transition(
+                &main_frame,
+                domain,
+                step,
+                &mut tm_evaluations,
+                &mut evaluations[..num_t_columns],
+            );
+            self.evaluate_aux_transition(
                 &main_frame,
                 &aux_frame,
                 domain,
                 step,
                 &mut ta_evaluations,
+                &mut evaluations[..num_t_columns],
             );
 
             // when in debug mode, save transition constraint evaluations
@@ -240,7 +330,7 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
                 aux_state,
                 domain,
                 step,
-                &mut evaluations[1..],
+                &mut evaluations[num_t_columns..],
             );
 
             // record the result in the evaluation table
@@ -254,6 +344,9 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
     /// Evaluates transition constraints of the main execution trace at the specified step of the
     /// constraint evaluation domain.
     ///
+    /// The evaluations are merged by their shared divisors, and the merged values are written
+    /// into the `result` slice (one value per divisor shared by transition constraints).
+    ///
     /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
     /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
     #[rustfmt::skip]
@@ -263,29 +356,38 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         domain: &StarkDomain<A::BaseField>,
         step: usize,
         evaluations: &mut [E::BaseField],
-    ) -> E {
+        result: &mut [E],
+    ) {
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::BaseField::ZERO);
+        result.fill(E::ZERO);
 
         // get periodic values at the evaluation step
-        let periodic_values = self.periodic_values.get_row(step);
+        let periodic_values = self.periodic_values.get_main_row(step);
 
         // evaluate transition constraints over the main segment of the execution trace and save
         // the results into evaluations buffer
-        self.air.evaluate_transition(main_frame, periodic_values, evaluations);
+        self.transition_evaluator.evaluate_main(main_frame, periodic_values, evaluations);
 
-        // merge transition constraint evaluations into a single value and return it;
-        // we can do this here because all transition constraints have the same divisor.
-        self.transition_constraints.main_constraints().iter().fold(E::ZERO, |result, group| {
+        // merge transition constraint evaluations into a single value per shared divisor; we can
+        // do this here because constraints in each group share a divisor; groups with extra
+        // exemptions are adjusted by their exemption multipliers.
+        let groups = self.transition_constraints.main_constraints();
+        for (group, multipliers) in groups.iter().zip(self.main_exemption_multipliers.iter()) {
             let (power, offset_exp) = (group.degree_adjustment(), group.domain_offset_exp());
             let xp = domain.get_ce_x_power_at(step, power, offset_exp);
-            result + group.merge_evaluations(evaluations, xp)
-        })
+            let evaluation: E = group.merge_evaluations(evaluations, xp);
+            let evaluation = apply_exemption_multiplier(evaluation, multipliers, step);
+            result[group.divisor_idx()] += evaluation;
+        }
     }
 
     /// Evaluates all transition constraints (i.e., for main and auxiliary trace segments) at the
     /// specified step of the constraint evaluation domain.
     ///
+    /// The evaluations are merged by their shared divisors, and the merged values are added to
+    /// the values in the `result` slice (one value per divisor shared by transition constraints).
+    ///
     /// `x` is the corresponding domain value at the specified step. That is, x = s * g^step,
     /// where g is the generator of the constraint evaluation domain, and s is the domain offset.
     #[rustfmt::skip]
@@ -296,7 +398,8 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         domain: &StarkDomain<A::BaseField>,
         step: usize,
         evaluations: &mut [E],
-    ) -> E {
+        result: &mut [E],
+    ) {
         // TODO: use a more efficient way to zero out memory
         evaluations.fill(E::ZERO);
 
@@ -305,7 +408,7 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
 
         // evaluate transition constraints over auxiliary trace segments and save the results into
         // evaluations buffer
-        self.air.evaluate_aux_transition(
+        self.transition_evaluator.evaluate_aux(
             main_frame,
             aux_frame,
             periodic_values,
@@ -313,13 +416,17 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
             evaluations,
         );
 
-        // merge transition constraint evaluations into a single value and return it;
-        // we can do this here because all transition constraints have the same divisor.
-        self.transition_constraints.aux_constraints().iter().fold(E::ZERO, |result, group| {
+        // merge transition constraint evaluations into a single value per shared divisor; we can
+        // do this here because constraints in each group share a divisor; groups with extra
+        // exemptions are adjusted by their exemption multipliers.
+        let groups = self.transition_constraints.aux_constraints();
+        for (group, multipliers) in groups.iter().zip(self.aux_exemption_multipliers.iter()) {
             let (power, offset_exp) = (group.degree_adjustment(), group.domain_offset_exp());
             let xp = domain.get_ce_x_power_at(step, power, offset_exp);
-            result + group.merge_evaluations::<E::BaseField, E>(evaluations, xp)
-        })
+            let evaluation = group.merge_evaluations::<E::BaseField, E>(evaluations, xp);
+            let evaluation = apply_exemption_multiplier(evaluation, multipliers, step);
+            result[group.divisor_idx()] += evaluation;
+        }
     }
 
     // ACCESSORS
@@ -336,3 +443,47 @@ impl<'a, A: Air, E: FieldElement<BaseField = A::BaseField>> ConstraintEvaluator<
         self.transition_constraints.num_aux_constraints()
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Evaluates exemption multipliers of the specified transition constraint groups over the
+/// constraint evaluation domain.
+///
+/// For groups without extra exemptions (which is the most common case), an empty vector is
+/// returned.
+fn build_exemption_multipliers<E: FieldElement, D: FftBackend<E::BaseField>>(
+    groups: &[TransitionConstraintGroup<E>],
+    domain: &StarkDomain<E::BaseField>,
+    backend: &D,
+) -> Vec<Vec<E::BaseField>> {
+    groups
+        .iter()
+        .map(|group| {
+            if group.num_extra_exemptions() == 0 {
+                Vec::new()
+            } else {
+                evaluate_exemptions(group.extra_exemptions(), domain, backend)
+            }
+        })
+        .collect()
+}
+
+/// Multiplies the merged evaluation of a transition constraint group by the exemption multiplier
+/// of the group at the specified step of the constraint evaluation domain.
+///
+/// `multipliers` must contain evaluations of the exemption multiplier of the group over the
+/// constraint evaluation domain; for groups without extra exemptions, `multipliers` is empty and
+/// the evaluation is returned unchanged.
+#[inline(always)]
+fn apply_exemption_multiplier<E: FieldElement>(
+    evaluation: E,
+    multipliers: &[E::BaseField],
+    step: usize,
+) -> E {
+    if multipliers.is_empty() {
+        evaluation
+    } else {
+        evaluation.mul_base(multipliers[step])
+    }
+}
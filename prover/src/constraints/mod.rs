@@ -4,7 +4,7 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{ConstraintDivisor, Matrix, ProverError, StarkDomain};
+use super::{zk, ConstraintDivisor, FftBackend, Matrix, ProverError, StarkDomain};
 
 mod boundary;
 use boundary::BoundaryConstraints;
@@ -23,3 +23,6 @@ pub use evaluation_table::ConstraintEvaluationTable;
 
 mod commitment;
 pub use commitment::ConstraintCommitment;
+
+mod ood;
+pub use ood::record_ood_evaluations;
@@ -0,0 +1,165 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{
+    labels, Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame,
+    PeriodicColumnPolys, TranscriptEntryKind, TranscriptLog, TransitionEvaluator,
+};
+use math::FieldElement;
+use utils::collections::Vec;
+
+// OUT-OF-DOMAIN EVALUATION RECORDING
+// ================================================================================================
+
+/// Records intermediate values of the out-of-domain consistency check into the specified `log`.
+///
+/// Constraints are evaluated at the out-of-domain point `z` over the trace states sent to the
+/// verifier, and the values are recorded in the same order in which the verifier records them.
+/// The value of the constraint composition polynomial, however, is computed from the evaluations
+/// of its columns sent to the verifier rather than from the constraints; thus, this value differs
+/// from the one recorded by the verifier if the trace does not satisfy the constraints.
+#[allow(clippy::too_many_arguments)]
+pub fn record_ood_evaluations<A, E>(
+    air: &A,
+    composition_coefficients: ConstraintCompositionCoefficients<E>,
+    aux_rand_elements: &AuxTraceRandElements<E>,
+    ood_trace_states: &[Vec<E>],
+    ood_periodic_values: &[E],
+    ood_constraint_evaluations: &[E],
+    z: E,
+    log: &mut TranscriptLog,
+) where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+{
+    // split the trace states into the main and auxiliary frames; each trace state contains main
+    // trace values followed by auxiliary trace values
+    let main_trace_width = air.trace_layout().main_trace_width();
+    let main_frame = EvaluationFrame::from_multi_rows(
+        ood_trace_states
+            .iter()
+            .map(|state| state[..main_trace_width].to_vec())
+            .collect(),
+    );
+    let aux_frame = if air.trace_layout().num_aux_segments() > 0 {
+        Some(EvaluationFrame::from_multi_rows(
+            ood_trace_states
+                .iter()
+                .map(|state| state[main_trace_width..].to_vec())
+                .collect(),
+        ))
+    } else {
+        None
+    };
+
+    // 1 ----- evaluate transition constraints ----------------------------------------------------
+
+    // values of committed periodic columns have been sent to the verifier; all other periodic
+    // columns are evaluated at z directly
+    let trace_length = air.trace_length();
+    let mut periodic_values = if air.has_committed_periodic_columns() {
+        ood_periodic_values.to_vec()
+    } else {
+        PeriodicColumnPolys::new(air.get_periodic_column_polys(), trace_length).evaluate_at(z)
+    };
+    let num_main_periodic_columns = periodic_values.len();
+    periodic_values.append(
+        &mut PeriodicColumnPolys::new(air.get_aux_periodic_column_polys(), trace_length)
+            .evaluate_at(z),
+    );
+
+    let t_constraints = air.get_transition_constraints(&composition_coefficients.transition);
+    let mut t_evaluations1 = E::zeroed_vector(t_constraints.num_main_constraints());
+    let transition_evaluator = TransitionEvaluator::new(air);
+    transition_evaluator.evaluate_main(
+        &main_frame,
+        &periodic_values[..num_main_periodic_columns],
+        &mut t_evaluations1,
+    );
+    let mut t_evaluations2 = E::zeroed_vector(t_constraints.num_aux_constraints());
+    if let Some(aux_frame) = aux_frame.as_ref() {
+        transition_evaluator.evaluate_aux(
+            &main_frame,
+            aux_frame,
+            &periodic_values,
+            aux_rand_elements,
+            &mut t_evaluations2,
+        );
+    }
+    let t_divisors = t_constraints
+        .divisors()
+        .iter()
+        .map(|divisor| divisor.evaluate_at(z))
+        .collect::<Vec<E>>();
+    let t_combination = t_constraints.combine_evaluations::<E>(&t_evaluations1, &t_evaluations2, z);
+
+    // 2 ----- evaluate boundary constraints ------------------------------------------------------
+
+    let b_constraints =
+        air.get_boundary_constraints(aux_rand_elements, &composition_coefficients.boundary);
+    let b_shared_divisors = b_constraints.evaluate_divisors_at(z);
+    let mut b_evaluations = Vec::new();
+    let mut b_divisors = Vec::new();
+    for group in b_constraints.main_constraints().iter() {
+        let xp = z.exp(group.degree_adjustment().into());
+        let divisor = b_shared_divisors[group.divisor_idx()];
+        b_evaluations.push(group.evaluate_at(main_frame.current(), z, xp, divisor));
+        b_divisors.push(divisor);
+    }
+    if let Some(aux_frame) = aux_frame.as_ref() {
+        for group in b_constraints.aux_constraints().iter() {
+            let xp = z.exp(group.degree_adjustment().into());
+            let divisor = b_shared_divisors[group.divisor_idx()];
+            b_evaluations.push(group.evaluate_at(aux_frame.current(), z, xp, divisor));
+            b_divisors.push(divisor);
+        }
+    }
+
+    // 3 ----- evaluate constraint composition polynomial -----------------------------------------
+
+    // the columns of the composition polynomial are evaluated at z^m, where m is the number of
+    // columns; thus, the value of the polynomial at z is sum(z^i * value_i). in zero-knowledge
+    // mode, the columns are evaluated at z, the value of the polynomial is
+    // sum(z^(i * n) * value_i), where n is the trace length, and the last (random) column is
+    // not a part of the polynomial
+    let composition_value = if air.options().is_zero_knowledge() {
+        let z_n = z.exp((air.trace_length() as u64).into());
+        let num_segments = ood_constraint_evaluations.len() - 1;
+        ood_constraint_evaluations[..num_segments]
+            .iter()
+            .rev()
+            .fold(E::ZERO, |result, &value| result * z_n + value)
+    } else {
+        ood_constraint_evaluations
+            .iter()
+            .enumerate()
+            .fold(E::ZERO, |result, (i, &value)| {
+                result + z.exp((i as u32).into()) * value
+            })
+    };
+
+    // 4 ----- record the values ------------------------------------------------------------------
+
+    let kind = TranscriptEntryKind::Evaluation;
+    log.record(kind, labels::OOD_EVAL_PERIODIC_VALUES, &periodic_values);
+    log.record(kind, labels::OOD_EVAL_MAIN_TRANSITIONS, &t_evaluations1);
+    if aux_frame.is_some() {
+        log.record(kind, labels::OOD_EVAL_AUX_TRANSITIONS, &t_evaluations2);
+    }
+    log.record(kind, labels::OOD_EVAL_TRANSITION_DIVISORS, &t_divisors);
+    log.record(
+        kind,
+        labels::OOD_EVAL_TRANSITION_COMBINATION,
+        &[t_combination],
+    );
+    log.record(kind, labels::OOD_EVAL_BOUNDARY_DIVISORS, &b_divisors);
+    log.record(kind, labels::OOD_EVAL_BOUNDARY_GROUPS, &b_evaluations);
+    log.record(
+        kind,
+        labels::OOD_EVAL_CONSTRAINT_COMPOSITION,
+        &[composition_value],
+    );
+}
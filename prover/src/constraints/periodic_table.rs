@@ -4,17 +4,16 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
+use super::FftBackend;
 use air::Air;
 use math::{fft, StarkField};
-use utils::{
-    collections::{BTreeMap, Vec},
-    uninit_vector,
-};
+use utils::collections::{BTreeMap, Vec};
 
 pub struct PeriodicValueTable<B: StarkField> {
     values: Vec<B>,
     length: usize,
     width: usize,
+    num_main_columns: usize,
 }
 
 impl<B: StarkField> PeriodicValueTable<B> {
@@ -23,15 +22,26 @@ impl<B: StarkField> PeriodicValueTable<B> {
     /// Builds a table of periodic column values for the specified AIR. The table contains expanded
     /// values of all periodic columns normalized to the same length. This enables simple lookup
     /// into the able using step index of the constraint evaluation domain.
-    pub fn new<A: Air<BaseField = B>>(air: &A) -> PeriodicValueTable<B> {
+    ///
+    /// Columns used by the main transition constraints come first in each row, followed by
+    /// columns used only by the auxiliary transition constraints.
+    ///
+    /// Periodic column polynomials are evaluated using the specified FFT backend.
+    pub fn new<A: Air<BaseField = B>, D: FftBackend<B>>(
+        air: &A,
+        backend: &D,
+    ) -> PeriodicValueTable<B> {
         // get a list of polynomials describing periodic columns from AIR. if there are no
         // periodic columns return an empty table
-        let polys = air.get_periodic_column_polys();
+        let mut polys = air.get_periodic_column_polys();
+        let num_main_columns = polys.len();
+        polys.append(&mut air.get_aux_periodic_column_polys());
         if polys.is_empty() {
             return PeriodicValueTable {
                 values: Vec::new(),
                 length: 0,
                 width: 0,
+                num_main_columns: 0,
             };
         }
 
@@ -53,7 +63,7 @@ impl<B: StarkField> PeriodicValueTable<B> {
                     .entry(poly_size)
                     .or_insert_with(|| fft::get_twiddles(poly_size));
 
-                fft::evaluate_poly_with_offset(poly, twiddles, offset, air.ce_blowup_factor())
+                backend.evaluate_poly_with_offset(poly, twiddles, offset, air.ce_blowup_factor())
             })
             .collect::<Vec<_>>();
 
@@ -61,7 +71,7 @@ impl<B: StarkField> PeriodicValueTable<B> {
         // table in such a way that values for the same row are adjacent to each other.
         let row_width = polys.len();
         let column_length = max_poly_size * air.ce_blowup_factor();
-        let mut values = unsafe { uninit_vector(row_width * column_length) };
+        let mut values = B::zeroed_vector(row_width * column_length);
         for i in 0..column_length {
             for (j, column) in evaluations.iter().enumerate() {
                 values[i * row_width + j] = column[i % column.len()];
@@ -72,6 +82,7 @@ impl<B: StarkField> PeriodicValueTable<B> {
             values,
             length: column_length,
             width: row_width,
+            num_main_columns,
         }
     }
 
@@ -82,6 +93,8 @@ impl<B: StarkField> PeriodicValueTable<B> {
         self.width == 0
     }
 
+    /// Returns values of all periodic columns at the specified step of the constraint evaluation
+    /// domain.
     pub fn get_row(&self, ce_step: usize) -> &[B] {
         if self.is_empty() {
             &[]
@@ -90,6 +103,12 @@ impl<B: StarkField> PeriodicValueTable<B> {
             &self.values[start..start + self.width]
         }
     }
+
+    /// Returns values of periodic columns used by the main transition constraints at the
+    /// specified step of the constraint evaluation domain.
+    pub fn get_main_row(&self, ce_step: usize) -> &[B] {
+        &self.get_row(ce_step)[..self.num_main_columns]
+    }
 }
 
 // TESTS
@@ -97,7 +116,7 @@ impl<B: StarkField> PeriodicValueTable<B> {
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::MockAir;
+    use crate::{tests::MockAir, CpuBackend};
     use air::Air;
     use math::{
         fields::f128::BaseElement, get_power_series_with_offset, log2, polynom, FieldElement,
@@ -121,9 +140,10 @@ mod tests {
         let air = MockAir::with_periodic_columns(vec![col1, col2], trace_length);
 
         // build a table of periodic values
-        let table = super::PeriodicValueTable::new(&air);
+        let table = super::PeriodicValueTable::new(&air, &CpuBackend);
 
         assert_eq!(2, table.width);
+        assert_eq!(2, table.num_main_columns);
         assert_eq!(4 * air.ce_blowup_factor(), table.length);
 
         let polys = air.get_periodic_column_polys();
@@ -4,7 +4,9 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::Air;
+use super::ProverError;
+use air::{Air, ProofOptions};
+use core::any::type_name;
 use math::{fft, get_power_series, log2, StarkField};
 use utils::collections::Vec;
 
@@ -16,6 +18,11 @@ pub struct StarkDomain<B: StarkField> {
     /// vector is half the length of the trace domain size.
     trace_twiddles: Vec<B>,
 
+    /// Twiddles which can be used to evaluate trace polynomials when these polynomials are larger
+    /// than the trace domain (i.e., when they are randomized in zero-knowledge mode); `None`
+    /// when trace polynomials have the same size as the trace domain.
+    trace_poly_twiddles: Option<Vec<B>>,
+
     /// [g^i for i in (0..ce_domain_size)] where g is the constraint evaluation domain generator.
     ce_domain: Vec<B>,
 
@@ -29,6 +36,9 @@ pub struct StarkDomain<B: StarkField> {
 
     /// Offset of the low-degree extension domain.
     domain_offset: B,
+
+    /// Offset of the coset over which the execution trace is defined.
+    trace_offset: B,
 }
 
 // STARK DOMAIN IMPLEMENTATION
@@ -38,6 +48,12 @@ impl<B: StarkField> StarkDomain<B> {
     /// Returns a new STARK domain initialized with the provided `context`.
     pub fn new<A: Air<BaseField = B>>(air: &A) -> Self {
         let trace_twiddles = fft::get_twiddles(air.trace_length());
+        let trace_poly_size = air.trace_poly_degree() + 1;
+        let trace_poly_twiddles = if trace_poly_size > air.trace_length() {
+            Some(fft::get_twiddles(trace_poly_size))
+        } else {
+            None
+        };
 
         // build constraint evaluation domain
         let domain_gen = B::get_root_of_unity(log2(air.ce_domain_size()));
@@ -45,10 +61,12 @@ impl<B: StarkField> StarkDomain<B> {
 
         StarkDomain {
             trace_twiddles,
+            trace_poly_twiddles,
             ce_domain,
             ce_to_lde_blowup: air.lde_domain_size() / air.ce_domain_size(),
             ce_domain_mod_mask: air.ce_domain_size() - 1,
             domain_offset: air.domain_offset(),
+            trace_offset: air.trace_domain_offset(),
         }
     }
 
@@ -60,11 +78,31 @@ impl<B: StarkField> StarkDomain<B> {
         &self.trace_twiddles.len() * 2
     }
 
-    /// Returns twiddles which can be used to evaluate trace polynomials.
+    /// Returns twiddles which can be used to evaluate polynomials in the trace domain.
     pub fn trace_twiddles(&self) -> &[B] {
         &self.trace_twiddles
     }
 
+    /// Returns the number of coefficients of trace polynomials for this computation.
+    ///
+    /// This is equal to the trace length, unless trace polynomials are randomized in
+    /// zero-knowledge mode.
+    pub fn trace_poly_size(&self) -> usize {
+        self.trace_poly_twiddles().len() * 2
+    }
+
+    /// Returns twiddles which can be used to evaluate trace polynomials.
+    pub fn trace_poly_twiddles(&self) -> &[B] {
+        self.trace_poly_twiddles
+            .as_deref()
+            .unwrap_or(&self.trace_twiddles)
+    }
+
+    /// Returns the offset of the coset over which the execution trace is defined.
+    pub fn trace_offset(&self) -> B {
+        self.trace_offset
+    }
+
     /// Returns blowup factor from trace to constraint evaluation domain.
     pub fn trace_to_ce_blowup(&self) -> usize {
         self.ce_domain_size() / self.trace_length()
@@ -75,6 +113,12 @@ impl<B: StarkField> StarkDomain<B> {
         self.lde_domain_size() / self.trace_length()
     }
 
+    /// Returns blowup factor from trace polynomial size to LDE domain; this is the blowup factor
+    /// specified by the proof options.
+    pub fn trace_poly_to_lde_blowup(&self) -> usize {
+        self.lde_domain_size() / self.trace_poly_size()
+    }
+
     // CONSTRAINT EVALUATION DOMAIN
     // --------------------------------------------------------------------------------------------
 
@@ -130,3 +174,79 @@ impl<B: StarkField> StarkDomain<B> {
         self.domain_offset
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that the low-degree extension domain for an execution trace of the specified length
+/// fits into the largest multiplicative subgroup of the field `B`.
+///
+/// # Errors
+/// Returns an error if the trace polynomial size for `trace_length` (see
+/// [ProofOptions::trace_poly_size()]) multiplied by the blowup factor specified by `options` is
+/// greater than [MAX_DOMAIN_SIZE](StarkField::MAX_DOMAIN_SIZE) of `B`.
+pub fn validate_domain_size<B: StarkField>(
+    trace_length: usize,
+    options: &ProofOptions,
+) -> Result<(), ProverError> {
+    let lde_domain_size = options
+        .trace_poly_size(trace_length)
+        .saturating_mul(options.blowup_factor());
+    if lde_domain_size > B::MAX_DOMAIN_SIZE {
+        return Err(ProverError::DomainTooLarge {
+            requested: lde_domain_size,
+            max_supported: B::MAX_DOMAIN_SIZE,
+            field: type_name::<B>(),
+        });
+    }
+    Ok(())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_domain_size, ProverError};
+    use air::{FieldExtension, ProofOptions};
+    use math::{
+        fields::{f128, f62, f64},
+        StarkField,
+    };
+
+    #[test]
+    fn domain_size_limits() {
+        check_domain_size_limit::<f62::BaseElement>();
+        check_domain_size_limit::<f64::BaseElement>();
+        check_domain_size_limit::<f128::BaseElement>();
+    }
+
+    fn check_domain_size_limit<B: StarkField>() {
+        for blowup_factor in [2, 8, 16] {
+            let options = ProofOptions::new(28, blowup_factor, 0, FieldExtension::None, 4, 256);
+            let max_trace_length = options.max_trace_length::<B>();
+            assert_eq!(B::MAX_DOMAIN_SIZE, max_trace_length * blowup_factor);
+
+            // in zero-knowledge mode, the largest trace is half as long, and a longer trace is
+            // rejected because its trace polynomials are larger than the trace
+            let zk_options = options.clone().with_zero_knowledge();
+            assert_eq!(max_trace_length / 2, zk_options.max_trace_length::<B>());
+            assert!(validate_domain_size::<B>(max_trace_length / 2, &zk_options).is_ok());
+            assert!(validate_domain_size::<B>(max_trace_length, &zk_options).is_err());
+
+            // exactly at the limit
+            assert!(validate_domain_size::<B>(max_trace_length, &options).is_ok());
+
+            // just above the limit
+            let err = validate_domain_size::<B>(max_trace_length * 2, &options).unwrap_err();
+            assert_eq!(
+                ProverError::DomainTooLarge {
+                    requested: B::MAX_DOMAIN_SIZE * 2,
+                    max_supported: B::MAX_DOMAIN_SIZE,
+                    field: core::any::type_name::<B>(),
+                },
+                err
+            );
+        }
+    }
+}
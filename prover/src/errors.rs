@@ -6,6 +6,7 @@
 
 //! Contains common error types for prover and verifier.
 
+use air::{proof::BatchProof, ProofOptionsError};
 use core::fmt;
 
 // PROVER ERROR
@@ -13,30 +14,223 @@ use core::fmt;
 /// Represents an error returned by the prover during an execution of the protocol.
 #[derive(Debug, PartialEq, Eq)]
 pub enum ProverError {
+    /// This error occurs when the execution trace has fewer rows than the minimum trace length
+    /// (see [TraceInfo::MIN_TRACE_LENGTH](air::TraceInfo::MIN_TRACE_LENGTH)).
+    TraceTooShort {
+        /// Number of rows in the execution trace.
+        length: usize,
+        /// Minimum number of rows in an execution trace.
+        min_length: usize,
+    },
+    /// This error occurs when the number of rows in the execution trace is not a power of two.
+    TraceLengthNotPowerOfTwo(usize),
+    /// This error occurs when the number of rows in the execution trace (or in one of its
+    /// segments) is different from the trace length expected by the AIR.
+    TraceLengthMismatch {
+        /// Trace length expected by the AIR.
+        expected: usize,
+        /// Number of rows in the execution trace or trace segment.
+        actual: usize,
+    },
+    /// This error occurs when the number of columns in the main segment of the execution trace
+    /// is different from the width of the main segment expected by the AIR.
+    TraceWidthMismatch {
+        /// Width of the main trace segment expected by the AIR.
+        expected: usize,
+        /// Number of columns in the main segment of the execution trace.
+        actual: usize,
+    },
+    /// This error occurs when the number of columns in an auxiliary segment of the execution
+    /// trace is different from the width of this segment expected by the AIR.
+    AuxSegmentWidthMismatch {
+        /// Index of the auxiliary trace segment.
+        segment: usize,
+        /// Width of the auxiliary trace segment expected by the AIR.
+        expected: usize,
+        /// Number of columns in the auxiliary trace segment.
+        actual: usize,
+    },
+    /// This error occurs when the number of columns built in a round of a multi-round auxiliary
+    /// trace segment is different from the width of this round expected by the AIR.
+    AuxSegmentRoundWidthMismatch {
+        /// Index of the auxiliary trace segment.
+        segment: usize,
+        /// Index of the round within the auxiliary trace segment.
+        round: usize,
+        /// Width of the round expected by the AIR.
+        expected: usize,
+        /// Number of columns built in the round.
+        actual: usize,
+    },
+    /// This error occurs when the AIR expects an auxiliary trace segment with the specified
+    /// index, but the execution trace does not describe or does not build this segment.
+    MissingAuxSegment(usize),
+    /// This error occurs when the AIR expects an auxiliary trace segment to be built in more than
+    /// one round, but the execution trace does not build the specified round of this segment.
+    MissingAuxSegmentRound {
+        /// Index of the auxiliary trace segment.
+        segment: usize,
+        /// Index of the round within the auxiliary trace segment.
+        round: usize,
+    },
+    /// This error occurs when the execution trace describes an auxiliary trace segment with the
+    /// specified index, but the AIR does not expect this segment.
+    UnexpectedAuxSegment(usize),
     /// This error occurs when a transition constraint evaluated over a specific execution trace
     /// does not evaluate to zero at any of the steps.
     UnsatisfiedTransitionConstraintError(usize),
     /// This error occurs when polynomials built from the columns of a constraint evaluation
     /// table do not all have the same degree.
     MismatchedConstraintPolynomialDegree(usize, usize),
+    /// This error occurs when the degree of a transition constraint evaluated over a specific
+    /// execution trace differs from the degree implied by the declared
+    /// [TransitionConstraintDegree](air::TransitionConstraintDegree) of the constraint. The
+    /// values are the index of the constraint (constraints against auxiliary trace segments
+    /// follow constraints against the main segment), the degree implied by the declaration, and
+    /// the actual degree. This is checked in debug mode only.
+    MismatchedTransitionConstraintDegree(usize, usize, usize),
+    /// This error occurs when the low-degree extension domain implied by the length of the
+    /// execution trace and the blowup factor is larger than the largest multiplicative subgroup
+    /// of the base field specified by the AIR.
+    DomainTooLarge {
+        /// Size of the low-degree extension domain required to generate the proof.
+        requested: usize,
+        /// Size of the largest domain supported by the base field.
+        max_supported: usize,
+        /// Name of the base field type.
+        field: &'static str,
+    },
+    /// This error occurs when the number of queries specified by proof options is not smaller
+    /// than the size of the low-degree extension domain, and thus, the required number of unique
+    /// query positions cannot be drawn from this domain.
+    TooManyQueries {
+        /// Number of queries specified by proof options.
+        num_queries: usize,
+        /// Size of the low-degree extension domain.
+        lde_domain_size: usize,
+    },
     /// This error occurs when the base field specified by the AIR does not support field extension
     /// of degree specified by proof options.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when the field in which the composition polynomial is constructed
+    /// provides fewer bits of security for the low-degree extension domain than the minimum
+    /// field security specified by proof options (see
+    /// [ProofOptions::with_min_field_security()](air::ProofOptions::with_min_field_security)).
+    InsufficientFieldSecurity {
+        /// Number of bits of security provided by the field.
+        achieved: u32,
+        /// Minimum number of bits of security specified by proof options.
+        required: u32,
+    },
+    /// This error occurs when proof options could not be constructed from the specified
+    /// parameters.
+    InvalidProofOptions(ProofOptionsError),
+    /// This error occurs when proof options specify zero-knowledge, but the prover was
+    /// compiled without a source of randomness (i.e., without the `std` feature).
+    UnsupportedZeroKnowledge,
+    /// This error occurs when a batch proof is requested for an empty set of execution traces.
+    EmptyBatch,
+    /// This error occurs when a batch proof is requested for more execution traces than can be
+    /// described by a [BatchProof](air::proof::BatchProof).
+    BatchTooLarge(usize),
+    /// This error occurs when the trace info of the execution trace at the specified index of a
+    /// batch differs from the trace info of the first execution trace of the batch.
+    InconsistentBatchTraceInfo(usize),
+    /// This error occurs when the size of a tile in which constraints are evaluated over the
+    /// constraint evaluation domain is not a power of two, or is smaller than 16.
+    InvalidConstraintEvaluationTileSize(usize),
+    /// This error occurs when the maximum number of threads used to generate a proof is zero.
+    #[cfg(feature = "concurrent")]
+    InvalidMaxParallelism(usize),
+    /// This error occurs when a thread pool for generating a proof could not be built.
+    #[cfg(feature = "concurrent")]
+    ThreadPoolUnavailable(String),
 }
 
 impl fmt::Display for ProverError {
     #[rustfmt::skip]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            Self::TraceTooShort { length, min_length } => {
+                write!(f, "execution trace must consist of at least {min_length} rows, but was {length}")
+            }
+            Self::TraceLengthNotPowerOfTwo(length) => {
+                write!(f, "execution trace length must be a power of two, but was {length}")
+            }
+            Self::TraceLengthMismatch { expected, actual } => {
+                write!(f, "inconsistent trace length: expected {expected} rows, but was {actual}")
+            }
+            Self::TraceWidthMismatch { expected, actual } => {
+                write!(f, "inconsistent main trace segment width: expected {expected} columns, but was {actual}")
+            }
+            Self::AuxSegmentWidthMismatch { segment, expected, actual } => {
+                write!(f, "inconsistent width of auxiliary trace segment {segment}: expected {expected} columns, but was {actual}")
+            }
+            Self::AuxSegmentRoundWidthMismatch { segment, round, expected, actual } => {
+                write!(f, "inconsistent width of round {round} of auxiliary trace segment {segment}: expected {expected} columns, but was {actual}")
+            }
+            Self::MissingAuxSegment(segment) => {
+                write!(f, "auxiliary trace segment {segment} is expected by the AIR but was not provided by the execution trace")
+            }
+            Self::MissingAuxSegmentRound { segment, round } => {
+                write!(f, "round {round} of auxiliary trace segment {segment} is expected by the AIR but was not built by the execution trace")
+            }
+            Self::UnexpectedAuxSegment(segment) => {
+                write!(f, "auxiliary trace segment {segment} is provided by the execution trace but is not expected by the AIR")
+            }
             Self::UnsatisfiedTransitionConstraintError(step) => {
                 write!(f, "a transition constraint was not satisfied at step {step}")
             }
             Self::MismatchedConstraintPolynomialDegree(expected, actual) => {
                 write!(f, "the constraint polynomial's components do not all have the same degree; expected {expected}, but was {actual}")
             }
+            Self::MismatchedTransitionConstraintDegree(index, declared, actual) => {
+                write!(f, "transition constraint {index} has degree {actual} over the execution trace, but its declared degree implies {declared}")
+            }
+            Self::DomainTooLarge { requested, max_supported, field } => {
+                write!(f, "low-degree extension domain of {requested} elements is larger than the maximum domain size of {max_supported} elements supported by field {field}")
+            }
+            Self::TooManyQueries { num_queries, lde_domain_size } => {
+                write!(f, "cannot draw {num_queries} unique query positions from a low-degree extension domain of {lde_domain_size} elements")
+            }
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the specified base field")
             }
+            Self::InsufficientFieldSecurity { achieved, required } => {
+                write!(f, "field provides only {achieved} bits of security for the low-degree extension domain, but at least {required} bits are required; use a field extension or allow insecure fields explicitly")
+            }
+            Self::InvalidProofOptions(err) => {
+                write!(f, "invalid proof options: {err}")
+            }
+            Self::UnsupportedZeroKnowledge => {
+                write!(f, "zero-knowledge proofs can be generated only when the std feature is enabled")
+            }
+            Self::EmptyBatch => {
+                write!(f, "a batch proof must contain at least one execution trace")
+            }
+            Self::BatchTooLarge(num_traces) => {
+                write!(f, "a batch proof can contain at most {} execution traces, but {num_traces} were provided", BatchProof::MAX_NUM_INSTANCES)
+            }
+            Self::InconsistentBatchTraceInfo(index) => {
+                write!(f, "trace info of execution trace {index} differs from the trace info of the first execution trace of the batch")
+            }
+            Self::InvalidConstraintEvaluationTileSize(size) => {
+                write!(f, "constraint evaluation tile size must be a power of two and at least 16, but was {size}")
+            }
+            #[cfg(feature = "concurrent")]
+            Self::InvalidMaxParallelism(max_threads) => {
+                write!(f, "maximum number of threads must be greater than zero, but was {max_threads}")
+            }
+            #[cfg(feature = "concurrent")]
+            Self::ThreadPoolUnavailable(err) => {
+                write!(f, "failed to build a thread pool for proof generation: {err}")
+            }
         }
     }
 }
+
+impl From<ProofOptionsError> for ProverError {
+    fn from(err: ProofOptionsError) -> Self {
+        Self::InvalidProofOptions(err)
+    }
+}
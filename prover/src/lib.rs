@@ -13,7 +13,9 @@
 //! When the crate is compiled with `concurrent` feature enabled, proof generation will be
 //! performed in multiple threads (usually, as many threads as there are logical cores on the
 //! machine). The number of threads can be configured via `RAYON_NUM_THREADS` environment
-//! variable.
+//! variable. Alternatively, proofs can be generated in a dedicated thread pool via
+//! [Prover::prove_in_thread_pool()], or with a bounded number of threads via
+//! [Prover::prove_with_max_parallelism()].
 //!
 //! # Usage
 //! To generate a proof that a computation was executed correctly, you'll need to do the
@@ -45,28 +47,37 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TraceLayout, TransitionConstraintDegree, TransitionConstraintGroup,
+    gadgets, introspection,
+    proof::{BatchProof, InstanceProof, ProofSizeBreakdown, StarkProof},
+    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ChainedAir, ChainedAirBuilder, ChainedComputation, ConstraintCompositionCoefficients,
+    ConstraintDivisor, DeepCompositionCoefficients, EvaluationFrame, FieldExtension,
+    PeriodicColumnPolys, ProofOptions, ProofOptionsBuilder, ProofOptionsError, RoundFunction,
+    SelectorColumn, Step, TraceInfo, TraceLayout, TranscriptDivergence, TranscriptEntry,
+    TranscriptEntryKind, TranscriptLog, TranscriptType, TransitionConstraintDegree,
+    TransitionConstraintGroup, TransitionDivisor,
 };
 pub use utils::{
-    iterators, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
-    SliceReader,
+    iterators, ByteReader, ByteWriter, CanonicalDeserializable, CanonicalSerializable,
+    Deserializable, DeserializationError, Serializable, SliceReader,
 };
 
+#[cfg(feature = "std")]
+pub use utils::{ReadAdapter, WriteAdapter};
+
+use core::cmp;
 use fri::FriProver;
 use utils::collections::Vec;
 
 pub use math;
 use math::{
     fft::infer_degree,
-    fields::{CubeExtension, QuadExtension},
-    ExtensibleField, FieldElement, StarkField,
+    fields::{CubeExtension, QuadExtension, SexticExtension},
+    polynom, ExtensibleField, FieldElement, StarkField, ToElements,
 };
 
 pub use crypto;
-use crypto::{ElementHasher, MerkleTree};
+use crypto::{ElementHasher, Hasher, MerkleTree};
 
 #[cfg(feature = "std")]
 use log::debug;
@@ -75,28 +86,52 @@ use math::log2;
 #[cfg(feature = "std")]
 use std::time::Instant;
 
+mod backend;
+pub use backend::{CpuBackend, FftBackend, HashBackend, ProverBackends};
+
 mod domain;
+use domain::validate_domain_size;
 pub use domain::StarkDomain;
 
 mod matrix;
-pub use matrix::Matrix;
+use matrix::build_matrix_commitment;
+pub use matrix::{build_commitment, build_salted_commitment, Matrix};
+
+mod zk;
 
 mod constraints;
-use constraints::{CompositionPoly, ConstraintCommitment, ConstraintEvaluator};
+use constraints::{
+    record_ood_evaluations, CompositionPoly, ConstraintCommitment, ConstraintEvaluator,
+};
 
 mod composer;
 use composer::DeepCompositionPoly;
 
 mod trace;
-pub use trace::{Trace, TraceTable, TraceTableFragment};
-use trace::{TraceCommitment, TraceLde, TracePolyTable};
+pub use trace::{
+    build_permutation_column, build_range_lookup_column, build_range_lookup_multiplicities,
+    trace_diff, CellDiff, MultiSegmentTraceBuilder, MultiSegmentTraceTable, PaddingPolicy,
+    RowMajorTraceBuilder, Trace, TracePadder, TraceTable, TraceTableFragment,
+    TraceValidationFailure, TraceValidationReport,
+};
+use trace::{
+    validate_aux_segment_round_shape, validate_aux_segment_shape, validate_trace_length,
+    validate_trace_shape, TraceCommitment, TraceLde, TracePolyTable,
+};
 
 mod channel;
 use channel::ProverChannel;
 
+mod stream;
+pub use stream::ProverStream;
+
 mod errors;
 pub use errors::ProverError;
 
+mod batch;
+pub use batch::BatchProver;
+use batch::CommittedInstance;
+
 #[cfg(test)]
 pub mod tests;
 
@@ -121,7 +156,7 @@ pub mod tests;
 /// return from [Prover::options] method.
 pub trait Prover {
     /// Base field for the computation described by this prover.
-    type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3>;
+    type BaseField: StarkField + ExtensibleField<2> + ExtensibleField<3> + ExtensibleField<6>;
 
     /// Algebraic intermediate representation (AIR) for the computation described by this prover.
     type Air: Air<BaseField = Self::BaseField>;
@@ -151,6 +186,22 @@ pub trait Prover {
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Returns the number of rows of the constraint evaluation domain over which constraints are
+    /// evaluated at a time, or `None` if constraints are evaluated over the entire domain at once.
+    ///
+    /// Evaluations of constraints over the domain are grouped into columns by constraint
+    /// divisors. When the tile size is specified, the memory needed for these columns is
+    /// proportional to the tile size rather than to the size of the domain; this reduces peak
+    /// memory usage of the prover for computations with many constraint divisors at the cost of
+    /// some parallelism for small tiles. The generated proof does not depend on the tile size.
+    ///
+    /// The tile size must be a power of two and at least 16; tiles larger than the constraint
+    /// evaluation domain are reduced to the size of the domain. The default implementation
+    /// returns `None`.
+    fn constraint_evaluation_tile_size(&self) -> Option<usize> {
+        None
+    }
+
     /// Returns a STARK proof attesting to a correct execution of a computation defined by the
     /// provided trace.
     ///
@@ -158,56 +209,293 @@ pub trait Prover {
     /// the computation described by [Self::Air](Prover::Air) and generated using some set of
     /// secret and public inputs. Public inputs must match the value returned from
     /// [Self::get_pub_inputs()](Prover::get_pub_inputs) for the provided trace.
-    #[rustfmt::skip]
+    ///
+    /// This is equivalent to calling [Self::prove_with_seed()](Prover::prove_with_seed) with
+    /// an all-zero seed.
     fn prove(&self, trace: Self::Trace) -> Result<StarkProof, ProverError> {
+        self.prove_with_seed(trace, [0; 32])
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, using the specified `seed` to derive all choices left to the prover.
+    ///
+    /// Currently, the seed determines where the search for the proof-of-work nonce starts. Unless
+    /// the zero-knowledge mode is enabled, proof generation is fully deterministic: the same seed,
+    /// trace, and proof options always result in byte-identical proofs, regardless of whether the
+    /// `concurrent` feature is enabled. Proofs generated with different seeds may differ, but are
+    /// equally valid. In zero-knowledge mode, polynomials and commitments are randomized with
+    /// fresh randomness, and thus, every proof is different (see
+    /// [ProofOptions::with_zero_knowledge()]).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The options of this prover specify a field extension not supported by the base field.
+    /// * The options of this prover specify zero-knowledge while the `std` feature is disabled.
+    /// * The trace polynomial size for the length of the `trace` multiplied by the blowup factor
+    ///   is greater than the largest domain supported by the base field (see
+    ///   [ProofOptions::max_trace_length()]).
+    /// * The `trace` is shorter than the minimum trace length, or its length is not a power of
+    ///   two.
+    /// * The field (including its extension) provides fewer bits of security for the low-degree
+    ///   extension domain than the minimum specified by the options (see
+    ///   [ProofOptions::with_min_field_security()]).
+    /// * The shape of the `trace` (including the shape of the auxiliary segments it builds) is
+    ///   not consistent with the trace info of [Self::Air](Prover::Air).
+    fn prove_with_seed(
+        &self,
+        trace: Self::Trace,
+        seed: [u8; 32],
+    ) -> Result<StarkProof, ProverError> {
+        self.prove_with_coin_hasher::<Self::HashFn>(trace, seed)
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, using hash function `C` for the public coin.
+    ///
+    /// Commitments to the execution trace, constraint evaluations, and FRI layers are built using
+    /// [Self::HashFn](Prover::HashFn), while all values drawn from the public coin (and the hashes
+    /// of out-of-domain evaluations absorbed into it) are computed using `C`. Commitments are
+    /// absorbed into the public coin by converting them into digests of `C`. This allows, for
+    /// example, using a fast hash function for Merkle trees and an algebraic hash function for the
+    /// transcript, so that a recursive verifier needs to emulate only the latter for the
+    /// transcript. Fingerprints of both hash functions are recorded in the proof context, and the
+    /// proof must be verified with the same pair of hash functions (see
+    /// `winter_verifier::verify_with_coin_hasher()`).
+    ///
+    /// [Self::prove_with_seed()](Prover::prove_with_seed) is equivalent to calling this method
+    /// with `C` set to [Self::HashFn](Prover::HashFn).
+    ///
+    /// # Errors
+    /// Returns an error for the same reasons as [Self::prove_with_seed()](Prover::prove_with_seed).
+    fn prove_with_coin_hasher<C>(
+        &self,
+        trace: Self::Trace,
+        seed: [u8; 32],
+    ) -> Result<StarkProof, ProverError>
+    where
+        C: ElementHasher<BaseField = Self::BaseField>,
+        C::Digest: From<<Self::HashFn as Hasher>::Digest>,
+    {
+        let backends = ProverBackends::new(CpuBackend, CpuBackend);
+        self.dispatch_proof::<C, _, _>(trace, seed, false, &backends)
+            .map(|(proof, _)| proof)
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, using the specified `backends` for polynomial evaluation and for building
+    /// commitments.
+    ///
+    /// All FFT-based computations (trace low-degree extension, evaluation of constraints, and
+    /// evaluation of the composition polynomials) are performed by the FFT backend, while all
+    /// Merkle tree commitments (to the trace, constraint evaluations, periodic columns, and FRI
+    /// layers) are built by the hash backend. This allows offloading the most expensive steps of
+    /// proof generation to external hardware (e.g., a GPU). As long as the backends compute the
+    /// same results as [CpuBackend], the proof is exactly the same as the one returned by
+    /// [Self::prove()](Prover::prove).
+    ///
+    /// # Errors
+    /// Returns an error for the same reasons as [Self::prove_with_seed()](Prover::prove_with_seed).
+    fn prove_with_backends<F, H>(
+        &self,
+        trace: Self::Trace,
+        backends: &ProverBackends<F, H>,
+    ) -> Result<StarkProof, ProverError>
+    where
+        F: FftBackend<Self::BaseField>,
+        H: HashBackend<Self::HashFn>,
+    {
+        self.dispatch_proof::<Self::HashFn, F, H>(trace, [0; 32], false, backends)
+            .map(|(proof, _)| proof)
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, together with a log of the proof generation.
+    ///
+    /// The proof is exactly the same as the one returned by [Self::prove()](Prover::prove). The
+    /// returned [TranscriptLog] contains all values absorbed into and drawn from the public coin,
+    /// as well as intermediate values of the out-of-domain consistency check computed from the
+    /// values sent to the verifier. For a valid proof, the log is identical to the log recorded by
+    /// `winter_verifier::verify_with_transcript_log()`; otherwise, the logs can be compared via
+    /// [TranscriptLog::diff()] to find the first value on which the prover and the verifier
+    /// disagree.
+    ///
+    /// # Errors
+    /// Returns an error for the same reasons as [Self::prove_with_seed()](Prover::prove_with_seed).
+    fn prove_with_transcript_log(
+        &self,
+        trace: Self::Trace,
+    ) -> Result<(StarkProof, TranscriptLog), ProverError> {
+        let backends = ProverBackends::new(CpuBackend, CpuBackend);
+        self.dispatch_proof::<Self::HashFn, _, _>(trace, [0; 32], true, &backends)
+            .map(|(proof, log)| (proof, log.unwrap_or_default()))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, generated using threads of the specified `pool` only.
+    ///
+    /// By default, proof generation with `concurrent` feature enabled runs in rayon's global
+    /// thread pool. This method instead runs the entire proof generation inside `pool`: all
+    /// parallel sections, including the nested ones in FFT computations, Merkle tree
+    /// construction and proof-of-work grinding, are scheduled on the threads of `pool`, and their
+    /// work is split according to the number of threads in `pool` rather than in the global
+    /// pool. This allows isolating proof generation from other parallel work of an application
+    /// (e.g., when several proofs are generated at once).
+    ///
+    /// If this method is called from a worker thread of another rayon pool, that thread is
+    /// blocked until the proof is generated. The proof is exactly the same as the one returned by
+    /// [Self::prove()](Prover::prove), regardless of the number of threads in `pool`.
+    ///
+    /// # Errors
+    /// Returns an error for the same reasons as [Self::prove_with_seed()](Prover::prove_with_seed).
+    #[cfg(feature = "concurrent")]
+    fn prove_in_thread_pool(
+        &self,
+        trace: Self::Trace,
+        pool: &utils::rayon::ThreadPool,
+    ) -> Result<StarkProof, ProverError>
+    where
+        Self: Sync,
+        Self::Trace: Send,
+    {
+        pool.install(|| self.prove(trace))
+    }
+
+    /// Returns a STARK proof attesting to a correct execution of a computation defined by the
+    /// provided trace, generated using at most `max_threads` threads.
+    ///
+    /// This builds a dedicated thread pool with `max_threads` threads, or with as many threads as
+    /// rayon's global pool if that is smaller, and generates the proof inside this pool (see
+    /// [Self::prove_in_thread_pool()](Prover::prove_in_thread_pool)). The pool is dropped once
+    /// the proof is generated; to avoid spawning threads for every proof, build a pool once and
+    /// use [Self::prove_in_thread_pool()](Prover::prove_in_thread_pool) instead.
+    ///
+    /// # Errors
+    /// Returns an error if `max_threads` is zero, if the thread pool could not be built, or for
+    /// the same reasons as [Self::prove_with_seed()](Prover::prove_with_seed).
+    #[cfg(feature = "concurrent")]
+    fn prove_with_max_parallelism(
+        &self,
+        trace: Self::Trace,
+        max_threads: usize,
+    ) -> Result<StarkProof, ProverError>
+    where
+        Self: Sync,
+        Self::Trace: Send,
+    {
+        if max_threads == 0 {
+            return Err(ProverError::InvalidMaxParallelism(max_threads));
+        }
+        let pool = utils::rayon::ThreadPoolBuilder::new()
+            .num_threads(max_threads.min(utils::rayon::current_num_threads()))
+            .build()
+            .map_err(|err| ProverError::ThreadPoolUnavailable(err.to_string()))?;
+        self.prove_in_thread_pool(trace, &pool)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Validates the provided execution `trace` against the options of this prover, and runs the
+    /// version of the proof generation procedure for the extension field specified by the
+    /// options. If `record` is set to true, a log of the proof generation is returned together
+    /// with the proof.
+    #[doc(hidden)]
+    #[rustfmt::skip]
+    fn dispatch_proof<C, F, H>(
+        &self,
+        trace: Self::Trace,
+        seed: [u8; 32],
+        record: bool,
+        backends: &ProverBackends<F, H>,
+    ) -> Result<(StarkProof, Option<TranscriptLog>), ProverError>
+    where
+        C: ElementHasher<BaseField = Self::BaseField>,
+        C::Digest: From<<Self::HashFn as Hasher>::Digest>,
+        F: FftBackend<Self::BaseField>,
+        H: HashBackend<Self::HashFn>,
+    {
+        // make sure a proof can be generated for a trace of this length with the options of this
+        // prover before doing any work
+        validate_proof_parameters::<Self::BaseField>(trace.length(), self.options())?;
+
         // figure out which version of the generic proof generation procedure to run. this is a sort
         // of static dispatch for selecting two generic parameter: extension field and hash function.
         match self.options().field_extension() {
-            FieldExtension::None => self.generate_proof::<Self::BaseField>(trace),
+            FieldExtension::None => {
+                self.generate_proof::<Self::BaseField, C, F, H>(trace, seed, record, backends)
+            }
             FieldExtension::Quadratic => {
                 if !<QuadExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(2));
                 }
-                self.generate_proof::<QuadExtension<Self::BaseField>>(trace)
+                self.generate_proof::<QuadExtension<Self::BaseField>, C, F, H>(
+                    trace, seed, record, backends,
+                )
             }
             FieldExtension::Cubic => {
                 if !<CubeExtension<Self::BaseField>>::is_supported() {
                     return Err(ProverError::UnsupportedFieldExtension(3));
                 }
-                self.generate_proof::<CubeExtension<Self::BaseField>>(trace)
+                self.generate_proof::<CubeExtension<Self::BaseField>, C, F, H>(
+                    trace, seed, record, backends,
+                )
+            }
+            FieldExtension::Sextic => {
+                if !<SexticExtension<Self::BaseField>>::is_supported() {
+                    return Err(ProverError::UnsupportedFieldExtension(6));
+                }
+                self.generate_proof::<SexticExtension<Self::BaseField>, C, F, H>(
+                    trace, seed, record, backends,
+                )
             }
         }
     }
 
-    // HELPER METHODS
-    // --------------------------------------------------------------------------------------------
-
     /// Performs the actual proof generation procedure, generating the proof that the provided
-    /// execution `trace` is valid against this prover's AIR.
+    /// execution `trace` is valid against this prover's AIR. If `record` is set to true, a log of
+    /// the proof generation is returned together with the proof. Polynomial evaluations and
+    /// commitments are computed using the specified `backends`.
     /// TODO: make this function un-callable externally?
     #[doc(hidden)]
-    fn generate_proof<E>(&self, mut trace: Self::Trace) -> Result<StarkProof, ProverError>
+    fn generate_proof<E, C, F, H>(
+        &self,
+        trace: Self::Trace,
+        seed: [u8; 32],
+        record: bool,
+        backends: &ProverBackends<F, H>,
+    ) -> Result<(StarkProof, Option<TranscriptLog>), ProverError>
     where
         E: FieldElement<BaseField = Self::BaseField>,
+        C: ElementHasher<BaseField = Self::BaseField>,
+        C::Digest: From<<Self::HashFn as Hasher>::Digest>,
+        F: FftBackend<Self::BaseField>,
+        H: HashBackend<Self::HashFn>,
     {
         // 0 ----- instantiate AIR and prover channel ---------------------------------------------
 
-        // serialize public inputs; these will be included in the seed for the public coin
+        // serialize the canonical element encoding of public inputs; these will be included in
+        // the seed for the public coin
         let pub_inputs = self.get_pub_inputs(&trace);
         let mut pub_inputs_bytes = Vec::new();
-        pub_inputs.write_into(&mut pub_inputs_bytes);
+        pub_inputs.to_elements().write_into(&mut pub_inputs_bytes);
 
         // create an instance of AIR for the provided parameters. this takes a generic description
         // of the computation (provided via AIR type), and creates a description of a specific
         // execution of the computation for the provided public inputs.
         let air = Self::Air::new(trace.get_info(), pub_inputs, self.options().clone());
 
+        // make sure the trace has the shape expected by the AIR before doing any expensive work;
+        // the AIR may describe a trace different from the one it was instantiated with
+        validate_trace_shape(&trace, air.trace_info())?;
+
         // create a channel which is used to simulate interaction between the prover and the
         // verifier; the channel will be used to commit to values and to draw randomness that
         // should come from the verifier.
-        let mut channel = ProverChannel::<Self::Air, E, Self::HashFn>::new(&air, pub_inputs_bytes);
-
-        // 1 ----- Commit to the execution trace --------------------------------------------------
+        let mut channel =
+            ProverChannel::<Self::Air, E, Self::HashFn, C>::new(&air, pub_inputs_bytes);
+        if record {
+            channel = channel.with_transcript_log();
+        }
 
         // build computation domain; this is used later for polynomial evaluations
         #[cfg(feature = "std")]
@@ -220,59 +508,199 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
+        // 1 - 5 ----- commit to the trace and the constraints, and build DEEP composition --------
+        let CommittedInstance {
+            trace_commitment,
+            constraint_commitment,
+            periodic_commitment,
+            deep_evaluations,
+        } = self.commit_to_instance(&air, trace, &domain, &mut channel, record, backends)?;
+
+        // 6 ----- compute FRI layers for the composition polynomial ------------------------------
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let mut fri_prover = FriProver::new(air.options().to_fri_options());
+        fri_prover.build_layers_with(&mut channel, deep_evaluations, |columns| {
+            backends.hash.build_tree(backends.hash.hash_leaves(columns))
+        });
+        #[cfg(feature = "std")]
+        debug!(
+            "Computed {} FRI layers from composition polynomial evaluations in {} ms",
+            fri_prover.num_layers(),
+            now.elapsed().as_millis()
+        );
+
+        // 7 ----- determine query positions ------------------------------------------------------
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+
+        // apply proof-of-work to the query seed
+        channel.grind_query_seed(seed);
+
+        // generate pseudo-random query positions
+        let query_positions = channel.get_query_positions();
+        #[cfg(feature = "std")]
+        debug!(
+            "Determined {} query positions in {} ms",
+            query_positions.shared().len(),
+            now.elapsed().as_millis()
+        );
+
+        // 8 ----- build proof object -------------------------------------------------------------
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+
+        // generate FRI proof; FRI layers are queried only at the positions at which both the
+        // trace and the constraint evaluations are opened
+        let fri_proof = fri_prover.build_proof(query_positions.shared());
+
+        // query the execution trace at the selected position; for each query, we need the
+        // state of the trace at that position + Merkle authentication path
+        let trace_queries = trace_commitment.query(query_positions.trace());
+
+        // query the constraint commitment at the selected positions; for each query, we need just
+        // a Merkle authentication path. this is because constraint evaluations for each step are
+        // merged into a single value and Merkle authentication paths contain these values already
+        let constraint_queries = constraint_commitment.query(query_positions.constraint());
+
+        // query the periodic column commitment (if any) at the selected positions; periodic
+        // values are composed together with the trace, and thus, are opened at the same positions
+        let periodic_queries = periodic_commitment.map(|c| c.query(query_positions.trace()));
+
+        // build the proof object
+        let log = channel.take_transcript_log();
+        let proof = channel.build_proof(
+            trace_queries,
+            constraint_queries,
+            periodic_queries,
+            fri_proof,
+        );
+        #[cfg(feature = "std")]
+        debug!("Built proof object in {} ms", now.elapsed().as_millis());
+
+        Ok((proof, log))
+    }
+
+    /// Commits to the provided execution `trace` of an instance of the computation described by
+    /// the specified `air`, and evaluates the DEEP composition polynomial of the instance over
+    /// the LDE `domain`.
+    ///
+    /// This executes all steps of the protocol which precede the FRI commit phase: commitments
+    /// to the trace and constraint evaluations are written into the `channel`, together with the
+    /// out-of-domain evaluation frame, and all randomness is drawn from the `channel`. If
+    /// `record` is set to true, intermediate values of the out-of-domain consistency check are
+    /// recorded into the transcript log of the `channel`.
+    #[doc(hidden)]
+    fn commit_to_instance<E, C, F, H>(
+        &self,
+        air: &Self::Air,
+        mut trace: Self::Trace,
+        domain: &StarkDomain<Self::BaseField>,
+        channel: &mut ProverChannel<Self::Air, E, Self::HashFn, C>,
+        record: bool,
+        backends: &ProverBackends<F, H>,
+    ) -> Result<CommittedInstance<E, Self::HashFn>, ProverError>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+        C: ElementHasher<BaseField = Self::BaseField>,
+        C::Digest: From<<Self::HashFn as Hasher>::Digest>,
+        F: FftBackend<Self::BaseField>,
+        H: HashBackend<Self::HashFn>,
+    {
+        // 1 ----- Commit to the execution trace --------------------------------------------------
+
         // extend the main execution trace and build a Merkle tree from the extended trace
-        let (main_trace_lde, main_trace_tree, main_trace_polys) =
-            self.build_trace_commitment::<Self::BaseField>(trace.main_segment(), &domain);
+        let (main_trace_lde, main_trace_tree, main_trace_salts) = self
+            .build_trace_commitment::<Self::BaseField, _, _>(
+                trace.main_segment(),
+                domain,
+                backends,
+            );
 
         // commit to the LDE of the main trace by writing the root of its Merkle tree into
         // the channel
         channel.commit_trace(*main_trace_tree.root());
 
-        // initialize trace commitment and trace polynomial table structs with the main trace
-        // data; for multi-segment traces these structs will be used as accumulators of all
-        // trace segments
+        // initialize trace commitment struct with the main trace data; for multi-segment traces
+        // this struct will be used as an accumulator of all trace segments
         let mut trace_commitment = TraceCommitment::new(
             main_trace_lde,
             main_trace_tree,
+            main_trace_salts,
             domain.trace_to_lde_blowup(),
         );
-        let mut trace_polys = TracePolyTable::new(main_trace_polys);
 
-        // build auxiliary trace segments (if any), and append the resulting segments to trace
-        // commitment and trace polynomial table structs
+        // build auxiliary trace segments (if any), and append the resulting segments to the trace
+        // commitment struct; segments built in several rounds are committed to once per round,
+        // and the columns of all rounds are appended to the trace commitment struct one round
+        // after another
         let mut aux_trace_segments = Vec::new();
         let mut aux_trace_rand_elements = AuxTraceRandElements::new();
-        for i in 0..trace.layout().num_aux_segments() {
-            #[cfg(feature = "std")]
-            let now = Instant::now();
-
-            // draw a set of random elements required to build an auxiliary trace segment
-            let rand_elements = channel.get_aux_trace_segment_rand_elements(i);
-
-            // build the trace segment
-            let aux_segment = trace
-                .build_aux_segment(&aux_trace_segments, &rand_elements)
-                .expect("failed build auxiliary trace segment");
-            #[cfg(feature = "std")]
-            debug!(
-                "Built auxiliary trace segment of {} columns and 2^{} steps in {} ms",
-                aux_segment.num_cols(),
-                log2(aux_segment.num_rows()),
-                now.elapsed().as_millis()
-            );
-
-            // extend the auxiliary trace segment and build a Merkle tree from the extended trace
-            let (aux_segment_lde, aux_segment_tree, aux_segment_polys) =
-                self.build_trace_commitment::<E>(&aux_segment, &domain);
+        for i in 0..air.trace_layout().num_aux_segments() {
+            let num_rounds = air.trace_layout().num_aux_segment_rounds(i);
+            let mut rounds = Vec::with_capacity(num_rounds);
+            for j in 0..num_rounds {
+                #[cfg(feature = "std")]
+                let now = Instant::now();
+
+                // draw a set of random elements required to build the next round of an auxiliary
+                // trace segment
+                let rand_elements = channel.get_aux_trace_round_rand_elements(i, j);
+                if j == 0 {
+                    aux_trace_rand_elements.add_segment_elements(rand_elements);
+                } else {
+                    aux_trace_rand_elements.add_round_elements(rand_elements);
+                }
 
-            // commit to the LDE of the extended auxiliary trace segment  by writing the root of
-            // its Merkle tree into the channel
-            channel.commit_trace(*aux_segment_tree.root());
+                // build the trace segment, or the columns of its next round
+                let columns = if num_rounds == 1 {
+                    let aux_segment = trace
+                        .build_aux_segment(&aux_trace_segments, &aux_trace_rand_elements)
+                        .ok_or(ProverError::MissingAuxSegment(i))?;
+                    validate_aux_segment_shape(&aux_segment, i, air.trace_info())?;
+                    aux_segment
+                } else {
+                    let columns = trace
+                        .build_aux_segment_round(
+                            &aux_trace_segments,
+                            &rounds,
+                            &aux_trace_rand_elements,
+                        )
+                        .ok_or(ProverError::MissingAuxSegmentRound {
+                            segment: i,
+                            round: j,
+                        })?;
+                    validate_aux_segment_round_shape(&columns, i, j, air.trace_info())?;
+                    columns
+                };
+                #[cfg(feature = "std")]
+                debug!(
+                    "Built auxiliary trace segment of {} columns and 2^{} steps in {} ms",
+                    columns.num_cols(),
+                    log2(columns.num_rows()),
+                    now.elapsed().as_millis()
+                );
+
+                // extend the auxiliary trace columns and build a Merkle tree from the extended
+                // trace
+                let (aux_segment_lde, aux_segment_tree, aux_segment_salts) =
+                    self.build_trace_commitment::<E, _, _>(&columns, domain, backends);
+
+                // commit to the LDE of the extended auxiliary trace columns by writing the root
+                // of its Merkle tree into the channel
+                channel.commit_trace(*aux_segment_tree.root());
+
+                // append the columns to the trace commitment struct
+                trace_commitment.add_segment(aux_segment_lde, aux_segment_tree, aux_segment_salts);
+                rounds.push(columns);
+            }
 
-            // append the segment to the trace commitment and trace polynomial table structs
-            trace_commitment.add_segment(aux_segment_lde, aux_segment_tree);
-            trace_polys.add_aux_segment(aux_segment_polys);
-            aux_trace_rand_elements.add_segment_elements(rand_elements);
+            // the columns of all rounds form the auxiliary trace segment
+            let aux_segment = if num_rounds == 1 {
+                rounds.remove(0)
+            } else {
+                Matrix::new(rounds.into_iter().flat_map(Matrix::into_columns).collect())
+            };
             aux_trace_segments.push(aux_segment);
         }
 
@@ -280,7 +708,13 @@ pub trait Prover {
         // This checks validity of both, assertions and state transitions. We do this in debug
         // mode only because this is a very expensive operation.
         #[cfg(debug_assertions)]
-        trace.validate(&air, &aux_trace_segments, &aux_trace_rand_elements);
+        trace.assert_valid(air, &aux_trace_segments, &aux_trace_rand_elements);
+
+        // the execution trace is not needed past this point; we release the memory it occupies
+        // before constraint evaluation because this is where peak memory usage of the prover
+        // happens
+        drop(aux_trace_segments);
+        drop(trace);
 
         // 2 ----- evaluate constraints -----------------------------------------------------------
         // evaluate constraints specified by the AIR over the constraint evaluation domain, and
@@ -292,8 +726,27 @@ pub trait Prover {
         #[cfg(feature = "std")]
         let now = Instant::now();
         let constraint_coeffs = channel.get_constraint_composition_coeffs();
-        let evaluator = ConstraintEvaluator::new(&air, aux_trace_rand_elements, constraint_coeffs);
-        let constraint_evaluations = evaluator.evaluate(trace_commitment.trace_table(), &domain);
+
+        // when the transcript is recorded, constraints are also evaluated at the out-of-domain
+        // point, and thus, the random elements used for constraint evaluation need to be retained
+        let ood_rand_elements = if record {
+            Some((constraint_coeffs.clone(), aux_trace_rand_elements.clone()))
+        } else {
+            None
+        };
+
+        let evaluator = ConstraintEvaluator::new(
+            air,
+            aux_trace_rand_elements,
+            constraint_coeffs,
+            &backends.fft,
+        );
+        let constraint_evaluations = evaluator.evaluate(
+            trace_commitment.trace_table(),
+            domain,
+            self.constraint_evaluation_tile_size(),
+            &backends.fft,
+        )?;
         #[cfg(feature = "std")]
         debug!(
             "Evaluated constraints over domain of 2^{} elements in {} ms",
@@ -308,10 +761,12 @@ pub trait Prover {
         // - combine them into a single column of evaluations,
         // - interpolate the column into a polynomial in coefficient form
         // - "break" the polynomial into a set of column polynomials each of degree equal to
-        //   trace_length - 1
+        //   trace_length - 1; in zero-knowledge mode, the columns are randomized to the degree of
+        //   trace polynomials, and a random column is appended to them
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let composition_poly = constraint_evaluations.into_poly()?;
+        let composition_poly =
+            constraint_evaluations.into_poly(num_composition_segments(air), &backends.fft)?;
         #[cfg(feature = "std")]
         debug!(
             "Converted constraint evaluations into {} composition polynomial columns of degree {} in {} ms",
@@ -322,12 +777,21 @@ pub trait Prover {
 
         // then, build a commitment to the evaluations of the composition polynomial columns
         let constraint_commitment =
-            self.build_constraint_commitment::<E>(&composition_poly, &domain);
+            self.build_constraint_commitment::<E, _, _>(&composition_poly, domain, backends);
 
         // then, commit to the evaluations of constraints by writing the root of the constraint
         // Merkle tree into the channel
         channel.commit_constraints(constraint_commitment.root());
 
+        // if the AIR commits to its periodic columns, build a commitment to their evaluations
+        // over the LDE domain; the root of this commitment is not sent to the verifier because
+        // the verifier computes it directly from the AIR
+        let periodic_commitment = if air.has_committed_periodic_columns() {
+            Some(self.build_periodic_commitment(air, &backends.hash))
+        } else {
+            None
+        };
+
         // 4 ----- build DEEP composition polynomial ----------------------------------------------
         #[cfg(feature = "std")]
         let now = Instant::now();
@@ -341,19 +805,61 @@ pub trait Prover {
         // from an extension field, rather than increasing the size of the field overall.
         let z = channel.get_ood_point();
 
+        // recover trace polynomials from the trace LDE; the polynomials are not kept in memory
+        // during constraint evaluation as they can be interpolated from the LDE at a fraction of
+        // the cost of extending the trace
+        let trace_polys = trace_commitment
+            .trace_table()
+            .interpolate_polys(domain, &backends.fft);
+
         // evaluate trace and constraint polynomials at the OOD point z, and send the results to
-        // the verifier. the trace polynomials are actually evaluated over two points: z and z * g,
-        // where g is the generator of the trace domain.
-        let ood_trace_states = trace_polys.get_ood_frame(z);
+        // the verifier. the trace polynomials are actually evaluated over points z * g^o for all
+        // frame offsets o, where g is the generator of the trace domain; for the default offsets,
+        // these are z and z * g.
+        let ood_trace_states =
+            trace_polys.get_ood_frame(z, air.trace_domain_generator(), air.frame_offsets());
         channel.send_ood_trace_states(&ood_trace_states);
 
         let ood_evaluations = composition_poly.evaluate_at(z);
         channel.send_ood_constraint_evaluations(&ood_evaluations);
 
+        // if periodic columns are committed, evaluate periodic column polynomials at z and send
+        // the results to the verifier; a polynomial of a column with k cycles is evaluated at z^k
+        let periodic_polys = if periodic_commitment.is_some() {
+            air.get_periodic_column_polys()
+        } else {
+            Vec::new()
+        };
+        let ood_periodic_values = periodic_polys
+            .iter()
+            .map(|poly| {
+                let num_cycles = (air.trace_length() / poly.len()) as u32;
+                polynom::eval(poly, z.exp(num_cycles.into()))
+            })
+            .collect::<Vec<E>>();
+        channel.send_ood_periodic_values(&ood_periodic_values);
+
+        // record intermediate values of the out-of-domain consistency check performed by the
+        // verifier, computed from the values sent to the verifier
+        if let (Some(log), Some((constraint_coeffs, aux_rand_elements))) =
+            (channel.transcript_log_mut(), ood_rand_elements)
+        {
+            record_ood_evaluations(
+                air,
+                constraint_coeffs,
+                &aux_rand_elements,
+                &ood_trace_states,
+                &ood_periodic_values,
+                &ood_evaluations,
+                z,
+                log,
+            );
+        }
+
         // draw random coefficients to use during DEEP polynomial composition, and use them to
         // initialize the DEEP composition polynomial
         let deep_coefficients = channel.get_deep_composition_coeffs();
-        let mut deep_composition_poly = DeepCompositionPoly::new(&air, z, deep_coefficients);
+        let mut deep_composition_poly = DeepCompositionPoly::new(air, z, deep_coefficients);
 
         // combine all trace polynomials together and merge them into the DEEP composition
         // polynomial
@@ -362,8 +868,12 @@ pub trait Prover {
         // merge columns of constraint composition polynomial into the DEEP composition polynomial;
         deep_composition_poly.add_composition_poly(composition_poly, ood_evaluations);
 
+        // merge committed periodic column polynomials (if any) into the DEEP composition
+        // polynomial
+        deep_composition_poly.add_periodic_polys(periodic_polys, ood_periodic_values);
+
         // raise the degree of the DEEP composition polynomial by one to make sure it is equal to
-        // trace_length - 1
+        // the degree of trace polynomials
         deep_composition_poly.adjust_degree();
 
         #[cfg(feature = "std")]
@@ -375,16 +885,16 @@ pub trait Prover {
 
         // make sure the degree of the DEEP composition polynomial is equal to trace polynomial
         // degree
-        assert_eq!(domain.trace_length() - 1, deep_composition_poly.degree());
+        assert_eq!(domain.trace_poly_size() - 1, deep_composition_poly.degree());
 
         // 5 ----- evaluate DEEP composition polynomial over LDE domain ---------------------------
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let deep_evaluations = deep_composition_poly.evaluate(&domain);
+        let deep_evaluations = deep_composition_poly.evaluate(domain, &backends.fft);
         // we check the following condition in debug mode only because infer_degree is an expensive
         // operation
         debug_assert_eq!(
-            domain.trace_length() - 1,
+            domain.trace_poly_size() - 1,
             infer_degree(&deep_evaluations, domain.offset())
         );
         #[cfg(feature = "std")]
@@ -394,56 +904,12 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
-        // 6 ----- compute FRI layers for the composition polynomial ------------------------------
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-        let mut fri_prover = FriProver::new(air.options().to_fri_options());
-        fri_prover.build_layers(&mut channel, deep_evaluations);
-        #[cfg(feature = "std")]
-        debug!(
-            "Computed {} FRI layers from composition polynomial evaluations in {} ms",
-            fri_prover.num_layers(),
-            now.elapsed().as_millis()
-        );
-
-        // 7 ----- determine query positions ------------------------------------------------------
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-
-        // apply proof-of-work to the query seed
-        channel.grind_query_seed();
-
-        // generate pseudo-random query positions
-        let query_positions = channel.get_query_positions();
-        #[cfg(feature = "std")]
-        debug!(
-            "Determined {} query positions in {} ms",
-            query_positions.len(),
-            now.elapsed().as_millis()
-        );
-
-        // 8 ----- build proof object -------------------------------------------------------------
-        #[cfg(feature = "std")]
-        let now = Instant::now();
-
-        // generate FRI proof
-        let fri_proof = fri_prover.build_proof(&query_positions);
-
-        // query the execution trace at the selected position; for each query, we need the
-        // state of the trace at that position + Merkle authentication path
-        let trace_queries = trace_commitment.query(&query_positions);
-
-        // query the constraint commitment at the selected positions; for each query, we need just
-        // a Merkle authentication path. this is because constraint evaluations for each step are
-        // merged into a single value and Merkle authentication paths contain these values already
-        let constraint_queries = constraint_commitment.query(&query_positions);
-
-        // build the proof object
-        let proof = channel.build_proof(trace_queries, constraint_queries, fri_proof);
-        #[cfg(feature = "std")]
-        debug!("Built proof object in {} ms", now.elapsed().as_millis());
-
-        Ok(proof)
+        Ok(CommittedInstance {
+            trace_commitment,
+            constraint_commitment,
+            periodic_commitment,
+            deep_evaluations,
+        })
     }
 
     /// Computes a low-degree extension (LDE) of the provided execution trace over the specified
@@ -451,28 +917,47 @@ pub trait Prover {
     ///
     /// The extension is performed by interpolating each column of the execution trace into a
     /// polynomial of degree = trace_length - 1, and then evaluating the polynomial over the LDE
-    /// domain.
+    /// domain. Both steps are done in a single buffer per column; thus, trace polynomials are not
+    /// retained (they can be recovered from the LDE when needed). In zero-knowledge mode, each
+    /// polynomial is randomized before it is evaluated (see
+    /// [Matrix::extend_randomized_columns_with()]).
     ///
     /// Trace commitment is computed by hashing each row of the extended execution trace, and then
-    /// building a Merkle tree from the resulting hashes.
-    fn build_trace_commitment<E>(
+    /// building a Merkle tree from the resulting hashes. In zero-knowledge mode, each row hash is
+    /// merged with a random salt before it is used as a leaf; the salts are returned together with
+    /// the tree.
+    ///
+    /// The extension is computed by the FFT backend, and the commitment is built by the hash
+    /// backend of the specified `backends`.
+    #[allow(clippy::type_complexity)]
+    fn build_trace_commitment<E, F, H>(
         &self,
         trace: &Matrix<E>,
         domain: &StarkDomain<Self::BaseField>,
-    ) -> (Matrix<E>, MerkleTree<Self::HashFn>, Matrix<E>)
+        backends: &ProverBackends<F, H>,
+    ) -> (
+        Matrix<E>,
+        MerkleTree<Self::HashFn>,
+        Option<Vec<<Self::HashFn as Hasher>::Digest>>,
+    )
     where
         E: FieldElement<BaseField = Self::BaseField>,
+        F: FftBackend<Self::BaseField>,
+        H: HashBackend<Self::HashFn>,
     {
         // extend the execution trace
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let trace_polys = trace.interpolate_columns();
-        let trace_lde = trace_polys.evaluate_columns_over(domain);
+        let trace_lde = if self.options().is_zero_knowledge() {
+            trace.extend_randomized_columns_with(domain, &backends.fft)
+        } else {
+            trace.extend_columns_with(domain, &backends.fft)
+        };
         #[cfg(feature = "std")]
         debug!(
             "Extended execution trace of {} columns from 2^{} to 2^{} steps ({}x blowup) in {} ms",
             trace_lde.num_cols(),
-            log2(trace_polys.num_rows()),
+            log2(trace.num_rows()),
             log2(trace_lde.num_rows()),
             domain.trace_to_lde_blowup(),
             now.elapsed().as_millis()
@@ -481,7 +966,11 @@ pub trait Prover {
         // build trace commitment
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let trace_tree = trace_lde.commit_to_rows();
+        let (trace_tree, trace_salts) = build_matrix_commitment(
+            &trace_lde,
+            self.options().is_zero_knowledge(),
+            &backends.hash,
+        );
         #[cfg(feature = "std")]
         debug!(
             "Computed execution trace commitment (Merkle tree of depth {}) in {} ms",
@@ -489,7 +978,7 @@ pub trait Prover {
             now.elapsed().as_millis()
         );
 
-        (trace_lde, trace_tree, trace_polys)
+        (trace_lde, trace_tree, trace_salts)
     }
 
     /// Evaluates constraint composition polynomial over the LDE domain and builds a commitment
@@ -499,19 +988,26 @@ pub trait Prover {
     /// domain.
     ///
     /// The commitment is computed by hashing each row in the evaluation matrix, and then building
-    /// a Merkle tree from the resulting hashes.
-    fn build_constraint_commitment<E>(
+    /// a Merkle tree from the resulting hashes. In zero-knowledge mode, each row hash is merged
+    /// with a random salt before it is used as a leaf.
+    ///
+    /// The evaluations are computed by the FFT backend, and the commitment is built by the hash
+    /// backend of the specified `backends`.
+    fn build_constraint_commitment<E, F, H>(
         &self,
         composition_poly: &CompositionPoly<E>,
         domain: &StarkDomain<Self::BaseField>,
+        backends: &ProverBackends<F, H>,
     ) -> ConstraintCommitment<E, Self::HashFn>
     where
         E: FieldElement<BaseField = Self::BaseField>,
+        F: FftBackend<Self::BaseField>,
+        H: HashBackend<Self::HashFn>,
     {
         // evaluate composition polynomial columns over the LDE domain
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let composed_evaluations = composition_poly.evaluate(domain);
+        let composed_evaluations = composition_poly.evaluate(domain, &backends.fft);
         #[cfg(feature = "std")]
         debug!(
             "Evaluated {} composition polynomial columns over LDE domain (2^{} elements) in {} ms",
@@ -523,8 +1019,13 @@ pub trait Prover {
         // build constraint evaluation commitment
         #[cfg(feature = "std")]
         let now = Instant::now();
-        let commitment = composed_evaluations.commit_to_rows();
-        let constraint_commitment = ConstraintCommitment::new(composed_evaluations, commitment);
+        let (commitment, salts) = build_matrix_commitment(
+            &composed_evaluations,
+            self.options().is_zero_knowledge(),
+            &backends.hash,
+        );
+        let constraint_commitment =
+            ConstraintCommitment::new(composed_evaluations, commitment, salts);
         #[cfg(feature = "std")]
         debug!(
             "Computed constraint evaluation commitment (Merkle tree of depth {}) in {} ms",
@@ -533,4 +1034,95 @@ pub trait Prover {
         );
         constraint_commitment
     }
+
+    /// Evaluates periodic columns of the computation over the LDE domain and builds a commitment
+    /// to the resulting evaluations.
+    ///
+    /// Periodic columns do not depend on the inputs of the computation; thus, the commitment is
+    /// never salted, even in zero-knowledge mode.
+    ///
+    /// The evaluations are computed by the AIR, while the commitment is built by the specified
+    /// hash backend.
+    fn build_periodic_commitment<H: HashBackend<Self::HashFn>>(
+        &self,
+        air: &Self::Air,
+        hash_backend: &H,
+    ) -> ConstraintCommitment<Self::BaseField, Self::HashFn> {
+        #[cfg(feature = "std")]
+        let now = Instant::now();
+        let evaluations = Matrix::new(air.get_periodic_column_evaluations());
+        let (commitment, salts) = build_matrix_commitment(&evaluations, false, hash_backend);
+
+        // make sure the commitment is the same as the one the verifier expects; we do this in
+        // debug mode only because the AIR may compute the expected commitment from scratch
+        debug_assert_eq!(
+            air.get_periodic_column_commitment::<Self::HashFn>(),
+            *commitment.root(),
+            "periodic column commitment does not match the commitment defined by the AIR"
+        );
+
+        let periodic_commitment = ConstraintCommitment::new(evaluations, commitment, salts);
+        #[cfg(feature = "std")]
+        debug!(
+            "Computed periodic column commitment (Merkle tree of depth {}) in {} ms",
+            periodic_commitment.tree_depth(),
+            now.elapsed().as_millis()
+        );
+        periodic_commitment
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of columns into which the constraint composition polynomial of the
+/// specified AIR is split; in zero-knowledge mode, this excludes the random column committed to
+/// together with the composition polynomial columns.
+fn num_composition_segments<A: Air>(air: &A) -> usize {
+    (air.composition_degree() + 1) / air.trace_length()
+}
+
+/// Makes sure that a proof can be generated for an execution trace of the specified length using
+/// the specified proof `options`.
+fn validate_proof_parameters<B: StarkField>(
+    trace_length: usize,
+    options: &ProofOptions,
+) -> Result<(), ProverError> {
+    // zero-knowledge proofs require a source of randomness
+    if cfg!(not(feature = "std")) && options.is_zero_knowledge() {
+        return Err(ProverError::UnsupportedZeroKnowledge);
+    }
+
+    // trace info cannot be built for a trace of invalid length, and thus, this must be checked
+    // before anything else
+    validate_trace_length(trace_length)?;
+
+    // the low-degree extension domain must fit into a multiplicative subgroup of the field; this
+    // is checked here so that an oversized trace results in an error rather than in a panic deep
+    // inside FFT routines
+    validate_domain_size::<B>(trace_length, options)?;
+
+    // all query positions must be unique, and thus, there must be fewer queries than there are
+    // elements in the low-degree extension domain
+    let num_queries = cmp::max(
+        options.num_trace_queries(),
+        options.num_constraint_queries(),
+    );
+    let lde_domain_size = options.trace_poly_size(trace_length) * options.blowup_factor();
+    if num_queries >= lde_domain_size {
+        return Err(ProverError::TooManyQueries {
+            num_queries,
+            lde_domain_size,
+        });
+    }
+
+    // small fields do not provide adequate security without an extension; unless this check was
+    // disabled explicitly, refuse to generate proofs whose security is limited by the field
+    let achieved = options.field_security(B::MODULUS_BITS, lde_domain_size);
+    let required = options.min_field_security();
+    if achieved < required {
+        return Err(ProverError::InsufficientFieldSecurity { achieved, required });
+    }
+
+    Ok(())
 }
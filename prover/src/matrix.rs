@@ -1,8 +1,8 @@
-use super::StarkDomain;
+use super::{zk, CpuBackend, FftBackend, HashBackend, StarkDomain};
 use core::{iter::FusedIterator, slice};
-use crypto::{ElementHasher, MerkleTree};
+use crypto::{ElementHasher, Hasher, MerkleTree};
 use math::{fft, polynom, FieldElement};
-use utils::{batch_iter_mut, collections::Vec, iter, iter_mut, uninit_vector};
+use utils::{collections::Vec, iter, iter_mut};
 
 #[cfg(feature = "concurrent")]
 use utils::iterators::*;
@@ -181,25 +181,209 @@ impl<E: FieldElement> Matrix<E> {
     ///
     /// The evaluation is done as follows:
     /// * Each column of the matrix is interpreted as coefficients of degree `num_rows - 1`
-    ///   polynomial.
+    ///   polynomial; the number of rows must be equal to the trace polynomial size of the
+    ///   domain.
     /// * These polynomials are evaluated over the LDE domain defined by the specified
     ///   [StarkDomain] using FFT algorithm. The domain specification includes the size of the
     ///   subgroup as well as the domain offset (to define a coset).
     /// * The resulting evaluations are returned in a new Matrix.
     pub fn evaluate_columns_over(&self, domain: &StarkDomain<E::BaseField>) -> Self {
+        self.evaluate_columns_over_with(domain, &CpuBackend)
+    }
+
+    /// Evaluates polynomials contained in the columns of this matrix over the specified domain
+    /// using the specified FFT backend and returns the result.
+    ///
+    /// This is equivalent to [evaluate_columns_over()](Matrix::evaluate_columns_over), but all
+    /// evaluations are performed by `backend`.
+    pub fn evaluate_columns_over_with<F>(
+        &self,
+        domain: &StarkDomain<E::BaseField>,
+        backend: &F,
+    ) -> Self
+    where
+        F: FftBackend<E::BaseField>,
+    {
         let columns = iter!(self.columns)
             .map(|poly| {
-                fft::evaluate_poly_with_offset(
+                backend.evaluate_poly_with_offset(
                     poly,
-                    domain.trace_twiddles(),
+                    domain.trace_poly_twiddles(),
                     domain.offset(),
-                    domain.trace_to_lde_blowup(),
+                    domain.trace_poly_to_lde_blowup(),
                 )
             })
             .collect();
         Self { columns }
     }
 
+    /// Evaluates polynomials contained in the columns of this matrix over the specified domain
+    /// and returns the result. The input matrix is consumed in the process.
+    ///
+    /// This is equivalent to [evaluate_columns_over()](Matrix::evaluate_columns_over), but each
+    /// column is extended to the size of the LDE domain and evaluated in place. Thus, the
+    /// coefficients and the evaluations of a polynomial are never held in memory at the same
+    /// time.
+    pub fn evaluate_columns_over_into(mut self, domain: &StarkDomain<E::BaseField>) -> Self {
+        iter_mut!(self.columns).for_each(|column| {
+            fft::evaluate_poly_with_offset_in_place(
+                column,
+                domain.trace_poly_twiddles(),
+                domain.offset(),
+                domain.trace_poly_to_lde_blowup(),
+            )
+        });
+        self
+    }
+
+    /// Computes a low-degree extension of the columns of this matrix over the specified domain
+    /// and returns the result.
+    ///
+    /// Each column is interpreted as evaluations of a polynomial over the coset of the trace
+    /// domain defined by the trace offset of the specified [StarkDomain]. For the default offset
+    /// of one, this is equivalent to interpolating the columns via
+    /// [interpolate_columns()](Matrix::interpolate_columns) and evaluating the resulting
+    /// polynomials via [evaluate_columns_over()](Matrix::evaluate_columns_over). However, every
+    /// column is extended in a single buffer allocated for the size of the LDE domain; thus, no
+    /// intermediate matrix of polynomials is allocated.
+    pub fn extend_columns(&self, domain: &StarkDomain<E::BaseField>) -> Self {
+        self.extend_columns_with(domain, &CpuBackend)
+    }
+
+    /// Computes a low-degree extension of the columns of this matrix over the specified domain
+    /// using the specified FFT backend and returns the result.
+    ///
+    /// This is equivalent to [extend_columns()](Matrix::extend_columns), but all interpolations
+    /// and evaluations are performed by `backend`.
+    pub fn extend_columns_with<F>(&self, domain: &StarkDomain<E::BaseField>, backend: &F) -> Self
+    where
+        F: FftBackend<E::BaseField>,
+    {
+        self.extend_columns_inner(domain, backend, false)
+    }
+
+    /// Computes a low-degree extension of randomized polynomials defined by the columns of this
+    /// matrix over the specified domain using the specified FFT backend and returns the result.
+    ///
+    /// This is the same as [extend_columns_with()](Matrix::extend_columns_with), except that
+    /// every column polynomial $T(x)$ is replaced by $T(x) + Z(x) \cdot r(x)$ before it is
+    /// evaluated over the LDE domain, where $Z(x)$ is the vanishing polynomial of the trace
+    /// domain and $r(x)$ is a fresh random polynomial such that the result has the trace
+    /// polynomial size of the domain. This is used to extend trace segments in zero-knowledge
+    /// mode; the randomized polynomials have the same values over the trace domain.
+    pub fn extend_randomized_columns_with<F>(
+        &self,
+        domain: &StarkDomain<E::BaseField>,
+        backend: &F,
+    ) -> Self
+    where
+        F: FftBackend<E::BaseField>,
+    {
+        assert!(
+            domain.trace_poly_size() > self.num_rows(),
+            "trace polynomials of {} coefficients cannot be randomized",
+            domain.trace_poly_size()
+        );
+        self.extend_columns_inner(domain, backend, true)
+    }
+
+    /// Extends the columns of this matrix over the specified domain, and randomizes the
+    /// interpolated polynomials before they are evaluated if `randomize` is set to true.
+    fn extend_columns_inner<F>(
+        &self,
+        domain: &StarkDomain<E::BaseField>,
+        backend: &F,
+        randomize: bool,
+    ) -> Self
+    where
+        F: FftBackend<E::BaseField>,
+    {
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(self.num_rows());
+        let columns = iter!(self.columns)
+            .map(|evaluations| {
+                let mut column = Vec::with_capacity(domain.lde_domain_size());
+                column.extend_from_slice(evaluations);
+                if domain.trace_offset() == E::BaseField::ONE {
+                    backend.interpolate_poly(&mut column, &inv_twiddles);
+                } else {
+                    backend.interpolate_poly_with_offset(
+                        &mut column,
+                        &inv_twiddles,
+                        domain.trace_offset(),
+                    );
+                }
+                column.resize(domain.trace_poly_size(), E::ZERO);
+                if randomize {
+                    zk::randomize_trace_poly(&mut column, self.num_rows(), domain.trace_offset());
+                }
+                backend.evaluate_poly_with_offset_in_place(
+                    &mut column,
+                    domain.trace_poly_twiddles(),
+                    domain.offset(),
+                    domain.trace_poly_to_lde_blowup(),
+                );
+                column
+            })
+            .collect();
+        Self { columns }
+    }
+
+    /// Interpolates columns of this matrix, interpreted as low-degree extensions over the
+    /// specified domain, into polynomials in coefficient form and returns the result.
+    ///
+    /// This reverses [extend_columns()](Matrix::extend_columns): every column is assumed to
+    /// contain evaluations of a polynomial of degree smaller than the trace polynomial size over
+    /// the LDE domain. Such a polynomial is fully defined by its evaluations at every `blowup`-th
+    /// point of the LDE domain, where `blowup` is the ratio of the LDE domain size to the trace
+    /// polynomial size; these points form a subgroup shifted by the domain offset, and thus, the
+    /// polynomial is interpolated from them using iFFT algorithm.
+    ///
+    /// # Panics
+    /// Panics if the number of rows in this matrix is not equal to the size of the LDE domain.
+    pub fn interpolate_lde_columns(&self, domain: &StarkDomain<E::BaseField>) -> Self {
+        self.interpolate_lde_columns_with(domain, &CpuBackend)
+    }
+
+    /// Interpolates columns of this matrix, interpreted as low-degree extensions over the
+    /// specified domain, into polynomials in coefficient form using the specified FFT backend
+    /// and returns the result.
+    ///
+    /// This is equivalent to [interpolate_lde_columns()](Matrix::interpolate_lde_columns), but
+    /// all interpolations are performed by `backend`.
+    ///
+    /// # Panics
+    /// Panics if the number of rows in this matrix is not equal to the size of the LDE domain.
+    pub fn interpolate_lde_columns_with<F>(
+        &self,
+        domain: &StarkDomain<E::BaseField>,
+        backend: &F,
+    ) -> Self
+    where
+        F: FftBackend<E::BaseField>,
+    {
+        assert_eq!(
+            self.num_rows(),
+            domain.lde_domain_size(),
+            "expected matrix of {} rows, but was {}",
+            domain.lde_domain_size(),
+            self.num_rows()
+        );
+        let blowup = domain.trace_poly_to_lde_blowup();
+        let inv_twiddles = fft::get_inv_twiddles::<E::BaseField>(domain.trace_poly_size());
+        let columns = iter!(self.columns)
+            .map(|evaluations| {
+                let mut column = evaluations
+                    .iter()
+                    .step_by(blowup)
+                    .copied()
+                    .collect::<Vec<_>>();
+                backend.interpolate_poly_with_offset(&mut column, &inv_twiddles, domain.offset());
+                column
+            })
+            .collect();
+        Self { columns }
+    }
+
     /// Evaluates polynomials contained in the columns of this matrix at a single point `x`.
     pub fn evaluate_columns_at<F>(&self, x: F) -> Vec<F>
     where
@@ -219,30 +403,13 @@ impl<E: FieldElement> Matrix<E> {
     ///   becomes a leaf in the tree. Thus, the number of leaves in the tree is equal to the
     ///   number of rows in the matrix.
     /// * The resulting Merkle tree is return as the commitment to the entire matrix.
+    ///
+    /// This is equivalent to calling [build_commitment()] with this matrix.
     pub fn commit_to_rows<H>(&self) -> MerkleTree<H>
     where
         H: ElementHasher<BaseField = E::BaseField>,
     {
-        // allocate vector to store row hashes
-        let mut row_hashes = unsafe { uninit_vector::<H::Digest>(self.num_rows()) };
-
-        // iterate though matrix rows, hashing each row; the hashing is done by first copying a
-        // row into row_buf to avoid heap allocations, and then by applying the hash function to
-        // the buffer.
-        batch_iter_mut!(
-            &mut row_hashes,
-            128, // min batch size
-            |batch: &mut [H::Digest], batch_offset: usize| {
-                let mut row_buf = vec![E::ZERO; self.num_cols()];
-                for (i, row_hash) in batch.iter_mut().enumerate() {
-                    self.read_row_into(i + batch_offset, &mut row_buf);
-                    *row_hash = H::hash_elements(&row_buf);
-                }
-            }
-        );
-
-        // build Merkle tree out of hashed rows
-        MerkleTree::new(row_hashes).expect("failed to construct trace Merkle tree")
+        build_commitment(self)
     }
 
     // CONVERSIONS
@@ -256,6 +423,132 @@ impl<E: FieldElement> Matrix<E> {
     }
 }
 
+// COMMITMENTS
+// ================================================================================================
+
+/// Returns a Merkle tree commitment to the rows of the specified matrix.
+///
+/// Each row of the matrix is hashed into a single digest of the specified hash function, and the
+/// resulting digests become leaves of a binary Merkle tree. This function is used to commit to
+/// the main and auxiliary trace segments, as well as to the evaluations of the constraint
+/// composition polynomial.
+///
+/// When the crate is compiled with `concurrent` feature enabled, rows are hashed in parallel, and
+/// internal nodes of the Merkle tree are built in parallel as well. The number of threads is
+/// determined by the global rayon thread pool. The resulting tree is the same regardless of
+/// whether the `concurrent` feature is enabled.
+pub fn build_commitment<E, H>(matrix: &Matrix<E>) -> MerkleTree<H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    build_row_commitment(matrix, None, &CpuBackend)
+}
+
+/// Returns a Merkle tree commitment to the rows of the specified matrix in which every leaf is
+/// salted with the corresponding value from `salts`.
+///
+/// Each row of the matrix is hashed into a single digest of the specified hash function, and the
+/// digest is then merged with the salt of the row; the results become leaves of a binary Merkle
+/// tree. This function is used to commit to the trace segments and to the evaluations of the
+/// constraint composition polynomial when proofs are generated with salted commitments.
+///
+/// # Panics
+/// Panics if the number of salts is not equal to the number of rows in the matrix.
+pub fn build_salted_commitment<E, H>(matrix: &Matrix<E>, salts: &[H::Digest]) -> MerkleTree<H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+{
+    build_row_commitment(matrix, Some(salts), &CpuBackend)
+}
+
+/// Returns a Merkle tree commitment to the rows of the specified matrix, together with the salts
+/// of the tree leaves when `salted_commitments` is set to true.
+///
+/// Row hashes and the internal nodes of the tree are computed by the specified hash backend.
+pub fn build_matrix_commitment<E, H, B>(
+    matrix: &Matrix<E>,
+    salted_commitments: bool,
+    backend: &B,
+) -> (MerkleTree<H>, Option<Vec<H::Digest>>)
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    B: HashBackend<H>,
+{
+    if salted_commitments {
+        let salts = draw_salts::<H>(matrix.num_rows());
+        (
+            build_row_commitment(matrix, Some(&salts), backend),
+            Some(salts),
+        )
+    } else {
+        (build_row_commitment(matrix, None, backend), None)
+    }
+}
+
+/// Returns the specified number of salts for a salted commitment.
+///
+/// Every salt is a hash of 32 bytes drawn from a cryptographically secure random number
+/// generator seeded by the operating system.
+#[cfg(feature = "std")]
+fn draw_salts<H: Hasher>(num_salts: usize) -> Vec<H::Digest> {
+    use rand::{thread_rng, RngCore};
+
+    let mut rng = thread_rng();
+    let mut bytes = [0u8; 32];
+    (0..num_salts)
+        .map(|_| {
+            rng.fill_bytes(&mut bytes);
+            H::hash(&bytes)
+        })
+        .collect()
+}
+
+/// Salts cannot be drawn without a source of randomness; this is never called because the
+/// prover rejects options with salted commitments when the `std` feature is disabled.
+#[cfg(not(feature = "std"))]
+fn draw_salts<H: Hasher>(_num_salts: usize) -> Vec<H::Digest> {
+    unreachable!("salts can be drawn only when the std feature is enabled")
+}
+
+/// Hashes each row of the specified matrix into a single digest, optionally merges the digests
+/// with the provided salts, and builds a Merkle tree from the results using the specified hash
+/// backend.
+fn build_row_commitment<E, H, B>(
+    matrix: &Matrix<E>,
+    salts: Option<&[H::Digest]>,
+    backend: &B,
+) -> MerkleTree<H>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    B: HashBackend<H>,
+{
+    let columns = matrix
+        .columns
+        .iter()
+        .map(|column| column.as_slice())
+        .collect::<Vec<_>>();
+    let mut leaves = backend.hash_leaves(&columns);
+
+    if let Some(salts) = salts {
+        assert_eq!(
+            matrix.num_rows(),
+            salts.len(),
+            "expected {} salts, but was {}",
+            matrix.num_rows(),
+            salts.len()
+        );
+        iter_mut!(leaves)
+            .zip(salts)
+            .for_each(|(leaf, &salt)| *leaf = H::merge(&[*leaf, salt]));
+    }
+
+    backend.build_tree(leaves)
+}
+
 // COLUMN ITERATOR
 // ================================================================================================
 
@@ -396,3 +689,76 @@ impl<'a, E: FieldElement> ExactSizeIterator for MultiColumnIter<'a, E> {
 }
 
 impl<'a, E: FieldElement> FusedIterator for MultiColumnIter<'a, E> {}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_commitment, build_matrix_commitment, build_salted_commitment, CpuBackend, Matrix,
+    };
+    use crypto::{hashers::Blake3_256, ElementHasher, Hasher};
+    use math::{fields::f128::BaseElement, FieldElement};
+    use rand_utils::rand_vector;
+    use utils::collections::Vec;
+
+    type Blake3 = Blake3_256<BaseElement>;
+
+    #[test]
+    fn build_commitment_matches_serial_tree() {
+        // the number of rows is large enough for the concurrent code path to be taken when the
+        // concurrent feature is enabled
+        let num_rows = 1 << 12;
+        let matrix = Matrix::new((0..4).map(|_| rand_vector(num_rows)).collect());
+        let tree = build_commitment::<BaseElement, Blake3>(&matrix);
+
+        // hash rows and merge tree nodes layer by layer using a single thread
+        let mut row = vec![BaseElement::ZERO; matrix.num_cols()];
+        let mut nodes = (0..num_rows)
+            .map(|i| {
+                matrix.read_row_into(i, &mut row);
+                Blake3::hash_elements(&row)
+            })
+            .collect::<Vec<_>>();
+        while nodes.len() > 1 {
+            nodes = nodes
+                .chunks(2)
+                .map(|pair| Blake3::merge(&[pair[0], pair[1]]))
+                .collect();
+        }
+
+        assert_eq!(nodes[0], *tree.root());
+        assert_eq!(*matrix.commit_to_rows::<Blake3>().root(), *tree.root());
+    }
+
+    #[test]
+    fn build_salted_commitment_leaves() {
+        let num_rows = 1 << 6;
+        let matrix = Matrix::new((0..4).map(|_| rand_vector(num_rows)).collect());
+        let salts = (0..num_rows as u64)
+            .map(|i| Blake3::hash(&i.to_le_bytes()))
+            .collect::<Vec<_>>();
+        let tree = build_salted_commitment::<BaseElement, Blake3>(&matrix, &salts);
+
+        // every leaf is a hash of the row merged with the salt of the row
+        let mut row = vec![BaseElement::ZERO; matrix.num_cols()];
+        for (i, (leaf, &salt)) in tree.leaves().iter().zip(&salts).enumerate() {
+            matrix.read_row_into(i, &mut row);
+            assert_eq!(Blake3::merge(&[Blake3::hash_elements(&row), salt]), *leaf);
+        }
+        assert_ne!(
+            build_commitment::<BaseElement, Blake3>(&matrix).root(),
+            tree.root()
+        );
+
+        // salts drawn for salted commitments are fresh for every commitment
+        let (tree1, salts1) = build_matrix_commitment::<_, Blake3, _>(&matrix, true, &CpuBackend);
+        let (tree2, salts2) = build_matrix_commitment::<_, Blake3, _>(&matrix, true, &CpuBackend);
+        assert_eq!(num_rows, salts1.as_ref().unwrap().len());
+        assert_ne!(salts1, salts2);
+        assert_ne!(tree1.root(), tree2.root());
+        let (_, salts) = build_matrix_commitment::<_, Blake3, _>(&matrix, false, &CpuBackend);
+        assert!(salts.is_none());
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Prover, ProverError, StarkProof, TraceTable};
+use air::TraceInfo;
+use math::StarkField;
+use utils::collections::Vec;
+
+#[cfg(feature = "concurrent")]
+use utils::iterators::*;
+
+// PROVER STREAM
+// ================================================================================================
+
+/// An incremental interface to a [Prover] which accepts the main execution trace in chunks of
+/// rows as the trace is being generated.
+///
+/// Rows appended via [append_rows()](ProverStream::append_rows) are immediately scattered into
+/// the columns of the execution trace, so that the conversion from the row-major layout (in
+/// which witnesses are usually produced) into the column-major layout required by the prover
+/// overlaps with witness generation rather than being done after the full trace is available.
+/// Once all rows have been appended, [finish()](ProverStream::finish) pads the trace to the next
+/// power of two and generates the proof.
+///
+/// The proof generated via the stream is byte-for-byte identical to the proof generated by
+/// calling [Prover::prove()] with a [TraceTable] containing the same rows. Low-degree extension
+/// and commitment of the trace are still performed in [finish()](ProverStream::finish): the
+/// extension of every column depends on all values in that column, and thus, neither can start
+/// before the last row of the trace is known.
+pub struct ProverStream<'a, P: Prover> {
+    prover: &'a P,
+    columns: Vec<Vec<P::BaseField>>,
+}
+
+impl<'a, B, P> ProverStream<'a, P>
+where
+    B: StarkField,
+    P: Prover<BaseField = B, Trace = TraceTable<B>>,
+{
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new stream which will generate a proof using the specified `prover`.
+    ///
+    /// The width of the execution trace is determined by the first appended row.
+    pub fn new(prover: &'a P) -> Self {
+        Self {
+            prover,
+            columns: Vec::new(),
+        }
+    }
+
+    /// Returns a new stream for an execution trace of the specified width with memory
+    /// pre-allocated for the specified number of rows.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero or greater than 1024.
+    pub fn with_capacity(prover: &'a P, width: usize, num_rows: usize) -> Self {
+        validate_width(width);
+        Self {
+            prover,
+            columns: (0..width).map(|_| Vec::with_capacity(num_rows)).collect(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in the execution trace, or zero if no rows have been
+    /// appended to this stream yet (and the stream was not created with a specific width).
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the number of rows appended to this stream so far.
+    pub fn num_rows(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.len())
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified rows to the end of the execution trace.
+    ///
+    /// When `concurrent` feature is enabled, the rows are scattered into trace columns in
+    /// multiple threads.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The rows are the first rows appended to this stream and their width is zero or greater
+    ///   than 1024.
+    /// * The number of values in any of the rows differs from the width of the trace.
+    pub fn append_rows<R: AsRef<[B]> + Sync>(&mut self, rows: &[R]) {
+        if rows.is_empty() {
+            return;
+        }
+
+        if self.columns.is_empty() {
+            let width = rows[0].as_ref().len();
+            validate_width(width);
+            self.columns = (0..width).map(|_| Vec::with_capacity(rows.len())).collect();
+        }
+
+        let width = self.width();
+        for row in rows.iter() {
+            assert_eq!(
+                row.as_ref().len(),
+                width,
+                "expected row with {} values, but was {}",
+                width,
+                row.as_ref().len()
+            );
+        }
+
+        let append_column = |(i, column): (usize, &mut Vec<B>)| {
+            column.extend(rows.iter().map(|row| row.as_ref()[i]));
+        };
+
+        #[cfg(not(feature = "concurrent"))]
+        self.columns.iter_mut().enumerate().for_each(append_column);
+
+        #[cfg(feature = "concurrent")]
+        self.columns
+            .par_iter_mut()
+            .enumerate()
+            .for_each(append_column);
+    }
+
+    // PROOF GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Pads the execution trace to the next power of two and returns a STARK proof attesting to
+    /// a correct execution of the computation described by the trace.
+    ///
+    /// The trace is padded to the smallest power of two which is greater than or equal to the
+    /// number of appended rows, but to no fewer than 8 rows. Padding rows are populated using the
+    /// `pad` closure, which receives two parameters:
+    /// - index of the last row in the trace.
+    /// - a mutable reference to the values of the last row; the contents of the state are copied
+    ///   into the next row of the trace after the closure returns.
+    ///
+    /// # Errors
+    /// Returns an error if proof generation fails for any of the reasons described in
+    /// [Prover::prove_with_seed()].
+    ///
+    /// # Panics
+    /// Panics if no rows were appended to this stream.
+    pub fn finish<F>(mut self, mut pad: F) -> Result<StarkProof, ProverError>
+    where
+        F: FnMut(usize, &mut [B]),
+    {
+        let num_rows = self.num_rows();
+        assert!(
+            num_rows > 0,
+            "execution trace must consist of at least one row"
+        );
+
+        let trace_length = num_rows
+            .next_power_of_two()
+            .max(TraceInfo::MIN_TRACE_LENGTH);
+        let mut state: Vec<B> = self
+            .columns
+            .iter()
+            .map(|column| column[num_rows - 1])
+            .collect();
+        for column in self.columns.iter_mut() {
+            column.reserve_exact(trace_length - num_rows);
+        }
+        for step in num_rows - 1..trace_length - 1 {
+            pad(step, &mut state);
+            for (column, &value) in self.columns.iter_mut().zip(state.iter()) {
+                column.push(value);
+            }
+        }
+
+        self.prover.prove(TraceTable::init(self.columns))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn validate_width(width: usize) {
+    assert!(
+        width > 0,
+        "execution trace must consist of at least one column"
+    );
+    assert!(
+        width <= TraceInfo::MAX_TRACE_WIDTH,
+        "execution trace width cannot be greater than {}, but was {}",
+        TraceInfo::MAX_TRACE_WIDTH,
+        width
+    );
+}
@@ -12,6 +12,8 @@ use air::{
 use math::{fields::f128::BaseElement, FieldElement, StarkField};
 use utils::collections::Vec;
 
+mod prove;
+
 // FIBONACCI TRACE BUILDER
 // ================================================================================================
 
@@ -105,6 +107,53 @@ impl Air for MockAir {
     }
 }
 
+// FIBONACCI AIR
+// ================================================================================================
+
+/// AIR for traces built by [build_fib_trace()]; the computation starts with (1, 1) in the first
+/// row, and every next row is computed from the previous one as (a + b, a + 2b).
+pub struct FibAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for FibAir {
+    type BaseField = BaseElement;
+    type PublicInputs = ();
+
+    fn new(trace_info: TraceInfo, _pub_inputs: (), options: ProofOptions) -> Self {
+        let degrees = vec![
+            TransitionConstraintDegree::new(1),
+            TransitionConstraintDegree::new(1),
+        ];
+        FibAir {
+            context: AirContext::new(trace_info, degrees, 2, options),
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        result[0] = next[0] - (current[0] + current[1]);
+        result[1] = next[1] - (current[1] + next[0]);
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![
+            Assertion::single(0, 0, BaseElement::ONE),
+            Assertion::single(1, 0, BaseElement::ONE),
+        ]
+    }
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
@@ -0,0 +1,638 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_fib_trace, FibAir};
+use crate::{
+    Air, AirContext, Assertion, AuxTraceRandElements, CpuBackend, EvaluationFrame, FftBackend,
+    FieldExtension, HashBackend, Matrix, ProofOptions, Prover, ProverBackends, ProverError,
+    ProverStream, StarkProof, Trace, TraceInfo, TraceLayout, TraceTable,
+    TransitionConstraintDegree,
+};
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crypto::{hashers::Blake3_256, ElementHasher};
+use math::{fields::f128::BaseElement, FieldElement, StarkField, ToElements};
+use utils::{collections::Vec, ByteWriter, Serializable};
+
+// TRACE LENGTH
+// ================================================================================================
+
+#[test]
+fn prove_trace_too_short() {
+    let trace = MockTrace::new(TraceLayout::new(2, [0, 0], [0, 0]), 2, 4);
+    assert_eq!(
+        Err(ProverError::TraceTooShort {
+            length: 4,
+            min_length: 8
+        }),
+        build_prover(2, [0, 0], 8).prove(trace)
+    );
+}
+
+#[test]
+fn prove_trace_length_not_power_of_two() {
+    // matrices cannot have a number of rows which is not a power of two, but custom traces
+    // may still report such lengths
+    let trace = MockTrace::new(TraceLayout::new(2, [0, 0], [0, 0]), 2, 16).with_length(12);
+    assert_eq!(
+        Err(ProverError::TraceLengthNotPowerOfTwo(12)),
+        build_prover(2, [0, 0], 8).prove(trace)
+    );
+}
+
+#[test]
+fn prove_trace_length_mismatch() {
+    let trace = MockTrace::new(TraceLayout::new(2, [0, 0], [0, 0]), 2, 16);
+    assert_eq!(
+        Err(ProverError::TraceLengthMismatch {
+            expected: 8,
+            actual: 16
+        }),
+        build_prover(2, [0, 0], 8).prove(trace)
+    );
+}
+
+// MAIN SEGMENT WIDTH
+// ================================================================================================
+
+#[test]
+fn prove_trace_width_mismatch() {
+    let trace = MockTrace::new(TraceLayout::new(3, [0, 0], [0, 0]), 3, 8);
+    assert_eq!(
+        Err(ProverError::TraceWidthMismatch {
+            expected: 2,
+            actual: 3
+        }),
+        build_prover(2, [0, 0], 8).prove(trace)
+    );
+}
+
+#[test]
+fn prove_main_segment_inconsistent_with_layout() {
+    // the layout of the trace agrees with the AIR, but the main segment does not
+    let trace = MockTrace::new(TraceLayout::new(2, [0, 0], [0, 0]), 3, 8);
+    assert_eq!(
+        Err(ProverError::TraceWidthMismatch {
+            expected: 2,
+            actual: 3
+        }),
+        build_prover(2, [0, 0], 8).prove(trace)
+    );
+}
+
+// AUXILIARY SEGMENTS
+// ================================================================================================
+
+#[test]
+fn prove_aux_segment_missing_from_layout() {
+    let trace = MockTrace::new(TraceLayout::new(2, [0, 0], [0, 0]), 2, 8);
+    assert_eq!(
+        Err(ProverError::MissingAuxSegment(0)),
+        build_prover(2, [1, 0], 8).prove(trace)
+    );
+}
+
+#[test]
+fn prove_aux_segment_not_built() {
+    // the layout of the trace describes the segment, but the trace does not build it
+    let trace = MockTrace::new(TraceLayout::new(2, [1, 0], [1, 0]), 2, 8);
+    assert_eq!(
+        Err(ProverError::MissingAuxSegment(0)),
+        build_prover(2, [1, 0], 8).prove(trace)
+    );
+}
+
+#[test]
+fn prove_unexpected_aux_segment() {
+    let trace = MockTrace::new(TraceLayout::new(2, [1, 0], [1, 0]), 2, 8).with_aux_segment(1);
+    assert_eq!(
+        Err(ProverError::UnexpectedAuxSegment(0)),
+        build_prover(2, [0, 0], 8).prove(trace)
+    );
+}
+
+#[test]
+fn prove_aux_segment_width_mismatch() {
+    let trace = MockTrace::new(TraceLayout::new(2, [2, 0], [1, 0]), 2, 8).with_aux_segment(2);
+    assert_eq!(
+        Err(ProverError::AuxSegmentWidthMismatch {
+            segment: 0,
+            expected: 1,
+            actual: 2
+        }),
+        build_prover(2, [1, 0], 8).prove(trace)
+    );
+}
+
+#[test]
+fn prove_built_aux_segment_width_mismatch() {
+    // the layout of the trace agrees with the AIR, but the segment built by the trace does not
+    let trace = MockTrace::new(TraceLayout::new(2, [1, 0], [1, 0]), 2, 8).with_aux_segment(2);
+    assert_eq!(
+        Err(ProverError::AuxSegmentWidthMismatch {
+            segment: 0,
+            expected: 1,
+            actual: 2
+        }),
+        build_prover(2, [1, 0], 8).prove(trace)
+    );
+}
+
+// BACKENDS
+// ================================================================================================
+
+#[test]
+fn prove_with_backends() {
+    for field_extension in [FieldExtension::None, FieldExtension::Quadratic] {
+        let prover = FibProver::new(ProofOptions::new(28, 8, 0, field_extension, 4, 32));
+        let backends = ProverBackends::new(CountingBackend::default(), CountingBackend::default());
+        let proof = prover
+            .prove_with_backends(build_fib_trace(128), &backends)
+            .unwrap();
+
+        // the backends compute the same results as the CPU backend, and thus, the proof must be
+        // the same as the one generated with default backends
+        assert_eq!(prover.prove(build_fib_trace(128)).unwrap(), proof);
+
+        // polynomial evaluations and interpolations were routed through the FFT backend
+        assert!(backends.fft.num_evaluations() > 0);
+        assert!(backends.fft.num_interpolations() > 0);
+        assert_eq!(0, backends.fft.num_leaf_hashes());
+        assert_eq!(0, backends.fft.num_tree_layers());
+
+        // trace, constraint, and FRI layer commitments were built by the hash backend; every
+        // tree over the LDE domain of 2^9 elements has 9 layers above the leaves, and every
+        // FRI layer is committed to with a tree of 2^9 / 4^(i + 1) leaves
+        assert_eq!(0, backends.hash.num_evaluations());
+        assert_eq!(0, backends.hash.num_interpolations());
+        let num_fri_layers = proof.fri_proof.num_layers();
+        assert_eq!(2 + num_fri_layers, backends.hash.num_leaf_hashes());
+        let fri_tree_layers = (0..num_fri_layers).map(|i| 7 - 2 * i).sum::<usize>();
+        assert_eq!(2 * 9 + fri_tree_layers, backends.hash.num_tree_layers());
+    }
+}
+
+// CONSTRAINT EVALUATION TILES
+// ================================================================================================
+
+#[test]
+fn prove_with_constraint_evaluation_tiles() {
+    for field_extension in [FieldExtension::None, FieldExtension::Quadratic] {
+        let options = ProofOptions::new(28, 8, 0, field_extension, 4, 32);
+        let expected = FibProver::new(options.clone())
+            .prove(build_fib_trace(64))
+            .unwrap();
+
+        // evaluating constraints in tiles changes only the order in which rows of the
+        // constraint evaluation domain are processed, and thus, the proof must be the same;
+        // tiles larger than the domain are clamped to the size of the domain
+        for tile_size in [16, 32, 1024] {
+            let prover = FibProver::new(options.clone()).with_tile_size(tile_size);
+            assert_eq!(expected, prover.prove(build_fib_trace(64)).unwrap());
+        }
+    }
+}
+
+#[test]
+fn prove_with_invalid_constraint_evaluation_tiles() {
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 32);
+    for tile_size in [0, 8, 24] {
+        let prover = FibProver::new(options.clone()).with_tile_size(tile_size);
+        assert_eq!(
+            Err(ProverError::InvalidConstraintEvaluationTileSize(tile_size)),
+            prover.prove(build_fib_trace(64))
+        );
+    }
+}
+
+// THREAD POOLS
+// ================================================================================================
+
+#[cfg(feature = "concurrent")]
+#[test]
+fn prove_in_thread_pool() {
+    let options = ProofOptions::new(28, 8, 4, FieldExtension::Quadratic, 4, 32);
+    let prover = FibProver::new(options);
+    let expected = prover.prove(build_fib_trace(64)).unwrap();
+
+    // a single-threaded pool runs all parallel sections sequentially, and thus, the proof must
+    // be the same as the one generated without `concurrent` feature; proofs generated with any
+    // number of threads must be the same as well
+    for num_threads in [1, 3] {
+        let pool = utils::rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+        let proof = prover
+            .prove_in_thread_pool(build_fib_trace(64), &pool)
+            .unwrap();
+        assert_eq!(expected.to_bytes(), proof.to_bytes());
+    }
+
+    for max_threads in [1, 2, usize::MAX] {
+        let proof = prover
+            .prove_with_max_parallelism(build_fib_trace(64), max_threads)
+            .unwrap();
+        assert_eq!(expected.to_bytes(), proof.to_bytes());
+    }
+}
+
+#[cfg(feature = "concurrent")]
+#[test]
+fn prove_with_zero_max_parallelism() {
+    let prover = FibProver::new(ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 32));
+    assert_eq!(
+        Err(ProverError::InvalidMaxParallelism(0)),
+        prover.prove_with_max_parallelism(build_fib_trace(64), 0)
+    );
+}
+
+// MOCK TRACE
+// ================================================================================================
+
+/// An execution trace whose layout and length are not required to be consistent with its
+/// segments. All cells of the trace are set to zeros.
+struct MockTrace {
+    layout: TraceLayout,
+    length: usize,
+    main: Matrix<BaseElement>,
+    aux_segments: Vec<Matrix<BaseElement>>,
+}
+
+impl MockTrace {
+    fn new(layout: TraceLayout, width: usize, length: usize) -> Self {
+        Self {
+            layout,
+            length,
+            main: build_matrix(width, length),
+            aux_segments: Vec::new(),
+        }
+    }
+
+    fn with_length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    fn with_aux_segment(mut self, width: usize) -> Self {
+        self.aux_segments
+            .push(build_matrix(width, self.main.num_rows()));
+        self
+    }
+}
+
+impl Trace for MockTrace {
+    type BaseField = BaseElement;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.length
+    }
+
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn main_segment(&self) -> &Matrix<BaseElement> {
+        &self.main
+    }
+
+    fn build_aux_segment<E: FieldElement<BaseField = BaseElement>>(
+        &mut self,
+        aux_segments: &[Matrix<E>],
+        _rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>> {
+        self.aux_segments.get(aux_segments.len()).map(|segment| {
+            let columns = (0..segment.num_cols())
+                .map(|i| segment.get_column(i).iter().map(|&v| E::from(v)).collect())
+                .collect();
+            Matrix::new(columns)
+        })
+    }
+
+    fn read_main_frame(
+        &self,
+        row_idx: usize,
+        frame_offsets: &[usize],
+        frame: &mut EvaluationFrame<BaseElement>,
+    ) {
+        for (i, &offset) in frame_offsets.iter().enumerate() {
+            let row_idx = (row_idx + offset) % self.main.num_rows();
+            self.main.read_row_into(row_idx, frame.row_mut(i));
+        }
+    }
+}
+
+// MOCK AIR AND PROVER
+// ================================================================================================
+
+/// Shape of the execution trace expected by [ShapeAir]; this is passed to the AIR as public
+/// inputs so that the AIR can describe a trace different from the one it is instantiated with.
+#[derive(Clone)]
+struct TraceShape {
+    layout: TraceLayout,
+    length: usize,
+}
+
+impl Serializable for TraceShape {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.layout.write_into(target);
+        target.write_u32(self.length as u32);
+    }
+}
+
+impl ToElements<BaseElement> for TraceShape {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        self.to_bytes().into_iter().map(BaseElement::from).collect()
+    }
+}
+
+/// AIR which ignores the trace info it is instantiated with, and instead describes the trace
+/// specified by the public inputs.
+struct ShapeAir {
+    context: AirContext<BaseElement>,
+}
+
+impl Air for ShapeAir {
+    type BaseField = BaseElement;
+    type PublicInputs = TraceShape;
+
+    fn new(_trace_info: TraceInfo, shape: TraceShape, options: ProofOptions) -> Self {
+        let trace_info = TraceInfo::new_multi_segment(shape.layout, shape.length, Vec::new());
+        let (aux_degrees, num_aux_assertions) = if trace_info.is_multi_segment() {
+            (vec![TransitionConstraintDegree::new(1)], 1)
+        } else {
+            (Vec::new(), 0)
+        };
+        let context = AirContext::new_multi_segment(
+            trace_info,
+            vec![TransitionConstraintDegree::new(1)],
+            aux_degrees,
+            1,
+            num_aux_assertions,
+            options,
+        );
+        Self { context }
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        result[0] = frame.next()[0] - frame.current()[0];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ZERO)]
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+}
+
+struct ShapeProver {
+    options: ProofOptions,
+    shape: TraceShape,
+}
+
+impl Prover for ShapeProver {
+    type BaseField = BaseElement;
+    type Air = ShapeAir;
+    type Trace = MockTrace;
+    type HashFn = Blake3_256<BaseElement>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) -> TraceShape {
+        self.shape.clone()
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// FIELD EXTENSIONS
+// ================================================================================================
+
+#[test]
+fn prove_with_unsupported_field_extension() {
+    // cubic extension is not supported for the 128-bit field; the prover must report this with
+    // an error rather than panic in the middle of proof generation
+    let prover = FibProver::new(ProofOptions::new(28, 8, 0, FieldExtension::Cubic, 4, 32));
+    assert_eq!(
+        Err(ProverError::UnsupportedFieldExtension(3)),
+        prover.prove(build_fib_trace(64))
+    );
+
+    // the same holds for sextic extension
+    let prover = FibProver::new(ProofOptions::new(28, 8, 0, FieldExtension::Sextic, 4, 32));
+    assert_eq!(
+        Err(ProverError::UnsupportedFieldExtension(6)),
+        prover.prove(build_fib_trace(64))
+    );
+}
+
+// PROVER STREAM
+// ================================================================================================
+
+#[test]
+fn prove_with_stream() {
+    // a proof generated from rows appended in chunks must be identical to the proof generated
+    // from the full execution trace
+    let prover = FibProver::new(ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 32));
+    let expected = prover.prove(build_fib_trace(64)).unwrap();
+    let trace = build_fib_trace(64);
+
+    let rows = (0..trace.length())
+        .map(|i| vec![trace.get(0, i), trace.get(1, i)])
+        .collect::<Vec<_>>();
+    let mut stream = ProverStream::new(&prover);
+    for chunk in rows.chunks(5) {
+        stream.append_rows(chunk);
+    }
+    assert_eq!(trace.length(), stream.num_rows());
+    let proof = stream.finish(|_, _| unreachable!()).unwrap();
+    assert_eq!(expected.to_bytes(), proof.to_bytes());
+}
+
+// FIELD SECURITY
+// ================================================================================================
+
+#[test]
+fn prove_with_insufficient_field_security() {
+    // the 128-bit field provides 128 - log2(512) = 119 bits of security for the low-degree
+    // extension domain of a trace of 64 rows with blowup factor 8
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 32);
+    assert!(FibProver::new(options.clone().with_min_field_security(119))
+        .prove(build_fib_trace(128))
+        .is_ok());
+    assert_eq!(
+        Err(ProverError::InsufficientFieldSecurity {
+            achieved: 119,
+            required: 120
+        }),
+        FibProver::new(options.clone().with_min_field_security(120)).prove(build_fib_trace(128))
+    );
+
+    // an extension doubles the size of the field
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 32);
+    assert!(FibProver::new(options.with_min_field_security(240))
+        .prove(build_fib_trace(128))
+        .is_ok());
+}
+
+// PROOF SIZE ESTIMATES
+// ================================================================================================
+
+#[test]
+fn prove_size_matches_estimate() {
+    for field_extension in [FieldExtension::None, FieldExtension::Quadratic] {
+        for blowup_factor in [8, 16] {
+            let options = ProofOptions::new(28, blowup_factor, 0, field_extension, 4, 32);
+            let trace = build_fib_trace(1024);
+            let air = FibAir::new(trace.get_info(), (), options.clone());
+            let estimate = StarkProof::estimated_size::<_, Blake3_256<BaseElement>>(&air, &options);
+
+            let proof = FibProver::new(options).prove(trace).unwrap();
+            let proof_size = proof.to_bytes().len();
+            assert!(
+                estimate.abs_diff(proof_size) * 10 <= proof_size,
+                "estimated {estimate} bytes, but the proof has {proof_size} bytes"
+            );
+        }
+    }
+}
+
+// FIBONACCI PROVER
+// ================================================================================================
+
+struct FibProver {
+    options: ProofOptions,
+    tile_size: Option<usize>,
+}
+
+impl FibProver {
+    fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            tile_size: None,
+        }
+    }
+
+    fn with_tile_size(mut self, tile_size: usize) -> Self {
+        self.tile_size = Some(tile_size);
+        self
+    }
+}
+
+impl Prover for FibProver {
+    type BaseField = BaseElement;
+    type Air = FibAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3_256<BaseElement>;
+
+    fn get_pub_inputs(&self, _trace: &Self::Trace) {}
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    fn constraint_evaluation_tile_size(&self) -> Option<usize> {
+        self.tile_size
+    }
+}
+
+// COUNTING BACKEND
+// ================================================================================================
+
+/// A backend which delegates all computations to [CpuBackend] and counts the number of times
+/// each of the backend methods was invoked.
+#[derive(Default)]
+struct CountingBackend {
+    evaluations: AtomicUsize,
+    interpolations: AtomicUsize,
+    leaf_hashes: AtomicUsize,
+    tree_layers: AtomicUsize,
+}
+
+impl CountingBackend {
+    fn num_evaluations(&self) -> usize {
+        self.evaluations.load(Ordering::Relaxed)
+    }
+
+    fn num_interpolations(&self) -> usize {
+        self.interpolations.load(Ordering::Relaxed)
+    }
+
+    fn num_leaf_hashes(&self) -> usize {
+        self.leaf_hashes.load(Ordering::Relaxed)
+    }
+
+    fn num_tree_layers(&self) -> usize {
+        self.tree_layers.load(Ordering::Relaxed)
+    }
+}
+
+impl<B: StarkField> FftBackend<B> for CountingBackend {
+    fn evaluate_poly_with_offset<E: FieldElement<BaseField = B>>(
+        &self,
+        p: &[E],
+        twiddles: &[B],
+        domain_offset: B,
+        blowup_factor: usize,
+    ) -> Vec<E> {
+        self.evaluations.fetch_add(1, Ordering::Relaxed);
+        CpuBackend.evaluate_poly_with_offset(p, twiddles, domain_offset, blowup_factor)
+    }
+
+    fn interpolate_poly<E: FieldElement<BaseField = B>>(
+        &self,
+        evaluations: &mut [E],
+        inv_twiddles: &[B],
+    ) {
+        self.interpolations.fetch_add(1, Ordering::Relaxed);
+        CpuBackend.interpolate_poly(evaluations, inv_twiddles)
+    }
+}
+
+impl<H: ElementHasher> HashBackend<H> for CountingBackend {
+    fn hash_leaves<E: FieldElement<BaseField = H::BaseField>>(
+        &self,
+        columns: &[&[E]],
+    ) -> Vec<H::Digest> {
+        self.leaf_hashes.fetch_add(1, Ordering::Relaxed);
+        <CpuBackend as HashBackend<H>>::hash_leaves(&CpuBackend, columns)
+    }
+
+    fn build_tree_layer(&self, nodes: &[H::Digest]) -> Vec<H::Digest> {
+        self.tree_layers.fetch_add(1, Ordering::Relaxed);
+        <CpuBackend as HashBackend<H>>::build_tree_layer(&CpuBackend, nodes)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Builds a prover whose AIR expects a trace of the specified shape; each auxiliary segment
+/// expected by the AIR requires a single random element.
+fn build_prover(main_width: usize, aux_widths: [usize; 2], length: usize) -> ShapeProver {
+    let aux_rands = aux_widths.map(|width| usize::from(width > 0));
+    ShapeProver {
+        options: ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 256),
+        shape: TraceShape {
+            layout: TraceLayout::new(main_width, aux_widths, aux_rands),
+            length,
+        },
+    }
+}
+
+fn build_matrix(width: usize, length: usize) -> Matrix<BaseElement> {
+    Matrix::new(vec![vec![BaseElement::ZERO; length]; width])
+}
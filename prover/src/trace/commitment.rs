@@ -20,20 +20,25 @@ use super::TraceLde;
 /// The describes one or more trace segments, each consisting of the following components:
 /// * Evaluations of a trace segment's polynomials over the LDE domain.
 /// * Merkle tree where each leaf in the tree corresponds to a row in the trace LDE matrix.
+/// * Salts of the Merkle tree leaves, if the trace segment was committed to with salts
+///   mode.
 pub struct TraceCommitment<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> {
     trace_lde: TraceLde<E>,
     main_segment_tree: MerkleTree<H>,
+    main_segment_salts: Option<Vec<H::Digest>>,
     aux_segment_trees: Vec<MerkleTree<H>>,
+    aux_segment_salts: Vec<Option<Vec<H::Digest>>>,
 }
 
 impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitment<E, H> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
-    /// Creates a new trace commitment from the provided main trace low-degree extension and the
-    /// corresponding Merkle tree commitment.
+    /// Creates a new trace commitment from the provided main trace low-degree extension, the
+    /// corresponding Merkle tree commitment, and the salts of the tree leaves (if any).
     pub fn new(
         main_trace_lde: Matrix<E::BaseField>,
         main_trace_tree: MerkleTree<H>,
+        main_trace_salts: Option<Vec<H::Digest>>,
         blowup: usize,
     ) -> Self {
         assert_eq!(
@@ -44,15 +49,23 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
         Self {
             trace_lde: TraceLde::new(main_trace_lde, blowup),
             main_segment_tree: main_trace_tree,
+            main_segment_salts: main_trace_salts,
             aux_segment_trees: Vec::new(),
+            aux_segment_salts: Vec::new(),
         }
     }
 
     // STATE MUTATORS
     // --------------------------------------------------------------------------------------------
 
-    /// Adds the provided auxiliary segment trace LDE and Merkle tree to this trace commitment.
-    pub fn add_segment(&mut self, aux_segment_lde: Matrix<E>, aux_segment_tree: MerkleTree<H>) {
+    /// Adds the provided auxiliary segment trace LDE, Merkle tree, and salts of the tree leaves
+    /// (if any) to this trace commitment.
+    pub fn add_segment(
+        &mut self,
+        aux_segment_lde: Matrix<E>,
+        aux_segment_tree: MerkleTree<H>,
+        aux_segment_salts: Option<Vec<H::Digest>>,
+    ) {
         assert_eq!(
             aux_segment_lde.num_rows(),
             aux_segment_tree.leaves().len(),
@@ -61,6 +74,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
 
         self.trace_lde.add_aux_segment(aux_segment_lde);
         self.aux_segment_trees.push(aux_segment_tree);
+        self.aux_segment_salts.push(aux_segment_salts);
     }
 
     // PUBLIC ACCESSORS
@@ -82,13 +96,20 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
         let mut result = vec![build_segment_queries(
             self.trace_lde.get_main_segment(),
             &self.main_segment_tree,
+            self.main_segment_salts.as_deref(),
             positions,
         )];
 
         // build queries for auxiliary trace segments
-        for (i, segment_tree) in self.aux_segment_trees.iter().enumerate() {
+        let aux_segments = self.aux_segment_trees.iter().zip(&self.aux_segment_salts);
+        for (i, (segment_tree, segment_salts)) in aux_segments.enumerate() {
             let segment_lde = self.trace_lde.get_aux_segment(i);
-            result.push(build_segment_queries(segment_lde, segment_tree, positions));
+            result.push(build_segment_queries(
+                segment_lde,
+                segment_tree,
+                segment_salts.as_deref(),
+                positions,
+            ));
         }
 
         result
@@ -116,6 +137,7 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceCommitmen
 fn build_segment_queries<E, H>(
     segment_lde: &Matrix<E>,
     segment_tree: &MerkleTree<H>,
+    segment_salts: Option<&[H::Digest]>,
     positions: &[usize],
 ) -> Queries
 where
@@ -137,5 +159,12 @@ where
         .prove_batch(positions)
         .expect("failed to generate a Merkle proof for trace queries");
 
-    Queries::new(trace_proof, trace_states)
+    // include salts of the queried leaves for salted commitments
+    match segment_salts {
+        Some(salts) => {
+            let query_salts = positions.iter().map(|&i| salts[i]).collect();
+            Queries::new_salted(trace_proof, trace_states, query_salts)
+        }
+        None => Queries::new(trace_proof, trace_states),
+    }
 }
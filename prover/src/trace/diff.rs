@@ -0,0 +1,82 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Trace;
+use core::fmt;
+use math::StarkField;
+use utils::collections::Vec;
+
+// CELL DIFF
+// ================================================================================================
+/// Describes a single cell in which the main segments of two execution traces differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellDiff<B: StarkField> {
+    /// Index of the column of the differing cell.
+    pub column: usize,
+    /// Index of the row of the differing cell.
+    pub row: usize,
+    /// Value of the cell in the first trace.
+    pub left: B,
+    /// Value of the cell in the second trace.
+    pub right: B,
+}
+
+impl<B: StarkField> fmt::Display for CellDiff<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "column {} at row {}: {} != {}",
+            self.column, self.row, self.left, self.right
+        )
+    }
+}
+
+// TRACE DIFF
+// ================================================================================================
+/// Returns up to `max_diffs` cells in which the main segments of the specified traces differ.
+///
+/// Cells are compared row by row, and thus, the returned cells are sorted by row first and by
+/// column second; the first returned cell is the earliest step at which the traces diverge.
+/// Auxiliary trace segments are not compared, as they are built only during proof generation.
+///
+/// # Panics
+/// Panics if the main segments of the traces have different widths or lengths.
+pub fn trace_diff<L, R>(left: &L, right: &R, max_diffs: usize) -> Vec<CellDiff<L::BaseField>>
+where
+    L: Trace,
+    R: Trace<BaseField = L::BaseField>,
+{
+    assert_eq!(
+        left.main_trace_width(),
+        right.main_trace_width(),
+        "traces must have the same main segment width"
+    );
+    assert_eq!(
+        left.length(),
+        right.length(),
+        "traces must have the same length"
+    );
+
+    let mut result = Vec::new();
+    for row in 0..left.length() {
+        for column in 0..left.main_trace_width() {
+            if result.len() == max_diffs {
+                return result;
+            }
+            let lhs = left.get_column(column)[row];
+            let rhs = right.get_column(column)[row];
+            if lhs != rhs {
+                result.push(CellDiff {
+                    column,
+                    row,
+                    left: lhs,
+                    right: rhs,
+                });
+            }
+        }
+    }
+    result
+}
@@ -4,9 +4,13 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{matrix::MultiColumnIter, Matrix};
-use air::{Air, AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout};
+use super::{matrix::MultiColumnIter, Matrix, ProverError};
+use air::{
+    Air, AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout, TransitionEvaluator,
+};
+use core::ops::Range;
 use math::{polynom, FieldElement, StarkField};
+use utils::collections::Vec;
 
 mod trace_lde;
 pub use trace_lde::TraceLde;
@@ -15,11 +19,29 @@ mod poly_table;
 pub use poly_table::TracePolyTable;
 
 mod trace_table;
-pub use trace_table::{TraceTable, TraceTableFragment};
+pub use trace_table::{RowMajorTraceBuilder, TraceTable, TraceTableFragment};
 
 mod commitment;
 pub use commitment::TraceCommitment;
 
+mod permutation;
+pub use permutation::build_permutation_column;
+
+mod range_lookup;
+pub use range_lookup::{build_range_lookup_column, build_range_lookup_multiplicities};
+
+mod padder;
+pub use padder::{PaddingPolicy, TracePadder};
+
+mod multi_segment;
+pub use multi_segment::{MultiSegmentTraceBuilder, MultiSegmentTraceTable};
+
+mod validation;
+pub use validation::{TraceValidationFailure, TraceValidationReport};
+
+mod diff;
+pub use diff::{trace_diff, CellDiff};
+
 #[cfg(test)]
 mod tests;
 
@@ -35,8 +57,9 @@ mod tests;
 /// instance of a computation must be supplied to [Prover::prove()](super::Prover::prove) method
 /// to generate a STARK proof.
 ///
-/// This crate exposes one concrete implementation of the [Trace] trait: [TraceTable]. This
-/// implementation supports concurrent trace generation and should be sufficient in most
+/// This crate exposes two concrete implementations of the [Trace] trait: [TraceTable] for
+/// single-segment traces, and [MultiSegmentTraceTable] for traces with auxiliary segments built
+/// by a [MultiSegmentTraceBuilder]. These implementations should be sufficient in most
 /// situations. However, if functionality provided by [TraceTable] is not sufficient, uses can
 /// provide custom implementations of the [Trace] trait which better suit their needs.
 pub trait Trace: Sized {
@@ -67,18 +90,53 @@ pub trait Trace: Sized {
     /// of prior invocations of this function. Thus, for example, on the first invocation,
     /// `aux_segments` will be empty; on the second invocation, it will contain a single matrix
     /// (the one built during the first invocation) etc.
+    ///
+    /// The `rand_elements` contain random elements for all auxiliary segments up to and
+    /// including the one to be built; elements for the segment being built can be retrieved via
+    /// `rand_elements.get_segment_elements(aux_segments.len())`.
     fn build_aux_segment<E: FieldElement<BaseField = Self::BaseField>>(
         &mut self,
         aux_segments: &[Matrix<E>],
-        rand_elements: &[E],
+        rand_elements: &AuxTraceRandElements<E>,
     ) -> Option<Matrix<E>>;
 
     /// Reads an evaluation frame from the main trace segment at the specified row.
-    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>);
+    ///
+    /// The $j$th row of the frame must be populated with the values of the trace at row
+    /// `row_idx + frame_offsets[j]`; rows past the end of the trace wrap around to its beginning.
+    fn read_main_frame(
+        &self,
+        row_idx: usize,
+        frame_offsets: &[usize],
+        frame: &mut EvaluationFrame<Self::BaseField>,
+    );
 
     // PROVIDED METHODS
     // --------------------------------------------------------------------------------------------
 
+    /// Builds and returns the columns of the next round of an auxiliary trace segment which is
+    /// built in several rounds (see [TraceLayout::with_aux_segment_rounds()]). If there are no
+    /// more rounds to build, None is returned.
+    ///
+    /// The `aux_segments` slice contains the auxiliary trace segments completed so far; thus, the
+    /// index of the segment being built is `aux_segments.len()`. The `prior_rounds` slice contains
+    /// the columns built by prior invocations of this function for the same segment; thus, the
+    /// index of the round being built is `prior_rounds.len()`. The `rand_elements` contain random
+    /// elements for all completed segments, and the elements drawn so far for the segment being
+    /// built; the latter can be retrieved via
+    /// `rand_elements.get_segment_elements(aux_segments.len())`.
+    ///
+    /// The prover invokes this function only for segments with more than one round; by default,
+    /// no rounds can be built.
+    fn build_aux_segment_round<E: FieldElement<BaseField = Self::BaseField>>(
+        &mut self,
+        _aux_segments: &[Matrix<E>],
+        _prior_rounds: &[Matrix<E>],
+        _rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>> {
+        None
+    }
+
     /// Returns trace info for this trace.
     fn get_info(&self) -> TraceInfo {
         TraceInfo::new_multi_segment(self.layout().clone(), self.length(), self.meta().to_vec())
@@ -94,12 +152,35 @@ pub trait Trace: Sized {
         self.layout().aux_trace_width()
     }
 
+    /// Returns a reference to the column at the specified index in the main segment of this
+    /// trace.
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is greater than or equal to the width of the main trace segment.
+    fn get_column(&self, col_idx: usize) -> &[Self::BaseField] {
+        self.main_segment().get_column(col_idx)
+    }
+
+    /// Copies values of the column at the specified index in the main segment of this trace,
+    /// starting at row `start_row`, into the specified `target` slice.
+    ///
+    /// # Panics
+    /// Panics if `col_idx` is greater than or equal to the width of the main trace segment, or
+    /// if `start_row` + `target.len()` is greater than the length of this trace.
+    fn read_column_into(&self, col_idx: usize, start_row: usize, target: &mut [Self::BaseField]) {
+        let column = self.get_column(col_idx);
+        target.copy_from_slice(&column[start_row..start_row + target.len()]);
+    }
+
     // VALIDATION
     // --------------------------------------------------------------------------------------------
     /// Checks if this trace is valid against the specified AIR, and panics if not.
     ///
+    /// This is a wrapper around [validate()](Trace::validate) which panics with a description of
+    /// all recorded failures.
+    ///
     /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
-    fn validate<A, E>(
+    fn assert_valid<A, E>(
         &self,
         air: &A,
         aux_segments: &[Matrix<E>],
@@ -108,27 +189,94 @@ pub trait Trace: Sized {
         A: Air<BaseField = Self::BaseField>,
         E: FieldElement<BaseField = Self::BaseField>,
     {
-        // make sure the width align; if they don't something went terribly wrong
-        assert_eq!(
-            self.main_trace_width(),
-            air.trace_layout().main_trace_width(),
-            "inconsistent trace width: expected {}, but was {}",
-            self.main_trace_width(),
-            air.trace_layout().main_trace_width(),
+        if let Err(report) = self.validate(air, aux_segments, aux_rand_elements) {
+            panic!("{report}");
+        }
+    }
+
+    /// Checks if this trace is valid against the specified AIR.
+    ///
+    /// All assertions and transition constraints are checked over the entire trace.
+    ///
+    /// NOTE: this is a very expensive operation and is intended for use only in debug mode.
+    ///
+    /// # Errors
+    /// Returns a [TraceValidationReport] listing the failed constraints if any assertion or
+    /// transition constraint is not satisfied by this trace.
+    ///
+    /// # Panics
+    /// Panics if the shape of this trace is not consistent with the trace info of the AIR (see
+    /// [ProverError] for a list of possible inconsistencies).
+    fn validate<A, E>(
+        &self,
+        air: &A,
+        aux_segments: &[Matrix<E>],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Result<(), TraceValidationReport<E>>
+    where
+        A: Air<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        self.validate_range(air, aux_segments, aux_rand_elements, 0..self.length())
+    }
+
+    /// Checks if the specified range of steps of this trace is valid against the specified AIR.
+    ///
+    /// Only assertions placed at steps within the range are checked, and transition constraints
+    /// are evaluated only over frames starting at steps within the range. This can be used to
+    /// validate a section of a large trace without paying for validation of the entire trace.
+    ///
+    /// # Errors
+    /// Returns a [TraceValidationReport] listing the failed constraints if any of the checked
+    /// assertions or transition constraints is not satisfied by this trace.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The shape of this trace is not consistent with the trace info of the AIR.
+    /// * The range of steps is empty or extends beyond the end of this trace.
+    fn validate_range<A, E>(
+        &self,
+        air: &A,
+        aux_segments: &[Matrix<E>],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        steps: Range<usize>,
+    ) -> Result<(), TraceValidationReport<E>>
+    where
+        A: Air<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        // make sure the shape of the trace matches the AIR; if it doesn't something went
+        // terribly wrong
+        if let Err(err) = validate_trace_shape(self, air.trace_info()) {
+            panic!("{err}");
+        }
+        assert!(
+            steps.start < steps.end && steps.end <= self.length(),
+            "invalid step range {}..{} for a trace of {} steps",
+            steps.start,
+            steps.end,
+            self.length()
         );
 
+        let mut report = TraceValidationReport::new();
+
         // --- 1. make sure the assertions are valid ----------------------------------------------
 
         // first, check assertions against the main segment of the execution trace
         for assertion in air.get_assertions() {
-            assertion.apply(self.length(), |step, value| {
-                assert!(
-                    value == self.main_segment().get(assertion.column(), step),
-                    "trace does not satisfy assertion main_trace({}, {}) == {}",
-                    assertion.column(),
-                    step,
-                    value
-                );
+            assertion.apply(self.length(), |step, expected| {
+                if !steps.contains(&step) {
+                    return;
+                }
+                let actual = self.main_segment().get(assertion.column(), step);
+                if expected != actual {
+                    report.add_failure(TraceValidationFailure::MainAssertion {
+                        column: assertion.column(),
+                        step,
+                        expected,
+                        actual,
+                    });
+                }
             });
         }
 
@@ -148,39 +296,66 @@ pub trait Trace: Sized {
             }
 
             // get the matrix and verify the assertion against it
-            assertion.apply(self.length(), |step, value| {
-                assert!(
-                    value == aux_segments[segment_idx].get(column_idx, step),
-                    "trace does not satisfy assertion aux_trace({}, {}) == {}",
-                    assertion.column(),
-                    step,
-                    value
-                );
+            assertion.apply(self.length(), |step, expected| {
+                if !steps.contains(&step) {
+                    return;
+                }
+                let actual = aux_segments[segment_idx].get(column_idx, step);
+                if expected != actual {
+                    report.add_failure(TraceValidationFailure::AuxAssertion {
+                        column: assertion.column(),
+                        step,
+                        expected,
+                        actual,
+                    });
+                }
             });
         }
 
         // --- 2. make sure this trace satisfies all transition constraints -----------------------
 
-        // collect the info needed to build periodic values for a specific step
+        // collect the info needed to build periodic values for a specific step; values of
+        // periodic columns used only by auxiliary constraints follow the values of the columns
+        // used by the main constraints
         let g = air.trace_domain_generator();
-        let periodic_values_polys = air.get_periodic_column_polys();
+        let mut periodic_values_polys = air.get_periodic_column_polys();
+        let num_main_periodic_columns = periodic_values_polys.len();
+        periodic_values_polys.append(&mut air.get_aux_periodic_column_polys());
         let mut periodic_values = vec![Self::BaseField::ZERO; periodic_values_polys.len()];
 
         // initialize buffers to hold evaluation frames and results of constraint evaluations
-        let mut x = Self::BaseField::ONE;
-        let mut main_frame = EvaluationFrame::new(self.main_trace_width());
+        let mut x = air.trace_domain_offset() * g.exp((steps.start as u64).into());
+        let frame_offsets = air.frame_offsets();
+        let mut main_frame =
+            EvaluationFrame::new_multi_row(frame_offsets.len(), self.main_trace_width());
         let mut aux_frame = if air.trace_info().is_multi_segment() {
-            Some(EvaluationFrame::<E>::new(self.aux_trace_width()))
+            Some(EvaluationFrame::<E>::new_multi_row(
+                frame_offsets.len(),
+                self.aux_trace_width(),
+            ))
         } else {
             None
         };
         let mut main_evaluations =
             vec![Self::BaseField::ZERO; air.context().num_main_transition_constraints()];
+        // constraints are evaluated over the trace domain, where constraints accumulated into
+        // their slots may legitimately leave them unchanged
+        let transition_evaluator = TransitionEvaluator::new(air).without_write_checks();
         let mut aux_evaluations = vec![E::ZERO; air.context().num_aux_transition_constraints()];
 
-        // we check transition constraints on all steps except the last k steps, where k is the
-        // number of steps exempt from transition constraints (guaranteed to be at least 1)
-        for step in 0..self.length() - air.context().num_transition_exemptions() {
+        // determine the steps on which each transition constraint must hold; constraints against
+        // the main trace segment come first, followed by constraints against auxiliary trace
+        // segments
+        let num_main_constraints = main_evaluations.len();
+        let divisors = (0..air.context().num_transition_constraints())
+            .map(|i| air.context().get_transition_divisor(i))
+            .collect::<Vec<_>>();
+
+        // we evaluate transition constraints on all steps in the range, and check that each
+        // constraint evaluates to zero on the steps on which the constraint must hold; by
+        // default, these are all steps except the last k steps, where k is the number of steps
+        // exempt from transition constraints
+        for step in steps {
             // build periodic values
             for (p, v) in periodic_values_polys.iter().zip(periodic_values.iter_mut()) {
                 let num_cycles = air.trace_length() / p.len();
@@ -190,20 +365,23 @@ pub trait Trace: Sized {
 
             // evaluate transition constraints for the main trace segment and make sure they all
             // evaluate to zeros
-            self.read_main_frame(step, &mut main_frame);
-            air.evaluate_transition(&main_frame, &periodic_values, &mut main_evaluations);
+            self.read_main_frame(step, frame_offsets, &mut main_frame);
+            transition_evaluator.evaluate_main(
+                &main_frame,
+                &periodic_values[..num_main_periodic_columns],
+                &mut main_evaluations,
+            );
             for (i, &evaluation) in main_evaluations.iter().enumerate() {
-                assert!(
-                    evaluation == Self::BaseField::ZERO,
-                    "main transition constraint {i} did not evaluate to ZERO at step {step}"
-                );
+                if evaluation != Self::BaseField::ZERO && divisors[i].is_enforced_at(step) {
+                    report.add_main_transition_failure(i, step, evaluation, &main_frame);
+                }
             }
 
             // evaluate transition constraints for auxiliary trace segments (if any) and make
             // sure they all evaluate to zeros
             if let Some(ref mut aux_frame) = aux_frame {
-                read_aux_frame(aux_segments, step, aux_frame);
-                air.evaluate_aux_transition(
+                read_aux_frame(aux_segments, step, frame_offsets, aux_frame);
+                transition_evaluator.evaluate_aux(
                     &main_frame,
                     aux_frame,
                     &periodic_values,
@@ -211,17 +389,161 @@ pub trait Trace: Sized {
                     &mut aux_evaluations,
                 );
                 for (i, &evaluation) in aux_evaluations.iter().enumerate() {
-                    assert!(
-                        evaluation == E::ZERO,
-                        "auxiliary transition constraint {i} did not evaluate to ZERO at step {step}"
-                    );
+                    if evaluation != E::ZERO
+                        && divisors[num_main_constraints + i].is_enforced_at(step)
+                    {
+                        report.add_aux_transition_failure(
+                            i,
+                            step,
+                            evaluation,
+                            &main_frame,
+                            aux_frame,
+                        );
+                    }
                 }
             }
 
             // update x coordinate of the domain
             x *= g;
         }
+
+        if report.is_empty() {
+            Ok(())
+        } else {
+            Err(report)
+        }
+    }
+}
+
+// TRACE SHAPE VALIDATION
+// ================================================================================================
+
+/// Checks that an execution trace with the specified number of rows can be used to generate a
+/// proof.
+///
+/// This is checked before trace info is built for the trace, because building trace info for a
+/// trace of invalid length panics.
+pub(crate) fn validate_trace_length(length: usize) -> Result<(), ProverError> {
+    if length < TraceInfo::MIN_TRACE_LENGTH {
+        return Err(ProverError::TraceTooShort {
+            length,
+            min_length: TraceInfo::MIN_TRACE_LENGTH,
+        });
+    }
+    if !length.is_power_of_two() {
+        return Err(ProverError::TraceLengthNotPowerOfTwo(length));
+    }
+    Ok(())
+}
+
+/// Checks that the shape of the provided execution trace is consistent with the trace info
+/// expected by the AIR.
+///
+/// This is used by the prover to reject misconfigured traces before any expensive work starts,
+/// and by [Trace::validate_range()] to make sure that the trace can be checked against the AIR.
+pub(crate) fn validate_trace_shape<T: Trace>(
+    trace: &T,
+    expected: &TraceInfo,
+) -> Result<(), ProverError> {
+    validate_trace_length(trace.length())?;
+    if trace.length() != expected.length() {
+        return Err(ProverError::TraceLengthMismatch {
+            expected: expected.length(),
+            actual: trace.length(),
+        });
+    }
+
+    // the main segment must match both the layout of the trace and the layout of the AIR
+    let main_segment = trace.main_segment();
+    if main_segment.num_rows() != expected.length() {
+        return Err(ProverError::TraceLengthMismatch {
+            expected: expected.length(),
+            actual: main_segment.num_rows(),
+        });
+    }
+    let expected_layout = expected.layout();
+    for actual_width in [trace.main_trace_width(), main_segment.num_cols()] {
+        if actual_width != expected_layout.main_trace_width() {
+            return Err(ProverError::TraceWidthMismatch {
+                expected: expected_layout.main_trace_width(),
+                actual: actual_width,
+            });
+        }
+    }
+
+    // auxiliary segments are built later; here we only check that the layouts agree
+    let layout = trace.layout();
+    for i in 0..expected_layout.num_aux_segments() {
+        if i >= layout.num_aux_segments() {
+            return Err(ProverError::MissingAuxSegment(i));
+        }
+        if layout.get_aux_segment_width(i) != expected_layout.get_aux_segment_width(i) {
+            return Err(ProverError::AuxSegmentWidthMismatch {
+                segment: i,
+                expected: expected_layout.get_aux_segment_width(i),
+                actual: layout.get_aux_segment_width(i),
+            });
+        }
+    }
+    if layout.num_aux_segments() > expected_layout.num_aux_segments() {
+        return Err(ProverError::UnexpectedAuxSegment(
+            expected_layout.num_aux_segments(),
+        ));
     }
+
+    Ok(())
+}
+
+/// Checks that the shape of the auxiliary trace segment at the specified index is consistent
+/// with the trace info expected by the AIR.
+pub(crate) fn validate_aux_segment_shape<E: FieldElement>(
+    segment: &Matrix<E>,
+    segment_idx: usize,
+    expected: &TraceInfo,
+) -> Result<(), ProverError> {
+    let expected_width = expected.layout().get_aux_segment_width(segment_idx);
+    if segment.num_cols() != expected_width {
+        return Err(ProverError::AuxSegmentWidthMismatch {
+            segment: segment_idx,
+            expected: expected_width,
+            actual: segment.num_cols(),
+        });
+    }
+    if segment.num_rows() != expected.length() {
+        return Err(ProverError::TraceLengthMismatch {
+            expected: expected.length(),
+            actual: segment.num_rows(),
+        });
+    }
+    Ok(())
+}
+
+/// Checks that the shape of the columns built in the specified round of a multi-round auxiliary
+/// trace segment is consistent with the trace info expected by the AIR.
+pub(crate) fn validate_aux_segment_round_shape<E: FieldElement>(
+    columns: &Matrix<E>,
+    segment_idx: usize,
+    round_idx: usize,
+    expected: &TraceInfo,
+) -> Result<(), ProverError> {
+    let expected_width = expected
+        .layout()
+        .get_aux_segment_round_width(segment_idx, round_idx);
+    if columns.num_cols() != expected_width {
+        return Err(ProverError::AuxSegmentRoundWidthMismatch {
+            segment: segment_idx,
+            round: round_idx,
+            expected: expected_width,
+            actual: columns.num_cols(),
+        });
+    }
+    if columns.num_rows() != expected.length() {
+        return Err(ProverError::TraceLengthMismatch {
+            expected: expected.length(),
+            actual: columns.num_rows(),
+        });
+    }
+    Ok(())
 }
 
 // HELPER FUNCTIONS
@@ -233,16 +555,18 @@ pub trait Trace: Sized {
 /// This is probably not the most efficient implementation, but since we call this function only
 /// for trace validation purposes (which is done in debug mode only), we don't care all that much
 /// about its performance.
-fn read_aux_frame<E>(aux_segments: &[Matrix<E>], row_idx: usize, frame: &mut EvaluationFrame<E>)
-where
+fn read_aux_frame<E>(
+    aux_segments: &[Matrix<E>],
+    row_idx: usize,
+    frame_offsets: &[usize],
+    frame: &mut EvaluationFrame<E>,
+) where
     E: FieldElement,
 {
-    for (column, current_value) in MultiColumnIter::new(aux_segments).zip(frame.current_mut()) {
-        *current_value = column[row_idx];
-    }
-
-    let next_row_idx = (row_idx + 1) % aux_segments[0].num_rows();
-    for (column, next_value) in MultiColumnIter::new(aux_segments).zip(frame.next_mut()) {
-        *next_value = column[next_row_idx];
+    for (i, &frame_offset) in frame_offsets.iter().enumerate() {
+        let frame_row_idx = (row_idx + frame_offset) % aux_segments[0].num_rows();
+        for (column, value) in MultiColumnIter::new(aux_segments).zip(frame.row_mut(i)) {
+            *value = column[frame_row_idx];
+        }
     }
 }
@@ -0,0 +1,184 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{Matrix, Trace, TraceTable};
+use air::{AuxTraceRandElements, EvaluationFrame, TraceLayout};
+use math::{FieldElement, StarkField};
+
+// MULTI-SEGMENT TRACE BUILDER
+// ================================================================================================
+/// Defines how auxiliary segments of an execution trace are built from its main segment.
+///
+/// Auxiliary trace segments usually depend on random elements drawn by the verifier after the
+/// prover commits to the main trace segment (e.g., running product columns of permutation
+/// arguments). Implementing this trait and wrapping the main segment into a
+/// [MultiSegmentTraceTable] is sufficient to generate proofs for such computations: the prover
+/// invokes [build_aux_segment()](MultiSegmentTraceBuilder::build_aux_segment) once for every
+/// single-round auxiliary segment defined by the layout of the trace, and
+/// [build_aux_segment_round()](MultiSegmentTraceBuilder::build_aux_segment_round) once for every
+/// round of a multi-round auxiliary segment.
+pub trait MultiSegmentTraceBuilder<B: StarkField> {
+    /// Builds and returns the next auxiliary trace segment, or None if all auxiliary segments
+    /// have already been built.
+    ///
+    /// The `aux_segments` slice contains auxiliary segments built by prior invocations of this
+    /// method; thus, the index of the segment to be built is `aux_segments.len()`. The
+    /// `rand_elements` contain random elements for all auxiliary segments up to and including
+    /// the one to be built.
+    fn build_aux_segment<E>(
+        &self,
+        main: &TraceTable<B>,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = B>;
+
+    /// Builds and returns the columns of the next round of a multi-round auxiliary trace segment,
+    /// or None if the builder does not build such segments.
+    ///
+    /// The index of the segment being built is `aux_segments.len()`, and the index of the round
+    /// being built is `prior_rounds.len()`; see
+    /// [Trace::build_aux_segment_round()](super::Trace::build_aux_segment_round) for details.
+    fn build_aux_segment_round<E>(
+        &self,
+        _main: &TraceTable<B>,
+        _aux_segments: &[Matrix<E>],
+        _prior_rounds: &[Matrix<E>],
+        _rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = B>,
+    {
+        None
+    }
+}
+
+// MULTI-SEGMENT TRACE TABLE
+// ================================================================================================
+/// An execution trace consisting of a main segment stored in a [TraceTable] and of auxiliary
+/// segments built by a [MultiSegmentTraceBuilder].
+///
+/// The main segment is built and filled in the same way as for a single-segment computation;
+/// it is then combined with a layout describing the auxiliary segments, and with a builder
+/// which populates them during proof generation.
+#[derive(Clone)]
+pub struct MultiSegmentTraceTable<B: StarkField, T: MultiSegmentTraceBuilder<B>> {
+    layout: TraceLayout,
+    main: TraceTable<B>,
+    builder: T,
+}
+
+impl<B: StarkField, T: MultiSegmentTraceBuilder<B>> MultiSegmentTraceTable<B, T> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new execution trace with the specified `main` segment, and with auxiliary
+    /// segments described by the `layout` and built by the `builder`.
+    ///
+    /// # Panics
+    /// Panics if the width of the main segment in the `layout` is not equal to the width of the
+    /// `main` trace table.
+    pub fn new(main: TraceTable<B>, layout: TraceLayout, builder: T) -> Self {
+        assert_eq!(
+            layout.main_trace_width(),
+            main.width(),
+            "expected main trace segment of {} columns, but was {}",
+            layout.main_trace_width(),
+            main.width()
+        );
+        Self {
+            layout,
+            main,
+            builder,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the main segment of this execution trace.
+    pub fn main_trace(&self) -> &TraceTable<B> {
+        &self.main
+    }
+
+    /// Returns the builder of auxiliary segments of this execution trace.
+    pub fn builder(&self) -> &T {
+        &self.builder
+    }
+
+    /// Returns value of the cell in the specified column at the specified row of the main
+    /// segment of this trace.
+    pub fn get(&self, column: usize, step: usize) -> B {
+        self.main.get(column, step)
+    }
+}
+
+// TRACE TRAIT IMPLEMENTATION
+// ================================================================================================
+
+impl<B: StarkField, T: MultiSegmentTraceBuilder<B>> Trace for MultiSegmentTraceTable<B, T> {
+    type BaseField = B;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.main.length()
+    }
+
+    fn meta(&self) -> &[u8] {
+        self.main.meta()
+    }
+
+    fn read_main_frame(
+        &self,
+        row_idx: usize,
+        frame_offsets: &[usize],
+        frame: &mut EvaluationFrame<Self::BaseField>,
+    ) {
+        self.main.read_main_frame(row_idx, frame_offsets, frame);
+    }
+
+    fn main_segment(&self) -> &Matrix<B> {
+        self.main.main_segment()
+    }
+
+    fn build_aux_segment<E>(
+        &mut self,
+        aux_segments: &[Matrix<E>],
+        rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        if aux_segments.len() >= self.layout.num_aux_segments() {
+            return None;
+        }
+        self.builder
+            .build_aux_segment(&self.main, aux_segments, rand_elements)
+    }
+
+    fn build_aux_segment_round<E>(
+        &mut self,
+        aux_segments: &[Matrix<E>],
+        prior_rounds: &[Matrix<E>],
+        rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let segment_idx = aux_segments.len();
+        if segment_idx >= self.layout.num_aux_segments()
+            || prior_rounds.len() >= self.layout.num_aux_segment_rounds(segment_idx)
+        {
+            return None;
+        }
+        self.builder
+            .build_aux_segment_round(&self.main, aux_segments, prior_rounds, rand_elements)
+    }
+}
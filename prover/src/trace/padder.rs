@@ -0,0 +1,163 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::TraceTable;
+use air::TraceInfo;
+use math::StarkField;
+use utils::collections::Vec;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+// PADDING POLICY
+// ================================================================================================
+/// Defines how the rows appended to an execution trace by a [TracePadder] are populated.
+pub enum PaddingPolicy<B: StarkField> {
+    /// Every padding row is a copy of the last row of the original trace.
+    RepeatLast,
+    /// Every padding row is set to the specified row.
+    FillWith(Vec<B>),
+    /// Every padding row is computed from the row preceding it. The closure receives the index
+    /// of the row being computed, the values of the previous row, and a mutable reference to the
+    /// values of the row being computed (initialized to the values of the previous row).
+    Compute(ComputeRowFn<B>),
+}
+
+/// Closure which computes a padding row for [PaddingPolicy::Compute].
+type ComputeRowFn<B> = Box<dyn Fn(usize, &[B], &mut [B])>;
+
+// TRACE PADDER
+// ================================================================================================
+/// Pads execution traces of arbitrary length to a length accepted by [TraceTable].
+///
+/// An execution trace is padded to the smallest power of two which is greater than or equal to
+/// the number of its rows plus the minimum number of padding rows (zero by default), but to no
+/// fewer than 8 rows. The padding rows are populated according to the [PaddingPolicy] of the
+/// padder.
+///
+/// Padding rows are subject to the same transition constraints as all other rows. Thus, unless
+/// the transition constraints of a computation accept repeated (or constant) rows, the padding
+/// rows should be computed with [PaddingPolicy::Compute] such that the transitions from the last
+/// row of the original trace, and between the padding rows, remain valid. The only exceptions
+/// are the last rows of the padded trace which are exempt from transition constraints (see
+/// [set_num_transition_exemptions()](air::AirContext::set_num_transition_exemptions)): with
+/// $k$ exemptions, the last $k - 1$ rows of the padded trace may contain arbitrary values.
+/// To make sure these rows are never part of the original trace, the minimum number of padding
+/// rows should be set to $k - 1$ via [with_min_padding()](TracePadder::with_min_padding).
+///
+/// Since the position of the last row of the original trace is not implied by the length of
+/// the padded trace, assertions against that row should be placed using the original length
+/// returned by [pad()](TracePadder::pad), which usually needs to be a part of public inputs.
+pub struct TracePadder<B: StarkField> {
+    policy: PaddingPolicy<B>,
+    min_padding: usize,
+}
+
+impl<B: StarkField> TracePadder<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new padder which populates padding rows according to the specified `policy`.
+    pub fn new(policy: PaddingPolicy<B>) -> Self {
+        Self {
+            policy,
+            min_padding: 0,
+        }
+    }
+
+    /// Sets the minimum number of padding rows appended to every trace by this padder.
+    pub fn with_min_padding(mut self, min_padding: usize) -> Self {
+        self.min_padding = min_padding;
+        self
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the policy used by this padder to populate padding rows.
+    pub fn policy(&self) -> &PaddingPolicy<B> {
+        &self.policy
+    }
+
+    /// Returns the minimum number of padding rows appended to every trace by this padder.
+    pub fn min_padding(&self) -> usize {
+        self.min_padding
+    }
+
+    /// Returns the length of the trace resulting from padding a trace of the specified length.
+    pub fn get_padded_length(&self, trace_length: usize) -> usize {
+        (trace_length + self.min_padding)
+            .next_power_of_two()
+            .max(TraceInfo::MIN_TRACE_LENGTH)
+    }
+
+    // PADDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Pads the execution trace described by the specified `columns` and returns the padded
+    /// trace together with the original length of the trace.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `columns` vector is empty or has over 1024 columns.
+    /// * The columns are empty, or the number of elements is not identical for all columns.
+    /// * The policy of this padder is [PaddingPolicy::FillWith] and the number of values in the
+    ///   padding row is not equal to the number of columns.
+    /// * The padded length of the trace is greater than the biggest multiplicative subgroup in
+    ///   the field `B`.
+    pub fn pad(&self, mut columns: Vec<Vec<B>>) -> (TraceTable<B>, usize) {
+        assert!(
+            !columns.is_empty(),
+            "execution trace must consist of at least one column"
+        );
+        let trace_length = columns[0].len();
+        assert!(
+            trace_length > 0,
+            "execution trace must consist of at least one row"
+        );
+        for column in columns.iter().skip(1) {
+            assert_eq!(
+                column.len(),
+                trace_length,
+                "all columns traces must have the same length"
+            );
+        }
+
+        let padded_length = self.get_padded_length(trace_length);
+        match &self.policy {
+            PaddingPolicy::RepeatLast => {
+                for column in columns.iter_mut() {
+                    column.resize(padded_length, column[trace_length - 1]);
+                }
+            }
+            PaddingPolicy::FillWith(row) => {
+                assert_eq!(
+                    row.len(),
+                    columns.len(),
+                    "expected padding row with {} values, but was {}",
+                    columns.len(),
+                    row.len()
+                );
+                for (column, &value) in columns.iter_mut().zip(row) {
+                    column.resize(padded_length, value);
+                }
+            }
+            PaddingPolicy::Compute(compute) => {
+                let mut prev_row: Vec<B> = columns.iter().map(|c| c[trace_length - 1]).collect();
+                let mut next_row = prev_row.clone();
+                for step in trace_length..padded_length {
+                    compute(step, &prev_row, &mut next_row);
+                    for (column, &value) in columns.iter_mut().zip(&next_row) {
+                        column.push(value);
+                    }
+                    prev_row.copy_from_slice(&next_row);
+                }
+            }
+        }
+
+        (TraceTable::init(columns), trace_length)
+    }
+}
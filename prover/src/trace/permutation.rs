@@ -0,0 +1,62 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Matrix;
+use air::gadgets::PermutationArgument;
+use math::{batch_inversion, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// PERMUTATION ARGUMENT
+// ================================================================================================
+
+/// Builds the running product column of the specified permutation argument from the provided
+/// main trace segment.
+///
+/// `rand_elements` must be the random elements drawn for the first auxiliary trace segment. The
+/// returned column starts with one, and ends with one if and only if the rows of the argument's
+/// column groups (excluding the last row) are permutations of each other.
+///
+/// # Panics
+/// Panics if fewer than [PermutationArgument::num_rand_elements()] random elements were
+/// provided.
+pub fn build_permutation_column<B, E>(
+    argument: &PermutationArgument,
+    main_trace: &Matrix<B>,
+    rand_elements: &[E],
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert!(
+        rand_elements.len() >= argument.num_rand_elements(),
+        "permutation argument requires {} random elements, but only {} were provided",
+        argument.num_rand_elements(),
+        rand_elements.len()
+    );
+
+    // reduce the rows of both column groups to single values
+    let trace_length = main_trace.num_rows();
+    let mut row = vec![B::ZERO; main_trace.num_cols()];
+    let mut numerators = Vec::with_capacity(trace_length - 1);
+    let mut denominators = Vec::with_capacity(trace_length - 1);
+    for i in 0..trace_length - 1 {
+        main_trace.read_row_into(i, &mut row);
+        numerators.push(argument.reduce_lhs(&row, rand_elements));
+        denominators.push(argument.reduce_rhs(&row, rand_elements));
+    }
+    let denominators = batch_inversion(&denominators);
+
+    // accumulate the running product
+    let mut result = Vec::with_capacity(trace_length);
+    result.push(E::ONE);
+    for (&num, &denom_inv) in numerators.iter().zip(&denominators) {
+        let last = result[result.len() - 1];
+        result.push(last * num * denom_inv);
+    }
+
+    result
+}
@@ -8,7 +8,7 @@ use crate::{
     matrix::{ColumnIter, MultiColumnIter},
     Matrix,
 };
-use math::{log2, FieldElement, StarkField};
+use math::FieldElement;
 use utils::collections::Vec;
 
 // TRACE POLYNOMIAL TABLE
@@ -66,10 +66,16 @@ impl<E: FieldElement> TracePolyTable<E> {
     }
 
     /// Returns an out-of-domain evaluation frame constructed by evaluating trace polynomials
-    /// for all columns at points z and z * g, where g is the generator of the trace domain.
-    pub fn get_ood_frame(&self, z: E) -> Vec<Vec<E>> {
-        let g = E::from(E::BaseField::get_root_of_unity(log2(self.poly_size())));
-        vec![self.evaluate_at(z), self.evaluate_at(z * g)]
+    /// for all columns at points z * g^o for each of the specified frame offsets o, where g is
+    /// the generator of the trace domain.
+    ///
+    /// The generator is passed in explicitly because trace polynomials may be larger than the
+    /// trace domain when they are randomized in zero-knowledge mode.
+    pub fn get_ood_frame(&self, z: E, g: E::BaseField, frame_offsets: &[usize]) -> Vec<Vec<E>> {
+        frame_offsets
+            .iter()
+            .map(|&offset| self.evaluate_at(z * E::from(g.exp((offset as u64).into()))))
+            .collect()
     }
 
     /// Returns an iterator over the polynomials of the main trace segment.
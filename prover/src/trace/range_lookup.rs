@@ -0,0 +1,129 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Matrix;
+use air::gadgets::RangeLookupArgument;
+use math::{batch_inversion, FieldElement, StarkField};
+use utils::collections::Vec;
+
+// RANGE LOOKUP ARGUMENT
+// ================================================================================================
+
+/// Builds the multiplicity column of the specified range lookup argument from the provided main
+/// trace segment.
+///
+/// The values looked up in all but the last row of the trace are counted, and the count of each
+/// table value $j$ is placed into row $j$ of the returned column; all other rows are set to
+/// zero. Values which do not fit into a limb are not counted, and thus the running sum of the
+/// argument will not end with zero for such traces.
+///
+/// The multiplicity column of the main trace segment is ignored, and so it may contain any
+/// values when this function is invoked.
+///
+/// # Panics
+/// Panics if the trace is not longer than the lookup table of the argument.
+pub fn build_range_lookup_multiplicities<B: StarkField>(
+    argument: &RangeLookupArgument,
+    main_trace: &Matrix<B>,
+) -> Vec<B> {
+    let trace_length = main_trace.num_rows();
+    let table_length = argument.table_length();
+    assert!(
+        trace_length > table_length,
+        "trace length must be greater than the lookup table length {table_length}, but was \
+        {trace_length}"
+    );
+
+    let one = B::Representation::from(1u32);
+    let bound = B::Representation::from(table_length as u64);
+    let mut counts = vec![0u64; table_length];
+    let mut row = vec![B::ZERO; main_trace.num_cols()];
+    for i in 0..trace_length - 1 {
+        main_trace.read_row_into(i, &mut row);
+        for value in argument.lookup_values(&row) {
+            let value = value.to_repr();
+            if value < bound {
+                let index = (0..argument.limb_bits())
+                    .filter(|&bit| (value >> bit) & one == one)
+                    .fold(0, |acc, bit| acc | (1 << bit));
+                counts[index] += 1;
+            }
+        }
+    }
+
+    let mut result = vec![B::ZERO; trace_length];
+    for (result, count) in result.iter_mut().zip(counts) {
+        *result = B::from(count);
+    }
+    result
+}
+
+/// Builds the running sum column of the specified range lookup argument from the provided main
+/// trace segment.
+///
+/// `rand_elements` must be the random elements drawn for the first auxiliary trace segment, and
+/// the main trace segment must already contain the limbs and the multiplicities of the
+/// argument. The returned column starts with zero, and ends with zero if and only if all values
+/// looked up in the table (excluding the last row) are accounted for by the multiplicities.
+///
+/// # Panics
+/// Panics if:
+/// * Fewer than [RangeLookupArgument::num_rand_elements()] random elements were provided.
+/// * The trace is not longer than the lookup table of the argument.
+pub fn build_range_lookup_column<B, E>(
+    argument: &RangeLookupArgument,
+    main_trace: &Matrix<B>,
+    rand_elements: &[E],
+) -> Vec<E>
+where
+    B: StarkField,
+    E: FieldElement<BaseField = B>,
+{
+    assert!(
+        rand_elements.len() >= argument.num_rand_elements(),
+        "range lookup argument requires {} random elements, but only {} were provided",
+        argument.num_rand_elements(),
+        rand_elements.len()
+    );
+    let trace_length = main_trace.num_rows();
+    let table_length = argument.table_length();
+    assert!(
+        trace_length > table_length,
+        "trace length must be greater than the lookup table length {table_length}, but was \
+        {trace_length}"
+    );
+
+    // collect the denominators of all terms; the table term of each row comes first, followed
+    // by the terms of the looked up values
+    let alpha = rand_elements[0];
+    let terms_per_row = argument.num_lookups() + 1;
+    let mut row = vec![B::ZERO; main_trace.num_cols()];
+    let mut multiplicities = Vec::with_capacity(trace_length - 1);
+    let mut denominators = Vec::with_capacity((trace_length - 1) * terms_per_row);
+    for i in 0..trace_length - 1 {
+        main_trace.read_row_into(i, &mut row);
+        multiplicities.push(E::from(row[argument.multiplicity_column()]));
+        denominators.push(alpha + E::from((i % table_length) as u64));
+        for value in argument.lookup_values(&row) {
+            denominators.push(alpha + E::from(value));
+        }
+    }
+    let denominators = batch_inversion(&denominators);
+
+    // accumulate the running sum
+    let mut result = Vec::with_capacity(trace_length);
+    result.push(E::ZERO);
+    for (&multiplicity, inverses) in multiplicities
+        .iter()
+        .zip(denominators.chunks(terms_per_row))
+    {
+        let last = result[result.len() - 1];
+        let lookups = inverses[1..].iter().fold(E::ZERO, |acc, &inv| acc + inv);
+        result.push(last + lookups - multiplicity * inverses[0]);
+    }
+
+    result
+}
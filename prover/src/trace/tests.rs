@@ -5,15 +5,19 @@
 // LICENSE file in the root directory of this source tree.
 
 use crate::{
-    tests::{build_fib_trace, MockAir},
+    tests::{build_fib_trace, FibAir, MockAir},
     trace::TracePolyTable,
-    StarkDomain, Trace, TraceCommitment,
+    trace_diff, Air, AuxTraceRandElements, CellDiff, CpuBackend, FieldExtension, Matrix,
+    MultiSegmentTraceBuilder, MultiSegmentTraceTable, PaddingPolicy, ProofOptions,
+    RowMajorTraceBuilder, StarkDomain, Trace, TraceCommitment, TraceLayout, TracePadder,
+    TraceTable, TraceValidationFailure,
 };
 use crypto::{hashers::Blake3_256, ElementHasher, MerkleTree};
 use math::{
     fields::f128::BaseElement, get_power_series, get_power_series_with_offset, log2, polynom,
     FieldElement, StarkField,
 };
+use proptest::prelude::*;
 use utils::collections::Vec;
 
 type Blake3 = Blake3_256<BaseElement>;
@@ -39,6 +43,368 @@ fn new_trace_table() {
     assert_eq!(expected, trace.get_column(1));
 }
 
+#[test]
+fn trace_table_from_rows() {
+    let trace = build_fib_trace(16);
+    let rows = (0..trace.length())
+        .map(|step| vec![trace.get(0, step), trace.get(1, step)])
+        .collect::<Vec<_>>();
+
+    let result = TraceTable::from_rows(rows);
+    assert_eq!(trace.main_trace_width(), result.main_trace_width());
+    assert_eq!(trace.length(), result.length());
+    assert_eq!(trace.get_column(0), result.get_column(0));
+    assert_eq!(trace.get_column(1), result.get_column(1));
+}
+
+#[test]
+fn trace_table_init_with_meta() {
+    let trace = build_fib_trace(16);
+    let columns = vec![trace.get_column(0).to_vec(), trace.get_column(1).to_vec()];
+    let meta = vec![1u8, 2, 3, 4];
+
+    let result = TraceTable::init_with_meta(columns, meta.clone());
+    assert_eq!(meta, result.meta());
+    assert_eq!(trace.get_column(0), result.get_column(0));
+    assert_eq!(meta, result.get_info().meta());
+}
+
+#[test]
+fn row_major_trace_builder() {
+    // build a trace with 40 columns and 11 rows, which should be padded to 16 rows
+    let width = 40;
+    let mut builder = RowMajorTraceBuilder::new(width);
+    for step in 0..11u32 {
+        let row = (0..width as u32)
+            .map(|i| BaseElement::from(step * 100 + i))
+            .collect::<Vec<_>>();
+        builder.push_row(&row);
+    }
+    assert_eq!(11, builder.num_rows());
+
+    let trace = builder.build(|_, state| {
+        state.iter_mut().for_each(|v| *v += BaseElement::ONE);
+    });
+    assert_eq!(width, trace.main_trace_width());
+    assert_eq!(16, trace.length());
+
+    for i in 0..width {
+        let mut expected = (0..11u32)
+            .map(|step| BaseElement::from(step * 100 + i as u32))
+            .collect::<Vec<_>>();
+        for j in 1..6u32 {
+            expected.push(BaseElement::from(1000 + i as u32 + j));
+        }
+        assert_eq!(expected, trace.get_column(i));
+    }
+}
+
+#[test]
+fn row_major_trace_builder_min_length() {
+    let mut builder = RowMajorTraceBuilder::new(1);
+    builder.push_row(&[BaseElement::ONE]);
+    let trace = builder.build(|_, state| state[0] = state[0].double());
+    assert_eq!(8, trace.length());
+    assert_eq!(BaseElement::from(128u32), trace.get(0, 7));
+}
+
+#[test]
+#[should_panic(expected = "expected row with 2 values, but was 3")]
+fn row_major_trace_builder_wrong_width() {
+    let mut builder = RowMajorTraceBuilder::new(2);
+    builder.push_row(&[BaseElement::ONE, BaseElement::ONE, BaseElement::ONE]);
+}
+
+#[test]
+fn trace_table_fragments() {
+    let expected = build_cube_trace(16);
+
+    // the fill closure of equally sized fragments receives indexes local to the fragment
+    let mut trace = TraceTable::new(2, 16);
+    trace.fragments(4).for_each(|mut fragment| {
+        let offset = fragment.offset();
+        fragment.fill(
+            |state| init_cube_state(offset, state),
+            |i, state| update_cube_state(offset + i, state),
+        );
+    });
+    assert_eq!(expected.get_column(0), trace.get_column(0));
+    assert_eq!(expected.get_column(1), trace.get_column(1));
+}
+
+#[test]
+fn trace_table_fragments_with_sizes() {
+    let expected = build_cube_trace(16);
+
+    let mut trace = TraceTable::new(2, 16);
+    let fragments = trace
+        .fragments_with_sizes(&[3, 1, 8, 4])
+        .collect::<Vec<_>>();
+    let offsets = fragments.iter().map(|f| f.offset()).collect::<Vec<_>>();
+    let lengths = fragments.iter().map(|f| f.length()).collect::<Vec<_>>();
+    let indexes = fragments.iter().map(|f| f.index()).collect::<Vec<_>>();
+    assert_eq!(vec![0, 3, 4, 12], offsets);
+    assert_eq!(vec![3, 1, 8, 4], lengths);
+    assert_eq!(vec![0, 1, 2, 3], indexes);
+
+    for mut fragment in fragments {
+        fragment.fill_absolute(init_cube_state, update_cube_state);
+    }
+    assert_eq!(expected.get_column(0), trace.get_column(0));
+    assert_eq!(expected.get_column(1), trace.get_column(1));
+}
+
+#[test]
+fn trace_table_fill_fragments_queued() {
+    let expected = build_cube_trace(32);
+
+    let mut trace = TraceTable::new(2, 32);
+    trace.fill_fragments_queued(&[9, 1, 2, 13, 7], |fragment| {
+        fragment.fill_absolute(init_cube_state, update_cube_state);
+    });
+    assert_eq!(expected.get_column(0), trace.get_column(0));
+    assert_eq!(expected.get_column(1), trace.get_column(1));
+}
+
+#[test]
+#[should_panic(
+    expected = "fragment sizes must add up to the trace length of 16, but added up to 15"
+)]
+fn trace_table_fragments_with_wrong_sizes() {
+    let mut trace = TraceTable::<BaseElement>::new(2, 16);
+    let _ = trace.fragments_with_sizes(&[8, 4, 3]);
+}
+
+#[test]
+#[should_panic(expected = "fragment sizes must be greater than zero")]
+fn trace_table_fragments_with_zero_size() {
+    let mut trace = TraceTable::<BaseElement>::new(2, 16);
+    let _ = trace.fragments_with_sizes(&[8, 0, 8]);
+}
+
+proptest! {
+    #[test]
+    fn trace_table_fragments_with_random_sizes(
+        cuts in prop::collection::btree_set(1..64usize, 0..16)
+    ) {
+        // split 64 rows at random points into fragments of random sizes
+        let mut sizes = Vec::new();
+        let mut start = 0;
+        for cut in cuts.into_iter().chain(core::iter::once(64)) {
+            sizes.push(cut - start);
+            start = cut;
+        }
+
+        let expected = build_cube_trace(64);
+
+        let mut trace = TraceTable::new(2, 64);
+        trace.fragments_with_sizes(&sizes).for_each(|mut fragment| {
+            fragment.fill_absolute(init_cube_state, update_cube_state);
+        });
+        prop_assert_eq!(expected.get_column(0), trace.get_column(0));
+        prop_assert_eq!(expected.get_column(1), trace.get_column(1));
+
+        let mut trace = TraceTable::new(2, 64);
+        trace.fill_fragments_queued(&sizes, |fragment| {
+            fragment.fill_absolute(init_cube_state, update_cube_state);
+        });
+        prop_assert_eq!(expected.get_column(0), trace.get_column(0));
+        prop_assert_eq!(expected.get_column(1), trace.get_column(1));
+    }
+}
+
+#[test]
+fn trace_padder_repeat_last() {
+    let column = (0..1000u32).map(BaseElement::from).collect::<Vec<_>>();
+    let padder = TracePadder::new(PaddingPolicy::RepeatLast);
+    let (trace, length) = padder.pad(vec![column.clone(), column.clone()]);
+    assert_eq!(1000, length);
+    assert_eq!(1024, trace.length());
+    assert_eq!(2, trace.main_trace_width());
+
+    for i in 0..2 {
+        assert_eq!(&column[..], &trace.get_column(i)[..1000]);
+        assert!(trace.get_column(i)[1000..]
+            .iter()
+            .all(|&v| v == BaseElement::from(999u32)));
+    }
+}
+
+#[test]
+fn trace_padder_fill_with() {
+    let padding = vec![BaseElement::ZERO, BaseElement::ONE];
+    let padder = TracePadder::new(PaddingPolicy::FillWith(padding));
+
+    // a single row should be padded to the minimum trace length
+    let (trace, length) = padder.pad(vec![vec![BaseElement::from(5u32)]; 2]);
+    assert_eq!(1, length);
+    assert_eq!(8, trace.length());
+    assert_eq!(BaseElement::from(5u32), trace.get(0, 0));
+    assert_eq!(BaseElement::from(5u32), trace.get(1, 0));
+    for step in 1..8 {
+        assert_eq!(BaseElement::ZERO, trace.get(0, step));
+        assert_eq!(BaseElement::ONE, trace.get(1, step));
+    }
+}
+
+#[test]
+fn trace_padder_compute() {
+    let padder = TracePadder::new(PaddingPolicy::Compute(Box::new(|step, prev, next| {
+        next[0] = prev[0] + BaseElement::from(step as u32);
+    })))
+    .with_min_padding(1);
+
+    // with one padding row required, a trace of 16 rows should be padded to 32 rows
+    let column = vec![BaseElement::ONE; 16];
+    let (trace, length) = padder.pad(vec![column]);
+    assert_eq!(16, length);
+    assert_eq!(32, trace.length());
+
+    let mut expected = BaseElement::ONE;
+    for step in 16..32 {
+        expected += BaseElement::from(step as u32);
+        assert_eq!(expected, trace.get(0, step));
+    }
+}
+
+#[test]
+#[should_panic(expected = "expected padding row with 2 values, but was 1")]
+fn trace_padder_fill_with_wrong_width() {
+    let padder = TracePadder::new(PaddingPolicy::FillWith(vec![BaseElement::ZERO]));
+    padder.pad(vec![vec![BaseElement::ONE; 5]; 2]);
+}
+
+#[test]
+fn multi_segment_trace_table() {
+    let main = build_fib_trace(16);
+    let layout = TraceLayout::new(2, [1, 0], [2, 0]);
+    let mut trace = MultiSegmentTraceTable::new(main, layout, ColumnSumBuilder);
+    assert_eq!(8, trace.length());
+    assert_eq!(2, trace.main_trace_width());
+    assert_eq!(1, trace.aux_trace_width());
+    assert!(trace.get_info().is_multi_segment());
+
+    // the auxiliary segment is built from the main segment and the provided random elements
+    let mut rand_elements = AuxTraceRandElements::new();
+    rand_elements.add_segment_elements(vec![BaseElement::new(3), BaseElement::new(5)]);
+    let aux_segment = trace.build_aux_segment(&[], &rand_elements).unwrap();
+    for step in 0..trace.length() {
+        let expected =
+            BaseElement::new(3) * trace.get(0, step) + BaseElement::new(5) * trace.get(1, step);
+        assert_eq!(expected, aux_segment.get(0, step));
+    }
+
+    // all auxiliary segments defined by the layout have been built
+    assert!(trace
+        .build_aux_segment(&[aux_segment], &rand_elements)
+        .is_none());
+}
+
+#[test]
+#[should_panic(expected = "expected main trace segment of 3 columns, but was 2")]
+fn multi_segment_trace_table_wrong_width() {
+    let layout = TraceLayout::new(3, [1, 0], [2, 0]);
+    MultiSegmentTraceTable::new(build_fib_trace(16), layout, ColumnSumBuilder);
+}
+
+#[test]
+fn validate_trace() {
+    let trace = build_fib_trace(16);
+    let air = build_fib_air(&trace);
+    let no_aux_segments: &[Matrix<BaseElement>] = &[];
+    let rand_elements = AuxTraceRandElements::new();
+    assert!(trace
+        .validate(&air, no_aux_segments, &rand_elements)
+        .is_ok());
+}
+
+#[test]
+fn validate_corrupted_trace() {
+    let mut trace = build_fib_trace(16);
+    let air = build_fib_air(&trace);
+    let no_aux_segments: &[Matrix<BaseElement>] = &[];
+    let rand_elements = AuxTraceRandElements::new();
+
+    // corrupt a single cell: 144 -> 145; this breaks the second constraint on the frame which
+    // ends at the corrupted row, and both constraints on the frame which starts at it
+    trace.set(1, 5, BaseElement::new(145));
+    let report = trace
+        .validate(&air, no_aux_segments, &rand_elements)
+        .unwrap_err();
+    assert_eq!(3, report.num_failures());
+
+    let expected = TraceValidationFailure::MainTransition {
+        constraint: 1,
+        step: 4,
+        evaluation: BaseElement::ONE,
+        frame: vec![
+            vec![BaseElement::new(34), BaseElement::new(55)],
+            vec![BaseElement::new(89), BaseElement::new(145)],
+        ],
+    };
+    assert_eq!(expected, report.failures()[0]);
+    assert!(report.failures()[1..]
+        .iter()
+        .all(|failure| failure.step() == 5));
+
+    // validating a range which does not include the affected frames succeeds
+    assert!(trace
+        .validate_range(&air, no_aux_segments, &rand_elements, 0..4)
+        .is_ok());
+    assert!(trace
+        .validate_range(&air, no_aux_segments, &rand_elements, 6..8)
+        .is_ok());
+
+    // and validating a range which includes only one of the frames reports only it
+    let report = trace
+        .validate_range(&air, no_aux_segments, &rand_elements, 2..5)
+        .unwrap_err();
+    assert_eq!(1, report.num_failures());
+    assert_eq!(expected, report.failures()[0]);
+}
+
+#[test]
+fn validate_trace_assertions() {
+    let mut trace = build_fib_trace(16);
+    let air = build_fib_air(&trace);
+    let no_aux_segments: &[Matrix<BaseElement>] = &[];
+    let rand_elements = AuxTraceRandElements::new();
+
+    // doubling all values keeps the transitions valid, but breaks both assertions
+    for step in 0..trace.length() {
+        trace.set(0, step, trace.get(0, step).double());
+        trace.set(1, step, trace.get(1, step).double());
+    }
+    let report = trace
+        .validate(&air, no_aux_segments, &rand_elements)
+        .unwrap_err();
+    assert_eq!(2, report.num_failures());
+    assert_eq!(
+        TraceValidationFailure::MainAssertion {
+            column: 0,
+            step: 0,
+            expected: BaseElement::ONE,
+            actual: BaseElement::new(2),
+        },
+        report.failures()[0]
+    );
+
+    // assertions outside of the validated range are not checked
+    assert!(trace
+        .validate_range(&air, no_aux_segments, &rand_elements, 1..8)
+        .is_ok());
+}
+
+#[test]
+#[should_panic(expected = "main transition constraint 1 did not evaluate to ZERO at step 4")]
+fn assert_valid_corrupted_trace() {
+    let mut trace = build_fib_trace(16);
+    let air = build_fib_air(&trace);
+    trace.set(1, 5, BaseElement::new(145));
+    let no_aux_segments: &[Matrix<BaseElement>] = &[];
+    trace.assert_valid(&air, no_aux_segments, &AuxTraceRandElements::new());
+}
+
 #[test]
 fn extend_trace_table() {
     // build the trace and the domain
@@ -48,15 +414,31 @@ fn extend_trace_table() {
     let domain = StarkDomain::new(&air);
 
     // build extended trace commitment
-    let trace_polys = trace.main_segment().interpolate_columns();
-    let trace_lde = trace_polys.evaluate_columns_over(&domain);
+    let trace_lde = trace.main_segment().extend_columns(&domain);
     let trace_tree = trace_lde.commit_to_rows::<Blake3>();
     let trace_comm = TraceCommitment::<BaseElement, Blake3>::new(
         trace_lde,
         trace_tree,
+        None,
         domain.trace_to_lde_blowup(),
     );
-    let trace_polys = TracePolyTable::<BaseElement>::new(trace_polys);
+
+    // the extension must be the same as the one computed via an intermediate polynomial matrix
+    let expected_lde = trace
+        .main_segment()
+        .interpolate_columns()
+        .evaluate_columns_over(&domain);
+    for i in 0..trace.main_trace_width() {
+        assert_eq!(
+            expected_lde.get_column(i),
+            trace_comm.get_main_trace_column(i)
+        );
+    }
+
+    // trace polynomials are recovered from the extended trace
+    let trace_polys: TracePolyTable<BaseElement> = trace_comm
+        .trace_table()
+        .interpolate_polys(&domain, &CpuBackend);
 
     assert_eq!(2, trace_comm.trace_table().main_trace_width());
     assert_eq!(64, trace_comm.trace_table().trace_len());
@@ -92,6 +474,55 @@ fn extend_trace_table() {
     );
 }
 
+#[test]
+fn extend_randomized_trace_table() {
+    // in zero-knowledge mode, trace polynomials are extended by 2 * (32 + 1) random coefficients,
+    // and thus, have 128 coefficients for a trace of 8 rows
+    let trace_length = 8;
+    let trace = build_fib_trace(trace_length * 2);
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256).with_zero_knowledge();
+    let air = FibAir::new(trace.get_info(), (), options);
+    let domain = StarkDomain::new(&air);
+    assert_eq!(128, domain.trace_poly_size());
+    assert_eq!(128 * 8, domain.lde_domain_size());
+
+    // every extension is randomized differently
+    let trace_lde = trace
+        .main_segment()
+        .extend_randomized_columns_with(&domain, &CpuBackend);
+    assert_ne!(
+        trace_lde.get_column(0),
+        trace
+            .main_segment()
+            .extend_randomized_columns_with(&domain, &CpuBackend)
+            .get_column(0)
+    );
+
+    // the randomized polynomials have the full degree, but still evaluate to the trace over the
+    // trace domain
+    let trace_polys = trace_lde.interpolate_lde_columns(&domain);
+    let trace_root = BaseElement::get_root_of_unity(log2(trace_length));
+    let trace_domain = get_power_series(trace_root, trace_length);
+    for i in 0..trace.main_trace_width() {
+        let poly = trace_polys.get_column(i);
+        assert_eq!(127, polynom::degree_of(poly));
+        assert_eq!(
+            trace.get_column(i),
+            polynom::eval_many(poly, &trace_domain).as_slice()
+        );
+    }
+
+    // without randomization, the trace polynomials are extended by zero coefficients only
+    let trace_polys = trace
+        .main_segment()
+        .extend_columns(&domain)
+        .interpolate_lde_columns(&domain);
+    assert_eq!(
+        trace_length - 1,
+        polynom::degree_of(trace_polys.get_column(0))
+    );
+}
+
 #[test]
 fn commit_trace_table() {
     // build the trade and the domain
@@ -102,11 +533,12 @@ fn commit_trace_table() {
 
     // build extended trace commitment
     let trace_polys = trace.main_segment().interpolate_columns();
-    let trace_lde = trace_polys.evaluate_columns_over(&domain);
+    let trace_lde = trace_polys.evaluate_columns_over_into(&domain);
     let trace_tree = trace_lde.commit_to_rows::<Blake3>();
     let trace_comm = TraceCommitment::<BaseElement, Blake3>::new(
         trace_lde,
         trace_tree,
+        None,
         domain.trace_to_lde_blowup(),
     );
 
@@ -128,9 +560,134 @@ fn commit_trace_table() {
     assert_eq!(*expected_tree.root(), trace_comm.main_trace_root())
 }
 
+// COLUMN ACCESSORS AND TRACE DIFF
+// ================================================================================================
+
+#[test]
+fn trace_column_accessors() {
+    let main = build_fib_trace(16);
+    let layout = TraceLayout::new(2, [1, 0], [2, 0]);
+    let trace = MultiSegmentTraceTable::new(build_fib_trace(16), layout, ColumnSumBuilder);
+
+    // columns of a multi-segment trace are the columns of its main segment
+    assert_eq!(main.get_column(1), Trace::get_column(&trace, 1));
+
+    let mut target = vec![BaseElement::ZERO; 3];
+    trace.read_column_into(0, 2, &mut target);
+    assert_eq!(&main.get_column(0)[2..5], target);
+    main.read_column_into(1, 5, &mut target);
+    assert_eq!(&main.get_column(1)[5..], target);
+}
+
+#[test]
+fn trace_diff_hand_crafted() {
+    let build_table = || {
+        TraceTable::init(vec![
+            (0..8u32).map(BaseElement::from).collect(),
+            (10..18u32).map(BaseElement::from).collect(),
+        ])
+    };
+    let left = build_table();
+
+    // identical traces have no differences
+    assert!(trace_diff(&left, &build_table(), 10).is_empty());
+
+    // differences are reported row by row
+    let mut right = build_table();
+    right.set(1, 2, BaseElement::new(100));
+    right.set(0, 5, BaseElement::new(200));
+    right.set(1, 5, BaseElement::new(300));
+    let expected = vec![
+        CellDiff {
+            column: 1,
+            row: 2,
+            left: BaseElement::new(12),
+            right: BaseElement::new(100),
+        },
+        CellDiff {
+            column: 0,
+            row: 5,
+            left: BaseElement::new(5),
+            right: BaseElement::new(200),
+        },
+        CellDiff {
+            column: 1,
+            row: 5,
+            left: BaseElement::new(15),
+            right: BaseElement::new(300),
+        },
+    ];
+    assert_eq!(expected, trace_diff(&left, &right, 10));
+    assert_eq!("column 1 at row 2: 12 != 100", expected[0].to_string());
+
+    // the number of reported differences is bounded
+    assert_eq!(expected[..2], trace_diff(&left, &right, 2));
+    assert!(trace_diff(&left, &right, 0).is_empty());
+
+    // traces of different types can be compared as long as their main segments match
+    let layout = TraceLayout::new(2, [1, 0], [2, 0]);
+    let multi = MultiSegmentTraceTable::new(right, layout, ColumnSumBuilder);
+    assert_eq!(expected, trace_diff(&left, &multi, 10));
+}
+
+#[test]
+#[should_panic(expected = "traces must have the same length")]
+fn trace_diff_different_lengths() {
+    let _ = trace_diff(&build_fib_trace(16), &build_fib_trace(32), 10);
+}
+
 // HELPER FUNCTIONS
 // ================================================================================================
 
+/// Builds a single auxiliary column holding a random linear combination of the main columns.
+struct ColumnSumBuilder;
+
+impl MultiSegmentTraceBuilder<BaseElement> for ColumnSumBuilder {
+    fn build_aux_segment<E>(
+        &self,
+        main: &TraceTable<BaseElement>,
+        _aux_segments: &[Matrix<E>],
+        rand_elements: &AuxTraceRandElements<E>,
+    ) -> Option<Matrix<E>>
+    where
+        E: FieldElement<BaseField = BaseElement>,
+    {
+        let alphas = rand_elements.get_segment_elements(0);
+        let column = (0..main.length())
+            .map(|step| {
+                (0..main.width()).fold(E::ZERO, |acc, i| {
+                    acc + alphas[i].mul_base(main.get(i, step))
+                })
+            })
+            .collect();
+        Some(Matrix::new(vec![column]))
+    }
+}
+
+/// Builds a trace in which the first column contains the row index and the second column
+/// contains the cube of the row index; every row depends only on its absolute position in the
+/// trace, and thus, the trace can be filled in fragments.
+fn build_cube_trace(length: usize) -> TraceTable<BaseElement> {
+    let mut trace = TraceTable::new(2, length);
+    trace.fill(|state| init_cube_state(0, state), update_cube_state);
+    trace
+}
+
+fn init_cube_state(step: usize, state: &mut [BaseElement]) {
+    state[0] = BaseElement::from(step as u64);
+    state[1] = state[0].cube();
+}
+
+fn update_cube_state(_step: usize, state: &mut [BaseElement]) {
+    state[0] += BaseElement::ONE;
+    state[1] = state[0].cube();
+}
+
+fn build_fib_air(trace: &TraceTable<BaseElement>) -> FibAir {
+    let options = ProofOptions::new(32, 8, 0, FieldExtension::None, 4, 256);
+    FibAir::new(trace.get_info(), (), options)
+}
+
 fn build_lde_domain<B: StarkField>(domain_size: usize) -> Vec<B> {
     let g = B::get_root_of_unity(log2(domain_size));
     get_power_series_with_offset(g, B::GENERATOR, domain_size)
@@ -4,7 +4,8 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use crate::Matrix;
+use super::TracePolyTable;
+use crate::{FftBackend, Matrix, StarkDomain};
 use air::EvaluationFrame;
 use math::FieldElement;
 use utils::collections::Vec;
@@ -68,33 +69,45 @@ impl<E: FieldElement> TraceLde<E> {
         self.blowup
     }
 
-    /// Reads current and next rows from the main trace segment into the specified frame.
+    /// Reads rows at the specified frame offsets from the main trace segment into the specified
+    /// frame.
+    ///
+    /// Frame offsets are expressed in steps of the execution trace, and thus, the row at offset
+    /// `o` is read from the LDE step `lde_step + o * blowup`.
     pub fn read_main_trace_frame_into(
         &self,
         lde_step: usize,
+        frame_offsets: &[usize],
         frame: &mut EvaluationFrame<E::BaseField>,
     ) {
-        // at the end of the trace, next state wraps around and we read the first step again
-        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
-
-        // copy main trace segment values into the frame
-        self.main_segment_lde
-            .read_row_into(lde_step, frame.current_mut());
-        self.main_segment_lde
-            .read_row_into(next_lde_step, frame.next_mut());
-    }
+        for (i, &frame_offset) in frame_offsets.iter().enumerate() {
+            // at the end of the trace, rows wrap around and we read the first steps again
+            let row_lde_step = (lde_step + frame_offset * self.blowup()) % self.trace_len();
 
-    /// Reads current and next rows from the auxiliary trace segment into the specified frame.
-    pub fn read_aux_trace_frame_into(&self, lde_step: usize, frame: &mut EvaluationFrame<E>) {
-        // at the end of the trace, next state wraps around and we read the first step again
-        let next_lde_step = (lde_step + self.blowup()) % self.trace_len();
+            // copy main trace segment values into the frame
+            self.main_segment_lde
+                .read_row_into(row_lde_step, frame.row_mut(i));
+        }
+    }
 
-        //copy auxiliary trace segment values into the frame
-        let mut offset = 0;
-        for segment in self.aux_segment_ldes.iter() {
-            segment.read_row_into(lde_step, &mut frame.current_mut()[offset..]);
-            segment.read_row_into(next_lde_step, &mut frame.next_mut()[offset..]);
-            offset += segment.num_cols();
+    /// Reads rows at the specified frame offsets from the auxiliary trace segment into the
+    /// specified frame.
+    pub fn read_aux_trace_frame_into(
+        &self,
+        lde_step: usize,
+        frame_offsets: &[usize],
+        frame: &mut EvaluationFrame<E>,
+    ) {
+        for (i, &frame_offset) in frame_offsets.iter().enumerate() {
+            // at the end of the trace, rows wrap around and we read the first steps again
+            let row_lde_step = (lde_step + frame_offset * self.blowup()) % self.trace_len();
+
+            //copy auxiliary trace segment values into the frame
+            let mut offset = 0;
+            for segment in self.aux_segment_ldes.iter() {
+                segment.read_row_into(row_lde_step, &mut frame.row_mut(i)[offset..]);
+                offset += segment.num_cols();
+            }
         }
     }
 
@@ -108,4 +121,33 @@ impl<E: FieldElement> TraceLde<E> {
     pub fn get_aux_segment(&self, aux_segment_idx: usize) -> &Matrix<E> {
         &self.aux_segment_ldes[aux_segment_idx]
     }
+
+    // POLYNOMIALS
+    // --------------------------------------------------------------------------------------------
+
+    /// Interpolates all segments of this trace LDE into trace polynomials in coefficient form.
+    ///
+    /// The polynomials are not kept alongside the trace LDE during constraint evaluation;
+    /// instead, they are recovered from the LDE (which is cheaper than keeping them in memory
+    /// for the duration of proof generation) once they are needed to build the out-of-domain
+    /// frame and the DEEP composition polynomial.
+    ///
+    /// The interpolation is performed using the specified FFT backend.
+    pub fn interpolate_polys<D>(
+        &self,
+        domain: &StarkDomain<E::BaseField>,
+        backend: &D,
+    ) -> TracePolyTable<E>
+    where
+        D: FftBackend<E::BaseField>,
+    {
+        let mut trace_polys = TracePolyTable::new(
+            self.main_segment_lde
+                .interpolate_lde_columns_with(domain, backend),
+        );
+        for segment in self.aux_segment_ldes.iter() {
+            trace_polys.add_aux_segment(segment.interpolate_lde_columns_with(domain, backend));
+        }
+        trace_polys
+    }
 }
@@ -5,13 +5,16 @@
 // LICENSE file in the root directory of this source tree.
 
 use super::{Matrix, Trace};
-use air::{EvaluationFrame, TraceInfo, TraceLayout};
+use air::{AuxTraceRandElements, EvaluationFrame, TraceInfo, TraceLayout};
 use math::{log2, FieldElement, StarkField};
-use utils::{collections::Vec, uninit_vector};
+use utils::collections::Vec;
 
 #[cfg(not(feature = "concurrent"))]
 use utils::collections::vec;
 
+#[cfg(feature = "concurrent")]
+use std::sync::Mutex;
+
 #[cfg(feature = "concurrent")]
 use utils::{iterators::*, rayon};
 
@@ -20,6 +23,9 @@ use utils::{iterators::*, rayon};
 
 const MIN_FRAGMENT_LENGTH: usize = 2;
 
+/// Number of columns transposed together when converting a row-major trace into columns.
+const TRANSPOSE_BLOCK_WIDTH: usize = 16;
+
 // TRACE TABLE
 // ================================================================================================
 /// A concrete implementation of the [Trace] trait.
@@ -45,6 +51,11 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// 2. The second closure receives the previous state of the execution trace as input, and must
 ///    update it to the next state of the computation.
 ///
+/// If the execution trace is naturally produced row by row, you can use
+/// [TraceTable::from_rows()] function, or accumulate rows incrementally using
+/// [RowMajorTraceBuilder]. In both cases, the rows are converted into the column-major layout
+/// in a single pass at the end.
+///
 /// You can also use [TraceTable::with_meta()] function to create a blank execution trace.
 /// This function work just like [TraceTable::new()] function, but also takes a metadata
 /// parameter which can be an arbitrary sequence of bytes up to 64KB in size.
@@ -60,6 +71,16 @@ const MIN_FRAGMENT_LENGTH: usize = 2;
 /// [fill()](TraceTableFragment::fill) method to fill all fragments with data in parallel.
 /// The semantics of the fragment's [TraceTableFragment::fill()] method are identical to the
 /// semantics of the [TraceTable::fill()] method.
+///
+/// If the cost of computing rows varies across the trace, equally sized fragments may take very
+/// different amounts of time to fill. In such cases, you can use
+/// [fragments_with_sizes()](TraceTable::fragments_with_sizes) method to break the trace into
+/// fragments of arbitrary sizes, and fill them via [TraceTableFragment::fill_absolute()] method
+/// which passes row indexes in the context of the entire trace to the closures. Alternatively,
+/// [fill_fragments_queued()](TraceTable::fill_fragments_queued) method places the fragments into
+/// a queue from which all threads take new fragments as soon as they are done with the previous
+/// ones.
+#[derive(Clone)]
 pub struct TraceTable<B: StarkField> {
     layout: TraceLayout,
     trace: Matrix<B>,
@@ -72,12 +93,14 @@ impl<B: StarkField> TraceTable<B> {
 
     /// Creates a new execution trace of the specified width and length.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
-    /// expected that the trace will be filled using one of the data mutator methods.
+    /// This allocates all the required memory for the trace and sets all of its cells to ZERO;
+    /// zero-filled memory is requested from the allocator directly, and thus, this is nearly as
+    /// fast as allocating un-initialized memory. It is expected that the trace will be filled
+    /// using one of the data mutator methods.
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 1024.
     /// * `length` is smaller than 8, greater than biggest multiplicative subgroup in the field
     ///   `B`, or is not a power of two.
     pub fn new(width: usize, length: usize) -> Self {
@@ -87,12 +110,14 @@ impl<B: StarkField> TraceTable<B> {
     /// Creates a new execution trace of the specified width and length, and with the specified
     /// metadata.
     ///
-    /// This allocates all the required memory for the trace, but does not initialize it. It is
-    /// expected that the trace will be filled using one of the data mutator methods.
+    /// This allocates all the required memory for the trace and sets all of its cells to ZERO;
+    /// zero-filled memory is requested from the allocator directly, and thus, this is nearly as
+    /// fast as allocating un-initialized memory. It is expected that the trace will be filled
+    /// using one of the data mutator methods.
     ///
     /// # Panics
     /// Panics if:
-    /// * `width` is zero or greater than 255.
+    /// * `width` is zero or greater than 1024.
     /// * `length` is smaller than 8, greater than the biggest multiplicative subgroup in the
     ///   field `B`, or is not a power of two.
     /// * Length of `meta` is greater than 65535;
@@ -130,9 +155,9 @@ impl<B: StarkField> TraceTable<B> {
             meta.len()
         );
 
-        let columns = unsafe { (0..width).map(|_| uninit_vector(length)).collect() };
+        let columns = (0..width).map(|_| B::zeroed_vector(length)).collect();
         Self {
-            layout: TraceLayout::new(width, [0], [0]),
+            layout: TraceLayout::new(width, [0, 0], [0, 0]),
             trace: Matrix::new(columns),
             meta,
         }
@@ -142,11 +167,25 @@ impl<B: StarkField> TraceTable<B> {
     ///
     /// # Panics
     /// Panics if:
-    /// * The `columns` vector is empty or has over 255 columns.
+    /// * The `columns` vector is empty or has over 1024 columns.
     /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
     ///   multiplicative subgroup in the field `B`, or is not a power of two.
     /// * Number of elements is not identical for all columns.
     pub fn init(columns: Vec<Vec<B>>) -> Self {
+        Self::init_with_meta(columns, vec![])
+    }
+
+    /// Creates a new execution trace from a list of provided trace columns, and with the
+    /// specified metadata.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `columns` vector is empty or has over 1024 columns.
+    /// * Number of elements in any of the columns is smaller than 8, greater than the biggest
+    ///   multiplicative subgroup in the field `B`, or is not a power of two.
+    /// * Number of elements is not identical for all columns.
+    /// * Length of `meta` is greater than 65535.
+    pub fn init_with_meta(columns: Vec<Vec<B>>, meta: Vec<u8>) -> Self {
         assert!(
             !columns.is_empty(),
             "execution trace must consist of at least one column"
@@ -181,12 +220,41 @@ impl<B: StarkField> TraceTable<B> {
                 "all columns traces must have the same length"
             );
         }
+        assert!(
+            meta.len() <= TraceInfo::MAX_META_LENGTH,
+            "number of metadata bytes cannot be greater than {}, but was {}",
+            TraceInfo::MAX_META_LENGTH,
+            meta.len()
+        );
 
         Self {
-            layout: TraceLayout::new(columns.len(), [0], [0]),
+            layout: TraceLayout::new(columns.len(), [0, 0], [0, 0]),
             trace: Matrix::new(columns),
-            meta: vec![],
+            meta,
+        }
+    }
+
+    /// Creates a new execution trace from a list of provided trace rows.
+    ///
+    /// The rows are transposed into the column-major layout used by the trace table in a single
+    /// pass (which is parallelized when `concurrent` feature is enabled).
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * The `rows` vector is empty, or the rows are empty or have over 1024 values.
+    /// * Number of values is not identical for all rows.
+    /// * Number of rows is smaller than 8, greater than the biggest multiplicative subgroup in
+    ///   the field `B`, or is not a power of two.
+    pub fn from_rows(rows: Vec<Vec<B>>) -> Self {
+        assert!(
+            !rows.is_empty(),
+            "execution trace must consist of at least one row"
+        );
+        let mut builder = RowMajorTraceBuilder::with_capacity(rows[0].len(), rows.len());
+        for row in rows.iter() {
+            builder.push_row(row);
         }
+        Self::init(builder.into_columns())
     }
 
     // DATA MUTATORS
@@ -280,6 +348,94 @@ impl<B: StarkField> TraceTable<B> {
         self.build_fragments(fragment_length).into_par_iter()
     }
 
+    /// Breaks the execution trace into mutable fragments of the specified sizes.
+    ///
+    /// The i-th fragment contains `fragment_sizes[i]` rows and starts right after the last row of
+    /// the previous fragment. Unlike with [fragments()](TraceTable::fragments) method, the
+    /// fragments do not need to be of the same size, and their sizes do not need to be powers of
+    /// two. The returned fragments can be used to update data in the trace from multiple threads.
+    ///
+    /// # Panics
+    /// Panics if any of the fragment sizes is zero, or if the fragment sizes do not add up to the
+    /// length of the trace.
+    #[cfg(not(feature = "concurrent"))]
+    pub fn fragments_with_sizes(
+        &mut self,
+        fragment_sizes: &[usize],
+    ) -> vec::IntoIter<TraceTableFragment<B>> {
+        self.build_fragments_with_sizes(fragment_sizes).into_iter()
+    }
+
+    /// Breaks the execution trace into mutable fragments of the specified sizes.
+    ///
+    /// The i-th fragment contains `fragment_sizes[i]` rows and starts right after the last row of
+    /// the previous fragment. Unlike with [fragments()](TraceTable::fragments) method, the
+    /// fragments do not need to be of the same size, and their sizes do not need to be powers of
+    /// two. The returned fragments can be used to update data in the trace from multiple threads.
+    ///
+    /// # Panics
+    /// Panics if any of the fragment sizes is zero, or if the fragment sizes do not add up to the
+    /// length of the trace.
+    #[cfg(feature = "concurrent")]
+    pub fn fragments_with_sizes(
+        &mut self,
+        fragment_sizes: &[usize],
+    ) -> rayon::vec::IntoIter<TraceTableFragment<B>> {
+        self.build_fragments_with_sizes(fragment_sizes)
+            .into_par_iter()
+    }
+
+    /// Breaks the execution trace into fragments of the specified sizes and fills each fragment
+    /// using the provided closure.
+    ///
+    /// The fragments are filled one after another in the order of `fragment_sizes`.
+    ///
+    /// # Panics
+    /// Panics if any of the fragment sizes is zero, or if the fragment sizes do not add up to the
+    /// length of the trace.
+    #[cfg(not(feature = "concurrent"))]
+    pub fn fill_fragments_queued<F>(&mut self, fragment_sizes: &[usize], fill: F)
+    where
+        F: Fn(&mut TraceTableFragment<B>) + Sync,
+    {
+        for mut fragment in self.build_fragments_with_sizes(fragment_sizes) {
+            fill(&mut fragment);
+        }
+    }
+
+    /// Breaks the execution trace into fragments of the specified sizes and fills each fragment
+    /// using the provided closure.
+    ///
+    /// The fragments are placed into a queue in the order of `fragment_sizes`. One task per
+    /// thread of the current thread pool takes fragments from the queue and fills them until the
+    /// queue is empty. Thus, a thread which is done with a cheap fragment moves on to the next
+    /// one rather than waiting for other threads, and all threads stay busy even if the cost of
+    /// filling fragments varies widely. Putting the most expensive fragments at the front of the
+    /// queue usually gives the best balance.
+    ///
+    /// # Panics
+    /// Panics if any of the fragment sizes is zero, or if the fragment sizes do not add up to the
+    /// length of the trace.
+    #[cfg(feature = "concurrent")]
+    pub fn fill_fragments_queued<F>(&mut self, fragment_sizes: &[usize], fill: F)
+    where
+        F: Fn(&mut TraceTableFragment<B>) + Sync,
+    {
+        let queue = Mutex::new(self.build_fragments_with_sizes(fragment_sizes).into_iter());
+        rayon::scope(|s| {
+            for _ in 0..rayon::current_num_threads() {
+                s.spawn(|_| loop {
+                    // the lock is released before the fragment is filled
+                    let fragment = queue.lock().unwrap().next();
+                    match fragment {
+                        Some(mut fragment) => fill(&mut fragment),
+                        None => break,
+                    }
+                });
+            }
+        });
+    }
+
     /// Returns a vector of trace fragments each covering the number of steps specified by the
     /// `fragment_length` parameter.
     fn build_fragments(&mut self, fragment_length: usize) -> Vec<TraceTableFragment<B>> {
@@ -298,21 +454,53 @@ impl<B: StarkField> TraceTable<B> {
             "fragment length must be a power of 2"
         );
         let num_fragments = self.length() / fragment_length;
+        self.build_fragments_with_sizes(&vec![fragment_length; num_fragments])
+    }
+
+    /// Returns a vector of trace fragments where the i-th fragment covers the number of steps
+    /// specified by `fragment_sizes[i]`.
+    fn build_fragments_with_sizes(
+        &mut self,
+        fragment_sizes: &[usize],
+    ) -> Vec<TraceTableFragment<B>> {
+        assert!(
+            fragment_sizes.iter().all(|&size| size > 0),
+            "fragment sizes must be greater than zero"
+        );
+        let total_size: usize = fragment_sizes.iter().sum();
+        assert_eq!(
+            total_size,
+            self.length(),
+            "fragment sizes must add up to the trace length of {}, but added up to {}",
+            self.length(),
+            total_size
+        );
 
-        let mut fragment_data = (0..num_fragments).map(|_| Vec::new()).collect::<Vec<_>>();
-        self.trace.columns_mut().for_each(|column| {
-            for (i, fragment) in column.chunks_mut(fragment_length).enumerate() {
-                fragment_data[i].push(fragment);
+        let mut fragment_data = fragment_sizes
+            .iter()
+            .map(|_| Vec::new())
+            .collect::<Vec<_>>();
+        self.trace.columns_mut().for_each(|mut column| {
+            for (data, &size) in fragment_data.iter_mut().zip(fragment_sizes) {
+                let (fragment, rest) = core::mem::take(&mut column).split_at_mut(size);
+                data.push(fragment);
+                column = rest;
             }
         });
 
+        let mut offset = 0;
         fragment_data
             .into_iter()
+            .zip(fragment_sizes)
             .enumerate()
-            .map(|(i, data)| TraceTableFragment {
-                index: i,
-                offset: i * fragment_length,
-                data,
+            .map(|(i, (data, &size))| {
+                let fragment = TraceTableFragment {
+                    index: i,
+                    offset,
+                    data,
+                };
+                offset += size;
+                fragment
             })
             .collect()
     }
@@ -359,10 +547,16 @@ impl<B: StarkField> Trace for TraceTable<B> {
         &self.meta
     }
 
-    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<Self::BaseField>) {
-        let next_row_idx = (row_idx + 1) % self.length();
-        self.trace.read_row_into(row_idx, frame.current_mut());
-        self.trace.read_row_into(next_row_idx, frame.next_mut());
+    fn read_main_frame(
+        &self,
+        row_idx: usize,
+        frame_offsets: &[usize],
+        frame: &mut EvaluationFrame<Self::BaseField>,
+    ) {
+        for (i, &frame_offset) in frame_offsets.iter().enumerate() {
+            let frame_row_idx = (row_idx + frame_offset) % self.length();
+            self.trace.read_row_into(frame_row_idx, frame.row_mut(i));
+        }
     }
 
     fn main_segment(&self) -> &Matrix<B> {
@@ -372,7 +566,7 @@ impl<B: StarkField> Trace for TraceTable<B> {
     fn build_aux_segment<E>(
         &mut self,
         _aux_segments: &[Matrix<E>],
-        _rand_elements: &[E],
+        _rand_elements: &AuxTraceRandElements<E>,
     ) -> Option<Matrix<E>>
     where
         E: FieldElement<BaseField = Self::BaseField>,
@@ -389,10 +583,10 @@ impl<B: StarkField> Trace for TraceTable<B> {
 /// the fragment, directly updates the data in the underlying execution trace.
 ///
 /// A fragment cannot be instantiated directly but is created by executing
-/// [TraceTable::fragments()] method.
+/// [TraceTable::fragments()] or [TraceTable::fragments_with_sizes()] methods.
 ///
-/// A fragment always contains contiguous rows, and the number of rows is guaranteed to be a power
-/// of two.
+/// A fragment always contains contiguous rows. For fragments created via
+/// [TraceTable::fragments()] method, the number of rows is guaranteed to be a power of two.
 pub struct TraceTableFragment<'a, B: StarkField> {
     index: usize,
     offset: usize,
@@ -442,13 +636,33 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
     where
         I: Fn(&mut [B]),
         T: Fn(usize, &mut [B]),
+    {
+        let offset = self.offset;
+        self.fill_absolute(
+            |_, state| init_state(state),
+            |step, state| update_state(step - offset, state),
+        );
+    }
+
+    /// Fills all rows in the fragment passing row indexes in the context of the original
+    /// execution trace to the closures.
+    ///
+    /// This works just like [fill()](TraceTableFragment::fill) method, except that:
+    /// - `init` closure also receives the step at which the fragment starts in the original
+    ///   execution trace (i.e., the [offset()](TraceTableFragment::offset) of the fragment).
+    /// - `update` closure receives the step of the last updated row in the original execution
+    ///   trace, rather than its index within the fragment.
+    pub fn fill_absolute<I, T>(&mut self, init_state: I, update_state: T)
+    where
+        I: Fn(usize, &mut [B]),
+        T: Fn(usize, &mut [B]),
     {
         let mut state = vec![B::ZERO; self.width()];
-        init_state(&mut state);
+        init_state(self.offset, &mut state);
         self.update_row(0, &state);
 
         for i in 0..self.length() - 1 {
-            update_state(i, &mut state);
+            update_state(self.offset + i, &mut state);
             self.update_row(i + 1, &state);
         }
     }
@@ -460,3 +674,166 @@ impl<'a, B: StarkField> TraceTableFragment<'a, B> {
         }
     }
 }
+
+// ROW-MAJOR TRACE BUILDER
+// ================================================================================================
+/// A builder of [TraceTable] which accepts execution trace rows one at a time.
+///
+/// This is useful when the execution trace is naturally produced row by row (e.g., when it is
+/// read from a file). The rows are stored contiguously in a row-major buffer, and are converted
+/// into the column-major layout of the [TraceTable] in a single cache-friendly pass when the
+/// trace table is built. When `concurrent` feature is enabled, the conversion is done in
+/// multiple threads.
+///
+/// The number of pushed rows does not need to be a power of two: the
+/// [build()](RowMajorTraceBuilder::build) method pads the trace to the next power of two (but
+/// to no fewer than 8 rows) using the provided closure.
+pub struct RowMajorTraceBuilder<B: StarkField> {
+    width: usize,
+    values: Vec<B>,
+}
+
+impl<B: StarkField> RowMajorTraceBuilder<B> {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new builder for an execution trace of the specified width.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero or greater than 1024.
+    pub fn new(width: usize) -> Self {
+        Self::with_capacity(width, 0)
+    }
+
+    /// Returns a new builder for an execution trace of the specified width with memory
+    /// pre-allocated for the specified number of rows.
+    ///
+    /// # Panics
+    /// Panics if `width` is zero or greater than 1024.
+    pub fn with_capacity(width: usize, num_rows: usize) -> Self {
+        assert!(
+            width > 0,
+            "execution trace must consist of at least one column"
+        );
+        assert!(
+            width <= TraceInfo::MAX_TRACE_WIDTH,
+            "execution trace width cannot be greater than {}, but was {}",
+            TraceInfo::MAX_TRACE_WIDTH,
+            width
+        );
+        Self {
+            width,
+            values: Vec::with_capacity(width * num_rows),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of columns in the execution trace being built.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows pushed into this builder so far.
+    pub fn num_rows(&self) -> usize {
+        self.values.len() / self.width
+    }
+
+    // DATA MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Appends the specified row to the end of the execution trace.
+    ///
+    /// # Panics
+    /// Panics if the number of values in `row` is not equal to the width of the trace.
+    pub fn push_row(&mut self, row: &[B]) {
+        assert_eq!(
+            row.len(),
+            self.width,
+            "expected row with {} values, but was {}",
+            self.width,
+            row.len()
+        );
+        self.values.extend_from_slice(row);
+    }
+
+    // TRACE BUILDING
+    // --------------------------------------------------------------------------------------------
+
+    /// Pads the execution trace to the next power of two and converts it into a [TraceTable].
+    ///
+    /// The trace is padded to the smallest power of two which is greater than or equal to the
+    /// number of pushed rows, but to no fewer than 8 rows. Padding rows are populated using the
+    /// `pad` closure, which receives two parameters:
+    /// - index of the last row in the trace.
+    /// - a mutable reference to the values of the last row; the contents of the state are copied
+    ///   into the next row of the trace after the closure returns.
+    ///
+    /// # Panics
+    /// Panics if:
+    /// * No rows were pushed into this builder.
+    /// * The padded length of the trace is greater than the biggest multiplicative subgroup in
+    ///   the field `B`.
+    pub fn build<P>(mut self, mut pad: P) -> TraceTable<B>
+    where
+        P: FnMut(usize, &mut [B]),
+    {
+        let num_rows = self.num_rows();
+        assert!(
+            num_rows > 0,
+            "execution trace must consist of at least one row"
+        );
+
+        let trace_length = num_rows
+            .next_power_of_two()
+            .max(TraceInfo::MIN_TRACE_LENGTH);
+        self.values.reserve((trace_length - num_rows) * self.width);
+        let mut state = self.values[(num_rows - 1) * self.width..].to_vec();
+        for step in num_rows - 1..trace_length - 1 {
+            pad(step, &mut state);
+            self.values.extend_from_slice(&state);
+        }
+
+        TraceTable::init(self.into_columns())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Transposes the rows accumulated in this builder into columns.
+    ///
+    /// Columns are processed in blocks of [TRANSPOSE_BLOCK_WIDTH] columns so that, for each
+    /// row, the values of the block are read from a contiguous region of memory and written
+    /// sequentially into each column; blocks are processed in parallel when `concurrent`
+    /// feature is enabled.
+    fn into_columns(self) -> Vec<Vec<B>> {
+        let width = self.width;
+        let num_rows = self.num_rows();
+        let values = self.values;
+
+        let mut columns: Vec<Vec<B>> = (0..width).map(|_| B::zeroed_vector(num_rows)).collect();
+        let transpose_block = |(block_idx, block): (usize, &mut [Vec<B>])| {
+            let first_column = block_idx * TRANSPOSE_BLOCK_WIDTH;
+            for (row_idx, row) in values.chunks(width).enumerate() {
+                for (column, &value) in block.iter_mut().zip(&row[first_column..]) {
+                    column[row_idx] = value;
+                }
+            }
+        };
+
+        #[cfg(not(feature = "concurrent"))]
+        columns
+            .chunks_mut(TRANSPOSE_BLOCK_WIDTH)
+            .enumerate()
+            .for_each(transpose_block);
+
+        #[cfg(feature = "concurrent")]
+        columns
+            .par_chunks_mut(TRANSPOSE_BLOCK_WIDTH)
+            .enumerate()
+            .for_each(transpose_block);
+
+        columns
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::EvaluationFrame;
+use core::fmt;
+use math::FieldElement;
+use utils::collections::Vec;
+
+// TRACE VALIDATION FAILURE
+// ================================================================================================
+/// Describes a single constraint which was not satisfied by an execution trace.
+///
+/// Values in auxiliary trace segments (and evaluations of auxiliary constraints) are elements in
+/// the extension field `E`; all other values are elements in the base field of `E`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraceValidationFailure<E: FieldElement> {
+    /// An assertion against the main trace segment was not satisfied.
+    MainAssertion {
+        /// Index of the column against which the assertion was placed.
+        column: usize,
+        /// Step at which the assertion was placed.
+        step: usize,
+        /// Value expected by the assertion.
+        expected: E::BaseField,
+        /// Value found in the trace.
+        actual: E::BaseField,
+    },
+    /// An assertion against auxiliary trace segments was not satisfied. The column index is the
+    /// index across all auxiliary segments.
+    AuxAssertion {
+        /// Index of the column against which the assertion was placed.
+        column: usize,
+        /// Step at which the assertion was placed.
+        step: usize,
+        /// Value expected by the assertion.
+        expected: E,
+        /// Value found in the trace.
+        actual: E,
+    },
+    /// A transition constraint against the main trace segment did not evaluate to zero.
+    MainTransition {
+        /// Index of the constraint as evaluated by the AIR.
+        constraint: usize,
+        /// Step at which the constraint was evaluated.
+        step: usize,
+        /// Value to which the constraint evaluated.
+        evaluation: E::BaseField,
+        /// Rows of the main evaluation frame over which the constraint was evaluated.
+        frame: Vec<Vec<E::BaseField>>,
+    },
+    /// A transition constraint against auxiliary trace segments did not evaluate to zero.
+    AuxTransition {
+        /// Index of the constraint as evaluated by the AIR.
+        constraint: usize,
+        /// Step at which the constraint was evaluated.
+        step: usize,
+        /// Value to which the constraint evaluated.
+        evaluation: E,
+        /// Rows of the main evaluation frame over which the constraint was evaluated.
+        main_frame: Vec<Vec<E::BaseField>>,
+        /// Rows of the auxiliary evaluation frame over which the constraint was evaluated.
+        aux_frame: Vec<Vec<E>>,
+    },
+}
+
+impl<E: FieldElement> TraceValidationFailure<E> {
+    /// Returns the step of the trace at which this failure occurred.
+    pub fn step(&self) -> usize {
+        match self {
+            Self::MainAssertion { step, .. }
+            | Self::AuxAssertion { step, .. }
+            | Self::MainTransition { step, .. }
+            | Self::AuxTransition { step, .. } => *step,
+        }
+    }
+}
+
+impl<E: FieldElement> fmt::Display for TraceValidationFailure<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MainAssertion {
+                column,
+                step,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "trace does not satisfy assertion main_trace({column}, {step}) == {expected}; \
+                    found {actual}"
+                )
+            }
+            Self::AuxAssertion {
+                column,
+                step,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "trace does not satisfy assertion aux_trace({column}, {step}) == {expected}; \
+                    found {actual}"
+                )
+            }
+            Self::MainTransition {
+                constraint,
+                step,
+                evaluation,
+                frame,
+            } => {
+                write!(
+                    f,
+                    "main transition constraint {constraint} did not evaluate to ZERO at step \
+                    {step}; evaluated to {evaluation} over frame "
+                )?;
+                write_frame(f, frame)
+            }
+            Self::AuxTransition {
+                constraint,
+                step,
+                evaluation,
+                main_frame,
+                aux_frame,
+            } => {
+                write!(
+                    f,
+                    "auxiliary transition constraint {constraint} did not evaluate to ZERO at step \
+                    {step}; evaluated to {evaluation} over main frame "
+                )?;
+                write_frame(f, main_frame)?;
+                write!(f, " and auxiliary frame ")?;
+                write_frame(f, aux_frame)
+            }
+        }
+    }
+}
+
+// TRACE VALIDATION REPORT
+// ================================================================================================
+/// A list of constraints which were not satisfied by an execution trace.
+///
+/// A report is returned by [Trace::validate()](super::Trace::validate) and
+/// [Trace::validate_range()](super::Trace::validate_range) methods. To keep the report
+/// manageable for large traces, at most [MAX_FAILURES](Self::MAX_FAILURES) failures are recorded;
+/// failures beyond that are only counted. Failures are recorded in the order in which they were
+/// found: assertions first, and then transition constraints ordered by step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceValidationReport<E: FieldElement> {
+    failures: Vec<TraceValidationFailure<E>>,
+    num_failures: usize,
+}
+
+impl<E: FieldElement> TraceValidationReport<E> {
+    /// Maximum number of failures recorded in a report.
+    pub const MAX_FAILURES: usize = 32;
+
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new report without any failures.
+    pub(super) fn new() -> Self {
+        Self {
+            failures: Vec::new(),
+            num_failures: 0,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the recorded failures.
+    pub fn failures(&self) -> &[TraceValidationFailure<E>] {
+        &self.failures
+    }
+
+    /// Returns the total number of failures found during validation; this may be greater than
+    /// the number of recorded failures.
+    pub fn num_failures(&self) -> usize {
+        self.num_failures
+    }
+
+    /// Returns true if no failures were found during validation.
+    pub fn is_empty(&self) -> bool {
+        self.num_failures == 0
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Records the specified failure unless the maximum number of failures has been reached.
+    pub(super) fn add_failure(&mut self, failure: TraceValidationFailure<E>) {
+        if self.failures.len() < Self::MAX_FAILURES {
+            self.failures.push(failure);
+        }
+        self.num_failures += 1;
+    }
+
+    /// Records a failed main transition constraint; the frame is copied only if the failure is
+    /// recorded.
+    pub(super) fn add_main_transition_failure(
+        &mut self,
+        constraint: usize,
+        step: usize,
+        evaluation: E::BaseField,
+        frame: &EvaluationFrame<E::BaseField>,
+    ) {
+        let frame = if self.failures.len() < Self::MAX_FAILURES {
+            frame_to_rows(frame)
+        } else {
+            Vec::new()
+        };
+        self.add_failure(TraceValidationFailure::MainTransition {
+            constraint,
+            step,
+            evaluation,
+            frame,
+        });
+    }
+
+    /// Records a failed auxiliary transition constraint; the frames are copied only if the
+    /// failure is recorded.
+    pub(super) fn add_aux_transition_failure(
+        &mut self,
+        constraint: usize,
+        step: usize,
+        evaluation: E,
+        main_frame: &EvaluationFrame<E::BaseField>,
+        aux_frame: &EvaluationFrame<E>,
+    ) {
+        let (main_frame, aux_frame) = if self.failures.len() < Self::MAX_FAILURES {
+            (frame_to_rows(main_frame), frame_to_rows(aux_frame))
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        self.add_failure(TraceValidationFailure::AuxTransition {
+            constraint,
+            step,
+            evaluation,
+            main_frame,
+            aux_frame,
+        });
+    }
+}
+
+impl<E: FieldElement> fmt::Display for TraceValidationReport<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "trace validation failed with {} failure(s)",
+            self.num_failures
+        )?;
+        for failure in self.failures.iter() {
+            write!(f, "\n  {failure}")?;
+        }
+        if self.num_failures > self.failures.len() {
+            write!(
+                f,
+                "\n  ... and {} more",
+                self.num_failures - self.failures.len()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn frame_to_rows<E: FieldElement>(frame: &EvaluationFrame<E>) -> Vec<Vec<E>> {
+    (0..frame.num_rows())
+        .map(|i| frame.row(i).to_vec())
+        .collect()
+}
+
+fn write_frame<E: FieldElement>(f: &mut fmt::Formatter<'_>, rows: &[Vec<E>]) -> fmt::Result {
+    write!(f, "[")?;
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "[")?;
+        for (j, value) in row.iter().enumerate() {
+            if j > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{value}")?;
+        }
+        write!(f, "]")?;
+    }
+    write!(f, "]")
+}
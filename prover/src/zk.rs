@@ -0,0 +1,60 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use math::FieldElement;
+use utils::collections::Vec;
+
+// TRACE RANDOMIZATION
+// ================================================================================================
+
+/// Randomizes the specified trace polynomial in place.
+///
+/// The polynomial `poly` is expected to be in coefficient form and to be zero-padded to the trace
+/// polynomial size `m`; it is updated to $T(x) + (x^n - c^n) \cdot r(x)$, where $n$ is
+/// `trace_length`, $c$ is `trace_offset` (thus, $x^n - c^n$ vanishes over the trace domain), and
+/// $r(x)$ is a random polynomial of degree $m - n - 1$. Thus, the values of the polynomial over the
+/// trace domain remain unchanged.
+pub fn randomize_trace_poly<E: FieldElement>(
+    poly: &mut [E],
+    trace_length: usize,
+    trace_offset: E::BaseField,
+) {
+    debug_assert!(poly.len() > trace_length);
+    let offset_exp = E::from(trace_offset.exp((trace_length as u64).into()));
+    let randomizer = draw_random_elements::<E>(poly.len() - trace_length);
+    for (i, &r) in randomizer.iter().enumerate() {
+        poly[i + trace_length] += r;
+        poly[i] -= offset_exp * r;
+    }
+}
+
+// RANDOMNESS
+// ================================================================================================
+
+/// Returns the specified number of field elements drawn from a cryptographically secure random
+/// number generator seeded by the operating system.
+#[cfg(feature = "std")]
+pub fn draw_random_elements<E: FieldElement>(num_elements: usize) -> Vec<E> {
+    use rand::{thread_rng, RngCore};
+
+    let mut rng = thread_rng();
+    let mut bytes = vec![0u8; E::VALUE_SIZE];
+    (0..num_elements)
+        .map(|_| loop {
+            rng.fill_bytes(&mut bytes);
+            if let Some(element) = E::from_random_bytes(&bytes) {
+                break element;
+            }
+        })
+        .collect()
+}
+
+/// Random elements cannot be drawn without a source of randomness; this is never called because
+/// the prover rejects zero-knowledge options when the `std` feature is disabled.
+#[cfg(not(feature = "std"))]
+pub fn draw_random_elements<E: FieldElement>(_num_elements: usize) -> Vec<E> {
+    unreachable!("random elements can be drawn only when the std feature is enabled")
+}
@@ -0,0 +1,148 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::{collections::Vec, ByteReader, ByteWriter, DeserializationError};
+use core::convert::{TryFrom, TryInto};
+
+// CANONICAL SERIALIZABLE
+// ================================================================================================
+
+/// Defines a canonical encoding of `Self` into bytes.
+///
+/// An encoding is canonical if every value of `Self` is serialized into exactly one sequence of
+/// bytes, and this sequence is the only one which deserializes into the same value (see
+/// [CanonicalDeserializable]). This is a requirement for values absorbed into the transcript of
+/// a proof (e.g., public inputs): otherwise, the same statement could be bound to the proof in
+/// several different ways.
+///
+/// This trait is implemented for unsigned integers, booleans, arrays and vectors of values with
+/// a canonical encoding, and for field elements and curve points defined in `winter-math`. Types
+/// which cannot guarantee canonical encodings, such as `usize` whose width is platform dependent,
+/// do not implement this trait. The trait is also implemented for all types deriving
+/// `Serializable` via the `derive` feature of this crate.
+pub trait CanonicalSerializable {
+    /// Writes the canonical encoding of `self` into the `target`.
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W);
+}
+
+/// Defines how to deserialize `Self` from its canonical encoding.
+///
+/// Implementations must reject all byte sequences which are not canonical encodings of `Self`
+/// (see [CanonicalSerializable]).
+pub trait CanonicalDeserializable: Sized {
+    /// Reads the canonical encoding of `Self` from the `source`.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// * The `source` does not contain enough bytes to deserialize `Self`.
+    /// * Bytes read from the `source` are not a canonical encoding of any value of `Self`.
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError>;
+}
+
+// PRIMITIVE TYPES
+// ================================================================================================
+
+impl CanonicalSerializable for () {
+    fn write_canonical<W: ByteWriter>(&self, _target: &mut W) {}
+}
+
+impl CanonicalDeserializable for () {
+    fn read_canonical<R: ByteReader>(_source: &mut R) -> Result<Self, DeserializationError> {
+        Ok(())
+    }
+}
+
+impl CanonicalSerializable for bool {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(*self as u8);
+    }
+}
+
+impl CanonicalDeserializable for bool {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(DeserializationError::InvalidValue(format!(
+                "{value} is not a valid boolean value"
+            ))),
+        }
+    }
+}
+
+macro_rules! impl_canonical_uint {
+    ($t:ty, $read:ident) => {
+        impl CanonicalSerializable for $t {
+            fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+                target.write_u8_slice(&self.to_le_bytes());
+            }
+        }
+
+        impl CanonicalDeserializable for $t {
+            fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+                source.$read()
+            }
+        }
+    };
+}
+
+impl_canonical_uint!(u8, read_u8);
+impl_canonical_uint!(u16, read_u16);
+impl_canonical_uint!(u32, read_u32);
+impl_canonical_uint!(u64, read_u64);
+impl_canonical_uint!(u128, read_u128);
+
+// COLLECTIONS
+// ================================================================================================
+
+impl<T: CanonicalSerializable, const N: usize> CanonicalSerializable for [T; N] {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        for item in self.iter() {
+            item.write_canonical(target);
+        }
+    }
+}
+
+impl<T: CanonicalDeserializable, const N: usize> CanonicalDeserializable for [T; N] {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let mut result = Vec::with_capacity(N);
+        for _ in 0..N {
+            result.push(T::read_canonical(source)?);
+        }
+        match result.try_into() {
+            Ok(result) => Ok(result),
+            Err(_) => unreachable!("vector of {} elements must convert into an array", N),
+        }
+    }
+}
+
+/// Vectors are encoded as the number of their elements (as a u64 value) followed by the canonical
+/// encodings of the elements; thus, vectors of different lengths can never have the same
+/// encoding.
+impl<T: CanonicalSerializable> CanonicalSerializable for Vec<T> {
+    fn write_canonical<W: ByteWriter>(&self, target: &mut W) {
+        (self.len() as u64).write_canonical(target);
+        for item in self.iter() {
+            item.write_canonical(target);
+        }
+    }
+}
+
+impl<T: CanonicalDeserializable> CanonicalDeserializable for Vec<T> {
+    fn read_canonical<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let len = source.read_u64()?;
+        let len = usize::try_from(len).map_err(|_| {
+            DeserializationError::InvalidValue(format!("vector length {len} is too big"))
+        })?;
+
+        // the length is not trusted, so the vector is grown as elements are read
+        let mut result = Vec::new();
+        for _ in 0..len {
+            result.push(T::read_canonical(source)?);
+        }
+        Ok(result)
+    }
+}
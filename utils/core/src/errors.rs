@@ -21,6 +21,8 @@ pub enum DeserializationError {
     UnconsumedBytes,
     /// An unknown error has occurred.
     UnknownError(String),
+    /// The input was serialized using a format version which is not supported.
+    UnsupportedVersion(u8),
 }
 
 impl fmt::Display for DeserializationError {
@@ -39,6 +41,9 @@ impl fmt::Display for DeserializationError {
             Self::UnknownError(err_msg) => {
                 write!(f, "unknown error: {err_msg}")
             }
+            Self::UnsupportedVersion(version) => {
+                write!(f, "format version {version} is not supported")
+            }
         }
     }
 }
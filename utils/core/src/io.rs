@@ -0,0 +1,213 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{ByteReader, ByteWriter, DeserializationError};
+use std::io::{self, Read, Write};
+
+// WRITE ADAPTER
+// ================================================================================================
+
+/// Implements [ByteWriter] trait for any type implementing [std::io::Write] trait.
+///
+/// This makes it possible to serialize values directly into files, sockets, or buffered writers
+/// without first building the entire serialized representation in memory.
+///
+/// Since methods of the [ByteWriter] trait cannot return errors, the first I/O error encountered
+/// by the adapter is recorded and all subsequent writes are skipped. The recorded error is
+/// returned by [finish()](WriteAdapter::finish).
+pub struct WriteAdapter<W: Write> {
+    target: W,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> WriteAdapter<W> {
+    /// Returns a new adapter which writes bytes into the specified `target`.
+    pub fn new(target: W) -> Self {
+        WriteAdapter {
+            target,
+            error: None,
+        }
+    }
+
+    /// Flushes the underlying writer and returns it.
+    ///
+    /// # Errors
+    /// Returns an error if any of the previous writes into the underlying writer has failed, or
+    /// if the underlying writer could not be flushed.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.target.flush()?;
+        Ok(self.target)
+    }
+}
+
+impl<W: Write> ByteWriter for WriteAdapter<W> {
+    fn write_u8(&mut self, value: u8) {
+        self.write_u8_slice(&[value]);
+    }
+
+    fn write_u8_slice(&mut self, values: &[u8]) {
+        if self.error.is_none() {
+            if let Err(err) = self.target.write_all(values) {
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
+// READ ADAPTER
+// ================================================================================================
+
+/// Implements [ByteReader] trait for any type implementing [std::io::Read] trait.
+///
+/// The adapter always reads one byte ahead of the consumer so that
+/// [has_more_bytes()](ByteReader::has_more_bytes) can be answered without mutating the reader.
+/// For best performance, the underlying reader should be buffered (e.g. wrapped into
+/// [std::io::BufReader]).
+pub struct ReadAdapter<R: Read> {
+    source: R,
+    next_byte: Option<u8>,
+    error: Option<io::Error>,
+}
+
+impl<R: Read> ReadAdapter<R> {
+    /// Returns a new adapter which reads bytes from the specified `source`.
+    pub fn new(source: R) -> Self {
+        let mut result = ReadAdapter {
+            source,
+            next_byte: None,
+            error: None,
+        };
+        result.read_next_byte();
+        result
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Note that a byte read ahead from the underlying reader (if any) is discarded.
+    pub fn into_inner(self) -> R {
+        self.source
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Fills the specified buffer with bytes read from the underlying reader.
+    fn read_into(&mut self, buf: &mut [u8]) -> Result<(), DeserializationError> {
+        if buf.is_empty() {
+            return Ok(());
+        }
+
+        buf[0] = self.take_next_byte()?;
+        self.source
+            .read_exact(&mut buf[1..])
+            .map_err(map_io_error)?;
+        self.read_next_byte();
+        Ok(())
+    }
+
+    /// Returns the byte read ahead from the underlying reader, or an error if the underlying
+    /// reader is exhausted or has failed.
+    fn take_next_byte(&mut self) -> Result<u8, DeserializationError> {
+        if let Some(err) = self.error.take() {
+            return Err(map_io_error(err));
+        }
+        self.next_byte
+            .take()
+            .ok_or(DeserializationError::UnexpectedEOF)
+    }
+
+    /// Reads the next byte from the underlying reader and caches it in the adapter.
+    fn read_next_byte(&mut self) {
+        let mut byte = [0u8; 1];
+        loop {
+            match self.source.read(&mut byte) {
+                Ok(0) => {
+                    self.next_byte = None;
+                    return;
+                }
+                Ok(_) => {
+                    self.next_byte = Some(byte[0]);
+                    return;
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    self.next_byte = None;
+                    self.error = Some(err);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> ByteReader for ReadAdapter<R> {
+    fn read_u8(&mut self) -> Result<u8, DeserializationError> {
+        let result = self.take_next_byte()?;
+        self.read_next_byte();
+        Ok(result)
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DeserializationError> {
+        self.read_u8_array().map(u16::from_le_bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DeserializationError> {
+        self.read_u8_array().map(u32::from_le_bytes)
+    }
+
+    fn read_u64(&mut self) -> Result<u64, DeserializationError> {
+        self.read_u8_array().map(u64::from_le_bytes)
+    }
+
+    fn read_u128(&mut self) -> Result<u128, DeserializationError> {
+        self.read_u8_array().map(u128::from_le_bytes)
+    }
+
+    fn read_u8_vec(&mut self, len: usize) -> Result<Vec<u8>, DeserializationError> {
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+
+        // the vector is grown as bytes are read so that a malformed length cannot force a large
+        // allocation up-front
+        let mut result = vec![self.take_next_byte()?];
+        let remaining = (len - 1) as u64;
+        let num_read = (&mut self.source)
+            .take(remaining)
+            .read_to_end(&mut result)
+            .map_err(map_io_error)?;
+        if (num_read as u64) < remaining {
+            return Err(DeserializationError::UnexpectedEOF);
+        }
+
+        self.read_next_byte();
+        Ok(result)
+    }
+
+    fn read_u8_array<const N: usize>(&mut self) -> Result<[u8; N], DeserializationError> {
+        let mut result = [0u8; N];
+        self.read_into(&mut result)?;
+        Ok(result)
+    }
+
+    fn has_more_bytes(&self) -> bool {
+        self.next_byte.is_some() || self.error.is_some()
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Maps an I/O error into a [DeserializationError].
+fn map_io_error(err: io::Error) -> DeserializationError {
+    match err.kind() {
+        io::ErrorKind::UnexpectedEof => DeserializationError::UnexpectedEOF,
+        _ => DeserializationError::UnknownError(err.to_string()),
+    }
+}
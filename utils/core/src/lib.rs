@@ -13,7 +13,7 @@
 #[macro_use]
 extern crate alloc;
 
-use core::{convert::TryInto, mem, slice};
+use core::{convert::TryInto, mem, mem::MaybeUninit, slice};
 
 pub mod collections;
 use collections::Vec;
@@ -26,6 +26,17 @@ pub mod iterators;
 mod errors;
 pub use errors::DeserializationError;
 
+mod canonical;
+pub use canonical::{CanonicalDeserializable, CanonicalSerializable};
+
+#[cfg(feature = "derive")]
+pub use winter_derive::{Deserializable, Serializable};
+
+#[cfg(feature = "std")]
+mod io;
+#[cfg(feature = "std")]
+pub use io::{ReadAdapter, WriteAdapter};
+
 #[cfg(test)]
 mod tests;
 
@@ -434,6 +445,14 @@ impl<const N: usize> AsBytes for [[u8; N]] {
 ///
 /// # Safety
 /// Using values from the returned vector before initializing them will lead to undefined behavior.
+/// Moreover, a vector must not contain un-initialized values even if these values are never
+/// read, and thus, calling this function is unsound for most types. Use `zeroed_vector()` method
+/// of field elements for vectors of field elements (this is backed by zero-filled memory requested
+/// from the allocator, and is nearly as fast), or [maybe_uninit_vector()] and
+/// [assume_init_vector()] for vectors of other types.
+#[deprecated(
+    note = "vectors of un-initialized values are unsound; use maybe_uninit_vector() instead"
+)]
 #[allow(clippy::uninit_vec)]
 pub unsafe fn uninit_vector<T>(length: usize) -> Vec<T> {
     let mut vector = Vec::with_capacity(length);
@@ -441,13 +460,43 @@ pub unsafe fn uninit_vector<T>(length: usize) -> Vec<T> {
     vector
 }
 
+/// Returns a vector of the specified length with un-initialized elements.
+///
+/// Unlike a vector of un-initialized values of type `T`, a vector of [MaybeUninit] values is
+/// valid even if none of its elements were written. Once all elements of the vector have been
+/// written, the vector can be converted into a vector of `T` via [assume_init_vector()].
+pub fn maybe_uninit_vector<T>(length: usize) -> Vec<MaybeUninit<T>> {
+    let mut vector = Vec::with_capacity(length);
+    // this is safe because MaybeUninit values do not require initialization
+    unsafe { vector.set_len(length) };
+    vector
+}
+
+/// Converts a vector of [MaybeUninit] values into a vector of initialized values.
+///
+/// This function just re-interprets the underlying memory and is thus zero-copy.
+///
+/// # Safety
+/// All elements of the `source` vector must have been initialized.
+pub unsafe fn assume_init_vector<T>(source: Vec<MaybeUninit<T>>) -> Vec<T> {
+    // MaybeUninit<T> is guaranteed to have the same size and alignment as T, and thus, the
+    // memory allocated for the source vector can be re-used as is
+    let mut v = mem::ManuallyDrop::new(source);
+    let p = v.as_mut_ptr();
+    let len = v.len();
+    let cap = v.capacity();
+    Vec::from_raw_parts(p as *mut T, len, cap)
+}
+
 // GROUPING / UN-GROUPING FUNCTIONS
 // ================================================================================================
 
 /// Transmutes a vector of `n` elements into a vector of `n` / `N` elements, each of which is
 /// an array of `N` elements.
 ///
-/// This function just re-interprets the underlying memory and is thus zero-copy.
+/// This function just re-interprets the underlying memory and is thus zero-copy, unless the
+/// capacity of the source vector is not divisible by `N`; in this case, the memory is first
+/// re-allocated to fit the elements of the vector exactly.
 /// # Panics
 /// Panics if `n` is not divisible by `N`.
 ///
@@ -467,6 +516,14 @@ pub fn group_vector_elements<T, const N: usize>(source: Vec<T>) -> Vec<[T; N]> {
         N,
         source.len()
     );
+    // the memory must be de-allocated with the same layout with which it was allocated; thus,
+    // the capacity of the source vector must be divisible by N. Converting the vector into a
+    // boxed slice shrinks its capacity to exactly its length, which is divisible by N.
+    let source = if source.capacity() % N == 0 {
+        source
+    } else {
+        source.into_boxed_slice().into_vec()
+    };
     let mut v = mem::ManuallyDrop::new(source);
     let p = v.as_mut_ptr();
     let len = v.len() / N;
@@ -529,8 +586,8 @@ pub fn flatten_slice_elements<T, const N: usize>(source: &[[T; N]]) -> &[T] {
 /// assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], b);
 /// ```
 pub fn flatten_vector_elements<T, const N: usize>(source: Vec<[T; N]>) -> Vec<T> {
-    let v = mem::ManuallyDrop::new(source);
-    let p = v.as_ptr();
+    let mut v = mem::ManuallyDrop::new(source);
+    let p = v.as_mut_ptr();
     let len = v.len() * N;
     let cap = v.capacity() * N;
     unsafe { Vec::from_raw_parts(p as *mut T, len, cap) }
@@ -564,15 +621,18 @@ pub fn transpose_slice<T: Copy + Send + Sync, const N: usize>(source: &[T]) -> V
         source.len()
     );
 
-    let mut result = unsafe { group_vector_elements(uninit_vector(row_count * N)) };
+    let mut result = maybe_uninit_vector::<[T; N]>(row_count);
     iter_mut!(result, 1024)
         .enumerate()
         .for_each(|(i, element)| {
+            let element = element.as_mut_ptr() as *mut T;
             for j in 0..N {
-                element[j] = source[i + j * row_count]
+                // this is safe because the element has space for exactly N values of type T
+                unsafe { element.add(j).write(source[i + j * row_count]) };
             }
         });
-    result
+    // all N values of each element were written above
+    unsafe { assume_init_vector(result) }
 }
 
 // RANDOMNESS
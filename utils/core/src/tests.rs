@@ -4,7 +4,10 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use super::{collections::Vec, ByteReader, ByteWriter, Serializable, SliceReader};
+use super::{
+    collections::Vec, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+    SliceReader,
+};
 
 // VECTOR UTILS TESTS
 // ================================================================================================
@@ -29,6 +32,38 @@ fn group_vector_elements() {
     }
 }
 
+#[test]
+fn group_vector_elements_with_excess_capacity() {
+    // the capacity of the source vector is not divisible by the group size; the memory of the
+    // grouped vector must still be de-allocated correctly
+    let mut a = Vec::with_capacity(19);
+    a.extend((0..16).map(|v| v as u64));
+    assert_eq!(19, a.capacity());
+
+    let b = super::group_vector_elements::<u64, 4>(a);
+    assert_eq!(4, b.len());
+    for (i, group) in b.iter().enumerate() {
+        for (j, &value) in group.iter().enumerate() {
+            assert_eq!((i * 4 + j) as u64, value);
+        }
+    }
+    assert_eq!(
+        (0..16).collect::<Vec<u64>>(),
+        super::flatten_vector_elements(b)
+    );
+}
+
+#[test]
+fn maybe_uninit_vector() {
+    let mut a = super::maybe_uninit_vector::<u64>(8);
+    assert_eq!(8, a.len());
+    for (i, value) in a.iter_mut().enumerate() {
+        value.write(i as u64);
+    }
+    let a = unsafe { super::assume_init_vector(a) };
+    assert_eq!((0..8).collect::<Vec<u64>>(), a);
+}
+
 // SLICE READER TESTS
 // ================================================================================================
 
@@ -148,3 +183,163 @@ fn write_serializable_array_batch() {
         assert_eq!(i, reader.read_u128().unwrap());
     }
 }
+
+// CANONICAL SERIALIZATION TESTS
+// ================================================================================================
+
+#[derive(Debug, PartialEq, Eq, winter_derive::Serializable, winter_derive::Deserializable)]
+struct Inputs {
+    flag: bool,
+    id: u32,
+    pair: [u16; 2],
+    values: Vec<u64>,
+    nested: Wrapper<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq, winter_derive::Serializable, winter_derive::Deserializable)]
+struct Wrapper<T>(T, Vec<T>);
+
+fn build_inputs() -> Inputs {
+    Inputs {
+        flag: true,
+        id: 7,
+        pair: [1, 2],
+        values: vec![3, 4],
+        nested: Wrapper(5, vec![6]),
+    }
+}
+
+#[test]
+fn derive_canonical_serialization() {
+    let inputs = build_inputs();
+    let bytes = inputs.to_bytes();
+
+    #[rustfmt::skip]
+    let expected: Vec<u8> = vec![
+        1,                                  // flag
+        7, 0, 0, 0,                         // id
+        1, 0, 2, 0,                         // pair
+        2, 0, 0, 0, 0, 0, 0, 0,             // values.len()
+        3, 0, 0, 0, 0, 0, 0, 0,             // values[0]
+        4, 0, 0, 0, 0, 0, 0, 0,             // values[1]
+        5,                                  // nested.0
+        1, 0, 0, 0, 0, 0, 0, 0,             // nested.1.len()
+        6,                                  // nested.1[0]
+    ];
+    assert_eq!(expected, bytes);
+
+    // serializing the same value again must result in the same transcript bytes
+    assert_eq!(bytes, build_inputs().to_bytes());
+
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(inputs, Inputs::read_from(&mut reader).unwrap());
+    assert!(!reader.has_more_bytes());
+}
+
+#[test]
+fn derive_canonical_deserialization_fail() {
+    // boolean values other than 0 and 1 are rejected
+    let mut bytes = build_inputs().to_bytes();
+    bytes[0] = 2;
+    let mut reader = SliceReader::new(&bytes);
+    assert!(matches!(
+        Inputs::read_from(&mut reader),
+        Err(DeserializationError::InvalidValue(_))
+    ));
+
+    // vectors claiming more elements than there are bytes are rejected
+    let mut bytes = build_inputs().to_bytes();
+    bytes[9] = 3;
+    let mut reader = SliceReader::new(&bytes);
+    assert!(matches!(
+        Inputs::read_from(&mut reader),
+        Err(DeserializationError::UnexpectedEOF)
+    ));
+}
+
+// I/O ADAPTER TESTS
+// ================================================================================================
+
+#[cfg(feature = "std")]
+#[test]
+fn write_adapter() {
+    use super::WriteAdapter;
+    use std::io::Cursor;
+
+    let mut target = WriteAdapter::new(Cursor::new(Vec::new()));
+    target.write_u8(1);
+    target.write_u16(2);
+    target.write_u64(3);
+    target.write_u8_slice(&[4, 5, 6]);
+    [7u128, 8].as_slice().write_into(&mut target);
+    let bytes = target.finish().unwrap().into_inner();
+
+    let mut expected = Vec::new();
+    expected.write_u8(1);
+    expected.write_u16(2);
+    expected.write_u64(3);
+    expected.write_u8_slice(&[4, 5, 6]);
+    [7u128, 8].as_slice().write_into(&mut expected);
+    assert_eq!(expected, bytes);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn write_adapter_error() {
+    use super::WriteAdapter;
+
+    // writing more bytes than a fixed-size buffer can hold should fail on finish
+    let mut buf = [0u8; 4];
+    let mut target = WriteAdapter::new(&mut buf[..]);
+    target.write_u64(1);
+    assert!(target.finish().is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_adapter() {
+    use super::{DeserializationError, ReadAdapter};
+    use std::io::Cursor;
+
+    let mut source = Vec::new();
+    source.write_u8(1);
+    source.write_u16(2);
+    source.write_u32(3);
+    source.write_u64(4);
+    source.write_u8_slice(&5u128.to_le_bytes());
+    source.write_u8_slice(&[6, 7, 8]);
+    source.write_u8_slice(&[9, 10]);
+
+    let mut reader = ReadAdapter::new(Cursor::new(source));
+    assert_eq!(1, reader.read_u8().unwrap());
+    assert_eq!(2, reader.read_u16().unwrap());
+    assert_eq!(3, reader.read_u32().unwrap());
+    assert_eq!(4, reader.read_u64().unwrap());
+    assert_eq!(5, reader.read_u128().unwrap());
+    assert_eq!(vec![6, 7, 8], reader.read_u8_vec(3).unwrap());
+    assert!(reader.has_more_bytes());
+    assert_eq!([9, 10], reader.read_u8_array::<2>().unwrap());
+    assert!(!reader.has_more_bytes());
+
+    assert_eq!(Err(DeserializationError::UnexpectedEOF), reader.read_u8());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn read_adapter_eof() {
+    use super::{DeserializationError, ReadAdapter};
+    use std::io::Cursor;
+
+    let mut reader = ReadAdapter::new(Cursor::new(vec![1u8, 2, 3]));
+    assert_eq!(Err(DeserializationError::UnexpectedEOF), reader.read_u32());
+
+    let mut reader = ReadAdapter::new(Cursor::new(vec![1u8, 2, 3]));
+    assert_eq!(
+        Err(DeserializationError::UnexpectedEOF),
+        reader.read_u8_vec(4)
+    );
+
+    let mut reader = ReadAdapter::new(Cursor::new(Vec::<u8>::new()));
+    assert!(!reader.has_more_bytes());
+    assert_eq!(Ok(Vec::new()), reader.read_u8_vec(0));
+}
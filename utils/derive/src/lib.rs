@@ -0,0 +1,274 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+//! This crate contains derive macros for `Serializable` and `Deserializable` traits of the
+//! `winter-utils` crate, and for `ToElements` trait of the `winter-math` crate.
+//!
+//! The derived implementations serialize fields of a struct in the order of their declaration,
+//! using canonical encodings of the fields as defined by `CanonicalSerializable` and
+//! `CanonicalDeserializable` traits. Thus, a struct can derive these traits only if all of its
+//! fields have a canonical encoding; otherwise, the code does not compile. In addition to
+//! `Serializable` (or `Deserializable`), the derived code also implements
+//! `CanonicalSerializable` (or `CanonicalDeserializable`) for the struct, so that structs
+//! deriving these traits can be nested in one another.
+//!
+//! The generated code refers to the traits via the `winter-utils` crate if it is a dependency of
+//! the crate using the macros (possibly renamed), and via the `winterfell` crate otherwise.
+//!
+//! Macros of this crate are usually accessed via the `derive` feature of the `winter-utils`
+//! crate:
+//!
+//! ```ignore
+//! use winter_utils::{Deserializable, Serializable};
+//!
+//! #[derive(Serializable, Deserializable)]
+//! struct PublicInputs {
+//!     seed: [u64; 2],
+//!     num_steps: u32,
+//!     results: Vec<u64>,
+//! }
+//! ```
+//!
+//! The `ToElements` macro is usually accessed via the `derive` feature of the `winter-math`
+//! crate. The derived implementation concatenates canonical element encodings of the fields of
+//! a struct in the order of their declaration, and is available for every base field in which
+//! all fields of the struct can be encoded:
+//!
+//! ```ignore
+//! use winter_math::{fields::f128::BaseElement, ToElements};
+//!
+//! #[derive(ToElements)]
+//! struct PublicInputs {
+//!     seed: [BaseElement; 2],
+//!     num_steps: u32,
+//!     results: Vec<BaseElement>,
+//! }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use proc_macro_crate::{crate_name, FoundCrate};
+use quote::quote;
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Generics, Ident, Index,
+};
+
+// DERIVE MACROS
+// ================================================================================================
+
+/// Derives `Serializable` and `CanonicalSerializable` traits for a struct.
+///
+/// Fields of the struct are written in the order of their declaration using their canonical
+/// encodings. Compilation fails if any of the fields does not implement `CanonicalSerializable`.
+#[proc_macro_derive(Serializable)]
+pub fn derive_serializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let krate = utils_crate_path();
+
+    let fields = match get_struct_fields(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let writes = field_accessors(fields).map(|field| {
+        quote! { #krate::CanonicalSerializable::write_canonical(&self.#field, target); }
+    });
+
+    let name = &input.ident;
+    let generics = add_trait_bound(&input.generics, quote!(#krate::CanonicalSerializable));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #krate::CanonicalSerializable for #name #ty_generics #where_clause {
+            fn write_canonical<W: #krate::ByteWriter>(&self, target: &mut W) {
+                #(#writes)*
+            }
+        }
+
+        impl #impl_generics #krate::Serializable for #name #ty_generics #where_clause {
+            fn write_into<W: #krate::ByteWriter>(&self, target: &mut W) {
+                #krate::CanonicalSerializable::write_canonical(self, target);
+            }
+        }
+    }
+    .into()
+}
+
+/// Derives `Deserializable` and `CanonicalDeserializable` traits for a struct.
+///
+/// Fields of the struct are read in the order of their declaration from their canonical
+/// encodings. Compilation fails if any of the fields does not implement
+/// `CanonicalDeserializable`.
+#[proc_macro_derive(Deserializable)]
+pub fn derive_deserializable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let krate = utils_crate_path();
+
+    let fields = match get_struct_fields(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let read = quote! { #krate::CanonicalDeserializable::read_canonical(source)? };
+    let body = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote! { Self { #(#names: #read,)* } }
+        }
+        Fields::Unnamed(unnamed) => {
+            let reads = unnamed.unnamed.iter().map(|_| &read);
+            quote! { Self(#(#reads,)*) }
+        }
+        Fields::Unit => quote! { Self },
+    };
+
+    let name = &input.ident;
+    let generics = add_trait_bound(&input.generics, quote!(#krate::CanonicalDeserializable));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #krate::CanonicalDeserializable for #name #ty_generics #where_clause {
+            fn read_canonical<R: #krate::ByteReader>(
+                source: &mut R,
+            ) -> ::core::result::Result<Self, #krate::DeserializationError> {
+                ::core::result::Result::Ok(#body)
+            }
+        }
+
+        impl #impl_generics #krate::Deserializable for #name #ty_generics #where_clause {
+            fn read_from<R: #krate::ByteReader>(
+                source: &mut R,
+            ) -> ::core::result::Result<Self, #krate::DeserializationError> {
+                #krate::CanonicalDeserializable::read_canonical(source)
+            }
+        }
+    }
+    .into()
+}
+
+/// Derives `ToElements` trait for a struct.
+///
+/// The elements of the struct are the elements of its fields concatenated in the order of their
+/// declaration. The trait is implemented for every base field such that all fields of the
+/// struct implement `ToElements` for this base field.
+#[proc_macro_derive(ToElements)]
+pub fn derive_to_elements(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let krate = math_crate_path();
+
+    let fields = match get_struct_fields(&input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let appends = field_accessors(fields).map(|field| {
+        quote! {
+            result.append(&mut #krate::ToElements::<__B>::to_elements(&self.#field));
+        }
+    });
+
+    // the base field is an extra type parameter of the implementation; every field of the struct
+    // must be convertible into elements of this field
+    let mut generics = input.generics.clone();
+    generics.params.push(parse_quote!(__B: #krate::StarkField));
+    let where_clause = generics.make_where_clause();
+    for field in fields.iter() {
+        let ty = &field.ty;
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: #krate::ToElements<__B>));
+    }
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let name = &input.ident;
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #krate::ToElements<__B> for #name #ty_generics #where_clause {
+            fn to_elements(&self) -> #krate::__private::Vec<__B> {
+                let mut result = #krate::__private::Vec::new();
+                #(#appends)*
+                result
+            }
+        }
+    }
+    .into()
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the path to the crate defining serialization traits, as seen from the crate invoking
+/// the macro.
+fn utils_crate_path() -> TokenStream2 {
+    let found = crate_name("winter-utils").or_else(|_| crate_name("winterfell"));
+    match found {
+        Ok(FoundCrate::Itself) => quote!(crate),
+        Ok(FoundCrate::Name(name)) => {
+            let ident = Ident::new(&name, Span::call_site());
+            quote!(::#ident)
+        }
+        // if neither of the crates could be found in the manifest, fall back onto the default
+        // name; this results in a meaningful error if the crate is indeed missing
+        Err(_) => quote!(::winter_utils),
+    }
+}
+
+/// Returns the path to the crate defining `ToElements` trait, as seen from the crate invoking the
+/// macro.
+fn math_crate_path() -> TokenStream2 {
+    match crate_name("winter-math") {
+        Ok(FoundCrate::Itself) => quote!(crate),
+        Ok(FoundCrate::Name(name)) => {
+            let ident = Ident::new(&name, Span::call_site());
+            quote!(::#ident)
+        }
+        Err(_) => match crate_name("winterfell") {
+            Ok(FoundCrate::Name(name)) => {
+                let ident = Ident::new(&name, Span::call_site());
+                quote!(::#ident::math)
+            }
+            // fall back onto the default name; this results in a meaningful error if the crate
+            // is indeed missing
+            _ => quote!(::winter_math),
+        },
+    }
+}
+
+/// Returns fields of the struct described by the `input`, or an error if the `input` is not a
+/// struct.
+fn get_struct_fields(input: &DeriveInput) -> syn::Result<&Fields> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "canonical encodings can be derived only for structs",
+        )),
+    }
+}
+
+/// Returns tokens accessing each of the `fields` on `self` (i.e., field names for named fields,
+/// and field indexes for unnamed fields).
+fn field_accessors(fields: &Fields) -> impl Iterator<Item = TokenStream2> + '_ {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(i);
+                quote!(#index)
+            }
+        })
+}
+
+/// Adds the specified trait bound to every type parameter of the `generics`.
+fn add_trait_bound(generics: &Generics, bound: TokenStream2) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.params.iter_mut() {
+        if let GenericParam::Type(type_param) = param {
+            type_param.bounds.push(parse_quote!(#bound));
+        }
+    }
+    generics
+}
@@ -23,13 +23,12 @@ mod internal {
     /// Returns a single random value of the specified type.
     ///
     /// # Panics
-    /// Panics if:
-    /// * A valid value requires over 32 bytes.
-    /// * A valid value could not be generated after 1000 tries.
+    /// Panics if a valid value could not be generated after 1000 tries.
     pub fn rand_value<R: Randomizable>() -> R {
+        let mut bytes = vec![0u8; R::VALUE_SIZE];
         for _ in 0..1000 {
-            let bytes = rand::thread_rng().gen::<[u8; 32]>();
-            if let Some(value) = R::from_random_bytes(&bytes[..R::VALUE_SIZE]) {
+            rand::thread_rng().fill_bytes(&mut bytes);
+            if let Some(value) = R::from_random_bytes(&bytes) {
                 return value;
             }
         }
@@ -40,16 +39,18 @@ mod internal {
     /// Returns a vector of random value of the specified type and the specified length.
     ///
     /// # Panics
-    /// Panics if:
-    /// * A valid value requires at over 32 bytes.
-    /// * A valid value could not be generated after 1000 tries.
+    /// Panics if a valid value could not be generated after 1000 tries.
     pub fn rand_vector<R: Randomizable>(n: usize) -> Vec<R> {
         let mut result = Vec::with_capacity(n);
+        if n == 0 {
+            return result;
+        }
         let seed = rand::thread_rng().gen::<[u8; 32]>();
         let mut g = StdRng::from_seed(seed);
+        let mut bytes = vec![0u8; R::VALUE_SIZE];
         for _ in 0..1000 * n {
-            let bytes = g.gen::<[u8; 32]>();
-            if let Some(element) = R::from_random_bytes(&bytes[..R::VALUE_SIZE]) {
+            g.fill_bytes(&mut bytes);
+            if let Some(element) = R::from_random_bytes(&bytes) {
                 result.push(element);
                 if result.len() == n {
                     return result;
@@ -63,9 +64,7 @@ mod internal {
     /// Returns an array of random value of the specified type and the specified length.
     ///
     /// # Panics
-    /// Panics if:
-    /// * A valid value requires at over 32 bytes.
-    /// * A valid value could not be generated after 1000 tries.
+    /// Panics if a valid value could not be generated after 1000 tries.
     pub fn rand_array<R: Randomizable + Debug, const N: usize>() -> [R; N] {
         let elements = rand_vector(N);
         elements
@@ -82,6 +81,9 @@ mod internal {
     /// * A valid value could not be generated after 1000 tries.
     pub fn prng_vector<R: Randomizable>(seed: [u8; 32], n: usize) -> Vec<R> {
         let mut result = Vec::with_capacity(n);
+        if n == 0 {
+            return result;
+        }
         let mut g = StdRng::from_seed(seed);
         for _ in 0..1000 * n {
             let bytes = g.gen::<[u8; 32]>();
@@ -0,0 +1,241 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    check_ood_consistency, compose_deep_evaluations, draw_query_positions, validate_context, Air,
+    BatchProof, FieldExtension, PreparedAir, ProofOptions, VerificationPhase, VerifierChannel,
+    VerifierError,
+};
+use air::{labels, PublicCoin};
+use crypto::{ElementHasher, Transcript};
+use fri::{DefaultVerifierChannel, FriVerifier};
+use math::{
+    fields::{CubeExtension, QuadExtension, SexticExtension},
+    mul_acc, FieldElement, ToElements,
+};
+use utils::{collections::Vec, string::ToString, ByteWriter, Serializable};
+
+// BATCH VERIFIER
+// ================================================================================================
+/// Verifies that the specified computation was executed correctly against each of the specified
+/// sets of public inputs.
+///
+/// Specifically, for a computation specified by `AIR` and `HashFn` type parameters, verifies that
+/// the provided batch `proof` attests to the correct execution of the computation against each
+/// set of public inputs in `pub_inputs`; the public inputs must be provided in the order in which
+/// the corresponding instances appear in the proof. If the verification is successful, `Ok(())`
+/// is returned.
+///
+/// The parts of the proof specific to each instance are checked in the same way as they are
+/// checked by [verify()](crate::verify), after which the FRI proof shared by all instances is
+/// checked once. The batch proof is accepted only if all of its instances are valid; see
+/// [BatchProof] for the discussion of the security of batch proofs.
+///
+/// # Errors
+/// Returns an error if the `proof` does not describe any instances, if more than
+/// [BatchProof::MAX_NUM_INSTANCES] sets of public inputs are provided, if the number of instances
+/// in the `proof` differs from the number of provided sets of public inputs, if values of
+/// periodic columns differ between the instances of the AIR built for the provided public inputs,
+/// or if the proof does not attest to a correct execution of the computation for any of the
+/// instances.
+pub fn verify_batch<AIR, HashFn>(
+    proof: BatchProof,
+    pub_inputs: Vec<AIR::PublicInputs>,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+{
+    if proof.instances.is_empty() {
+        return Err(VerifierError::EmptyBatch);
+    }
+    if pub_inputs.len() > BatchProof::MAX_NUM_INSTANCES {
+        return Err(VerifierError::BatchTooLarge(pub_inputs.len()));
+    }
+    if proof.num_instances() != pub_inputs.len() {
+        return Err(VerifierError::InconsistentBatchSize {
+            expected: pub_inputs.len(),
+            actual: proof.num_instances(),
+        });
+    }
+
+    // make sure the proof context is consistent with the verifier before any values are
+    // computed for the AIRs of the instances
+    validate_context::<AIR, HashFn, HashFn>(
+        &proof.context,
+        ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+    )?;
+
+    // build a seed for the public coin in the same way as the prover does: the canonical element
+    // encodings of public inputs of all instances, each preceded by its length, are followed by
+    // the proof context
+    let mut public_coin_seed = Vec::new();
+    public_coin_seed.write_u16(pub_inputs.len() as u16);
+    for pub_inputs in pub_inputs.iter() {
+        let elements = pub_inputs.to_elements();
+        public_coin_seed.write_u32(elements.len() as u32);
+        elements.write_into(&mut public_coin_seed);
+    }
+    proof.context.write_unversioned_into(&mut public_coin_seed);
+
+    // create AIR instances for all instances of the computation; data which does not depend on
+    // public inputs is prepared once for all of them
+    let airs = pub_inputs
+        .into_iter()
+        .map(|pub_inputs| AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone()))
+        .collect::<Vec<_>>();
+    let prepared = PreparedAir::<AIR, HashFn>::new(&airs[0]);
+    if !airs[1..].iter().all(|air| prepared.is_consistent_with(air)) {
+        return Err(VerifierError::InconsistentPreparedAir);
+    }
+
+    let mut public_coin = PublicCoin::<AIR::BaseField, HashFn>::new(
+        airs[0].options().transcript_type(),
+        &public_coin_seed,
+    );
+
+    // figure out which version of the generic proof verification procedure to run; all
+    // instances are verified over the same extension field
+    match airs[0].options().field_extension() {
+        FieldExtension::None => perform_batch_verification::<AIR, AIR::BaseField, HashFn>(
+            &airs,
+            &prepared,
+            proof,
+            &mut public_coin,
+        ),
+        FieldExtension::Quadratic => {
+            if !<QuadExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(2));
+            }
+            perform_batch_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn>(
+                &airs,
+                &prepared,
+                proof,
+                &mut public_coin,
+            )
+        }
+        FieldExtension::Cubic => {
+            if !<CubeExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(3));
+            }
+            perform_batch_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn>(
+                &airs,
+                &prepared,
+                proof,
+                &mut public_coin,
+            )
+        }
+        FieldExtension::Sextic => {
+            if !<SexticExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(6));
+            }
+            perform_batch_verification::<AIR, SexticExtension<AIR::BaseField>, HashFn>(
+                &airs,
+                &prepared,
+                proof,
+                &mut public_coin,
+            )
+        }
+    }
+}
+
+// BATCH VERIFICATION PROCEDURE
+// ================================================================================================
+/// Performs the actual verification of the batch `proof` for instances of the computation
+/// described by the provided `airs`.
+fn perform_batch_verification<A, E, H>(
+    airs: &[A],
+    prepared: &PreparedAir<A, H>,
+    proof: BatchProof,
+    public_coin: &mut PublicCoin<A::BaseField, H>,
+) -> Result<(), VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
+    let BatchProof {
+        instances,
+        fri_commitments,
+        fri_proof,
+        pow_nonce,
+        ..
+    } = proof;
+
+    // 1 - 4 ----- trace and constraint commitments, OOD consistency checks -----------------------
+    // each instance is processed in the same way as a single proof would be; the commitments and
+    // out-of-domain frames of all instances are absorbed into the public coin in order
+    let mut channels = Vec::with_capacity(airs.len());
+    let mut ood_checks = Vec::with_capacity(airs.len());
+    for (air, instance) in airs.iter().zip(instances) {
+        let mut channel =
+            VerifierChannel::<E, H>::for_batch_instance(air, instance, prepared.periodic_root())?;
+        ood_checks.push(check_ood_consistency(
+            air,
+            prepared,
+            &mut channel,
+            public_coin,
+        )?);
+        channels.push(channel);
+    }
+
+    // draw the coefficient for combining DEEP composition polynomials of all instances; this is
+    // done only after the out-of-domain frames of all instances have been absorbed
+    let batch_coefficient = public_coin
+        .draw::<E>(labels::BATCH_COEFFICIENT)
+        .map_err(|_| VerifierError::RandomCoinError(VerificationPhase::OodConsistency))?;
+
+    // 5 ----- FRI commitments --------------------------------------------------------------------
+    // all instances share the proof context, and thus, the LDE domain and the FRI options
+    let air = &airs[0];
+    let lde_domain_size = air.lde_domain_size();
+    let fri_options = air.options().to_fri_options();
+    let fri_roots = fri_commitments
+        .parse_fri_layers::<H>(fri_options.num_fri_layers(lde_domain_size))
+        .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    let mut fri_channel = DefaultVerifierChannel::<E, H>::new(
+        fri_proof,
+        fri_roots,
+        lde_domain_size,
+        fri_options.folding_factor(),
+    )
+    .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+    let fri_verifier = FriVerifier::new(
+        &mut fri_channel,
+        public_coin,
+        fri_options,
+        air.trace_poly_degree(),
+    )
+    .map_err(VerifierError::FriVerificationFailed)?;
+
+    // 6 ----- trace and constraint queries -------------------------------------------------------
+    // all instances are queried at the same positions
+    let (query_positions, _) = draw_query_positions(air, public_coin, pow_nonce)?;
+
+    // 7 ----- DEEP composition -------------------------------------------------------------------
+    // compute evaluations of the DEEP composition polynomial of each instance at the queried
+    // positions, and combine them using powers of the batch coefficient
+    let mut deep_evaluations = vec![E::ZERO; query_positions.shared().len()];
+    let mut coefficient = E::ONE;
+    let instances = airs.iter().zip(channels).zip(ood_checks);
+    for ((air, mut channel), ood_check) in instances {
+        let evaluations = compose_deep_evaluations(air, &mut channel, &query_positions, ood_check)?;
+        mul_acc::<E, E>(&mut deep_evaluations, &evaluations, coefficient);
+        coefficient *= batch_coefficient;
+    }
+
+    // 8 ----- Verify low-degree proof -------------------------------------------------------------
+    // make sure that the combined evaluations are evaluations of a polynomial of degree equal to
+    // trace polynomial degree; this holds only if the DEEP composition polynomials of all
+    // instances are of this degree (except with negligible probability)
+    fri_verifier
+        .verify(
+            &mut fri_channel,
+            &deep_evaluations,
+            query_positions.shared(),
+        )
+        .map_err(VerifierError::FriVerificationFailed)
+}
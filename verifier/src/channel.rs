@@ -6,13 +6,13 @@
 
 use crate::VerifierError;
 use air::{
-    proof::{Queries, StarkProof, Table},
+    proof::{InstanceProof, OodFrame, Queries, StarkProof, Table},
     Air, EvaluationFrame,
 };
-use crypto::{BatchMerkleProof, ElementHasher, MerkleTree};
+use crypto::{BatchMerkleProof, Digest, ElementHasher, MerkleTree};
 use fri::VerifierChannel as FriVerifierChannel;
 use math::{FieldElement, StarkField};
-use utils::{collections::Vec, string::ToString};
+use utils::{collections::Vec, string::ToString, DeserializationError};
 
 // VERIFIER CHANNEL
 // ================================================================================================
@@ -29,6 +29,9 @@ pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::Base
     // constraint queries
     constraint_root: H::Digest,
     constraint_queries: Option<ConstraintQueries<E, H>>,
+    // periodic column queries
+    periodic_root: Option<H::Digest>,
+    periodic_queries: Option<PeriodicQueries<H>>,
     // FRI proof
     fri_roots: Option<Vec<H::Digest>>,
     fri_layer_proofs: Vec<BatchMerkleProof<H>>,
@@ -38,6 +41,7 @@ pub struct VerifierChannel<E: FieldElement, H: ElementHasher<BaseField = E::Base
     // out-of-domain frame
     ood_trace_frame: Option<TraceOodFrame<E>>,
     ood_constraint_evaluations: Option<Vec<E>>,
+    ood_periodic_values: Option<Vec<E>>,
     // query proof-of-work
     pow_nonce: u64,
 }
@@ -46,15 +50,20 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
     /// Creates and returns a new [VerifierChannel] initialized from the specified `proof`.
+    ///
+    /// The `periodic_root` is the commitment to periodic columns defined by the AIR, or None if
+    /// the AIR does not commit to its periodic columns.
     pub fn new<A: Air<BaseField = E::BaseField>>(
         air: &A,
         proof: StarkProof,
+        periodic_root: Option<H::Digest>,
     ) -> Result<Self, VerifierError> {
         let StarkProof {
             context,
             commitments,
             trace_queries,
             constraint_queries,
+            periodic_queries,
             ood_frame,
             fri_proof,
             pow_nonce,
@@ -65,36 +74,150 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             return Err(VerifierError::InconsistentBaseField);
         }
 
-        let num_trace_segments = air.trace_layout().num_segments();
-        let main_trace_width = air.trace_layout().main_trace_width();
-        let aux_trace_width = air.trace_layout().aux_trace_width();
+        let num_trace_commitments = air.trace_layout().num_trace_commitments();
         let lde_domain_size = air.lde_domain_size();
         let fri_options = air.options().to_fri_options();
 
         // --- parse commitments ------------------------------------------------------------------
         let (trace_roots, constraint_root, fri_roots) = commitments
             .parse::<H>(
-                num_trace_segments,
+                num_trace_commitments,
                 fri_options.num_fri_layers(lde_domain_size),
             )
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
 
-        // --- parse trace and constraint queries -------------------------------------------------
-        let trace_queries = TraceQueries::new(trace_queries, air)?;
-        let constraint_queries = ConstraintQueries::new(constraint_queries, air)?;
+        // --- parse queries and out-of-domain evaluation frame -----------------------------------
+        let mut channel = Self::from_instance_parts(
+            air,
+            trace_roots,
+            constraint_root,
+            trace_queries,
+            constraint_queries,
+            periodic_queries,
+            ood_frame,
+            periodic_root,
+        )?;
 
         // --- parse FRI proofs -------------------------------------------------------------------
-        let fri_num_partitions = fri_proof.num_partitions();
-        let fri_remainder = fri_proof
-            .parse_remainder()
-            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        channel.fri_num_partitions = fri_proof.num_partitions();
+        channel.fri_remainder = Some(
+            fri_proof
+                .parse_remainder()
+                .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?,
+        );
         let (fri_layer_queries, fri_layer_proofs) = fri_proof
             .parse_layers::<H, E>(lde_domain_size, fri_options.folding_factor())
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+        if fri_layer_queries.len() != fri_roots.len() - 1 {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "expected {} FRI layers, but was {}",
+                fri_roots.len() - 1,
+                fri_layer_queries.len()
+            )));
+        }
+        channel.fri_roots = Some(fri_roots);
+        channel.fri_layer_queries = fri_layer_queries;
+        channel.fri_layer_proofs = fri_layer_proofs;
+
+        // --- query seed -------------------------------------------------------------------------
+        channel.pow_nonce = pow_nonce;
+
+        Ok(channel)
+    }
+
+    /// Creates and returns a new [VerifierChannel] initialized from the specified `instance` of
+    /// a batch proof.
+    ///
+    /// The FRI proof and the proof-of-work nonce of a batch proof are shared by all of its
+    /// instances; thus, they are not available from the returned channel and must be read from
+    /// the batch proof directly.
+    pub fn for_batch_instance<A: Air<BaseField = E::BaseField>>(
+        air: &A,
+        instance: InstanceProof,
+        periodic_root: Option<H::Digest>,
+    ) -> Result<Self, VerifierError> {
+        let InstanceProof {
+            commitments,
+            trace_queries,
+            constraint_queries,
+            periodic_queries,
+            ood_frame,
+        } = instance;
+
+        let (trace_roots, constraint_root) = commitments
+            .parse_instance::<H>(air.trace_layout().num_trace_commitments())
+            .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
+
+        Self::from_instance_parts(
+            air,
+            trace_roots,
+            constraint_root,
+            trace_queries,
+            constraint_queries,
+            periodic_queries,
+            ood_frame,
+            periodic_root,
+        )
+    }
+
+    /// Returns a new [VerifierChannel] initialized from the parts of a proof which are specific
+    /// to a single instance of a computation; the FRI proof and the query seed of the returned
+    /// channel are empty.
+    #[allow(clippy::too_many_arguments)]
+    fn from_instance_parts<A: Air<BaseField = E::BaseField>>(
+        air: &A,
+        trace_roots: Vec<H::Digest>,
+        constraint_root: H::Digest,
+        trace_queries: Vec<Queries>,
+        constraint_queries: Queries,
+        periodic_queries: Option<Queries>,
+        ood_frame: OodFrame,
+        periodic_root: Option<H::Digest>,
+    ) -> Result<Self, VerifierError> {
+        let main_trace_width = air.trace_layout().main_trace_width();
+        let aux_trace_width = air.trace_layout().aux_trace_width();
+
+        // --- parse trace and constraint queries -------------------------------------------------
+        let trace_queries = TraceQueries::new(trace_queries, air)?;
+        let constraint_queries = ConstraintQueries::new(constraint_queries, air)?;
+
+        // --- parse periodic column queries ------------------------------------------------------
+        // periodic column queries must be present if and only if the AIR commits to its periodic
+        // columns; the commitment itself is never sent by the prover but is defined by the AIR
+        let (periodic_root, periodic_queries, num_periodic_values) =
+            match (periodic_root, periodic_queries) {
+                (Some(root), Some(queries)) => {
+                    let queries = PeriodicQueries::new(queries, air)?;
+                    let num_values = queries.values.num_columns();
+                    (Some(root), Some(queries), num_values)
+                }
+                (None, None) => (None, None, 0),
+                (Some(_), None) => {
+                    return Err(VerifierError::ProofDeserializationError(
+                        "periodic column queries are missing from the proof".to_string(),
+                    ))
+                }
+                (None, Some(_)) => {
+                    return Err(VerifierError::ProofDeserializationError(
+                        "proof contains unexpected periodic column queries".to_string(),
+                    ))
+                }
+            };
 
         // --- parse out-of-domain evaluation frame -----------------------------------------------
-        let (ood_main_trace_frame, ood_aux_trace_frame, ood_constraint_evaluations) = ood_frame
-            .parse(main_trace_width, aux_trace_width, air.ce_blowup_factor())
+        let (
+            ood_main_trace_frame,
+            ood_aux_trace_frame,
+            ood_constraint_evaluations,
+            ood_periodic_values,
+        ) = ood_frame
+            .parse(
+                main_trace_width,
+                aux_trace_width,
+                air.frame_offsets().len(),
+                air.num_constraint_composition_columns(),
+                num_periodic_values,
+            )
             .map_err(|err| VerifierError::ProofDeserializationError(err.to_string()))?;
         let ood_trace_frame = TraceOodFrame::new(ood_main_trace_frame, ood_aux_trace_frame);
 
@@ -105,17 +228,21 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             // constraint queries
             constraint_root,
             constraint_queries: Some(constraint_queries),
+            // periodic column queries
+            periodic_root,
+            periodic_queries,
             // FRI proof
-            fri_roots: Some(fri_roots),
-            fri_layer_proofs,
-            fri_layer_queries,
-            fri_remainder: Some(fri_remainder),
-            fri_num_partitions,
+            fri_roots: None,
+            fri_layer_proofs: Vec::new(),
+            fri_layer_queries: Vec::new(),
+            fri_remainder: None,
+            fri_num_partitions: 0,
             // out-of-domain evaluation
             ood_trace_frame: Some(ood_trace_frame),
             ood_constraint_evaluations: Some(ood_constraint_evaluations),
+            ood_periodic_values: Some(ood_periodic_values),
             // query seed
-            pow_nonce,
+            pow_nonce: 0,
         })
     }
 
@@ -135,8 +262,8 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         self.constraint_root
     }
 
-    /// Returns trace polynomial evaluations at out-of-domain points z and z * g, where g is the
-    /// generator of the LDE domain.
+    /// Returns trace polynomial evaluations at out-of-domain points z * g^o for all frame offsets
+    /// o, where g is the generator of the trace domain.
     ///
     /// For computations requiring multiple trace segments, evaluations of auxiliary trace
     /// polynomials are also included as the second value of the returned tuple. Otherwise, the
@@ -154,6 +281,13 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
             .expect("already read")
     }
 
+    /// Returns evaluations of committed periodic column polynomials at z^k, where z is the
+    /// out-of-domain point, and k is the number of cycles of a given column in the execution
+    /// trace. If periodic columns are not committed, the returned vector is empty.
+    pub fn read_ood_periodic_values(&mut self) -> Vec<E> {
+        self.ood_periodic_values.take().expect("already read")
+    }
+
     /// Returns query proof-of-work nonce sent by the prover.
     pub fn read_pow_nonce(&self) -> u64 {
         self.pow_nonce
@@ -173,9 +307,15 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
         let queries = self.trace_queries.take().expect("already read");
 
         // make sure the states included in the proof correspond to the trace commitment
-        for (root, proof) in self.trace_roots.iter().zip(queries.query_proofs.iter()) {
-            MerkleTree::verify_batch(root, positions, proof)
-                .map_err(|_| VerifierError::TraceQueryDoesNotMatchCommitment)?;
+        let segment_proofs = self.trace_roots.iter().zip(queries.query_proofs.iter());
+        for (segment, (root, proof)) in segment_proofs.enumerate() {
+            MerkleTree::verify_batch(root, positions, proof).map_err(|_| {
+                VerifierError::TraceQueryDoesNotMatchCommitment {
+                    segment,
+                    expected: root.as_bytes(),
+                    actual: proof.get_root(positions).ok().map(|root| root.as_bytes()),
+                }
+            })?;
         }
 
         Ok((queries.main_states, queries.aux_states))
@@ -190,11 +330,45 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> VerifierChanne
     ) -> Result<Table<E>, VerifierError> {
         let queries = self.constraint_queries.take().expect("already read");
 
-        MerkleTree::verify_batch(&self.constraint_root, positions, &queries.query_proofs)
-            .map_err(|_| VerifierError::ConstraintQueryDoesNotMatchCommitment)?;
+        MerkleTree::verify_batch(&self.constraint_root, positions, &queries.query_proofs).map_err(
+            |_| VerifierError::ConstraintQueryDoesNotMatchCommitment {
+                expected: self.constraint_root.as_bytes(),
+                actual: queries
+                    .query_proofs
+                    .get_root(positions)
+                    .ok()
+                    .map(|root| root.as_bytes()),
+            },
+        )?;
 
         Ok(queries.evaluations)
     }
+
+    /// Returns committed periodic column values at the specified positions of the LDE domain, or
+    /// None if periodic columns are not committed. This also checks if the values are valid
+    /// against the periodic column commitment defined by the AIR.
+    pub fn read_queried_periodic_values(
+        &mut self,
+        positions: &[usize],
+    ) -> Result<Option<Table<E::BaseField>>, VerifierError> {
+        let (root, queries) = match (self.periodic_root, self.periodic_queries.take()) {
+            (Some(root), Some(queries)) => (root, queries),
+            _ => return Ok(None),
+        };
+
+        MerkleTree::verify_batch(&root, positions, &queries.query_proofs).map_err(|_| {
+            VerifierError::PeriodicQueryDoesNotMatchCommitment {
+                expected: root.as_bytes(),
+                actual: queries
+                    .query_proofs
+                    .get_root(positions)
+                    .ok()
+                    .map(|root| root.as_bytes()),
+            }
+        })?;
+
+        Ok(Some(queries.values))
+    }
 }
 
 // FRI VERIFIER CHANNEL IMPLEMENTATION
@@ -249,45 +423,50 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> TraceQueries<E
         mut queries: Vec<Queries>,
         air: &A,
     ) -> Result<Self, VerifierError> {
-        assert_eq!(
-            queries.len(),
-            air.trace_layout().num_segments(),
-            "expected {} trace segment queries, but received {}",
-            air.trace_layout().num_segments(),
-            queries.len()
-        );
-
-        let num_queries = air.options().num_queries();
+        let num_trace_commitments = air.trace_layout().num_trace_commitments();
+        if queries.len() != num_trace_commitments {
+            return Err(VerifierError::ProofDeserializationError(format!(
+                "expected {} trace segment queries, but was {}",
+                num_trace_commitments,
+                queries.len()
+            )));
+        }
 
         // parse main trace segment queries; parsing also validates that hashes of each table row
         // form the leaves of Merkle authentication paths in the proofs
+        let num_queries = air.options().num_trace_queries();
         let main_segment_width = air.trace_layout().main_trace_width();
         let main_segment_queries = queries.remove(0);
-        let (main_segment_query_proofs, main_segment_states) = main_segment_queries
-            .parse::<H, E::BaseField>(air.lde_domain_size(), num_queries, main_segment_width)
-            .map_err(|err| {
-                VerifierError::ProofDeserializationError(format!(
-                    "main trace segment query deserialization failed: {err}"
-                ))
-            })?;
+        let (main_segment_query_proofs, main_segment_states) = parse_queries::<H, E::BaseField, A>(
+            main_segment_queries,
+            air,
+            num_queries,
+            main_segment_width,
+        )
+        .map_err(|err| {
+            VerifierError::ProofDeserializationError(format!(
+                "main trace segment query deserialization failed: {err}"
+            ))
+        })?;
 
         // all query proofs will be aggregated into a single vector
         let mut query_proofs = vec![main_segment_query_proofs];
 
         // parse auxiliary trace segment queries (if any), and merge resulting tables into a
         // single table; parsing also validates that hashes of each table row form the leaves
-        // of Merkle authentication paths in the proofs
+        // of Merkle authentication paths in the proofs. segments built in several rounds are
+        // queried once per round
         let aux_trace_states = if air.trace_info().is_multi_segment() {
             let mut aux_trace_states = Vec::new();
-            for (i, segment_queries) in queries.into_iter().enumerate() {
-                let segment_width = air.trace_layout().get_aux_segment_width(i);
-                let (segment_query_proof, segment_trace_states) = segment_queries
-                    .parse::<H, E>(air.lde_domain_size(), num_queries, segment_width)
-                    .map_err(|err| {
-                        VerifierError::ProofDeserializationError(format!(
-                            "auxiliary trace segment query deserialization failed: {err}"
-                        ))
-                    })?;
+            let segment_widths = air.trace_layout().aux_commitment_widths();
+            for (segment_queries, segment_width) in queries.into_iter().zip(segment_widths) {
+                let (segment_query_proof, segment_trace_states) =
+                    parse_queries::<H, E, A>(segment_queries, air, num_queries, segment_width)
+                        .map_err(|err| {
+                            VerifierError::ProofDeserializationError(format!(
+                                "auxiliary trace segment query deserialization failed: {err}"
+                            ))
+                        })?;
 
                 query_proofs.push(segment_query_proof);
                 aux_trace_states.push(segment_trace_states);
@@ -325,18 +504,60 @@ impl<E: FieldElement, H: ElementHasher<BaseField = E::BaseField>> ConstraintQuer
         queries: Queries,
         air: &A,
     ) -> Result<Self, VerifierError> {
-        let num_queries = air.options().num_queries();
-        let (query_proofs, evaluations) = queries
-            .parse::<H, E>(air.lde_domain_size(), num_queries, air.ce_blowup_factor())
+        let (query_proofs, evaluations) = parse_queries::<H, E, A>(
+            queries,
+            air,
+            air.options().num_constraint_queries(),
+            air.num_constraint_composition_columns(),
+        )
+        .map_err(|err| {
+            VerifierError::ProofDeserializationError(format!(
+                "constraint evaluation query deserialization failed: {err}"
+            ))
+        })?;
+
+        Ok(Self {
+            query_proofs,
+            evaluations,
+        })
+    }
+}
+
+// PERIODIC COLUMN QUERIES
+// ================================================================================================
+
+/// Container of committed periodic column query data, including:
+/// * Queried periodic column values.
+/// * Merkle authentication paths for all queries.
+struct PeriodicQueries<H: ElementHasher> {
+    query_proofs: BatchMerkleProof<H>,
+    values: Table<H::BaseField>,
+}
+
+impl<H: ElementHasher> PeriodicQueries<H> {
+    /// Parses the provided periodic column queries into values in the base field and
+    /// corresponding Merkle authentication paths.
+    pub fn new<A: Air<BaseField = H::BaseField>>(
+        queries: Queries,
+        air: &A,
+    ) -> Result<Self, VerifierError> {
+        // periodic column commitments are never salted because periodic columns are public;
+        // periodic values are opened at the same positions as the execution trace
+        let (query_proofs, values) = queries
+            .parse::<H, H::BaseField>(
+                air.lde_domain_size(),
+                air.options().num_trace_queries(),
+                air.get_periodic_column_values().len(),
+            )
             .map_err(|err| {
                 VerifierError::ProofDeserializationError(format!(
-                    "constraint evaluation query deserialization failed: {err}"
+                    "periodic column query deserialization failed: {err}"
                 ))
             })?;
 
         Ok(Self {
             query_proofs,
-            evaluations,
+            values,
         })
     }
 }
@@ -357,3 +578,27 @@ impl<E: FieldElement> TraceOodFrame<E> {
         }
     }
 }
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Parses the provided queries into a table of values and a batch Merkle proof; if the proof was
+/// generated with salted commitments, salts of the queried rows are read from the queries as well.
+fn parse_queries<H, E, A>(
+    queries: Queries,
+    air: &A,
+    num_queries: usize,
+    values_per_query: usize,
+) -> Result<(BatchMerkleProof<H>, Table<E>), DeserializationError>
+where
+    E: FieldElement,
+    H: ElementHasher<BaseField = E::BaseField>,
+    A: Air<BaseField = E::BaseField>,
+{
+    let domain_size = air.lde_domain_size();
+    if air.options().is_zero_knowledge() {
+        queries.parse_salted::<H, E>(domain_size, num_queries, values_per_query)
+    } else {
+        queries.parse::<H, E>(domain_size, num_queries, values_per_query)
+    }
+}
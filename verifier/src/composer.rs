@@ -15,7 +15,8 @@ pub struct DeepComposer<E: FieldElement> {
     field_extension: FieldExtension,
     cc: DeepCompositionCoefficients<E>,
     x_coordinates: Vec<E>,
-    z: [E; 2],
+    z: Vec<E>,
+    zero_knowledge: bool,
 }
 
 impl<E: FieldElement> DeepComposer<E> {
@@ -34,11 +35,21 @@ impl<E: FieldElement> DeepComposer<E> {
             .map(|&p| E::from(g_lde.exp((p as u64).into()) * domain_offset))
             .collect();
 
+        // compute out-of-domain points z * g^o for all frame offsets o; the first offset is
+        // always zero, and thus, the first point is always z
+        let g_trace = air.trace_domain_generator();
+        let z = air
+            .frame_offsets()
+            .iter()
+            .map(|&offset| z * E::from(g_trace.exp((offset as u64).into())))
+            .collect();
+
         DeepComposer {
             field_extension: air.options().field_extension(),
             cc,
             x_coordinates,
-            z: [z, z * E::from(air.trace_domain_generator())],
+            z,
+            zero_knowledge: air.options().is_zero_knowledge(),
         }
     }
 
@@ -46,19 +57,20 @@ impl<E: FieldElement> DeepComposer<E> {
     /// their random linear combinations as follows:
     ///
     /// - Assume each column value is an evaluation of a trace polynomial T_i(x).
-    /// - For each T_i(x) compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z) and
-    ///   T''_i = (T_i(x) - T_i(z * g)) / (x - z * g), where z is the out-of-domain point and
-    ///   g is the generation of the LDE domain.
-    /// - Then, combine all T'_i(x) and T''_i(x) values together by computing
-    ///   T(x) = sum(T'_i(x) * cc'_i + T''_i(x) * cc''_i) for all i, where cc'_i and cc''_i are
-    ///   the coefficients for the random linear combination drawn from the public coin.
+    /// - For each T_i(x) and each frame offset o_j compute
+    ///   T_i,j(x) = (T_i(x) - T_i(z * g^o_j)) / (x - z * g^o_j), where z is the out-of-domain
+    ///   point and g is the generator of the trace domain. For the default offsets, these are
+    ///   T'_i(x) = (T_i(x) - T_i(z)) / (x - z) and T''_i = (T_i(x) - T_i(z * g)) / (x - z * g).
+    /// - Then, combine all T_i,j(x) values together by computing
+    ///   T(x) = sum(T_i,j(x) * cc_i,j) for all i and j, where cc_i,j are the coefficients for
+    ///   the random linear combination drawn from the public coin.
     /// - In cases when the proof was generated using an extension field, we also compute
     ///   T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate), and add it to T(x) similarly
     ///   to the way described above. This is needed in order to verify that the trace is defined
     ///   over the base field, rather than the extension field.
     ///
-    /// Note that values of T_i(z) and T_i(z * g) are received from the prover and passed into
-    /// this function via the `ood_frame` parameter.
+    /// Note that values of T_i(z * g^o_j) are received from the prover and passed into this
+    /// function via the `ood_main_frame` and `ood_aux_frame` parameters.
     pub fn compose_trace_columns(
         &self,
         queried_main_trace_states: Table<E::BaseField>,
@@ -66,13 +78,11 @@ impl<E: FieldElement> DeepComposer<E> {
         ood_main_frame: EvaluationFrame<E>,
         ood_aux_frame: Option<EvaluationFrame<E>>,
     ) -> Vec<E> {
-        let ood_main_trace_states = [ood_main_frame.current(), ood_main_frame.next()];
-
         // when field extension is enabled, these will be set to conjugates of trace values at
         // z as well as conjugate of z itself. we do this only for the main trace since auxiliary
         // trace columns are in the extension field.
         let conjugate_values =
-            get_conjugate_values(self.field_extension, ood_main_trace_states[0], self.z[0]);
+            get_conjugate_values(self.field_extension, ood_main_frame.current(), self.z[0]);
 
         // compose columns of of the main trace segment
         let mut result = E::zeroed_vector(queried_main_trace_states.num_rows());
@@ -83,21 +93,19 @@ impl<E: FieldElement> DeepComposer<E> {
         {
             for (i, &value) in row.iter().enumerate() {
                 let value = E::from(value);
-                // compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z), multiply it by a composition
-                // coefficient, and add the result to T(x)
-                let t1 = (value - ood_main_trace_states[0][i]) / (x - self.z[0]);
-                *result += t1 * self.cc.trace[i].0;
-
-                // compute T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g), multiply it by a
-                // composition coefficient, and add the result to T(x)
-                let t2 = (value - ood_main_trace_states[1][i]) / (x - self.z[1]);
-                *result += t2 * self.cc.trace[i].1;
+                // for each frame offset, compute T_i,j(x) = (T_i(x) - T_i(z * g^o_j)) /
+                // (x - z * g^o_j), multiply it by a composition coefficient, and add the result
+                // to T(x)
+                for (j, &z) in self.z.iter().enumerate() {
+                    let t = (value - ood_main_frame.row(j)[i]) / (x - z);
+                    *result += t * self.cc.trace[i][j];
+                }
 
                 // when extension field is enabled compute
                 // T'''_i(x) = (T_i(x) - T_i(z_conjugate)) / (x - z_conjugate)
                 if let Some((z_conjugate, ref trace_at_z1_conjugates)) = conjugate_values {
                     let t3 = (value - trace_at_z1_conjugates[i]) / (x - z_conjugate);
-                    *result += t3 * self.cc.trace[i].2;
+                    *result += t3 * self.cc.trace_conjugate[i];
                 }
             }
         }
@@ -105,7 +113,6 @@ impl<E: FieldElement> DeepComposer<E> {
         // if the trace has auxiliary segments, compose columns from these segments as well
         if let Some(queried_aux_trace_states) = queried_aux_trace_states {
             let ood_aux_frame = ood_aux_frame.expect("missing auxiliary OOD frame");
-            let ood_aux_trace_states = [ood_aux_frame.current(), ood_aux_frame.next()];
 
             // we define this offset here because composition of the main trace columns has
             // consumed some number of composition coefficients already.
@@ -117,15 +124,13 @@ impl<E: FieldElement> DeepComposer<E> {
                 .zip(&self.x_coordinates)
             {
                 for (i, &value) in row.iter().enumerate() {
-                    // compute T'_i(x) = (T_i(x) - T_i(z)) / (x - z), multiply it by a composition
-                    // coefficient, and add the result to T(x)
-                    let t1 = (value - ood_aux_trace_states[0][i]) / (x - self.z[0]);
-                    *result += t1 * self.cc.trace[cc_offset + i].0;
-
-                    // compute T''_i(x) = (T_i(x) - T_i(z * g)) / (x - z * g), multiply it by a
-                    // composition coefficient, and add the result to T(x)
-                    let t2 = (value - ood_aux_trace_states[1][i]) / (x - self.z[1]);
-                    *result += t2 * self.cc.trace[cc_offset + i].1;
+                    // for each frame offset, compute T_i,j(x) = (T_i(x) - T_i(z * g^o_j)) /
+                    // (x - z * g^o_j), multiply it by a composition coefficient, and add the
+                    // result to T(x)
+                    for (j, &z) in self.z.iter().enumerate() {
+                        let t = (value - ood_aux_frame.row(j)[i]) / (x - z);
+                        *result += t * self.cc.trace[cc_offset + i][j];
+                    }
                 }
             }
         }
@@ -138,7 +143,8 @@ impl<E: FieldElement> DeepComposer<E> {
     ///
     /// - Assume each queried value is an evaluation of a composition polynomial column H_i(x).
     /// - For each H_i(x), compute H'_i(x) = (H_i(x) - H(z^m)) / (x - z^m), where m is the total
-    ///   number of composition polynomial columns.
+    ///   number of composition polynomial columns. In zero-knowledge mode, the columns are
+    ///   randomized by the prover and are evaluated at z instead of z^m.
     /// - Then, combine all H_i(x) values together by computing H(x) = sum(H_i(x) * cc_i) for
     ///   all i, where cc_i is the coefficient for the random linear combination drawn from the
     ///   public coin.
@@ -154,9 +160,13 @@ impl<E: FieldElement> DeepComposer<E> {
 
         let mut result = Vec::with_capacity(queried_evaluations.num_rows());
 
-        // compute z^m
-        let num_evaluation_columns = ood_evaluations.len() as u32;
-        let z_m = self.z[0].exp(num_evaluation_columns.into());
+        // compute z^m, or take z in zero-knowledge mode
+        let z_m = if self.zero_knowledge {
+            self.z[0]
+        } else {
+            let num_evaluation_columns = ood_evaluations.len() as u32;
+            self.z[0].exp(num_evaluation_columns.into())
+        };
 
         for (query_values, &x) in queried_evaluations.rows().zip(&self.x_coordinates) {
             let mut composition = E::ZERO;
@@ -172,6 +182,40 @@ impl<E: FieldElement> DeepComposer<E> {
         result
     }
 
+    /// For each queried row of committed periodic column values, combines the values into a
+    /// single value by computing their random linear combination as follows:
+    ///
+    /// - Assume each queried value is an evaluation of a periodic column polynomial P_i(x^k),
+    ///   where k is the number of cycles of the column in the execution trace.
+    /// - For each P_i(x^k), compute P'_i(x) = (P_i(x^k) - P_i(z^k)) / (x - z).
+    /// - Then, combine all P'_i(x) values together by computing P(x) = sum(P'_i(x) * cc_i) for
+    ///   all i, where cc_i is the coefficient for the random linear combination drawn from the
+    ///   public coin.
+    ///
+    /// Note that values of P_i(z^k) are received from the prover and passed into this function
+    /// via the `ood_values` parameter.
+    pub fn compose_periodic_columns(
+        &self,
+        queried_values: Table<E::BaseField>,
+        ood_values: Vec<E>,
+    ) -> Vec<E> {
+        assert_eq!(queried_values.num_rows(), self.x_coordinates.len());
+
+        let mut result = Vec::with_capacity(queried_values.num_rows());
+        for (query_values, &x) in queried_values.rows().zip(&self.x_coordinates) {
+            let mut composition = E::ZERO;
+            for (i, &value) in query_values.iter().enumerate() {
+                // compute P'_i(x) = (P_i(x^k) - P_i(z^k)) / (x - z)
+                let p_i = (E::from(value) - ood_values[i]) / (x - self.z[0]);
+                // multiply it by a pseudo-random coefficient, and add the result to P(x)
+                composition += p_i * self.cc.periodic[i];
+            }
+            result.push(composition);
+        }
+
+        result
+    }
+
     /// Combines trace and constraint compositions together, and also rases the degree of the
     /// resulting value by one to match trace polynomial degree. This is needed because when
     /// we divide evaluations by (x - z), (x - z * g) etc. the degree is reduced by one - so,
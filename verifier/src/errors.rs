@@ -6,13 +6,18 @@
 
 //! Contains common error types for prover and verifier.
 
+use air::{proof::BatchProof, ProofOptionsError};
 use core::fmt;
 use utils::string::String;
 
 // VERIFIER ERROR
 // ================================================================================================
 /// Represents an error returned by the verifier during an execution of the protocol.
+///
+/// The phase of the protocol in which an error occurred can be retrieved via
+/// [VerifierError::phase()].
 #[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum VerifierError {
     /// This error occurs when base field read by a verifier from a proof does not match the
     /// base field of AIR with which the verifier was instantiated.
@@ -20,28 +25,163 @@ pub enum VerifierError {
     /// This error occurs when the base field in which the proof was generated does not support
     /// field extension of degree specified by the proof.
     UnsupportedFieldExtension(usize),
+    /// This error occurs when trace info or proof options of a proof, or values of periodic
+    /// columns of the AIR instance for which the proof is verified, differ from the ones of the
+    /// prepared AIR with which the verifier was invoked; in a batch proof, periodic columns of
+    /// all instances must be the same.
+    InconsistentPreparedAir,
+    /// This error occurs when the hash function used by the prover to build commitments differs
+    /// from the commitment hash function with which the verifier was invoked.
+    InconsistentCommitmentHash,
+    /// This error occurs when the hash function used by the prover for the public coin differs
+    /// from the public coin hash function with which the verifier was invoked.
+    InconsistentCoinHash,
+    /// This error occurs when the low-degree extension domain specified by the proof context is
+    /// larger than the largest multiplicative subgroup of the base field of the AIR.
+    DomainTooLarge {
+        /// Size of the low-degree extension domain specified by the proof context.
+        requested: usize,
+        /// Size of the largest domain supported by the base field.
+        max_supported: usize,
+        /// Name of the base field type.
+        field: &'static str,
+    },
+    /// This error occurs when the number of queries specified by the proof is not smaller than
+    /// the size of the low-degree extension domain, and thus, the required number of unique query
+    /// positions cannot be drawn from this domain.
+    TooManyQueries {
+        /// Number of queries specified by the proof options.
+        num_queries: usize,
+        /// Size of the low-degree extension domain specified by the proof context.
+        lde_domain_size: usize,
+    },
+    /// This error occurs when the field in which the proof was generated provides fewer bits of
+    /// security for the low-degree extension domain than the minimum field security required by
+    /// the verifier (see [verify_with_min_field_security()](crate::verify_with_min_field_security)).
+    InsufficientFieldSecurity {
+        /// Number of bits of security provided by the field.
+        achieved: u32,
+        /// Minimum number of bits of security required by the verifier.
+        required: u32,
+    },
     /// This error occurs when a verifier cannot deserialize the specified proof.
     ProofDeserializationError(String),
     /// This error occurs when a verifier fails to draw a random value from a random coin
-    /// within a specified number of tries.
-    RandomCoinError,
+    /// within a specified number of tries during the specified phase of the protocol.
+    RandomCoinError(VerificationPhase),
     /// This error occurs when constraints evaluated over out-of-domain trace rows do not match
     /// evaluations of the constraint composition polynomial at the out-of-domain point.
-    InconsistentOodConstraintEvaluations,
+    InconsistentOodConstraintEvaluations {
+        /// Result of evaluating constraints over the out-of-domain trace rows.
+        expected: String,
+        /// Value of the constraint composition polynomial at the out-of-domain point computed
+        /// from the column evaluations sent by the prover.
+        actual: String,
+    },
     /// This error occurs when Merkle authentication paths of trace queries do not resolve to the
     /// execution trace commitment included in the proof.
-    TraceQueryDoesNotMatchCommitment,
+    TraceQueryDoesNotMatchCommitment {
+        /// Index of the trace commitment for which the queries did not match; this is the index of
+        /// the trace segment, unless a preceding auxiliary segment was built in several rounds.
+        segment: usize,
+        /// Trace segment commitment included in the proof.
+        expected: [u8; 32],
+        /// Root computed from the trace queries, if the queries could be resolved to a root.
+        actual: Option<[u8; 32]>,
+    },
     /// This error occurs when Merkle authentication paths of constraint evaluation queries do not
     /// resolve to the constraint evaluation commitment included in the proof.
-    ConstraintQueryDoesNotMatchCommitment,
+    ConstraintQueryDoesNotMatchCommitment {
+        /// Constraint evaluation commitment included in the proof.
+        expected: [u8; 32],
+        /// Root computed from the constraint queries, if the queries could be resolved to a root.
+        actual: Option<[u8; 32]>,
+    },
+    /// This error occurs when Merkle authentication paths of periodic column queries do not
+    /// resolve to the periodic column commitment defined by the AIR.
+    PeriodicQueryDoesNotMatchCommitment {
+        /// Periodic column commitment defined by the AIR.
+        expected: [u8; 32],
+        /// Root computed from the periodic column queries, if the queries could be resolved to a
+        /// root.
+        actual: Option<[u8; 32]>,
+    },
     /// This error occurs when the proof-of-work nonce hashed with the current state of the public
     /// coin resolves to a value which does not meet the proof-of-work threshold specified by the
-    // proof options.
-    QuerySeedProofOfWorkVerificationFailed,
+    /// proof options.
+    QuerySeedProofOfWorkVerificationFailed {
+        /// Number of leading zeros required by the proof options.
+        expected: u32,
+        /// Number of leading zeros in the value derived from the proof-of-work nonce.
+        actual: u32,
+    },
     /// This error occurs when the DEEP composition polynomial evaluations derived from trace and
     /// constraint evaluation queries do not represent a polynomial of the degree expected by the
     /// verifier.
     FriVerificationFailed(fri::VerifierError),
+    /// This error occurs when proof options could not be constructed from the specified
+    /// parameters.
+    InvalidProofOptions(ProofOptionsError),
+    /// This error occurs when a batch proof does not describe any instances of the computation.
+    EmptyBatch,
+    /// This error occurs when more sets of public inputs are provided to the verifier than a
+    /// batch proof can describe instances of the computation.
+    BatchTooLarge(usize),
+    /// This error occurs when the number of instances described by a batch proof differs from
+    /// the number of sets of public inputs provided to the verifier.
+    InconsistentBatchSize {
+        /// Number of sets of public inputs provided to the verifier.
+        expected: usize,
+        /// Number of instances described by the batch proof.
+        actual: usize,
+    },
+}
+
+impl VerifierError {
+    /// Returns the phase of the protocol during which this error occurred.
+    pub fn phase(&self) -> VerificationPhase {
+        match self {
+            Self::InconsistentBaseField
+            | Self::UnsupportedFieldExtension(_)
+            | Self::InconsistentPreparedAir
+            | Self::InconsistentCommitmentHash
+            | Self::InconsistentCoinHash
+            | Self::DomainTooLarge { .. }
+            | Self::TooManyQueries { .. }
+            | Self::InsufficientFieldSecurity { .. }
+            | Self::ProofDeserializationError(_)
+            | Self::InvalidProofOptions(_)
+            | Self::EmptyBatch
+            | Self::BatchTooLarge(_)
+            | Self::InconsistentBatchSize { .. } => VerificationPhase::ProofParsing,
+            Self::RandomCoinError(phase) => *phase,
+            Self::InconsistentOodConstraintEvaluations { .. } => VerificationPhase::OodConsistency,
+            Self::TraceQueryDoesNotMatchCommitment { .. } => VerificationPhase::TraceCommitment,
+            Self::ConstraintQueryDoesNotMatchCommitment { .. } => {
+                VerificationPhase::ConstraintCommitment
+            }
+            Self::PeriodicQueryDoesNotMatchCommitment { .. } => {
+                VerificationPhase::PeriodicCommitment
+            }
+            Self::QuerySeedProofOfWorkVerificationFailed { .. } => VerificationPhase::QuerySeed,
+            Self::FriVerificationFailed(err) => match err {
+                fri::VerifierError::LayerCommitmentMismatch(layer)
+                | fri::VerifierError::InvalidLayerFolding(layer, _)
+                | fri::VerifierError::DegreeTruncation(_, _, layer) => {
+                    VerificationPhase::FriLayer(*layer)
+                }
+                fri::VerifierError::RemainderCommitmentMismatch
+                | fri::VerifierError::InvalidRemainderFolding(_)
+                | fri::VerifierError::RemainderDegreeNotValid
+                | fri::VerifierError::RemainderDegreeMismatch(_) => VerificationPhase::FriRemainder,
+                fri::VerifierError::PublicCoinError(_)
+                | fri::VerifierError::UnsupportedFoldingFactor(_)
+                | fri::VerifierError::NumPositionEvaluationMismatch(_, _) => {
+                    VerificationPhase::FriCommitment
+                }
+            },
+        }
+    }
 }
 
 impl fmt::Display for VerifierError {
@@ -54,27 +194,141 @@ impl fmt::Display for VerifierError {
             Self::UnsupportedFieldExtension(degree) => {
                 write!(f, "field extension of degree {degree} is not supported for the proof base field")
             }
+            Self::InconsistentPreparedAir => {
+                write!(f, "trace info, proof options, or periodic columns of the proof do not match the prepared AIR")
+            }
+            Self::InconsistentCommitmentHash => {
+                write!(f, "commitment hash function of the proof does not match the specified commitment hash function")
+            }
+            Self::InconsistentCoinHash => {
+                write!(f, "public coin hash function of the proof does not match the specified public coin hash function")
+            }
+            Self::DomainTooLarge { requested, max_supported, field } => {
+                write!(f, "low-degree extension domain of {requested} elements is larger than the maximum domain size of {max_supported} elements supported by field {field}")
+            }
+            Self::TooManyQueries { num_queries, lde_domain_size } => {
+                write!(f, "cannot draw {num_queries} unique query positions from a low-degree extension domain of {lde_domain_size} elements")
+            }
+            Self::InsufficientFieldSecurity { achieved, required } => {
+                write!(f, "field provides only {achieved} bits of security for the low-degree extension domain of the proof, but at least {required} bits are required")
+            }
             Self::ProofDeserializationError(msg) => {
                 write!(f, "proof deserialization failed: {msg}")
             }
-            Self::RandomCoinError => {
-                write!(f, "failed to draw a random value from a random coin")
+            Self::RandomCoinError(phase) => {
+                write!(f, "failed to draw a random value from a random coin during {phase}")
             }
-            Self::InconsistentOodConstraintEvaluations => {
-                write!(f, "constraint evaluations over the out-of-domain frame are inconsistent")
+            Self::InconsistentOodConstraintEvaluations { expected, actual } => {
+                write!(f, "constraint evaluations over the out-of-domain frame are inconsistent: expected {expected}, but composition polynomial evaluated to {actual}")
             }
-            Self::TraceQueryDoesNotMatchCommitment => {
-                write!(f, "trace query did not match the commitment")
+            Self::TraceQueryDoesNotMatchCommitment { segment, expected, actual } => {
+                write!(f, "trace queries for segment {segment} did not match the commitment: expected root ")?;
+                write_root_mismatch(f, expected, actual)
             }
-            Self::ConstraintQueryDoesNotMatchCommitment => {
-                write!(f, "constraint query did not match the commitment")
+            Self::ConstraintQueryDoesNotMatchCommitment { expected, actual } => {
+                write!(f, "constraint queries did not match the commitment: expected root ")?;
+                write_root_mismatch(f, expected, actual)
             }
-            Self::QuerySeedProofOfWorkVerificationFailed => {
-                write!(f, "query seed proof-of-work verification failed")
+            Self::PeriodicQueryDoesNotMatchCommitment { expected, actual } => {
+                write!(f, "periodic column queries did not match the commitment: expected root ")?;
+                write_root_mismatch(f, expected, actual)
+            }
+            Self::QuerySeedProofOfWorkVerificationFailed { expected, actual } => {
+                write!(f, "query seed proof-of-work verification failed: expected at least {expected} leading zeros, but was {actual}")
             }
             Self::FriVerificationFailed(err) => {
                 write!(f, "verification of low-degree proof failed: {err}")
             }
+            Self::InvalidProofOptions(err) => {
+                write!(f, "invalid proof options: {err}")
+            }
+            Self::EmptyBatch => {
+                write!(f, "batch proof does not describe any instances of the computation")
+            }
+            Self::BatchTooLarge(num_instances) => {
+                write!(f, "batch proof can describe at most {} instances, but {num_instances} sets of public inputs were provided", BatchProof::MAX_NUM_INSTANCES)
+            }
+            Self::InconsistentBatchSize { expected, actual } => {
+                write!(f, "batch proof describes {actual} instances, but {expected} sets of public inputs were provided")
+            }
         }
     }
 }
+
+impl From<ProofOptionsError> for VerifierError {
+    fn from(err: ProofOptionsError) -> Self {
+        Self::InvalidProofOptions(err)
+    }
+}
+
+// VERIFICATION PHASE
+// ================================================================================================
+/// Identifies the phase of the verification protocol during which a [VerifierError] occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VerificationPhase {
+    /// Parsing of the proof and validation of its parameters against the AIR.
+    ProofParsing,
+    /// Processing of execution trace commitments, including checking queried trace states
+    /// against these commitments.
+    TraceCommitment,
+    /// Processing of the constraint evaluation commitment, including checking queried constraint
+    /// evaluations against this commitment.
+    ConstraintCommitment,
+    /// Checking queried periodic column values against the periodic column commitment defined
+    /// by the AIR.
+    PeriodicCommitment,
+    /// Checking consistency of constraint evaluations at the out-of-domain point.
+    OodConsistency,
+    /// Processing of FRI layer commitments.
+    FriCommitment,
+    /// Verification of the query proof-of-work and drawing of query positions.
+    QuerySeed,
+    /// Verification of queries against the FRI layer at the specified depth.
+    FriLayer(usize),
+    /// Verification of the FRI remainder.
+    FriRemainder,
+}
+
+impl fmt::Display for VerificationPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ProofParsing => write!(f, "proof parsing"),
+            Self::TraceCommitment => write!(f, "trace commitment verification"),
+            Self::ConstraintCommitment => write!(f, "constraint commitment verification"),
+            Self::PeriodicCommitment => write!(f, "periodic column commitment verification"),
+            Self::OodConsistency => write!(f, "out-of-domain consistency check"),
+            Self::FriCommitment => write!(f, "FRI commitment verification"),
+            Self::QuerySeed => write!(f, "query seed verification"),
+            Self::FriLayer(layer) => write!(f, "verification of FRI layer {layer}"),
+            Self::FriRemainder => write!(f, "FRI remainder verification"),
+        }
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Writes the expected root followed by the actual root (if available) as hex strings.
+fn write_root_mismatch(
+    f: &mut fmt::Formatter<'_>,
+    expected: &[u8; 32],
+    actual: &Option<[u8; 32]>,
+) -> fmt::Result {
+    write_hex(f, expected)?;
+    match actual {
+        Some(actual) => {
+            write!(f, ", but queries resolved to ")?;
+            write_hex(f, actual)
+        }
+        None => write!(f, ", but queries could not be resolved to a root"),
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8]) -> fmt::Result {
+    write!(f, "0x")?;
+    for byte in bytes {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
@@ -4,46 +4,72 @@
 // This source code is licensed under the MIT license found in the
 // LICENSE file in the root directory of this source tree.
 
-use air::{Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame};
-use math::{polynom, FieldElement};
+use crate::PreparedAir;
+use air::{
+    labels, Air, AuxTraceRandElements, ConstraintCompositionCoefficients, EvaluationFrame,
+    TranscriptEntryKind, TranscriptLog, TransitionEvaluator,
+};
+use crypto::ElementHasher;
+use math::FieldElement;
 use utils::collections::Vec;
 
 // CONSTRAINT EVALUATION
 // ================================================================================================
 
 /// Evaluates constraints for the specified evaluation frame.
-pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
+///
+/// Periodic column polynomials are taken from the `prepared` AIR. If the AIR commits to its
+/// periodic columns, values of these columns at `x` are taken from `committed_periodic_values`
+/// rather than computed by evaluating periodic column polynomials.
+///
+/// If a `log` is provided, intermediate values of the evaluation are recorded in it.
+#[allow(clippy::too_many_arguments)]
+pub fn evaluate_constraints<A, E, H>(
     air: &A,
+    prepared: &PreparedAir<A, H>,
     composition_coefficients: ConstraintCompositionCoefficients<E>,
     main_trace_frame: &EvaluationFrame<E>,
     aux_trace_frame: &Option<EvaluationFrame<E>>,
     aux_rand_elements: AuxTraceRandElements<E>,
+    committed_periodic_values: &[E],
     x: E,
-) -> E {
+    mut log: Option<&mut TranscriptLog>,
+) -> E
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
     // 1 ----- evaluate transition constraints ----------------------------------------------------
 
     // initialize a buffer to hold transition constraint evaluations
     let t_constraints = air.get_transition_constraints(&composition_coefficients.transition);
 
-    // compute values of periodic columns at x
-    let periodic_values = air
-        .get_periodic_column_polys()
-        .iter()
-        .map(|poly| {
-            let num_cycles = air.trace_length() / poly.len();
-            let x = x.exp((num_cycles as u32).into());
-            polynom::eval(poly, x)
-        })
-        .collect::<Vec<_>>();
+    // compute values of periodic columns at x; values of periodic columns used only by
+    // auxiliary transition constraints follow the values of the columns used by the main
+    // transition constraints; committed periodic columns are not evaluated because their values
+    // are provided by the prover and are checked against the commitment during DEEP composition
+    let mut periodic_values = if air.has_committed_periodic_columns() {
+        committed_periodic_values.to_vec()
+    } else {
+        prepared.main_periodic_polys().evaluate_at(x)
+    };
+    let num_main_periodic_columns = periodic_values.len();
+    periodic_values.append(&mut prepared.aux_periodic_polys().evaluate_at(x));
 
     // evaluate transition constraints for the main trace segment
     let mut t_evaluations1 = E::zeroed_vector(t_constraints.num_main_constraints());
-    air.evaluate_transition(main_trace_frame, &periodic_values, &mut t_evaluations1);
+    let transition_evaluator = TransitionEvaluator::new(air);
+    transition_evaluator.evaluate_main(
+        main_trace_frame,
+        &periodic_values[..num_main_periodic_columns],
+        &mut t_evaluations1,
+    );
 
     // evaluate transition constraints for auxiliary trace segments (if any)
     let mut t_evaluations2 = E::zeroed_vector(t_constraints.num_aux_constraints());
     if let Some(aux_trace_frame) = aux_trace_frame {
-        air.evaluate_aux_transition(
+        transition_evaluator.evaluate_aux(
             main_trace_frame,
             aux_trace_frame,
             &periodic_values,
@@ -57,16 +83,41 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
     // by the divisor of transition constraints.
     let mut result = t_constraints.combine_evaluations::<E>(&t_evaluations1, &t_evaluations2, x);
 
+    if let Some(log) = log.as_deref_mut() {
+        let divisors = t_constraints
+            .divisors()
+            .iter()
+            .map(|divisor| divisor.evaluate_at(x))
+            .collect::<Vec<E>>();
+        let kind = TranscriptEntryKind::Evaluation;
+        log.record(kind, labels::OOD_EVAL_PERIODIC_VALUES, &periodic_values);
+        log.record(kind, labels::OOD_EVAL_MAIN_TRANSITIONS, &t_evaluations1);
+        if aux_trace_frame.is_some() {
+            log.record(kind, labels::OOD_EVAL_AUX_TRANSITIONS, &t_evaluations2);
+        }
+        log.record(kind, labels::OOD_EVAL_TRANSITION_DIVISORS, &divisors);
+        log.record(kind, labels::OOD_EVAL_TRANSITION_COMBINATION, &[result]);
+    }
+
     // 2 ----- evaluate boundary constraints ------------------------------------------------------
 
     // get boundary constraints grouped by common divisor from the AIR
     let b_constraints =
         air.get_boundary_constraints(&aux_rand_elements, &composition_coefficients.boundary);
 
+    // evaluate divisors shared by boundary constraint groups; groups of constraints against the
+    // main and auxiliary trace segments may have the same divisor, and thus, each divisor is
+    // evaluated only once
+    let b_shared_divisors = b_constraints.evaluate_divisors_at(x);
+
     // cache power of x here so that we only re-compute it when degree_adjustment changes
     let mut degree_adjustment = b_constraints.main_constraints()[0].degree_adjustment();
     let mut xp = x.exp(degree_adjustment.into());
 
+    // evaluations and divisors of boundary constraint groups are collected only for the log
+    let mut b_evaluations = Vec::new();
+    let mut b_divisors = Vec::new();
+
     // iterate over boundary constraint groups for the main trace segment (each group has a
     // distinct divisor), evaluate constraints in each group and add their combination to the
     // result
@@ -78,7 +129,13 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
             xp = x.exp(degree_adjustment.into());
         }
         // evaluate all constraints in the group, and add the evaluation to the result
-        result += group.evaluate_at(main_trace_frame.current(), x, xp);
+        let divisor = b_shared_divisors[group.divisor_idx()];
+        let evaluation = group.evaluate_at(main_trace_frame.current(), x, xp, divisor);
+        result += evaluation;
+        if log.is_some() {
+            b_evaluations.push(evaluation);
+            b_divisors.push(divisor);
+        }
     }
 
     // iterate over boundary constraint groups for auxiliary trace segments (each group has a
@@ -93,9 +150,22 @@ pub fn evaluate_constraints<A: Air, E: FieldElement<BaseField = A::BaseField>>(
                 xp = x.exp(degree_adjustment.into());
             }
             // evaluate all constraints in the group, and add the evaluation to the result
-            result += group.evaluate_at(aux_trace_frame.current(), x, xp);
+            let divisor = b_shared_divisors[group.divisor_idx()];
+            let evaluation = group.evaluate_at(aux_trace_frame.current(), x, xp, divisor);
+            result += evaluation;
+            if log.is_some() {
+                b_evaluations.push(evaluation);
+                b_divisors.push(divisor);
+            }
         }
     }
 
+    if let Some(log) = log {
+        let kind = TranscriptEntryKind::Evaluation;
+        log.record(kind, labels::OOD_EVAL_BOUNDARY_DIVISORS, &b_divisors);
+        log.record(kind, labels::OOD_EVAL_BOUNDARY_GROUPS, &b_evaluations);
+        log.record(kind, labels::OOD_EVAL_CONSTRAINT_COMPOSITION, &[result]);
+    }
+
     result
 }
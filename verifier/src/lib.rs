@@ -16,6 +16,36 @@
 //! 2. Execute [verify()] function and supply the AIR of your computation together with the
 //!    [StarkProof] and related public inputs as parameters.
 //!
+//! To get access to the intermediate values accepted by the verifier (e.g., the out-of-domain
+//! evaluation frame, the DEEP composition coefficients, or the query positions), execute
+//! [verify_with_report()] function instead. This function performs exactly the same checks as
+//! [verify()], and returns a [VerificationReport] on success.
+//!
+//! To verify many proofs of the same computation (i.e., proofs generated for the same AIR with
+//! the same trace info and proof options, but for different public inputs), build a
+//! [PreparedAir] once and execute [verify_prepared()] function for each proof. This skips
+//! re-computation of data which does not depend on public inputs (e.g., periodic column
+//! polynomials and commitments) while performing exactly the same checks as [verify()].
+//!
+//! To debug a proof which fails to verify, execute [verify_with_transcript_log()] function. In
+//! addition to the result of the verification, this function returns a [TranscriptLog] of all
+//! values which went through the public coin, which can be compared to the log recorded by the
+//! prover for the same proof.
+//!
+//! By default, the verifier rejects proofs for which the field provides fewer than
+//! [ProofOptions::DEFAULT_MIN_FIELD_SECURITY] bits of security, regardless of the options
+//! specified in the proof. To verify a proof with a different minimum (e.g., a proof generated in
+//! a small field without an extension for testing purposes), execute
+//! [verify_with_min_field_security()] function instead.
+//!
+//! By default, the same hash function is used to build commitments and to instantiate the public
+//! coin. To verify a proof generated with different hash functions for these purposes, execute
+//! [verify_with_coin_hasher()] function instead.
+//!
+//! To verify a [BatchProof] attesting to several instances of the same computation at once,
+//! execute [verify_batch()] function and supply the public inputs of all instances in the order
+//! in which the instances appear in the proof.
+//!
 //! # Performance
 //! Proof verification is extremely fast and is nearly independent of the complexity of the
 //! computation being verified. In vast majority of cases proofs can be verified in 3 - 5 ms
@@ -34,25 +64,32 @@
 extern crate alloc;
 
 pub use air::{
-    proof::StarkProof, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ConstraintCompositionCoefficients, ConstraintDivisor,
-    DeepCompositionCoefficients, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    gadgets, introspection,
+    proof::{BatchProof, InstanceProof, StarkProof},
+    Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint, BoundaryConstraintGroup,
+    ConstraintCompositionCoefficients, ConstraintDivisor, DeepCompositionCoefficients,
+    EvaluationFrame, FieldExtension, ProofOptions, ProofOptionsBuilder, ProofOptionsError,
+    TraceInfo, TranscriptDivergence, TranscriptEntry, TranscriptEntryKind, TranscriptLog,
+    TranscriptType, TransitionConstraintDegree, TransitionConstraintGroup, TransitionDivisor,
 };
+use air::{labels, proof::Context, PublicCoin, QueryPositions};
+use core::cmp;
 
 pub use math;
 use math::{
-    fields::{CubeExtension, QuadExtension},
-    FieldElement,
+    add_in_place,
+    fields::{CubeExtension, QuadExtension, SexticExtension},
+    FieldElement, StarkField, ToElements,
 };
 
-use utils::collections::Vec;
+use utils::{collections::Vec, string::ToString};
 pub use utils::{
-    ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SliceReader,
+    ByteReader, ByteWriter, CanonicalDeserializable, CanonicalSerializable, Deserializable,
+    DeserializationError, Serializable, SliceReader,
 };
 
 pub use crypto;
-use crypto::{ElementHasher, RandomCoin};
+use crypto::{ElementHasher, Transcript};
 
 use fri::FriVerifier;
 
@@ -66,7 +103,19 @@ mod composer;
 use composer::DeepComposer;
 
 mod errors;
-pub use errors::VerifierError;
+pub use errors::{VerificationPhase, VerifierError};
+
+mod report;
+pub use report::VerificationReport;
+
+mod prepared;
+pub use prepared::PreparedAir;
+
+mod batch;
+pub use batch::verify_batch;
+
+#[cfg(test)]
+mod tests;
 
 // VERIFIER
 // ================================================================================================
@@ -81,61 +130,435 @@ pub use errors::VerifierError;
 /// a correct execution of the computation. This could happen for many various reasons, including:
 /// - The specified proof was generated for a different computation.
 /// - The specified proof was generated for this computation but for different public inputs.
-#[rustfmt::skip]
 pub fn verify<AIR: Air, HashFn: ElementHasher<BaseField = AIR::BaseField>>(
     proof: StarkProof,
     pub_inputs: AIR::PublicInputs,
 ) -> Result<(), VerifierError> {
-    // build a seed for the public coin; the initial seed is the hash of public inputs and proof
-    // context, but as the protocol progresses, the coin will be reseeded with the info received
-    // from the prover
+    verify_with_report::<AIR, HashFn>(proof, pub_inputs).map(|_| ())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and returns the intermediate values accepted by the verifier.
+///
+/// This function performs exactly the same checks as [verify()]. If the verification is
+/// successful, a [VerificationReport] containing the out-of-domain evaluation frame, the trace
+/// and constraint DEEP composition coefficients, the query positions, the FRI layer commitments,
+/// and the conjectured security level of the proof is returned.
+///
+/// # Errors
+/// Returns an error under the same conditions as [verify()].
+pub fn verify_with_report<AIR: Air, HashFn: ElementHasher<BaseField = AIR::BaseField>>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<VerificationReport<HashFn>, VerifierError> {
+    dispatch_verification::<AIR, HashFn, HashFn>(
+        proof,
+        pub_inputs,
+        ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+        None,
+        None,
+    )
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and that the field in which the proof was generated provides at least `min_field_security`
+/// bits of security for the low-degree extension domain of the proof.
+///
+/// This function performs the same checks as [verify()], but replaces the minimum field security
+/// of the verifier, which defaults to [ProofOptions::DEFAULT_MIN_FIELD_SECURITY] bits. Setting
+/// `min_field_security` to zero disables the check; the security of proofs accepted in this way
+/// is limited by the size of the field, and they should not be relied upon in production.
+///
+/// The minimum field security specified in the proof options is not taken into account by the
+/// verifier, as these options are provided by the prover.
+///
+/// # Errors
+/// Returns an error if the field provides fewer than `min_field_security` bits of security, and
+/// otherwise, under the same conditions as [verify()].
+pub fn verify_with_min_field_security<AIR, HashFn>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    min_field_security: u32,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+{
+    dispatch_verification::<AIR, HashFn, HashFn>(proof, pub_inputs, min_field_security, None, None)
+        .map(|_| ())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs,
+/// and records a log of the verification.
+///
+/// This function performs exactly the same checks as [verify_with_report()]. In addition to the
+/// result of the verification, it returns a [TranscriptLog] containing all values absorbed into
+/// and drawn from the public coin, as well as intermediate values of the out-of-domain
+/// consistency check. The log is returned even if the verification fails, and can be compared to
+/// the log recorded by the prover via [TranscriptLog::diff()] to find the first value on which
+/// the prover and the verifier disagree.
+pub fn verify_with_transcript_log<AIR, HashFn>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> (
+    Result<VerificationReport<HashFn>, VerifierError>,
+    TranscriptLog,
+)
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+{
+    let mut log = TranscriptLog::new();
+    let result = dispatch_verification::<AIR, HashFn, HashFn>(
+        proof,
+        pub_inputs,
+        ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+        None,
+        Some(&mut log),
+    );
+    (result, log)
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// for a proof in which commitments were built using `HashFn` while the public coin was
+/// instantiated with `CoinHashFn`.
+///
+/// This function performs the same checks as [verify()], and additionally makes sure that the
+/// hash functions recorded in the proof context match `HashFn` and `CoinHashFn`. Commitments
+/// read from the proof are absorbed into the public coin by converting them into digests of
+/// `CoinHashFn`.
+///
+/// # Errors
+/// Returns an error if the commitment hash function or the public coin hash function of the
+/// proof differs from `HashFn` or `CoinHashFn` respectively, and otherwise, under the same
+/// conditions as [verify()].
+pub fn verify_with_coin_hasher<AIR, HashFn, CoinHashFn>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    CoinHashFn: ElementHasher<BaseField = AIR::BaseField>,
+    CoinHashFn::Digest: From<HashFn::Digest>,
+{
+    dispatch_verification::<AIR, HashFn, CoinHashFn>(
+        proof,
+        pub_inputs,
+        ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+        None,
+        None,
+    )
+    .map(|_| ())
+}
+
+/// Verifies that the specified computation was executed correctly against the specified inputs
+/// using data precomputed in the `prepared` AIR.
+///
+/// This function performs exactly the same checks as [verify()], but does not re-compute data
+/// which does not depend on public inputs; thus, it is faster when many proofs of the same
+/// computation need to be verified.
+///
+/// # Errors
+/// Returns an error if trace info or proof options of the `proof`, or values of periodic columns
+/// of the AIR instantiated for the `pub_inputs`, are different from the ones of the `prepared`
+/// AIR, and otherwise, under the same conditions as [verify()].
+pub fn verify_prepared<AIR: Air, HashFn: ElementHasher<BaseField = AIR::BaseField>>(
+    prepared: &PreparedAir<AIR, HashFn>,
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+) -> Result<(), VerifierError> {
+    if proof.get_trace_info() != *prepared.trace_info() || proof.options() != prepared.options() {
+        return Err(VerifierError::InconsistentPreparedAir);
+    }
+    dispatch_verification::<AIR, HashFn, HashFn>(
+        proof,
+        pub_inputs,
+        ProofOptions::DEFAULT_MIN_FIELD_SECURITY,
+        Some(prepared),
+        None,
+    )
+    .map(|_| ())
+}
+
+/// Instantiates the AIR and the verifier channel for the specified proof, and runs the version
+/// of the verification procedure for the extension field specified by the proof options.
+///
+/// The proof is rejected if the field provides fewer than `min_field_security` bits of security
+/// for its low-degree extension domain. If `prepared` AIR is not provided, data which does not
+/// depend on public inputs is computed for this proof only. If a `log` is provided, the public coin records all operations into it;
+/// the log is written even if the verification fails.
+#[rustfmt::skip]
+fn dispatch_verification<AIR, HashFn, CoinHashFn>(
+    proof: StarkProof,
+    pub_inputs: AIR::PublicInputs,
+    min_field_security: u32,
+    prepared: Option<&PreparedAir<AIR, HashFn>>,
+    log: Option<&mut TranscriptLog>,
+) -> Result<VerificationReport<HashFn>, VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    CoinHashFn: ElementHasher<BaseField = AIR::BaseField>,
+    CoinHashFn::Digest: From<HashFn::Digest>,
+{
+    // make sure the proof context is consistent with the verifier before any values are
+    // computed for the AIR
+    validate_context::<AIR, HashFn, CoinHashFn>(&proof.context, min_field_security)?;
+
+    // compute the security level of the proof before the proof is consumed by the channel
+    let security_level = proof.security_level::<HashFn>(true);
+
+    // build a seed for the public coin; the initial seed is the hash of the canonical element
+    // encoding of public inputs and proof context, but as the protocol progresses, the coin will
+    // be reseeded with the info received from the prover
     let mut public_coin_seed = Vec::new();
-    pub_inputs.write_into(&mut public_coin_seed);
-    proof.context.write_into(&mut public_coin_seed);
+    pub_inputs.to_elements().write_into(&mut public_coin_seed);
+    proof.context.write_unversioned_into(&mut public_coin_seed);
 
     // create AIR instance for the computation specified in the proof
     let air = AIR::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
 
+    // prepare data which does not depend on public inputs, unless it has been prepared already;
+    // data prepared from another instance can be used only if the periodic columns of both
+    // instances are the same
+    let local_prepared;
+    let prepared = match prepared {
+        Some(prepared) if !prepared.is_consistent_with(&air) => {
+            return Err(VerifierError::InconsistentPreparedAir);
+        }
+        Some(prepared) => prepared,
+        None => {
+            local_prepared = PreparedAir::new(&air);
+            &local_prepared
+        }
+    };
+
+    // the public coin is instantiated as the transcript type specified in the proof context; this
+    // way, a proof generated with a different transcript type fails to verify
+    let mut public_coin = PublicCoin::new(air.options().transcript_type(), &public_coin_seed);
+    if log.is_some() {
+        public_coin = public_coin.with_log();
+    }
+
     // figure out which version of the generic proof verification procedure to run. this is a sort
-    // of static dispatch for selecting two generic parameter: extension field and hash function.
-    match air.options().field_extension() {
+    // of static dispatch for selecting the extension field generic parameter.
+    let result = match air.options().field_extension() {
         FieldExtension::None => {
-            let public_coin = RandomCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, AIR::BaseField, HashFn>(air, channel, public_coin)
+            let channel = VerifierChannel::new(&air, proof, prepared.periodic_root())?;
+            perform_verification::<AIR, AIR::BaseField, HashFn, CoinHashFn>(air, prepared, channel, &mut public_coin, security_level)
         },
         FieldExtension::Quadratic => {
             if !<QuadExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(2));
             }
-            let public_coin = RandomCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn>(air, channel, public_coin)
+            let channel = VerifierChannel::new(&air, proof, prepared.periodic_root())?;
+            perform_verification::<AIR, QuadExtension<AIR::BaseField>, HashFn, CoinHashFn>(air, prepared, channel, &mut public_coin, security_level)
         },
         FieldExtension::Cubic => {
             if !<CubeExtension<AIR::BaseField>>::is_supported() {
                 return Err(VerifierError::UnsupportedFieldExtension(3));
             }
-            let public_coin = RandomCoin::new(&public_coin_seed);
-            let channel = VerifierChannel::new(&air, proof)?;
-            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn>(air, channel, public_coin)
+            let channel = VerifierChannel::new(&air, proof, prepared.periodic_root())?;
+            perform_verification::<AIR, CubeExtension<AIR::BaseField>, HashFn, CoinHashFn>(air, prepared, channel, &mut public_coin, security_level)
+        },
+        FieldExtension::Sextic => {
+            if !<SexticExtension<AIR::BaseField>>::is_supported() {
+                return Err(VerifierError::UnsupportedFieldExtension(6));
+            }
+            let channel = VerifierChannel::new(&air, proof, prepared.periodic_root())?;
+            perform_verification::<AIR, SexticExtension<AIR::BaseField>, HashFn, CoinHashFn>(air, prepared, channel, &mut public_coin, security_level)
         },
+    };
+
+    if let (Some(log), Some(recorded)) = (log, public_coin.take_log()) {
+        *log = recorded;
     }
+    result
 }
 
 // VERIFICATION PROCEDURE
 // ================================================================================================
 /// Performs the actual verification by reading the data from the `channel` and making sure it
 /// attests to a correct execution of the computation specified by the provided `air`.
-fn perform_verification<A, E, H>(
+///
+/// On success, returns a report containing the intermediate values accepted by the verifier.
+fn perform_verification<A, E, H, C>(
     air: A,
+    prepared: &PreparedAir<A, H>,
     mut channel: VerifierChannel<E, H>,
-    mut public_coin: RandomCoin<A::BaseField, H>,
+    public_coin: &mut PublicCoin<A::BaseField, C>,
+    security_level: u32,
+) -> Result<VerificationReport<H>, VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+    C: ElementHasher<BaseField = A::BaseField>,
+    C::Digest: From<H::Digest>,
+{
+    // 1 - 3 ----- trace and constraint commitments, OOD consistency check ------------------------
+    let ood_check = check_ood_consistency(&air, prepared, &mut channel, public_coin)?;
+
+    // 4 ----- FRI commitments --------------------------------------------------------------------
+    // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
+    // verifier's perspective, this is equivalent to executing the commit phase of the FRI protocol.
+    // The verifier uses these commitments to update the public coin and draw random points alpha
+    // from them; in the interactive version of the protocol, the verifier sends these alphas to
+    // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
+    let fri_verifier = FriVerifier::new(
+        &mut channel,
+        public_coin,
+        air.options().to_fri_options(),
+        air.trace_poly_degree(),
+    )
+    .map_err(VerifierError::FriVerificationFailed)?;
+    // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
+
+    // 5 ----- trace and constraint queries -------------------------------------------------------
+    let (query_positions, pow_difficulty) =
+        draw_query_positions(&air, public_coin, channel.read_pow_nonce())?;
+
+    // build the report before the OOD frame is consumed by the DEEP composer
+    let report = VerificationReport::new(
+        &ood_check.main_trace_frame,
+        ood_check.aux_trace_frame.as_ref(),
+        &ood_check.constraint_evaluations,
+        &ood_check.deep_coefficients,
+        query_positions.shared().to_vec(),
+        fri_verifier.layer_commitments().to_vec(),
+        pow_difficulty,
+        security_level,
+    );
+
+    // 6 ----- DEEP composition -------------------------------------------------------------------
+    // compute evaluations of the DEEP composition polynomial at the queried positions
+    let deep_evaluations =
+        compose_deep_evaluations(&air, &mut channel, &query_positions, ood_check)?;
+
+    // 7 ----- Verify low-degree proof -------------------------------------------------------------
+    // make sure that evaluations of the DEEP composition polynomial we computed in the previous
+    // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
+    fri_verifier
+        .verify(&mut channel, &deep_evaluations, query_positions.shared())
+        .map_err(VerifierError::FriVerificationFailed)?;
+
+    Ok(report)
+}
+
+// VERIFICATION STEPS
+// ================================================================================================
+
+/// Makes sure that a proof with the specified `context` can be verified for a computation
+/// specified by `AIR`, with commitments built using `HashFn` and the public coin instantiated
+/// with `CoinHashFn` in the base field of the `AIR`, and that the field provides at least
+/// `min_field_security` bits of security for the low-degree extension domain of the proof.
+fn validate_context<AIR, HashFn, CoinHashFn>(
+    context: &Context,
+    min_field_security: u32,
 ) -> Result<(), VerifierError>
+where
+    AIR: Air,
+    HashFn: ElementHasher<BaseField = AIR::BaseField>,
+    CoinHashFn: ElementHasher<BaseField = AIR::BaseField>,
+{
+    // make sure the proof was generated in the base field of the AIR; the security level of the
+    // proof is estimated from the modulus recorded in the context, and thus, it cannot be trusted
+    // until this check passes
+    if AIR::BaseField::get_modulus_le_bytes() != context.field_modulus_bytes() {
+        return Err(VerifierError::InconsistentBaseField);
+    }
+
+    // make sure the proof was generated with the hash functions the verifier was invoked with
+    if context.commitment_hash_id() != Context::hash_fingerprint::<HashFn>() {
+        return Err(VerifierError::InconsistentCommitmentHash);
+    }
+    if context.coin_hash_id() != Context::hash_fingerprint::<CoinHashFn>() {
+        return Err(VerifierError::InconsistentCoinHash);
+    }
+
+    // make sure the low-degree extension domain specified by the proof fits into the base field
+    // before any domain-dependent values are computed for the AIR
+    let lde_domain_size = context
+        .options()
+        .trace_poly_size(context.trace_length())
+        .saturating_mul(context.options().blowup_factor());
+    if lde_domain_size > AIR::BaseField::MAX_DOMAIN_SIZE {
+        return Err(VerifierError::DomainTooLarge {
+            requested: lde_domain_size,
+            max_supported: AIR::BaseField::MAX_DOMAIN_SIZE,
+            field: core::any::type_name::<AIR::BaseField>(),
+        });
+    }
+
+    // all query positions must be unique, and thus, there must be fewer queries than there are
+    // elements in the low-degree extension domain
+    let options = context.options();
+    let num_queries = cmp::max(
+        options.num_trace_queries(),
+        options.num_constraint_queries(),
+    );
+    if num_queries >= lde_domain_size {
+        return Err(VerifierError::TooManyQueries {
+            num_queries,
+            lde_domain_size,
+        });
+    }
+
+    // the field must provide the minimum security required by the verifier; the minimum
+    // specified by the proof options is chosen by the prover, and thus, it is not used here. the
+    // field extension is taken from the options, and thus, this is checked for the field in which
+    // the proof was actually generated
+    let achieved = options.field_security(AIR::BaseField::MODULUS_BITS, lde_domain_size);
+    if achieved < min_field_security {
+        return Err(VerifierError::InsufficientFieldSecurity {
+            achieved,
+            required: min_field_security,
+        });
+    }
+
+    // the trace domain must not overlap the low-degree extension domain; the AIR cannot be
+    // instantiated for such options, and thus, they must be rejected before the AIR is built
+    let offset_ratio =
+        options.trace_domain_offset::<AIR::BaseField>() / options.domain_offset::<AIR::BaseField>();
+    if offset_ratio.exp((lde_domain_size as u64).into()) == AIR::BaseField::ONE {
+        return Err(VerifierError::InvalidProofOptions(
+            ProofOptionsError::TraceDomainOffsetInLdeDomain,
+        ));
+    }
+
+    Ok(())
+}
+
+/// Values sent by the prover and drawn by the verifier for a single instance of a computation
+/// which are needed to compute the DEEP composition polynomial of the instance.
+struct OodCheck<E: FieldElement> {
+    z: E,
+    deep_coefficients: DeepCompositionCoefficients<E>,
+    main_trace_frame: EvaluationFrame<E>,
+    aux_trace_frame: Option<EvaluationFrame<E>>,
+    constraint_evaluations: Vec<E>,
+    periodic_values: Vec<E>,
+}
+
+/// Reads the trace and constraint commitments and the out-of-domain frame of a single instance
+/// of a computation from the `channel`, and makes sure that the out-of-domain frame is
+/// consistent with the constraints of the specified `air`.
+///
+/// All values read from the channel are absorbed into the `public_coin`; on success, the DEEP
+/// composition coefficients for the instance are drawn from the `public_coin`.
+fn check_ood_consistency<A, E, H, C>(
+    air: &A,
+    prepared: &PreparedAir<A, H>,
+    channel: &mut VerifierChannel<E, H>,
+    public_coin: &mut PublicCoin<A::BaseField, C>,
+) -> Result<OodCheck<E>, VerifierError>
 where
     A: Air,
     E: FieldElement<BaseField = A::BaseField>,
     H: ElementHasher<BaseField = A::BaseField>,
+    C: ElementHasher<BaseField = A::BaseField>,
+    C::Digest: From<H::Digest>,
 {
     // 1 ----- trace commitment -------------------------------------------------------------------
     // Read the commitments to evaluations of the trace polynomials over the LDE domain sent by the
@@ -149,118 +572,178 @@ where
     let trace_commitments = channel.read_trace_commitments();
 
     // reseed the coin with the commitment to the main trace segment
-    public_coin.reseed(trace_commitments[0]);
+    public_coin.absorb(labels::TRACE_COMMITMENT, trace_commitments[0].into());
 
-    // process auxiliary trace segments (if any), to build a set of random elements for each segment
+    // process auxiliary trace segments (if any), to build a set of random elements for each
+    // segment; for segments built in several rounds, the elements of each round are drawn before
+    // the commitment to the columns of the round is absorbed, in the same order as the prover did
     let mut aux_trace_rand_elements = AuxTraceRandElements::<E>::new();
-    for (i, commitment) in trace_commitments.iter().skip(1).enumerate() {
-        let rand_elements = air
-            .get_aux_trace_segment_random_elements(i, &mut public_coin)
-            .map_err(|_| VerifierError::RandomCoinError)?;
-        aux_trace_rand_elements.add_segment_elements(rand_elements);
-        public_coin.reseed(*commitment);
+    let mut aux_commitments = trace_commitments.iter().skip(1);
+    for i in 0..air.trace_layout().num_aux_segments() {
+        for j in 0..air.trace_layout().num_aux_segment_rounds(i) {
+            let rand_elements = air
+                .get_aux_trace_round_random_elements(i, j, public_coin)
+                .map_err(|_| VerifierError::RandomCoinError(VerificationPhase::TraceCommitment))?;
+            if j == 0 {
+                aux_trace_rand_elements.add_segment_elements(rand_elements);
+            } else {
+                aux_trace_rand_elements.add_round_elements(rand_elements);
+            }
+            let commitment = aux_commitments.next().expect("missing trace commitment");
+            public_coin.absorb(labels::TRACE_COMMITMENT, (*commitment).into());
+        }
     }
 
     // build random coefficients for the composition polynomial
     let constraint_coeffs = air
-        .get_constraint_composition_coefficients(&mut public_coin)
-        .map_err(|_| VerifierError::RandomCoinError)?;
+        .get_constraint_composition_coefficients(public_coin)
+        .map_err(|_| VerifierError::RandomCoinError(VerificationPhase::TraceCommitment))?;
 
     // 2 ----- constraint commitment --------------------------------------------------------------
     // read the commitment to evaluations of the constraint composition polynomial over the LDE
     // domain sent by the prover, use it to update the public coin, and draw an out-of-domain point
     // z from the coin; in the interactive version of the protocol, the verifier sends this point z
     // to the prover, and the prover evaluates trace and constraint composition polynomials at z,
-    // and sends the results back to the verifier.
+    // and sends the results back to the verifier. points which fall into the trace domain or into
+    // the LDE domain are discarded, and a new point is drawn instead.
     let constraint_commitment = channel.read_constraint_commitment();
-    public_coin.reseed(constraint_commitment);
-    let z = public_coin
-        .draw::<E>()
-        .map_err(|_| VerifierError::RandomCoinError)?;
+    public_coin.absorb(labels::CONSTRAINT_COMMITMENT, constraint_commitment.into());
+    let z = loop {
+        let z = public_coin
+            .draw::<E>(labels::OOD_POINT)
+            .map_err(|_| VerifierError::RandomCoinError(VerificationPhase::ConstraintCommitment))?;
+        if air.is_out_of_domain(z) {
+            break z;
+        }
+    };
 
     // 3 ----- OOD consistency check --------------------------------------------------------------
     // make sure that evaluations obtained by evaluating constraints over the out-of-domain frame
     // are consistent with the evaluations of composition polynomial columns sent by the prover
 
     // read the out-of-domain trace frames (the main trace frame and auxiliary trace frame, if
-    // provided) sent by the prover and evaluate constraints over them; also, reseed the public
-    // coin with the OOD frames received from the prover.
+    // provided) sent by the prover, and reseed the public coin with them.
+    // if the AIR commits to its periodic columns, values of these columns at z are also sent
+    // by the prover; otherwise, the verifier evaluates periodic columns at z itself.
     let (ood_main_trace_frame, ood_aux_trace_frame) = channel.read_ood_trace_frame();
-    let ood_constraint_evaluation_1 = evaluate_constraints(
-        &air,
-        constraint_coeffs,
-        &ood_main_trace_frame,
-        &ood_aux_trace_frame,
-        aux_trace_rand_elements,
-        z,
-    );
-
-    if let Some(ref aux_trace_frame) = ood_aux_trace_frame {
-        // when the trace contains auxiliary segments, append auxiliary trace elements at the
-        // end of main trace elements for both current and next rows in the frame. this is
-        // needed to be consistent with how the prover writes OOD frame into the channel.
-
-        let mut current = ood_main_trace_frame.current().to_vec();
-        current.extend_from_slice(aux_trace_frame.current());
-        public_coin.reseed(H::hash_elements(&current));
-
-        let mut next = ood_main_trace_frame.next().to_vec();
-        next.extend_from_slice(aux_trace_frame.next());
-        public_coin.reseed(H::hash_elements(&next));
-    } else {
-        public_coin.reseed(H::hash_elements(ood_main_trace_frame.current()));
-        public_coin.reseed(H::hash_elements(ood_main_trace_frame.next()));
+    let ood_periodic_values = channel.read_ood_periodic_values();
+
+    for i in 0..ood_main_trace_frame.num_rows() {
+        if let Some(ref aux_trace_frame) = ood_aux_trace_frame {
+            // when the trace contains auxiliary segments, append auxiliary trace elements at the
+            // end of main trace elements for all rows in the frame. this is needed to be
+            // consistent with how the prover writes OOD frame into the channel.
+            let mut row = ood_main_trace_frame.row(i).to_vec();
+            row.extend_from_slice(aux_trace_frame.row(i));
+            public_coin.absorb(labels::OOD_TRACE_STATES, C::hash_elements(&row));
+        } else {
+            let row_hash = C::hash_elements(ood_main_trace_frame.row(i));
+            public_coin.absorb(labels::OOD_TRACE_STATES, row_hash);
+        }
     }
 
     // read evaluations of composition polynomial columns sent by the prover, and reduce them into
     // a single value by computing sum(z^i * value_i), where value_i is the evaluation of the ith
     // column polynomial at z^m, where m is the total number of column polynomials; also, reseed
     // the public coin with the OOD constraint evaluations received from the prover.
+    // in zero-knowledge mode, value_i is the evaluation of the ith column polynomial at z, the
+    // single value is computed as sum(z^(i * n) * value_i), where n is the trace length, and the
+    // last column is skipped because it is a random polynomial rather than a part of the
+    // composition polynomial.
     let ood_constraint_evaluations = channel.read_ood_constraint_evaluations();
-    let ood_constraint_evaluation_2 = ood_constraint_evaluations
-        .iter()
-        .enumerate()
-        .fold(E::ZERO, |result, (i, &value)| {
-            result + z.exp((i as u32).into()) * value
-        });
-    public_coin.reseed(H::hash_elements(&ood_constraint_evaluations));
+    let ood_constraint_evaluation_2 = if air.options().is_zero_knowledge() {
+        let z_n = z.exp((air.trace_length() as u64).into());
+        let num_segments = ood_constraint_evaluations.len() - 1;
+        ood_constraint_evaluations[..num_segments]
+            .iter()
+            .rev()
+            .fold(E::ZERO, |result, &value| result * z_n + value)
+    } else {
+        ood_constraint_evaluations
+            .iter()
+            .enumerate()
+            .fold(E::ZERO, |result, (i, &value)| {
+                result + z.exp((i as u32).into()) * value
+            })
+    };
+    public_coin.absorb(
+        labels::OOD_CONSTRAINT_EVALUATIONS,
+        C::hash_elements(&ood_constraint_evaluations),
+    );
+
+    // reseed the public coin with the committed periodic values (if any) received from the prover
+    if !ood_periodic_values.is_empty() {
+        public_coin.absorb(
+            labels::OOD_PERIODIC_VALUES,
+            C::hash_elements(&ood_periodic_values),
+        );
+    }
+
+    // evaluate constraints over the OOD frames; this is done after the public coin is reseeded so
+    // that intermediate values are recorded in the same order by the prover and the verifier
+    let ood_constraint_evaluation_1 = evaluate_constraints(
+        air,
+        prepared,
+        constraint_coeffs,
+        &ood_main_trace_frame,
+        &ood_aux_trace_frame,
+        aux_trace_rand_elements,
+        &ood_periodic_values,
+        z,
+        public_coin.log_mut(),
+    );
 
     // finally, make sure the values are the same
     if ood_constraint_evaluation_1 != ood_constraint_evaluation_2 {
-        return Err(VerifierError::InconsistentOodConstraintEvaluations);
+        return Err(VerifierError::InconsistentOodConstraintEvaluations {
+            expected: ood_constraint_evaluation_1.to_string(),
+            actual: ood_constraint_evaluation_2.to_string(),
+        });
     }
 
-    // 4 ----- FRI commitments --------------------------------------------------------------------
+    // 4 ----- DEEP composition coefficients ------------------------------------------------------
     // draw coefficients for computing DEEP composition polynomial from the public coin; in the
     // interactive version of the protocol, the verifier sends these coefficients to the prover
     // and the prover uses them to compute the DEEP composition polynomial. the prover, then
     // applies FRI protocol to the evaluations of the DEEP composition polynomial.
     let deep_coefficients = air
-        .get_deep_composition_coefficients::<E, H>(&mut public_coin)
-        .map_err(|_| VerifierError::RandomCoinError)?;
+        .get_deep_composition_coefficients::<E, _>(public_coin)
+        .map_err(|_| VerifierError::RandomCoinError(VerificationPhase::OodConsistency))?;
 
-    // instantiates a FRI verifier with the FRI layer commitments read from the channel. From the
-    // verifier's perspective, this is equivalent to executing the commit phase of the FRI protocol.
-    // The verifier uses these commitments to update the public coin and draw random points alpha
-    // from them; in the interactive version of the protocol, the verifier sends these alphas to
-    // the prover, and the prover uses them to compute and commit to the subsequent FRI layers.
-    let fri_verifier = FriVerifier::new(
-        &mut channel,
-        &mut public_coin,
-        air.options().to_fri_options(),
-        air.trace_poly_degree(),
-    )
-    .map_err(VerifierError::FriVerificationFailed)?;
-    // TODO: make sure air.lde_domain_size() == fri_verifier.domain_size()
+    Ok(OodCheck {
+        z,
+        deep_coefficients,
+        main_trace_frame: ood_main_trace_frame,
+        aux_trace_frame: ood_aux_trace_frame,
+        constraint_evaluations: ood_constraint_evaluations,
+        periodic_values: ood_periodic_values,
+    })
+}
 
-    // 5 ----- trace and constraint queries -------------------------------------------------------
-    // read proof-of-work nonce sent by the prover and update the public coin with it
-    let pow_nonce = channel.read_pow_nonce();
-    public_coin.reseed_with_int(pow_nonce);
+/// Checks the proof-of-work `pow_nonce` sent by the prover, and draws query positions from the
+/// `public_coin`.
+///
+/// On success, returns the query positions together with the number of leading zeros in the
+/// seed of the public coin after the nonce has been absorbed.
+fn draw_query_positions<A, C>(
+    air: &A,
+    public_coin: &mut PublicCoin<A::BaseField, C>,
+    pow_nonce: u64,
+) -> Result<(QueryPositions, u32), VerifierError>
+where
+    A: Air,
+    C: ElementHasher<BaseField = A::BaseField>,
+{
+    // update the public coin with the proof-of-work nonce sent by the prover
+    public_coin.absorb_int(labels::POW_NONCE, pow_nonce);
 
     // make sure the proof-of-work specified by the grinding factor is satisfied
-    if public_coin.leading_zeros() < air.options().grinding_factor() {
-        return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed);
+    let pow_difficulty = public_coin.leading_zeros();
+    if pow_difficulty < air.options().grinding_factor() {
+        return Err(VerifierError::QuerySeedProofOfWorkVerificationFailed {
+            expected: air.options().grinding_factor(),
+            actual: pow_difficulty,
+        });
     }
 
     // draw pseudo-random query positions for the LDE domain from the public coin; in the
@@ -268,32 +751,73 @@ where
     // and the prover responds with decommitments against these positions for trace and constraint
     // composition polynomial evaluations.
     let query_positions = public_coin
-        .draw_integers(air.options().num_queries(), air.lde_domain_size())
-        .map_err(|_| VerifierError::RandomCoinError)?;
+        .draw_query_positions(air.options(), air.lde_domain_size())
+        .map_err(|_| VerifierError::RandomCoinError(VerificationPhase::QuerySeed))?;
+
+    Ok((query_positions, pow_difficulty))
+}
 
+/// Reads the trace and constraint evaluations of a single instance of a computation at the
+/// specified `query_positions` from the `channel`, and computes evaluations of the DEEP
+/// composition polynomial of the instance at the shared query positions.
+///
+/// All read values are checked against the commitments of the instance.
+fn compose_deep_evaluations<A, E, H>(
+    air: &A,
+    channel: &mut VerifierChannel<E, H>,
+    query_positions: &QueryPositions,
+    ood_check: OodCheck<E>,
+) -> Result<Vec<E>, VerifierError>
+where
+    A: Air,
+    E: FieldElement<BaseField = A::BaseField>,
+    H: ElementHasher<BaseField = A::BaseField>,
+{
     // read evaluations of trace and constraint composition polynomials at the queried positions;
     // this also checks that the read values are valid against trace and constraint commitments
-    let (queried_main_trace_states, queried_aux_trace_states) =
-        channel.read_queried_trace_states(&query_positions)?;
-    let queried_constraint_evaluations = channel.read_constraint_evaluations(&query_positions)?;
+    let (mut queried_main_trace_states, mut queried_aux_trace_states) =
+        channel.read_queried_trace_states(query_positions.trace())?;
+    let mut queried_constraint_evaluations =
+        channel.read_constraint_evaluations(query_positions.constraint())?;
+
+    // read committed periodic column values at the queried positions (if any); this also checks
+    // that the values are valid against the periodic column commitment defined by the AIR
+    let mut queried_periodic_values =
+        channel.read_queried_periodic_values(query_positions.trace())?;
+
+    // openings beyond the shared query positions have been checked against their commitments,
+    // but do not take part in DEEP composition; the shared positions come first in each set
+    let num_queries = query_positions.shared().len();
+    queried_main_trace_states.truncate(num_queries);
+    if let Some(states) = queried_aux_trace_states.as_mut() {
+        states.truncate(num_queries);
+    }
+    queried_constraint_evaluations.truncate(num_queries);
+    if let Some(values) = queried_periodic_values.as_mut() {
+        values.truncate(num_queries);
+    }
 
-    // 6 ----- DEEP composition -------------------------------------------------------------------
-    // compute evaluations of the DEEP composition polynomial at the queried positions
-    let composer = DeepComposer::new(&air, &query_positions, z, deep_coefficients);
-    let t_composition = composer.compose_trace_columns(
+    // compute evaluations of the DEEP composition polynomial at the shared query positions
+    let composer = DeepComposer::new(
+        air,
+        query_positions.shared(),
+        ood_check.z,
+        ood_check.deep_coefficients,
+    );
+    let mut t_composition = composer.compose_trace_columns(
         queried_main_trace_states,
         queried_aux_trace_states,
-        ood_main_trace_frame,
-        ood_aux_trace_frame,
+        ood_check.main_trace_frame,
+        ood_check.aux_trace_frame,
     );
-    let c_composition = composer
-        .compose_constraint_evaluations(queried_constraint_evaluations, ood_constraint_evaluations);
-    let deep_evaluations = composer.combine_compositions(t_composition, c_composition);
-
-    // 7 ----- Verify low-degree proof -------------------------------------------------------------
-    // make sure that evaluations of the DEEP composition polynomial we computed in the previous
-    // step are in fact evaluations of a polynomial of degree equal to trace polynomial degree
-    fri_verifier
-        .verify(&mut channel, &deep_evaluations, &query_positions)
-        .map_err(VerifierError::FriVerificationFailed)
+    if let Some(queried_periodic_values) = queried_periodic_values {
+        let p_composition =
+            composer.compose_periodic_columns(queried_periodic_values, ood_check.periodic_values);
+        add_in_place(&mut t_composition, &p_composition);
+    }
+    let c_composition = composer.compose_constraint_evaluations(
+        queried_constraint_evaluations,
+        ood_check.constraint_evaluations,
+    );
+    Ok(composer.combine_compositions(t_composition, c_composition))
 }
@@ -0,0 +1,108 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{Air, PeriodicColumnPolys, ProofOptions, TraceInfo};
+use crypto::ElementHasher;
+use utils::collections::Vec;
+
+// PREPARED AIR
+// ================================================================================================
+/// Data about a computation which does not depend on public inputs, computed once and reused
+/// across verifications of many proofs.
+///
+/// A prepared AIR is built from an instance of the computation's [Air] and can be used to verify
+/// proofs generated with the same trace info and proof options as this instance (via
+/// [verify_prepared()](crate::verify_prepared)). The following data is cached:
+/// * Coefficients of periodic column polynomials, for both main and auxiliary transition
+///   constraints, grouped by cycle length (see [PeriodicColumnPolys]). For AIRs which commit to
+///   their periodic columns, polynomials for the main transition constraints are not needed and
+///   are not cached.
+/// * The commitment to evaluations of periodic columns, for AIRs which commit to their periodic
+///   columns. Computing this commitment requires evaluating all periodic columns over the LDE
+///   domain, and thus, is by far the most expensive part of the preparation.
+///
+/// The instance of the AIR used to build a prepared AIR may be instantiated with any public
+/// inputs. However, the cached data is valid only for instances with the same values of periodic
+/// columns; these values are checked against every instance verified with the prepared AIR (see
+/// [PreparedAir::is_consistent_with()]).
+pub struct PreparedAir<A: Air, H: ElementHasher<BaseField = A::BaseField>> {
+    trace_info: TraceInfo,
+    options: ProofOptions,
+    periodic_values: Vec<Vec<A::BaseField>>,
+    aux_periodic_values: Vec<Vec<A::BaseField>>,
+    main_periodic_polys: PeriodicColumnPolys<A::BaseField>,
+    aux_periodic_polys: PeriodicColumnPolys<A::BaseField>,
+    periodic_root: Option<H::Digest>,
+}
+
+impl<A: Air, H: ElementHasher<BaseField = A::BaseField>> PreparedAir<A, H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new prepared AIR built from the specified instance of the AIR.
+    pub fn new(air: &A) -> Self {
+        let trace_length = air.trace_length();
+        let (main_periodic_polys, periodic_root) = if air.has_committed_periodic_columns() {
+            (Vec::new(), Some(air.get_periodic_column_commitment::<H>()))
+        } else {
+            (air.get_periodic_column_polys(), None)
+        };
+
+        Self {
+            trace_info: air.trace_info().clone(),
+            options: air.options().clone(),
+            periodic_values: air.get_periodic_column_values(),
+            aux_periodic_values: air.get_aux_periodic_column_values(),
+            main_periodic_polys: PeriodicColumnPolys::new(main_periodic_polys, trace_length),
+            aux_periodic_polys: PeriodicColumnPolys::new(
+                air.get_aux_periodic_column_polys(),
+                trace_length,
+            ),
+            periodic_root,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns trace info of the proofs which can be verified with this prepared AIR.
+    pub fn trace_info(&self) -> &TraceInfo {
+        &self.trace_info
+    }
+
+    /// Returns proof options of the proofs which can be verified with this prepared AIR.
+    pub fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+
+    /// Returns true if the specified instance of the AIR has the same trace info, proof options,
+    /// and values of periodic columns as the instance from which this prepared AIR was built.
+    ///
+    /// Only values of periodic columns are compared; this is cheap as compared to interpolating
+    /// the columns or committing to their evaluations over the LDE domain.
+    pub fn is_consistent_with(&self, air: &A) -> bool {
+        *air.trace_info() == self.trace_info
+            && *air.options() == self.options
+            && air.get_periodic_column_values() == self.periodic_values
+            && air.get_aux_periodic_column_values() == self.aux_periodic_values
+    }
+
+    /// Returns polynomials of periodic columns used by the main transition constraints; this is
+    /// empty if the AIR commits to its periodic columns.
+    pub(crate) fn main_periodic_polys(&self) -> &PeriodicColumnPolys<A::BaseField> {
+        &self.main_periodic_polys
+    }
+
+    /// Returns polynomials of periodic columns used only by auxiliary transition constraints.
+    pub(crate) fn aux_periodic_polys(&self) -> &PeriodicColumnPolys<A::BaseField> {
+        &self.aux_periodic_polys
+    }
+
+    /// Returns the commitment to evaluations of periodic columns, or None if the AIR does not
+    /// commit to its periodic columns.
+    pub(crate) fn periodic_root(&self) -> Option<H::Digest> {
+        self.periodic_root
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use air::{DeepCompositionCoefficients, EvaluationFrame};
+use crypto::ElementHasher;
+use math::FieldElement;
+use utils::collections::Vec;
+
+// VERIFICATION REPORT
+// ================================================================================================
+/// Intermediate values accepted by the verifier during a successful proof verification.
+///
+/// A report is returned by [verify_with_report()](crate::verify_with_report) function. Values
+/// in the extension field used by the proof are represented by their coordinates in the base
+/// field (as returned by [FieldElement::as_base_elements()]). Thus, for proofs generated in a
+/// quadratic extension field, every row of the out-of-domain frame contains two base field
+/// elements per trace column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport<H: ElementHasher> {
+    ood_main_trace_frame: Vec<Vec<H::BaseField>>,
+    ood_aux_trace_frame: Option<Vec<Vec<H::BaseField>>>,
+    ood_constraint_evaluations: Vec<H::BaseField>,
+    deep_trace_coefficients: Vec<Vec<H::BaseField>>,
+    deep_constraint_coefficients: Vec<H::BaseField>,
+    query_positions: Vec<usize>,
+    fri_layer_commitments: Vec<H::Digest>,
+    pow_difficulty: u32,
+    security_level: u32,
+}
+
+impl<H: ElementHasher> VerificationReport<H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+    /// Returns a new report built from the values accepted by the verifier.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new<E: FieldElement<BaseField = H::BaseField>>(
+        ood_main_trace_frame: &EvaluationFrame<E>,
+        ood_aux_trace_frame: Option<&EvaluationFrame<E>>,
+        ood_constraint_evaluations: &[E],
+        deep_coefficients: &DeepCompositionCoefficients<E>,
+        query_positions: Vec<usize>,
+        fri_layer_commitments: Vec<H::Digest>,
+        pow_difficulty: u32,
+        security_level: u32,
+    ) -> Self {
+        VerificationReport {
+            ood_main_trace_frame: frame_to_base_elements(ood_main_trace_frame),
+            ood_aux_trace_frame: ood_aux_trace_frame.map(frame_to_base_elements),
+            ood_constraint_evaluations: E::as_base_elements(ood_constraint_evaluations).to_vec(),
+            deep_trace_coefficients: deep_coefficients
+                .trace
+                .iter()
+                .map(|column_coefficients| E::as_base_elements(column_coefficients).to_vec())
+                .collect(),
+            deep_constraint_coefficients: E::as_base_elements(&deep_coefficients.constraints)
+                .to_vec(),
+            query_positions,
+            fri_layer_commitments,
+            pow_difficulty,
+            security_level,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns rows of the out-of-domain frame of the main trace segment, one row per frame
+    /// offset of the computation.
+    pub fn ood_main_trace_frame(&self) -> &[Vec<H::BaseField>] {
+        &self.ood_main_trace_frame
+    }
+
+    /// Returns rows of the out-of-domain frame of the auxiliary trace segments, or None if the
+    /// computation does not have auxiliary trace segments.
+    pub fn ood_aux_trace_frame(&self) -> Option<&[Vec<H::BaseField>]> {
+        self.ood_aux_trace_frame.as_deref()
+    }
+
+    /// Returns evaluations of the constraint composition polynomial columns at the out-of-domain
+    /// point.
+    pub fn ood_constraint_evaluations(&self) -> &[H::BaseField] {
+        &self.ood_constraint_evaluations
+    }
+
+    /// Returns the coefficients with which the trace polynomials were combined into the DEEP
+    /// composition polynomial; the i-th entry contains one coefficient for each frame offset
+    /// of the i-th trace column (main segment columns first, followed by auxiliary segment
+    /// columns).
+    pub fn deep_trace_coefficients(&self) -> &[Vec<H::BaseField>] {
+        &self.deep_trace_coefficients
+    }
+
+    /// Returns the coefficients with which the constraint composition polynomial columns were
+    /// combined into the DEEP composition polynomial; there is one coefficient per column.
+    pub fn deep_constraint_coefficients(&self) -> &[H::BaseField] {
+        &self.deep_constraint_coefficients
+    }
+
+    /// Returns positions in the LDE domain at which both the trace and constraint commitments
+    /// were queried, and at which the DEEP composition polynomial was evaluated.
+    ///
+    /// When the trace and the constraint evaluations are opened at different numbers of
+    /// positions, the remaining positions of the larger set are not included.
+    pub fn query_positions(&self) -> &[usize] {
+        &self.query_positions
+    }
+
+    /// Returns commitments to the layers of the FRI proof.
+    pub fn fri_layer_commitments(&self) -> &[H::Digest] {
+        &self.fri_layer_commitments
+    }
+
+    /// Returns the number of leading zeros in the query seed after it was reseeded with the
+    /// proof-of-work nonce; this is never smaller than the grinding factor of the proof.
+    pub fn pow_difficulty(&self) -> u32 {
+        self.pow_difficulty
+    }
+
+    /// Returns conjectured security level of the verified proof (in bits).
+    pub fn security_level(&self) -> u32 {
+        self.security_level
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn frame_to_base_elements<E: FieldElement>(frame: &EvaluationFrame<E>) -> Vec<Vec<E::BaseField>> {
+    (0..frame.num_rows())
+        .map(|i| E::as_base_elements(frame.row(i)).to_vec())
+        .collect()
+}
@@ -0,0 +1,100 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_counter_trace, build_options, prove_and_verify, Blake3, CounterAir, CounterProver,
+};
+use crate::{verify_batch, VerifierError};
+use air::{proof::BatchProof, FieldExtension, ProofOptionsBuilder};
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::{BatchProver, Prover, ProverError};
+use utils::collections::Vec;
+
+// BATCH PROOFS
+// ================================================================================================
+
+#[test]
+fn verify_batch_of_counter_proofs() {
+    let prover = CounterProver::new(build_options());
+    let traces = (0..4)
+        .map(|_| build_counter_trace(2, 64))
+        .collect::<Vec<_>>();
+    let pub_inputs = prover.get_pub_inputs(&traces[0]);
+    let proof = prover.prove_batch(traces).unwrap();
+    assert_eq!(4, proof.num_instances());
+
+    let verify = verify_batch::<CounterAir, Blake3>;
+    assert!(verify(proof.clone(), vec![pub_inputs; 4]).is_ok());
+
+    // the batch proof survives serialization, and is smaller than separate proofs of instances
+    let proof_bytes = proof.to_bytes();
+    assert_eq!(proof, BatchProof::from_bytes(&proof_bytes).unwrap());
+    let (single_proof, _) = prove_and_verify(build_options());
+    assert!(proof_bytes.len() < 4 * single_proof.to_bytes().len());
+
+    // the whole batch is rejected if any of the instances is invalid
+    let mut wrong_inputs = vec![pub_inputs; 4];
+    wrong_inputs[2] += BaseElement::ONE;
+    assert!(verify(proof.clone(), wrong_inputs).is_err());
+
+    // the number of instances must match the number of provided public inputs
+    assert_eq!(
+        Err(VerifierError::InconsistentBatchSize {
+            expected: 3,
+            actual: 4
+        }),
+        verify(proof.clone(), vec![pub_inputs; 3])
+    );
+    let mut empty_proof = proof.clone();
+    empty_proof.instances.clear();
+    assert_eq!(Err(VerifierError::EmptyBatch), verify(empty_proof, vec![]));
+
+    // the number of instances is serialized as a 16-bit integer, and thus, larger batches are
+    // rejected rather than truncated
+    let num_instances = BatchProof::MAX_NUM_INSTANCES + 1;
+    assert_eq!(
+        Err(VerifierError::BatchTooLarge(num_instances)),
+        verify(proof, vec![pub_inputs; num_instances])
+    );
+
+    // the prover accepts only non-empty batches of at most 2^16 - 1 traces with the same trace
+    // info
+    assert_eq!(Err(ProverError::EmptyBatch), prover.prove_batch(vec![]));
+    let traces = (0..num_instances)
+        .map(|_| build_counter_trace(2, 8))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        Err(ProverError::BatchTooLarge(num_instances)),
+        prover.prove_batch(traces)
+    );
+    let traces = vec![build_counter_trace(2, 64), build_counter_trace(2, 32)];
+    assert_eq!(
+        Err(ProverError::InconsistentBatchTraceInfo(1)),
+        prover.prove_batch(traces)
+    );
+}
+
+#[test]
+fn verify_batch_with_separate_query_counts() {
+    // all instances are opened at the same trace and constraint query positions
+    for (num_trace_queries, num_constraint_queries) in [(20, 28), (28, 20)] {
+        let options = ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 32)
+            .num_trace_queries(num_trace_queries)
+            .num_constraint_queries(num_constraint_queries)
+            .build()
+            .unwrap();
+        let prover = CounterProver::new(options);
+        let traces = (0..3)
+            .map(|_| build_counter_trace(2, 64))
+            .collect::<Vec<_>>();
+        let pub_inputs = prover.get_pub_inputs(&traces[0]);
+        let proof = prover.prove_batch(traces).unwrap();
+
+        let verify = verify_batch::<CounterAir, Blake3>;
+        assert!(verify(proof.clone(), vec![pub_inputs; 3]).is_ok());
+        assert!(verify(proof, vec![pub_inputs + BaseElement::ONE; 3]).is_err());
+    }
+}
@@ -0,0 +1,281 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_counter_trace, build_options, prove_and_verify, replace_options, Blake3, CounterAir,
+    CounterProver,
+};
+use crate::{verify, verify_with_coin_hasher, verify_with_transcript_log, VerifierError};
+use air::{
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
+    TranscriptEntryKind, TranscriptLog, TranscriptType,
+};
+use crypto::hashers::Sha3_256;
+use math::{fields::f128::BaseElement, FieldElement, ToElements};
+use prover::{Prover, TraceTable};
+use utils::{
+    collections::Vec, string::ToString, ByteWriter, Deserializable, Serializable, SliceReader,
+};
+
+type Sha3 = Sha3_256<BaseElement>;
+
+// COIN HASH FUNCTION
+// ================================================================================================
+
+#[test]
+fn verify_with_different_coin_hasher() {
+    let prover = CounterProver::new(build_options());
+    let trace = build_counter_trace(2, 64);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+
+    // commit using BLAKE3 and instantiate the public coin with SHA3
+    let proof = prover
+        .prove_with_coin_hasher::<Sha3>(trace, [0; 32])
+        .unwrap();
+    let verify_mixed = verify_with_coin_hasher::<CounterAir, Blake3, Sha3>;
+    assert!(verify_mixed(proof.clone(), pub_inputs).is_ok());
+    assert!(verify_mixed(proof.clone(), pub_inputs + BaseElement::ONE).is_err());
+
+    // the proof must be rejected when verified with a different pair of hash functions
+    assert_eq!(
+        Err(VerifierError::InconsistentCoinHash),
+        verify::<CounterAir, Blake3>(proof.clone(), pub_inputs)
+    );
+    assert_eq!(
+        Err(VerifierError::InconsistentCommitmentHash),
+        verify_with_coin_hasher::<CounterAir, Sha3, Sha3>(proof, pub_inputs)
+    );
+
+    // using the same hash function for both purposes is equivalent to regular proving
+    let proof = prover
+        .prove_with_coin_hasher::<Blake3>(build_counter_trace(2, 64), [0; 32])
+        .unwrap();
+    let (expected, _) = prove_and_verify(build_options());
+    assert_eq!(expected.to_bytes(), proof.to_bytes());
+}
+
+// PROVER SEED
+// ================================================================================================
+
+#[test]
+fn verify_seeded_proofs() {
+    // use a non-zero grinding factor so that the seed affects the search for the nonce
+    let options = ProofOptions::new(28, 8, 8, FieldExtension::None, 4, 32);
+    let prover = CounterProver::new(options);
+    let pub_inputs = prover.get_pub_inputs(&build_counter_trace(2, 64));
+    let prove = |seed| {
+        prover
+            .prove_with_seed(build_counter_trace(2, 64), seed)
+            .unwrap()
+    };
+
+    // the same seed must result in byte-identical proofs
+    let proof = prove([1; 32]);
+    assert_eq!(proof.to_bytes(), prove([1; 32]).to_bytes());
+
+    // different seeds may result in different proofs, but all of them must be valid
+    let other_proof = prove([2; 32]);
+    assert_ne!(proof.pow_nonce, other_proof.pow_nonce);
+    assert!(verify::<CounterAir, Blake3>(proof, pub_inputs).is_ok());
+    assert!(verify::<CounterAir, Blake3>(other_proof, pub_inputs).is_ok());
+}
+
+// TRANSCRIPT TYPES
+// ================================================================================================
+
+#[test]
+fn verify_with_transcript_types() {
+    for field_extension in [FieldExtension::None, FieldExtension::Quadratic] {
+        let options = ProofOptions::new(28, 8, 0, field_extension, 4, 32);
+        for transcript_type in [TranscriptType::Legacy, TranscriptType::Labeled] {
+            // a proof verifies under the transcript type it was generated with
+            let options = options.clone().with_transcript_type(transcript_type);
+            let (proof, pub_inputs) = prove_and_verify(options.clone());
+            assert_eq!(transcript_type, proof.options().transcript_type());
+
+            // the proof does not verify when the verifier reads a different transcript type from
+            // the proof context
+            let other_type = match transcript_type {
+                TranscriptType::Legacy => TranscriptType::Labeled,
+                TranscriptType::Labeled => TranscriptType::Legacy,
+            };
+            let other_options = options.clone().with_transcript_type(other_type);
+            let proof = replace_options(&proof.to_bytes(), &options, &other_options);
+            assert!(verify::<CounterAir, Blake3>(proof, pub_inputs).is_err());
+        }
+    }
+}
+
+// TRANSCRIPT LOGS
+// ================================================================================================
+
+#[test]
+fn verify_with_transcript_log_diff() {
+    for field_extension in [FieldExtension::None, FieldExtension::Quadratic] {
+        let options = ProofOptions::new(28, 8, 0, field_extension, 4, 32);
+        let prover = CounterProver::new(options.clone());
+        let trace = build_counter_trace(2, 64);
+        let pub_inputs = prover.get_pub_inputs(&trace);
+        let (proof, prover_log) = prover.prove_with_transcript_log(trace).unwrap();
+
+        // recording the transcript does not affect the proof
+        let (expected_proof, _) = prove_and_verify(options);
+        assert_eq!(expected_proof.to_bytes(), proof.to_bytes());
+
+        // for a valid proof, the prover and the verifier record identical logs, including the
+        // intermediate values of the out-of-domain consistency check
+        let (result, verifier_log) =
+            verify_with_transcript_log::<CounterAir, Blake3>(proof.clone(), pub_inputs);
+        assert!(result.is_ok());
+        assert_eq!(None, prover_log.diff(&verifier_log));
+        assert!(prover_log
+            .entries()
+            .iter()
+            .any(|entry| entry.kind() == TranscriptEntryKind::Evaluation));
+
+        // the log can be saved and loaded back
+        let log_bytes = prover_log.to_bytes();
+        let loaded_log = TranscriptLog::read_from(&mut SliceReader::new(&log_bytes)).unwrap();
+        assert_eq!(prover_log, loaded_log);
+
+        // with wrong public inputs, the public coin is seeded differently; the main trace
+        // commitment is absorbed identically, but the values drawn after it diverge
+        let (result, verifier_log) =
+            verify_with_transcript_log::<CounterAir, Blake3>(proof, pub_inputs + BaseElement::ONE);
+        assert!(result.is_err());
+        let divergence = prover_log.diff(&verifier_log).unwrap();
+        assert_eq!(1, divergence.index);
+        let expected = divergence.left.as_ref().unwrap();
+        let actual = divergence.right.as_ref().unwrap();
+        assert_eq!(TranscriptEntryKind::Draw, expected.kind());
+        assert_eq!("constraint-coefficients", expected.label());
+        assert_eq!(expected.label(), actual.label());
+        assert_ne!(expected.values(), actual.values());
+        assert!(divergence.to_string().contains("constraint-coefficients"));
+    }
+}
+
+// PUBLIC INPUTS ENCODING
+// ================================================================================================
+
+#[test]
+fn verify_with_public_inputs_element_encoding() {
+    // public inputs which serialize into different bytes but map to the same field elements
+    // seed the public coin identically, and thus produce the same proof transcript
+    let prover1 = PaddedCounterProver::new(build_options(), 0);
+    let prover2 = PaddedCounterProver::new(build_options(), 5);
+
+    let trace = build_counter_trace(2, 64);
+    let pub_inputs1 = prover1.get_pub_inputs(&trace);
+    let pub_inputs2 = prover2.get_pub_inputs(&trace);
+    assert_ne!(pub_inputs1.to_bytes(), pub_inputs2.to_bytes());
+    assert_eq!(pub_inputs1.to_elements(), pub_inputs2.to_elements());
+
+    let (proof1, prover_log1) = prover1.prove_with_transcript_log(trace).unwrap();
+    let (proof2, prover_log2) = prover2
+        .prove_with_transcript_log(build_counter_trace(2, 64))
+        .unwrap();
+    assert_eq!(None, prover_log1.diff(&prover_log2));
+    assert_eq!(proof1.to_bytes(), proof2.to_bytes());
+
+    // the proof can be verified against either encoding of the public inputs
+    let (result, verifier_log) =
+        verify_with_transcript_log::<PaddedCounterAir, Blake3>(proof1.clone(), pub_inputs2);
+    assert!(result.is_ok());
+    assert_eq!(None, prover_log1.diff(&verifier_log));
+    assert!(verify::<PaddedCounterAir, Blake3>(proof2, pub_inputs1.clone()).is_ok());
+
+    // the proof for the padded inputs is the same as the proof for the unpadded result
+    let (proof, _) = prove_and_verify(build_options());
+    assert_eq!(proof.to_bytes(), proof1.to_bytes());
+    assert!(verify::<CounterAir, Blake3>(proof1, pub_inputs1.result).is_ok());
+}
+
+// PADDED COUNTER AIR AND PROVER
+// ================================================================================================
+
+/// Public inputs of the counter computation whose byte serialization is followed by `padding`
+/// zero bytes; the padding has no effect on the element encoding of the inputs.
+#[derive(Clone)]
+struct PaddedInputs {
+    result: BaseElement,
+    padding: usize,
+}
+
+impl Serializable for PaddedInputs {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.result.write_into(target);
+        target.write_u8_slice(&vec![0; self.padding]);
+    }
+}
+
+impl ToElements<BaseElement> for PaddedInputs {
+    fn to_elements(&self) -> Vec<BaseElement> {
+        vec![self.result]
+    }
+}
+
+/// Counter AIR which accepts [PaddedInputs] as public inputs.
+struct PaddedCounterAir(CounterAir);
+
+impl Air for PaddedCounterAir {
+    type BaseField = BaseElement;
+    type PublicInputs = PaddedInputs;
+
+    fn new(trace_info: TraceInfo, pub_inputs: PaddedInputs, options: ProofOptions) -> Self {
+        Self(CounterAir::new(trace_info, pub_inputs.result, options))
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        self.0.context()
+    }
+
+    fn evaluate_transition<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        self.0.evaluate_transition(frame, periodic_values, result)
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        self.0.get_assertions()
+    }
+}
+
+/// Counter prover which pads the public inputs with the specified number of bytes.
+struct PaddedCounterProver {
+    inner: CounterProver,
+    padding: usize,
+}
+
+impl PaddedCounterProver {
+    fn new(options: ProofOptions, padding: usize) -> Self {
+        Self {
+            inner: CounterProver::new(options),
+            padding,
+        }
+    }
+}
+
+impl Prover for PaddedCounterProver {
+    type BaseField = BaseElement;
+    type Air = PaddedCounterAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PaddedInputs {
+        PaddedInputs {
+            result: self.inner.get_pub_inputs(trace),
+            padding: self.padding,
+        }
+    }
+
+    fn options(&self) -> &ProofOptions {
+        self.inner.options()
+    }
+}
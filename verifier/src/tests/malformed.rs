@@ -0,0 +1,268 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_counter_trace, build_options, prove_and_verify, Blake3, CounterAir, CounterProver,
+};
+use crate::{verify, VerificationPhase, VerifierError};
+use air::proof::StarkProof;
+use math::{fields::f128::BaseElement, StarkField};
+use prover::Prover;
+use utils::{collections::Vec, Deserializable, DeserializationError, Serializable, SliceReader};
+
+// FIELD MODULUS
+// ================================================================================================
+
+#[test]
+fn verify_proof_with_tiny_field_modulus() {
+    // found by the verify_proof fuzz target: the security level of a proof is estimated from the
+    // modulus recorded in the proof context, and for a modulus of 1 the estimate used to
+    // underflow before the modulus was compared against the base field of the AIR
+    let prover = CounterProver::new(build_options());
+    let trace = build_counter_trace(2, 32);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+
+    let modulus = BaseElement::get_modulus_le_bytes();
+    let mut tiny_modulus = vec![0; modulus.len()];
+    tiny_modulus[0] = 1;
+    let proof_bytes = replace_once(&proof.to_bytes(), &modulus, &tiny_modulus);
+    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert_eq!(1, proof.context.num_modulus_bits());
+    assert_eq!(0, proof.security_level::<Blake3>(true));
+
+    assert_eq!(
+        Err(VerifierError::InconsistentBaseField),
+        verify::<CounterAir, Blake3>(proof, pub_inputs)
+    );
+}
+
+// DOMAIN SIZE
+// ================================================================================================
+
+#[test]
+fn verify_proof_with_domain_too_large() {
+    // a proof claiming a trace whose low-degree extension does not fit into the f128 field is
+    // rejected before any domain-dependent values are computed
+    let (proof, pub_inputs) = prove_and_verify(build_options());
+
+    // trace length is stored as a power of two right after the format version and the trace
+    // layout
+    let offset = 1 + proof.get_trace_info().layout().to_bytes().len();
+    let mut proof_bytes = proof.to_bytes();
+    proof_bytes[offset] = (BaseElement::TWO_ADICITY - 2) as u8;
+    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+
+    assert_eq!(
+        Err(VerifierError::DomainTooLarge {
+            requested: 1 << (BaseElement::TWO_ADICITY + 1),
+            max_supported: 1 << BaseElement::TWO_ADICITY,
+            field: core::any::type_name::<BaseElement>(),
+        }),
+        verify::<CounterAir, Blake3>(proof, pub_inputs)
+    );
+}
+
+// TAMPERED PROOFS
+// ================================================================================================
+
+#[test]
+fn verify_tampered_proof_error_phases() {
+    let (proof, pub_inputs) = prove_and_verify(build_options());
+    let verify = |proof| verify::<CounterAir, Blake3>(proof, pub_inputs);
+
+    // flip a bit in the first value of the out-of-domain trace frame (the frame is serialized
+    // with a 4-byte length prefix)
+    let mut tampered = proof.clone();
+    tampered.ood_frame = flip_bit(&proof.ood_frame, 4);
+    let err = verify(tampered).unwrap_err();
+    assert!(matches!(
+        err,
+        VerifierError::InconsistentOodConstraintEvaluations { .. }
+    ));
+    assert_eq!(VerificationPhase::OodConsistency, err.phase());
+
+    // flip a bit in the first queried value of the main trace segment (query values are
+    // serialized with a 4-byte length prefix)
+    let mut tampered = proof.clone();
+    tampered.trace_queries[0] = flip_bit(&proof.trace_queries[0], 4);
+    let err = verify(tampered).unwrap_err();
+    assert!(matches!(
+        err,
+        VerifierError::TraceQueryDoesNotMatchCommitment { segment: 0, .. }
+    ));
+    assert_eq!(VerificationPhase::TraceCommitment, err.phase());
+
+    // flip a bit in the first queried constraint evaluation
+    let mut tampered = proof.clone();
+    tampered.constraint_queries = flip_bit(&proof.constraint_queries, 4);
+    let err = verify(tampered).unwrap_err();
+    assert!(matches!(
+        err,
+        VerifierError::ConstraintQueryDoesNotMatchCommitment { .. }
+    ));
+    assert_eq!(VerificationPhase::ConstraintCommitment, err.phase());
+
+    // flip a bit in the first queried value of the first FRI layer (the FRI proof is serialized
+    // with a 1-byte number of layers followed by a 4-byte length prefix of layer values)
+    let mut tampered = proof;
+    tampered.fri_proof = flip_bit(&tampered.fri_proof, 5);
+    let err = verify(tampered).unwrap_err();
+    assert!(matches!(err, VerifierError::FriVerificationFailed(_)));
+    assert_eq!(VerificationPhase::FriLayer(0), err.phase());
+    assert!(err.to_string().contains("layer 0"));
+}
+
+#[test]
+fn verify_malformed_proof() {
+    let (proof, pub_inputs) = prove_and_verify(build_options());
+    let verify = |proof| verify::<CounterAir, Blake3>(proof, pub_inputs);
+
+    // out-of-domain trace states and constraint evaluations must have the widths defined by the
+    // AIR; an extra byte is inserted into each part of the frame (the trace states are serialized
+    // with a 4-byte length prefix followed by the constraint evaluations)
+    let mut tampered = proof.clone();
+    tampered.ood_frame = rewrite(&proof.ood_frame, |bytes| insert_byte(bytes, 0)).unwrap();
+    assert_deserialization_error(verify(tampered), "trace states");
+
+    let mut tampered = proof.clone();
+    tampered.ood_frame = rewrite(&proof.ood_frame, |bytes| {
+        let offset = 4 + u32::from_le_bytes(bytes[..4].try_into().unwrap()) as usize;
+        insert_byte(bytes, offset)
+    })
+    .unwrap();
+    assert_deserialization_error(verify(tampered), "constraint evaluations");
+
+    // the proof must contain queries for each trace segment
+    let mut tampered = proof.clone();
+    tampered.trace_queries.push(proof.trace_queries[0].clone());
+    assert_deserialization_error(verify(tampered), "trace segment queries");
+    let mut tampered = proof.clone();
+    tampered.trace_queries.clear();
+    assert_deserialization_error(verify(tampered), "trace segment queries");
+
+    // the FRI proof must not contain more layers than can fold the domain; the extra layers fail
+    // to parse at the latest when the domain cannot be folded any further
+    let mut tampered = proof.clone();
+    tampered.fri_proof = rewrite(&proof.fri_proof, |bytes| {
+        let (mut layers, remainder, num_partitions) = split_fri_proof(bytes);
+        let extra_layers = vec![layers[0].clone(); 255 - layers.len()];
+        layers.extend(extra_layers);
+        *bytes = join_fri_proof(&layers, &remainder, num_partitions);
+    })
+    .unwrap();
+    assert_deserialization_error(verify(tampered), "FRI layer");
+
+    // the FRI proof must contain as many layers as implied by the proof options, even if the
+    // remainder is consistent with the number of layers in the proof
+    let folding_factor = proof.options().to_fri_options().folding_factor();
+    let mut tampered = proof.clone();
+    tampered.fri_proof = rewrite(&proof.fri_proof, |bytes| {
+        let (mut layers, remainder, num_partitions) = split_fri_proof(bytes);
+        layers
+            .pop()
+            .expect("proof must contain at least one FRI layer");
+        let remainder = remainder.repeat(folding_factor);
+        *bytes = join_fri_proof(&layers, &remainder, num_partitions);
+    })
+    .unwrap();
+    assert_deserialization_error(verify(tampered), "FRI layers");
+
+    // the number of FRI partitions must fit into the address space
+    let result = rewrite(&proof.fri_proof, |bytes| *bytes.last_mut().unwrap() = 64);
+    assert!(matches!(result, Err(DeserializationError::InvalidValue(_))));
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns a copy of `bytes` in which the first occurrence of `pattern` is replaced with
+/// `replacement` of the same length.
+fn replace_once(bytes: &[u8], pattern: &[u8], replacement: &[u8]) -> Vec<u8> {
+    let start = bytes
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+        .expect("pattern not found");
+    let mut result = bytes.to_vec();
+    result[start..start + pattern.len()].copy_from_slice(replacement);
+    result
+}
+
+/// Serializes the value, flips the least significant bit of the byte at the specified index, and
+/// deserializes the result.
+fn flip_bit<T: Serializable + Deserializable>(value: &T, index: usize) -> T {
+    let mut bytes = value.to_bytes();
+    bytes[index] ^= 1;
+    T::read_from(&mut SliceReader::new(&bytes)).unwrap()
+}
+
+/// Serializes the value, applies the specified modification to the resulting bytes, and
+/// deserializes the modified bytes.
+fn rewrite<T: Serializable + Deserializable>(
+    value: &T,
+    modify: impl FnOnce(&mut Vec<u8>),
+) -> Result<T, DeserializationError> {
+    let mut bytes = value.to_bytes();
+    modify(&mut bytes);
+    T::read_from(&mut SliceReader::new(&bytes))
+}
+
+/// Inserts a zero byte into a byte sequence with a 4-byte length prefix starting at `offset`, and
+/// updates the prefix.
+fn insert_byte(bytes: &mut Vec<u8>, offset: usize) {
+    let len = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) + 1;
+    bytes[offset..offset + 4].copy_from_slice(&len.to_le_bytes());
+    bytes.insert(offset + 4, 0);
+}
+
+/// Splits a serialized FRI proof into serialized layers, remainder bytes, and the number of
+/// partitions.
+fn split_fri_proof(bytes: &[u8]) -> (Vec<Vec<u8>>, Vec<u8>, u8) {
+    let read_len = |offset: usize, size: usize| {
+        let mut len = [0; 8];
+        len[..size].copy_from_slice(&bytes[offset..offset + size]);
+        u64::from_le_bytes(len) as usize
+    };
+
+    // each layer consists of query values and Merkle paths with 4-byte length prefixes
+    let mut offset = 1;
+    let mut layers = Vec::new();
+    for _ in 0..bytes[0] {
+        let values_len = read_len(offset, 4);
+        let paths_len = read_len(offset + 4 + values_len, 4);
+        let layer_len = 8 + values_len + paths_len;
+        layers.push(bytes[offset..offset + layer_len].to_vec());
+        offset += layer_len;
+    }
+
+    // the remainder is serialized with a 2-byte length prefix
+    let remainder_len = read_len(offset, 2);
+    let remainder = bytes[offset + 2..offset + 2 + remainder_len].to_vec();
+    (layers, remainder, bytes[offset + 2 + remainder_len])
+}
+
+/// Serializes a FRI proof from the parts returned by [split_fri_proof()].
+fn join_fri_proof(layers: &[Vec<u8>], remainder: &[u8], num_partitions: u8) -> Vec<u8> {
+    let mut bytes = vec![layers.len() as u8];
+    layers
+        .iter()
+        .for_each(|layer| bytes.extend_from_slice(layer));
+    bytes.extend_from_slice(&(remainder.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(remainder);
+    bytes.push(num_partitions);
+    bytes
+}
+
+/// Makes sure that verification failed because the proof could not be parsed, and that the
+/// error message contains the specified text.
+fn assert_deserialization_error(result: Result<(), VerifierError>, text: &str) {
+    match result {
+        Err(VerifierError::ProofDeserializationError(msg)) => {
+            assert!(msg.contains(text), "'{msg}' does not contain '{text}'")
+        }
+        result => panic!("expected a proof deserialization error, but was {result:?}"),
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use crate::verify;
+use air::{
+    proof::StarkProof, Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions,
+    TraceInfo, TransitionConstraintDegree,
+};
+use crypto::hashers::Blake3_256;
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::{Prover, Trace, TraceTable};
+use utils::{collections::Vec, Serializable};
+
+mod batch;
+mod coin;
+mod malformed;
+mod options;
+mod prepared;
+mod report;
+mod roundtrip;
+
+type Blake3 = Blake3_256<BaseElement>;
+
+// PROOF FIXTURE
+// ================================================================================================
+
+/// Returns proof options with which a counter trace of 64 rows results in a proof with two FRI
+/// layers.
+pub fn build_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 32)
+}
+
+/// Proves a counter trace of two columns and 64 rows with the specified options, makes sure that
+/// the proof verifies, and returns the proof together with the public inputs of the computation.
+pub fn prove_and_verify(options: ProofOptions) -> (StarkProof, BaseElement) {
+    let prover = CounterProver::new(options);
+    let trace = build_counter_trace(2, 64);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    assert_eq!(
+        Ok(()),
+        verify::<CounterAir, Blake3>(proof.clone(), pub_inputs)
+    );
+    (proof, pub_inputs)
+}
+
+// WIDE TRACE BUILDER
+// ================================================================================================
+
+/// Builds a trace of the specified width and length in which every column is a counter; column
+/// `j` starts at `j` and is incremented by one in every row.
+pub fn build_counter_trace(width: usize, length: usize) -> TraceTable<BaseElement> {
+    let mut trace = TraceTable::new(width, length);
+    trace.fill(
+        |state| {
+            for (j, value) in state.iter_mut().enumerate() {
+                *value = BaseElement::from(j as u64);
+            }
+        },
+        |_, state| {
+            for value in state.iter_mut() {
+                *value += BaseElement::ONE;
+            }
+        },
+    );
+    trace
+}
+
+// COUNTER AIR
+// ================================================================================================
+
+/// AIR for traces built by [build_counter_trace()]; one transition constraint is defined for
+/// every column of the trace. The public input of the computation is the value of the first
+/// column in the last row of the trace.
+pub struct CounterAir {
+    context: AirContext<BaseElement>,
+    result: BaseElement,
+}
+
+impl Air for CounterAir {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, result: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::new(1); trace_info.width()];
+        CounterAir {
+            context: AirContext::new(trace_info, degrees, 3, options),
+            result,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        _periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current();
+        let next = frame.next();
+        for (j, result) in result.iter_mut().enumerate() {
+            *result = next[j] - current[j] - E::ONE;
+        }
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        let last_column = self.trace_info().width() - 1;
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, BaseElement::ZERO),
+            Assertion::single(last_column, 0, BaseElement::from(last_column as u64)),
+            Assertion::single(0, last_step, self.result),
+        ]
+    }
+}
+
+// COUNTER PROVER
+// ================================================================================================
+
+pub struct CounterProver {
+    options: ProofOptions,
+}
+
+impl CounterProver {
+    pub fn new(options: ProofOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Prover for CounterProver {
+    type BaseField = BaseElement;
+    type Air = CounterAir;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        trace.get(0, trace.length() - 1)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Replaces proof options `from` with options `to` directly in the serialized proof, and parses
+/// the result.
+pub fn replace_options(proof_bytes: &[u8], from: &ProofOptions, to: &ProofOptions) -> StarkProof {
+    let from_bytes = from.to_bytes();
+    let to_bytes = to.to_bytes();
+    assert_eq!(from_bytes.len(), to_bytes.len());
+    let position = proof_bytes
+        .windows(from_bytes.len())
+        .position(|window| window == from_bytes)
+        .expect("proof options not found in the serialized proof");
+    let mut tampered_bytes = proof_bytes.to_vec();
+    tampered_bytes[position..position + from_bytes.len()].copy_from_slice(&to_bytes);
+    StarkProof::from_bytes(&tampered_bytes).unwrap()
+}
@@ -0,0 +1,211 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_options, prove_and_verify, replace_options, Blake3, CounterAir};
+use crate::{verify, verify_with_report, VerifierError};
+use air::{
+    proof::StarkProof, FieldExtension, ProofOptions, ProofOptionsBuilder, ProofOptionsError,
+    TranscriptType,
+};
+use math::{fields::f128::BaseElement, FieldElement, StarkField};
+
+// ZERO-KNOWLEDGE
+// ================================================================================================
+
+#[test]
+fn verify_with_zero_knowledge() {
+    // without zero-knowledge, proofs of the same trace are identical
+    let (proof, _) = prove_and_verify(build_options());
+    assert_eq!(
+        proof.to_bytes(),
+        prove_and_verify(build_options()).0.to_bytes()
+    );
+
+    // in zero-knowledge mode, every proof commits to freshly randomized polynomials; all proofs
+    // must be valid
+    let zk_options = build_options().with_zero_knowledge();
+    let (zk_proof1, pub_inputs) = prove_and_verify(zk_options.clone());
+    let (zk_proof2, _) = prove_and_verify(zk_options.clone());
+    assert!(zk_proof1.options().is_zero_knowledge());
+    let num_fri_layers = zk_proof1
+        .options()
+        .to_fri_options()
+        .num_fri_layers(zk_proof1.lde_domain_size());
+    let parse_commitments = |proof: &StarkProof| {
+        proof
+            .commitments
+            .clone()
+            .parse::<Blake3>(1, num_fri_layers)
+            .unwrap()
+    };
+    let (trace_roots1, constraint_root1, _) = parse_commitments(&zk_proof1);
+    let (trace_roots2, constraint_root2, _) = parse_commitments(&zk_proof2);
+    assert_ne!(trace_roots1, trace_roots2);
+    assert_ne!(constraint_root1, constraint_root2);
+
+    // trace polynomials of the 64-step trace are extended by 2 * (28 + 1) random coefficients to
+    // 128 coefficients, and thus, the LDE domain is twice as large as without zero-knowledge
+    let lde_domain_size = zk_proof1.lde_domain_size();
+    assert_eq!(2 * proof.lde_domain_size(), lde_domain_size);
+    assert_eq!(128 * 8, lde_domain_size);
+
+    // salts of the queried rows are sent together with the queries, and thus, trace queries of
+    // zero-knowledge proofs can be parsed only as salted queries
+    let num_queries = zk_proof1.options().num_queries();
+    let trace_queries = zk_proof1.trace_queries[0].clone();
+    assert!(trace_queries
+        .clone()
+        .parse::<Blake3, BaseElement>(lde_domain_size, num_queries, 2)
+        .is_err());
+    assert!(trace_queries
+        .parse_salted::<Blake3, BaseElement>(lde_domain_size, num_queries, 2)
+        .is_ok());
+
+    // the composition polynomial is split into two randomized columns, which are followed by a
+    // random column
+    let constraint_queries = zk_proof1.constraint_queries.clone();
+    assert!(constraint_queries
+        .clone()
+        .parse_salted::<Blake3, BaseElement>(lde_domain_size, num_queries, 2)
+        .is_err());
+    assert!(constraint_queries
+        .parse_salted::<Blake3, BaseElement>(lde_domain_size, num_queries, 3)
+        .is_ok());
+
+    let wrong_inputs = pub_inputs + BaseElement::ONE;
+    assert!(verify::<CounterAir, Blake3>(zk_proof1.clone(), wrong_inputs).is_err());
+
+    // a zero-knowledge proof is not valid when the verifier reads options without zero-knowledge
+    // from the proof context, and vice versa
+    let proof = replace_options(&zk_proof1.to_bytes(), &zk_options, &build_options());
+    assert!(verify::<CounterAir, Blake3>(proof, pub_inputs).is_err());
+    let (proof, _) = prove_and_verify(build_options());
+    let proof = replace_options(&proof.to_bytes(), &build_options(), &zk_options);
+    assert!(verify::<CounterAir, Blake3>(proof, pub_inputs).is_err());
+}
+
+// TRACE DOMAIN OFFSET
+// ================================================================================================
+
+#[test]
+fn verify_with_trace_domain_offset() {
+    let shifted_options = build_options().with_trace_domain_offset(7);
+    let (proof, pub_inputs) = prove_and_verify(shifted_options.clone());
+    assert_eq!(
+        BaseElement::new(7),
+        proof.options().trace_domain_offset::<BaseElement>()
+    );
+
+    // a proof over a shifted trace domain survives serialization and verifies
+    let proof_bytes = proof.to_bytes();
+    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    assert!(verify::<CounterAir, Blake3>(proof, pub_inputs).is_ok());
+
+    // the proof does not verify when the verifier reads a different trace domain offset from the
+    // proof context
+    for options in [
+        build_options(),
+        build_options().with_trace_domain_offset(11),
+    ] {
+        let proof = replace_options(&proof_bytes, &shifted_options, &options);
+        assert!(verify::<CounterAir, Blake3>(proof, pub_inputs).is_err());
+    }
+
+    // an offset which places the trace domain inside the LDE domain is rejected before the AIR
+    // is instantiated; the LDE domain is offset by the generator of the field
+    let offset = 3;
+    assert_eq!(BaseElement::GENERATOR, BaseElement::from(offset));
+    let options = shifted_options.clone().with_trace_domain_offset(offset);
+    let proof = replace_options(&proof_bytes, &shifted_options, &options);
+    assert_eq!(
+        Err(VerifierError::InvalidProofOptions(
+            ProofOptionsError::TraceDomainOffsetInLdeDomain
+        )),
+        verify::<CounterAir, Blake3>(proof, pub_inputs)
+    );
+}
+
+// QUERY COUNTS
+// ================================================================================================
+
+#[test]
+fn verify_with_separate_query_counts() {
+    let build = |num_trace_queries, num_constraint_queries, transcript_type| {
+        ProofOptionsBuilder::new(28, 8, 0, FieldExtension::None, 4, 32)
+            .num_trace_queries(num_trace_queries)
+            .num_constraint_queries(num_constraint_queries)
+            .transcript_type(transcript_type)
+            .allow_insecure()
+            .build()
+            .unwrap()
+    };
+
+    for transcript_type in [TranscriptType::Legacy, TranscriptType::Labeled] {
+        // with equal numbers, proofs are the same as the proofs generated with a single number of
+        // queries, including their serialization
+        let (proof, _) = prove_and_verify(build(28, 28, transcript_type));
+        let (expected, _) = prove_and_verify(build_options().with_transcript_type(transcript_type));
+        assert_eq!(expected.to_bytes(), proof.to_bytes());
+        let full_size = proof.to_bytes().len();
+
+        let cases = [(28, 20), (20, 28), (8, 28), (28, 8)];
+        for (num_trace_queries, num_constraint_queries) in cases {
+            let options = build(num_trace_queries, num_constraint_queries, transcript_type);
+            let (proof, pub_inputs) = prove_and_verify(options.clone());
+            let proof_bytes = proof.to_bytes();
+            assert_eq!(proof, StarkProof::from_bytes(&proof_bytes).unwrap());
+            assert!(proof_bytes.len() < full_size);
+
+            // the trace is opened at the number of positions specified for it
+            let lde_domain_size = proof.lde_domain_size();
+            let trace_queries = proof.trace_queries[0].clone();
+            assert!(trace_queries
+                .clone()
+                .parse::<Blake3, BaseElement>(lde_domain_size, num_trace_queries, 2)
+                .is_ok());
+            assert!(trace_queries
+                .parse::<Blake3, BaseElement>(lde_domain_size, num_constraint_queries, 2)
+                .is_err());
+
+            // only the positions at which both the trace and the constraint evaluations are
+            // opened contribute to soundness
+            let num_queries = num_trace_queries.min(num_constraint_queries);
+            let report = verify_with_report::<CounterAir, Blake3>(proof.clone(), pub_inputs)
+                .expect("verification failed");
+            assert_eq!(num_queries, report.query_positions().len());
+            let (equal_proof, _) =
+                prove_and_verify(build(num_queries, num_queries, transcript_type));
+            assert_eq!(
+                equal_proof.security_level::<Blake3>(true),
+                proof.security_level::<Blake3>(true)
+            );
+            assert_eq!(
+                ProofOptions::new(num_queries, 8, 0, FieldExtension::None, 4, 32)
+                    .conjectured_security(),
+                options.conjectured_security()
+            );
+
+            // the verifier reads both numbers from the proof context, and rejects the proof when
+            // either of them is different
+            for other_options in [
+                build(num_constraint_queries, num_trace_queries, transcript_type),
+                build(
+                    num_trace_queries + 1,
+                    num_constraint_queries,
+                    transcript_type,
+                ),
+                build(
+                    num_trace_queries,
+                    num_constraint_queries + 1,
+                    transcript_type,
+                ),
+            ] {
+                let proof = replace_options(&proof_bytes, &options, &other_options);
+                assert!(verify::<CounterAir, Blake3>(proof, pub_inputs).is_err());
+            }
+        }
+    }
+}
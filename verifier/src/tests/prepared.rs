@@ -0,0 +1,177 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::Blake3;
+use crate::{verify, verify_batch, verify_prepared, PreparedAir, VerifierError};
+use air::{
+    Air, AirContext, Assertion, EvaluationFrame, FieldExtension, ProofOptions, TraceInfo,
+    TransitionConstraintDegree,
+};
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::{BatchProver, Prover, TraceTable};
+use utils::collections::Vec;
+
+// PERIODIC COLUMNS OF PREPARED AIR
+// ================================================================================================
+
+#[test]
+fn verify_prepared_with_different_periodic_columns() {
+    let one = BaseElement::ONE;
+    let two = BaseElement::from(2u8);
+    let prover = StepProver::<0>::new(build_options());
+    let proof = prover.prove(build_step_trace(1, 64)).unwrap();
+    let prepared = PreparedAir::<StepAir, Blake3>::new(&StepAir::new(
+        proof.get_trace_info(),
+        one,
+        proof.options().clone(),
+    ));
+    assert!(verify_prepared(&prepared, proof, one).is_ok());
+
+    // a proof for a step of one which is bound to public inputs claiming a step of two; the
+    // periodic columns of the computation are built for a step of one regardless of the inputs
+    let prover = StepProver::<1>::with_claimed_step(build_options(), two);
+    let proof = prover.prove(build_step_trace(1, 64)).unwrap();
+    assert!(verify::<StepAir, Blake3>(proof.clone(), two).is_err());
+
+    // data prepared for a step of one must not be used to verify the proof against a step of two,
+    // because periodic columns of both instances differ
+    assert_eq!(
+        Err(VerifierError::InconsistentPreparedAir),
+        verify_prepared(&prepared, proof, two)
+    );
+}
+
+#[test]
+fn verify_batch_with_different_periodic_columns() {
+    let one = BaseElement::ONE;
+    let two = BaseElement::from(2u8);
+    let prover = StepProver::<0>::new(build_options());
+
+    let proof = prover
+        .prove_batch(vec![build_step_trace(1, 64), build_step_trace(1, 64)])
+        .unwrap();
+    assert!(verify_batch::<StepAir, Blake3>(proof, vec![one, one]).is_ok());
+
+    // all instances of a batch must have the same periodic columns
+    let proof = prover
+        .prove_batch(vec![build_step_trace(1, 64), build_step_trace(2, 64)])
+        .unwrap();
+    assert_eq!(
+        Err(VerifierError::InconsistentPreparedAir),
+        verify_batch::<StepAir, Blake3>(proof, vec![one, two])
+    );
+}
+
+// STEP AIR
+// ================================================================================================
+
+fn build_options() -> ProofOptions {
+    ProofOptions::new(28, 8, 0, FieldExtension::None, 4, 32)
+}
+
+/// Builds a trace with a single column which starts at one and is multiplied by `step + 1` in
+/// every even row.
+fn build_step_trace(step: u64, length: usize) -> TraceTable<BaseElement> {
+    let factor = BaseElement::from(step + 1);
+    let mut trace = TraceTable::new(1, length);
+    trace.fill(
+        |state| state[0] = BaseElement::ONE,
+        |i, state| {
+            if i % 2 == 0 {
+                state[0] *= factor;
+            }
+        },
+    );
+    trace
+}
+
+/// AIR for traces built by [build_step_trace()]; the step is a public input of the computation
+/// and is provided to the transition constraint via a periodic column.
+///
+/// If `FORGED_STEP` is not zero, the periodic column is built for this step regardless of the
+/// public inputs.
+struct StepAir<const FORGED_STEP: u64 = 0> {
+    context: AirContext<BaseElement>,
+    step: BaseElement,
+}
+
+impl<const FORGED_STEP: u64> Air for StepAir<FORGED_STEP> {
+    type BaseField = BaseElement;
+    type PublicInputs = BaseElement;
+
+    fn new(trace_info: TraceInfo, step: BaseElement, options: ProofOptions) -> Self {
+        let degrees = vec![TransitionConstraintDegree::with_cycles(1, vec![2])];
+        let step = match FORGED_STEP {
+            0 => step,
+            forged_step => BaseElement::from(forged_step),
+        };
+        StepAir {
+            context: AirContext::new(trace_info, degrees, 1, options),
+            step,
+        }
+    }
+
+    fn context(&self) -> &AirContext<Self::BaseField> {
+        &self.context
+    }
+
+    fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
+        &self,
+        frame: &EvaluationFrame<E>,
+        periodic_values: &[E],
+        result: &mut [E],
+    ) {
+        let current = frame.current()[0];
+        result[0] = frame.next()[0] - current - current * periodic_values[0];
+    }
+
+    fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
+        vec![Assertion::single(0, 0, BaseElement::ONE)]
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        vec![vec![self.step, BaseElement::ZERO]]
+    }
+}
+
+/// Prover generating proofs against [StepAir]; unless a step is claimed explicitly, the step
+/// is read from the trace.
+struct StepProver<const FORGED_STEP: u64> {
+    options: ProofOptions,
+    claimed_step: Option<BaseElement>,
+}
+
+impl<const FORGED_STEP: u64> StepProver<FORGED_STEP> {
+    fn new(options: ProofOptions) -> Self {
+        Self {
+            options,
+            claimed_step: None,
+        }
+    }
+
+    fn with_claimed_step(options: ProofOptions, step: BaseElement) -> Self {
+        Self {
+            options,
+            claimed_step: Some(step),
+        }
+    }
+}
+
+impl<const FORGED_STEP: u64> Prover for StepProver<FORGED_STEP> {
+    type BaseField = BaseElement;
+    type Air = StepAir<FORGED_STEP>;
+    type Trace = TraceTable<BaseElement>;
+    type HashFn = Blake3;
+
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> BaseElement {
+        self.claimed_step
+            .unwrap_or_else(|| trace.get(0, 1) - BaseElement::ONE)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
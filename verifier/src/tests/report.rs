@@ -0,0 +1,151 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{
+    build_counter_trace, prove_and_verify, replace_options, Blake3, CounterAir, CounterProver,
+};
+use crate::{verify, verify_with_report, VerifierError};
+use air::{Air, FieldExtension, ProofOptions};
+use crypto::{ElementHasher, RandomCoin};
+use math::{fields::f128::BaseElement, FieldElement};
+use prover::{Prover, ProverError};
+use utils::{collections::Vec, Serializable};
+
+// VERIFICATION REPORT
+// ================================================================================================
+
+#[test]
+fn verify_with_report_values() {
+    let options = ProofOptions::new(28, 8, 8, FieldExtension::None, 4, 32);
+    let (proof, pub_inputs) = prove_and_verify(options);
+
+    let report = verify_with_report::<CounterAir, Blake3>(proof.clone(), pub_inputs)
+        .expect("verification failed");
+    assert_eq!(2, report.ood_main_trace_frame().len());
+    assert!(report.ood_aux_trace_frame().is_none());
+    assert_eq!(
+        proof.security_level::<Blake3>(true),
+        report.security_level()
+    );
+
+    // re-derive the DEEP composition coefficients and the query positions by replaying the
+    // protocol against a fresh public coin; the OOD frame and FRI layer commitments are taken
+    // from the report
+    let air = CounterAir::new(proof.get_trace_info(), pub_inputs, proof.options().clone());
+    let mut coin_seed = Vec::new();
+    pub_inputs.write_into(&mut coin_seed);
+    proof.context.write_unversioned_into(&mut coin_seed);
+    let mut public_coin = RandomCoin::<BaseElement, Blake3>::new(&coin_seed);
+
+    let num_fri_layers = air
+        .options()
+        .to_fri_options()
+        .num_fri_layers(air.lde_domain_size());
+    let (trace_roots, constraint_root, fri_roots) = proof
+        .commitments
+        .clone()
+        .parse::<Blake3>(1, num_fri_layers)
+        .unwrap();
+    assert_eq!(fri_roots, report.fri_layer_commitments());
+
+    public_coin.reseed(trace_roots[0]);
+    air.get_constraint_composition_coefficients::<BaseElement, _>(&mut public_coin)
+        .unwrap();
+    public_coin.reseed(constraint_root);
+    public_coin.draw::<BaseElement>().unwrap();
+    for row in report.ood_main_trace_frame() {
+        public_coin.reseed(Blake3::hash_elements(row));
+    }
+    let ood_constraint_evaluations = report.ood_constraint_evaluations();
+    public_coin.reseed(Blake3::hash_elements(ood_constraint_evaluations));
+    let deep_coefficients = air
+        .get_deep_composition_coefficients::<BaseElement, _>(&mut public_coin)
+        .unwrap();
+    assert_eq!(deep_coefficients.trace, report.deep_trace_coefficients());
+    assert_eq!(
+        deep_coefficients.constraints,
+        report.deep_constraint_coefficients()
+    );
+    for &commitment in report.fri_layer_commitments() {
+        public_coin.reseed(commitment);
+        public_coin.draw::<BaseElement>().unwrap();
+    }
+    public_coin.reseed_with_int(proof.pow_nonce);
+    let query_positions = public_coin
+        .draw_integers(air.options().num_queries(), air.lde_domain_size())
+        .unwrap();
+    assert_eq!(query_positions, report.query_positions());
+
+    // the report is not returned when the verification fails
+    let wrong_inputs = pub_inputs + BaseElement::ONE;
+    assert!(verify_with_report::<CounterAir, Blake3>(proof, wrong_inputs).is_err());
+
+    // coefficients drawn from a quadratic extension field are reported by their coordinates in
+    // the base field; the counter AIR has two trace columns, each with two frame offsets
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 32);
+    let (proof, pub_inputs) = prove_and_verify(options);
+    let report = verify_with_report::<CounterAir, Blake3>(proof, pub_inputs).unwrap();
+    assert_eq!(2, report.deep_trace_coefficients().len());
+    assert!(report
+        .deep_trace_coefficients()
+        .iter()
+        .all(|column_coefficients| column_coefficients.len() == 4));
+    assert_eq!(2, report.deep_constraint_coefficients().len());
+}
+
+#[test]
+fn verify_with_report_pow_difficulty() {
+    for grinding_factor in [0, 4, 8, 12, 16] {
+        let options = ProofOptions::new(28, 8, grinding_factor, FieldExtension::None, 4, 32);
+        let (proof, pub_inputs) = prove_and_verify(options);
+
+        let report = verify_with_report::<CounterAir, Blake3>(proof, pub_inputs)
+            .expect("verification failed");
+        assert!(report.pow_difficulty() >= grinding_factor);
+    }
+}
+
+#[test]
+fn verify_with_report_query_positions_in_small_domain() {
+    // a trace of 8 rows with blowup factor 4 results in a domain of 32 elements
+    let options = ProofOptions::new(31, 4, 0, FieldExtension::None, 4, 32);
+    let prover = CounterProver::new(options.clone());
+    let trace = build_counter_trace(2, 8);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    assert_eq!(32, proof.lde_domain_size());
+    let proof_bytes = proof.to_bytes();
+
+    // all query positions are unique; the proof does not record the number of unique positions
+    // because the verifier draws exactly as many unique positions as specified by the options
+    let report = verify_with_report::<CounterAir, Blake3>(proof, pub_inputs).unwrap();
+    let mut positions = report.query_positions().to_vec();
+    positions.sort_unstable();
+    positions.dedup();
+    assert_eq!(31, positions.len());
+
+    // the domain is too small to draw as many unique positions as there are elements in it
+    let too_many_queries = ProofOptions::new(32, 4, 0, FieldExtension::None, 4, 32);
+    let prover = CounterProver::new(too_many_queries.clone());
+    assert_eq!(
+        Err(ProverError::TooManyQueries {
+            num_queries: 32,
+            lde_domain_size: 32
+        }),
+        prover.prove(build_counter_trace(2, 8))
+    );
+
+    // a proof claiming as many queries as there are elements in the domain is rejected before
+    // the verifier attempts to draw the query positions
+    let proof = replace_options(&proof_bytes, &options, &too_many_queries);
+    assert_eq!(
+        Err(VerifierError::TooManyQueries {
+            num_queries: 32,
+            lde_domain_size: 32
+        }),
+        verify::<CounterAir, Blake3>(proof, pub_inputs)
+    );
+}
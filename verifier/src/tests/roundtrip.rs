@@ -0,0 +1,34 @@
+// Copyright (c) Facebook, Inc. and its affiliates.
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// This source code is licensed under the MIT license found in the
+// LICENSE file in the root directory of this source tree.
+
+use super::{build_counter_trace, Blake3, CounterAir, CounterProver};
+use crate::verify;
+use air::{proof::StarkProof, FieldExtension, ProofOptions, TraceInfo};
+use prover::Prover;
+use utils::Serializable;
+
+// MAXIMUM TRACE WIDTH
+// ================================================================================================
+
+#[test]
+fn prove_and_verify_max_width_trace() {
+    // with quadratic extension of the 128-bit field, the out-of-domain trace states of the widest
+    // trace take 1024 columns * 2 rows * 32 bytes = 65536 bytes, which do not fit into a 16-bit
+    // length prefix
+    let options = ProofOptions::new(28, 8, 0, FieldExtension::Quadratic, 4, 32);
+    let prover = CounterProver::new(options);
+    let trace = build_counter_trace(TraceInfo::MAX_TRACE_WIDTH, 8);
+    let pub_inputs = prover.get_pub_inputs(&trace);
+    let proof = prover.prove(trace).unwrap();
+    assert!(proof.ood_frame.to_bytes().len() > u16::MAX as usize);
+
+    let proof = StarkProof::from_bytes(&proof.to_bytes()).unwrap();
+    assert_eq!(
+        TraceInfo::MAX_TRACE_WIDTH,
+        proof.trace_layout().main_trace_width()
+    );
+    assert!(verify::<CounterAir, Blake3>(proof, pub_inputs).is_ok());
+}
@@ -20,7 +20,9 @@
 //!    be done by implementing [Air] trait.
 //! 2. Define an execution trace for your computation. This can be done by implementing [Trace]
 //!    trait. Alternatively, you can use [TraceTable] struct which already implements [Trace]
-//!    trait in cases when this generic implementation works for your use case.
+//!    trait in cases when this generic implementation works for your use case. For computations
+//!    with auxiliary trace segments, you can use [MultiSegmentTraceTable] struct and implement
+//!    only the logic for building auxiliary segments via [MultiSegmentTraceBuilder] trait.
 //! 3. Execute your computation and record its execution trace.
 //! 4. Define your prover by implementing [Prover] trait. Then execute [Prover::prove()] function
 //!    passing the trace generated in the previous step into it as a parameter. The function will
@@ -150,7 +152,7 @@
 //!
 //! ```no_run
 //! use winterfell::{
-//!     math::{fields::f128::BaseElement, FieldElement},
+//!     math::{fields::f128::BaseElement, FieldElement, ToElements},
 //!     Air, AirContext, Assertion, ByteWriter, EvaluationFrame, ProofOptions, Serializable,
 //!     TraceInfo, TransitionConstraintDegree, crypto::hashers::Blake3_256,
 //! };
@@ -161,7 +163,8 @@
 //!     result: BaseElement,
 //! }
 //!
-//! // We need to describe how public inputs can be converted to bytes.
+//! // We need to describe how public inputs can be converted to bytes. When `derive` feature
+//! // is enabled, this can also be done via `#[derive(Serializable)]`.
 //! impl Serializable for PublicInputs {
 //!     fn write_into<W: ByteWriter>(&self, target: &mut W) {
 //!         target.write(self.start);
@@ -169,6 +172,15 @@
 //!     }
 //! }
 //!
+//! // We also need to describe how public inputs can be converted to field elements; the public
+//! // coin of the protocol is seeded with these elements. When `derive` feature is enabled, this
+//! // can also be done via `#[derive(ToElements)]`.
+//! impl ToElements<BaseElement> for PublicInputs {
+//!     fn to_elements(&self) -> Vec<BaseElement> {
+//!         vec![self.start, self.result]
+//!     }
+//! }
+//!
 //! // For a specific instance of our computation, we'll keep track of the public inputs and
 //! // the computation's context which we'll build in the constructor. The context is used
 //! // internally by the Winterfell prover/verifier when interpreting this AIR.
@@ -262,7 +274,7 @@
 //! };
 //!
 //! # use winterfell::{
-//! #   Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable,
+//! #   math::ToElements, Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable,
 //! #   TraceInfo, TransitionConstraintDegree,
 //! # };
 //! #
@@ -278,6 +290,12 @@
 //! #     }
 //! # }
 //! #
+//! # impl ToElements<BaseElement> for PublicInputs {
+//! #     fn to_elements(&self) -> Vec<BaseElement> {
+//! #         vec![self.start, self.result]
+//! #     }
+//! # }
+//! #
 //! # pub struct WorkAir {
 //! #     context: AirContext<BaseElement>,
 //! #     start: BaseElement,
@@ -367,7 +385,7 @@
 //!
 //! ```
 //! # use winterfell::{
-//! #    math::{fields::f128::BaseElement, FieldElement},
+//! #    math::{fields::f128::BaseElement, FieldElement, ToElements},
 //! #    Air, AirContext, Assertion, ByteWriter, EvaluationFrame, Serializable,
 //! #    TraceInfo, TransitionConstraintDegree, TraceTable, FieldExtension,
 //! #    Prover, ProofOptions, StarkProof, Trace, crypto::hashers::Blake3_256,
@@ -400,6 +418,12 @@
 //! #     }
 //! # }
 //! #
+//! # impl ToElements<BaseElement> for PublicInputs {
+//! #     fn to_elements(&self) -> Vec<BaseElement> {
+//! #         vec![self.start, self.result]
+//! #     }
+//! # }
+//! #
 //! # pub struct WorkAir {
 //! #     context: AirContext<BaseElement>,
 //! #     start: BaseElement,
@@ -528,11 +552,26 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use prover::{
-    crypto, iterators, math, Air, AirContext, Assertion, AuxTraceRandElements, BoundaryConstraint,
-    BoundaryConstraintGroup, ByteReader, ByteWriter, ConstraintCompositionCoefficients,
-    ConstraintDivisor, DeepCompositionCoefficients, Deserializable, DeserializationError,
-    EvaluationFrame, FieldExtension, Matrix, ProofOptions, Prover, ProverError, Serializable,
-    SliceReader, StarkProof, Trace, TraceInfo, TraceLayout, TraceTable, TraceTableFragment,
-    TransitionConstraintDegree, TransitionConstraintGroup,
+    build_permutation_column, build_range_lookup_column, build_range_lookup_multiplicities, crypto,
+    gadgets, introspection, iterators, math, trace_diff, Air, AirContext, Assertion,
+    AuxTraceRandElements, BatchProof, BatchProver, BoundaryConstraint, BoundaryConstraintGroup,
+    ByteReader, ByteWriter, CanonicalDeserializable, CanonicalSerializable, CellDiff, ChainedAir,
+    ChainedAirBuilder, ChainedComputation, ConstraintCompositionCoefficients, ConstraintDivisor,
+    CpuBackend, DeepCompositionCoefficients, Deserializable, DeserializationError, EvaluationFrame,
+    FftBackend, FieldExtension, HashBackend, InstanceProof, Matrix, MultiSegmentTraceBuilder,
+    MultiSegmentTraceTable, PaddingPolicy, PeriodicColumnPolys, ProofOptions, ProofOptionsBuilder,
+    ProofOptionsError, ProofSizeBreakdown, Prover, ProverBackends, ProverError, ProverStream,
+    RoundFunction, RowMajorTraceBuilder, SelectorColumn, Serializable, SliceReader, StarkProof,
+    Step, Trace, TraceInfo, TraceLayout, TracePadder, TraceTable, TraceTableFragment,
+    TraceValidationFailure, TraceValidationReport, TranscriptDivergence, TranscriptEntry,
+    TranscriptEntryKind, TranscriptLog, TranscriptType, TransitionConstraintDegree,
+    TransitionConstraintGroup, TransitionDivisor,
+};
+pub use verifier::{
+    verify, verify_batch, verify_prepared, verify_with_coin_hasher, verify_with_min_field_security,
+    verify_with_report, verify_with_transcript_log, PreparedAir, VerificationPhase,
+    VerificationReport, VerifierError,
 };
-pub use verifier::{verify, VerifierError};
+
+#[cfg(feature = "std")]
+pub use prover::{ReadAdapter, WriteAdapter};